@@ -0,0 +1,161 @@
+//! Named API keys
+//!
+//! Stored API keys are accepted next to the admin key from the environment.
+//! The running process keeps the active ones in a registry keyed by key hash,
+//! which the auth middleware consults once a presented key does not match the
+//! admin key. When a key is used its last-used time is updated in memory and
+//! written back to the database at most once a minute.
+
+use chrono::{DateTime, Duration, Utc};
+use once_cell::sync::Lazy;
+use radarr_core::{domain::repositories::ApiKeyRepository, models::ApiKey};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use uuid::Uuid;
+
+/// How stale the stored last-used time may get before it is written back
+const LAST_USED_RESOLUTION_SECS: i64 = 60;
+
+/// Generate a new API key: 32 hex characters, like the admin key
+pub fn generate_api_key() -> String {
+    Uuid::new_v4().simple().to_string()
+}
+
+/// Hex SHA-256 digest under which an API key is stored
+pub fn digest_api_key(key: &str) -> String {
+    hex::encode(Sha256::digest(key.as_bytes()))
+}
+
+/// Active API keys known to the running process, keyed by key hash
+#[derive(Default)]
+pub struct ApiKeyRegistry {
+    keys: RwLock<HashMap<String, ApiKey>>,
+    /// Where last-used times are written back, once set at startup
+    repository: RwLock<Option<Arc<dyn ApiKeyRepository>>>,
+}
+
+impl ApiKeyRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Write last-used times back to `repository`
+    pub fn set_repository(&self, repository: Arc<dyn ApiKeyRepository>) {
+        *self.repository.write().unwrap_or_else(|e| e.into_inner()) = Some(repository);
+    }
+
+    /// Replace the known keys, e.g. with the stored ones at startup
+    pub fn replace(&self, keys: Vec<ApiKey>) {
+        let mut known = self.keys.write().unwrap_or_else(|e| e.into_inner());
+        *known = keys
+            .into_iter()
+            .filter(ApiKey::is_active)
+            .map(|key| (key.key_hash.clone(), key))
+            .collect();
+    }
+
+    /// Add a new key, or replace a rotated one
+    pub fn insert(&self, key: ApiKey) {
+        let mut known = self.keys.write().unwrap_or_else(|e| e.into_inner());
+        known.retain(|_, existing| existing.id != key.id);
+        if key.is_active() {
+            known.insert(key.key_hash.clone(), key);
+        }
+    }
+
+    /// Forget a revoked key
+    pub fn remove(&self, id: Uuid) {
+        let mut known = self.keys.write().unwrap_or_else(|e| e.into_inner());
+        known.retain(|_, key| key.id != id);
+    }
+
+    /// Active key matching a presented one
+    pub fn find(&self, presented: &str) -> Option<ApiKey> {
+        let known = self.keys.read().unwrap_or_else(|e| e.into_inner());
+        known.get(&digest_api_key(presented)).cloned()
+    }
+
+    /// Note that `key` authenticated a request at `now`
+    ///
+    /// Returns whether the stored last-used time was due for an update.
+    pub fn record_use(&self, key: &ApiKey, now: DateTime<Utc>) -> bool {
+        let due = {
+            let mut known = self.keys.write().unwrap_or_else(|e| e.into_inner());
+            let Some(known) = known.get_mut(&key.key_hash) else {
+                return false;
+            };
+            let due = known
+                .last_used_at
+                .is_none_or(|last| now - last >= Duration::seconds(LAST_USED_RESOLUTION_SECS));
+            if due {
+                known.last_used_at = Some(now);
+            }
+            due
+        };
+
+        let repository = self
+            .repository
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        if let (true, Some(repository)) = (due, repository) {
+            let id = key.id;
+            tokio::spawn(async move {
+                if let Err(e) = repository.touch(id, now).await {
+                    tracing::warn!("Failed to record last use of API key {}: {}", id, e);
+                }
+            });
+        }
+        due
+    }
+}
+
+static GLOBAL_REGISTRY: Lazy<ApiKeyRegistry> = Lazy::new(ApiKeyRegistry::new);
+
+/// Process-wide registry consulted by the auth middleware
+pub fn global_registry() -> &'static ApiKeyRegistry {
+    &GLOBAL_REGISTRY
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use radarr_core::models::ApiKeyScope;
+
+    #[test]
+    fn test_registry_finds_active_keys_and_throttles_last_used() {
+        let registry = ApiKeyRegistry::new();
+        let now = Utc::now();
+        let presented = generate_api_key();
+        assert_eq!(presented.len(), 32);
+
+        let key = ApiKey::new(
+            "Overseerr".to_string(),
+            digest_api_key(&presented),
+            ApiKeyScope::Full,
+        );
+        let id = key.id;
+        registry.insert(key);
+
+        let found = registry.find(&presented).unwrap();
+        assert_eq!(found.id, id);
+        assert!(registry.find(&generate_api_key()).is_none());
+
+        assert!(registry.record_use(&found, now));
+        assert!(!registry.record_use(&found, now + Duration::seconds(30)));
+        assert!(registry.record_use(&found, now + Duration::seconds(90)));
+
+        // Rotation replaces the hash the key is found under
+        let rotated = generate_api_key();
+        registry.insert(ApiKey {
+            key_hash: digest_api_key(&rotated),
+            ..found
+        });
+        assert!(registry.find(&presented).is_none());
+        assert_eq!(registry.find(&rotated).unwrap().id, id);
+
+        registry.remove(id);
+        assert!(registry.find(&rotated).is_none());
+    }
+}
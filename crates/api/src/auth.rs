@@ -0,0 +1,574 @@
+//! API key verification
+//!
+//! The configured API key is only kept in memory as a peppered SHA-256 digest,
+//! presented keys are compared against it in constant time, and repeated
+//! failures from the same source IP lock that client out for a while, whichever
+//! keys or passwords it tried. Requests without a key do not count as failures.
+//! At most [`MAX_TRACKED_CLIENTS`] clients are tracked at once, and expired
+//! records are dropped by [`ApiKeyVerifier::spawn_pruning`]. Every
+//! authentication failure is written to the `audit` tracing target together
+//! with the source IP.
+
+use once_cell::sync::Lazy;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use subtle::ConstantTimeEq;
+use tokio::task::JoinHandle;
+
+/// Prefix identifying an already hashed key in `RADARR_API_KEY`/`RADARR_API_KEY_HASH`
+pub const API_KEY_HASH_PREFIX: &str = "sha256:";
+
+/// Default number of failed attempts before a key is locked out
+pub const DEFAULT_MAX_FAILED_ATTEMPTS: u32 = 5;
+
+/// Default lockout duration once the failure limit is reached
+pub const DEFAULT_LOCKOUT_DURATION: Duration = Duration::from_secs(15 * 60);
+
+/// Most clients whose failures are tracked at once
+pub const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+/// Hash an API key with the given pepper, returning the `sha256:<hex>` form
+pub fn hash_api_key(key: &str, pepper: &str) -> String {
+    format!(
+        "{}{}",
+        API_KEY_HASH_PREFIX,
+        hex::encode(digest(key, pepper))
+    )
+}
+
+fn digest(key: &str, pepper: &str) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(pepper.as_bytes());
+    hasher.update(key.as_bytes());
+    hasher.finalize().into()
+}
+
+/// Outcome of an authentication attempt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthOutcome {
+    /// The key matched
+    Authorized,
+    /// No key was presented
+    Missing,
+    /// The key did not match
+    Invalid,
+    /// The client is locked out after too many failures
+    LockedOut { retry_after: Duration },
+}
+
+#[derive(Debug, Clone, Copy)]
+struct FailureRecord {
+    count: u32,
+    first_failure: Instant,
+    locked_until: Option<Instant>,
+}
+
+impl FailureRecord {
+    /// Whether the record no longer counts: its lockout has ended, or no
+    /// lockout followed within `window` of the first failure
+    fn is_expired(&self, now: Instant, window: Duration) -> bool {
+        match self.locked_until {
+            Some(until) => until <= now,
+            None => now.duration_since(self.first_failure) >= window,
+        }
+    }
+}
+
+/// Verifies presented API keys against a peppered hash of the configured key
+pub struct ApiKeyVerifier {
+    key_hash: [u8; 32],
+    pepper: String,
+    max_failed_attempts: u32,
+    lockout_duration: Duration,
+    failures: Mutex<HashMap<String, FailureRecord>>,
+}
+
+impl std::fmt::Debug for ApiKeyVerifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ApiKeyVerifier")
+            .field("max_failed_attempts", &self.max_failed_attempts)
+            .field("lockout_duration", &self.lockout_duration)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ApiKeyVerifier {
+    /// Create a verifier for a plain API key; only its hash is retained
+    pub fn from_key(key: &str, pepper: impl Into<String>) -> Self {
+        let pepper = pepper.into();
+        Self::with_digest(digest(key, &pepper), pepper)
+    }
+
+    /// Create a verifier from a stored `sha256:<hex>` hash
+    pub fn from_hash(hash: &str, pepper: impl Into<String>) -> Option<Self> {
+        let encoded = hash.strip_prefix(API_KEY_HASH_PREFIX)?;
+        let key_hash: [u8; 32] = hex::decode(encoded).ok()?.try_into().ok()?;
+        Some(Self::with_digest(key_hash, pepper.into()))
+    }
+
+    /// Build a verifier from the environment
+    ///
+    /// `RADARR_API_KEY_HASH` takes precedence over `RADARR_API_KEY`; either may
+    /// hold a `sha256:` hash computed with `RADARR_API_KEY_PEPPER`. Lockouts are
+    /// tuned with `RADARR_AUTH_MAX_FAILURES` and `RADARR_AUTH_LOCKOUT_SECS`.
+    pub fn from_env() -> Option<Self> {
+        let pepper = std::env::var("RADARR_API_KEY_PEPPER").unwrap_or_default();
+        let configured = std::env::var("RADARR_API_KEY_HASH")
+            .or_else(|_| std::env::var("RADARR_API_KEY"))
+            .ok()
+            .filter(|value| !value.is_empty())?;
+
+        let verifier = if configured.starts_with(API_KEY_HASH_PREFIX) {
+            match Self::from_hash(&configured, pepper) {
+                Some(verifier) => verifier,
+                None => {
+                    tracing::error!("Configured API key hash is malformed");
+                    return None;
+                }
+            }
+        } else {
+            Self::from_key(&configured, pepper)
+        };
+
+        let max_failed_attempts = std::env::var("RADARR_AUTH_MAX_FAILURES")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(DEFAULT_MAX_FAILED_ATTEMPTS);
+        let lockout_duration = std::env::var("RADARR_AUTH_LOCKOUT_SECS")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_LOCKOUT_DURATION);
+
+        Some(verifier.with_lockout(max_failed_attempts, lockout_duration))
+    }
+
+    fn with_digest(key_hash: [u8; 32], pepper: String) -> Self {
+        Self {
+            key_hash,
+            pepper,
+            max_failed_attempts: DEFAULT_MAX_FAILED_ATTEMPTS,
+            lockout_duration: DEFAULT_LOCKOUT_DURATION,
+            failures: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Configure the lockout policy; zero attempts disables lockouts
+    pub fn with_lockout(mut self, max_failed_attempts: u32, lockout_duration: Duration) -> Self {
+        self.max_failed_attempts = max_failed_attempts;
+        self.lockout_duration = lockout_duration;
+        self
+    }
+
+    /// Compare a presented key against the stored hash in constant time
+    pub fn matches(&self, presented: &str) -> bool {
+        digest(presented, &self.pepper).ct_eq(&self.key_hash).into()
+    }
+
+    /// Passkey for `purpose`, e.g. `calendar`
+    ///
+    /// Passkeys let feeds be subscribed to by URL without the API key
+    /// header. They are derived from the key hash, so they change when the
+    /// API key does and reveal nothing about it.
+    pub fn passkey(&self, purpose: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.key_hash);
+        hasher.update(b"passkey:");
+        hasher.update(purpose.as_bytes());
+        hex::encode(hasher.finalize())
+    }
+
+    /// Compare a presented passkey for `purpose` in constant time
+    pub fn verify_passkey(&self, presented: &str, purpose: &str) -> bool {
+        self.passkey(purpose)
+            .as_bytes()
+            .ct_eq(presented.as_bytes())
+            .into()
+    }
+
+    /// Authenticate a request, tracking failures per client
+    ///
+    /// `client` identifies the caller for lockout purposes (normally the
+    /// source IP); `path` is only used for the audit record.
+    pub fn authenticate(&self, presented: Option<&str>, client: &str, path: &str) -> AuthOutcome {
+        self.authenticate_with(presented, client, path, |_| false)
+    }
+
+    /// Authenticate a request, also accepting keys `other_keys` accepts
+    ///
+    /// `other_keys` is only asked about keys that do not match the configured
+    /// one; lockouts and failure tracking apply to both alike.
+    pub fn authenticate_with(
+        &self,
+        presented: Option<&str>,
+        client: &str,
+        path: &str,
+        other_keys: impl FnOnce(&str) -> bool,
+    ) -> AuthOutcome {
+        let now = Instant::now();
+
+        // Nothing to guess with, so missing keys are not held against anyone
+        let Some(key) = presented else {
+            audit_failure(client, path, "missing_api_key");
+            return AuthOutcome::Missing;
+        };
+
+        if let Some(retry_after) = self.locked_for(client, now) {
+            audit_failure(client, path, "locked_out");
+            return AuthOutcome::LockedOut { retry_after };
+        }
+
+        if self.matches(key) || other_keys(key) {
+            self.clear_failures(client);
+            return AuthOutcome::Authorized;
+        }
+
+        audit_failure(client, path, "invalid_api_key");
+        self.record_failure(client, now);
+        AuthOutcome::Invalid
+    }
+
+    /// How long `client` stays locked out, if it is
+    ///
+    /// Lets callers refuse locked-out clients before doing expensive work,
+    /// such as hashing a password.
+    pub fn lockout_remaining(&self, client: &str, path: &str) -> Option<Duration> {
+        let remaining = self.locked_for(client, Instant::now());
+        if remaining.is_some() {
+            audit_failure(client, path, "locked_out");
+        }
+        remaining
+    }
+
+    /// Authenticate a login whose credentials `valid` checks
+    ///
+    /// Wrong passwords count against the client like wrong API keys do.
+    pub fn authenticate_login(
+        &self,
+        client: &str,
+        path: &str,
+        valid: impl FnOnce() -> bool,
+    ) -> AuthOutcome {
+        let now = Instant::now();
+
+        if let Some(retry_after) = self.locked_for(client, now) {
+            audit_failure(client, path, "locked_out");
+            return AuthOutcome::LockedOut { retry_after };
+        }
+        if valid() {
+            self.clear_failures(client);
+            return AuthOutcome::Authorized;
+        }
+
+        audit_failure(client, path, "invalid_credentials");
+        self.record_failure(client, now);
+        AuthOutcome::Invalid
+    }
+
+    fn locked_for(&self, client: &str, now: Instant) -> Option<Duration> {
+        let failures = self.failures.lock().unwrap_or_else(|e| e.into_inner());
+        failures
+            .get(client)
+            .and_then(|record| record.locked_until)
+            .and_then(|until| until.checked_duration_since(now))
+            .filter(|remaining| !remaining.is_zero())
+    }
+
+    fn record_failure(&self, client: &str, now: Instant) {
+        if self.max_failed_attempts == 0 {
+            return;
+        }
+
+        let window = self.lockout_duration;
+        let mut failures = self.failures.lock().unwrap_or_else(|e| e.into_inner());
+        if failures.len() >= MAX_TRACKED_CLIENTS && !failures.contains_key(client) {
+            // Make room by forgetting the oldest failures, sparing lockouts
+            let oldest = failures
+                .iter()
+                .min_by_key(|(_, record)| {
+                    let locked = record.locked_until.is_some_and(|until| until > now);
+                    (locked, record.first_failure)
+                })
+                .map(|(client, _)| client.clone());
+            if let Some(oldest) = oldest {
+                failures.remove(&oldest);
+            }
+        }
+
+        let fresh = FailureRecord {
+            count: 0,
+            first_failure: now,
+            locked_until: None,
+        };
+        let record = failures.entry(client.to_string()).or_insert(fresh);
+        if record.is_expired(now, window) {
+            *record = fresh;
+        }
+        record.count += 1;
+
+        if record.count >= self.max_failed_attempts {
+            record.locked_until = Some(now + self.lockout_duration);
+            tracing::warn!(
+                target: "audit",
+                source_ip = %client,
+                failed_attempts = record.count,
+                lockout_secs = self.lockout_duration.as_secs(),
+                "Client locked out after repeated authentication failures"
+            );
+        }
+    }
+
+    fn clear_failures(&self, client: &str) {
+        let mut failures = self.failures.lock().unwrap_or_else(|e| e.into_inner());
+        failures.remove(client);
+    }
+
+    /// Drop failure records that no longer count, returning how many
+    pub fn prune_failures(&self, now: Instant) -> usize {
+        let window = self.lockout_duration;
+        let mut failures = self.failures.lock().unwrap_or_else(|e| e.into_inner());
+        let before = failures.len();
+        failures.retain(|_, record| !record.is_expired(now, window));
+        before - failures.len()
+    }
+
+    /// Prune expired failure records every `interval`
+    pub fn spawn_pruning(&'static self, interval: Duration) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                self.prune_failures(Instant::now());
+            }
+        })
+    }
+}
+
+fn audit_failure(client: &str, path: &str, reason: &str) {
+    tracing::warn!(
+        target: "audit",
+        source_ip = %client,
+        path = %path,
+        reason = reason,
+        "API authentication failed"
+    );
+}
+
+static GLOBAL_VERIFIER: Lazy<Option<ApiKeyVerifier>> = Lazy::new(ApiKeyVerifier::from_env);
+
+/// Process-wide verifier built from the environment on first use
+pub fn global_verifier() -> Option<&'static ApiKeyVerifier> {
+    GLOBAL_VERIFIER.as_ref()
+}
+
+/// Describe a client for lockout and audit purposes
+pub fn client_identity(source_ip: Option<IpAddr>) -> String {
+    source_ip
+        .map(|ip| ip.to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_and_hashed_keys_verify_the_same() {
+        let hash = hash_api_key("correct-horse-battery", "pepper");
+        assert!(hash.starts_with(API_KEY_HASH_PREFIX));
+
+        let from_key = ApiKeyVerifier::from_key("correct-horse-battery", "pepper");
+        let from_hash = ApiKeyVerifier::from_hash(&hash, "pepper").unwrap();
+        assert!(from_key.matches("correct-horse-battery"));
+        assert!(from_hash.matches("correct-horse-battery"));
+        assert!(!from_hash.matches("correct-horse-batter"));
+
+        // A different pepper produces an unrelated hash
+        let wrong_pepper = ApiKeyVerifier::from_hash(&hash, "other").unwrap();
+        assert!(!wrong_pepper.matches("correct-horse-battery"));
+        assert!(ApiKeyVerifier::from_hash("sha256:zz", "pepper").is_none());
+    }
+
+    #[test]
+    fn test_passkeys_are_tied_to_key_and_purpose() {
+        let verifier = ApiKeyVerifier::from_key("secret-key", "pepper");
+        let passkey = verifier.passkey("calendar");
+        assert!(verifier.verify_passkey(&passkey, "calendar"));
+        assert!(!verifier.verify_passkey(&passkey, "other"));
+        assert!(!verifier.verify_passkey("secret-key", "calendar"));
+
+        let rotated = ApiKeyVerifier::from_key("new-key", "pepper");
+        assert!(!rotated.verify_passkey(&passkey, "calendar"));
+    }
+
+    #[test]
+    fn test_lockout_after_repeated_failures() {
+        let verifier =
+            ApiKeyVerifier::from_key("secret-key", "").with_lockout(3, Duration::from_secs(60));
+
+        for _ in 0..3 {
+            assert_eq!(
+                verifier.authenticate(Some("wrong"), "10.0.0.1", "/api/v3/movie"),
+                AuthOutcome::Invalid
+            );
+        }
+
+        assert!(matches!(
+            verifier.authenticate(Some("wrong"), "10.0.0.1", "/api/v3/movie"),
+            AuthOutcome::LockedOut { .. }
+        ));
+        // The lockout covers the client, whatever key it sends next
+        assert!(matches!(
+            verifier.authenticate(Some("secret-key"), "10.0.0.1", "/api/v3/movie"),
+            AuthOutcome::LockedOut { .. }
+        ));
+        // Other clients are tracked separately
+        assert_eq!(
+            verifier.authenticate(Some("wrong"), "10.0.0.2", "/api/v3/movie"),
+            AuthOutcome::Invalid
+        );
+        assert_eq!(
+            verifier.authenticate(Some("secret-key"), "10.0.0.3", "/api/v3/movie"),
+            AuthOutcome::Authorized
+        );
+    }
+
+    #[test]
+    fn test_a_different_wrong_key_each_time_still_locks_out() {
+        let verifier =
+            ApiKeyVerifier::from_key("secret-key", "").with_lockout(3, Duration::from_secs(60));
+
+        for attempt in 0..3 {
+            let guess = format!("guess-{}", attempt);
+            assert_eq!(
+                verifier.authenticate(Some(&guess), "10.0.0.1", "/api/v3/movie"),
+                AuthOutcome::Invalid
+            );
+        }
+        assert!(matches!(
+            verifier.authenticate(Some("guess-3"), "10.0.0.1", "/api/v3/movie"),
+            AuthOutcome::LockedOut { .. }
+        ));
+    }
+
+    #[test]
+    fn test_tracked_clients_are_capped_and_pruned() {
+        let verifier =
+            ApiKeyVerifier::from_key("secret-key", "").with_lockout(2, Duration::from_secs(60));
+        let now = Instant::now();
+
+        verifier.record_failure("10.0.0.1", now);
+        verifier.record_failure("10.0.0.1", now);
+        for client in 0..MAX_TRACKED_CLIENTS {
+            verifier.record_failure(&format!("client-{}", client), now);
+        }
+        assert_eq!(verifier.failures.lock().unwrap().len(), MAX_TRACKED_CLIENTS);
+        // Records without a lockout make room first
+        assert!(verifier.locked_for("10.0.0.1", now).is_some());
+
+        assert_eq!(
+            verifier.prune_failures(now + Duration::from_secs(61)),
+            MAX_TRACKED_CLIENTS
+        );
+        assert!(verifier.failures.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_missing_keys_are_not_failures() {
+        let verifier =
+            ApiKeyVerifier::from_key("secret-key", "").with_lockout(2, Duration::from_secs(60));
+
+        for _ in 0..3 {
+            assert_eq!(
+                verifier.authenticate(None, "10.0.0.1", "/api/v3/movie"),
+                AuthOutcome::Missing
+            );
+        }
+        assert_eq!(
+            verifier.authenticate(Some("secret-key"), "10.0.0.1", "/api/v3/movie"),
+            AuthOutcome::Authorized
+        );
+    }
+
+    #[test]
+    fn test_success_resets_failure_count() {
+        let verifier =
+            ApiKeyVerifier::from_key("secret-key", "").with_lockout(2, Duration::from_secs(60));
+
+        let stored = |key: &str| key == "stored-key";
+
+        assert_eq!(
+            verifier.authenticate_with(Some("stored-key"), "10.0.0.1", "/api/v3/movie", |_| false),
+            AuthOutcome::Invalid
+        );
+        assert_eq!(
+            verifier.authenticate_with(Some("stored-key"), "10.0.0.1", "/api/v3/movie", stored),
+            AuthOutcome::Authorized
+        );
+        assert_eq!(
+            verifier.authenticate_with(Some("stored-key"), "10.0.0.1", "/api/v3/movie", |_| false),
+            AuthOutcome::Invalid
+        );
+    }
+
+    #[test]
+    fn test_locked_out_keys_stay_locked_for_other_verifiers() {
+        let verifier =
+            ApiKeyVerifier::from_key("secret-key", "").with_lockout(2, Duration::from_secs(60));
+        let stored = |key: &str| key == "stored-key";
+
+        for _ in 0..2 {
+            assert_eq!(
+                verifier.authenticate_with(Some("wrong"), "10.0.0.1", "/api/v3/movie", stored),
+                AuthOutcome::Invalid
+            );
+        }
+        // A key stored later is refused until the lockout ends
+        assert!(matches!(
+            verifier.authenticate_with(Some("wrong"), "10.0.0.1", "/api/v3/movie", |_| true),
+            AuthOutcome::LockedOut { .. }
+        ));
+        assert_eq!(
+            verifier.authenticate_with(Some("stored-key"), "10.0.0.2", "/api/v3/movie", stored),
+            AuthOutcome::Authorized
+        );
+    }
+
+    #[test]
+    fn test_failed_logins_lock_out_the_client() {
+        let verifier =
+            ApiKeyVerifier::from_key("secret-key", "").with_lockout(2, Duration::from_secs(60));
+
+        assert_eq!(
+            verifier.authenticate_login("10.0.0.1", "/api/v3/login", || true),
+            AuthOutcome::Authorized
+        );
+        for _ in 0..2 {
+            assert_eq!(
+                verifier.authenticate_login("10.0.0.1", "/api/v3/login", || false),
+                AuthOutcome::Invalid
+            );
+        }
+        assert!(matches!(
+            verifier.authenticate_login("10.0.0.1", "/api/v3/login", || true),
+            AuthOutcome::LockedOut { .. }
+        ));
+        assert!(verifier
+            .lockout_remaining("10.0.0.1", "/api/v3/login")
+            .is_some());
+        assert!(verifier
+            .lockout_remaining("10.0.0.2", "/api/v3/login")
+            .is_none());
+        assert!(matches!(
+            verifier.authenticate(Some("secret-key"), "10.0.0.1", "/api/v3/movie"),
+            AuthOutcome::LockedOut { .. }
+        ));
+        assert_eq!(
+            verifier.authenticate_login("10.0.0.2", "/api/v3/login", || true),
+            AuthOutcome::Authorized
+        );
+    }
+}
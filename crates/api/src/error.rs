@@ -1,7 +1,7 @@
 //! API error handling and response types
 
 use axum::{
-    http::StatusCode,
+    http::{header, HeaderValue, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
@@ -21,6 +21,9 @@ pub enum ApiError {
     #[error("Bad request: {message}")]
     BadRequest { message: String },
 
+    #[error("Unprocessable entity: {message}")]
+    UnprocessableEntity { message: String },
+
     #[error("Resource not found: {resource}")]
     NotFound { resource: String },
 
@@ -44,81 +47,203 @@ pub enum ApiError {
 
     #[error("Not implemented: {message}")]
     NotImplemented { message: String },
+
+    #[error("Service unavailable: {message}")]
+    ServiceUnavailable { message: String },
 }
 
 /// Type alias for API results
 pub type ApiResult<T> = Result<T, ApiError>;
 
-impl IntoResponse for ApiError {
-    fn into_response(self) -> Response {
-        let (status, error_message) = match self {
-            ApiError::CoreError(core_error) => match core_error {
-                RadarrError::ValidationError { field, message } => (
-                    StatusCode::BAD_REQUEST,
-                    format!("Validation error in field '{}': {}", field, message),
-                ),
-                RadarrError::NotFound { resource } => (
-                    StatusCode::NOT_FOUND,
-                    format!("Resource not found: {}", resource),
-                ),
-                RadarrError::DatabaseError { .. } => (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    "Database error occurred".to_string(),
-                ),
-                RadarrError::ExternalServiceError { service, error } => (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    format!("External service '{}' error: {}", service, error),
-                ),
-                _ => (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    "Internal server error".to_string(),
-                ),
-            },
-            ApiError::ValidationError { field, message } => (
-                StatusCode::BAD_REQUEST,
-                format!("Validation error in field '{}': {}", field, message),
-            ),
-            ApiError::BadRequest { message } => {
-                (StatusCode::BAD_REQUEST, format!("Bad request: {}", message))
+/// Retry hint for an open circuit breaker, which stays open for tens of seconds
+const CIRCUIT_OPEN_RETRY_AFTER_SECS: u64 = 30;
+
+/// Retry hint for a rate limit that did not say when it resets
+const RATE_LIMIT_RETRY_AFTER_SECS: u64 = 60;
+
+/// Retry hint for other temporary failures
+const TEMPORARY_RETRY_AFTER_SECS: u64 = 5;
+
+/// HTTP status for a core error
+///
+/// Rate limits are 429 and open circuits or temporary failures 503, both
+/// with a Retry-After hint; failing upstream services are 502 (504 on
+/// timeout), so only errors inside Radarr itself are 500.
+pub fn core_error_status(error: &RadarrError) -> StatusCode {
+    match error {
+        RadarrError::MovieNotFound { .. }
+        | RadarrError::NotFound { .. }
+        | RadarrError::NotFoundError { .. } => StatusCode::NOT_FOUND,
+        RadarrError::ValidationError { .. } => StatusCode::BAD_REQUEST,
+        RadarrError::InvalidQualityProfile { .. } | RadarrError::ConfigurationError { .. } => {
+            StatusCode::UNPROCESSABLE_ENTITY
+        }
+        RadarrError::RateLimited { .. } => StatusCode::TOO_MANY_REQUESTS,
+        RadarrError::CircuitBreakerOpen { .. } | RadarrError::TemporaryError { .. } => {
+            StatusCode::SERVICE_UNAVAILABLE
+        }
+        RadarrError::Timeout { .. } => StatusCode::GATEWAY_TIMEOUT,
+        RadarrError::IndexerError { .. }
+        | RadarrError::ExternalServiceError { .. }
+        | RadarrError::NetworkError { .. }
+        | RadarrError::AuthenticationRequired { .. } => StatusCode::BAD_GATEWAY,
+        RadarrError::RetryExhausted { last_error, .. } => core_error_status(last_error),
+        RadarrError::DatabaseError { .. }
+        | RadarrError::IoError(_)
+        | RadarrError::SerializationError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+/// Seconds a client should wait before retrying a core error, for errors
+/// that are expected to clear up on their own
+pub fn core_error_retry_after(error: &RadarrError) -> Option<u64> {
+    match error {
+        RadarrError::RateLimited { retry_after, .. } => {
+            Some(retry_after.unwrap_or(RATE_LIMIT_RETRY_AFTER_SECS))
+        }
+        RadarrError::CircuitBreakerOpen { .. } => Some(CIRCUIT_OPEN_RETRY_AFTER_SECS),
+        RadarrError::TemporaryError { .. } => Some(TEMPORARY_RETRY_AFTER_SECS),
+        RadarrError::RetryExhausted { last_error, .. } => core_error_retry_after(last_error),
+        _ => None,
+    }
+}
+
+impl ApiError {
+    /// HTTP status of the response for this error
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            ApiError::CoreError(core_error) => core_error_status(core_error),
+            ApiError::ValidationError { .. } | ApiError::BadRequest { .. } => {
+                StatusCode::BAD_REQUEST
             }
-            ApiError::NotFound { resource } => (
-                StatusCode::NOT_FOUND,
-                format!("Resource not found: {}", resource),
-            ),
-            ApiError::Conflict { resource } => (
-                StatusCode::CONFLICT,
-                format!("Resource conflict: {}", resource),
-            ),
-            ApiError::ExternalServiceError { service, error } => (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                format!("External service '{}' error: {}", service, error),
-            ),
-            ApiError::Unauthorized => (
-                StatusCode::UNAUTHORIZED,
-                "Authentication required".to_string(),
-            ),
-            ApiError::Forbidden => (StatusCode::FORBIDDEN, "Access forbidden".to_string()),
-            ApiError::RateLimitExceeded => (
-                StatusCode::TOO_MANY_REQUESTS,
-                "Rate limit exceeded".to_string(),
-            ),
-            ApiError::InternalError { message } => (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                format!("Internal server error: {}", message),
-            ),
-            ApiError::NotImplemented { message } => (
-                StatusCode::NOT_IMPLEMENTED,
-                format!("Not implemented: {}", message),
-            ),
-        };
+            ApiError::UnprocessableEntity { .. } => StatusCode::UNPROCESSABLE_ENTITY,
+            ApiError::NotFound { .. } => StatusCode::NOT_FOUND,
+            ApiError::Conflict { .. } => StatusCode::CONFLICT,
+            ApiError::ExternalServiceError { .. } => StatusCode::BAD_GATEWAY,
+            ApiError::Unauthorized => StatusCode::UNAUTHORIZED,
+            ApiError::Forbidden => StatusCode::FORBIDDEN,
+            ApiError::RateLimitExceeded => StatusCode::TOO_MANY_REQUESTS,
+            ApiError::InternalError { .. } => StatusCode::INTERNAL_SERVER_ERROR,
+            ApiError::NotImplemented { .. } => StatusCode::NOT_IMPLEMENTED,
+            ApiError::ServiceUnavailable { .. } => StatusCode::SERVICE_UNAVAILABLE,
+        }
+    }
 
-        let body = Json(json!({
-            "error": {
-                "message": error_message,
-                "code": status.as_u16(),
+    /// Retry-After hint in seconds, if retrying later may succeed
+    pub fn retry_after(&self) -> Option<u64> {
+        match self {
+            ApiError::CoreError(core_error) => core_error_retry_after(core_error),
+            ApiError::RateLimitExceeded => Some(RATE_LIMIT_RETRY_AFTER_SECS),
+            _ => None,
+        }
+    }
+
+    /// Message returned to the client; storage details stay in the logs
+    fn client_message(&self) -> String {
+        match self {
+            ApiError::CoreError(RadarrError::DatabaseError { .. }) => {
+                "Database error occurred".to_string()
+            }
+            ApiError::CoreError(RadarrError::IoError(_) | RadarrError::SerializationError(_)) => {
+                "Internal server error".to_string()
             }
-        }));
+            ApiError::CoreError(core_error) => core_error.to_string(),
+            other => other.to_string(),
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = self.status_code();
+        let retry_after = self.retry_after();
+
+        let mut error = json!({
+            "message": self.client_message(),
+            "code": status.as_u16(),
+        });
+        if let Some(seconds) = retry_after {
+            error["retryAfter"] = json!(seconds);
+        }
+
+        let mut response = (status, Json(json!({ "error": error }))).into_response();
+        if let Some(seconds) = retry_after {
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(seconds));
+        }
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transient_errors_carry_retry_after() {
+        let response = ApiError::from(RadarrError::RateLimited {
+            service: "prowlarr".to_string(),
+            retry_after: Some(120),
+        })
+        .into_response();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[header::RETRY_AFTER], "120");
+
+        let response = ApiError::from(RadarrError::CircuitBreakerOpen {
+            service: "TMDB".to_string(),
+        })
+        .into_response();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(response.headers()[header::RETRY_AFTER], "30");
+
+        // Retries that ended on a rate limit are still a rate limit
+        let exhausted = RadarrError::RetryExhausted {
+            operation: "search".to_string(),
+            attempts: 3,
+            last_error: Box::new(RadarrError::RateLimited {
+                service: "prowlarr".to_string(),
+                retry_after: None,
+            }),
+        };
+        assert_eq!(core_error_status(&exhausted), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(core_error_retry_after(&exhausted), Some(60));
+    }
 
-        (status, body).into_response()
+    #[test]
+    fn test_error_statuses() {
+        let status = |error: RadarrError| ApiError::from(error).status_code();
+        assert_eq!(
+            status(RadarrError::NotFoundError {
+                entity: "queue item".to_string(),
+                id: "1".to_string(),
+            }),
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(
+            status(RadarrError::ValidationError {
+                field: "name".to_string(),
+                message: "required".to_string(),
+            }),
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            status(RadarrError::InvalidQualityProfile {
+                profile: "4K".to_string(),
+            }),
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+        assert_eq!(
+            status(RadarrError::DatabaseError {
+                message: "connection reset".to_string(),
+            }),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+        let response = ApiError::from(RadarrError::Timeout {
+            operation: "tmdb search".to_string(),
+        })
+        .into_response();
+        assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+        assert!(response.headers().get(header::RETRY_AFTER).is_none());
     }
 }
@@ -523,6 +523,8 @@ fn create_diverse_mock_releases() -> ApiResult<Vec<EnhancedReleaseResponse>> {
                 freeleech: Some(freeleech),
                 quality_score: Some(quality_score),
                 progress: 0.0,
+                rejected: false,
+                rejections: Vec::new(),
             },
             relevance_score: 95.0 - (i as f64 * 2.0),
             quality_tier: quality_tier.to_string(),
@@ -0,0 +1,216 @@
+//! API key management handlers
+//!
+//! Named API keys let other applications use the API without the admin key,
+//! and can be rotated or revoked on their own. The key is only returned by
+//! the create and rotate calls; listings show its metadata. Rotating or
+//! revoking a key takes effect immediately. Only the admin key can reach
+//! these endpoints.
+
+use crate::api_keys::{digest_api_key, generate_api_key, global_registry};
+use crate::error::{ApiError, ApiResult};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    routing::{delete, get, post},
+    Router,
+};
+use radarr_core::{
+    domain::repositories::ApiKeyRepository,
+    models::{ApiKey, ApiKeyScope},
+};
+use radarr_infrastructure::{DatabasePool, PostgresApiKeyRepository};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{info, instrument};
+use uuid::Uuid;
+
+/// API key state
+#[derive(Clone)]
+pub struct ApiKeyState {
+    pub api_key_repo: Arc<dyn ApiKeyRepository>,
+}
+
+impl ApiKeyState {
+    pub fn new(database_pool: DatabasePool) -> Self {
+        Self {
+            api_key_repo: Arc::new(PostgresApiKeyRepository::new(database_pool)),
+        }
+    }
+}
+
+/// API key create request
+#[derive(Debug, Deserialize)]
+pub struct ApiKeyRequest {
+    pub name: String,
+    pub scope: String,
+}
+
+/// API key as returned by the API
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiKeyResource {
+    pub id: Uuid,
+    pub name: String,
+    pub scope: ApiKeyScope,
+    pub created_at: String,
+    pub last_used_at: Option<String>,
+    pub revoked_at: Option<String>,
+    pub active: bool,
+    /// The key itself, only present in the create and rotate responses
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+}
+
+impl From<ApiKey> for ApiKeyResource {
+    fn from(key: ApiKey) -> Self {
+        Self {
+            active: key.is_active(),
+            id: key.id,
+            name: key.name,
+            scope: key.scope,
+            created_at: key.created_at.to_rfc3339(),
+            last_used_at: key.last_used_at.map(|at| at.to_rfc3339()),
+            revoked_at: key.revoked_at.map(|at| at.to_rfc3339()),
+            key: None,
+        }
+    }
+}
+
+/// Validate a create request, returning the name and scope
+fn parse_request(request: ApiKeyRequest) -> ApiResult<(String, ApiKeyScope)> {
+    let name = request.name.trim().to_string();
+    if name.is_empty() {
+        return Err(ApiError::ValidationError {
+            field: "name".to_string(),
+            message: "API key name cannot be empty".to_string(),
+        });
+    }
+
+    let scope =
+        ApiKeyScope::parse(request.scope.trim()).ok_or_else(|| ApiError::ValidationError {
+            field: "scope".to_string(),
+            message: format!(
+                "Unsupported API key scope '{}', expected readOnly or full",
+                request.scope
+            ),
+        })?;
+
+    Ok((name, scope))
+}
+
+/// GET /api/v3/apikey - List API keys
+#[instrument(skip(state))]
+pub async fn list_api_keys(
+    State(state): State<ApiKeyState>,
+) -> ApiResult<Json<Vec<ApiKeyResource>>> {
+    let keys = state.api_key_repo.list().await?;
+    Ok(Json(keys.into_iter().map(Into::into).collect()))
+}
+
+/// POST /api/v3/apikey - Generate an API key
+#[instrument(skip(state, request))]
+pub async fn create_api_key(
+    State(state): State<ApiKeyState>,
+    Json(request): Json<ApiKeyRequest>,
+) -> ApiResult<(StatusCode, Json<ApiKeyResource>)> {
+    let (name, scope) = parse_request(request)?;
+    let key = generate_api_key();
+
+    let created = state
+        .api_key_repo
+        .create(&ApiKey::new(name, digest_api_key(&key), scope))
+        .await?;
+    global_registry().insert(created.clone());
+
+    info!(
+        "Created {} API key '{}' ({})",
+        created.scope, created.name, created.id
+    );
+    let mut resource = ApiKeyResource::from(created);
+    resource.key = Some(key);
+    Ok((StatusCode::CREATED, Json(resource)))
+}
+
+/// POST /api/v3/apikey/:id/rotate - Replace an API key, keeping its name and scope
+#[instrument(skip(state))]
+pub async fn rotate_api_key(
+    State(state): State<ApiKeyState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<ApiKeyResource>> {
+    let key = generate_api_key();
+
+    let rotated = state
+        .api_key_repo
+        .rotate(id, &digest_api_key(&key))
+        .await?
+        .ok_or_else(|| ApiError::NotFound {
+            resource: format!("active API key with id {}", id),
+        })?;
+    global_registry().insert(rotated.clone());
+
+    info!("Rotated API key '{}' ({})", rotated.name, rotated.id);
+    let mut resource = ApiKeyResource::from(rotated);
+    resource.key = Some(key);
+    Ok(Json(resource))
+}
+
+/// DELETE /api/v3/apikey/:id - Revoke an API key
+#[instrument(skip(state))]
+pub async fn revoke_api_key(
+    State(state): State<ApiKeyState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<StatusCode> {
+    let revoked = state
+        .api_key_repo
+        .revoke(id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound {
+            resource: format!("API key with id {}", id),
+        })?;
+    global_registry().remove(id);
+
+    info!("Revoked API key '{}' ({})", revoked.name, revoked.id);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Create the API key router
+pub fn create_api_key_router(state: ApiKeyState) -> Router {
+    Router::new()
+        .route("/v3/apikey", get(list_api_keys).post(create_api_key))
+        .route("/v3/apikey/:id", delete(revoke_api_key))
+        .route("/v3/apikey/:id/rotate", post(rotate_api_key))
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(name: &str, scope: &str) -> ApiKeyRequest {
+        ApiKeyRequest {
+            name: name.to_string(),
+            scope: scope.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_request_validates_name_and_scope() {
+        let (name, scope) = parse_request(request(" Overseerr ", "full")).unwrap();
+        assert_eq!(name, "Overseerr");
+        assert_eq!(scope, ApiKeyScope::Full);
+        assert_eq!(
+            parse_request(request("Dashboard", "readonly")).unwrap().1,
+            ApiKeyScope::ReadOnly
+        );
+
+        assert!(matches!(
+            parse_request(request("  ", "full")),
+            Err(ApiError::ValidationError { field, .. }) if field == "name"
+        ));
+        assert!(matches!(
+            parse_request(request("Overseerr", "admin")),
+            Err(ApiError::ValidationError { field, .. }) if field == "scope"
+        ));
+    }
+}
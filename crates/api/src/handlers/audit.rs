@@ -0,0 +1,253 @@
+//! Audit log API handlers
+//!
+//! Movie and file deletions, blocklist removals and settings changes are
+//! published as `DestructiveOperation` events carrying the credential the
+//! auth middleware tagged the request with. The recorded entries are paged
+//! through here, newest first, filtered by action, actor and date.
+
+use crate::error::{ApiError, ApiResult};
+use crate::models::PaginatedResponse;
+use axum::{
+    extract::{Query, State},
+    response::Json,
+    routing::get,
+    Extension, Router,
+};
+use radarr_core::{
+    domain::repositories::AuditRepository,
+    events::{EventBus, SystemEvent},
+    models::{AuditAction, AuditActor, AuditQuery, AuditRecord},
+};
+use radarr_infrastructure::{DatabasePool, PostgresAuditRepository};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{instrument, warn};
+use uuid::Uuid;
+
+/// Largest page a client may request
+const MAX_AUDIT_PAGE_SIZE: u32 = 1000;
+
+/// Publish a destructive operation for the audit log
+///
+/// Requests the auth middleware did not tag with an actor are recorded as
+/// performed by the system. A failure to publish is only logged; the
+/// operation itself has already happened.
+pub async fn publish_audit(
+    event_bus: Option<&Arc<EventBus>>,
+    actor: Option<Extension<AuditActor>>,
+    action: AuditAction,
+    target: impl Into<String>,
+    details: serde_json::Value,
+) {
+    let Some(event_bus) = event_bus else {
+        return;
+    };
+
+    let event = SystemEvent::DestructiveOperation {
+        action,
+        actor: actor
+            .map(|Extension(actor)| actor)
+            .unwrap_or_else(AuditActor::system),
+        target: target.into(),
+        details,
+    };
+    if let Err(e) = event_bus.publish(event).await {
+        warn!("Failed to publish {} audit event: {}", action, e);
+    }
+}
+
+/// Audit log state
+#[derive(Clone)]
+pub struct AuditState {
+    pub audit_repo: Arc<dyn AuditRepository>,
+}
+
+impl AuditState {
+    pub fn new(database_pool: DatabasePool) -> Self {
+        Self {
+            audit_repo: Arc::new(PostgresAuditRepository::new(database_pool)),
+        }
+    }
+}
+
+/// Query parameters for the audit log listing
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditQueryParams {
+    pub page: Option<u32>,
+    pub page_size: Option<u32>,
+    /// Comma separated actions, e.g. `movieDelete,configChange`
+    pub action: Option<String>,
+    /// Actor name, e.g. `admin` or the name of an API key
+    pub actor: Option<String>,
+    pub api_key_id: Option<Uuid>,
+    /// Only entries dated at or after this time
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl AuditQueryParams {
+    fn page(&self) -> u32 {
+        self.page.unwrap_or(1).max(1)
+    }
+
+    fn page_size(&self) -> u32 {
+        self.page_size.unwrap_or(50).clamp(1, MAX_AUDIT_PAGE_SIZE)
+    }
+
+    /// Build the repository query, rejecting unknown actions
+    fn to_query(&self) -> ApiResult<AuditQuery> {
+        let actions = match &self.action {
+            Some(actions) => actions
+                .split(',')
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(|value| {
+                    AuditAction::parse(value).ok_or_else(|| ApiError::ValidationError {
+                        field: "action".to_string(),
+                        message: format!(
+                            "Unknown action '{}', expected one of: {}",
+                            value,
+                            AuditAction::ALL
+                                .iter()
+                                .map(|action| action.as_str())
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        ),
+                    })
+                })
+                .collect::<ApiResult<Vec<_>>>()?,
+            None => Vec::new(),
+        };
+
+        let page_size = self.page_size();
+        Ok(AuditQuery {
+            actions,
+            api_key_id: self.api_key_id,
+            actor: self
+                .actor
+                .as_deref()
+                .map(str::trim)
+                .filter(|actor| !actor.is_empty())
+                .map(str::to_string),
+            since: self.since,
+            limit: page_size as i64,
+            offset: (self.page() - 1) as i64 * page_size as i64,
+        })
+    }
+}
+
+/// Audit entry as returned by the API
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditResource {
+    pub id: Uuid,
+    pub action: AuditAction,
+    pub actor: String,
+    pub api_key_id: Option<Uuid>,
+    pub target: String,
+    pub details: serde_json::Value,
+    pub correlation_id: Option<Uuid>,
+    pub date: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<AuditRecord> for AuditResource {
+    fn from(record: AuditRecord) -> Self {
+        Self {
+            id: record.id,
+            action: record.action,
+            actor: record.actor.name,
+            api_key_id: record.actor.api_key_id,
+            target: record.target,
+            details: record.details,
+            correlation_id: record.correlation_id,
+            date: record.date,
+        }
+    }
+}
+
+/// GET /api/v3/audit - Page through the audit log, optionally filtered
+#[instrument(skip(state))]
+pub async fn list_audit(
+    State(state): State<AuditState>,
+    Query(params): Query<AuditQueryParams>,
+) -> ApiResult<Json<PaginatedResponse<AuditResource>>> {
+    let (records, total) = state
+        .audit_repo
+        .list(&params.to_query()?)
+        .await
+        .map_err(ApiError::CoreError)?;
+
+    Ok(Json(PaginatedResponse::new(
+        params.page(),
+        params.page_size(),
+        total,
+        records.into_iter().map(AuditResource::from).collect(),
+    )))
+}
+
+/// Create audit log router
+pub fn create_audit_router(state: AuditState) -> Router {
+    Router::new()
+        .route("/v3/audit", get(list_audit))
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_params_paging_and_filters() {
+        let params = AuditQueryParams {
+            page: Some(2),
+            page_size: Some(25),
+            action: Some("movieDelete, blocklistRemove".to_string()),
+            actor: Some(" Overseerr ".to_string()),
+            ..Default::default()
+        };
+
+        let query = params.to_query().unwrap();
+        assert_eq!((query.limit, query.offset), (25, 25));
+        assert_eq!(
+            query.actions,
+            vec![AuditAction::MovieDelete, AuditAction::BlocklistRemove]
+        );
+        assert_eq!(query.actor.as_deref(), Some("Overseerr"));
+    }
+
+    #[test]
+    fn test_query_params_reject_unknown_action() {
+        let params = AuditQueryParams {
+            action: Some("movieAdd".to_string()),
+            ..Default::default()
+        };
+        assert!(params.to_query().is_err());
+
+        let defaults = AuditQueryParams::default().to_query().unwrap();
+        assert_eq!((defaults.limit, defaults.offset), (50, 0));
+        assert!(defaults.actions.is_empty() && defaults.actor.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_publish_audit_defaults_to_system_actor() {
+        let event_bus = Arc::new(EventBus::new());
+        let mut events = event_bus.subscribe();
+
+        publish_audit(
+            Some(&event_bus),
+            None,
+            AuditAction::ConfigChange,
+            "naming",
+            serde_json::json!({}),
+        )
+        .await;
+
+        match events.recv_event().await.unwrap() {
+            SystemEvent::DestructiveOperation { actor, target, .. } => {
+                assert_eq!(actor, AuditActor::system());
+                assert_eq!(target, "naming");
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,134 @@
+//! Backup API handlers
+//!
+//! `/api/v3/system/backup` lists the configuration backups written by the
+//! scheduled `Backup` task, makes one on demand, downloads and deletes them,
+//! and restores one that is stored or uploaded as a zip. Restores are checked
+//! inside a transaction and back up the replaced configuration first; settings
+//! loaded at startup apply after a restart. Backups hold indexer and download
+//! client credentials, so only the admin key can reach these endpoints.
+
+use crate::error::ApiResult;
+use axum::{
+    body::Bytes,
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Json, Response},
+    routing::{get, post},
+    Router,
+};
+use radarr_infrastructure::{BackupService, BackupSummary, BackupType, RestoreReport};
+use serde::Serialize;
+use std::sync::Arc;
+use tracing::{info, instrument};
+
+/// Backup state
+#[derive(Clone)]
+pub struct BackupState {
+    pub backups: Arc<BackupService>,
+}
+
+impl BackupState {
+    pub fn new(backups: Arc<BackupService>) -> Self {
+        Self { backups }
+    }
+}
+
+/// Outcome of a restore
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreResource {
+    #[serde(flatten)]
+    pub report: RestoreReport,
+    /// Settings loaded at startup only apply after a restart
+    pub restart_required: bool,
+}
+
+impl From<RestoreReport> for RestoreResource {
+    fn from(report: RestoreReport) -> Self {
+        Self {
+            report,
+            restart_required: true,
+        }
+    }
+}
+
+/// GET /api/v3/system/backup - List backups, newest first
+#[instrument(skip(state))]
+pub async fn list_backups(State(state): State<BackupState>) -> ApiResult<Json<Vec<BackupSummary>>> {
+    Ok(Json(state.backups.list().await?))
+}
+
+/// POST /api/v3/system/backup - Back up the configuration now
+#[instrument(skip(state))]
+pub async fn create_backup(
+    State(state): State<BackupState>,
+) -> ApiResult<(StatusCode, Json<BackupSummary>)> {
+    let backup = state.backups.create(BackupType::Manual).await?;
+    Ok((StatusCode::CREATED, Json(backup)))
+}
+
+/// GET /api/v3/system/backup/:name - Download a backup
+#[instrument(skip(state))]
+pub async fn download_backup(
+    State(state): State<BackupState>,
+    Path(name): Path<String>,
+) -> ApiResult<Response> {
+    let contents = state.backups.read(&name).await?;
+    let disposition = format!("attachment; filename=\"{}\"", name);
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/zip".to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        contents,
+    )
+        .into_response())
+}
+
+/// DELETE /api/v3/system/backup/:name - Delete a backup
+#[instrument(skip(state))]
+pub async fn delete_backup(
+    State(state): State<BackupState>,
+    Path(name): Path<String>,
+) -> ApiResult<StatusCode> {
+    state.backups.delete(&name).await?;
+    info!("Deleted backup {}", name);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// POST /api/v3/system/backup/restore/:name - Restore a stored backup
+#[instrument(skip(state))]
+pub async fn restore_backup(
+    State(state): State<BackupState>,
+    Path(name): Path<String>,
+) -> ApiResult<Json<RestoreResource>> {
+    let contents = state.backups.read(&name).await?;
+    let report = state.backups.restore(&contents).await?;
+    info!("Restored backup {}", name);
+    Ok(Json(report.into()))
+}
+
+/// POST /api/v3/system/backup/restore - Restore a backup zip sent as the body
+#[instrument(skip(state, body))]
+pub async fn restore_uploaded_backup(
+    State(state): State<BackupState>,
+    body: Bytes,
+) -> ApiResult<Json<RestoreResource>> {
+    let report = state.backups.restore(&body).await?;
+    info!("Restored uploaded backup ({} bytes)", body.len());
+    Ok(Json(report.into()))
+}
+
+/// Create the backup router
+pub fn create_backup_router(state: BackupState) -> Router {
+    Router::new()
+        .route("/v3/system/backup", get(list_backups).post(create_backup))
+        .route(
+            "/v3/system/backup/:name",
+            get(download_backup).delete(delete_backup),
+        )
+        .route("/v3/system/backup/restore", post(restore_uploaded_backup))
+        .route("/v3/system/backup/restore/:name", post(restore_backup))
+        .with_state(state)
+}
@@ -0,0 +1,365 @@
+//! Blocklist API handlers
+//!
+//! Releases that failed to download or import are blocklisted so searches
+//! skip them until their block expires. These endpoints page through the
+//! blocklist, remove entries one at a time or in bulk, and retry an entry:
+//! removing it and queueing a `MoviesSearch` for the affected movie.
+
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::commands::CommandResource;
+use crate::models::PaginatedResponse;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+    routing::{delete, get, post},
+    Extension, Router,
+};
+use radarr_core::{
+    blocklist::{BlocklistEntry, BlocklistQuery, BlocklistRepository},
+    events::EventBus,
+    models::{AuditAction, AuditActor},
+    CommandName, CommandQueue, CommandTrigger,
+};
+use radarr_infrastructure::{DatabasePool, PostgresBlocklistRepository};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{info, instrument};
+use uuid::Uuid;
+
+/// Largest page a client may request
+const MAX_BLOCKLIST_PAGE_SIZE: u32 = 1000;
+
+/// Blocklist state
+#[derive(Clone)]
+pub struct BlocklistState {
+    pub blocklist_repo: Arc<dyn BlocklistRepository>,
+    pub command_queue: Arc<CommandQueue>,
+    /// Where removals are published for the audit log
+    pub event_bus: Option<Arc<EventBus>>,
+}
+
+impl BlocklistState {
+    pub fn new(database_pool: DatabasePool, command_queue: Arc<CommandQueue>) -> Self {
+        Self {
+            blocklist_repo: Arc::new(PostgresBlocklistRepository::new(database_pool)),
+            command_queue,
+            event_bus: None,
+        }
+    }
+
+    /// Publish removals on `event_bus`
+    pub fn with_event_bus(mut self, event_bus: Arc<EventBus>) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+}
+
+/// Query parameters for the blocklist listing
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlocklistQueryParams {
+    pub page: Option<u32>,
+    pub page_size: Option<u32>,
+}
+
+impl BlocklistQueryParams {
+    fn page(&self) -> u32 {
+        self.page.unwrap_or(1).max(1)
+    }
+
+    fn page_size(&self) -> u32 {
+        self.page_size
+            .unwrap_or(20)
+            .clamp(1, MAX_BLOCKLIST_PAGE_SIZE)
+    }
+
+    /// Repository query for the requested page, newest entries first
+    fn to_query(&self) -> BlocklistQuery {
+        BlocklistQuery {
+            offset: (self.page() as i64 - 1) * self.page_size() as i64,
+            limit: self.page_size() as i32,
+            ..Default::default()
+        }
+    }
+}
+
+/// Query parameters for a movie's blocklist
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlocklistMovieParams {
+    pub movie_id: Uuid,
+}
+
+/// Bulk remove request
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlocklistBulkRequest {
+    pub ids: Vec<Uuid>,
+}
+
+/// Outcome of a bulk remove
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlocklistBulkResult {
+    pub removed: usize,
+    /// Requested IDs that were not on the blocklist
+    pub not_found: Vec<Uuid>,
+}
+
+/// Outcome of a retry: the removed entry and the search queued for its movie
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlocklistRetryResult {
+    pub id: Uuid,
+    pub movie_id: Option<Uuid>,
+    /// None when the entry was not tied to a movie
+    pub command: Option<CommandResource>,
+}
+
+/// Blocklist entry as returned by the API
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlocklistResource {
+    pub id: Uuid,
+    pub movie_id: Option<Uuid>,
+    pub source_title: String,
+    pub release_id: String,
+    pub indexer: String,
+    pub reason: String,
+    pub retry_count: u32,
+    pub blocked_until: chrono::DateTime<chrono::Utc>,
+    /// Whether the block has expired and the release may be grabbed again
+    pub expired: bool,
+    /// Whether the release will never be retried automatically
+    pub permanent: bool,
+    pub date: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<BlocklistEntry> for BlocklistResource {
+    fn from(entry: BlocklistEntry) -> Self {
+        Self {
+            id: entry.id,
+            movie_id: entry.movie_id,
+            expired: entry.is_expired(),
+            permanent: entry.is_permanent_block(),
+            reason: entry.reason.description().to_string(),
+            source_title: entry.release_title,
+            release_id: entry.release_id,
+            indexer: entry.indexer,
+            retry_count: entry.retry_count,
+            blocked_until: entry.blocked_until,
+            date: entry.created_at,
+        }
+    }
+}
+
+/// GET /api/v3/blocklist - Page through the blocklist, newest first
+#[instrument(skip(state))]
+pub async fn list_blocklist(
+    State(state): State<BlocklistState>,
+    Query(params): Query<BlocklistQueryParams>,
+) -> ApiResult<Json<PaginatedResponse<BlocklistResource>>> {
+    let query = params.to_query();
+    let entries = state.blocklist_repo.search_entries(&query).await?;
+    let total = state
+        .blocklist_repo
+        .count_entries(&BlocklistQuery::default())
+        .await?;
+
+    Ok(Json(PaginatedResponse::new(
+        params.page(),
+        params.page_size(),
+        total,
+        entries.into_iter().map(Into::into).collect(),
+    )))
+}
+
+/// GET /api/v3/blocklist/movie?movieId= - All blocklist entries of a movie
+#[instrument(skip(state))]
+pub async fn list_movie_blocklist(
+    State(state): State<BlocklistState>,
+    Query(params): Query<BlocklistMovieParams>,
+) -> ApiResult<Json<Vec<BlocklistResource>>> {
+    let entries = state
+        .blocklist_repo
+        .get_entries_for_movie(params.movie_id)
+        .await?;
+    Ok(Json(entries.into_iter().map(Into::into).collect()))
+}
+
+/// DELETE /api/v3/blocklist/:id - Remove an entry so the release can be
+/// grabbed again
+#[instrument(skip(state, actor))]
+pub async fn delete_blocklist_entry(
+    State(state): State<BlocklistState>,
+    Path(id): Path<Uuid>,
+    actor: Option<Extension<AuditActor>>,
+) -> ApiResult<StatusCode> {
+    if !state.blocklist_repo.remove_entry_by_id(id).await? {
+        return Err(ApiError::NotFound {
+            resource: format!("blocklist entry with id {}", id),
+        });
+    }
+    info!("Removed blocklist entry {}", id);
+    crate::handlers::audit::publish_audit(
+        state.event_bus.as_ref(),
+        actor,
+        AuditAction::BlocklistRemove,
+        id.to_string(),
+        serde_json::json!({}),
+    )
+    .await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// DELETE /api/v3/blocklist/bulk - Remove several entries at once
+#[instrument(skip(state, actor, request))]
+pub async fn delete_blocklist_bulk(
+    State(state): State<BlocklistState>,
+    actor: Option<Extension<AuditActor>>,
+    Json(request): Json<BlocklistBulkRequest>,
+) -> ApiResult<Json<BlocklistBulkResult>> {
+    if request.ids.is_empty() {
+        return Err(ApiError::ValidationError {
+            field: "ids".to_string(),
+            message: "At least one blocklist entry ID is required".to_string(),
+        });
+    }
+
+    let mut result = BlocklistBulkResult {
+        removed: 0,
+        not_found: Vec::new(),
+    };
+    let mut removed = Vec::new();
+    for id in request.ids {
+        if state.blocklist_repo.remove_entry_by_id(id).await? {
+            result.removed += 1;
+            removed.push(id);
+        } else {
+            result.not_found.push(id);
+        }
+    }
+    info!(
+        "Removed {} blocklist entries ({} not found)",
+        result.removed,
+        result.not_found.len()
+    );
+    if !removed.is_empty() {
+        crate::handlers::audit::publish_audit(
+            state.event_bus.as_ref(),
+            actor,
+            AuditAction::BlocklistRemove,
+            "bulk",
+            serde_json::json!({ "ids": removed }),
+        )
+        .await;
+    }
+    Ok(Json(result))
+}
+
+/// POST /api/v3/blocklist/:id/retry - Remove an entry and search again for
+/// its movie
+#[instrument(skip(state, actor))]
+pub async fn retry_blocklist_entry(
+    State(state): State<BlocklistState>,
+    Path(id): Path<Uuid>,
+    actor: Option<Extension<AuditActor>>,
+) -> ApiResult<Json<BlocklistRetryResult>> {
+    let entry = state
+        .blocklist_repo
+        .get_entry_by_id(id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound {
+            resource: format!("blocklist entry with id {}", id),
+        })?;
+    state.blocklist_repo.remove_entry_by_id(id).await?;
+    crate::handlers::audit::publish_audit(
+        state.event_bus.as_ref(),
+        actor,
+        AuditAction::BlocklistRemove,
+        id.to_string(),
+        serde_json::json!({ "movieId": entry.movie_id, "retry": true }),
+    )
+    .await;
+
+    let command = match entry.movie_id {
+        Some(movie_id) => {
+            let command = state
+                .command_queue
+                .enqueue(
+                    CommandName::MoviesSearch,
+                    serde_json::json!({ "movieIds": [movie_id] }),
+                    CommandTrigger::Manual,
+                )
+                .await?;
+            info!(
+                "Removed blocklist entry {} and queued a search for movie {} ({})",
+                id, movie_id, command.id
+            );
+            Some(command.into())
+        }
+        None => {
+            info!("Removed blocklist entry {}, no movie to search for", id);
+            None
+        }
+    };
+
+    Ok(Json(BlocklistRetryResult {
+        id,
+        movie_id: entry.movie_id,
+        command,
+    }))
+}
+
+/// Create the blocklist router
+pub fn create_blocklist_router(state: BlocklistState) -> Router {
+    Router::new()
+        .route("/v3/blocklist", get(list_blocklist))
+        .route("/v3/blocklist/movie", get(list_movie_blocklist))
+        .route("/v3/blocklist/bulk", delete(delete_blocklist_bulk))
+        .route("/v3/blocklist/:id", delete(delete_blocklist_entry))
+        .route("/v3/blocklist/:id/retry", post(retry_blocklist_entry))
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use radarr_core::blocklist::FailureReason;
+
+    #[test]
+    fn test_query_params_page_through_blocklist() {
+        let params = BlocklistQueryParams {
+            page: Some(3),
+            page_size: Some(50),
+        };
+        let query = params.to_query();
+        assert_eq!((query.offset, query.limit), (100, 50));
+        assert!(query.movie_id.is_none() && query.indexer.is_none());
+
+        let query = BlocklistQueryParams {
+            page: Some(0),
+            page_size: Some(5000),
+        }
+        .to_query();
+        assert_eq!((query.offset, query.limit), (0, 1000));
+    }
+
+    #[test]
+    fn test_resource_from_entry() {
+        let movie_id = Uuid::new_v4();
+        let entry = BlocklistEntry::new_for_movie(
+            "release-1".to_string(),
+            "indexer".to_string(),
+            FailureReason::ManuallyRejected,
+            "Movie.2020.1080p.WEB-DL".to_string(),
+            movie_id,
+        );
+        let resource = BlocklistResource::from(entry);
+        assert_eq!(resource.movie_id, Some(movie_id));
+        assert_eq!(resource.source_title, "Movie.2020.1080p.WEB-DL");
+        assert_eq!(resource.reason, "Manually rejected");
+        assert!(resource.permanent);
+    }
+}
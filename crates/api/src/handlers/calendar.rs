@@ -1,205 +1,430 @@
-//! Calendar and RSS feed handlers
+//! Calendar handlers
 //!
-//! Provides calendar views of movie releases and RSS/iCal feed functionality
-//! for integration with external calendar applications.
+//! `/api/v3/calendar` lists the cinema, digital and physical releases of
+//! monitored movies within a date range. `/feed/v3/calendar/radarr.ics`
+//! serves the same releases as an iCal feed for calendar applications;
+//! since those can't send the API key header, the feed is authorized by a
+//! passkey derived from the API key, which `/api/v3/calendar/feed` hands out.
 
+use crate::auth;
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::media_cover::MediaCoverResource;
 use axum::{
     extract::{Query, State},
-    http::{HeaderMap, StatusCode},
-    response::IntoResponse,
-    Json,
+    http::header,
+    response::{IntoResponse, Json, Response},
+    routing::get,
+    Router,
 };
-use chrono::{DateTime, Utc};
-use radarr_core::Movie;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use radarr_core::models::{Movie, ReleaseType};
+use radarr_infrastructure::{DatabasePool, PostgresMovieRepository};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
 use std::sync::Arc;
+use tracing::instrument;
+use uuid::Uuid;
+
+/// Passkey purpose of the iCal feed
+const CALENDAR_PASSKEY_PURPOSE: &str = "calendar";
+
+/// Path of the iCal feed, served outside `/api`
+const CALENDAR_FEED_PATH: &str = "/feed/v3/calendar/radarr.ics";
+
+/// Days shown from today when no end date is given
+const DEFAULT_FUTURE_DAYS: i64 = 28;
+
+/// Days of past releases the iCal feed includes by default
+const DEFAULT_PAST_DAYS: i64 = 7;
+
+/// Longest range a calendar request may span
+const MAX_CALENDAR_DAYS: i64 = 366;
+
+/// Calendar state
+#[derive(Clone)]
+pub struct CalendarState {
+    pub movie_repo: Arc<PostgresMovieRepository>,
+}
+
+impl CalendarState {
+    pub fn new(database_pool: DatabasePool) -> Self {
+        Self {
+            movie_repo: Arc::new(PostgresMovieRepository::new(database_pool)),
+        }
+    }
+
+    /// Movies with a release from `start` to `end`, each with those releases
+    async fn releases(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+        include_unmonitored: bool,
+    ) -> ApiResult<Vec<(Movie, Vec<(ReleaseType, NaiveDate)>)>> {
+        let movies = self
+            .movie_repo
+            .find_releasing_between(start, end, include_unmonitored)
+            .await?;
+        Ok(movies
+            .into_iter()
+            .map(|movie| {
+                let releases = movie.releases_between(start, end);
+                (movie, releases)
+            })
+            .filter(|(_, releases)| !releases.is_empty())
+            .collect())
+    }
+}
 
 /// Calendar query parameters
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 pub struct CalendarParams {
-    /// Start date (YYYY-MM-DD format)
+    /// First day, `YYYY-MM-DD` or an RFC 3339 timestamp; defaults to today
     pub start: Option<String>,
-    /// End date (YYYY-MM-DD format)  
+    /// Last day, inclusive; defaults to four weeks after `start`
     pub end: Option<String>,
     /// Whether to include unmonitored movies
     pub unmonitored: Option<bool>,
 }
 
-/// Calendar entry representing a movie release
+impl CalendarParams {
+    /// The requested range as dates, both inclusive
+    fn range(&self, today: NaiveDate) -> ApiResult<(NaiveDate, NaiveDate)> {
+        let start = parse_date("start", self.start.as_deref())?.unwrap_or(today);
+        let end = parse_date("end", self.end.as_deref())?
+            .unwrap_or(start + Duration::days(DEFAULT_FUTURE_DAYS));
+        if end < start {
+            return Err(ApiError::ValidationError {
+                field: "end".to_string(),
+                message: "End date must not be before the start date".to_string(),
+            });
+        }
+        if (end - start).num_days() > MAX_CALENDAR_DAYS {
+            return Err(ApiError::ValidationError {
+                field: "end".to_string(),
+                message: format!("Range must not exceed {} days", MAX_CALENDAR_DAYS),
+            });
+        }
+        Ok((start, end))
+    }
+}
+
+fn parse_date(field: &str, value: Option<&str>) -> ApiResult<Option<NaiveDate>> {
+    let Some(value) = value.filter(|value| !value.is_empty()) else {
+        return Ok(None);
+    };
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .or_else(|| {
+            DateTime::parse_from_rfc3339(value)
+                .ok()
+                .map(|date| date.with_timezone(&Utc).date_naive())
+        })
+        .map(Some)
+        .ok_or_else(|| ApiError::ValidationError {
+            field: field.to_string(),
+            message: format!("Invalid date '{}', expected YYYY-MM-DD", value),
+        })
+}
+
+/// A movie with releases in the requested range
 #[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CalendarEntry {
-    /// Movie ID
-    pub id: String,
-    /// Movie title
+    pub id: Uuid,
     pub title: String,
-    /// Release year
     pub year: Option<i32>,
-    /// TMDB ID
-    pub tmdb_id: Option<i32>,
-    /// Whether movie is monitored
+    pub tmdb_id: i32,
+    pub imdb_id: Option<String>,
     pub monitored: bool,
-    /// Physical release date
-    pub physical_release: Option<DateTime<Utc>>,
-    /// Digital release date
-    pub digital_release: Option<DateTime<Utc>>,
-    /// In theaters date
-    pub in_theaters: Option<DateTime<Utc>>,
-    /// Movie poster URL
-    pub poster_url: Option<String>,
-    /// Movie status
-    pub status: String,
-}
-
-impl From<Movie> for CalendarEntry {
-    fn from(movie: Movie) -> Self {
-        // Generate TMDB poster URL if poster_path exists in metadata
-        let poster_url = movie
-            .metadata
-            .get("poster_path")
-            .and_then(|v| v.as_str())
-            .map(|poster_path| format!("https://image.tmdb.org/t/p/w500{}", poster_path));
+    pub has_file: bool,
+    pub overview: Option<String>,
+    /// All of the movie's known release dates, including those outside the
+    /// range
+    pub in_cinemas: Option<NaiveDate>,
+    pub digital_release: Option<NaiveDate>,
+    pub physical_release: Option<NaiveDate>,
+    /// Releases within the range, in date order
+    pub releases: Vec<CalendarRelease>,
+    pub images: Vec<MediaCoverResource>,
+}
+
+/// One release of a calendar entry
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarRelease {
+    pub release_type: ReleaseType,
+    pub date: NaiveDate,
+}
 
+impl CalendarEntry {
+    fn new(movie: Movie, releases: Vec<(ReleaseType, NaiveDate)>) -> Self {
         Self {
-            id: movie.id.to_string(),
+            images: MediaCoverResource::for_movie(&movie),
+            in_cinemas: movie.release_date(ReleaseType::InCinemas),
+            digital_release: movie.release_date(ReleaseType::DigitalRelease),
+            physical_release: movie.release_date(ReleaseType::PhysicalRelease),
+            overview: movie.overview().map(str::to_string),
+            releases: releases
+                .into_iter()
+                .map(|(release_type, date)| CalendarRelease { release_type, date })
+                .collect(),
+            id: movie.id,
             title: movie.title,
             year: movie.year,
-            tmdb_id: Some(movie.tmdb_id),
+            tmdb_id: movie.tmdb_id,
+            imdb_id: movie.imdb_id,
             monitored: movie.monitored,
-            // For MVP, we'll use creation date as placeholder
-            // In production, these would be extracted from movie metadata or separate release date fields
-            physical_release: Some(movie.created_at),
-            digital_release: Some(movie.created_at),
-            in_theaters: Some(movie.created_at),
-            poster_url,
-            status: format!("{:?}", movie.status),
+            has_file: movie.has_file,
         }
     }
 }
 
-/// Get calendar entries for movies
+/// GET /api/v3/calendar - Releases of monitored movies within a date range
+#[instrument(skip(state))]
 pub async fn get_calendar(
-    Query(_params): Query<CalendarParams>,
-    State(_state): State<Arc<crate::SimpleApiState>>,
-) -> std::result::Result<Json<Vec<CalendarEntry>>, StatusCode> {
-    // For MVP, return mock calendar data
-    // In production, this would query the database with date filters
-
-    let calendar_entries = vec![
-        CalendarEntry {
-            id: "8c09a7b1-a772-4835-8e09-cdfa9ecefc54".to_string(),
-            title: "The Matrix".to_string(),
-            year: Some(1999),
-            tmdb_id: Some(603),
-            monitored: true,
-            physical_release: Some(Utc::now()),
-            digital_release: Some(Utc::now()),
-            in_theaters: Some(Utc::now()),
-            poster_url: Some(
-                "https://image.tmdb.org/t/p/w500/f89U3ADr1oiB1s9GkdPOEpXUk5H.jpg".to_string(),
-            ),
-            status: "released".to_string(),
-        },
-        CalendarEntry {
-            id: "c437fae8-a65e-48cf-b6f6-dcbcbc02a4d9".to_string(),
-            title: "Forrest Gump".to_string(),
-            year: Some(1994),
-            tmdb_id: Some(13),
-            monitored: true,
-            physical_release: Some(Utc::now()),
-            digital_release: Some(Utc::now()),
-            in_theaters: Some(Utc::now()),
-            poster_url: Some(
-                "https://image.tmdb.org/t/p/w500/arw2vcBveWOVZr6pxd9XTd1TdQa.jpg".to_string(),
-            ),
-            status: "released".to_string(),
-        },
-    ];
+    State(state): State<CalendarState>,
+    Query(params): Query<CalendarParams>,
+) -> ApiResult<Json<Vec<CalendarEntry>>> {
+    let (start, end) = params.range(Utc::now().date_naive())?;
+    let entries = state
+        .releases(start, end, params.unmonitored.unwrap_or(false))
+        .await?
+        .into_iter()
+        .map(|(movie, releases)| CalendarEntry::new(movie, releases))
+        .collect();
+    Ok(Json(entries))
+}
+
+/// Where to subscribe to the iCal feed
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarFeedResource {
+    /// Feed path including the passkey, relative to the server root
+    pub url: String,
+    pub passkey: String,
+}
+
+/// GET /api/v3/calendar/feed - Subscription URL of the iCal feed
+#[instrument]
+pub async fn get_calendar_feed_url() -> ApiResult<Json<CalendarFeedResource>> {
+    let verifier = auth::global_verifier().ok_or_else(|| ApiError::ServiceUnavailable {
+        message: "No API key is configured".to_string(),
+    })?;
+    let passkey = verifier.passkey(CALENDAR_PASSKEY_PURPOSE);
+    Ok(Json(CalendarFeedResource {
+        url: format!("{}?passkey={}", CALENDAR_FEED_PATH, passkey),
+        passkey,
+    }))
+}
 
-    Ok(Json(calendar_entries))
+/// iCal feed query parameters
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarFeedParams {
+    pub passkey: Option<String>,
+    pub past_days: Option<i64>,
+    pub future_days: Option<i64>,
+    pub unmonitored: Option<bool>,
 }
 
-/// Generate iCal feed for calendar integration
+/// GET /feed/v3/calendar/radarr.ics - iCal feed of upcoming releases
+#[instrument(skip(state, params))]
 pub async fn get_ical_feed(
-    Query(_params): Query<HashMap<String, String>>,
-    State(_state): State<Arc<crate::SimpleApiState>>,
-) -> impl IntoResponse {
-    // Basic iCal format for calendar integration
-    let ical_content = format!(
-        "BEGIN:VCALENDAR\r\n\
-         VERSION:2.0\r\n\
-         PRODID:-//Radarr MVP//Calendar//EN\r\n\
-         CALSCALE:GREGORIAN\r\n\
-         METHOD:PUBLISH\r\n\
-         X-WR-CALNAME:Radarr Movie Calendar\r\n\
-         X-WR-CALDESC:Upcoming movie releases from Radarr\r\n\
-         BEGIN:VEVENT\r\n\
-         UID:matrix-1999@radarr-mvp\r\n\
-         DTSTART:{}Z\r\n\
-         DTEND:{}Z\r\n\
-         SUMMARY:The Matrix (1999)\r\n\
-         DESCRIPTION:Movie release: The Matrix\r\n\
-         CATEGORIES:Movie\r\n\
-         END:VEVENT\r\n\
-         BEGIN:VEVENT\r\n\
-         UID:forrest-gump-1994@radarr-mvp\r\n\
-         DTSTART:{}Z\r\n\
-         DTEND:{}Z\r\n\
-         SUMMARY:Forrest Gump (1994)\r\n\
-         DESCRIPTION:Movie release: Forrest Gump\r\n\
-         CATEGORIES:Movie\r\n\
-         END:VEVENT\r\n\
-         END:VCALENDAR\r\n",
-        Utc::now().format("%Y%m%dT%H%M%S"),
-        Utc::now().format("%Y%m%dT%H%M%S"),
-        Utc::now().format("%Y%m%dT%H%M%S"),
-        Utc::now().format("%Y%m%dT%H%M%S"),
-    );
-
-    let mut headers = HeaderMap::new();
-    headers.insert(
-        "content-type",
-        "text/calendar; charset=utf-8".parse().unwrap(),
-    );
-    headers.insert(
-        "content-disposition",
-        "attachment; filename=radarr.ics".parse().unwrap(),
-    );
-
-    (headers, ical_content)
+    State(state): State<CalendarState>,
+    Query(params): Query<CalendarFeedParams>,
+) -> ApiResult<Response> {
+    let authorized = match (auth::global_verifier(), params.passkey.as_deref()) {
+        (Some(verifier), Some(passkey)) => {
+            verifier.verify_passkey(passkey, CALENDAR_PASSKEY_PURPOSE)
+        }
+        _ => false,
+    };
+    if !authorized {
+        tracing::warn!(target: "audit", path = CALENDAR_FEED_PATH, "Calendar feed passkey rejected");
+        return Err(ApiError::Unauthorized);
+    }
+
+    let now = Utc::now();
+    let days = |days: Option<i64>, default| days.unwrap_or(default).clamp(0, MAX_CALENDAR_DAYS);
+    let start = now.date_naive() - Duration::days(days(params.past_days, DEFAULT_PAST_DAYS));
+    let end = now.date_naive() + Duration::days(days(params.future_days, DEFAULT_FUTURE_DAYS));
+    let releases = state
+        .releases(start, end, params.unmonitored.unwrap_or(false))
+        .await?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/calendar; charset=utf-8"),
+            (header::CONTENT_DISPOSITION, "inline; filename=radarr.ics"),
+        ],
+        render_ical(&releases, now),
+    )
+        .into_response())
+}
+
+/// Render releases as an iCalendar (RFC 5545) document of all-day events
+fn render_ical(releases: &[(Movie, Vec<(ReleaseType, NaiveDate)>)], now: DateTime<Utc>) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//Radarr MVP//Calendar//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+        "X-WR-CALNAME:Radarr Movie Calendar".to_string(),
+    ];
+    for (movie, movie_releases) in releases {
+        for (release_type, date) in movie_releases {
+            let title = match movie.year {
+                Some(year) => format!("{} ({})", movie.title, year),
+                None => movie.title.clone(),
+            };
+            lines.extend([
+                "BEGIN:VEVENT".to_string(),
+                format!("UID:radarr_movie_{}_{}", movie.id, release_type),
+                format!("DTSTAMP:{}", now.format("%Y%m%dT%H%M%SZ")),
+                format!("DTSTART;VALUE=DATE:{}", date.format("%Y%m%d")),
+                format!(
+                    "DTEND;VALUE=DATE:{}",
+                    (*date + Duration::days(1)).format("%Y%m%d")
+                ),
+                format!(
+                    "SUMMARY:{}",
+                    escape_ical_text(&format!("{} - {}", title, release_type.label()))
+                ),
+                format!("CATEGORIES:{}", release_type.label()),
+                "TRANSP:TRANSPARENT".to_string(),
+            ]);
+            if let Some(overview) = movie.overview().filter(|overview| !overview.is_empty()) {
+                lines.push(format!("DESCRIPTION:{}", escape_ical_text(overview)));
+            }
+            lines.push("END:VEVENT".to_string());
+        }
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    lines
+        .iter()
+        .map(|line| fold_ical_line(line))
+        .collect::<Vec<_>>()
+        .concat()
+}
+
+/// Escape a TEXT property value
+fn escape_ical_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' | ';' | ',' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Fold a content line at 75 octets and terminate it with CRLF
+fn fold_ical_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + 8);
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            width = 1;
+        }
+        folded.push(c);
+        width += c.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
+}
+
+/// Create the calendar router
+pub fn create_calendar_router(state: CalendarState) -> Router {
+    Router::new()
+        .route("/v3/calendar", get(get_calendar))
+        .route("/v3/calendar/feed", get(get_calendar_feed_url))
+        .with_state(state)
+}
+
+/// Create the iCal feed router, served outside `/api`
+pub fn create_calendar_feed_router(state: CalendarState) -> Router {
+    Router::new()
+        .route(CALENDAR_FEED_PATH, get(get_ical_feed))
+        .with_state(state)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::Utc;
 
-    #[test]
-    fn test_calendar_entry_creation() {
-        let mut movie = Movie::new(12345, "Test Movie".to_string());
-        movie.year = Some(2023);
-        movie.imdb_id = Some("tt1234567".to_string());
-        movie.status = radarr_core::MovieStatus::Released;
-        movie.minimum_availability = radarr_core::MinimumAvailability::Released;
-
-        let entry = CalendarEntry::from(movie);
-        assert_eq!(entry.title, "Test Movie");
-        assert_eq!(entry.year, Some(2023));
-        assert_eq!(entry.tmdb_id, Some(12345));
-        assert!(entry.monitored);
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
     }
 
     #[test]
-    fn test_calendar_params_parsing() {
-        // Test that calendar params can be parsed from query strings
+    fn test_calendar_params_range() {
+        let today = date("2024-03-01");
+        assert_eq!(
+            CalendarParams::default().range(today).unwrap(),
+            (today, date("2024-03-29"))
+        );
+
         let params = CalendarParams {
-            start: Some("2023-01-01".to_string()),
-            end: Some("2023-01-31".to_string()),
-            unmonitored: Some(false),
+            start: Some("2024-02-01T00:00:00Z".to_string()),
+            end: Some("2024-02-10".to_string()),
+            unmonitored: None,
         };
+        assert_eq!(
+            params.range(today).unwrap(),
+            (date("2024-02-01"), date("2024-02-10"))
+        );
 
-        assert_eq!(params.start, Some("2023-01-01".to_string()));
-        assert_eq!(params.end, Some("2023-01-31".to_string()));
-        assert_eq!(params.unmonitored, Some(false));
+        let reversed = CalendarParams {
+            start: Some("2024-02-10".to_string()),
+            end: Some("2024-02-01".to_string()),
+            unmonitored: None,
+        };
+        assert!(reversed.range(today).is_err());
+        let invalid = CalendarParams {
+            start: Some("next week".to_string()),
+            ..CalendarParams::default()
+        };
+        assert!(invalid.range(today).is_err());
+    }
+
+    #[test]
+    fn test_render_ical() {
+        let mut movie = Movie::new(550, "Fight Club".to_string());
+        movie.year = Some(1999);
+        movie.metadata = serde_json::json!({
+            "tmdb": { "overview": "An insomniac, a soap salesman; and mayhem." }
+        });
+        let releases = vec![(
+            movie.clone(),
+            vec![(ReleaseType::DigitalRelease, date("2024-03-05"))],
+        )];
+        let now = DateTime::parse_from_rfc3339("2024-03-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let ical = render_ical(&releases, now);
+        assert!(ical.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ical.ends_with("END:VCALENDAR\r\n"));
+        assert!(ical.contains(&format!("UID:radarr_movie_{}_digitalRelease\r\n", movie.id)));
+        assert!(ical.contains("DTSTART;VALUE=DATE:20240305\r\n"));
+        assert!(ical.contains("DTEND;VALUE=DATE:20240306\r\n"));
+        assert!(ical.contains("SUMMARY:Fight Club (1999) - Digital Release\r\n"));
+        assert!(ical.contains("DESCRIPTION:An insomniac\\, a soap salesman\\; and mayhem.\r\n"));
+    }
+
+    #[test]
+    fn test_fold_ical_line() {
+        let line = format!("SUMMARY:{}", "é".repeat(40));
+        let folded = fold_ical_line(&line);
+        assert!(folded.split("\r\n").all(|part| part.len() <= 75));
+        assert_eq!(folded.replace("\r\n ", ""), format!("{}\r\n", line));
     }
 }
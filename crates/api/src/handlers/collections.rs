@@ -0,0 +1,391 @@
+//! Collection API handlers
+//!
+//! Collections are synced from TMDB when a film that belongs to one is added.
+//! Monitoring a collection adds its remaining films to the library, both
+//! right away and on every later sync.
+
+use crate::error::{ApiError, ApiResult};
+use axum::{
+    extract::{Path, State},
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use radarr_core::{
+    domain::repositories::{CollectionRepository, ListExclusionRepository, MovieRepository},
+    models::{MinimumAvailability, Movie, MovieCollection},
+};
+use radarr_infrastructure::{
+    CachedTmdbClient, DatabasePool, PostgresCollectionRepository, PostgresListExclusionRepository,
+    PostgresMovieRepository,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tracing::{info, instrument, warn};
+use uuid::Uuid;
+
+/// Collection state
+#[derive(Clone)]
+pub struct CollectionState {
+    pub collection_repo: Arc<dyn CollectionRepository>,
+    pub movie_repo: Arc<dyn MovieRepository>,
+    pub exclusion_repo: Arc<dyn ListExclusionRepository>,
+    pub tmdb_client: Option<Arc<CachedTmdbClient>>,
+}
+
+impl CollectionState {
+    pub fn new(database_pool: DatabasePool) -> Self {
+        Self {
+            collection_repo: Arc::new(PostgresCollectionRepository::new(database_pool.clone())),
+            movie_repo: Arc::new(PostgresMovieRepository::new(database_pool.clone())),
+            exclusion_repo: Arc::new(PostgresListExclusionRepository::new(database_pool)),
+            tmdb_client: None,
+        }
+    }
+
+    /// Use a TMDB client for syncing collections
+    pub fn with_tmdb_client(mut self, client: Arc<CachedTmdbClient>) -> Self {
+        self.tmdb_client = Some(client);
+        self
+    }
+
+    fn tmdb_client(&self) -> ApiResult<&CachedTmdbClient> {
+        self.tmdb_client
+            .as_deref()
+            .ok_or_else(|| ApiError::ExternalServiceError {
+                service: "tmdb".to_string(),
+                error: "TMDB client not configured".to_string(),
+            })
+    }
+
+    /// Fetch a collection from TMDB and store it, keeping existing settings
+    pub async fn sync_collection(&self, tmdb_id: i32) -> ApiResult<MovieCollection> {
+        let fetched = self
+            .tmdb_client()?
+            .get_movie_collection(tmdb_id)
+            .await
+            .map_err(|e| ApiError::ExternalServiceError {
+                service: "tmdb".to_string(),
+                error: e.to_string(),
+            })?;
+
+        let collection = match self.collection_repo.find_by_tmdb_id(tmdb_id).await? {
+            Some(mut stored) => {
+                stored.merge_metadata(fetched);
+                stored
+            }
+            None => fetched,
+        };
+
+        Ok(self.collection_repo.upsert(&collection).await?)
+    }
+
+    /// Add the collection's films that are not in the library yet
+    ///
+    /// Films on the exclusion list are skipped.
+    pub async fn add_missing_movies(&self, collection: &MovieCollection) -> ApiResult<Vec<Movie>> {
+        let library = self.library_movies(collection).await?;
+        let library_tmdb_ids: HashSet<i32> = library.keys().copied().collect();
+
+        let mut added = Vec::new();
+        for part in collection.missing_movies(&library_tmdb_ids) {
+            if self
+                .exclusion_repo
+                .find_by_tmdb_id(part.tmdb_id)
+                .await?
+                .is_some()
+            {
+                continue;
+            }
+            let movie = self.movie_repo.create(&collection.movie_for(part)).await?;
+            info!(
+                "Added {} to the library from collection {}",
+                movie.title, collection.title
+            );
+            added.push(movie);
+        }
+
+        Ok(added)
+    }
+
+    /// Sync the collection of a newly added movie and fill in the rest of it
+    ///
+    /// The remaining films are added when `add_collection` is set or the
+    /// collection was already monitored. Movies that belong to no collection
+    /// are left alone.
+    pub async fn on_movie_added(
+        &self,
+        movie: &Movie,
+        add_collection: bool,
+    ) -> ApiResult<Option<MovieCollection>> {
+        let collection_tmdb_id = match movie.collection_tmdb_id() {
+            Some(id) => Some(id),
+            None => self
+                .tmdb_client()?
+                .get_movie(movie.tmdb_id)
+                .await
+                .map_err(|e| ApiError::ExternalServiceError {
+                    service: "tmdb".to_string(),
+                    error: e.to_string(),
+                })?
+                .collection_tmdb_id(),
+        };
+        let Some(collection_tmdb_id) = collection_tmdb_id else {
+            return Ok(None);
+        };
+
+        let mut collection = self.sync_collection(collection_tmdb_id).await?;
+        if add_collection && !collection.monitored {
+            collection.monitored = true;
+            collection.quality_profile_id = movie.quality_profile_id;
+            collection.minimum_availability = movie.minimum_availability.clone();
+            collection = self.collection_repo.upsert(&collection).await?;
+        }
+        if collection.monitored {
+            self.add_missing_movies(&collection).await?;
+        }
+
+        Ok(Some(collection))
+    }
+
+    /// Library movies for each of the collection's films, keyed by TMDB ID
+    async fn library_movies(&self, collection: &MovieCollection) -> ApiResult<HashMap<i32, Movie>> {
+        let mut library = HashMap::new();
+        for part in &collection.movies {
+            if let Some(movie) = self.movie_repo.find_by_tmdb_id(part.tmdb_id).await? {
+                library.insert(part.tmdb_id, movie);
+            }
+        }
+        Ok(library)
+    }
+
+    async fn to_resource(&self, collection: MovieCollection) -> ApiResult<CollectionResource> {
+        let library = self.library_movies(&collection).await?;
+        Ok(CollectionResource::new(collection, &library))
+    }
+
+    async fn find_collection(&self, id: Uuid) -> ApiResult<MovieCollection> {
+        self.collection_repo
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| ApiError::NotFound {
+                resource: format!("collection {}", id),
+            })
+    }
+}
+
+/// Film of a collection as returned by the API
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectionMovieResource {
+    pub tmdb_id: i32,
+    pub title: String,
+    pub year: Option<i32>,
+    pub overview: Option<String>,
+    pub poster_path: Option<String>,
+    /// Library movie, when the film has been added
+    pub movie_id: Option<Uuid>,
+    pub monitored: bool,
+    pub has_file: bool,
+}
+
+/// Collection as returned by the API
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectionResource {
+    pub id: Uuid,
+    pub tmdb_id: i32,
+    pub title: String,
+    pub overview: Option<String>,
+    pub poster_path: Option<String>,
+    pub backdrop_path: Option<String>,
+    pub monitored: bool,
+    pub quality_profile_id: Option<i32>,
+    pub minimum_availability: MinimumAvailability,
+    pub movies: Vec<CollectionMovieResource>,
+    pub missing_movies: usize,
+    pub last_info_sync: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl CollectionResource {
+    fn new(collection: MovieCollection, library: &HashMap<i32, Movie>) -> Self {
+        let movies: Vec<CollectionMovieResource> = collection
+            .movies
+            .into_iter()
+            .map(|part| {
+                let movie = library.get(&part.tmdb_id);
+                CollectionMovieResource {
+                    tmdb_id: part.tmdb_id,
+                    title: part.title,
+                    year: part.year,
+                    overview: part.overview,
+                    poster_path: part.poster_path,
+                    movie_id: movie.map(|movie| movie.id),
+                    monitored: movie.is_some_and(|movie| movie.monitored),
+                    has_file: movie.is_some_and(|movie| movie.has_file),
+                }
+            })
+            .collect();
+
+        Self {
+            id: collection.id,
+            tmdb_id: collection.tmdb_id,
+            title: collection.title,
+            overview: collection.overview,
+            poster_path: collection.poster_path,
+            backdrop_path: collection.backdrop_path,
+            monitored: collection.monitored,
+            quality_profile_id: collection.quality_profile_id,
+            minimum_availability: collection.minimum_availability,
+            missing_movies: movies
+                .iter()
+                .filter(|movie| movie.movie_id.is_none())
+                .count(),
+            movies,
+            last_info_sync: collection.last_info_sync,
+        }
+    }
+}
+
+/// Collection settings update; omitted fields are left unchanged
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateCollectionRequest {
+    pub monitored: Option<bool>,
+    pub quality_profile_id: Option<i32>,
+    pub minimum_availability: Option<MinimumAvailability>,
+}
+
+/// GET /api/v3/collection - List collections
+#[instrument(skip(state))]
+pub async fn list_collections(
+    State(state): State<CollectionState>,
+) -> ApiResult<Json<Vec<CollectionResource>>> {
+    let mut resources = Vec::new();
+    for collection in state.collection_repo.list().await? {
+        resources.push(state.to_resource(collection).await?);
+    }
+    Ok(Json(resources))
+}
+
+/// GET /api/v3/collection/:id - Get a single collection
+#[instrument(skip(state))]
+pub async fn get_collection(
+    State(state): State<CollectionState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<CollectionResource>> {
+    let collection = state.find_collection(id).await?;
+    Ok(Json(state.to_resource(collection).await?))
+}
+
+/// PUT /api/v3/collection/:id - Update collection settings
+///
+/// Monitoring a collection adds its missing films immediately.
+#[instrument(skip(state))]
+pub async fn update_collection(
+    State(state): State<CollectionState>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<UpdateCollectionRequest>,
+) -> ApiResult<Json<CollectionResource>> {
+    let mut collection = state.find_collection(id).await?;
+
+    if let Some(monitored) = request.monitored {
+        collection.monitored = monitored;
+    }
+    if let Some(quality_profile_id) = request.quality_profile_id {
+        collection.quality_profile_id = Some(quality_profile_id);
+    }
+    if let Some(minimum_availability) = request.minimum_availability {
+        collection.minimum_availability = minimum_availability;
+    }
+    collection.updated_at = chrono::Utc::now();
+
+    let collection = state.collection_repo.upsert(&collection).await?;
+    if collection.monitored {
+        state.add_missing_movies(&collection).await?;
+    }
+
+    Ok(Json(state.to_resource(collection).await?))
+}
+
+/// POST /api/v3/collection/:id/refresh - Re-sync a collection from TMDB
+#[instrument(skip(state))]
+pub async fn refresh_collection(
+    State(state): State<CollectionState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<CollectionResource>> {
+    let collection = state.find_collection(id).await?;
+    let collection = state.sync_collection(collection.tmdb_id).await?;
+    if collection.monitored {
+        let added = state.add_missing_movies(&collection).await?;
+        if !added.is_empty() {
+            info!(
+                "Refresh of collection {} added {} movies",
+                collection.title,
+                added.len()
+            );
+        }
+    }
+
+    Ok(Json(state.to_resource(collection).await?))
+}
+
+/// DELETE /api/v3/collection/:id - Forget a collection; its movies stay
+#[instrument(skip(state))]
+pub async fn delete_collection(
+    State(state): State<CollectionState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<axum::http::StatusCode> {
+    state.collection_repo.delete(id).await.map_err(|e| {
+        warn!("Failed to delete collection {}: {}", id, e);
+        ApiError::from(e)
+    })?;
+    Ok(axum::http::StatusCode::NO_CONTENT)
+}
+
+/// Create collection router
+pub fn create_collection_router(state: CollectionState) -> Router {
+    Router::new()
+        .route("/v3/collection", get(list_collections))
+        .route(
+            "/v3/collection/:id",
+            get(get_collection)
+                .put(update_collection)
+                .delete(delete_collection),
+        )
+        .route("/v3/collection/:id/refresh", post(refresh_collection))
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use radarr_core::models::CollectionMovie;
+
+    #[test]
+    fn test_resource_marks_library_movies() {
+        let mut collection = MovieCollection::new(10, "The Matrix Collection".to_string());
+        collection.movies = [(603, "The Matrix"), (604, "The Matrix Reloaded")]
+            .into_iter()
+            .map(|(tmdb_id, title)| CollectionMovie {
+                tmdb_id,
+                title: title.to_string(),
+                year: None,
+                overview: None,
+                poster_path: None,
+            })
+            .collect();
+
+        let mut owned = Movie::new(603, "The Matrix".to_string());
+        owned.has_file = true;
+        let owned_id = owned.id;
+        let library = HashMap::from([(603, owned)]);
+
+        let resource = CollectionResource::new(collection, &library);
+        assert_eq!(resource.missing_movies, 1);
+        assert_eq!(resource.movies[0].movie_id, Some(owned_id));
+        assert!(resource.movies[0].has_file);
+        assert_eq!(resource.movies[1].movie_id, None);
+    }
+}
@@ -1,4 +1,7 @@
 //! Command API handlers
+//!
+//! `/api/v3/command` queues long-running tasks (RefreshMovie, RssSync,
+//! ImportListSync, DownloadedMoviesScan) and reports their state for polling.
 
 use crate::{
     error::{ApiError, ApiResult},
@@ -7,11 +10,13 @@ use crate::{
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
-    Json,
+    routing::get,
+    Json, Router,
 };
 use radarr_core::models::{Download, DownloadStatus};
+use radarr_core::{Command, CommandName, CommandQueue, CommandStatus, CommandTrigger};
 use radarr_infrastructure::{repositories::PostgresDownloadRepository, DatabasePool};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tracing::{info, instrument};
 use uuid::Uuid;
@@ -124,3 +129,184 @@ pub async fn import_download(
     info!("Successfully imported download: {}", download_id);
     Ok(StatusCode::ACCEPTED)
 }
+
+/// Command queue state
+#[derive(Clone)]
+pub struct CommandQueueState {
+    pub queue: Arc<CommandQueue>,
+}
+
+impl CommandQueueState {
+    pub fn new(queue: Arc<CommandQueue>) -> Self {
+        Self { queue }
+    }
+}
+
+/// Command response
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandResource {
+    pub id: i32,
+    pub name: String,
+    pub command_name: String,
+    pub message: Option<String>,
+    pub body: serde_json::Value,
+    pub status: CommandStatus,
+    /// `successful` or `unsuccessful` once finished
+    pub result: Option<String>,
+    pub queued: String,
+    pub started: Option<String>,
+    pub ended: Option<String>,
+    /// Run time as `HH:MM:SS`
+    pub duration: Option<String>,
+    pub trigger: CommandTrigger,
+    pub state_change_time: String,
+    pub progress: Option<f32>,
+}
+
+impl From<Command> for CommandResource {
+    fn from(command: Command) -> Self {
+        let result = match command.status {
+            CommandStatus::Completed => Some("successful".to_string()),
+            CommandStatus::Failed => Some("unsuccessful".to_string()),
+            _ => None,
+        };
+        let duration = command.duration().map(|duration| {
+            let seconds = duration.num_seconds().max(0);
+            format!(
+                "{:02}:{:02}:{:02}",
+                seconds / 3600,
+                seconds % 3600 / 60,
+                seconds % 60
+            )
+        });
+
+        Self {
+            id: command.id,
+            name: command.name.to_string(),
+            command_name: command.name.to_string(),
+            message: command.message.clone(),
+            body: command.body.clone(),
+            status: command.status,
+            result,
+            queued: command.queued_at.to_rfc3339(),
+            started: command.started_at.map(|t| t.to_rfc3339()),
+            ended: command.ended_at.map(|t| t.to_rfc3339()),
+            duration,
+            trigger: command.trigger,
+            state_change_time: command.state_change_time().to_rfc3339(),
+            progress: command.progress,
+        }
+    }
+}
+
+/// GET /api/v3/command - List queued, running and recent commands
+#[instrument(skip(state))]
+pub async fn list_commands(
+    State(state): State<CommandQueueState>,
+) -> ApiResult<Json<Vec<CommandResource>>> {
+    let commands = state.queue.list().await;
+    Ok(Json(
+        commands.into_iter().map(CommandResource::from).collect(),
+    ))
+}
+
+/// GET /api/v3/command/{id} - Poll a command
+#[instrument(skip(state))]
+pub async fn get_command(
+    State(state): State<CommandQueueState>,
+    Path(id): Path<i32>,
+) -> ApiResult<Json<CommandResource>> {
+    let command = state
+        .queue
+        .get(id)
+        .await
+        .ok_or_else(|| ApiError::NotFound {
+            resource: format!("command {}", id),
+        })?;
+    Ok(Json(CommandResource::from(command)))
+}
+
+/// POST /api/v3/command - Queue a command
+///
+/// The body names the command in `name`; every other field is passed to the
+/// command as its arguments.
+#[instrument(skip(state, request))]
+pub async fn queue_command(
+    State(state): State<CommandQueueState>,
+    Json(request): Json<serde_json::Value>,
+) -> ApiResult<(StatusCode, Json<CommandResource>)> {
+    let mut body = match request {
+        serde_json::Value::Object(body) => body,
+        _ => {
+            return Err(ApiError::BadRequest {
+                message: "Command must be a JSON object".to_string(),
+            })
+        }
+    };
+
+    let name = body
+        .remove("name")
+        .and_then(|name| name.as_str().and_then(CommandName::parse))
+        .ok_or_else(|| ApiError::ValidationError {
+            field: "name".to_string(),
+            message: format!(
+                "Unknown command; expected one of {}",
+                CommandName::ALL.map(|name| name.as_str()).join(", ")
+            ),
+        })?;
+
+    let command = state
+        .queue
+        .enqueue(
+            name,
+            serde_json::Value::Object(body),
+            CommandTrigger::Manual,
+        )
+        .await?;
+    info!("Queued command {} ({}) via API", command.name, command.id);
+
+    Ok((StatusCode::CREATED, Json(CommandResource::from(command))))
+}
+
+/// Create the command queue router
+pub fn create_command_router(state: CommandQueueState) -> Router {
+    Router::new()
+        .route("/v3/command", get(list_commands).post(queue_command))
+        .route("/v3/command/:id", get(get_command))
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_resource_fields() {
+        let mut command: Command = serde_json::from_value(serde_json::json!({
+            "id": 7,
+            "name": "RssSync",
+            "body": {},
+            "status": "completed",
+            "trigger": "manual",
+            "message": "Processed 3 new RSS items",
+            "progress": 100.0,
+            "queued_at": "2025-01-01T00:00:00Z",
+            "started_at": "2025-01-01T00:00:01Z",
+            "ended_at": "2025-01-01T01:02:04Z"
+        }))
+        .unwrap();
+
+        let resource = CommandResource::from(command.clone());
+        assert_eq!(resource.command_name, "RssSync");
+        assert_eq!(resource.result.as_deref(), Some("successful"));
+        assert_eq!(resource.duration.as_deref(), Some("01:02:03"));
+        assert_eq!(resource.state_change_time, "2025-01-01T01:02:04+00:00");
+
+        command.status = CommandStatus::Queued;
+        command.started_at = None;
+        command.ended_at = None;
+        let resource = CommandResource::from(command);
+        assert!(resource.result.is_none() && resource.duration.is_none());
+    }
+}
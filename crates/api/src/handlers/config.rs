@@ -0,0 +1,361 @@
+//! Settings API handlers
+//!
+//! Host, naming and media management settings can be changed while the
+//! instance runs. Saved settings are stored in the database and win over the
+//! environment on the next start. Naming changes apply to imports and renames
+//! right away; host and media management changes take effect after a restart.
+
+use crate::error::{ApiError, ApiResult};
+use axum::{extract::State, response::Json, routing::get, Extension, Router};
+use radarr_core::{
+    domain::repositories::ConfigRepository,
+    events::EventBus,
+    models::{AuditAction, AuditActor, ConfigSection},
+};
+use radarr_decision::ProperHandling;
+use radarr_import::{
+    ColonReplacement, ExtraAction, FileDateMode, ImportPipeline, RenameConfig, RenameEngine,
+};
+use radarr_infrastructure::{DatabasePool, PostgresConfigRepository};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, RwLock};
+use tracing::{info, instrument};
+
+/// Host settings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HostConfigResource {
+    pub port: u16,
+    /// Default locale of user-facing messages
+    pub locale: String,
+}
+
+impl Default for HostConfigResource {
+    fn default() -> Self {
+        Self {
+            port: 7878,
+            locale: radarr_core::i18n::DEFAULT_LOCALE.to_string(),
+        }
+    }
+}
+
+/// Movie file and folder naming settings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NamingConfigResource {
+    pub standard_movie_format: String,
+    pub movie_folder_format: String,
+    pub colon_replacement_format: ColonReplacement,
+}
+
+impl NamingConfigResource {
+    /// Apply these settings to the naming of imports and renames
+    pub fn apply_to(&self, config: &mut RenameConfig) {
+        config.movie_template = self.standard_movie_format.clone();
+        config.folder_template = self.movie_folder_format.clone();
+        config.colon_replacement = self.colon_replacement_format;
+    }
+}
+
+impl From<&RenameConfig> for NamingConfigResource {
+    fn from(config: &RenameConfig) -> Self {
+        Self {
+            standard_movie_format: config.movie_template.clone(),
+            movie_folder_format: config.folder_template.clone(),
+            colon_replacement_format: config.colon_replacement,
+        }
+    }
+}
+
+/// Media management settings
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaManagementConfigResource {
+    /// Whether propers and repacks replace files of the same quality
+    pub download_propers_and_repacks: ProperHandling,
+    /// Modification time given to imported files
+    pub file_date: FileDateMode,
+    /// What is done with samples, trailers, featurettes and other extras
+    pub samples: ExtraAction,
+    pub trailers: ExtraAction,
+    pub featurettes: ExtraAction,
+    pub other_extras: ExtraAction,
+}
+
+/// Settings state
+#[derive(Clone)]
+pub struct ConfigState {
+    pub config_repo: Arc<dyn ConfigRepository>,
+    /// Host settings the instance started with, or saved since
+    pub host: Arc<RwLock<HostConfigResource>>,
+    /// Naming imports and renames use
+    pub rename_config: Arc<RwLock<RenameConfig>>,
+    /// Media management settings the instance started with, or saved since
+    pub media_management: Arc<RwLock<MediaManagementConfigResource>>,
+    /// Pipeline that is told about naming changes
+    pub import_pipeline: Option<Arc<ImportPipeline>>,
+    /// Where saved settings are published for the audit log
+    pub event_bus: Option<Arc<EventBus>>,
+}
+
+impl ConfigState {
+    pub fn new(database_pool: DatabasePool) -> Self {
+        Self {
+            config_repo: Arc::new(PostgresConfigRepository::new(database_pool)),
+            host: Arc::new(RwLock::new(HostConfigResource::default())),
+            rename_config: Arc::new(RwLock::new(RenameConfig::default())),
+            media_management: Arc::new(RwLock::new(MediaManagementConfigResource::default())),
+            import_pipeline: None,
+            event_bus: None,
+        }
+    }
+
+    /// Apply naming changes to the files `pipeline` imports
+    pub fn with_import_pipeline(mut self, pipeline: Arc<ImportPipeline>) -> Self {
+        self.import_pipeline = Some(pipeline);
+        self
+    }
+
+    /// Publish saved settings on `event_bus`
+    pub fn with_event_bus(mut self, event_bus: Arc<EventBus>) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
+    async fn save<T: Serialize>(
+        &self,
+        section: ConfigSection,
+        settings: &T,
+        actor: Option<Extension<AuditActor>>,
+    ) -> ApiResult<()> {
+        let value = serde_json::to_value(settings).map_err(|e| ApiError::InternalError {
+            message: format!("Failed to serialize {} settings: {}", section, e),
+        })?;
+        self.config_repo
+            .save(section, &value)
+            .await
+            .map_err(ApiError::CoreError)?;
+
+        crate::handlers::audit::publish_audit(
+            self.event_bus.as_ref(),
+            actor,
+            AuditAction::ConfigChange,
+            section.as_str(),
+            serde_json::json!({ "settings": value }),
+        )
+        .await;
+        Ok(())
+    }
+}
+
+fn validate_host(mut host: HostConfigResource) -> ApiResult<HostConfigResource> {
+    if host.port == 0 {
+        return Err(ApiError::ValidationError {
+            field: "port".to_string(),
+            message: "Port must be greater than 0".to_string(),
+        });
+    }
+
+    let available = radarr_core::i18n::localizer().available_locales();
+    let requested = host.locale.trim().replace('_', "-");
+    host.locale = available
+        .iter()
+        .find(|locale| locale.eq_ignore_ascii_case(&requested))
+        .cloned()
+        .ok_or_else(|| ApiError::ValidationError {
+            field: "locale".to_string(),
+            message: format!(
+                "Unknown locale '{}', expected one of {}",
+                host.locale,
+                available.join(", ")
+            ),
+        })?;
+    Ok(host)
+}
+
+fn validate_naming(naming: &NamingConfigResource) -> ApiResult<()> {
+    let engine = RenameEngine::default();
+    for (field, template) in [
+        ("standardMovieFormat", &naming.standard_movie_format),
+        ("movieFolderFormat", &naming.movie_folder_format),
+    ] {
+        if template.trim().is_empty() {
+            return Err(ApiError::ValidationError {
+                field: field.to_string(),
+                message: "Format cannot be empty".to_string(),
+            });
+        }
+        engine
+            .validate_template(template)
+            .map_err(|e| ApiError::ValidationError {
+                field: field.to_string(),
+                message: e.to_string(),
+            })?;
+    }
+    Ok(())
+}
+
+/// GET /api/v3/config/host - Host settings
+#[instrument(skip(state))]
+pub async fn get_host_config(State(state): State<ConfigState>) -> Json<HostConfigResource> {
+    Json(state.host.read().unwrap_or_else(|e| e.into_inner()).clone())
+}
+
+/// PUT /api/v3/config/host - Save host settings, used from the next start
+#[instrument(skip(state, actor, request))]
+pub async fn update_host_config(
+    State(state): State<ConfigState>,
+    actor: Option<Extension<AuditActor>>,
+    Json(request): Json<HostConfigResource>,
+) -> ApiResult<Json<HostConfigResource>> {
+    let host = validate_host(request)?;
+    state.save(ConfigSection::Host, &host, actor).await?;
+    *state.host.write().unwrap_or_else(|e| e.into_inner()) = host.clone();
+
+    info!("Saved host settings, restart to apply them");
+    Ok(Json(host))
+}
+
+/// GET /api/v3/config/naming - Naming settings
+#[instrument(skip(state))]
+pub async fn get_naming_config(State(state): State<ConfigState>) -> Json<NamingConfigResource> {
+    let config = state
+        .rename_config
+        .read()
+        .unwrap_or_else(|e| e.into_inner());
+    Json(NamingConfigResource::from(&*config))
+}
+
+/// PUT /api/v3/config/naming - Save naming settings and apply them
+#[instrument(skip(state, actor, request))]
+pub async fn update_naming_config(
+    State(state): State<ConfigState>,
+    actor: Option<Extension<AuditActor>>,
+    Json(request): Json<NamingConfigResource>,
+) -> ApiResult<Json<NamingConfigResource>> {
+    validate_naming(&request)?;
+    state.save(ConfigSection::Naming, &request, actor).await?;
+
+    let config = {
+        let mut config = state
+            .rename_config
+            .write()
+            .unwrap_or_else(|e| e.into_inner());
+        request.apply_to(&mut config);
+        config.clone()
+    };
+    if let Some(pipeline) = &state.import_pipeline {
+        pipeline.set_rename_config(config);
+    }
+
+    info!(
+        "Saved naming settings: '{}' in '{}'",
+        request.standard_movie_format, request.movie_folder_format
+    );
+    Ok(Json(request))
+}
+
+/// GET /api/v3/config/mediamanagement - Media management settings
+#[instrument(skip(state))]
+pub async fn get_media_management_config(
+    State(state): State<ConfigState>,
+) -> Json<MediaManagementConfigResource> {
+    Json(
+        state
+            .media_management
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone(),
+    )
+}
+
+/// PUT /api/v3/config/mediamanagement - Save media management settings, used
+/// from the next start
+#[instrument(skip(state, actor, request))]
+pub async fn update_media_management_config(
+    State(state): State<ConfigState>,
+    actor: Option<Extension<AuditActor>>,
+    Json(request): Json<MediaManagementConfigResource>,
+) -> ApiResult<Json<MediaManagementConfigResource>> {
+    state
+        .save(ConfigSection::MediaManagement, &request, actor)
+        .await?;
+    *state
+        .media_management
+        .write()
+        .unwrap_or_else(|e| e.into_inner()) = request.clone();
+
+    info!("Saved media management settings, restart to apply them");
+    Ok(Json(request))
+}
+
+/// Create settings router
+pub fn create_config_router(state: ConfigState) -> Router {
+    Router::new()
+        .route(
+            "/v3/config/host",
+            get(get_host_config).put(update_host_config),
+        )
+        .route(
+            "/v3/config/naming",
+            get(get_naming_config).put(update_naming_config),
+        )
+        .route(
+            "/v3/config/mediamanagement",
+            get(get_media_management_config).put(update_media_management_config),
+        )
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_host_normalizes_locale() {
+        let host = validate_host(HostConfigResource {
+            port: 8080,
+            locale: "en_us".to_string(),
+        })
+        .unwrap();
+        assert_eq!(host.locale, "en-US");
+
+        assert!(matches!(
+            validate_host(HostConfigResource {
+                port: 0,
+                ..HostConfigResource::default()
+            }),
+            Err(ApiError::ValidationError { field, .. }) if field == "port"
+        ));
+        assert!(matches!(
+            validate_host(HostConfigResource {
+                port: 8080,
+                locale: "xx-XX".to_string(),
+            }),
+            Err(ApiError::ValidationError { field, .. }) if field == "locale"
+        ));
+    }
+
+    #[test]
+    fn test_naming_round_trips_through_rename_config() {
+        let naming = NamingConfigResource {
+            standard_movie_format: "{Movie Title} ({Release Year})".to_string(),
+            movie_folder_format: "{Movie Title}".to_string(),
+            colon_replacement_format: ColonReplacement::SpaceDash,
+        };
+        assert!(validate_naming(&naming).is_ok());
+
+        let mut config = RenameConfig::default();
+        naming.apply_to(&mut config);
+        assert_eq!(NamingConfigResource::from(&config), naming);
+
+        let unbalanced = NamingConfigResource {
+            movie_folder_format: "{Movie Title".to_string(),
+            ..naming
+        };
+        assert!(matches!(
+            validate_naming(&unbalanced),
+            Err(ApiError::ValidationError { field, .. }) if field == "movieFolderFormat"
+        ));
+    }
+}
@@ -0,0 +1,66 @@
+//! Diagnostics API handlers
+//!
+//! `/api/v3/system/diagnostics/import` lists the forensic bundles written
+//! for failed imports and downloads one as a JSON attachment.
+
+use crate::error::ApiResult;
+use axum::{
+    extract::{Path, State},
+    http::header,
+    response::{IntoResponse, Json, Response},
+    routing::get,
+    Router,
+};
+use radarr_import::{ForensicBundleStore, ForensicBundleSummary};
+use std::sync::Arc;
+use tracing::instrument;
+
+/// Diagnostics state
+#[derive(Clone)]
+pub struct DiagnosticsState {
+    pub forensics: Arc<ForensicBundleStore>,
+}
+
+impl DiagnosticsState {
+    pub fn new(forensics: Arc<ForensicBundleStore>) -> Self {
+        Self { forensics }
+    }
+}
+
+/// GET /api/v3/system/diagnostics/import - List import forensic bundles
+#[instrument(skip(state))]
+pub async fn list_import_bundles(
+    State(state): State<DiagnosticsState>,
+) -> ApiResult<Json<Vec<ForensicBundleSummary>>> {
+    Ok(Json(state.forensics.list().await?))
+}
+
+/// GET /api/v3/system/diagnostics/import/:id - Download an import forensic bundle
+#[instrument(skip(state))]
+pub async fn download_import_bundle(
+    State(state): State<DiagnosticsState>,
+    Path(id): Path<String>,
+) -> ApiResult<Response> {
+    let contents = state.forensics.read(&id).await?;
+    let disposition = format!("attachment; filename=\"import-{}.json\"", id);
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/json".to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        contents,
+    )
+        .into_response())
+}
+
+/// Create the diagnostics router
+pub fn create_diagnostics_router(state: DiagnosticsState) -> Router {
+    Router::new()
+        .route("/v3/system/diagnostics/import", get(list_import_bundles))
+        .route(
+            "/v3/system/diagnostics/import/:id",
+            get(download_import_bundle),
+        )
+        .with_state(state)
+}
@@ -0,0 +1,158 @@
+//! Disk space API handlers
+//!
+//! Reports free and total space for every root folder that is currently
+//! mounted. Folders whose directory is missing or unreadable are left out
+//! rather than reported as empty.
+//!
+//! Every measurement also feeds the storage throttle, which holds grabs for
+//! nearly full root folders as pending; its state and a manual override are
+//! exposed under `/v3/diskspace/throttle`.
+
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::root_folders::RootFolderState;
+use axum::{
+    extract::{Path, State},
+    response::Json,
+    routing::{get, put},
+    Router,
+};
+use radarr_core::services::RootFolderSpace;
+use radarr_import::{disk_usage, refresh_storage_throttle};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, instrument};
+
+/// Disk space of one root folder
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiskSpaceResource {
+    pub path: String,
+    /// Last path component, or the path itself for `/`
+    pub label: String,
+    pub free_space: u64,
+    pub total_space: u64,
+}
+
+/// Display label for a root folder path
+fn label_for(path: &str) -> String {
+    std::path::Path::new(path)
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.to_string())
+}
+
+/// GET /api/v3/diskspace - Free and total space per mounted root folder
+#[instrument(skip(state))]
+pub async fn get_disk_space(
+    State(state): State<RootFolderState>,
+) -> ApiResult<Json<Vec<DiskSpaceResource>>> {
+    let root_folders = state
+        .root_folder_repo
+        .list()
+        .await
+        .map_err(ApiError::CoreError)?;
+
+    let mut resources = Vec::with_capacity(root_folders.len());
+    for root_folder in root_folders {
+        let is_dir = tokio::fs::metadata(&root_folder.path)
+            .await
+            .map(|metadata| metadata.is_dir())
+            .unwrap_or(false);
+        if !is_dir {
+            debug!("Root folder {} is not mounted", root_folder.path);
+            continue;
+        }
+
+        match disk_usage(std::path::Path::new(&root_folder.path)).await {
+            Ok(usage) => {
+                state
+                    .storage_throttle
+                    .record(&root_folder, usage.free_bytes);
+                resources.push(DiskSpaceResource {
+                    label: label_for(&root_folder.path),
+                    path: root_folder.path,
+                    free_space: usage.free_bytes,
+                    total_space: usage.total_bytes,
+                })
+            }
+            Err(e) => debug!("Cannot read disk space of {}: {}", root_folder.path, e),
+        }
+    }
+
+    Ok(Json(resources))
+}
+
+/// Grab throttling state of every measured root folder
+#[derive(Debug, Serialize)]
+pub struct StorageThrottleResource {
+    pub folders: Vec<RootFolderSpace>,
+    /// Health warnings for folders that are low on space
+    pub warnings: Vec<String>,
+}
+
+/// Manual throttle override
+#[derive(Debug, Deserialize)]
+pub struct StorageOverrideRequest {
+    /// Let grabs through while the folder is low on space
+    #[serde(rename = "override")]
+    pub overridden: bool,
+}
+
+/// GET /api/v3/diskspace/throttle - Measure root folders and report which
+/// ones are holding grabs
+#[instrument(skip(state))]
+pub async fn get_storage_throttle(
+    State(state): State<RootFolderState>,
+) -> ApiResult<Json<StorageThrottleResource>> {
+    let root_folders = state
+        .root_folder_repo
+        .list()
+        .await
+        .map_err(ApiError::CoreError)?;
+    refresh_storage_throttle(&state.storage_throttle, &root_folders).await;
+
+    Ok(Json(StorageThrottleResource {
+        folders: state.storage_throttle.snapshot(),
+        warnings: state.storage_throttle.warnings(),
+    }))
+}
+
+/// PUT /api/v3/diskspace/throttle/:id - Override throttling of a root folder
+#[instrument(skip(state))]
+pub async fn set_storage_override(
+    State(state): State<RootFolderState>,
+    Path(id): Path<i32>,
+    Json(request): Json<StorageOverrideRequest>,
+) -> ApiResult<Json<RootFolderSpace>> {
+    let space = state
+        .storage_throttle
+        .set_override(id, request.overridden)
+        .ok_or_else(|| ApiError::NotFound {
+            resource: format!("Measured root folder {}", id),
+        })?;
+    info!(
+        "Grab throttle override for {} {}",
+        space.path,
+        if space.overridden { "set" } else { "cleared" }
+    );
+    Ok(Json(space))
+}
+
+/// Create disk space router
+pub fn create_diskspace_router(state: RootFolderState) -> Router {
+    Router::new()
+        .route("/v3/diskspace", get(get_disk_space))
+        .route("/v3/diskspace/throttle", get(get_storage_throttle))
+        .route("/v3/diskspace/throttle/:id", put(set_storage_override))
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_label_for() {
+        assert_eq!(label_for("/media/movies"), "movies");
+        assert_eq!(label_for("/"), "/");
+    }
+}
@@ -0,0 +1,47 @@
+//! Downloads cleanup API handlers
+//!
+//! `/api/v3/system/cleanup/downloads` is a dry run of the downloads folder
+//! cleanup: it lists the orphaned entries the `DownloadsCleanup` command
+//! would remove, without removing anything.
+
+use crate::error::{ApiError, ApiResult};
+use axum::{extract::State, response::Json, routing::get, Router};
+use radarr_import::{CleanupReport, DownloadsCleanupService};
+use std::sync::Arc;
+use tracing::instrument;
+
+/// Downloads cleanup state
+#[derive(Clone, Default)]
+pub struct DownloadsCleanupState {
+    /// `None` when there is no download queue to check entries against
+    pub cleanup: Option<Arc<DownloadsCleanupService>>,
+}
+
+impl DownloadsCleanupState {
+    pub fn new(cleanup: Arc<DownloadsCleanupService>) -> Self {
+        Self {
+            cleanup: Some(cleanup),
+        }
+    }
+}
+
+/// GET /api/v3/system/cleanup/downloads - List orphaned downloads entries
+#[instrument(skip(state))]
+pub async fn preview_downloads_cleanup(
+    State(state): State<DownloadsCleanupState>,
+) -> ApiResult<Json<CleanupReport>> {
+    let cleanup = state.cleanup.ok_or_else(|| ApiError::ServiceUnavailable {
+        message: "Downloads cleanup needs a configured download client".to_string(),
+    })?;
+    Ok(Json(cleanup.scan().await?))
+}
+
+/// Create the downloads cleanup router
+pub fn create_downloads_cleanup_router(state: DownloadsCleanupState) -> Router {
+    Router::new()
+        .route(
+            "/v3/system/cleanup/downloads",
+            get(preview_downloads_cleanup),
+        )
+        .with_state(state)
+}
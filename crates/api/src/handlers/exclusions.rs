@@ -0,0 +1,218 @@
+//! Import list exclusion API handlers
+//!
+//! Excluded movies are skipped by list sync and monitored collections, and
+//! adding one by hand requires removing its exclusion first. Deleting a movie
+//! with "add exclusion" creates one; these endpoints list, add and remove
+//! them directly.
+
+use crate::error::{ApiError, ApiResult};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    routing::get,
+    Router,
+};
+use radarr_core::{domain::repositories::ListExclusionRepository, models::ListExclusion};
+use radarr_infrastructure::{DatabasePool, PostgresListExclusionRepository};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{info, instrument};
+use uuid::Uuid;
+
+/// Exclusion state
+#[derive(Clone)]
+pub struct ExclusionState {
+    pub exclusion_repo: Arc<dyn ListExclusionRepository>,
+}
+
+impl ExclusionState {
+    pub fn new(database_pool: DatabasePool) -> Self {
+        Self {
+            exclusion_repo: Arc::new(PostgresListExclusionRepository::new(database_pool)),
+        }
+    }
+}
+
+/// Exclusion create request
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExclusionRequest {
+    pub tmdb_id: Option<i32>,
+    pub imdb_id: Option<String>,
+    #[serde(default)]
+    pub movie_title: String,
+    pub movie_year: Option<i32>,
+    pub reason: Option<String>,
+}
+
+/// Exclusion as returned by the API
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExclusionResource {
+    pub id: Uuid,
+    pub tmdb_id: Option<i32>,
+    pub imdb_id: Option<String>,
+    pub movie_title: String,
+    pub movie_year: Option<i32>,
+    pub reason: Option<String>,
+    pub excluded_by: Option<String>,
+    pub created_at: String,
+}
+
+impl From<ListExclusion> for ExclusionResource {
+    fn from(exclusion: ListExclusion) -> Self {
+        Self {
+            id: exclusion.id,
+            tmdb_id: exclusion.tmdb_id,
+            imdb_id: exclusion.imdb_id,
+            movie_title: exclusion.title,
+            movie_year: exclusion.year,
+            reason: exclusion.reason,
+            excluded_by: exclusion.excluded_by,
+            created_at: exclusion.created_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Validate a create request into an exclusion
+fn parse_request(request: ExclusionRequest) -> ApiResult<ListExclusion> {
+    let imdb_id = request
+        .imdb_id
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty());
+    if request.tmdb_id.is_none() && imdb_id.is_none() {
+        return Err(ApiError::ValidationError {
+            field: "tmdbId".to_string(),
+            message: "A TMDb or IMDb ID is required".to_string(),
+        });
+    }
+    if let Some(id) = &imdb_id {
+        let digits = id.strip_prefix("tt").unwrap_or_default();
+        if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+            return Err(ApiError::ValidationError {
+                field: "imdbId".to_string(),
+                message: format!("Invalid IMDb ID: {}", id),
+            });
+        }
+    }
+
+    Ok(ListExclusion {
+        id: Uuid::new_v4(),
+        tmdb_id: request.tmdb_id,
+        imdb_id,
+        title: request.movie_title.trim().to_string(),
+        year: request.movie_year,
+        reason: request.reason,
+        excluded_by: Some("api".to_string()),
+        created_at: chrono::Utc::now(),
+    })
+}
+
+/// GET /api/v3/exclusions - List exclusions, newest first
+#[instrument(skip(state))]
+pub async fn list_exclusions(
+    State(state): State<ExclusionState>,
+) -> ApiResult<Json<Vec<ExclusionResource>>> {
+    let exclusions = state.exclusion_repo.list().await?;
+    Ok(Json(exclusions.into_iter().map(Into::into).collect()))
+}
+
+/// GET /api/v3/exclusions/:id - Get an exclusion
+#[instrument(skip(state))]
+pub async fn get_exclusion(
+    State(state): State<ExclusionState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<ExclusionResource>> {
+    let exclusion =
+        state
+            .exclusion_repo
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| ApiError::NotFound {
+                resource: format!("exclusion with id {}", id),
+            })?;
+    Ok(Json(exclusion.into()))
+}
+
+/// POST /api/v3/exclusions - Exclude a movie; an existing exclusion for the
+/// same movie is returned as is
+#[instrument(skip(state, request))]
+pub async fn create_exclusion(
+    State(state): State<ExclusionState>,
+    Json(request): Json<ExclusionRequest>,
+) -> ApiResult<(StatusCode, Json<ExclusionResource>)> {
+    let exclusion = parse_request(request)?;
+
+    let existing = match exclusion.tmdb_id {
+        Some(tmdb_id) => state.exclusion_repo.find_by_tmdb_id(tmdb_id).await?,
+        None => None,
+    };
+    let existing = match (existing, &exclusion.imdb_id) {
+        (None, Some(imdb_id)) => state.exclusion_repo.find_by_imdb_id(imdb_id).await?,
+        (existing, _) => existing,
+    };
+    if let Some(existing) = existing {
+        return Ok((StatusCode::OK, Json(existing.into())));
+    }
+
+    state.exclusion_repo.create(&exclusion).await?;
+    info!(
+        "Excluded '{}' (tmdb {:?}, imdb {:?})",
+        exclusion.title, exclusion.tmdb_id, exclusion.imdb_id
+    );
+    Ok((StatusCode::CREATED, Json(exclusion.into())))
+}
+
+/// DELETE /api/v3/exclusions/:id - Remove an exclusion so the movie can be
+/// added again
+#[instrument(skip(state))]
+pub async fn delete_exclusion(
+    State(state): State<ExclusionState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<StatusCode> {
+    state.exclusion_repo.delete(id).await?;
+    info!("Removed exclusion {}", id);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Create the exclusion router
+pub fn create_exclusion_router(state: ExclusionState) -> Router {
+    Router::new()
+        .route(
+            "/v3/exclusions",
+            get(list_exclusions).post(create_exclusion),
+        )
+        .route(
+            "/v3/exclusions/:id",
+            get(get_exclusion).delete(delete_exclusion),
+        )
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(tmdb_id: Option<i32>, imdb_id: Option<&str>) -> ExclusionRequest {
+        ExclusionRequest {
+            tmdb_id,
+            imdb_id: imdb_id.map(str::to_string),
+            movie_title: " Dune ".to_string(),
+            movie_year: Some(2021),
+            reason: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_request_requires_an_external_id() {
+        let exclusion = parse_request(request(Some(438631), Some(" tt1160419 "))).unwrap();
+        assert_eq!(exclusion.title, "Dune");
+        assert_eq!(exclusion.imdb_id.as_deref(), Some("tt1160419"));
+
+        assert!(parse_request(request(None, Some("tt1160419"))).is_ok());
+        assert!(parse_request(request(None, None)).is_err());
+        assert!(parse_request(request(None, Some(""))).is_err());
+        assert!(parse_request(request(Some(438631), Some("1160419"))).is_err());
+    }
+}
@@ -0,0 +1,230 @@
+//! History API handlers
+//!
+//! Exposes the persisted grab/import/failure/deletion history, newest first,
+//! with pagination and filtering by event type, movie and date.
+
+use crate::error::{ApiError, ApiResult};
+use crate::models::PaginatedResponse;
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+    routing::get,
+    Router,
+};
+use radarr_core::{
+    domain::repositories::HistoryRepository,
+    models::{HistoryEventType, HistoryQuery, HistoryRecord},
+};
+use radarr_infrastructure::{DatabasePool, PostgresHistoryRepository};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::instrument;
+use uuid::Uuid;
+
+/// Largest page a client may request
+const MAX_HISTORY_PAGE_SIZE: u32 = 1000;
+
+/// History state
+#[derive(Clone)]
+pub struct HistoryState {
+    pub history_repo: Arc<dyn HistoryRepository>,
+}
+
+impl HistoryState {
+    pub fn new(database_pool: DatabasePool) -> Self {
+        Self {
+            history_repo: Arc::new(PostgresHistoryRepository::new(database_pool)),
+        }
+    }
+}
+
+/// Query parameters for history listing
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryQueryParams {
+    pub page: Option<u32>,
+    pub page_size: Option<u32>,
+    /// Comma separated event types, e.g. `grabbed,imported`
+    pub event_type: Option<String>,
+    pub movie_id: Option<Uuid>,
+    /// Only entries dated at or after this time
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl HistoryQueryParams {
+    fn page(&self) -> u32 {
+        self.page.unwrap_or(1).max(1)
+    }
+
+    fn page_size(&self) -> u32 {
+        self.page_size.unwrap_or(50).clamp(1, MAX_HISTORY_PAGE_SIZE)
+    }
+
+    /// Build the repository query, rejecting unknown event types
+    fn to_query(&self) -> ApiResult<HistoryQuery> {
+        let event_types = match &self.event_type {
+            Some(event_types) => event_types
+                .split(',')
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(|value| {
+                    HistoryEventType::parse(value).ok_or_else(|| ApiError::ValidationError {
+                        field: "eventType".to_string(),
+                        message: format!(
+                            "Unknown event type '{}', expected one of: {}",
+                            value,
+                            HistoryEventType::ALL
+                                .iter()
+                                .map(|event_type| event_type.as_str())
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        ),
+                    })
+                })
+                .collect::<ApiResult<Vec<_>>>()?,
+            None => Vec::new(),
+        };
+
+        let page_size = self.page_size();
+        Ok(HistoryQuery {
+            event_types,
+            movie_id: self.movie_id,
+            since: self.since,
+            limit: page_size as i64,
+            offset: (self.page() - 1) as i64 * page_size as i64,
+        })
+    }
+}
+
+/// History entry as returned by the API
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryResource {
+    pub id: Uuid,
+    pub event_type: HistoryEventType,
+    pub movie_id: Option<Uuid>,
+    pub source_title: Option<String>,
+    pub download_id: Option<String>,
+    pub data: serde_json::Value,
+    pub date: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<HistoryRecord> for HistoryResource {
+    fn from(record: HistoryRecord) -> Self {
+        Self {
+            id: record.id,
+            event_type: record.event_type,
+            movie_id: record.movie_id,
+            source_title: record.source_title,
+            download_id: record.download_id,
+            data: record.data,
+            date: record.date,
+        }
+    }
+}
+
+/// GET /api/v3/history - Page through history, optionally filtered
+#[instrument(skip(state))]
+pub async fn list_history(
+    State(state): State<HistoryState>,
+    Query(params): Query<HistoryQueryParams>,
+) -> ApiResult<Json<PaginatedResponse<HistoryResource>>> {
+    page_history(&state, &params, params.to_query()?).await
+}
+
+/// GET /api/v3/history/movie/:movie_id - History for a single movie
+#[instrument(skip(state))]
+pub async fn list_movie_history(
+    State(state): State<HistoryState>,
+    Path(movie_id): Path<Uuid>,
+    Query(params): Query<HistoryQueryParams>,
+) -> ApiResult<Json<PaginatedResponse<HistoryResource>>> {
+    let query = HistoryQuery {
+        movie_id: Some(movie_id),
+        ..params.to_query()?
+    };
+    page_history(&state, &params, query).await
+}
+
+/// GET /api/v3/history/:id - A single history entry
+#[instrument(skip(state))]
+pub async fn get_history_entry(
+    State(state): State<HistoryState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<HistoryResource>> {
+    let record = state
+        .history_repo
+        .find_by_id(id)
+        .await
+        .map_err(ApiError::CoreError)?
+        .ok_or_else(|| ApiError::NotFound {
+            resource: format!("history entry {}", id),
+        })?;
+
+    Ok(Json(record.into()))
+}
+
+async fn page_history(
+    state: &HistoryState,
+    params: &HistoryQueryParams,
+    query: HistoryQuery,
+) -> ApiResult<Json<PaginatedResponse<HistoryResource>>> {
+    let (records, total) = state
+        .history_repo
+        .list(&query)
+        .await
+        .map_err(ApiError::CoreError)?;
+
+    Ok(Json(PaginatedResponse::new(
+        params.page(),
+        params.page_size(),
+        total,
+        records.into_iter().map(HistoryResource::from).collect(),
+    )))
+}
+
+/// Create history router
+pub fn create_history_router(state: HistoryState) -> Router {
+    Router::new()
+        .route("/v3/history", get(list_history))
+        .route("/v3/history/movie/:movie_id", get(list_movie_history))
+        .route("/v3/history/:id", get(get_history_entry))
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_params_paging_and_filters() {
+        let params = HistoryQueryParams {
+            page: Some(3),
+            page_size: Some(20),
+            event_type: Some("grabbed, importFailed".to_string()),
+            movie_id: None,
+            since: None,
+        };
+
+        let query = params.to_query().unwrap();
+        assert_eq!(query.limit, 20);
+        assert_eq!(query.offset, 40);
+        assert_eq!(
+            query.event_types,
+            vec![HistoryEventType::Grabbed, HistoryEventType::ImportFailed]
+        );
+    }
+
+    #[test]
+    fn test_query_params_reject_unknown_event_type() {
+        let params = HistoryQueryParams {
+            event_type: Some("renamed".to_string()),
+            ..Default::default()
+        };
+        assert!(params.to_query().is_err());
+
+        let defaults = HistoryQueryParams::default().to_query().unwrap();
+        assert_eq!((defaults.limit, defaults.offset), (50, 0));
+        assert!(defaults.event_types.is_empty());
+    }
+}
@@ -0,0 +1,696 @@
+//! Indexer definition API handlers
+//!
+//! Indexers are persisted in the `indexers` table so they can be managed
+//! through the API rather than only through environment variables.
+//!
+//! Saving an enabled indexer queues a check in the background: a capability
+//! probe followed by a canary search for a well-known movie. The outcome is
+//! stored per indexer and served from `/v3/indexer/:id/check`.
+
+use crate::error::{ApiError, ApiResult};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use radarr_core::{
+    domain::repositories::{IndexerCheckRepository, IndexerRepository},
+    models::{Indexer, IndexerCheck, IndexerImplementation},
+};
+use radarr_decision::Quality;
+use radarr_indexers::{
+    HDBitsClient, HDBitsConfig, IndexerClient, ProwlarrClient, ProwlarrConfigBuilder,
+    ProwlarrSearchResult, SearchRequest, TorznabClient, TorznabConfig,
+};
+use radarr_infrastructure::{
+    DatabasePool, PostgresIndexerCheckRepository, PostgresIndexerRepository,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::{debug, error, info, instrument, warn};
+
+/// Canary movie: popular and old enough that every general indexer has it
+const CANARY_IMDB_ID: &str = "tt0133093";
+const CANARY_TITLE: &str = "The Matrix 1999";
+/// Results requested from the canary search
+const CANARY_LIMIT: i32 = 25;
+/// Result titles kept with a check
+const CANARY_SAMPLE_TITLES: usize = 5;
+
+/// Indexer management state
+#[derive(Clone)]
+pub struct IndexerState {
+    pub database_pool: DatabasePool,
+    pub indexer_repo: Arc<PostgresIndexerRepository>,
+    pub check_repo: Arc<dyn IndexerCheckRepository>,
+}
+
+impl IndexerState {
+    pub fn new(database_pool: DatabasePool) -> Self {
+        let indexer_repo = Arc::new(PostgresIndexerRepository::new(database_pool.clone()));
+        let check_repo = Arc::new(PostgresIndexerCheckRepository::new(database_pool.clone()));
+        Self {
+            database_pool,
+            indexer_repo,
+            check_repo,
+        }
+    }
+
+    /// Store a pending check and run the real one in the background
+    async fn queue_check(&self, indexer: Indexer) -> ApiResult<IndexerCheck> {
+        let pending = IndexerCheck::pending(indexer.id);
+        self.check_repo
+            .upsert(&pending)
+            .await
+            .map_err(ApiError::CoreError)?;
+
+        let check_repo = self.check_repo.clone();
+        tokio::spawn(async move {
+            let check = run_check(&indexer).await;
+            info!(
+                "Indexer '{}' check {}: {}",
+                indexer.name, check.status, check.message
+            );
+            if let Err(e) = check_repo.upsert(&check).await {
+                error!("Failed to store check of indexer '{}': {}", indexer.name, e);
+            }
+        });
+
+        Ok(pending)
+    }
+}
+
+/// Indexer create/update request for API
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexerRequest {
+    pub name: String,
+    pub implementation: String,
+    #[serde(default)]
+    pub settings: serde_json::Value,
+    pub enabled: Option<bool>,
+    pub priority: Option<i32>,
+    pub enable_rss: Option<bool>,
+    pub enable_automatic_search: Option<bool>,
+    pub enable_interactive_search: Option<bool>,
+    pub download_client_id: Option<i32>,
+    /// Only use the indexer for movies with one of these tags
+    pub tags: Option<Vec<i32>>,
+}
+
+/// Indexer response for API
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexerResponse {
+    pub id: i32,
+    pub name: String,
+    pub implementation: String,
+    pub settings: serde_json::Value,
+    pub enabled: bool,
+    pub priority: i32,
+    pub enable_rss: bool,
+    pub enable_automatic_search: bool,
+    pub enable_interactive_search: bool,
+    pub download_client_id: Option<i32>,
+    pub tags: Vec<i32>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Result of an indexer connectivity test
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexerTestResponse {
+    pub id: i32,
+    pub name: String,
+    pub success: bool,
+    pub message: String,
+    pub execution_time_ms: u128,
+}
+
+/// Settings keys that hold secrets and are masked in responses
+const SECRET_SETTINGS: &[&str] = &["api_key", "passkey"];
+
+impl From<Indexer> for IndexerResponse {
+    fn from(indexer: Indexer) -> Self {
+        Self {
+            id: indexer.id,
+            name: indexer.name,
+            implementation: indexer.implementation.to_string(),
+            settings: mask_secrets(indexer.settings),
+            enabled: indexer.enabled,
+            priority: indexer.priority,
+            enable_rss: indexer.enable_rss,
+            enable_automatic_search: indexer.enable_automatic_search,
+            enable_interactive_search: indexer.enable_interactive_search,
+            download_client_id: indexer.download_client_id,
+            tags: indexer.tags,
+            created_at: indexer.created_at.to_rfc3339(),
+            updated_at: indexer.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Replace secret setting values so they are never echoed back to clients
+fn mask_secrets(mut settings: serde_json::Value) -> serde_json::Value {
+    if let Some(obj) = settings.as_object_mut() {
+        for key in SECRET_SETTINGS {
+            if let Some(value) = obj.get_mut(*key) {
+                if value.as_str().is_some_and(|s| !s.is_empty()) {
+                    *value = serde_json::Value::String("********".to_string());
+                }
+            }
+        }
+    }
+    settings
+}
+
+/// Parse an implementation name as accepted by the API
+fn parse_implementation(value: &str) -> ApiResult<IndexerImplementation> {
+    match value.to_lowercase().as_str() {
+        "prowlarr" => Ok(IndexerImplementation::Prowlarr),
+        "jackett" => Ok(IndexerImplementation::Jackett),
+        "torznab" => Ok(IndexerImplementation::Torznab),
+        "newznab" => Ok(IndexerImplementation::Newznab),
+        "hdbits" => Ok(IndexerImplementation::HDBits),
+        other => Err(ApiError::ValidationError {
+            field: "implementation".to_string(),
+            message: format!("Unsupported indexer implementation: {}", other),
+        }),
+    }
+}
+
+/// Settings keys required for each implementation
+fn required_settings(implementation: &IndexerImplementation) -> &'static [&'static str] {
+    match implementation {
+        IndexerImplementation::HDBits => &["username", "passkey"],
+        _ => &["base_url", "api_key"],
+    }
+}
+
+fn setting<'a>(settings: &'a serde_json::Value, key: &str) -> Option<&'a str> {
+    settings
+        .get(key)
+        .and_then(|v| v.as_str())
+        .filter(|v| !v.trim().is_empty())
+}
+
+/// Validate a request and apply it onto an indexer
+fn apply_request(indexer: &mut Indexer, request: IndexerRequest) -> ApiResult<()> {
+    if request.name.trim().is_empty() {
+        return Err(ApiError::ValidationError {
+            field: "name".to_string(),
+            message: "Indexer name cannot be empty".to_string(),
+        });
+    }
+
+    let implementation = parse_implementation(&request.implementation)?;
+
+    let mut settings = if request.settings.is_null() {
+        serde_json::json!({})
+    } else {
+        request.settings
+    };
+    if !settings.is_object() {
+        return Err(ApiError::ValidationError {
+            field: "settings".to_string(),
+            message: "Settings must be a JSON object".to_string(),
+        });
+    }
+
+    // Masked secrets sent back unchanged keep the stored value
+    if let (Some(new), Some(old)) = (settings.as_object_mut(), indexer.settings.as_object()) {
+        for key in SECRET_SETTINGS {
+            if new.get(*key).and_then(|v| v.as_str()) == Some("********") {
+                match old.get(*key) {
+                    Some(existing) => new.insert(key.to_string(), existing.clone()),
+                    None => new.remove(*key),
+                };
+            }
+        }
+    }
+
+    for key in required_settings(&implementation) {
+        if setting(&settings, key).is_none() {
+            return Err(ApiError::ValidationError {
+                field: format!("settings.{}", key),
+                message: format!("{} indexers require '{}'", implementation, key),
+            });
+        }
+    }
+
+    if let Some(base_url) = setting(&settings, "base_url") {
+        if url::Url::parse(base_url).is_err() {
+            return Err(ApiError::ValidationError {
+                field: "settings.base_url".to_string(),
+                message: format!("Invalid base URL: {}", base_url),
+            });
+        }
+    }
+
+    let priority = request.priority.unwrap_or(indexer.priority);
+    if !(1..=50).contains(&priority) {
+        return Err(ApiError::ValidationError {
+            field: "priority".to_string(),
+            message: "Priority must be between 1 and 50".to_string(),
+        });
+    }
+
+    indexer.name = request.name.trim().to_string();
+    indexer.implementation = implementation;
+    indexer.update_settings(settings);
+    indexer.priority = priority;
+    indexer.enabled = request.enabled.unwrap_or(indexer.enabled);
+    indexer.enable_rss = request.enable_rss.unwrap_or(indexer.enable_rss);
+    indexer.enable_automatic_search = request
+        .enable_automatic_search
+        .unwrap_or(indexer.enable_automatic_search);
+    indexer.enable_interactive_search = request
+        .enable_interactive_search
+        .unwrap_or(indexer.enable_interactive_search);
+    indexer.download_client_id = request.download_client_id;
+    if let Some(tags) = request.tags {
+        indexer.tags = crate::handlers::tags::normalize_tag_ids(tags);
+    }
+
+    Ok(())
+}
+
+/// Build a live client for a stored indexer definition
+fn build_client(indexer: &Indexer) -> ApiResult<Arc<dyn IndexerClient + Send + Sync>> {
+    let settings = &indexer.settings;
+    let required = |key: &str| {
+        setting(settings, key)
+            .map(str::to_string)
+            .ok_or_else(|| ApiError::ValidationError {
+                field: format!("settings.{}", key),
+                message: format!("Indexer '{}' has no '{}' configured", indexer.name, key),
+            })
+    };
+
+    let client: Arc<dyn IndexerClient + Send + Sync> = match indexer.implementation {
+        IndexerImplementation::Prowlarr => {
+            let config = ProwlarrConfigBuilder::new()
+                .base_url(required("base_url")?)
+                .api_key(required("api_key")?)
+                .build();
+            Arc::new(ProwlarrClient::new(config)?)
+        }
+        IndexerImplementation::Jackett | IndexerImplementation::Torznab => {
+            let config =
+                TorznabConfig::torznab(&indexer.name, required("base_url")?, required("api_key")?)
+                    .with_indexer_id(indexer.id);
+            Arc::new(TorznabClient::new(config)?)
+        }
+        IndexerImplementation::Newznab => {
+            let config =
+                TorznabConfig::newznab(&indexer.name, required("base_url")?, required("api_key")?)
+                    .with_indexer_id(indexer.id);
+            Arc::new(TorznabClient::new(config)?)
+        }
+        IndexerImplementation::HDBits => {
+            let config = HDBitsConfig {
+                username: required("username")?,
+                passkey: required("passkey")?,
+                ..HDBitsConfig::default()
+            };
+            Arc::new(HDBitsClient::new(config)?)
+        }
+    };
+
+    Ok(client)
+}
+
+/// Whether a search result is something the decision engine can work with
+fn is_parseable(result: &ProwlarrSearchResult) -> bool {
+    !result.title.trim().is_empty()
+        && !result.download_url.trim().is_empty()
+        && Quality::from_resolution(&result.title) != Quality::Unknown
+}
+
+/// Probe an indexer's capabilities, then run the canary search
+async fn run_check(indexer: &Indexer) -> IndexerCheck {
+    let start_time = Instant::now();
+    let mut check = IndexerCheck::pending(indexer.id);
+
+    let finished = |mut check: IndexerCheck| {
+        check.duration_ms = start_time.elapsed().as_millis() as i64;
+        check.checked_at = chrono::Utc::now();
+        check
+    };
+
+    let client = match build_client(indexer) {
+        Ok(client) => client,
+        Err(e) => {
+            check.fail(format!("Invalid configuration: {}", e));
+            return finished(check);
+        }
+    };
+
+    // Capability probe; the health check also validates the credentials
+    if let Err(e) = client.health_check().await {
+        check.fail(format!("Capability probe failed: {}", e));
+        return finished(check);
+    }
+    match client.get_indexers().await {
+        Ok(definitions) => {
+            check.movie_search = definitions
+                .iter()
+                .any(|definition| definition.capabilities.movie_search);
+            check.search_params = definitions
+                .iter()
+                .flat_map(|definition| definition.capabilities.search_params.iter().cloned())
+                .collect();
+            check.search_params.sort();
+            check.search_params.dedup();
+        }
+        Err(e) => {
+            check.fail(format!("Capability probe failed: {}", e));
+            return finished(check);
+        }
+    }
+
+    // Canary search, by IMDb ID where the indexer supports it
+    let supports_imdb = check
+        .search_params
+        .iter()
+        .any(|param| param.eq_ignore_ascii_case("imdbid"));
+    let (request, query) = if supports_imdb {
+        (
+            SearchRequest::for_movie_imdb(CANARY_IMDB_ID),
+            CANARY_IMDB_ID,
+        )
+    } else {
+        (SearchRequest::for_movie_title(CANARY_TITLE), CANARY_TITLE)
+    };
+    check.canary_query = Some(query.to_string());
+
+    match client.search(&request.with_limit(CANARY_LIMIT)).await {
+        Ok(response) => {
+            check.canary_results = response.results.len() as i32;
+            check.parseable_results =
+                response.results.iter().filter(|r| is_parseable(r)).count() as i32;
+            check.sample_titles = response
+                .results
+                .iter()
+                .take(CANARY_SAMPLE_TITLES)
+                .map(|result| result.title.clone())
+                .collect();
+            debug!(
+                "Canary search on '{}' returned {} results",
+                indexer.name, check.canary_results
+            );
+            check.grade();
+        }
+        Err(e) => check.fail(format!("Canary search failed: {}", e)),
+    }
+
+    finished(check)
+}
+
+async fn find_indexer(state: &IndexerState, id: i32) -> ApiResult<Indexer> {
+    state
+        .indexer_repo
+        .find_by_id(id)
+        .await
+        .map_err(ApiError::CoreError)?
+        .ok_or_else(|| ApiError::NotFound {
+            resource: format!("indexer with id {}", id),
+        })
+}
+
+/// GET /api/v3/indexer - List all indexers
+#[instrument(skip(state))]
+pub async fn list_indexers(
+    State(state): State<IndexerState>,
+) -> ApiResult<Json<Vec<IndexerResponse>>> {
+    let indexers = state
+        .indexer_repo
+        .list()
+        .await
+        .map_err(ApiError::CoreError)?;
+
+    Ok(Json(indexers.into_iter().map(Into::into).collect()))
+}
+
+/// GET /api/v3/indexer/:id - Get indexer by ID
+#[instrument(skip(state))]
+pub async fn get_indexer(
+    State(state): State<IndexerState>,
+    Path(id): Path<i32>,
+) -> ApiResult<Json<IndexerResponse>> {
+    Ok(Json(find_indexer(&state, id).await?.into()))
+}
+
+/// POST /api/v3/indexer - Create new indexer
+#[instrument(skip(state, request))]
+pub async fn create_indexer(
+    State(state): State<IndexerState>,
+    Json(request): Json<IndexerRequest>,
+) -> ApiResult<(StatusCode, Json<IndexerResponse>)> {
+    info!("Creating indexer: {}", request.name);
+
+    if let Ok(Some(_)) = state.indexer_repo.find_by_name(request.name.trim()).await {
+        return Err(ApiError::Conflict {
+            resource: format!("indexer '{}'", request.name),
+        });
+    }
+
+    let mut indexer = Indexer::new(String::new(), IndexerImplementation::Torznab);
+    apply_request(&mut indexer, request)?;
+
+    let created = state
+        .indexer_repo
+        .create(&indexer)
+        .await
+        .map_err(ApiError::CoreError)?;
+
+    info!("Created indexer '{}' with ID: {}", created.name, created.id);
+    // The indexer is saved either way; a check that cannot be queued is only logged
+    if created.enabled {
+        if let Err(e) = state.queue_check(created.clone()).await {
+            warn!("Failed to queue check of indexer '{}': {}", created.name, e);
+        }
+    }
+    Ok((StatusCode::CREATED, Json(created.into())))
+}
+
+/// PUT /api/v3/indexer/:id - Update indexer
+#[instrument(skip(state, request))]
+pub async fn update_indexer(
+    State(state): State<IndexerState>,
+    Path(id): Path<i32>,
+    Json(request): Json<IndexerRequest>,
+) -> ApiResult<Json<IndexerResponse>> {
+    let mut indexer = find_indexer(&state, id).await?;
+
+    if let Ok(Some(other)) = state.indexer_repo.find_by_name(request.name.trim()).await {
+        if other.id != id {
+            return Err(ApiError::Conflict {
+                resource: format!("indexer '{}'", request.name),
+            });
+        }
+    }
+
+    apply_request(&mut indexer, request)?;
+
+    let updated = state
+        .indexer_repo
+        .update(&indexer)
+        .await
+        .map_err(ApiError::CoreError)?;
+
+    info!("Updated indexer '{}'", updated.name);
+    // The indexer is saved either way; a check that cannot be queued is only logged
+    if updated.enabled {
+        if let Err(e) = state.queue_check(updated.clone()).await {
+            warn!("Failed to queue check of indexer '{}': {}", updated.name, e);
+        }
+    }
+    Ok(Json(updated.into()))
+}
+
+/// DELETE /api/v3/indexer/:id - Delete indexer
+#[instrument(skip(state))]
+pub async fn delete_indexer(
+    State(state): State<IndexerState>,
+    Path(id): Path<i32>,
+) -> ApiResult<StatusCode> {
+    let indexer = find_indexer(&state, id).await?;
+
+    state
+        .indexer_repo
+        .delete(id)
+        .await
+        .map_err(ApiError::CoreError)?;
+
+    info!("Deleted indexer '{}'", indexer.name);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// POST /api/v3/indexer/:id/test - Validate connectivity of a stored indexer
+#[instrument(skip(state))]
+pub async fn test_indexer(
+    State(state): State<IndexerState>,
+    Path(id): Path<i32>,
+) -> ApiResult<Json<IndexerTestResponse>> {
+    let indexer = find_indexer(&state, id).await?;
+    let start_time = Instant::now();
+
+    let client = build_client(&indexer)?;
+    let (success, message) = match client.health_check().await {
+        Ok(true) => (true, "Connection successful".to_string()),
+        Ok(false) => (false, "Indexer did not respond as healthy".to_string()),
+        Err(e) => {
+            warn!("Indexer '{}' connectivity test failed: {}", indexer.name, e);
+            (false, e.to_string())
+        }
+    };
+
+    Ok(Json(IndexerTestResponse {
+        id: indexer.id,
+        name: indexer.name,
+        success,
+        message,
+        execution_time_ms: start_time.elapsed().as_millis(),
+    }))
+}
+
+/// GET /api/v3/indexer/:id/check - Latest capability probe and canary search
+#[instrument(skip(state))]
+pub async fn get_indexer_check(
+    State(state): State<IndexerState>,
+    Path(id): Path<i32>,
+) -> ApiResult<Json<IndexerCheck>> {
+    let indexer = find_indexer(&state, id).await?;
+
+    let check = state
+        .check_repo
+        .find_by_indexer_id(id)
+        .await
+        .map_err(ApiError::CoreError)?
+        .ok_or_else(|| ApiError::NotFound {
+            resource: format!("check of indexer '{}'", indexer.name),
+        })?;
+
+    Ok(Json(check))
+}
+
+/// POST /api/v3/indexer/:id/check - Queue a new check of an indexer
+#[instrument(skip(state))]
+pub async fn run_indexer_check(
+    State(state): State<IndexerState>,
+    Path(id): Path<i32>,
+) -> ApiResult<(StatusCode, Json<IndexerCheck>)> {
+    let indexer = find_indexer(&state, id).await?;
+    let pending = state.queue_check(indexer).await?;
+    Ok((StatusCode::ACCEPTED, Json(pending)))
+}
+
+/// Create indexer management router
+pub fn create_indexer_router(state: IndexerState) -> Router {
+    Router::new()
+        .route("/v3/indexer", get(list_indexers).post(create_indexer))
+        .route(
+            "/v3/indexer/:id",
+            get(get_indexer).put(update_indexer).delete(delete_indexer),
+        )
+        .route("/v3/indexer/:id/test", post(test_indexer))
+        .route(
+            "/v3/indexer/:id/check",
+            get(get_indexer_check).post(run_indexer_check),
+        )
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(implementation: &str, settings: serde_json::Value) -> IndexerRequest {
+        IndexerRequest {
+            name: "Test Indexer".to_string(),
+            implementation: implementation.to_string(),
+            settings,
+            enabled: None,
+            priority: None,
+            enable_rss: None,
+            enable_automatic_search: None,
+            enable_interactive_search: None,
+            download_client_id: None,
+            tags: None,
+        }
+    }
+
+    #[test]
+    fn test_apply_request_validates_required_settings() {
+        let mut indexer = Indexer::new(String::new(), IndexerImplementation::Torznab);
+        let result = apply_request(
+            &mut indexer,
+            request(
+                "torznab",
+                serde_json::json!({"base_url": "http://localhost:9117"}),
+            ),
+        );
+        assert!(
+            matches!(result, Err(ApiError::ValidationError { field, .. }) if field == "settings.api_key")
+        );
+
+        let result = apply_request(
+            &mut indexer,
+            request(
+                "hdbits",
+                serde_json::json!({"username": "user", "passkey": "secret"}),
+            ),
+        );
+        assert!(result.is_ok());
+        assert_eq!(indexer.implementation, IndexerImplementation::HDBits);
+        assert!(matches!(
+            apply_request(&mut indexer, request("rarbg", serde_json::json!({}))),
+            Err(ApiError::ValidationError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_masked_secrets_preserve_stored_value() {
+        let mut indexer = Indexer::new("Jackett".to_string(), IndexerImplementation::Jackett);
+        indexer.settings = serde_json::json!({
+            "base_url": "http://localhost:9117",
+            "api_key": "real-key"
+        });
+
+        let response = IndexerResponse::from(indexer.clone());
+        assert_eq!(response.settings["api_key"], "********");
+
+        apply_request(&mut indexer, request("jackett", response.settings)).unwrap();
+        assert_eq!(indexer.api_key(), Some("real-key"));
+    }
+
+    #[test]
+    fn test_is_parseable() {
+        let result = |title: &str, download_url: &str| -> ProwlarrSearchResult {
+            serde_json::from_value(serde_json::json!({
+                "title": title,
+                "download_url": download_url,
+                "indexer_id": 1,
+                "indexer": "Test Indexer",
+                "categories": [],
+                "attributes": {}
+            }))
+            .unwrap()
+        };
+
+        assert!(is_parseable(&result(
+            "The.Matrix.1999.1080p.BluRay.x264-GROUP",
+            "http://localhost/1.torrent"
+        )));
+        assert!(!is_parseable(&result(
+            "The Matrix 1999",
+            "http://localhost/2.torrent"
+        )));
+        assert!(!is_parseable(&result("The.Matrix.1999.2160p.WEB-DL", "")));
+    }
+}
@@ -0,0 +1,173 @@
+//! Log API handlers
+//!
+//! `/api/v3/log` pages through the stored warnings and errors (and info
+//! messages when `RADARR_LOG_STORE_LEVEL=info`), newest first, optionally only
+//! those at or above a level. `/api/v3/log/file` lists the rotated log files
+//! and downloads them as they are on disk.
+
+use crate::error::{ApiError, ApiResult};
+use crate::models::PaginatedResponse;
+use axum::{
+    extract::{Path, Query, State},
+    http::header,
+    response::{IntoResponse, Json, Response},
+    routing::get,
+    Router,
+};
+use radarr_core::logs::{LogFile, LogLevel, LogQuery, LogRecord, LogStore};
+use serde::Deserialize;
+use std::sync::Arc;
+use tracing::instrument;
+
+/// Largest page a client may request
+const MAX_LOG_PAGE_SIZE: u32 = 1000;
+
+/// Log state
+#[derive(Clone)]
+pub struct LogState {
+    /// `None` when log storage is turned off or could not be opened
+    pub store: Option<Arc<LogStore>>,
+}
+
+impl LogState {
+    pub fn new(store: Option<Arc<LogStore>>) -> Self {
+        Self { store }
+    }
+
+    fn store(&self) -> ApiResult<Arc<LogStore>> {
+        self.store
+            .clone()
+            .ok_or_else(|| ApiError::ServiceUnavailable {
+                message: "Log storage is turned off or its directory is unavailable".to_string(),
+            })
+    }
+}
+
+/// Query parameters for log listing
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogQueryParams {
+    pub page: Option<u32>,
+    pub page_size: Option<u32>,
+    /// Only records at this level or more severe, e.g. `warn`
+    pub level: Option<String>,
+}
+
+impl LogQueryParams {
+    fn page(&self) -> u32 {
+        self.page.unwrap_or(1).max(1)
+    }
+
+    fn page_size(&self) -> u32 {
+        self.page_size.unwrap_or(50).clamp(1, MAX_LOG_PAGE_SIZE)
+    }
+
+    /// Build the store query, rejecting unknown levels
+    fn to_query(&self) -> ApiResult<LogQuery> {
+        let level = self
+            .level
+            .as_deref()
+            .map(|level| {
+                LogLevel::parse(level).ok_or_else(|| ApiError::ValidationError {
+                    field: "level".to_string(),
+                    message: format!(
+                        "Unknown level '{}', expected one of: trace, debug, info, warn, error",
+                        level
+                    ),
+                })
+            })
+            .transpose()?;
+
+        let page_size = self.page_size() as usize;
+        Ok(LogQuery {
+            level,
+            limit: page_size,
+            offset: (self.page() as usize - 1) * page_size,
+        })
+    }
+}
+
+/// GET /api/v3/log - Page through stored log records, newest first
+#[instrument(skip(state))]
+pub async fn list_logs(
+    State(state): State<LogState>,
+    Query(params): Query<LogQueryParams>,
+) -> ApiResult<Json<PaginatedResponse<LogRecord>>> {
+    let query = params.to_query()?;
+    let (records, total) = state.store()?.query(&query);
+
+    Ok(Json(PaginatedResponse::new(
+        params.page(),
+        params.page_size(),
+        total as i64,
+        records,
+    )))
+}
+
+/// GET /api/v3/log/file - Log files, the one being written first
+#[instrument(skip(state))]
+pub async fn list_log_files(State(state): State<LogState>) -> ApiResult<Json<Vec<LogFile>>> {
+    Ok(Json(state.store()?.files()))
+}
+
+/// GET /api/v3/log/file/:filename - Download a log file
+#[instrument(skip(state))]
+pub async fn download_log_file(
+    State(state): State<LogState>,
+    Path(filename): Path<String>,
+) -> ApiResult<Response> {
+    let store = state.store()?;
+    let name = filename.clone();
+    let contents = tokio::task::spawn_blocking(move || store.read_file(&name))
+        .await
+        .map_err(|e| ApiError::InternalError {
+            message: format!("Reading log file {} failed: {}", filename, e),
+        })??;
+
+    Ok((
+        [
+            (
+                header::CONTENT_TYPE,
+                "text/plain; charset=utf-8".to_string(),
+            ),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        contents,
+    )
+        .into_response())
+}
+
+/// Create the log router
+pub fn create_log_router(state: LogState) -> Router {
+    Router::new()
+        .route("/v3/log", get(list_logs))
+        .route("/v3/log/file", get(list_log_files))
+        .route("/v3/log/file/:filename", get(download_log_file))
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_params_paging_and_level() {
+        let params = LogQueryParams {
+            page: Some(3),
+            page_size: Some(20),
+            level: Some("Warn".to_string()),
+        };
+        let query = params.to_query().unwrap();
+        assert_eq!(query.level, Some(LogLevel::Warn));
+        assert_eq!((query.offset, query.limit), (40, 20));
+
+        let params = LogQueryParams {
+            level: Some("fatal".to_string()),
+            ..Default::default()
+        };
+        assert!(params.to_query().is_err());
+    }
+}
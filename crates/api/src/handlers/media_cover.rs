@@ -0,0 +1,149 @@
+//! MediaCover handlers
+//!
+//! `/MediaCover/:movieId/:file` serves a movie's cached poster or fanart,
+//! e.g. `poster.jpg` or `poster-250.jpg`, the way Radarr UIs request them.
+//! Covers are fetched from TMDB on first request. Responses carry an ETag
+//! from the cached file's size and modification time and may be kept by
+//! browsers for a day.
+
+use crate::error::{ApiError, ApiResult};
+use axum::{
+    extract::{Path, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use radarr_core::{
+    domain::repositories::MovieRepository,
+    models::{MediaCoverType, Movie},
+    RadarrError,
+};
+use radarr_infrastructure::{DatabasePool, MediaCoverService, PostgresMovieRepository};
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::UNIX_EPOCH;
+use tracing::instrument;
+use uuid::Uuid;
+
+/// Cache-Control of served covers
+const COVER_CACHE_CONTROL: &str = "public, max-age=86400";
+
+/// MediaCover state
+#[derive(Clone)]
+pub struct MediaCoverState {
+    pub movie_repo: Arc<dyn MovieRepository>,
+    /// Artwork cache; covers are not served without one
+    pub covers: Option<Arc<MediaCoverService>>,
+}
+
+impl MediaCoverState {
+    pub fn new(database_pool: DatabasePool) -> Self {
+        Self {
+            movie_repo: Arc::new(PostgresMovieRepository::new(database_pool)),
+            covers: None,
+        }
+    }
+
+    /// Serve covers from `covers`
+    pub fn with_media_covers(mut self, covers: Arc<MediaCoverService>) -> Self {
+        self.covers = Some(covers);
+        self
+    }
+}
+
+/// Artwork of a movie as listed in movie responses
+#[derive(Debug, Serialize)]
+pub struct MediaCoverResource {
+    pub cover_type: MediaCoverType,
+    /// Cached cover, under `/MediaCover`
+    pub url: String,
+    /// Full-size TMDB image
+    pub remote_url: String,
+}
+
+impl MediaCoverResource {
+    /// The artwork `movie` has on TMDB
+    pub fn for_movie(movie: &Movie) -> Vec<Self> {
+        MediaCoverType::ALL
+            .into_iter()
+            .filter_map(|cover_type| {
+                Some(Self {
+                    cover_type,
+                    url: format!("/MediaCover/{}/{}", movie.id, cover_type.file_name(None)),
+                    remote_url: movie.media_cover_url(cover_type)?,
+                })
+            })
+            .collect()
+    }
+}
+
+/// GET /MediaCover/:movie_id/:file - Serve a cached poster or fanart
+#[instrument(skip(state, headers))]
+pub async fn get_media_cover(
+    State(state): State<MediaCoverState>,
+    Path((movie_id, file)): Path<(Uuid, String)>,
+    headers: HeaderMap,
+) -> ApiResult<Response> {
+    let not_found = || ApiError::NotFound {
+        resource: format!("media cover {}/{}", movie_id, file),
+    };
+    let covers = state.covers.as_ref().ok_or_else(not_found)?;
+    let (cover_type, width) = MediaCoverType::parse_file_name(&file).ok_or_else(not_found)?;
+    let movie = state
+        .movie_repo
+        .find_by_id(movie_id)
+        .await?
+        .ok_or_else(not_found)?;
+    let path = covers
+        .cover(&movie, cover_type, width)
+        .await?
+        .ok_or_else(not_found)?;
+
+    let metadata = tokio::fs::metadata(&path)
+        .await
+        .map_err(RadarrError::from)?;
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |modified| modified.as_secs());
+    let etag = format!("\"{:x}-{:x}\"", metadata.len(), modified);
+
+    let cached = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| {
+            value
+                .split(',')
+                .any(|tag| tag.trim() == etag || tag.trim() == "*")
+        });
+    if cached {
+        return Ok((
+            StatusCode::NOT_MODIFIED,
+            [
+                (header::ETAG, etag),
+                (header::CACHE_CONTROL, COVER_CACHE_CONTROL.to_string()),
+            ],
+        )
+            .into_response());
+    }
+
+    let contents = tokio::fs::read(&path).await.map_err(RadarrError::from)?;
+    Ok((
+        [
+            (header::CONTENT_TYPE, "image/jpeg".to_string()),
+            (header::ETAG, etag),
+            (header::CACHE_CONTROL, COVER_CACHE_CONTROL.to_string()),
+        ],
+        contents,
+    )
+        .into_response())
+}
+
+/// Create the MediaCover router, served outside `/api`
+pub fn create_media_cover_router(state: MediaCoverState) -> Router {
+    Router::new()
+        .route("/MediaCover/:movie_id/:file", get(get_media_cover))
+        .with_state(state)
+}
@@ -4,25 +4,83 @@
 //! implementing the business logic for each route.
 
 pub mod advanced_search;
+pub mod api_keys;
+pub mod audit;
+pub mod backup;
+pub mod blocklist;
 pub mod calendar;
+pub mod collections;
 pub mod commands;
+pub mod config;
+pub mod diagnostics;
+pub mod diskspace;
 pub mod downloads;
+pub mod downloads_cleanup;
+pub mod exclusions;
 pub mod health;
+pub mod history;
+pub mod indexers;
+pub mod logs;
+pub mod media_cover;
 pub mod monitoring;
+pub mod movie_editor;
+pub mod movie_files;
 pub mod movies;
+pub mod notifications;
 pub mod quality;
+pub mod quality_profiles;
 pub mod queue;
+pub mod rename;
+pub mod root_folders;
 pub mod search;
+pub mod sessions;
+pub mod share_tokens;
+pub mod statistics;
 pub mod streaming;
+pub mod system_health;
+pub mod tags;
+pub mod tasks;
+pub mod trakt_lists;
+pub mod update;
+pub mod wanted;
 
 // Re-export handler functions
 pub use advanced_search::*;
+pub use api_keys::*;
+pub use audit::*;
+pub use backup::*;
+pub use blocklist::*;
 pub use calendar::*;
+pub use collections::*;
 pub use commands::*;
+pub use config::*;
+pub use diagnostics::*;
+pub use diskspace::*;
 pub use downloads::*;
+pub use downloads_cleanup::*;
+pub use exclusions::*;
 pub use health::*;
+pub use history::*;
+pub use indexers::*;
+pub use logs::*;
+pub use media_cover::*;
 pub use monitoring::*;
+pub use movie_editor::*;
+pub use movie_files::*;
 pub use movies::*;
+pub use notifications::*;
 pub use quality::*;
+pub use quality_profiles::*;
 pub use queue::*;
+pub use rename::*;
+pub use root_folders::*;
 pub use search::*;
+pub use sessions::*;
+pub use share_tokens::*;
+pub use statistics::*;
+pub use system_health::*;
+pub use tags::*;
+pub use tasks::*;
+pub use trakt_lists::*;
+pub use update::*;
+pub use wanted::*;
@@ -513,6 +513,66 @@ pub async fn get_circuit_breaker_states(
     Ok(Json(circuit_breakers))
 }
 
+/// Response model for an automated recovery attempt
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecoveryAttemptResponse {
+    pub id: String,
+    pub service: String,
+    pub action: String,
+    pub outcome: String,
+    pub message: String,
+    pub consecutive_failures: u32,
+    pub attempted_at: DateTime<Utc>,
+}
+
+/// Query parameters for the recovery audit trail
+#[derive(Debug, Deserialize)]
+pub struct RecoveryQueryParams {
+    /// Maximum number of attempts to return (newest first)
+    #[serde(default = "default_recovery_limit")]
+    pub limit: usize,
+}
+
+fn default_recovery_limit() -> usize {
+    50
+}
+
+/// GET /api/v3/monitoring/recovery - Automated recovery audit trail
+pub async fn get_recovery_attempts(
+    Query(params): Query<RecoveryQueryParams>,
+    monitor: Option<Extension<Arc<ListSyncMonitor>>>,
+) -> ApiResult<Json<serde_json::Value>> {
+    debug!("Fetching recovery audit trail (limit={})", params.limit);
+
+    let (registered_services, attempts) = if let Some(Extension(monitor)) = monitor {
+        let recovery_manager = monitor.recovery_manager();
+        let attempts = recovery_manager
+            .get_audit_trail(params.limit.min(500))
+            .await
+            .into_iter()
+            .map(|attempt| RecoveryAttemptResponse {
+                id: attempt.id.to_string(),
+                service: attempt.service,
+                action: attempt.action,
+                outcome: attempt.outcome.as_str().to_string(),
+                message: attempt.message,
+                consecutive_failures: attempt.consecutive_failures,
+                attempted_at: attempt.attempted_at,
+            })
+            .collect::<Vec<_>>();
+        (recovery_manager.registered_services().await, attempts)
+    } else {
+        warn!("ListSyncMonitor not available, no recovery data");
+        (Vec::new(), Vec::new())
+    };
+
+    Ok(Json(json!({
+        "registeredServices": registered_services,
+        "attempts": attempts
+    })))
+}
+
 // ========================================
 // Helper functions for real monitor integration
 // ========================================
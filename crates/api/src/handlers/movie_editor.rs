@@ -0,0 +1,498 @@
+//! Movie editor API handlers
+//!
+//! Edits or deletes many movies in one request, as the Radarr v3 mass
+//! editor does. An edit sets the monitored flag, quality profile, minimum
+//! availability and root folder of every listed movie and adds, removes or
+//! replaces their tags. Edits are all or nothing: the movies are saved in one
+//! transaction. With `moveFiles`, `MoveMovie` commands then move the folders
+//! of the movies whose root folder changed.
+
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::movie_files::MovieFileState;
+use crate::simple_api::{SimpleMovieResponse, SimpleUpdateMovieRequest};
+use axum::{extract::State, http::StatusCode, response::Json, routing::put, Extension, Router};
+use radarr_core::{
+    domain::repositories::{
+        MovieFileRepository, MovieRepository, QualityProfileRepository, RootFolderRepository,
+        TagRepository,
+    },
+    events::{EventBus, SystemEvent},
+    models::{AuditAction, AuditActor, ListExclusion, MinimumAvailability, Movie},
+    CommandName, CommandQueue, CommandTrigger, RadarrError,
+};
+use radarr_import::MoveMovieRequest;
+use radarr_infrastructure::{
+    DatabasePool, PostgresMovieFileRepository, PostgresMovieRepository,
+    PostgresQualityProfileRepository, PostgresRootFolderRepository, PostgresTagRepository,
+};
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::{error, info, instrument, warn};
+use uuid::Uuid;
+
+/// Movie editor state
+#[derive(Clone)]
+pub struct MovieEditorState {
+    pub movie_repo: Arc<PostgresMovieRepository>,
+    pub movie_file_repo: Arc<dyn MovieFileRepository>,
+    pub profile_repo: Arc<dyn QualityProfileRepository>,
+    pub root_folder_repo: Arc<dyn RootFolderRepository>,
+    pub tag_repo: Arc<dyn TagRepository>,
+    /// Removes movie files from disk when movies are deleted with their files
+    pub movie_files: MovieFileState,
+    pub event_bus: Option<Arc<EventBus>>,
+    /// Runs the folder moves of edits with `moveFiles`
+    pub command_queue: Option<Arc<CommandQueue>>,
+}
+
+impl MovieEditorState {
+    pub fn new(database_pool: DatabasePool, movie_files: MovieFileState) -> Self {
+        Self {
+            movie_repo: Arc::new(PostgresMovieRepository::new(database_pool.clone())),
+            movie_file_repo: Arc::new(PostgresMovieFileRepository::new(database_pool.clone())),
+            profile_repo: Arc::new(PostgresQualityProfileRepository::new(database_pool.clone())),
+            root_folder_repo: Arc::new(PostgresRootFolderRepository::new(database_pool.clone())),
+            tag_repo: Arc::new(PostgresTagRepository::new(database_pool)),
+            movie_files,
+            event_bus: None,
+            command_queue: None,
+        }
+    }
+
+    /// Publish movie updates and deletions on the event bus
+    pub fn with_event_bus(mut self, event_bus: Arc<EventBus>) -> Self {
+        self.movie_files = self.movie_files.with_event_bus(event_bus.clone());
+        self.event_bus = Some(event_bus);
+        self
+    }
+
+    /// Queue `MoveMovie` commands on `queue`
+    pub fn with_command_queue(mut self, queue: Arc<CommandQueue>) -> Self {
+        self.command_queue = Some(queue);
+        self
+    }
+}
+
+/// How the tags of a movie editor request combine with a movie's tags
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ApplyTags {
+    /// Add the tags to those the movie has
+    #[default]
+    Add,
+    /// Take the tags off the movie
+    Remove,
+    /// Give the movie exactly these tags
+    Replace,
+}
+
+impl ApplyTags {
+    /// Tags of a movie with `current` tags after applying `tags`
+    pub fn apply(self, current: &[i32], tags: &[i32]) -> Vec<i32> {
+        let combined = match self {
+            Self::Add => current.iter().chain(tags).copied().collect(),
+            Self::Remove => current
+                .iter()
+                .filter(|tag| !tags.contains(tag))
+                .copied()
+                .collect(),
+            Self::Replace => tags.to_vec(),
+        };
+        crate::handlers::tags::normalize_tag_ids(combined)
+    }
+}
+
+/// Movie editor request; omitted fields are left unchanged
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MovieEditorRequest {
+    pub movie_ids: Vec<Uuid>,
+    pub monitored: Option<bool>,
+    pub quality_profile_id: Option<i32>,
+    pub minimum_availability: Option<MinimumAvailability>,
+    /// Root folder the movies are filed under; must be a configured one
+    pub root_folder_path: Option<String>,
+    pub tags: Option<Vec<i32>>,
+    #[serde(default)]
+    pub apply_tags: ApplyTags,
+    /// Also move the movies' folders to the new root folder
+    #[serde(default)]
+    pub move_files: bool,
+}
+
+impl MovieEditorRequest {
+    /// Apply the request to a movie, returning the names of changed fields
+    fn apply(&self, movie: &mut Movie) -> Vec<String> {
+        let update = SimpleUpdateMovieRequest {
+            monitored: self.monitored,
+            quality_profile_id: self.quality_profile_id,
+            minimum_availability: self.minimum_availability.clone(),
+            excluded_indexer_ids: None,
+            tags: self
+                .tags
+                .as_ref()
+                .map(|tags| self.apply_tags.apply(&movie.tags, tags)),
+        };
+        let mut changes = update.apply(movie);
+
+        if let Some(root) = self
+            .root_folder_path
+            .as_deref()
+            .filter(|root| movie.root_folder_path() != Some(*root))
+        {
+            if !movie.metadata.is_object() {
+                movie.metadata = serde_json::json!({});
+            }
+            if let Some(metadata) = movie.metadata.as_object_mut() {
+                metadata.insert("root_folder_path".to_string(), serde_json::json!(root));
+            }
+            movie.updated_at = chrono::Utc::now();
+            changes.push("rootFolderPath".to_string());
+        }
+        changes
+    }
+}
+
+/// Movie editor deletion request, named as in Radarr v3
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MovieEditorDeleteRequest {
+    pub movie_ids: Vec<Uuid>,
+    /// Also delete the movies' files from disk
+    #[serde(default)]
+    pub delete_files: bool,
+    /// Keep lists and collections from adding the movies again
+    #[serde(default)]
+    pub add_import_exclusion: bool,
+}
+
+/// IDs in request order without repeats; an empty list is refused
+fn unique_movie_ids(ids: &[Uuid]) -> ApiResult<Vec<Uuid>> {
+    if ids.is_empty() {
+        return Err(ApiError::ValidationError {
+            field: "movieIds".to_string(),
+            message: "At least one movie must be selected".to_string(),
+        });
+    }
+    let mut seen = HashSet::new();
+    Ok(ids.iter().copied().filter(|id| seen.insert(*id)).collect())
+}
+
+/// Load every listed movie, 404 when one doesn't exist
+async fn find_movies(state: &MovieEditorState, ids: &[Uuid]) -> ApiResult<Vec<Movie>> {
+    let mut movies = Vec::with_capacity(ids.len());
+    for id in ids {
+        let movie = state
+            .movie_repo
+            .find_by_id(*id)
+            .await
+            .inspect_err(|e| error!("Failed to look up movie {}: {}", id, e))?
+            .ok_or_else(|| ApiError::NotFound {
+                resource: format!("movie {}", id),
+            })?;
+        movies.push(movie);
+    }
+    Ok(movies)
+}
+
+/// Refuse edits naming a quality profile, root folder or tag that doesn't exist
+///
+/// Returns the root folder path as configured.
+async fn validate_request(
+    state: &MovieEditorState,
+    request: &MovieEditorRequest,
+) -> ApiResult<Option<String>> {
+    if let Some(profile_id) = request.quality_profile_id {
+        if state.profile_repo.find_by_id(profile_id).await?.is_none() {
+            return Err(ApiError::ValidationError {
+                field: "qualityProfileId".to_string(),
+                message: format!("Quality profile {} not found", profile_id),
+            });
+        }
+    }
+
+    for tag_id in request.tags.iter().flatten() {
+        if state.tag_repo.find_by_id(*tag_id).await?.is_none() {
+            return Err(ApiError::ValidationError {
+                field: "tags".to_string(),
+                message: format!("Tag {} not found", tag_id),
+            });
+        }
+    }
+
+    let Some(path) = &request.root_folder_path else {
+        return Ok(None);
+    };
+    let root_folder = state
+        .root_folder_repo
+        .find_by_path(path.trim_end_matches('/'))
+        .await?
+        .ok_or_else(|| ApiError::ValidationError {
+            field: "rootFolderPath".to_string(),
+            message: format!("{} is not a root folder", path),
+        })?;
+    Ok(Some(root_folder.path))
+}
+
+/// Refuse `moveFiles` when no `MoveMovie` executor would run the moves
+fn check_move_files(state: &MovieEditorState, request: &MovieEditorRequest) -> ApiResult<()> {
+    let available = state
+        .command_queue
+        .as_ref()
+        .is_some_and(|queue| queue.available().contains(&CommandName::MoveMovie));
+    if request.move_files && !available {
+        return Err(ApiError::ValidationError {
+            field: "moveFiles".to_string(),
+            message: "Moving movie folders is not available".to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// PUT /api/v3/movie/editor - Edit many movies at once
+///
+/// Returns the listed movies as saved. Nothing changes when a movie, the
+/// quality profile, the root folder or a tag doesn't exist, or when saving any
+/// of the movies fails. With `moveFiles`, a `MoveMovie` command is queued for
+/// every movie whose root folder changed.
+#[instrument(skip(state))]
+pub async fn edit_movies(
+    State(state): State<MovieEditorState>,
+    Json(mut request): Json<MovieEditorRequest>,
+) -> ApiResult<Json<Vec<SimpleMovieResponse>>> {
+    let ids = unique_movie_ids(&request.movie_ids)?;
+    check_move_files(&state, &request)?;
+    request.root_folder_path = validate_request(&state, &request).await?;
+    let mut movies = find_movies(&state, &ids).await?;
+
+    let mut changed = Vec::new();
+    let mut moves = Vec::new();
+    for movie in &mut movies {
+        let old_root = movie.root_folder_path().map(PathBuf::from);
+        let changes = request.apply(movie);
+        if changes.is_empty() {
+            continue;
+        }
+        if request.move_files && changes.iter().any(|change| change == "rootFolderPath") {
+            moves.push(MoveMovieRequest {
+                movie_id: movie.id,
+                source_root: old_root,
+                rename_folder: false,
+            });
+        }
+        changed.push((movie.clone(), changes));
+    }
+
+    let edited: Vec<Movie> = changed.iter().map(|(movie, _)| movie.clone()).collect();
+    state
+        .movie_repo
+        .update_batch(&edited)
+        .await
+        .inspect_err(|e| error!("Failed to save {} edited movies: {}", edited.len(), e))?;
+    info!("Edited {} of {} movies", changed.len(), movies.len());
+
+    // Folders are moved in the background once the movies point at their
+    // new root folder; the command's progress shows under /api/v3/command
+    if let Some(queue) = &state.command_queue {
+        for move_request in moves {
+            let body = serde_json::to_value(&move_request).map_err(RadarrError::from)?;
+            queue
+                .enqueue(CommandName::MoveMovie, body, CommandTrigger::Manual)
+                .await
+                .inspect_err(|e| {
+                    error!(
+                        "Failed to queue moving movie {}: {}",
+                        move_request.movie_id, e
+                    )
+                })?;
+        }
+    }
+
+    if let Some(event_bus) = &state.event_bus {
+        for (movie, changes) in changed {
+            let event = SystemEvent::MovieUpdated {
+                movie_id: movie.id,
+                changes,
+            };
+            if let Err(e) = event_bus.publish(event).await {
+                warn!("Failed to publish movie update event: {}", e);
+            }
+        }
+    }
+
+    Ok(Json(
+        movies.into_iter().map(SimpleMovieResponse::from).collect(),
+    ))
+}
+
+/// DELETE /api/v3/movie/editor - Delete many movies at once
+///
+/// `deleteFiles` also removes the movies' files from disk and
+/// `addImportExclusion` keeps lists and collections from re-adding them.
+#[instrument(skip(state))]
+pub async fn delete_movies(
+    State(state): State<MovieEditorState>,
+    actor: Option<Extension<AuditActor>>,
+    Json(request): Json<MovieEditorDeleteRequest>,
+) -> ApiResult<StatusCode> {
+    let ids = unique_movie_ids(&request.movie_ids)?;
+    let movies = find_movies(&state, &ids).await?;
+
+    // Read the file records before the movie rows (and with them the records) go
+    let mut movie_files = Vec::new();
+    if request.delete_files {
+        for id in &ids {
+            movie_files.extend(state.movie_file_repo.list(Some(*id)).await?);
+        }
+    }
+
+    // Exclusions are added in the movies' delete transaction
+    let exclusions: Vec<ListExclusion> = if request.add_import_exclusion {
+        movies
+            .iter()
+            .map(|movie| ListExclusion::for_movie(movie, "Deleted from library"))
+            .collect()
+    } else {
+        Vec::new()
+    };
+    let deleted = state
+        .movie_repo
+        .delete_batch(&ids, &exclusions)
+        .await
+        .inspect_err(|e| error!("Failed to delete {} movies: {}", ids.len(), e))?;
+    info!("Deleted {} movies through the movie editor", deleted);
+
+    for movie in &movies {
+        crate::handlers::audit::publish_audit(
+            state.event_bus.as_ref(),
+            actor.clone(),
+            AuditAction::MovieDelete,
+            movie.id.to_string(),
+            serde_json::json!({
+                "title": movie.title,
+                "tmdbId": movie.tmdb_id,
+                "deleteFiles": request.delete_files,
+                "addImportExclusion": request.add_import_exclusion,
+            }),
+        )
+        .await;
+    }
+
+    // The movies are gone either way; files left behind are only logged
+    for movie_file in movie_files {
+        match state.movie_files.remove_from_disk(&movie_file).await {
+            Ok(removed) => {
+                if !removed {
+                    warn!(
+                        "Movie file was already missing: {}",
+                        movie_file.relative_path
+                    );
+                }
+                if let Some(event_bus) = &state.event_bus {
+                    let event = SystemEvent::MovieFileDeleted {
+                        movie_id: movie_file.movie_id,
+                        movie_file_id: movie_file.id,
+                        relative_path: movie_file.relative_path.clone(),
+                        reason: "movieDeleted".to_string(),
+                    };
+                    if let Err(e) = event_bus.publish(event).await {
+                        warn!("Failed to publish movie file deletion event: {}", e);
+                    }
+                }
+            }
+            Err(e) => error!("Failed to delete {}: {}", movie_file.relative_path, e),
+        }
+    }
+
+    if let Some(event_bus) = &state.event_bus {
+        for movie in movies {
+            let event = SystemEvent::MovieDeleted {
+                movie_id: movie.id,
+                title: movie.title,
+            };
+            if let Err(e) = event_bus.publish(event).await {
+                warn!("Failed to publish movie deletion event: {}", e);
+            }
+        }
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Create movie editor router
+pub fn create_movie_editor_router(state: MovieEditorState) -> Router {
+    Router::new()
+        .route("/v3/movie/editor", put(edit_movies).delete(delete_movies))
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_tags() {
+        assert_eq!(ApplyTags::Add.apply(&[1, 3], &[2, 3]), vec![1, 2, 3]);
+        assert_eq!(ApplyTags::Remove.apply(&[1, 2, 3], &[2, 4]), vec![1, 3]);
+        assert_eq!(ApplyTags::Replace.apply(&[1, 2], &[5, 4]), vec![4, 5]);
+    }
+
+    #[test]
+    fn test_editor_request_parses_upstream_fields() {
+        let request: MovieEditorRequest = serde_json::from_value(serde_json::json!({
+            "movieIds": [Uuid::nil()],
+            "monitored": false,
+            "rootFolderPath": "/movies/4k",
+            "tags": [2],
+            "applyTags": "remove",
+            "moveFiles": true
+        }))
+        .unwrap();
+        assert_eq!(request.apply_tags, ApplyTags::Remove);
+        assert!(request.move_files);
+        assert_eq!(request.quality_profile_id, None);
+    }
+
+    #[test]
+    fn test_apply_reports_changed_fields() {
+        let mut movie = Movie::new(603, "The Matrix".to_string());
+        movie.monitored = true;
+        movie.tags = vec![1];
+        movie.metadata = serde_json::json!({"root_folder_path": "/movies"});
+
+        let request = MovieEditorRequest {
+            monitored: Some(true),
+            root_folder_path: Some("/movies/4k".to_string()),
+            tags: Some(vec![2]),
+            ..Default::default()
+        };
+        let changes = request.apply(&mut movie);
+
+        assert_eq!(changes, vec!["tags", "rootFolderPath"]);
+        assert_eq!(movie.tags, vec![1, 2]);
+        assert_eq!(movie.root_folder_path(), Some("/movies/4k"));
+        assert!(request.apply(&mut movie).is_empty());
+    }
+
+    #[test]
+    fn test_apply_root_folder_without_metadata_object() {
+        let mut movie = Movie::new(603, "The Matrix".to_string());
+        movie.metadata = serde_json::Value::Null;
+
+        let request = MovieEditorRequest {
+            root_folder_path: Some("/movies/4k".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(request.apply(&mut movie), vec!["rootFolderPath"]);
+        assert_eq!(movie.root_folder_path(), Some("/movies/4k"));
+    }
+
+    #[test]
+    fn test_unique_movie_ids() {
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        assert_eq!(unique_movie_ids(&[a, b, a]).unwrap(), vec![a, b]);
+        assert!(unique_movie_ids(&[]).is_err());
+    }
+}
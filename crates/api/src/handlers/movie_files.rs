@@ -0,0 +1,644 @@
+//! Movie file API handlers
+//!
+//! Lists, shows and deletes the files imported for movies. Deleting a file
+//! removes it from disk, marks the movie as missing and resets its search
+//! cooldown, so a bad file is replaced by the next automatic search.
+//!
+//! Also streams imported files so the web UI can preview them in the browser.
+//! Streaming is off by default; when enabled it honours single `Range`
+//! requests (seeking in a `<video>` element) and an optional bandwidth cap
+//! so previews cannot saturate the uplink. Since `<video>` elements can't
+//! send the API key header, streams are also authorized by a passkey in the
+//! query string; each file's `streamUrl` carries it.
+
+use crate::auth;
+use crate::error::{ApiError, ApiResult};
+use axum::{
+    body::{Body, Bytes},
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Json, Response},
+    routing::get,
+    Extension, Router,
+};
+use radarr_core::{
+    domain::repositories::{MovieFileRepository, MovieRepository},
+    events::{EventBus, SystemEvent},
+    models::{media_root, resolve_media_path, AuditAction, AuditActor, MovieFile},
+};
+use radarr_infrastructure::{DatabasePool, PostgresMovieFileRepository, PostgresMovieRepository};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncSeekExt};
+use tracing::{debug, info, instrument, warn};
+use uuid::Uuid;
+
+/// Deletion reason recorded for files removed through the API
+const MANUAL_DELETE_REASON: &str = "manual";
+
+/// Passkey purpose of movie file streams
+pub(crate) const STREAM_PASSKEY_PURPOSE: &str = "stream";
+
+/// Size of each chunk sent to the client
+const STREAM_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Movie file streaming settings
+#[derive(Debug, Clone)]
+pub struct FileStreamingConfig {
+    /// Whether `/stream` is served at all
+    pub enabled: bool,
+    /// Bandwidth cap per stream, unlimited when `None`
+    pub max_bytes_per_second: Option<u64>,
+    /// Root folder that movie file paths are relative to
+    pub media_root: PathBuf,
+}
+
+impl Default for FileStreamingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_bytes_per_second: None,
+            media_root: PathBuf::from("/movies"),
+        }
+    }
+}
+
+impl FileStreamingConfig {
+    /// Create streaming configuration from environment variables
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            enabled: std::env::var("RADARR_FILE_STREAMING_ENABLED")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(defaults.enabled),
+            max_bytes_per_second: std::env::var("RADARR_FILE_STREAMING_MAX_BYTES_PER_SEC")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .filter(|limit| *limit > 0),
+            media_root: media_root(),
+        }
+    }
+
+    /// Absolute path of a stored movie file
+    ///
+    /// Returns `None` for paths that are absolute or climb out of the media
+    /// root, so a bad database row cannot expose arbitrary files.
+    pub fn resolve(&self, relative_path: &str) -> Option<PathBuf> {
+        resolve_media_path(&self.media_root, relative_path)
+    }
+}
+
+/// Movie file state
+#[derive(Clone)]
+pub struct MovieFileState {
+    pub movie_file_repo: Arc<dyn MovieFileRepository>,
+    pub movie_repo: Arc<dyn MovieRepository>,
+    pub streaming: Arc<FileStreamingConfig>,
+    pub event_bus: Option<Arc<EventBus>>,
+}
+
+impl MovieFileState {
+    pub fn new(database_pool: DatabasePool) -> Self {
+        Self {
+            movie_file_repo: Arc::new(PostgresMovieFileRepository::new(database_pool.clone())),
+            movie_repo: Arc::new(PostgresMovieRepository::new(database_pool)),
+            streaming: Arc::new(FileStreamingConfig::from_env()),
+            event_bus: None,
+        }
+    }
+
+    /// Replace the streaming settings
+    pub fn with_streaming_config(mut self, config: FileStreamingConfig) -> Self {
+        self.streaming = Arc::new(config);
+        self
+    }
+
+    /// Publish file deletions on the event bus
+    pub fn with_event_bus(mut self, event_bus: Arc<EventBus>) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
+    async fn find_movie_file(&self, id: Uuid) -> ApiResult<MovieFile> {
+        self.movie_file_repo
+            .find_by_id(id)
+            .await
+            .map_err(ApiError::CoreError)?
+            .ok_or_else(|| ApiError::NotFound {
+                resource: format!("movie file {}", id),
+            })
+    }
+
+    fn to_resource(&self, movie_file: MovieFile) -> MovieFileResource {
+        let path = self
+            .streaming
+            .resolve(&movie_file.relative_path)
+            .map(|path| path.to_string_lossy().to_string());
+        let stream_url = self
+            .streaming
+            .enabled
+            .then(auth::global_verifier)
+            .flatten()
+            .map(|verifier| {
+                format!(
+                    "/api/v3/moviefile/{}/stream?passkey={}",
+                    movie_file.id,
+                    verifier.passkey(STREAM_PASSKEY_PURPOSE)
+                )
+            });
+        MovieFileResource::new(movie_file, path, stream_url)
+    }
+
+    /// Delete a movie file from disk
+    ///
+    /// Returns `false` when the file was already gone. The database record is
+    /// left to the caller.
+    pub(crate) async fn remove_from_disk(&self, movie_file: &MovieFile) -> ApiResult<bool> {
+        let path = self
+            .streaming
+            .resolve(&movie_file.relative_path)
+            .ok_or_else(|| ApiError::ValidationError {
+                field: "relativePath".to_string(),
+                message: format!(
+                    "Movie file {} is outside the media root: {}",
+                    movie_file.id, movie_file.relative_path
+                ),
+            })?;
+
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(ApiError::InternalError {
+                message: format!("Failed to delete {}: {}", path.display(), e),
+            }),
+        }
+    }
+}
+
+/// Query parameters for movie file listing
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MovieFileQueryParams {
+    pub movie_id: Option<Uuid>,
+}
+
+/// Movie file as returned by the API
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MovieFileResource {
+    pub id: Uuid,
+    pub movie_id: Uuid,
+    /// Path below the media root
+    pub relative_path: String,
+    /// Absolute path on disk; `None` when the stored path leaves the media root
+    pub path: Option<String>,
+    pub size: i64,
+    pub quality: serde_json::Value,
+    pub media_info: Option<serde_json::Value>,
+    pub custom_format_score: i32,
+    /// Edition of the file, e.g. `Director's Cut`
+    pub edition: Option<String>,
+    pub date_added: chrono::DateTime<chrono::Utc>,
+    pub last_write_time: Option<chrono::DateTime<chrono::Utc>>,
+    /// Stream path including the passkey; only present while streaming is on
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream_url: Option<String>,
+}
+
+impl MovieFileResource {
+    fn new(movie_file: MovieFile, path: Option<String>, stream_url: Option<String>) -> Self {
+        Self {
+            id: movie_file.id,
+            movie_id: movie_file.movie_id,
+            relative_path: movie_file.relative_path,
+            path,
+            size: movie_file.size_bytes,
+            quality: movie_file.quality,
+            media_info: movie_file.media_info,
+            custom_format_score: movie_file.custom_format_score,
+            edition: movie_file.edition,
+            date_added: movie_file.date_added,
+            last_write_time: movie_file.last_write_time,
+            stream_url,
+        }
+    }
+}
+
+/// GET /api/v3/moviefile - List movie files, optionally of one movie
+#[instrument(skip(state))]
+pub async fn list_movie_files(
+    State(state): State<MovieFileState>,
+    Query(params): Query<MovieFileQueryParams>,
+) -> ApiResult<Json<Vec<MovieFileResource>>> {
+    let movie_files = state
+        .movie_file_repo
+        .list(params.movie_id)
+        .await
+        .map_err(ApiError::CoreError)?;
+
+    Ok(Json(
+        movie_files
+            .into_iter()
+            .map(|movie_file| state.to_resource(movie_file))
+            .collect(),
+    ))
+}
+
+/// GET /api/v3/moviefile/:id - A single movie file
+#[instrument(skip(state))]
+pub async fn get_movie_file(
+    State(state): State<MovieFileState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<MovieFileResource>> {
+    let movie_file = state.find_movie_file(id).await?;
+    Ok(Json(state.to_resource(movie_file)))
+}
+
+/// DELETE /api/v3/moviefile/:id - Delete a movie file from disk and library
+///
+/// The movie goes back to missing, with its search cooldown reset so a
+/// replacement is searched for right away.
+#[instrument(skip(state, actor))]
+pub async fn delete_movie_file(
+    State(state): State<MovieFileState>,
+    Path(id): Path<Uuid>,
+    actor: Option<Extension<AuditActor>>,
+) -> ApiResult<StatusCode> {
+    let movie_file = state.find_movie_file(id).await?;
+
+    // Disk first: if the file cannot be removed the library stays as it was
+    if !state.remove_from_disk(&movie_file).await? {
+        warn!(
+            "Movie file {} was already missing from disk: {}",
+            id, movie_file.relative_path
+        );
+    }
+
+    // The movie references its current file, so detach it before deleting
+    if let Some(mut movie) = state
+        .movie_repo
+        .find_by_id(movie_file.movie_id)
+        .await
+        .map_err(ApiError::CoreError)?
+    {
+        if movie.movie_file_id == Some(id) {
+            movie.clear_file();
+            state
+                .movie_repo
+                .update(&movie)
+                .await
+                .map_err(ApiError::CoreError)?;
+        }
+    }
+
+    state
+        .movie_file_repo
+        .delete(id)
+        .await
+        .map_err(ApiError::CoreError)?;
+    info!("Deleted movie file {}: {}", id, movie_file.relative_path);
+    crate::handlers::audit::publish_audit(
+        state.event_bus.as_ref(),
+        actor,
+        AuditAction::MovieFileDelete,
+        id.to_string(),
+        serde_json::json!({
+            "movieId": movie_file.movie_id,
+            "relativePath": movie_file.relative_path,
+        }),
+    )
+    .await;
+
+    if let Some(event_bus) = &state.event_bus {
+        let event = SystemEvent::MovieFileDeleted {
+            movie_id: movie_file.movie_id,
+            movie_file_id: id,
+            relative_path: movie_file.relative_path,
+            reason: MANUAL_DELETE_REASON.to_string(),
+        };
+        if let Err(e) = event_bus.publish(event).await {
+            warn!("Failed to publish movie file deletion event: {}", e);
+        }
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Inclusive byte range within a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ByteRange {
+    start: u64,
+    end: u64,
+}
+
+impl ByteRange {
+    fn len(&self) -> u64 {
+        self.end - self.start + 1
+    }
+}
+
+/// The requested range lies entirely outside the file
+#[derive(Debug, PartialEq, Eq)]
+struct RangeNotSatisfiable;
+
+/// Parse a `Range` header against a file of `file_size` bytes
+///
+/// Only a single `bytes` range is supported. Headers we do not understand
+/// (other units, multiple ranges, bad syntax) are ignored, which per
+/// RFC 9110 means the whole file is sent.
+fn parse_range(
+    header: &str,
+    file_size: u64,
+) -> std::result::Result<Option<ByteRange>, RangeNotSatisfiable> {
+    let Some(spec) = header.trim().strip_prefix("bytes=") else {
+        return Ok(None);
+    };
+    if spec.contains(',') {
+        return Ok(None);
+    }
+    let Some((start, end)) = spec.trim().split_once('-') else {
+        return Ok(None);
+    };
+
+    let range = match (start.trim(), end.trim()) {
+        // bytes=-500: the final 500 bytes
+        ("", suffix) => {
+            let Ok(suffix) = suffix.parse::<u64>() else {
+                return Ok(None);
+            };
+            if suffix == 0 || file_size == 0 {
+                return Err(RangeNotSatisfiable);
+            }
+            ByteRange {
+                start: file_size.saturating_sub(suffix),
+                end: file_size - 1,
+            }
+        }
+        (start, end) => {
+            let Ok(start) = start.parse::<u64>() else {
+                return Ok(None);
+            };
+            let end = match end {
+                "" => u64::MAX,
+                end => match end.parse::<u64>() {
+                    Ok(end) if end >= start => end,
+                    _ => return Ok(None),
+                },
+            };
+            if start >= file_size {
+                return Err(RangeNotSatisfiable);
+            }
+            ByteRange {
+                start,
+                end: end.min(file_size - 1),
+            }
+        }
+    };
+
+    Ok(Some(range))
+}
+
+/// How long to wait before sending more once `sent` bytes have gone out
+fn throttle_delay(sent: u64, max_bytes_per_second: u64, elapsed: Duration) -> Option<Duration> {
+    let due = Duration::from_secs_f64(sent as f64 / max_bytes_per_second as f64);
+    due.checked_sub(elapsed).filter(|wait| !wait.is_zero())
+}
+
+/// Stream a reader as a response body, optionally capped in bytes per second
+fn throttled_body<R>(reader: R, max_bytes_per_second: Option<u64>) -> Body
+where
+    R: AsyncRead + Unpin + Send + 'static,
+{
+    let chunk_size = max_bytes_per_second
+        .map(|limit| (limit as usize).clamp(1, STREAM_CHUNK_SIZE))
+        .unwrap_or(STREAM_CHUNK_SIZE);
+    let started = Instant::now();
+
+    let stream =
+        futures::stream::try_unfold((reader, 0u64), move |(mut reader, sent)| async move {
+            let mut buffer = vec![0u8; chunk_size];
+            let read = reader.read(&mut buffer).await?;
+            if read == 0 {
+                return Ok(None);
+            }
+            buffer.truncate(read);
+
+            let sent = sent + read as u64;
+            if let Some(limit) = max_bytes_per_second {
+                if let Some(wait) = throttle_delay(sent, limit, started.elapsed()) {
+                    tokio::time::sleep(wait).await;
+                }
+            }
+
+            Ok::<_, std::io::Error>(Some((Bytes::from(buffer), (reader, sent))))
+        });
+
+    Body::from_stream(stream)
+}
+
+/// GET /api/v3/moviefile/:id/stream - Stream a movie file, honouring `Range`
+#[instrument(skip(state, headers))]
+pub async fn stream_movie_file(
+    State(state): State<MovieFileState>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+) -> ApiResult<Response> {
+    if !state.streaming.enabled {
+        return Err(ApiError::NotFound {
+            resource: "movie file streaming (disabled)".to_string(),
+        });
+    }
+
+    let movie_file = state.find_movie_file(id).await?;
+
+    let path = state
+        .streaming
+        .resolve(&movie_file.relative_path)
+        .ok_or_else(|| {
+            warn!(
+                "Refusing to stream movie file {} outside the media root: {}",
+                id, movie_file.relative_path
+            );
+            ApiError::NotFound {
+                resource: format!("movie file {}", id),
+            }
+        })?;
+
+    let mut file = tokio::fs::File::open(&path).await.map_err(|e| {
+        debug!("Cannot open {}: {}", path.display(), e);
+        ApiError::NotFound {
+            resource: format!("movie file {} on disk", id),
+        }
+    })?;
+    let file_size = file
+        .metadata()
+        .await
+        .map_err(|e| ApiError::InternalError {
+            message: format!("Failed to read file metadata: {}", e),
+        })?
+        .len();
+
+    let range = match headers
+        .get(header::RANGE)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| parse_range(value, file_size))
+        .transpose()
+    {
+        Ok(range) => range.flatten(),
+        Err(RangeNotSatisfiable) => {
+            return Ok((
+                StatusCode::RANGE_NOT_SATISFIABLE,
+                [
+                    (header::CONTENT_RANGE, format!("bytes */{}", file_size)),
+                    (header::ACCEPT_RANGES, "bytes".to_string()),
+                ],
+            )
+                .into_response());
+        }
+    };
+
+    let content_type = mime_guess::from_path(&path)
+        .first_or_octet_stream()
+        .to_string();
+
+    let (status, start, length) = match range {
+        Some(range) => (StatusCode::PARTIAL_CONTENT, range.start, range.len()),
+        None => (StatusCode::OK, 0, file_size),
+    };
+    if start > 0 {
+        file.seek(std::io::SeekFrom::Start(start))
+            .await
+            .map_err(|e| ApiError::InternalError {
+                message: format!("Failed to seek in movie file: {}", e),
+            })?;
+    }
+
+    let body = throttled_body(file.take(length), state.streaming.max_bytes_per_second);
+    let mut response = Response::new(body);
+    *response.status_mut() = status;
+
+    let response_headers = response.headers_mut();
+    response_headers.insert(header::ACCEPT_RANGES, HeaderValue::from_static("bytes"));
+    response_headers.insert(header::CONTENT_LENGTH, HeaderValue::from(length));
+    if let Ok(value) = HeaderValue::from_str(&content_type) {
+        response_headers.insert(header::CONTENT_TYPE, value);
+    }
+    if let Some(range) = range {
+        if let Ok(value) = HeaderValue::from_str(&format!(
+            "bytes {}-{}/{}",
+            range.start, range.end, file_size
+        )) {
+            response_headers.insert(header::CONTENT_RANGE, value);
+        }
+    }
+
+    Ok(response)
+}
+
+/// Whether `path` is `/api/v3/moviefile/:id/stream`, the one route that
+/// accepts the stream passkey
+pub(crate) fn is_stream_path(path: &str) -> bool {
+    path.strip_prefix("/api/v3/moviefile/")
+        .and_then(|rest| rest.strip_suffix("/stream"))
+        .is_some_and(|id| !id.is_empty() && !id.contains('/'))
+}
+
+/// Create movie file router
+pub fn create_movie_file_router(state: MovieFileState) -> Router {
+    Router::new()
+        .route("/v3/moviefile", get(list_movie_files))
+        .route(
+            "/v3/moviefile/:id",
+            get(get_movie_file).delete(delete_movie_file),
+        )
+        .route("/v3/moviefile/:id/stream", get(stream_movie_file))
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_range_forms() {
+        assert_eq!(
+            parse_range("bytes=0-499", 1000),
+            Ok(Some(ByteRange { start: 0, end: 499 }))
+        );
+        assert_eq!(
+            parse_range("bytes=500-", 1000),
+            Ok(Some(ByteRange {
+                start: 500,
+                end: 999
+            }))
+        );
+        assert_eq!(
+            parse_range("bytes=-100", 1000),
+            Ok(Some(ByteRange {
+                start: 900,
+                end: 999
+            }))
+        );
+        // Ends past the file are clamped
+        assert_eq!(
+            parse_range("bytes=900-5000", 1000),
+            Ok(Some(ByteRange {
+                start: 900,
+                end: 999
+            }))
+        );
+    }
+
+    #[test]
+    fn test_parse_range_ignored_and_unsatisfiable() {
+        assert_eq!(parse_range("items=0-10", 1000), Ok(None));
+        assert_eq!(parse_range("bytes=0-10,20-30", 1000), Ok(None));
+        assert_eq!(parse_range("bytes=50-10", 1000), Ok(None));
+        assert_eq!(parse_range("bytes=abc", 1000), Ok(None));
+
+        assert_eq!(parse_range("bytes=1000-", 1000), Err(RangeNotSatisfiable));
+        assert_eq!(parse_range("bytes=-0", 1000), Err(RangeNotSatisfiable));
+    }
+
+    #[test]
+    fn test_resolve_stays_in_media_root() {
+        let config = FileStreamingConfig {
+            media_root: PathBuf::from("/media/movies"),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            config.resolve("Dune (2021)/Dune.mkv"),
+            Some(PathBuf::from("/media/movies/Dune (2021)/Dune.mkv"))
+        );
+        assert_eq!(config.resolve("../secrets/key.pem"), None);
+        assert_eq!(config.resolve("/etc/passwd"), None);
+    }
+
+    #[test]
+    fn test_throttle_delay() {
+        let limit = 1024 * 1024;
+        assert_eq!(
+            throttle_delay(limit, limit, Duration::from_millis(250)),
+            Some(Duration::from_millis(750))
+        );
+        assert_eq!(throttle_delay(limit, limit, Duration::from_secs(2)), None);
+    }
+
+    #[test]
+    fn test_only_stream_paths_take_the_passkey() {
+        let id = Uuid::new_v4();
+        assert!(is_stream_path(&format!("/api/v3/moviefile/{}/stream", id)));
+        assert!(!is_stream_path(&format!("/api/v3/moviefile/{}", id)));
+        assert!(!is_stream_path("/api/v3/moviefile/stream"));
+        assert!(!is_stream_path("/api/v3/moviefile//stream"));
+        assert!(!is_stream_path("/api/v3/movie/1/stream"));
+        assert!(!is_stream_path(&format!(
+            "/api/v3/moviefile/{}/../../apikey/stream",
+            id
+        )));
+    }
+}
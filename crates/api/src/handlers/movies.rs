@@ -115,6 +115,7 @@ pub async fn create_movie(
     let mut movie = Movie::new(request.tmdb_id, title);
     movie.monitored = request.monitored;
     movie.quality_profile_id = request.quality_profile_id;
+    movie.tags = crate::handlers::tags::normalize_tag_ids(request.tags);
 
     if let Some(min_availability) = request.minimum_availability {
         movie.minimum_availability = min_availability;
@@ -173,6 +174,10 @@ pub async fn update_movie(
         movie.minimum_availability = minimum_availability;
     }
 
+    if let Some(tags) = request.tags {
+        movie.tags = crate::handlers::tags::normalize_tag_ids(tags);
+    }
+
     if let Some(metadata) = request.metadata {
         movie.update_metadata(metadata);
     }
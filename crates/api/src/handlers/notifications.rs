@@ -0,0 +1,527 @@
+//! Notification management API handlers
+//!
+//! Notifications are stored provider configurations (a Discord webhook, an
+//! SMTP account) with per-event trigger toggles. Every change reloads the
+//! providers of the running notification service, so edits take effect
+//! without a restart.
+
+use crate::error::{ApiError, ApiResult};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use radarr_core::{
+    domain::repositories::NotificationRepository,
+    models::{NotificationDefinition, NotificationImplementation},
+};
+use radarr_infrastructure::{DatabasePool, PostgresNotificationRepository};
+use radarr_notifications::{build_provider, NotificationService};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::{error, info, instrument, warn};
+
+/// Settings keys that hold secrets and are masked in responses
+///
+/// Webhook URLs often carry a token, so `url` counts as a secret too.
+const SECRET_SETTINGS: &[&str] = &[
+    "password",
+    "url",
+    "webhook_url",
+    "bot_token",
+    "api_token",
+    "user_key",
+    "app_token",
+];
+
+/// Settings keys holding maps, such as webhook headers, whose values are all
+/// masked since any of them may be a credential (e.g. `Authorization`)
+const SECRET_SETTING_MAPS: &[&str] = &["headers"];
+
+/// Placeholder returned instead of a secret value
+const MASKED_SECRET: &str = "********";
+
+/// Notification management state
+#[derive(Clone)]
+pub struct NotificationState {
+    pub notification_repo: Arc<dyn NotificationRepository>,
+    /// Running service whose configured providers are reloaded on changes
+    pub notifications: Option<Arc<NotificationService>>,
+}
+
+impl NotificationState {
+    pub fn new(database_pool: DatabasePool) -> Self {
+        Self {
+            notification_repo: Arc::new(PostgresNotificationRepository::new(database_pool)),
+            notifications: None,
+        }
+    }
+
+    /// Reload the running service's providers after a change
+    pub fn with_notification_service(mut self, notifications: Arc<NotificationService>) -> Self {
+        self.notifications = Some(notifications);
+        self
+    }
+
+    /// Rebuild the configured providers from the stored definitions
+    async fn reload(&self) {
+        if let Some(notifications) = &self.notifications {
+            match self.notification_repo.list().await {
+                Ok(definitions) => {
+                    notifications.load_definitions(definitions);
+                }
+                Err(e) => error!("Failed to reload notification providers: {}", e),
+            }
+        }
+    }
+}
+
+/// Notification create/update/test request
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationRequest {
+    pub name: String,
+    pub implementation: String,
+    #[serde(default)]
+    pub settings: serde_json::Value,
+    #[serde(default)]
+    pub on_grab: bool,
+    #[serde(default)]
+    pub on_import: bool,
+    #[serde(default)]
+    pub on_upgrade: bool,
+    #[serde(default)]
+    pub on_health_issue: bool,
+    #[serde(default)]
+    pub on_application_update: bool,
+    /// Only fire for movies with one of these tags
+    #[serde(default)]
+    pub tags: Vec<i32>,
+}
+
+/// Notification as returned by the API
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationResource {
+    pub id: i32,
+    pub name: String,
+    pub implementation: String,
+    pub settings: serde_json::Value,
+    pub on_grab: bool,
+    pub on_import: bool,
+    pub on_upgrade: bool,
+    pub on_health_issue: bool,
+    pub on_application_update: bool,
+    pub tags: Vec<i32>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Result of sending a test notification
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationTestResponse {
+    pub name: String,
+    pub success: bool,
+    pub message: String,
+    pub execution_time_ms: u128,
+}
+
+impl From<NotificationDefinition> for NotificationResource {
+    fn from(definition: NotificationDefinition) -> Self {
+        Self {
+            id: definition.id,
+            name: definition.name,
+            implementation: definition.implementation.to_string(),
+            settings: mask_secrets(definition.settings),
+            on_grab: definition.on_grab,
+            on_import: definition.on_import,
+            on_upgrade: definition.on_upgrade,
+            on_health_issue: definition.on_health_issue,
+            on_application_update: definition.on_application_update,
+            tags: definition.tags,
+            created_at: definition.created_at.to_rfc3339(),
+            updated_at: definition.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Replace secret setting values so they are never echoed back to clients
+fn mask_secrets(mut settings: serde_json::Value) -> serde_json::Value {
+    fn mask(value: &mut serde_json::Value) {
+        if value.as_str().is_some_and(|s| !s.is_empty()) {
+            *value = serde_json::Value::String(MASKED_SECRET.to_string());
+        }
+    }
+
+    if let Some(obj) = settings.as_object_mut() {
+        for key in SECRET_SETTINGS {
+            if let Some(value) = obj.get_mut(*key) {
+                mask(value);
+            }
+        }
+        for key in SECRET_SETTING_MAPS {
+            if let Some(map) = obj.get_mut(*key).and_then(|map| map.as_object_mut()) {
+                map.values_mut().for_each(mask);
+            }
+        }
+    }
+    settings
+}
+
+/// Put stored values back for secrets sent back as the masked placeholder
+fn restore_masked_secrets(
+    new: &mut serde_json::Map<String, serde_json::Value>,
+    old: &serde_json::Map<String, serde_json::Value>,
+) {
+    for key in SECRET_SETTINGS {
+        if new.get(*key).and_then(|v| v.as_str()) == Some(MASKED_SECRET) {
+            match old.get(*key) {
+                Some(existing) => new.insert(key.to_string(), existing.clone()),
+                None => new.remove(*key),
+            };
+        }
+    }
+    for key in SECRET_SETTING_MAPS {
+        let old_map = old.get(*key).and_then(|map| map.as_object());
+        if let Some(new_map) = new.get_mut(*key).and_then(|map| map.as_object_mut()) {
+            restore_masked_secrets_in(new_map, old_map);
+        }
+    }
+}
+
+fn restore_masked_secrets_in(
+    new: &mut serde_json::Map<String, serde_json::Value>,
+    old: Option<&serde_json::Map<String, serde_json::Value>>,
+) {
+    let masked: Vec<String> = new
+        .iter()
+        .filter(|(_, value)| value.as_str() == Some(MASKED_SECRET))
+        .map(|(name, _)| name.clone())
+        .collect();
+    for name in masked {
+        match old.and_then(|old| old.get(&name)) {
+            Some(existing) => new.insert(name, existing.clone()),
+            None => new.remove(&name),
+        };
+    }
+}
+
+/// Validate a request and apply it onto a notification definition
+fn apply_request(
+    definition: &mut NotificationDefinition,
+    request: NotificationRequest,
+) -> ApiResult<()> {
+    if request.name.trim().is_empty() {
+        return Err(ApiError::ValidationError {
+            field: "name".to_string(),
+            message: "Notification name cannot be empty".to_string(),
+        });
+    }
+
+    let implementation =
+        NotificationImplementation::parse(&request.implementation).ok_or_else(|| {
+            ApiError::ValidationError {
+                field: "implementation".to_string(),
+                message: format!(
+                    "Unsupported notification implementation: {}",
+                    request.implementation
+                ),
+            }
+        })?;
+
+    let mut settings = if request.settings.is_null() {
+        serde_json::json!({})
+    } else {
+        request.settings
+    };
+
+    // Masked secrets sent back unchanged keep the stored value
+    if let Some(new) = settings.as_object_mut() {
+        let empty = serde_json::Map::new();
+        restore_masked_secrets(new, definition.settings.as_object().unwrap_or(&empty));
+    }
+
+    build_provider(implementation, &settings).map_err(|e| ApiError::ValidationError {
+        field: "settings".to_string(),
+        message: e.to_string(),
+    })?;
+
+    definition.name = request.name.trim().to_string();
+    definition.implementation = implementation;
+    definition.settings = settings;
+    definition.on_grab = request.on_grab;
+    definition.on_import = request.on_import;
+    definition.on_upgrade = request.on_upgrade;
+    definition.on_health_issue = request.on_health_issue;
+    definition.on_application_update = request.on_application_update;
+    definition.tags = crate::handlers::tags::normalize_tag_ids(request.tags);
+    definition.updated_at = chrono::Utc::now();
+    Ok(())
+}
+
+async fn find_notification(
+    state: &NotificationState,
+    id: i32,
+) -> ApiResult<NotificationDefinition> {
+    state
+        .notification_repo
+        .find_by_id(id)
+        .await
+        .map_err(ApiError::CoreError)?
+        .ok_or_else(|| ApiError::NotFound {
+            resource: format!("notification with id {}", id),
+        })
+}
+
+/// Send a test notification through a definition's provider
+async fn send_test(definition: &NotificationDefinition) -> ApiResult<NotificationTestResponse> {
+    let start_time = Instant::now();
+    let provider =
+        build_provider(definition.implementation, &definition.settings).map_err(|e| {
+            ApiError::ValidationError {
+                field: "settings".to_string(),
+                message: e.to_string(),
+            }
+        })?;
+
+    let (success, message) = match provider.test().await {
+        Ok(()) => (true, "Test notification sent".to_string()),
+        Err(e) => {
+            warn!("Notification '{}' test failed: {}", definition.name, e);
+            (false, e.to_string())
+        }
+    };
+
+    Ok(NotificationTestResponse {
+        name: definition.name.clone(),
+        success,
+        message,
+        execution_time_ms: start_time.elapsed().as_millis(),
+    })
+}
+
+/// GET /api/v3/notification - List notifications
+#[instrument(skip(state))]
+pub async fn list_notifications(
+    State(state): State<NotificationState>,
+) -> ApiResult<Json<Vec<NotificationResource>>> {
+    let definitions = state
+        .notification_repo
+        .list()
+        .await
+        .map_err(ApiError::CoreError)?;
+
+    Ok(Json(definitions.into_iter().map(Into::into).collect()))
+}
+
+/// GET /api/v3/notification/:id - Get notification by ID
+#[instrument(skip(state))]
+pub async fn get_notification(
+    State(state): State<NotificationState>,
+    Path(id): Path<i32>,
+) -> ApiResult<Json<NotificationResource>> {
+    let definition = find_notification(&state, id).await?;
+    Ok(Json(definition.into()))
+}
+
+/// POST /api/v3/notification - Create new notification
+#[instrument(skip(state, request))]
+pub async fn create_notification(
+    State(state): State<NotificationState>,
+    Json(request): Json<NotificationRequest>,
+) -> ApiResult<(StatusCode, Json<NotificationResource>)> {
+    let mut definition = NotificationDefinition::new(
+        String::new(),
+        NotificationImplementation::Discord,
+        serde_json::json!({}),
+    );
+    apply_request(&mut definition, request)?;
+
+    let created = state
+        .notification_repo
+        .create(&definition)
+        .await
+        .map_err(ApiError::CoreError)?;
+    state.reload().await;
+
+    info!(
+        "Created notification '{}' with ID: {}",
+        created.name, created.id
+    );
+    Ok((StatusCode::CREATED, Json(created.into())))
+}
+
+/// PUT /api/v3/notification/:id - Update notification
+#[instrument(skip(state, request))]
+pub async fn update_notification(
+    State(state): State<NotificationState>,
+    Path(id): Path<i32>,
+    Json(request): Json<NotificationRequest>,
+) -> ApiResult<Json<NotificationResource>> {
+    let mut definition = find_notification(&state, id).await?;
+    apply_request(&mut definition, request)?;
+
+    let updated = state
+        .notification_repo
+        .update(&definition)
+        .await
+        .map_err(ApiError::CoreError)?;
+    state.reload().await;
+
+    info!("Updated notification '{}'", updated.name);
+    Ok(Json(updated.into()))
+}
+
+/// DELETE /api/v3/notification/:id - Delete notification
+#[instrument(skip(state))]
+pub async fn delete_notification(
+    State(state): State<NotificationState>,
+    Path(id): Path<i32>,
+) -> ApiResult<StatusCode> {
+    let definition = find_notification(&state, id).await?;
+
+    state
+        .notification_repo
+        .delete(id)
+        .await
+        .map_err(ApiError::CoreError)?;
+    state.reload().await;
+
+    info!("Deleted notification '{}'", definition.name);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// POST /api/v3/notification/test - Send a test through unsaved settings
+#[instrument(skip(request))]
+pub async fn test_notification_settings(
+    Json(request): Json<NotificationRequest>,
+) -> ApiResult<Json<NotificationTestResponse>> {
+    let mut definition = NotificationDefinition::new(
+        String::new(),
+        NotificationImplementation::Discord,
+        serde_json::json!({}),
+    );
+    apply_request(&mut definition, request)?;
+
+    Ok(Json(send_test(&definition).await?))
+}
+
+/// POST /api/v3/notification/:id/test - Send a test through a stored notification
+#[instrument(skip(state))]
+pub async fn test_notification(
+    State(state): State<NotificationState>,
+    Path(id): Path<i32>,
+) -> ApiResult<Json<NotificationTestResponse>> {
+    let definition = find_notification(&state, id).await?;
+    Ok(Json(send_test(&definition).await?))
+}
+
+/// Create notification management router
+pub fn create_notification_router(state: NotificationState) -> Router {
+    Router::new()
+        .route(
+            "/v3/notification",
+            get(list_notifications).post(create_notification),
+        )
+        .route("/v3/notification/test", post(test_notification_settings))
+        .route(
+            "/v3/notification/:id",
+            get(get_notification)
+                .put(update_notification)
+                .delete(delete_notification),
+        )
+        .route("/v3/notification/:id/test", post(test_notification))
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(settings: serde_json::Value) -> NotificationRequest {
+        NotificationRequest {
+            name: " Discord ".to_string(),
+            implementation: "discord".to_string(),
+            settings,
+            on_grab: true,
+            on_import: false,
+            on_upgrade: false,
+            on_health_issue: true,
+            on_application_update: false,
+            tags: vec![2, 1, 2],
+        }
+    }
+
+    #[test]
+    fn test_masked_secret_keeps_stored_value() {
+        let mut definition = NotificationDefinition::new(
+            String::new(),
+            NotificationImplementation::Discord,
+            serde_json::json!({ "webhook_url": "https://discord.com/api/webhooks/1/abc" }),
+        );
+
+        apply_request(
+            &mut definition,
+            request(serde_json::json!({ "webhook_url": "********" })),
+        )
+        .unwrap();
+
+        assert_eq!(definition.name, "Discord");
+        assert_eq!(
+            definition.settings["webhook_url"],
+            "https://discord.com/api/webhooks/1/abc"
+        );
+        assert!(definition.on_grab && definition.on_health_issue);
+        assert_eq!(definition.tags, vec![1, 2]);
+        assert_eq!(
+            NotificationResource::from(definition).settings["webhook_url"],
+            "********"
+        );
+    }
+
+    #[test]
+    fn test_webhook_url_and_headers_are_masked() {
+        let mut definition = NotificationDefinition::new(
+            "Webhook".to_string(),
+            NotificationImplementation::Webhook,
+            serde_json::json!({
+                "url": "https://example.com/hook?token=abc",
+                "headers": {"Authorization": "Bearer secret", "X-Source": "radarr"}
+            }),
+        );
+
+        let resource = NotificationResource::from(definition.clone());
+        assert_eq!(resource.settings["url"], "********");
+        assert_eq!(resource.settings["headers"]["Authorization"], "********");
+        assert_eq!(resource.settings["headers"]["X-Source"], "********");
+
+        let mut update = request(resource.settings);
+        update.implementation = "webhook".to_string();
+        update.settings["headers"]["X-Source"] = serde_json::json!("radarr-mvp");
+        update.settings["headers"]["X-New"] = serde_json::json!("********");
+        apply_request(&mut definition, update).unwrap();
+
+        assert_eq!(
+            definition.settings,
+            serde_json::json!({
+                "url": "https://example.com/hook?token=abc",
+                "headers": {"Authorization": "Bearer secret", "X-Source": "radarr-mvp"}
+            })
+        );
+    }
+
+    #[test]
+    fn test_invalid_settings_are_rejected() {
+        let mut definition = NotificationDefinition::new(
+            String::new(),
+            NotificationImplementation::Discord,
+            serde_json::json!({}),
+        );
+
+        assert!(apply_request(&mut definition, request(serde_json::json!({}))).is_err());
+    }
+}
@@ -5,16 +5,20 @@ use axum::{
     extract::{Path, State},
     http::StatusCode,
     response::Json,
-    routing::{get, post},
+    routing::{get, post, put},
     Router,
 };
-use radarr_decision::{CustomFormat, CustomFormatEngine, FormatSpecification, ReleaseData};
+use radarr_core::models::{AudioCodec, MovieRatings, VideoCodec};
+use radarr_decision::{
+    CustomFormat, CustomFormatEngine, FormatSpecification, ReleaseData, ReleaseScoreCache,
+    ScoredRelease,
+};
 use radarr_infrastructure::{
     CustomFormatsRepository, DatabasePool, PostgresCustomFormatsRepository,
 };
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
-use tracing::{debug, info, instrument};
+use tracing::{debug, info, instrument, warn};
 use uuid::Uuid;
 
 /// Quality management state
@@ -22,6 +26,8 @@ use uuid::Uuid;
 pub struct QualityState {
     pub database_pool: DatabasePool,
     pub custom_formats_repo: Arc<PostgresCustomFormatsRepository>,
+    /// Recent search results, re-scored whenever a custom format changes
+    pub release_cache: Arc<ReleaseScoreCache>,
 }
 
 impl QualityState {
@@ -31,10 +37,47 @@ impl QualityState {
         Self {
             database_pool,
             custom_formats_repo,
+            release_cache: Arc::new(ReleaseScoreCache::default()),
+        }
+    }
+
+    /// Build a scoring engine from the currently enabled custom formats
+    pub async fn format_engine(&self) -> ApiResult<CustomFormatEngine> {
+        let formats = self
+            .custom_formats_repo
+            .list_enabled()
+            .await
+            .map_err(ApiError::CoreError)?;
+        Ok(CustomFormatEngine::with_formats(formats))
+    }
+
+    /// Re-score cached releases after a custom format change
+    ///
+    /// Failures are logged rather than returned: the format change itself has
+    /// already been persisted and the cache catches up on the next change.
+    async fn rescore_cached_releases(&self) {
+        if self.release_cache.is_empty() {
+            return;
+        }
+
+        match self.format_engine().await {
+            Ok(engine) => {
+                let changed = self.release_cache.rescore(&engine);
+                info!("Re-scored cached releases, {} scores changed", changed);
+            }
+            Err(e) => warn!("Failed to re-score cached releases: {}", e),
         }
     }
 }
 
+/// Validate a custom format before it is persisted
+fn validate_custom_format(format: &CustomFormat) -> ApiResult<()> {
+    format.validate().map_err(|e| ApiError::ValidationError {
+        field: e.field,
+        message: e.message,
+    })
+}
+
 /// Custom format request for API
 #[derive(Debug, Deserialize)]
 pub struct CustomFormatRequest {
@@ -132,6 +175,8 @@ pub struct TestReleaseRequest {
     pub freeleech: Option<bool>,
     pub internal: Option<bool>,
     pub indexer: Option<String>,
+    /// Ratings of the movie, for rating specifications
+    pub movie_ratings: Option<MovieRatings>,
 }
 
 /// Test release response
@@ -141,6 +186,12 @@ pub struct TestReleaseResponse {
     pub matching_formats: Vec<MatchingFormatInfo>,
 }
 
+/// Enable/disable request for a custom format
+#[derive(Debug, Deserialize)]
+pub struct SetEnabledRequest {
+    pub enabled: bool,
+}
+
 /// Information about a matching custom format
 #[derive(Debug, Serialize)]
 pub struct MatchingFormatInfo {
@@ -200,15 +251,22 @@ pub async fn create_custom_format(
 ) -> ApiResult<Json<CustomFormatResponse>> {
     info!("Creating custom format: {}", request.name);
 
+    let format: CustomFormat = request.into();
+    validate_custom_format(&format)?;
+
     // Check if format with this name already exists
-    if let Ok(Some(_)) = state.custom_formats_repo.find_by_name(&request.name).await {
+    if state
+        .custom_formats_repo
+        .find_by_name(&format.name)
+        .await
+        .map_err(ApiError::CoreError)?
+        .is_some()
+    {
         return Err(ApiError::Conflict {
-            resource: format!("custom format '{}'", request.name),
+            resource: format!("custom format '{}'", format.name),
         });
     }
 
-    let format: CustomFormat = request.into();
-
     let created_format = state
         .custom_formats_repo
         .create(&format)
@@ -219,6 +277,7 @@ pub async fn create_custom_format(
         "Created custom format '{}' with ID: {}",
         created_format.name, created_format.id
     );
+    state.rescore_cached_releases().await;
     Ok(Json(created_format.into()))
 }
 
@@ -250,6 +309,19 @@ pub async fn update_custom_format(
 
     let mut format: CustomFormat = request.into();
     format.id = format_id; // Preserve the existing ID
+    validate_custom_format(&format)?;
+
+    if state
+        .custom_formats_repo
+        .find_by_name_excluding(&format.name, &format_id)
+        .await
+        .map_err(ApiError::CoreError)?
+        .is_some()
+    {
+        return Err(ApiError::Conflict {
+            resource: format!("custom format '{}'", format.name),
+        });
+    }
 
     let updated_format = state
         .custom_formats_repo
@@ -258,9 +330,54 @@ pub async fn update_custom_format(
         .map_err(ApiError::CoreError)?;
 
     info!("Updated custom format '{}'", updated_format.name);
+    state.rescore_cached_releases().await;
     Ok(Json(updated_format.into()))
 }
 
+/// PUT /api/v3/customformat/:id/enabled - Enable or disable a custom format
+#[instrument(skip(state))]
+pub async fn set_custom_format_enabled(
+    State(state): State<QualityState>,
+    Path(id): Path<String>,
+    Json(request): Json<SetEnabledRequest>,
+) -> ApiResult<Json<CustomFormatResponse>> {
+    let format_id = Uuid::parse_str(&id).map_err(|_| ApiError::BadRequest {
+        message: "Invalid custom format ID".to_string(),
+    })?;
+
+    let updated = state
+        .custom_formats_repo
+        .set_enabled(&format_id, request.enabled)
+        .await
+        .map_err(ApiError::CoreError)?;
+    if !updated {
+        return Err(ApiError::NotFound {
+            resource: format!("custom format with id {}", id),
+        });
+    }
+
+    let format = state
+        .custom_formats_repo
+        .find_by_id(&format_id)
+        .await
+        .map_err(ApiError::CoreError)?
+        .ok_or_else(|| ApiError::NotFound {
+            resource: format!("custom format with id {}", id),
+        })?;
+
+    info!(
+        "Custom format '{}' {}",
+        format.name,
+        if format.enabled {
+            "enabled"
+        } else {
+            "disabled"
+        }
+    );
+    state.rescore_cached_releases().await;
+    Ok(Json(format.into()))
+}
+
 /// DELETE /api/v3/customformat/:id - Delete custom format
 #[instrument(skip(state))]
 pub async fn delete_custom_format(
@@ -290,9 +407,66 @@ pub async fn delete_custom_format(
         .map_err(ApiError::CoreError)?;
 
     info!("Deleted custom format '{}'", format.name);
+    state.rescore_cached_releases().await;
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// GET /api/v3/customformat/releases - Cached search results with current scores
+#[instrument(skip(state))]
+pub async fn list_scored_releases(
+    State(state): State<QualityState>,
+) -> ApiResult<Json<Vec<ScoredRelease>>> {
+    let releases = state.release_cache.list();
+    debug!("Returning {} cached releases", releases.len());
+    Ok(Json(releases))
+}
+
+/// Codec that `codec` and `audio` specifications can target
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodecDefinitionResource {
+    pub name: String,
+    pub score: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub generation: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lossless: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub object_based: Option<bool>,
+}
+
+/// Video and audio codec definitions
+#[derive(Debug, Serialize)]
+pub struct CodecDefinitionsResponse {
+    pub video: Vec<CodecDefinitionResource>,
+    pub audio: Vec<CodecDefinitionResource>,
+}
+
+/// GET /api/v3/customformat/codecs - Codecs known to the release parser
+pub async fn list_codec_definitions() -> Json<CodecDefinitionsResponse> {
+    let video = VideoCodec::ALL
+        .iter()
+        .map(|codec| CodecDefinitionResource {
+            name: codec.name().to_string(),
+            score: codec.score(),
+            generation: Some(codec.generation().to_string()),
+            lossless: None,
+            object_based: None,
+        })
+        .collect();
+    let audio = AudioCodec::ALL
+        .iter()
+        .map(|codec| CodecDefinitionResource {
+            name: codec.name().to_string(),
+            score: codec.score(),
+            generation: None,
+            lossless: Some(codec.is_lossless()),
+            object_based: Some(codec.is_object_based()),
+        })
+        .collect();
+    Json(CodecDefinitionsResponse { video, audio })
+}
+
 /// POST /api/v3/customformat/test - Test release against custom formats
 #[instrument(skip(state))]
 pub async fn test_custom_formats(
@@ -301,15 +475,8 @@ pub async fn test_custom_formats(
 ) -> ApiResult<Json<TestReleaseResponse>> {
     info!("Testing release against custom formats: {}", request.title);
 
-    // Load all enabled custom formats
-    let formats = state
-        .custom_formats_repo
-        .list_enabled()
-        .await
-        .map_err(ApiError::CoreError)?;
-
-    // Create custom format engine
-    let engine = CustomFormatEngine::with_formats(formats);
+    // Create custom format engine from all enabled custom formats
+    let engine = state.format_engine().await?;
 
     // Create release data for testing
     let release_data = ReleaseData {
@@ -325,6 +492,7 @@ pub async fn test_custom_formats(
             .split('-')
             .last()
             .map(|s| s.trim().to_string()),
+        movie_ratings: request.movie_ratings,
     };
 
     // Calculate score and get matching formats
@@ -363,15 +531,56 @@ pub async fn test_custom_formats(
 pub fn create_quality_router(state: QualityState) -> Router {
     Router::new()
         .route(
-            "/customformat",
+            "/v3/customformat",
             get(list_custom_formats).post(create_custom_format),
         )
+        .route("/v3/customformat/releases", get(list_scored_releases))
+        .route("/v3/customformat/codecs", get(list_codec_definitions))
+        .route("/v3/customformat/test", post(test_custom_formats))
         .route(
-            "/customformat/:id",
+            "/v3/customformat/:id",
             get(get_custom_format)
                 .put(update_custom_format)
                 .delete(delete_custom_format),
         )
-        .route("/customformat/test", post(test_custom_formats))
+        .route(
+            "/v3/customformat/:id/enabled",
+            put(set_custom_format_enabled),
+        )
         .with_state(state)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invalid_regex_is_reported_against_its_specification() {
+        let request = CustomFormatRequest {
+            name: "HDR".to_string(),
+            specifications: vec![
+                FormatSpecificationRequest {
+                    spec_type: "release_title".to_string(),
+                    negate: None,
+                    required: None,
+                    value: "HDR10|DV".to_string(),
+                },
+                FormatSpecificationRequest {
+                    spec_type: "release_title".to_string(),
+                    negate: None,
+                    required: None,
+                    value: "Dolby(Vision".to_string(),
+                },
+            ],
+            score: 10,
+            enabled: None,
+        };
+
+        match validate_custom_format(&request.into()) {
+            Err(ApiError::ValidationError { field, .. }) => {
+                assert_eq!(field, "specifications[1].value")
+            }
+            other => panic!("expected validation error, got {:?}", other.err()),
+        }
+    }
+}
@@ -1,219 +1,849 @@
 //! Quality profile management API handlers
+//!
+//! Profiles are exposed in the Radarr v3 `QualityProfileResource` shape so
+//! existing Radarr UIs can list, create and edit them. The ordered `items`
+//! array (lowest to highest quality, optionally grouped) is stored as JSON on
+//! the profile row; quality names, sources and resolutions are filled in from
+//! the built-in quality definitions on the way out.
+//!
+//! `POST /api/v3/qualityprofile/:id/simulate` scores hypothetical release
+//! names against a profile and the enabled custom formats without grabbing
+//! anything, optionally with unsaved profile edits applied.
 
+use crate::error::{ApiError, ApiResult};
 use axum::{
     extract::{Path, State},
     http::StatusCode,
     response::Json,
+    routing::{get, post},
+    Router,
+};
+use radarr_core::{
+    domain::repositories::QualityProfileRepository,
+    models::{detect_quality_id, QualityProfile, QUALITY_DEFINITIONS},
+};
+use radarr_decision::{CustomFormatEngine, ReleaseData};
+use radarr_infrastructure::{
+    CustomFormatsRepository, DatabasePool, PostgresCustomFormatsRepository,
+    PostgresQualityProfileRepository,
 };
 use serde::{Deserialize, Serialize};
-use uuid::Uuid;
+use std::collections::HashSet;
+use std::sync::Arc;
+use tracing::{info, instrument};
 
-use crate::models::ApiResponse;
-use radarr_infrastructure::DatabasePool;
+/// Group item IDs start here so they never collide with quality IDs
+const GROUP_ID_START: i32 = 1000;
 
-/// Quality profile response structure
-#[derive(Debug, Serialize)]
-pub struct QualityProfile {
-    pub id: i32,
-    pub name: String,
-    pub cutoff: i32,
-    pub items: Vec<QualityItem>,
-    pub min_format_score: i32,
-    pub cutoff_format_score: i32,
-    pub format_items: Vec<FormatItem>,
+/// Profile languages: (id, stored name, display name)
+const LANGUAGES: &[(i32, &str, &str)] = &[
+    (-2, "original", "Original"),
+    (-1, "any", "Any"),
+    (1, "english", "English"),
+    (2, "french", "French"),
+    (3, "spanish", "Spanish"),
+    (4, "german", "German"),
+    (5, "italian", "Italian"),
+    (7, "dutch", "Dutch"),
+    (8, "japanese", "Japanese"),
+];
+
+/// Quality profile management state
+#[derive(Clone)]
+pub struct QualityProfileState {
+    pub database_pool: DatabasePool,
+    pub profile_repo: Arc<PostgresQualityProfileRepository>,
+    pub custom_formats_repo: Arc<PostgresCustomFormatsRepository>,
 }
 
-/// Quality item within a profile
-#[derive(Debug, Serialize)]
-pub struct QualityItem {
-    pub quality: Quality,
-    pub allowed: bool,
+impl QualityProfileState {
+    pub fn new(database_pool: DatabasePool) -> Self {
+        let profile_repo = Arc::new(PostgresQualityProfileRepository::new(database_pool.clone()));
+        let custom_formats_repo =
+            Arc::new(PostgresCustomFormatsRepository::new(database_pool.clone()));
+        Self {
+            database_pool,
+            profile_repo,
+            custom_formats_repo,
+        }
+    }
 }
 
-/// Quality definition
-#[derive(Debug, Serialize)]
-pub struct Quality {
+/// Quality definition as exposed by the v3 API
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct QualityResource {
     pub id: i32,
+    #[serde(default)]
     pub name: String,
+    #[serde(default)]
     pub source: String,
+    #[serde(default)]
     pub resolution: i32,
+    #[serde(default = "default_modifier")]
+    pub modifier: String,
 }
 
-/// Format item for custom formats
-#[derive(Debug, Serialize)]
-pub struct FormatItem {
-    pub format: CustomFormat,
+fn default_modifier() -> String {
+    "none".to_string()
+}
+
+/// A single quality or a named group of qualities within a profile
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct QualityProfileItemResource {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub quality: Option<QualityResource>,
+    #[serde(default)]
+    pub items: Vec<QualityProfileItemResource>,
+    pub allowed: bool,
+}
+
+impl QualityProfileItemResource {
+    /// ID used when referencing this item as the profile cutoff
+    fn cutoff_id(&self) -> Option<i32> {
+        match &self.quality {
+            Some(quality) => Some(quality.id),
+            None => self.id,
+        }
+    }
+}
+
+/// Custom format score entry of a profile
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileFormatItemResource {
+    pub format: i32,
     pub name: String,
     pub score: i32,
 }
 
-/// Custom format definition
-#[derive(Debug, Serialize)]
-pub struct CustomFormat {
+/// Profile language
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LanguageResource {
     pub id: i32,
     pub name: String,
-    pub include_custom_format_when_renaming: bool,
 }
 
-/// State for quality profile handlers
-#[derive(Clone)]
-pub struct QualityProfileState {
-    pub pool: DatabasePool,
+/// Quality profile in the Radarr v3 resource shape
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QualityProfileResource {
+    #[serde(default)]
+    pub id: i32,
+    pub name: String,
+    #[serde(default = "default_true")]
+    pub upgrade_allowed: bool,
+    pub cutoff: i32,
+    pub items: Vec<QualityProfileItemResource>,
+    #[serde(default)]
+    pub min_format_score: i32,
+    #[serde(default)]
+    pub cutoff_format_score: i32,
+    /// Custom format scores are configured on the custom formats themselves
+    #[serde(default)]
+    pub format_items: Vec<ProfileFormatItemResource>,
+    #[serde(default)]
+    pub language: Option<LanguageResource>,
 }
 
-impl QualityProfileState {
-    pub fn new(pool: DatabasePool) -> Self {
-        Self { pool }
+fn default_true() -> bool {
+    true
+}
+
+/// Look up a quality definition by ID
+fn quality_definition(id: i32) -> Option<QualityResource> {
+    QUALITY_DEFINITIONS
+        .iter()
+        .find(|(def_id, ..)| *def_id == id)
+        .map(|(id, name, source, resolution)| QualityResource {
+            id: *id,
+            name: name.to_string(),
+            source: source.to_string(),
+            resolution: *resolution,
+            modifier: default_modifier(),
+        })
+}
+
+fn language_resource(language: &str) -> LanguageResource {
+    LANGUAGES
+        .iter()
+        .find(|(_, stored, _)| stored.eq_ignore_ascii_case(language))
+        .map(|(id, _, name)| LanguageResource {
+            id: *id,
+            name: name.to_string(),
+        })
+        .unwrap_or_else(|| LanguageResource {
+            id: 0,
+            name: language.to_string(),
+        })
+}
+
+fn parse_language(language: Option<&LanguageResource>) -> ApiResult<String> {
+    let Some(language) = language else {
+        return Ok("english".to_string());
+    };
+
+    LANGUAGES
+        .iter()
+        .find(|(id, _, name)| *id == language.id || name.eq_ignore_ascii_case(&language.name))
+        .map(|(_, stored, _)| stored.to_string())
+        .ok_or_else(|| ApiError::ValidationError {
+            field: "language".to_string(),
+            message: format!("Unsupported language: {}", language.name),
+        })
+}
+
+/// Fill in quality details from the built-in definitions
+fn hydrate_items(items: &mut [QualityProfileItemResource]) {
+    for item in items {
+        if let Some(quality) = item.quality.as_mut() {
+            if let Some(definition) = quality_definition(quality.id) {
+                *quality = definition;
+            }
+        }
+        hydrate_items(&mut item.items);
+    }
+}
+
+impl From<QualityProfile> for QualityProfileResource {
+    fn from(profile: QualityProfile) -> Self {
+        let mut items: Vec<QualityProfileItemResource> =
+            serde_json::from_value(profile.items).unwrap_or_default();
+        hydrate_items(&mut items);
+
+        Self {
+            id: profile.id,
+            name: profile.name,
+            upgrade_allowed: profile.upgrade_allowed,
+            cutoff: profile.cutoff_quality_id,
+            items,
+            min_format_score: profile.min_format_score,
+            cutoff_format_score: profile.cutoff_format_score,
+            format_items: Vec::new(),
+            language: Some(language_resource(&profile.language)),
+        }
+    }
+}
+
+/// Validate the ordered item list and cutoff of a profile resource
+fn validate_items(resource: &QualityProfileResource) -> ApiResult<()> {
+    let invalid = |message: String| ApiError::ValidationError {
+        field: "items".to_string(),
+        message,
+    };
+
+    if resource.items.is_empty() {
+        return Err(invalid(
+            "Profile must contain at least one quality".to_string(),
+        ));
+    }
+
+    let mut seen_qualities = HashSet::new();
+    let mut seen_groups = HashSet::new();
+    let mut allowed_cutoffs = Vec::new();
+
+    for item in &resource.items {
+        match &item.quality {
+            Some(quality) => {
+                if quality_definition(quality.id).is_none() {
+                    return Err(invalid(format!("Unknown quality ID {}", quality.id)));
+                }
+                if !seen_qualities.insert(quality.id) {
+                    return Err(invalid(format!("Quality {} is listed twice", quality.id)));
+                }
+            }
+            None => {
+                let group_id = item.id.filter(|id| *id >= GROUP_ID_START).ok_or_else(|| {
+                    invalid(format!(
+                        "Quality groups need an ID of {} or above",
+                        GROUP_ID_START
+                    ))
+                })?;
+                if item.name.as_deref().is_none_or(|n| n.trim().is_empty()) {
+                    return Err(invalid(format!("Quality group {} needs a name", group_id)));
+                }
+                if item.items.is_empty() {
+                    return Err(invalid(format!("Quality group {} is empty", group_id)));
+                }
+                if !seen_groups.insert(group_id) {
+                    return Err(invalid(format!(
+                        "Quality group {} is listed twice",
+                        group_id
+                    )));
+                }
+                for child in &item.items {
+                    let quality = child
+                        .quality
+                        .as_ref()
+                        .ok_or_else(|| invalid("Quality groups cannot be nested".to_string()))?;
+                    if quality_definition(quality.id).is_none() {
+                        return Err(invalid(format!("Unknown quality ID {}", quality.id)));
+                    }
+                    if !seen_qualities.insert(quality.id) {
+                        return Err(invalid(format!("Quality {} is listed twice", quality.id)));
+                    }
+                }
+            }
+        }
+
+        if item.allowed {
+            allowed_cutoffs.extend(item.cutoff_id());
+        }
+    }
+
+    if allowed_cutoffs.is_empty() {
+        return Err(invalid("At least one quality must be allowed".to_string()));
+    }
+
+    if !allowed_cutoffs.contains(&resource.cutoff) {
+        return Err(ApiError::ValidationError {
+            field: "cutoff".to_string(),
+            message: format!("Cutoff {} must be an allowed quality", resource.cutoff),
+        });
+    }
+
+    Ok(())
+}
+
+/// Validate a resource and apply it onto a domain profile
+fn apply_resource(profile: &mut QualityProfile, resource: QualityProfileResource) -> ApiResult<()> {
+    if resource.name.trim().is_empty() {
+        return Err(ApiError::ValidationError {
+            field: "name".to_string(),
+            message: "Profile name cannot be empty".to_string(),
+        });
+    }
+    validate_items(&resource)?;
+    let language = parse_language(resource.language.as_ref())?;
+
+    let mut items = resource.items;
+    hydrate_items(&mut items);
+    let items = serde_json::to_value(items).map_err(|e| ApiError::InternalError {
+        message: format!("Failed to serialize quality items: {}", e),
+    })?;
+
+    profile.name = resource.name.trim().to_string();
+    profile.cutoff_quality_id = resource.cutoff;
+    profile.upgrade_allowed = resource.upgrade_allowed;
+    profile.min_format_score = resource.min_format_score;
+    profile.cutoff_format_score = resource.cutoff_format_score;
+    profile.update_items(items);
+    profile.set_language(language);
+
+    Ok(())
+}
+
+async fn find_profile(state: &QualityProfileState, id: i32) -> ApiResult<QualityProfile> {
+    state
+        .profile_repo
+        .find_by_id(id)
+        .await
+        .map_err(ApiError::CoreError)?
+        .ok_or_else(|| ApiError::NotFound {
+            resource: format!("quality profile with id {}", id),
+        })
+}
+
+/// Release names to simulate, with optional unsaved profile edits
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulateProfileRequest {
+    pub releases: Vec<String>,
+    /// Profile edits to simulate instead of the stored profile
+    #[serde(default)]
+    pub profile: Option<QualityProfileResource>,
+}
+
+/// How a profile treats one simulated release
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedReleaseResource {
+    pub title: String,
+    /// Position among accepted releases, 1 being the one that would be grabbed
+    pub rank: Option<usize>,
+    pub quality: QualityResource,
+    /// Position of the quality in the profile, higher is better
+    pub quality_rank: Option<usize>,
+    pub custom_format_score: i32,
+    pub custom_formats: Vec<String>,
+    pub meets_cutoff: bool,
+    pub rejected: bool,
+    pub rejections: Vec<String>,
+}
+
+/// Result of simulating releases against a profile
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileSimulationResource {
+    pub profile_id: i32,
+    pub profile_name: String,
+    /// Title of the release that would be grabbed, if any is accepted
+    pub would_grab: Option<String>,
+    /// Accepted releases in grab order, then rejected ones
+    pub releases: Vec<SimulatedReleaseResource>,
+}
+
+/// Radarr quality of a release title, from its resolution and source
+fn detect_quality(title: &str) -> QualityResource {
+    quality_definition(detect_quality_id(title))
+        .or_else(|| quality_definition(0))
+        .expect("Unknown quality is defined")
+}
+
+/// Position of `quality_id` in the profile items and whether it is allowed
+fn profile_quality(items: &[QualityProfileItemResource], quality_id: i32) -> Option<(usize, bool)> {
+    items.iter().enumerate().find_map(|(index, item)| {
+        let listed = match &item.quality {
+            Some(quality) => quality.id == quality_id,
+            None => item
+                .items
+                .iter()
+                .any(|child| child.quality.as_ref().is_some_and(|q| q.id == quality_id)),
+        };
+        listed.then_some((index, item.allowed))
+    })
+}
+
+/// Score and rank `titles` the way automatic grabs would
+fn simulate_releases(
+    profile: &QualityProfileResource,
+    engine: &CustomFormatEngine,
+    titles: &[String],
+) -> Vec<SimulatedReleaseResource> {
+    let cutoff_rank = profile
+        .items
+        .iter()
+        .position(|item| item.cutoff_id() == Some(profile.cutoff));
+
+    let mut releases: Vec<SimulatedReleaseResource> = titles
+        .iter()
+        .map(|title| {
+            let quality = detect_quality(title);
+            let data = ReleaseData {
+                title: title.clone(),
+                size_bytes: None,
+                seeders: None,
+                leechers: None,
+                freeleech: None,
+                internal: Some(title.to_lowercase().contains("internal")),
+                indexer: String::new(),
+                release_group: title
+                    .rsplit_once('-')
+                    .map(|(_, group)| group.trim().to_string()),
+                movie_ratings: None,
+            };
+            let custom_format_score = engine.calculate_format_score(&data);
+            let custom_formats = engine
+                .get_matching_formats(&data)
+                .into_iter()
+                .map(|format| format.name.clone())
+                .collect();
+
+            let mut rejections = Vec::new();
+            let quality_rank = match profile_quality(&profile.items, quality.id) {
+                Some((rank, true)) => Some(rank),
+                Some((rank, false)) => {
+                    rejections.push(format!("{} is not wanted in profile", quality.name));
+                    Some(rank)
+                }
+                None => {
+                    rejections.push(format!("{} is not in profile", quality.name));
+                    None
+                }
+            };
+            if custom_format_score < profile.min_format_score {
+                rejections.push(format!(
+                    "Custom format score {} is below minimum {}",
+                    custom_format_score, profile.min_format_score
+                ));
+            }
+
+            let meets_cutoff = rejections.is_empty()
+                && quality_rank >= cutoff_rank
+                && custom_format_score >= profile.cutoff_format_score;
+
+            SimulatedReleaseResource {
+                title: title.clone(),
+                rank: None,
+                quality,
+                quality_rank,
+                custom_format_score,
+                custom_formats,
+                meets_cutoff,
+                rejected: !rejections.is_empty(),
+                rejections,
+            }
+        })
+        .collect();
+
+    // Accepted first, then by quality and custom format score; ties keep input order
+    releases.sort_by(|a, b| {
+        a.rejected
+            .cmp(&b.rejected)
+            .then(b.quality_rank.cmp(&a.quality_rank))
+            .then(b.custom_format_score.cmp(&a.custom_format_score))
+    });
+    for (index, release) in releases.iter_mut().filter(|r| !r.rejected).enumerate() {
+        release.rank = Some(index + 1);
     }
+
+    releases
+}
+
+/// POST /api/v3/qualityprofile/:id/simulate - Rank hypothetical releases
+#[instrument(skip(state, request))]
+pub async fn simulate_quality_profile(
+    State(state): State<QualityProfileState>,
+    Path(id): Path<i32>,
+    Json(request): Json<SimulateProfileRequest>,
+) -> ApiResult<Json<ProfileSimulationResource>> {
+    if request.releases.is_empty() {
+        return Err(ApiError::ValidationError {
+            field: "releases".to_string(),
+            message: "At least one release name is required".to_string(),
+        });
+    }
+
+    let mut profile = find_profile(&state, id).await?;
+    if let Some(edits) = request.profile {
+        // Validated like an update, but never saved
+        apply_resource(&mut profile, edits)?;
+    }
+    let profile = QualityProfileResource::from(profile);
+
+    let formats = state
+        .custom_formats_repo
+        .list_enabled()
+        .await
+        .map_err(ApiError::CoreError)?;
+    let engine = CustomFormatEngine::with_formats(formats);
+
+    let releases = simulate_releases(&profile, &engine, &request.releases);
+    let would_grab = releases
+        .iter()
+        .find(|release| release.rank == Some(1))
+        .map(|release| release.title.clone());
+
+    Ok(Json(ProfileSimulationResource {
+        profile_id: id,
+        profile_name: profile.name,
+        would_grab,
+        releases,
+    }))
 }
 
 /// GET /api/v3/qualityprofile - List all quality profiles
+#[instrument(skip(state))]
 pub async fn list_quality_profiles(
-    State(_state): State<QualityProfileState>,
-) -> Json<ApiResponse<Vec<QualityProfile>>> {
-    // For now, return default profiles until database implementation is ready
-    let default_profiles = vec![
-        QualityProfile {
-            id: 1,
-            name: "HD-1080p".to_string(),
-            cutoff: 7,
-            items: vec![
-                QualityItem {
-                    quality: Quality {
-                        id: 1,
-                        name: "SDTV".to_string(),
-                        source: "Television".to_string(),
-                        resolution: 480,
-                    },
-                    allowed: false,
-                },
-                QualityItem {
-                    quality: Quality {
-                        id: 2,
-                        name: "DVD".to_string(),
-                        source: "DVD".to_string(),
-                        resolution: 480,
-                    },
-                    allowed: false,
-                },
-                QualityItem {
-                    quality: Quality {
-                        id: 4,
-                        name: "HDTV-720p".to_string(),
-                        source: "Television".to_string(),
-                        resolution: 720,
-                    },
-                    allowed: true,
-                },
-                QualityItem {
-                    quality: Quality {
-                        id: 5,
-                        name: "WEBDL-720p".to_string(),
-                        source: "WebDL".to_string(),
-                        resolution: 720,
-                    },
-                    allowed: true,
-                },
-                QualityItem {
-                    quality: Quality {
-                        id: 6,
-                        name: "Bluray-720p".to_string(),
-                        source: "BluRay".to_string(),
-                        resolution: 720,
-                    },
-                    allowed: true,
-                },
-                QualityItem {
-                    quality: Quality {
-                        id: 7,
-                        name: "WEBDL-1080p".to_string(),
-                        source: "WebDL".to_string(),
-                        resolution: 1080,
-                    },
-                    allowed: true,
-                },
-                QualityItem {
-                    quality: Quality {
-                        id: 8,
-                        name: "Bluray-1080p".to_string(),
-                        source: "BluRay".to_string(),
-                        resolution: 1080,
-                    },
-                    allowed: true,
-                },
-            ],
-            min_format_score: 0,
-            cutoff_format_score: 0,
-            format_items: vec![],
-        },
-        QualityProfile {
-            id: 2,
-            name: "Ultra-HD".to_string(),
-            cutoff: 19,
-            items: vec![
-                QualityItem {
-                    quality: Quality {
-                        id: 18,
-                        name: "WEBDL-2160p".to_string(),
-                        source: "WebDL".to_string(),
-                        resolution: 2160,
-                    },
-                    allowed: true,
-                },
-                QualityItem {
-                    quality: Quality {
-                        id: 19,
-                        name: "Bluray-2160p".to_string(),
-                        source: "BluRay".to_string(),
-                        resolution: 2160,
-                    },
-                    allowed: true,
-                },
-            ],
-            min_format_score: 0,
-            cutoff_format_score: 0,
-            format_items: vec![],
-        },
-    ];
+    State(state): State<QualityProfileState>,
+) -> ApiResult<Json<Vec<QualityProfileResource>>> {
+    let profiles = state
+        .profile_repo
+        .list()
+        .await
+        .map_err(ApiError::CoreError)?;
+
+    Ok(Json(profiles.into_iter().map(Into::into).collect()))
+}
 
-    Json(ApiResponse::success(default_profiles))
+/// GET /api/v3/qualityprofile/schema - Template for a new profile
+pub async fn get_quality_profile_schema() -> Json<QualityProfileResource> {
+    let items = QUALITY_DEFINITIONS
+        .iter()
+        .filter_map(|(id, ..)| quality_definition(*id))
+        .map(|quality| QualityProfileItemResource {
+            id: None,
+            name: None,
+            quality: Some(quality),
+            items: Vec::new(),
+            allowed: false,
+        })
+        .collect();
+
+    Json(QualityProfileResource {
+        id: 0,
+        name: String::new(),
+        upgrade_allowed: false,
+        cutoff: 0,
+        items,
+        min_format_score: 0,
+        cutoff_format_score: 0,
+        format_items: Vec::new(),
+        language: Some(language_resource("english")),
+    })
 }
 
-/// GET /api/v3/qualityprofile/{id} - Get specific quality profile
+/// GET /api/v3/qualityprofile/:id - Get quality profile by ID
+#[instrument(skip(state))]
 pub async fn get_quality_profile(
-    State(_state): State<QualityProfileState>,
+    State(state): State<QualityProfileState>,
     Path(id): Path<i32>,
-) -> Result<Json<ApiResponse<QualityProfile>>, (StatusCode, Json<ApiResponse<()>>)> {
-    // Return default profile based on ID
-    let profile = match id {
-        1 => QualityProfile {
-            id: 1,
+) -> ApiResult<Json<QualityProfileResource>> {
+    Ok(Json(find_profile(&state, id).await?.into()))
+}
+
+/// POST /api/v3/qualityprofile - Create new quality profile
+#[instrument(skip(state, resource))]
+pub async fn create_quality_profile(
+    State(state): State<QualityProfileState>,
+    Json(resource): Json<QualityProfileResource>,
+) -> ApiResult<(StatusCode, Json<QualityProfileResource>)> {
+    info!("Creating quality profile: {}", resource.name);
+
+    if let Ok(Some(_)) = state.profile_repo.find_by_name(resource.name.trim()).await {
+        return Err(ApiError::Conflict {
+            resource: format!("quality profile '{}'", resource.name),
+        });
+    }
+
+    let mut profile = QualityProfile::new(String::new(), resource.cutoff);
+    apply_resource(&mut profile, resource)?;
+
+    let created = state
+        .profile_repo
+        .create(&profile)
+        .await
+        .map_err(ApiError::CoreError)?;
+
+    info!(
+        "Created quality profile '{}' with ID: {}",
+        created.name, created.id
+    );
+    Ok((StatusCode::CREATED, Json(created.into())))
+}
+
+/// PUT /api/v3/qualityprofile/:id - Update quality profile
+#[instrument(skip(state, resource))]
+pub async fn update_quality_profile(
+    State(state): State<QualityProfileState>,
+    Path(id): Path<i32>,
+    Json(resource): Json<QualityProfileResource>,
+) -> ApiResult<Json<QualityProfileResource>> {
+    let mut profile = find_profile(&state, id).await?;
+
+    if let Ok(Some(other)) = state.profile_repo.find_by_name(resource.name.trim()).await {
+        if other.id != id {
+            return Err(ApiError::Conflict {
+                resource: format!("quality profile '{}'", resource.name),
+            });
+        }
+    }
+
+    apply_resource(&mut profile, resource)?;
+
+    let updated = state
+        .profile_repo
+        .update(&profile)
+        .await
+        .map_err(ApiError::CoreError)?;
+
+    info!("Updated quality profile '{}'", updated.name);
+    Ok(Json(updated.into()))
+}
+
+/// DELETE /api/v3/qualityprofile/:id - Delete quality profile
+#[instrument(skip(state))]
+pub async fn delete_quality_profile(
+    State(state): State<QualityProfileState>,
+    Path(id): Path<i32>,
+) -> ApiResult<StatusCode> {
+    let profile = find_profile(&state, id).await?;
+
+    let in_use = state
+        .profile_repo
+        .count_movies_using(id)
+        .await
+        .map_err(ApiError::CoreError)?;
+    if in_use > 0 {
+        return Err(ApiError::Conflict {
+            resource: format!(
+                "quality profile '{}' is assigned to {} movie(s)",
+                profile.name, in_use
+            ),
+        });
+    }
+
+    state
+        .profile_repo
+        .delete(id)
+        .await
+        .map_err(ApiError::CoreError)?;
+
+    info!("Deleted quality profile '{}'", profile.name);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Create quality profile management router
+pub fn create_quality_profile_router(state: QualityProfileState) -> Router {
+    Router::new()
+        .route(
+            "/v3/qualityprofile",
+            get(list_quality_profiles).post(create_quality_profile),
+        )
+        .route("/v3/qualityprofile/schema", get(get_quality_profile_schema))
+        .route(
+            "/v3/qualityprofile/:id",
+            get(get_quality_profile)
+                .put(update_quality_profile)
+                .delete(delete_quality_profile),
+        )
+        .route(
+            "/v3/qualityprofile/:id/simulate",
+            post(simulate_quality_profile),
+        )
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quality_item(id: i32, allowed: bool) -> QualityProfileItemResource {
+        QualityProfileItemResource {
+            id: None,
+            name: None,
+            quality: Some(QualityResource {
+                id,
+                name: String::new(),
+                source: String::new(),
+                resolution: 0,
+                modifier: default_modifier(),
+            }),
+            items: Vec::new(),
+            allowed,
+        }
+    }
+
+    fn resource(cutoff: i32, items: Vec<QualityProfileItemResource>) -> QualityProfileResource {
+        QualityProfileResource {
+            id: 0,
             name: "HD-1080p".to_string(),
-            cutoff: 7,
-            items: vec![], // Simplified for this response
+            upgrade_allowed: true,
+            cutoff,
+            items,
             min_format_score: 0,
             cutoff_format_score: 0,
-            format_items: vec![],
-        },
-        2 => QualityProfile {
-            id: 2,
-            name: "Ultra-HD".to_string(),
-            cutoff: 19,
-            items: vec![], // Simplified for this response
-            min_format_score: 0,
-            cutoff_format_score: 0,
-            format_items: vec![],
-        },
-        _ => {
-            return Err((
-                StatusCode::NOT_FOUND,
-                Json(ApiResponse::error(format!(
-                    "Quality profile with ID {} not found",
-                    id
-                ))),
-            ))
+            format_items: Vec::new(),
+            language: None,
         }
-    };
+    }
+
+    #[test]
+    fn test_cutoff_must_be_allowed() {
+        let items = vec![quality_item(4, false), quality_item(7, true)];
+        assert!(validate_items(&resource(7, items.clone())).is_ok());
+        assert!(matches!(
+            validate_items(&resource(4, items)),
+            Err(ApiError::ValidationError { field, .. }) if field == "cutoff"
+        ));
+    }
+
+    #[test]
+    fn test_groups_and_duplicates() {
+        let group = QualityProfileItemResource {
+            id: Some(1001),
+            name: Some("WEB 1080p".to_string()),
+            quality: None,
+            items: vec![quality_item(3, true), quality_item(15, true)],
+            allowed: true,
+        };
+        assert!(
+            validate_items(&resource(1001, vec![quality_item(7, true), group.clone()])).is_ok()
+        );
+        assert!(validate_items(&resource(1001, vec![quality_item(3, true), group])).is_err());
+        assert!(validate_items(&resource(99, vec![quality_item(99, true)])).is_err());
+    }
 
-    Ok(Json(ApiResponse::success(profile)))
-}
\ No newline at end of file
+    #[test]
+    fn test_profile_round_trip_preserves_order() {
+        let mut profile = QualityProfile::new(String::new(), 0);
+        let items = vec![
+            quality_item(19, true),
+            quality_item(4, true),
+            quality_item(7, true),
+        ];
+        apply_resource(&mut profile, resource(7, items)).unwrap();
+
+        let response = QualityProfileResource::from(profile);
+        let ids: Vec<i32> = response
+            .items
+            .iter()
+            .filter_map(|item| item.cutoff_id())
+            .collect();
+        assert_eq!(ids, vec![19, 4, 7]);
+        assert_eq!(
+            response.items[2].quality.as_ref().unwrap().name,
+            "Bluray-1080p"
+        );
+        assert_eq!(response.language.unwrap().name, "English");
+        assert_eq!(response.cutoff, 7);
+    }
+
+    #[test]
+    fn test_simulation_ranks_by_quality_then_format_score() {
+        let items = vec![
+            quality_item(5, true),
+            quality_item(3, true),
+            quality_item(7, true),
+            quality_item(19, false),
+        ];
+        let mut profile = QualityProfile::new(String::new(), 0);
+        apply_resource(&mut profile, resource(3, items)).unwrap();
+        let profile = QualityProfileResource::from(profile);
+        let engine =
+            CustomFormatEngine::with_formats(vec![radarr_decision::CustomFormat::new("HDR", 10)
+                .add_spec(radarr_decision::FormatSpecification::new(
+                    "release_title",
+                    "HDR",
+                ))]);
+
+        let titles = [
+            "Dune.2021.720p.WEB-DL.DDP5.1-GROUP",
+            "Dune.2021.1080p.BluRay.x264-GROUP",
+            "Dune.2021.1080p.BluRay.HDR.x265-OTHER",
+            "Dune.2021.2160p.BluRay.x265-GROUP",
+            "Dune.2021.DVDRip.XviD-GROUP",
+        ]
+        .map(String::from);
+        let releases = simulate_releases(&profile, &engine, &titles);
+
+        let order: Vec<&str> = releases.iter().map(|r| r.title.as_str()).collect();
+        assert_eq!(order[0], "Dune.2021.1080p.BluRay.HDR.x265-OTHER");
+        assert_eq!(order[1], "Dune.2021.1080p.BluRay.x264-GROUP");
+        assert_eq!(order[2], "Dune.2021.720p.WEB-DL.DDP5.1-GROUP");
+        assert_eq!(releases[0].rank, Some(1));
+        assert_eq!(releases[0].custom_format_score, 10);
+        assert!(releases[0].meets_cutoff);
+        assert!(!releases[2].meets_cutoff);
+
+        let uhd = releases
+            .iter()
+            .find(|r| r.quality.name == "Bluray-2160p")
+            .unwrap();
+        assert!(uhd.rejected && uhd.rank.is_none());
+        let dvd = releases.iter().find(|r| r.quality.name == "DVD").unwrap();
+        assert_eq!(dvd.rejections, vec!["DVD is not in profile"]);
+    }
+
+    #[test]
+    fn test_detect_quality() {
+        assert_eq!(
+            detect_quality("Movie.2020.1080p.WEBRip.x264").name,
+            "WEBRip-1080p"
+        );
+        assert_eq!(
+            detect_quality("Movie.2020.2160p.UHD.BluRay.REMUX").name,
+            "Remux-2160p"
+        );
+        assert_eq!(
+            detect_quality("Movie.2020.720p.HDTV.x264").name,
+            "HDTV-720p"
+        );
+        assert_eq!(detect_quality("Movie 2020").name, "Unknown");
+    }
+}
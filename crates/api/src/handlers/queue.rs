@@ -1,539 +1,363 @@
-//! Queue management API handlers
-
+//! Queue API handlers
+//!
+//! `/api/v3/queue` pages through the download queue with each download's
+//! status, progress, time left and tracked download state. Downloads are
+//! removed with `DELETE /api/v3/queue/:id` or, several at once, with
+//! `DELETE /api/v3/queue/bulk`, taking the upstream `removeFromClient`,
+//! `blocklist` and `skipRedownload` flags.
+
+use crate::error::{ApiError, ApiResult};
+use crate::models::PaginatedResponse;
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
     response::Json,
+    routing::{delete, get},
+    Router,
 };
-use serde::{Deserialize, Serialize};
-use uuid::Uuid;
-
-use crate::models::{ApiResponse, PaginationQuery};
 use radarr_core::{
-    services::{ClientDownloadStatus, DownloadClientService, QueueService},
-    QueueItem, QueuePriority, QueueStats, QueueStatus, RadarrError,
+    blocklist::BlocklistRepository,
+    domain::repositories::MovieRepository,
+    models::{QueueItem, QueueStatus, TrackedDownloadState, TrackedDownloadStatus},
+    services::{
+        DownloadClientService, QueueRemovalOptions, QueueRemovalOutcome, QueueRemover,
+        QueueRepository,
+    },
+    CommandQueue,
 };
-use radarr_infrastructure::{repositories::PostgresQueueRepository, DatabasePool};
-
-/// Request to add a release to the queue
-#[derive(Debug, Deserialize)]
-pub struct GrabReleaseRequest {
-    pub release_id: Uuid,
-    pub movie_id: Uuid,
-    pub title: String,
-    pub download_url: String,
-    pub priority: Option<QueuePriority>,
-    pub category: Option<String>,
-}
+use radarr_infrastructure::{
+    DatabasePool, PostgresBlocklistRepository, PostgresMovieRepository, PostgresQueueRepository,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{info, instrument};
+use uuid::Uuid;
 
-/// Query parameters for queue listing
-#[derive(Debug, Deserialize)]
-pub struct QueueQuery {
-    pub status: Option<QueueStatus>,
-    pub movie_id: Option<Uuid>,
-    #[serde(flatten)]
-    pub pagination: PaginationQuery,
-}
+/// Largest page a client may request
+const MAX_QUEUE_PAGE_SIZE: u32 = 1000;
 
-/// Queue item response compatible with frontend expectations
-#[derive(Debug, Serialize, Clone)]
-pub struct QueueItemResponse {
-    pub id: String,
-    #[serde(rename = "movieId")]
-    pub movie_id: i32, // Frontend expects number, not UUID
-    #[serde(rename = "movieTitle")]
-    pub movie_title: String,
-    pub quality: String,
-    pub protocol: String,
-    pub indexer: String,
-    #[serde(rename = "downloadClient")]
-    pub download_client: String,
-    pub status: String,
-    pub size: i64,
-    #[serde(rename = "sizeLeft")]
-    pub size_left: i64,
-    #[serde(rename = "downloadedSize")]
-    pub downloaded_size: i64,
-    pub progress: f64,
-    #[serde(rename = "downloadRate")]
-    pub download_rate: Option<u64>,
-    #[serde(rename = "uploadRate")]
-    pub upload_rate: Option<u64>,
-    pub seeders: Option<i32>,
-    pub leechers: Option<i32>,
-    pub eta: Option<String>,
-    #[serde(rename = "errorMessage")]
-    pub error_message: Option<String>,
-    pub added: String,
-    // Additional display fields for testing
-    #[serde(rename = "sizeDisplay")]
-    pub size_display: Option<String>,
-    #[serde(rename = "downloadSpeedDisplay")]
-    pub download_speed_display: Option<String>,
-    #[serde(rename = "etaDisplay")]
-    pub eta_display: Option<String>,
-    #[serde(rename = "canRetry")]
-    pub can_retry: bool,
+/// Queue state
+#[derive(Clone)]
+pub struct QueueState {
+    pub queue_repo: Arc<dyn QueueRepository>,
+    pub movie_repo: Arc<dyn MovieRepository>,
+    pub blocklist_repo: Arc<dyn BlocklistRepository>,
+    /// Client downloads are removed from; left alone without one
+    pub download_client: Option<Arc<dyn DownloadClientService>>,
+    pub command_queue: Arc<CommandQueue>,
 }
 
-impl From<QueueItem> for QueueItemResponse {
-    fn from(queue_item: QueueItem) -> Self {
-        let eta = queue_item.human_readable_eta();
-
-        // Format display fields
-        let size_display = queue_item.size_bytes.map(|s| format_bytes(s));
-        let download_speed_display = queue_item
-            .download_speed
-            .map(|s| format!("{}/s", format_bytes(s as i64)));
-        let eta_display = queue_item.eta_seconds.map(|seconds| {
-            let hours = seconds / 3600;
-            let minutes = (seconds % 3600) / 60;
-            format!("{}h {}m", hours, minutes)
-        });
-        let can_retry = matches!(queue_item.status, QueueStatus::Failed);
-
+impl QueueState {
+    pub fn new(database_pool: DatabasePool, command_queue: Arc<CommandQueue>) -> Self {
         Self {
-            id: queue_item.id.to_string(),
-            movie_id: queue_item.movie_id.as_u128() as i32, // Convert UUID to int (simplified)
-            movie_title: queue_item.title,
-            quality: "1080p BluRay".to_string(), // Mock quality
-            protocol: "torrent".to_string(),
-            indexer: "Mock Indexer".to_string(),
-            download_client: "qBittorrent".to_string(),
-            status: queue_item.status.to_string(),
-            size: queue_item.size_bytes.unwrap_or(0),
-            size_left: queue_item.size_bytes.unwrap_or(0)
-                - queue_item.downloaded_bytes.unwrap_or(0),
-            downloaded_size: queue_item.downloaded_bytes.unwrap_or(0),
-            progress: queue_item.progress,
-            download_rate: queue_item.download_speed,
-            upload_rate: queue_item.upload_speed,
-            seeders: queue_item.seeders,
-            leechers: queue_item.leechers,
-            eta,
-            error_message: queue_item.error_message,
-            added: queue_item.created_at.to_rfc3339(),
-            size_display,
-            download_speed_display,
-            eta_display,
-            can_retry,
+            queue_repo: Arc::new(PostgresQueueRepository::new(database_pool.clone())),
+            movie_repo: Arc::new(PostgresMovieRepository::new(database_pool.clone())),
+            blocklist_repo: Arc::new(PostgresBlocklistRepository::new(database_pool)),
+            download_client: None,
+            command_queue,
         }
     }
-}
-
-/// Enhanced queue statistics with additional metadata
-#[derive(Debug, Serialize)]
-pub struct QueueStatsResponse {
-    #[serde(flatten)]
-    pub stats: QueueStats,
-    /// Human readable total size
-    pub total_size_display: String,
-    /// Human readable total downloaded
-    pub total_downloaded_display: String,
-    /// Human readable download speed
-    pub total_download_speed_display: String,
-    /// Human readable upload speed
-    pub total_upload_speed_display: String,
-    /// Overall progress percentage
-    pub overall_progress: f64,
-}
 
-impl From<QueueStats> for QueueStatsResponse {
-    fn from(stats: QueueStats) -> Self {
-        let overall_progress = if stats.total_size_bytes > 0 {
-            (stats.total_downloaded_bytes as f64 / stats.total_size_bytes as f64) * 100.0
-        } else {
-            0.0
-        };
+    /// Remove downloads from `client`
+    pub fn with_download_client(mut self, client: Arc<dyn DownloadClientService>) -> Self {
+        self.download_client = Some(client);
+        self
+    }
 
-        Self {
-            total_size_display: format_bytes(stats.total_size_bytes),
-            total_downloaded_display: format_bytes(stats.total_downloaded_bytes),
-            total_download_speed_display: format_bytes_per_sec(stats.total_download_speed),
-            total_upload_speed_display: format_bytes_per_sec(stats.total_upload_speed),
-            overall_progress,
-            stats,
+    fn remover(&self) -> QueueRemover {
+        let remover = QueueRemover::new(self.queue_repo.clone(), self.blocklist_repo.clone())
+            .with_command_queue(self.command_queue.clone());
+        match &self.download_client {
+            Some(client) => remover.with_download_client(client.clone()),
+            None => remover,
         }
     }
 }
 
-/// Queue service state for dependency injection
-#[derive(Clone)]
-pub struct QueueServiceState {
-    pub queue_service: Arc<QueueService<PostgresQueueRepository, MockDownloadClient>>,
+/// Query parameters for the queue listing
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueQueryParams {
+    pub page: Option<u32>,
+    pub page_size: Option<u32>,
+    /// Only downloads of this movie
+    pub movie_id: Option<Uuid>,
 }
 
-use async_trait::async_trait;
-use std::sync::Arc;
-
-/// Mock download client for API handlers
-#[derive(Clone)]
-pub struct MockDownloadClient;
-
-#[async_trait]
-impl DownloadClientService for MockDownloadClient {
-    async fn add_download(
-        &self,
-        _download_url: &str,
-        _category: Option<String>,
-        _save_path: Option<String>,
-    ) -> radarr_core::Result<String> {
-        Ok(format!("mock_client_{}", uuid::Uuid::new_v4()))
-    }
-
-    async fn get_download_status(
-        &self,
-        _client_id: &str,
-    ) -> radarr_core::Result<Option<ClientDownloadStatus>> {
-        Ok(Some(ClientDownloadStatus {
-            client_id: _client_id.to_string(),
-            name: "Mock Download".to_string(),
-            status: "downloading".to_string(),
-            progress: 0.5,
-            download_speed: Some(1024 * 1024),
-            upload_speed: Some(512 * 1024),
-            downloaded_bytes: Some(500 * 1024 * 1024),
-            upload_bytes: Some(100 * 1024 * 1024),
-            eta_seconds: Some(600),
-            seeders: Some(10),
-            leechers: Some(5),
-            save_path: Some("/downloads".to_string()),
-        }))
-    }
-
-    async fn remove_download(
-        &self,
-        _client_id: &str,
-        _delete_files: bool,
-    ) -> radarr_core::Result<()> {
-        Ok(())
+impl QueueQueryParams {
+    fn page(&self) -> u32 {
+        self.page.unwrap_or(1).max(1)
     }
 
-    async fn pause_download(&self, _client_id: &str) -> radarr_core::Result<()> {
-        Ok(())
-    }
-
-    async fn resume_download(&self, _client_id: &str) -> radarr_core::Result<()> {
-        Ok(())
-    }
-
-    async fn get_all_downloads(&self) -> radarr_core::Result<Vec<ClientDownloadStatus>> {
-        Ok(vec![])
+    fn page_size(&self) -> u32 {
+        self.page_size.unwrap_or(20).clamp(1, MAX_QUEUE_PAGE_SIZE)
     }
 }
 
-impl QueueServiceState {
-    pub fn new(pool: DatabasePool) -> Self {
-        let queue_repo = PostgresQueueRepository::new(pool);
-        let download_client = MockDownloadClient;
-        let queue_service = Arc::new(QueueService::new(queue_repo, download_client));
-
-        Self { queue_service }
-    }
+/// Query parameters for removing downloads, as upstream takes them
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueRemoveParams {
+    #[serde(default = "default_true")]
+    pub remove_from_client: bool,
+    #[serde(default)]
+    pub blocklist: bool,
+    #[serde(default)]
+    pub skip_redownload: bool,
 }
 
-/// POST /api/v3/queue/grab - Add release to queue
-pub async fn grab_release(
-    State(_state): State<QueueServiceState>,
-    Json(request): Json<GrabReleaseRequest>,
-) -> std::result::Result<Json<ApiResponse<QueueItemResponse>>, (StatusCode, Json<ApiResponse<()>>)>
-{
-    // For now, return not implemented as this requires movie/release data
-    let _ = request;
-
-    Err((
-        StatusCode::NOT_IMPLEMENTED,
-        Json(ApiResponse::error(
-            "Grab release endpoint requires movie/release integration".to_string(),
-        )),
-    ))
+fn default_true() -> bool {
+    true
 }
 
-/// PUT /api/v3/queue/{id}/priority - Update queue item priority  
-pub async fn update_queue_priority(
-    State(state): State<QueueServiceState>,
-    Path(id): Path<Uuid>,
-    Json(request): Json<PriorityUpdateRequest>,
-) -> std::result::Result<Json<ApiResponse<()>>, (StatusCode, Json<ApiResponse<()>>)> {
-    // This is a simplified implementation - in practice you'd need to
-    // implement priority reordering logic in the queue service
-    let _ = request; // For now, just acknowledge the request
-
-    match state.queue_service.pause_queue_item(id).await {
-        Ok(()) => Ok(Json(ApiResponse::success(()))),
-        Err(e) => {
-            tracing::error!("Failed to update priority for queue item {}: {}", id, e);
-            let status_code = match e {
-                RadarrError::NotFoundError { .. } => StatusCode::NOT_FOUND,
-                _ => StatusCode::INTERNAL_SERVER_ERROR,
-            };
-            Err((
-                status_code,
-                Json(ApiResponse::error(format!(
-                    "Failed to update priority: {}",
-                    e
-                ))),
-            ))
+impl From<QueueRemoveParams> for QueueRemovalOptions {
+    fn from(params: QueueRemoveParams) -> Self {
+        Self {
+            remove_from_client: params.remove_from_client,
+            blocklist: params.blocklist,
+            skip_redownload: params.skip_redownload,
         }
     }
 }
 
-/// Request to update queue item priority
-#[derive(Debug, Deserialize)]
-pub struct PriorityUpdateRequest {
-    pub direction: String, // "up" or "down"
+/// A download as returned by the API
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueResource {
+    pub id: Uuid,
+    pub movie_id: Uuid,
+    /// None when the movie is no longer in the library
+    pub movie_title: Option<String>,
+    pub title: String,
+    pub status: &'static str,
+    pub tracked_download_status: TrackedDownloadStatus,
+    pub tracked_download_state: TrackedDownloadState,
+    pub status_messages: Vec<String>,
+    pub error_message: Option<String>,
+    pub protocol: &'static str,
+    /// ID of the download in the download client, e.g. the torrent hash
+    pub download_id: Option<String>,
+    pub indexer_id: Option<i32>,
+    pub output_path: Option<String>,
+    pub preview_path: Option<String>,
+    pub size: i64,
+    #[serde(rename = "sizeleft")]
+    pub size_left: i64,
+    /// Percent downloaded
+    pub progress: f64,
+    /// Time left as `hh:mm:ss`
+    #[serde(rename = "timeleft")]
+    pub time_left: Option<String>,
+    pub estimated_completion_time: Option<chrono::DateTime<chrono::Utc>>,
+    pub download_rate: Option<u64>,
+    pub added: chrono::DateTime<chrono::Utc>,
 }
 
-/// GET /api/v3/queue - List queue items
-pub async fn list_queue(
-    State(state): State<QueueServiceState>,
-    Query(query): Query<QueueQuery>,
-) -> std::result::Result<Json<ApiResponse<QueueResponseData>>, (StatusCode, Json<ApiResponse<()>>)>
-{
-    match state
-        .queue_service
-        .get_queue_items(
-            query.status,
-            query.movie_id,
-            Some(query.pagination.page_size as usize),
-            Some(((query.pagination.page - 1) * query.pagination.page_size) as usize),
-        )
-        .await
-    {
-        Ok(items) => {
-            let response_items: Vec<QueueItemResponse> =
-                items.into_iter().map(QueueItemResponse::from).collect();
-
-            let response = QueueResponseData {
-                items: response_items.clone(),
-                total_records: response_items.len() as i64,
-                page: Some(query.pagination.page as i32),
-                page_size: Some(query.pagination.page_size as i32),
-            };
-
-            Ok(Json(ApiResponse::success(response)))
-        }
-        Err(e) => {
-            tracing::error!("Failed to list queue items: {}", e);
-            Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error(format!(
-                    "Failed to list queue items: {}",
-                    e
-                ))),
-            ))
+impl QueueResource {
+    fn new(item: QueueItem, movie_title: Option<String>) -> Self {
+        let eta = item.eta_seconds.filter(|seconds| *seconds > 0);
+        Self {
+            status: status_name(item.status),
+            tracked_download_status: item.tracked_download_status(),
+            tracked_download_state: item.tracked_download_state(),
+            status_messages: item.error_message.iter().cloned().collect(),
+            size_left: item.size_left().unwrap_or(0),
+            time_left: eta.map(format_time_left),
+            estimated_completion_time: eta
+                .map(|seconds| item.updated_at + chrono::Duration::seconds(seconds)),
+            id: item.id,
+            movie_id: item.movie_id,
+            movie_title,
+            title: item.title,
+            error_message: item.error_message,
+            // qBittorrent is the only download client
+            protocol: "torrent",
+            download_id: item.download_client_id,
+            indexer_id: item.indexer_id,
+            output_path: item.download_path,
+            preview_path: item.preview_path,
+            size: item.size_bytes.unwrap_or(0),
+            progress: item.progress * 100.0,
+            download_rate: item.download_speed,
+            added: item.created_at,
         }
     }
 }
 
-/// Queue response data structure matching frontend expectations
-#[derive(Debug, Serialize)]
-pub struct QueueResponseData {
-    pub items: Vec<QueueItemResponse>,
-    #[serde(rename = "totalRecords")]
-    pub total_records: i64,
-    pub page: Option<i32>,
-    #[serde(rename = "pageSize")]
-    pub page_size: Option<i32>,
+/// Upstream name of a queue status
+fn status_name(status: QueueStatus) -> &'static str {
+    match status {
+        QueueStatus::Queued => "queued",
+        QueueStatus::Pending => "delay",
+        QueueStatus::Downloading => "downloading",
+        QueueStatus::Paused => "paused",
+        QueueStatus::Stalled => "warning",
+        QueueStatus::Completed | QueueStatus::Seeding => "completed",
+        QueueStatus::Failed | QueueStatus::Cancelled => "failed",
+    }
 }
 
-/// POST /api/v3/queue/grab/{release_id} - Add release to queue by ID
-pub async fn grab_release_by_id(
-    State(_state): State<QueueServiceState>,
-    Path(release_id): Path<Uuid>,
-) -> std::result::Result<Json<ApiResponse<QueueItemResponse>>, (StatusCode, Json<ApiResponse<()>>)>
-{
-    // Placeholder implementation
-    // This would grab a release by ID, typically from search results
-
-    let _ = release_id; // Suppress unused warning
-
-    Err((
-        StatusCode::NOT_IMPLEMENTED,
-        Json(ApiResponse::error(
-            "Queue service not yet wired".to_string(),
-        )),
-    ))
+fn format_time_left(seconds: i64) -> String {
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        (seconds % 3600) / 60,
+        seconds % 60
+    )
 }
 
-/// DELETE /api/v3/queue/{id} - Remove from queue
-pub async fn remove_queue_item(
-    State(state): State<QueueServiceState>,
-    Path(id): Path<Uuid>,
-    Query(query): Query<serde_json::Value>,
-) -> std::result::Result<Json<ApiResponse<()>>, (StatusCode, Json<ApiResponse<()>>)> {
-    // Extract deleteFiles parameter
-    let delete_files = query
-        .get("deleteFiles")
-        .and_then(|v| v.as_bool())
-        .unwrap_or(false);
-
-    match state
-        .queue_service
-        .remove_queue_item(id, delete_files)
-        .await
-    {
-        Ok(()) => Ok(Json(ApiResponse::success(()))),
-        Err(e) => {
-            tracing::error!("Failed to remove queue item {}: {}", id, e);
-            let status_code = match e {
-                radarr_core::RadarrError::NotFoundError { .. } => StatusCode::NOT_FOUND,
-                _ => StatusCode::INTERNAL_SERVER_ERROR,
-            };
-            Err((
-                status_code,
-                Json(ApiResponse::error(format!(
-                    "Failed to remove queue item: {}",
-                    e
-                ))),
-            ))
-        }
-    }
+/// Bulk remove request
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueBulkRequest {
+    pub ids: Vec<Uuid>,
 }
 
-/// PUT /api/v3/queue/{id}/pause - Pause queue item
-pub async fn pause_queue_item(
-    State(state): State<QueueServiceState>,
-    Path(id): Path<Uuid>,
-) -> std::result::Result<Json<ApiResponse<()>>, (StatusCode, Json<ApiResponse<()>>)> {
-    match state.queue_service.pause_queue_item(id).await {
-        Ok(()) => Ok(Json(ApiResponse::success(()))),
-        Err(e) => {
-            tracing::error!("Failed to pause queue item {}: {}", id, e);
-            let status_code = match e {
-                radarr_core::RadarrError::NotFoundError { .. } => StatusCode::NOT_FOUND,
-                _ => StatusCode::INTERNAL_SERVER_ERROR,
-            };
-            Err((
-                status_code,
-                Json(ApiResponse::error(format!(
-                    "Failed to pause queue item: {}",
-                    e
-                ))),
-            ))
-        }
-    }
+/// Outcome of removing one download
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueRemovalResource {
+    pub id: Uuid,
+    pub removed_from_client: bool,
+    pub blocklisted: bool,
+    /// ID of the search queued for another release
+    pub search_command_id: Option<i32>,
 }
 
-/// PUT /api/v3/queue/{id}/resume - Resume queue item
-pub async fn resume_queue_item(
-    State(state): State<QueueServiceState>,
-    Path(id): Path<Uuid>,
-) -> std::result::Result<Json<ApiResponse<()>>, (StatusCode, Json<ApiResponse<()>>)> {
-    match state.queue_service.resume_queue_item(id).await {
-        Ok(()) => Ok(Json(ApiResponse::success(()))),
-        Err(e) => {
-            tracing::error!("Failed to resume queue item {}: {}", id, e);
-            let status_code = match e {
-                radarr_core::RadarrError::NotFoundError { .. } => StatusCode::NOT_FOUND,
-                _ => StatusCode::INTERNAL_SERVER_ERROR,
-            };
-            Err((
-                status_code,
-                Json(ApiResponse::error(format!(
-                    "Failed to resume queue item: {}",
-                    e
-                ))),
-            ))
+impl QueueRemovalResource {
+    fn new(id: Uuid, outcome: QueueRemovalOutcome) -> Self {
+        Self {
+            id,
+            removed_from_client: outcome.removed_from_client,
+            blocklisted: outcome.blocklisted,
+            search_command_id: outcome.search_command_id,
         }
     }
 }
 
-/// GET /api/v3/queue/status - Queue statistics
-pub async fn get_queue_status(
-    State(state): State<QueueServiceState>,
-) -> std::result::Result<Json<ApiResponse<QueueStatsResponse>>, (StatusCode, Json<ApiResponse<()>>)>
-{
-    match state.queue_service.get_queue_statistics().await {
-        Ok(stats) => Ok(Json(ApiResponse::success(QueueStatsResponse::from(stats)))),
-        Err(e) => {
-            tracing::error!("Failed to get queue statistics: {}", e);
-            Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error(format!(
-                    "Failed to get queue statistics: {}",
-                    e
-                ))),
-            ))
-        }
-    }
+/// Outcome of a bulk remove
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueBulkResult {
+    pub removed: Vec<QueueRemovalResource>,
+    /// Requested IDs that were not in the queue
+    pub not_found: Vec<Uuid>,
 }
 
-/// POST /api/v3/queue/retry - Retry failed downloads
-pub async fn retry_failed_downloads(
-    State(_state): State<QueueServiceState>,
-) -> std::result::Result<Json<ApiResponse<Vec<Uuid>>>, (StatusCode, Json<ApiResponse<()>>)> {
-    Err((
-        StatusCode::NOT_IMPLEMENTED,
-        Json(ApiResponse::error(
-            "Queue service not yet wired".to_string(),
-        )),
-    ))
-}
+/// GET /api/v3/queue - Page through the download queue
+#[instrument(skip(state))]
+pub async fn list_queue(
+    State(state): State<QueueState>,
+    Query(params): Query<QueueQueryParams>,
+) -> ApiResult<Json<PaginatedResponse<QueueResource>>> {
+    let items = match params.movie_id {
+        Some(movie_id) => state.queue_repo.get_queue_items_for_movie(movie_id).await?,
+        None => state.queue_repo.get_queue_items(None).await?,
+    };
+
+    let total = items.len() as i64;
+    let (page, page_size) = (params.page(), params.page_size());
+    let mut records = Vec::new();
+    for item in items
+        .into_iter()
+        .skip((page as usize - 1) * page_size as usize)
+        .take(page_size as usize)
+    {
+        let movie_title = state
+            .movie_repo
+            .find_by_id(item.movie_id)
+            .await?
+            .map(|movie| movie.title);
+        records.push(QueueResource::new(item, movie_title));
+    }
 
-/// POST /api/v3/queue/process - Manually trigger queue processing
-pub async fn process_queue(
-    State(_state): State<QueueServiceState>,
-) -> std::result::Result<Json<ApiResponse<Vec<Uuid>>>, (StatusCode, Json<ApiResponse<()>>)> {
-    Err((
-        StatusCode::NOT_IMPLEMENTED,
-        Json(ApiResponse::error(
-            "Queue service not yet wired".to_string(),
-        )),
-    ))
+    Ok(Json(PaginatedResponse::new(
+        page, page_size, total, records,
+    )))
 }
 
-/// DELETE /api/v3/queue/cleanup - Clean up completed items
-pub async fn cleanup_completed(
-    State(_state): State<QueueServiceState>,
-    Query(query): Query<serde_json::Value>,
-) -> std::result::Result<Json<ApiResponse<usize>>, (StatusCode, Json<ApiResponse<()>>)> {
-    let days_old = query.get("daysOld").and_then(|v| v.as_i64()).unwrap_or(7); // Default to 7 days
-
-    let _ = days_old; // Suppress unused warning
-
-    Err((
-        StatusCode::NOT_IMPLEMENTED,
-        Json(ApiResponse::error(
-            "Queue service not yet wired".to_string(),
-        )),
-    ))
+/// GET /api/v3/queue/:id - One download
+#[instrument(skip(state))]
+pub async fn get_queue_item(
+    State(state): State<QueueState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<QueueResource>> {
+    let item = state
+        .queue_repo
+        .get_queue_item(id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound {
+            resource: format!("queue item with id {}", id),
+        })?;
+    let movie_title = state
+        .movie_repo
+        .find_by_id(item.movie_id)
+        .await?
+        .map(|movie| movie.title);
+    Ok(Json(QueueResource::new(item, movie_title)))
 }
 
-// Helper functions for formatting
-
-/// Format bytes as human readable string
-fn format_bytes(bytes: i64) -> String {
-    const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
-
-    // Special case for 0 bytes
-    if bytes == 0 {
-        return "0 B".to_string();
-    }
-
-    let mut size = bytes as f64;
-    let mut unit_index = 0;
+/// DELETE /api/v3/queue/:id - Remove a download, optionally blocklisting
+/// its release
+#[instrument(skip(state))]
+pub async fn remove_queue_item(
+    State(state): State<QueueState>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<QueueRemoveParams>,
+) -> ApiResult<Json<QueueRemovalResource>> {
+    let outcome = state
+        .remover()
+        .remove(id, &params.into())
+        .await?
+        .ok_or_else(|| ApiError::NotFound {
+            resource: format!("queue item with id {}", id),
+        })?;
+    Ok(Json(QueueRemovalResource::new(id, outcome)))
+}
 
-    while size >= 1024.0 && unit_index < UNITS.len() - 1 {
-        size /= 1024.0;
-        unit_index += 1;
+/// DELETE /api/v3/queue/bulk - Remove several downloads at once
+#[instrument(skip(state, request))]
+pub async fn remove_queue_bulk(
+    State(state): State<QueueState>,
+    Query(params): Query<QueueRemoveParams>,
+    Json(request): Json<QueueBulkRequest>,
+) -> ApiResult<Json<QueueBulkResult>> {
+    if request.ids.is_empty() {
+        return Err(ApiError::ValidationError {
+            field: "ids".to_string(),
+            message: "At least one queue item ID is required".to_string(),
+        });
     }
 
-    if unit_index == 0 {
-        // For bytes, don't show decimal places
-        format!("{:.0} {}", size, UNITS[unit_index])
-    } else if size >= 100.0 {
-        format!("{:.0} {}", size, UNITS[unit_index])
-    } else if size >= 10.0 {
-        format!("{:.1} {}", size, UNITS[unit_index])
-    } else {
-        format!("{:.2} {}", size, UNITS[unit_index])
+    let remover = state.remover();
+    let options = params.into();
+    let mut result = QueueBulkResult {
+        removed: Vec::new(),
+        not_found: Vec::new(),
+    };
+    for id in request.ids {
+        match remover.remove(id, &options).await? {
+            Some(outcome) => result.removed.push(QueueRemovalResource::new(id, outcome)),
+            None => result.not_found.push(id),
+        }
     }
+    info!(
+        "Removed {} queue items ({} not found)",
+        result.removed.len(),
+        result.not_found.len()
+    );
+    Ok(Json(result))
 }
 
-/// Format bytes per second as human readable string
-fn format_bytes_per_sec(bytes_per_sec: u64) -> String {
-    format!("{}/s", format_bytes(bytes_per_sec as i64))
+/// Create the queue router
+pub fn create_queue_router(state: QueueState) -> Router {
+    Router::new()
+        .route("/v3/queue", get(list_queue))
+        .route("/v3/queue/bulk", delete(remove_queue_bulk))
+        .route(
+            "/v3/queue/:id",
+            get(get_queue_item).delete(remove_queue_item),
+        )
+        .with_state(state)
 }
 
 #[cfg(test)]
@@ -541,66 +365,52 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_format_bytes() {
-        assert_eq!(format_bytes(0), "0 B");
-        assert_eq!(format_bytes(512), "512 B");
-        assert_eq!(format_bytes(1024), "1.00 KB");
-        assert_eq!(format_bytes(1536), "1.50 KB");
-        assert_eq!(format_bytes(1024 * 1024), "1.00 MB");
-        assert_eq!(format_bytes(1024 * 1024 * 1024), "1.00 GB");
-        assert_eq!(format_bytes(1024 * 1024 * 1024 * 1024), "1.00 TB");
-    }
+    fn test_remove_params_default_to_upstream() {
+        let params: QueueRemoveParams = serde_urlencoded::from_str("").unwrap();
+        assert_eq!(
+            QueueRemovalOptions::from(params),
+            QueueRemovalOptions::default()
+        );
 
-    #[test]
-    fn test_format_bytes_per_sec() {
-        assert_eq!(format_bytes_per_sec(1024), "1.00 KB/s");
-        assert_eq!(format_bytes_per_sec(1024 * 1024), "1.00 MB/s");
+        let params: QueueRemoveParams =
+            serde_urlencoded::from_str("removeFromClient=false&blocklist=true&skipRedownload=true")
+                .unwrap();
+        assert_eq!(
+            QueueRemovalOptions::from(params),
+            QueueRemovalOptions {
+                remove_from_client: false,
+                blocklist: true,
+                skip_redownload: true,
+            }
+        );
     }
 
     #[test]
-    fn test_queue_item_response_conversion() {
-        let mut queue_item = QueueItem::new(
+    fn test_queue_resource() {
+        let mut item = QueueItem::new(
             Uuid::new_v4(),
             Uuid::new_v4(),
-            "Test Movie".to_string(),
-            "magnet:test".to_string(),
+            "Dune.2021.1080p.BluRay.x264".to_string(),
+            "magnet:?xt=urn:btih:abc".to_string(),
         );
-        queue_item.size_bytes = Some(1024 * 1024 * 1024); // 1GB
-        queue_item.download_speed = Some(1024 * 1024); // 1MB/s
-        queue_item.eta_seconds = Some(3600); // 1 hour
-
-        let response = QueueItemResponse::from(queue_item);
+        item.size_bytes = Some(8_000);
+        item.update_status(QueueStatus::Downloading);
+        item.update_progress(0.75, Some(6_000), Some(1_024), Some(3_725));
 
-        assert_eq!(response.size_display, Some("1.00 GB".to_string()));
+        let resource = QueueResource::new(item, Some("Dune".to_string()));
+        assert_eq!(resource.status, "downloading");
         assert_eq!(
-            response.download_speed_display,
-            Some("1.00 MB/s".to_string())
+            resource.tracked_download_state,
+            TrackedDownloadState::Downloading
         );
-        assert_eq!(response.eta_display, Some("1h 0m".to_string()));
-        assert!(!response.can_retry);
-    }
-
-    #[test]
-    fn test_queue_stats_response_conversion() {
-        let stats = QueueStats {
-            total_count: 10,
-            downloading_count: 3,
-            queued_count: 5,
-            completed_count: 2,
-            failed_count: 0,
-            paused_count: 0,
-            total_download_speed: 2 * 1024 * 1024,     // 2MB/s
-            total_upload_speed: 512 * 1024,            // 512KB/s
-            total_size_bytes: 10 * 1024 * 1024 * 1024, // 10GB
-            total_downloaded_bytes: 3 * 1024 * 1024 * 1024, // 3GB
-        };
-
-        let response = QueueStatsResponse::from(stats);
-
-        assert_eq!(response.total_size_display, "10.0 GB");
-        assert_eq!(response.total_downloaded_display, "3.00 GB");
-        assert_eq!(response.total_download_speed_display, "2.00 MB/s");
-        assert_eq!(response.total_upload_speed_display, "512 KB/s");
-        assert_eq!(response.overall_progress, 30.0);
+        assert_eq!(resource.size_left, 2_000);
+        assert_eq!(resource.progress, 75.0);
+        assert_eq!(resource.time_left.as_deref(), Some("01:02:05"));
+        assert!(resource.estimated_completion_time.is_some());
+
+        let json = serde_json::to_value(&resource).unwrap();
+        assert_eq!(json["sizeleft"], 2_000);
+        assert_eq!(json["trackedDownloadStatus"], "ok");
+        assert_eq!(json["movieTitle"], "Dune");
     }
 }
@@ -0,0 +1,120 @@
+//! Bulk rename API handlers
+//!
+//! Previews and carries out renaming imported movie files to the current
+//! naming templates, for when the templates changed after files were
+//! imported. A rename either succeeds for every file or is rolled back.
+
+use crate::error::{ApiError, ApiResult};
+use axum::{
+    extract::{Query, State},
+    response::Json,
+    routing::get,
+    Router,
+};
+use radarr_core::{
+    domain::repositories::{MovieFileRepository, MovieRepository},
+    models::media_root,
+};
+use radarr_import::{LibraryRenameService, RenameConfig, RenamePreview};
+use radarr_infrastructure::{DatabasePool, PostgresMovieFileRepository, PostgresMovieRepository};
+use serde::Deserialize;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+use tracing::{info, instrument};
+use uuid::Uuid;
+
+/// Bulk rename state
+#[derive(Clone)]
+pub struct RenameState {
+    pub movie_repo: Arc<dyn MovieRepository>,
+    pub movie_file_repo: Arc<dyn MovieFileRepository>,
+    /// Naming settings, shared with the settings API
+    pub rename_config: Arc<RwLock<RenameConfig>>,
+    /// Root folder that movie file paths are relative to
+    pub media_root: PathBuf,
+}
+
+impl RenameState {
+    pub fn new(database_pool: DatabasePool) -> Self {
+        Self {
+            movie_repo: Arc::new(PostgresMovieRepository::new(database_pool.clone())),
+            movie_file_repo: Arc::new(PostgresMovieFileRepository::new(database_pool)),
+            rename_config: Arc::new(RwLock::new(RenameConfig::default())),
+            media_root: media_root(),
+        }
+    }
+
+    /// Rename files with the templates the import pipeline uses
+    pub fn with_rename_config(mut self, config: Arc<RwLock<RenameConfig>>) -> Self {
+        self.rename_config = config;
+        self
+    }
+
+    fn service(&self) -> LibraryRenameService {
+        LibraryRenameService::new(
+            self.movie_repo.clone(),
+            self.movie_file_repo.clone(),
+            self.rename_config
+                .read()
+                .unwrap_or_else(|e| e.into_inner())
+                .clone(),
+            self.media_root.clone(),
+        )
+    }
+}
+
+/// Query parameters for the rename preview
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenameQueryParams {
+    /// Only the file of this movie; every movie when unset
+    pub movie_id: Option<Uuid>,
+}
+
+/// Rename request
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenameRequest {
+    /// Only the files of these movies; every movie when unset
+    pub movie_ids: Option<Vec<Uuid>>,
+}
+
+/// GET /api/v3/rename - Files whose name differs from the naming templates
+#[instrument(skip(state))]
+pub async fn preview_rename(
+    State(state): State<RenameState>,
+    Query(params): Query<RenameQueryParams>,
+) -> ApiResult<Json<Vec<RenamePreview>>> {
+    let movie_ids = params.movie_id.map(|id| vec![id]);
+    let previews = state
+        .service()
+        .preview(movie_ids.as_deref())
+        .await
+        .map_err(ApiError::CoreError)?;
+    Ok(Json(previews))
+}
+
+/// POST /api/v3/rename - Rename files to the naming templates
+///
+/// Returns the renames carried out. When one fails, the files renamed
+/// before it are moved back and the error is returned.
+#[instrument(skip(state))]
+pub async fn execute_rename(
+    State(state): State<RenameState>,
+    Json(request): Json<RenameRequest>,
+) -> ApiResult<Json<Vec<RenamePreview>>> {
+    let renamed = state
+        .service()
+        .rename(request.movie_ids.as_deref())
+        .await
+        .map_err(ApiError::CoreError)?;
+    info!("Renamed {} movie files through the API", renamed.len());
+    Ok(Json(renamed))
+}
+
+/// Create bulk rename router
+pub fn create_rename_router(state: RenameState) -> Router {
+    Router::new()
+        .route("/v3/rename", get(preview_rename).post(execute_rename))
+        .with_state(state)
+}
@@ -0,0 +1,316 @@
+//! Root folder management API handlers
+//!
+//! Root folders are the library directories movies are imported into.
+//! Responses report the free space currently available on each folder, which
+//! the import pipeline also checks against the folder's reserve before it
+//! copies a file in.
+
+use crate::error::{ApiError, ApiResult};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    routing::get,
+    Router,
+};
+use radarr_core::{
+    domain::repositories::{QualityProfileRepository, RootFolderRepository},
+    models::RootFolder,
+    services::StorageThrottle,
+};
+use radarr_import::free_space;
+use radarr_infrastructure::{
+    DatabasePool, PostgresQualityProfileRepository, PostgresRootFolderRepository,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{debug, info, instrument};
+
+/// Root folder management state
+#[derive(Clone)]
+pub struct RootFolderState {
+    pub root_folder_repo: Arc<dyn RootFolderRepository>,
+    pub profile_repo: Arc<dyn QualityProfileRepository>,
+    /// Free space per root folder, shared with the queue processor
+    pub storage_throttle: Arc<StorageThrottle>,
+}
+
+impl RootFolderState {
+    pub fn new(database_pool: DatabasePool) -> Self {
+        Self {
+            root_folder_repo: Arc::new(PostgresRootFolderRepository::new(database_pool.clone())),
+            profile_repo: Arc::new(PostgresQualityProfileRepository::new(database_pool)),
+            storage_throttle: Arc::new(StorageThrottle::default()),
+        }
+    }
+
+    /// Use the throttle the queue processor holds grabs with
+    pub fn with_storage_throttle(mut self, throttle: Arc<StorageThrottle>) -> Self {
+        self.storage_throttle = throttle;
+        self
+    }
+}
+
+/// Root folder create/update request
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RootFolderRequest {
+    pub path: String,
+    pub default_quality_profile_id: Option<i32>,
+    pub min_free_space_bytes: Option<i64>,
+}
+
+/// Root folder as returned by the API
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RootFolderResource {
+    pub id: i32,
+    pub path: String,
+    pub default_quality_profile_id: Option<i32>,
+    pub min_free_space_bytes: i64,
+    /// Whether the directory exists and its free space could be read
+    pub accessible: bool,
+    pub free_space: Option<u64>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl RootFolderResource {
+    async fn from_root_folder(root_folder: RootFolder) -> Self {
+        let is_dir = tokio::fs::metadata(&root_folder.path)
+            .await
+            .map(|metadata| metadata.is_dir())
+            .unwrap_or(false);
+        let free_space = if is_dir {
+            free_space(std::path::Path::new(&root_folder.path))
+                .await
+                .map_err(|e| debug!("Cannot read free space of {}: {}", root_folder.path, e))
+                .ok()
+        } else {
+            None
+        };
+
+        Self {
+            id: root_folder.id,
+            path: root_folder.path,
+            default_quality_profile_id: root_folder.default_quality_profile_id,
+            min_free_space_bytes: root_folder.min_free_space_bytes,
+            accessible: free_space.is_some(),
+            free_space,
+            created_at: root_folder.created_at.to_rfc3339(),
+            updated_at: root_folder.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Trim trailing separators so `/movies/` and `/movies` are the same folder
+fn normalize_path(path: &str) -> String {
+    let trimmed = path.trim();
+    match trimmed.trim_end_matches('/') {
+        "" if trimmed.starts_with('/') => "/".to_string(),
+        normalized => normalized.to_string(),
+    }
+}
+
+/// Validate a request and apply it onto a root folder
+async fn apply_request(
+    state: &RootFolderState,
+    root_folder: &mut RootFolder,
+    request: RootFolderRequest,
+) -> ApiResult<()> {
+    let path = normalize_path(&request.path);
+    if !std::path::Path::new(&path).is_absolute() {
+        return Err(ApiError::ValidationError {
+            field: "path".to_string(),
+            message: format!("Root folder path must be absolute: {}", request.path),
+        });
+    }
+    let is_dir = tokio::fs::metadata(&path)
+        .await
+        .map(|metadata| metadata.is_dir())
+        .unwrap_or(false);
+    if !is_dir {
+        return Err(ApiError::ValidationError {
+            field: "path".to_string(),
+            message: format!("Root folder does not exist or is not a directory: {}", path),
+        });
+    }
+
+    if let Some(other) = state
+        .root_folder_repo
+        .find_by_path(&path)
+        .await
+        .map_err(ApiError::CoreError)?
+    {
+        if other.id != root_folder.id {
+            return Err(ApiError::Conflict {
+                resource: format!("root folder '{}'", path),
+            });
+        }
+    }
+
+    if let Some(profile_id) = request.default_quality_profile_id {
+        let profile = state
+            .profile_repo
+            .find_by_id(profile_id)
+            .await
+            .map_err(ApiError::CoreError)?;
+        if profile.is_none() {
+            return Err(ApiError::ValidationError {
+                field: "defaultQualityProfileId".to_string(),
+                message: format!("Quality profile {} does not exist", profile_id),
+            });
+        }
+    }
+
+    let min_free_space_bytes = request
+        .min_free_space_bytes
+        .unwrap_or(root_folder.min_free_space_bytes);
+    if min_free_space_bytes < 0 {
+        return Err(ApiError::ValidationError {
+            field: "minFreeSpaceBytes".to_string(),
+            message: "Minimum free space cannot be negative".to_string(),
+        });
+    }
+
+    root_folder.path = path;
+    root_folder.default_quality_profile_id = request.default_quality_profile_id;
+    root_folder.min_free_space_bytes = min_free_space_bytes;
+    root_folder.updated_at = chrono::Utc::now();
+    Ok(())
+}
+
+async fn find_root_folder(state: &RootFolderState, id: i32) -> ApiResult<RootFolder> {
+    state
+        .root_folder_repo
+        .find_by_id(id)
+        .await
+        .map_err(ApiError::CoreError)?
+        .ok_or_else(|| ApiError::NotFound {
+            resource: format!("root folder with id {}", id),
+        })
+}
+
+/// GET /api/v3/rootfolder - List root folders with their free space
+#[instrument(skip(state))]
+pub async fn list_root_folders(
+    State(state): State<RootFolderState>,
+) -> ApiResult<Json<Vec<RootFolderResource>>> {
+    let root_folders = state
+        .root_folder_repo
+        .list()
+        .await
+        .map_err(ApiError::CoreError)?;
+
+    let mut resources = Vec::with_capacity(root_folders.len());
+    for root_folder in root_folders {
+        resources.push(RootFolderResource::from_root_folder(root_folder).await);
+    }
+    Ok(Json(resources))
+}
+
+/// GET /api/v3/rootfolder/:id - Get root folder by ID
+#[instrument(skip(state))]
+pub async fn get_root_folder(
+    State(state): State<RootFolderState>,
+    Path(id): Path<i32>,
+) -> ApiResult<Json<RootFolderResource>> {
+    let root_folder = find_root_folder(&state, id).await?;
+    Ok(Json(
+        RootFolderResource::from_root_folder(root_folder).await,
+    ))
+}
+
+/// POST /api/v3/rootfolder - Create new root folder
+#[instrument(skip(state, request))]
+pub async fn create_root_folder(
+    State(state): State<RootFolderState>,
+    Json(request): Json<RootFolderRequest>,
+) -> ApiResult<(StatusCode, Json<RootFolderResource>)> {
+    let mut root_folder = RootFolder::new(String::new());
+    apply_request(&state, &mut root_folder, request).await?;
+
+    let created = state
+        .root_folder_repo
+        .create(&root_folder)
+        .await
+        .map_err(ApiError::CoreError)?;
+
+    info!(
+        "Created root folder '{}' with ID: {}",
+        created.path, created.id
+    );
+    Ok((
+        StatusCode::CREATED,
+        Json(RootFolderResource::from_root_folder(created).await),
+    ))
+}
+
+/// PUT /api/v3/rootfolder/:id - Update root folder
+#[instrument(skip(state, request))]
+pub async fn update_root_folder(
+    State(state): State<RootFolderState>,
+    Path(id): Path<i32>,
+    Json(request): Json<RootFolderRequest>,
+) -> ApiResult<Json<RootFolderResource>> {
+    let mut root_folder = find_root_folder(&state, id).await?;
+    apply_request(&state, &mut root_folder, request).await?;
+
+    let updated = state
+        .root_folder_repo
+        .update(&root_folder)
+        .await
+        .map_err(ApiError::CoreError)?;
+
+    info!("Updated root folder '{}'", updated.path);
+    Ok(Json(RootFolderResource::from_root_folder(updated).await))
+}
+
+/// DELETE /api/v3/rootfolder/:id - Delete root folder
+///
+/// Only the definition is removed; files on disk are left alone.
+#[instrument(skip(state))]
+pub async fn delete_root_folder(
+    State(state): State<RootFolderState>,
+    Path(id): Path<i32>,
+) -> ApiResult<StatusCode> {
+    let root_folder = find_root_folder(&state, id).await?;
+
+    state
+        .root_folder_repo
+        .delete(id)
+        .await
+        .map_err(ApiError::CoreError)?;
+
+    info!("Deleted root folder '{}'", root_folder.path);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Create root folder management router
+pub fn create_root_folder_router(state: RootFolderState) -> Router {
+    Router::new()
+        .route(
+            "/v3/rootfolder",
+            get(list_root_folders).post(create_root_folder),
+        )
+        .route(
+            "/v3/rootfolder/:id",
+            get(get_root_folder)
+                .put(update_root_folder)
+                .delete(delete_root_folder),
+        )
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_path() {
+        assert_eq!(normalize_path(" /media/movies/ "), "/media/movies");
+        assert_eq!(normalize_path("/media/movies"), "/media/movies");
+        assert_eq!(normalize_path("/"), "/");
+        assert_eq!(normalize_path("movies/"), "movies");
+    }
+}
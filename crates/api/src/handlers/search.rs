@@ -66,6 +66,8 @@ pub async fn search_releases(
             freeleech: Some(false),
             quality_score: Some(85),
             progress: 0.0,
+            rejected: false,
+            rejections: Vec::new(),
         },
         ReleaseResponse {
             guid: "test-guid-2".to_string(),
@@ -85,6 +87,8 @@ pub async fn search_releases(
             freeleech: Some(false),
             quality_score: Some(75),
             progress: 0.0,
+            rejected: false,
+            rejections: Vec::new(),
         },
     ];
 
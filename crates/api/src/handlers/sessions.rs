@@ -0,0 +1,218 @@
+//! Login and logout handlers
+//!
+//! Logging in with the configured username and password sets the session
+//! cookie the auth middleware accepts in place of the API key. Requests can
+//! be JSON or come straight from an HTML form; forms are redirected back into
+//! the web UI instead of getting a JSON response. These endpoints are public,
+//! so the web UI can show a login form before it has any credentials.
+
+use crate::auth::{self, AuthOutcome};
+use crate::error::{ApiError, ApiResult};
+use crate::sessions::{self, global_sessions};
+use axum::{
+    body::Bytes,
+    extract::ConnectInfo,
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Json, Redirect, Response},
+    routing::{get, post},
+    Router,
+};
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+use tracing::{info, instrument};
+
+/// Where failed form logins are sent back to
+const LOGIN_FAILED_PATH: &str = "/login?loginFailed=true";
+
+/// Login request, as JSON or form fields
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoginRequest {
+    pub username: String,
+    pub password: String,
+    /// Page a form login returns to; only paths on this server are followed
+    pub return_url: Option<String>,
+}
+
+/// Whether logins are enabled and who is logged in
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoginStatusResource {
+    pub authentication_enabled: bool,
+    pub authenticated: bool,
+    pub username: Option<String>,
+}
+
+fn is_form(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/x-www-form-urlencoded"))
+}
+
+fn parse_request(headers: &HeaderMap, body: &[u8]) -> ApiResult<LoginRequest> {
+    let parsed = if is_form(headers) {
+        serde_urlencoded::from_bytes(body).map_err(|e| e.to_string())
+    } else {
+        serde_json::from_slice(body).map_err(|e| e.to_string())
+    };
+    parsed.map_err(|e| ApiError::BadRequest {
+        message: format!("Invalid login request: {}", e),
+    })
+}
+
+/// Local path a form login may return to, defaulting to the web UI root
+fn return_path(return_url: Option<&str>) -> &str {
+    return_url
+        .filter(|url| url.starts_with('/') && !url.starts_with("//") && !url.contains('\\'))
+        .unwrap_or("/")
+}
+
+/// Error for a client that is still locked out
+fn locked_out(retry_after: std::time::Duration) -> ApiError {
+    ApiError::CoreError(radarr_core::RadarrError::RateLimited {
+        service: "api".to_string(),
+        retry_after: Some(retry_after.as_secs().max(1)),
+    })
+}
+
+/// GET /api/v3/login - Whether logins are enabled and the session is valid
+#[instrument(skip(headers))]
+pub async fn get_login_status(headers: HeaderMap) -> Json<LoginStatusResource> {
+    let Some(store) = global_sessions() else {
+        return Json(LoginStatusResource {
+            authentication_enabled: false,
+            authenticated: false,
+            username: None,
+        });
+    };
+
+    let username = sessions::session_token(&headers)
+        .and_then(|token| store.authenticate(token, chrono::Utc::now()));
+    Json(LoginStatusResource {
+        authentication_enabled: true,
+        authenticated: username.is_some(),
+        username,
+    })
+}
+
+/// POST /api/v3/login - Check the credentials and start a session
+#[instrument(skip(connect_info, headers, body))]
+pub async fn login(
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> ApiResult<Response> {
+    let store = global_sessions().ok_or_else(|| ApiError::BadRequest {
+        message: "Username and password authentication is not configured".to_string(),
+    })?;
+    let verifier = auth::global_verifier().ok_or_else(|| ApiError::ServiceUnavailable {
+        message: "No API key is configured".to_string(),
+    })?;
+    let request = parse_request(&headers, &body)?;
+    let form = is_form(&headers);
+    let client = auth::client_identity(connect_info.map(|ConnectInfo(addr)| addr.ip()));
+
+    // Locked-out clients are refused before their password costs a hash
+    if let Some(retry_after) = verifier.lockout_remaining(&client, "/api/v3/login") {
+        return Err(locked_out(retry_after));
+    }
+
+    // Argon2 is deliberately slow; keep it off the async workers
+    let valid = {
+        let (username, password) = (request.username.clone(), request.password.clone());
+        tokio::task::spawn_blocking(move || store.verify(&username, &password))
+            .await
+            .map_err(|e| ApiError::InternalError {
+                message: format!("Password check failed: {}", e),
+            })?
+    };
+
+    match verifier.authenticate_login(&client, "/api/v3/login", || valid) {
+        AuthOutcome::Authorized => {}
+        AuthOutcome::LockedOut { retry_after } => return Err(locked_out(retry_after)),
+        AuthOutcome::Missing | AuthOutcome::Invalid if form => {
+            return Ok(Redirect::to(LOGIN_FAILED_PATH).into_response());
+        }
+        AuthOutcome::Missing | AuthOutcome::Invalid => return Err(ApiError::Unauthorized),
+    }
+
+    let token = store.create(chrono::Utc::now());
+    let cookie = [(
+        header::SET_COOKIE,
+        sessions::session_cookie(&token, store.ttl()),
+    )];
+    info!("User '{}' logged in from {}", store.username(), client);
+
+    if form {
+        let location = return_path(request.return_url.as_deref());
+        return Ok((cookie, Redirect::to(location)).into_response());
+    }
+    Ok((
+        cookie,
+        Json(LoginStatusResource {
+            authentication_enabled: true,
+            authenticated: true,
+            username: Some(store.username().to_string()),
+        }),
+    )
+        .into_response())
+}
+
+/// POST /api/v3/logout - End the session and clear its cookie
+#[instrument(skip(headers))]
+pub async fn logout(headers: HeaderMap) -> Response {
+    if let (Some(store), Some(token)) = (global_sessions(), sessions::session_token(&headers)) {
+        store.remove(token);
+        info!("User '{}' logged out", store.username());
+    }
+    (
+        StatusCode::NO_CONTENT,
+        [(header::SET_COOKIE, sessions::expired_session_cookie())],
+    )
+        .into_response()
+}
+
+/// Create the login router
+pub fn create_session_router() -> Router {
+    Router::new()
+        .route("/v3/login", get(get_login_status).post(login))
+        .route("/v3/logout", post(logout))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn test_parse_request_accepts_json_and_forms() {
+        let mut headers = HeaderMap::new();
+        let request =
+            parse_request(&headers, br#"{"username":"admin","password":"secret"}"#).unwrap();
+        assert_eq!(request.username, "admin");
+        assert!(request.return_url.is_none());
+
+        headers.insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/x-www-form-urlencoded"),
+        );
+        let request = parse_request(
+            &headers,
+            b"username=admin&password=p%40ss&returnUrl=%2Fmovies",
+        )
+        .unwrap();
+        assert_eq!(request.password, "p@ss");
+        assert_eq!(request.return_url.as_deref(), Some("/movies"));
+        assert!(parse_request(&headers, b"username=admin").is_err());
+    }
+
+    #[test]
+    fn test_return_path_stays_on_this_server() {
+        assert_eq!(return_path(Some("/movies/1")), "/movies/1");
+        assert_eq!(return_path(None), "/");
+        assert_eq!(return_path(Some("https://evil.example")), "/");
+        assert_eq!(return_path(Some("//evil.example")), "/");
+        assert_eq!(return_path(Some("/\\evil.example")), "/");
+    }
+}
@@ -0,0 +1,233 @@
+//! Share token API handlers
+//!
+//! Share tokens give friends or dashboards read-only access to the library
+//! listing and the calendar until they expire. The token is only returned by
+//! the create call; listings show its metadata, and revoking a token takes
+//! effect immediately.
+
+use crate::error::{ApiError, ApiResult};
+use crate::share_tokens::{generate_share_token, global_registry, hash_share_token};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    routing::{delete, get},
+    Router,
+};
+use chrono::{DateTime, Duration, Utc};
+use radarr_core::{
+    domain::repositories::ShareTokenRepository,
+    models::{ShareScope, ShareToken},
+};
+use radarr_infrastructure::{DatabasePool, PostgresShareTokenRepository};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{info, instrument};
+use uuid::Uuid;
+
+/// Lifetime of a token created without an expiry
+const DEFAULT_EXPIRY_HOURS: i64 = 7 * 24;
+
+/// Longest lifetime a token can be created with
+const MAX_EXPIRY_HOURS: i64 = 365 * 24;
+
+/// Share token state
+#[derive(Clone)]
+pub struct ShareTokenState {
+    pub share_token_repo: Arc<dyn ShareTokenRepository>,
+}
+
+impl ShareTokenState {
+    pub fn new(database_pool: DatabasePool) -> Self {
+        Self {
+            share_token_repo: Arc::new(PostgresShareTokenRepository::new(database_pool)),
+        }
+    }
+}
+
+/// Share token create request
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShareTokenRequest {
+    pub name: String,
+    pub scopes: Vec<String>,
+    /// Hours until the token expires; ignored when `expires_at` is set
+    pub expires_in_hours: Option<i64>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Share token as returned by the API
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShareTokenResource {
+    pub id: Uuid,
+    pub name: String,
+    pub scopes: Vec<ShareScope>,
+    pub created_at: String,
+    pub expires_at: String,
+    pub revoked_at: Option<String>,
+    pub active: bool,
+    /// The token itself, only present in the create response
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+}
+
+impl From<ShareToken> for ShareTokenResource {
+    fn from(token: ShareToken) -> Self {
+        Self {
+            active: token.is_active(Utc::now()),
+            id: token.id,
+            name: token.name,
+            scopes: token.scopes,
+            created_at: token.created_at.to_rfc3339(),
+            expires_at: token.expires_at.to_rfc3339(),
+            revoked_at: token.revoked_at.map(|at| at.to_rfc3339()),
+            token: None,
+        }
+    }
+}
+
+/// Validate a create request, returning the name, scopes and expiry
+fn parse_request(
+    request: ShareTokenRequest,
+    now: DateTime<Utc>,
+) -> ApiResult<(String, Vec<ShareScope>, DateTime<Utc>)> {
+    let name = request.name.trim().to_string();
+    if name.is_empty() {
+        return Err(ApiError::ValidationError {
+            field: "name".to_string(),
+            message: "Share token name cannot be empty".to_string(),
+        });
+    }
+
+    let mut scopes = Vec::new();
+    for value in &request.scopes {
+        let scope = ShareScope::parse(value).ok_or_else(|| ApiError::ValidationError {
+            field: "scopes".to_string(),
+            message: format!("Unsupported share token scope: {}", value),
+        })?;
+        if !scopes.contains(&scope) {
+            scopes.push(scope);
+        }
+    }
+    if scopes.is_empty() {
+        return Err(ApiError::ValidationError {
+            field: "scopes".to_string(),
+            message: "At least one scope is required".to_string(),
+        });
+    }
+
+    let expires_at = match request.expires_at {
+        Some(expires_at) => expires_at,
+        None => now + Duration::hours(request.expires_in_hours.unwrap_or(DEFAULT_EXPIRY_HOURS)),
+    };
+    if expires_at <= now || expires_at > now + Duration::hours(MAX_EXPIRY_HOURS) {
+        return Err(ApiError::ValidationError {
+            field: "expiresAt".to_string(),
+            message: format!(
+                "Expiry must be in the future and at most {} days away",
+                MAX_EXPIRY_HOURS / 24
+            ),
+        });
+    }
+
+    Ok((name, scopes, expires_at))
+}
+
+/// GET /api/v3/sharetoken - List share tokens
+#[instrument(skip(state))]
+pub async fn list_share_tokens(
+    State(state): State<ShareTokenState>,
+) -> ApiResult<Json<Vec<ShareTokenResource>>> {
+    let tokens = state.share_token_repo.list().await?;
+    Ok(Json(tokens.into_iter().map(Into::into).collect()))
+}
+
+/// POST /api/v3/sharetoken - Mint a share token
+#[instrument(skip(state, request))]
+pub async fn create_share_token(
+    State(state): State<ShareTokenState>,
+    Json(request): Json<ShareTokenRequest>,
+) -> ApiResult<(StatusCode, Json<ShareTokenResource>)> {
+    let (name, scopes, expires_at) = parse_request(request, Utc::now())?;
+    let token = generate_share_token();
+
+    let created = state
+        .share_token_repo
+        .create(&ShareToken::new(
+            name,
+            hash_share_token(&token),
+            scopes,
+            expires_at,
+        ))
+        .await?;
+    global_registry().insert(created.clone());
+
+    info!(
+        "Created share token '{}' ({}) expiring at {}",
+        created.name, created.id, created.expires_at
+    );
+    let mut resource = ShareTokenResource::from(created);
+    resource.token = Some(token);
+    Ok((StatusCode::CREATED, Json(resource)))
+}
+
+/// DELETE /api/v3/sharetoken/:id - Revoke a share token
+#[instrument(skip(state))]
+pub async fn revoke_share_token(
+    State(state): State<ShareTokenState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<StatusCode> {
+    let revoked = state
+        .share_token_repo
+        .revoke(id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound {
+            resource: format!("share token with id {}", id),
+        })?;
+    global_registry().remove(id);
+
+    info!("Revoked share token '{}' ({})", revoked.name, revoked.id);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Create the share token router
+pub fn create_share_token_router(state: ShareTokenState) -> Router {
+    Router::new()
+        .route(
+            "/v3/sharetoken",
+            get(list_share_tokens).post(create_share_token),
+        )
+        .route("/v3/sharetoken/:id", delete(revoke_share_token))
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(scopes: &[&str], expires_in_hours: Option<i64>) -> ShareTokenRequest {
+        ShareTokenRequest {
+            name: " Dashboard ".to_string(),
+            scopes: scopes.iter().map(|scope| scope.to_string()).collect(),
+            expires_in_hours,
+            expires_at: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_request_validates_scopes_and_expiry() {
+        let now = Utc::now();
+
+        let (name, scopes, expires_at) =
+            parse_request(request(&["library", "Library", "calendar"], None), now).unwrap();
+        assert_eq!(name, "Dashboard");
+        assert_eq!(scopes, vec![ShareScope::Library, ShareScope::Calendar]);
+        assert_eq!(expires_at, now + Duration::hours(DEFAULT_EXPIRY_HOURS));
+
+        assert!(parse_request(request(&[], Some(1)), now).is_err());
+        assert!(parse_request(request(&["movies"], Some(1)), now).is_err());
+        assert!(parse_request(request(&["library"], Some(0)), now).is_err());
+        assert!(parse_request(request(&["library"], Some(MAX_EXPIRY_HOURS + 1)), now).is_err());
+    }
+}
@@ -0,0 +1,57 @@
+//! Library statistics API handlers
+//!
+//! `/api/v3/movie/stats` reports library totals for dashboards: movies,
+//! monitored and unmonitored ones, files and their size, files per quality and
+//! monitored movies below their cutoff. The same statistics are part of the
+//! system status. They are recomputed at most once a minute.
+
+use crate::error::{ApiError, ApiResult};
+use axum::{extract::State, response::Json, routing::get, Router};
+use radarr_core::{LibraryStatistics, LibraryStatisticsService};
+use radarr_infrastructure::{
+    DatabasePool, PostgresMovieFileRepository, PostgresMovieRepository,
+    PostgresQualityProfileRepository,
+};
+use std::sync::Arc;
+use tracing::instrument;
+
+/// Library statistics state
+#[derive(Clone)]
+pub struct StatisticsState {
+    pub statistics: Arc<LibraryStatisticsService>,
+}
+
+impl StatisticsState {
+    pub fn new(statistics: Arc<LibraryStatisticsService>) -> Self {
+        Self { statistics }
+    }
+
+    /// Statistics computed from the library in `database_pool`
+    pub fn from_pool(database_pool: DatabasePool) -> Self {
+        Self::new(Arc::new(LibraryStatisticsService::new(
+            Arc::new(PostgresMovieRepository::new(database_pool.clone())),
+            Arc::new(PostgresMovieFileRepository::new(database_pool.clone())),
+            Arc::new(PostgresQualityProfileRepository::new(database_pool)),
+        )))
+    }
+}
+
+/// GET /api/v3/movie/stats - Library totals
+#[instrument(skip(state))]
+pub async fn get_movie_stats(
+    State(state): State<StatisticsState>,
+) -> ApiResult<Json<LibraryStatistics>> {
+    let statistics = state
+        .statistics
+        .statistics()
+        .await
+        .map_err(ApiError::CoreError)?;
+    Ok(Json(statistics))
+}
+
+/// Create the library statistics router
+pub fn create_statistics_router(state: StatisticsState) -> Router {
+    Router::new()
+        .route("/v3/movie/stats", get(get_movie_stats))
+        .with_state(state)
+}
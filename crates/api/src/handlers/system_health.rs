@@ -0,0 +1,40 @@
+//! Health issue API handlers
+//!
+//! `/api/v3/health` lists what the registered health checks found wrong:
+//! unreachable indexers or download client, missing root folders, low disk
+//! space and recent import failures. Each issue is a warning or an error with
+//! a link to the wiki section explaining it; an empty list means all is well.
+//! Results are reused for a short while, so polling the endpoint is cheap.
+
+use crate::error::ApiResult;
+use axum::{extract::State, response::Json, routing::get, Router};
+use radarr_core::{HealthCheckRegistry, HealthIssue};
+use std::sync::Arc;
+use tracing::instrument;
+
+/// Health issue state
+#[derive(Clone)]
+pub struct SystemHealthState {
+    pub checks: Arc<HealthCheckRegistry>,
+}
+
+impl SystemHealthState {
+    pub fn new(checks: Arc<HealthCheckRegistry>) -> Self {
+        Self { checks }
+    }
+}
+
+/// GET /api/v3/health - Current health issues, errors first
+#[instrument(skip(state))]
+pub async fn list_health_issues(
+    State(state): State<SystemHealthState>,
+) -> ApiResult<Json<Vec<HealthIssue>>> {
+    Ok(Json(state.checks.issues().await))
+}
+
+/// Create the health issue router
+pub fn create_system_health_router(state: SystemHealthState) -> Router {
+    Router::new()
+        .route("/v3/health", get(list_health_issues))
+        .with_state(state)
+}
@@ -0,0 +1,283 @@
+//! Tag management API handlers
+//!
+//! Tags scope indexers and notifications to movies: one with tags is only
+//! used for movies sharing at least one of them. Labels are normalized to
+//! lowercase letters, digits and dashes; adding a label that already exists
+//! returns the existing tag. Tags still attached to anything cannot be
+//! deleted, and `/v3/tag/detail` shows what each tag is attached to.
+
+use crate::error::{ApiError, ApiResult};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    routing::get,
+    Router,
+};
+use radarr_core::{
+    domain::repositories::TagRepository,
+    models::{Tag, TagUsage},
+};
+use radarr_infrastructure::{DatabasePool, PostgresTagRepository};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{info, instrument};
+use uuid::Uuid;
+
+/// Tag management state
+#[derive(Clone)]
+pub struct TagState {
+    pub tag_repo: Arc<dyn TagRepository>,
+}
+
+impl TagState {
+    pub fn new(database_pool: DatabasePool) -> Self {
+        Self {
+            tag_repo: Arc::new(PostgresTagRepository::new(database_pool)),
+        }
+    }
+}
+
+/// Tag create/update request
+#[derive(Debug, Deserialize)]
+pub struct TagRequest {
+    pub label: String,
+}
+
+/// Tag as returned by the API
+#[derive(Debug, Serialize)]
+pub struct TagResource {
+    pub id: i32,
+    pub label: String,
+}
+
+impl From<Tag> for TagResource {
+    fn from(tag: Tag) -> Self {
+        Self {
+            id: tag.id,
+            label: tag.label,
+        }
+    }
+}
+
+/// Tag with everything it is attached to
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TagDetailsResource {
+    pub id: i32,
+    pub label: String,
+    pub movie_ids: Vec<Uuid>,
+    pub indexer_ids: Vec<i32>,
+    pub notification_ids: Vec<i32>,
+    pub import_list_ids: Vec<Uuid>,
+}
+
+impl TagDetailsResource {
+    fn new(tag: Tag, usage: TagUsage) -> Self {
+        Self {
+            id: tag.id,
+            label: tag.label,
+            movie_ids: usage.movie_ids,
+            indexer_ids: usage.indexer_ids,
+            notification_ids: usage.notification_ids,
+            import_list_ids: usage.import_list_ids,
+        }
+    }
+}
+
+/// Sort and deduplicate tag IDs sent for a movie, indexer or notification
+pub(crate) fn normalize_tag_ids(mut ids: Vec<i32>) -> Vec<i32> {
+    ids.sort_unstable();
+    ids.dedup();
+    ids
+}
+
+fn normalize_label(label: &str) -> ApiResult<String> {
+    Tag::normalize_label(label).ok_or_else(|| ApiError::ValidationError {
+        field: "label".to_string(),
+        message: "Tag label must contain letters or digits".to_string(),
+    })
+}
+
+async fn find_tag(state: &TagState, id: i32) -> ApiResult<Tag> {
+    state
+        .tag_repo
+        .find_by_id(id)
+        .await
+        .map_err(ApiError::CoreError)?
+        .ok_or_else(|| ApiError::NotFound {
+            resource: format!("tag with id {}", id),
+        })
+}
+
+async fn tag_details(state: &TagState, tag: Tag) -> ApiResult<TagDetailsResource> {
+    let usage = state
+        .tag_repo
+        .usage(tag.id)
+        .await
+        .map_err(ApiError::CoreError)?;
+    Ok(TagDetailsResource::new(tag, usage))
+}
+
+/// GET /api/v3/tag - List tags
+#[instrument(skip(state))]
+pub async fn list_tags(State(state): State<TagState>) -> ApiResult<Json<Vec<TagResource>>> {
+    let tags = state.tag_repo.list().await.map_err(ApiError::CoreError)?;
+    Ok(Json(tags.into_iter().map(Into::into).collect()))
+}
+
+/// GET /api/v3/tag/:id - Get tag by ID
+#[instrument(skip(state))]
+pub async fn get_tag(
+    State(state): State<TagState>,
+    Path(id): Path<i32>,
+) -> ApiResult<Json<TagResource>> {
+    Ok(Json(find_tag(&state, id).await?.into()))
+}
+
+/// POST /api/v3/tag - Create a tag, or return the one with the same label
+#[instrument(skip(state, request))]
+pub async fn create_tag(
+    State(state): State<TagState>,
+    Json(request): Json<TagRequest>,
+) -> ApiResult<(StatusCode, Json<TagResource>)> {
+    let label = normalize_label(&request.label)?;
+
+    if let Some(existing) = state
+        .tag_repo
+        .find_by_label(&label)
+        .await
+        .map_err(ApiError::CoreError)?
+    {
+        return Ok((StatusCode::OK, Json(existing.into())));
+    }
+
+    let tag = Tag::new(&label).expect("label is normalized");
+    let created = state
+        .tag_repo
+        .create(&tag)
+        .await
+        .map_err(ApiError::CoreError)?;
+
+    info!("Created tag '{}' with ID: {}", created.label, created.id);
+    Ok((StatusCode::CREATED, Json(created.into())))
+}
+
+/// PUT /api/v3/tag/:id - Rename a tag
+#[instrument(skip(state, request))]
+pub async fn update_tag(
+    State(state): State<TagState>,
+    Path(id): Path<i32>,
+    Json(request): Json<TagRequest>,
+) -> ApiResult<Json<TagResource>> {
+    let mut tag = find_tag(&state, id).await?;
+    let label = normalize_label(&request.label)?;
+
+    if let Some(other) = state
+        .tag_repo
+        .find_by_label(&label)
+        .await
+        .map_err(ApiError::CoreError)?
+    {
+        if other.id != id {
+            return Err(ApiError::Conflict {
+                resource: format!("tag '{}'", label),
+            });
+        }
+    }
+
+    tag.label = label;
+    let updated = state
+        .tag_repo
+        .update(&tag)
+        .await
+        .map_err(ApiError::CoreError)?;
+
+    info!("Renamed tag {} to '{}'", updated.id, updated.label);
+    Ok(Json(updated.into()))
+}
+
+/// DELETE /api/v3/tag/:id - Delete a tag that is no longer attached to anything
+#[instrument(skip(state))]
+pub async fn delete_tag(
+    State(state): State<TagState>,
+    Path(id): Path<i32>,
+) -> ApiResult<StatusCode> {
+    let tag = find_tag(&state, id).await?;
+    let usage = state
+        .tag_repo
+        .usage(id)
+        .await
+        .map_err(ApiError::CoreError)?;
+    if usage.in_use() {
+        return Err(ApiError::Conflict {
+            resource: format!("tag '{}' is still in use", tag.label),
+        });
+    }
+
+    state
+        .tag_repo
+        .delete(id)
+        .await
+        .map_err(ApiError::CoreError)?;
+
+    info!("Deleted tag '{}'", tag.label);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// GET /api/v3/tag/detail - List tags with what they are attached to
+#[instrument(skip(state))]
+pub async fn list_tag_details(
+    State(state): State<TagState>,
+) -> ApiResult<Json<Vec<TagDetailsResource>>> {
+    let tags = state.tag_repo.list().await.map_err(ApiError::CoreError)?;
+
+    let mut details = Vec::with_capacity(tags.len());
+    for tag in tags {
+        details.push(tag_details(&state, tag).await?);
+    }
+    Ok(Json(details))
+}
+
+/// GET /api/v3/tag/detail/:id - Get a tag with what it is attached to
+#[instrument(skip(state))]
+pub async fn get_tag_details(
+    State(state): State<TagState>,
+    Path(id): Path<i32>,
+) -> ApiResult<Json<TagDetailsResource>> {
+    let tag = find_tag(&state, id).await?;
+    Ok(Json(tag_details(&state, tag).await?))
+}
+
+/// Create tag management router
+pub fn create_tag_router(state: TagState) -> Router {
+    Router::new()
+        .route("/v3/tag", get(list_tags).post(create_tag))
+        .route("/v3/tag/detail", get(list_tag_details))
+        .route("/v3/tag/detail/:id", get(get_tag_details))
+        .route(
+            "/v3/tag/:id",
+            get(get_tag).put(update_tag).delete(delete_tag),
+        )
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_tag_ids() {
+        assert_eq!(normalize_tag_ids(vec![3, 1, 3, 2]), vec![1, 2, 3]);
+        assert!(normalize_tag_ids(Vec::new()).is_empty());
+    }
+
+    #[test]
+    fn test_normalize_label_rejects_punctuation_only() {
+        assert_eq!(normalize_label("Kids Movies").unwrap(), "kids-movies");
+        assert!(matches!(
+            normalize_label("!!!"),
+            Err(ApiError::ValidationError { .. })
+        ));
+    }
+}
@@ -0,0 +1,148 @@
+//! Scheduled task API handlers
+//!
+//! `/api/v3/system/task` lists the recurring tasks with their interval and
+//! last/next execution, changes how often a task runs and runs one now.
+
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::commands::CommandResource;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use radarr_core::jobs::{ScheduledTask, TaskScheduler};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, instrument};
+
+/// Scheduled task state
+#[derive(Clone)]
+pub struct TaskState {
+    pub scheduler: Arc<TaskScheduler>,
+}
+
+impl TaskState {
+    pub fn new(scheduler: Arc<TaskScheduler>) -> Self {
+        Self { scheduler }
+    }
+}
+
+/// Scheduled task response
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskResource {
+    pub id: i32,
+    pub name: String,
+    pub task_name: String,
+    /// Interval in minutes
+    pub interval: u64,
+    pub last_execution: Option<String>,
+    pub last_start_time: Option<String>,
+    pub next_execution: String,
+    /// Run time of the last run as `HH:MM:SS`, once it has started
+    pub last_duration: Option<String>,
+}
+
+/// Task update request
+#[derive(Debug, Deserialize)]
+pub struct TaskUpdateRequest {
+    /// Interval in minutes
+    pub interval: u64,
+}
+
+/// Build the resource for `task`, reading its last run from the queue
+async fn task_resource(scheduler: &TaskScheduler, task: ScheduledTask) -> TaskResource {
+    let last_command = match task.last_command_id {
+        Some(id) => scheduler.queue().get(id).await,
+        None => None,
+    };
+    let last_resource = last_command.map(CommandResource::from);
+
+    TaskResource {
+        id: task.id,
+        name: task.name.to_string(),
+        task_name: task.name.to_string(),
+        interval: task.interval.as_secs() / 60,
+        last_execution: last_resource
+            .as_ref()
+            .and_then(|command| command.ended.clone())
+            .or_else(|| task.last_execution.map(|t| t.to_rfc3339())),
+        last_start_time: last_resource
+            .as_ref()
+            .and_then(|command| command.started.clone()),
+        next_execution: task.next_execution.to_rfc3339(),
+        last_duration: last_resource.and_then(|command| command.duration),
+    }
+}
+
+fn find_task(scheduler: &TaskScheduler, id: i32) -> ApiResult<ScheduledTask> {
+    scheduler.get(id).ok_or_else(|| ApiError::NotFound {
+        resource: format!("task {}", id),
+    })
+}
+
+/// GET /api/v3/system/task - List scheduled tasks
+#[instrument(skip(state))]
+pub async fn list_tasks(State(state): State<TaskState>) -> ApiResult<Json<Vec<TaskResource>>> {
+    let mut tasks = Vec::new();
+    for task in state.scheduler.list() {
+        tasks.push(task_resource(&state.scheduler, task).await);
+    }
+    Ok(Json(tasks))
+}
+
+/// GET /api/v3/system/task/:id - Get a scheduled task
+#[instrument(skip(state))]
+pub async fn get_task(
+    State(state): State<TaskState>,
+    Path(id): Path<i32>,
+) -> ApiResult<Json<TaskResource>> {
+    let task = find_task(&state.scheduler, id)?;
+    Ok(Json(task_resource(&state.scheduler, task).await))
+}
+
+/// PUT /api/v3/system/task/:id - Change how often a task runs
+#[instrument(skip(state))]
+pub async fn update_task(
+    State(state): State<TaskState>,
+    Path(id): Path<i32>,
+    Json(request): Json<TaskUpdateRequest>,
+) -> ApiResult<Json<TaskResource>> {
+    find_task(&state.scheduler, id)?;
+    let interval = Duration::from_secs(request.interval.saturating_mul(60));
+    let task = state.scheduler.reschedule(id, interval)?;
+
+    info!(
+        "Task {} now runs every {} minutes",
+        task.name, request.interval
+    );
+    Ok(Json(task_resource(&state.scheduler, task).await))
+}
+
+/// POST /api/v3/system/task/:id/execute - Run a task now
+#[instrument(skip(state))]
+pub async fn execute_task(
+    State(state): State<TaskState>,
+    Path(id): Path<i32>,
+) -> ApiResult<(StatusCode, Json<CommandResource>)> {
+    find_task(&state.scheduler, id)?;
+    let command = state.scheduler.trigger(id).await?;
+
+    info!(
+        "Task {} run manually as command {}",
+        command.name, command.id
+    );
+    Ok((StatusCode::CREATED, Json(CommandResource::from(command))))
+}
+
+/// Create the scheduled task router
+pub fn create_task_router(state: TaskState) -> Router {
+    Router::new()
+        .route("/v3/system/task", get(list_tasks))
+        .route("/v3/system/task/:id", get(get_task).put(update_task))
+        .route("/v3/system/task/:id/execute", post(execute_task))
+        .with_state(state)
+}
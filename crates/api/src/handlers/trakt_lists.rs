@@ -0,0 +1,184 @@
+//! Trakt import list API handlers
+//!
+//! Authorizes a Trakt account with the OAuth device flow and previews Trakt
+//! lists before they are added as import lists. The client starts the flow,
+//! shows the user code and verification URL, then polls until the user has
+//! approved the device; the token is stored and refreshed server-side.
+
+use crate::error::{ApiError, ApiResult};
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use radarr_infrastructure::{
+    lists::common::ListParser, DatabasePool, ListItem, PostgresStreamingCache,
+    TraktDeviceAuthStatus, TraktListKind, TraktListProvider,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{info, instrument};
+
+/// Trakt import list state
+#[derive(Clone)]
+pub struct TraktListState {
+    /// `None` when no Trakt client credentials are configured
+    pub provider: Option<Arc<TraktListProvider>>,
+}
+
+impl TraktListState {
+    /// Build the provider from `TRAKT_CLIENT_ID` and `TRAKT_CLIENT_SECRET`
+    pub fn new(database_pool: DatabasePool) -> Self {
+        let provider = match (
+            std::env::var("TRAKT_CLIENT_ID"),
+            std::env::var("TRAKT_CLIENT_SECRET"),
+        ) {
+            (Ok(client_id), Ok(client_secret)) => Some(Arc::new(TraktListProvider::new(
+                client_id,
+                client_secret,
+                Arc::new(PostgresStreamingCache::new(database_pool)),
+            ))),
+            _ => None,
+        };
+        Self { provider }
+    }
+
+    fn provider(&self) -> ApiResult<&TraktListProvider> {
+        self.provider
+            .as_deref()
+            .ok_or_else(|| ApiError::ValidationError {
+                field: "TRAKT_CLIENT_ID".to_string(),
+                message: "Trakt client credentials are not configured".to_string(),
+            })
+    }
+}
+
+/// Device code the user enters at the verification URL
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraktDeviceAuthResource {
+    pub device_code: String,
+    pub user_code: String,
+    pub verification_url: String,
+    pub expires_in: i32,
+    /// Seconds to wait between polls
+    pub interval: i32,
+}
+
+/// Device authorization poll request
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraktDeviceAuthPoll {
+    pub device_code: String,
+}
+
+/// Trakt authorization status
+#[derive(Debug, Serialize)]
+pub struct TraktAuthStatusResource {
+    pub configured: bool,
+    pub authorized: bool,
+}
+
+/// Trakt list preview query
+#[derive(Debug, Deserialize)]
+pub struct TraktListQuery {
+    /// `watchlist`, `popular`, `anticipated`, `<user>/<list>` or a trakt.tv URL
+    pub list: String,
+}
+
+/// GET /api/v3/importlist/trakt/auth - Whether a Trakt account is authorized
+#[instrument(skip(state))]
+pub async fn get_trakt_auth_status(
+    State(state): State<TraktListState>,
+) -> ApiResult<Json<TraktAuthStatusResource>> {
+    let authorized = match &state.provider {
+        Some(provider) => provider.is_authorized().await?,
+        None => false,
+    };
+    Ok(Json(TraktAuthStatusResource {
+        configured: state.provider.is_some(),
+        authorized,
+    }))
+}
+
+/// POST /api/v3/importlist/trakt/auth - Start the device flow
+#[instrument(skip(state))]
+pub async fn start_trakt_auth(
+    State(state): State<TraktListState>,
+) -> ApiResult<Json<TraktDeviceAuthResource>> {
+    let code = state.provider()?.start_device_auth().await?;
+    info!(
+        "Started Trakt device authorization, code expires in {}s",
+        code.expires_in
+    );
+    Ok(Json(TraktDeviceAuthResource {
+        device_code: code.device_code,
+        user_code: code.user_code,
+        verification_url: code.verification_url,
+        expires_in: code.expires_in,
+        interval: code.interval,
+    }))
+}
+
+/// POST /api/v3/importlist/trakt/auth/poll - Check whether the user approved
+/// the device; 202 while still pending
+#[instrument(skip(state, request))]
+pub async fn poll_trakt_auth(
+    State(state): State<TraktListState>,
+    Json(request): Json<TraktDeviceAuthPoll>,
+) -> ApiResult<(StatusCode, Json<TraktAuthStatusResource>)> {
+    let status = state
+        .provider()?
+        .complete_device_auth(&request.device_code)
+        .await?;
+    let (code, authorized) = match status {
+        TraktDeviceAuthStatus::Authorized => (StatusCode::OK, true),
+        TraktDeviceAuthStatus::Pending => (StatusCode::ACCEPTED, false),
+    };
+    Ok((
+        code,
+        Json(TraktAuthStatusResource {
+            configured: true,
+            authorized,
+        }),
+    ))
+}
+
+/// GET /api/v3/importlist/trakt/preview - Movies a Trakt list would import
+#[instrument(skip(state))]
+pub async fn preview_trakt_list(
+    State(state): State<TraktListState>,
+    Query(query): Query<TraktListQuery>,
+) -> ApiResult<Json<Vec<ListItem>>> {
+    let provider = state.provider()?;
+    if TraktListKind::parse(&query.list).is_none() {
+        return Err(ApiError::ValidationError {
+            field: "list".to_string(),
+            message: format!("Not a Trakt list: {}", query.list),
+        });
+    }
+
+    let items =
+        provider
+            .parse_list(&query.list)
+            .await
+            .map_err(|e| ApiError::ExternalServiceError {
+                service: "trakt".to_string(),
+                error: e.to_string(),
+            })?;
+    Ok(Json(items))
+}
+
+/// Create the Trakt import list router
+pub fn create_trakt_list_router(state: TraktListState) -> Router {
+    Router::new()
+        .route(
+            "/v3/importlist/trakt/auth",
+            get(get_trakt_auth_status).post(start_trakt_auth),
+        )
+        .route("/v3/importlist/trakt/auth/poll", post(poll_trakt_auth))
+        .route("/v3/importlist/trakt/preview", get(preview_trakt_list))
+        .with_state(state)
+}
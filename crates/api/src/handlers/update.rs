@@ -0,0 +1,99 @@
+//! Update API handlers
+//!
+//! `/api/v3/update` lists the running version and the releases newer than it
+//! found by the latest `ApplicationUpdateCheck`, newest first. With update
+//! checks turned off only the running version is listed.
+
+use crate::error::ApiResult;
+use axum::{extract::State, response::Json, routing::get, Router};
+use chrono::{DateTime, Utc};
+use radarr_infrastructure::{updates::CURRENT_VERSION, UpdateChecker, UpdateRelease};
+use serde::Serialize;
+use std::sync::Arc;
+use tracing::instrument;
+
+/// Update state
+#[derive(Clone)]
+pub struct UpdateState {
+    pub updates: Arc<UpdateChecker>,
+}
+
+impl UpdateState {
+    pub fn new(updates: Arc<UpdateChecker>) -> Self {
+        Self { updates }
+    }
+}
+
+/// A release as returned by the API
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateResource {
+    pub version: String,
+    pub release_date: Option<DateTime<Utc>>,
+    pub url: Option<String>,
+    /// Whether this is the running version
+    pub installed: bool,
+    /// Always false: updates are installed by whatever deployed the instance
+    pub installable: bool,
+    /// Whether this is the newest version known
+    pub latest: bool,
+    pub changes: Option<String>,
+}
+
+impl UpdateResource {
+    fn release(release: UpdateRelease, latest: bool) -> Self {
+        Self {
+            version: release.version,
+            release_date: release.release_date,
+            url: Some(release.url),
+            installed: false,
+            installable: false,
+            latest,
+            changes: release.changes,
+        }
+    }
+
+    fn installed(latest: bool) -> Self {
+        Self {
+            version: CURRENT_VERSION.to_string(),
+            release_date: None,
+            url: None,
+            installed: true,
+            installable: false,
+            latest,
+            changes: None,
+        }
+    }
+}
+
+/// GET /api/v3/update - Running version and newer releases
+///
+/// Checks right away when update checks are on but none has run yet.
+#[instrument(skip(state))]
+pub async fn list_updates(
+    State(state): State<UpdateState>,
+) -> ApiResult<Json<Vec<UpdateResource>>> {
+    let releases = if !state.updates.config().enabled {
+        Vec::new()
+    } else if let Some(status) = state.updates.status() {
+        status.releases
+    } else {
+        state.updates.check().await?.releases
+    };
+
+    let installed_is_latest = releases.is_empty();
+    let mut updates: Vec<_> = releases
+        .into_iter()
+        .enumerate()
+        .map(|(index, release)| UpdateResource::release(release, index == 0))
+        .collect();
+    updates.push(UpdateResource::installed(installed_is_latest));
+    Ok(Json(updates))
+}
+
+/// Create the update router
+pub fn create_update_router(state: UpdateState) -> Router {
+    Router::new()
+        .route("/v3/update", get(list_updates))
+        .with_state(state)
+}
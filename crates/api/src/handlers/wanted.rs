@@ -0,0 +1,417 @@
+//! Wanted API handlers
+//!
+//! `/api/v3/wanted/missing` lists movies without a file and
+//! `/api/v3/wanted/cutoff` monitored movies whose file is below their
+//! quality profile's cutoff, paged and sorted so large libraries can be
+//! worked through progressively. Searches are queued as the
+//! `MissingMoviesSearch` and `CutoffUnmetSearch` commands.
+
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::media_cover::MediaCoverResource;
+use crate::models::PaginatedResponse;
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use radarr_core::{
+    domain::repositories::{MovieFileRepository, MovieRepository, QualityProfileRepository},
+    jobs::{cutoff_unmet, CutoffUnmet},
+    models::{MinimumAvailability, Movie, ReleaseType, QUALITY_DEFINITIONS},
+    CommandName, CommandQueue, CommandTrigger,
+};
+use radarr_infrastructure::{
+    DatabasePool, PostgresMovieFileRepository, PostgresMovieRepository,
+    PostgresQualityProfileRepository,
+};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::sync::Arc;
+use tracing::{info, instrument};
+use uuid::Uuid;
+
+/// Largest page a client may request
+const MAX_WANTED_PAGE_SIZE: u32 = 1000;
+
+/// Wanted state
+#[derive(Clone)]
+pub struct WantedState {
+    pub movie_repo: Arc<PostgresMovieRepository>,
+    pub movie_file_repo: Arc<dyn MovieFileRepository>,
+    pub quality_profile_repo: Arc<dyn QualityProfileRepository>,
+    pub command_queue: Arc<CommandQueue>,
+}
+
+impl WantedState {
+    pub fn new(database_pool: DatabasePool, command_queue: Arc<CommandQueue>) -> Self {
+        Self {
+            movie_repo: Arc::new(PostgresMovieRepository::new(database_pool.clone())),
+            movie_file_repo: Arc::new(PostgresMovieFileRepository::new(database_pool.clone())),
+            quality_profile_repo: Arc::new(PostgresQualityProfileRepository::new(database_pool)),
+            command_queue,
+        }
+    }
+}
+
+/// Query parameters for wanted listings
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WantedQueryParams {
+    pub page: Option<u32>,
+    pub page_size: Option<u32>,
+    /// `title` (default), `year`, `added`, `inCinemas` or `lastSearchTime`
+    pub sort_key: Option<String>,
+    /// `ascending` (default) or `descending`
+    pub sort_direction: Option<String>,
+    /// Missing movies only: list monitored (default) or unmonitored ones
+    pub monitored: Option<bool>,
+}
+
+impl WantedQueryParams {
+    fn page(&self) -> u32 {
+        self.page.unwrap_or(1).max(1)
+    }
+
+    fn page_size(&self) -> u32 {
+        self.page_size.unwrap_or(20).clamp(1, MAX_WANTED_PAGE_SIZE)
+    }
+
+    /// Ordering of two movies by the requested sort key and direction
+    fn comparator(&self) -> ApiResult<impl Fn(&Movie, &Movie) -> Ordering> {
+        let key = self.sort_key.as_deref().unwrap_or("title");
+        let compare: fn(&Movie, &Movie) -> Ordering = match key {
+            "title" => |a, b| a.title.to_lowercase().cmp(&b.title.to_lowercase()),
+            "year" => |a, b| a.year.cmp(&b.year),
+            "added" => |a, b| a.created_at.cmp(&b.created_at),
+            // Movies without a known date sort last
+            "inCinemas" => |a, b| {
+                let date = |movie: &Movie| {
+                    let date = movie.release_date(ReleaseType::InCinemas);
+                    (date.is_none(), date)
+                };
+                date(a).cmp(&date(b))
+            },
+            "lastSearchTime" => |a, b| a.last_search_time.cmp(&b.last_search_time),
+            _ => {
+                return Err(ApiError::ValidationError {
+                    field: "sortKey".to_string(),
+                    message: format!(
+                        "Unknown sort key '{}', expected one of: title, year, added, \
+                         inCinemas, lastSearchTime",
+                        key
+                    ),
+                })
+            }
+        };
+        let descending = match self.sort_direction.as_deref() {
+            None | Some("ascending") => false,
+            Some("descending") => true,
+            Some(direction) => {
+                return Err(ApiError::ValidationError {
+                    field: "sortDirection".to_string(),
+                    message: format!(
+                        "Unknown sort direction '{}', expected ascending or descending",
+                        direction
+                    ),
+                })
+            }
+        };
+
+        Ok(move |a: &Movie, b: &Movie| {
+            let ordering = compare(a, b).then_with(|| a.id.cmp(&b.id));
+            if descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        })
+    }
+
+    /// Sort `items` by their movie and return the requested page
+    fn paginate<T>(
+        &self,
+        mut items: Vec<T>,
+        movie: impl Fn(&T) -> &Movie,
+    ) -> ApiResult<PaginatedResponse<T>> {
+        let compare = self.comparator()?;
+        items.sort_by(|a, b| compare(movie(a), movie(b)));
+
+        let total = items.len() as i64;
+        let (page, page_size) = (self.page(), self.page_size());
+        let records = items
+            .into_iter()
+            .skip((page as usize - 1) * page_size as usize)
+            .take(page_size as usize)
+            .collect();
+        Ok(PaginatedResponse::new(page, page_size, total, records))
+    }
+}
+
+/// Quality of a movie file
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WantedQualityResource {
+    pub id: i32,
+    pub name: String,
+}
+
+/// A movie below its quality cutoff, as returned by the API
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CutoffUnmetResource {
+    pub movie_id: Uuid,
+    pub title: String,
+    pub year: Option<i32>,
+    pub movie_file_id: Uuid,
+    pub relative_path: String,
+    pub quality: WantedQualityResource,
+    pub quality_profile_id: i32,
+    /// Cutoff quality (or group) ID of the profile
+    pub cutoff: i32,
+    pub custom_format_score: i32,
+    pub added: chrono::DateTime<chrono::Utc>,
+    pub last_search_time: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl From<CutoffUnmet> for CutoffUnmetResource {
+    fn from(entry: CutoffUnmet) -> Self {
+        let name = QUALITY_DEFINITIONS
+            .iter()
+            .find(|(id, ..)| *id == entry.quality_id)
+            .map(|(_, name, ..)| name.to_string())
+            .unwrap_or_else(|| "Unknown".to_string());
+        Self {
+            movie_id: entry.movie.id,
+            title: entry.movie.title,
+            year: entry.movie.year,
+            movie_file_id: entry.file.id,
+            relative_path: entry.file.relative_path,
+            quality: WantedQualityResource {
+                id: entry.quality_id,
+                name,
+            },
+            quality_profile_id: entry.profile_id,
+            cutoff: entry.cutoff_quality_id,
+            custom_format_score: entry.file.custom_format_score,
+            added: entry.movie.created_at,
+            last_search_time: entry.movie.last_search_time,
+        }
+    }
+}
+
+/// A movie without a file, as returned by the API
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MissingMovieResource {
+    pub movie_id: Uuid,
+    pub title: String,
+    pub year: Option<i32>,
+    pub tmdb_id: i32,
+    pub imdb_id: Option<String>,
+    pub monitored: bool,
+    pub quality_profile_id: Option<i32>,
+    pub minimum_availability: MinimumAvailability,
+    /// Whether the movie has reached its minimum availability, so searches
+    /// may find it
+    pub is_available: bool,
+    pub in_cinemas: Option<chrono::NaiveDate>,
+    pub digital_release: Option<chrono::NaiveDate>,
+    pub physical_release: Option<chrono::NaiveDate>,
+    pub added: chrono::DateTime<chrono::Utc>,
+    pub last_search_time: Option<chrono::DateTime<chrono::Utc>>,
+    pub images: Vec<MediaCoverResource>,
+}
+
+impl From<Movie> for MissingMovieResource {
+    fn from(movie: Movie) -> Self {
+        Self {
+            is_available: movie.is_available(chrono::Utc::now()),
+            in_cinemas: movie.release_date(ReleaseType::InCinemas),
+            digital_release: movie.release_date(ReleaseType::DigitalRelease),
+            physical_release: movie.release_date(ReleaseType::PhysicalRelease),
+            images: MediaCoverResource::for_movie(&movie),
+            movie_id: movie.id,
+            title: movie.title,
+            year: movie.year,
+            tmdb_id: movie.tmdb_id,
+            imdb_id: movie.imdb_id,
+            monitored: movie.monitored,
+            quality_profile_id: movie.quality_profile_id,
+            minimum_availability: movie.minimum_availability,
+            added: movie.created_at,
+            last_search_time: movie.last_search_time,
+        }
+    }
+}
+
+/// GET /api/v3/wanted/missing - Page through movies without a file
+#[instrument(skip(state))]
+pub async fn list_missing(
+    State(state): State<WantedState>,
+    Query(params): Query<WantedQueryParams>,
+) -> ApiResult<Json<PaginatedResponse<MissingMovieResource>>> {
+    let movies = state
+        .movie_repo
+        .find_missing(params.monitored.unwrap_or(true))
+        .await
+        .map_err(ApiError::CoreError)?;
+
+    let page = params.paginate(movies, |movie| movie)?;
+    Ok(Json(PaginatedResponse::new(
+        page.page,
+        page.page_size,
+        page.total_records,
+        page.records
+            .into_iter()
+            .map(MissingMovieResource::from)
+            .collect(),
+    )))
+}
+
+/// POST /api/v3/wanted/missing/search - Queue a MissingMoviesSearch command
+#[instrument(skip(state))]
+pub async fn search_missing(
+    State(state): State<WantedState>,
+) -> ApiResult<(StatusCode, Json<crate::handlers::commands::CommandResource>)> {
+    let command = state
+        .command_queue
+        .enqueue(
+            CommandName::MissingMoviesSearch,
+            serde_json::json!({}),
+            CommandTrigger::Manual,
+        )
+        .await?;
+    info!("Queued missing movie search ({})", command.id);
+
+    Ok((StatusCode::CREATED, Json(command.into())))
+}
+
+/// GET /api/v3/wanted/cutoff - Page through movies below their cutoff
+#[instrument(skip(state))]
+pub async fn list_cutoff_unmet(
+    State(state): State<WantedState>,
+    Query(params): Query<WantedQueryParams>,
+) -> ApiResult<Json<PaginatedResponse<CutoffUnmetResource>>> {
+    let movies = state
+        .movie_repo
+        .find_monitored()
+        .await
+        .map_err(ApiError::CoreError)?;
+    let files = state
+        .movie_file_repo
+        .list(None)
+        .await
+        .map_err(ApiError::CoreError)?;
+    let profiles = state
+        .quality_profile_repo
+        .list()
+        .await
+        .map_err(ApiError::CoreError)?;
+    let default_profile = state
+        .quality_profile_repo
+        .get_default()
+        .await
+        .map_err(ApiError::CoreError)?;
+
+    let unmet = cutoff_unmet(movies, files, &profiles, default_profile.as_ref());
+    let page = params.paginate(unmet, |entry| &entry.movie)?;
+    Ok(Json(PaginatedResponse::new(
+        page.page,
+        page.page_size,
+        page.total_records,
+        page.records
+            .into_iter()
+            .map(CutoffUnmetResource::from)
+            .collect(),
+    )))
+}
+
+/// POST /api/v3/wanted/cutoff/search - Queue a CutoffUnmetSearch command
+#[instrument(skip(state))]
+pub async fn search_cutoff_unmet(
+    State(state): State<WantedState>,
+) -> ApiResult<(StatusCode, Json<crate::handlers::commands::CommandResource>)> {
+    let command = state
+        .command_queue
+        .enqueue(
+            CommandName::CutoffUnmetSearch,
+            serde_json::json!({}),
+            CommandTrigger::Manual,
+        )
+        .await?;
+    info!("Queued cutoff unmet search ({})", command.id);
+
+    Ok((StatusCode::CREATED, Json(command.into())))
+}
+
+/// Create wanted router
+pub fn create_wanted_router(state: WantedState) -> Router {
+    Router::new()
+        .route("/v3/wanted/missing", get(list_missing))
+        .route("/v3/wanted/missing/search", post(search_missing))
+        .route("/v3/wanted/cutoff", get(list_cutoff_unmet))
+        .route("/v3/wanted/cutoff/search", post(search_cutoff_unmet))
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn movie(title: &str, year: i32) -> Movie {
+        let mut movie = Movie::new(year, title.to_string());
+        movie.year = Some(year);
+        movie
+    }
+
+    #[test]
+    fn test_paginate_sorts_and_pages() {
+        let movies = vec![movie("b", 2001), movie("C", 1999), movie("a", 2010)];
+
+        let params = WantedQueryParams {
+            page: Some(1),
+            page_size: Some(2),
+            ..Default::default()
+        };
+        let page = params.paginate(movies.clone(), |movie| movie).unwrap();
+        let titles: Vec<&str> = page.records.iter().map(|m| m.title.as_str()).collect();
+        assert_eq!(titles, vec!["a", "b"]);
+        assert_eq!((page.total_records, page.total_pages), (3, 2));
+
+        let params = WantedQueryParams {
+            page: Some(2),
+            page_size: Some(2),
+            sort_key: Some("year".to_string()),
+            sort_direction: Some("descending".to_string()),
+            monitored: None,
+        };
+        let page = params.paginate(movies.clone(), |movie| movie).unwrap();
+        let titles: Vec<&str> = page.records.iter().map(|m| m.title.as_str()).collect();
+        assert_eq!(titles, vec!["C"]);
+
+        let params = WantedQueryParams {
+            sort_key: Some("rating".to_string()),
+            ..Default::default()
+        };
+        assert!(params.paginate(movies, |movie| movie).is_err());
+    }
+
+    #[test]
+    fn test_sort_by_in_cinemas_puts_unknown_dates_last() {
+        let mut released = movie("released", 1999);
+        released.metadata = serde_json::json!({ "tmdb": { "release_date": "1999-10-15" } });
+        let mut upcoming = movie("upcoming", 2030);
+        upcoming.metadata = serde_json::json!({ "tmdb": { "release_date": "2030-01-01" } });
+        let movies = vec![movie("unknown", 2030), upcoming, released];
+
+        let params = WantedQueryParams {
+            sort_key: Some("inCinemas".to_string()),
+            ..Default::default()
+        };
+        let page = params.paginate(movies, |movie| movie).unwrap();
+        let titles: Vec<&str> = page.records.iter().map(|m| m.title.as_str()).collect();
+        assert_eq!(titles, vec!["released", "upcoming", "unknown"]);
+    }
+}
@@ -4,24 +4,34 @@
 //! implementing the Radarr v3 API specification with proper error handling,
 //! pagination, and integration with the domain services.
 
+pub mod api_keys;
+pub mod auth;
 pub mod error;
 pub mod extractors;
 pub mod handlers;
 pub mod metrics;
 pub mod middleware;
 pub mod models;
+pub mod rate_limit;
 pub mod routes;
 pub mod security;
+pub mod sessions;
+pub mod share_tokens;
 pub mod simple_api;
 pub mod telemetry;
 pub mod tracing;
 pub mod validation;
 
 // Re-export main types
-pub use error::{ApiError, ApiResult};
+pub use api_keys::ApiKeyRegistry;
+pub use auth::{hash_api_key, ApiKeyVerifier, AuthOutcome};
+pub use error::{core_error_retry_after, core_error_status, ApiError, ApiResult};
 pub use metrics::MetricsCollector;
 pub use models::*;
+pub use rate_limit::{RateLimiter, RouteClass};
 pub use security::{apply_security, configure_cors, security_headers, SecurityConfig};
+pub use sessions::SessionStore;
+pub use share_tokens::{ShareAuthOutcome, ShareTokenRegistry};
 pub use simple_api::{create_simple_api_router, SimpleApiState};
 pub use telemetry::{init_telemetry, shutdown_telemetry, ServiceInfo, TelemetryConfig};
 pub use tracing::{instrument_business_operation, simple_tracing_middleware, DistributedTracing};
@@ -10,6 +10,8 @@ use prometheus::{
     register_counter_vec, register_gauge_vec, register_histogram_vec, CounterVec,
     GaugeVec, HistogramVec, TextEncoder,
 };
+use radarr_import::{ImportFailureReason, ImportMetrics};
+use radarr_infrastructure::{CacheMetrics, PoolMetrics};
 use std::{
     sync::Arc,
     time::{Duration, Instant},
@@ -32,6 +34,26 @@ pub struct MetricsCollector {
     import_failure_total: CounterVec,
     queue_length: GaugeVec,
     search_duration_seconds: HistogramVec,
+
+    // Cache lookups of TMDB and streaming data
+    cache_hits_total: CounterVec,
+    cache_misses_total: CounterVec,
+    cache_evictions_total: CounterVec,
+
+    // Import pipeline, labeled by root folder
+    import_files_scanned_total: CounterVec,
+    import_media_files_total: CounterVec,
+    import_analysis_confidence: HistogramVec,
+    import_transfers_total: CounterVec,
+    import_transfer_bytes_total: CounterVec,
+    import_duration_seconds: HistogramVec,
+    import_failures_by_reason_total: CounterVec,
+
+    // Database connection pools, labeled by pool
+    db_pool_connections: GaugeVec,
+    db_pool_max_connections: GaugeVec,
+    db_pool_acquire_duration_seconds: HistogramVec,
+    db_pool_acquire_failures_total: CounterVec,
 }
 
 impl MetricsCollector {
@@ -103,6 +125,93 @@ impl MetricsCollector {
             vec![0.1, 0.5, 1.0, 2.0, 5.0, 10.0, 30.0, 60.0]
         )?;
 
+        let cache_hits_total = register_counter_vec!(
+            "radarr_cache_hits_total",
+            "Total number of cache lookups answered by a cache layer",
+            &["cache", "backend"]
+        )?;
+
+        let cache_misses_total = register_counter_vec!(
+            "radarr_cache_misses_total",
+            "Total number of cache lookups a cache layer could not answer",
+            &["cache", "backend"]
+        )?;
+
+        let cache_evictions_total = register_counter_vec!(
+            "radarr_cache_evictions_total",
+            "Total number of cache entries evicted to make room",
+            &["backend"]
+        )?;
+
+        let import_files_scanned_total = register_counter_vec!(
+            "radarr_import_files_scanned_total",
+            "Total number of files examined while scanning downloads",
+            &["root_folder"]
+        )?;
+
+        let import_media_files_total = register_counter_vec!(
+            "radarr_import_media_files_total",
+            "Total number of media files found while scanning downloads",
+            &["root_folder"]
+        )?;
+
+        let import_analysis_confidence = register_histogram_vec!(
+            "radarr_import_analysis_confidence",
+            "Confidence with which scanned files were recognized",
+            &["root_folder"],
+            vec![0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9, 1.0]
+        )?;
+
+        let import_transfers_total = register_counter_vec!(
+            "radarr_import_transfers_total",
+            "Total number of imported files by transfer method",
+            &["root_folder", "method"]
+        )?;
+
+        let import_transfer_bytes_total = register_counter_vec!(
+            "radarr_import_transfer_bytes_total",
+            "Total size of imported files by transfer method",
+            &["root_folder", "method"]
+        )?;
+
+        let import_duration_seconds = register_histogram_vec!(
+            "radarr_import_duration_seconds",
+            "Duration of file imports in seconds",
+            &["root_folder", "status"],
+            vec![0.01, 0.05, 0.1, 0.5, 1.0, 5.0, 15.0, 60.0, 300.0, 900.0]
+        )?;
+
+        let import_failures_by_reason_total = register_counter_vec!(
+            "radarr_import_failures_by_reason_total",
+            "Total number of files that failed to import by reason",
+            &["root_folder", "reason"]
+        )?;
+
+        let db_pool_connections = register_gauge_vec!(
+            "radarr_db_pool_connections",
+            "Database pool connections by state",
+            &["pool", "state"]
+        )?;
+
+        let db_pool_max_connections = register_gauge_vec!(
+            "radarr_db_pool_max_connections",
+            "Maximum number of connections in the database pool",
+            &["pool"]
+        )?;
+
+        let db_pool_acquire_duration_seconds = register_histogram_vec!(
+            "radarr_db_pool_acquire_duration_seconds",
+            "Time spent waiting for a database connection in seconds",
+            &["pool"],
+            vec![0.001, 0.005, 0.01, 0.05, 0.1, 0.25, 0.5, 1.0, 5.0, 30.0]
+        )?;
+
+        let db_pool_acquire_failures_total = register_counter_vec!(
+            "radarr_db_pool_acquire_failures_total",
+            "Total number of failed attempts to get a database connection",
+            &["pool"]
+        )?;
+
         Ok(Self {
             prom_http_requests,
             prom_http_duration,
@@ -115,6 +224,20 @@ impl MetricsCollector {
             import_failure_total,
             queue_length,
             search_duration_seconds,
+            cache_hits_total,
+            cache_misses_total,
+            cache_evictions_total,
+            import_files_scanned_total,
+            import_media_files_total,
+            import_analysis_confidence,
+            import_transfers_total,
+            import_transfer_bytes_total,
+            import_duration_seconds,
+            import_failures_by_reason_total,
+            db_pool_connections,
+            db_pool_max_connections,
+            db_pool_acquire_duration_seconds,
+            db_pool_acquire_failures_total,
         })
     }
 
@@ -258,6 +381,98 @@ impl MetricsCollector {
     }
 }
 
+impl CacheMetrics for MetricsCollector {
+    fn record_hit(&self, cache: &str, backend: &str) {
+        self.cache_hits_total
+            .with_label_values(&[cache, backend])
+            .inc();
+    }
+
+    fn record_miss(&self, cache: &str, backend: &str) {
+        self.cache_misses_total
+            .with_label_values(&[cache, backend])
+            .inc();
+    }
+
+    fn record_evictions(&self, backend: &str, count: u64) {
+        self.cache_evictions_total
+            .with_label_values(&[backend])
+            .inc_by(count as f64);
+    }
+}
+
+impl ImportMetrics for MetricsCollector {
+    fn record_scan(&self, root_folder: &str, files_examined: usize, media_files: usize) {
+        self.import_files_scanned_total
+            .with_label_values(&[root_folder])
+            .inc_by(files_examined as f64);
+        self.import_media_files_total
+            .with_label_values(&[root_folder])
+            .inc_by(media_files as f64);
+    }
+
+    fn record_analysis(&self, root_folder: &str, confidence: f32) {
+        self.import_analysis_confidence
+            .with_label_values(&[root_folder])
+            .observe(confidence as f64);
+    }
+
+    fn record_transfer(&self, root_folder: &str, hardlink: bool, bytes: u64) {
+        let method = if hardlink { "hardlink" } else { "copy" };
+
+        self.import_transfers_total
+            .with_label_values(&[root_folder, method])
+            .inc();
+        self.import_transfer_bytes_total
+            .with_label_values(&[root_folder, method])
+            .inc_by(bytes as f64);
+    }
+
+    fn record_import(
+        &self,
+        root_folder: &str,
+        duration: Duration,
+        failure: Option<ImportFailureReason>,
+    ) {
+        let status = if failure.is_some() { "error" } else { "success" };
+
+        self.import_duration_seconds
+            .with_label_values(&[root_folder, status])
+            .observe(duration.as_secs_f64());
+        if let Some(reason) = failure {
+            self.import_failures_by_reason_total
+                .with_label_values(&[root_folder, reason.as_str()])
+                .inc();
+        }
+    }
+}
+
+impl PoolMetrics for MetricsCollector {
+    fn record_pool_usage(&self, pool: &str, busy: u32, idle: u32, max: u32) {
+        self.db_pool_connections
+            .with_label_values(&[pool, "busy"])
+            .set(busy as f64);
+        self.db_pool_connections
+            .with_label_values(&[pool, "idle"])
+            .set(idle as f64);
+        self.db_pool_max_connections
+            .with_label_values(&[pool])
+            .set(max as f64);
+    }
+
+    fn record_acquire(&self, pool: &str, latency: Duration, success: bool) {
+        if success {
+            self.db_pool_acquire_duration_seconds
+                .with_label_values(&[pool])
+                .observe(latency.as_secs_f64());
+        } else {
+            self.db_pool_acquire_failures_total
+                .with_label_values(&[pool])
+                .inc();
+        }
+    }
+}
+
 /// Middleware for automatic HTTP metrics collection
 pub async fn metrics_middleware(req: Request<Body>, next: Next) -> Result<Response, Response> {
     let start = Instant::now();
@@ -1,11 +1,21 @@
 //! API middleware
 
+use crate::api_keys;
+use crate::auth::{self, AuthOutcome};
+use crate::handlers::movie_files;
+use crate::rate_limit::{self, ClientKey, RouteClass};
+use crate::sessions;
+use crate::share_tokens::{self, ShareAuthOutcome, SHARE_TOKEN_PREFIX};
 use axum::{
     body::Body,
+    extract::ConnectInfo,
     http::{HeaderMap, Request, Response, StatusCode},
     middleware::Next,
-    response::Response as AxumResponse,
+    response::{IntoResponse, Response as AxumResponse},
 };
+use radarr_core::models::AuditActor;
+use std::net::SocketAddr;
+use std::time::Instant;
 
 /// Simple request logging middleware
 pub async fn request_logger(request: Request<Body>, next: Next) -> Response<Body> {
@@ -20,15 +30,31 @@ pub async fn request_logger(request: Request<Body>, next: Next) -> Response<Body
 }
 
 /// API key authentication middleware
+///
+/// Keys are checked by the process-wide [`ApiKeyVerifier`](crate::auth::ApiKeyVerifier),
+/// which compares hashes in constant time and counts failures per source IP: once an
+/// address has sent too many wrong keys, every request from it gets 429 until the
+/// lockout ends, whichever key it carries. Requests without any key are not counted.
+/// Keys carrying the share token prefix are checked against the share token registry
+/// first: active tokens may only read within their scopes (403 otherwise), while unknown,
+/// expired or revoked ones fall through and count as failed attempts. Keys that do not
+/// match the admin key are looked up among the stored API keys, whose scope is checked
+/// the same way. Requests without a key may instead carry the session cookie of a
+/// username and password login or, on movie file streams only, the stream passkey in
+/// the query string. Authenticated requests are tagged with a
+/// [`ClientKey`] for [`rate_limit`] and, unless read-only, with the
+/// [`AuditActor`] the audit log records. Wrong keys never reach [`rate_limit`], so
+/// they are charged to the source IP's budget here, and an address with no budget
+/// left gets 429 before its key is checked.
 pub async fn require_api_key(
     headers: HeaderMap,
-    request: Request<Body>,
+    mut request: Request<Body>,
     next: Next,
 ) -> Result<AxumResponse, StatusCode> {
-    let path = request.uri().path();
+    let path = request.uri().path().to_string();
 
     // Skip authentication for public endpoints
-    if is_public_endpoint(path) {
+    if is_public_endpoint(&path) {
         let response = next.run(request).await;
         return Ok(response);
     }
@@ -40,26 +66,184 @@ pub async fn require_api_key(
         .or_else(|| headers.get("ApiKey"))
         .and_then(|v| v.to_str().ok());
 
-    // Get expected API key from environment - fail fast if not set
-    let expected_api_key = std::env::var("RADARR_API_KEY")
-        .expect("RADARR_API_KEY environment variable must be set for security");
+    let Some(verifier) = auth::global_verifier() else {
+        tracing::error!("RADARR_API_KEY or RADARR_API_KEY_HASH must be set for security");
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    };
 
-    match api_key {
-        Some(key) if key == expected_api_key => {
+    let source_ip = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+    let client = auth::client_identity(source_ip);
+
+    // The web UI sends its session cookie instead of a key once logged in
+    if api_key.is_none() {
+        let session = sessions::global_sessions().zip(sessions::session_token(&headers));
+        if let Some((store, token)) = session {
+            if let Some(username) = store.authenticate(token, chrono::Utc::now()) {
+                request
+                    .extensions_mut()
+                    .insert(ClientKey::for_credential(token));
+                request.extensions_mut().insert(AuditActor::user(username));
+                let response = next.run(request).await;
+                return Ok(response);
+            }
+        }
+    }
+
+    // A `<video>` element cannot set headers, so movie file streams also take
+    // a passkey in the query string, which is good for nothing else
+    if api_key.is_none() && movie_files::is_stream_path(&path) {
+        let passkey = request.uri().query().and_then(|query| {
+            url::form_urlencoded::parse(query.as_bytes())
+                .find(|(name, _)| name == "passkey")
+                .map(|(_, value)| value.into_owned())
+        });
+        if let Some(passkey) = passkey {
+            if !verifier.verify_passkey(&passkey, movie_files::STREAM_PASSKEY_PURPOSE) {
+                tracing::warn!(
+                    target: "audit",
+                    source_ip = %client,
+                    path = %path,
+                    "Movie file stream passkey rejected"
+                );
+                return Err(StatusCode::UNAUTHORIZED);
+            }
+            request
+                .extensions_mut()
+                .insert(ClientKey::for_credential(&passkey));
+            let response = next.run(request).await;
+            return Ok(response);
+        }
+    }
+
+    if let Some(token) = api_key.filter(|key| key.starts_with(SHARE_TOKEN_PREFIX)) {
+        let method = request.method().as_str();
+        match share_tokens::global_registry().authorize(token, method, &path, chrono::Utc::now()) {
+            ShareAuthOutcome::Authorized { .. } => {
+                request
+                    .extensions_mut()
+                    .insert(ClientKey::for_credential(token));
+                let response = next.run(request).await;
+                return Ok(response);
+            }
+            ShareAuthOutcome::OutOfScope { token_id } => {
+                tracing::warn!(
+                    target: "audit",
+                    source_ip = %client,
+                    path = %path,
+                    method = method,
+                    share_token = %token_id,
+                    "Share token used outside its scopes"
+                );
+                return Err(StatusCode::FORBIDDEN);
+            }
+            ShareAuthOutcome::Unknown => {}
+        }
+    }
+
+    // Key guesses are throttled by the source IP's budget
+    let limiter = rate_limit::global_rate_limiter();
+    if api_key.is_some() {
+        if let Err(retry_after) = limiter.peek(&client, RouteClass::Standard, Instant::now()) {
+            return Ok(rate_limited(retry_after));
+        }
+    }
+
+    let mut stored_key = None;
+    let outcome = verifier.authenticate_with(api_key, &client, &path, |key| {
+        stored_key = api_keys::global_registry().find(key);
+        stored_key.is_some()
+    });
+
+    match outcome {
+        AuthOutcome::Authorized => {
+            if let Some(key) = stored_key {
+                let method = request.method().as_str();
+                if !key.allows(method, &path) {
+                    tracing::warn!(
+                        target: "audit",
+                        source_ip = %client,
+                        path = %path,
+                        method = method,
+                        api_key = %key.id,
+                        "API key used outside its scope"
+                    );
+                    return Err(StatusCode::FORBIDDEN);
+                }
+                api_keys::global_registry().record_use(&key, chrono::Utc::now());
+                request.extensions_mut().insert(AuditActor::api_key(&key));
+            } else {
+                request.extensions_mut().insert(AuditActor::admin());
+            }
+            if let Some(key) = api_key {
+                request
+                    .extensions_mut()
+                    .insert(ClientKey::for_credential(key));
+            }
             let response = next.run(request).await;
             Ok(response)
         }
-        Some(_) => {
-            tracing::warn!("Invalid API key provided for {}", path);
-            Err(StatusCode::UNAUTHORIZED)
+        AuthOutcome::LockedOut { retry_after } => {
+            let locked_out = radarr_core::RadarrError::RateLimited {
+                service: "api".to_string(),
+                retry_after: Some(retry_after.as_secs().max(1)),
+            };
+            Ok(crate::error::ApiError::from(locked_out).into_response())
         }
-        None => {
-            tracing::warn!("No API key provided for {}", path);
+        AuthOutcome::Invalid => {
+            let _ = limiter.check(&client, RouteClass::Standard, Instant::now());
             Err(StatusCode::UNAUTHORIZED)
         }
+        AuthOutcome::Missing => Err(StatusCode::UNAUTHORIZED),
     }
 }
 
+/// Per-client rate limiting middleware
+///
+/// Runs inside [`require_api_key`] and limits each authenticated credential, or
+/// else each source IP, to the budgets of the process-wide
+/// [`RateLimiter`](crate::rate_limit::RateLimiter). Requests over budget get 429
+/// with a Retry-After header.
+pub async fn rate_limit(request: Request<Body>, next: Next) -> AxumResponse {
+    let Some(class) = RouteClass::of(request.method().as_str(), request.uri().path()) else {
+        return next.run(request).await;
+    };
+
+    let client = match request.extensions().get::<ClientKey>() {
+        Some(ClientKey(key)) => key.clone(),
+        None => auth::client_identity(
+            request
+                .extensions()
+                .get::<ConnectInfo<SocketAddr>>()
+                .map(|ConnectInfo(addr)| addr.ip()),
+        ),
+    };
+
+    match rate_limit::global_rate_limiter().check(&client, class, Instant::now()) {
+        Ok(()) => next.run(request).await,
+        Err(retry_after) => {
+            tracing::debug!(
+                client = %client,
+                path = %request.uri().path(),
+                class = ?class,
+                "Rate limit exceeded"
+            );
+            rate_limited(retry_after)
+        }
+    }
+}
+
+/// 429 with a Retry-After of `retry_after`, rounded up to whole seconds
+fn rate_limited(retry_after: std::time::Duration) -> AxumResponse {
+    let limited = radarr_core::RadarrError::RateLimited {
+        service: "api".to_string(),
+        retry_after: Some(retry_after.as_secs_f64().ceil().max(1.0) as u64),
+    };
+    crate::error::ApiError::from(limited).into_response()
+}
+
 /// Determine if an endpoint should be publicly accessible (no API key required)
 fn is_public_endpoint(path: &str) -> bool {
     // Health check endpoints
@@ -90,6 +274,11 @@ fn is_public_endpoint(path: &str) -> bool {
         return true;
     }
 
+    // Login and logout must be reachable without credentials
+    if path == "/api/v3/login" || path == "/api/v3/logout" {
+        return true;
+    }
+
     // WebSocket endpoint handles its own authentication via query parameters
     if path == "/ws" {
         return true;
@@ -4,7 +4,9 @@
 //! API requests and responses, implementing proper serialization and validation.
 
 use chrono::{DateTime, Utc};
-use radarr_core::{Download, MinimumAvailability, Movie, MovieStatus};
+use radarr_core::{
+    Download, MinimumAvailability, Movie, MovieRatings, MovieStatus, SearchCooldownPolicy,
+};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -95,11 +97,16 @@ pub struct MovieResponse {
     pub movie_file_id: Option<Uuid>,
     pub metadata: serde_json::Value,
     pub alternative_titles: serde_json::Value,
+    pub ratings: MovieRatings,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub last_search_time: Option<DateTime<Utc>>,
     pub last_info_sync: Option<DateTime<Utc>>,
+    pub failed_search_count: i32,
+    pub excluded_indexer_ids: Vec<i32>,
+    pub tags: Vec<i32>,
     /// Computed fields
+    pub next_search_time: Option<DateTime<Utc>>,
     pub rating: Option<f64>,
     pub overview: Option<String>,
 }
@@ -108,6 +115,7 @@ impl From<Movie> for MovieResponse {
     fn from(movie: Movie) -> Self {
         let rating = movie.rating();
         let overview = movie.overview().map(|s| s.to_string());
+        let next_search_time = SearchCooldownPolicy::default().next_search_time(&movie);
 
         Self {
             id: movie.id,
@@ -125,10 +133,15 @@ impl From<Movie> for MovieResponse {
             movie_file_id: movie.movie_file_id,
             metadata: movie.metadata,
             alternative_titles: movie.alternative_titles,
+            ratings: movie.ratings,
             created_at: movie.created_at,
             updated_at: movie.updated_at,
             last_search_time: movie.last_search_time,
             last_info_sync: movie.last_info_sync,
+            failed_search_count: movie.failed_search_count,
+            excluded_indexer_ids: movie.excluded_indexer_ids,
+            tags: movie.tags,
+            next_search_time,
             rating,
             overview,
         }
@@ -144,6 +157,8 @@ pub struct CreateMovieRequest {
     pub monitored: bool,
     pub quality_profile_id: Option<i32>,
     pub minimum_availability: Option<MinimumAvailability>,
+    #[serde(default)]
+    pub tags: Vec<i32>,
     /// Custom title override (optional)
     pub title: Option<String>,
     /// Additional metadata to merge
@@ -161,6 +176,7 @@ pub struct UpdateMovieRequest {
     pub monitored: Option<bool>,
     pub quality_profile_id: Option<i32>,
     pub minimum_availability: Option<MinimumAvailability>,
+    pub tags: Option<Vec<i32>>,
     pub metadata: Option<serde_json::Value>,
 }
 
@@ -225,6 +241,12 @@ pub struct ReleaseResponse {
     pub quality_score: Option<u8>,
     /// Progress percentage for active downloads
     pub progress: f64,
+    /// Whether the decision engine or blocklist rejected this release
+    #[serde(default)]
+    pub rejected: bool,
+    /// Human-readable rejection reasons
+    #[serde(default)]
+    pub rejections: Vec<String>,
 }
 
 /// Download request to start downloading a release
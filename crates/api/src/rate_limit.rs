@@ -0,0 +1,312 @@
+//! Per-client request rate limits
+//!
+//! Every client gets a token bucket per route class. A bucket holds up to a
+//! minute's budget and refills continuously, so short bursts go through while
+//! sustained load is capped. Searches, imports and other requests that fan out
+//! to indexers, TMDB or the disk have a much smaller budget than the rest of
+//! the API. Clients are told apart by the credential they authenticated with,
+//! falling back to the source IP. Requests with a wrong key are charged to the
+//! source IP too, and an address whose budget is used up cannot try keys until
+//! it refills.
+
+use once_cell::sync::Lazy;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Default budget of ordinary API requests per client and minute
+pub const DEFAULT_REQUESTS_PER_MINUTE: u32 = 300;
+
+/// Default budget of expensive requests per client and minute
+pub const DEFAULT_EXPENSIVE_REQUESTS_PER_MINUTE: u32 = 20;
+
+/// Number of buckets above which idle ones are dropped
+const PRUNE_THRESHOLD: usize = 1024;
+
+/// Requests that fan out to indexers, TMDB or the disk; `*` matches one segment
+const EXPENSIVE_ROUTES: &[(&str, &str)] = &[
+    ("GET", "/api/v3/movie/lookup"),
+    ("GET", "/api/v3/movies/*/search"),
+    ("GET", "/api/v3/search/advanced"),
+    ("POST", "/api/v3/search/bulk"),
+    ("POST", "/api/v3/release"),
+    ("GET", "/api/v3/importlist/trakt/preview"),
+    ("POST", "/api/v3/indexer/search"),
+    ("POST", "/api/v3/command"),
+    ("POST", "/api/v3/command/import"),
+    ("POST", "/api/v3/wanted/missing/search"),
+    ("POST", "/api/v3/wanted/cutoff/search"),
+    ("POST", "/api/v3/rename"),
+    ("POST", "/api/v3/system/backup"),
+    ("POST", "/api/v3/system/backup/restore"),
+    ("POST", "/api/v3/system/backup/restore/*"),
+];
+
+/// Budget a request is counted against
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RouteClass {
+    Standard,
+    Expensive,
+}
+
+impl RouteClass {
+    /// Class of a request, or `None` for requests outside the API, which are
+    /// not limited
+    pub fn of(method: &str, path: &str) -> Option<Self> {
+        if !path.starts_with("/api/") {
+            return None;
+        }
+        let path = path.trim_end_matches('/');
+        let expensive = EXPENSIVE_ROUTES.iter().any(|(route_method, pattern)| {
+            route_method.eq_ignore_ascii_case(method) && matches_route(pattern, path)
+        });
+        Some(if expensive {
+            RouteClass::Expensive
+        } else {
+            RouteClass::Standard
+        })
+    }
+}
+
+fn matches_route(pattern: &str, path: &str) -> bool {
+    let mut segments = path.split('/');
+    pattern.split('/').all(|expected| {
+        segments
+            .next()
+            .is_some_and(|s| expected == "*" || expected == s)
+    }) && segments.next().is_none()
+}
+
+/// Identity a request is limited under, set by the auth middleware
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientKey(pub String);
+
+impl ClientKey {
+    /// Identity of a request authenticated with `credential`, which is only
+    /// kept as a digest
+    pub fn for_credential(credential: &str) -> Self {
+        let digest = hex::encode(Sha256::digest(credential.as_bytes()));
+        Self(format!("credential:{}", &digest[..16]))
+    }
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// Token buckets per client and route class
+#[derive(Debug)]
+pub struct RateLimiter {
+    requests_per_minute: u32,
+    expensive_requests_per_minute: u32,
+    buckets: Mutex<HashMap<(String, RouteClass), Bucket>>,
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        Self::new(
+            DEFAULT_REQUESTS_PER_MINUTE,
+            DEFAULT_EXPENSIVE_REQUESTS_PER_MINUTE,
+        )
+    }
+}
+
+impl RateLimiter {
+    /// Create a limiter with per-minute budgets; 0 turns a budget off
+    pub fn new(requests_per_minute: u32, expensive_requests_per_minute: u32) -> Self {
+        Self {
+            requests_per_minute,
+            expensive_requests_per_minute,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Load budgets from the environment
+    ///
+    /// Reads `RADARR_RATE_LIMIT_PER_MINUTE` and
+    /// `RADARR_RATE_LIMIT_EXPENSIVE_PER_MINUTE`; 0 turns a budget off.
+    pub fn from_env() -> Self {
+        let budget = |name: &str, default: u32| {
+            std::env::var(name)
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(default)
+        };
+        Self::new(
+            budget("RADARR_RATE_LIMIT_PER_MINUTE", DEFAULT_REQUESTS_PER_MINUTE),
+            budget(
+                "RADARR_RATE_LIMIT_EXPENSIVE_PER_MINUTE",
+                DEFAULT_EXPENSIVE_REQUESTS_PER_MINUTE,
+            ),
+        )
+    }
+
+    /// Requests per minute allowed for `class`; 0 means unlimited
+    pub fn budget(&self, class: RouteClass) -> u32 {
+        match class {
+            RouteClass::Standard => self.requests_per_minute,
+            RouteClass::Expensive => self.expensive_requests_per_minute,
+        }
+    }
+
+    /// Whether `client` has a token left, without taking it
+    ///
+    /// Returns how long to wait for the next token if the bucket is empty.
+    pub fn peek(&self, client: &str, class: RouteClass, now: Instant) -> Result<(), Duration> {
+        let budget = self.budget(class);
+        if budget == 0 {
+            return Ok(());
+        }
+        let capacity = f64::from(budget);
+        let per_second = capacity / 60.0;
+
+        let buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(bucket) = buckets.get(&(client.to_string(), class)) else {
+            return Ok(());
+        };
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        let tokens = (bucket.tokens + elapsed * per_second).min(capacity);
+        if tokens >= 1.0 {
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - tokens) / per_second))
+        }
+    }
+
+    /// Take a token for a request from `client`
+    ///
+    /// Returns how long to wait for the next token if the bucket is empty.
+    pub fn check(&self, client: &str, class: RouteClass, now: Instant) -> Result<(), Duration> {
+        let budget = self.budget(class);
+        if budget == 0 {
+            return Ok(());
+        }
+        let capacity = f64::from(budget);
+        let per_second = capacity / 60.0;
+
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        if buckets.len() >= PRUNE_THRESHOLD {
+            // A bucket that has refilled completely is the same as none
+            buckets.retain(|(_, class), bucket| {
+                let per_second = f64::from(self.budget(*class)) / 60.0;
+                let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+                bucket.tokens + elapsed * per_second < f64::from(self.budget(*class))
+            });
+        }
+
+        let bucket = buckets
+            .entry((client.to_string(), class))
+            .or_insert(Bucket {
+                tokens: capacity,
+                updated: now,
+            });
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * per_second).min(capacity);
+        bucket.updated = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / per_second))
+        }
+    }
+}
+
+static GLOBAL_RATE_LIMITER: Lazy<RateLimiter> = Lazy::new(RateLimiter::from_env);
+
+/// Process-wide limiter built from the environment on first use
+pub fn global_rate_limiter() -> &'static RateLimiter {
+    &GLOBAL_RATE_LIMITER
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_routes_are_classified_by_method_and_path() {
+        assert_eq!(
+            RouteClass::of("GET", "/api/v3/movie"),
+            Some(RouteClass::Standard)
+        );
+        assert_eq!(
+            RouteClass::of("GET", "/api/v3/movie/lookup"),
+            Some(RouteClass::Expensive)
+        );
+        assert_eq!(
+            RouteClass::of("GET", "/api/v3/movies/42/search/"),
+            Some(RouteClass::Expensive)
+        );
+        assert_eq!(
+            RouteClass::of("POST", "/api/v3/command"),
+            Some(RouteClass::Expensive)
+        );
+        for (method, path) in [
+            ("POST", "/api/v3/release"),
+            ("GET", "/api/v3/search/advanced"),
+            ("POST", "/api/v3/search/bulk"),
+        ] {
+            assert_eq!(RouteClass::of(method, path), Some(RouteClass::Expensive));
+        }
+        // Polling a command is cheap
+        assert_eq!(
+            RouteClass::of("GET", "/api/v3/command/7"),
+            Some(RouteClass::Standard)
+        );
+        assert_eq!(
+            RouteClass::of("GET", "/api/v3/movies/42/search/extra"),
+            Some(RouteClass::Standard)
+        );
+        assert_eq!(RouteClass::of("GET", "/assets/index.js"), None);
+        assert_eq!(RouteClass::of("GET", "/health"), None);
+    }
+
+    #[test]
+    fn test_buckets_refill_per_client_and_class() {
+        let limiter = RateLimiter::new(60, 2);
+        let now = Instant::now();
+
+        assert!(limiter.check("a", RouteClass::Expensive, now).is_ok());
+        assert!(limiter.check("a", RouteClass::Expensive, now).is_ok());
+        let retry_after = limiter.check("a", RouteClass::Expensive, now).unwrap_err();
+        assert_eq!(retry_after.as_secs_f64().round(), 30.0);
+
+        // Other clients and the standard budget are unaffected
+        assert!(limiter.check("b", RouteClass::Expensive, now).is_ok());
+        assert!(limiter.check("a", RouteClass::Standard, now).is_ok());
+
+        let later = now + Duration::from_secs(31);
+        assert!(limiter.check("a", RouteClass::Expensive, later).is_ok());
+        assert!(limiter.check("a", RouteClass::Expensive, later).is_err());
+    }
+
+    #[test]
+    fn test_peek_does_not_take_tokens() {
+        let limiter = RateLimiter::new(1, 1);
+        let now = Instant::now();
+
+        assert!(limiter.peek("a", RouteClass::Standard, now).is_ok());
+        assert!(limiter.peek("a", RouteClass::Standard, now).is_ok());
+        assert!(limiter.check("a", RouteClass::Standard, now).is_ok());
+        let retry_after = limiter.peek("a", RouteClass::Standard, now).unwrap_err();
+        assert_eq!(retry_after.as_secs_f64().round(), 60.0);
+        assert!(limiter
+            .peek("a", RouteClass::Standard, now + Duration::from_secs(60))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_zero_budget_is_unlimited() {
+        let limiter = RateLimiter::new(0, 1);
+        let now = Instant::now();
+        for _ in 0..1000 {
+            assert!(limiter.check("a", RouteClass::Standard, now).is_ok());
+        }
+        assert!(limiter.check("a", RouteClass::Expensive, now).is_ok());
+        assert!(limiter.check("a", RouteClass::Expensive, now).is_err());
+    }
+}
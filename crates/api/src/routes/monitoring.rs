@@ -5,7 +5,7 @@
 
 use crate::handlers::monitoring::{
     get_alert_by_id, get_alerts, get_circuit_breaker_states, get_health_status,
-    get_monitoring_status, get_prometheus_metrics,
+    get_monitoring_status, get_prometheus_metrics, get_recovery_attempts,
 };
 use axum::{routing::get, Router};
 // Note: ListSyncMonitor integration is planned for future implementation
@@ -22,6 +22,7 @@ use axum::{routing::get, Router};
 /// - GET /api/v3/monitoring/alerts/{id} - Get specific alert by ID  
 /// - GET /api/v3/monitoring/health - Service health status
 /// - GET /api/v3/monitoring/circuit-breakers - Circuit breaker states
+/// - GET /api/v3/monitoring/recovery - Automated recovery audit trail
 pub fn create_monitoring_routes() -> Router {
     Router::new()
         // Prometheus metrics endpoint (standard path for metrics scraping)
@@ -38,6 +39,8 @@ pub fn create_monitoring_routes() -> Router {
             "/api/v3/monitoring/circuit-breakers",
             get(get_circuit_breaker_states),
         )
+        // Automated recovery audit trail
+        .route("/api/v3/monitoring/recovery", get(get_recovery_attempts))
 }
 
 /// Create monitoring routes with middleware
@@ -0,0 +1,261 @@
+//! Username and password sessions
+//!
+//! When `RADARR_AUTH_USERNAME` and a password are configured, the web UI can
+//! log in with them instead of holding the API key. A successful login hands
+//! out a session cookie that the auth middleware accepts like the API key.
+//! Passwords are only kept as argon2 hashes, and sessions only as hashes of
+//! their tokens; sessions live in memory and end with the process, after
+//! [`DEFAULT_SESSION_TTL_HOURS`] without use, or on logout.
+
+use argon2::{
+    password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2,
+};
+use axum::http::{header, HeaderMap};
+use chrono::{DateTime, Duration, Utc};
+use once_cell::sync::Lazy;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::RwLock;
+use subtle::ConstantTimeEq;
+use uuid::Uuid;
+
+/// Name of the session cookie
+pub const SESSION_COOKIE: &str = "radarr_session";
+
+/// Hours a session stays valid after its last use
+pub const DEFAULT_SESSION_TTL_HOURS: i64 = 7 * 24;
+
+/// Hash a password into an argon2 PHC string for `RADARR_AUTH_PASSWORD_HASH`
+pub fn hash_password(password: &str) -> Result<String, argon2::password_hash::Error> {
+    let salt = SaltString::encode_b64(Uuid::new_v4().as_bytes())?;
+    Ok(Argon2::default()
+        .hash_password(password.as_bytes(), &salt)?
+        .to_string())
+}
+
+/// Token presented in the session cookie, if any
+pub fn session_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|cookies| cookies.split(';'))
+        .filter_map(|cookie| cookie.trim().split_once('='))
+        .find(|(name, _)| *name == SESSION_COOKIE)
+        .map(|(_, token)| token)
+        .filter(|token| !token.is_empty())
+}
+
+/// `Set-Cookie` value handing out a session token
+pub fn session_cookie(token: &str, ttl: Duration) -> String {
+    format!(
+        "{}={}; Path=/; HttpOnly; SameSite=Strict; Max-Age={}",
+        SESSION_COOKIE,
+        token,
+        ttl.num_seconds()
+    )
+}
+
+/// `Set-Cookie` value removing the session cookie
+pub fn expired_session_cookie() -> String {
+    format!(
+        "{}=; Path=/; HttpOnly; SameSite=Strict; Max-Age=0",
+        SESSION_COOKIE
+    )
+}
+
+fn digest_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+/// A logged-in user
+#[derive(Debug, Clone)]
+struct Session {
+    username: String,
+    expires_at: DateTime<Utc>,
+}
+
+/// Configured credentials and the sessions handed out for them
+pub struct SessionStore {
+    username: String,
+    /// Argon2 PHC string of the password
+    password_hash: String,
+    ttl: Duration,
+    sessions: RwLock<HashMap<String, Session>>,
+}
+
+impl SessionStore {
+    /// Create a store for a user whose password hashes to `password_hash`
+    ///
+    /// Returns `None` if `password_hash` is not a valid PHC string.
+    pub fn new(username: impl Into<String>, password_hash: impl Into<String>) -> Option<Self> {
+        let password_hash = password_hash.into();
+        PasswordHash::new(&password_hash).ok()?;
+        Some(Self {
+            username: username.into(),
+            password_hash,
+            ttl: Duration::hours(DEFAULT_SESSION_TTL_HOURS),
+            sessions: RwLock::new(HashMap::new()),
+        })
+    }
+
+    /// Load credentials from the environment
+    ///
+    /// Reads `RADARR_AUTH_USERNAME` and either `RADARR_AUTH_PASSWORD_HASH`
+    /// (an argon2 PHC string) or `RADARR_AUTH_PASSWORD`, which is hashed on
+    /// load. Returns `None` when either is unset, leaving only API keys.
+    pub fn from_env() -> Option<Self> {
+        let username = std::env::var("RADARR_AUTH_USERNAME")
+            .ok()
+            .filter(|username| !username.trim().is_empty())?;
+
+        let password_hash = match std::env::var("RADARR_AUTH_PASSWORD_HASH") {
+            Ok(hash) => hash,
+            Err(_) => {
+                let password = std::env::var("RADARR_AUTH_PASSWORD")
+                    .ok()
+                    .filter(|password| !password.is_empty())?;
+                match hash_password(&password) {
+                    Ok(hash) => hash,
+                    Err(e) => {
+                        tracing::error!("Failed to hash RADARR_AUTH_PASSWORD: {}", e);
+                        return None;
+                    }
+                }
+            }
+        };
+
+        let store = Self::new(username.trim(), password_hash);
+        if store.is_none() {
+            tracing::error!("RADARR_AUTH_PASSWORD_HASH is not an argon2 hash; logins disabled");
+        }
+        store
+    }
+
+    /// Keep sessions for `ttl` after their last use
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// How long sessions stay valid after their last use
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// The configured username
+    pub fn username(&self) -> &str {
+        &self.username
+    }
+
+    /// Whether `username` and `password` match the configured credentials
+    pub fn verify(&self, username: &str, password: &str) -> bool {
+        let username_matches: bool = username.as_bytes().ct_eq(self.username.as_bytes()).into();
+        // Always hash, so a wrong username takes as long as a wrong password
+        let password_matches = PasswordHash::new(&self.password_hash)
+            .map(|hash| {
+                Argon2::default()
+                    .verify_password(password.as_bytes(), &hash)
+                    .is_ok()
+            })
+            .unwrap_or(false);
+        username_matches && password_matches
+    }
+
+    /// Start a session, returning its token
+    pub fn create(&self, now: DateTime<Utc>) -> String {
+        let token = format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+        let mut sessions = self.sessions.write().unwrap_or_else(|e| e.into_inner());
+        // Forget expired sessions so the map does not grow without bound
+        sessions.retain(|_, session| session.expires_at > now);
+        sessions.insert(
+            digest_token(&token),
+            Session {
+                username: self.username.clone(),
+                expires_at: now + self.ttl,
+            },
+        );
+        token
+    }
+
+    /// User of an unexpired session, extending it by the TTL
+    pub fn authenticate(&self, token: &str, now: DateTime<Utc>) -> Option<String> {
+        let mut sessions = self.sessions.write().unwrap_or_else(|e| e.into_inner());
+        let session = sessions
+            .get_mut(&digest_token(token))
+            .filter(|session| session.expires_at > now)?;
+        session.expires_at = now + self.ttl;
+        Some(session.username.clone())
+    }
+
+    /// End a session
+    pub fn remove(&self, token: &str) {
+        let mut sessions = self.sessions.write().unwrap_or_else(|e| e.into_inner());
+        sessions.remove(&digest_token(token));
+    }
+}
+
+static GLOBAL_SESSIONS: Lazy<Option<SessionStore>> = Lazy::new(SessionStore::from_env);
+
+/// Process-wide session store, if credentials are configured
+pub fn global_sessions() -> Option<&'static SessionStore> {
+    GLOBAL_SESSIONS.as_ref()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn store() -> SessionStore {
+        SessionStore::new("admin", hash_password("hunter22").unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_verify_checks_username_and_password() {
+        let store = store();
+        assert!(store.verify("admin", "hunter22"));
+        assert!(!store.verify("admin", "hunter2"));
+        assert!(!store.verify("Admin", "hunter22"));
+        assert!(SessionStore::new("admin", "hunter22").is_none());
+    }
+
+    #[test]
+    fn test_sessions_slide_and_end_on_logout() {
+        let store = store().with_ttl(Duration::hours(1));
+        let now = Utc::now();
+        let token = store.create(now);
+
+        assert_eq!(store.authenticate(&token, now).as_deref(), Some("admin"));
+        // Each use extends the session
+        let later = now + Duration::minutes(50);
+        assert!(store.authenticate(&token, later).is_some());
+        assert!(store
+            .authenticate(&token, later + Duration::minutes(40))
+            .is_some());
+        assert!(store
+            .authenticate(&token, later + Duration::hours(3))
+            .is_none());
+
+        let token = store.create(now);
+        store.remove(&token);
+        assert!(store.authenticate(&token, now).is_none());
+        assert!(store.authenticate("unknown", now).is_none());
+    }
+
+    #[test]
+    fn test_session_token_reads_the_cookie_header() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(session_token(&headers), None);
+
+        headers.insert(
+            header::COOKIE,
+            HeaderValue::from_static("theme=dark; radarr_session=abc123"),
+        );
+        assert_eq!(session_token(&headers), Some("abc123"));
+
+        headers.insert(header::COOKIE, HeaderValue::from_static("radarr_session="));
+        assert_eq!(session_token(&headers), None);
+    }
+}
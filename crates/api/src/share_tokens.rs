@@ -0,0 +1,160 @@
+//! Read-only share tokens
+//!
+//! Share tokens are presented like the API key but only unlock reads of the
+//! paths their scopes name, until they expire or are revoked. The running
+//! process keeps the stored tokens in a registry keyed by token hash, which
+//! the auth middleware consults for any key carrying [`SHARE_TOKEN_PREFIX`].
+
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use radarr_core::models::ShareToken;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::RwLock;
+use uuid::Uuid;
+
+/// Prefix telling share tokens apart from the API key
+pub const SHARE_TOKEN_PREFIX: &str = "share_";
+
+/// Generate a new share token; two UUIDv4s, 244 random bits, behind the prefix
+pub fn generate_share_token() -> String {
+    format!(
+        "{}{}{}",
+        SHARE_TOKEN_PREFIX,
+        Uuid::new_v4().simple(),
+        Uuid::new_v4().simple()
+    )
+}
+
+/// Hex SHA-256 digest under which a share token is stored
+pub fn hash_share_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+/// Outcome of checking a presented share token
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShareAuthOutcome {
+    /// The token is active and its scopes cover the request
+    Authorized { token_id: Uuid },
+    /// The token is active but the request is outside its scopes
+    OutOfScope { token_id: Uuid },
+    /// No active token matches
+    Unknown,
+}
+
+/// Share tokens known to the running process, keyed by token hash
+#[derive(Debug, Default)]
+pub struct ShareTokenRegistry {
+    tokens: RwLock<HashMap<String, ShareToken>>,
+}
+
+impl ShareTokenRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace the known tokens, e.g. with the stored ones at startup
+    pub fn replace(&self, tokens: Vec<ShareToken>) {
+        let mut known = self.tokens.write().unwrap_or_else(|e| e.into_inner());
+        *known = tokens
+            .into_iter()
+            .map(|token| (token.token_hash.clone(), token))
+            .collect();
+    }
+
+    /// Add a newly minted token
+    pub fn insert(&self, token: ShareToken) {
+        let mut known = self.tokens.write().unwrap_or_else(|e| e.into_inner());
+        known.insert(token.token_hash.clone(), token);
+    }
+
+    /// Forget a revoked token
+    pub fn remove(&self, id: Uuid) {
+        let mut known = self.tokens.write().unwrap_or_else(|e| e.into_inner());
+        known.retain(|_, token| token.id != id);
+    }
+
+    /// Check a presented token against a request
+    pub fn authorize(
+        &self,
+        presented: &str,
+        method: &str,
+        path: &str,
+        now: DateTime<Utc>,
+    ) -> ShareAuthOutcome {
+        let known = self.tokens.read().unwrap_or_else(|e| e.into_inner());
+        match known.get(&hash_share_token(presented)) {
+            Some(token) if token.is_active(now) => {
+                if token.allows(method, path) {
+                    ShareAuthOutcome::Authorized { token_id: token.id }
+                } else {
+                    ShareAuthOutcome::OutOfScope { token_id: token.id }
+                }
+            }
+            _ => ShareAuthOutcome::Unknown,
+        }
+    }
+}
+
+static GLOBAL_REGISTRY: Lazy<ShareTokenRegistry> = Lazy::new(ShareTokenRegistry::new);
+
+/// Process-wide registry consulted by the auth middleware
+pub fn global_registry() -> &'static ShareTokenRegistry {
+    &GLOBAL_REGISTRY
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use radarr_core::models::ShareScope;
+
+    #[test]
+    fn test_registry_authorizes_active_tokens_within_scope() {
+        let registry = ShareTokenRegistry::new();
+        let now = Utc::now();
+        let presented = generate_share_token();
+        assert!(presented.starts_with(SHARE_TOKEN_PREFIX));
+
+        let token = ShareToken::new(
+            "Friends".to_string(),
+            hash_share_token(&presented),
+            vec![ShareScope::Library],
+            now + chrono::Duration::days(7),
+        );
+        let id = token.id;
+        registry.insert(token);
+
+        assert_eq!(
+            registry.authorize(&presented, "GET", "/api/v3/movie", now),
+            ShareAuthOutcome::Authorized { token_id: id }
+        );
+        assert_eq!(
+            registry.authorize(&presented, "DELETE", "/api/v3/movie/1", now),
+            ShareAuthOutcome::OutOfScope { token_id: id }
+        );
+        assert_eq!(
+            registry.authorize(&presented, "GET", "/api/rss/calendar", now),
+            ShareAuthOutcome::OutOfScope { token_id: id }
+        );
+        // Expired and unknown tokens look the same
+        assert_eq!(
+            registry.authorize(
+                &presented,
+                "GET",
+                "/api/v3/movie",
+                now + chrono::Duration::days(8)
+            ),
+            ShareAuthOutcome::Unknown
+        );
+        assert_eq!(
+            registry.authorize(&generate_share_token(), "GET", "/api/v3/movie", now),
+            ShareAuthOutcome::Unknown
+        );
+
+        registry.remove(id);
+        assert_eq!(
+            registry.authorize(&presented, "GET", "/api/v3/movie", now),
+            ShareAuthOutcome::Unknown
+        );
+    }
+}
@@ -4,24 +4,35 @@
 //! for demonstration purposes.
 
 use crate::{
+    error::{ApiError, ApiResult},
     metrics::MetricsCollector,
     security::{apply_security, SecurityConfig},
 };
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
-    response::Json,
-    routing::{delete, get, post},
+    response::{IntoResponse, Json},
+    routing::{delete, get, post, put},
     Router,
 };
-use radarr_core::{repositories::MovieRepository, Movie, MovieStatus, RadarrError};
+use radarr_core::{
+    events::{EventBus, SystemEvent},
+    repositories::{
+        IndexerRepository, ListExclusionRepository, MovieRepository, QualityProfileRepository,
+    },
+    AudioCodec, DownloadClientService, ListExclusion, MinimumAvailability, Movie, MovieStatus,
+    RadarrError, SearchCooldownPolicy, VideoCodec,
+};
 use tower_http::services::ServeDir;
 // Quality analysis integration commented out for now until we ensure proper crate setup
 // use radarr_analysis::{SceneGroupAnalyzer, SceneGroupMetrics};
 use chrono;
 use radarr_core::circuit_breaker::{CircuitBreaker, CircuitBreakerConfig, CircuitBreakerState};
 use radarr_indexers::{IndexerClient, ProwlarrSearchResult, SearchRequest, SearchResponse};
-use radarr_infrastructure::{CachedTmdbClient, DatabasePool, PostgresMovieRepository};
+use radarr_infrastructure::{
+    CachedTmdbClient, DatabasePool, PostgresHistoryRepository, PostgresListExclusionRepository,
+    PostgresMovieRepository, ReadPool,
+};
 use regex;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -35,11 +46,52 @@ use uuid::Uuid;
 #[derive(Clone)]
 pub struct SimpleApiState {
     pub database_pool: DatabasePool,
+    /// Read-only queries, served by the read replica while it keeps up
+    pub read_pool: ReadPool,
     pub indexer_client: Option<Arc<dyn IndexerClient + Send + Sync>>,
     pub movie_repo: Arc<PostgresMovieRepository>,
+    pub exclusion_repo: Arc<dyn ListExclusionRepository>,
     pub tmdb_client: Option<Arc<CachedTmdbClient>>,
     pub metrics_collector: Option<Arc<MetricsCollector>>,
     pub quality_state: crate::handlers::quality::QualityState,
+    pub quality_profile_state: crate::handlers::quality_profiles::QualityProfileState,
+    pub indexer_state: crate::handlers::indexers::IndexerState,
+    pub history_state: crate::handlers::history::HistoryState,
+    pub movie_file_state: crate::handlers::movie_files::MovieFileState,
+    pub movie_editor_state: crate::handlers::movie_editor::MovieEditorState,
+    pub rename_state: crate::handlers::rename::RenameState,
+    pub collection_state: crate::handlers::collections::CollectionState,
+    pub root_folder_state: crate::handlers::root_folders::RootFolderState,
+    pub notification_state: crate::handlers::notifications::NotificationState,
+    pub command_state: crate::handlers::commands::CommandQueueState,
+    pub task_state: crate::handlers::tasks::TaskState,
+    pub diagnostics_state: crate::handlers::diagnostics::DiagnosticsState,
+    pub downloads_cleanup_state: crate::handlers::downloads_cleanup::DownloadsCleanupState,
+    pub share_token_state: crate::handlers::share_tokens::ShareTokenState,
+    pub api_key_state: crate::handlers::api_keys::ApiKeyState,
+    pub trakt_list_state: crate::handlers::trakt_lists::TraktListState,
+    pub exclusion_state: crate::handlers::exclusions::ExclusionState,
+    pub wanted_state: crate::handlers::wanted::WantedState,
+    pub blocklist_state: crate::handlers::blocklist::BlocklistState,
+    pub media_cover_state: crate::handlers::media_cover::MediaCoverState,
+    pub calendar_state: crate::handlers::calendar::CalendarState,
+    pub queue_state: crate::handlers::queue::QueueState,
+    pub tag_state: crate::handlers::tags::TagState,
+    pub download_client: Option<Arc<dyn DownloadClientService>>,
+    pub event_bus: Option<Arc<EventBus>>,
+    pub config_state: crate::handlers::config::ConfigState,
+    pub backup_state: crate::handlers::backup::BackupState,
+    pub update_state: crate::handlers::update::UpdateState,
+    pub system_health_state: crate::handlers::system_health::SystemHealthState,
+    pub log_state: crate::handlers::logs::LogState,
+    pub audit_state: crate::handlers::audit::AuditState,
+    pub statistics_state: crate::handlers::statistics::StatisticsState,
+    /// File and folder naming the import pipeline uses
+    pub rename_config: Arc<std::sync::RwLock<radarr_import::RenameConfig>>,
+    /// Whether propers and repacks replace existing files of the same quality
+    pub proper_handling: radarr_decision::ProperHandling,
+    /// Languages releases must be in, and which are preferred
+    pub language_profile: radarr_decision::LanguageProfile,
     // Circuit breakers for testing
     pub tmdb_circuit_breaker: Arc<CircuitBreaker>,
     pub hdbits_circuit_breaker: Arc<CircuitBreaker>,
@@ -81,14 +133,114 @@ impl SimpleApiState {
         ));
 
         let quality_state = crate::handlers::quality::QualityState::new(database_pool.clone());
+        let quality_profile_state =
+            crate::handlers::quality_profiles::QualityProfileState::new(database_pool.clone());
+        let indexer_state = crate::handlers::indexers::IndexerState::new(database_pool.clone());
+        let history_state = crate::handlers::history::HistoryState::new(database_pool.clone());
+        let movie_file_state =
+            crate::handlers::movie_files::MovieFileState::new(database_pool.clone());
+        let movie_editor_state = crate::handlers::movie_editor::MovieEditorState::new(
+            database_pool.clone(),
+            movie_file_state.clone(),
+        );
+        let config_state = crate::handlers::config::ConfigState::new(database_pool.clone());
+        let rename_state = crate::handlers::rename::RenameState::new(database_pool.clone())
+            .with_rename_config(config_state.rename_config.clone());
+        let collection_state =
+            crate::handlers::collections::CollectionState::new(database_pool.clone());
+        let root_folder_state =
+            crate::handlers::root_folders::RootFolderState::new(database_pool.clone());
+        let notification_state =
+            crate::handlers::notifications::NotificationState::new(database_pool.clone());
+        let share_token_state =
+            crate::handlers::share_tokens::ShareTokenState::new(database_pool.clone());
+        let api_key_state = crate::handlers::api_keys::ApiKeyState::new(database_pool.clone());
+        let trakt_list_state =
+            crate::handlers::trakt_lists::TraktListState::new(database_pool.clone());
+        let exclusion_state =
+            crate::handlers::exclusions::ExclusionState::new(database_pool.clone());
+        let media_cover_state =
+            crate::handlers::media_cover::MediaCoverState::new(database_pool.clone());
+        let calendar_state = crate::handlers::calendar::CalendarState::new(database_pool.clone());
+        let command_queue = Arc::new(radarr_core::CommandQueue::default());
+        let wanted_state =
+            crate::handlers::wanted::WantedState::new(database_pool.clone(), command_queue.clone());
+        let blocklist_state = crate::handlers::blocklist::BlocklistState::new(
+            database_pool.clone(),
+            command_queue.clone(),
+        );
+        let queue_state =
+            crate::handlers::queue::QueueState::new(database_pool.clone(), command_queue.clone());
+        let tag_state = crate::handlers::tags::TagState::new(database_pool.clone());
+        let audit_state = crate::handlers::audit::AuditState::new(database_pool.clone());
+        let statistics_state =
+            crate::handlers::statistics::StatisticsState::from_pool(database_pool.clone());
+        let backup_state = crate::handlers::backup::BackupState::new(Arc::new(
+            radarr_infrastructure::BackupService::new(
+                database_pool.clone(),
+                &radarr_infrastructure::BackupConfig::default(),
+            ),
+        ));
+        // Nothing calls out to GitHub until the application's checker is set
+        let update_state = crate::handlers::update::UpdateState::new(Arc::new(
+            radarr_infrastructure::UpdateChecker::new(radarr_infrastructure::UpdateConfig {
+                enabled: false,
+                ..Default::default()
+            }),
+        ));
+        let exclusion_repo = Arc::new(PostgresListExclusionRepository::new(database_pool.clone()));
 
         Self {
+            read_pool: ReadPool::primary_only(database_pool.clone()),
             database_pool,
             indexer_client: None,
             movie_repo,
+            exclusion_repo,
             tmdb_client: None,
             metrics_collector: None,
             quality_state,
+            quality_profile_state,
+            indexer_state,
+            history_state,
+            movie_file_state,
+            movie_editor_state,
+            rename_state,
+            collection_state,
+            root_folder_state,
+            notification_state,
+            command_state: crate::handlers::commands::CommandQueueState::new(command_queue.clone()),
+            task_state: crate::handlers::tasks::TaskState::new(Arc::new(
+                radarr_core::jobs::TaskScheduler::new(command_queue),
+            )),
+            diagnostics_state: crate::handlers::diagnostics::DiagnosticsState::new(Arc::new(
+                radarr_import::ForensicBundleStore::new("diagnostics"),
+            )),
+            downloads_cleanup_state: Default::default(),
+            share_token_state,
+            api_key_state,
+            trakt_list_state,
+            exclusion_state,
+            wanted_state,
+            blocklist_state,
+            media_cover_state,
+            calendar_state,
+            queue_state,
+            tag_state,
+            backup_state,
+            update_state,
+            system_health_state: crate::handlers::system_health::SystemHealthState::new(Arc::new(
+                radarr_core::HealthCheckRegistry::new(),
+            )),
+            // Filled by the log store layer installed with the telemetry
+            log_state: crate::handlers::logs::LogState::new(radarr_core::logs::log_store()),
+            audit_state,
+            statistics_state,
+            download_client: None,
+            event_bus: None,
+            rename_config: config_state.rename_config.clone(),
+            config_state,
+            proper_handling: radarr_decision::ProperHandling::default(),
+            language_profile: radarr_decision::LanguageProfile::default(),
             tmdb_circuit_breaker: tmdb_cb,
             hdbits_circuit_breaker: hdbits_cb,
             qbittorrent_circuit_breaker: qbittorrent_cb,
@@ -96,6 +248,19 @@ impl SimpleApiState {
         }
     }
 
+    /// Serve the library listing and history from a read replica
+    pub fn with_read_pool(mut self, reads: ReadPool) -> Self {
+        self.movie_repo = Arc::new(
+            PostgresMovieRepository::new(self.database_pool.clone()).with_read_pool(reads.clone()),
+        );
+        self.history_state.history_repo = Arc::new(
+            PostgresHistoryRepository::new(self.database_pool.clone())
+                .with_read_pool(reads.clone()),
+        );
+        self.read_pool = reads;
+        self
+    }
+
     /// Create new state with indexer client
     pub fn with_indexer_client(mut self, client: Arc<dyn IndexerClient + Send + Sync>) -> Self {
         self.indexer_client = Some(client);
@@ -104,6 +269,7 @@ impl SimpleApiState {
 
     /// Create new state with TMDB client
     pub fn with_tmdb_client(mut self, client: Arc<CachedTmdbClient>) -> Self {
+        self.collection_state = self.collection_state.with_tmdb_client(client.clone());
         self.tmdb_client = Some(client);
         self
     }
@@ -113,6 +279,159 @@ impl SimpleApiState {
         self.metrics_collector = Some(metrics);
         self
     }
+
+    /// Create new state with a download client for manual grabs
+    pub fn with_download_client(mut self, client: Arc<dyn DownloadClientService>) -> Self {
+        self.queue_state = self.queue_state.with_download_client(client.clone());
+        self.download_client = Some(client);
+        self
+    }
+
+    /// Create new state with the notification service to reload on changes
+    pub fn with_notification_service(
+        mut self,
+        notifications: Arc<radarr_notifications::NotificationService>,
+    ) -> Self {
+        self.notification_state = self
+            .notification_state
+            .with_notification_service(notifications);
+        self
+    }
+
+    /// Create new state with the queue commands are run on
+    pub fn with_command_queue(mut self, queue: Arc<radarr_core::CommandQueue>) -> Self {
+        self.wanted_state.command_queue = queue.clone();
+        self.blocklist_state.command_queue = queue.clone();
+        self.queue_state.command_queue = queue.clone();
+        self.movie_editor_state = self.movie_editor_state.with_command_queue(queue.clone());
+        self.command_state = crate::handlers::commands::CommandQueueState::new(queue);
+        self
+    }
+
+    /// Create new state with the scheduler running recurring tasks
+    pub fn with_task_scheduler(mut self, scheduler: Arc<radarr_core::jobs::TaskScheduler>) -> Self {
+        self.task_state = crate::handlers::tasks::TaskState::new(scheduler);
+        self
+    }
+
+    /// Create new state with the store failed imports write forensic bundles to
+    pub fn with_forensic_bundles(mut self, store: Arc<radarr_import::ForensicBundleStore>) -> Self {
+        self.diagnostics_state = crate::handlers::diagnostics::DiagnosticsState::new(store);
+        self
+    }
+
+    /// Create new state with the cleanup of orphaned downloads entries
+    pub fn with_downloads_cleanup(
+        mut self,
+        cleanup: Arc<radarr_import::DownloadsCleanupService>,
+    ) -> Self {
+        self.downloads_cleanup_state =
+            crate::handlers::downloads_cleanup::DownloadsCleanupState::new(cleanup);
+        self
+    }
+
+    /// Create new state with the throttle holding grabs for nearly full root
+    /// folders
+    pub fn with_storage_throttle(mut self, throttle: Arc<radarr_core::StorageThrottle>) -> Self {
+        self.root_folder_state = self.root_folder_state.with_storage_throttle(throttle);
+        self
+    }
+
+    /// Create new state with the naming settings the import pipeline uses
+    pub fn with_rename_config(self, config: radarr_import::RenameConfig) -> Self {
+        *self
+            .rename_config
+            .write()
+            .unwrap_or_else(|e| e.into_inner()) = config;
+        self
+    }
+
+    /// Create new state that applies naming saved through the settings API
+    /// to the files `pipeline` imports
+    pub fn with_import_pipeline(mut self, pipeline: Arc<radarr_import::ImportPipeline>) -> Self {
+        self.config_state = self.config_state.with_import_pipeline(pipeline);
+        self
+    }
+
+    /// Create new state serving the host settings the instance started with
+    pub fn with_host_config(mut self, host: crate::handlers::config::HostConfigResource) -> Self {
+        self.config_state.host = Arc::new(std::sync::RwLock::new(host));
+        self
+    }
+
+    /// Create new state serving the media management settings the instance
+    /// started with
+    pub fn with_media_management_config(
+        mut self,
+        settings: crate::handlers::config::MediaManagementConfigResource,
+    ) -> Self {
+        self.config_state.media_management = Arc::new(std::sync::RwLock::new(settings));
+        self
+    }
+
+    /// Create new state with the service writing scheduled backups
+    pub fn with_backups(mut self, backups: Arc<radarr_infrastructure::BackupService>) -> Self {
+        self.backup_state = crate::handlers::backup::BackupState::new(backups);
+        self
+    }
+
+    /// Create new state listing the releases `updates` finds
+    pub fn with_update_checker(
+        mut self,
+        updates: Arc<radarr_infrastructure::UpdateChecker>,
+    ) -> Self {
+        self.update_state = crate::handlers::update::UpdateState::new(updates);
+        self
+    }
+
+    /// Create new state reporting what `checks` find
+    pub fn with_health_checks(mut self, checks: Arc<radarr_core::HealthCheckRegistry>) -> Self {
+        self.system_health_state = crate::handlers::system_health::SystemHealthState::new(checks);
+        self
+    }
+
+    /// Create new state serving the application's cached library statistics
+    pub fn with_library_statistics(
+        mut self,
+        statistics: Arc<radarr_core::LibraryStatisticsService>,
+    ) -> Self {
+        self.statistics_state = crate::handlers::statistics::StatisticsState::new(statistics);
+        self
+    }
+
+    /// Create new state that judges propers and repacks like RSS sync does
+    pub fn with_proper_handling(
+        mut self,
+        proper_handling: radarr_decision::ProperHandling,
+    ) -> Self {
+        self.proper_handling = proper_handling;
+        self
+    }
+
+    /// Create new state that rejects and prefers languages like RSS sync does
+    pub fn with_language_profile(mut self, profile: radarr_decision::LanguageProfile) -> Self {
+        self.language_profile = profile;
+        self
+    }
+
+    /// Create new state serving artwork from the media cover cache
+    pub fn with_media_covers(
+        mut self,
+        covers: Arc<radarr_infrastructure::MediaCoverService>,
+    ) -> Self {
+        self.media_cover_state = self.media_cover_state.with_media_covers(covers);
+        self
+    }
+
+    /// Create new state with the event bus
+    pub fn with_event_bus(mut self, event_bus: Arc<EventBus>) -> Self {
+        self.movie_file_state = self.movie_file_state.with_event_bus(event_bus.clone());
+        self.movie_editor_state = self.movie_editor_state.with_event_bus(event_bus.clone());
+        self.blocklist_state = self.blocklist_state.with_event_bus(event_bus.clone());
+        self.config_state = self.config_state.with_event_bus(event_bus.clone());
+        self.event_bus = Some(event_bus);
+        self
+    }
 }
 
 /// Simple movie response for MVP
@@ -125,10 +444,21 @@ pub struct SimpleMovieResponse {
     pub status: MovieStatus,
     pub monitored: bool,
     pub created_at: String,
+    pub last_search_time: Option<String>,
+    pub next_search_time: Option<String>,
+    pub excluded_indexer_ids: Vec<i32>,
+    pub tags: Vec<i32>,
+    pub images: Vec<crate::handlers::media_cover::MediaCoverResource>,
+    pub ratings: radarr_core::MovieRatings,
 }
 
 impl From<Movie> for SimpleMovieResponse {
     fn from(movie: Movie) -> Self {
+        let next_search_time = SearchCooldownPolicy::default()
+            .next_search_time(&movie)
+            .map(|t| t.to_rfc3339());
+        let images = crate::handlers::media_cover::MediaCoverResource::for_movie(&movie);
+
         Self {
             id: movie.id,
             tmdb_id: movie.tmdb_id,
@@ -137,6 +467,12 @@ impl From<Movie> for SimpleMovieResponse {
             status: movie.status,
             monitored: movie.monitored,
             created_at: movie.created_at.to_rfc3339(),
+            last_search_time: movie.last_search_time.map(|t| t.to_rfc3339()),
+            next_search_time,
+            excluded_indexer_ids: movie.excluded_indexer_ids,
+            tags: movie.tags,
+            images,
+            ratings: movie.ratings,
         }
     }
 }
@@ -148,6 +484,88 @@ pub struct SimpleCreateMovieRequest {
     pub title: String,
     #[serde(default)]
     pub monitored: bool,
+    /// Also add and monitor the rest of the movie's collection
+    #[serde(default)]
+    pub add_collection: bool,
+    /// Remove the movie's list exclusion instead of refusing to add it
+    #[serde(default)]
+    pub remove_exclusion: bool,
+    /// Tags scoping the indexers and notifications used for the movie
+    #[serde(default)]
+    pub tags: Vec<i32>,
+}
+
+/// Movie editor request; omitted fields are left unchanged
+///
+/// Field names follow the Radarr v3 movie resource so clients can send back
+/// a whole movie they fetched earlier. Fields that cannot be edited are ignored.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimpleUpdateMovieRequest {
+    pub monitored: Option<bool>,
+    pub quality_profile_id: Option<i32>,
+    pub minimum_availability: Option<MinimumAvailability>,
+    /// Indexers never searched for this movie
+    pub excluded_indexer_ids: Option<Vec<i32>>,
+    /// Tags scoping the indexers and notifications used for the movie
+    pub tags: Option<Vec<i32>>,
+}
+
+impl SimpleUpdateMovieRequest {
+    /// Apply the request to a movie, returning the names of changed fields
+    pub(crate) fn apply(&self, movie: &mut Movie) -> Vec<String> {
+        let mut changes = Vec::new();
+        if let Some(monitored) = self.monitored.filter(|m| *m != movie.monitored) {
+            movie.monitored = monitored;
+            changes.push("monitored".to_string());
+        }
+        if let Some(profile_id) = self
+            .quality_profile_id
+            .filter(|id| Some(*id) != movie.quality_profile_id)
+        {
+            movie.quality_profile_id = Some(profile_id);
+            changes.push("qualityProfileId".to_string());
+        }
+        if let Some(availability) = self
+            .minimum_availability
+            .clone()
+            .filter(|a| *a != movie.minimum_availability)
+        {
+            movie.minimum_availability = availability;
+            changes.push("minimumAvailability".to_string());
+        }
+        if let Some(mut excluded) = self.excluded_indexer_ids.clone() {
+            excluded.sort_unstable();
+            excluded.dedup();
+            if excluded != movie.excluded_indexer_ids {
+                movie.excluded_indexer_ids = excluded;
+                changes.push("excludedIndexerIds".to_string());
+            }
+        }
+        if let Some(tags) = self.tags.clone() {
+            let tags = crate::handlers::tags::normalize_tag_ids(tags);
+            if tags != movie.tags {
+                movie.tags = tags;
+                changes.push("tags".to_string());
+            }
+        }
+        if !changes.is_empty() {
+            movie.updated_at = chrono::Utc::now();
+        }
+        changes
+    }
+}
+
+/// Movie deletion options, named as in Radarr v3
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteMovieParams {
+    /// Also delete the movie's file from disk
+    #[serde(default)]
+    pub delete_files: bool,
+    /// Keep lists and collections from adding the movie again
+    #[serde(default)]
+    pub add_import_exclusion: bool,
 }
 
 /// Simple query parameters
@@ -325,15 +743,30 @@ pub fn create_simple_api_router(state: SimpleApiState) -> Router {
         .route("/v3/movie", post(create_movie))
         .route("/v3/movie/lookup", get(lookup_movies)) // IMPORTANT: Must come before /:id route
         .route("/v3/movie/:id", get(get_movie))
+        .route("/v3/movie/:id", put(update_movie))
         .route("/v3/movie/:id", delete(delete_movie))
         // Protected search endpoint (real Prowlarr integration)
         .route("/v3/indexer/search", post(search_movies))
         // Protected Prowlarr test endpoint
         .route("/v3/indexer/test", post(test_prowlarr_connection))
+        // Per-indexer request, throttling and backoff counters
+        .route("/v3/indexer/metrics", get(indexer_metrics))
+        .route(
+            "/v3/indexer/metrics/prometheus",
+            get(indexer_metrics_prometheus),
+        )
+        // Sample names rendered from the naming templates
+        .route("/v3/config/naming/examples", get(naming_examples))
+        // Manual grab of a release from the latest search results
+        .route("/v3/release", post(grab_release))
         // Protected download endpoint (mock)
         .route("/v3/download", post(start_download))
         // Protected import endpoint (real import pipeline)
         .route("/v3/command/import", post(import_download))
+        // Read replica lag and whether it serves reads
+        .route("/v3/system/database/replica", get(replica_status))
+        // Applied and pending schema migrations
+        .route("/v3/system/migrations", get(migration_status))
         // Circuit breaker test endpoints
         .route(
             "/v3/test/circuit-breaker/status",
@@ -347,12 +780,7 @@ pub fn create_simple_api_router(state: SimpleApiState) -> Router {
             "/v3/test/circuit-breaker/reset/:service",
             post(reset_circuit_breaker),
         )
-        // Quality profile endpoints
-        .route("/v3/qualityprofile", get(list_quality_profiles_simple))
-        .route("/v3/qualityprofile/:id", get(get_quality_profile_simple))
         // Queue endpoints
-        .route("/v3/queue", get(list_queue_simple))
-        .route("/v3/queue/:id", delete(remove_queue_item_simple))
         .route(
             "/v3/queue/:id/pause",
             axum::routing::put(pause_queue_item_simple),
@@ -361,7 +789,136 @@ pub fn create_simple_api_router(state: SimpleApiState) -> Router {
             "/v3/queue/:id/resume",
             axum::routing::put(resume_queue_item_simple),
         )
-        .with_state(state.clone());
+        .with_state(state.clone())
+        // Quality profile CRUD endpoints
+        .merge(
+            crate::handlers::quality_profiles::create_quality_profile_router(
+                state.quality_profile_state.clone(),
+            ),
+        )
+        // Indexer definition CRUD endpoints
+        .merge(crate::handlers::indexers::create_indexer_router(
+            state.indexer_state.clone(),
+        ))
+        // Custom format CRUD endpoints
+        .merge(crate::handlers::quality::create_quality_router(
+            state.quality_state.clone(),
+        ))
+        // Grab/import/failure/deletion history
+        .merge(crate::handlers::history::create_history_router(
+            state.history_state.clone(),
+        ))
+        // Movie file listing, deletion and preview streaming
+        .merge(crate::handlers::movie_files::create_movie_file_router(
+            state.movie_file_state.clone(),
+        ))
+        // Editing and deleting many movies at once
+        .merge(crate::handlers::movie_editor::create_movie_editor_router(
+            state.movie_editor_state.clone(),
+        ))
+        // Renaming imported files to the naming templates
+        .merge(crate::handlers::rename::create_rename_router(
+            state.rename_state.clone(),
+        ))
+        // TMDB collections
+        .merge(crate::handlers::collections::create_collection_router(
+            state.collection_state.clone(),
+        ))
+        // Root folders with free space
+        .merge(crate::handlers::root_folders::create_root_folder_router(
+            state.root_folder_state.clone(),
+        ))
+        .merge(crate::handlers::diskspace::create_diskspace_router(
+            state.root_folder_state.clone(),
+        ))
+        // Notification providers with per-event triggers
+        .merge(crate::handlers::notifications::create_notification_router(
+            state.notification_state.clone(),
+        ))
+        // Command queue with status polling
+        .merge(crate::handlers::commands::create_command_router(
+            state.command_state.clone(),
+        ))
+        // Scheduled tasks with intervals and manual runs
+        .merge(crate::handlers::tasks::create_task_router(
+            state.task_state.clone(),
+        ))
+        // Forensic bundles of failed imports
+        .merge(crate::handlers::diagnostics::create_diagnostics_router(
+            state.diagnostics_state.clone(),
+        ))
+        // Dry run of the downloads folder cleanup
+        .merge(
+            crate::handlers::downloads_cleanup::create_downloads_cleanup_router(
+                state.downloads_cleanup_state.clone(),
+            ),
+        )
+        // Read-only share tokens with expiry and revocation
+        .merge(crate::handlers::share_tokens::create_share_token_router(
+            state.share_token_state.clone(),
+        ))
+        // Named API keys with scopes, rotation and revocation
+        .merge(crate::handlers::api_keys::create_api_key_router(
+            state.api_key_state.clone(),
+        ))
+        // Username and password login with session cookies; public
+        .merge(crate::handlers::sessions::create_session_router())
+        // Trakt account authorization and list previews
+        .merge(crate::handlers::trakt_lists::create_trakt_list_router(
+            state.trakt_list_state.clone(),
+        ))
+        // Import list exclusions
+        .merge(crate::handlers::exclusions::create_exclusion_router(
+            state.exclusion_state.clone(),
+        ))
+        // Movies below their quality cutoff, with upgrade searches
+        .merge(crate::handlers::wanted::create_wanted_router(
+            state.wanted_state.clone(),
+        ))
+        // Failed releases, with removal and retry
+        .merge(crate::handlers::blocklist::create_blocklist_router(
+            state.blocklist_state.clone(),
+        ))
+        // Upcoming releases of monitored movies
+        .merge(crate::handlers::calendar::create_calendar_router(
+            state.calendar_state.clone(),
+        ))
+        // Download queue, with removal and blocklisting
+        .merge(crate::handlers::queue::create_queue_router(
+            state.queue_state.clone(),
+        ))
+        // Tags scoping indexers and notifications to movies
+        .merge(crate::handlers::tags::create_tag_router(
+            state.tag_state.clone(),
+        ))
+        // Host, naming and media management settings stored in the database
+        .merge(crate::handlers::config::create_config_router(
+            state.config_state.clone(),
+        ))
+        // Configuration backups with download and restore
+        .merge(crate::handlers::backup::create_backup_router(
+            state.backup_state.clone(),
+        ))
+        // Running version and newer releases
+        .merge(crate::handlers::update::create_update_router(
+            state.update_state.clone(),
+        ))
+        // Issues found by the health checks
+        .merge(crate::handlers::system_health::create_system_health_router(
+            state.system_health_state.clone(),
+        ))
+        // Stored warnings and errors, and the log files
+        .merge(crate::handlers::logs::create_log_router(
+            state.log_state.clone(),
+        ))
+        // Deletions and settings changes, with the credential that made them
+        .merge(crate::handlers::audit::create_audit_router(
+            state.audit_state.clone(),
+        ))
+        // Library totals for dashboards
+        .merge(crate::handlers::statistics::create_statistics_router(
+            state.statistics_state.clone(),
+        ));
 
     // Create static file service for React app
     let static_service = ServeDir::new("web/dist")
@@ -372,6 +929,14 @@ pub fn create_simple_api_router(state: SimpleApiState) -> Router {
     let full_router = Router::new()
         .route("/health", get(health_check)) // Public health check
         .nest("/api", api_router) // Protected API routes under /api prefix
+        // Cached posters and fanart where Radarr UIs expect them
+        .merge(crate::handlers::media_cover::create_media_cover_router(
+            state.media_cover_state.clone(),
+        ))
+        // iCal feed, authorized by passkey instead of the API key
+        .merge(crate::handlers::calendar::create_calendar_feed_router(
+            state.calendar_state.clone(),
+        ))
         .fallback_service(static_service); // Serve React app for all other routes
 
     // Apply comprehensive security features
@@ -443,51 +1008,60 @@ async fn list_movies(
 async fn get_movie(
     State(state): State<SimpleApiState>,
     Path(id): Path<Uuid>,
-) -> Result<Json<Value>, StatusCode> {
+) -> ApiResult<Json<Value>> {
     info!("Getting movie by ID: {}", id);
 
-    match state.movie_repo.find_by_id(id).await {
-        Ok(Some(movie)) => {
-            info!("Found movie: {}", movie.title);
-            let response = SimpleMovieResponse::from(movie);
-            Ok(Json(serde_json::to_value(&response).unwrap_or_else(|_| {
-                serde_json::json!({
-                    "error": "Failed to serialize movie response"
-                })
-            })))
-        }
-        Ok(None) => {
-            warn!("Movie not found: {}", id);
-            Err(StatusCode::NOT_FOUND)
-        }
-        Err(e) => {
-            error!("Database error while getting movie {}: {}", id, e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
-        }
-    }
+    let movie = find_movie(&state, id).await?;
+    info!("Found movie: {}", movie.title);
+    let response = SimpleMovieResponse::from(movie);
+    Ok(Json(serde_json::to_value(&response).unwrap_or_else(|_| {
+        serde_json::json!({
+            "error": "Failed to serialize movie response"
+        })
+    })))
 }
 
 /// Create movie endpoint
 async fn create_movie(
     State(state): State<SimpleApiState>,
     Json(request): Json<SimpleCreateMovieRequest>,
-) -> Result<(StatusCode, Json<Value>), StatusCode> {
+) -> ApiResult<(StatusCode, Json<Value>)> {
     info!(
         "Creating movie: {} (TMDB: {})",
         request.title, request.tmdb_id
     );
 
     // Check if movie already exists
-    match state.movie_repo.find_by_tmdb_id(request.tmdb_id).await {
-        Ok(Some(_)) => {
-            warn!("Movie with TMDB ID {} already exists", request.tmdb_id);
-            return Err(StatusCode::CONFLICT);
-        }
-        Ok(None) => {} // Good, doesn't exist yet
-        Err(e) => {
-            error!("Database error checking for existing movie: {}", e);
-            return Err(StatusCode::INTERNAL_SERVER_ERROR);
-        }
+    if state
+        .movie_repo
+        .find_by_tmdb_id(request.tmdb_id)
+        .await
+        .inspect_err(|e| error!("Database error checking for existing movie: {}", e))?
+        .is_some()
+    {
+        warn!("Movie with TMDB ID {} already exists", request.tmdb_id);
+        return Err(ApiError::Conflict {
+            resource: format!("movie with TMDB ID {}", request.tmdb_id),
+        });
+    }
+
+    // Movies deleted with "add exclusion" stay out unless asked otherwise
+    let exclusion = state
+        .exclusion_repo
+        .find_by_tmdb_id(request.tmdb_id)
+        .await
+        .inspect_err(|e| error!("Database error checking list exclusions: {}", e))?;
+    if exclusion.is_some() && !request.remove_exclusion {
+        warn!(
+            "Movie with TMDB ID {} is excluded; set remove_exclusion to add it",
+            request.tmdb_id
+        );
+        return Err(ApiError::Conflict {
+            resource: format!(
+                "movie with TMDB ID {} is excluded; set remove_exclusion to add it",
+                request.tmdb_id
+            ),
+        });
     }
 
     // Create new movie
@@ -495,10 +1069,45 @@ async fn create_movie(
 
     // Set monitored flag from request
     movie.monitored = request.monitored;
+    movie.tags = crate::handlers::tags::normalize_tag_ids(request.tags.clone());
 
     match state.movie_repo.create(&movie).await {
         Ok(created_movie) => {
             info!("Movie created successfully: {}", created_movie.title);
+            if let Some(exclusion) = exclusion {
+                match state.exclusion_repo.delete(exclusion.id).await {
+                    Ok(()) => info!("Removed list exclusion for {}", created_movie.title),
+                    Err(e) => warn!(
+                        "Failed to remove list exclusion for {}: {}",
+                        created_movie.title, e
+                    ),
+                }
+            }
+            if let Some(event_bus) = &state.event_bus {
+                let event = SystemEvent::MovieAdded {
+                    movie_id: created_movie.id,
+                    tmdb_id: created_movie.tmdb_id,
+                    title: created_movie.title.clone(),
+                };
+                if let Err(e) = event_bus.publish(event).await {
+                    warn!("Failed to publish movie added event: {}", e);
+                }
+            }
+
+            // Collection sync is best effort; the movie itself was added
+            if state.tmdb_client.is_some() {
+                if let Err(e) = state
+                    .collection_state
+                    .on_movie_added(&created_movie, request.add_collection)
+                    .await
+                {
+                    warn!(
+                        "Failed to sync collection for {}: {}",
+                        created_movie.title, e
+                    );
+                }
+            }
+
             let response = SimpleMovieResponse::from(created_movie);
             Ok((
                 StatusCode::CREATED,
@@ -511,33 +1120,183 @@ async fn create_movie(
         }
         Err(e) => {
             error!("Failed to create movie: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            Err(e.into())
         }
     }
 }
 
+/// Look up a movie, 404 when it doesn't exist
+async fn find_movie(state: &SimpleApiState, id: Uuid) -> ApiResult<Movie> {
+    state
+        .movie_repo
+        .find_by_id(id)
+        .await
+        .inspect_err(|e| error!("Failed to look up movie {}: {}", id, e))?
+        .ok_or_else(|| {
+            warn!("Movie not found: {}", id);
+            ApiError::NotFound {
+                resource: format!("movie {}", id),
+            }
+        })
+}
+
+/// Update movie endpoint (Radarr v3 movie editor)
+async fn update_movie(
+    State(state): State<SimpleApiState>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<SimpleUpdateMovieRequest>,
+) -> ApiResult<Json<Value>> {
+    let mut movie = find_movie(&state, id).await?;
+
+    if let Some(profile_id) = request.quality_profile_id {
+        let profile = state
+            .quality_profile_state
+            .profile_repo
+            .find_by_id(profile_id)
+            .await
+            .inspect_err(|e| error!("Failed to look up quality profile {}: {}", profile_id, e))?;
+        if profile.is_none() {
+            return Err(ApiError::ValidationError {
+                field: "qualityProfileId".to_string(),
+                message: format!("Quality profile {} not found", profile_id),
+            });
+        }
+    }
+
+    let changes = request.apply(&mut movie);
+    if !changes.is_empty() {
+        movie = state
+            .movie_repo
+            .update(&movie)
+            .await
+            .inspect_err(|e| error!("Failed to update movie {}: {}", id, e))?;
+
+        info!("Updated movie {} ({})", movie.title, changes.join(", "));
+        if let Some(event_bus) = &state.event_bus {
+            let event = SystemEvent::MovieUpdated {
+                movie_id: id,
+                changes,
+            };
+            if let Err(e) = event_bus.publish(event).await {
+                warn!("Failed to publish movie update event: {}", e);
+            }
+        }
+    }
+
+    let response = SimpleMovieResponse::from(movie);
+    Ok(Json(serde_json::to_value(&response).unwrap_or_else(|_| {
+        serde_json::json!({
+            "error": "Failed to serialize movie response"
+        })
+    })))
+}
+
 /// Delete movie endpoint
-async fn delete_movie(State(_state): State<SimpleApiState>, Path(id): Path<Uuid>) -> StatusCode {
-    // For MVP, always return success
-    StatusCode::NO_CONTENT
+///
+/// `deleteFiles` also removes the movie file from disk and
+/// `addImportExclusion` keeps lists and collections from re-adding the movie.
+async fn delete_movie(
+    State(state): State<SimpleApiState>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<DeleteMovieParams>,
+    actor: Option<axum::Extension<radarr_core::models::AuditActor>>,
+) -> ApiResult<StatusCode> {
+    let movie = find_movie(&state, id).await?;
+
+    // Read the file records before the movie row (and with it the records) goes
+    let movie_files = if params.delete_files {
+        state
+            .movie_file_state
+            .movie_file_repo
+            .list(Some(id))
+            .await
+            .inspect_err(|e| error!("Failed to look up files of movie {}: {}", id, e))?
+    } else {
+        Vec::new()
+    };
+
+    if params.add_import_exclusion {
+        let exclusion = ListExclusion::for_movie(&movie, "Deleted from library");
+        state
+            .exclusion_repo
+            .create(&exclusion)
+            .await
+            .inspect_err(|e| error!("Failed to add import exclusion for {}: {}", movie.title, e))?;
+    }
+
+    state
+        .movie_repo
+        .delete(id)
+        .await
+        .inspect_err(|e| error!("Failed to delete movie {}: {}", id, e))?;
+    info!("Deleted movie: {} (ID: {})", movie.title, id);
+    crate::handlers::audit::publish_audit(
+        state.event_bus.as_ref(),
+        actor,
+        radarr_core::models::AuditAction::MovieDelete,
+        id.to_string(),
+        serde_json::json!({
+            "title": movie.title,
+            "tmdbId": movie.tmdb_id,
+            "deleteFiles": params.delete_files,
+            "addImportExclusion": params.add_import_exclusion,
+        }),
+    )
+    .await;
+
+    // The movie is gone either way; a file left behind is only logged
+    for movie_file in movie_files {
+        match state.movie_file_state.remove_from_disk(&movie_file).await {
+            Ok(removed) => {
+                if !removed {
+                    warn!(
+                        "File of {} was already missing: {}",
+                        movie.title, movie_file.relative_path
+                    );
+                }
+                if let Some(event_bus) = &state.event_bus {
+                    let event = SystemEvent::MovieFileDeleted {
+                        movie_id: id,
+                        movie_file_id: movie_file.id,
+                        relative_path: movie_file.relative_path.clone(),
+                        reason: "movieDeleted".to_string(),
+                    };
+                    if let Err(e) = event_bus.publish(event).await {
+                        warn!("Failed to publish movie file deletion event: {}", e);
+                    }
+                }
+            }
+            Err(e) => error!("Failed to delete file of {}: {}", movie.title, e),
+        }
+    }
+
+    if let Some(event_bus) = &state.event_bus {
+        let event = SystemEvent::MovieDeleted {
+            movie_id: id,
+            title: movie.title,
+        };
+        if let Err(e) = event_bus.publish(event).await {
+            warn!("Failed to publish movie deletion event: {}", e);
+        }
+    }
+
+    Ok(StatusCode::NO_CONTENT)
 }
 
 /// Movie lookup endpoint - searches TMDB for movies
 async fn lookup_movies(
     State(state): State<SimpleApiState>,
     Query(params): Query<MovieLookupParams>,
-) -> Result<Json<Vec<MovieLookupResponse>>, (StatusCode, Json<Value>)> {
+) -> ApiResult<Json<Vec<MovieLookupResponse>>> {
     info!("Looking up movies with term: '{}'", params.term);
 
     let tmdb_client = match state.tmdb_client.as_ref() {
         Some(client) => client,
         None => {
             error!("TMDB client not configured");
-            let error_response = serde_json::json!({
-                "error": "TMDB client not configured",
-                "message": "Movie lookup service is not available"
+            return Err(ApiError::ServiceUnavailable {
+                message: "TMDB client not configured, movie lookup is not available".to_string(),
             });
-            return Err((StatusCode::SERVICE_UNAVAILABLE, Json(error_response)));
         }
     };
 
@@ -563,11 +1322,7 @@ async fn lookup_movies(
         }
         Err(e) => {
             error!("TMDB search failed: {}", e);
-            let error_response = serde_json::json!({
-                "error": "Movie search failed",
-                "message": e.to_string()
-            });
-            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+            Err(RadarrError::from(e).into())
         }
     }
 }
@@ -576,7 +1331,7 @@ async fn lookup_movies(
 async fn search_movies(
     State(state): State<SimpleApiState>,
     Json(request): Json<Value>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+) -> ApiResult<Json<Value>> {
     use std::time::Instant;
     use tracing::{error, info, warn};
 
@@ -629,6 +1384,32 @@ async fn search_movies(
         search_request.categories = vec![2000]; // Movie category
     }
 
+    let movie_id = request
+        .get("movieId")
+        .and_then(|id| id.as_str())
+        .and_then(|id| Uuid::parse_str(id).ok());
+
+    // Skip indexers the movie excludes or isn't tagged for; its ratings are
+    // matched by rating custom formats
+    let mut movie_ratings = None;
+    if let Some(movie_id) = movie_id {
+        match state.movie_repo.find_by_id(movie_id).await {
+            Ok(Some(movie)) => {
+                let indexers = state
+                    .indexer_state
+                    .indexer_repo
+                    .list()
+                    .await
+                    .inspect_err(|e| warn!("Failed to load indexer tags: {}", e))
+                    .unwrap_or_default();
+                search_request.excluded_indexer_ids = movie.excluded_indexers(&indexers);
+                movie_ratings = Some(movie.ratings);
+            }
+            Ok(None) => {}
+            Err(e) => warn!("Failed to load indexer exclusions for {}: {}", movie_id, e),
+        }
+    }
+
     info!("Searching Prowlarr with request: {:?}", search_request);
 
     // Perform search with retry logic
@@ -658,12 +1439,33 @@ async fn search_movies(
                 metrics.record_search("prowlarr", start_time.elapsed(), true);
             }
 
+            // Score against the enabled custom formats and cache the results so
+            // format edits can re-score them without searching again
+            let format_engine = match state.quality_state.format_engine().await {
+                Ok(engine) => engine,
+                Err(e) => {
+                    warn!("Failed to load custom formats for scoring: {}", e);
+                    radarr_decision::CustomFormatEngine::with_formats(Vec::new())
+                }
+            };
+
+            // Decision engine and blocklist checks, reported per release
+            let rejections =
+                search_result_rejections(&state, &response.results, movie_id, &format_engine).await;
+
             // Convert to API response format
             let api_response = serde_json::json!({
                 "total": response.total,
-                "releases": response.results.iter().map(|result| {
+                "releases": response.results.iter().zip(rejections).map(|(result, rejections)| {
+                    let guid = search_result_guid(result);
+                    let scored = state.quality_state.release_cache.insert_with_download_url(
+                        guid.clone(),
+                        Some(result.download_url.clone()),
+                        release_data_from_search_result(result, movie_ratings.clone()),
+                        &format_engine,
+                    );
                     serde_json::json!({
-                        "guid": format!("{}-{}", result.indexer_id, result.title.chars().take(20).collect::<String>()),
+                        "guid": guid,
                         "title": result.title,
                         "downloadUrl": result.download_url,
                         "infoUrl": result.info_url,
@@ -678,8 +1480,16 @@ async fn search_movies(
                         "imdbId": result.imdb_id,
                         "tmdbId": result.tmdb_id,
                         "freeleech": result.freeleech,
+                        "languages": radarr_core::parse_languages(&result.title)
+                            .iter()
+                            .map(radarr_core::Language::name)
+                            .collect::<Vec<_>>(),
                         "qualityScore": calculate_quality_score(&result.title),
                         "qualityMetadata": extract_quality_metadata(&result.title, result.size),
+                        "customFormatScore": scored.custom_format_score,
+                        "customFormats": scored.custom_formats,
+                        "rejected": !rejections.is_empty(),
+                        "rejections": rejections,
                     })
                 }).collect::<Vec<_>>(),
                 "indexersSearched": response.indexers_searched,
@@ -698,19 +1508,72 @@ async fn search_movies(
                 metrics.record_search("prowlarr", start_time.elapsed(), false);
             }
 
-            // Return error response
-            let error_response = serde_json::json!({
-                "error": "Search failed",
-                "message": e.to_string(),
-                "executionTimeMs": execution_time,
-                "fallbackUsed": false
-            });
-
-            Err((StatusCode::SERVICE_UNAVAILABLE, Json(error_response)))
+            // Rate limits and open circuits tell the client when to retry
+            Err(e.into())
         }
     }
 }
 
+/// Per-indexer request, throttling and backoff counters
+async fn indexer_metrics(
+    State(state): State<SimpleApiState>,
+) -> Json<Vec<radarr_indexers::IndexerThrottleMetrics>> {
+    let metrics = match state.indexer_client.as_ref() {
+        Some(client) => client.get_indexer_metrics().await,
+        None => Vec::new(),
+    };
+    Json(metrics)
+}
+
+/// Per-indexer counters in the Prometheus text format
+async fn indexer_metrics_prometheus(State(state): State<SimpleApiState>) -> impl IntoResponse {
+    let metrics = match state.indexer_client.as_ref() {
+        Some(client) => client.get_indexer_metrics().await,
+        None => Vec::new(),
+    };
+    (
+        [(
+            axum::http::header::CONTENT_TYPE,
+            "text/plain; version=0.0.4; charset=utf-8",
+        )],
+        radarr_indexers::indexer_throttle::render_prometheus(&metrics),
+    )
+}
+
+/// Naming settings to preview instead of the configured ones
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NamingExamplesParams {
+    pub standard_movie_format: Option<String>,
+    pub movie_folder_format: Option<String>,
+    pub colon_replacement_format: Option<radarr_import::ColonReplacement>,
+}
+
+/// Sample movie file and folder names from the naming settings, with any
+/// formats given in the query in place of the configured ones
+async fn naming_examples(
+    State(state): State<SimpleApiState>,
+    Query(params): Query<NamingExamplesParams>,
+) -> ApiResult<Json<radarr_import::NamingExamples>> {
+    let mut config = state
+        .rename_config
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone();
+    if let Some(format) = params.standard_movie_format {
+        config.movie_template = format;
+    }
+    if let Some(format) = params.movie_folder_format {
+        config.folder_template = format;
+    }
+    if let Some(colon_replacement) = params.colon_replacement_format {
+        config.colon_replacement = colon_replacement;
+    }
+
+    let examples = radarr_import::RenameEngine::new(config).naming_examples()?;
+    Ok(Json(examples))
+}
+
 /// Test Prowlarr connectivity endpoint
 async fn test_prowlarr_connection(
     State(state): State<SimpleApiState>,
@@ -822,6 +1685,77 @@ async fn start_download(
     ))
 }
 
+/// Manual grab request for a release returned by a previous search
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GrabReleaseRequest {
+    guid: String,
+    movie_id: Option<Uuid>,
+}
+
+/// Push a specific release to the download client, bypassing the decision engine
+async fn grab_release(
+    State(state): State<SimpleApiState>,
+    Json(request): Json<GrabReleaseRequest>,
+) -> ApiResult<(StatusCode, Json<Value>)> {
+    let Some(release) = state.quality_state.release_cache.get(&request.guid) else {
+        return Err(ApiError::NotFound {
+            resource: format!(
+                "release with guid '{}' in recent search results",
+                request.guid
+            ),
+        });
+    };
+
+    let Some(download_url) = release.download_url.clone() else {
+        return Err(ApiError::UnprocessableEntity {
+            message: format!("Release '{}' has no download URL", request.guid),
+        });
+    };
+
+    let Some(download_client) = state.download_client.as_ref() else {
+        return Err(ApiError::ServiceUnavailable {
+            message: "No download client configured".to_string(),
+        });
+    };
+
+    let download_client_id = download_client
+        .add_download(&download_url, None, None)
+        .await
+        .inspect_err(|e| error!("Manual grab of '{}' failed: {}", release.title, e))?;
+
+    info!(
+        "Manually grabbed '{}' from {} (client id {})",
+        release.title, release.indexer, download_client_id
+    );
+
+    if let Some(event_bus) = &state.event_bus {
+        let event = SystemEvent::OverrideGrab {
+            movie_id: request.movie_id,
+            guid: release.guid.clone(),
+            title: release.title.clone(),
+            indexer: release.indexer.clone(),
+            download_client_id: download_client_id.clone(),
+        };
+        if let Err(e) = event_bus.publish(event).await {
+            warn!("Failed to publish manual grab event: {}", e);
+        }
+    }
+
+    Ok((
+        StatusCode::CREATED,
+        Json(serde_json::json!({
+            "guid": release.guid,
+            "title": release.title,
+            "indexer": release.indexer,
+            "movieId": request.movie_id,
+            "downloadClientId": download_client_id,
+            "customFormatScore": release.custom_format_score,
+            "override": true,
+        })),
+    ))
+}
+
 /// Import download endpoint - implements basic import pipeline
 async fn import_download(
     State(_state): State<SimpleApiState>,
@@ -886,6 +1820,21 @@ async fn import_download(
     Ok(Json(mock_response))
 }
 
+/// Read replica status endpoint
+async fn replica_status(
+    State(state): State<SimpleApiState>,
+) -> Json<radarr_infrastructure::ReplicaStatus> {
+    Json(state.read_pool.status())
+}
+
+/// Schema migrations applied to the database and still pending
+async fn migration_status(
+    State(state): State<SimpleApiState>,
+) -> ApiResult<Json<radarr_infrastructure::MigrationStatus>> {
+    let status = radarr_infrastructure::migrations::migration_status(&state.database_pool).await?;
+    Ok(Json(status))
+}
+
 /// Circuit breaker status endpoint - shows all circuit breaker states
 async fn circuit_breaker_status(
     State(state): State<SimpleApiState>,
@@ -1187,6 +2136,170 @@ fn extract_scene_group_simple(torrent_name: &str) -> Option<String> {
     None
 }
 
+/// Stable identifier for a search result within the API
+fn search_result_guid(result: &ProwlarrSearchResult) -> String {
+    format!(
+        "{}-{}",
+        result.indexer_id,
+        result.title.chars().take(20).collect::<String>()
+    )
+}
+
+/// Build a decision engine release from an indexer search result
+fn decision_release_from_search_result(result: &ProwlarrSearchResult) -> radarr_decision::Release {
+    let mut release =
+        radarr_decision::Release::from_title(result.title.clone(), result.download_url.clone());
+
+    if let Some(size) = result.size.and_then(|size| u64::try_from(size).ok()) {
+        release = release.with_size(size);
+    }
+    if let Some(seeders) = result
+        .seeders
+        .and_then(|seeders| u32::try_from(seeders).ok())
+    {
+        release = release.with_seeders(seeders);
+    }
+    if let Some(leechers) = result
+        .leechers
+        .and_then(|leechers| u32::try_from(leechers).ok())
+    {
+        release = release.with_leechers(leechers);
+    }
+    if let Some(publish_date) = result.publish_date {
+        release = release.with_publish_date(publish_date);
+    }
+    if let Some(freeleech) = result.freeleech {
+        release = release.with_freeleech(freeleech);
+    }
+    if let Some(group) = result.title.rsplit('-').next() {
+        release = release.with_release_group(group.trim().to_string());
+    }
+
+    release
+}
+
+/// Rejection reasons for each search result, in result order
+///
+/// Runs the decision engine against the movie's existing file (when a movie
+/// is given) and checks the blocklist. Lookup failures are logged and treated
+/// as "no rejection" so search results are never hidden by a side check.
+async fn search_result_rejections(
+    state: &SimpleApiState,
+    results: &[ProwlarrSearchResult],
+    movie_id: Option<Uuid>,
+    format_engine: &radarr_decision::CustomFormatEngine,
+) -> Vec<Vec<String>> {
+    use radarr_core::blocklist::BlocklistService;
+    use radarr_core::domain::repositories::MovieFileRepository;
+    use radarr_decision::{DecisionEngine, ExistingFile, RejectionReason};
+    use radarr_infrastructure::{PostgresBlocklistRepository, PostgresMovieFileRepository};
+
+    let existing = match movie_id {
+        Some(movie_id) => {
+            match PostgresMovieFileRepository::new(state.database_pool.clone())
+                .list(Some(movie_id))
+                .await
+            {
+                Ok(files) => radarr_core::MovieFile::current_per_edition(files)
+                    .iter()
+                    .map(ExistingFile::from_movie_file)
+                    .collect(),
+                Err(e) => {
+                    warn!(
+                        "Failed to load existing files for movie {}: {}",
+                        movie_id, e
+                    );
+                    Vec::new()
+                }
+            }
+        }
+        None => Vec::new(),
+    };
+
+    let mut engine = DecisionEngine::new(radarr_decision::QualityProfile::default())
+        .with_custom_formats(radarr_decision::CustomFormatEngine::with_formats(
+            format_engine.formats.clone(),
+        ))
+        .with_proper_handling(state.proper_handling)
+        .with_language_profile(state.language_profile.clone());
+    // Interactive search lists releases of any age
+    engine.max_age_hours = None;
+
+    // Grabs for a nearly full root folder would wait as pending, and rating
+    // custom formats match the movie's ratings
+    if let Some(movie_id) = movie_id {
+        match state.movie_repo.find_by_id(movie_id).await {
+            Ok(Some(movie)) => {
+                let hold = movie
+                    .root_folder_path()
+                    .and_then(|path| state.root_folder_state.storage_throttle.hold_for(path));
+                engine = engine
+                    .with_storage_hold(hold)
+                    .with_movie_ratings(movie.ratings);
+            }
+            Ok(None) => {}
+            Err(e) => warn!("Failed to load movie {}: {}", movie_id, e),
+        }
+    }
+
+    let blocklist = BlocklistService::new(Arc::new(PostgresBlocklistRepository::new(
+        state.database_pool.clone(),
+    )));
+    let mut check_blocklist = true;
+
+    let mut all_rejections = Vec::with_capacity(results.len());
+    for result in results {
+        let release = decision_release_from_search_result(result);
+        let existing_file = ExistingFile::for_release(&existing, &release);
+        let mut score = engine.assess_release(&release, existing_file);
+
+        if check_blocklist {
+            match blocklist
+                .is_release_blocked(&search_result_guid(result), &result.indexer)
+                .await
+            {
+                Ok(true) => score.rejections.push(RejectionReason::Blocklisted),
+                Ok(false) => {}
+                Err(e) => {
+                    warn!("Blocklist unavailable, skipping blocklist checks: {}", e);
+                    check_blocklist = false;
+                }
+            }
+        }
+
+        all_rejections.push(score.rejections.iter().map(ToString::to_string).collect());
+    }
+
+    all_rejections
+}
+
+/// Build custom format release data from an indexer search result for a
+/// movie with `movie_ratings`
+fn release_data_from_search_result(
+    result: &ProwlarrSearchResult,
+    movie_ratings: Option<radarr_core::MovieRatings>,
+) -> radarr_decision::ReleaseData {
+    radarr_decision::ReleaseData {
+        title: result.title.clone(),
+        size_bytes: result.size.and_then(|size| u64::try_from(size).ok()),
+        seeders: result
+            .seeders
+            .and_then(|seeders| u32::try_from(seeders).ok()),
+        leechers: result
+            .leechers
+            .and_then(|leechers| u32::try_from(leechers).ok()),
+        freeleech: result.freeleech,
+        internal: Some(result.title.to_lowercase().contains("internal")),
+        indexer: result.indexer.clone(),
+        release_group: result
+            .title
+            .split('-')
+            .next_back()
+            .map(|group| group.trim().to_string()),
+        movie_ratings,
+    }
+}
+
 /// Enhanced quality scoring using HDBits scene group intelligence  
 /// Provides superior quality assessment over basic metadata extraction
 fn calculate_quality_score(title: &str) -> i32 {
@@ -1306,36 +2419,24 @@ fn calculate_resolution_score(title_lower: &str) -> i32 {
     }
 }
 
-/// Premium audio format detection
+/// Premium audio format detection, by the best audio codec in the title
 fn detect_premium_audio(title_lower: &str) -> i32 {
-    let mut bonus = 0;
-
-    // Dolby Atmos
-    if title_lower.contains("atmos") {
-        bonus += 12;
-    }
-
-    // TrueHD/DTS-HD MA (lossless)
-    if title_lower.contains("truehd") || title_lower.contains("dts.hd.ma") {
-        bonus += 10;
-    }
-
-    // DTS-X
-    if title_lower.contains("dts.x") || title_lower.contains("dtsx") {
-        bonus += 8;
-    }
-
-    // DTS (lossy but good)
-    if title_lower.contains("dts") && !title_lower.contains("dts.hd") {
-        bonus += 5;
-    }
+    AudioCodec::parse(title_lower).map_or(0, audio_bonus)
+}
 
-    // DD+ (Dolby Digital Plus)
-    if title_lower.contains("ddp") || title_lower.contains("dd+") {
-        bonus += 4;
+/// Score bonus of an audio codec
+fn audio_bonus(codec: AudioCodec) -> i32 {
+    match codec {
+        AudioCodec::TrueHdAtmos => 22, // Lossless with Atmos objects
+        AudioCodec::Eac3Atmos => 16,   // Atmos over lossy DD+
+        AudioCodec::DtsX => 13,
+        AudioCodec::TrueHd | AudioCodec::DtsHdMa => 10, // Lossless
+        AudioCodec::Flac | AudioCodec::Pcm => 8,
+        AudioCodec::DtsHd => 7,
+        AudioCodec::Dts => 5,
+        AudioCodec::Eac3 => 4,
+        AudioCodec::Ac3 | AudioCodec::Opus | AudioCodec::Aac => 0,
     }
-
-    bonus
 }
 
 /// Source quality assessment with premium format detection
@@ -1363,16 +2464,18 @@ fn calculate_source_score(title_lower: &str) -> i32 {
 
 /// Advanced encoding assessment
 fn calculate_encoding_score(title_lower: &str) -> i32 {
-    if title_lower.contains("av1") {
-        15 // Next-gen codec, excellent efficiency
-    } else if title_lower.contains("x265") || title_lower.contains("hevc") {
-        12 // Modern efficient codec
-    } else if title_lower.contains("x264") || title_lower.contains("h.264") {
-        8 // Mature reliable codec
-    } else if title_lower.contains("xvid") {
-        3 // Older codec
-    } else {
-        0
+    VideoCodec::parse(title_lower).map_or(0, encoding_score)
+}
+
+/// Score of a video codec by its efficiency
+fn encoding_score(codec: VideoCodec) -> i32 {
+    match codec {
+        VideoCodec::H266 => 16,   // Newest standard, best efficiency
+        VideoCodec::Av1(_) => 15, // Next-gen royalty-free codec
+        VideoCodec::H265 => 12,   // Modern efficient codec
+        VideoCodec::Vp9 => 10,
+        VideoCodec::H264 => 8, // Mature reliable codec
+        VideoCodec::Xvid => 3, // Older codec
     }
 }
 
@@ -1519,78 +2622,51 @@ fn detect_source(title_lower: &str) -> serde_json::Value {
 
 /// Comprehensive codec detection
 fn detect_codec(title_lower: &str) -> serde_json::Value {
-    if title_lower.contains("av1") {
-        serde_json::json!({
-            "name": "AV1",
-            "generation": "Next-Gen",
-            "efficiency": "Excellent",
-            "score": 15
-        })
-    } else if title_lower.contains("x265") || title_lower.contains("hevc") {
-        serde_json::json!({
-            "name": "x265/HEVC",
-            "generation": "Modern",
-            "efficiency": "High",
-            "score": 12
-        })
-    } else if title_lower.contains("x264") || title_lower.contains("h.264") {
-        serde_json::json!({
-            "name": "x264/H.264",
-            "generation": "Mature",
-            "efficiency": "Good",
-            "score": 8
-        })
-    } else {
-        serde_json::json!({
+    match VideoCodec::parse(title_lower) {
+        Some(codec) => serde_json::json!({
+            "name": codec.name(),
+            "generation": codec.generation(),
+            "efficiency": match codec.score() {
+                s if s >= 75 => "Excellent",
+                s if s >= 70 => "High",
+                s if s >= 50 => "Good",
+                _ => "Low",
+            },
+            "score": encoding_score(codec)
+        }),
+        None => serde_json::json!({
             "name": "Unknown",
             "generation": "Unknown",
             "efficiency": "Unknown",
             "score": 0
-        })
+        }),
     }
 }
 
 /// Detect all audio formats present
 fn detect_audio_formats(title_lower: &str) -> Vec<serde_json::Value> {
-    let mut formats = Vec::new();
-
-    if title_lower.contains("atmos") {
-        formats.push(serde_json::json!({
-            "name": "Dolby Atmos",
-            "type": "Object-based surround",
-            "quality": "Premium",
-            "score": 12
-        }));
-    }
-
-    if title_lower.contains("truehd") {
-        formats.push(serde_json::json!({
-            "name": "Dolby TrueHD",
-            "type": "Lossless",
-            "quality": "Premium",
-            "score": 10
-        }));
-    }
-
-    if title_lower.contains("dts.hd.ma") {
-        formats.push(serde_json::json!({
-            "name": "DTS-HD MA",
-            "type": "Lossless",
-            "quality": "Premium",
-            "score": 10
-        }));
-    }
-
-    if title_lower.contains("dts.x") || title_lower.contains("dtsx") {
-        formats.push(serde_json::json!({
-            "name": "DTS:X",
-            "type": "Object-based surround",
-            "quality": "High",
-            "score": 8
-        }));
-    }
-
-    formats
+    AudioCodec::parse_all(title_lower)
+        .into_iter()
+        .map(|codec| {
+            let kind = if codec.is_object_based() {
+                "Object-based surround"
+            } else if codec.is_lossless() {
+                "Lossless"
+            } else {
+                "Lossy"
+            };
+            serde_json::json!({
+                "name": codec.name(),
+                "type": kind,
+                "quality": match audio_bonus(codec) {
+                    b if b >= 10 => "Premium",
+                    b if b >= 5 => "High",
+                    _ => "Standard",
+                },
+                "score": audio_bonus(codec)
+            })
+        })
+        .collect()
 }
 
 /// Comprehensive HDR information detection
@@ -1954,291 +3030,10 @@ fn create_mock_search_response() -> Value {
     })
 }
 
-// ============================================================================
-// QUALITY PROFILE ENDPOINTS
-// ============================================================================
-
-/// Quality profile for API responses
-#[derive(Debug, Serialize)]
-struct QualityProfileResponse {
-    pub id: i32,
-    pub name: String,
-    pub cutoff: i32,
-    pub items: Vec<QualityItemResponse>,
-    pub min_format_score: i32,
-    pub cutoff_format_score: i32,
-    pub format_items: Vec<FormatItemResponse>,
-}
-
-#[derive(Debug, Serialize)]
-struct QualityItemResponse {
-    pub quality: QualityResponse,
-    pub allowed: bool,
-}
-
-#[derive(Debug, Serialize)]
-struct QualityResponse {
-    pub id: i32,
-    pub name: String,
-    pub source: String,
-    pub resolution: i32,
-}
-
-#[derive(Debug, Serialize)]
-struct FormatItemResponse {
-    pub format: CustomFormatResponse,
-    pub name: String,
-    pub score: i32,
-}
-
-#[derive(Debug, Serialize)]
-struct CustomFormatResponse {
-    pub id: i32,
-    pub name: String,
-    pub include_custom_format_when_renaming: bool,
-}
-
-/// GET /v3/qualityprofile - List all quality profiles
-async fn list_quality_profiles_simple(
-    State(_state): State<SimpleApiState>,
-) -> Json<Vec<QualityProfileResponse>> {
-    let default_profiles = vec![
-        QualityProfileResponse {
-            id: 1,
-            name: "HD-1080p".to_string(),
-            cutoff: 7,
-            items: vec![
-                QualityItemResponse {
-                    quality: QualityResponse {
-                        id: 4,
-                        name: "HDTV-720p".to_string(),
-                        source: "Television".to_string(),
-                        resolution: 720,
-                    },
-                    allowed: true,
-                },
-                QualityItemResponse {
-                    quality: QualityResponse {
-                        id: 6,
-                        name: "Bluray-720p".to_string(),
-                        source: "BluRay".to_string(),
-                        resolution: 720,
-                    },
-                    allowed: true,
-                },
-                QualityItemResponse {
-                    quality: QualityResponse {
-                        id: 7,
-                        name: "WEBDL-1080p".to_string(),
-                        source: "WebDL".to_string(),
-                        resolution: 1080,
-                    },
-                    allowed: true,
-                },
-                QualityItemResponse {
-                    quality: QualityResponse {
-                        id: 8,
-                        name: "Bluray-1080p".to_string(),
-                        source: "BluRay".to_string(),
-                        resolution: 1080,
-                    },
-                    allowed: true,
-                },
-            ],
-            min_format_score: 0,
-            cutoff_format_score: 0,
-            format_items: vec![],
-        },
-        QualityProfileResponse {
-            id: 2,
-            name: "Ultra-HD".to_string(),
-            cutoff: 19,
-            items: vec![
-                QualityItemResponse {
-                    quality: QualityResponse {
-                        id: 18,
-                        name: "WEBDL-2160p".to_string(),
-                        source: "WebDL".to_string(),
-                        resolution: 2160,
-                    },
-                    allowed: true,
-                },
-                QualityItemResponse {
-                    quality: QualityResponse {
-                        id: 19,
-                        name: "Bluray-2160p".to_string(),
-                        source: "BluRay".to_string(),
-                        resolution: 2160,
-                    },
-                    allowed: true,
-                },
-            ],
-            min_format_score: 0,
-            cutoff_format_score: 0,
-            format_items: vec![],
-        },
-    ];
-
-    Json(default_profiles)
-}
-
-/// GET /v3/qualityprofile/{id} - Get specific quality profile
-async fn get_quality_profile_simple(
-    State(_state): State<SimpleApiState>,
-    Path(id): Path<i32>,
-) -> Result<Json<QualityProfileResponse>, StatusCode> {
-    let profile = match id {
-        1 => QualityProfileResponse {
-            id: 1,
-            name: "HD-1080p".to_string(),
-            cutoff: 7,
-            items: vec![
-                QualityItemResponse {
-                    quality: QualityResponse {
-                        id: 7,
-                        name: "WEBDL-1080p".to_string(),
-                        source: "WebDL".to_string(),
-                        resolution: 1080,
-                    },
-                    allowed: true,
-                },
-                QualityItemResponse {
-                    quality: QualityResponse {
-                        id: 8,
-                        name: "Bluray-1080p".to_string(),
-                        source: "BluRay".to_string(),
-                        resolution: 1080,
-                    },
-                    allowed: true,
-                },
-            ],
-            min_format_score: 0,
-            cutoff_format_score: 0,
-            format_items: vec![],
-        },
-        2 => QualityProfileResponse {
-            id: 2,
-            name: "Ultra-HD".to_string(),
-            cutoff: 19,
-            items: vec![
-                QualityItemResponse {
-                    quality: QualityResponse {
-                        id: 18,
-                        name: "WEBDL-2160p".to_string(),
-                        source: "WebDL".to_string(),
-                        resolution: 2160,
-                    },
-                    allowed: true,
-                },
-                QualityItemResponse {
-                    quality: QualityResponse {
-                        id: 19,
-                        name: "Bluray-2160p".to_string(),
-                        source: "BluRay".to_string(),
-                        resolution: 2160,
-                    },
-                    allowed: true,
-                },
-            ],
-            min_format_score: 0,
-            cutoff_format_score: 0,
-            format_items: vec![],
-        },
-        _ => return Err(StatusCode::NOT_FOUND),
-    };
-
-    Ok(Json(profile))
-}
-
 // ============================================================================
 // QUEUE ENDPOINTS
 // ============================================================================
 
-/// Queue item for API responses (compatible with frontend expectations)
-#[derive(Debug, Serialize)]
-struct QueueItemSimpleResponse {
-    pub id: String,
-    #[serde(rename = "movieId")]
-    pub movie_id: i32,
-    #[serde(rename = "movieTitle")]
-    pub movie_title: String,
-    pub quality: String,
-    pub protocol: String,
-    pub indexer: String,
-    #[serde(rename = "downloadClient")]
-    pub download_client: String,
-    pub status: String,
-    pub size: i64,
-    #[serde(rename = "sizeLeft")]
-    pub size_left: i64,
-    #[serde(rename = "downloadedSize")]
-    pub downloaded_size: i64,
-    pub progress: f64,
-    #[serde(rename = "downloadRate")]
-    pub download_rate: Option<u64>,
-    #[serde(rename = "uploadRate")]
-    pub upload_rate: Option<u64>,
-    pub seeders: Option<i32>,
-    pub leechers: Option<i32>,
-    pub eta: Option<String>,
-    #[serde(rename = "errorMessage")]
-    pub error_message: Option<String>,
-    pub added: String,
-}
-
-#[derive(Debug, Serialize)]
-struct QueueResponseSimple {
-    pub records: Vec<QueueItemSimpleResponse>,
-    #[serde(rename = "totalRecords")]
-    pub total_records: i32,
-    pub page: i32,
-    #[serde(rename = "pageSize")]
-    pub page_size: i32,
-}
-
-/// GET /v3/queue - List queue items
-async fn list_queue_simple(State(_state): State<SimpleApiState>) -> Json<QueueResponseSimple> {
-    // Return mock queue data
-    let mock_items = vec![QueueItemSimpleResponse {
-        id: Uuid::new_v4().to_string(),
-        movie_id: 1,
-        movie_title: "The Matrix".to_string(),
-        quality: "Bluray-1080p".to_string(),
-        protocol: "torrent".to_string(),
-        indexer: "HDBits".to_string(),
-        download_client: "qBittorrent".to_string(),
-        status: "downloading".to_string(),
-        size: 8_000_000_000,            // 8GB
-        size_left: 2_000_000_000,       // 2GB
-        downloaded_size: 6_000_000_000, // 6GB
-        progress: 75.0,
-        download_rate: Some(1_048_576), // 1MB/s
-        upload_rate: Some(524_288),     // 512KB/s
-        seeders: Some(15),
-        leechers: Some(3),
-        eta: Some("00:32:00".to_string()),
-        error_message: None,
-        added: chrono::Utc::now().to_rfc3339(),
-    }];
-
-    Json(QueueResponseSimple {
-        records: mock_items,
-        total_records: 1,
-        page: 1,
-        page_size: 50,
-    })
-}
-
-/// DELETE /v3/queue/{id} - Remove queue item
-async fn remove_queue_item_simple(
-    State(_state): State<SimpleApiState>,
-    Path(id): Path<String>,
-) -> StatusCode {
-    info!("Removing queue item: {}", id);
-    // For MVP, always return success
-    StatusCode::OK
-}
-
 /// PUT /v3/queue/{id}/pause - Pause queue item
 async fn pause_queue_item_simple(
     State(_state): State<SimpleApiState>,
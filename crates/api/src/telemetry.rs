@@ -1,4 +1,7 @@
 use anyhow::Result;
+use radarr_core::logs::{install_log_store, LogLevel, LogStore, LogStoreConfig, LogStoreLayer};
+use radarr_core::trace_context::{install_trace_context_source, TraceContext, TraceContextLayer};
+use radarr_core::tracing::LogBufferLayer;
 use std::env;
 use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
 
@@ -32,7 +35,11 @@ pub struct TelemetryConfig {
     pub enable_logging: bool,
     pub enable_metrics: bool,
     pub enable_tracing: bool,
+    /// Export spans to `otlp_endpoint`; only on when the endpoint is set
+    pub export_traces: bool,
     pub log_level: String,
+    /// Log kept for `/api/v3/log`; `None` keeps nothing
+    pub log_store: Option<LogStoreConfig>,
 }
 
 impl Default for TelemetryConfig {
@@ -53,16 +60,62 @@ impl Default for TelemetryConfig {
             enable_tracing: env::var("OTEL_TRACES_ENABLED")
                 .map(|v| v.parse().unwrap_or(true))
                 .unwrap_or(true),
+            export_traces: env::var("OTEL_EXPORTER_OTLP_ENDPOINT").is_ok(),
             log_level: env::var("RUST_LOG").unwrap_or_else(|_| "info".to_string()),
+            log_store: log_store_config_from_env(),
         }
     }
 }
 
+/// Log store settings from `RADARR_LOG_STORE_ENABLED`, `RADARR_LOG_DIR` and
+/// `RADARR_LOG_STORE_LEVEL`
+fn log_store_config_from_env() -> Option<LogStoreConfig> {
+    let enabled = env::var("RADARR_LOG_STORE_ENABLED")
+        .map(|v| v.parse().unwrap_or(true))
+        .unwrap_or(true);
+    if !enabled {
+        return None;
+    }
+
+    let defaults = LogStoreConfig::default();
+    Some(LogStoreConfig {
+        directory: env::var("RADARR_LOG_DIR")
+            .map(Into::into)
+            .unwrap_or_else(|_| defaults.directory.clone()),
+        min_level: env::var("RADARR_LOG_STORE_LEVEL")
+            .ok()
+            .and_then(|level| LogLevel::parse(&level))
+            .unwrap_or(defaults.min_level),
+        ..defaults
+    })
+}
+
 /// Initialize telemetry with simplified configuration for MVP
 pub fn init_telemetry(config: TelemetryConfig) -> Result<()> {
     // For MVP, use simple JSON logging with structured fields
     let filter = EnvFilter::from_env("RUST_LOG");
 
+    // Stored log for /api/v3/log; the application runs on without it
+    let (log_store, log_store_error) = match config.log_store.clone().map(LogStore::open) {
+        Some(Ok(store)) => {
+            let store = std::sync::Arc::new(store);
+            install_log_store(store.clone());
+            (Some(store), None)
+        }
+        Some(Err(e)) => (None, Some(e)),
+        None => (None, None),
+    };
+
+    // Spans reach Jaeger through the OTLP collector when one is configured;
+    // either way outbound calls carry a traceparent header
+    let otel_layer = if config.enable_tracing && config.export_traces {
+        let tracer = init_tracer(&config)?;
+        install_trace_context_source(otel_trace_context);
+        Some(tracing_opentelemetry::layer().with_tracer(tracer))
+    } else {
+        None
+    };
+
     tracing_subscriber::registry()
         .with(
             fmt::layer()
@@ -73,9 +126,20 @@ pub fn init_telemetry(config: TelemetryConfig) -> Result<()> {
                 .with_line_number(true)
                 .json(),
         )
+        // Keeps correlated log lines for import forensic bundles
+        .with(LogBufferLayer::new())
+        // Warnings and errors for /api/v3/log
+        .with(log_store.map(LogStoreLayer::new))
+        // Trace IDs for the traceparent header of outbound calls
+        .with(config.enable_tracing.then_some(TraceContextLayer))
+        .with(otel_layer)
         .with(filter)
         .init();
 
+    if let Some(e) = log_store_error {
+        tracing::warn!("Log store unavailable, /api/v3/log stays empty: {}", e);
+    }
+
     tracing::info!(
         service.name = config.service.name,
         service.version = config.service.version,
@@ -87,10 +151,49 @@ pub fn init_telemetry(config: TelemetryConfig) -> Result<()> {
     Ok(())
 }
 
-// Simplified for MVP - full OpenTelemetry integration can be added later
+/// OTLP span exporter describing this instance
+fn init_tracer(config: &TelemetryConfig) -> Result<opentelemetry_sdk::trace::Tracer> {
+    use opentelemetry::KeyValue;
+    use opentelemetry_otlp::WithExportConfig;
+
+    let resource = opentelemetry_sdk::Resource::new(vec![
+        KeyValue::new("service.name", config.service.name),
+        KeyValue::new("service.version", config.service.version),
+        KeyValue::new("deployment.environment", config.service.environment.clone()),
+        KeyValue::new("service.instance.id", config.service.instance_id.clone()),
+    ]);
+    let tracer = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(
+            opentelemetry_otlp::new_exporter()
+                .tonic()
+                .with_endpoint(config.otlp_endpoint.clone()),
+        )
+        .with_trace_config(opentelemetry_sdk::trace::config().with_resource(resource))
+        .install_batch(opentelemetry_sdk::runtime::Tokio)?;
+    Ok(tracer)
+}
+
+/// Trace context of the current span as exported, for outbound calls
+fn otel_trace_context() -> Option<TraceContext> {
+    use opentelemetry::trace::TraceContextExt;
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+    let context = tracing::Span::current().context();
+    let span = context.span();
+    let span_context = span.span_context();
+    span_context.is_valid().then(|| TraceContext {
+        trace_id: u128::from_be_bytes(span_context.trace_id().to_bytes()),
+        span_id: u64::from_be_bytes(span_context.span_id().to_bytes()),
+        sampled: span_context.is_sampled(),
+    })
+}
 
 /// Shutdown telemetry gracefully
+///
+/// Spans still waiting to be exported are flushed first.
 pub fn shutdown_telemetry() {
+    opentelemetry::global::shutdown_tracer_provider();
     tracing::info!("Telemetry shutdown complete");
 }
 
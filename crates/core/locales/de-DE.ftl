@@ -0,0 +1,86 @@
+# Radarr user-facing strings (German, Germany)
+
+## Notifications
+
+notification-movie-added-title = Film hinzugefügt: { $title }
+notification-movie-added = 🎬 **{ $title }** ({ $year }) wurde deiner Bibliothek hinzugefügt
+notification-movie-deleted-title = Film entfernt: { $title }
+notification-movie-deleted = 🗑️ **{ $title }** wurde aus deiner Bibliothek entfernt
+notification-download-started-title = Download gestartet: { $movie_title }
+notification-download-started = ⬇️ Download von **{ $movie_title }** ({ $quality }) über { $indexer } gestartet
+notification-download-completed-title = Download abgeschlossen: { $movie_title }
+notification-download-completed = ✅ Download von **{ $movie_title }** ({ $quality }) abgeschlossen! Größe: { $size }
+notification-download-failed-title = Download fehlgeschlagen: { $movie_title }
+notification-download-failed = ❌ Download von **{ $movie_title }** ({ $quality }) fehlgeschlagen
+notification-import-started-title = Import gestartet: { $movie_title }
+notification-import-started = 📁 **{ $movie_title }** wird aus { $source_path } importiert
+notification-import-completed-title = Import abgeschlossen: { $movie_title }
+notification-import-completed = 📁 **{ $movie_title }** wurde nach { $destination_path } importiert
+notification-import-failed-title = Import fehlgeschlagen: { $movie_title }
+notification-import-failed = ❌ Import von **{ $movie_title }** fehlgeschlagen - { $error_message }
+notification-movie-upgraded-title = Film verbessert: { $title }
+notification-movie-upgraded = ⬆️ **{ $title }** ({ $year }) wurde durch ein besseres Release ersetzt
+notification-health-check-failed-title = Statusprüfung fehlgeschlagen: { $check_name }
+notification-health-check-failed = ⚠️ { $check_name } ist { $status }: { $error_message }
+notification-update-available-title = Update verfügbar: { $new_version }
+notification-update-available = 🆕 Version { $new_version } ist verfügbar (aktuell { $current_version })
+
+notification-digest-title = Tageszusammenfassung: { $date }
+notification-digest =
+    📰 Seit { $period_start }: { $movies_added } hinzugefügt, { $imports } importiert, { $upgrades } verbessert, { $failures } fehlgeschlagen, { $health_issues } Statusprobleme
+    { $details }
+notification-digest-section-added = Hinzugefügt
+notification-digest-section-imported = Importiert
+notification-digest-section-upgraded = Verbessert
+notification-digest-section-failed = Fehlgeschlagen
+notification-digest-section-health = Statusprobleme
+notification-digest-more = und { $count } weitere
+notification-digest-quiet = In deiner Bibliothek hat sich nichts geändert.
+
+notification-test-title = Testbenachrichtigung
+notification-test-message = Dies ist eine Testbenachrichtigung von Radarr MVP
+notification-test-check-name = { $provider }-Integration
+notification-test-check-ok = { $provider } ist korrekt konfiguriert
+
+notification-field-title = Titel
+notification-field-year = Jahr
+notification-field-tmdb-id = TMDB-ID
+notification-field-movie = Film
+notification-field-quality = Qualität
+notification-field-size = Größe
+notification-field-indexer = Indexer
+notification-field-download-client = Download-Client
+notification-field-progress = Fortschritt
+notification-field-destination = Ziel
+notification-value-unknown = Unbekannt
+notification-value-not-available = k. A.
+
+email-subject = [Radarr] { $title }
+email-label-subject = Betreff
+email-label-message = Nachricht
+email-label-event-type = Ereignistyp
+email-label-timestamp = Zeitpunkt
+email-footer = Diese E-Mail wurde von Radarr MVP gesendet
+
+## Health checks
+
+health-check-timed-out = Zeitüberschreitung bei der Statusprüfung
+health-request-failed = HTTP-Anfrage fehlgeschlagen: { $error }
+health-request-timed-out = Zeitüberschreitung der Anfrage
+health-unexpected-status = Unerwarteter Statuscode: { $status }
+health-http-error = HTTP-Fehler: { $status }
+health-indexer-check-failed = Statusprüfung des Indexers fehlgeschlagen
+health-rss-not-running = RSS-Überwachung läuft nicht
+health-config-drift = Konfiguration weicht an { $count } Stellen vom Sollzustand ab: { $details }
+health-update-available = Version { $version } ist verfügbar (installiert: { $current_version })
+health-check-failed = Statusprüfung konnte nicht ausgeführt werden: { $error }
+health-download-client-unavailable = Download-Client ist nicht erreichbar: { $error }
+health-root-folder-missing = Stammordner { $path } fehlt oder ist kein Verzeichnis
+health-disk-space-low = Stammordner { $path } hat { $free } frei, unter dem Schwellenwert von { $threshold }; neue Downloads warten
+health-import-failures = { $count } Importe sind in den letzten { $hours } Stunden fehlgeschlagen, zuletzt { $title }
+health-indexers-unavailable = Indexer sind nicht erreichbar: { $error }
+health-no-indexers-enabled = Keine Indexer aktiviert; Suchen finden nichts
+health-indexers-disabled = Indexer wegen Fehlern nicht verfügbar: { $names }
+health-all-indexers-disabled = Alle Indexer sind wegen Fehlern nicht verfügbar
+health-database-unavailable = Datenbank ist nicht erreichbar: { $error }
+health-database-pool-saturated = Datenbankverbindungen sind ausgeschöpft: { $busy } von { $max } belegt, { $acquire_ms } ms auf eine gewartet
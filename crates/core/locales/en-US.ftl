@@ -0,0 +1,89 @@
+# Radarr user-facing strings (English, United States)
+#
+# This catalog is the fallback for every other locale, so every message must
+# be defined here.
+
+## Notifications
+
+notification-movie-added-title = Movie Added: { $title }
+notification-movie-added = 🎬 **{ $title }** ({ $year }) has been added to your library
+notification-movie-deleted-title = Movie Removed: { $title }
+notification-movie-deleted = 🗑️ **{ $title }** has been removed from your library
+notification-download-started-title = Download Started: { $movie_title }
+notification-download-started = ⬇️ Started downloading **{ $movie_title }** ({ $quality }) from { $indexer }
+notification-download-completed-title = Download Complete: { $movie_title }
+notification-download-completed = ✅ **{ $movie_title }** ({ $quality }) download completed! Size: { $size }
+notification-download-failed-title = Download Failed: { $movie_title }
+notification-download-failed = ❌ Download failed for **{ $movie_title }** ({ $quality })
+notification-import-started-title = Import Started: { $movie_title }
+notification-import-started = 📁 Importing **{ $movie_title }** from { $source_path }
+notification-import-completed-title = Import Complete: { $movie_title }
+notification-import-completed = 📁 **{ $movie_title }** has been imported to your library at { $destination_path }
+notification-import-failed-title = Import Failed: { $movie_title }
+notification-import-failed = ❌ Failed to import **{ $movie_title }** - { $error_message }
+notification-movie-upgraded-title = Movie Upgraded: { $title }
+notification-movie-upgraded = ⬆️ **{ $title }** ({ $year }) has been upgraded to a better release
+notification-health-check-failed-title = Health Check Failed: { $check_name }
+notification-health-check-failed = ⚠️ { $check_name } is { $status }: { $error_message }
+notification-update-available-title = Update Available: { $new_version }
+notification-update-available = 🆕 Version { $new_version } is available (currently running { $current_version })
+
+notification-digest-title = Daily Digest: { $date }
+notification-digest =
+    📰 Since { $period_start }: { $movies_added } added, { $imports } imported, { $upgrades } upgraded, { $failures } failed, { $health_issues } health issues
+    { $details }
+notification-digest-section-added = Added
+notification-digest-section-imported = Imported
+notification-digest-section-upgraded = Upgraded
+notification-digest-section-failed = Failed
+notification-digest-section-health = Health issues
+notification-digest-more = and { $count } more
+notification-digest-quiet = Nothing changed in your library.
+
+notification-test-title = Test Notification
+notification-test-message = This is a test notification from Radarr MVP
+notification-test-check-name = { $provider } Integration
+notification-test-check-ok = { $provider } is configured correctly
+
+notification-field-title = Title
+notification-field-year = Year
+notification-field-tmdb-id = TMDB ID
+notification-field-movie = Movie
+notification-field-quality = Quality
+notification-field-size = Size
+notification-field-indexer = Indexer
+notification-field-download-client = Download Client
+notification-field-progress = Progress
+notification-field-destination = Destination
+notification-value-unknown = Unknown
+notification-value-not-available = N/A
+
+email-subject = [Radarr] { $title }
+email-label-subject = Subject
+email-label-message = Message
+email-label-event-type = Event Type
+email-label-timestamp = Timestamp
+email-footer = This email was sent by Radarr MVP
+
+## Health checks
+
+health-check-timed-out = Health check timed out
+health-request-failed = HTTP request failed: { $error }
+health-request-timed-out = Request timed out
+health-unexpected-status = Unexpected status code: { $status }
+health-http-error = HTTP error: { $status }
+health-indexer-check-failed = Indexer health check returned false
+health-rss-not-running = RSS monitoring tasks are not running
+health-config-drift = Configuration differs from the desired state in { $count } places: { $details }
+health-update-available = Version { $version } is available (running { $current_version })
+health-check-failed = Health check could not run: { $error }
+health-download-client-unavailable = Download client is unavailable: { $error }
+health-root-folder-missing = Root folder { $path } is missing or not a directory
+health-disk-space-low = Root folder { $path } has { $free } free, below the { $threshold } threshold; new grabs are pending
+health-import-failures = { $count } imports failed in the last { $hours } hours, most recently { $title }
+health-indexers-unavailable = Indexers cannot be reached: { $error }
+health-no-indexers-enabled = No indexers are enabled; searches will find nothing
+health-indexers-disabled = Indexers unavailable due to failures: { $names }
+health-all-indexers-disabled = All indexers are unavailable due to failures
+health-database-unavailable = Database cannot be reached: { $error }
+health-database-pool-saturated = Database connections are exhausted: { $busy } of { $max } in use, waited { $acquire_ms } ms for one
@@ -15,11 +15,11 @@ use tracing::{debug, info, warn};
 use std::collections::HashMap;
 
 /// Service for managing blocked releases and failure handling
-pub struct BlocklistService<R: BlocklistRepository> {
+pub struct BlocklistService<R: BlocklistRepository + ?Sized> {
     repository: Arc<R>,
 }
 
-impl<R: BlocklistRepository> BlocklistService<R> {
+impl<R: BlocklistRepository + ?Sized> BlocklistService<R> {
     /// Create a new blocklist service
     pub fn new(repository: Arc<R>) -> Self {
         Self { repository }
@@ -449,7 +449,7 @@ pub trait BlocklistIntegration {
 }
 
 #[async_trait]
-impl<R: BlocklistRepository> BlocklistIntegration for BlocklistService<R> {
+impl<R: BlocklistRepository + ?Sized> BlocklistIntegration for BlocklistService<R> {
     async fn report_failure(
         &self,
         release_id: &str,
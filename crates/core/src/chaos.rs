@@ -0,0 +1,264 @@
+//! Runtime fault injection ("chaos mode")
+//!
+//! Operators can make outbound calls slow or fail on purpose to check that
+//! their alerting and circuit-breaker settings react the way they expect,
+//! against the real binary in staging. Faults are injected by
+//! [`CircuitBreaker::call`](crate::circuit_breaker::CircuitBreaker::call),
+//! which every outbound client goes through, so an injected failure or a
+//! delay past the request timeout counts against the breaker like a real one.
+//!
+//! The hook is only compiled in with the `chaos` cargo feature; without it,
+//! a configured chaos mode is ignored. Configuration comes from the
+//! environment:
+//!
+//! - `RADARR_CHAOS_ENABLED`: `true` to turn chaos mode on
+//! - `RADARR_CHAOS_SERVICES`: comma-separated circuit-breaker service names
+//!   (e.g. `TMDB,HDBits`); all services when unset
+//! - `RADARR_CHAOS_ERROR_RATE`: probability (0-1) that a call fails
+//! - `RADARR_CHAOS_LATENCY_RATE`: probability (0-1) that a call is delayed
+//! - `RADARR_CHAOS_LATENCY_MS`: delay added to delayed calls
+
+use crate::{RadarrError, Result};
+use rand::Rng;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+use std::time::Duration;
+use tracing::warn;
+
+/// Faults to inject into outbound calls
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChaosConfig {
+    /// Services to inject into, matched case-insensitively; empty for all
+    pub services: Vec<String>,
+    /// Probability that a call fails without being made
+    pub error_rate: f64,
+    /// Probability that a call is delayed by `latency`
+    pub latency_rate: f64,
+    pub latency: Duration,
+}
+
+impl Default for ChaosConfig {
+    fn default() -> Self {
+        Self {
+            services: Vec::new(),
+            error_rate: 0.0,
+            latency_rate: 0.0,
+            latency: Duration::from_millis(2000),
+        }
+    }
+}
+
+impl ChaosConfig {
+    /// Read chaos mode settings; `None` unless `RADARR_CHAOS_ENABLED` is true
+    pub fn from_env() -> Result<Option<Self>> {
+        let enabled = std::env::var("RADARR_CHAOS_ENABLED")
+            .map(|value| value.trim().eq_ignore_ascii_case("true") || value.trim() == "1")
+            .unwrap_or(false);
+        if !enabled {
+            return Ok(None);
+        }
+
+        let mut config = Self::default();
+        if let Ok(services) = std::env::var("RADARR_CHAOS_SERVICES") {
+            config.services = services
+                .split(',')
+                .map(str::trim)
+                .filter(|service| !service.is_empty() && *service != "*")
+                .map(str::to_string)
+                .collect();
+        }
+        config.error_rate = parse_env("RADARR_CHAOS_ERROR_RATE", config.error_rate)?;
+        config.latency_rate = parse_env("RADARR_CHAOS_LATENCY_RATE", config.latency_rate)?;
+        config.latency = Duration::from_millis(parse_env(
+            "RADARR_CHAOS_LATENCY_MS",
+            config.latency.as_millis() as u64,
+        )?);
+
+        config.validate()?;
+        Ok(Some(config))
+    }
+
+    /// Check that the rates are probabilities
+    pub fn validate(&self) -> Result<()> {
+        for (field, rate) in [
+            ("RADARR_CHAOS_ERROR_RATE", self.error_rate),
+            ("RADARR_CHAOS_LATENCY_RATE", self.latency_rate),
+        ] {
+            if !(0.0..=1.0).contains(&rate) {
+                return Err(RadarrError::ConfigurationError {
+                    field: field.to_string(),
+                    message: format!("Must be between 0 and 1, got {}", rate),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether faults are injected into calls to `service`
+    pub fn targets(&self, service: &str) -> bool {
+        self.services.is_empty()
+            || self
+                .services
+                .iter()
+                .any(|target| target.eq_ignore_ascii_case(service))
+    }
+}
+
+fn parse_env<T: std::str::FromStr>(name: &str, default: T) -> Result<T> {
+    match std::env::var(name) {
+        Ok(value) => value
+            .trim()
+            .parse()
+            .map_err(|_| RadarrError::ConfigurationError {
+                field: name.to_string(),
+                message: format!("Invalid value: {}", value),
+            }),
+        Err(_) => Ok(default),
+    }
+}
+
+/// Counts of injected faults
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChaosStats {
+    pub delayed_calls: u64,
+    pub failed_calls: u64,
+}
+
+/// Injects the faults of a [`ChaosConfig`] into outbound calls
+#[derive(Debug)]
+pub struct ChaosInjector {
+    config: ChaosConfig,
+    delayed_calls: AtomicU64,
+    failed_calls: AtomicU64,
+}
+
+impl ChaosInjector {
+    pub fn new(config: ChaosConfig) -> Self {
+        Self {
+            config,
+            delayed_calls: AtomicU64::new(0),
+            failed_calls: AtomicU64::new(0),
+        }
+    }
+
+    pub fn config(&self) -> &ChaosConfig {
+        &self.config
+    }
+
+    pub fn stats(&self) -> ChaosStats {
+        ChaosStats {
+            delayed_calls: self.delayed_calls.load(Ordering::Relaxed),
+            failed_calls: self.failed_calls.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Run `operation` for `service`, possibly delayed or replaced by a failure
+    pub async fn inject<F, T, E>(&self, service: &str, operation: F) -> Result<T>
+    where
+        F: Future<Output = std::result::Result<T, E>>,
+        E: Into<RadarrError>,
+    {
+        if self.config.targets(service) {
+            let (delay, fail) = {
+                let mut rng = rand::thread_rng();
+                (
+                    rng.gen_bool(self.config.latency_rate),
+                    rng.gen_bool(self.config.error_rate),
+                )
+            };
+
+            if delay {
+                self.delayed_calls.fetch_add(1, Ordering::Relaxed);
+                warn!(
+                    service = service,
+                    delay_ms = self.config.latency.as_millis() as u64,
+                    "Chaos mode: delaying outbound call"
+                );
+                tokio::time::sleep(self.config.latency).await;
+            }
+
+            if fail {
+                self.failed_calls.fetch_add(1, Ordering::Relaxed);
+                warn!(service = service, "Chaos mode: failing outbound call");
+                return Err(RadarrError::ExternalServiceError {
+                    service: service.to_string(),
+                    error: "Fault injected by chaos mode".to_string(),
+                });
+            }
+        }
+
+        operation.await.map_err(Into::into)
+    }
+}
+
+static GLOBAL_INJECTOR: OnceLock<ChaosInjector> = OnceLock::new();
+
+/// Turn chaos mode on for the process; only the first call has an effect
+pub fn install(config: ChaosConfig) -> &'static ChaosInjector {
+    GLOBAL_INJECTOR.get_or_init(|| ChaosInjector::new(config))
+}
+
+/// The process-wide injector, if chaos mode was turned on
+pub fn global() -> Option<&'static ChaosInjector> {
+    GLOBAL_INJECTOR.get()
+}
+
+/// Run `operation` through the process-wide injector, if any
+pub async fn wrap<F, T, E>(service: &str, operation: F) -> Result<T>
+where
+    F: Future<Output = std::result::Result<T, E>>,
+    E: Into<RadarrError>,
+{
+    match global() {
+        Some(injector) => injector.inject(service, operation).await,
+        None => operation.await.map_err(Into::into),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_injects_only_into_targeted_services() {
+        let injector = ChaosInjector::new(ChaosConfig {
+            services: vec!["tmdb".to_string()],
+            error_rate: 1.0,
+            latency_rate: 1.0,
+            latency: Duration::from_millis(1),
+        });
+
+        let result = injector
+            .inject("TMDB", async { Ok::<_, RadarrError>(42) })
+            .await;
+        assert!(matches!(
+            result,
+            Err(RadarrError::ExternalServiceError { .. })
+        ));
+        assert_eq!(
+            injector.stats(),
+            ChaosStats {
+                delayed_calls: 1,
+                failed_calls: 1
+            }
+        );
+
+        let result = injector
+            .inject("HDBits", async { Ok::<_, RadarrError>(42) })
+            .await;
+        assert_eq!(result.unwrap(), 42);
+        assert_eq!(injector.stats().failed_calls, 1);
+    }
+
+    #[test]
+    fn test_rates_must_be_probabilities() {
+        let config = ChaosConfig {
+            error_rate: 1.5,
+            ..ChaosConfig::default()
+        };
+        assert!(config.validate().is_err());
+        assert!(ChaosConfig::default().validate().is_ok());
+        assert!(ChaosConfig::default().targets("anything"));
+    }
+}
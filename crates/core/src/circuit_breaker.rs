@@ -220,6 +220,10 @@ impl CircuitBreaker {
 
         self.total_requests.fetch_add(1, Ordering::Relaxed);
 
+        // Chaos mode delays or fails calls inside the timeout, like a real outage
+        #[cfg(feature = "chaos")]
+        let operation = crate::chaos::wrap(&self.config.service_name, operation);
+
         // Execute the operation with timeout
         let start_time = Instant::now();
         let operation_result = tokio::time::timeout(self.config.request_timeout, operation).await;
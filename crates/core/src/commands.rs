@@ -0,0 +1,725 @@
+//! Command queue for long-running tasks
+//!
+//! Commands are tasks queued on demand, typically through
+//! `POST /api/v3/command`: refreshing movie metadata, an RSS sync, an import
+//! list sync or a scan of the download folder. Each command runs on a
+//! background task while its state, timestamps and progress are kept in memory
+//! so clients can poll it. What a command does is supplied by the
+//! [`CommandExecutor`] registered for its name; names without one are
+//! rejected when queued.
+//!
+//! With a [`CommandRepository`] the queue also stores every state change, and
+//! [`CommandQueue::recover`] picks up after a restart: commands still queued
+//! run again, commands that were running are marked failed.
+
+use crate::domain::repositories::CommandRepository;
+use crate::{RadarrError, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::Arc;
+use tokio::sync::{RwLock, Semaphore};
+use tracing::{info, warn};
+
+/// Finished commands kept for polling; older ones are dropped
+const MAX_FINISHED_COMMANDS: usize = 100;
+
+/// Message of commands that were running when the process stopped
+const INTERRUPTED_MESSAGE: &str = "Interrupted by restart";
+
+/// Commands that can be queued
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CommandName {
+    /// Refresh metadata of the movies in `movieIds`, or of every movie
+    RefreshMovie,
+    /// Check all RSS feeds now
+    RssSync,
+    /// Sync all enabled import lists now
+    ImportListSync,
+    /// Import completed downloads from `path`
+    DownloadedMoviesScan,
+    /// Measure root folder free space, holding or releasing grabs
+    CheckFreeSpace,
+    /// Search indexers for monitored movies without files
+    MissingMoviesSearch,
+    /// Search indexers for upgrades of movies below their quality cutoff
+    CutoffUnmetSearch,
+    /// Search indexers for the movies in `movieIds`
+    MoviesSearch,
+    /// Compare the configuration with the desired state spec, applying it
+    /// when auto-apply is on or the body has `"apply": true`
+    ReconcileConfig,
+    /// Remove imported torrents that met their seed goals
+    SeedingCleanup,
+    /// Add the movies already in the library folder `path`, or in every root
+    /// folder, without moving any files
+    LibraryImport,
+    /// Back up the configuration to the backups directory
+    Backup,
+    /// Look for a newer release
+    ApplicationUpdateCheck,
+    /// Move the folder of the movie `movieId` from below `sourceRoot` to its
+    /// root folder, renaming it to the naming templates with `renameFolder`
+    MoveMovie,
+    /// Remove orphaned entries from the downloads folder; `"dryRun": true`
+    /// only lists them
+    DownloadsCleanup,
+}
+
+impl CommandName {
+    /// All command names
+    pub const ALL: [CommandName; 15] = [
+        CommandName::RefreshMovie,
+        CommandName::RssSync,
+        CommandName::ImportListSync,
+        CommandName::DownloadedMoviesScan,
+        CommandName::CheckFreeSpace,
+        CommandName::MissingMoviesSearch,
+        CommandName::CutoffUnmetSearch,
+        CommandName::MoviesSearch,
+        CommandName::ReconcileConfig,
+        CommandName::SeedingCleanup,
+        CommandName::LibraryImport,
+        CommandName::Backup,
+        CommandName::ApplicationUpdateCheck,
+        CommandName::MoveMovie,
+        CommandName::DownloadsCleanup,
+    ];
+
+    /// Name used by the API
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CommandName::RefreshMovie => "RefreshMovie",
+            CommandName::RssSync => "RssSync",
+            CommandName::ImportListSync => "ImportListSync",
+            CommandName::DownloadedMoviesScan => "DownloadedMoviesScan",
+            CommandName::CheckFreeSpace => "CheckFreeSpace",
+            CommandName::MissingMoviesSearch => "MissingMoviesSearch",
+            CommandName::CutoffUnmetSearch => "CutoffUnmetSearch",
+            CommandName::MoviesSearch => "MoviesSearch",
+            CommandName::ReconcileConfig => "ReconcileConfig",
+            CommandName::SeedingCleanup => "SeedingCleanup",
+            CommandName::LibraryImport => "LibraryImport",
+            CommandName::Backup => "Backup",
+            CommandName::ApplicationUpdateCheck => "ApplicationUpdateCheck",
+            CommandName::MoveMovie => "MoveMovie",
+            CommandName::DownloadsCleanup => "DownloadsCleanup",
+        }
+    }
+
+    /// Parse a command name, ignoring case
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|name| name.as_str().eq_ignore_ascii_case(value))
+    }
+}
+
+impl std::fmt::Display for CommandName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Lifecycle state of a command
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CommandStatus {
+    Queued,
+    Started,
+    Completed,
+    Failed,
+}
+
+impl CommandStatus {
+    /// Whether the command has stopped running
+    pub fn is_finished(&self) -> bool {
+        matches!(self, CommandStatus::Completed | CommandStatus::Failed)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CommandStatus::Queued => "queued",
+            CommandStatus::Started => "started",
+            CommandStatus::Completed => "completed",
+            CommandStatus::Failed => "failed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "queued" => Some(CommandStatus::Queued),
+            "started" => Some(CommandStatus::Started),
+            "completed" => Some(CommandStatus::Completed),
+            "failed" => Some(CommandStatus::Failed),
+            _ => None,
+        }
+    }
+}
+
+/// What queued a command
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CommandTrigger {
+    Manual,
+    Scheduled,
+}
+
+impl CommandTrigger {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CommandTrigger::Manual => "manual",
+            CommandTrigger::Scheduled => "scheduled",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "manual" => Some(CommandTrigger::Manual),
+            "scheduled" => Some(CommandTrigger::Scheduled),
+            _ => None,
+        }
+    }
+}
+
+/// A queued, running or finished command
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Command {
+    pub id: i32,
+    pub name: CommandName,
+    /// Command arguments, e.g. `{"movieIds": [...]}`
+    pub body: serde_json::Value,
+    pub status: CommandStatus,
+    pub trigger: CommandTrigger,
+    /// Latest progress message, or the result or error once finished
+    pub message: Option<String>,
+    /// Progress percentage (0-100), when the executor reports it
+    pub progress: Option<f32>,
+    pub queued_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub ended_at: Option<DateTime<Utc>>,
+}
+
+impl Command {
+    fn new(id: i32, name: CommandName, body: serde_json::Value, trigger: CommandTrigger) -> Self {
+        Self {
+            id,
+            name,
+            body,
+            status: CommandStatus::Queued,
+            trigger,
+            message: None,
+            progress: None,
+            queued_at: Utc::now(),
+            started_at: None,
+            ended_at: None,
+        }
+    }
+
+    /// Time from start to end, or to now while running
+    pub fn duration(&self) -> Option<chrono::Duration> {
+        self.started_at
+            .map(|started| self.ended_at.unwrap_or_else(Utc::now) - started)
+    }
+
+    /// Time of the last state change
+    pub fn state_change_time(&self) -> DateTime<Utc> {
+        self.ended_at.or(self.started_at).unwrap_or(self.queued_at)
+    }
+}
+
+/// Runs commands of one name
+#[async_trait]
+pub trait CommandExecutor: Send + Sync {
+    /// Run `command`, returning a short result message
+    async fn execute(&self, command: &Command, progress: &CommandProgress) -> Result<String>;
+}
+
+/// Handle for an executor to report progress of its command
+pub struct CommandProgress {
+    id: i32,
+    queue: Arc<CommandQueue>,
+}
+
+impl CommandProgress {
+    /// Report progress as a percentage with a message
+    pub async fn update(&self, percentage: f32, message: impl Into<String>) {
+        let message = message.into();
+        self.queue
+            .update(self.id, |command| {
+                command.progress = Some(percentage.clamp(0.0, 100.0));
+                command.message = Some(message);
+            })
+            .await;
+    }
+}
+
+/// What [`CommandQueue::recover`] did with the stored commands
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CommandRecovery {
+    /// Commands loaded from the repository
+    pub loaded: usize,
+    /// Queued commands started again
+    pub resumed: usize,
+    /// Running commands marked failed
+    pub interrupted: usize,
+    /// Queued commands failed because their executor is gone
+    pub unavailable: usize,
+}
+
+/// Queue of commands with their executors
+pub struct CommandQueue {
+    commands: RwLock<VecDeque<Command>>,
+    executors: std::sync::RwLock<HashMap<CommandName, Arc<dyn CommandExecutor>>>,
+    next_id: AtomicI32,
+    slots: Semaphore,
+    repository: Option<Arc<dyn CommandRepository>>,
+}
+
+impl CommandQueue {
+    /// Create a queue running at most `max_concurrent` commands at once
+    pub fn new(max_concurrent: usize) -> Self {
+        Self {
+            commands: RwLock::new(VecDeque::new()),
+            executors: std::sync::RwLock::new(HashMap::new()),
+            next_id: AtomicI32::new(1),
+            slots: Semaphore::new(max_concurrent.max(1)),
+            repository: None,
+        }
+    }
+
+    /// Store commands in `repository` so they survive a restart
+    pub fn with_repository(mut self, repository: Arc<dyn CommandRepository>) -> Self {
+        self.repository = Some(repository);
+        self
+    }
+
+    /// Run commands named `name` with `executor`
+    pub fn register(&self, name: CommandName, executor: Arc<dyn CommandExecutor>) {
+        self.executors
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(name, executor);
+    }
+
+    fn executor(&self, name: CommandName) -> Option<Arc<dyn CommandExecutor>> {
+        self.executors
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&name)
+            .cloned()
+    }
+
+    /// Names with a registered executor
+    pub fn available(&self) -> Vec<CommandName> {
+        let executors = self.executors.read().unwrap_or_else(|e| e.into_inner());
+        CommandName::ALL
+            .into_iter()
+            .filter(|name| executors.contains_key(name))
+            .collect()
+    }
+
+    /// Queue a command and start it in the background
+    ///
+    /// An identical command (same name and body) that has not finished yet is
+    /// returned instead of queueing a second one.
+    pub async fn enqueue(
+        self: &Arc<Self>,
+        name: CommandName,
+        body: serde_json::Value,
+        trigger: CommandTrigger,
+    ) -> Result<Command> {
+        let executor = self
+            .executor(name)
+            .ok_or_else(|| RadarrError::ValidationError {
+                field: "name".to_string(),
+                message: format!("Command {} is not available", name),
+            })?;
+
+        let command = {
+            let mut commands = self.commands.write().await;
+            if let Some(existing) = commands
+                .iter()
+                .find(|c| c.name == name && c.body == body && !c.status.is_finished())
+            {
+                return Ok(existing.clone());
+            }
+
+            let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+            let command = Command::new(id, name, body, trigger);
+            commands.push_back(command.clone());
+            prune_finished(&mut commands);
+            command
+        };
+
+        info!("Queued command {} ({})", command.name, command.id);
+        self.persist(&command).await;
+        let queue = Arc::clone(self);
+        let id = command.id;
+        tokio::spawn(async move { queue.run(id, executor).await });
+
+        Ok(command)
+    }
+
+    /// A command by ID
+    pub async fn get(&self, id: i32) -> Option<Command> {
+        self.commands
+            .read()
+            .await
+            .iter()
+            .find(|command| command.id == id)
+            .cloned()
+    }
+
+    /// All known commands, newest first
+    pub async fn list(&self) -> Vec<Command> {
+        self.commands.read().await.iter().rev().cloned().collect()
+    }
+
+    /// Load the commands stored before a restart
+    ///
+    /// Commands still queued run again and commands that were running are
+    /// marked failed, since what they had done is unknown. Call once the
+    /// executors are registered and before anything is queued, as stored IDs
+    /// are reserved here.
+    pub async fn recover(self: &Arc<Self>) -> Result<CommandRecovery> {
+        let repository = match &self.repository {
+            Some(repository) => Arc::clone(repository),
+            None => return Ok(CommandRecovery::default()),
+        };
+
+        let stored = repository.load(MAX_FINISHED_COMMANDS as i64).await?;
+        let max_id = repository.max_id().await?;
+        self.next_id.fetch_max(max_id + 1, Ordering::Relaxed);
+
+        let mut recovery = CommandRecovery {
+            loaded: stored.len(),
+            ..CommandRecovery::default()
+        };
+        let mut changed = Vec::new();
+        let mut resume = Vec::new();
+        {
+            let mut commands = self.commands.write().await;
+            for mut command in stored {
+                if commands.iter().any(|c| c.id == command.id) {
+                    continue;
+                }
+
+                match command.status {
+                    CommandStatus::Started => {
+                        command.status = CommandStatus::Failed;
+                        command.ended_at = Some(Utc::now());
+                        command.message = Some(INTERRUPTED_MESSAGE.to_string());
+                        recovery.interrupted += 1;
+                        changed.push(command.clone());
+                    }
+                    CommandStatus::Queued => match self.executor(command.name) {
+                        Some(executor) => {
+                            recovery.resumed += 1;
+                            resume.push((command.id, executor));
+                        }
+                        None => {
+                            command.status = CommandStatus::Failed;
+                            command.ended_at = Some(Utc::now());
+                            command.message =
+                                Some(format!("Command {} is not available", command.name));
+                            recovery.unavailable += 1;
+                            changed.push(command.clone());
+                        }
+                    },
+                    CommandStatus::Completed | CommandStatus::Failed => {}
+                }
+                commands.push_back(command);
+            }
+            commands.make_contiguous().sort_by_key(|command| command.id);
+            prune_finished(&mut commands);
+        }
+
+        for command in &changed {
+            self.persist(command).await;
+        }
+        if let Err(e) = repository.prune(MAX_FINISHED_COMMANDS as i64).await {
+            warn!("Failed to prune stored commands: {}", e);
+        }
+        for (id, executor) in resume {
+            let queue = Arc::clone(self);
+            tokio::spawn(async move { queue.run(id, executor).await });
+        }
+
+        info!(
+            "Recovered {} stored commands: {} resumed, {} interrupted, {} unavailable",
+            recovery.loaded, recovery.resumed, recovery.interrupted, recovery.unavailable
+        );
+        Ok(recovery)
+    }
+
+    async fn update(&self, id: i32, apply: impl FnOnce(&mut Command)) -> Option<Command> {
+        let mut commands = self.commands.write().await;
+        let command = commands.iter_mut().find(|c| c.id == id)?;
+        apply(command);
+        Some(command.clone())
+    }
+
+    /// Store the state of `command`; failures are logged, not returned, so
+    /// an unreachable database doesn't stop commands from running
+    async fn persist(&self, command: &Command) {
+        if let Some(repository) = &self.repository {
+            if let Err(e) = repository.save(command).await {
+                warn!(
+                    "Failed to store command {} ({}): {}",
+                    command.name, command.id, e
+                );
+            }
+        }
+    }
+
+    async fn run(self: Arc<Self>, id: i32, executor: Arc<dyn CommandExecutor>) {
+        let _slot = match self.slots.acquire().await {
+            Ok(slot) => slot,
+            Err(_) => return, // Semaphore is never closed
+        };
+
+        let command = match self
+            .update(id, |command| {
+                command.status = CommandStatus::Started;
+                command.started_at = Some(Utc::now());
+            })
+            .await
+        {
+            Some(command) => command,
+            None => return,
+        };
+        self.persist(&command).await;
+
+        let progress = CommandProgress {
+            id,
+            queue: Arc::clone(&self),
+        };
+        let result = executor.execute(&command, &progress).await;
+
+        let finished = self
+            .update(id, |command| {
+                command.ended_at = Some(Utc::now());
+                match result {
+                    Ok(message) => {
+                        info!("Command {} ({}) completed: {}", command.name, id, message);
+                        command.status = CommandStatus::Completed;
+                        command.progress = Some(100.0);
+                        command.message = Some(message);
+                    }
+                    Err(e) => {
+                        warn!("Command {} ({}) failed: {}", command.name, id, e);
+                        command.status = CommandStatus::Failed;
+                        command.message = Some(e.to_string());
+                    }
+                }
+            })
+            .await;
+        if let Some(command) = finished {
+            self.persist(&command).await;
+        }
+    }
+}
+
+impl Default for CommandQueue {
+    fn default() -> Self {
+        Self::new(3)
+    }
+}
+
+/// Drop the oldest finished commands beyond the retention limit
+fn prune_finished(commands: &mut VecDeque<Command>) {
+    let mut finished = commands.iter().filter(|c| c.status.is_finished()).count();
+    commands.retain(|command| {
+        if finished > MAX_FINISHED_COMMANDS && command.status.is_finished() {
+            finished -= 1;
+            false
+        } else {
+            true
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    struct EchoExecutor;
+
+    #[async_trait]
+    impl CommandExecutor for EchoExecutor {
+        async fn execute(&self, command: &Command, progress: &CommandProgress) -> Result<String> {
+            progress.update(50.0, "Halfway").await;
+            match command.body.get("fail").and_then(|v| v.as_bool()) {
+                Some(true) => Err(RadarrError::ValidationError {
+                    field: "fail".to_string(),
+                    message: "asked to fail".to_string(),
+                }),
+                _ => Ok("Done".to_string()),
+            }
+        }
+    }
+
+    async fn wait_finished(queue: &CommandQueue, id: i32) -> Command {
+        for _ in 0..100 {
+            if let Some(command) = queue.get(id).await {
+                if command.status.is_finished() {
+                    return command;
+                }
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("command {} did not finish", id);
+    }
+
+    #[tokio::test]
+    async fn test_commands_run_to_completion() {
+        let queue = Arc::new(CommandQueue::default());
+        queue.register(CommandName::RssSync, Arc::new(EchoExecutor));
+
+        let queued = queue
+            .enqueue(
+                CommandName::RssSync,
+                serde_json::json!({}),
+                CommandTrigger::Manual,
+            )
+            .await
+            .unwrap();
+        let command = wait_finished(&queue, queued.id).await;
+        assert_eq!(command.status, CommandStatus::Completed);
+        assert_eq!(command.message.as_deref(), Some("Done"));
+        assert_eq!(command.progress, Some(100.0));
+        assert!(command.started_at.is_some() && command.ended_at.is_some());
+
+        let failed = queue
+            .enqueue(
+                CommandName::RssSync,
+                serde_json::json!({ "fail": true }),
+                CommandTrigger::Manual,
+            )
+            .await
+            .unwrap();
+        let command = wait_finished(&queue, failed.id).await;
+        assert_eq!(command.status, CommandStatus::Failed);
+        assert!(command.message.unwrap().contains("asked to fail"));
+
+        assert_eq!(queue.list().await[0].id, failed.id);
+    }
+
+    #[derive(Default)]
+    struct MemoryCommandRepository {
+        commands: std::sync::Mutex<Vec<Command>>,
+    }
+
+    #[async_trait]
+    impl CommandRepository for MemoryCommandRepository {
+        async fn save(&self, command: &Command) -> Result<()> {
+            let mut commands = self.commands.lock().unwrap();
+            commands.retain(|c| c.id != command.id);
+            commands.push(command.clone());
+            Ok(())
+        }
+
+        async fn load(&self, _finished_limit: i64) -> Result<Vec<Command>> {
+            Ok(self.commands.lock().unwrap().clone())
+        }
+
+        async fn max_id(&self) -> Result<i32> {
+            Ok(self
+                .commands
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|c| c.id)
+                .max()
+                .unwrap_or(0))
+        }
+
+        async fn prune(&self, _keep: i64) -> Result<u64> {
+            Ok(0)
+        }
+    }
+
+    fn stored(id: i32, name: CommandName, status: CommandStatus) -> Command {
+        Command {
+            status,
+            ..Command::new(id, name, serde_json::json!({}), CommandTrigger::Manual)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_recover_resumes_queued_and_fails_interrupted_commands() {
+        let repository = Arc::new(MemoryCommandRepository::default());
+        for command in [
+            stored(3, CommandName::RefreshMovie, CommandStatus::Completed),
+            stored(5, CommandName::DownloadedMoviesScan, CommandStatus::Started),
+            stored(7, CommandName::RssSync, CommandStatus::Queued),
+            stored(8, CommandName::ImportListSync, CommandStatus::Queued),
+        ] {
+            repository.save(&command).await.unwrap();
+        }
+
+        let queue = Arc::new(CommandQueue::default().with_repository(repository.clone()));
+        queue.register(CommandName::RssSync, Arc::new(EchoExecutor));
+        let recovery = queue.recover().await.unwrap();
+        assert_eq!(
+            recovery,
+            CommandRecovery {
+                loaded: 4,
+                resumed: 1,
+                interrupted: 1,
+                unavailable: 1,
+            }
+        );
+
+        let interrupted = queue.get(5).await.unwrap();
+        assert_eq!(interrupted.status, CommandStatus::Failed);
+        assert_eq!(interrupted.message.as_deref(), Some(INTERRUPTED_MESSAGE));
+        assert_eq!(queue.get(8).await.unwrap().status, CommandStatus::Failed);
+        assert_eq!(
+            wait_finished(&queue, 7).await.status,
+            CommandStatus::Completed
+        );
+
+        // Every state change reaches the repository, and IDs continue after it
+        let all_finished = || async {
+            let saved = repository.load(100).await.unwrap();
+            saved.iter().all(|command| command.status.is_finished())
+        };
+        for _ in 0..100 {
+            if all_finished().await {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert!(all_finished().await);
+        let next = queue
+            .enqueue(
+                CommandName::RssSync,
+                serde_json::json!({ "new": true }),
+                CommandTrigger::Manual,
+            )
+            .await
+            .unwrap();
+        assert_eq!(next.id, 9);
+    }
+
+    #[tokio::test]
+    async fn test_unavailable_commands_are_rejected() {
+        let queue = Arc::new(CommandQueue::default());
+        assert!(queue
+            .enqueue(
+                CommandName::ImportListSync,
+                serde_json::json!({}),
+                CommandTrigger::Manual,
+            )
+            .await
+            .is_err());
+        assert_eq!(CommandName::parse("rsssync"), Some(CommandName::RssSync));
+        assert_eq!(CommandName::parse("backup"), Some(CommandName::Backup));
+        assert_eq!(CommandName::parse("ApplicationUpdate"), None);
+    }
+}
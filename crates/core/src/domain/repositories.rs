@@ -27,7 +27,12 @@ pub trait MovieRepository: Send + Sync {
     /// Get movies without files
     async fn find_missing_files(&self) -> Result<Vec<Movie>>;
 
-    /// Search movies by title
+    /// Get movies added to the library at or after `since`, oldest first
+    async fn find_added_since(&self, since: chrono::DateTime<chrono::Utc>) -> Result<Vec<Movie>>;
+
+    /// Search movies by title, original title and alternative titles,
+    /// ignoring case, accents and punctuation and tolerating typos; closest
+    /// matches first
     async fn search_by_title(&self, query: &str, limit: i32) -> Result<Vec<Movie>>;
 
     /// Create a new movie
@@ -47,6 +52,9 @@ pub trait MovieRepository: Send + Sync {
 
     /// Update last search time
     async fn update_last_search_time(&self, id: Uuid) -> Result<()>;
+
+    /// Record an automatic search, resetting the failure count when a release was found
+    async fn record_search_result(&self, id: Uuid, found: bool) -> Result<()>;
 }
 
 /// Repository trait for Indexer entities
@@ -77,6 +85,16 @@ pub trait IndexerRepository: Send + Sync {
     async fn test_connection(&self, id: i32) -> Result<bool>;
 }
 
+/// Repository trait for the latest check of each indexer
+#[async_trait]
+pub trait IndexerCheckRepository: Send + Sync {
+    /// Store a check, replacing the indexer's previous one
+    async fn upsert(&self, check: &IndexerCheck) -> Result<()>;
+
+    /// Find the latest check of an indexer
+    async fn find_by_indexer_id(&self, indexer_id: i32) -> Result<Option<IndexerCheck>>;
+}
+
 /// Repository trait for QualityProfile entities
 #[async_trait]
 pub trait QualityProfileRepository: Send + Sync {
@@ -100,6 +118,246 @@ pub trait QualityProfileRepository: Send + Sync {
 
     /// Get the default quality profile
     async fn get_default(&self) -> Result<Option<QualityProfile>>;
+
+    /// Count movies assigned to a quality profile
+    async fn count_movies_using(&self, id: i32) -> Result<i64>;
+}
+
+/// Repository trait for MovieFile entities
+#[async_trait]
+pub trait MovieFileRepository: Send + Sync {
+    /// Find a movie file by its ID
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<MovieFile>>;
+
+    /// Find the current file for a movie
+    async fn find_by_movie_id(&self, movie_id: Uuid) -> Result<Option<MovieFile>>;
+
+    /// List movie files, newest first, optionally only those of one movie
+    async fn list(&self, movie_id: Option<Uuid>) -> Result<Vec<MovieFile>>;
+
+    /// Create a new movie file
+    async fn create(&self, file: &MovieFile) -> Result<MovieFile>;
+
+    /// Update an existing movie file
+    async fn update(&self, file: &MovieFile) -> Result<MovieFile>;
+
+    /// Delete a movie file by ID
+    async fn delete(&self, id: Uuid) -> Result<()>;
+}
+
+/// Repository trait for history entries
+#[async_trait]
+pub trait HistoryRepository: Send + Sync {
+    /// Record a history entry; entries for an already recorded event are ignored
+    async fn create(&self, record: &HistoryRecord) -> Result<()>;
+
+    /// List entries matching the query, newest first, with the total match count
+    async fn list(&self, query: &HistoryQuery) -> Result<(Vec<HistoryRecord>, i64)>;
+
+    /// Find a history entry by its ID
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<HistoryRecord>>;
+}
+
+/// Repository trait for audit log entries
+#[async_trait]
+pub trait AuditRepository: Send + Sync {
+    /// Record an audit entry; entries for an already recorded event are ignored
+    async fn create(&self, record: &AuditRecord) -> Result<()>;
+
+    /// List entries matching the query, newest first, with the total match count
+    async fn list(&self, query: &AuditQuery) -> Result<(Vec<AuditRecord>, i64)>;
+}
+
+/// Repository trait for ListExclusion entities
+#[async_trait]
+pub trait ListExclusionRepository: Send + Sync {
+    /// Add an exclusion; movies that are already excluded are left as they are
+    async fn create(&self, exclusion: &ListExclusion) -> Result<()>;
+
+    /// Find the exclusion for a TMDB ID
+    async fn find_by_tmdb_id(&self, tmdb_id: i32) -> Result<Option<ListExclusion>>;
+
+    /// Find the exclusion for an IMDb ID
+    async fn find_by_imdb_id(&self, imdb_id: &str) -> Result<Option<ListExclusion>>;
+
+    /// Find an exclusion by its ID
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<ListExclusion>>;
+
+    /// List all exclusions, newest first
+    async fn list(&self) -> Result<Vec<ListExclusion>>;
+
+    /// Delete an exclusion so the movie can be added again
+    async fn delete(&self, id: Uuid) -> Result<()>;
+}
+
+/// Repository trait for ImportList entities
+#[async_trait]
+pub trait ImportListRepository: Send + Sync {
+    /// Find an import list by its name
+    async fn find_by_name(&self, name: &str) -> Result<Option<ImportList>>;
+
+    /// List all import lists, ordered by name
+    async fn list(&self) -> Result<Vec<ImportList>>;
+
+    /// Create a new import list
+    async fn create(&self, list: &ImportList) -> Result<ImportList>;
+
+    /// Update an existing import list
+    async fn update(&self, list: &ImportList) -> Result<ImportList>;
+}
+
+/// Repository trait for RootFolder entities
+#[async_trait]
+pub trait RootFolderRepository: Send + Sync {
+    /// Find a root folder by its ID
+    async fn find_by_id(&self, id: i32) -> Result<Option<RootFolder>>;
+
+    /// Find a root folder by its path
+    async fn find_by_path(&self, path: &str) -> Result<Option<RootFolder>>;
+
+    /// List all root folders, ordered by path
+    async fn list(&self) -> Result<Vec<RootFolder>>;
+
+    /// Create a new root folder
+    async fn create(&self, root_folder: &RootFolder) -> Result<RootFolder>;
+
+    /// Update an existing root folder
+    async fn update(&self, root_folder: &RootFolder) -> Result<RootFolder>;
+
+    /// Delete a root folder by ID
+    async fn delete(&self, id: i32) -> Result<()>;
+}
+
+/// Repository trait for NotificationDefinition entities
+#[async_trait]
+pub trait NotificationRepository: Send + Sync {
+    /// Find a notification definition by its ID
+    async fn find_by_id(&self, id: i32) -> Result<Option<NotificationDefinition>>;
+
+    /// List all notification definitions, ordered by name
+    async fn list(&self) -> Result<Vec<NotificationDefinition>>;
+
+    /// Create a new notification definition
+    async fn create(&self, definition: &NotificationDefinition) -> Result<NotificationDefinition>;
+
+    /// Update an existing notification definition
+    async fn update(&self, definition: &NotificationDefinition) -> Result<NotificationDefinition>;
+
+    /// Delete a notification definition by ID
+    async fn delete(&self, id: i32) -> Result<()>;
+}
+
+/// Repository trait for Tag entities
+#[async_trait]
+pub trait TagRepository: Send + Sync {
+    /// Find a tag by its ID
+    async fn find_by_id(&self, id: i32) -> Result<Option<Tag>>;
+
+    /// Find a tag by its normalized label
+    async fn find_by_label(&self, label: &str) -> Result<Option<Tag>>;
+
+    /// List all tags, ordered by label
+    async fn list(&self) -> Result<Vec<Tag>>;
+
+    /// Create a new tag
+    async fn create(&self, tag: &Tag) -> Result<Tag>;
+
+    /// Rename a tag
+    async fn update(&self, tag: &Tag) -> Result<Tag>;
+
+    /// Delete a tag by ID
+    async fn delete(&self, id: i32) -> Result<()>;
+
+    /// Movies, indexers, notifications and import lists tagged with a tag
+    async fn usage(&self, id: i32) -> Result<TagUsage>;
+}
+
+/// Repository trait for settings stored per section
+#[async_trait]
+pub trait ConfigRepository: Send + Sync {
+    /// Stored settings of a section, `None` when they were never saved
+    async fn get(&self, section: ConfigSection) -> Result<Option<serde_json::Value>>;
+
+    /// Store the settings of a section, replacing the previous ones
+    async fn save(&self, section: ConfigSection, settings: &serde_json::Value) -> Result<()>;
+}
+
+/// Repository trait for ShareToken entities
+#[async_trait]
+pub trait ShareTokenRepository: Send + Sync {
+    /// List all share tokens, newest first
+    async fn list(&self) -> Result<Vec<ShareToken>>;
+
+    /// Store a newly minted share token
+    async fn create(&self, token: &ShareToken) -> Result<ShareToken>;
+
+    /// Mark a share token as revoked, returning the updated token
+    async fn revoke(&self, id: Uuid) -> Result<Option<ShareToken>>;
+}
+
+/// Repository trait for ApiKey entities
+#[async_trait]
+pub trait ApiKeyRepository: Send + Sync {
+    /// List all API keys, newest first
+    async fn list(&self) -> Result<Vec<ApiKey>>;
+
+    /// Store a newly generated API key
+    async fn create(&self, key: &ApiKey) -> Result<ApiKey>;
+
+    /// Replace the hash of an active key, returning the updated key
+    async fn rotate(&self, id: Uuid, key_hash: &str) -> Result<Option<ApiKey>>;
+
+    /// Mark an API key as revoked, returning the updated key
+    async fn revoke(&self, id: Uuid) -> Result<Option<ApiKey>>;
+
+    /// Record when the key was last used
+    async fn touch(&self, id: Uuid, at: chrono::DateTime<chrono::Utc>) -> Result<()>;
+}
+
+/// Locks on import source paths shared by every instance using the database
+#[async_trait]
+pub trait ImportLockRepository: Send + Sync {
+    /// Try to lock `source_path`; false if another holder already has it
+    async fn try_lock(&self, source_path: &str) -> Result<bool>;
+
+    /// Release a lock taken with `try_lock`
+    async fn unlock(&self, source_path: &str) -> Result<()>;
+}
+
+/// Commands of the command queue, stored so they survive a restart
+#[async_trait]
+pub trait CommandRepository: Send + Sync {
+    /// Insert a command, or overwrite the stored command with its ID
+    async fn save(&self, command: &crate::commands::Command) -> Result<()>;
+
+    /// Unfinished commands and the newest `finished_limit` finished ones,
+    /// oldest first
+    async fn load(&self, finished_limit: i64) -> Result<Vec<crate::commands::Command>>;
+
+    /// Highest stored command ID, or 0 when none are stored
+    async fn max_id(&self) -> Result<i32>;
+
+    /// Delete finished commands beyond the newest `keep`, returning how many
+    async fn prune(&self, keep: i64) -> Result<u64>;
+}
+
+/// Repository trait for MovieCollection entities
+#[async_trait]
+pub trait CollectionRepository: Send + Sync {
+    /// Find a collection by its ID
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<MovieCollection>>;
+
+    /// Find a collection by its TMDB ID
+    async fn find_by_tmdb_id(&self, tmdb_id: i32) -> Result<Option<MovieCollection>>;
+
+    /// List all collections ordered by title
+    async fn list(&self) -> Result<Vec<MovieCollection>>;
+
+    /// Insert a collection, or update it if its TMDB ID already exists
+    async fn upsert(&self, collection: &MovieCollection) -> Result<MovieCollection>;
+
+    /// Delete a collection by ID
+    async fn delete(&self, id: Uuid) -> Result<()>;
 }
 
 /// Repository trait for Download entities
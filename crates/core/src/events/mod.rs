@@ -4,16 +4,25 @@
 //! to enable loose coupling between components like downloads, imports, and notifications.
 
 use crate::correlation::{current_correlation_id, CorrelationContext, CorrelationId};
+use crate::shutdown::ShutdownCoordinator;
 use crate::{RadarrError, Result};
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
 use tokio::sync::broadcast;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
+pub mod resource;
+
+pub use resource::*;
+
 /// Maximum number of events to buffer in the channel
 const EVENT_BUFFER_SIZE: usize = 1000;
 
+/// Number of recent events kept for clients resuming a stream
+const REPLAY_BUFFER_SIZE: usize = 500;
+
 /// Event envelope that includes correlation information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EventEnvelope {
@@ -84,6 +93,14 @@ pub enum SystemEvent {
         download_url: String,
         title: String,
     },
+    /// A release was grabbed manually, bypassing the decision engine
+    OverrideGrab {
+        movie_id: Option<Uuid>,
+        guid: String,
+        title: String,
+        indexer: String,
+        download_client_id: String,
+    },
     /// Download started
     DownloadStarted {
         movie_id: Uuid,
@@ -124,19 +141,48 @@ pub enum SystemEvent {
         source_path: String,
         error: String,
     },
+    /// Movie added to the library
+    MovieAdded {
+        movie_id: Uuid,
+        tmdb_id: i32,
+        title: String,
+    },
     /// Movie metadata updated
     MovieUpdated {
         movie_id: Uuid,
         changes: Vec<String>,
     },
+    /// Movie removed from the library
+    MovieDeleted { movie_id: Uuid, title: String },
+    /// Movie file deleted from disk
+    MovieFileDeleted {
+        movie_id: Uuid,
+        movie_file_id: Uuid,
+        relative_path: String,
+        reason: String,
+    },
     /// Quality profile changed
     QualityProfileUpdated { profile_id: Uuid, name: String },
+    /// A deletion or settings change was performed through the API
+    DestructiveOperation {
+        action: crate::models::AuditAction,
+        actor: crate::models::AuditActor,
+        /// What was changed, e.g. a movie ID or settings section
+        target: String,
+        details: serde_json::Value,
+    },
     /// System health event
     SystemHealth {
         component: String,
         status: String,
         message: Option<String>,
     },
+    /// A release newer than the running version was published
+    ApplicationUpdateAvailable {
+        current_version: String,
+        new_version: String,
+        release_url: Option<String>,
+    },
     /// Progress update for any operation
     ProgressUpdate {
         operation_id: Uuid,
@@ -155,6 +201,31 @@ pub enum SystemEvent {
 }
 
 impl SystemEvent {
+    /// Variant name, matching the serialized `type` tag
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            SystemEvent::DownloadQueued { .. } => "DownloadQueued",
+            SystemEvent::OverrideGrab { .. } => "OverrideGrab",
+            SystemEvent::DownloadStarted { .. } => "DownloadStarted",
+            SystemEvent::DownloadProgress { .. } => "DownloadProgress",
+            SystemEvent::DownloadComplete { .. } => "DownloadComplete",
+            SystemEvent::DownloadFailed { .. } => "DownloadFailed",
+            SystemEvent::ImportTriggered { .. } => "ImportTriggered",
+            SystemEvent::ImportComplete { .. } => "ImportComplete",
+            SystemEvent::ImportFailed { .. } => "ImportFailed",
+            SystemEvent::MovieAdded { .. } => "MovieAdded",
+            SystemEvent::MovieUpdated { .. } => "MovieUpdated",
+            SystemEvent::MovieDeleted { .. } => "MovieDeleted",
+            SystemEvent::MovieFileDeleted { .. } => "MovieFileDeleted",
+            SystemEvent::QualityProfileUpdated { .. } => "QualityProfileUpdated",
+            SystemEvent::DestructiveOperation { .. } => "DestructiveOperation",
+            SystemEvent::SystemHealth { .. } => "SystemHealth",
+            SystemEvent::ApplicationUpdateAvailable { .. } => "ApplicationUpdateAvailable",
+            SystemEvent::ProgressUpdate { .. } => "ProgressUpdate",
+            SystemEvent::OperationComplete { .. } => "OperationComplete",
+        }
+    }
+
     /// Get the movie ID associated with this event, if any
     pub fn movie_id(&self) -> Option<Uuid> {
         match self {
@@ -166,7 +237,11 @@ impl SystemEvent {
             | SystemEvent::ImportTriggered { movie_id, .. }
             | SystemEvent::ImportComplete { movie_id, .. }
             | SystemEvent::ImportFailed { movie_id, .. }
-            | SystemEvent::MovieUpdated { movie_id, .. } => Some(*movie_id),
+            | SystemEvent::MovieAdded { movie_id, .. }
+            | SystemEvent::MovieUpdated { movie_id, .. }
+            | SystemEvent::MovieDeleted { movie_id, .. }
+            | SystemEvent::MovieFileDeleted { movie_id, .. } => Some(*movie_id),
+            SystemEvent::OverrideGrab { movie_id, .. } => *movie_id,
             _ => None,
         }
     }
@@ -175,6 +250,7 @@ impl SystemEvent {
     pub fn description(&self) -> String {
         match self {
             SystemEvent::DownloadQueued { title, .. } => format!("Download queued: {}", title),
+            SystemEvent::OverrideGrab { title, .. } => format!("Manual grab: {}", title),
             SystemEvent::DownloadStarted { client_id, .. } => {
                 format!("Download started: {}", client_id)
             }
@@ -199,15 +275,29 @@ impl SystemEvent {
                 )
             }
             SystemEvent::ImportFailed { error, .. } => format!("Import failed: {}", error),
+            SystemEvent::MovieAdded { title, .. } => format!("Movie added: {}", title),
             SystemEvent::MovieUpdated { changes, .. } => {
                 format!("Movie updated: {}", changes.join(", "))
             }
+            SystemEvent::MovieDeleted { title, .. } => format!("Movie deleted: {}", title),
+            SystemEvent::MovieFileDeleted { relative_path, .. } => {
+                format!("Movie file deleted: {}", relative_path)
+            }
             SystemEvent::QualityProfileUpdated { name, .. } => {
                 format!("Quality profile updated: {}", name)
             }
+            SystemEvent::DestructiveOperation {
+                action,
+                actor,
+                target,
+                ..
+            } => format!("{} of {} by {}", action, target, actor.name),
             SystemEvent::SystemHealth {
                 component, status, ..
             } => format!("Health: {} is {}", component, status),
+            SystemEvent::ApplicationUpdateAvailable { new_version, .. } => {
+                format!("Update available: {}", new_version)
+            }
             SystemEvent::ProgressUpdate {
                 percentage,
                 message,
@@ -228,13 +318,18 @@ impl SystemEvent {
 #[derive(Clone)]
 pub struct EventBus {
     sender: broadcast::Sender<EventEnvelope>,
+    /// Most recent events, oldest first
+    recent: Arc<Mutex<VecDeque<EventEnvelope>>>,
 }
 
 impl EventBus {
     /// Create a new event bus
     pub fn new() -> Self {
         let (sender, _) = broadcast::channel(EVENT_BUFFER_SIZE);
-        Self { sender }
+        Self {
+            sender,
+            recent: Arc::new(Mutex::new(VecDeque::with_capacity(REPLAY_BUFFER_SIZE))),
+        }
     }
 
     /// Publish an event to all subscribers
@@ -257,6 +352,14 @@ impl EventBus {
     pub async fn publish_envelope(&self, envelope: EventEnvelope) -> Result<()> {
         debug!("Publishing event: {}", envelope.description());
 
+        {
+            let mut recent = self.recent.lock().unwrap_or_else(|e| e.into_inner());
+            if recent.len() == REPLAY_BUFFER_SIZE {
+                recent.pop_front();
+            }
+            recent.push_back(envelope.clone());
+        }
+
         match self.sender.send(envelope.clone()) {
             Ok(receiver_count) => {
                 if receiver_count > 0 {
@@ -279,6 +382,19 @@ impl EventBus {
         EventSubscriber { receiver }
     }
 
+    /// Recently published events after `last_event_id`, oldest first
+    ///
+    /// Returns every kept event when `last_event_id` is `None` or no longer
+    /// kept, since the caller may have missed any of them.
+    pub fn replay_after(&self, last_event_id: Option<Uuid>) -> Vec<EventEnvelope> {
+        let recent = self.recent.lock().unwrap_or_else(|e| e.into_inner());
+        let start = last_event_id
+            .and_then(|id| recent.iter().position(|envelope| envelope.event_id == id))
+            .map(|index| index + 1)
+            .unwrap_or(0);
+        recent.iter().skip(start).cloned().collect()
+    }
+
     /// Get the number of active subscribers
     pub fn subscriber_count(&self) -> usize {
         self.sender.receiver_count()
@@ -379,6 +495,7 @@ pub trait EventHandler: Send + Sync {
 pub struct EventProcessor {
     subscriber: EventSubscriber,
     handlers: Vec<Arc<dyn EventHandler>>,
+    shutdown: ShutdownCoordinator,
 }
 
 impl EventProcessor {
@@ -387,6 +504,7 @@ impl EventProcessor {
         Self {
             subscriber: event_bus.subscribe(),
             handlers: Vec::new(),
+            shutdown: ShutdownCoordinator::new(),
         }
     }
 
@@ -396,7 +514,15 @@ impl EventProcessor {
         self
     }
 
-    /// Start processing events (runs until the event bus is closed)
+    /// Stop when `shutdown` is signalled, after handling the events already
+    /// published
+    pub fn with_shutdown(mut self, shutdown: ShutdownCoordinator) -> Self {
+        self.shutdown = shutdown;
+        self
+    }
+
+    /// Start processing events (runs until the event bus is closed or
+    /// shutdown is signalled)
     pub async fn run(mut self) -> Result<()> {
         info!(
             "Starting event processor with {} handlers",
@@ -404,33 +530,15 @@ impl EventProcessor {
         );
 
         loop {
-            match self.subscriber.recv().await {
-                Ok(envelope) => {
-                    debug!(
-                        "Processing event: {} with correlation_id={}",
-                        envelope.description(),
-                        envelope.correlation_id
-                    );
-
-                    // Set correlation context for this processing
-                    let ctx = CorrelationContext::new("event_processor");
-                    crate::correlation::set_current_context(ctx);
-
-                    // Process event with all interested handlers
-                    for handler in &self.handlers {
-                        if handler.should_handle(&envelope) {
-                            if let Err(e) = handler.handle_event(&envelope).await {
-                                error!(
-                                    "Handler failed to process event {} with correlation_id={}: {}",
-                                    envelope.description(),
-                                    envelope.correlation_id,
-                                    e
-                                );
-                                // Continue with other handlers
-                            }
-                        }
-                    }
+            let received = tokio::select! {
+                received = self.subscriber.recv() => received,
+                _ = self.shutdown.cancelled() => {
+                    self.flush().await;
+                    break;
                 }
+            };
+            match received {
+                Ok(envelope) => self.dispatch(&envelope).await,
                 Err(e) => {
                     error!("Event processor error: {}", e);
                     break;
@@ -441,6 +549,45 @@ impl EventProcessor {
         info!("Event processor shutting down");
         Ok(())
     }
+
+    /// Handle the events published but not yet received
+    async fn flush(&mut self) {
+        let mut flushed = 0;
+        while let Ok(Some(envelope)) = self.subscriber.try_recv() {
+            self.dispatch(&envelope).await;
+            flushed += 1;
+        }
+        if flushed > 0 {
+            info!("Handled {} pending events before shutdown", flushed);
+        }
+    }
+
+    /// Pass an event to every interested handler
+    async fn dispatch(&self, envelope: &EventEnvelope) {
+        debug!(
+            "Processing event: {} with correlation_id={}",
+            envelope.description(),
+            envelope.correlation_id
+        );
+
+        // Set correlation context for this processing
+        let ctx = CorrelationContext::new("event_processor");
+        crate::correlation::set_current_context(ctx);
+
+        for handler in &self.handlers {
+            if handler.should_handle(envelope) {
+                if let Err(e) = handler.handle_event(envelope).await {
+                    error!(
+                        "Handler failed to process event {} with correlation_id={}: {}",
+                        envelope.description(),
+                        envelope.correlation_id,
+                        e
+                    );
+                    // Continue with other handlers
+                }
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -564,6 +711,56 @@ mod tests {
         let _ = timeout(Duration::from_millis(100), processor_handle).await;
     }
 
+    #[tokio::test]
+    async fn test_event_processor_flushes_pending_events_on_shutdown() {
+        let event_bus = EventBus::new();
+        let counter = Arc::new(AtomicUsize::new(0));
+        let shutdown = ShutdownCoordinator::new();
+        let processor = EventProcessor::new(&event_bus)
+            .add_handler(Arc::new(TestHandler {
+                counter: counter.clone(),
+            }))
+            .with_shutdown(shutdown.clone());
+
+        for i in 0..3 {
+            let event = SystemEvent::MovieUpdated {
+                movie_id: Uuid::new_v4(),
+                changes: vec![format!("change_{}", i)],
+            };
+            event_bus.publish(event).await.unwrap();
+        }
+        shutdown.token().cancel();
+
+        timeout(Duration::from_secs(1), processor.run())
+            .await
+            .expect("processor stops on shutdown")
+            .unwrap();
+        assert_eq!(counter.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_replay_after_last_event_id() {
+        let event_bus = EventBus::new();
+        for i in 0..3 {
+            let event = SystemEvent::MovieUpdated {
+                movie_id: Uuid::new_v4(),
+                changes: vec![format!("change_{}", i)],
+            };
+            event_bus.publish(event).await.unwrap();
+        }
+
+        let all = event_bus.replay_after(None);
+        assert_eq!(all.len(), 3);
+
+        let resumed = event_bus.replay_after(Some(all[0].event_id));
+        let ids: Vec<Uuid> = resumed.iter().map(|envelope| envelope.event_id).collect();
+        assert_eq!(ids, vec![all[1].event_id, all[2].event_id]);
+
+        assert!(event_bus.replay_after(Some(all[2].event_id)).is_empty());
+        // An evicted or unknown ID replays everything kept
+        assert_eq!(event_bus.replay_after(Some(Uuid::new_v4())).len(), 3);
+    }
+
     #[test]
     fn test_event_movie_id_extraction() {
         let movie_id = Uuid::new_v4();
@@ -642,4 +839,16 @@ mod tests {
         assert!(desc.contains("75.0%"));
         assert!(desc.contains("Download progress"));
     }
+
+    #[test]
+    fn test_event_type_matches_serialized_tag() {
+        let event = SystemEvent::ImportComplete {
+            movie_id: Uuid::new_v4(),
+            destination_path: "/movies/Test (2023)".to_string(),
+            file_count: 1,
+        };
+
+        let value = serde_json::to_value(&event).unwrap();
+        assert_eq!(value["type"], event.event_type());
+    }
 }
@@ -0,0 +1,139 @@
+//! Resource update messages for push clients
+//!
+//! Push clients (the `/ws` endpoint) do not want raw system events; they want
+//! to know which resource changed so they can refresh it. A [`ResourceMessage`]
+//! names the resource topic and carries the action and the changed resource,
+//! following the `{ name, body: { action, resource } }` shape of Radarr's
+//! SignalR messages.
+
+use super::SystemEvent;
+use serde::{Deserialize, Serialize};
+
+/// Kind of resource a push message is about
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ResourceTopic {
+    Movie,
+    MovieFile,
+    Queue,
+    Health,
+    QualityProfile,
+}
+
+impl ResourceTopic {
+    /// All topics
+    pub const ALL: [ResourceTopic; 5] = [
+        ResourceTopic::Movie,
+        ResourceTopic::MovieFile,
+        ResourceTopic::Queue,
+        ResourceTopic::Health,
+        ResourceTopic::QualityProfile,
+    ];
+}
+
+/// What happened to the resource
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ResourceAction {
+    Created,
+    Updated,
+    Deleted,
+}
+
+/// Action and resource of a push message
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceBody {
+    pub action: ResourceAction,
+    /// Fields of the event that changed the resource
+    pub resource: serde_json::Value,
+}
+
+/// A typed resource change pushed to clients
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceMessage {
+    pub name: ResourceTopic,
+    pub body: ResourceBody,
+}
+
+impl ResourceMessage {
+    /// The resource change described by `event`, if it changes one
+    pub fn from_event(event: &SystemEvent) -> Option<Self> {
+        let (name, action) = match event {
+            SystemEvent::MovieAdded { .. } => (ResourceTopic::Movie, ResourceAction::Created),
+            SystemEvent::MovieUpdated { .. } => (ResourceTopic::Movie, ResourceAction::Updated),
+            SystemEvent::MovieDeleted { .. } => (ResourceTopic::Movie, ResourceAction::Deleted),
+            SystemEvent::ImportComplete { .. } => {
+                (ResourceTopic::MovieFile, ResourceAction::Created)
+            }
+            SystemEvent::MovieFileDeleted { .. } => {
+                (ResourceTopic::MovieFile, ResourceAction::Deleted)
+            }
+            SystemEvent::DownloadQueued { .. } | SystemEvent::OverrideGrab { .. } => {
+                (ResourceTopic::Queue, ResourceAction::Created)
+            }
+            SystemEvent::DownloadStarted { .. }
+            | SystemEvent::DownloadProgress { .. }
+            | SystemEvent::DownloadComplete { .. }
+            | SystemEvent::DownloadFailed { .. } => (ResourceTopic::Queue, ResourceAction::Updated),
+            SystemEvent::SystemHealth { .. } => (ResourceTopic::Health, ResourceAction::Updated),
+            SystemEvent::QualityProfileUpdated { .. } => {
+                (ResourceTopic::QualityProfile, ResourceAction::Updated)
+            }
+            _ => return None,
+        };
+
+        // Events serialize as `{ "type": ..., "data": { fields } }`
+        let resource = match serde_json::to_value(event) {
+            Ok(mut value) => value
+                .get_mut("data")
+                .map(serde_json::Value::take)
+                .unwrap_or_default(),
+            Err(_) => return None,
+        };
+
+        Some(Self {
+            name,
+            body: ResourceBody { action, resource },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_resource_messages_from_events() {
+        let movie_id = Uuid::new_v4();
+        let message = ResourceMessage::from_event(&SystemEvent::MovieAdded {
+            movie_id,
+            tmdb_id: 603,
+            title: "The Matrix".to_string(),
+        })
+        .unwrap();
+        assert_eq!(message.name, ResourceTopic::Movie);
+        assert_eq!(message.body.action, ResourceAction::Created);
+        assert_eq!(message.body.resource["tmdb_id"], 603);
+        assert_eq!(message.body.resource["movie_id"], movie_id.to_string());
+
+        let json = serde_json::to_value(&message).unwrap();
+        assert_eq!(json["name"], "movie");
+        assert_eq!(json["body"]["action"], "created");
+
+        let health = ResourceMessage::from_event(&SystemEvent::SystemHealth {
+            component: "tmdb".to_string(),
+            status: "unhealthy".to_string(),
+            message: None,
+        })
+        .unwrap();
+        assert_eq!(health.name, ResourceTopic::Health);
+
+        // Progress of internal operations is not a resource change
+        assert!(ResourceMessage::from_event(&SystemEvent::ImportTriggered {
+            movie_id,
+            source_path: "/downloads/movie".to_string(),
+        })
+        .is_none());
+    }
+}
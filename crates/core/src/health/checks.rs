@@ -0,0 +1,219 @@
+//! Health checks for the parts of the system owned by the core
+//!
+//! The indexer check lives with the indexer client in `radarr-indexers`.
+
+use super::{HealthCheck, HealthIssue};
+use crate::domain::repositories::{HistoryRepository, RootFolderRepository};
+use crate::i18n::translate;
+use crate::models::{HistoryEventType, HistoryQuery};
+use crate::services::{DownloadClientService, StorageThrottle};
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+
+/// Issue for a check that could not read what it checks
+fn check_failed(source: &str, error: impl std::fmt::Display, wiki_anchor: &str) -> HealthIssue {
+    HealthIssue::error(
+        source,
+        translate("health-check-failed", &[("error", error.to_string())]),
+        wiki_anchor,
+    )
+}
+
+/// Errors when the download client cannot be reached
+pub struct DownloadClientCheck {
+    client: Arc<dyn DownloadClientService>,
+}
+
+impl DownloadClientCheck {
+    pub fn new(client: Arc<dyn DownloadClientService>) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl HealthCheck for DownloadClientCheck {
+    fn name(&self) -> &'static str {
+        "DownloadClientCheck"
+    }
+
+    async fn check(&self) -> Vec<HealthIssue> {
+        match self.client.get_all_downloads().await {
+            Ok(_) => Vec::new(),
+            Err(e) => vec![HealthIssue::error(
+                self.name(),
+                translate(
+                    "health-download-client-unavailable",
+                    &[("error", e.to_string())],
+                ),
+                "download-clients-are-unavailable-due-to-failures",
+            )],
+        }
+    }
+}
+
+/// Errors for root folders that are missing or not directories
+pub struct RootFolderCheck {
+    root_folders: Arc<dyn RootFolderRepository>,
+}
+
+impl RootFolderCheck {
+    pub fn new(root_folders: Arc<dyn RootFolderRepository>) -> Self {
+        Self { root_folders }
+    }
+}
+
+#[async_trait]
+impl HealthCheck for RootFolderCheck {
+    fn name(&self) -> &'static str {
+        "RootFolderCheck"
+    }
+
+    async fn check(&self) -> Vec<HealthIssue> {
+        let folders = match self.root_folders.list().await {
+            Ok(folders) => folders,
+            Err(e) => return vec![check_failed(self.name(), e, "missing-root-folder")],
+        };
+
+        let mut issues = Vec::new();
+        for folder in folders {
+            let accessible = tokio::fs::metadata(&folder.path)
+                .await
+                .map(|metadata| metadata.is_dir())
+                .unwrap_or(false);
+            if !accessible {
+                issues.push(HealthIssue::error(
+                    self.name(),
+                    translate("health-root-folder-missing", &[("path", folder.path)]),
+                    "missing-root-folder",
+                ));
+            }
+        }
+        issues
+    }
+}
+
+/// Warns about root folders whose free space is below their threshold
+///
+/// Reads the measurements of the scheduled `CheckFreeSpace` task rather than
+/// measuring again.
+pub struct DiskSpaceCheck {
+    storage_throttle: Arc<StorageThrottle>,
+}
+
+impl DiskSpaceCheck {
+    pub fn new(storage_throttle: Arc<StorageThrottle>) -> Self {
+        Self { storage_throttle }
+    }
+}
+
+#[async_trait]
+impl HealthCheck for DiskSpaceCheck {
+    fn name(&self) -> &'static str {
+        "DiskSpaceCheck"
+    }
+
+    async fn check(&self) -> Vec<HealthIssue> {
+        self.storage_throttle
+            .snapshot()
+            .into_iter()
+            .filter(|space| space.throttled)
+            .map(|space| {
+                HealthIssue::warning(
+                    self.name(),
+                    translate(
+                        "health-disk-space-low",
+                        &[
+                            ("path", space.path),
+                            ("free", format_gib(space.free_bytes)),
+                            ("threshold", format_gib(space.threshold_bytes)),
+                        ],
+                    ),
+                    "disk-space",
+                )
+            })
+            .collect()
+    }
+}
+
+fn format_gib(bytes: u64) -> String {
+    format!("{:.1} GiB", bytes as f64 / (1024.0 * 1024.0 * 1024.0))
+}
+
+/// Warns when imports failed recently
+pub struct ImportFailureCheck {
+    history: Arc<dyn HistoryRepository>,
+    window: Duration,
+}
+
+impl ImportFailureCheck {
+    pub fn new(history: Arc<dyn HistoryRepository>) -> Self {
+        Self {
+            history,
+            window: Duration::hours(24),
+        }
+    }
+}
+
+#[async_trait]
+impl HealthCheck for ImportFailureCheck {
+    fn name(&self) -> &'static str {
+        "ImportFailureCheck"
+    }
+
+    async fn check(&self) -> Vec<HealthIssue> {
+        let query = HistoryQuery {
+            event_types: vec![HistoryEventType::ImportFailed],
+            since: Some(Utc::now() - self.window),
+            limit: 1,
+            ..Default::default()
+        };
+        let (latest, total) = match self.history.list(&query).await {
+            Ok(found) => found,
+            Err(e) => return vec![check_failed(self.name(), e, "import-failures")],
+        };
+        if total == 0 {
+            return Vec::new();
+        }
+
+        let title = latest
+            .first()
+            .and_then(|record| record.source_title.clone())
+            .unwrap_or_default();
+        vec![HealthIssue::warning(
+            self.name(),
+            translate(
+                "health-import-failures",
+                &[
+                    ("count", total.to_string()),
+                    ("hours", self.window.num_hours().to_string()),
+                    ("title", title),
+                ],
+            ),
+            "import-failures",
+        )]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::health::HealthIssueType;
+    use crate::models::RootFolder;
+
+    #[tokio::test]
+    async fn test_disk_space_check_warns_for_throttled_folders() {
+        let throttle = Arc::new(StorageThrottle::default());
+        let mut low = RootFolder::new("/media/low".to_string());
+        low.id = 1;
+        let mut roomy = RootFolder::new("/media/roomy".to_string());
+        roomy.id = 2;
+        throttle.record(&low, 1024);
+        throttle.record(&roomy, u64::MAX / 2);
+
+        let issues = DiskSpaceCheck::new(throttle).check().await;
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].issue_type, HealthIssueType::Warning);
+        assert!(issues[0].message.contains("/media/low"));
+    }
+}
@@ -0,0 +1,296 @@
+//! Health checks reported through `/api/v3/health`
+//!
+//! Each [`HealthCheck`] looks at one part of the system, such as the indexers,
+//! the download client or the root folders, and returns the problems it found
+//! as warnings or errors. Every issue links to the wiki section explaining how
+//! to fix it. The [`HealthCheckRegistry`] runs the registered checks
+//! concurrently and reuses their results briefly, so clients polling the
+//! endpoint do not turn into a stream of requests to indexers and clients.
+
+pub mod checks;
+
+pub use checks::*;
+
+use crate::i18n::translate;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use tokio::task::JoinSet;
+use tracing::{debug, warn};
+
+/// Wiki page describing every health issue
+pub const HEALTH_WIKI_URL: &str = "https://wiki.servarr.com/radarr/system";
+
+/// Longest a single check may take before it is reported as failed
+const DEFAULT_CHECK_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// How long results are reused before the checks run again
+const DEFAULT_RESULT_TTL: Duration = Duration::from_secs(30);
+
+/// Severity of a health issue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthIssueType {
+    /// Something needs attention but keeps working
+    Warning,
+    /// Something does not work until it is fixed
+    Error,
+}
+
+/// A problem found by a health check
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthIssue {
+    /// Name of the check that found it
+    pub source: String,
+    #[serde(rename = "type")]
+    pub issue_type: HealthIssueType,
+    pub message: String,
+    /// Wiki section explaining the issue
+    pub wiki_url: String,
+}
+
+impl HealthIssue {
+    /// A warning described under `wiki_anchor` on the wiki's system page
+    pub fn warning(source: &str, message: impl Into<String>, wiki_anchor: &str) -> Self {
+        Self::new(
+            source,
+            HealthIssueType::Warning,
+            message.into(),
+            wiki_anchor,
+        )
+    }
+
+    /// An error described under `wiki_anchor` on the wiki's system page
+    pub fn error(source: &str, message: impl Into<String>, wiki_anchor: &str) -> Self {
+        Self::new(source, HealthIssueType::Error, message.into(), wiki_anchor)
+    }
+
+    fn new(source: &str, issue_type: HealthIssueType, message: String, wiki_anchor: &str) -> Self {
+        Self {
+            source: source.to_string(),
+            issue_type,
+            message,
+            wiki_url: format!("{}#{}", HEALTH_WIKI_URL, wiki_anchor),
+        }
+    }
+}
+
+/// A check of one part of the system
+#[async_trait]
+pub trait HealthCheck: Send + Sync {
+    /// Name reported as the source of its issues, e.g. `RootFolderCheck`
+    fn name(&self) -> &'static str;
+
+    /// Problems found; empty when everything is fine
+    async fn check(&self) -> Vec<HealthIssue>;
+}
+
+/// The registered health checks and their latest results
+pub struct HealthCheckRegistry {
+    checks: RwLock<Vec<Arc<dyn HealthCheck>>>,
+    check_timeout: Duration,
+    result_ttl: Duration,
+    /// When the checks last ran and what they found
+    latest: Mutex<Option<(Instant, Vec<HealthIssue>)>>,
+}
+
+impl Default for HealthCheckRegistry {
+    fn default() -> Self {
+        Self {
+            checks: RwLock::new(Vec::new()),
+            check_timeout: DEFAULT_CHECK_TIMEOUT,
+            result_ttl: DEFAULT_RESULT_TTL,
+            latest: Mutex::new(None),
+        }
+    }
+}
+
+impl HealthCheckRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Report checks that take longer than `timeout` as failed
+    pub fn with_check_timeout(mut self, timeout: Duration) -> Self {
+        self.check_timeout = timeout;
+        self
+    }
+
+    /// Reuse results for `ttl` before running the checks again
+    pub fn with_result_ttl(mut self, ttl: Duration) -> Self {
+        self.result_ttl = ttl;
+        self
+    }
+
+    /// Add a check; it runs from the next request on
+    pub fn register(&self, check: Arc<dyn HealthCheck>) {
+        debug!("Registered health check {}", check.name());
+        self.checks
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(check);
+        if let Ok(mut latest) = self.latest.try_lock() {
+            *latest = None;
+        }
+    }
+
+    /// Names of the registered checks
+    pub fn names(&self) -> Vec<&'static str> {
+        self.checks
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .map(|check| check.name())
+            .collect()
+    }
+
+    /// Issues found by the checks, errors first, reusing recent results
+    pub async fn issues(&self) -> Vec<HealthIssue> {
+        let mut latest = self.latest.lock().await;
+        if let Some((checked_at, issues)) = latest.as_ref() {
+            if checked_at.elapsed() < self.result_ttl {
+                return issues.clone();
+            }
+        }
+
+        let issues = self.run_checks().await;
+        *latest = Some((Instant::now(), issues.clone()));
+        issues
+    }
+
+    /// Run every check now, errors first
+    pub async fn run(&self) -> Vec<HealthIssue> {
+        let mut latest = self.latest.lock().await;
+        let issues = self.run_checks().await;
+        *latest = Some((Instant::now(), issues.clone()));
+        issues
+    }
+
+    async fn run_checks(&self) -> Vec<HealthIssue> {
+        let checks = self
+            .checks
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+
+        let mut running = JoinSet::new();
+        for check in checks {
+            let timeout = self.check_timeout;
+            running.spawn(async move {
+                match tokio::time::timeout(timeout, check.check()).await {
+                    Ok(issues) => issues,
+                    Err(_) => vec![HealthIssue::error(
+                        check.name(),
+                        translate("health-check-timed-out", &[]),
+                        "health-checks",
+                    )],
+                }
+            });
+        }
+
+        let mut issues = Vec::new();
+        while let Some(result) = running.join_next().await {
+            match result {
+                Ok(found) => issues.extend(found),
+                Err(e) => warn!("Health check panicked: {}", e),
+            }
+        }
+        issues.sort_by(|a, b| {
+            b.issue_type
+                .cmp(&a.issue_type)
+                .then_with(|| a.source.cmp(&b.source))
+        });
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct FixedCheck {
+        name: &'static str,
+        issues: Vec<HealthIssue>,
+        runs: AtomicUsize,
+    }
+
+    impl FixedCheck {
+        fn new(name: &'static str, issues: Vec<HealthIssue>) -> Arc<Self> {
+            Arc::new(Self {
+                name,
+                issues,
+                runs: AtomicUsize::new(0),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl HealthCheck for FixedCheck {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        async fn check(&self) -> Vec<HealthIssue> {
+            self.runs.fetch_add(1, Ordering::SeqCst);
+            self.issues.clone()
+        }
+    }
+
+    struct StuckCheck;
+
+    #[async_trait]
+    impl HealthCheck for StuckCheck {
+        fn name(&self) -> &'static str {
+            "StuckCheck"
+        }
+
+        async fn check(&self) -> Vec<HealthIssue> {
+            std::future::pending().await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_issues_are_sorted_errors_first_and_reused() {
+        let registry = HealthCheckRegistry::new();
+        let warnings = FixedCheck::new(
+            "AWarningCheck",
+            vec![HealthIssue::warning("AWarningCheck", "Low", "disk-space")],
+        );
+        let errors = FixedCheck::new(
+            "BErrorCheck",
+            vec![HealthIssue::error("BErrorCheck", "Down", "indexers")],
+        );
+        registry.register(warnings.clone());
+        registry.register(errors);
+        registry.register(FixedCheck::new("HealthyCheck", Vec::new()));
+
+        let issues = registry.issues().await;
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].source, "BErrorCheck");
+        assert_eq!(issues[0].issue_type, HealthIssueType::Error);
+        assert_eq!(
+            issues[1].wiki_url,
+            format!("{}#disk-space", HEALTH_WIKI_URL)
+        );
+
+        registry.issues().await;
+        assert_eq!(warnings.runs.load(Ordering::SeqCst), 1);
+        registry.run().await;
+        assert_eq!(warnings.runs.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_slow_checks_are_reported_as_errors() {
+        let registry = HealthCheckRegistry::new().with_check_timeout(Duration::from_millis(20));
+        registry.register(Arc::new(StuckCheck));
+
+        let issues = registry.issues().await;
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].source, "StuckCheck");
+        assert_eq!(issues[0].issue_type, HealthIssueType::Error);
+    }
+}
@@ -0,0 +1,288 @@
+//! Localization of user-facing strings
+//!
+//! Messages live in Fluent catalogs under `crates/core/locales`, one file per
+//! locale. The instance has a default locale (see [`init_localizer`]) and
+//! callers such as notification providers may ask for a different one per
+//! message. Lookups fall back to the instance default and then to `en-US`,
+//! so a partially translated catalog never produces a missing string.
+
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use tracing::{debug, warn};
+use unic_langid::LanguageIdentifier;
+
+/// Locale used when nothing else is configured; its catalog must be complete
+pub const DEFAULT_LOCALE: &str = "en-US";
+
+/// Catalogs compiled into the binary
+const BUILTIN_CATALOGS: &[(&str, &str)] = &[
+    ("en-US", include_str!("../locales/en-US.ftl")),
+    ("de-DE", include_str!("../locales/de-DE.ftl")),
+];
+
+/// Named argument passed into a message
+pub type MessageArg<'a> = (&'a str, String);
+
+/// Set of Fluent bundles keyed by locale
+pub struct Localizer {
+    bundles: HashMap<String, FluentBundle<FluentResource>>,
+    default_locale: String,
+}
+
+impl std::fmt::Debug for Localizer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Localizer")
+            .field("locales", &self.available_locales())
+            .field("default_locale", &self.default_locale)
+            .finish()
+    }
+}
+
+impl Localizer {
+    /// Create a localizer with the built-in catalogs
+    ///
+    /// Unknown default locales are resolved the same way as per-message
+    /// locales, ending at [`DEFAULT_LOCALE`].
+    pub fn new(default_locale: &str) -> Self {
+        let mut localizer = Self {
+            bundles: HashMap::new(),
+            default_locale: DEFAULT_LOCALE.to_string(),
+        };
+        for (locale, source) in BUILTIN_CATALOGS {
+            localizer.add_catalog(locale, source);
+        }
+        localizer.default_locale = localizer.resolve_locale(Some(default_locale)).to_string();
+        localizer
+    }
+
+    /// Add (or extend) the catalog for a locale from Fluent source
+    ///
+    /// Entries that fail to parse are skipped and logged; the rest of the
+    /// catalog is still used.
+    pub fn add_catalog(&mut self, locale: &str, source: &str) {
+        let lang_id: LanguageIdentifier = match locale.parse() {
+            Ok(lang_id) => lang_id,
+            Err(e) => {
+                warn!("Ignoring catalog with invalid locale '{}': {}", locale, e);
+                return;
+            }
+        };
+
+        let resource = match FluentResource::try_new(source.to_string()) {
+            Ok(resource) => resource,
+            Err((resource, errors)) => {
+                warn!(
+                    "Catalog for {} has {} parse errors: {:?}",
+                    locale,
+                    errors.len(),
+                    errors
+                );
+                resource
+            }
+        };
+
+        let bundle = self.bundles.entry(lang_id.to_string()).or_insert_with(|| {
+            let mut bundle = FluentBundle::new_concurrent(vec![lang_id]);
+            // Messages end up in plain text (Discord, email), where the
+            // Unicode isolation marks would show up as garbage
+            bundle.set_use_isolating(false);
+            bundle
+        });
+        bundle.add_resource_overriding(resource);
+    }
+
+    /// Locale used when a caller does not ask for one
+    pub fn default_locale(&self) -> &str {
+        &self.default_locale
+    }
+
+    /// Locales that have a catalog, sorted
+    pub fn available_locales(&self) -> Vec<String> {
+        let mut locales: Vec<String> = self.bundles.keys().cloned().collect();
+        locales.sort();
+        locales
+    }
+
+    /// Map a requested locale onto one with a catalog
+    ///
+    /// Matches exactly first (`de-DE`, case and `_` insensitive), then by
+    /// language (`de` or `de-AT` → `de-DE`), then falls back to the default.
+    pub fn resolve_locale<'a>(&'a self, requested: Option<&str>) -> &'a str {
+        let Some(requested) = requested.filter(|locale| !locale.trim().is_empty()) else {
+            return &self.default_locale;
+        };
+        let normalized = requested.trim().replace('_', "-").to_lowercase();
+
+        if let Some(locale) = self
+            .bundles
+            .keys()
+            .find(|locale| locale.to_lowercase() == normalized)
+        {
+            return locale;
+        }
+
+        let language = normalized.split('-').next().unwrap_or_default();
+        let mut candidates: Vec<&String> = self
+            .bundles
+            .keys()
+            .filter(|locale| {
+                locale
+                    .split('-')
+                    .next()
+                    .is_some_and(|lang| lang.eq_ignore_ascii_case(language))
+            })
+            .collect();
+        candidates.sort();
+        if let Some(locale) = candidates.first() {
+            return locale;
+        }
+
+        &self.default_locale
+    }
+
+    /// Format a message in the requested locale, or the best fallback
+    ///
+    /// Returns the message id itself when no catalog defines it.
+    pub fn message(&self, locale: Option<&str>, id: &str, args: &[MessageArg<'_>]) -> String {
+        let resolved = self.resolve_locale(locale);
+        [resolved, self.default_locale.as_str(), DEFAULT_LOCALE]
+            .into_iter()
+            .find_map(|candidate| self.format(candidate, id, args))
+            .unwrap_or_else(|| {
+                debug!("No translation for message '{}'", id);
+                id.to_string()
+            })
+    }
+
+    fn format(&self, locale: &str, id: &str, args: &[MessageArg<'_>]) -> Option<String> {
+        let bundle = self.bundles.get(locale)?;
+        let pattern = bundle.get_message(id)?.value()?;
+
+        let mut fluent_args = FluentArgs::new();
+        for (name, value) in args {
+            fluent_args.set(*name, value.clone());
+        }
+
+        let mut errors = Vec::new();
+        let text = bundle.format_pattern(pattern, Some(&fluent_args), &mut errors);
+        if !errors.is_empty() {
+            debug!("Formatting '{}' in {} reported {:?}", id, locale, errors);
+        }
+        Some(text.into_owned())
+    }
+}
+
+impl Default for Localizer {
+    fn default() -> Self {
+        Self::new(DEFAULT_LOCALE)
+    }
+}
+
+static LOCALIZER: OnceLock<Localizer> = OnceLock::new();
+
+/// Set up the process-wide localizer with the instance's default locale
+///
+/// Only the first call has an effect; later calls return the existing
+/// localizer.
+pub fn init_localizer(default_locale: &str) -> &'static Localizer {
+    let localizer = LOCALIZER.get_or_init(|| Localizer::new(default_locale));
+    if localizer.resolve_locale(Some(default_locale)) != localizer.default_locale() {
+        warn!(
+            "Localizer already initialized with {}, ignoring {}",
+            localizer.default_locale(),
+            default_locale
+        );
+    }
+    localizer
+}
+
+/// Process-wide localizer, initialized with [`DEFAULT_LOCALE`] if
+/// [`init_localizer`] has not run yet
+pub fn localizer() -> &'static Localizer {
+    LOCALIZER.get_or_init(Localizer::default)
+}
+
+/// Format a message in the instance's default locale
+pub fn translate(id: &str, args: &[MessageArg<'_>]) -> String {
+    localizer().message(None, id, args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_catalogs_parse() {
+        let localizer = Localizer::default();
+        assert_eq!(localizer.available_locales(), vec!["de-DE", "en-US"]);
+        assert_eq!(
+            localizer.message(None, "health-check-timed-out", &[]),
+            "Health check timed out"
+        );
+    }
+
+    #[test]
+    fn test_arguments_and_locale_selection() {
+        let localizer = Localizer::new("de");
+        assert_eq!(localizer.default_locale(), "de-DE");
+
+        let args = [("status", "503".to_string())];
+        assert_eq!(
+            localizer.message(None, "health-http-error", &args),
+            "HTTP-Fehler: 503"
+        );
+        assert_eq!(
+            localizer.message(Some("en_us"), "health-http-error", &args),
+            "HTTP error: 503"
+        );
+    }
+
+    #[test]
+    fn test_fallback_chain() {
+        let mut localizer = Localizer::new("fr-FR");
+        assert_eq!(localizer.default_locale(), DEFAULT_LOCALE);
+
+        // A partial catalog falls back to English for missing messages
+        localizer.add_catalog(
+            "fr-FR",
+            "health-request-timed-out = Délai de requête dépassé\n",
+        );
+        assert_eq!(
+            localizer.message(Some("fr"), "health-request-timed-out", &[]),
+            "Délai de requête dépassé"
+        );
+        assert_eq!(
+            localizer.message(Some("fr"), "health-check-timed-out", &[]),
+            "Health check timed out"
+        );
+        assert_eq!(
+            localizer.message(None, "no-such-message", &[]),
+            "no-such-message"
+        );
+    }
+
+    #[test]
+    fn test_every_catalog_defines_only_known_messages() {
+        let english: Vec<&str> = message_ids(BUILTIN_CATALOGS[0].1);
+        for (locale, source) in BUILTIN_CATALOGS {
+            for id in message_ids(source) {
+                assert!(
+                    english.contains(&id),
+                    "{} defines unknown message {}",
+                    locale,
+                    id
+                );
+            }
+        }
+    }
+
+    fn message_ids(source: &str) -> Vec<&str> {
+        source
+            .lines()
+            .filter(|line| !line.starts_with('#') && !line.starts_with(' '))
+            .filter_map(|line| line.split_once(" = ").map(|(id, _)| id))
+            .collect()
+    }
+}
@@ -0,0 +1,267 @@
+//! Upgrade search for movies below their quality cutoff
+//!
+//! A monitored movie whose file ranks below its quality profile's cutoff, or
+//! whose custom format score is below the profile's cutoff score, is "cutoff
+//! unmet" and searched for again as the `CutoffUnmetSearch` scheduled task or
+//! on demand as a command. The searcher is upgrade-aware, so only releases
+//! better than the existing file are grabbed. Each run searches a bounded
+//! number of movies, least recently searched first, so a large library is
+//! worked through over several runs.
+
+use super::missing_search::MovieSearcher;
+use crate::domain::repositories::{MovieFileRepository, MovieRepository, QualityProfileRepository};
+use crate::models::{Movie, MovieFile, QualityProfile};
+use crate::services::SearchCooldownPolicy;
+use crate::{CommandProgress, Result};
+use chrono::Utc;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+/// A monitored movie whose file has not met its profile's cutoff
+#[derive(Debug, Clone)]
+pub struct CutoffUnmet {
+    pub movie: Movie,
+    pub file: MovieFile,
+    /// Radarr quality ID of the current file
+    pub quality_id: i32,
+    pub profile_id: i32,
+    /// Cutoff quality (or group) ID of the profile
+    pub cutoff_quality_id: i32,
+}
+
+/// Cutoff unmet search settings
+#[derive(Debug, Clone)]
+pub struct CutoffUnmetSearchConfig {
+    /// Movies searched per run; the rest wait for the next run
+    pub max_movies_per_run: usize,
+    /// Pause between two searches
+    pub search_delay: Duration,
+}
+
+impl Default for CutoffUnmetSearchConfig {
+    fn default() -> Self {
+        Self {
+            max_movies_per_run: 25,
+            search_delay: Duration::from_secs(2),
+        }
+    }
+}
+
+/// Outcome of a cutoff unmet search run
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CutoffUnmetSummary {
+    /// Monitored movies below their cutoff
+    pub cutoff_unmet: usize,
+    /// Movies searched this run
+    pub searched: usize,
+    /// Searches that grabbed an upgrade
+    pub grabbed: usize,
+    /// Searches that failed, e.g. because the indexers were unreachable
+    pub failed: usize,
+}
+
+/// Movies among `movies` whose file is below the cutoff of their profile,
+/// or of `default_profile` when they have none
+pub fn cutoff_unmet(
+    movies: Vec<Movie>,
+    files: Vec<MovieFile>,
+    profiles: &[QualityProfile],
+    default_profile: Option<&QualityProfile>,
+) -> Vec<CutoffUnmet> {
+    let mut files: HashMap<_, _> = files
+        .into_iter()
+        .map(|file| (file.movie_id, file))
+        .collect();
+    let profiles: HashMap<_, _> = profiles
+        .iter()
+        .map(|profile| (profile.id, profile))
+        .collect();
+
+    movies
+        .into_iter()
+        .filter(|movie| movie.monitored && movie.has_file)
+        .filter_map(|movie| {
+            let profile = match movie.quality_profile_id {
+                Some(id) => profiles.get(&id).copied(),
+                None => default_profile,
+            }?;
+            let file = files.remove(&movie.id)?;
+            let quality_id = file.quality_id();
+            if profile.cutoff_met(quality_id, file.custom_format_score) {
+                return None;
+            }
+            Some(CutoffUnmet {
+                movie,
+                file,
+                quality_id,
+                profile_id: profile.id,
+                cutoff_quality_id: profile.cutoff_quality_id,
+            })
+        })
+        .collect()
+}
+
+/// Searches indexers for upgrades of movies below their quality cutoff
+pub struct CutoffUnmetSearchJob {
+    movie_repository: Arc<dyn MovieRepository>,
+    movie_file_repository: Arc<dyn MovieFileRepository>,
+    quality_profile_repository: Arc<dyn QualityProfileRepository>,
+    searcher: Arc<dyn MovieSearcher>,
+    cooldown: SearchCooldownPolicy,
+    config: CutoffUnmetSearchConfig,
+}
+
+impl CutoffUnmetSearchJob {
+    pub fn new(
+        movie_repository: Arc<dyn MovieRepository>,
+        movie_file_repository: Arc<dyn MovieFileRepository>,
+        quality_profile_repository: Arc<dyn QualityProfileRepository>,
+        searcher: Arc<dyn MovieSearcher>,
+        config: CutoffUnmetSearchConfig,
+    ) -> Self {
+        Self {
+            movie_repository,
+            movie_file_repository,
+            quality_profile_repository,
+            searcher,
+            cooldown: SearchCooldownPolicy::default(),
+            config,
+        }
+    }
+
+    /// Set the per-movie cooldown applied between automatic searches
+    pub fn with_search_cooldown(mut self, cooldown: SearchCooldownPolicy) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    /// All monitored movies currently below their quality cutoff
+    pub async fn find_cutoff_unmet(&self) -> Result<Vec<CutoffUnmet>> {
+        let movies = self.movie_repository.find_monitored().await?;
+        let files = self.movie_file_repository.list(None).await?;
+        let profiles = self.quality_profile_repository.list().await?;
+        let default_profile = self.quality_profile_repository.get_default().await?;
+        Ok(cutoff_unmet(
+            movies,
+            files,
+            &profiles,
+            default_profile.as_ref(),
+        ))
+    }
+
+    /// Search for upgrades of the cutoff unmet movies that are due
+    pub async fn run(&self, progress: Option<&CommandProgress>) -> Result<CutoffUnmetSummary> {
+        let unmet = self.find_cutoff_unmet().await?;
+        let mut summary = CutoffUnmetSummary {
+            cutoff_unmet: unmet.len(),
+            ..Default::default()
+        };
+        let movies = unmet.into_iter().map(|entry| entry.movie).collect();
+        let mut movies = self.cooldown.select_due(movies, Utc::now());
+        movies.truncate(self.config.max_movies_per_run);
+        let total = movies.len();
+        info!(
+            "Searching for upgrades of {} of {} cutoff unmet movies",
+            total, summary.cutoff_unmet
+        );
+
+        for (index, movie) in movies.iter().enumerate() {
+            if index > 0 && !self.config.search_delay.is_zero() {
+                tokio::time::sleep(self.config.search_delay).await;
+            }
+
+            match self.searcher.search_and_grab(movie).await {
+                Ok(grabbed) => {
+                    if let Some(release) = &grabbed {
+                        info!("Grabbed upgrade '{}' for {}", release, movie.title);
+                        summary.grabbed += 1;
+                    } else {
+                        debug!("No upgrade found for {}", movie.title);
+                    }
+                    if let Err(e) = self
+                        .movie_repository
+                        .record_search_result(movie.id, grabbed.is_some())
+                        .await
+                    {
+                        warn!("Failed to record search for {}: {}", movie.title, e);
+                    }
+                }
+                Err(e) => {
+                    warn!("Upgrade search for {} failed: {}", movie.title, e);
+                    summary.failed += 1;
+                }
+            }
+            summary.searched += 1;
+
+            if let Some(progress) = progress {
+                progress
+                    .update(
+                        (index + 1) as f32 * 100.0 / total as f32,
+                        format!("Searched {} of {} cutoff unmet movies", index + 1, total),
+                    )
+                    .await;
+            }
+        }
+
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(id: i32, cutoff_quality_id: i32) -> QualityProfile {
+        let mut profile = QualityProfile::new(format!("Profile {}", id), cutoff_quality_id);
+        profile.id = id;
+        profile.update_items(serde_json::json!([
+            {"quality": {"id": 5}, "items": [], "allowed": true},
+            {"quality": {"id": 3}, "items": [], "allowed": true},
+            {"quality": {"id": 7}, "items": [], "allowed": true}
+        ]));
+        profile
+    }
+
+    fn movie_with_file(
+        title: &str,
+        profile_id: Option<i32>,
+        file_name: &str,
+    ) -> (Movie, MovieFile) {
+        let mut movie = Movie::new(1, title.to_string());
+        movie.monitored = true;
+        movie.has_file = true;
+        movie.quality_profile_id = profile_id;
+        let file = MovieFile::new(movie.id, file_name.to_string(), 1, serde_json::json!({}));
+        (movie, file)
+    }
+
+    #[test]
+    fn test_cutoff_unmet_uses_file_quality_and_profile() {
+        let profiles = vec![profile(1, 3), profile(2, 7)];
+        let entries = vec![
+            movie_with_file("Below", Some(1), "Below.720p.WEB-DL.mkv"),
+            movie_with_file("At Cutoff", Some(1), "At.Cutoff.1080p.WEB-DL.mkv"),
+            movie_with_file("Stricter", Some(2), "Stricter.1080p.WEB-DL.mkv"),
+            movie_with_file("Default", None, "Default.720p.WEB-DL.mkv"),
+            movie_with_file("Stored", Some(1), "Stored.mkv"),
+        ];
+        let (mut movies, mut files): (Vec<_>, Vec<_>) = entries.into_iter().unzip();
+        files[4].quality = serde_json::json!({ "quality_id": 7 });
+        let mut unmonitored = movie_with_file("Unmonitored", Some(1), "Unmonitored.720p.WEB.mkv");
+        unmonitored.0.monitored = false;
+        movies.push(unmonitored.0);
+        files.push(unmonitored.1);
+
+        let unmet = cutoff_unmet(movies, files, &profiles, Some(&profiles[1]));
+        let titles: Vec<&str> = unmet
+            .iter()
+            .map(|entry| entry.movie.title.as_str())
+            .collect();
+        assert_eq!(titles, vec!["Below", "Stricter", "Default"]);
+        assert_eq!(unmet[0].quality_id, 5);
+        assert_eq!(unmet[0].cutoff_quality_id, 3);
+        assert_eq!(unmet[2].profile_id, 2);
+    }
+}
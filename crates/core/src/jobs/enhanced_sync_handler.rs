@@ -3,8 +3,10 @@
 //! Provides sophisticated sync handling with performance monitoring,
 //! conflict resolution strategies, and comprehensive audit logging.
 
+use crate::domain::repositories::ListExclusionRepository;
 use crate::jobs::list_sync::{
-    ConflictResolution, MovieProvenance, SyncError, SyncHandler, SyncJob, SyncResult, SyncStatus,
+    ConflictResolution, ListFetcher, ListSyncItem, MovieProvenance, SyncError, SyncHandler,
+    SyncJob, SyncResult, SyncStatus,
 };
 use crate::models::Movie;
 use chrono::Utc;
@@ -25,6 +27,8 @@ pub struct EnhancedSyncHandler {
     monitoring: Arc<dyn SyncMonitoring>,
     conflict_resolver: Arc<ConflictResolver>,
     performance_tracker: Arc<RwLock<PerformanceTracker>>,
+    list_fetcher: Option<Arc<dyn ListFetcher>>,
+    exclusion_repository: Option<Arc<dyn ListExclusionRepository>>,
     config: SyncHandlerConfig,
 }
 
@@ -568,9 +572,27 @@ impl EnhancedSyncHandler {
             monitoring,
             conflict_resolver,
             performance_tracker: Arc::new(RwLock::new(PerformanceTracker::default())),
+            list_fetcher: None,
+            exclusion_repository: None,
             config,
         }
     }
+
+    /// Fetch list contents from real providers instead of simulating a sync
+    pub fn with_list_fetcher(mut self, list_fetcher: Arc<dyn ListFetcher>) -> Self {
+        self.list_fetcher = Some(list_fetcher);
+        self
+    }
+
+    /// Skip list items the user excluded, e.g. by deleting the movie with
+    /// "add exclusion"
+    pub fn with_exclusions(
+        mut self,
+        exclusion_repository: Arc<dyn ListExclusionRepository>,
+    ) -> Self {
+        self.exclusion_repository = Some(exclusion_repository);
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -688,8 +710,12 @@ impl EnhancedSyncHandler {
         job: &SyncJob,
         _sync_id: Uuid,
     ) -> Result<SyncResult, SyncError> {
-        // This would contain the actual sync logic specific to each source type
-        // For now, we'll return a mock result
+        if let Some(fetcher) = &self.list_fetcher {
+            return self.sync_from_fetcher(fetcher.as_ref(), job).await;
+        }
+
+        // Without a list fetcher there is no provider to talk to, so the sync
+        // is simulated
 
         let start_time = Utc::now();
 
@@ -730,6 +756,159 @@ impl EnhancedSyncHandler {
     }
 }
 
+impl EnhancedSyncHandler {
+    /// Fetch the job's list and add the movies that are not in the library yet
+    async fn sync_from_fetcher(
+        &self,
+        fetcher: &dyn ListFetcher,
+        job: &SyncJob,
+    ) -> Result<SyncResult, SyncError> {
+        let start_time = Utc::now();
+        let fetch_started = Instant::now();
+        let items = fetcher.fetch(job).await?;
+        {
+            let mut tracker = self.performance_tracker.write().await;
+            tracker.record_api_request();
+            tracker.record_batch_processed(items.len(), fetch_started.elapsed());
+        }
+        info!(
+            "Fetched {} items from {} list '{}'",
+            items.len(),
+            job.source_type,
+            job.list_name
+        );
+
+        let mut items_added = 0;
+        let mut items_excluded = 0;
+        let mut errors = Vec::new();
+        let mut provenance = Vec::new();
+
+        for item in &items {
+            match self.add_list_item(job, item).await {
+                Ok(Some(movie)) => {
+                    items_added += 1;
+                    provenance.push(MovieProvenance {
+                        movie_id: movie.id,
+                        list_id: job.list_id,
+                        list_name: job.list_name.clone(),
+                        source_type: job.source_type.clone(),
+                        added_at: Utc::now(),
+                        metadata: item.metadata.clone(),
+                    });
+                }
+                Ok(None) => items_excluded += 1,
+                Err(e) => {
+                    warn!("Failed to add '{}' from list: {}", item.title, e);
+                    self.performance_tracker
+                        .write()
+                        .await
+                        .record_error(e.to_string());
+                    errors.push(format!("{}: {}", item.title, e));
+                }
+            }
+        }
+
+        let status = match (errors.is_empty(), errors.len() == items.len()) {
+            (true, _) => SyncStatus::Success,
+            (false, true) => SyncStatus::Failed,
+            (false, false) => SyncStatus::Partial,
+        };
+        let end_time = Utc::now();
+
+        Ok(SyncResult {
+            job_id: job.id,
+            list_id: job.list_id,
+            status,
+            started_at: start_time,
+            completed_at: end_time,
+            duration_ms: (end_time - start_time).num_milliseconds(),
+            items_found: items.len(),
+            items_added,
+            items_updated: 0,
+            items_excluded,
+            items_conflicted: 0,
+            error_message: (!errors.is_empty()).then(|| errors.join("; ")),
+            provenance,
+        })
+    }
+
+    /// Whether the user excluded the item by TMDb or IMDb ID
+    async fn is_excluded(&self, item: &ListSyncItem) -> Result<bool, SyncError> {
+        let Some(exclusions) = &self.exclusion_repository else {
+            return Ok(false);
+        };
+        let db_error = |e: crate::RadarrError| SyncError::DatabaseError(e.to_string());
+
+        if let Some(tmdb_id) = item.tmdb_id {
+            if exclusions
+                .find_by_tmdb_id(tmdb_id)
+                .await
+                .map_err(db_error)?
+                .is_some()
+            {
+                return Ok(true);
+            }
+        }
+        if let Some(imdb_id) = &item.imdb_id {
+            if exclusions
+                .find_by_imdb_id(imdb_id)
+                .await
+                .map_err(db_error)?
+                .is_some()
+            {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Add a list item to the library; `None` when it is already there, is
+    /// excluded or cannot be identified
+    async fn add_list_item(
+        &self,
+        job: &SyncJob,
+        item: &ListSyncItem,
+    ) -> Result<Option<Movie>, SyncError> {
+        let tmdb_id = match item.tmdb_id {
+            Some(tmdb_id) => tmdb_id,
+            None => {
+                debug!("Skipping '{}' without a TMDb ID", item.title);
+                return Ok(None);
+            }
+        };
+        if self.is_excluded(item).await? {
+            debug!(
+                "Skipping excluded '{}' from '{}'",
+                item.title, job.list_name
+            );
+            return Ok(None);
+        }
+
+        let existing = match self.movie_repository.find_by_tmdb_id(tmdb_id).await? {
+            Some(movie) => Some(movie),
+            None => match &item.imdb_id {
+                Some(imdb_id) => self.movie_repository.find_by_imdb_id(imdb_id).await?,
+                None => None,
+            },
+        };
+        if existing.is_some() {
+            return Ok(None);
+        }
+
+        let mut movie = Movie::new(tmdb_id, item.title.clone());
+        movie.year = item.year;
+        movie.imdb_id = item.imdb_id.clone();
+        movie.quality_profile_id = job.quality_profile_id;
+        movie.tags = job.tags.clone();
+        if let Some(root_folder_path) = &job.root_folder_path {
+            movie.metadata["root_folder_path"] = serde_json::json!(root_folder_path);
+        }
+        let created = self.movie_repository.create(&movie).await?;
+        debug!("Added '{}' from list '{}'", created.title, job.list_name);
+        Ok(Some(created))
+    }
+}
+
 // Include comprehensive test module
 #[cfg(test)]
 #[path = "enhanced_sync_handler_tests.rs"]
@@ -5,12 +5,15 @@
 
 #[cfg(test)]
 mod tests {
+    use crate::domain::repositories::ListExclusionRepository;
     use crate::jobs::enhanced_sync_handler::{
         ConflictResolver, ConflictStrategy, EnhancedSyncHandler, ListSyncRepository,
         MovieRepository, PerformanceMetrics, PerformanceTracker, SyncHandlerConfig, SyncMonitoring,
     };
-    use crate::jobs::list_sync::{ConflictResolution, SyncError, SyncHandler, SyncJob, SyncStatus};
-    use crate::models::Movie;
+    use crate::jobs::list_sync::{
+        ConflictResolution, ListFetcher, ListSyncItem, SyncError, SyncHandler, SyncJob, SyncStatus,
+    };
+    use crate::models::{ListExclusion, Movie};
     use chrono::{DateTime, Utc};
     use std::collections::HashMap;
     use std::sync::Arc;
@@ -325,6 +328,10 @@ mod tests {
             list_id: Uuid::new_v4(),
             list_name: "Test List".to_string(),
             source_type: "imdb".to_string(),
+            list_url: None,
+            quality_profile_id: None,
+            root_folder_path: None,
+            tags: vec![],
             enabled: true,
             sync_interval: chrono::Duration::hours(6),
             next_sync: Utc::now(),
@@ -360,6 +367,177 @@ mod tests {
         assert_eq!(perf_records[0].1, job.list_id);
     }
 
+    /// List fetcher returning fixed items
+    struct StaticListFetcher {
+        items: Vec<ListSyncItem>,
+    }
+
+    #[async_trait::async_trait]
+    impl ListFetcher for StaticListFetcher {
+        async fn fetch(&self, _job: &SyncJob) -> Result<Vec<ListSyncItem>, SyncError> {
+            Ok(self.items.clone())
+        }
+    }
+
+    fn list_item(tmdb_id: Option<i32>, title: &str) -> ListSyncItem {
+        ListSyncItem {
+            tmdb_id,
+            imdb_id: None,
+            title: title.to_string(),
+            year: Some(2021),
+            metadata: serde_json::json!({ "source": "trakt" }),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sync_adds_fetched_movies() {
+        let movie_repo = Arc::new(MockMovieRepository::new());
+        movie_repo.add_movie(create_test_movie(603, "high")).await;
+
+        let fetcher = Arc::new(StaticListFetcher {
+            items: vec![
+                list_item(Some(603), "The Matrix"),
+                list_item(Some(438631), "Dune"),
+                list_item(None, "Unmatched"),
+            ],
+        });
+        let handler = EnhancedSyncHandler::new(
+            movie_repo.clone(),
+            Arc::new(MockListSyncRepository::new()),
+            Arc::new(MockSyncMonitoring::new()),
+            SyncHandlerConfig::default(),
+        )
+        .with_list_fetcher(fetcher);
+
+        let job = SyncJob {
+            id: Uuid::new_v4(),
+            list_id: Uuid::new_v4(),
+            list_name: "Watchlist".to_string(),
+            source_type: "trakt".to_string(),
+            list_url: Some("watchlist".to_string()),
+            quality_profile_id: Some(4),
+            root_folder_path: Some("/movies/lists".to_string()),
+            tags: vec![7],
+            enabled: true,
+            sync_interval: chrono::Duration::hours(6),
+            next_sync: Utc::now(),
+            last_sync: None,
+            priority: 5,
+            retry_count: 0,
+            max_retries: 3,
+        };
+
+        let result = handler.execute_sync(&job).await.unwrap();
+        assert_eq!(result.status, SyncStatus::Success);
+        assert_eq!(result.items_found, 3);
+        assert_eq!(result.items_added, 1);
+        assert_eq!(result.items_excluded, 2);
+        assert_eq!(result.provenance.len(), 1);
+
+        let added = movie_repo.find_by_tmdb_id(438631).await.unwrap().unwrap();
+        assert_eq!(added.title, "Dune");
+        assert_eq!(added.year, Some(2021));
+        assert_eq!(added.quality_profile_id, Some(4));
+        assert_eq!(added.metadata["root_folder_path"], "/movies/lists");
+        assert_eq!(added.tags, vec![7]);
+    }
+
+    /// Exclusions kept in memory
+    struct MockExclusionRepository {
+        exclusions: Vec<ListExclusion>,
+    }
+
+    #[async_trait::async_trait]
+    impl ListExclusionRepository for MockExclusionRepository {
+        async fn create(&self, _exclusion: &ListExclusion) -> crate::Result<()> {
+            Ok(())
+        }
+
+        async fn find_by_tmdb_id(&self, tmdb_id: i32) -> crate::Result<Option<ListExclusion>> {
+            Ok(self
+                .exclusions
+                .iter()
+                .find(|e| e.tmdb_id == Some(tmdb_id))
+                .cloned())
+        }
+
+        async fn find_by_imdb_id(&self, imdb_id: &str) -> crate::Result<Option<ListExclusion>> {
+            Ok(self
+                .exclusions
+                .iter()
+                .find(|e| e.imdb_id.as_deref() == Some(imdb_id))
+                .cloned())
+        }
+
+        async fn find_by_id(&self, id: Uuid) -> crate::Result<Option<ListExclusion>> {
+            Ok(self.exclusions.iter().find(|e| e.id == id).cloned())
+        }
+
+        async fn list(&self) -> crate::Result<Vec<ListExclusion>> {
+            Ok(self.exclusions.clone())
+        }
+
+        async fn delete(&self, _id: Uuid) -> crate::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sync_skips_excluded_movies() {
+        let movie_repo = Arc::new(MockMovieRepository::new());
+        let mut by_imdb = list_item(Some(27205), "Inception");
+        by_imdb.imdb_id = Some("tt1375666".to_string());
+
+        let fetcher = Arc::new(StaticListFetcher {
+            items: vec![
+                list_item(Some(438631), "Dune"),
+                by_imdb,
+                list_item(Some(603), "The Matrix"),
+            ],
+        });
+        let mut dune = ListExclusion::for_movie(&create_test_movie(438631, "high"), "Deleted");
+        dune.imdb_id = None;
+        let mut inception = ListExclusion::for_movie(&create_test_movie(1, "high"), "Deleted");
+        inception.tmdb_id = None;
+        inception.imdb_id = Some("tt1375666".to_string());
+
+        let handler = EnhancedSyncHandler::new(
+            movie_repo.clone(),
+            Arc::new(MockListSyncRepository::new()),
+            Arc::new(MockSyncMonitoring::new()),
+            SyncHandlerConfig::default(),
+        )
+        .with_list_fetcher(fetcher)
+        .with_exclusions(Arc::new(MockExclusionRepository {
+            exclusions: vec![dune, inception],
+        }));
+
+        let job = SyncJob {
+            id: Uuid::new_v4(),
+            list_id: Uuid::new_v4(),
+            list_name: "Watchlist".to_string(),
+            source_type: "imdb".to_string(),
+            list_url: Some("ls000000001".to_string()),
+            quality_profile_id: None,
+            root_folder_path: None,
+            tags: vec![],
+            enabled: true,
+            sync_interval: chrono::Duration::hours(6),
+            next_sync: Utc::now(),
+            last_sync: None,
+            priority: 5,
+            retry_count: 0,
+            max_retries: 3,
+        };
+
+        let result = handler.execute_sync(&job).await.unwrap();
+        assert_eq!(result.items_added, 1);
+        assert_eq!(result.items_excluded, 2);
+        assert!(movie_repo.find_by_tmdb_id(438631).await.unwrap().is_none());
+        assert!(movie_repo.find_by_tmdb_id(27205).await.unwrap().is_none());
+        assert!(movie_repo.find_by_tmdb_id(603).await.unwrap().is_some());
+    }
+
     #[tokio::test]
     async fn test_intelligent_conflict_resolution() {
         let movie_repo = Arc::new(MockMovieRepository::new());
@@ -469,6 +647,10 @@ mod tests {
             list_id: Uuid::new_v4(),
             list_name: "Performance Test List".to_string(),
             source_type: "tmdb".to_string(),
+            list_url: None,
+            quality_profile_id: None,
+            root_folder_path: None,
+            tags: vec![],
             enabled: true,
             sync_interval: chrono::Duration::hours(6),
             next_sync: Utc::now(),
@@ -229,6 +229,10 @@ impl MockSetup {
             list_id: Uuid::new_v4(),
             list_name: name.to_string(),
             source_type: source.to_string(),
+            list_url: None,
+            quality_profile_id: None,
+            root_folder_path: None,
+            tags: vec![],
             enabled: true,
             sync_interval: Duration::hours(6),
             next_sync: Utc::now() + Duration::minutes(1),
@@ -1,10 +1,12 @@
+use crate::commands::{Command, CommandExecutor, CommandProgress};
 use crate::models::Movie;
+use crate::polling::{apply_jitter, PollConfig, PollSchedule};
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::{Mutex, RwLock};
-use tokio::time::{interval, Duration as TokioDuration};
+use tokio::time::Duration as TokioDuration;
 use tracing::{error, info, warn};
 // use tracing::debug; // Currently unused
 use uuid::Uuid;
@@ -24,6 +26,19 @@ pub struct SyncJob {
     pub list_id: Uuid,
     pub list_name: String,
     pub source_type: String,
+    /// Where the list lives (URL or provider-specific location), passed to
+    /// the list fetcher
+    #[serde(default)]
+    pub list_url: Option<String>,
+    /// Quality profile given to movies added from this list
+    #[serde(default)]
+    pub quality_profile_id: Option<i32>,
+    /// Root folder movies added from this list are filed under
+    #[serde(default)]
+    pub root_folder_path: Option<String>,
+    /// Tags given to movies added from this list
+    #[serde(default)]
+    pub tags: Vec<i32>,
     pub enabled: bool,
     pub sync_interval: Duration,
     pub next_sync: DateTime<Utc>,
@@ -54,6 +69,23 @@ pub trait SyncHandler: Send + Sync {
     async fn store_results(&self, results: &SyncResult) -> Result<(), SyncError>;
 }
 
+/// A movie found on an external list
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListSyncItem {
+    pub tmdb_id: Option<i32>,
+    pub imdb_id: Option<String>,
+    pub title: String,
+    pub year: Option<i32>,
+    /// Provider-specific details kept in the movie's provenance
+    pub metadata: serde_json::Value,
+}
+
+/// Fetches the movies of a job's list from its provider
+#[async_trait::async_trait]
+pub trait ListFetcher: Send + Sync {
+    async fn fetch(&self, job: &SyncJob) -> Result<Vec<ListSyncItem>, SyncError>;
+}
+
 /// Result of a sync operation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SyncResult {
@@ -189,11 +221,9 @@ impl ListSyncScheduler {
     /// Start the scheduler loop
     pub async fn start(&self) {
         info!("Starting list sync scheduler");
-        let mut interval = interval(TokioDuration::from_secs(60)); // Check every minute
+        let schedule = PollSchedule::new(PollConfig::new(TokioDuration::from_secs(60))); // Check every minute
 
         loop {
-            interval.tick().await;
-
             // Check for jobs that need to run
             let jobs = self.jobs.read().await;
             let now = Utc::now();
@@ -213,7 +243,7 @@ impl ListSyncScheduler {
                                 let mut jobs = scheduler.jobs.write().await;
                                 if let Some(job) = jobs.get_mut(&job.id) {
                                     job.retry_count += 1;
-                                    job.next_sync = Utc::now() + Duration::minutes(5); // Retry in 5 minutes
+                                    job.next_sync = Utc::now() + retry_delay(job.retry_count);
                                     warn!(
                                         "Scheduled retry {} of {} for job {}",
                                         job.retry_count, job.max_retries, job.id
@@ -224,6 +254,9 @@ impl ListSyncScheduler {
                     });
                 }
             }
+            drop(jobs);
+
+            schedule.wait().await;
         }
     }
 
@@ -295,7 +328,8 @@ impl ListSyncScheduler {
                 let mut jobs = self.jobs.write().await;
                 if let Some(job) = jobs.get_mut(&job.id) {
                     job.last_sync = Some(Utc::now());
-                    job.next_sync = Utc::now() + job.sync_interval;
+                    // Jitter keeps lists added together from syncing in lockstep
+                    job.next_sync = Utc::now() + jittered(job.sync_interval);
                     job.retry_count = 0; // Reset retry count on success
                     info!(
                         "Next sync for {} scheduled at {}",
@@ -338,6 +372,75 @@ impl ListSyncScheduler {
     }
 }
 
+/// Delay before retry number `retry_count` of a failed sync
+///
+/// Starts at 5 minutes and doubles per retry, up to an hour.
+fn retry_delay(retry_count: u32) -> Duration {
+    let mut schedule = PollSchedule::new(
+        PollConfig::new(TokioDuration::from_secs(5 * 60))
+            .with_max_backoff(TokioDuration::from_secs(60 * 60)),
+    );
+    for _ in 1..retry_count {
+        schedule.record_failure();
+    }
+    Duration::from_std(schedule.next_delay()).unwrap_or_else(|_| Duration::minutes(5))
+}
+
+/// `interval` spread by ±10%
+fn jittered(interval: Duration) -> Duration {
+    interval
+        .to_std()
+        .ok()
+        .and_then(|interval| Duration::from_std(apply_jitter(interval, 0.1)).ok())
+        .unwrap_or(interval)
+}
+
+/// Runs `ImportListSync` commands by syncing every enabled list now
+#[async_trait::async_trait]
+impl CommandExecutor for ListSyncScheduler {
+    async fn execute(
+        &self,
+        _command: &Command,
+        progress: &CommandProgress,
+    ) -> crate::Result<String> {
+        let job_ids: Vec<Uuid> = self
+            .jobs
+            .read()
+            .await
+            .values()
+            .filter(|job| job.enabled)
+            .map(|job| job.id)
+            .collect();
+        let total = job_ids.len();
+        let mut failed = 0;
+
+        for (index, job_id) in job_ids.into_iter().enumerate() {
+            progress
+                .update(
+                    index as f32 * 100.0 / total as f32,
+                    format!("Syncing list {} of {}", index + 1, total),
+                )
+                .await;
+            if let Err(e) = self.trigger_sync(job_id).await {
+                warn!("Import list sync {} failed: {}", job_id, e);
+                failed += 1;
+            }
+        }
+
+        if total > 0 && failed == total {
+            return Err(crate::RadarrError::ExternalServiceError {
+                service: "import_lists".to_string(),
+                error: format!("All {} import lists failed to sync", total),
+            });
+        }
+        Ok(format!(
+            "Synced {} of {} import lists",
+            total - failed,
+            total
+        ))
+    }
+}
+
 /// Status information for a sync job
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JobStatus {
@@ -397,6 +500,10 @@ mod tests {
             list_id: Uuid::new_v4(),
             list_name: "Test List".to_string(),
             source_type: "test".to_string(),
+            list_url: None,
+            quality_profile_id: None,
+            root_folder_path: None,
+            tags: vec![],
             enabled: true,
             sync_interval: Duration::hours(6),
             next_sync: Utc::now() + Duration::hours(1),
@@ -413,4 +520,15 @@ mod tests {
         assert_eq!(statuses.len(), 1);
         assert!(statuses[0].last_sync.is_some());
     }
+
+    #[test]
+    fn test_retry_delay_backs_off() {
+        let first = retry_delay(1);
+        assert!(first >= Duration::seconds(270) && first <= Duration::seconds(330));
+
+        let third = retry_delay(3);
+        assert!(third >= Duration::seconds(1080) && third <= Duration::seconds(1320));
+
+        assert!(retry_delay(20) <= Duration::seconds(3960));
+    }
 }
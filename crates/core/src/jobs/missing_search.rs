@@ -0,0 +1,310 @@
+//! Automatic search for missing movies
+//!
+//! Monitored movies without a file are searched for periodically, as the
+//! `MissingMoviesSearch` scheduled task or on demand as a command. Movies
+//! that have not reached their minimum availability are skipped, as are
+//! movies still cooling down after earlier searches came up empty. Each run
+//! searches a bounded number of movies, least recently searched first, with a
+//! pause between searches on top of the indexer client's own rate limiting.
+
+use crate::domain::repositories::MovieRepository;
+use crate::models::Movie;
+use crate::services::SearchCooldownPolicy;
+use crate::{CommandProgress, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+/// Searches the indexers for a movie and grabs the best release
+#[async_trait]
+pub trait MovieSearcher: Send + Sync {
+    /// Title of the grabbed release, `None` when nothing suitable was found
+    async fn search_and_grab(&self, movie: &Movie) -> Result<Option<String>>;
+}
+
+/// Missing movie search settings
+#[derive(Debug, Clone)]
+pub struct MissingMovieSearchConfig {
+    /// Movies searched per run; the rest wait for the next run
+    pub max_movies_per_run: usize,
+    /// Pause between two searches
+    pub search_delay: Duration,
+}
+
+impl Default for MissingMovieSearchConfig {
+    fn default() -> Self {
+        Self {
+            max_movies_per_run: 50,
+            search_delay: Duration::from_secs(2),
+        }
+    }
+}
+
+/// Outcome of a missing movie search run
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MissingSearchSummary {
+    /// Monitored movies without a file
+    pub missing: usize,
+    /// Movies searched this run
+    pub searched: usize,
+    /// Searches that grabbed a release
+    pub grabbed: usize,
+    /// Searches that failed, e.g. because the indexers were unreachable
+    pub failed: usize,
+}
+
+/// Searches indexers for monitored movies without files
+pub struct MissingMovieSearchJob {
+    movie_repository: Arc<dyn MovieRepository>,
+    searcher: Arc<dyn MovieSearcher>,
+    cooldown: SearchCooldownPolicy,
+    config: MissingMovieSearchConfig,
+}
+
+impl MissingMovieSearchJob {
+    pub fn new(
+        movie_repository: Arc<dyn MovieRepository>,
+        searcher: Arc<dyn MovieSearcher>,
+        config: MissingMovieSearchConfig,
+    ) -> Self {
+        Self {
+            movie_repository,
+            searcher,
+            cooldown: SearchCooldownPolicy::default(),
+            config,
+        }
+    }
+
+    /// Set the per-movie cooldown applied between automatic searches
+    pub fn with_search_cooldown(mut self, cooldown: SearchCooldownPolicy) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    /// The movies a run at `now` searches, least recently searched first
+    pub fn select(&self, missing: Vec<Movie>, now: DateTime<Utc>) -> Vec<Movie> {
+        let available: Vec<Movie> = missing
+            .into_iter()
+            .filter(|movie| movie.monitored && !movie.has_file && movie.is_available(now))
+            .collect();
+        let mut due = self.cooldown.select_due(available, now);
+        due.truncate(self.config.max_movies_per_run);
+        due
+    }
+
+    /// Search for the missing movies that are due
+    pub async fn run(&self, progress: Option<&CommandProgress>) -> Result<MissingSearchSummary> {
+        let missing = self.movie_repository.find_missing_files().await?;
+        let mut summary = MissingSearchSummary {
+            missing: missing.len(),
+            ..Default::default()
+        };
+        let movies = self.select(missing, Utc::now());
+        let total = movies.len();
+        info!(
+            "Searching for {} of {} missing movies",
+            total, summary.missing
+        );
+
+        for (index, movie) in movies.iter().enumerate() {
+            if index > 0 && !self.config.search_delay.is_zero() {
+                tokio::time::sleep(self.config.search_delay).await;
+            }
+
+            match self.searcher.search_and_grab(movie).await {
+                Ok(grabbed) => {
+                    if let Some(release) = &grabbed {
+                        info!("Grabbed '{}' for missing movie {}", release, movie.title);
+                        summary.grabbed += 1;
+                    } else {
+                        debug!("No suitable release for missing movie {}", movie.title);
+                    }
+                    // Indexer errors don't count against the movie's cooldown
+                    if let Err(e) = self
+                        .movie_repository
+                        .record_search_result(movie.id, grabbed.is_some())
+                        .await
+                    {
+                        warn!("Failed to record search for {}: {}", movie.title, e);
+                    }
+                }
+                Err(e) => {
+                    warn!("Search for missing movie {} failed: {}", movie.title, e);
+                    summary.failed += 1;
+                }
+            }
+            summary.searched += 1;
+
+            if let Some(progress) = progress {
+                progress
+                    .update(
+                        (index + 1) as f32 * 100.0 / total as f32,
+                        format!("Searched {} of {} missing movies", index + 1, total),
+                    )
+                    .await;
+            }
+        }
+
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{MinimumAvailability, MovieStatus};
+
+    struct NoSearcher;
+
+    #[async_trait]
+    impl MovieSearcher for NoSearcher {
+        async fn search_and_grab(&self, _movie: &Movie) -> Result<Option<String>> {
+            Ok(None)
+        }
+    }
+
+    fn movie(title: &str, release_date: &str, availability: MinimumAvailability) -> Movie {
+        let mut movie = Movie::new(1, title.to_string());
+        movie.monitored = true;
+        movie.minimum_availability = availability;
+        movie.metadata = serde_json::json!({ "tmdb": { "release_date": release_date } });
+        movie
+    }
+
+    /// Repository whose only movies are the given missing ones
+    struct MissingRepository(Vec<Movie>);
+
+    #[async_trait]
+    impl MovieRepository for MissingRepository {
+        async fn find_by_id(&self, _id: uuid::Uuid) -> Result<Option<Movie>> {
+            Ok(None)
+        }
+        async fn find_by_tmdb_id(&self, _tmdb_id: i32) -> Result<Option<Movie>> {
+            Ok(None)
+        }
+        async fn find_by_imdb_id(&self, _imdb_id: &str) -> Result<Option<Movie>> {
+            Ok(None)
+        }
+        async fn find_monitored(&self) -> Result<Vec<Movie>> {
+            Ok(vec![])
+        }
+        async fn find_missing_files(&self) -> Result<Vec<Movie>> {
+            Ok(self.0.clone())
+        }
+        async fn find_added_since(&self, _since: DateTime<Utc>) -> Result<Vec<Movie>> {
+            Ok(vec![])
+        }
+        async fn search_by_title(&self, _query: &str, _limit: i32) -> Result<Vec<Movie>> {
+            Ok(vec![])
+        }
+        async fn create(&self, movie: &Movie) -> Result<Movie> {
+            Ok(movie.clone())
+        }
+        async fn update(&self, movie: &Movie) -> Result<Movie> {
+            Ok(movie.clone())
+        }
+        async fn delete(&self, _id: uuid::Uuid) -> Result<()> {
+            Ok(())
+        }
+        async fn list(&self, _offset: i64, _limit: i32) -> Result<Vec<Movie>> {
+            Ok(vec![])
+        }
+        async fn count(&self) -> Result<i64> {
+            Ok(0)
+        }
+        async fn update_last_search_time(&self, _id: uuid::Uuid) -> Result<()> {
+            Ok(())
+        }
+        async fn record_search_result(&self, _id: uuid::Uuid, _found: bool) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_select_respects_availability_and_cooldown() {
+        let job = MissingMovieSearchJob::new(
+            Arc::new(MissingRepository(vec![])),
+            Arc::new(NoSearcher),
+            MissingMovieSearchConfig {
+                max_movies_per_run: 2,
+                search_delay: Duration::ZERO,
+            },
+        );
+        let now = "2025-06-01T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+
+        let old = movie("Old", "2024-01-01", MinimumAvailability::Released);
+        let mut searched = movie("Searched", "2024-01-01", MinimumAvailability::Released);
+        searched.last_search_time = Some(now - chrono::Duration::hours(1));
+        let mut stale = movie("Stale", "2024-01-01", MinimumAvailability::Released);
+        stale.last_search_time = Some(now - chrono::Duration::days(2));
+        // In cinemas for a month: available for InCinemas, not yet for Released
+        let cinemas = movie("Cinemas", "2025-05-01", MinimumAvailability::InCinemas);
+        let not_released = movie("Not Released", "2025-05-01", MinimumAvailability::Released);
+        let mut unmonitored = movie("Unmonitored", "2024-01-01", MinimumAvailability::Announced);
+        unmonitored.monitored = false;
+
+        let missing = vec![
+            searched,
+            not_released,
+            unmonitored,
+            stale.clone(),
+            cinemas.clone(),
+            old,
+        ];
+        let titles: Vec<String> = job
+            .select(missing.clone(), now)
+            .into_iter()
+            .map(|movie| movie.title)
+            .collect();
+        assert_eq!(titles, vec!["Cinemas", "Old"]);
+
+        let job = MissingMovieSearchJob::new(
+            Arc::new(MissingRepository(vec![])),
+            Arc::new(NoSearcher),
+            MissingMovieSearchConfig::default(),
+        );
+        let titles: Vec<String> = job
+            .select(missing, now)
+            .into_iter()
+            .map(|movie| movie.title)
+            .collect();
+        assert_eq!(titles, vec!["Cinemas", "Old", "Stale"]);
+
+        let mut tbd = movie("TBD", "", MinimumAvailability::Released);
+        assert!(!tbd.is_available(now));
+        tbd.status = MovieStatus::Released;
+        assert!(tbd.is_available(now));
+    }
+
+    #[tokio::test]
+    async fn test_run_applies_search_cooldown() {
+        let mut searched = movie("Searched", "2024-01-01", MinimumAvailability::Released);
+        searched.last_search_time = Some(Utc::now() - chrono::Duration::hours(2));
+        let job = |cooldown: SearchCooldownPolicy| {
+            MissingMovieSearchJob::new(
+                Arc::new(MissingRepository(vec![searched.clone()])),
+                Arc::new(NoSearcher),
+                MissingMovieSearchConfig {
+                    search_delay: Duration::ZERO,
+                    ..Default::default()
+                },
+            )
+            .with_search_cooldown(cooldown)
+        };
+
+        let summary = job(SearchCooldownPolicy::default())
+            .run(None)
+            .await
+            .unwrap();
+        assert_eq!((summary.missing, summary.searched), (1, 0));
+
+        let hourly = SearchCooldownPolicy {
+            daily_interval: chrono::Duration::hours(1),
+            ..Default::default()
+        };
+        let summary = job(hourly).run(None).await.unwrap();
+        assert_eq!((summary.missing, summary.searched), (1, 1));
+    }
+}
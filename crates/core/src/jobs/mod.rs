@@ -1,12 +1,31 @@
+pub mod cutoff_unmet;
 pub mod enhanced_sync_handler;
 pub mod integration_simple;
 pub mod list_sync;
+pub mod missing_search;
+pub mod refresh_movie;
+pub mod scheduler;
 
 pub use list_sync::{
-    ConflictResolution, JobStatus, ListSyncScheduler, MovieProvenance, SyncError, SyncHandler,
-    SyncJob, SyncResult, SyncStatus,
+    ConflictResolution, JobStatus, ListFetcher, ListSyncItem, ListSyncScheduler, MovieProvenance,
+    SyncError, SyncHandler, SyncJob, SyncResult, SyncStatus,
 };
 
+pub use cutoff_unmet::{
+    cutoff_unmet, CutoffUnmet, CutoffUnmetSearchConfig, CutoffUnmetSearchJob, CutoffUnmetSummary,
+};
+
+pub use missing_search::{
+    MissingMovieSearchConfig, MissingMovieSearchJob, MissingSearchSummary, MovieSearcher,
+};
+
+pub use refresh_movie::{
+    apply_refresh, diff_movie, MovieFieldChange, MovieMetadataSource, MovieRatingsSource,
+    RefreshMovieJob, RefreshSummary, REFRESH_CHANGES_KEY,
+};
+
+pub use scheduler::{ScheduledTask, TaskScheduler, MIN_TASK_INTERVAL};
+
 pub use enhanced_sync_handler::{
     ConflictResolver, ConflictStrategy, EnhancedSyncHandler, PerformanceMetrics, SyncHandlerConfig,
 };
@@ -0,0 +1,354 @@
+//! Movie metadata refresh
+//!
+//! Movies keep the details TMDB returned when they were added. The
+//! `RefreshMovie` scheduled task, or the command for specific movies,
+//! fetches the current details and compares them with the stored ones:
+//! titles, year, runtime, status, overview, images, ratings, release dates
+//! and alternative titles. With a ratings source, the IMDb, Rotten Tomatoes
+//! and Metacritic ratings are looked up too. The changes of the latest
+//! refresh that changed anything are kept in the movie's metadata and
+//! published as a `MovieUpdated` event. Settings stored next to the TMDB
+//! details, such as the root folder, are left alone.
+
+use crate::domain::repositories::MovieRepository;
+use crate::events::{EventBus, SystemEvent};
+use crate::models::{Movie, MovieRatings};
+use crate::{CommandProgress, RadarrError, Result};
+use async_trait::async_trait;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+/// Metadata key holding the changes of the latest refresh that changed
+/// anything
+pub const REFRESH_CHANGES_KEY: &str = "refresh_changes";
+
+/// Page size used when refreshing the whole library
+const REFRESH_PAGE_SIZE: i32 = 500;
+
+/// TMDB metadata keys compared on refresh, by reported field. Keys such as
+/// `popularity` change daily and are stored without counting as a change.
+const TMDB_FIELDS: &[(&str, &[&str])] = &[
+    ("overview", &["overview", "tagline"]),
+    ("images", &["poster_path", "backdrop_path"]),
+    ("inCinemas", &["release_date"]),
+    ("digitalRelease", &["digital_release_date"]),
+    ("physicalRelease", &["physical_release_date"]),
+    ("collection", &["collection"]),
+];
+
+/// Fetches the current details of a movie
+#[async_trait]
+pub trait MovieMetadataSource: Send + Sync {
+    /// Current details of the movie with TMDB ID `tmdb_id`, bypassing any
+    /// cache
+    async fn movie_metadata(&self, tmdb_id: i32) -> Result<Movie>;
+}
+
+/// Looks up the ratings of a movie on other services
+#[async_trait]
+pub trait MovieRatingsSource: Send + Sync {
+    /// Ratings of the movie with IMDb ID `imdb_id`
+    async fn movie_ratings(&self, imdb_id: &str) -> Result<MovieRatings>;
+}
+
+/// A field changed by a refresh
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MovieFieldChange {
+    /// API name of the field, e.g. `digitalRelease`
+    pub field: String,
+    pub old_value: Value,
+    pub new_value: Value,
+}
+
+/// Outcome of a refresh run
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RefreshSummary {
+    /// Movies refreshed
+    pub refreshed: usize,
+    /// Refreshed movies with changed details
+    pub updated: usize,
+    /// Movies that could not be refreshed
+    pub failed: usize,
+}
+
+/// Compared values of a movie, by reported field
+fn tracked_fields(movie: &Movie) -> Vec<(&'static str, Value)> {
+    let tmdb = movie.metadata.get("tmdb");
+    let mut fields = vec![
+        ("title", json!(movie.title)),
+        ("originalTitle", json!(movie.original_title)),
+        ("year", json!(movie.year)),
+        ("runtime", json!(movie.runtime)),
+        ("imdbId", json!(movie.imdb_id)),
+        ("status", json!(movie.status)),
+        ("alternativeTitles", movie.alternative_titles.clone()),
+        ("ratings", json!(movie.ratings)),
+    ];
+    for (field, keys) in TMDB_FIELDS {
+        let value = match keys {
+            [key] => tmdb.and_then(|tmdb| tmdb.get(*key)).cloned(),
+            _ => Some(Value::Object(
+                keys.iter()
+                    .filter_map(|key| Some((key.to_string(), tmdb?.get(*key)?.clone())))
+                    .collect(),
+            )),
+        };
+        fields.push((field, value.unwrap_or(Value::Null)));
+    }
+    fields
+}
+
+/// Fields that differ between the stored movie and the latest details
+pub fn diff_movie(current: &Movie, latest: &Movie) -> Vec<MovieFieldChange> {
+    tracked_fields(current)
+        .into_iter()
+        .zip(tracked_fields(latest))
+        .filter(|((_, old), (_, new))| old != new)
+        .map(|((field, old_value), (_, new_value))| MovieFieldChange {
+            field: field.to_string(),
+            old_value,
+            new_value,
+        })
+        .collect()
+}
+
+/// Update `movie` with the latest details and return what changed
+///
+/// The TMDB details replace the stored ones; other metadata is kept. An IMDb
+/// ID TMDB no longer reports is kept too, as are ratings that weren't looked
+/// up this time.
+pub fn apply_refresh(movie: &mut Movie, mut latest: Movie) -> Vec<MovieFieldChange> {
+    if latest.imdb_id.is_none() {
+        latest.imdb_id = movie.imdb_id.clone();
+    }
+    latest.ratings = latest.ratings.or(&movie.ratings);
+    let changes = diff_movie(movie, &latest);
+    let now = Utc::now();
+
+    movie.title = latest.title;
+    movie.original_title = latest.original_title;
+    movie.year = latest.year;
+    movie.runtime = latest.runtime;
+    movie.imdb_id = latest.imdb_id;
+    movie.status = latest.status;
+    movie.alternative_titles = latest.alternative_titles;
+    movie.ratings = latest.ratings;
+    if !movie.metadata.is_object() {
+        movie.metadata = json!({});
+    }
+    movie.metadata["tmdb"] = latest.metadata.get("tmdb").cloned().unwrap_or(Value::Null);
+    if !changes.is_empty() {
+        movie.metadata[REFRESH_CHANGES_KEY] = json!({
+            "refreshed_at": now,
+            "changes": changes,
+        });
+    }
+    movie.last_info_sync = Some(now);
+    movie.updated_at = now;
+    changes
+}
+
+/// Refreshes movie details from TMDB
+pub struct RefreshMovieJob {
+    movie_repository: Arc<dyn MovieRepository>,
+    source: Arc<dyn MovieMetadataSource>,
+    ratings_source: Option<Arc<dyn MovieRatingsSource>>,
+    event_bus: Option<Arc<EventBus>>,
+}
+
+impl RefreshMovieJob {
+    pub fn new(
+        movie_repository: Arc<dyn MovieRepository>,
+        source: Arc<dyn MovieMetadataSource>,
+    ) -> Self {
+        Self {
+            movie_repository,
+            source,
+            ratings_source: None,
+            event_bus: None,
+        }
+    }
+
+    /// Look up IMDb, Rotten Tomatoes and Metacritic ratings from `source`
+    pub fn with_ratings_source(mut self, source: Arc<dyn MovieRatingsSource>) -> Self {
+        self.ratings_source = Some(source);
+        self
+    }
+
+    /// Publish `MovieUpdated` events for refreshes that change a movie
+    pub fn with_event_bus(mut self, event_bus: Arc<EventBus>) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
+    /// Refresh one movie, returning the fields that changed
+    pub async fn refresh(&self, movie_id: Uuid) -> Result<Vec<MovieFieldChange>> {
+        let mut movie = self
+            .movie_repository
+            .find_by_id(movie_id)
+            .await?
+            .ok_or_else(|| RadarrError::NotFound {
+                resource: format!("movie {}", movie_id),
+            })?;
+        let mut latest = self.source.movie_metadata(movie.tmdb_id).await?;
+        let imdb_id = latest.imdb_id.clone().or_else(|| movie.imdb_id.clone());
+        if let (Some(ratings_source), Some(imdb_id)) = (&self.ratings_source, imdb_id) {
+            // The stored ratings are kept when the lookup fails
+            match ratings_source.movie_ratings(&imdb_id).await {
+                Ok(ratings) => latest.ratings = ratings.or(&latest.ratings),
+                Err(e) => warn!("Failed to look up ratings of {}: {}", movie.title, e),
+            }
+        }
+
+        let changes = apply_refresh(&mut movie, latest);
+        self.movie_repository.update(&movie).await?;
+        if changes.is_empty() {
+            debug!("No changes refreshing {}", movie.title);
+            return Ok(changes);
+        }
+
+        let fields: Vec<String> = changes.iter().map(|change| change.field.clone()).collect();
+        info!("Refreshed {} ({})", movie.title, fields.join(", "));
+        if let Some(event_bus) = &self.event_bus {
+            let event = SystemEvent::MovieUpdated {
+                movie_id,
+                changes: fields,
+            };
+            if let Err(e) = event_bus.publish(event).await {
+                warn!("Failed to publish movie update event: {}", e);
+            }
+        }
+        Ok(changes)
+    }
+
+    /// Refresh `movie_ids`, or every movie when `None`
+    pub async fn run(
+        &self,
+        movie_ids: Option<Vec<Uuid>>,
+        progress: Option<&CommandProgress>,
+    ) -> Result<RefreshSummary> {
+        let ids = match movie_ids {
+            Some(ids) => ids,
+            None => self.all_movie_ids().await?,
+        };
+        let total = ids.len();
+        let mut summary = RefreshSummary::default();
+
+        for (index, id) in ids.into_iter().enumerate() {
+            match self.refresh(id).await {
+                Ok(changes) => {
+                    summary.refreshed += 1;
+                    if !changes.is_empty() {
+                        summary.updated += 1;
+                    }
+                }
+                Err(e) => {
+                    warn!("Failed to refresh movie {}: {}", id, e);
+                    summary.failed += 1;
+                }
+            }
+
+            if let Some(progress) = progress {
+                progress
+                    .update(
+                        (index + 1) as f32 * 100.0 / total as f32,
+                        format!("Refreshed {} of {} movies", index + 1, total),
+                    )
+                    .await;
+            }
+        }
+
+        Ok(summary)
+    }
+
+    async fn all_movie_ids(&self) -> Result<Vec<Uuid>> {
+        let mut ids = Vec::new();
+        let mut offset = 0;
+        loop {
+            let page = self
+                .movie_repository
+                .list(offset, REFRESH_PAGE_SIZE)
+                .await?;
+            let page_len = page.len();
+            ids.extend(page.into_iter().map(|movie| movie.id));
+            if page_len < REFRESH_PAGE_SIZE as usize {
+                return Ok(ids);
+            }
+            offset += page_len as i64;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{MovieStatus, Rating};
+
+    fn stored() -> Movie {
+        let mut movie = Movie::new(550, "Fight Club".to_string());
+        movie.year = Some(1999);
+        movie.imdb_id = Some("tt0137523".to_string());
+        movie.ratings.imdb = Some(Rating::new(8.8, 2300000));
+        movie.metadata = json!({
+            "root_folder_path": "/movies",
+            "tmdb": {
+                "overview": "An insomniac office worker...",
+                "poster_path": "/old.jpg",
+                "vote_average": 8.4,
+                "vote_count": 100,
+                "release_date": "1999-10-15",
+                "popularity": 10.0
+            }
+        });
+        movie
+    }
+
+    #[test]
+    fn test_apply_refresh_reports_changed_fields() {
+        let mut movie = stored();
+
+        let mut latest = stored();
+        latest.imdb_id = None;
+        latest.ratings = MovieRatings::default();
+        latest.status = MovieStatus::Released;
+        latest.metadata = json!({
+            "tmdb": {
+                "overview": "An insomniac office worker...",
+                "poster_path": "/new.jpg",
+                "vote_average": 8.4,
+                "vote_count": 100,
+                "release_date": "1999-10-15",
+                "digital_release_date": "2000-06-06",
+                "popularity": 55.0
+            }
+        });
+
+        let changes = apply_refresh(&mut movie, latest);
+        let fields: Vec<&str> = changes.iter().map(|c| c.field.as_str()).collect();
+        assert_eq!(fields, vec!["status", "images", "digitalRelease"]);
+        assert_eq!(changes[1].new_value, json!({ "poster_path": "/new.jpg" }));
+
+        assert_eq!(movie.status, MovieStatus::Released);
+        assert_eq!(movie.imdb_id.as_deref(), Some("tt0137523"));
+        assert_eq!(movie.ratings.imdb, Some(Rating::new(8.8, 2300000)));
+        assert_eq!(movie.root_folder_path(), Some("/movies"));
+        assert_eq!(movie.metadata["tmdb"]["popularity"], json!(55.0));
+        assert_eq!(
+            movie.metadata[REFRESH_CHANGES_KEY]["changes"][0]["field"],
+            "status"
+        );
+        assert!(movie.last_info_sync.is_some());
+
+        // Refreshing again changes nothing and keeps the last diff
+        let latest = movie.clone();
+        assert!(apply_refresh(&mut movie, latest).is_empty());
+        assert_eq!(
+            movie.metadata[REFRESH_CHANGES_KEY]["changes"][0]["field"],
+            "status"
+        );
+    }
+}
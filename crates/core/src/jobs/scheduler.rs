@@ -0,0 +1,367 @@
+//! Central scheduler for recurring tasks
+//!
+//! Each task queues a [`CommandName`] on the [`CommandQueue`] every
+//! `interval`, so scheduled and manual runs share one executor and show up in
+//! `/api/v3/command` alike. The scheduler keeps the last and next execution
+//! times, lets a task run now and lets its interval be changed while running.
+//! After a restart, [`TaskScheduler::recover`] takes each task's last run from
+//! the stored commands so intervals carry over.
+
+use crate::commands::{Command, CommandName, CommandQueue, CommandRecovery, CommandTrigger};
+use crate::polling::apply_jitter;
+use crate::{RadarrError, Result};
+use chrono::{DateTime, Utc};
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+/// Shortest interval a task can be scheduled at
+pub const MIN_TASK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Longest the scheduler sleeps before re-checking due tasks
+const MAX_SLEEP: Duration = Duration::from_secs(60);
+
+/// Spread applied to each interval so tasks don't fire in lockstep
+const INTERVAL_JITTER: f64 = 0.05;
+
+/// A recurring task
+#[derive(Debug, Clone)]
+pub struct ScheduledTask {
+    pub id: i32,
+    /// Command queued when the task runs
+    pub name: CommandName,
+    pub interval: Duration,
+    /// When the task last queued its command
+    pub last_execution: Option<DateTime<Utc>>,
+    pub next_execution: DateTime<Utc>,
+    /// Command queued by the last run, for its status and duration
+    pub last_command_id: Option<i32>,
+}
+
+impl ScheduledTask {
+    fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.next_execution <= now
+    }
+
+    /// Record a run queued at `now` and schedule the next one
+    fn record_run(&mut self, now: DateTime<Utc>, command_id: Option<i32>) {
+        self.last_execution = Some(now);
+        self.last_command_id = command_id.or(self.last_command_id);
+        self.next_execution = now + next_delay(self.interval);
+    }
+}
+
+/// Interval with jitter, as a chrono duration
+fn next_delay(interval: Duration) -> chrono::Duration {
+    chrono::Duration::from_std(apply_jitter(interval, INTERVAL_JITTER))
+        .unwrap_or_else(|_| chrono::Duration::days(365))
+}
+
+/// Queues commands for recurring tasks when they are due
+pub struct TaskScheduler {
+    queue: Arc<CommandQueue>,
+    tasks: RwLock<Vec<ScheduledTask>>,
+    next_id: AtomicI32,
+    wake: Notify,
+}
+
+impl TaskScheduler {
+    pub fn new(queue: Arc<CommandQueue>) -> Self {
+        Self {
+            queue,
+            tasks: RwLock::new(Vec::new()),
+            next_id: AtomicI32::new(1),
+            wake: Notify::new(),
+        }
+    }
+
+    /// Queue on which tasks run their commands
+    pub fn queue(&self) -> &Arc<CommandQueue> {
+        &self.queue
+    }
+
+    /// Run `name` every `interval`, first after one interval
+    ///
+    /// Registering a name again replaces its interval.
+    pub fn register(&self, name: CommandName, interval: Duration) -> i32 {
+        let interval = interval.max(MIN_TASK_INTERVAL);
+        let mut tasks = self.tasks.write().unwrap_or_else(|e| e.into_inner());
+
+        let id = match tasks.iter_mut().find(|task| task.name == name) {
+            Some(task) => {
+                task.interval = interval;
+                task.next_execution = Utc::now() + next_delay(interval);
+                task.id
+            }
+            None => {
+                let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+                tasks.push(ScheduledTask {
+                    id,
+                    name,
+                    interval,
+                    last_execution: None,
+                    next_execution: Utc::now() + next_delay(interval),
+                    last_command_id: None,
+                });
+                id
+            }
+        };
+        drop(tasks);
+
+        info!("Scheduled task {} every {:?}", name, interval);
+        self.wake.notify_one();
+        id
+    }
+
+    /// All tasks, in registration order
+    pub fn list(&self) -> Vec<ScheduledTask> {
+        self.tasks.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// A task by ID
+    pub fn get(&self, id: i32) -> Option<ScheduledTask> {
+        self.tasks
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .find(|task| task.id == id)
+            .cloned()
+    }
+
+    /// Change how often a task runs
+    ///
+    /// The next run moves to one new interval after the last run, or after
+    /// now if the task has not run yet.
+    pub fn reschedule(&self, id: i32, interval: Duration) -> Result<ScheduledTask> {
+        if interval < MIN_TASK_INTERVAL {
+            return Err(RadarrError::ValidationError {
+                field: "interval".to_string(),
+                message: format!(
+                    "Interval must be at least {} minute",
+                    MIN_TASK_INTERVAL.as_secs() / 60
+                ),
+            });
+        }
+
+        let task = {
+            let mut tasks = self.tasks.write().unwrap_or_else(|e| e.into_inner());
+            let task = find_task(&mut tasks, id)?;
+            task.interval = interval;
+            task.next_execution =
+                task.last_execution.unwrap_or_else(Utc::now) + next_delay(interval);
+            task.clone()
+        };
+
+        info!("Rescheduled task {} to every {:?}", task.name, interval);
+        self.wake.notify_one();
+        Ok(task)
+    }
+
+    /// Run a task now, restarting its interval
+    pub async fn trigger(&self, id: i32) -> Result<Command> {
+        let name = self
+            .get(id)
+            .map(|task| task.name)
+            .ok_or_else(|| RadarrError::NotFound {
+                resource: format!("task {}", id),
+            })?;
+
+        let command = self
+            .queue
+            .enqueue(name, serde_json::json!({}), CommandTrigger::Manual)
+            .await?;
+
+        let mut tasks = self.tasks.write().unwrap_or_else(|e| e.into_inner());
+        find_task(&mut tasks, id)?.record_run(Utc::now(), Some(command.id));
+        drop(tasks);
+
+        self.wake.notify_one();
+        Ok(command)
+    }
+
+    /// Queue the commands of every due task
+    pub async fn run_due(&self) {
+        let now = Utc::now();
+        let due: Vec<(i32, CommandName)> = self
+            .tasks
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .filter(|task| task.is_due(now))
+            .map(|task| (task.id, task.name))
+            .collect();
+
+        for (id, name) in due {
+            debug!("Task {} is due", name);
+            let command_id = match self
+                .queue
+                .enqueue(name, serde_json::json!({}), CommandTrigger::Scheduled)
+                .await
+            {
+                Ok(command) => Some(command.id),
+                Err(e) => {
+                    // Still advance, so a missing executor isn't retried every loop
+                    warn!("Failed to queue scheduled task {}: {}", name, e);
+                    None
+                }
+            };
+
+            let mut tasks = self.tasks.write().unwrap_or_else(|e| e.into_inner());
+            if let Ok(task) = find_task(&mut tasks, id) {
+                task.record_run(now, command_id);
+            }
+        }
+    }
+
+    /// Recover the command queue after a restart and take each task's last
+    /// run from the recovered commands
+    ///
+    /// A task whose next run passed while the process was down runs right
+    /// away. Call after registering the tasks and before starting.
+    pub async fn recover(&self) -> Result<CommandRecovery> {
+        let recovery = self.queue.recover().await?;
+        // Newest first, so the first match is the last run
+        let commands = self.queue.list().await;
+        let now = Utc::now();
+
+        let mut tasks = self.tasks.write().unwrap_or_else(|e| e.into_inner());
+        for task in tasks
+            .iter_mut()
+            .filter(|task| task.last_execution.is_none())
+        {
+            if let Some(command) = commands.iter().find(|command| command.name == task.name) {
+                task.last_execution = Some(command.queued_at);
+                task.last_command_id = Some(command.id);
+                task.next_execution = (command.queued_at + next_delay(task.interval)).max(now);
+                debug!("Task {} last ran at {}", task.name, command.queued_at);
+            }
+        }
+        drop(tasks);
+
+        self.wake.notify_one();
+        Ok(recovery)
+    }
+
+    /// Time until the earliest task is due, capped at a minute
+    fn sleep_duration(&self) -> Duration {
+        let now = Utc::now();
+        self.tasks
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .map(|task| (task.next_execution - now).to_std().unwrap_or_default())
+            .min()
+            .unwrap_or(MAX_SLEEP)
+            .min(MAX_SLEEP)
+    }
+
+    /// Run due tasks in the background until the returned handle is aborted
+    pub fn start(self: &Arc<Self>) -> JoinHandle<()> {
+        let scheduler = Arc::clone(self);
+        tokio::spawn(async move {
+            info!("Task scheduler started");
+            loop {
+                scheduler.run_due().await;
+                tokio::select! {
+                    _ = tokio::time::sleep(scheduler.sleep_duration()) => {}
+                    _ = scheduler.wake.notified() => {}
+                }
+            }
+        })
+    }
+}
+
+fn find_task(tasks: &mut [ScheduledTask], id: i32) -> Result<&mut ScheduledTask> {
+    tasks
+        .iter_mut()
+        .find(|task| task.id == id)
+        .ok_or_else(|| RadarrError::NotFound {
+            resource: format!("task {}", id),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{CommandExecutor, CommandProgress};
+    use async_trait::async_trait;
+
+    struct NoopExecutor;
+
+    #[async_trait]
+    impl CommandExecutor for NoopExecutor {
+        async fn execute(&self, _command: &Command, _progress: &CommandProgress) -> Result<String> {
+            Ok("Done".to_string())
+        }
+    }
+
+    fn scheduler() -> TaskScheduler {
+        let queue = Arc::new(CommandQueue::default());
+        queue.register(CommandName::RssSync, Arc::new(NoopExecutor));
+        TaskScheduler::new(queue)
+    }
+
+    #[tokio::test]
+    async fn test_due_tasks_are_queued_and_rescheduled() {
+        let scheduler = scheduler();
+        let id = scheduler.register(CommandName::RssSync, Duration::from_secs(900));
+        let task = scheduler.get(id).unwrap();
+        assert!(task.last_execution.is_none());
+        assert!(task.next_execution > Utc::now());
+
+        // Nothing is due yet
+        scheduler.run_due().await;
+        assert!(scheduler.queue().list().await.is_empty());
+
+        scheduler.tasks.write().unwrap()[0].next_execution = Utc::now();
+        scheduler.run_due().await;
+
+        let commands = scheduler.queue().list().await;
+        assert_eq!(commands.len(), 1);
+        assert_eq!(commands[0].trigger, CommandTrigger::Scheduled);
+        let task = scheduler.get(id).unwrap();
+        assert_eq!(task.last_command_id, Some(commands[0].id));
+        assert!(task.next_execution > Utc::now() + chrono::Duration::minutes(14));
+    }
+
+    #[tokio::test]
+    async fn test_trigger_and_reschedule() {
+        let scheduler = scheduler();
+        let id = scheduler.register(CommandName::RssSync, Duration::from_secs(900));
+
+        let command = scheduler.trigger(id).await.unwrap();
+        assert_eq!(command.trigger, CommandTrigger::Manual);
+        assert!(scheduler.get(id).unwrap().last_execution.is_some());
+        assert!(scheduler.trigger(99).await.is_err());
+
+        assert!(scheduler.reschedule(id, Duration::from_secs(10)).is_err());
+        let task = scheduler.reschedule(id, Duration::from_secs(3600)).unwrap();
+        assert_eq!(task.interval, Duration::from_secs(3600));
+        assert!(task.next_execution > Utc::now() + chrono::Duration::minutes(55));
+    }
+
+    #[tokio::test]
+    async fn test_recover_takes_last_run_from_commands() {
+        let scheduler = scheduler();
+        let command = scheduler
+            .queue()
+            .enqueue(
+                CommandName::RssSync,
+                serde_json::json!({}),
+                CommandTrigger::Scheduled,
+            )
+            .await
+            .unwrap();
+        let id = scheduler.register(CommandName::RssSync, Duration::from_secs(900));
+
+        scheduler.recover().await.unwrap();
+
+        let task = scheduler.get(id).unwrap();
+        assert_eq!(task.last_execution, Some(command.queued_at));
+        assert_eq!(task.last_command_id, Some(command.id));
+        assert!(task.next_execution > Utc::now() + chrono::Duration::minutes(13));
+    }
+}
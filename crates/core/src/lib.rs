@@ -4,19 +4,27 @@
 //! and business rules that define the Radarr application.
 
 pub mod blocklist;
+pub mod chaos;
 pub mod circuit_breaker;
+pub mod commands;
 pub mod correlation;
 pub mod domain;
 pub mod error;
 pub mod events;
+pub mod health;
+pub mod i18n;
 pub mod jobs;
+pub mod logs;
 pub mod models;
 pub mod notifications;
+pub mod polling;
 pub mod progress;
 pub mod retry;
 pub mod rss;
 pub mod services;
+pub mod shutdown;
 pub mod streaming;
+pub mod trace_context;
 pub mod tracing;
 
 // Re-export core types
@@ -29,4 +37,15 @@ pub use services::*;
 // Selective re-exports to avoid naming conflicts
 pub use blocklist::*;
 pub use circuit_breaker::{CircuitBreaker, CircuitBreakerMetrics};
+pub use commands::{
+    Command, CommandExecutor, CommandName, CommandProgress, CommandQueue, CommandRecovery,
+    CommandStatus, CommandTrigger,
+};
+pub use health::{
+    DiskSpaceCheck, DownloadClientCheck, HealthCheck, HealthCheckRegistry, HealthIssue,
+    HealthIssueType, ImportFailureCheck, RootFolderCheck,
+};
+pub use polling::{PollConfig, PollSchedule};
 pub use retry::{retry_with_backoff, RetryConfig, RetryPolicy};
+pub use shutdown::{ShutdownCoordinator, ShutdownOutcome};
+pub use trace_context::{TraceContext, TraceContextLayer, TracedRequest};
@@ -0,0 +1,483 @@
+//! Stored application log behind `/api/v3/log`
+//!
+//! [`LogStoreLayer`] copies warnings and errors, and optionally info messages,
+//! into a [`LogStore`]. The store appends them as JSON lines to `radarr.log`
+//! in its directory, rotating it to `radarr.1.log`, `radarr.2.log` and so on
+//! once it grows past its size limit, and keeps the most recent records in
+//! memory for paging. Records in the files are read back on startup, so the
+//! log survives restarts.
+
+use crate::{RadarrError, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, OnceLock};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+/// Name of the log file being written
+const CURRENT_LOG_FILE: &str = "radarr.log";
+
+/// Severity of a log record, least severe first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    /// Parse a level name such as `warn`, ignoring case
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "trace" => Some(Self::Trace),
+            "debug" => Some(Self::Debug),
+            "info" => Some(Self::Info),
+            "warn" | "warning" => Some(Self::Warn),
+            "error" => Some(Self::Error),
+            _ => None,
+        }
+    }
+
+    fn from_tracing(level: &tracing::Level) -> Self {
+        match *level {
+            tracing::Level::TRACE => Self::Trace,
+            tracing::Level::DEBUG => Self::Debug,
+            tracing::Level::INFO => Self::Info,
+            tracing::Level::WARN => Self::Warn,
+            tracing::Level::ERROR => Self::Error,
+        }
+    }
+}
+
+/// Where and how much of the log is stored
+#[derive(Debug, Clone)]
+pub struct LogStoreConfig {
+    /// Directory holding the log files
+    pub directory: PathBuf,
+    /// Least severe level stored; `Info` keeps info messages too
+    pub min_level: LogLevel,
+    /// Size at which `radarr.log` is rotated
+    pub max_file_bytes: u64,
+    /// Log files kept, the one being written included
+    pub max_files: usize,
+    /// Records kept in memory for `/api/v3/log`
+    pub capacity: usize,
+}
+
+impl Default for LogStoreConfig {
+    fn default() -> Self {
+        Self {
+            directory: PathBuf::from("logs"),
+            min_level: LogLevel::Warn,
+            max_file_bytes: 1024 * 1024, // 1 MiB
+            max_files: 5,
+            capacity: 10_000,
+        }
+    }
+}
+
+/// A stored log message
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogRecord {
+    /// Increases with every record; assigned again when the files are read
+    pub id: u64,
+    pub time: DateTime<Utc>,
+    pub level: LogLevel,
+    /// Module that logged it
+    pub logger: String,
+    pub message: String,
+    /// The `error` field of the event, if it had one
+    pub exception: Option<String>,
+}
+
+/// Which records to return, newest first
+#[derive(Debug, Clone, Default)]
+pub struct LogQuery {
+    /// Only records at this level or more severe
+    pub level: Option<LogLevel>,
+    pub limit: usize,
+    pub offset: usize,
+}
+
+/// A log file on disk
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogFile {
+    pub filename: String,
+    pub last_write_time: DateTime<Utc>,
+    pub size: u64,
+}
+
+struct LogStoreState {
+    records: VecDeque<LogRecord>,
+    next_id: u64,
+    file: Option<File>,
+    file_bytes: u64,
+}
+
+/// Recent log records in memory, backed by rotated files
+pub struct LogStore {
+    config: LogStoreConfig,
+    state: Mutex<LogStoreState>,
+}
+
+impl LogStore {
+    /// Open the store in the configured directory, reading back the records
+    /// already in its files
+    pub fn open(config: LogStoreConfig) -> Result<Self> {
+        fs::create_dir_all(&config.directory).map_err(|e| Self::error(&config, e))?;
+
+        let mut state = LogStoreState {
+            records: VecDeque::new(),
+            next_id: 1,
+            file: None,
+            file_bytes: 0,
+        };
+        // Oldest file first, so the newest records stay in memory
+        for index in (0..config.max_files.max(1)).rev() {
+            let Ok(file) = File::open(config.directory.join(file_name(index))) else {
+                continue;
+            };
+            for line in BufReader::new(file).lines().map_while(|line| line.ok()) {
+                if let Ok(mut record) = serde_json::from_str::<LogRecord>(&line) {
+                    record.id = state.next_id;
+                    state.next_id += 1;
+                    push_capped(&mut state.records, record, config.capacity);
+                }
+            }
+        }
+
+        Ok(Self {
+            config,
+            state: Mutex::new(state),
+        })
+    }
+
+    pub fn config(&self) -> &LogStoreConfig {
+        &self.config
+    }
+
+    /// Whether records at `level` are stored
+    pub fn stores(&self, level: LogLevel) -> bool {
+        level >= self.config.min_level
+    }
+
+    /// Store a record, writing it to the current log file
+    ///
+    /// A failed write is reported on stderr; logging it would come back here.
+    pub fn append(
+        &self,
+        level: LogLevel,
+        logger: &str,
+        message: String,
+        exception: Option<String>,
+    ) -> LogRecord {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let record = LogRecord {
+            id: state.next_id,
+            time: Utc::now(),
+            level,
+            logger: logger.to_string(),
+            message,
+            exception,
+        };
+        state.next_id += 1;
+
+        if let Err(e) = self.write(&mut state, &record) {
+            state.file = None;
+            eprintln!("Failed to write log file: {}", e);
+        }
+        push_capped(&mut state.records, record.clone(), self.config.capacity);
+        record
+    }
+
+    /// Matching records, newest first, with the total number of matches
+    pub fn query(&self, query: &LogQuery) -> (Vec<LogRecord>, usize) {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let matching = state
+            .records
+            .iter()
+            .rev()
+            .filter(|record| query.level.is_none_or(|level| record.level >= level));
+
+        let total = matching.clone().count();
+        let records = matching
+            .skip(query.offset)
+            .take(query.limit)
+            .cloned()
+            .collect();
+        (records, total)
+    }
+
+    /// Log files on disk, the one being written first
+    pub fn files(&self) -> Vec<LogFile> {
+        let mut files = Vec::new();
+        for index in 0..self.config.max_files.max(1) {
+            let filename = file_name(index);
+            let Ok(metadata) = fs::metadata(self.config.directory.join(&filename)) else {
+                continue;
+            };
+            files.push(LogFile {
+                filename,
+                last_write_time: metadata
+                    .modified()
+                    .map(DateTime::<Utc>::from)
+                    .unwrap_or_else(|_| Utc::now()),
+                size: metadata.len(),
+            });
+        }
+        files
+    }
+
+    /// Contents of the log file named `filename`, as listed by [`files`]
+    ///
+    /// [`files`]: LogStore::files
+    pub fn read_file(&self, filename: &str) -> Result<Vec<u8>> {
+        let known = (0..self.config.max_files.max(1)).any(|index| file_name(index) == filename);
+        let path = self.config.directory.join(filename);
+        if !known || !path.is_file() {
+            return Err(RadarrError::NotFoundError {
+                entity: "log file".to_string(),
+                id: filename.to_string(),
+            });
+        }
+        // Flushed writes only; the file is appended to while being read
+        let _state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        fs::read(&path).map_err(|e| Self::error(&self.config, e))
+    }
+
+    fn write(&self, state: &mut LogStoreState, record: &LogRecord) -> std::io::Result<()> {
+        let mut line = serde_json::to_string(record).map_err(std::io::Error::other)?;
+        line.push('\n');
+
+        if state.file.is_some() && state.file_bytes + line.len() as u64 > self.config.max_file_bytes
+        {
+            state.file = None;
+            self.rotate()?;
+        }
+        if state.file.is_none() {
+            let path = self.config.directory.join(CURRENT_LOG_FILE);
+            let file = OpenOptions::new().create(true).append(true).open(path)?;
+            state.file_bytes = file.metadata()?.len();
+            state.file = Some(file);
+        }
+
+        if let Some(file) = state.file.as_mut() {
+            file.write_all(line.as_bytes())?;
+            state.file_bytes += line.len() as u64;
+        }
+        Ok(())
+    }
+
+    /// Shift every file one number up, dropping the oldest
+    fn rotate(&self) -> std::io::Result<()> {
+        let directory = &self.config.directory;
+        let last = self.config.max_files.max(1) - 1;
+        if last == 0 {
+            return fs::remove_file(directory.join(CURRENT_LOG_FILE));
+        }
+        let _ = fs::remove_file(directory.join(file_name(last)));
+        for index in (0..last).rev() {
+            let from = directory.join(file_name(index));
+            if from.exists() {
+                fs::rename(from, directory.join(file_name(index + 1)))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn error(config: &LogStoreConfig, error: std::io::Error) -> RadarrError {
+        RadarrError::IoError(format!(
+            "Log directory {}: {}",
+            config.directory.display(),
+            error
+        ))
+    }
+}
+
+/// `radarr.log` for the current file, `radarr.N.log` for rotated ones
+fn file_name(index: usize) -> String {
+    if index == 0 {
+        CURRENT_LOG_FILE.to_string()
+    } else {
+        format!("radarr.{}.log", index)
+    }
+}
+
+fn push_capped(records: &mut VecDeque<LogRecord>, record: LogRecord, capacity: usize) {
+    if records.len() >= capacity.max(1) {
+        records.pop_front();
+    }
+    records.push_back(record);
+}
+
+/// The store filled by the application's [`LogStoreLayer`], once installed
+pub fn log_store() -> Option<Arc<LogStore>> {
+    LOG_STORE.get().cloned()
+}
+
+/// Make `store` the one returned by [`log_store`]; only the first call counts
+pub fn install_log_store(store: Arc<LogStore>) {
+    let _ = LOG_STORE.set(store);
+}
+
+static LOG_STORE: OnceLock<Arc<LogStore>> = OnceLock::new();
+
+/// Collects an event's message, error and other fields
+#[derive(Default)]
+struct RecordVisitor {
+    message: String,
+    exception: Option<String>,
+    fields: Vec<String>,
+}
+
+impl Visit for RecordVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record_value(field, value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.record_value(field, format!("{:?}", value));
+    }
+}
+
+impl RecordVisitor {
+    fn record_value(&mut self, field: &Field, value: String) {
+        match field.name() {
+            "message" => self.message = value,
+            "error" => self.exception = Some(value),
+            name => self.fields.push(format!("{}={}", name, value)),
+        }
+    }
+
+    /// The message followed by the other fields
+    fn into_message(self) -> String {
+        if self.fields.is_empty() {
+            self.message
+        } else {
+            format!("{} {}", self.message, self.fields.join(" "))
+        }
+    }
+}
+
+/// A tracing layer storing events at the store's level or above
+pub struct LogStoreLayer {
+    store: Arc<LogStore>,
+}
+
+impl LogStoreLayer {
+    pub fn new(store: Arc<LogStore>) -> Self {
+        Self { store }
+    }
+}
+
+impl<S: Subscriber> Layer<S> for LogStoreLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let level = LogLevel::from_tracing(metadata.level());
+        if !self.store.stores(level) {
+            return;
+        }
+
+        let mut visitor = RecordVisitor::default();
+        event.record(&mut visitor);
+        let exception = visitor.exception.take();
+        self.store
+            .append(level, metadata.target(), visitor.into_message(), exception);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn config(directory: PathBuf) -> LogStoreConfig {
+        LogStoreConfig {
+            directory,
+            max_file_bytes: 400,
+            max_files: 3,
+            capacity: 4,
+            ..LogStoreConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_layer_stores_warnings_and_errors() {
+        use tracing_subscriber::prelude::*;
+
+        let directory = std::env::temp_dir().join(format!("radarr-logs-{}", Uuid::new_v4()));
+        let store = Arc::new(LogStore::open(config(directory.clone())).unwrap());
+        let subscriber = tracing_subscriber::registry().with(LogStoreLayer::new(store.clone()));
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!("Not stored");
+            tracing::warn!(path = "/movies", "Root folder is low on space");
+            tracing::error!(error = "connection refused", "Indexer search failed");
+        });
+
+        let (records, total) = store.query(&LogQuery {
+            limit: 10,
+            ..Default::default()
+        });
+        assert_eq!(total, 2);
+        assert_eq!(records[0].level, LogLevel::Error);
+        assert_eq!(records[0].exception.as_deref(), Some("connection refused"));
+        assert_eq!(
+            records[1].message,
+            "Root folder is low on space path=/movies"
+        );
+
+        let (errors, total) = store.query(&LogQuery {
+            level: Some(LogLevel::Error),
+            limit: 10,
+            offset: 0,
+        });
+        assert_eq!((errors.len(), total), (1, 1));
+
+        let _ = fs::remove_dir_all(directory);
+    }
+
+    #[test]
+    fn test_files_rotate_and_are_read_back() {
+        let directory = std::env::temp_dir().join(format!("radarr-logs-{}", Uuid::new_v4()));
+        let store = LogStore::open(config(directory.clone())).unwrap();
+        for n in 0..12 {
+            store.append(LogLevel::Warn, "radarr", format!("Warning {}", n), None);
+        }
+
+        let files = store.files();
+        let names: Vec<_> = files.iter().map(|file| file.filename.as_str()).collect();
+        assert_eq!(names, vec!["radarr.log", "radarr.1.log", "radarr.2.log"]);
+        assert!(files.iter().all(|file| file.size <= 400));
+        assert!(store.read_file("radarr.1.log").is_ok());
+        assert!(store.read_file("../radarr.log").is_err());
+        assert!(store.read_file("radarr.3.log").is_err());
+
+        // Only the newest records fit in memory, before and after a restart
+        let (records, total) = store.query(&LogQuery {
+            limit: 2,
+            offset: 1,
+            ..Default::default()
+        });
+        assert_eq!(total, 4);
+        assert_eq!(records[0].message, "Warning 10");
+
+        let reopened = LogStore::open(config(directory.clone())).unwrap();
+        let (records, _) = reopened.query(&LogQuery {
+            limit: 1,
+            ..Default::default()
+        });
+        assert_eq!(records[0].message, "Warning 11");
+
+        let _ = fs::remove_dir_all(directory);
+    }
+}
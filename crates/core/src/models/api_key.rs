@@ -0,0 +1,145 @@
+//! API key domain model
+//!
+//! Besides the admin key configured in the environment, named API keys can be
+//! handed to other applications and revoked on their own. A key is either
+//! read-only or has full access, except to the management of API keys, to
+//! backups, which hold every other credential, and to share tokens, which
+//! would outlive a revoked key; those stay with the admin key.
+//! Like share tokens, the key itself is shown once and only its hash is stored.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Paths only the admin key can reach, whatever a key's scope
+const ADMIN_ONLY_PATHS: &[&str] = &[
+    "/api/v3/apikey",
+    "/api/v3/system/backup",
+    "/api/v3/sharetoken",
+];
+
+/// What an API key may do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ApiKeyScope {
+    /// Reads only
+    ReadOnly,
+    /// Reads and changes
+    Full,
+}
+
+impl ApiKeyScope {
+    /// All scopes
+    pub const ALL: [ApiKeyScope; 2] = [ApiKeyScope::ReadOnly, ApiKeyScope::Full];
+
+    /// Name used in the database and the API
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiKeyScope::ReadOnly => "readOnly",
+            ApiKeyScope::Full => "full",
+        }
+    }
+
+    /// Parse a stored name, ignoring case
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|scope| scope.as_str().eq_ignore_ascii_case(value))
+    }
+
+    /// Whether a request with `method` falls inside this scope
+    pub fn allows(&self, method: &str) -> bool {
+        match self {
+            ApiKeyScope::ReadOnly => {
+                method.eq_ignore_ascii_case("GET") || method.eq_ignore_ascii_case("HEAD")
+            }
+            ApiKeyScope::Full => true,
+        }
+    }
+}
+
+impl std::fmt::Display for ApiKeyScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A named, revocable API key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiKey {
+    pub id: Uuid,
+    pub name: String,
+    /// Hex SHA-256 digest of the key
+    pub key_hash: String,
+    pub scope: ApiKeyScope,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Last authenticated request, to the minute
+    pub last_used_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub revoked_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl ApiKey {
+    /// Create a key record for the hash of a freshly generated key
+    pub fn new(name: String, key_hash: String, scope: ApiKeyScope) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name,
+            key_hash,
+            scope,
+            created_at: chrono::Utc::now(),
+            last_used_at: None,
+            revoked_at: None,
+        }
+    }
+
+    /// Whether the key has not been revoked
+    pub fn is_active(&self) -> bool {
+        self.revoked_at.is_none()
+    }
+
+    /// Whether the key's scope allows the request
+    ///
+    /// `path` is the full request path, including the `/api` prefix.
+    pub fn allows(&self, method: &str, path: &str) -> bool {
+        let admin_only = ADMIN_ONLY_PATHS.iter().any(|base| {
+            path.strip_prefix(base)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+        });
+        !admin_only && self.scope.allows(method)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scopes_limit_methods_and_never_allow_key_management() {
+        let read_only = ApiKey::new(
+            "Dashboard".to_string(),
+            "hash".to_string(),
+            ApiKeyScope::ReadOnly,
+        );
+        assert!(read_only.allows("GET", "/api/v3/movie"));
+        assert!(read_only.allows("HEAD", "/api/v3/movie/1"));
+        assert!(!read_only.allows("POST", "/api/v3/movie"));
+        assert!(!read_only.allows("DELETE", "/api/v3/movie/1"));
+
+        let full = ApiKey::new(
+            "Overseerr".to_string(),
+            "hash".to_string(),
+            ApiKeyScope::Full,
+        );
+        assert!(full.allows("POST", "/api/v3/movie"));
+        assert!(full.allows("GET", "/api/v3/apikeys"));
+        assert!(!full.allows("GET", "/api/v3/apikey"));
+        assert!(!full.allows("POST", "/api/v3/apikey/9b2c4e1a/rotate"));
+        assert!(!full.allows("GET", "/api/v3/system/backup"));
+        assert!(!read_only.allows("GET", "/api/v3/system/backup/radarr_backup.zip"));
+        assert!(full.allows("GET", "/api/v3/system/task"));
+        assert!(!full.allows("POST", "/api/v3/sharetoken"));
+        assert!(!read_only.allows("GET", "/api/v3/sharetoken"));
+
+        assert_eq!(ApiKeyScope::parse("READONLY"), Some(ApiKeyScope::ReadOnly));
+        assert_eq!(ApiKeyScope::parse("admin"), None);
+    }
+}
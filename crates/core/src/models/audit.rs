@@ -0,0 +1,228 @@
+//! Audit log domain model
+//!
+//! Destructive operations performed through the API are published as
+//! [`SystemEvent::DestructiveOperation`] and recorded with the credential that
+//! performed them, so that deletions and settings changes can be traced back
+//! to an API key or login long after the request logs have rotated.
+
+use crate::events::{EventEnvelope, SystemEvent};
+use crate::models::ApiKey;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Kind of audited operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AuditAction {
+    /// A movie was removed from the library
+    MovieDelete,
+    /// A movie file was deleted from disk
+    MovieFileDelete,
+    /// A release was removed from the blocklist
+    BlocklistRemove,
+    /// A settings section was saved
+    ConfigChange,
+}
+
+impl AuditAction {
+    /// All actions, in display order
+    pub const ALL: [AuditAction; 4] = [
+        AuditAction::MovieDelete,
+        AuditAction::MovieFileDelete,
+        AuditAction::BlocklistRemove,
+        AuditAction::ConfigChange,
+    ];
+
+    /// Stored and serialized name
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::MovieDelete => "movieDelete",
+            AuditAction::MovieFileDelete => "movieFileDelete",
+            AuditAction::BlocklistRemove => "blocklistRemove",
+            AuditAction::ConfigChange => "configChange",
+        }
+    }
+
+    /// Parse a stored name, ignoring case
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|action| action.as_str().eq_ignore_ascii_case(value))
+    }
+}
+
+impl std::fmt::Display for AuditAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Credential an audited operation was performed with
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditActor {
+    /// `admin`, the name of a stored API key, or the user of a login session
+    pub name: String,
+    /// Stored API key, when one was used
+    pub api_key_id: Option<Uuid>,
+}
+
+impl AuditActor {
+    /// Name recorded for the admin key from the environment
+    pub const ADMIN: &'static str = "admin";
+
+    /// Name recorded when no credential is known, e.g. for background jobs
+    pub const SYSTEM: &'static str = "system";
+
+    /// The admin key from the environment
+    pub fn admin() -> Self {
+        Self {
+            name: Self::ADMIN.to_string(),
+            api_key_id: None,
+        }
+    }
+
+    /// A stored API key
+    pub fn api_key(key: &ApiKey) -> Self {
+        Self {
+            name: key.name.clone(),
+            api_key_id: Some(key.id),
+        }
+    }
+
+    /// The user of a username and password login
+    pub fn user(username: impl Into<String>) -> Self {
+        Self {
+            name: username.into(),
+            api_key_id: None,
+        }
+    }
+
+    /// The application itself
+    pub fn system() -> Self {
+        Self {
+            name: Self::SYSTEM.to_string(),
+            api_key_id: None,
+        }
+    }
+}
+
+/// A single audit log entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditRecord {
+    pub id: Uuid,
+    pub action: AuditAction,
+    pub actor: AuditActor,
+    /// What was changed, e.g. a movie ID or settings section
+    pub target: String,
+    /// Operation-specific details
+    pub details: serde_json::Value,
+    /// Event that produced this entry, used to avoid duplicates
+    pub event_id: Option<Uuid>,
+    pub correlation_id: Option<Uuid>,
+    pub date: chrono::DateTime<chrono::Utc>,
+}
+
+impl AuditRecord {
+    /// Build the audit entry for an event, if the event is an audited one
+    pub fn from_event(envelope: &EventEnvelope) -> Option<Self> {
+        let SystemEvent::DestructiveOperation {
+            action,
+            actor,
+            target,
+            details,
+        } = &envelope.event
+        else {
+            return None;
+        };
+
+        Some(AuditRecord {
+            id: Uuid::new_v4(),
+            action: *action,
+            actor: actor.clone(),
+            target: target.clone(),
+            details: details.clone(),
+            event_id: Some(envelope.event_id),
+            correlation_id: Some(envelope.correlation_id.as_uuid()),
+            date: envelope.timestamp,
+        })
+    }
+}
+
+/// Filter and page selection for audit log queries
+#[derive(Debug, Clone)]
+pub struct AuditQuery {
+    /// Only these actions; empty means all
+    pub actions: Vec<AuditAction>,
+    /// Only entries performed with this stored API key
+    pub api_key_id: Option<Uuid>,
+    /// Only entries by this actor name, ignoring case
+    pub actor: Option<String>,
+    /// Only entries dated at or after this time
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+impl Default for AuditQuery {
+    fn default() -> Self {
+        Self {
+            actions: Vec::new(),
+            api_key_id: None,
+            actor: None,
+            since: None,
+            limit: 50,
+            offset: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ApiKeyScope;
+
+    #[test]
+    fn test_action_round_trip() {
+        for action in AuditAction::ALL {
+            assert_eq!(AuditAction::parse(action.as_str()), Some(action));
+        }
+        assert_eq!(
+            AuditAction::parse("CONFIGCHANGE"),
+            Some(AuditAction::ConfigChange)
+        );
+        assert_eq!(AuditAction::parse("movieAdd"), None);
+    }
+
+    #[test]
+    fn test_from_event_records_actor_and_target() {
+        let key = ApiKey::new(
+            "Overseerr".to_string(),
+            "hash".to_string(),
+            ApiKeyScope::Full,
+        );
+        let envelope = EventEnvelope::new(SystemEvent::DestructiveOperation {
+            action: AuditAction::BlocklistRemove,
+            actor: AuditActor::api_key(&key),
+            target: "blocklist/42".to_string(),
+            details: serde_json::json!({ "sourceTitle": "Movie.2024.1080p-GRP" }),
+        });
+
+        let record = AuditRecord::from_event(&envelope).unwrap();
+        assert_eq!(record.action, AuditAction::BlocklistRemove);
+        assert_eq!(record.actor.name, "Overseerr");
+        assert_eq!(record.actor.api_key_id, Some(key.id));
+        assert_eq!(record.target, "blocklist/42");
+        assert_eq!(record.event_id, Some(envelope.event_id));
+        assert_eq!(record.date, envelope.timestamp);
+    }
+
+    #[test]
+    fn test_from_event_ignores_other_events() {
+        let envelope = EventEnvelope::new(SystemEvent::MovieDeleted {
+            movie_id: Uuid::new_v4(),
+            title: "Movie".to_string(),
+        });
+        assert!(AuditRecord::from_event(&envelope).is_none());
+    }
+}
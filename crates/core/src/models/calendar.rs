@@ -0,0 +1,120 @@
+//! Release calendar
+//!
+//! A movie's cinema, digital and physical release dates come from its TMDB
+//! details. The calendar lists the releases of movies falling within a date
+//! range, one event per release.
+
+use super::Movie;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// Kind of movie release
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ReleaseType {
+    InCinemas,
+    DigitalRelease,
+    PhysicalRelease,
+}
+
+impl ReleaseType {
+    pub const ALL: [ReleaseType; 3] = [
+        ReleaseType::InCinemas,
+        ReleaseType::DigitalRelease,
+        ReleaseType::PhysicalRelease,
+    ];
+
+    /// Name used in the API
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReleaseType::InCinemas => "inCinemas",
+            ReleaseType::DigitalRelease => "digitalRelease",
+            ReleaseType::PhysicalRelease => "physicalRelease",
+        }
+    }
+
+    /// Human readable name, e.g. for calendar event titles
+    pub fn label(&self) -> &'static str {
+        match self {
+            ReleaseType::InCinemas => "In Cinemas",
+            ReleaseType::DigitalRelease => "Digital Release",
+            ReleaseType::PhysicalRelease => "Physical Release",
+        }
+    }
+
+    /// Key of the date in the movie's TMDB metadata
+    pub fn tmdb_key(&self) -> &'static str {
+        match self {
+            ReleaseType::InCinemas => "release_date",
+            ReleaseType::DigitalRelease => "digital_release_date",
+            ReleaseType::PhysicalRelease => "physical_release_date",
+        }
+    }
+}
+
+impl std::fmt::Display for ReleaseType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Movie {
+    /// Date of the movie's `release_type` release, if known
+    pub fn release_date(&self, release_type: ReleaseType) -> Option<NaiveDate> {
+        self.metadata
+            .get("tmdb")?
+            .get(release_type.tmdb_key())?
+            .as_str()
+            .and_then(|value| NaiveDate::parse_from_str(value, "%Y-%m-%d").ok())
+    }
+
+    /// Releases of the movie from `start` to `end`, both inclusive, in date
+    /// order
+    pub fn releases_between(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Vec<(ReleaseType, NaiveDate)> {
+        let mut releases: Vec<_> = ReleaseType::ALL
+            .into_iter()
+            .filter_map(|release_type| Some((release_type, self.release_date(release_type)?)))
+            .filter(|(_, date)| (start..=end).contains(date))
+            .collect();
+        releases.sort_by_key(|(_, date)| *date);
+        releases
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_releases_between() {
+        let mut movie = Movie::new(550, "Fight Club".to_string());
+        movie.metadata = serde_json::json!({
+            "tmdb": {
+                "release_date": "1999-10-15",
+                "digital_release_date": "2000-06-06",
+                "physical_release_date": ""
+            }
+        });
+        let date = |value: &str| NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap();
+
+        assert_eq!(
+            movie.release_date(ReleaseType::InCinemas),
+            Some(date("1999-10-15"))
+        );
+        assert_eq!(movie.release_date(ReleaseType::PhysicalRelease), None);
+        assert_eq!(
+            movie.releases_between(date("1999-01-01"), date("2000-06-06")),
+            vec![
+                (ReleaseType::InCinemas, date("1999-10-15")),
+                (ReleaseType::DigitalRelease, date("2000-06-06"))
+            ]
+        );
+        assert!(movie
+            .releases_between(date("2000-06-07"), date("2001-01-01"))
+            .is_empty());
+    }
+}
@@ -0,0 +1,498 @@
+//! Video and audio codec taxonomy
+//!
+//! One place that knows how codecs appear in release names, what they are
+//! called and how they rank, shared by the release parsers, the release
+//! analyzer and custom format specifications. Newer formats are kept apart
+//! from the ones they extend: H.266/VVC from H.265, AV1 by profile, and
+//! Dolby Atmos by its carrier (lossy DD+ streams vs lossless TrueHD).
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+/// AV1 profile, from `AV1.Main10`-style tags or the bit depth
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Av1Profile {
+    /// Main profile, 8-bit
+    Main,
+    /// Main profile, 10-bit
+    Main10,
+    /// High profile (4:4:4 chroma)
+    High,
+    /// Professional profile (12-bit, 4:2:2)
+    Professional,
+}
+
+/// Video codec of a release
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum VideoCodec {
+    Xvid,
+    H264,
+    Vp9,
+    H265,
+    /// AV1; the profile is `None` when the release name does not say
+    Av1(Option<Av1Profile>),
+    /// H.266/VVC
+    H266,
+}
+
+/// Audio codec of a release
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AudioCodec {
+    Aac,
+    Opus,
+    /// Dolby Digital (AC3)
+    Ac3,
+    /// Dolby Digital Plus (E-AC3)
+    Eac3,
+    /// Dolby Atmos carried in Dolby Digital Plus (streaming releases)
+    Eac3Atmos,
+    Dts,
+    DtsHd,
+    DtsHdMa,
+    DtsX,
+    Flac,
+    Pcm,
+    TrueHd,
+    /// Dolby Atmos carried in Dolby TrueHD (disc releases)
+    TrueHdAtmos,
+}
+
+/// Case-insensitive pattern that only matches between non-alphanumerics
+fn token_regex(pattern: &str) -> Regex {
+    Regex::new(&format!(
+        r"(?i)(?:^|[^a-z0-9])(?:{})(?:[^a-z0-9]|$)",
+        pattern
+    ))
+    .expect("codec pattern is valid")
+}
+
+macro_rules! token_regex {
+    ($pattern:expr) => {{
+        static REGEX: OnceLock<Regex> = OnceLock::new();
+        REGEX.get_or_init(|| token_regex($pattern))
+    }};
+}
+
+impl Av1Profile {
+    pub const ALL: [Av1Profile; 4] = [
+        Av1Profile::Main,
+        Av1Profile::Main10,
+        Av1Profile::High,
+        Av1Profile::Professional,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Av1Profile::Main => "Main",
+            Av1Profile::Main10 => "Main10",
+            Av1Profile::High => "High",
+            Av1Profile::Professional => "Professional",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "main" | "main8" => Some(Av1Profile::Main),
+            "main10" | "10bit" => Some(Av1Profile::Main10),
+            "high" => Some(Av1Profile::High),
+            "professional" | "pro" => Some(Av1Profile::Professional),
+            _ => None,
+        }
+    }
+}
+
+impl VideoCodec {
+    /// Every codec, from least to most efficient
+    pub const ALL: [VideoCodec; 10] = [
+        VideoCodec::Xvid,
+        VideoCodec::H264,
+        VideoCodec::Vp9,
+        VideoCodec::H265,
+        VideoCodec::Av1(None),
+        VideoCodec::Av1(Some(Av1Profile::Main)),
+        VideoCodec::Av1(Some(Av1Profile::Main10)),
+        VideoCodec::Av1(Some(Av1Profile::High)),
+        VideoCodec::Av1(Some(Av1Profile::Professional)),
+        VideoCodec::H266,
+    ];
+
+    /// Codec named in a release title, preferring the newest when several are
+    pub fn parse(title: &str) -> Option<Self> {
+        if token_regex!(r"x266|h\.?266|vvc").is_match(title) {
+            return Some(VideoCodec::H266);
+        }
+        if token_regex!("av1").is_match(title) {
+            return Some(VideoCodec::Av1(Self::parse_av1_profile(title)));
+        }
+        if token_regex!(r"x265|h\.?265|hevc").is_match(title) {
+            return Some(VideoCodec::H265);
+        }
+        if token_regex!(r"x264|h\.?264|avc").is_match(title) {
+            return Some(VideoCodec::H264);
+        }
+        if token_regex!("vp9").is_match(title) {
+            return Some(VideoCodec::Vp9);
+        }
+        if token_regex!("xvid|divx").is_match(title) {
+            return Some(VideoCodec::Xvid);
+        }
+        None
+    }
+
+    fn parse_av1_profile(title: &str) -> Option<Av1Profile> {
+        static PROFILE: OnceLock<Regex> = OnceLock::new();
+        let profile = PROFILE.get_or_init(|| {
+            Regex::new(r"(?i)av1[ ._-]?(main10|main|high|professional)(?:[^a-z0-9]|$)")
+                .expect("AV1 profile pattern is valid")
+        });
+
+        match profile.captures(title) {
+            Some(captures) => Av1Profile::from_name(&captures[1]),
+            None if token_regex!(r"10[ ._-]?bit").is_match(title) => Some(Av1Profile::Main10),
+            None => None,
+        }
+    }
+
+    /// Codec for a name or alias such as `x265`, `HEVC`, `VVC` or
+    /// `AV1 Main10`; plain `AV1` stands for every profile
+    pub fn from_name(name: &str) -> Option<Self> {
+        let normalized: String = name
+            .to_lowercase()
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .collect();
+
+        match normalized.as_str() {
+            "xvid" | "divx" => Some(VideoCodec::Xvid),
+            "x264" | "h264" | "avc" => Some(VideoCodec::H264),
+            "vp9" => Some(VideoCodec::Vp9),
+            "x265" | "h265" | "hevc" => Some(VideoCodec::H265),
+            "x266" | "h266" | "vvc" => Some(VideoCodec::H266),
+            "av1" => Some(VideoCodec::Av1(None)),
+            other => other
+                .strip_prefix("av1")
+                .and_then(Av1Profile::from_name)
+                .map(|profile| VideoCodec::Av1(Some(profile))),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            VideoCodec::Xvid => "XviD",
+            VideoCodec::H264 => "H.264",
+            VideoCodec::Vp9 => "VP9",
+            VideoCodec::H265 => "H.265",
+            VideoCodec::Av1(None) => "AV1",
+            VideoCodec::Av1(Some(Av1Profile::Main)) => "AV1 Main",
+            VideoCodec::Av1(Some(Av1Profile::Main10)) => "AV1 Main10",
+            VideoCodec::Av1(Some(Av1Profile::High)) => "AV1 High",
+            VideoCodec::Av1(Some(Av1Profile::Professional)) => "AV1 Professional",
+            VideoCodec::H266 => "H.266",
+        }
+    }
+
+    /// Generation of the codec, for display
+    pub fn generation(&self) -> &'static str {
+        match self {
+            VideoCodec::Xvid => "Legacy",
+            VideoCodec::H264 => "Mature",
+            VideoCodec::Vp9 | VideoCodec::H265 => "Modern",
+            VideoCodec::Av1(_) | VideoCodec::H266 => "Next-Gen",
+        }
+    }
+
+    /// Rank from 0 to 100 by compression efficiency
+    pub fn score(&self) -> u32 {
+        match self {
+            VideoCodec::Xvid => 10,
+            VideoCodec::H264 => 50,
+            VideoCodec::Vp9 => 55,
+            VideoCodec::H265 => 70,
+            VideoCodec::Av1(None) | VideoCodec::Av1(Some(Av1Profile::Main)) => 75,
+            VideoCodec::Av1(Some(Av1Profile::Main10)) => 80,
+            VideoCodec::Av1(Some(Av1Profile::High)) => 80,
+            VideoCodec::Av1(Some(Av1Profile::Professional)) => 85,
+            VideoCodec::H266 => 90,
+        }
+    }
+
+    /// Whether a release with codec `other` satisfies this one; AV1 without
+    /// a profile covers every AV1 profile
+    pub fn includes(&self, other: &VideoCodec) -> bool {
+        match (self, other) {
+            (VideoCodec::Av1(None), VideoCodec::Av1(_)) => true,
+            _ => self == other,
+        }
+    }
+}
+
+impl AudioCodec {
+    /// Every codec, from lowest to highest ranked
+    pub const ALL: [AudioCodec; 13] = [
+        AudioCodec::Aac,
+        AudioCodec::Opus,
+        AudioCodec::Ac3,
+        AudioCodec::Eac3,
+        AudioCodec::Dts,
+        AudioCodec::DtsHd,
+        AudioCodec::Eac3Atmos,
+        AudioCodec::Flac,
+        AudioCodec::Pcm,
+        AudioCodec::DtsHdMa,
+        AudioCodec::TrueHd,
+        AudioCodec::DtsX,
+        AudioCodec::TrueHdAtmos,
+    ];
+
+    /// Every audio codec named in a release title, best first
+    ///
+    /// A bare `Atmos` tag is TrueHD Atmos unless the release is from a
+    /// streaming source, where Atmos always comes as DD+.
+    pub fn parse_all(title: &str) -> Vec<Self> {
+        let mut found = Vec::new();
+        let atmos = token_regex!("atmos").is_match(title);
+        let truehd = token_regex!(r"true[ ._-]?hd").is_match(title);
+        // Channel layouts follow DD+ directly (`DDP5.1`)
+        static EAC3: OnceLock<Regex> = OnceLock::new();
+        let eac3 = EAC3
+            .get_or_init(|| {
+                Regex::new(r"(?i)(?:^|[^a-z0-9])(?:ddp|dd\+|e-?ac-?3)(?:[^a-z]|$)")
+                    .expect("E-AC3 pattern is valid")
+            })
+            .is_match(title);
+
+        if truehd {
+            found.push(if atmos {
+                AudioCodec::TrueHdAtmos
+            } else {
+                AudioCodec::TrueHd
+            });
+        }
+        if eac3 {
+            found.push(if atmos && !truehd {
+                AudioCodec::Eac3Atmos
+            } else {
+                AudioCodec::Eac3
+            });
+        }
+        if atmos && !truehd && !eac3 {
+            let streaming = token_regex!(r"web[ ._-]?dl|web[ ._-]?rip|web").is_match(title);
+            found.push(if streaming {
+                AudioCodec::Eac3Atmos
+            } else {
+                AudioCodec::TrueHdAtmos
+            });
+        }
+
+        if token_regex!(r"dts[ ._:-]?x").is_match(title) {
+            found.push(AudioCodec::DtsX);
+        } else if token_regex!(r"dts[ ._-]?hd[ ._-]?ma").is_match(title) {
+            found.push(AudioCodec::DtsHdMa);
+        } else if token_regex!(r"dts[ ._-]?hd").is_match(title) {
+            found.push(AudioCodec::DtsHd);
+        } else if token_regex!("dts").is_match(title) {
+            found.push(AudioCodec::Dts);
+        }
+
+        let simple: [(&Regex, AudioCodec); 5] = [
+            (token_regex!("flac"), AudioCodec::Flac),
+            (token_regex!(r"l?pcm"), AudioCodec::Pcm),
+            (
+                token_regex!(r"ac-?3|dd[ ._]?[1-7]\.[01]|dd"),
+                AudioCodec::Ac3,
+            ),
+            (token_regex!("opus"), AudioCodec::Opus),
+            (token_regex!(r"aac(?:[ ._]?[1-7]\.[01])?"), AudioCodec::Aac),
+        ];
+        for (regex, codec) in simple {
+            if regex.is_match(title) && !(codec == AudioCodec::Ac3 && eac3) {
+                found.push(codec);
+            }
+        }
+
+        found.sort_by_key(|codec| std::cmp::Reverse(codec.score()));
+        found
+    }
+
+    /// Best audio codec named in a release title
+    pub fn parse(title: &str) -> Option<Self> {
+        Self::parse_all(title).into_iter().next()
+    }
+
+    /// Codec for a name or alias such as `DD+`, `EAC3 Atmos` or `DTS-HD MA`
+    pub fn from_name(name: &str) -> Option<Self> {
+        let normalized: String = name
+            .to_lowercase()
+            .replace('+', "p")
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .collect();
+
+        match normalized.as_str() {
+            "aac" => Some(AudioCodec::Aac),
+            "opus" => Some(AudioCodec::Opus),
+            "ac3" | "dd" | "dolbydigital" => Some(AudioCodec::Ac3),
+            "eac3" | "ddp" | "dolbydigitalplus" => Some(AudioCodec::Eac3),
+            "eac3atmos" | "ddpatmos" => Some(AudioCodec::Eac3Atmos),
+            "dts" => Some(AudioCodec::Dts),
+            "dtshd" => Some(AudioCodec::DtsHd),
+            "dtshdma" => Some(AudioCodec::DtsHdMa),
+            "dtsx" => Some(AudioCodec::DtsX),
+            "flac" => Some(AudioCodec::Flac),
+            "pcm" | "lpcm" => Some(AudioCodec::Pcm),
+            "truehd" => Some(AudioCodec::TrueHd),
+            "truehdatmos" => Some(AudioCodec::TrueHdAtmos),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            AudioCodec::Aac => "AAC",
+            AudioCodec::Opus => "Opus",
+            AudioCodec::Ac3 => "DD",
+            AudioCodec::Eac3 => "DD+",
+            AudioCodec::Eac3Atmos => "DD+ Atmos",
+            AudioCodec::Dts => "DTS",
+            AudioCodec::DtsHd => "DTS-HD",
+            AudioCodec::DtsHdMa => "DTS-HD MA",
+            AudioCodec::DtsX => "DTS:X",
+            AudioCodec::Flac => "FLAC",
+            AudioCodec::Pcm => "PCM",
+            AudioCodec::TrueHd => "TrueHD",
+            AudioCodec::TrueHdAtmos => "TrueHD Atmos",
+        }
+    }
+
+    pub fn is_lossless(&self) -> bool {
+        matches!(
+            self,
+            AudioCodec::DtsHdMa
+                | AudioCodec::DtsX
+                | AudioCodec::Flac
+                | AudioCodec::Pcm
+                | AudioCodec::TrueHd
+                | AudioCodec::TrueHdAtmos
+        )
+    }
+
+    /// Whether the codec carries object-based surround (Atmos or DTS:X)
+    pub fn is_object_based(&self) -> bool {
+        matches!(
+            self,
+            AudioCodec::Eac3Atmos | AudioCodec::TrueHdAtmos | AudioCodec::DtsX
+        )
+    }
+
+    pub fn is_atmos(&self) -> bool {
+        matches!(self, AudioCodec::Eac3Atmos | AudioCodec::TrueHdAtmos)
+    }
+
+    /// Rank from 0 to 100
+    pub fn score(&self) -> u32 {
+        match self {
+            AudioCodec::Aac => 20,
+            AudioCodec::Opus => 25,
+            AudioCodec::Ac3 => 30,
+            AudioCodec::Eac3 => 40,
+            AudioCodec::Dts => 45,
+            AudioCodec::DtsHd => 55,
+            AudioCodec::Eac3Atmos => 60,
+            AudioCodec::Flac => 65,
+            AudioCodec::Pcm => 70,
+            AudioCodec::DtsHdMa | AudioCodec::TrueHd => 80,
+            AudioCodec::DtsX => 85,
+            AudioCodec::TrueHdAtmos => 90,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_video_codecs() {
+        let cases = [
+            (
+                "Movie.2024.2160p.WEB-DL.DDP5.1.H.266-GROUP",
+                Some(VideoCodec::H266),
+            ),
+            ("Movie.2024.2160p.WEB-DL.VVC-GROUP", Some(VideoCodec::H266)),
+            (
+                "Movie.2024.1080p.WEB-DL.AV1-GROUP",
+                Some(VideoCodec::Av1(None)),
+            ),
+            (
+                "Movie.2024.2160p.WEB-DL.AV1.10bit-GROUP",
+                Some(VideoCodec::Av1(Some(Av1Profile::Main10))),
+            ),
+            (
+                "Movie 2024 2160p AV1 Professional",
+                Some(VideoCodec::Av1(Some(Av1Profile::Professional))),
+            ),
+            ("Movie.2024.1080p.BluRay.x265-GROUP", Some(VideoCodec::H265)),
+            (
+                "Movie.2024.1080p.BluRay.H.264-GROUP",
+                Some(VideoCodec::H264),
+            ),
+            ("Movie.2004.DVDRip.XviD-GROUP", Some(VideoCodec::Xvid)),
+            ("Movie.2024.1080p.BluRay-GROUP", None),
+        ];
+        for (title, expected) in cases {
+            assert_eq!(VideoCodec::parse(title), expected, "{}", title);
+        }
+    }
+
+    #[test]
+    fn test_parse_audio_codecs_separates_atmos_carriers() {
+        let cases = [
+            (
+                "Movie.2024.2160p.WEB-DL.DDP5.1.Atmos.H.265",
+                AudioCodec::Eac3Atmos,
+            ),
+            ("Movie.2024.2160p.WEB-DL.DD+5.1.H.265", AudioCodec::Eac3),
+            (
+                "Movie.2024.2160p.BluRay.TrueHD.7.1.Atmos.x265",
+                AudioCodec::TrueHdAtmos,
+            ),
+            (
+                "Movie.2024.2160p.BluRay.TrueHD.5.1.x265",
+                AudioCodec::TrueHd,
+            ),
+            ("Movie.2024.2160p.WEB.Atmos.AV1", AudioCodec::Eac3Atmos),
+            (
+                "Movie.2024.2160p.BluRay.Atmos.x265",
+                AudioCodec::TrueHdAtmos,
+            ),
+            (
+                "Movie.2024.1080p.BluRay.DTS-HD.MA.5.1.x264",
+                AudioCodec::DtsHdMa,
+            ),
+            ("Movie.2024.2160p.BluRay.DTS-X.7.1.x265", AudioCodec::DtsX),
+            ("Movie.2024.1080p.WEB-DL.DD5.1.H.264", AudioCodec::Ac3),
+            ("Movie.2024.1080p.WEB-DL.AAC2.0.H.264", AudioCodec::Aac),
+        ];
+        for (title, expected) in cases {
+            assert_eq!(AudioCodec::parse(title), Some(expected), "{}", title);
+        }
+        assert_eq!(AudioCodec::parse("Movie.2024.1080p.BluRay.x264"), None);
+    }
+
+    #[test]
+    fn test_names_round_trip() {
+        for codec in VideoCodec::ALL {
+            assert_eq!(VideoCodec::from_name(codec.name()), Some(codec));
+        }
+        for codec in AudioCodec::ALL {
+            assert_eq!(AudioCodec::from_name(codec.name()), Some(codec));
+        }
+        assert_eq!(VideoCodec::from_name("x266"), Some(VideoCodec::H266));
+        assert!(VideoCodec::Av1(None).includes(&VideoCodec::Av1(Some(Av1Profile::High))));
+        assert!(!VideoCodec::Av1(Some(Av1Profile::Main)).includes(&VideoCodec::Av1(None)));
+    }
+}
@@ -0,0 +1,165 @@
+//! Movie collection domain model
+//!
+//! Collections group related films (a trilogy, a franchise) as TMDB defines
+//! them. The films themselves come from TMDB; whether the collection is
+//! monitored and which profile new films get are the user's settings and
+//! survive every sync.
+
+use super::movie::{MinimumAvailability, Movie};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// A film that is part of a collection
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CollectionMovie {
+    pub tmdb_id: i32,
+    pub title: String,
+    pub year: Option<i32>,
+    pub overview: Option<String>,
+    pub poster_path: Option<String>,
+}
+
+/// Movie collection entity
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MovieCollection {
+    pub id: Uuid,
+    pub tmdb_id: i32,
+    pub title: String,
+    pub overview: Option<String>,
+    pub poster_path: Option<String>,
+    pub backdrop_path: Option<String>,
+
+    /// Add missing films to the library whenever the collection is synced
+    pub monitored: bool,
+    /// Settings for films added from the collection
+    pub quality_profile_id: Option<i32>,
+    pub minimum_availability: MinimumAvailability,
+
+    /// Films in release order, as last fetched from TMDB
+    pub movies: Vec<CollectionMovie>,
+
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+    pub last_info_sync: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl MovieCollection {
+    /// Create a new, unmonitored collection
+    pub fn new(tmdb_id: i32, title: String) -> Self {
+        let now = chrono::Utc::now();
+
+        Self {
+            id: Uuid::new_v4(),
+            tmdb_id,
+            title,
+            overview: None,
+            poster_path: None,
+            backdrop_path: None,
+            monitored: false,
+            quality_profile_id: None,
+            minimum_availability: MinimumAvailability::default(),
+            movies: Vec::new(),
+            created_at: now,
+            updated_at: now,
+            last_info_sync: None,
+        }
+    }
+
+    /// Take the TMDB details from a freshly fetched copy
+    ///
+    /// The id and user settings of `self` are kept.
+    pub fn merge_metadata(&mut self, fetched: MovieCollection) {
+        let now = chrono::Utc::now();
+        self.title = fetched.title;
+        self.overview = fetched.overview;
+        self.poster_path = fetched.poster_path;
+        self.backdrop_path = fetched.backdrop_path;
+        self.movies = fetched.movies;
+        self.last_info_sync = Some(now);
+        self.updated_at = now;
+    }
+
+    /// Films that are not in the library yet
+    pub fn missing_movies(&self, library_tmdb_ids: &HashSet<i32>) -> Vec<&CollectionMovie> {
+        self.movies
+            .iter()
+            .filter(|movie| !library_tmdb_ids.contains(&movie.tmdb_id))
+            .collect()
+    }
+
+    /// Build a library movie for one of the collection's films
+    pub fn movie_for(&self, part: &CollectionMovie) -> Movie {
+        let mut movie = Movie::new(part.tmdb_id, part.title.clone());
+        movie.year = part.year;
+        movie.monitored = true;
+        movie.quality_profile_id = self.quality_profile_id;
+        movie.minimum_availability = self.minimum_availability.clone();
+        movie.metadata = serde_json::json!({
+            "tmdb": {
+                "id": part.tmdb_id,
+                "title": part.title,
+                "overview": part.overview,
+                "poster_path": part.poster_path,
+                "collection": {
+                    "id": self.tmdb_id,
+                    "name": self.title,
+                }
+            }
+        });
+        movie
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trilogy() -> MovieCollection {
+        let mut collection = MovieCollection::new(10, "The Matrix Collection".to_string());
+        collection.quality_profile_id = Some(4);
+        collection.movies = [(603, "The Matrix"), (604, "The Matrix Reloaded")]
+            .into_iter()
+            .map(|(tmdb_id, title)| CollectionMovie {
+                tmdb_id,
+                title: title.to_string(),
+                year: None,
+                overview: None,
+                poster_path: None,
+            })
+            .collect();
+        collection
+    }
+
+    #[test]
+    fn test_missing_movies_and_new_movie_settings() {
+        let collection = trilogy();
+        let library = HashSet::from([603]);
+
+        let missing = collection.missing_movies(&library);
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].tmdb_id, 604);
+
+        let movie = collection.movie_for(missing[0]);
+        assert_eq!(movie.tmdb_id, 604);
+        assert_eq!(movie.quality_profile_id, Some(4));
+        assert_eq!(movie.collection_tmdb_id(), Some(10));
+    }
+
+    #[test]
+    fn test_merge_metadata_keeps_settings() {
+        let mut stored = trilogy();
+        stored.monitored = true;
+        let id = stored.id;
+
+        let fetched = MovieCollection::new(10, "Matrix Collection".to_string());
+        stored.merge_metadata(fetched);
+
+        assert_eq!(stored.id, id);
+        assert!(stored.monitored);
+        assert_eq!(stored.quality_profile_id, Some(4));
+        assert_eq!(stored.title, "Matrix Collection");
+        assert!(stored.movies.is_empty());
+        assert!(stored.last_info_sync.is_some());
+    }
+}
@@ -0,0 +1,55 @@
+//! Settings stored in the database
+//!
+//! Settings changed through the API are stored per section as JSON and win
+//! over the environment, which only supplies their initial values.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Section of the stored settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigSection {
+    /// Port and locale of the instance
+    Host,
+    /// Movie file and folder naming
+    Naming,
+    /// Propers, file dates and extras handling of imports
+    MediaManagement,
+}
+
+impl ConfigSection {
+    /// Key the section is stored under
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConfigSection::Host => "host",
+            ConfigSection::Naming => "naming",
+            ConfigSection::MediaManagement => "mediamanagement",
+        }
+    }
+}
+
+impl fmt::Display for ConfigSection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_section_keys_match_serde() {
+        for section in [
+            ConfigSection::Host,
+            ConfigSection::Naming,
+            ConfigSection::MediaManagement,
+        ] {
+            assert_eq!(
+                serde_json::to_value(section).unwrap(),
+                serde_json::Value::from(section.as_str())
+            );
+        }
+    }
+}
@@ -0,0 +1,98 @@
+//! Movie editions
+//!
+//! A movie can be in the library more than once, as a Director's Cut next to
+//! the theatrical release for example. Editions are parsed from release and
+//! file names into one spelling, so the file analyzer, the rename engine and
+//! the decision engine agree on which files are the same edition.
+
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Words an edition is made of, e.g. `Ultimate.Extended.Edition`
+const EDITION_PATTERN: &str = r"(?i)(?:^|[^a-z0-9'])((?:(?:\d{1,3}(?:st|nd|rd|th)[ ._-]Anniversary|Anniversary|Ultimate|Special|Collector'?s?|Director'?s?'?|Final|Extended|Theatrical|Unrated|Uncut|IMAX|Remastered|Restored|Criterion|Open[ ._-]Matte)[ ._-]?)+(?:Cut|Edition|Version|Collection)?)(?:[^a-z0-9]|$)";
+
+/// Edition named in a release or file name, e.g. `Director's Cut`
+///
+/// Only the part after the release year is searched when there is one, so
+/// titles such as `Uncut Gems` aren't taken for an edition.
+pub fn parse_edition(name: &str) -> Option<String> {
+    static EDITION: OnceLock<Regex> = OnceLock::new();
+    static YEAR: OnceLock<Regex> = OnceLock::new();
+    let edition = EDITION.get_or_init(|| Regex::new(EDITION_PATTERN).expect("valid pattern"));
+    let year = YEAR.get_or_init(|| {
+        Regex::new(r"(?:^|[^0-9])(?:19|20)\d{2}(?:[^0-9]|$)").expect("valid pattern")
+    });
+
+    let after_year = year.find(name).map_or(name, |m| &name[m.end()..]);
+    let words = edition.captures(after_year)?.get(1)?.as_str();
+    let normalized = words
+        .split(['.', '_', '-', ' '])
+        .filter(|word| !word.is_empty())
+        .map(normalize_word)
+        .collect::<Vec<_>>()
+        .join(" ");
+    (!normalized.is_empty()).then_some(normalized)
+}
+
+/// Whether two editions are the same; no edition only matches no edition
+pub fn same_edition(a: Option<&str>, b: Option<&str>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => a.eq_ignore_ascii_case(b),
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+fn normalize_word(word: &str) -> String {
+    let lower = word.to_lowercase();
+    match lower.trim_end_matches('\'') {
+        "director's" | "directors" | "director" => "Director's".to_string(),
+        "collector's" | "collectors" | "collector" => "Collector's".to_string(),
+        "imax" => "IMAX".to_string(),
+        _ => {
+            let mut chars = lower.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect())
+                .unwrap_or_default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_edition() {
+        let parse = |name: &str| parse_edition(name);
+
+        assert_eq!(
+            parse("Blade.Runner.1982.Directors.Cut.1080p.BluRay.x264-GRP").as_deref(),
+            Some("Director's Cut")
+        );
+        assert_eq!(
+            parse("The.Movie.Title.2010.Ultimate.Extended.Edition.1080p.BluRay").as_deref(),
+            Some("Ultimate Extended Edition")
+        );
+        assert_eq!(
+            parse("Aliens (1986) 25th Anniversary Edition 2160p").as_deref(),
+            Some("25th Anniversary Edition")
+        );
+        assert_eq!(
+            parse("Dune.2021.IMAX.2160p.WEB-DL").as_deref(),
+            Some("IMAX")
+        );
+        assert_eq!(parse("The.Matrix.1999.1080p.BluRay.x264-GRP"), None);
+        // Title words before the year are not an edition
+        assert_eq!(parse("Uncut.Gems.2019.1080p.WEB-DL"), None);
+    }
+
+    #[test]
+    fn test_same_edition() {
+        assert!(same_edition(Some("Director's Cut"), Some("director's cut")));
+        assert!(same_edition(None, None));
+        assert!(!same_edition(Some("Director's Cut"), None));
+        assert!(!same_edition(Some("Extended"), Some("Theatrical")));
+    }
+}
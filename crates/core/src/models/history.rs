@@ -0,0 +1,327 @@
+//! History domain model
+//!
+//! History rows are derived from [`SystemEvent`]s so that grabs, imports,
+//! failures and deletions stay visible after the queue and event bus have
+//! forgotten about them.
+
+use crate::events::{EventEnvelope, SystemEvent};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Kind of history entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum HistoryEventType {
+    /// A release was sent to the download client
+    Grabbed,
+    /// The download client reported a failure
+    DownloadFailed,
+    /// A download was imported into the library
+    Imported,
+    /// Importing a download failed
+    ImportFailed,
+    /// A movie was removed from the library
+    MovieDeleted,
+    /// A movie file was deleted from disk
+    MovieFileDeleted,
+    /// A component reported itself unhealthy
+    HealthIssue,
+}
+
+impl HistoryEventType {
+    /// All event types, in display order
+    pub const ALL: [HistoryEventType; 7] = [
+        HistoryEventType::Grabbed,
+        HistoryEventType::DownloadFailed,
+        HistoryEventType::Imported,
+        HistoryEventType::ImportFailed,
+        HistoryEventType::MovieDeleted,
+        HistoryEventType::MovieFileDeleted,
+        HistoryEventType::HealthIssue,
+    ];
+
+    /// Stored and serialized name
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HistoryEventType::Grabbed => "grabbed",
+            HistoryEventType::DownloadFailed => "downloadFailed",
+            HistoryEventType::Imported => "imported",
+            HistoryEventType::ImportFailed => "importFailed",
+            HistoryEventType::MovieDeleted => "movieDeleted",
+            HistoryEventType::MovieFileDeleted => "movieFileDeleted",
+            HistoryEventType::HealthIssue => "healthIssue",
+        }
+    }
+
+    /// Parse a stored name, ignoring case
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|event_type| event_type.as_str().eq_ignore_ascii_case(value))
+    }
+}
+
+impl std::fmt::Display for HistoryEventType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// `MovieFileDeleted` reason for a file replaced by a better release
+pub const UPGRADE_DELETE_REASON: &str = "upgrade";
+
+/// `SystemHealth` status reported by components that are fine
+pub const HEALTHY_STATUS: &str = "healthy";
+
+/// A single history entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryRecord {
+    pub id: Uuid,
+    pub event_type: HistoryEventType,
+    pub movie_id: Option<Uuid>,
+    /// Release or file name the entry is about
+    pub source_title: Option<String>,
+    /// Download client or queue identifier, when known
+    pub download_id: Option<String>,
+    /// Event-specific details
+    pub data: serde_json::Value,
+    /// Event that produced this entry, used to avoid duplicates
+    pub event_id: Option<Uuid>,
+    pub correlation_id: Option<Uuid>,
+    pub date: chrono::DateTime<chrono::Utc>,
+}
+
+impl HistoryRecord {
+    /// Create a new history entry dated now
+    pub fn new(event_type: HistoryEventType) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            event_type,
+            movie_id: None,
+            source_title: None,
+            download_id: None,
+            data: serde_json::json!({}),
+            event_id: None,
+            correlation_id: None,
+            date: chrono::Utc::now(),
+        }
+    }
+
+    /// Set the movie this entry belongs to
+    pub fn with_movie(mut self, movie_id: Option<Uuid>) -> Self {
+        self.movie_id = movie_id;
+        self
+    }
+
+    /// Set the source title
+    pub fn with_source_title(mut self, source_title: impl Into<String>) -> Self {
+        self.source_title = Some(source_title.into());
+        self
+    }
+
+    /// Set the download identifier
+    pub fn with_download_id(mut self, download_id: impl Into<String>) -> Self {
+        self.download_id = Some(download_id.into());
+        self
+    }
+
+    /// Set the event-specific details
+    pub fn with_data(mut self, data: serde_json::Value) -> Self {
+        self.data = data;
+        self
+    }
+
+    /// Build the history entry for an event, if the event is one we record
+    pub fn from_event(envelope: &EventEnvelope) -> Option<Self> {
+        let record = match &envelope.event {
+            SystemEvent::DownloadQueued {
+                movie_id,
+                release_id,
+                download_url,
+                title,
+            } => HistoryRecord::new(HistoryEventType::Grabbed)
+                .with_movie(Some(*movie_id))
+                .with_source_title(title.clone())
+                .with_data(serde_json::json!({
+                    "releaseId": release_id,
+                    "downloadUrl": download_url,
+                    "manual": false,
+                })),
+            SystemEvent::OverrideGrab {
+                movie_id,
+                guid,
+                title,
+                indexer,
+                download_client_id,
+            } => HistoryRecord::new(HistoryEventType::Grabbed)
+                .with_movie(*movie_id)
+                .with_source_title(title.clone())
+                .with_download_id(download_client_id.clone())
+                .with_data(serde_json::json!({
+                    "guid": guid,
+                    "indexer": indexer,
+                    "manual": true,
+                })),
+            SystemEvent::DownloadFailed {
+                movie_id,
+                queue_item_id,
+                error,
+            } => HistoryRecord::new(HistoryEventType::DownloadFailed)
+                .with_movie(Some(*movie_id))
+                .with_download_id(queue_item_id.to_string())
+                .with_data(serde_json::json!({ "message": error })),
+            SystemEvent::ImportComplete {
+                movie_id,
+                destination_path,
+                file_count,
+            } => HistoryRecord::new(HistoryEventType::Imported)
+                .with_movie(Some(*movie_id))
+                .with_source_title(destination_path.clone())
+                .with_data(serde_json::json!({
+                    "destinationPath": destination_path,
+                    "fileCount": file_count,
+                })),
+            SystemEvent::ImportFailed {
+                movie_id,
+                source_path,
+                error,
+            } => HistoryRecord::new(HistoryEventType::ImportFailed)
+                .with_movie(Some(*movie_id))
+                .with_source_title(source_path.clone())
+                .with_data(serde_json::json!({ "message": error })),
+            SystemEvent::MovieDeleted { movie_id, title } => {
+                HistoryRecord::new(HistoryEventType::MovieDeleted)
+                    .with_movie(Some(*movie_id))
+                    .with_source_title(title.clone())
+            }
+            SystemEvent::MovieFileDeleted {
+                movie_id,
+                movie_file_id,
+                relative_path,
+                reason,
+            } => HistoryRecord::new(HistoryEventType::MovieFileDeleted)
+                .with_movie(Some(*movie_id))
+                .with_source_title(relative_path.clone())
+                .with_data(serde_json::json!({
+                    "movieFileId": movie_file_id,
+                    "reason": reason,
+                })),
+            SystemEvent::SystemHealth {
+                component,
+                status,
+                message,
+            } if !status.eq_ignore_ascii_case(HEALTHY_STATUS) => {
+                HistoryRecord::new(HistoryEventType::HealthIssue)
+                    .with_source_title(component.clone())
+                    .with_data(serde_json::json!({
+                        "status": status,
+                        "message": message,
+                    }))
+            }
+            _ => return None,
+        };
+
+        Some(HistoryRecord {
+            event_id: Some(envelope.event_id),
+            correlation_id: Some(envelope.correlation_id.as_uuid()),
+            date: envelope.timestamp,
+            ..record
+        })
+    }
+}
+
+/// Filter and page selection for history queries
+#[derive(Debug, Clone)]
+pub struct HistoryQuery {
+    /// Only these event types; empty means all
+    pub event_types: Vec<HistoryEventType>,
+    pub movie_id: Option<Uuid>,
+    /// Only entries dated at or after this time
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+impl Default for HistoryQuery {
+    fn default() -> Self {
+        Self {
+            event_types: Vec::new(),
+            movie_id: None,
+            since: None,
+            limit: 50,
+            offset: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_type_round_trip() {
+        for event_type in HistoryEventType::ALL {
+            assert_eq!(
+                HistoryEventType::parse(event_type.as_str()),
+                Some(event_type)
+            );
+        }
+        assert_eq!(
+            HistoryEventType::parse("IMPORTED"),
+            Some(HistoryEventType::Imported)
+        );
+        assert_eq!(HistoryEventType::parse("renamed"), None);
+    }
+
+    #[test]
+    fn test_from_event_records_only_unhealthy_status() {
+        let healthy = EventEnvelope::new(SystemEvent::SystemHealth {
+            component: "qbittorrent".to_string(),
+            status: "healthy".to_string(),
+            message: None,
+        });
+        assert!(HistoryRecord::from_event(&healthy).is_none());
+
+        let unhealthy = EventEnvelope::new(SystemEvent::SystemHealth {
+            component: "qbittorrent".to_string(),
+            status: "unhealthy".to_string(),
+            message: Some("connection refused".to_string()),
+        });
+        let record = HistoryRecord::from_event(&unhealthy).unwrap();
+        assert_eq!(record.event_type, HistoryEventType::HealthIssue);
+        assert_eq!(record.source_title.as_deref(), Some("qbittorrent"));
+        assert_eq!(record.data["message"], "connection refused");
+    }
+
+    #[test]
+    fn test_from_event_records_manual_grab() {
+        let movie_id = Uuid::new_v4();
+        let envelope = EventEnvelope::new(SystemEvent::OverrideGrab {
+            movie_id: Some(movie_id),
+            guid: "1-Movie.2024".to_string(),
+            title: "Movie.2024.1080p.BluRay.x264-GRP".to_string(),
+            indexer: "HDBits".to_string(),
+            download_client_id: "abc123".to_string(),
+        });
+
+        let record = HistoryRecord::from_event(&envelope).unwrap();
+        assert_eq!(record.event_type, HistoryEventType::Grabbed);
+        assert_eq!(record.movie_id, Some(movie_id));
+        assert_eq!(record.download_id.as_deref(), Some("abc123"));
+        assert_eq!(record.data["manual"], true);
+        assert_eq!(record.event_id, Some(envelope.event_id));
+        assert_eq!(record.date, envelope.timestamp);
+    }
+
+    #[test]
+    fn test_from_event_ignores_progress() {
+        let envelope = EventEnvelope::new(SystemEvent::DownloadProgress {
+            movie_id: Uuid::new_v4(),
+            queue_item_id: Uuid::new_v4(),
+            progress: 0.5,
+            speed: None,
+            eta_seconds: None,
+        });
+        assert!(HistoryRecord::from_event(&envelope).is_none());
+    }
+}
@@ -0,0 +1,58 @@
+//! Import list domain model
+//!
+//! Import lists are the configured external sources (IMDb, TMDb, Trakt,
+//! Plex, ...) that list sync adds movies from.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A configured external list source
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportList {
+    pub id: Uuid,
+    pub name: String,
+    /// Source platform: "imdb", "tmdb", "plex", "trakt", "letterboxd" or "custom"
+    pub source_type: String,
+    /// URL of a public list
+    pub list_url: Option<String>,
+    /// Platform-specific list ID
+    pub list_id: Option<String>,
+    pub enabled: bool,
+    pub sync_enabled: bool,
+    /// Minutes between syncs
+    pub sync_interval: i32,
+    /// Monitor movies added from the list
+    pub add_monitored: bool,
+    pub minimum_availability: String,
+    pub quality_profile_id: Option<i32>,
+    pub root_folder_path: Option<String>,
+    /// Tags given to movies added from the list
+    pub tags: Vec<i32>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl ImportList {
+    /// Create a new import list with the database defaults
+    pub fn new(name: String, source_type: String) -> Self {
+        let now = chrono::Utc::now();
+
+        Self {
+            id: Uuid::new_v4(),
+            name,
+            source_type,
+            list_url: None,
+            list_id: None,
+            enabled: true,
+            sync_enabled: true,
+            sync_interval: 360,
+            add_monitored: true,
+            minimum_availability: "announced".to_string(),
+            quality_profile_id: None,
+            root_folder_path: None,
+            tags: Vec::new(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+}
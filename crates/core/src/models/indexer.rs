@@ -32,6 +32,10 @@ pub struct Indexer {
     // Download client association
     pub download_client_id: Option<i32>,
 
+    // Only used for movies with one of these tags; empty for every movie
+    #[serde(default)]
+    pub tags: Vec<i32>,
+
     // Timestamps
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
@@ -53,6 +57,7 @@ impl Indexer {
             enable_automatic_search: true,
             enable_interactive_search: true,
             download_client_id: None,
+            tags: Vec::new(),
             created_at: now,
             updated_at: now,
         }
@@ -0,0 +1,173 @@
+//! Indexer check domain model
+//!
+//! When an indexer is saved its capabilities are probed and a canary search
+//! for a well-known movie is run. The outcome is kept per indexer, so the UI
+//! can show whether the indexer returns releases we can actually use, not
+//! just whether it accepted the credentials.
+
+use serde::{Deserialize, Serialize};
+
+/// Outcome of an indexer check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum IndexerCheckStatus {
+    /// Queued or still running
+    Pending,
+    /// The canary search returned parseable releases
+    Passed,
+    /// The indexer answered, but with few or no usable releases
+    Warning,
+    /// The capability probe or the canary search failed
+    Failed,
+}
+
+impl IndexerCheckStatus {
+    /// All statuses
+    pub const ALL: [IndexerCheckStatus; 4] = [
+        IndexerCheckStatus::Pending,
+        IndexerCheckStatus::Passed,
+        IndexerCheckStatus::Warning,
+        IndexerCheckStatus::Failed,
+    ];
+
+    /// Name used in the database and the API
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IndexerCheckStatus::Pending => "pending",
+            IndexerCheckStatus::Passed => "passed",
+            IndexerCheckStatus::Warning => "warning",
+            IndexerCheckStatus::Failed => "failed",
+        }
+    }
+
+    /// Parse a stored name, ignoring case
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|status| status.as_str().eq_ignore_ascii_case(value))
+    }
+}
+
+impl std::fmt::Display for IndexerCheckStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Latest check of an indexer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexerCheck {
+    pub indexer_id: i32,
+    pub status: IndexerCheckStatus,
+    pub message: String,
+
+    /// Whether the capability probe reported movie search support
+    pub movie_search: bool,
+    /// Search parameters the indexer advertises (q, imdbid, ...)
+    pub search_params: Vec<String>,
+
+    /// Query sent for the canary search
+    pub canary_query: Option<String>,
+    pub canary_results: i32,
+    /// Canary results with a title, a download link and a recognizable quality
+    pub parseable_results: i32,
+    /// A few result titles to show alongside the outcome
+    pub sample_titles: Vec<String>,
+
+    pub duration_ms: i64,
+    pub checked_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl IndexerCheck {
+    /// A check that has been queued but not run yet
+    pub fn pending(indexer_id: i32) -> Self {
+        Self {
+            indexer_id,
+            status: IndexerCheckStatus::Pending,
+            message: "Check queued".to_string(),
+            movie_search: false,
+            search_params: Vec::new(),
+            canary_query: None,
+            canary_results: 0,
+            parseable_results: 0,
+            sample_titles: Vec::new(),
+            duration_ms: 0,
+            checked_at: chrono::Utc::now(),
+        }
+    }
+
+    /// Mark the check as failed
+    pub fn fail(&mut self, message: impl Into<String>) {
+        self.status = IndexerCheckStatus::Failed;
+        self.message = message.into();
+    }
+
+    /// Set the status from the canary search counts
+    ///
+    /// At least half of the results must be parseable for the check to pass.
+    pub fn grade(&mut self) {
+        let (status, message) = if self.canary_results == 0 {
+            (
+                IndexerCheckStatus::Warning,
+                "Canary search returned no results".to_string(),
+            )
+        } else if self.parseable_results * 2 < self.canary_results {
+            (
+                IndexerCheckStatus::Warning,
+                format!(
+                    "Only {} of {} canary results could be parsed",
+                    self.parseable_results, self.canary_results
+                ),
+            )
+        } else {
+            (
+                IndexerCheckStatus::Passed,
+                format!(
+                    "Canary search returned {} results, {} parseable",
+                    self.canary_results, self.parseable_results
+                ),
+            )
+        };
+
+        self.status = status;
+        self.message = if self.movie_search {
+            message
+        } else {
+            format!("{}; indexer does not advertise movie search", message)
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graded(canary_results: i32, parseable_results: i32) -> IndexerCheck {
+        let mut check = IndexerCheck::pending(1);
+        check.movie_search = true;
+        check.canary_results = canary_results;
+        check.parseable_results = parseable_results;
+        check.grade();
+        check
+    }
+
+    #[test]
+    fn test_grade() {
+        assert_eq!(graded(0, 0).status, IndexerCheckStatus::Warning);
+        assert_eq!(graded(10, 4).status, IndexerCheckStatus::Warning);
+        assert_eq!(graded(10, 5).status, IndexerCheckStatus::Passed);
+        assert_eq!(
+            graded(10, 10).message,
+            "Canary search returned 10 results, 10 parseable"
+        );
+    }
+
+    #[test]
+    fn test_status_round_trip() {
+        for status in IndexerCheckStatus::ALL {
+            assert_eq!(IndexerCheckStatus::parse(status.as_str()), Some(status));
+        }
+        assert_eq!(IndexerCheckStatus::parse("unknown"), None);
+    }
+}
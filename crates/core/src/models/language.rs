@@ -0,0 +1,219 @@
+//! Release languages
+//!
+//! Scene releases are English unless tagged otherwise, e.g. `FRENCH`,
+//! `GERMAN` or `VOSTFR`. `MULTI` and dual-audio releases (`Dual-Audio`,
+//! `GERMAN.DL`) carry the original track, taken to be English, next to the
+//! dubs they name.
+
+use crate::error::RadarrError;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Audio language of a release
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    English,
+    French,
+    German,
+    Spanish,
+    Italian,
+    Dutch,
+    Portuguese,
+    Russian,
+    Polish,
+    Swedish,
+    Danish,
+    Norwegian,
+    Finnish,
+    Japanese,
+    Korean,
+    Chinese,
+    Hindi,
+}
+
+/// Name tokens of each language, upper case
+const LANGUAGE_TOKENS: &[(Language, &[&str])] = &[
+    (Language::English, &["ENGLISH", "ENG"]),
+    (
+        Language::French,
+        &[
+            "FRENCH",
+            "TRUEFRENCH",
+            "VFF",
+            "VFQ",
+            "VFI",
+            "VF2",
+            "VOSTFR",
+            "SUBFRENCH",
+        ],
+    ),
+    (Language::German, &["GERMAN", "DEUTSCH", "GER"]),
+    (
+        Language::Spanish,
+        &["SPANISH", "ESPANOL", "CASTELLANO", "LATINO", "SPA"],
+    ),
+    (Language::Italian, &["ITALIAN", "ITA"]),
+    (Language::Dutch, &["DUTCH", "FLEMISH"]),
+    (Language::Portuguese, &["PORTUGUESE", "POR"]),
+    (Language::Russian, &["RUSSIAN", "RUS"]),
+    (Language::Polish, &["POLISH", "PLDUB", "PL"]),
+    (Language::Swedish, &["SWEDISH", "SWE"]),
+    (Language::Danish, &["DANISH"]),
+    (Language::Norwegian, &["NORWEGIAN", "NOR"]),
+    (Language::Finnish, &["FINNISH", "FIN"]),
+    (Language::Japanese, &["JAPANESE", "JAP", "JPN"]),
+    (Language::Korean, &["KOREAN", "KOR"]),
+    (
+        Language::Chinese,
+        &["CHINESE", "MANDARIN", "CANTONESE", "CHI"],
+    ),
+    (Language::Hindi, &["HINDI"]),
+];
+
+impl Language {
+    /// Display name, e.g. `English`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::French => "French",
+            Language::German => "German",
+            Language::Spanish => "Spanish",
+            Language::Italian => "Italian",
+            Language::Dutch => "Dutch",
+            Language::Portuguese => "Portuguese",
+            Language::Russian => "Russian",
+            Language::Polish => "Polish",
+            Language::Swedish => "Swedish",
+            Language::Danish => "Danish",
+            Language::Norwegian => "Norwegian",
+            Language::Finnish => "Finnish",
+            Language::Japanese => "Japanese",
+            Language::Korean => "Korean",
+            Language::Chinese => "Chinese",
+            Language::Hindi => "Hindi",
+        }
+    }
+
+    fn from_token(token: &str) -> Option<Self> {
+        LANGUAGE_TOKENS
+            .iter()
+            .find(|(_, tokens)| tokens.contains(&token))
+            .map(|(language, _)| *language)
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Language {
+    type Err = RadarrError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        LANGUAGE_TOKENS
+            .iter()
+            .map(|(language, _)| *language)
+            .find(|language| language.name().eq_ignore_ascii_case(value.trim()))
+            .ok_or_else(|| RadarrError::ValidationError {
+                field: "language".to_string(),
+                message: format!("Unknown language '{}'", value),
+            })
+    }
+}
+
+/// Audio languages of a release or file name, English when none is tagged
+///
+/// Only the part after the release year is searched when there is one, so
+/// titles such as `The French Dispatch` aren't taken for a language tag.
+pub fn parse_languages(name: &str) -> Vec<Language> {
+    let tokens: Vec<String> = name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .map(str::to_ascii_uppercase)
+        .collect();
+    let after_year = tokens
+        .iter()
+        .position(|token| is_year(token))
+        .map_or(0, |index| index + 1);
+
+    let mut languages = Vec::new();
+    let mut original_track = false;
+    let mut previous: Option<Language> = None;
+    for token in &tokens[after_year..] {
+        let language = Language::from_token(token);
+        match token.as_str() {
+            "MULTI" | "DUAL" => original_track = true,
+            // German scene dual language, not the DL of WEB-DL
+            "DL" if previous.is_some() => original_track = true,
+            _ => {}
+        }
+        if let Some(language) = language {
+            if !languages.contains(&language) {
+                languages.push(language);
+            }
+        }
+        previous = language;
+    }
+
+    if (languages.is_empty() || original_track) && !languages.contains(&Language::English) {
+        languages.insert(0, Language::English);
+    }
+    languages
+}
+
+fn is_year(token: &str) -> bool {
+    token.len() == 4
+        && (token.starts_with("19") || token.starts_with("20"))
+        && token.bytes().all(|b| b.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_languages() {
+        use Language::*;
+
+        assert_eq!(
+            parse_languages("Movie.2023.1080p.BluRay.x264-GRP"),
+            vec![English]
+        );
+        assert_eq!(
+            parse_languages("Movie.2023.FRENCH.1080p.WEB-DL.x264-GRP"),
+            vec![French]
+        );
+        assert_eq!(
+            parse_languages("Movie.2023.VOSTFR.720p.WEBRip-GRP"),
+            vec![French]
+        );
+        assert_eq!(
+            parse_languages("Movie.2023.MULTI.VFF.1080p.BluRay-GRP"),
+            vec![English, French]
+        );
+        assert_eq!(
+            parse_languages("Movie.2023.German.DL.1080p.WEB-DL.h264-GRP"),
+            vec![English, German]
+        );
+        assert_eq!(
+            parse_languages("Movie (2023) 1080p BluRay Dual-Audio Japanese"),
+            vec![English, Japanese]
+        );
+        // Title words before the year are not a language tag
+        assert_eq!(
+            parse_languages("The.French.Dispatch.2021.1080p.WEB-DL-GRP"),
+            vec![English]
+        );
+    }
+
+    #[test]
+    fn test_language_from_str() {
+        assert_eq!("german".parse::<Language>().unwrap(), Language::German);
+        assert_eq!(" French ".parse::<Language>().unwrap(), Language::French);
+        assert!("klingon".parse::<Language>().is_err());
+    }
+}
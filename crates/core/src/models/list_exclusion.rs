@@ -0,0 +1,38 @@
+//! List exclusion domain model
+//!
+//! Excluded movies are never added back to the library automatically, e.g.
+//! by list sync or a monitored collection, after the user deleted them.
+
+use super::movie::Movie;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A movie that must not be re-added automatically
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListExclusion {
+    pub id: Uuid,
+    pub tmdb_id: Option<i32>,
+    pub imdb_id: Option<String>,
+    pub title: String,
+    pub year: Option<i32>,
+    pub reason: Option<String>,
+    /// User or component that created the exclusion
+    pub excluded_by: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl ListExclusion {
+    /// Exclusion for a movie that is being removed from the library
+    pub fn for_movie(movie: &Movie, reason: impl Into<String>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            tmdb_id: Some(movie.tmdb_id),
+            imdb_id: movie.imdb_id.clone(),
+            title: movie.title.clone(),
+            year: movie.year,
+            reason: Some(reason.into()),
+            excluded_by: None,
+            created_at: chrono::Utc::now(),
+        }
+    }
+}
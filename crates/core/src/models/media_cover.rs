@@ -0,0 +1,131 @@
+//! Movie artwork
+//!
+//! Posters and fanart are downloaded from TMDB and served from
+//! `/MediaCover/{movieId}/{file}` under the file names Radarr UIs request:
+//! `poster.jpg` for the full image and `poster-500.jpg` or `poster-250.jpg`
+//! for resized copies.
+
+use super::Movie;
+use serde::{Deserialize, Serialize};
+
+/// Base URL of full-size TMDB images
+pub const TMDB_IMAGE_BASE_URL: &str = "https://image.tmdb.org/t/p/original";
+
+/// Kind of movie artwork
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MediaCoverType {
+    Poster,
+    Fanart,
+}
+
+impl MediaCoverType {
+    pub const ALL: [MediaCoverType; 2] = [MediaCoverType::Poster, MediaCoverType::Fanart];
+
+    /// Name used in file names and the API
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MediaCoverType::Poster => "poster",
+            MediaCoverType::Fanart => "fanart",
+        }
+    }
+
+    /// Widths resized copies are made in, largest first
+    pub fn resized_widths(&self) -> &'static [u32] {
+        match self {
+            MediaCoverType::Poster => &[500, 250],
+            MediaCoverType::Fanart => &[360, 180],
+        }
+    }
+
+    /// Key of the image path in the movie's TMDB metadata
+    fn tmdb_key(&self) -> &'static str {
+        match self {
+            MediaCoverType::Poster => "poster_path",
+            MediaCoverType::Fanart => "backdrop_path",
+        }
+    }
+
+    /// File name of the full image, or of the copy resized to `width`
+    pub fn file_name(&self, width: Option<u32>) -> String {
+        match width {
+            Some(width) => format!("{}-{}.jpg", self.as_str(), width),
+            None => format!("{}.jpg", self.as_str()),
+        }
+    }
+
+    /// Cover type and width of a requested file name such as
+    /// `poster-250.jpg`; the extension is optional and only known widths
+    /// are accepted
+    pub fn parse_file_name(name: &str) -> Option<(Self, Option<u32>)> {
+        let stem = name.strip_suffix(".jpg").unwrap_or(name);
+        let (type_name, width) = match stem.split_once('-') {
+            Some((type_name, width)) => (type_name, Some(width.parse::<u32>().ok()?)),
+            None => (stem, None),
+        };
+        let cover_type = Self::ALL
+            .into_iter()
+            .find(|cover_type| cover_type.as_str().eq_ignore_ascii_case(type_name))?;
+        match width {
+            Some(width) if !cover_type.resized_widths().contains(&width) => None,
+            _ => Some((cover_type, width)),
+        }
+    }
+}
+
+impl std::fmt::Display for MediaCoverType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Movie {
+    /// TMDB URL of the movie's full-size artwork of `cover_type`, if it has
+    /// any
+    pub fn media_cover_url(&self, cover_type: MediaCoverType) -> Option<String> {
+        self.metadata
+            .get("tmdb")?
+            .get(cover_type.tmdb_key())?
+            .as_str()
+            .filter(|path| !path.is_empty())
+            .map(|path| format!("{}{}", TMDB_IMAGE_BASE_URL, path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_media_cover_file_name() {
+        assert_eq!(
+            MediaCoverType::parse_file_name("poster.jpg"),
+            Some((MediaCoverType::Poster, None))
+        );
+        assert_eq!(
+            MediaCoverType::parse_file_name("poster-250.jpg"),
+            Some((MediaCoverType::Poster, Some(250)))
+        );
+        assert_eq!(
+            MediaCoverType::parse_file_name("fanart"),
+            Some((MediaCoverType::Fanart, None))
+        );
+        assert_eq!(MediaCoverType::parse_file_name("poster-123.jpg"), None);
+        assert_eq!(MediaCoverType::parse_file_name("banner.jpg"), None);
+        assert_eq!(
+            MediaCoverType::Fanart.file_name(Some(360)),
+            "fanart-360.jpg"
+        );
+    }
+
+    #[test]
+    fn test_media_cover_url() {
+        let mut movie = Movie::new(550, "Fight Club".to_string());
+        movie.metadata = serde_json::json!({ "tmdb": { "poster_path": "/poster.jpg" } });
+        assert_eq!(
+            movie.media_cover_url(MediaCoverType::Poster).as_deref(),
+            Some("https://image.tmdb.org/t/p/original/poster.jpg")
+        );
+        assert_eq!(movie.media_cover_url(MediaCoverType::Fanart), None);
+    }
+}
@@ -3,17 +3,57 @@
 //! This module contains the fundamental entities and value objects
 //! that represent the core concepts in the Radarr domain.
 
+pub mod api_key;
+pub mod audit;
+pub mod calendar;
+pub mod codec;
+pub mod collection;
+pub mod config;
 pub mod download;
+pub mod edition;
+pub mod history;
+pub mod import_list;
 pub mod indexer;
+pub mod indexer_check;
+pub mod language;
+pub mod list_exclusion;
+pub mod media_cover;
 pub mod movie;
+pub mod movie_file;
+pub mod notification;
 pub mod quality;
 pub mod queue;
+pub mod rating;
 pub mod release;
+pub mod revision;
+pub mod root_folder;
+pub mod share_token;
+pub mod tag;
 
 // Re-export all models for easier access
+pub use api_key::*;
+pub use audit::*;
+pub use calendar::*;
+pub use codec::*;
+pub use collection::*;
+pub use config::*;
 pub use download::*;
+pub use edition::*;
+pub use history::*;
+pub use import_list::*;
 pub use indexer::*;
+pub use indexer_check::*;
+pub use language::*;
+pub use list_exclusion::*;
+pub use media_cover::*;
 pub use movie::*;
+pub use movie_file::*;
+pub use notification::*;
 pub use quality::*;
 pub use queue::*;
+pub use rating::*;
 pub use release::*;
+pub use revision::*;
+pub use root_folder::*;
+pub use share_token::*;
+pub use tag::*;
@@ -1,5 +1,6 @@
 //! Movie domain model
 
+use super::{tags_match, Indexer, MovieRatings, ReleaseType};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -17,10 +18,14 @@ pub enum MovieStatus {
 /// Minimum availability requirements
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub enum MinimumAvailability {
+    #[serde(alias = "announced")]
     Announced,
+    #[serde(alias = "inCinemas")]
     InCinemas,
     #[default]
+    #[serde(alias = "released")]
     Released,
+    #[serde(alias = "preDB", alias = "predb")]
     Predb,
 }
 
@@ -53,11 +58,27 @@ pub struct Movie {
     pub metadata: serde_json::Value,
     pub alternative_titles: serde_json::Value,
 
+    // Ratings by service
+    #[serde(default)]
+    pub ratings: MovieRatings,
+
     // Timestamps
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
     pub last_search_time: Option<chrono::DateTime<chrono::Utc>>,
     pub last_info_sync: Option<chrono::DateTime<chrono::Utc>>,
+
+    // Consecutive automatic searches that found no acceptable release
+    #[serde(default)]
+    pub failed_search_count: i32,
+
+    // Indexers never searched for this movie
+    #[serde(default)]
+    pub excluded_indexer_ids: Vec<i32>,
+
+    // Tags scoping the indexers and notifications used for this movie
+    #[serde(default)]
+    pub tags: Vec<i32>,
 }
 
 impl Movie {
@@ -81,10 +102,14 @@ impl Movie {
             movie_file_id: None,
             metadata: serde_json::json!({}),
             alternative_titles: serde_json::json!([]),
+            ratings: MovieRatings::default(),
             created_at: now,
             updated_at: now,
             last_search_time: None,
             last_info_sync: None,
+            failed_search_count: 0,
+            excluded_indexer_ids: Vec::new(),
+            tags: Vec::new(),
         }
     }
 
@@ -94,6 +119,11 @@ impl Movie {
         self.updated_at = chrono::Utc::now();
     }
 
+    /// Root folder the movie is filed under, when one was chosen for it
+    pub fn root_folder_path(&self) -> Option<&str> {
+        self.metadata.get("root_folder_path")?.as_str()
+    }
+
     /// Mark the movie as having a file
     pub fn set_has_file(&mut self, movie_file_id: Uuid) {
         self.has_file = true;
@@ -101,6 +131,62 @@ impl Movie {
         self.updated_at = chrono::Utc::now();
     }
 
+    /// Mark the movie as missing its file again
+    ///
+    /// The search cooldown is reset too, so the next automatic search looks
+    /// for a replacement straight away.
+    pub fn clear_file(&mut self) {
+        self.has_file = false;
+        self.movie_file_id = None;
+        self.last_search_time = None;
+        self.failed_search_count = 0;
+        self.updated_at = chrono::Utc::now();
+    }
+
+    /// Record the outcome of an automatic search
+    pub fn record_search(&mut self, found: bool) {
+        let now = chrono::Utc::now();
+        self.last_search_time = Some(now);
+        self.failed_search_count = if found {
+            0
+        } else {
+            self.failed_search_count.saturating_add(1)
+        };
+        self.updated_at = now;
+    }
+
+    /// Whether the movie has reached its minimum availability at `now`
+    ///
+    /// Release dates come from the TMDB metadata. Without a digital or
+    /// physical release date a movie counts as released 90 days after its
+    /// cinema release; without any dates the TMDB status decides. PreDB is
+    /// treated like released since there is no PreDB source.
+    pub fn is_available(&self, now: chrono::DateTime<chrono::Utc>) -> bool {
+        let date = |release_type: ReleaseType| {
+            self.release_date(release_type)
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+                .map(|date| date.and_utc())
+        };
+        let in_cinemas = date(ReleaseType::InCinemas);
+        let released = date(ReleaseType::DigitalRelease)
+            .into_iter()
+            .chain(date(ReleaseType::PhysicalRelease))
+            .min()
+            .or_else(|| in_cinemas.map(|date| date + chrono::Duration::days(90)));
+
+        match self.minimum_availability {
+            MinimumAvailability::Announced => true,
+            MinimumAvailability::InCinemas => match in_cinemas {
+                Some(date) => date <= now,
+                None => self.status == MovieStatus::Released,
+            },
+            MinimumAvailability::Released | MinimumAvailability::Predb => match released {
+                Some(date) => date <= now,
+                None => self.status == MovieStatus::Released,
+            },
+        }
+    }
+
     /// Get the movie's rating from metadata
     pub fn rating(&self) -> Option<f64> {
         self.metadata
@@ -116,6 +202,82 @@ impl Movie {
             .and_then(|tmdb| tmdb.get("overview"))
             .and_then(|overview| overview.as_str())
     }
+
+    /// Get the TMDB ID of the collection the movie belongs to, if any
+    pub fn collection_tmdb_id(&self) -> Option<i32> {
+        self.metadata
+            .get("tmdb")
+            .and_then(|tmdb| tmdb.get("collection"))
+            .and_then(|collection| collection.get("id"))
+            .and_then(|id| id.as_i64())
+            .and_then(|id| i32::try_from(id).ok())
+    }
+
+    /// Title, original title and alternative titles, for matching
+    pub fn search_titles(&self) -> Vec<&str> {
+        let alternatives = self
+            .alternative_titles
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|title| title.as_str().or_else(|| title.get("title")?.as_str()));
+        std::iter::once(self.title.as_str())
+            .chain(self.original_title.as_deref())
+            .chain(alternatives)
+            .collect()
+    }
+
+    /// Indexers never searched for this movie: the ones it excludes and
+    /// those restricted to tags it doesn't have
+    pub fn excluded_indexers(&self, indexers: &[Indexer]) -> Vec<i32> {
+        let mut excluded = self.excluded_indexer_ids.clone();
+        excluded.extend(
+            indexers
+                .iter()
+                .filter(|indexer| !tags_match(&indexer.tags, &self.tags))
+                .map(|indexer| indexer.id),
+        );
+        excluded.sort_unstable();
+        excluded.dedup();
+        excluded
+    }
+}
+
+/// Lowercase a title, strip accents and collapse punctuation to single
+/// spaces, so "Amélie: Le Fabuleux" and "amelie le fabuleux" compare equal.
+/// Matches the `movie_search_text` SQL function behind library search.
+pub fn normalize_search_title(title: &str) -> String {
+    let mut normalized = String::with_capacity(title.len());
+    for c in title.chars().flat_map(char::to_lowercase) {
+        let folded = match c {
+            'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+            'æ' => "ae",
+            'ç' | 'ć' | 'č' => "c",
+            'ď' | 'đ' | 'ð' => "d",
+            'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ė' | 'ę' | 'ě' => "e",
+            'ğ' => "g",
+            'ì' | 'í' | 'î' | 'ï' | 'ī' | 'į' | 'ı' => "i",
+            'ł' | 'ľ' | 'ĺ' => "l",
+            'ñ' | 'ń' | 'ň' => "n",
+            'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ő' => "o",
+            'œ' => "oe",
+            'ř' | 'ŕ' => "r",
+            'ß' => "ss",
+            'ś' | 'š' | 'ş' => "s",
+            'ť' | 'ţ' => "t",
+            'ù' | 'ú' | 'û' | 'ü' | 'ū' | 'ů' | 'ű' | 'ų' => "u",
+            'ý' | 'ÿ' => "y",
+            'ź' | 'ż' | 'ž' => "z",
+            'þ' => "th",
+            c if c.is_alphanumeric() => {
+                normalized.push(c);
+                continue;
+            }
+            _ => " ",
+        };
+        normalized.push_str(folded);
+    }
+    normalized.split_whitespace().collect::<Vec<_>>().join(" ")
 }
 
 // Implement Display for enum serialization to string
@@ -141,3 +303,64 @@ impl std::fmt::Display for MinimumAvailability {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::IndexerImplementation;
+
+    #[test]
+    fn test_normalize_search_title_folds_accents_and_punctuation() {
+        assert_eq!(
+            normalize_search_title("Amélie: Le Fabuleux Destin d'Amélie Poulain"),
+            "amelie le fabuleux destin d amelie poulain"
+        );
+        assert_eq!(
+            normalize_search_title("  Léon -- The PROFESSIONAL "),
+            "leon the professional"
+        );
+        assert_eq!(normalize_search_title("Straße"), "strasse");
+        assert_eq!(
+            normalize_search_title("千と千尋の神隠し"),
+            "千と千尋の神隠し"
+        );
+    }
+
+    #[test]
+    fn test_search_titles_include_original_and_alternative_titles() {
+        let mut movie = Movie::new(129, "Spirited Away".to_string());
+        movie.original_title = Some("千と千尋の神隠し".to_string());
+        movie.alternative_titles =
+            serde_json::json!(["Sen to Chihiro no Kamikakushi", {"title": "Le Voyage de Chihiro"}]);
+        assert_eq!(
+            movie.search_titles(),
+            vec![
+                "Spirited Away",
+                "千と千尋の神隠し",
+                "Sen to Chihiro no Kamikakushi",
+                "Le Voyage de Chihiro"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_excluded_indexers_include_indexers_for_other_tags() {
+        let indexer = |id: i32, tags: Vec<i32>| Indexer {
+            id,
+            tags,
+            ..Indexer::new(format!("Indexer {}", id), IndexerImplementation::Torznab)
+        };
+        let indexers = [
+            indexer(1, vec![]),
+            indexer(2, vec![10]),
+            indexer(3, vec![20]),
+        ];
+
+        let mut movie = Movie::new(603, "The Matrix".to_string());
+        movie.excluded_indexer_ids = vec![1];
+        assert_eq!(movie.excluded_indexers(&indexers), vec![1, 2, 3]);
+
+        movie.tags = vec![10];
+        assert_eq!(movie.excluded_indexers(&indexers), vec![1, 3]);
+    }
+}
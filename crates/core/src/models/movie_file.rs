@@ -0,0 +1,204 @@
+//! Movie file domain model
+
+use super::quality::detect_quality_id;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
+use uuid::Uuid;
+
+/// Folder movie file paths are stored relative to: `MEDIA_ROOT`, or
+/// `/movies` when unset
+///
+/// Every root folder lies below it, so a movie keeps one stored path scheme
+/// wherever its folder moves.
+pub fn media_root() -> PathBuf {
+    std::env::var("MEDIA_ROOT")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from("/movies"))
+}
+
+/// Absolute path of a movie file path stored below `media_root`
+///
+/// Returns `None` for paths that are absolute or climb out of the media
+/// root, so a bad database row cannot point at arbitrary files.
+pub fn resolve_media_path(media_root: &Path, relative_path: &str) -> Option<PathBuf> {
+    let relative = Path::new(relative_path);
+    let contained = relative
+        .components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+    contained.then(|| media_root.join(relative))
+}
+
+/// Path of `path` below `media_root`, as movie files store it
+///
+/// Returns `None` when `path` is not below the media root.
+pub fn media_relative_path(media_root: &Path, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(media_root).ok()?;
+    if relative.as_os_str().is_empty() {
+        return None;
+    }
+    Some(relative.to_string_lossy().to_string())
+}
+
+/// A media file imported into the library for a movie
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MovieFile {
+    pub id: Uuid,
+    pub movie_id: Uuid,
+
+    // File location
+    pub relative_path: String,
+    pub size_bytes: i64,
+
+    // Quality information (quality_name, resolution, source, codec, ...)
+    pub quality: serde_json::Value,
+    pub media_info: Option<serde_json::Value>,
+    /// Custom format score of the release this file was imported from
+    pub custom_format_score: i32,
+    /// Edition of the file, e.g. `Director's Cut`; a movie can have a file
+    /// of each edition
+    #[serde(default)]
+    pub edition: Option<String>,
+
+    // File metadata
+    pub date_added: chrono::DateTime<chrono::Utc>,
+    pub last_write_time: Option<chrono::DateTime<chrono::Utc>>,
+    pub checksum: Option<String>,
+
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl MovieFile {
+    /// Create a new movie file record
+    pub fn new(
+        movie_id: Uuid,
+        relative_path: String,
+        size_bytes: i64,
+        quality: serde_json::Value,
+    ) -> Self {
+        let now = chrono::Utc::now();
+
+        Self {
+            id: Uuid::new_v4(),
+            movie_id,
+            relative_path,
+            size_bytes,
+            quality,
+            media_info: None,
+            custom_format_score: 0,
+            edition: None,
+            date_added: now,
+            last_write_time: None,
+            checksum: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Read a string field from the quality payload
+    pub fn quality_field(&self, key: &str) -> Option<&str> {
+        self.quality.get(key).and_then(|value| value.as_str())
+    }
+
+    /// File name without the directory part
+    pub fn file_name(&self) -> &str {
+        self.relative_path
+            .rsplit(['/', '\\'])
+            .next()
+            .unwrap_or(&self.relative_path)
+    }
+
+    /// Radarr quality ID of the file, from a stored `quality_id` or detected
+    /// from its quality fields and file name
+    pub fn quality_id(&self) -> i32 {
+        if let Some(id) = self.quality.get("quality_id").and_then(|id| id.as_i64()) {
+            return id as i32;
+        }
+        let text = ["quality_name", "resolution", "source"]
+            .iter()
+            .filter_map(|key| self.quality_field(key))
+            .chain(std::iter::once(self.file_name()))
+            .collect::<Vec<_>>()
+            .join(" ");
+        detect_quality_id(&text)
+    }
+
+    /// The current file of each movie and edition: the newest one
+    ///
+    /// Older files of an edition were replaced by upgrades. Files keep the
+    /// order they came in.
+    pub fn current_per_edition(files: Vec<MovieFile>) -> Vec<MovieFile> {
+        let mut newest: HashMap<(Uuid, Option<String>), usize> = HashMap::new();
+        for (index, file) in files.iter().enumerate() {
+            let key = (
+                file.movie_id,
+                file.edition.as_deref().map(str::to_lowercase),
+            );
+            match newest.entry(key) {
+                Entry::Occupied(mut entry) => {
+                    let current = &files[*entry.get()];
+                    if (file.date_added, file.id) > (current.date_added, current.id) {
+                        entry.insert(index);
+                    }
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(index);
+                }
+            }
+        }
+        let current: HashSet<usize> = newest.into_values().collect();
+        files
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, file)| current.contains(&index).then_some(file))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_media_paths() {
+        let root = Path::new("/movies");
+        assert_eq!(
+            media_relative_path(root, Path::new("/movies/4k/Heat (1995)/Heat.mkv")).as_deref(),
+            Some("4k/Heat (1995)/Heat.mkv")
+        );
+        assert_eq!(
+            media_relative_path(root, Path::new("/other/Heat.mkv")),
+            None
+        );
+        assert_eq!(media_relative_path(root, root), None);
+
+        assert_eq!(
+            resolve_media_path(root, "4k/Heat (1995)/Heat.mkv"),
+            Some(PathBuf::from("/movies/4k/Heat (1995)/Heat.mkv"))
+        );
+        assert_eq!(resolve_media_path(root, "/etc/passwd"), None);
+        assert_eq!(resolve_media_path(root, "../etc/passwd"), None);
+    }
+
+    #[test]
+    fn test_current_per_edition() {
+        let movie_id = Uuid::new_v4();
+        let file = |name: &str, edition: Option<&str>, age_days: i64| {
+            let mut file = MovieFile::new(movie_id, name.to_string(), 1, serde_json::json!({}));
+            file.edition = edition.map(str::to_string);
+            file.date_added = chrono::Utc::now() - chrono::Duration::days(age_days);
+            file
+        };
+
+        let current = MovieFile::current_per_edition(vec![
+            file("theatrical-720p.mkv", None, 30),
+            file("theatrical-1080p.mkv", None, 10),
+            file("directors-cut.mkv", Some("Director's Cut"), 20),
+        ]);
+
+        let names: Vec<_> = current.iter().map(|file| file.file_name()).collect();
+        assert_eq!(names, vec!["theatrical-1080p.mkv", "directors-cut.mkv"]);
+    }
+}
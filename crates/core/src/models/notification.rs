@@ -0,0 +1,199 @@
+//! Notification definition domain model
+//!
+//! A notification definition is a user-configured provider (a Discord
+//! webhook, an SMTP account, a Telegram bot) together with the events it should fire on.
+//! Provider settings are kept as JSON and interpreted by the notifications
+//! crate, so adding a provider does not need a schema change.
+
+use super::tags_match;
+use serde::{Deserialize, Serialize};
+
+/// Provider a notification definition sends through
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum NotificationImplementation {
+    Discord,
+    Email,
+    Telegram,
+    Pushover,
+    Gotify,
+    Webhook,
+}
+
+impl NotificationImplementation {
+    /// All implementations
+    pub const ALL: [NotificationImplementation; 6] = [
+        NotificationImplementation::Discord,
+        NotificationImplementation::Email,
+        NotificationImplementation::Telegram,
+        NotificationImplementation::Pushover,
+        NotificationImplementation::Gotify,
+        NotificationImplementation::Webhook,
+    ];
+
+    /// Name used in the database and the API
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationImplementation::Discord => "discord",
+            NotificationImplementation::Email => "email",
+            NotificationImplementation::Telegram => "telegram",
+            NotificationImplementation::Pushover => "pushover",
+            NotificationImplementation::Gotify => "gotify",
+            NotificationImplementation::Webhook => "webhook",
+        }
+    }
+
+    /// Parse a stored name, ignoring case
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|implementation| implementation.as_str().eq_ignore_ascii_case(value))
+    }
+}
+
+impl std::fmt::Display for NotificationImplementation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Events a notification definition can be toggled on for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum NotificationTrigger {
+    /// A release was sent to the download client
+    Grab,
+    /// A download was imported into the library
+    Import,
+    /// An existing file was replaced by a better one
+    Upgrade,
+    /// A health check reported a problem
+    HealthIssue,
+    /// A newer release is available
+    ApplicationUpdate,
+}
+
+/// A configured notification provider
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationDefinition {
+    pub id: i32,
+    pub name: String,
+    pub implementation: NotificationImplementation,
+    /// Provider-specific settings (webhook URL, SMTP server, ...)
+    pub settings: serde_json::Value,
+
+    pub on_grab: bool,
+    pub on_import: bool,
+    pub on_upgrade: bool,
+    pub on_health_issue: bool,
+    #[serde(default)]
+    pub on_application_update: bool,
+
+    /// Only fires for movies with one of these tags; empty for every movie
+    #[serde(default)]
+    pub tags: Vec<i32>,
+
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl NotificationDefinition {
+    /// Create a new definition with every trigger turned off
+    pub fn new(
+        name: String,
+        implementation: NotificationImplementation,
+        settings: serde_json::Value,
+    ) -> Self {
+        let now = chrono::Utc::now();
+
+        Self {
+            id: 0, // Will be set by database
+            name,
+            implementation,
+            settings,
+            on_grab: false,
+            on_import: false,
+            on_upgrade: false,
+            on_health_issue: false,
+            on_application_update: false,
+            tags: Vec::new(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Whether this definition fires on `trigger`
+    pub fn triggers_on(&self, trigger: NotificationTrigger) -> bool {
+        match trigger {
+            NotificationTrigger::Grab => self.on_grab,
+            NotificationTrigger::Import => self.on_import,
+            NotificationTrigger::Upgrade => self.on_upgrade,
+            NotificationTrigger::HealthIssue => self.on_health_issue,
+            NotificationTrigger::ApplicationUpdate => self.on_application_update,
+        }
+    }
+
+    /// Whether this definition fires for a movie tagged `movie_tags`
+    pub fn applies_to(&self, movie_tags: &[i32]) -> bool {
+        tags_match(&self.tags, movie_tags)
+    }
+
+    /// Whether any trigger is turned on
+    pub fn is_active(&self) -> bool {
+        self.on_grab
+            || self.on_import
+            || self.on_upgrade
+            || self.on_health_issue
+            || self.on_application_update
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_implementation_round_trip() {
+        for implementation in NotificationImplementation::ALL {
+            assert_eq!(
+                NotificationImplementation::parse(implementation.as_str()),
+                Some(implementation)
+            );
+        }
+        assert_eq!(
+            NotificationImplementation::parse("Discord"),
+            Some(NotificationImplementation::Discord)
+        );
+        assert!(NotificationImplementation::parse("slack").is_none());
+    }
+
+    #[test]
+    fn test_triggers_on() {
+        let mut definition = NotificationDefinition::new(
+            "Discord".to_string(),
+            NotificationImplementation::Discord,
+            serde_json::json!({}),
+        );
+        assert!(!definition.is_active());
+
+        definition.on_import = true;
+        assert!(definition.triggers_on(NotificationTrigger::Import));
+        assert!(!definition.triggers_on(NotificationTrigger::Grab));
+        assert!(definition.is_active());
+    }
+
+    #[test]
+    fn test_applies_to_tagged_movies() {
+        let mut definition = NotificationDefinition::new(
+            "Kids".to_string(),
+            NotificationImplementation::Discord,
+            serde_json::json!({}),
+        );
+        assert!(definition.applies_to(&[]));
+
+        definition.tags = vec![3];
+        assert!(definition.applies_to(&[1, 3]));
+        assert!(!definition.applies_to(&[1]));
+        assert!(!definition.applies_to(&[]));
+    }
+}
@@ -2,6 +2,79 @@
 
 use serde::{Deserialize, Serialize};
 
+/// Radarr quality definition: (id, name, source, resolution)
+pub type QualityDefinition = (i32, &'static str, &'static str, i32);
+
+/// Built-in quality definitions using Radarr's quality IDs, ordered from
+/// lowest to highest quality
+pub const QUALITY_DEFINITIONS: &[QualityDefinition] = &[
+    (0, "Unknown", "unknown", 0),
+    (1, "SDTV", "tv", 480),
+    (2, "DVD", "dvd", 480),
+    (8, "WEBDL-480p", "webdl", 480),
+    (12, "WEBRip-480p", "webrip", 480),
+    (20, "Bluray-480p", "bluray", 480),
+    (21, "Bluray-576p", "bluray", 576),
+    (4, "HDTV-720p", "tv", 720),
+    (5, "WEBDL-720p", "webdl", 720),
+    (14, "WEBRip-720p", "webrip", 720),
+    (6, "Bluray-720p", "bluray", 720),
+    (9, "HDTV-1080p", "tv", 1080),
+    (3, "WEBDL-1080p", "webdl", 1080),
+    (15, "WEBRip-1080p", "webrip", 1080),
+    (7, "Bluray-1080p", "bluray", 1080),
+    (30, "Remux-1080p", "bluray", 1080),
+    (16, "HDTV-2160p", "tv", 2160),
+    (17, "WEBRip-2160p", "webrip", 2160),
+    (18, "WEBDL-2160p", "webdl", 2160),
+    (19, "Bluray-2160p", "bluray", 2160),
+    (31, "Remux-2160p", "bluray", 2160),
+];
+
+/// Radarr quality ID of a release or file name, from its resolution and
+/// source; 0 (Unknown) when neither can be recognised
+pub fn detect_quality_id(title: &str) -> i32 {
+    let title = title.to_lowercase();
+    let has = |tokens: &[&str]| tokens.iter().any(|token| title.contains(token));
+
+    let resolution = if has(&["2160p", "4k", "uhd"]) {
+        Some(2160)
+    } else if has(&["1080p", "1080i"]) {
+        Some(1080)
+    } else if has(&["720p"]) {
+        Some(720)
+    } else if has(&["576p"]) {
+        Some(576)
+    } else if has(&["480p"]) {
+        Some(480)
+    } else {
+        None
+    };
+
+    let name = match resolution {
+        _ if has(&["remux"]) => resolution
+            .filter(|r| *r >= 1080)
+            .map(|r| format!("Remux-{}p", r)),
+        Some(r) if has(&["bluray", "blu-ray", "bdrip", "brrip"]) => {
+            Some(format!("Bluray-{}p", r.max(480)))
+        }
+        Some(r) if has(&["webrip", "web-rip"]) => Some(format!("WEBRip-{}p", r)),
+        Some(r) if has(&["web-dl", "webdl", "web"]) => Some(format!("WEBDL-{}p", r)),
+        Some(r) if r >= 720 && has(&["hdtv"]) => Some(format!("HDTV-{}p", r)),
+        _ if has(&["dvd"]) => Some("DVD".to_string()),
+        _ if has(&["sdtv", "hdtv", "tvrip", "pdtv"]) => Some("SDTV".to_string()),
+        _ => None,
+    };
+
+    name.and_then(|name| {
+        QUALITY_DEFINITIONS
+            .iter()
+            .find(|(_, def_name, ..)| *def_name == name)
+            .map(|(id, ..)| *id)
+    })
+    .unwrap_or(0)
+}
+
 /// Quality profile for movie requirements
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QualityProfile {
@@ -18,6 +91,12 @@ pub struct QualityProfile {
     // Language preferences
     pub language: String,
 
+    // Custom format score thresholds
+    #[serde(default)]
+    pub min_format_score: i32,
+    #[serde(default)]
+    pub cutoff_format_score: i32,
+
     // Timestamps
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
@@ -35,6 +114,8 @@ impl QualityProfile {
             upgrade_allowed: true,
             items: serde_json::json!([]),
             language: "english".to_string(),
+            min_format_score: 0,
+            cutoff_format_score: 0,
             created_at: now,
             updated_at: now,
         }
@@ -51,4 +132,99 @@ impl QualityProfile {
         self.language = language;
         self.updated_at = chrono::Utc::now();
     }
+
+    /// Position of `quality_id` in the profile items, lowest first; a quality
+    /// inside a group ranks as the group
+    pub fn quality_rank(&self, quality_id: i32) -> Option<usize> {
+        self.item_list().iter().position(|item| {
+            item_quality_id(item) == Some(quality_id)
+                || item["items"].as_array().is_some_and(|children| {
+                    children
+                        .iter()
+                        .any(|child| item_quality_id(child) == Some(quality_id))
+                })
+        })
+    }
+
+    /// Position of the cutoff in the profile items, which may be a quality
+    /// or a group
+    pub fn cutoff_rank(&self) -> Option<usize> {
+        self.item_list().iter().position(|item| {
+            let id = item_quality_id(item).or_else(|| item["id"].as_i64().map(|id| id as i32));
+            id == Some(self.cutoff_quality_id)
+        })
+    }
+
+    /// Whether a file of `quality_id` scoring `format_score` needs no upgrade.
+    /// Profiles without upgrades or a resolvable cutoff are always met; a
+    /// quality missing from the profile never is.
+    pub fn cutoff_met(&self, quality_id: i32, format_score: i32) -> bool {
+        if !self.upgrade_allowed {
+            return true;
+        }
+        let Some(cutoff) = self.cutoff_rank() else {
+            return true;
+        };
+        match self.quality_rank(quality_id) {
+            Some(rank) => rank >= cutoff && format_score >= self.cutoff_format_score,
+            None => false,
+        }
+    }
+
+    fn item_list(&self) -> &[serde_json::Value] {
+        self.items.as_array().map(Vec::as_slice).unwrap_or_default()
+    }
+}
+
+/// Quality ID of a single profile item, `None` for groups
+fn item_quality_id(item: &serde_json::Value) -> Option<i32> {
+    item["quality"]["id"].as_i64().map(|id| id as i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile() -> QualityProfile {
+        let mut profile = QualityProfile::new("HD".to_string(), 1001);
+        profile.update_items(serde_json::json!([
+            {"quality": {"id": 4}, "items": [], "allowed": true},
+            {"id": 1001, "name": "WEB 1080p", "quality": null, "allowed": true, "items": [
+                {"quality": {"id": 3}, "items": [], "allowed": true},
+                {"quality": {"id": 15}, "items": [], "allowed": true}
+            ]},
+            {"quality": {"id": 7}, "items": [], "allowed": true}
+        ]));
+        profile
+    }
+
+    #[test]
+    fn test_cutoff_met_by_rank_and_score() {
+        let mut profile = profile();
+        assert_eq!(profile.cutoff_rank(), Some(1));
+        assert!(!profile.cutoff_met(4, 0));
+        assert!(profile.cutoff_met(15, 0));
+        assert!(profile.cutoff_met(7, 0));
+        // Not in the profile at all
+        assert!(!profile.cutoff_met(31, 0));
+
+        profile.cutoff_format_score = 10;
+        assert!(!profile.cutoff_met(7, 5));
+        assert!(profile.cutoff_met(7, 10));
+
+        profile.upgrade_allowed = false;
+        assert!(profile.cutoff_met(4, 0));
+    }
+
+    #[test]
+    fn test_detect_quality_id() {
+        assert_eq!(detect_quality_id("Movie.2020.1080p.BluRay.x264-GRP"), 7);
+        assert_eq!(
+            detect_quality_id("Movie.2020.2160p.UHD.BluRay.REMUX-GRP"),
+            31
+        );
+        assert_eq!(detect_quality_id("Movie.2020.720p.WEB-DL-GRP"), 5);
+        assert_eq!(detect_quality_id("Movie.2020.DVDRip"), 2);
+        assert_eq!(detect_quality_id("Movie"), 0);
+    }
 }
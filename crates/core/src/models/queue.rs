@@ -9,6 +9,8 @@ pub enum QueueStatus {
     /// Item is in queue waiting to be processed
     #[default]
     Queued,
+    /// Held back until its root folder has enough free space again
+    Pending,
     /// Item is currently being downloaded
     Downloading,
     /// Download completed successfully
@@ -35,12 +37,44 @@ pub enum QueuePriority {
     VeryHigh,
 }
 
+/// Where a download is on its way into the library, as reported by the
+/// queue API
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TrackedDownloadState {
+    /// Queued, paused or downloading
+    Downloading,
+    /// Downloaded and waiting to be imported
+    ImportPending,
+    /// Imported and still seeding
+    Imported,
+    /// Failed, to be retried
+    FailedPending,
+    /// Failed for good
+    Failed,
+    /// Cancelled by the user
+    Ignored,
+}
+
+/// Whether a download needs attention
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TrackedDownloadStatus {
+    Ok,
+    /// Held back or stalled
+    Warning,
+    Error,
+}
+
 /// A download queue item
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QueueItem {
     pub id: Uuid,
     pub movie_id: Uuid,
     pub release_id: Uuid,
+    /// Indexer the release was grabbed from, for its seeding goals
+    #[serde(default)]
+    pub indexer_id: Option<i32>,
 
     // Download information
     pub title: String,
@@ -57,6 +91,11 @@ pub struct QueueItem {
     pub download_client_id: Option<String>, // ID from download client (e.g., torrent hash)
     pub download_path: Option<String>,
     pub category: Option<String>,
+    /// Root folder the movie will be imported into
+    pub root_folder_path: Option<String>,
+    /// Staged early-import preview of a sequential download
+    #[serde(default)]
+    pub preview_path: Option<String>,
 
     // Progress tracking
     pub downloaded_bytes: Option<i64>,
@@ -88,6 +127,7 @@ impl QueueItem {
             id: Uuid::new_v4(),
             movie_id,
             release_id,
+            indexer_id: None,
             title,
             download_url,
             magnet_url: None,
@@ -98,6 +138,8 @@ impl QueueItem {
             download_client_id: None,
             download_path: None,
             category: None,
+            root_folder_path: None,
+            preview_path: None,
             downloaded_bytes: None,
             upload_bytes: None,
             download_speed: None,
@@ -266,7 +308,10 @@ impl QueueItem {
     pub fn is_active(&self) -> bool {
         matches!(
             self.status,
-            QueueStatus::Queued | QueueStatus::Downloading | QueueStatus::Seeding
+            QueueStatus::Queued
+                | QueueStatus::Pending
+                | QueueStatus::Downloading
+                | QueueStatus::Seeding
         )
     }
 
@@ -279,6 +324,40 @@ impl QueueItem {
     pub fn is_failed(&self) -> bool {
         self.status == QueueStatus::Failed
     }
+
+    /// Where the download is on its way into the library
+    pub fn tracked_download_state(&self) -> TrackedDownloadState {
+        match self.status {
+            QueueStatus::Queued
+            | QueueStatus::Pending
+            | QueueStatus::Downloading
+            | QueueStatus::Paused
+            | QueueStatus::Stalled => TrackedDownloadState::Downloading,
+            QueueStatus::Completed => TrackedDownloadState::ImportPending,
+            QueueStatus::Seeding => TrackedDownloadState::Imported,
+            QueueStatus::Failed if self.can_retry() => TrackedDownloadState::FailedPending,
+            QueueStatus::Failed => TrackedDownloadState::Failed,
+            QueueStatus::Cancelled => TrackedDownloadState::Ignored,
+        }
+    }
+
+    /// Whether the download needs attention
+    pub fn tracked_download_status(&self) -> TrackedDownloadStatus {
+        match self.status {
+            QueueStatus::Failed => TrackedDownloadStatus::Error,
+            QueueStatus::Pending | QueueStatus::Stalled => TrackedDownloadStatus::Warning,
+            _ => TrackedDownloadStatus::Ok,
+        }
+    }
+
+    /// Bytes still to download, when the size is known
+    pub fn size_left(&self) -> Option<i64> {
+        let size = self.size_bytes?;
+        let downloaded = self
+            .downloaded_bytes
+            .unwrap_or((size as f64 * self.progress) as i64);
+        Some((size - downloaded).max(0))
+    }
 }
 
 /// Queue statistics
@@ -311,6 +390,7 @@ impl std::fmt::Display for QueueStatus {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             QueueStatus::Queued => write!(f, "queued"),
+            QueueStatus::Pending => write!(f, "pending"),
             QueueStatus::Downloading => write!(f, "downloading"),
             QueueStatus::Completed => write!(f, "completed"),
             QueueStatus::Failed => write!(f, "failed"),
@@ -332,3 +412,41 @@ impl std::fmt::Display for QueuePriority {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tracked_download_state() {
+        let mut item = QueueItem::new(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            "Movie.2024.1080p".to_string(),
+            "magnet:?xt=urn:btih:abc".to_string(),
+        );
+        item.size_bytes = Some(1000);
+        item.progress = 0.25;
+        assert_eq!(
+            item.tracked_download_state(),
+            TrackedDownloadState::Downloading
+        );
+        assert_eq!(item.size_left(), Some(750));
+
+        item.update_status(QueueStatus::Completed);
+        assert_eq!(
+            item.tracked_download_state(),
+            TrackedDownloadState::ImportPending
+        );
+        assert_eq!(item.size_left(), Some(0));
+
+        item.set_error("Download stalled".to_string());
+        assert_eq!(
+            item.tracked_download_state(),
+            TrackedDownloadState::FailedPending
+        );
+        assert_eq!(item.tracked_download_status(), TrackedDownloadStatus::Error);
+        item.retry_count = item.max_retries;
+        assert_eq!(item.tracked_download_state(), TrackedDownloadState::Failed);
+    }
+}
@@ -0,0 +1,139 @@
+//! Movie ratings
+//!
+//! The TMDB rating comes with the movie details. IMDb, Rotten Tomatoes and
+//! Metacritic ratings are looked up by IMDb ID when movies are refreshed,
+//! if a ratings source such as OMDb is configured. IMDb and TMDB rate out of
+//! 10, Rotten Tomatoes and Metacritic out of 100.
+
+use serde::{Deserialize, Serialize};
+
+/// Service a rating comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RatingSource {
+    Imdb,
+    Tmdb,
+    Metacritic,
+    RottenTomatoes,
+}
+
+impl RatingSource {
+    pub const ALL: [RatingSource; 4] = [
+        RatingSource::Imdb,
+        RatingSource::Tmdb,
+        RatingSource::Metacritic,
+        RatingSource::RottenTomatoes,
+    ];
+
+    /// Name used in the API
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RatingSource::Imdb => "imdb",
+            RatingSource::Tmdb => "tmdb",
+            RatingSource::Metacritic => "metacritic",
+            RatingSource::RottenTomatoes => "rottenTomatoes",
+        }
+    }
+}
+
+impl std::fmt::Display for RatingSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A rating from one service
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Rating {
+    pub value: f64,
+    /// Votes the rating is made of, 0 when the service doesn't say
+    #[serde(default)]
+    pub votes: i64,
+}
+
+impl Rating {
+    pub fn new(value: f64, votes: i64) -> Self {
+        Self { value, votes }
+    }
+}
+
+/// Ratings of a movie by service
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MovieRatings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub imdb: Option<Rating>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tmdb: Option<Rating>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metacritic: Option<Rating>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rotten_tomatoes: Option<Rating>,
+}
+
+impl MovieRatings {
+    /// Rating from `source`, if known
+    pub fn get(&self, source: RatingSource) -> Option<&Rating> {
+        match source {
+            RatingSource::Imdb => self.imdb.as_ref(),
+            RatingSource::Tmdb => self.tmdb.as_ref(),
+            RatingSource::Metacritic => self.metacritic.as_ref(),
+            RatingSource::RottenTomatoes => self.rotten_tomatoes.as_ref(),
+        }
+    }
+
+    /// Set the rating from `source`
+    pub fn set(&mut self, source: RatingSource, rating: Option<Rating>) {
+        match source {
+            RatingSource::Imdb => self.imdb = rating,
+            RatingSource::Tmdb => self.tmdb = rating,
+            RatingSource::Metacritic => self.metacritic = rating,
+            RatingSource::RottenTomatoes => self.rotten_tomatoes = rating,
+        }
+    }
+
+    /// Fill the ratings missing here with those of `other`
+    pub fn or(mut self, other: &MovieRatings) -> Self {
+        for source in RatingSource::ALL {
+            if self.get(source).is_none() {
+                self.set(source, other.get(source).cloned());
+            }
+        }
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        RatingSource::ALL
+            .iter()
+            .all(|source| self.get(*source).is_none())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_movie_ratings_serialize_by_service() {
+        let mut ratings = MovieRatings {
+            tmdb: Some(Rating::new(8.4, 27000)),
+            ..MovieRatings::default()
+        };
+        let fetched = MovieRatings {
+            imdb: Some(Rating::new(8.8, 2300000)),
+            rotten_tomatoes: Some(Rating::new(79.0, 0)),
+            ..MovieRatings::default()
+        };
+        ratings = fetched.or(&ratings);
+
+        assert_eq!(
+            serde_json::to_value(&ratings).unwrap(),
+            serde_json::json!({
+                "imdb": { "value": 8.8, "votes": 2300000 },
+                "tmdb": { "value": 8.4, "votes": 27000 },
+                "rottenTomatoes": { "value": 79.0, "votes": 0 }
+            })
+        );
+        assert!(MovieRatings::default().is_empty());
+    }
+}
@@ -0,0 +1,125 @@
+//! Release revisions
+//!
+//! Groups fix broken releases with a `PROPER` (another group's fix) or a
+//! `REPACK` (the same group's), and `REAL` marks a release that replaces a
+//! bad proper. A higher revision of a file's quality replaces the file.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::sync::OnceLock;
+
+/// Revision of a release
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Revision {
+    /// 1 for the original release, 2 for a proper or repack, 3 for a second
+    /// (`REPACK2`) and so on
+    pub version: u8,
+    /// How often `REAL` is in the name
+    pub real: u8,
+    /// Whether the new version is a repack rather than a proper
+    pub is_repack: bool,
+}
+
+impl Default for Revision {
+    fn default() -> Self {
+        Self {
+            version: 1,
+            real: 0,
+            is_repack: false,
+        }
+    }
+}
+
+impl Revision {
+    /// Revision of a release or file name
+    ///
+    /// `REAL` only counts in upper case, as it is also an English word.
+    pub fn parse(name: &str) -> Self {
+        static FIX: OnceLock<Regex> = OnceLock::new();
+        let fix = FIX.get_or_init(|| {
+            Regex::new(r"(?i)(?:^|[^a-z0-9])(PROPER|REPACK|RERIP)([2-9])?(?:[^a-z0-9]|$)")
+                .expect("valid pattern")
+        });
+
+        let mut revision = Self::default();
+        if let Some(captures) = fix.captures(name) {
+            revision.version = captures
+                .get(2)
+                .and_then(|n| n.as_str().parse::<u8>().ok())
+                .map_or(2, |n| n + 1);
+            revision.is_repack = !captures[1].eq_ignore_ascii_case("proper");
+        }
+        revision.real = name
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|word| *word == "REAL")
+            .count()
+            .min(u8::MAX as usize) as u8;
+        revision
+    }
+
+    /// Whether this is a proper, repack or real release
+    pub fn is_fix(&self) -> bool {
+        self.version > 1 || self.real > 0
+    }
+
+    /// `Proper` or `Repack` for fixed releases, empty for originals
+    pub fn label(&self) -> &'static str {
+        match (self.version > 1, self.is_repack) {
+            (false, _) => "",
+            (true, false) => "Proper",
+            (true, true) => "Repack",
+        }
+    }
+}
+
+impl PartialOrd for Revision {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Revision {
+    /// `REAL` outranks any version, then the higher version wins
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.real, self.version, self.is_repack).cmp(&(other.real, other.version, other.is_repack))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_revision() {
+        let original = Revision::parse("Movie.2023.1080p.BluRay.x264-GRP");
+        assert_eq!(original, Revision::default());
+        assert!(!original.is_fix());
+
+        let proper = Revision::parse("Movie.2023.PROPER.1080p.BluRay.x264-OTHER");
+        assert_eq!(proper.version, 2);
+        assert_eq!(proper.label(), "Proper");
+
+        let repack = Revision::parse("Movie.2023.1080p.BluRay.REPACK2.x264-GRP");
+        assert_eq!(repack.version, 3);
+        assert_eq!(repack.label(), "Repack");
+
+        let real = Revision::parse("Movie.2023.REAL.PROPER.1080p.WEB-DL-GRP");
+        assert_eq!((real.real, real.version), (1, 2));
+        // Only upper case REAL counts
+        assert_eq!(Revision::parse("The.Real.Thing.2023.1080p").real, 0);
+    }
+
+    #[test]
+    fn test_revision_order() {
+        let original = Revision::default();
+        let proper = Revision::parse("Movie.2023.PROPER.1080p");
+        let repack2 = Revision::parse("Movie.2023.REPACK2.1080p");
+        let real = Revision::parse("Movie.2023.REAL.1080p");
+
+        assert!(proper > original);
+        assert!(repack2 > proper);
+        assert!(real > repack2);
+    }
+}
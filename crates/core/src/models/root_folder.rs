@@ -0,0 +1,92 @@
+//! Root folder domain model
+//!
+//! Root folders are the library directories movies are imported into. Each
+//! one carries the quality profile new movies in it default to and the free
+//! space an import must leave behind.
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Free space kept on a root folder unless configured otherwise (100 MiB)
+pub const DEFAULT_MIN_FREE_SPACE_BYTES: i64 = 100 * 1024 * 1024;
+
+/// A library directory movies are imported into
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RootFolder {
+    pub id: i32,
+    /// Absolute path of the directory
+    pub path: String,
+    /// Quality profile for movies added to this folder
+    pub default_quality_profile_id: Option<i32>,
+    /// Imports that would leave less free space than this are refused
+    pub min_free_space_bytes: i64,
+
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl RootFolder {
+    /// Create a new root folder with the default free space reserve
+    pub fn new(path: String) -> Self {
+        let now = chrono::Utc::now();
+
+        Self {
+            id: 0, // Will be set by database
+            path,
+            default_quality_profile_id: None,
+            min_free_space_bytes: DEFAULT_MIN_FREE_SPACE_BYTES,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Whether `path` lies inside this root folder
+    pub fn contains(&self, path: &Path) -> bool {
+        path.starts_with(&self.path)
+    }
+
+    /// Whether `required_bytes` can be written while keeping the reserve free
+    pub fn has_room_for(&self, free_bytes: u64, required_bytes: u64) -> bool {
+        let reserve = self.min_free_space_bytes.max(0) as u64;
+        free_bytes.saturating_sub(required_bytes) >= reserve && free_bytes >= required_bytes
+    }
+
+    /// The most specific root folder containing `path`
+    pub fn find_containing<'a>(folders: &'a [RootFolder], path: &Path) -> Option<&'a RootFolder> {
+        folders
+            .iter()
+            .filter(|folder| folder.contains(path))
+            .max_by_key(|folder| folder.path.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_containing_prefers_deepest_folder() {
+        let movies = RootFolder::new("/media/movies".to_string());
+        let kids = RootFolder::new("/media/movies/kids".to_string());
+        let folders = vec![movies, kids];
+
+        let found =
+            RootFolder::find_containing(&folders, Path::new("/media/movies/kids/Up (2009)/Up.mkv"));
+        assert_eq!(found.map(|f| f.path.as_str()), Some("/media/movies/kids"));
+
+        // Component-wise: /media/movies-4k is not inside /media/movies
+        assert!(
+            RootFolder::find_containing(&folders, Path::new("/media/movies-4k/Dune.mkv")).is_none()
+        );
+    }
+
+    #[test]
+    fn test_has_room_for() {
+        let mut folder = RootFolder::new("/media/movies".to_string());
+        folder.min_free_space_bytes = 100;
+
+        assert!(folder.has_room_for(1000, 900));
+        assert!(!folder.has_room_for(1000, 901));
+        assert!(!folder.has_room_for(50, 60));
+    }
+}
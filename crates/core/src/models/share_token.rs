@@ -0,0 +1,154 @@
+//! Share token domain model
+//!
+//! A share token is a time-limited, read-only credential handed to a friend
+//! or a dashboard. It only unlocks the parts of the API named by its scopes,
+//! and only for reads. The token itself is shown once when it is created;
+//! afterwards only its hash is stored.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Part of the API a share token can read
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ShareScope {
+    /// Movie and collection listings
+    Library,
+    /// Upcoming releases and the calendar feed
+    Calendar,
+}
+
+impl ShareScope {
+    /// All scopes
+    pub const ALL: [ShareScope; 2] = [ShareScope::Library, ShareScope::Calendar];
+
+    /// Name used in the database and the API
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ShareScope::Library => "library",
+            ShareScope::Calendar => "calendar",
+        }
+    }
+
+    /// Parse a stored name, ignoring case
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|scope| scope.as_str().eq_ignore_ascii_case(value))
+    }
+
+    /// Whether a request for `path` with `method` falls inside this scope
+    ///
+    /// Only reads are ever allowed; `path` is the full request path,
+    /// including the `/api` prefix.
+    pub fn allows(&self, method: &str, path: &str) -> bool {
+        if !method.eq_ignore_ascii_case("GET") && !method.eq_ignore_ascii_case("HEAD") {
+            return false;
+        }
+        let path = path.trim_end_matches('/');
+
+        match self {
+            ShareScope::Library => {
+                ["/api/v3/movie", "/api/v3/collection"]
+                    .into_iter()
+                    .any(|base| match path.strip_prefix(base) {
+                        Some("") => true,
+                        // Single items only; `/movie/lookup` searches TMDB
+                        Some(rest) => rest
+                            .strip_prefix('/')
+                            .is_some_and(|id| !id.contains('/') && id != "lookup"),
+                        None => false,
+                    })
+            }
+            ShareScope::Calendar => path == "/api/v3/calendar" || path == "/api/rss/calendar",
+        }
+    }
+}
+
+impl std::fmt::Display for ShareScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A read-only API token with an expiry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShareToken {
+    pub id: Uuid,
+    pub name: String,
+    /// Hex SHA-256 digest of the token
+    pub token_hash: String,
+    pub scopes: Vec<ShareScope>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+    pub revoked_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl ShareToken {
+    /// Create a token record for the hash of a freshly generated token
+    pub fn new(
+        name: String,
+        token_hash: String,
+        scopes: Vec<ShareScope>,
+        expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name,
+            token_hash,
+            scopes,
+            created_at: chrono::Utc::now(),
+            expires_at,
+            revoked_at: None,
+        }
+    }
+
+    /// Whether the token is neither revoked nor expired at `now`
+    pub fn is_active(&self, now: chrono::DateTime<chrono::Utc>) -> bool {
+        self.revoked_at.is_none() && now < self.expires_at
+    }
+
+    /// Whether any of the token's scopes allows the request
+    pub fn allows(&self, method: &str, path: &str) -> bool {
+        self.scopes.iter().any(|scope| scope.allows(method, path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scopes_only_allow_reads_of_their_paths() {
+        let library = ShareScope::Library;
+        assert!(library.allows("GET", "/api/v3/movie"));
+        assert!(library.allows("GET", "/api/v3/movie/9b2c4e1a"));
+        assert!(library.allows("GET", "/api/v3/collection/"));
+        assert!(!library.allows("POST", "/api/v3/movie"));
+        assert!(!library.allows("DELETE", "/api/v3/movie/9b2c4e1a"));
+        assert!(!library.allows("GET", "/api/v3/movie/lookup"));
+        assert!(!library.allows("GET", "/api/v3/movie/9b2c4e1a/files"));
+        assert!(!library.allows("GET", "/api/v3/movies"));
+        assert!(!library.allows("GET", "/api/rss/calendar"));
+
+        assert!(ShareScope::Calendar.allows("GET", "/api/rss/calendar"));
+        assert!(!ShareScope::Calendar.allows("POST", "/api/rss/calendar"));
+        assert_eq!(ShareScope::parse("CALENDAR"), Some(ShareScope::Calendar));
+    }
+
+    #[test]
+    fn test_revoked_and_expired_tokens_are_inactive() {
+        let now = chrono::Utc::now();
+        let mut token = ShareToken::new(
+            "Dashboard".to_string(),
+            "hash".to_string(),
+            vec![ShareScope::Calendar],
+            now + chrono::Duration::hours(1),
+        );
+        assert!(token.is_active(now));
+        assert!(!token.is_active(now + chrono::Duration::hours(2)));
+
+        token.revoked_at = Some(now);
+        assert!(!token.is_active(now));
+    }
+}
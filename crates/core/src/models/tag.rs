@@ -0,0 +1,96 @@
+//! Tag domain model
+//!
+//! Tags are labels attached to movies, indexers, notifications and import
+//! lists. An indexer or notification with tags only applies to movies that
+//! share at least one of them; one without tags applies to every movie.
+//! Movies added by an import list get the list's tags.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A tag
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Tag {
+    pub id: i32,
+    /// Lowercase letters, digits and dashes
+    pub label: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl Tag {
+    /// Create a tag, normalizing the label; `None` when nothing is left of it
+    pub fn new(label: &str) -> Option<Self> {
+        Some(Self {
+            id: 0, // Will be set by database
+            label: Self::normalize_label(label)?,
+            created_at: chrono::Utc::now(),
+        })
+    }
+
+    /// Lowercase a label and turn runs of anything but letters and digits
+    /// into single dashes, so "4K HDR" becomes "4k-hdr"
+    pub fn normalize_label(label: &str) -> Option<String> {
+        let mut normalized = String::with_capacity(label.len());
+        for c in label.chars().flat_map(char::to_lowercase) {
+            if c.is_ascii_alphanumeric() {
+                normalized.push(c);
+            } else if !normalized.is_empty() && !normalized.ends_with('-') {
+                normalized.push('-');
+            }
+        }
+        let normalized = normalized.trim_end_matches('-');
+        (!normalized.is_empty()).then(|| normalized.to_string())
+    }
+}
+
+/// Whether something restricted to `scope` applies to an item tagged `tags`
+///
+/// An empty scope applies to everything.
+pub fn tags_match(scope: &[i32], tags: &[i32]) -> bool {
+    scope.is_empty() || scope.iter().any(|tag| tags.contains(tag))
+}
+
+/// What a tag is attached to
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TagUsage {
+    pub movie_ids: Vec<Uuid>,
+    pub indexer_ids: Vec<i32>,
+    pub notification_ids: Vec<i32>,
+    pub import_list_ids: Vec<Uuid>,
+}
+
+impl TagUsage {
+    /// Whether the tag is attached to anything
+    pub fn in_use(&self) -> bool {
+        !self.movie_ids.is_empty()
+            || !self.indexer_ids.is_empty()
+            || !self.notification_ids.is_empty()
+            || !self.import_list_ids.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_label() {
+        assert_eq!(Tag::normalize_label("4K HDR").as_deref(), Some("4k-hdr"));
+        assert_eq!(
+            Tag::normalize_label("  Kids' Movies! ").as_deref(),
+            Some("kids-movies")
+        );
+        assert_eq!(Tag::normalize_label("anime").as_deref(), Some("anime"));
+        assert_eq!(Tag::normalize_label(" -- "), None);
+        assert!(Tag::new("").is_none());
+    }
+
+    #[test]
+    fn test_tags_match() {
+        assert!(tags_match(&[], &[]));
+        assert!(tags_match(&[], &[1]));
+        assert!(tags_match(&[1, 2], &[2, 3]));
+        assert!(!tags_match(&[1], &[]));
+        assert!(!tags_match(&[1], &[2]));
+    }
+}
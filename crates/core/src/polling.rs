@@ -0,0 +1,224 @@
+//! Interval scheduling for background polling loops
+//!
+//! Loops driven by a fixed `tokio::time::interval` all wake at the same
+//! moments after startup and keep hitting a service that is already failing.
+//! [`PollSchedule`] spreads wake-ups with jitter, backs off after consecutive
+//! failures and lets a loop poll faster while it has work in flight and slower
+//! while idle.
+
+use rand::Rng;
+use std::time::Duration;
+
+/// Configuration for a polling loop
+#[derive(Debug, Clone)]
+pub struct PollConfig {
+    /// Interval while activity is unknown (before the first cycle reports it)
+    pub interval: Duration,
+    /// Interval while there is work in flight
+    pub active_interval: Option<Duration>,
+    /// Interval while there is nothing to do
+    pub idle_interval: Option<Duration>,
+    /// Longest delay failures can back off to
+    pub max_backoff: Duration,
+    /// Multiplier applied per consecutive failure
+    pub backoff_multiplier: f64,
+    /// Random spread applied to every delay, as a fraction (0.1 = ±10%)
+    pub jitter: f64,
+}
+
+impl PollConfig {
+    /// Poll every `interval`, backing off to at most eight times that
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            active_interval: None,
+            idle_interval: None,
+            max_backoff: interval.saturating_mul(8),
+            backoff_multiplier: 2.0,
+            jitter: 0.1,
+        }
+    }
+
+    /// Poll every `interval` while there is work in flight
+    pub fn with_active_interval(mut self, interval: Duration) -> Self {
+        self.active_interval = Some(interval);
+        self
+    }
+
+    /// Poll every `interval` while there is nothing to do
+    pub fn with_idle_interval(mut self, interval: Duration) -> Self {
+        self.idle_interval = Some(interval);
+        self
+    }
+
+    /// Cap failure backoff at `max_backoff`
+    pub fn with_max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Spread delays by `jitter` (a fraction of the delay)
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter;
+        self
+    }
+}
+
+/// Whether a polling loop currently has work in flight
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Activity {
+    Unknown,
+    Active,
+    Idle,
+}
+
+/// Delay state of one polling loop
+#[derive(Debug, Clone)]
+pub struct PollSchedule {
+    config: PollConfig,
+    consecutive_failures: u32,
+    activity: Activity,
+}
+
+impl PollSchedule {
+    pub fn new(config: PollConfig) -> Self {
+        Self {
+            config,
+            consecutive_failures: 0,
+            activity: Activity::Unknown,
+        }
+    }
+
+    /// Record a successful cycle, ending any backoff
+    pub fn record_success(&mut self) {
+        self.consecutive_failures = 0;
+    }
+
+    /// Record a failed cycle, lengthening the next delay
+    pub fn record_failure(&mut self) {
+        self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+    }
+
+    /// Report whether the loop has work in flight
+    pub fn set_active(&mut self, active: bool) {
+        self.activity = if active {
+            Activity::Active
+        } else {
+            Activity::Idle
+        };
+    }
+
+    /// Failed cycles since the last success
+    pub fn consecutive_failures(&self) -> u32 {
+        self.consecutive_failures
+    }
+
+    /// Delay before the next cycle, without jitter
+    pub fn base_delay(&self) -> Duration {
+        let interval = match self.activity {
+            Activity::Active => self.config.active_interval,
+            Activity::Idle => self.config.idle_interval,
+            Activity::Unknown => None,
+        }
+        .unwrap_or(self.config.interval);
+
+        if self.consecutive_failures == 0 {
+            return interval;
+        }
+
+        // Backoff never shortens the interval, even with a small cap
+        let cap = self.config.max_backoff.max(interval).as_secs_f64();
+        let factor = self
+            .config
+            .backoff_multiplier
+            .max(1.0)
+            .powi(self.consecutive_failures.min(64) as i32);
+        Duration::from_secs_f64((interval.as_secs_f64() * factor).min(cap))
+    }
+
+    /// Delay before the next cycle, with jitter applied
+    pub fn next_delay(&self) -> Duration {
+        apply_jitter(self.base_delay(), self.config.jitter)
+    }
+
+    /// Sleep until the next cycle is due
+    pub async fn wait(&self) {
+        tokio::time::sleep(self.next_delay()).await;
+    }
+}
+
+/// Spread `delay` uniformly by ±`jitter` of its length
+pub fn apply_jitter(delay: Duration, jitter: f64) -> Duration {
+    let jitter = jitter.clamp(0.0, 1.0);
+    if jitter == 0.0 || delay.is_zero() {
+        return delay;
+    }
+
+    let factor = rand::thread_rng().gen_range(1.0 - jitter..=1.0 + jitter);
+    delay.mul_f64(factor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schedule() -> PollSchedule {
+        PollSchedule::new(
+            PollConfig::new(Duration::from_secs(60))
+                .with_active_interval(Duration::from_secs(10))
+                .with_idle_interval(Duration::from_secs(300))
+                .with_max_backoff(Duration::from_secs(600)),
+        )
+    }
+
+    #[test]
+    fn test_activity_changes_interval() {
+        let mut schedule = schedule();
+        assert_eq!(schedule.base_delay(), Duration::from_secs(60));
+
+        schedule.set_active(true);
+        assert_eq!(schedule.base_delay(), Duration::from_secs(10));
+
+        schedule.set_active(false);
+        assert_eq!(schedule.base_delay(), Duration::from_secs(300));
+    }
+
+    #[test]
+    fn test_failures_back_off_up_to_cap() {
+        let mut schedule = schedule();
+
+        schedule.record_failure();
+        assert_eq!(schedule.base_delay(), Duration::from_secs(120));
+        schedule.record_failure();
+        assert_eq!(schedule.base_delay(), Duration::from_secs(240));
+
+        for _ in 0..100 {
+            schedule.record_failure();
+        }
+        assert_eq!(schedule.base_delay(), Duration::from_secs(600));
+
+        schedule.record_success();
+        assert_eq!(schedule.base_delay(), Duration::from_secs(60));
+
+        // A cap below the interval does not shorten it
+        let mut schedule = PollSchedule::new(
+            PollConfig::new(Duration::from_secs(300)).with_max_backoff(Duration::from_secs(120)),
+        );
+        schedule.record_failure();
+        assert_eq!(schedule.base_delay(), Duration::from_secs(300));
+    }
+
+    #[test]
+    fn test_jitter_stays_within_spread() {
+        let schedule = schedule();
+        for _ in 0..100 {
+            let delay = schedule.next_delay();
+            assert!(delay >= Duration::from_secs(54) && delay <= Duration::from_secs(66));
+        }
+
+        assert_eq!(
+            apply_jitter(Duration::from_secs(60), 0.0),
+            Duration::from_secs(60)
+        );
+    }
+}
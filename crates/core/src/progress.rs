@@ -19,6 +19,9 @@ pub enum OperationType {
     QueueProcessing,
     Backup,
     Update,
+    Transcode,
+    Extract,
+    MoveMovie,
 }
 
 /// Status of a tracked operation
@@ -0,0 +1,754 @@
+//! Configuration drift detection against a declarative spec
+//!
+//! A `desired-state.yml` file declares the quality profiles, indexers,
+//! import lists and naming templates the instance should have. The
+//! reconciler compares the spec with the live configuration on a schedule
+//! and reports every difference as drift, which the health checks surface.
+//! With auto-apply on, missing entries are created and changed ones are
+//! updated to match the spec, so the settings can be managed from git.
+//!
+//! Fields left out of the spec are not managed; entries that exist only in
+//! the database are left alone. Naming comes from the application config
+//! rather than the database, so naming drift is only reported.
+
+use crate::domain::repositories::{
+    ImportListRepository, IndexerRepository, QualityProfileRepository,
+};
+use crate::models::{ImportList, Indexer, IndexerImplementation, QualityProfile};
+use crate::{RadarrError, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use tracing::{info, warn};
+
+/// Where the spec lives and what happens on drift
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConfigDriftConfig {
+    /// Path of the desired state file; drift detection is off when unset
+    pub spec_path: Option<String>,
+    /// Change the live configuration to match the spec
+    pub auto_apply: bool,
+    /// Minutes between checks
+    pub check_interval_minutes: u64,
+}
+
+impl Default for ConfigDriftConfig {
+    fn default() -> Self {
+        Self {
+            spec_path: None,
+            auto_apply: false,
+            check_interval_minutes: 60,
+        }
+    }
+}
+
+/// Contents of `desired-state.yml`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DesiredState {
+    pub quality_profiles: Vec<DesiredQualityProfile>,
+    pub indexers: Vec<DesiredIndexer>,
+    pub import_lists: Vec<DesiredImportList>,
+    pub naming: Option<DesiredNaming>,
+}
+
+impl DesiredState {
+    /// Parse a spec from YAML
+    pub fn from_yaml(yaml: &str) -> Result<Self> {
+        serde_yaml::from_str(yaml).map_err(|e| RadarrError::ConfigurationError {
+            field: "desired_state".to_string(),
+            message: format!("Invalid desired state spec: {}", e),
+        })
+    }
+
+    /// Read and parse the spec at `path`
+    pub async fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let yaml = tokio::fs::read_to_string(path).await.map_err(|e| {
+            RadarrError::IoError(format!(
+                "Failed to read desired state spec {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        Self::from_yaml(&yaml)
+    }
+}
+
+/// A quality profile as declared in the spec
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DesiredQualityProfile {
+    pub name: String,
+    pub cutoff_quality_id: i32,
+    pub upgrade_allowed: Option<bool>,
+    pub items: Option<serde_json::Value>,
+    pub language: Option<String>,
+    pub min_format_score: Option<i32>,
+    pub cutoff_format_score: Option<i32>,
+}
+
+impl DesiredQualityProfile {
+    fn apply_to(&self, profile: &mut QualityProfile) {
+        profile.cutoff_quality_id = self.cutoff_quality_id;
+        set(&mut profile.upgrade_allowed, &self.upgrade_allowed);
+        set(&mut profile.items, &self.items);
+        set(&mut profile.language, &self.language);
+        set(&mut profile.min_format_score, &self.min_format_score);
+        set(&mut profile.cutoff_format_score, &self.cutoff_format_score);
+    }
+
+    fn to_profile(&self) -> QualityProfile {
+        let mut profile = QualityProfile::new(self.name.clone(), self.cutoff_quality_id);
+        self.apply_to(&mut profile);
+        profile
+    }
+}
+
+/// An indexer as declared in the spec
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DesiredIndexer {
+    pub name: String,
+    pub implementation: IndexerImplementation,
+    pub settings: Option<serde_json::Value>,
+    pub enabled: Option<bool>,
+    pub priority: Option<i32>,
+    pub enable_rss: Option<bool>,
+    pub enable_automatic_search: Option<bool>,
+    pub enable_interactive_search: Option<bool>,
+    /// Tag IDs the indexer is restricted to
+    pub tags: Option<Vec<i32>>,
+}
+
+impl DesiredIndexer {
+    fn apply_to(&self, indexer: &mut Indexer) {
+        indexer.implementation = self.implementation.clone();
+        set(&mut indexer.settings, &self.settings);
+        set(&mut indexer.enabled, &self.enabled);
+        set(&mut indexer.priority, &self.priority);
+        set(&mut indexer.enable_rss, &self.enable_rss);
+        set(
+            &mut indexer.enable_automatic_search,
+            &self.enable_automatic_search,
+        );
+        set(
+            &mut indexer.enable_interactive_search,
+            &self.enable_interactive_search,
+        );
+        set(&mut indexer.tags, &self.tags);
+    }
+
+    fn to_indexer(&self) -> Indexer {
+        let mut indexer = Indexer::new(self.name.clone(), self.implementation.clone());
+        self.apply_to(&mut indexer);
+        indexer
+    }
+}
+
+/// An import list as declared in the spec
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DesiredImportList {
+    pub name: String,
+    pub source_type: String,
+    pub list_url: Option<String>,
+    pub list_id: Option<String>,
+    pub enabled: Option<bool>,
+    pub sync_enabled: Option<bool>,
+    pub sync_interval: Option<i32>,
+    pub add_monitored: Option<bool>,
+    pub minimum_availability: Option<String>,
+    pub quality_profile_id: Option<i32>,
+    pub root_folder_path: Option<String>,
+    /// Tag IDs given to movies added from the list
+    pub tags: Option<Vec<i32>>,
+}
+
+impl DesiredImportList {
+    fn apply_to(&self, list: &mut ImportList) {
+        list.source_type = self.source_type.clone();
+        if self.list_url.is_some() {
+            list.list_url = self.list_url.clone();
+        }
+        if self.list_id.is_some() {
+            list.list_id = self.list_id.clone();
+        }
+        set(&mut list.enabled, &self.enabled);
+        set(&mut list.sync_enabled, &self.sync_enabled);
+        set(&mut list.sync_interval, &self.sync_interval);
+        set(&mut list.add_monitored, &self.add_monitored);
+        set(&mut list.minimum_availability, &self.minimum_availability);
+        if self.quality_profile_id.is_some() {
+            list.quality_profile_id = self.quality_profile_id;
+        }
+        if self.root_folder_path.is_some() {
+            list.root_folder_path = self.root_folder_path.clone();
+        }
+        set(&mut list.tags, &self.tags);
+    }
+
+    fn to_import_list(&self) -> ImportList {
+        let mut list = ImportList::new(self.name.clone(), self.source_type.clone());
+        self.apply_to(&mut list);
+        list
+    }
+}
+
+/// Naming templates as declared in the spec
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DesiredNaming {
+    pub movie_template: Option<String>,
+    pub folder_template: Option<String>,
+}
+
+/// Naming templates the import pipeline runs with
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NamingSettings {
+    pub movie_template: String,
+    pub folder_template: String,
+}
+
+/// Overwrite `field` with the spec's value when the spec manages it
+fn set<T: Clone>(field: &mut T, desired: &Option<T>) {
+    if let Some(value) = desired {
+        *field = value.clone();
+    }
+}
+
+/// Top-level fields whose serialized values differ between `live` and
+/// `desired`; timestamps are not compared
+fn changed_fields<T: Serialize>(live: &T, desired: &T) -> Vec<String> {
+    let (Ok(serde_json::Value::Object(live)), Ok(serde_json::Value::Object(desired))) =
+        (serde_json::to_value(live), serde_json::to_value(desired))
+    else {
+        return Vec::new();
+    };
+    let mut fields: Vec<String> = desired
+        .iter()
+        .filter(|(key, _)| !matches!(key.as_str(), "created_at" | "updated_at"))
+        .filter(|(key, value)| live.get(*key) != Some(value))
+        .map(|(key, _)| key.clone())
+        .collect();
+    fields.sort();
+    fields
+}
+
+/// Kind of configuration an entry of the spec describes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DriftSection {
+    QualityProfile,
+    Indexer,
+    ImportList,
+    Naming,
+}
+
+impl DriftSection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DriftSection::QualityProfile => "quality profile",
+            DriftSection::Indexer => "indexer",
+            DriftSection::ImportList => "import list",
+            DriftSection::Naming => "naming",
+        }
+    }
+}
+
+/// How the live configuration differs from the spec
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DriftKind {
+    /// The entry is declared but doesn't exist
+    Missing,
+    /// The entry exists with different values
+    Changed,
+}
+
+/// One entry of the spec that the live configuration doesn't match
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigDrift {
+    pub section: DriftSection,
+    pub name: String,
+    pub kind: DriftKind,
+    /// Fields that differ, for changed entries
+    pub fields: Vec<String>,
+    /// Whether the live configuration was changed to match the spec
+    pub applied: bool,
+}
+
+impl ConfigDrift {
+    fn describe(&self) -> String {
+        match self.kind {
+            DriftKind::Missing => format!(
+                "{} '{}' is declared in the desired state but missing",
+                self.section.as_str(),
+                self.name
+            ),
+            DriftKind::Changed => format!(
+                "{} '{}' differs from the desired state ({})",
+                self.section.as_str(),
+                self.name,
+                self.fields.join(", ")
+            ),
+        }
+    }
+}
+
+/// Result of comparing the live configuration with the spec
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DriftReport {
+    pub checked_at: DateTime<Utc>,
+    pub drift: Vec<ConfigDrift>,
+}
+
+impl DriftReport {
+    /// Drift that is still there, i.e. wasn't applied
+    pub fn outstanding(&self) -> impl Iterator<Item = &ConfigDrift> {
+        self.drift.iter().filter(|drift| !drift.applied)
+    }
+
+    /// Number of entries changed to match the spec
+    pub fn applied(&self) -> usize {
+        self.drift.iter().filter(|drift| drift.applied).count()
+    }
+
+    /// Human-readable warnings for the outstanding drift
+    pub fn warnings(&self) -> Vec<String> {
+        self.outstanding().map(ConfigDrift::describe).collect()
+    }
+}
+
+/// Compares the live configuration with `desired-state.yml`, optionally
+/// applying the spec
+pub struct ConfigDriftReconciler {
+    config: ConfigDriftConfig,
+    quality_profiles: Arc<dyn QualityProfileRepository>,
+    indexers: Arc<dyn IndexerRepository>,
+    import_lists: Option<Arc<dyn ImportListRepository>>,
+    naming: Option<NamingSettings>,
+    last_report: Mutex<Option<DriftReport>>,
+}
+
+impl ConfigDriftReconciler {
+    pub fn new(
+        config: ConfigDriftConfig,
+        quality_profiles: Arc<dyn QualityProfileRepository>,
+        indexers: Arc<dyn IndexerRepository>,
+    ) -> Self {
+        Self {
+            config,
+            quality_profiles,
+            indexers,
+            import_lists: None,
+            naming: None,
+            last_report: Mutex::new(None),
+        }
+    }
+
+    /// Compare the spec's import lists with this repository
+    pub fn with_import_lists(mut self, repository: Arc<dyn ImportListRepository>) -> Self {
+        self.import_lists = Some(repository);
+        self
+    }
+
+    /// Compare the spec's naming with the templates the import runs with
+    pub fn with_naming(mut self, naming: NamingSettings) -> Self {
+        self.naming = Some(naming);
+        self
+    }
+
+    pub fn config(&self) -> &ConfigDriftConfig {
+        &self.config
+    }
+
+    /// Report of the latest check
+    pub fn last_report(&self) -> Option<DriftReport> {
+        self.last_report
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Warnings for the drift found by the latest check
+    pub fn warnings(&self) -> Vec<String> {
+        self.last_report()
+            .map(|report| report.warnings())
+            .unwrap_or_default()
+    }
+
+    /// Load the spec from the configured path and compare it with the live
+    /// configuration, applying it when `apply` is set
+    pub async fn reconcile(&self, apply: bool) -> Result<DriftReport> {
+        let path =
+            self.config
+                .spec_path
+                .as_deref()
+                .ok_or_else(|| RadarrError::ConfigurationError {
+                    field: "desired_state.spec_path".to_string(),
+                    message: "No desired state spec configured".to_string(),
+                })?;
+        let spec = DesiredState::load(path).await?;
+        self.reconcile_spec(&spec, apply).await
+    }
+
+    /// Compare `spec` with the live configuration, applying it when `apply`
+    /// is set
+    pub async fn reconcile_spec(&self, spec: &DesiredState, apply: bool) -> Result<DriftReport> {
+        let mut drift = Vec::new();
+        drift.extend(self.reconcile_quality_profiles(spec, apply).await?);
+        drift.extend(self.reconcile_indexers(spec, apply).await?);
+        drift.extend(self.reconcile_import_lists(spec, apply).await?);
+        drift.extend(self.naming_drift(spec));
+
+        let report = DriftReport {
+            checked_at: Utc::now(),
+            drift,
+        };
+        for warning in report.warnings() {
+            warn!("Configuration drift: {}", warning);
+        }
+        if report.applied() > 0 {
+            info!(
+                "Applied desired state to {} configuration entries",
+                report.applied()
+            );
+        }
+        *self.last_report.lock().unwrap_or_else(|e| e.into_inner()) = Some(report.clone());
+        Ok(report)
+    }
+
+    async fn reconcile_quality_profiles(
+        &self,
+        spec: &DesiredState,
+        apply: bool,
+    ) -> Result<Vec<ConfigDrift>> {
+        let mut drift = Vec::new();
+        for desired in &spec.quality_profiles {
+            let section = DriftSection::QualityProfile;
+            match self.quality_profiles.find_by_name(&desired.name).await? {
+                None => {
+                    if apply {
+                        self.quality_profiles.create(&desired.to_profile()).await?;
+                    }
+                    drift.push(missing(section, &desired.name, apply));
+                }
+                Some(mut live) => {
+                    let mut target = live.clone();
+                    desired.apply_to(&mut target);
+                    let fields = changed_fields(&live, &target);
+                    if fields.is_empty() {
+                        continue;
+                    }
+                    if apply {
+                        desired.apply_to(&mut live);
+                        live.updated_at = Utc::now();
+                        self.quality_profiles.update(&live).await?;
+                    }
+                    drift.push(changed(section, &desired.name, fields, apply));
+                }
+            }
+        }
+        Ok(drift)
+    }
+
+    async fn reconcile_indexers(
+        &self,
+        spec: &DesiredState,
+        apply: bool,
+    ) -> Result<Vec<ConfigDrift>> {
+        let mut drift = Vec::new();
+        for desired in &spec.indexers {
+            let section = DriftSection::Indexer;
+            match self.indexers.find_by_name(&desired.name).await? {
+                None => {
+                    if apply {
+                        self.indexers.create(&desired.to_indexer()).await?;
+                    }
+                    drift.push(missing(section, &desired.name, apply));
+                }
+                Some(mut live) => {
+                    let mut target = live.clone();
+                    desired.apply_to(&mut target);
+                    let fields = changed_fields(&live, &target);
+                    if fields.is_empty() {
+                        continue;
+                    }
+                    if apply {
+                        desired.apply_to(&mut live);
+                        live.updated_at = Utc::now();
+                        self.indexers.update(&live).await?;
+                    }
+                    drift.push(changed(section, &desired.name, fields, apply));
+                }
+            }
+        }
+        Ok(drift)
+    }
+
+    async fn reconcile_import_lists(
+        &self,
+        spec: &DesiredState,
+        apply: bool,
+    ) -> Result<Vec<ConfigDrift>> {
+        let Some(repository) = &self.import_lists else {
+            return Ok(Vec::new());
+        };
+        let mut drift = Vec::new();
+        for desired in &spec.import_lists {
+            let section = DriftSection::ImportList;
+            match repository.find_by_name(&desired.name).await? {
+                None => {
+                    if apply {
+                        repository.create(&desired.to_import_list()).await?;
+                    }
+                    drift.push(missing(section, &desired.name, apply));
+                }
+                Some(mut live) => {
+                    let mut target = live.clone();
+                    desired.apply_to(&mut target);
+                    let fields = changed_fields(&live, &target);
+                    if fields.is_empty() {
+                        continue;
+                    }
+                    if apply {
+                        desired.apply_to(&mut live);
+                        live.updated_at = Utc::now();
+                        repository.update(&live).await?;
+                    }
+                    drift.push(changed(section, &desired.name, fields, apply));
+                }
+            }
+        }
+        Ok(drift)
+    }
+
+    /// Naming drift; never applied since the templates come from the
+    /// application config
+    fn naming_drift(&self, spec: &DesiredState) -> Option<ConfigDrift> {
+        let (Some(desired), Some(live)) = (&spec.naming, &self.naming) else {
+            return None;
+        };
+        let mut target = live.clone();
+        set(&mut target.movie_template, &desired.movie_template);
+        set(&mut target.folder_template, &desired.folder_template);
+        let fields = changed_fields(live, &target);
+        (!fields.is_empty()).then(|| changed(DriftSection::Naming, "naming", fields, false))
+    }
+}
+
+fn missing(section: DriftSection, name: &str, applied: bool) -> ConfigDrift {
+    ConfigDrift {
+        section,
+        name: name.to_string(),
+        kind: DriftKind::Missing,
+        fields: Vec::new(),
+        applied,
+    }
+}
+
+fn changed(section: DriftSection, name: &str, fields: Vec<String>, applied: bool) -> ConfigDrift {
+    ConfigDrift {
+        section,
+        name: name.to_string(),
+        kind: DriftKind::Changed,
+        fields,
+        applied,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+
+    #[derive(Default)]
+    struct MockProfiles {
+        profiles: Mutex<Vec<QualityProfile>>,
+    }
+
+    #[async_trait]
+    impl QualityProfileRepository for MockProfiles {
+        async fn find_by_id(&self, id: i32) -> Result<Option<QualityProfile>> {
+            let profiles = self.profiles.lock().unwrap();
+            Ok(profiles.iter().find(|p| p.id == id).cloned())
+        }
+        async fn find_by_name(&self, name: &str) -> Result<Option<QualityProfile>> {
+            let profiles = self.profiles.lock().unwrap();
+            Ok(profiles.iter().find(|p| p.name == name).cloned())
+        }
+        async fn create(&self, profile: &QualityProfile) -> Result<QualityProfile> {
+            let mut profiles = self.profiles.lock().unwrap();
+            let mut profile = profile.clone();
+            profile.id = profiles.len() as i32 + 1;
+            profiles.push(profile.clone());
+            Ok(profile)
+        }
+        async fn update(&self, profile: &QualityProfile) -> Result<QualityProfile> {
+            let mut profiles = self.profiles.lock().unwrap();
+            if let Some(existing) = profiles.iter_mut().find(|p| p.id == profile.id) {
+                *existing = profile.clone();
+            }
+            Ok(profile.clone())
+        }
+        async fn delete(&self, _id: i32) -> Result<()> {
+            Ok(())
+        }
+        async fn list(&self) -> Result<Vec<QualityProfile>> {
+            Ok(self.profiles.lock().unwrap().clone())
+        }
+        async fn get_default(&self) -> Result<Option<QualityProfile>> {
+            Ok(None)
+        }
+        async fn count_movies_using(&self, _id: i32) -> Result<i64> {
+            Ok(0)
+        }
+    }
+
+    #[derive(Default)]
+    struct MockIndexers {
+        indexers: Mutex<Vec<Indexer>>,
+    }
+
+    #[async_trait]
+    impl IndexerRepository for MockIndexers {
+        async fn find_by_id(&self, id: i32) -> Result<Option<Indexer>> {
+            let indexers = self.indexers.lock().unwrap();
+            Ok(indexers.iter().find(|i| i.id == id).cloned())
+        }
+        async fn find_by_name(&self, name: &str) -> Result<Option<Indexer>> {
+            let indexers = self.indexers.lock().unwrap();
+            Ok(indexers.iter().find(|i| i.name == name).cloned())
+        }
+        async fn find_enabled(&self) -> Result<Vec<Indexer>> {
+            Ok(Vec::new())
+        }
+        async fn create(&self, indexer: &Indexer) -> Result<Indexer> {
+            self.indexers.lock().unwrap().push(indexer.clone());
+            Ok(indexer.clone())
+        }
+        async fn update(&self, indexer: &Indexer) -> Result<Indexer> {
+            Ok(indexer.clone())
+        }
+        async fn delete(&self, _id: i32) -> Result<()> {
+            Ok(())
+        }
+        async fn list(&self) -> Result<Vec<Indexer>> {
+            Ok(self.indexers.lock().unwrap().clone())
+        }
+        async fn test_connection(&self, _id: i32) -> Result<bool> {
+            Ok(true)
+        }
+    }
+
+    const SPEC: &str = r#"
+quality_profiles:
+  - name: HD-1080p
+    cutoff_quality_id: 7
+    upgrade_allowed: true
+  - name: Ultra-HD
+    cutoff_quality_id: 19
+indexers:
+  - name: Prowlarr
+    implementation: Prowlarr
+    priority: 10
+naming:
+  movie_template: "{title} ({year})"
+"#;
+
+    fn reconciler(
+        profiles: Arc<MockProfiles>,
+        indexers: Arc<MockIndexers>,
+    ) -> ConfigDriftReconciler {
+        ConfigDriftReconciler::new(ConfigDriftConfig::default(), profiles, indexers).with_naming(
+            NamingSettings {
+                movie_template: "{title} ({year}) [{quality}]".to_string(),
+                folder_template: "{title} ({year})".to_string(),
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_drift_is_reported_without_changes() {
+        let profiles = Arc::new(MockProfiles::default());
+        let mut hd = QualityProfile::new("HD-1080p".to_string(), 3);
+        hd.id = 1;
+        profiles.profiles.lock().unwrap().push(hd);
+        let indexers = Arc::new(MockIndexers::default());
+        let reconciler = reconciler(profiles.clone(), indexers.clone());
+
+        let spec = DesiredState::from_yaml(SPEC).unwrap();
+        let report = reconciler.reconcile_spec(&spec, false).await.unwrap();
+
+        let summary: Vec<_> = report
+            .drift
+            .iter()
+            .map(|d| (d.section, d.name.as_str(), d.kind, d.fields.clone()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    DriftSection::QualityProfile,
+                    "HD-1080p",
+                    DriftKind::Changed,
+                    vec!["cutoff_quality_id".to_string()]
+                ),
+                (
+                    DriftSection::QualityProfile,
+                    "Ultra-HD",
+                    DriftKind::Missing,
+                    vec![]
+                ),
+                (
+                    DriftSection::Indexer,
+                    "Prowlarr",
+                    DriftKind::Missing,
+                    vec![]
+                ),
+                (
+                    DriftSection::Naming,
+                    "naming",
+                    DriftKind::Changed,
+                    vec!["movie_template".to_string()]
+                ),
+            ]
+        );
+        assert_eq!(reconciler.warnings().len(), 4);
+        assert_eq!(profiles.profiles.lock().unwrap()[0].cutoff_quality_id, 3);
+        assert!(indexers.indexers.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_apply_brings_config_in_line_with_spec() {
+        let profiles = Arc::new(MockProfiles::default());
+        let mut hd = QualityProfile::new("HD-1080p".to_string(), 3);
+        hd.id = 1;
+        profiles.profiles.lock().unwrap().push(hd);
+        let indexers = Arc::new(MockIndexers::default());
+        let reconciler = reconciler(profiles.clone(), indexers.clone());
+
+        let spec = DesiredState::from_yaml(SPEC).unwrap();
+        let report = reconciler.reconcile_spec(&spec, true).await.unwrap();
+        assert_eq!(report.applied(), 3);
+        // Naming lives in the application config and can only be reported
+        assert_eq!(report.warnings().len(), 1);
+
+        let stored = profiles.profiles.lock().unwrap().clone();
+        assert_eq!(stored[0].cutoff_quality_id, 7);
+        assert_eq!(stored[1].name, "Ultra-HD");
+        assert_eq!(indexers.indexers.lock().unwrap()[0].priority, 10);
+
+        let report = reconciler.reconcile_spec(&spec, false).await.unwrap();
+        assert_eq!(report.drift.len(), 1);
+        assert_eq!(report.drift[0].section, DriftSection::Naming);
+    }
+
+    #[test]
+    fn test_invalid_spec_is_a_configuration_error() {
+        let err = DesiredState::from_yaml("quality_profiles: [{name: 1080p}]").unwrap_err();
+        assert!(matches!(err, RadarrError::ConfigurationError { .. }));
+    }
+}
@@ -0,0 +1,789 @@
+//! Multi download client management
+//!
+//! This service manages any number of configured download clients, routes
+//! releases to the right client based on protocol and indexer, and fails over
+//! to the next client by priority when a client's circuit breaker is open.
+
+use crate::circuit_breaker::{CircuitBreaker, CircuitBreakerConfig, CircuitBreakerState};
+use crate::models::ReleaseProtocol;
+use crate::services::{
+    ClientDownloadStatus, DownloadClientService, DownloadFile, DownloadMetadata, DownloadPieces,
+};
+use crate::{RadarrError, Result};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tracing::{debug, info, warn};
+
+/// A configured download client managed by the [`DownloadClientManager`]
+#[derive(Clone)]
+pub struct ManagedDownloadClient {
+    pub id: i32,
+    pub name: String,
+    pub protocol: ReleaseProtocol,
+    /// Lower values are preferred (Radarr semantics: 1 is highest priority)
+    pub priority: i32,
+    pub enabled: bool,
+    pub client: Arc<dyn DownloadClientService>,
+    pub circuit_breaker: Arc<CircuitBreaker>,
+}
+
+impl ManagedDownloadClient {
+    /// Create a managed client with a default circuit breaker
+    pub fn new(
+        id: i32,
+        name: impl Into<String>,
+        protocol: ReleaseProtocol,
+        priority: i32,
+        client: Arc<dyn DownloadClientService>,
+    ) -> Self {
+        let name = name.into();
+        let circuit_breaker = Arc::new(CircuitBreaker::new(
+            CircuitBreakerConfig::new(format!("download_client_{}", name))
+                .with_failure_threshold(3)
+                .with_timeout(Duration::from_secs(60))
+                .with_request_timeout(Duration::from_secs(30)),
+        ));
+
+        Self {
+            id,
+            name,
+            protocol,
+            priority,
+            enabled: true,
+            client,
+            circuit_breaker,
+        }
+    }
+
+    /// Replace the circuit breaker (useful for custom thresholds)
+    pub fn with_circuit_breaker(mut self, circuit_breaker: Arc<CircuitBreaker>) -> Self {
+        self.circuit_breaker = circuit_breaker;
+        self
+    }
+
+    /// Check whether this client is currently accepting requests
+    pub async fn is_available(&self) -> bool {
+        self.enabled && self.circuit_breaker.get_state().await != CircuitBreakerState::Open
+    }
+}
+
+/// Rule routing releases from an indexer to a specific download client
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DownloadRoutingRule {
+    pub indexer_id: i32,
+    pub download_client_id: i32,
+}
+
+/// Result of a routed grab
+#[derive(Debug, Clone)]
+pub struct RoutedDownload {
+    pub download_client_id: i32,
+    pub download_client_name: String,
+    pub client_download_id: String,
+}
+
+/// Manages N download clients with priorities, routing and failover
+pub struct DownloadClientManager {
+    clients: RwLock<Vec<ManagedDownloadClient>>,
+    routing_rules: RwLock<Vec<DownloadRoutingRule>>,
+    /// Tracks which client owns a client-specific download ID
+    ownership: RwLock<HashMap<String, i32>>,
+}
+
+impl DownloadClientManager {
+    /// Create an empty manager
+    pub fn new() -> Self {
+        Self {
+            clients: RwLock::new(Vec::new()),
+            routing_rules: RwLock::new(Vec::new()),
+            ownership: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Register a download client, replacing any client with the same ID
+    pub async fn add_client(&self, client: ManagedDownloadClient) {
+        info!(
+            "Registered download client '{}' ({:?}, priority {})",
+            client.name, client.protocol, client.priority
+        );
+        let mut clients = self.clients.write().await;
+        clients.retain(|c| c.id != client.id);
+        clients.push(client);
+        clients.sort_by_key(|c| (c.priority, c.id));
+    }
+
+    /// Remove a download client by ID
+    pub async fn remove_client(&self, id: i32) -> bool {
+        let mut clients = self.clients.write().await;
+        let before = clients.len();
+        clients.retain(|c| c.id != id);
+        self.routing_rules
+            .write()
+            .await
+            .retain(|r| r.download_client_id != id);
+        clients.len() != before
+    }
+
+    /// Get all registered clients ordered by priority
+    pub async fn clients(&self) -> Vec<ManagedDownloadClient> {
+        self.clients.read().await.clone()
+    }
+
+    /// Route all releases from an indexer to a specific client
+    pub async fn set_indexer_route(&self, indexer_id: i32, download_client_id: i32) {
+        let mut rules = self.routing_rules.write().await;
+        rules.retain(|r| r.indexer_id != indexer_id);
+        rules.push(DownloadRoutingRule {
+            indexer_id,
+            download_client_id,
+        });
+    }
+
+    /// Remove the routing rule for an indexer
+    pub async fn clear_indexer_route(&self, indexer_id: i32) {
+        self.routing_rules
+            .write()
+            .await
+            .retain(|r| r.indexer_id != indexer_id);
+    }
+
+    /// Resolve the ordered list of candidate clients for a release.
+    ///
+    /// A client routed from the indexer comes first, followed by every other
+    /// enabled client for the same protocol in priority order.
+    pub async fn select_clients(
+        &self,
+        protocol: &ReleaseProtocol,
+        indexer_id: Option<i32>,
+    ) -> Vec<ManagedDownloadClient> {
+        let routed_id = match indexer_id {
+            Some(indexer_id) => self
+                .routing_rules
+                .read()
+                .await
+                .iter()
+                .find(|r| r.indexer_id == indexer_id)
+                .map(|r| r.download_client_id),
+            None => None,
+        };
+
+        let clients = self.clients.read().await.clone();
+        let mut candidates: Vec<ManagedDownloadClient> = Vec::new();
+
+        if let Some(routed_id) = routed_id {
+            if let Some(routed) = clients
+                .iter()
+                .find(|c| c.id == routed_id && &c.protocol == protocol)
+            {
+                candidates.push(routed.clone());
+            }
+        }
+
+        for client in clients
+            .into_iter()
+            .filter(|c| &c.protocol == protocol && Some(c.id) != routed_id)
+        {
+            candidates.push(client);
+        }
+
+        // Clients with an open circuit go last: they are only tried (and may
+        // transition to half-open) once every healthy client has failed.
+        let mut available = Vec::new();
+        let mut tripped = Vec::new();
+        for client in candidates.into_iter().filter(|c| c.enabled) {
+            if client.is_available().await {
+                available.push(client);
+            } else {
+                debug!(
+                    "Download client '{}' circuit is open, deprioritizing",
+                    client.name
+                );
+                tripped.push(client);
+            }
+        }
+        available.extend(tripped);
+        available
+    }
+
+    /// Send a release to the best available client, failing over on errors
+    pub async fn add_download_routed(
+        &self,
+        download_url: &str,
+        protocol: ReleaseProtocol,
+        indexer_id: Option<i32>,
+        category: Option<String>,
+        save_path: Option<String>,
+    ) -> Result<RoutedDownload> {
+        let candidates = self.select_clients(&protocol, indexer_id).await;
+        if candidates.is_empty() {
+            return Err(RadarrError::ExternalServiceError {
+                service: "download_client".to_string(),
+                error: format!("No available download client for {:?} releases", protocol),
+            });
+        }
+
+        let mut last_error = None;
+        for candidate in candidates {
+            let result = candidate
+                .circuit_breaker
+                .call(candidate.client.add_download(
+                    download_url,
+                    category.clone(),
+                    save_path.clone(),
+                ))
+                .await;
+
+            match result {
+                Ok(client_download_id) => {
+                    self.ownership
+                        .write()
+                        .await
+                        .insert(client_download_id.clone(), candidate.id);
+                    info!(
+                        "Sent download to client '{}' (id {})",
+                        candidate.name, client_download_id
+                    );
+                    return Ok(RoutedDownload {
+                        download_client_id: candidate.id,
+                        download_client_name: candidate.name,
+                        client_download_id,
+                    });
+                }
+                Err(e) => {
+                    warn!(
+                        "Download client '{}' failed, trying next client: {}",
+                        candidate.name, e
+                    );
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        Err(
+            last_error.unwrap_or_else(|| RadarrError::ExternalServiceError {
+                service: "download_client".to_string(),
+                error: "All download clients failed".to_string(),
+            }),
+        )
+    }
+
+    /// Protocol of a grab: that of the client an indexer is routed to, or
+    /// else the one inferred from the download URL
+    pub async fn protocol_for(
+        &self,
+        download_url: &str,
+        indexer_id: Option<i32>,
+    ) -> ReleaseProtocol {
+        if let Some(indexer_id) = indexer_id {
+            let routed_id = self
+                .routing_rules
+                .read()
+                .await
+                .iter()
+                .find(|r| r.indexer_id == indexer_id)
+                .map(|r| r.download_client_id);
+            if let Some(routed) = self
+                .clients
+                .read()
+                .await
+                .iter()
+                .find(|c| Some(c.id) == routed_id)
+            {
+                return routed.protocol.clone();
+            }
+        }
+        Self::infer_protocol(download_url)
+    }
+
+    /// Infer the release protocol from a download URL
+    pub fn infer_protocol(download_url: &str) -> ReleaseProtocol {
+        let url = download_url.to_lowercase();
+        if url.ends_with(".nzb") || (url.contains("t=get") && url.contains("nzb")) {
+            ReleaseProtocol::Usenet
+        } else {
+            ReleaseProtocol::Torrent
+        }
+    }
+
+    /// Find the client that owns a download ID, probing clients if unknown
+    ///
+    /// The download is only reported as not found when every enabled client
+    /// answered; otherwise the first client failure is returned, since the
+    /// download may belong to the client that couldn't be asked.
+    async fn owning_client(&self, client_id: &str) -> Result<ManagedDownloadClient> {
+        let owner = self.ownership.read().await.get(client_id).copied();
+        let clients = self.clients.read().await.clone();
+
+        if let Some(owner) = owner {
+            if let Some(client) = clients.iter().find(|c| c.id == owner) {
+                return Ok(client.clone());
+            }
+        }
+
+        let mut failure = None;
+        for client in clients {
+            if !client.enabled {
+                continue;
+            }
+            if !client.is_available().await {
+                failure.get_or_insert_with(|| Self::unavailable(&client));
+                continue;
+            }
+            match client.client.get_download_status(client_id).await {
+                Ok(Some(_)) => {
+                    self.ownership
+                        .write()
+                        .await
+                        .insert(client_id.to_string(), client.id);
+                    return Ok(client);
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    warn!(
+                        "Failed to look up download '{}' in client '{}': {}",
+                        client_id, client.name, e
+                    );
+                    failure.get_or_insert(e);
+                }
+            }
+        }
+
+        Err(failure.unwrap_or_else(|| RadarrError::NotFoundError {
+            entity: "download".to_string(),
+            id: client_id.to_string(),
+        }))
+    }
+
+    /// Error for an enabled client whose circuit breaker is open
+    fn unavailable(client: &ManagedDownloadClient) -> RadarrError {
+        RadarrError::ExternalServiceError {
+            service: client.name.clone(),
+            error: "download client is unavailable (circuit breaker open)".to_string(),
+        }
+    }
+}
+
+impl Default for DownloadClientManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl DownloadClientService for DownloadClientManager {
+    async fn add_download(
+        &self,
+        download_url: &str,
+        category: Option<String>,
+        save_path: Option<String>,
+    ) -> Result<String> {
+        let protocol = Self::infer_protocol(download_url);
+        self.add_download_routed(download_url, protocol, None, category, save_path)
+            .await
+            .map(|routed| routed.client_download_id)
+    }
+
+    async fn add_download_from_indexer(
+        &self,
+        download_url: &str,
+        indexer_id: Option<i32>,
+        category: Option<String>,
+        save_path: Option<String>,
+    ) -> Result<String> {
+        let protocol = self.protocol_for(download_url, indexer_id).await;
+        self.add_download_routed(download_url, protocol, indexer_id, category, save_path)
+            .await
+            .map(|routed| routed.client_download_id)
+    }
+
+    async fn get_download_status(&self, client_id: &str) -> Result<Option<ClientDownloadStatus>> {
+        match self.owning_client(client_id).await {
+            Ok(client) => client.client.get_download_status(client_id).await,
+            Err(RadarrError::NotFoundError { .. }) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn remove_download(&self, client_id: &str, delete_files: bool) -> Result<()> {
+        let client = self.owning_client(client_id).await?;
+        client
+            .client
+            .remove_download(client_id, delete_files)
+            .await?;
+        self.ownership.write().await.remove(client_id);
+        Ok(())
+    }
+
+    async fn pause_download(&self, client_id: &str) -> Result<()> {
+        let client = self.owning_client(client_id).await?;
+        client.client.pause_download(client_id).await
+    }
+
+    async fn resume_download(&self, client_id: &str) -> Result<()> {
+        let client = self.owning_client(client_id).await?;
+        client.client.resume_download(client_id).await
+    }
+
+    /// Downloads of every enabled client
+    ///
+    /// Fails when any enabled client is unavailable or errors: callers such
+    /// as downloads cleanup treat a missing download as gone, so a partial
+    /// list would make a down client's downloads look orphaned.
+    async fn get_all_downloads(&self) -> Result<Vec<ClientDownloadStatus>> {
+        let clients = self.clients.read().await.clone();
+        let mut all_downloads = Vec::new();
+
+        for client in clients {
+            if !client.enabled {
+                continue;
+            }
+            if !client.is_available().await {
+                return Err(Self::unavailable(&client));
+            }
+            let downloads = client.client.get_all_downloads().await.map_err(|e| {
+                warn!(
+                    "Failed to list downloads from client '{}': {}",
+                    client.name, e
+                );
+                e
+            })?;
+            let mut ownership = self.ownership.write().await;
+            for download in &downloads {
+                ownership.insert(download.client_id.clone(), client.id);
+            }
+            all_downloads.extend(downloads);
+        }
+
+        Ok(all_downloads)
+    }
+
+    async fn free_space(&self, download_url: &str) -> Result<Option<u64>> {
+        // Ask the client the grab would be routed to first
+        let protocol = Self::infer_protocol(download_url);
+        match self.select_clients(&protocol, None).await.first() {
+            Some(client) => client.client.free_space(download_url).await,
+            None => Ok(None),
+        }
+    }
+
+    async fn download_pieces(&self, client_id: &str) -> Result<Option<DownloadPieces>> {
+        match self.owning_client(client_id).await {
+            Ok(client) => client.client.download_pieces(client_id).await,
+            Err(RadarrError::NotFoundError { .. }) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn download_files(&self, client_id: &str) -> Result<Option<Vec<DownloadFile>>> {
+        match self.owning_client(client_id).await {
+            Ok(client) => client.client.download_files(client_id).await,
+            Err(RadarrError::NotFoundError { .. }) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn skip_files(&self, client_id: &str, indices: &[usize]) -> Result<()> {
+        let client = self.owning_client(client_id).await?;
+        client.client.skip_files(client_id, indices).await
+    }
+
+    async fn inspect_download(&self, download_url: &str) -> Result<Option<DownloadMetadata>> {
+        // Read by the client the grab would be routed to first
+        let protocol = Self::infer_protocol(download_url);
+        match self.select_clients(&protocol, None).await.first() {
+            Some(client) => client.client.inspect_download(download_url).await,
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    struct MockClient {
+        prefix: &'static str,
+        fail: AtomicBool,
+    }
+
+    impl MockClient {
+        fn new(prefix: &'static str) -> Arc<Self> {
+            Arc::new(Self {
+                prefix,
+                fail: AtomicBool::new(false),
+            })
+        }
+
+        fn check(&self) -> Result<()> {
+            if self.fail.load(Ordering::SeqCst) {
+                return Err(RadarrError::ExternalServiceError {
+                    service: self.prefix.to_string(),
+                    error: "unavailable".to_string(),
+                });
+            }
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl DownloadClientService for MockClient {
+        async fn add_download(
+            &self,
+            _download_url: &str,
+            _category: Option<String>,
+            _save_path: Option<String>,
+        ) -> Result<String> {
+            self.check()?;
+            Ok(format!("{}-1", self.prefix))
+        }
+
+        async fn get_download_status(
+            &self,
+            _client_id: &str,
+        ) -> Result<Option<ClientDownloadStatus>> {
+            self.check()?;
+            Ok(None)
+        }
+
+        async fn remove_download(&self, _client_id: &str, _delete_files: bool) -> Result<()> {
+            Ok(())
+        }
+
+        async fn pause_download(&self, _client_id: &str) -> Result<()> {
+            Ok(())
+        }
+
+        async fn resume_download(&self, _client_id: &str) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_all_downloads(&self) -> Result<Vec<ClientDownloadStatus>> {
+            self.check()?;
+            Ok(vec![])
+        }
+    }
+
+    #[tokio::test]
+    async fn test_routes_by_protocol_and_priority() {
+        let manager = DownloadClientManager::new();
+        manager
+            .add_client(ManagedDownloadClient::new(
+                1,
+                "qbit-secondary",
+                ReleaseProtocol::Torrent,
+                2,
+                MockClient::new("qbit2"),
+            ))
+            .await;
+        manager
+            .add_client(ManagedDownloadClient::new(
+                2,
+                "qbit-primary",
+                ReleaseProtocol::Torrent,
+                1,
+                MockClient::new("qbit1"),
+            ))
+            .await;
+        manager
+            .add_client(ManagedDownloadClient::new(
+                3,
+                "sabnzbd",
+                ReleaseProtocol::Usenet,
+                1,
+                MockClient::new("sab"),
+            ))
+            .await;
+
+        let torrent = manager
+            .add_download_routed(
+                "magnet:?xt=urn:btih:abc",
+                ReleaseProtocol::Torrent,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(torrent.download_client_name, "qbit-primary");
+
+        let usenet = manager
+            .add_download_routed("http://x/a.nzb", ReleaseProtocol::Usenet, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(usenet.download_client_name, "sabnzbd");
+
+        // Indexer routing overrides priority
+        manager.set_indexer_route(7, 1).await;
+        let routed = manager
+            .add_download_routed(
+                "magnet:?xt=urn:btih:abc",
+                ReleaseProtocol::Torrent,
+                Some(7),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(routed.download_client_name, "qbit-secondary");
+    }
+
+    #[tokio::test]
+    async fn test_indexer_route_decides_protocol() {
+        let manager = DownloadClientManager::new();
+        manager
+            .add_client(ManagedDownloadClient::new(
+                1,
+                "qbittorrent",
+                ReleaseProtocol::Torrent,
+                1,
+                MockClient::new("qbit"),
+            ))
+            .await;
+        manager
+            .add_client(ManagedDownloadClient::new(
+                2,
+                "sabnzbd",
+                ReleaseProtocol::Usenet,
+                1,
+                MockClient::new("sab"),
+            ))
+            .await;
+        manager.set_indexer_route(12, 2).await;
+
+        // Prowlarr download links do not say whether they are an NZB
+        let url = "http://prowlarr:9696/12/download?link=abc";
+        let id = manager
+            .add_download_from_indexer(url, Some(12), None, None)
+            .await
+            .unwrap();
+        assert_eq!(id, "sab-1");
+
+        let id = manager
+            .add_download_from_indexer(url, Some(5), None, None)
+            .await
+            .unwrap();
+        assert_eq!(id, "qbit-1");
+    }
+
+    #[tokio::test]
+    async fn test_fails_over_when_circuit_open() {
+        let manager = DownloadClientManager::new();
+        let primary = ManagedDownloadClient::new(
+            1,
+            "primary",
+            ReleaseProtocol::Torrent,
+            1,
+            MockClient::new("primary"),
+        );
+        primary.circuit_breaker.force_open().await;
+        manager.add_client(primary).await;
+        manager
+            .add_client(ManagedDownloadClient::new(
+                2,
+                "backup",
+                ReleaseProtocol::Torrent,
+                5,
+                MockClient::new("backup"),
+            ))
+            .await;
+
+        let id = manager
+            .add_download("magnet:?xt=urn:btih:abc", None, None)
+            .await
+            .unwrap();
+        assert_eq!(id, "backup-1");
+    }
+
+    #[tokio::test]
+    async fn test_fails_over_on_client_error() {
+        let manager = DownloadClientManager::new();
+        let failing = MockClient::new("failing");
+        failing.fail.store(true, Ordering::SeqCst);
+        manager
+            .add_client(ManagedDownloadClient::new(
+                1,
+                "failing",
+                ReleaseProtocol::Torrent,
+                1,
+                failing,
+            ))
+            .await;
+        manager
+            .add_client(ManagedDownloadClient::new(
+                2,
+                "working",
+                ReleaseProtocol::Torrent,
+                2,
+                MockClient::new("working"),
+            ))
+            .await;
+
+        let id = manager
+            .add_download("magnet:?xt=urn:btih:abc", None, None)
+            .await
+            .unwrap();
+        assert_eq!(id, "working-1");
+
+        // No usenet client configured
+        assert!(manager
+            .add_download("http://indexer/get/file.nzb", None, None)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_failing_client_is_not_reported_as_empty() {
+        let manager = DownloadClientManager::new();
+        let failing = MockClient::new("failing");
+        manager
+            .add_client(ManagedDownloadClient::new(
+                1,
+                "failing",
+                ReleaseProtocol::Torrent,
+                1,
+                failing.clone(),
+            ))
+            .await;
+        manager
+            .add_client(ManagedDownloadClient::new(
+                2,
+                "working",
+                ReleaseProtocol::Torrent,
+                2,
+                MockClient::new("working"),
+            ))
+            .await;
+
+        // Every client answered and none has the download
+        assert!(manager.get_all_downloads().await.unwrap().is_empty());
+        assert!(manager.get_download_status("abc").await.unwrap().is_none());
+
+        failing.fail.store(true, Ordering::SeqCst);
+        assert!(manager.get_all_downloads().await.is_err());
+        assert!(manager.get_download_status("abc").await.is_err());
+        assert!(manager.download_files("abc").await.is_err());
+
+        // An open circuit fails the same way, a disabled client is skipped
+        failing.fail.store(false, Ordering::SeqCst);
+        let mut tripped = ManagedDownloadClient::new(
+            3,
+            "tripped",
+            ReleaseProtocol::Usenet,
+            1,
+            MockClient::new("tripped"),
+        );
+        tripped.circuit_breaker.force_open().await;
+        manager.add_client(tripped.clone()).await;
+        assert!(manager.get_all_downloads().await.is_err());
+        assert!(manager.get_download_status("abc").await.is_err());
+
+        tripped.enabled = false;
+        manager.add_client(tripped).await;
+        assert!(manager.get_all_downloads().await.is_ok());
+        assert!(manager.get_download_status("abc").await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,305 @@
+//! Failed download handling
+//!
+//! When the download client reports a download as failed, the queue
+//! processor publishes `DownloadFailed`. The handler here picks that up,
+//! blocklists the release so later searches pass it over, removes the
+//! download from the client and the queue, and queues a new search for the
+//! movie. Each step can be switched off through [`DownloadHandlingConfig`];
+//! with handling disabled, failed items stay in the queue for the queue
+//! processor's own retries.
+
+use crate::blocklist::{BlocklistRepository, BlocklistService, FailureReason};
+use crate::commands::{CommandName, CommandQueue, CommandTrigger};
+use crate::events::{EventEnvelope, EventHandler, SystemEvent};
+use crate::models::QueueItem;
+use crate::services::{DownloadClientService, QueueRepository};
+use crate::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+/// Indexer recorded on blocklist entries for failed downloads; queue items
+/// don't remember which indexer their release came from
+pub const FAILED_DOWNLOAD_INDEXER: &str = "unknown";
+
+/// What happens to a download once it has failed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DownloadHandlingConfig {
+    /// Handle failed downloads at all
+    pub enabled: bool,
+    /// Blocklist the failed release so searches skip it
+    pub blocklist_failed: bool,
+    /// Remove the failed download from the download client
+    pub remove_from_client: bool,
+    /// Delete downloaded data along with the download
+    pub delete_files: bool,
+    /// Search for another release of the movie
+    pub redownload: bool,
+}
+
+impl Default for DownloadHandlingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            blocklist_failed: true,
+            remove_from_client: true,
+            delete_files: true,
+            redownload: true,
+        }
+    }
+}
+
+/// What the handler did for one failed download
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FailedDownloadOutcome {
+    pub blocklisted: bool,
+    pub removed_from_client: bool,
+    /// ID of the queued `MoviesSearch` command
+    pub search_command_id: Option<i32>,
+}
+
+/// Blocklists, removes and replaces downloads that failed
+pub struct FailedDownloadHandler {
+    config: DownloadHandlingConfig,
+    queue_repo: Arc<dyn QueueRepository>,
+    download_client: Arc<dyn DownloadClientService>,
+    blocklist: BlocklistService<dyn BlocklistRepository>,
+    command_queue: Option<Arc<CommandQueue>>,
+}
+
+impl FailedDownloadHandler {
+    pub fn new(
+        config: DownloadHandlingConfig,
+        queue_repo: Arc<dyn QueueRepository>,
+        download_client: Arc<dyn DownloadClientService>,
+        blocklist_repo: Arc<dyn BlocklistRepository>,
+    ) -> Self {
+        Self {
+            config,
+            queue_repo,
+            download_client,
+            blocklist: BlocklistService::new(blocklist_repo),
+            command_queue: None,
+        }
+    }
+
+    /// Queue `MoviesSearch` commands here to replace failed downloads
+    pub fn with_command_queue(mut self, queue: Arc<CommandQueue>) -> Self {
+        self.command_queue = Some(queue);
+        self
+    }
+
+    /// Handle the failure of a queue item
+    pub async fn handle_failure(
+        &self,
+        queue_item_id: Uuid,
+        error: &str,
+    ) -> Result<FailedDownloadOutcome> {
+        let mut outcome = FailedDownloadOutcome::default();
+        if !self.config.enabled {
+            return Ok(outcome);
+        }
+
+        let Some(item) = self.queue_repo.get_queue_item(queue_item_id).await? else {
+            debug!("Failed queue item {} is already gone", queue_item_id);
+            return Ok(outcome);
+        };
+        info!("Handling failed download '{}': {}", item.title, error);
+
+        if self.config.blocklist_failed {
+            self.blocklist
+                .block_release(
+                    item.release_id.to_string(),
+                    FAILED_DOWNLOAD_INDEXER,
+                    failure_reason(error),
+                    item.title.clone(),
+                    Some(item.movie_id),
+                    Some(serde_json::json!({
+                        "error": error,
+                        "download_url": item.download_url,
+                        "queue_item_id": item.id,
+                    })),
+                )
+                .await?;
+            outcome.blocklisted = true;
+        }
+
+        if self.config.remove_from_client {
+            outcome.removed_from_client = self.remove_from_client(&item).await;
+        }
+
+        // Handled here, so the queue processor must not retry the same release
+        self.queue_repo.delete_queue_item(item.id).await?;
+
+        if self.config.redownload {
+            outcome.search_command_id = self.search_again(item.movie_id).await;
+        }
+
+        Ok(outcome)
+    }
+
+    /// Remove the download from the client, returning whether it was removed
+    async fn remove_from_client(&self, item: &QueueItem) -> bool {
+        let Some(client_id) = &item.download_client_id else {
+            return false;
+        };
+        match self
+            .download_client
+            .remove_download(client_id, self.config.delete_files)
+            .await
+        {
+            Ok(()) => true,
+            Err(e) => {
+                warn!(
+                    "Failed to remove failed download '{}' from client: {}",
+                    item.title, e
+                );
+                false
+            }
+        }
+    }
+
+    /// Queue a search for another release of the movie
+    async fn search_again(&self, movie_id: Uuid) -> Option<i32> {
+        let queue = self.command_queue.as_ref()?;
+        match queue
+            .enqueue(
+                CommandName::MoviesSearch,
+                serde_json::json!({ "movieIds": [movie_id] }),
+                CommandTrigger::Scheduled,
+            )
+            .await
+        {
+            Ok(command) => {
+                info!(
+                    "Queued a search for movie {} after a failed download ({})",
+                    movie_id, command.id
+                );
+                Some(command.id)
+            }
+            Err(e) => {
+                warn!("Could not search again for movie {}: {}", movie_id, e);
+                None
+            }
+        }
+    }
+}
+
+/// Blocklist reason for a download client's error message
+fn failure_reason(error: &str) -> FailureReason {
+    let error = error.to_lowercase();
+    if error.contains("stalled") {
+        FailureReason::DownloadStalled
+    } else if error.contains("hash") {
+        FailureReason::HashMismatch
+    } else if error.contains("space") {
+        FailureReason::DiskFull
+    } else if error.contains("not found") {
+        FailureReason::ReleasePurged
+    } else {
+        FailureReason::DownloadClientError
+    }
+}
+
+#[async_trait]
+impl EventHandler for FailedDownloadHandler {
+    async fn handle_event(&self, envelope: &EventEnvelope) -> Result<()> {
+        if let SystemEvent::DownloadFailed {
+            queue_item_id,
+            error,
+            ..
+        } = &envelope.event
+        {
+            self.handle_failure(*queue_item_id, error).await?;
+        }
+        Ok(())
+    }
+
+    fn should_handle(&self, envelope: &EventEnvelope) -> bool {
+        self.config.enabled && matches!(envelope.event, SystemEvent::DownloadFailed { .. })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocklist::tests::MockBlocklistRepository;
+    use crate::services::test_support::{queued_item, MemoryQueue, NoopSearch, RecordingClient};
+
+    async fn failed_item(queue: &MemoryQueue) -> QueueItem {
+        let mut item = queued_item(queue).await;
+        item.set_error("Download client reported an error".to_string());
+        queue.update_queue_item(&item).await.unwrap();
+        item
+    }
+
+    #[tokio::test]
+    async fn test_failed_download_is_blocklisted_removed_and_searched_again() {
+        let queue = Arc::new(MemoryQueue::default());
+        let client = Arc::new(RecordingClient::default());
+        let blocklist = Arc::new(MockBlocklistRepository::new());
+        let commands = Arc::new(CommandQueue::new(1));
+        commands.register(CommandName::MoviesSearch, Arc::new(NoopSearch));
+        let handler = FailedDownloadHandler::new(
+            DownloadHandlingConfig::default(),
+            queue.clone(),
+            client.clone(),
+            blocklist.clone(),
+        )
+        .with_command_queue(commands.clone());
+
+        let item = failed_item(&queue).await;
+        let outcome = handler
+            .handle_failure(item.id, "Torrent stalled")
+            .await
+            .unwrap();
+
+        assert!(outcome.blocklisted);
+        assert!(outcome.removed_from_client);
+        assert!(outcome.search_command_id.is_some());
+        assert!(queue.get_queue_item(item.id).await.unwrap().is_none());
+        assert_eq!(
+            client.removed.lock().unwrap().as_slice(),
+            &[("hash".to_string(), true)]
+        );
+
+        let entries = blocklist
+            .get_entries_for_movie(item.movie_id)
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].reason, FailureReason::DownloadStalled);
+        assert_eq!(entries[0].release_title, item.title);
+
+        let command = commands
+            .get(outcome.search_command_id.unwrap())
+            .await
+            .unwrap();
+        assert_eq!(command.body["movieIds"][0], item.movie_id.to_string());
+    }
+
+    #[tokio::test]
+    async fn test_disabled_handling_leaves_item_for_retries() {
+        let queue = Arc::new(MemoryQueue::default());
+        let client = Arc::new(RecordingClient::default());
+        let handler = FailedDownloadHandler::new(
+            DownloadHandlingConfig {
+                enabled: false,
+                ..Default::default()
+            },
+            queue.clone(),
+            client.clone(),
+            Arc::new(MockBlocklistRepository::new()),
+        );
+
+        let item = failed_item(&queue).await;
+        let outcome = handler.handle_failure(item.id, "error").await.unwrap();
+
+        assert_eq!(outcome, FailedDownloadOutcome::default());
+        assert!(queue.get_queue_item(item.id).await.unwrap().is_some());
+        assert!(client.removed.lock().unwrap().is_empty());
+    }
+}
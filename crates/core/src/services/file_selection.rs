@@ -0,0 +1,116 @@
+//! Skipping unwanted files of multi-file downloads
+//!
+//! Releases often bundle samples, proof images and alternative encodes with
+//! the movie. Once a download's file list is known, every file other than
+//! the movie and its companions (subtitles, nfo) is marked as skipped in the
+//! download client, so no bandwidth goes to it and the import only finds the
+//! movie. Downloads without a video file, e.g. RAR sets, are left alone.
+
+use crate::services::DownloadFile;
+use std::path::Path;
+
+/// Extensions of video files
+const VIDEO_EXTENSIONS: &[&str] = &[
+    "mkv", "mp4", "avi", "mov", "wmv", "flv", "webm", "m4v", "mpg", "mpeg", "m2ts", "ts",
+];
+
+/// Extensions of files that are never useful for an import
+const JUNK_EXTENSIONS: &[&str] = &[
+    "jpg", "jpeg", "png", "gif", "bmp", "exe", "lnk", "scr", "bat", "cmd", "url", "txt",
+];
+
+/// Name fragments marking samples, trailers and proofs
+const EXTRA_INDICATORS: &[&str] = &["sample", "trailer", "proof"];
+
+fn extension(name: &str) -> String {
+    Path::new(name)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or_default()
+        .to_lowercase()
+}
+
+fn is_video(file: &DownloadFile) -> bool {
+    VIDEO_EXTENSIONS.contains(&extension(&file.name).as_str())
+}
+
+fn is_extra(file: &DownloadFile) -> bool {
+    let name = file.name.to_lowercase();
+    EXTRA_INDICATORS
+        .iter()
+        .any(|indicator| name.contains(indicator))
+}
+
+/// Indices of the files in `files` that should not be downloaded
+///
+/// The movie is the largest video file that isn't a sample; every other
+/// video file, extras and junk such as images and executables are unwanted.
+/// Files the client already skips are not returned again.
+pub fn unwanted_files(files: &[DownloadFile]) -> Vec<usize> {
+    let movie = files
+        .iter()
+        .enumerate()
+        .filter(|(_, file)| is_video(file) && !is_extra(file))
+        .max_by_key(|(_, file)| file.size)
+        .map(|(index, _)| index);
+    let Some(movie) = movie else {
+        return Vec::new();
+    };
+
+    files
+        .iter()
+        .enumerate()
+        .filter(|(index, file)| {
+            *index != movie
+                && !file.skipped
+                && (is_video(file)
+                    || is_extra(file)
+                    || JUNK_EXTENSIONS.contains(&extension(&file.name).as_str()))
+        })
+        .map(|(index, _)| index)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(name: &str, size: u64) -> DownloadFile {
+        DownloadFile {
+            name: name.to_string(),
+            size,
+            first_piece: 0,
+            last_piece: 0,
+            skipped: false,
+        }
+    }
+
+    #[test]
+    fn test_unwanted_files_keep_movie_and_companions() {
+        let mut files = vec![
+            file("Movie/Sample/movie-sample.mkv", 50),
+            file("Movie/Movie.2023.1080p.x264.mkv", 8_000),
+            file("Movie/Movie.2023.1080p.x265.mkv", 5_000),
+            file("Movie/Movie.2023.nfo", 1),
+            file("Movie/Subs/English.srt", 2),
+            file("Movie/Proof/proof.jpg", 3),
+            file("Movie/RARBG.txt", 1),
+            file("Movie/Movie.2023.1080p.x264.mkv.exe", 1),
+        ];
+        assert_eq!(unwanted_files(&files), vec![0, 2, 5, 6, 7]);
+
+        files[2].skipped = true;
+        assert_eq!(unwanted_files(&files), vec![0, 5, 6, 7]);
+    }
+
+    #[test]
+    fn test_downloads_without_video_are_left_alone() {
+        let files = vec![
+            file("Movie/movie.rar", 4_000),
+            file("Movie/movie.r00", 4_000),
+            file("Movie/proof.jpg", 3),
+        ];
+        assert!(unwanted_files(&files).is_empty());
+        assert!(unwanted_files(&[file("Movie/sample.mkv", 50)]).is_empty());
+    }
+}
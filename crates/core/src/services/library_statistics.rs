@@ -0,0 +1,259 @@
+//! Library statistics
+//!
+//! Totals over the whole library for dashboards: how many movies there are and
+//! how many are monitored, how many files they have and how much space those
+//! take, how the files spread over qualities, and how many monitored movies
+//! are still below their profile's cutoff. Computing them reads every movie
+//! file, so results are cached for a short while.
+
+use crate::domain::repositories::{MovieFileRepository, MovieRepository, QualityProfileRepository};
+use crate::jobs::cutoff_unmet;
+use crate::models::{Movie, MovieFile, QualityProfile, QUALITY_DEFINITIONS};
+use crate::Result;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+/// How long computed statistics are served before they are recomputed
+pub const DEFAULT_STATISTICS_TTL: Duration = Duration::from_secs(60);
+
+/// Files of one quality
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QualityStatistics {
+    /// Radarr quality ID
+    pub quality_id: i32,
+    pub quality: String,
+    pub file_count: u64,
+    pub size_on_disk: u64,
+}
+
+/// Totals over the whole library
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LibraryStatistics {
+    pub movie_count: u64,
+    pub monitored_count: u64,
+    pub unmonitored_count: u64,
+    /// Movies with at least one file
+    pub movies_with_files: u64,
+    pub file_count: u64,
+    /// Combined size of all movie files in bytes
+    pub size_on_disk: u64,
+    /// Monitored movies whose file is below their profile's cutoff
+    pub cutoff_unmet_count: u64,
+    /// Files by quality, lowest quality first
+    pub qualities: Vec<QualityStatistics>,
+    pub computed_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl LibraryStatistics {
+    /// Statistics of a library with `movie_count` movies, of which `monitored`
+    /// are the monitored ones, and `files`
+    pub fn compute(
+        movie_count: u64,
+        monitored: Vec<Movie>,
+        files: Vec<MovieFile>,
+        profiles: &[QualityProfile],
+        default_profile: Option<&QualityProfile>,
+    ) -> Self {
+        let monitored_count = monitored.len() as u64;
+
+        let mut movies_with_files = HashSet::new();
+        let mut size_on_disk = 0u64;
+        // Keyed by position in QUALITY_DEFINITIONS, so lowest quality first
+        let mut qualities: BTreeMap<usize, QualityStatistics> = BTreeMap::new();
+        for file in &files {
+            let size = file.size_bytes.max(0) as u64;
+            movies_with_files.insert(file.movie_id);
+            size_on_disk += size;
+
+            let quality_id = file.quality_id();
+            let (rank, quality_id, name) = QUALITY_DEFINITIONS
+                .iter()
+                .enumerate()
+                .find(|(_, (id, ..))| *id == quality_id)
+                .map(|(rank, (id, name, ..))| (rank, *id, *name))
+                .unwrap_or((0, 0, "Unknown"));
+            let entry = qualities.entry(rank).or_insert_with(|| QualityStatistics {
+                quality_id,
+                quality: name.to_string(),
+                file_count: 0,
+                size_on_disk: 0,
+            });
+            entry.file_count += 1;
+            entry.size_on_disk += size;
+        }
+
+        let file_count = files.len() as u64;
+        let cutoff_unmet_count =
+            cutoff_unmet(monitored, files, profiles, default_profile).len() as u64;
+
+        Self {
+            movie_count,
+            monitored_count,
+            unmonitored_count: movie_count.saturating_sub(monitored_count),
+            movies_with_files: movies_with_files.len() as u64,
+            file_count,
+            size_on_disk,
+            cutoff_unmet_count,
+            qualities: qualities.into_values().collect(),
+            computed_at: chrono::Utc::now(),
+        }
+    }
+}
+
+/// Computes library statistics and caches them for a while
+pub struct LibraryStatisticsService {
+    movie_repository: Arc<dyn MovieRepository>,
+    movie_file_repository: Arc<dyn MovieFileRepository>,
+    quality_profile_repository: Arc<dyn QualityProfileRepository>,
+    ttl: Duration,
+    /// Last result and when it was computed; the lock also keeps concurrent
+    /// requests from computing the same statistics twice
+    cached: Mutex<Option<(Instant, LibraryStatistics)>>,
+}
+
+impl LibraryStatisticsService {
+    pub fn new(
+        movie_repository: Arc<dyn MovieRepository>,
+        movie_file_repository: Arc<dyn MovieFileRepository>,
+        quality_profile_repository: Arc<dyn QualityProfileRepository>,
+    ) -> Self {
+        Self {
+            movie_repository,
+            movie_file_repository,
+            quality_profile_repository,
+            ttl: DEFAULT_STATISTICS_TTL,
+            cached: Mutex::new(None),
+        }
+    }
+
+    /// Serve computed statistics for `ttl` before recomputing them
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Current statistics, computed at most `ttl` ago
+    pub async fn statistics(&self) -> Result<LibraryStatistics> {
+        let mut cached = self.cached.lock().await;
+        if let Some((computed, statistics)) = cached.as_ref() {
+            if computed.elapsed() < self.ttl {
+                return Ok(statistics.clone());
+            }
+        }
+
+        let statistics = self.compute().await?;
+        *cached = Some((Instant::now(), statistics.clone()));
+        Ok(statistics)
+    }
+
+    /// Drop the cached statistics, e.g. after movies were added or deleted
+    pub async fn invalidate(&self) {
+        *self.cached.lock().await = None;
+    }
+
+    async fn compute(&self) -> Result<LibraryStatistics> {
+        let movie_count = self.movie_repository.count().await?.max(0) as u64;
+        let monitored = self.movie_repository.find_monitored().await?;
+        let files = self.movie_file_repository.list(None).await?;
+        let profiles = self.quality_profile_repository.list().await?;
+        let default_profile = self.quality_profile_repository.get_default().await?;
+
+        Ok(LibraryStatistics::compute(
+            movie_count,
+            monitored,
+            files,
+            &profiles,
+            default_profile.as_ref(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(cutoff_quality_id: i32) -> QualityProfile {
+        let mut profile = QualityProfile::new("HD".to_string(), cutoff_quality_id);
+        profile.id = 1;
+        profile.update_items(serde_json::json!([
+            {"quality": {"id": 5}, "items": [], "allowed": true},
+            {"quality": {"id": 3}, "items": [], "allowed": true}
+        ]));
+        profile
+    }
+
+    fn movie(title: &str, monitored: bool) -> Movie {
+        let mut movie = Movie::new(1, title.to_string());
+        movie.monitored = monitored;
+        movie.has_file = true;
+        movie.quality_profile_id = Some(1);
+        movie
+    }
+
+    #[test]
+    fn test_compute_totals_and_quality_distribution() {
+        let below = movie("Below", true);
+        let met = movie("Met", true);
+        let unmonitored = movie("Unmonitored", false);
+        let files = vec![
+            MovieFile::new(
+                below.id,
+                "Below.720p.WEB-DL.mkv".to_string(),
+                4,
+                serde_json::json!({}),
+            ),
+            MovieFile::new(
+                met.id,
+                "Met.1080p.WEB-DL.mkv".to_string(),
+                10,
+                serde_json::json!({}),
+            ),
+            MovieFile::new(
+                unmonitored.id,
+                "Unmonitored.1080p.WEB-DL.mkv".to_string(),
+                8,
+                serde_json::json!({}),
+            ),
+        ];
+
+        let statistics =
+            LibraryStatistics::compute(4, vec![below, met], files, &[profile(3)], None);
+
+        assert_eq!(statistics.movie_count, 4);
+        assert_eq!(statistics.monitored_count, 2);
+        assert_eq!(statistics.unmonitored_count, 2);
+        assert_eq!(statistics.movies_with_files, 3);
+        assert_eq!(statistics.file_count, 3);
+        assert_eq!(statistics.size_on_disk, 22);
+        assert_eq!(statistics.cutoff_unmet_count, 1);
+
+        let qualities: Vec<_> = statistics
+            .qualities
+            .iter()
+            .map(|quality| {
+                (
+                    quality.quality.as_str(),
+                    quality.file_count,
+                    quality.size_on_disk,
+                )
+            })
+            .collect();
+        assert_eq!(
+            qualities,
+            vec![("WEBDL-720p", 1, 4), ("WEBDL-1080p", 2, 18)]
+        );
+    }
+
+    #[test]
+    fn test_compute_empty_library() {
+        let statistics = LibraryStatistics::compute(0, Vec::new(), Vec::new(), &[], None);
+        assert_eq!(statistics.file_count, 0);
+        assert_eq!(statistics.cutoff_unmet_count, 0);
+        assert!(statistics.qualities.is_empty());
+    }
+}
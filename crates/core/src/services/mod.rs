@@ -3,11 +3,36 @@
 //! This module contains business logic services that orchestrate
 //! operations across multiple domain entities.
 
+pub mod config_drift;
+pub mod download_client_manager;
+pub mod failed_download;
+pub mod file_selection;
+pub mod library_statistics;
+pub mod preview_import;
 pub mod queue_processor;
+pub mod queue_removal;
 pub mod queue_service;
+pub mod search_cooldown;
 pub mod search_integration;
+pub mod seeding;
+pub mod stall_detection;
+pub mod storage_throttle;
+
+#[cfg(test)]
+pub(crate) mod test_support;
 
 // Re-export services
+pub use config_drift::*;
+pub use download_client_manager::*;
+pub use failed_download::*;
+pub use file_selection::*;
+pub use library_statistics::*;
+pub use preview_import::*;
 pub use queue_processor::*;
+pub use queue_removal::*;
 pub use queue_service::*;
+pub use search_cooldown::*;
 pub use search_integration::*;
+pub use seeding::*;
+pub use stall_detection::*;
+pub use storage_throttle::*;
@@ -0,0 +1,226 @@
+//! Early import preview for sequentially downloaded torrents
+//!
+//! A torrent downloaded in piece order can be played from the start well
+//! before it completes. Once the gap-free leading part of its main file
+//! reaches the configured share, the queue processor hardlinks the file into
+//! a staging directory for preview streaming. The link shares the file's
+//! data, so it fills in as the download continues. When the torrent completes
+//! the staged link is removed and the download is imported as usual; a
+//! failed or removed download has its preview removed as well.
+
+use crate::Result;
+use std::path::{Path, PathBuf};
+use tracing::{debug, info};
+use uuid::Uuid;
+
+/// Suffix qBittorrent can append to files that are still downloading
+const INCOMPLETE_SUFFIX: &str = ".!qB";
+
+/// Piece-level state of a download, as reported by its client
+#[derive(Debug, Clone, Default)]
+pub struct DownloadPieces {
+    /// Pieces are downloaded in order
+    pub sequential: bool,
+    /// Directory the download's files are saved under
+    pub save_path: String,
+    pub files: Vec<DownloadFile>,
+    /// Whether each piece is downloaded, in piece order
+    pub downloaded: Vec<bool>,
+}
+
+/// A file of a download and the pieces holding its data
+#[derive(Debug, Clone)]
+pub struct DownloadFile {
+    /// Path relative to the save path
+    pub name: String,
+    pub size: u64,
+    pub first_piece: usize,
+    pub last_piece: usize,
+    /// The client does not download this file
+    pub skipped: bool,
+}
+
+impl DownloadPieces {
+    /// The largest file, normally the movie itself
+    pub fn main_file(&self) -> Option<&DownloadFile> {
+        self.files.iter().max_by_key(|file| file.size)
+    }
+
+    /// Share of `file`'s pieces downloaded without a gap from its first piece
+    pub fn contiguous_progress(&self, file: &DownloadFile) -> f64 {
+        let Some(pieces) = self.downloaded.get(file.first_piece..=file.last_piece) else {
+            return 0.0;
+        };
+        if pieces.is_empty() {
+            return 0.0;
+        }
+        let leading = pieces.iter().take_while(|downloaded| **downloaded).count();
+        leading as f64 / pieces.len() as f64
+    }
+}
+
+/// Early import preview settings
+#[derive(Debug, Clone)]
+pub struct PreviewImportConfig {
+    /// Directory previews are hardlinked into; it must be on the same
+    /// filesystem as the downloads
+    pub staging_dir: PathBuf,
+    /// Gap-free share (0.0-1.0) of the main file required before staging
+    pub min_progress: f64,
+}
+
+impl PreviewImportConfig {
+    pub fn new(staging_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            staging_dir: staging_dir.into(),
+            min_progress: 0.95,
+        }
+    }
+
+    /// The file to preview, once the download is sequential and its main
+    /// file is playable up to `min_progress`
+    pub fn preview_file<'a>(&self, pieces: &'a DownloadPieces) -> Option<&'a DownloadFile> {
+        if !pieces.sequential {
+            return None;
+        }
+        pieces
+            .main_file()
+            .filter(|file| pieces.contiguous_progress(file) >= self.min_progress)
+    }
+
+    /// Hardlink `file` into the staging directory of `queue_item_id`
+    pub async fn stage(
+        &self,
+        queue_item_id: Uuid,
+        pieces: &DownloadPieces,
+        file: &DownloadFile,
+    ) -> Result<PathBuf> {
+        let mut source = Path::new(&pieces.save_path).join(&file.name);
+        if !tokio::fs::try_exists(&source).await.unwrap_or(false) {
+            source = PathBuf::from(format!("{}{}", source.display(), INCOMPLETE_SUFFIX));
+        }
+        let file_name = Path::new(&file.name)
+            .file_name()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(&file.name));
+
+        let directory = self.staging_dir.join(queue_item_id.to_string());
+        tokio::fs::create_dir_all(&directory).await?;
+        let target = directory.join(file_name);
+        if tokio::fs::try_exists(&target).await.unwrap_or(false) {
+            tokio::fs::remove_file(&target).await?;
+        }
+        tokio::fs::hard_link(&source, &target).await?;
+
+        info!(
+            "Staged preview of {} at {}",
+            source.display(),
+            target.display()
+        );
+        Ok(target)
+    }
+
+    /// Remove a staged preview and its directory
+    pub async fn remove(&self, preview_path: &str) -> Result<()> {
+        let path = Path::new(preview_path);
+        match tokio::fs::remove_file(path).await {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+        if let Some(directory) = path
+            .parent()
+            .filter(|dir| dir.starts_with(&self.staging_dir))
+        {
+            // Only succeeds once the directory is empty
+            let _ = tokio::fs::remove_dir(directory).await;
+        }
+        debug!("Removed preview {}", preview_path);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pieces(sequential: bool, downloaded: &[bool]) -> DownloadPieces {
+        DownloadPieces {
+            sequential,
+            save_path: String::new(),
+            files: vec![
+                DownloadFile {
+                    name: "Movie/sample.mkv".to_string(),
+                    size: 10,
+                    first_piece: 0,
+                    last_piece: 0,
+                    skipped: false,
+                },
+                DownloadFile {
+                    name: "Movie/Movie.mkv".to_string(),
+                    size: 1000,
+                    first_piece: 0,
+                    last_piece: downloaded.len() - 1,
+                    skipped: false,
+                },
+            ],
+            downloaded: downloaded.to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_preview_requires_sequential_gap_free_progress() {
+        let config = PreviewImportConfig::new("/previews");
+        let mut downloaded = vec![true; 20];
+        downloaded[19] = false;
+
+        let ready = pieces(true, &downloaded);
+        let file = config.preview_file(&ready).unwrap();
+        assert_eq!(file.name, "Movie/Movie.mkv");
+        assert_eq!(ready.contiguous_progress(file), 0.95);
+
+        assert!(config.preview_file(&pieces(false, &downloaded)).is_none());
+
+        // 95% downloaded, but with a gap early in the file
+        downloaded[19] = true;
+        downloaded[3] = false;
+        assert!(config.preview_file(&pieces(true, &downloaded)).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_stage_and_remove_preview() {
+        let root = std::env::temp_dir().join(format!("radarr-preview-{}", Uuid::new_v4()));
+        let downloads = root.join("downloads");
+        tokio::fs::create_dir_all(downloads.join("Movie"))
+            .await
+            .unwrap();
+        // Still downloading, with qBittorrent's incomplete suffix
+        tokio::fs::write(downloads.join("Movie/Movie.mkv.!qB"), b"data")
+            .await
+            .unwrap();
+
+        let config = PreviewImportConfig::new(root.join("previews"));
+        let mut pieces = pieces(true, &[true]);
+        pieces.save_path = downloads.display().to_string();
+        let file = pieces.main_file().unwrap().clone();
+
+        let queue_item_id = Uuid::new_v4();
+        let staged = config.stage(queue_item_id, &pieces, &file).await.unwrap();
+        assert_eq!(
+            staged,
+            root.join("previews")
+                .join(queue_item_id.to_string())
+                .join("Movie.mkv")
+        );
+        assert_eq!(tokio::fs::read(&staged).await.unwrap(), b"data");
+
+        config.remove(&staged.display().to_string()).await.unwrap();
+        assert!(!tokio::fs::try_exists(staged.parent().unwrap())
+            .await
+            .unwrap());
+        // Removing twice is fine
+        config.remove(&staged.display().to_string()).await.unwrap();
+
+        tokio::fs::remove_dir_all(&root).await.unwrap();
+    }
+}
@@ -3,16 +3,22 @@
 //! This service runs in the background to automatically process queued items,
 //! monitor download progress, and sync with download clients.
 
-use crate::services::{DownloadClientService, QueueRepository};
-use crate::Result;
-// use crate::RadarrError; // Currently unused
 use crate::events::{EventBus, SystemEvent};
+use crate::polling::{PollConfig, PollSchedule};
 use crate::progress::{OperationType, ProgressTracker};
 use crate::retry::{retry_with_backoff, CircuitBreaker, RetryConfig, RetryPolicy};
+use crate::services::{
+    unwanted_files, ClientDownloadStatus, DownloadClientService, DownloadSpeedTracker,
+    PreviewImportConfig, QueueRepository, StallDetectionConfig, StallState, StorageThrottle,
+};
+use crate::shutdown::ShutdownCoordinator;
+use crate::{RadarrError, Result};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::Mutex;
-use tokio::time;
 use tracing::{debug, error, info, warn};
 // use uuid::Uuid; // Currently unused
 
@@ -25,8 +31,24 @@ pub struct QueueProcessorConfig {
     pub check_interval_seconds: u64,
     /// How often to sync with download client (seconds)  
     pub sync_interval_seconds: u64,
+    /// Sync interval while downloads are in progress (seconds)
+    pub active_sync_interval_seconds: u64,
+    /// Sync interval while nothing is downloading (seconds)
+    pub idle_sync_interval_seconds: u64,
     /// How often to retry failed downloads (seconds)
     pub retry_interval_seconds: u64,
+    /// Minimum progress change (0.0-1.0) since the last persisted value
+    /// before a sync cycle writes the item again
+    pub progress_write_threshold: f64,
+    /// Free space (bytes) that must remain on the download client's disk
+    /// after a release is grabbed
+    pub free_space_headroom_bytes: u64,
+    /// Stop the client from downloading samples, proofs and other files
+    /// besides the movie
+    pub skip_unwanted_files: bool,
+    /// Fail downloads that stay slow for too long, so failed download
+    /// handling blocklists and replaces them
+    pub stall_detection: StallDetectionConfig,
     /// Whether the processor is enabled
     pub enabled: bool,
 }
@@ -37,12 +59,79 @@ impl Default for QueueProcessorConfig {
             max_concurrent_downloads: 5,
             check_interval_seconds: 30,
             sync_interval_seconds: 60,
-            retry_interval_seconds: 300, // 5 minutes
+            active_sync_interval_seconds: 15,
+            idle_sync_interval_seconds: 300, // 5 minutes
+            retry_interval_seconds: 300,     // 5 minutes
+            progress_write_threshold: 0.01,
+            free_space_headroom_bytes: 1024 * 1024 * 1024, // 1 GiB
+            skip_unwanted_files: true,
+            stall_detection: StallDetectionConfig::default(),
             enabled: true,
         }
     }
 }
 
+/// Counters for queue writes issued by the download client sync loop
+///
+/// Before batching, every polled item cost one UPDATE per cycle; comparing
+/// `items_polled` with `batch_writes` shows how many statements were saved.
+#[derive(Debug, Default)]
+pub struct QueueSyncMetrics {
+    sync_cycles: AtomicU64,
+    items_polled: AtomicU64,
+    items_written: AtomicU64,
+    writes_skipped: AtomicU64,
+    batch_writes: AtomicU64,
+    last_polled: AtomicU64,
+}
+
+/// Point-in-time copy of [`QueueSyncMetrics`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct QueueSyncStats {
+    pub sync_cycles: u64,
+    pub items_polled: u64,
+    pub items_written: u64,
+    pub writes_skipped: u64,
+    pub batch_writes: u64,
+    /// Statements avoided compared to one UPDATE per polled item
+    pub writes_saved: u64,
+}
+
+impl QueueSyncMetrics {
+    fn record_cycle(&self, polled: usize, written: usize, skipped: usize, batches: usize) {
+        self.sync_cycles.fetch_add(1, Ordering::Relaxed);
+        self.last_polled.store(polled as u64, Ordering::Relaxed);
+        self.items_polled
+            .fetch_add(polled as u64, Ordering::Relaxed);
+        self.items_written
+            .fetch_add(written as u64, Ordering::Relaxed);
+        self.writes_skipped
+            .fetch_add(skipped as u64, Ordering::Relaxed);
+        self.batch_writes
+            .fetch_add(batches as u64, Ordering::Relaxed);
+    }
+
+    /// Items polled by the most recent sync cycle
+    pub fn last_polled(&self) -> u64 {
+        self.last_polled.load(Ordering::Relaxed)
+    }
+
+    /// Current counter values
+    pub fn snapshot(&self) -> QueueSyncStats {
+        let items_polled = self.items_polled.load(Ordering::Relaxed);
+        let batch_writes = self.batch_writes.load(Ordering::Relaxed);
+
+        QueueSyncStats {
+            sync_cycles: self.sync_cycles.load(Ordering::Relaxed),
+            items_polled,
+            items_written: self.items_written.load(Ordering::Relaxed),
+            writes_skipped: self.writes_skipped.load(Ordering::Relaxed),
+            batch_writes,
+            writes_saved: items_polled.saturating_sub(batch_writes),
+        }
+    }
+}
+
 /// Background queue processor
 pub struct QueueProcessor<Q: QueueRepository, D: DownloadClientService> {
     config: QueueProcessorConfig,
@@ -51,6 +140,16 @@ pub struct QueueProcessor<Q: QueueRepository, D: DownloadClientService> {
     download_circuit_breaker: Arc<Mutex<CircuitBreaker>>,
     progress_tracker: Option<Arc<ProgressTracker>>,
     event_bus: Option<Arc<EventBus>>,
+    sync_metrics: Arc<QueueSyncMetrics>,
+    storage_throttle: Option<Arc<StorageThrottle>>,
+    preview: Option<PreviewImportConfig>,
+    /// Downloads whose unwanted files have been skipped
+    files_selected: std::sync::Mutex<HashSet<String>>,
+    /// Speed and ETA history of active downloads, when stalled downloads
+    /// are replaced
+    stall_tracker: Option<std::sync::Mutex<DownloadSpeedTracker>>,
+    /// Stops the loops between cycles once shutdown starts
+    shutdown: ShutdownCoordinator,
 }
 
 impl<Q: QueueRepository, D: DownloadClientService> QueueProcessor<Q, D>
@@ -67,15 +166,28 @@ where
         )));
 
         Self {
-            config,
             queue_repo,
             download_client,
             download_circuit_breaker,
             progress_tracker: None,
             event_bus: None,
+            sync_metrics: Arc::new(QueueSyncMetrics::default()),
+            storage_throttle: None,
+            preview: None,
+            files_selected: std::sync::Mutex::new(HashSet::new()),
+            stall_tracker: config.stall_detection.enabled.then(|| {
+                std::sync::Mutex::new(DownloadSpeedTracker::new(config.stall_detection.clone()))
+            }),
+            shutdown: ShutdownCoordinator::new(),
+            config,
         }
     }
 
+    /// Write counters for the sync loop, shared with the running processor
+    pub fn sync_metrics(&self) -> Arc<QueueSyncMetrics> {
+        self.sync_metrics.clone()
+    }
+
     /// Set progress tracker for this processor
     pub fn with_progress_tracker(mut self, tracker: Arc<ProgressTracker>) -> Self {
         self.progress_tracker = Some(tracker);
@@ -88,7 +200,28 @@ where
         self
     }
 
+    /// Hold items for root folders that are low on space as pending, and
+    /// queue them again once space frees up
+    pub fn with_storage_throttle(mut self, throttle: Arc<StorageThrottle>) -> Self {
+        self.storage_throttle = Some(throttle);
+        self
+    }
+
+    /// Stage early import previews of sequential downloads
+    pub fn with_preview_import(mut self, config: PreviewImportConfig) -> Self {
+        self.preview = Some(config);
+        self
+    }
+
+    /// Stop processing between cycles when `shutdown` is signalled
+    pub fn with_shutdown(mut self, shutdown: ShutdownCoordinator) -> Self {
+        self.shutdown = shutdown;
+        self
+    }
+
     /// Start the background processor
+    ///
+    /// Runs until shutdown is signalled; a cycle in progress is finished first.
     pub async fn start(self) -> Result<()> {
         if !self.config.enabled {
             info!("Queue processor is disabled");
@@ -123,19 +256,20 @@ where
             })
         };
 
-        // Wait for all tasks to complete (they shouldn't unless there's an error)
+        // Wait for all tasks to complete, which they do on shutdown
         let _ = tokio::try_join!(process_task, sync_task, retry_task)?;
 
+        info!("Queue processor stopped");
         Ok(())
     }
 
     /// Run the queue processing loop
     async fn run_queue_processing(&self) {
-        let mut interval = time::interval(Duration::from_secs(self.config.check_interval_seconds));
+        let mut schedule = PollSchedule::new(PollConfig::new(Duration::from_secs(
+            self.config.check_interval_seconds,
+        )));
 
         loop {
-            interval.tick().await;
-
             // Use retry logic for queue processing
             let retry_config = RetryConfig::quick();
             match retry_with_backoff(
@@ -147,24 +281,35 @@ where
             .await
             {
                 Ok(processed_count) => {
+                    schedule.record_success();
                     if processed_count > 0 {
                         debug!("Processed {} queue items", processed_count);
                     }
                 }
                 Err(e) => {
+                    schedule.record_failure();
                     error!("Error processing queue items after retries: {}", e);
                 }
             }
+
+            tokio::select! {
+                _ = self.shutdown.cancelled() => break,
+                _ = schedule.wait() => {}
+            }
         }
     }
 
     /// Run the sync task loop
     async fn run_sync_task(&self) {
-        let mut interval = time::interval(Duration::from_secs(self.config.sync_interval_seconds));
+        let mut schedule = PollSchedule::new(
+            PollConfig::new(Duration::from_secs(self.config.sync_interval_seconds))
+                .with_active_interval(Duration::from_secs(
+                    self.config.active_sync_interval_seconds,
+                ))
+                .with_idle_interval(Duration::from_secs(self.config.idle_sync_interval_seconds)),
+        );
 
         loop {
-            interval.tick().await;
-
             // Use retry logic for sync operations
             let retry_config = RetryConfig::quick();
             match retry_with_backoff(
@@ -176,34 +321,50 @@ where
             .await
             {
                 Ok(updated_count) => {
+                    schedule.record_success();
+                    // Poll faster while something is downloading
+                    schedule.set_active(self.sync_metrics.last_polled() > 0);
                     if updated_count > 0 {
                         debug!("Updated {} items from download client", updated_count);
                     }
                 }
                 Err(e) => {
+                    schedule.record_failure();
                     error!("Error syncing with download client after retries: {}", e);
                 }
             }
+
+            tokio::select! {
+                _ = self.shutdown.cancelled() => break,
+                _ = schedule.wait() => {}
+            }
         }
     }
 
     /// Run the retry task loop
     async fn run_retry_task(&self) {
-        let mut interval = time::interval(Duration::from_secs(self.config.retry_interval_seconds));
+        let mut schedule = PollSchedule::new(PollConfig::new(Duration::from_secs(
+            self.config.retry_interval_seconds,
+        )));
 
         loop {
-            interval.tick().await;
-
             match self.retry_failed_items().await {
                 Ok(retried_count) => {
+                    schedule.record_success();
                     if retried_count > 0 {
                         info!("Retried {} failed downloads", retried_count);
                     }
                 }
                 Err(e) => {
+                    schedule.record_failure();
                     error!("Error retrying failed downloads: {}", e);
                 }
             }
+
+            tokio::select! {
+                _ = self.shutdown.cancelled() => break,
+                _ = schedule.wait() => {}
+            }
         }
     }
 
@@ -211,6 +372,8 @@ where
     async fn process_queue_items(&self) -> Result<usize> {
         use crate::models::QueueStatus;
 
+        self.release_pending_items().await?;
+
         // Get current downloading count
         let downloading_items = self
             .queue_repo
@@ -253,7 +416,14 @@ where
             }
         });
 
-        for item in sorted_items.iter().take(slots_available) {
+        for item in sorted_items.iter() {
+            if processed_count >= slots_available {
+                break;
+            }
+            if self.hold_for_storage(item).await? {
+                continue;
+            }
+
             match self.start_download(item).await {
                 Ok(()) => {
                     processed_count += 1;
@@ -273,10 +443,66 @@ where
         Ok(processed_count)
     }
 
+    /// Queue pending items again whose root folder has room now
+    async fn release_pending_items(&self) -> Result<usize> {
+        use crate::models::QueueStatus;
+
+        let Some(throttle) = &self.storage_throttle else {
+            return Ok(0);
+        };
+
+        let mut released = 0;
+        for mut item in self
+            .queue_repo
+            .get_queue_items(Some(QueueStatus::Pending))
+            .await?
+        {
+            let held = item
+                .root_folder_path
+                .as_deref()
+                .and_then(|path| throttle.hold_for(path))
+                .is_some();
+            if !held {
+                info!("Free space recovered, queueing {} again", item.title);
+                item.update_status(QueueStatus::Queued);
+                self.queue_repo.update_queue_item(&item).await?;
+                released += 1;
+            }
+        }
+        Ok(released)
+    }
+
+    /// Move a queued item to pending when its root folder is low on space
+    async fn hold_for_storage(&self, item: &crate::models::QueueItem) -> Result<bool> {
+        use crate::models::QueueStatus;
+
+        let space = match (&self.storage_throttle, &item.root_folder_path) {
+            (Some(throttle), Some(path)) => throttle.hold_for(path),
+            _ => None,
+        };
+        let Some(space) = space else {
+            return Ok(false);
+        };
+
+        warn!(
+            "Holding {} as pending: {} has {} bytes free, below {}",
+            item.title, space.path, space.free_bytes, space.threshold_bytes
+        );
+        let mut pending = item.clone();
+        pending.update_status(QueueStatus::Pending);
+        self.queue_repo.update_queue_item(&pending).await?;
+        Ok(true)
+    }
+
     /// Start a download for a queue item with retry and circuit breaker
     async fn start_download(&self, queue_item: &crate::models::QueueItem) -> Result<()> {
         use crate::models::QueueStatus;
 
+        let mut queue_item = queue_item.clone();
+        self.inspect_download(&mut queue_item).await?;
+        let queue_item = &queue_item;
+        self.verify_free_space(queue_item).await?;
+
         // Start progress tracking if available
         let progress_id = if let Some(tracker) = &self.progress_tracker {
             let id = tracker
@@ -312,8 +538,9 @@ where
                 // Retry logic for adding download
                 let retry_config = RetryConfig::slow();
                 retry_with_backoff(retry_config, RetryPolicy::Transient, "add_download", || {
-                    self.download_client.add_download(
+                    self.download_client.add_download_from_indexer(
                         &queue_item.download_url,
+                        queue_item.indexer_id,
                         queue_item.category.clone(),
                         queue_item.download_path.clone(),
                     )
@@ -361,49 +588,287 @@ where
         Ok(())
     }
 
+    /// Read the release before it is sent to the client: take its exact
+    /// size, and refuse it when the queue already has the same download
+    async fn inspect_download(&self, queue_item: &mut crate::models::QueueItem) -> Result<()> {
+        let metadata = match self
+            .download_client
+            .inspect_download(&queue_item.download_url)
+            .await
+        {
+            Ok(Some(metadata)) => metadata,
+            Ok(None) => return Ok(()),
+            Err(e) => {
+                debug!("Could not inspect {}: {}", queue_item.title, e);
+                return Ok(());
+            }
+        };
+
+        if let Some(existing) = self
+            .queue_repo
+            .get_queue_item_by_client_id(&metadata.client_id)
+            .await?
+            .filter(|existing| existing.id != queue_item.id)
+        {
+            return Err(RadarrError::ValidationError {
+                field: "download_url".to_string(),
+                message: format!(
+                    "Same download as queued '{}' ({})",
+                    existing.title, metadata.client_id
+                ),
+            });
+        }
+        if let Some(size) = metadata.size_bytes.filter(|size| *size > 0) {
+            queue_item.size_bytes = Some(size as i64);
+        }
+        Ok(())
+    }
+
+    /// Refuse grabs whose expected size would not fit on the client's disk
+    ///
+    /// Items without a known size, and clients that cannot report free space,
+    /// are let through.
+    async fn verify_free_space(&self, queue_item: &crate::models::QueueItem) -> Result<()> {
+        let size_bytes = match queue_item.size_bytes {
+            Some(size) if size > 0 => size as u64,
+            _ => return Ok(()),
+        };
+
+        let free_bytes = match self
+            .download_client
+            .free_space(&queue_item.download_url)
+            .await
+        {
+            Ok(Some(free)) => free,
+            Ok(None) => return Ok(()),
+            Err(e) => {
+                warn!(
+                    "Could not read free space for {}, grabbing anyway: {}",
+                    queue_item.title, e
+                );
+                return Ok(());
+            }
+        };
+
+        check_free_space(
+            size_bytes,
+            free_bytes,
+            self.config.free_space_headroom_bytes,
+        )
+    }
+
     /// Sync with download client
     async fn sync_with_download_client(&self) -> Result<usize> {
         use crate::models::QueueStatus;
 
-        let active_items = self
+        let mut active_items = self
             .queue_repo
             .get_queue_items(Some(QueueStatus::Downloading))
             .await?;
-        let mut updated_count = 0;
+        // Stalled downloads may pick up again, or be replaced once they stay stalled
+        if self.stall_tracker.is_some() {
+            active_items.extend(
+                self.queue_repo
+                    .get_queue_items(Some(QueueStatus::Stalled))
+                    .await?,
+            );
+        }
+        let polled = active_items.len();
+        let mut pending = Vec::new();
+        let mut preview_updates = Vec::new();
+        let mut failed = Vec::new();
 
         for mut item in active_items {
-            if let Some(client_id) = &item.download_client_id {
-                match self.download_client.get_download_status(client_id).await? {
-                    Some(status) => {
-                        let old_progress = item.progress;
-                        let old_status = item.status;
-
-                        self.update_queue_item_from_client_status(&mut item, &status)?;
-
-                        // Only update if something changed
-                        if item.status != old_status || (item.progress - old_progress).abs() > 0.01
-                        {
-                            self.queue_repo.update_queue_item(&item).await?;
-                            updated_count += 1;
-
-                            // Log completion
-                            if item.is_completed() && old_status != item.status {
-                                info!("Download completed: {}", item.title);
-                            }
+            let Some(client_id) = item.download_client_id.clone() else {
+                continue;
+            };
+
+            match self.download_client.get_download_status(&client_id).await? {
+                Some(status) => {
+                    self.skip_unwanted_files(&item, &client_id).await;
+
+                    let old_progress = item.progress;
+                    let old_status = item.status;
+
+                    self.update_queue_item_from_client_status(&mut item, &status)?;
+                    let mut preview_changed = self.update_preview(&mut item, &status).await;
+                    if let Some(error) = self.detect_stall(&item) {
+                        warn!("Download stalled: {} ({})", item.title, error);
+                        item.set_error(error);
+                        preview_changed |= self.remove_preview(&mut item).await;
+                    }
+
+                    // Only persist material changes
+                    if preview_changed
+                        || needs_write(
+                            old_status,
+                            old_progress,
+                            &item,
+                            self.config.progress_write_threshold,
+                        )
+                    {
+                        if item.is_completed() && old_status != item.status {
+                            info!("Download completed: {}", item.title);
+                        }
+                        if item.is_failed() && old_status != item.status {
+                            warn!("Download failed in client: {}", item.title);
+                            failed.push(item.clone());
+                        }
+                        if preview_changed {
+                            preview_updates.push(item.clone());
                         }
+                        pending.push(item);
                     }
-                    None => {
-                        // Download not found in client, mark as failed
-                        warn!("Download not found in client: {}", item.title);
-                        item.set_error("Download not found in client".to_string());
-                        self.queue_repo.update_queue_item(&item).await?;
-                        updated_count += 1;
+                }
+                None => {
+                    // Download not found in client, mark as failed
+                    warn!("Download not found in client: {}", item.title);
+                    item.set_error("Download not found in client".to_string());
+                    if self.remove_preview(&mut item).await {
+                        preview_updates.push(item.clone());
                     }
+                    failed.push(item.clone());
+                    pending.push(item);
                 }
             }
         }
 
-        Ok(updated_count)
+        let batches = if pending.is_empty() {
+            0
+        } else {
+            self.queue_repo.update_queue_items_batch(&pending).await?;
+            1
+        };
+        // The batch update leaves previews alone
+        for item in &preview_updates {
+            self.queue_repo.update_queue_item(item).await?;
+        }
+        self.sweep_previews().await;
+        self.forget_selected_files(&pending);
+        self.forget_speed_history(&pending);
+        // Published once persisted, so failed download handling sees the
+        // item in its failed state
+        for item in &failed {
+            self.publish_download_failed(item).await;
+        }
+
+        self.sync_metrics
+            .record_cycle(polled, pending.len(), polled - pending.len(), batches);
+        debug!(
+            "Sync cycle polled {} items, persisted {} in {} statement(s)",
+            polled,
+            pending.len(),
+            batches
+        );
+
+        Ok(pending.len())
+    }
+
+    /// Tell the client to skip a download's samples, proofs and other files
+    /// besides the movie, once its file list is known
+    async fn skip_unwanted_files(&self, item: &crate::models::QueueItem, client_id: &str) {
+        if !self.config.skip_unwanted_files
+            || self
+                .files_selected
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .contains(client_id)
+        {
+            return;
+        }
+
+        let files = match self.download_client.download_files(client_id).await {
+            // Magnets list no files until their metadata arrives
+            Ok(Some(files)) if !files.is_empty() => files,
+            Ok(_) => return,
+            Err(e) => {
+                debug!("Could not list files of {}: {}", item.title, e);
+                return;
+            }
+        };
+
+        let unwanted = unwanted_files(&files);
+        if !unwanted.is_empty() {
+            if let Err(e) = self.download_client.skip_files(client_id, &unwanted).await {
+                warn!("Could not skip unwanted files of {}: {}", item.title, e);
+                return;
+            }
+            info!(
+                "Skipping {} unwanted files of {}: {}",
+                unwanted.len(),
+                item.title,
+                unwanted
+                    .iter()
+                    .map(|index| files[*index].name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+        self.files_selected
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(client_id.to_string());
+    }
+
+    /// Stop tracking file selection for downloads that finished or failed
+    fn forget_selected_files(&self, items: &[crate::models::QueueItem]) {
+        let mut selected = self
+            .files_selected
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        for item in items.iter().filter(|item| !item.is_active()) {
+            if let Some(client_id) = &item.download_client_id {
+                selected.remove(client_id);
+            }
+        }
+    }
+
+    /// Record the download's speed and ETA; the error to fail it with once
+    /// it has been slow for longer than allowed
+    fn detect_stall(&self, item: &crate::models::QueueItem) -> Option<String> {
+        use crate::models::QueueStatus;
+
+        let tracker = self.stall_tracker.as_ref()?;
+        if !matches!(item.status, QueueStatus::Downloading | QueueStatus::Stalled)
+            || item.progress >= 1.0
+        {
+            return None;
+        }
+
+        let mut tracker = tracker.lock().unwrap_or_else(|e| e.into_inner());
+        let speed = item.download_speed.unwrap_or(0);
+        match tracker.record(item.id, speed, item.eta_seconds, chrono::Utc::now()) {
+            StallState::Stalled(slow_for) => {
+                let min_speed = tracker.config().min_speed_bytes_per_sec;
+                tracker.forget(item.id);
+                Some(format!(
+                    "Download stalled: below {} KB/s for {} minutes",
+                    min_speed / 1024,
+                    slow_for.num_minutes()
+                ))
+            }
+            StallState::Slow(since) => {
+                debug!("{} has been slow since {}", item.title, since);
+                None
+            }
+            StallState::Healthy => None,
+        }
+    }
+
+    /// Drop the speed history of downloads that stopped downloading
+    fn forget_speed_history(&self, items: &[crate::models::QueueItem]) {
+        use crate::models::QueueStatus;
+
+        let Some(tracker) = &self.stall_tracker else {
+            return;
+        };
+        let mut tracker = tracker.lock().unwrap_or_else(|e| e.into_inner());
+        for item in items
+            .iter()
+            .filter(|item| !matches!(item.status, QueueStatus::Downloading | QueueStatus::Stalled))
+        {
+            tracker.forget(item.id);
+        }
     }
 
     /// Update queue item from client status
@@ -447,6 +912,170 @@ where
         Ok(())
     }
 
+    /// Stage a preview once the download qualifies, and finalize it when the
+    /// download leaves the downloading state; true when the preview changed
+    async fn update_preview(
+        &self,
+        item: &mut crate::models::QueueItem,
+        client_status: &ClientDownloadStatus,
+    ) -> bool {
+        use crate::models::QueueStatus;
+
+        let Some(preview) = &self.preview else {
+            return false;
+        };
+
+        match (&item.preview_path, item.status) {
+            (None, QueueStatus::Downloading) if item.progress >= preview.min_progress => {
+                let pieces = match self
+                    .download_client
+                    .download_pieces(&client_status.client_id)
+                    .await
+                {
+                    Ok(Some(pieces)) => pieces,
+                    Ok(None) => return false,
+                    Err(e) => {
+                        debug!("Could not read pieces of {}: {}", item.title, e);
+                        return false;
+                    }
+                };
+                let Some(file) = preview.preview_file(&pieces) else {
+                    return false;
+                };
+                match preview.stage(item.id, &pieces, file).await {
+                    Ok(path) => {
+                        item.preview_path = Some(path.to_string_lossy().to_string());
+                        true
+                    }
+                    Err(e) => {
+                        warn!("Could not stage preview of {}: {}", item.title, e);
+                        false
+                    }
+                }
+            }
+            (Some(_), QueueStatus::Downloading | QueueStatus::Paused | QueueStatus::Stalled) => {
+                false
+            }
+            (Some(_), status) => {
+                self.remove_preview(item).await;
+                if matches!(status, QueueStatus::Completed | QueueStatus::Seeding) {
+                    self.publish_download_complete(item, client_status).await;
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Remove the item's staged preview; true when it had one
+    async fn remove_preview(&self, item: &mut crate::models::QueueItem) -> bool {
+        let Some(path) = item.preview_path.take() else {
+            return false;
+        };
+        if let Some(preview) = &self.preview {
+            if let Err(e) = preview.remove(&path).await {
+                warn!("Could not remove preview {}: {}", path, e);
+            }
+        }
+        true
+    }
+
+    /// Hand a previewed download that just completed to import
+    async fn publish_download_complete(
+        &self,
+        item: &crate::models::QueueItem,
+        client_status: &ClientDownloadStatus,
+    ) {
+        let Some(bus) = &self.event_bus else {
+            return;
+        };
+
+        // The main file the preview was made from, or the download itself
+        let file_path = match self
+            .download_client
+            .download_pieces(&client_status.client_id)
+            .await
+        {
+            Ok(Some(pieces)) => pieces
+                .main_file()
+                .map(|file| std::path::Path::new(&pieces.save_path).join(&file.name)),
+            _ => None,
+        }
+        .or_else(|| {
+            client_status
+                .save_path
+                .as_ref()
+                .map(|path| std::path::Path::new(path).join(&client_status.name))
+        });
+        let Some(file_path) = file_path else {
+            warn!("No file to import for previewed download {}", item.title);
+            return;
+        };
+
+        info!("Previewed download completed, importing: {}", item.title);
+        let _ = bus
+            .publish(SystemEvent::DownloadComplete {
+                movie_id: item.movie_id,
+                queue_item_id: item.id,
+                file_path: file_path.to_string_lossy().to_string(),
+            })
+            .await;
+    }
+
+    /// Announce a download that failed in the client
+    async fn publish_download_failed(&self, item: &crate::models::QueueItem) {
+        let Some(bus) = &self.event_bus else {
+            return;
+        };
+
+        let _ = bus
+            .publish(SystemEvent::DownloadFailed {
+                movie_id: item.movie_id,
+                queue_item_id: item.id,
+                error: item
+                    .error_message
+                    .clone()
+                    .unwrap_or_else(|| "Download client reported an error".to_string()),
+            })
+            .await;
+    }
+
+    /// Remove staged previews whose queue item is gone or no longer
+    /// downloading, e.g. after it was removed from the queue
+    async fn sweep_previews(&self) {
+        use crate::models::QueueStatus;
+
+        let Some(preview) = &self.preview else {
+            return;
+        };
+        let Ok(mut entries) = tokio::fs::read_dir(&preview.staging_dir).await else {
+            return;
+        };
+
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let Ok(id) = entry.file_name().to_string_lossy().parse::<uuid::Uuid>() else {
+                continue;
+            };
+            let active = match self.queue_repo.get_queue_item(id).await {
+                Ok(Some(item)) => {
+                    item.preview_path.is_some()
+                        && matches!(
+                            item.status,
+                            QueueStatus::Downloading | QueueStatus::Paused | QueueStatus::Stalled
+                        )
+                }
+                Ok(None) => false,
+                Err(_) => true,
+            };
+            if !active {
+                info!("Removing orphaned preview {}", entry.path().display());
+                if let Err(e) = tokio::fs::remove_dir_all(entry.path()).await {
+                    warn!("Could not remove preview {}: {}", entry.path().display(), e);
+                }
+            }
+        }
+    }
+
     /// Retry failed items
     async fn retry_failed_items(&self) -> Result<usize> {
         let retry_items = self.queue_repo.get_retry_items().await?;
@@ -471,6 +1100,33 @@ where
     }
 }
 
+/// Whether a synced item differs enough from its persisted state to be written
+fn needs_write(
+    old_status: crate::models::QueueStatus,
+    old_progress: f64,
+    item: &crate::models::QueueItem,
+    threshold: f64,
+) -> bool {
+    item.status != old_status
+        || (item.progress >= 1.0 && old_progress < 1.0)
+        || (item.progress - old_progress).abs() >= threshold
+}
+
+/// Whether `size_bytes` fits in `free_bytes` with `headroom_bytes` left over
+fn check_free_space(size_bytes: u64, free_bytes: u64, headroom_bytes: u64) -> Result<()> {
+    let required = size_bytes.saturating_add(headroom_bytes);
+    if free_bytes < required {
+        return Err(RadarrError::ValidationError {
+            field: "freeSpace".to_string(),
+            message: format!(
+                "Not enough free space: {} bytes required ({} release + {} headroom), {} available",
+                required, size_bytes, headroom_bytes, free_bytes
+            ),
+        });
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -594,6 +1250,8 @@ mod tests {
                 seeders: Some(10),
                 leechers: Some(5),
                 save_path: Some("/downloads/movies".to_string()),
+                ratio: None,
+                seeding_time_seconds: None,
             }))
         }
 
@@ -623,6 +1281,312 @@ mod tests {
         let _processor = QueueProcessor::new(config, repo, client);
     }
 
+    #[test]
+    fn test_needs_write_respects_progress_threshold() {
+        let mut item = QueueItem::new(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            "Test Movie".to_string(),
+            "magnet:test".to_string(),
+        );
+        item.status = QueueStatus::Downloading;
+        item.progress = 0.505;
+
+        assert!(!needs_write(QueueStatus::Downloading, 0.5, &item, 0.01));
+        assert!(needs_write(QueueStatus::Downloading, 0.49, &item, 0.01));
+        assert!(needs_write(QueueStatus::Queued, 0.505, &item, 0.01));
+
+        item.progress = 1.0;
+        assert!(needs_write(QueueStatus::Downloading, 0.995, &item, 0.01));
+    }
+
+    #[test]
+    fn test_check_free_space_keeps_headroom() {
+        assert!(check_free_space(900, 1000, 100).is_ok());
+        assert!(check_free_space(901, 1000, 100).is_err());
+        assert!(check_free_space(u64::MAX, 1000, 100).is_err());
+    }
+
+    /// Knows every release as the same torrent of 2 GB
+    struct InspectingClient;
+
+    #[async_trait]
+    impl DownloadClientService for InspectingClient {
+        async fn add_download(
+            &self,
+            _download_url: &str,
+            _category: Option<String>,
+            _save_path: Option<String>,
+        ) -> Result<String> {
+            Ok("C12FE1C06BBA254A9DC9F519B335AA7C1367A88A".to_string())
+        }
+        async fn get_download_status(
+            &self,
+            _client_id: &str,
+        ) -> Result<Option<ClientDownloadStatus>> {
+            Ok(None)
+        }
+        async fn remove_download(&self, _client_id: &str, _delete_files: bool) -> Result<()> {
+            Ok(())
+        }
+        async fn pause_download(&self, _client_id: &str) -> Result<()> {
+            Ok(())
+        }
+        async fn resume_download(&self, _client_id: &str) -> Result<()> {
+            Ok(())
+        }
+        async fn get_all_downloads(&self) -> Result<Vec<ClientDownloadStatus>> {
+            Ok(vec![])
+        }
+        async fn inspect_download(
+            &self,
+            _download_url: &str,
+        ) -> Result<Option<crate::services::DownloadMetadata>> {
+            Ok(Some(crate::services::DownloadMetadata {
+                client_id: "C12FE1C06BBA254A9DC9F519B335AA7C1367A88A".to_string(),
+                name: Some("Movie.2024.1080p".to_string()),
+                size_bytes: Some(2 * 1024 * 1024 * 1024),
+                files: vec![("Movie.mkv".to_string(), 2 * 1024 * 1024 * 1024)],
+            }))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_start_download_uses_metadata_and_refuses_duplicates() {
+        let repo = Arc::new(MockQueueRepository::new());
+        let processor = QueueProcessor::new(
+            QueueProcessorConfig::default(),
+            repo.clone(),
+            Arc::new(InspectingClient),
+        );
+
+        let first = QueueItem::new(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            "Movie.2024.1080p".to_string(),
+            "http://indexer/download/1".to_string(),
+        );
+        repo.add_queue_item(&first).await.unwrap();
+        processor.start_download(&first).await.unwrap();
+        let started = repo.get_queue_item(first.id).await.unwrap().unwrap();
+        assert_eq!(started.status, QueueStatus::Downloading);
+        assert_eq!(started.size_bytes, Some(2 * 1024 * 1024 * 1024));
+
+        // Another indexer's copy of the same torrent
+        let second = QueueItem::new(
+            first.movie_id,
+            Uuid::new_v4(),
+            "Movie 2024 1080p".to_string(),
+            "http://other-indexer/download/9".to_string(),
+        );
+        repo.add_queue_item(&second).await.unwrap();
+        let error = processor.start_download(&second).await.unwrap_err();
+        assert!(error.to_string().contains("Same download as queued"));
+    }
+
+    #[tokio::test]
+    async fn test_sync_skips_unchanged_progress() {
+        let repo = Arc::new(MockQueueRepository::new());
+        let client = Arc::new(MockDownloadClient);
+
+        let mut queue_item = QueueItem::new(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            "Test Movie".to_string(),
+            "magnet:test".to_string(),
+        );
+        queue_item.status = QueueStatus::Downloading;
+        queue_item.download_client_id = Some("mock_client_id_123".to_string());
+        repo.add_queue_item(&queue_item).await.unwrap();
+
+        let processor = QueueProcessor::new(QueueProcessorConfig::default(), repo.clone(), client);
+
+        // First cycle moves progress from 0.0 to 0.5 and persists it
+        assert_eq!(processor.sync_with_download_client().await.unwrap(), 1);
+        // Client still reports 0.5, so nothing is written
+        assert_eq!(processor.sync_with_download_client().await.unwrap(), 0);
+
+        let stats = processor.sync_metrics().snapshot();
+        assert_eq!(stats.sync_cycles, 2);
+        assert_eq!(stats.items_polled, 2);
+        assert_eq!(stats.items_written, 1);
+        assert_eq!(stats.writes_skipped, 1);
+        assert_eq!(stats.batch_writes, 1);
+        assert_eq!(stats.writes_saved, 1);
+    }
+
+    #[tokio::test]
+    async fn test_slow_download_is_failed_as_stalled() {
+        let repo = Arc::new(MockQueueRepository::new());
+        let mut queue_item = QueueItem::new(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            "Test Movie".to_string(),
+            "magnet:test".to_string(),
+        );
+        queue_item.status = QueueStatus::Downloading;
+        queue_item.download_client_id = Some("mock_client_id_123".to_string());
+        repo.add_queue_item(&queue_item).await.unwrap();
+
+        // The mock client reports 1 MiB/s, below this minimum
+        let config = QueueProcessorConfig {
+            stall_detection: StallDetectionConfig {
+                min_speed_bytes_per_sec: 2 * 1024 * 1024,
+                stall_minutes: 0,
+                ..StallDetectionConfig::default()
+            },
+            ..QueueProcessorConfig::default()
+        };
+        let processor = QueueProcessor::new(config, repo.clone(), Arc::new(MockDownloadClient));
+        processor.sync_with_download_client().await.unwrap();
+
+        let item = repo.get_queue_item(queue_item.id).await.unwrap().unwrap();
+        assert_eq!(item.status, QueueStatus::Failed);
+        assert!(item.error_message.unwrap().contains("stalled"));
+    }
+
+    /// Sequential download of a single file whose progress the test sets
+    struct SequentialClient {
+        save_path: std::path::PathBuf,
+        progress: RwLock<f64>,
+    }
+
+    #[async_trait]
+    impl DownloadClientService for SequentialClient {
+        async fn add_download(
+            &self,
+            _download_url: &str,
+            _category: Option<String>,
+            _save_path: Option<String>,
+        ) -> Result<String> {
+            Ok("seq".to_string())
+        }
+
+        async fn get_download_status(
+            &self,
+            client_id: &str,
+        ) -> Result<Option<ClientDownloadStatus>> {
+            let progress = *self.progress.read().unwrap();
+            Ok(Some(ClientDownloadStatus {
+                client_id: client_id.to_string(),
+                name: "Movie.mkv".to_string(),
+                status: if progress >= 1.0 {
+                    "uploading"
+                } else {
+                    "downloading"
+                }
+                .to_string(),
+                progress,
+                download_speed: None,
+                upload_speed: None,
+                downloaded_bytes: None,
+                upload_bytes: None,
+                eta_seconds: None,
+                seeders: None,
+                leechers: None,
+                save_path: Some(self.save_path.display().to_string()),
+                ratio: None,
+                seeding_time_seconds: None,
+            }))
+        }
+
+        async fn remove_download(&self, _client_id: &str, _delete_files: bool) -> Result<()> {
+            Ok(())
+        }
+
+        async fn pause_download(&self, _client_id: &str) -> Result<()> {
+            Ok(())
+        }
+
+        async fn resume_download(&self, _client_id: &str) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_all_downloads(&self) -> Result<Vec<ClientDownloadStatus>> {
+            Ok(vec![])
+        }
+
+        async fn download_pieces(
+            &self,
+            _client_id: &str,
+        ) -> Result<Option<crate::services::DownloadPieces>> {
+            let downloaded = (*self.progress.read().unwrap() * 100.0).round() as usize;
+            Ok(Some(crate::services::DownloadPieces {
+                sequential: true,
+                save_path: self.save_path.display().to_string(),
+                files: vec![crate::services::DownloadFile {
+                    name: "Movie.mkv".to_string(),
+                    size: 100,
+                    first_piece: 0,
+                    last_piece: 99,
+                    skipped: false,
+                }],
+                downloaded: (0..100).map(|piece| piece < downloaded).collect(),
+            }))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_preview_staged_then_finalized_on_completion() {
+        let root = std::env::temp_dir().join(format!("radarr-queue-preview-{}", Uuid::new_v4()));
+        let downloads = root.join("downloads");
+        tokio::fs::create_dir_all(&downloads).await.unwrap();
+        tokio::fs::write(downloads.join("Movie.mkv"), b"data")
+            .await
+            .unwrap();
+
+        let repo = Arc::new(MockQueueRepository::new());
+        let client = Arc::new(SequentialClient {
+            save_path: downloads.clone(),
+            progress: RwLock::new(0.5),
+        });
+        let bus = Arc::new(EventBus::new());
+        let mut events = bus.subscribe();
+        let processor = QueueProcessor::new(
+            QueueProcessorConfig::default(),
+            repo.clone(),
+            client.clone(),
+        )
+        .with_event_bus(bus)
+        .with_preview_import(PreviewImportConfig::new(root.join("previews")));
+
+        let mut item = QueueItem::new(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            "Test Movie".to_string(),
+            "magnet:test".to_string(),
+        );
+        item.status = QueueStatus::Downloading;
+        item.download_client_id = Some("seq".to_string());
+        repo.add_queue_item(&item).await.unwrap();
+
+        processor.sync_with_download_client().await.unwrap();
+        let synced = repo.get_queue_item(item.id).await.unwrap().unwrap();
+        assert!(synced.preview_path.is_none());
+
+        *client.progress.write().unwrap() = 0.96;
+        processor.sync_with_download_client().await.unwrap();
+        let synced = repo.get_queue_item(item.id).await.unwrap().unwrap();
+        let preview_path = synced.preview_path.expect("preview staged");
+        assert_eq!(tokio::fs::read(&preview_path).await.unwrap(), b"data");
+
+        *client.progress.write().unwrap() = 1.0;
+        processor.sync_with_download_client().await.unwrap();
+        let synced = repo.get_queue_item(item.id).await.unwrap().unwrap();
+        assert_eq!(synced.status, QueueStatus::Completed);
+        assert!(synced.preview_path.is_none());
+        assert!(!tokio::fs::try_exists(&preview_path).await.unwrap());
+
+        match events.recv().await.unwrap().event {
+            SystemEvent::DownloadComplete { file_path, .. } => {
+                assert_eq!(file_path, downloads.join("Movie.mkv").display().to_string())
+            }
+            other => panic!("Unexpected event: {:?}", other),
+        }
+
+        tokio::fs::remove_dir_all(&root).await.unwrap();
+    }
+
     #[tokio::test]
     async fn test_process_queue_items() {
         let config = QueueProcessorConfig {
@@ -654,4 +1618,45 @@ mod tests {
         assert_eq!(updated_item.status, QueueStatus::Downloading);
         assert!(updated_item.download_client_id.is_some());
     }
+
+    #[tokio::test]
+    async fn test_low_space_holds_items_until_space_frees_up() {
+        use crate::models::RootFolder;
+        use crate::services::StorageThrottleConfig;
+
+        let repo = Arc::new(MockQueueRepository::new());
+        let throttle = Arc::new(StorageThrottle::new(StorageThrottleConfig {
+            threshold_bytes: 100,
+            resume_margin_bytes: 50,
+        }));
+        let mut folder = RootFolder::new("/media/movies".to_string());
+        folder.id = 1;
+        folder.min_free_space_bytes = 0;
+        throttle.record(&folder, 10);
+
+        let mut queue_item = QueueItem::new(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            "Test Movie".to_string(),
+            "magnet:test".to_string(),
+        );
+        queue_item.root_folder_path = Some("/media/movies/Test Movie (2023)".to_string());
+        repo.add_queue_item(&queue_item).await.unwrap();
+
+        let processor = QueueProcessor::new(
+            QueueProcessorConfig::default(),
+            repo.clone(),
+            Arc::new(MockDownloadClient),
+        )
+        .with_storage_throttle(throttle.clone());
+
+        assert_eq!(processor.process_queue_items().await.unwrap(), 0);
+        let held = repo.get_queue_item(queue_item.id).await.unwrap().unwrap();
+        assert_eq!(held.status, QueueStatus::Pending);
+
+        throttle.record(&folder, 200);
+        assert_eq!(processor.process_queue_items().await.unwrap(), 1);
+        let started = repo.get_queue_item(queue_item.id).await.unwrap().unwrap();
+        assert_eq!(started.status, QueueStatus::Downloading);
+    }
 }
@@ -0,0 +1,217 @@
+//! Removing downloads from the queue
+//!
+//! Removing a queue item also removes its download, with the downloaded
+//! data, from the download client unless asked not to. Blocklisting it as
+//! well marks the release as manually rejected so searches pass it over, and
+//! queues a search for another release of the movie unless the redownload is
+//! skipped.
+
+use crate::blocklist::{BlocklistRepository, BlocklistService, FailureReason};
+use crate::commands::{CommandName, CommandQueue, CommandTrigger};
+use crate::services::{DownloadClientService, QueueRepository, FAILED_DOWNLOAD_INDEXER};
+use crate::Result;
+use std::sync::Arc;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// How to remove a queue item
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueueRemovalOptions {
+    /// Remove the download and its data from the download client
+    pub remove_from_client: bool,
+    /// Blocklist the release so searches skip it
+    pub blocklist: bool,
+    /// Don't search for another release after blocklisting
+    pub skip_redownload: bool,
+}
+
+impl Default for QueueRemovalOptions {
+    fn default() -> Self {
+        Self {
+            remove_from_client: true,
+            blocklist: false,
+            skip_redownload: false,
+        }
+    }
+}
+
+/// What removing one queue item did
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QueueRemovalOutcome {
+    pub removed_from_client: bool,
+    pub blocklisted: bool,
+    /// ID of the queued `MoviesSearch` command
+    pub search_command_id: Option<i32>,
+}
+
+/// Removes items from the download queue
+pub struct QueueRemover {
+    queue_repo: Arc<dyn QueueRepository>,
+    download_client: Option<Arc<dyn DownloadClientService>>,
+    blocklist: BlocklistService<dyn BlocklistRepository>,
+    command_queue: Option<Arc<CommandQueue>>,
+}
+
+impl QueueRemover {
+    pub fn new(
+        queue_repo: Arc<dyn QueueRepository>,
+        blocklist_repo: Arc<dyn BlocklistRepository>,
+    ) -> Self {
+        Self {
+            queue_repo,
+            download_client: None,
+            blocklist: BlocklistService::new(blocklist_repo),
+            command_queue: None,
+        }
+    }
+
+    /// Remove downloads from this client
+    pub fn with_download_client(mut self, client: Arc<dyn DownloadClientService>) -> Self {
+        self.download_client = Some(client);
+        self
+    }
+
+    /// Queue `MoviesSearch` commands here after blocklisting
+    pub fn with_command_queue(mut self, queue: Arc<CommandQueue>) -> Self {
+        self.command_queue = Some(queue);
+        self
+    }
+
+    /// Remove a queue item; `None` if there is no such item
+    ///
+    /// When the download client fails to remove the download, the item is
+    /// left in the queue and the error returned.
+    pub async fn remove(
+        &self,
+        queue_item_id: Uuid,
+        options: &QueueRemovalOptions,
+    ) -> Result<Option<QueueRemovalOutcome>> {
+        let Some(item) = self.queue_repo.get_queue_item(queue_item_id).await? else {
+            return Ok(None);
+        };
+        let mut outcome = QueueRemovalOutcome::default();
+
+        if options.remove_from_client {
+            match (&self.download_client, &item.download_client_id) {
+                (Some(client), Some(client_id)) => {
+                    client.remove_download(client_id, true).await?;
+                    outcome.removed_from_client = true;
+                }
+                (None, Some(_)) => warn!(
+                    "No download client configured, leaving '{}' in the client",
+                    item.title
+                ),
+                _ => {}
+            }
+        }
+
+        if options.blocklist {
+            self.blocklist
+                .block_release(
+                    item.release_id.to_string(),
+                    FAILED_DOWNLOAD_INDEXER,
+                    FailureReason::ManuallyRejected,
+                    item.title.clone(),
+                    Some(item.movie_id),
+                    Some(serde_json::json!({
+                        "download_url": item.download_url,
+                        "queue_item_id": item.id,
+                    })),
+                )
+                .await?;
+            outcome.blocklisted = true;
+        }
+
+        self.queue_repo.delete_queue_item(item.id).await?;
+        info!(
+            "Removed '{}' from the queue (client: {}, blocklisted: {})",
+            item.title, outcome.removed_from_client, outcome.blocklisted
+        );
+
+        if options.blocklist && !options.skip_redownload {
+            outcome.search_command_id = self.search_again(item.movie_id).await;
+        }
+
+        Ok(Some(outcome))
+    }
+
+    /// Queue a search for another release of the movie
+    async fn search_again(&self, movie_id: Uuid) -> Option<i32> {
+        let queue = self.command_queue.as_ref()?;
+        match queue
+            .enqueue(
+                CommandName::MoviesSearch,
+                serde_json::json!({ "movieIds": [movie_id] }),
+                CommandTrigger::Manual,
+            )
+            .await
+        {
+            Ok(command) => Some(command.id),
+            Err(e) => {
+                warn!("Could not search again for movie {}: {}", movie_id, e);
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocklist::tests::MockBlocklistRepository;
+    use crate::services::test_support::{queued_item, MemoryQueue, NoopSearch, RecordingClient};
+
+    #[tokio::test]
+    async fn test_remove_with_blocklist_searches_again() {
+        let queue = Arc::new(MemoryQueue::default());
+        let client = Arc::new(RecordingClient::default());
+        let blocklist = Arc::new(MockBlocklistRepository::new());
+        let commands = Arc::new(CommandQueue::new(1));
+        commands.register(CommandName::MoviesSearch, Arc::new(NoopSearch));
+        let remover = QueueRemover::new(queue.clone(), blocklist.clone())
+            .with_download_client(client.clone())
+            .with_command_queue(commands);
+
+        let item = queued_item(&queue).await;
+        let options = QueueRemovalOptions {
+            blocklist: true,
+            ..Default::default()
+        };
+        let outcome = remover.remove(item.id, &options).await.unwrap().unwrap();
+
+        assert!(outcome.removed_from_client && outcome.blocklisted);
+        assert!(outcome.search_command_id.is_some());
+        assert!(queue.get_queue_item(item.id).await.unwrap().is_none());
+        assert_eq!(
+            client.removed.lock().unwrap().as_slice(),
+            &[("hash".to_string(), true)]
+        );
+        let entries = blocklist
+            .get_entries_for_movie(item.movie_id)
+            .await
+            .unwrap();
+        assert_eq!(entries[0].reason, FailureReason::ManuallyRejected);
+
+        // Already gone
+        assert_eq!(remover.remove(item.id, &options).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_remove_keeping_download_in_client() {
+        let queue = Arc::new(MemoryQueue::default());
+        let client = Arc::new(RecordingClient::default());
+        let remover = QueueRemover::new(queue.clone(), Arc::new(MockBlocklistRepository::new()))
+            .with_download_client(client.clone());
+
+        let item = queued_item(&queue).await;
+        let options = QueueRemovalOptions {
+            remove_from_client: false,
+            ..Default::default()
+        };
+        let outcome = remover.remove(item.id, &options).await.unwrap().unwrap();
+
+        assert_eq!(outcome, QueueRemovalOutcome::default());
+        assert!(queue.get_queue_item(item.id).await.unwrap().is_none());
+        assert!(client.removed.lock().unwrap().is_empty());
+    }
+}
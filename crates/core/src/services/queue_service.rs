@@ -4,8 +4,10 @@
 //! monitoring progress, and coordinating with download clients.
 
 use crate::models::{Movie, QueueItem, QueuePriority, QueueStats, QueueStatus, Release};
+use crate::services::{DownloadFile, DownloadPieces, StorageThrottle};
 use crate::{RadarrError, Result};
 use async_trait::async_trait;
+use std::sync::Arc;
 use uuid::Uuid;
 
 /// Repository trait for queue data persistence
@@ -29,6 +31,17 @@ pub trait QueueRepository: Send + Sync {
     /// Update queue item
     async fn update_queue_item(&self, item: &QueueItem) -> Result<()>;
 
+    /// Persist status and progress for several items in one write
+    ///
+    /// The default issues one update per item; database-backed repositories
+    /// override it with a single multi-row statement.
+    async fn update_queue_items_batch(&self, items: &[QueueItem]) -> Result<usize> {
+        for item in items {
+            self.update_queue_item(item).await?;
+        }
+        Ok(items.len())
+    }
+
     /// Delete queue item
     async fn delete_queue_item(&self, id: Uuid) -> Result<()>;
 
@@ -50,6 +63,20 @@ pub trait DownloadClientService: Send + Sync {
         save_path: Option<String>,
     ) -> Result<String>; // Returns client-specific ID
 
+    /// Add a download grabbed from `indexer_id`
+    ///
+    /// Clients that route grabs by indexer override this; the rest ignore
+    /// the indexer.
+    async fn add_download_from_indexer(
+        &self,
+        download_url: &str,
+        _indexer_id: Option<i32>,
+        category: Option<String>,
+        save_path: Option<String>,
+    ) -> Result<String> {
+        self.add_download(download_url, category, save_path).await
+    }
+
     /// Get download status from client
     async fn get_download_status(&self, client_id: &str) -> Result<Option<ClientDownloadStatus>>;
 
@@ -64,6 +91,50 @@ pub trait DownloadClientService: Send + Sync {
 
     /// Get all downloads from client
     async fn get_all_downloads(&self) -> Result<Vec<ClientDownloadStatus>>;
+
+    /// Free space (bytes) where `download_url` would be saved
+    ///
+    /// `None` means the client cannot report it and grabs are not checked.
+    async fn free_space(&self, _download_url: &str) -> Result<Option<u64>> {
+        Ok(None)
+    }
+
+    /// Piece-level state of a download, for early import previews
+    ///
+    /// `None` means the client cannot report pieces and is never previewed.
+    async fn download_pieces(&self, _client_id: &str) -> Result<Option<DownloadPieces>> {
+        Ok(None)
+    }
+
+    /// Files of a download, in the client's file order
+    ///
+    /// `None` means the client cannot list files and nothing is skipped.
+    async fn download_files(&self, _client_id: &str) -> Result<Option<Vec<DownloadFile>>> {
+        Ok(None)
+    }
+
+    /// Stop downloading the files at `indices` of the client's file order
+    async fn skip_files(&self, _client_id: &str, _indices: &[usize]) -> Result<()> {
+        Ok(())
+    }
+
+    /// What `download_url` holds, read before it is sent to the client
+    ///
+    /// `None` means the client cannot tell and grabs are not checked.
+    async fn inspect_download(&self, _download_url: &str) -> Result<Option<DownloadMetadata>> {
+        Ok(None)
+    }
+}
+
+/// A download's identity and contents, known before it is added
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DownloadMetadata {
+    /// ID the client will know the download by, e.g. the torrent's info hash
+    pub client_id: String,
+    pub name: Option<String>,
+    pub size_bytes: Option<u64>,
+    /// Paths and sizes of the files, when known
+    pub files: Vec<(String, u64)>,
 }
 
 /// Download status information from client
@@ -81,12 +152,17 @@ pub struct ClientDownloadStatus {
     pub seeders: Option<i32>,
     pub leechers: Option<i32>,
     pub save_path: Option<String>,
+    /// Uploaded divided by downloaded, for seeding goals
+    pub ratio: Option<f64>,
+    /// Time spent seeding since the download completed
+    pub seeding_time_seconds: Option<i64>,
 }
 
 /// Queue service for managing downloads
 pub struct QueueService<Q: QueueRepository, D: DownloadClientService> {
     queue_repo: Q,
     download_client: D,
+    storage_throttle: Option<Arc<StorageThrottle>>,
 }
 
 impl<Q: QueueRepository, D: DownloadClientService> QueueService<Q, D> {
@@ -95,9 +171,16 @@ impl<Q: QueueRepository, D: DownloadClientService> QueueService<Q, D> {
         Self {
             queue_repo,
             download_client,
+            storage_throttle: None,
         }
     }
 
+    /// Hold grabs for root folders that are low on space as pending
+    pub fn with_storage_throttle(mut self, throttle: Arc<StorageThrottle>) -> Self {
+        self.storage_throttle = Some(throttle);
+        self
+    }
+
     /// Add a release to the download queue
     pub async fn grab_release(
         &self,
@@ -132,12 +215,28 @@ impl<Q: QueueRepository, D: DownloadClientService> QueueService<Q, D> {
             queue_item.category = Some(cat);
         }
 
+        // Wait for space when the movie's root folder is running out
+        queue_item.root_folder_path = movie.root_folder_path().map(str::to_string);
+        if let (Some(throttle), Some(path)) = (&self.storage_throttle, &queue_item.root_folder_path)
+        {
+            if let Some(space) = throttle.hold_for(path) {
+                tracing::info!(
+                    "Holding {} as pending: {} has {} bytes free",
+                    queue_item.title,
+                    space.path,
+                    space.free_bytes
+                );
+                queue_item.status = QueueStatus::Pending;
+            }
+        }
+
         // Save to database
         self.queue_repo.add_queue_item(&queue_item).await?;
 
         // Start download immediately if high priority
-        if queue_item.priority == QueuePriority::High
-            || queue_item.priority == QueuePriority::VeryHigh
+        if queue_item.status == QueueStatus::Queued
+            && (queue_item.priority == QueuePriority::High
+                || queue_item.priority == QueuePriority::VeryHigh)
         {
             self.process_queue_item(queue_item.id).await?;
         }
@@ -166,8 +265,9 @@ impl<Q: QueueRepository, D: DownloadClientService> QueueService<Q, D> {
         // Add to download client
         let client_id = self
             .download_client
-            .add_download(
+            .add_download_from_indexer(
                 &queue_item.download_url,
+                queue_item.indexer_id,
                 queue_item.category.clone(),
                 queue_item.download_path.clone(),
             )
@@ -476,6 +576,7 @@ impl<Q: QueueRepository, D: DownloadClientService> QueueService<Q, D> {
 mod tests {
     use super::*;
     use crate::models::{Movie, Release, ReleaseProtocol};
+    use crate::services::StorageThrottleConfig;
     use std::collections::HashMap;
     use std::sync::Arc;
     use tokio::sync::RwLock;
@@ -608,6 +709,8 @@ mod tests {
                 seeders: Some(10),
                 leechers: Some(5),
                 save_path: Some("/downloads/movies".to_string()),
+                ratio: None,
+                seeding_time_seconds: None,
             }))
         }
 
@@ -695,4 +798,46 @@ mod tests {
         assert_eq!(updated_item.status, QueueStatus::Downloading);
         assert!(updated_item.download_client_id.is_some());
     }
+
+    #[tokio::test]
+    async fn test_grab_release_pending_while_root_folder_low() {
+        let throttle = Arc::new(StorageThrottle::new(StorageThrottleConfig {
+            threshold_bytes: 100,
+            resume_margin_bytes: 0,
+        }));
+        let mut folder = crate::models::RootFolder::new("/media/movies".to_string());
+        folder.id = 1;
+        throttle.record(&folder, 10);
+
+        let service = QueueService::new(MockQueueRepository::new(), MockDownloadClient)
+            .with_storage_throttle(throttle.clone());
+
+        let mut movie = Movie::new(123, "Test Movie".to_string());
+        movie.metadata["root_folder_path"] = serde_json::json!("/media/movies");
+        let release = Release::new(
+            1,
+            "Test Movie 2023 1080p".to_string(),
+            "magnet:?xt=urn:btih:test".to_string(),
+            "test-guid".to_string(),
+            ReleaseProtocol::Torrent,
+        );
+
+        let queue_item = service
+            .grab_release(&movie, &release, Some(QueuePriority::High), None)
+            .await
+            .unwrap();
+        assert_eq!(queue_item.status, QueueStatus::Pending);
+        assert_eq!(
+            queue_item.root_folder_path.as_deref(),
+            Some("/media/movies")
+        );
+        assert!(service.process_queue_item(queue_item.id).await.is_err());
+
+        throttle.set_override(1, true);
+        let queue_item = service
+            .grab_release(&movie, &release, None, None)
+            .await
+            .unwrap();
+        assert_eq!(queue_item.status, QueueStatus::Queued);
+    }
 }
@@ -0,0 +1,137 @@
+//! Per-movie automatic search cooldown
+//!
+//! Movies that keep coming back empty are searched less and less often so
+//! backlog searches don't hammer indexers: daily at first, then weekly, then
+//! monthly. A successful search resets the movie back to the daily tier.
+
+use crate::models::Movie;
+use chrono::{DateTime, Duration, Utc};
+
+/// Cooldown policy applied between automatic searches of the same movie
+#[derive(Debug, Clone)]
+pub struct SearchCooldownPolicy {
+    /// Spacing while the movie has failed fewer than `weekly_after` searches
+    pub daily_interval: Duration,
+    /// Spacing once the movie has failed `weekly_after` searches
+    pub weekly_interval: Duration,
+    /// Spacing once the movie has failed `monthly_after` searches
+    pub monthly_interval: Duration,
+    /// Failed searches before moving to the weekly tier
+    pub weekly_after: i32,
+    /// Failed searches before moving to the monthly tier
+    pub monthly_after: i32,
+}
+
+impl Default for SearchCooldownPolicy {
+    fn default() -> Self {
+        Self {
+            daily_interval: Duration::days(1),
+            weekly_interval: Duration::weeks(1),
+            monthly_interval: Duration::days(30),
+            weekly_after: 7,
+            monthly_after: 11,
+        }
+    }
+}
+
+impl SearchCooldownPolicy {
+    /// Cooldown to apply after the given number of consecutive failed searches
+    pub fn cooldown_for(&self, failed_search_count: i32) -> Duration {
+        if failed_search_count >= self.monthly_after {
+            self.monthly_interval
+        } else if failed_search_count >= self.weekly_after {
+            self.weekly_interval
+        } else {
+            self.daily_interval
+        }
+    }
+
+    /// When the movie becomes eligible for its next automatic search.
+    ///
+    /// Returns `None` when the movie has never been searched and is due now.
+    pub fn next_search_time(&self, movie: &Movie) -> Option<DateTime<Utc>> {
+        movie
+            .last_search_time
+            .map(|last| last + self.cooldown_for(movie.failed_search_count))
+    }
+
+    /// Check whether an automatic search is allowed at `now`
+    pub fn is_due(&self, movie: &Movie, now: DateTime<Utc>) -> bool {
+        match self.next_search_time(movie) {
+            Some(next) => now >= next,
+            None => true,
+        }
+    }
+
+    /// Select the movies due for a backlog search, least recently searched first
+    pub fn select_due(&self, movies: Vec<Movie>, now: DateTime<Utc>) -> Vec<Movie> {
+        let mut due: Vec<Movie> = movies
+            .into_iter()
+            .filter(|movie| self.is_due(movie, now))
+            .collect();
+        due.sort_by_key(|movie| movie.last_search_time);
+        due
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn searched_movie(failed_search_count: i32, hours_ago: i64) -> Movie {
+        let mut movie = Movie::new(603, "The Matrix".to_string());
+        movie.last_search_time = Some(Utc::now() - Duration::hours(hours_ago));
+        movie.failed_search_count = failed_search_count;
+        movie
+    }
+
+    #[test]
+    fn test_cooldown_tiers() {
+        let policy = SearchCooldownPolicy::default();
+        assert_eq!(policy.cooldown_for(0), Duration::days(1));
+        assert_eq!(policy.cooldown_for(6), Duration::days(1));
+        assert_eq!(policy.cooldown_for(7), Duration::weeks(1));
+        assert_eq!(policy.cooldown_for(11), Duration::days(30));
+    }
+
+    #[test]
+    fn test_is_due() {
+        let policy = SearchCooldownPolicy::default();
+        let now = Utc::now();
+
+        let never_searched = Movie::new(1, "New".to_string());
+        assert!(policy.next_search_time(&never_searched).is_none());
+        assert!(policy.is_due(&never_searched, now));
+
+        assert!(policy.is_due(&searched_movie(1, 25), now));
+        assert!(!policy.is_due(&searched_movie(1, 12), now));
+        // Weekly tier: two days is not enough
+        assert!(!policy.is_due(&searched_movie(8, 48), now));
+    }
+
+    #[test]
+    fn test_record_search_resets_on_success() {
+        let mut movie = searched_movie(9, 200);
+        movie.record_search(false);
+        assert_eq!(movie.failed_search_count, 10);
+        movie.record_search(true);
+        assert_eq!(movie.failed_search_count, 0);
+        assert!(movie.last_search_time.unwrap() > Utc::now() - Duration::minutes(1));
+    }
+
+    #[test]
+    fn test_select_due_orders_oldest_first() {
+        let policy = SearchCooldownPolicy::default();
+        let movies = vec![
+            searched_movie(0, 30),
+            searched_movie(0, 2),
+            Movie::new(2, "Never".to_string()),
+            searched_movie(0, 90),
+        ];
+
+        let due = policy.select_due(movies, Utc::now());
+        assert_eq!(due.len(), 3);
+        assert!(due[0].last_search_time.is_none());
+        assert!(due[1].last_search_time < due[2].last_search_time);
+    }
+}
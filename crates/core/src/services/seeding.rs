@@ -0,0 +1,450 @@
+//! Seeding policy enforcement after import
+//!
+//! Imported torrents keep seeding until they meet their seed goals. When an
+//! import completes, the movie's completed queue items move to `Seeding`;
+//! the `SeedingCleanup` command then checks each of them against the seed
+//! ratio and time of the indexer the release came from (falling back to
+//! [`SeedingConfig`]) and removes the torrents that are done. Downloaded data
+//! is only deleted along with the torrent when the library holds a hardlink
+//! of it, so removing a torrent never loses the imported movie.
+
+use crate::domain::repositories::IndexerRepository;
+use crate::events::{EventEnvelope, EventHandler, SystemEvent};
+use crate::models::{QueueItem, QueueStatus};
+use crate::services::{ClientDownloadStatus, DownloadClientService, QueueRepository};
+use crate::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+/// Default seed goals and what happens once they are met
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SeedingConfig {
+    /// Remove imported torrents that met their seed goals
+    pub enabled: bool,
+    /// Seed ratio for indexers without their own
+    pub seed_ratio: Option<f64>,
+    /// Minutes to seed for indexers without their own
+    pub seed_time_minutes: Option<u64>,
+    /// Delete the downloaded data with the torrent when it is hardlinked
+    /// into the library
+    pub delete_hardlinked_data: bool,
+    /// How often seeding torrents are checked
+    pub check_interval_minutes: u64,
+}
+
+impl Default for SeedingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            seed_ratio: None,
+            seed_time_minutes: None,
+            delete_hardlinked_data: true,
+            check_interval_minutes: 15,
+        }
+    }
+}
+
+/// Seed goals of a torrent; it is done once either goal is met
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SeedCriteria {
+    pub ratio: Option<f64>,
+    pub seed_time_minutes: Option<u64>,
+}
+
+impl SeedCriteria {
+    /// Goals from an indexer's `seed_ratio` and `seed_time_minutes` settings
+    pub fn from_indexer_settings(settings: &serde_json::Value) -> Self {
+        Self {
+            ratio: settings.get("seed_ratio").and_then(|v| v.as_f64()),
+            seed_time_minutes: settings.get("seed_time_minutes").and_then(|v| v.as_u64()),
+        }
+    }
+
+    /// No goals, so the torrent seeds until removed by hand
+    pub fn is_empty(&self) -> bool {
+        self.ratio.is_none() && self.seed_time_minutes.is_none()
+    }
+
+    /// Whether the download met either goal
+    pub fn is_met(&self, status: &ClientDownloadStatus) -> bool {
+        let ratio_met = self
+            .ratio
+            .zip(status.ratio)
+            .is_some_and(|(goal, ratio)| ratio >= goal);
+        let time_met = self
+            .seed_time_minutes
+            .zip(status.seeding_time_seconds)
+            .is_some_and(|(goal, seconds)| seconds >= 0 && seconds as u64 >= goal * 60);
+        ratio_met || time_met
+    }
+}
+
+/// What a cleanup run did
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SeedingCleanupReport {
+    /// Seeding torrents checked
+    pub checked: usize,
+    /// Torrents removed after meeting their goals
+    pub removed: usize,
+    /// Removed torrents whose data was deleted too
+    pub data_deleted: usize,
+    /// Queue items whose torrent was already gone from the client
+    pub gone: usize,
+}
+
+/// Tracks imported torrents and removes them once they seeded enough
+pub struct SeedingManager {
+    config: SeedingConfig,
+    queue_repo: Arc<dyn QueueRepository>,
+    download_client: Arc<dyn DownloadClientService>,
+    indexers: Option<Arc<dyn IndexerRepository>>,
+}
+
+impl SeedingManager {
+    pub fn new(
+        config: SeedingConfig,
+        queue_repo: Arc<dyn QueueRepository>,
+        download_client: Arc<dyn DownloadClientService>,
+    ) -> Self {
+        Self {
+            config,
+            queue_repo,
+            download_client,
+            indexers: None,
+        }
+    }
+
+    /// Read per-indexer seed goals from these indexers' settings
+    pub fn with_indexers(mut self, indexers: Arc<dyn IndexerRepository>) -> Self {
+        self.indexers = Some(indexers);
+        self
+    }
+
+    pub fn config(&self) -> &SeedingConfig {
+        &self.config
+    }
+
+    /// Move the movie's completed downloads to seeding after their import
+    pub async fn mark_imported(&self, movie_id: Uuid) -> Result<usize> {
+        let mut marked = 0;
+        for mut item in self.queue_repo.get_queue_items_for_movie(movie_id).await? {
+            if item.status != QueueStatus::Completed {
+                continue;
+            }
+            item.update_status(QueueStatus::Seeding);
+            self.queue_repo.update_queue_item(&item).await?;
+            debug!("Seeding imported download '{}'", item.title);
+            marked += 1;
+        }
+        Ok(marked)
+    }
+
+    /// Remove the seeding torrents that met their goals
+    pub async fn cleanup(&self) -> Result<SeedingCleanupReport> {
+        let mut report = SeedingCleanupReport::default();
+        if !self.config.enabled {
+            return Ok(report);
+        }
+
+        let items = self
+            .queue_repo
+            .get_queue_items(Some(QueueStatus::Seeding))
+            .await?;
+        for item in items {
+            let Some(client_id) = item.download_client_id.clone() else {
+                continue;
+            };
+            report.checked += 1;
+
+            let Some(status) = self.download_client.get_download_status(&client_id).await? else {
+                debug!("Seeding download '{}' left the client", item.title);
+                self.queue_repo.delete_queue_item(item.id).await?;
+                report.gone += 1;
+                continue;
+            };
+
+            let criteria = self.criteria(&item).await;
+            if criteria.is_empty() || !criteria.is_met(&status) {
+                continue;
+            }
+
+            let delete_files =
+                self.config.delete_hardlinked_data && self.is_hardlinked(&client_id, &status).await;
+            if let Err(e) = self
+                .download_client
+                .remove_download(&client_id, delete_files)
+                .await
+            {
+                warn!("Failed to remove seeded download '{}': {}", item.title, e);
+                continue;
+            }
+            self.queue_repo.delete_queue_item(item.id).await?;
+            info!(
+                "Removed '{}' after seeding (ratio {:.2}, data deleted: {})",
+                item.title,
+                status.ratio.unwrap_or_default(),
+                delete_files
+            );
+            report.removed += 1;
+            if delete_files {
+                report.data_deleted += 1;
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Seed goals of the indexer the item came from, else the defaults
+    async fn criteria(&self, item: &QueueItem) -> SeedCriteria {
+        let defaults = SeedCriteria {
+            ratio: self.config.seed_ratio,
+            seed_time_minutes: self.config.seed_time_minutes,
+        };
+        let (Some(indexers), Some(indexer_id)) = (&self.indexers, item.indexer_id) else {
+            return defaults;
+        };
+        match indexers.find_by_id(indexer_id).await {
+            Ok(Some(indexer)) => {
+                let criteria = SeedCriteria::from_indexer_settings(&indexer.settings);
+                SeedCriteria {
+                    ratio: criteria.ratio.or(defaults.ratio),
+                    seed_time_minutes: criteria.seed_time_minutes.or(defaults.seed_time_minutes),
+                }
+            }
+            Ok(None) => defaults,
+            Err(e) => {
+                warn!("Could not load indexer {}: {}", indexer_id, e);
+                defaults
+            }
+        }
+    }
+
+    /// Whether the download's main file has another link, i.e. was
+    /// hardlinked into the library on import
+    async fn is_hardlinked(&self, client_id: &str, status: &ClientDownloadStatus) -> bool {
+        let Some(save_path) = &status.save_path else {
+            return false;
+        };
+        let main_file = match self.download_client.download_files(client_id).await {
+            Ok(Some(files)) => files
+                .into_iter()
+                .filter(|file| !file.skipped)
+                .max_by_key(|file| file.size)
+                .map(|file| file.name),
+            _ => None,
+        }
+        .unwrap_or_else(|| status.name.clone());
+
+        match tokio::fs::metadata(Path::new(save_path).join(main_file)).await {
+            Ok(metadata) => metadata.is_file() && link_count(&metadata) > 1,
+            Err(_) => false,
+        }
+    }
+}
+
+#[cfg(unix)]
+fn link_count(metadata: &std::fs::Metadata) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    metadata.nlink()
+}
+
+#[cfg(not(unix))]
+fn link_count(_metadata: &std::fs::Metadata) -> u64 {
+    1
+}
+
+#[async_trait]
+impl EventHandler for SeedingManager {
+    async fn handle_event(&self, envelope: &EventEnvelope) -> Result<()> {
+        if let SystemEvent::ImportComplete { movie_id, .. } = &envelope.event {
+            self.mark_imported(*movie_id).await?;
+        }
+        Ok(())
+    }
+
+    fn should_handle(&self, envelope: &EventEnvelope) -> bool {
+        self.config.enabled && matches!(envelope.event, SystemEvent::ImportComplete { .. })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::test_support::MemoryQueue;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    /// Reports each torrent with the given ratio, saved under `save_path`
+    struct SeedingClient {
+        save_path: std::path::PathBuf,
+        ratios: HashMap<String, f64>,
+        removed: Mutex<Vec<(String, bool)>>,
+    }
+
+    #[async_trait]
+    impl DownloadClientService for SeedingClient {
+        async fn add_download(
+            &self,
+            _download_url: &str,
+            _category: Option<String>,
+            _save_path: Option<String>,
+        ) -> Result<String> {
+            Ok("hash".to_string())
+        }
+        async fn get_download_status(
+            &self,
+            client_id: &str,
+        ) -> Result<Option<ClientDownloadStatus>> {
+            Ok(self
+                .ratios
+                .get(client_id)
+                .map(|ratio| ClientDownloadStatus {
+                    client_id: client_id.to_string(),
+                    name: format!("{}.mkv", client_id),
+                    status: "uploading".to_string(),
+                    progress: 1.0,
+                    download_speed: None,
+                    upload_speed: None,
+                    downloaded_bytes: None,
+                    upload_bytes: None,
+                    eta_seconds: None,
+                    seeders: None,
+                    leechers: None,
+                    save_path: Some(self.save_path.display().to_string()),
+                    ratio: Some(*ratio),
+                    seeding_time_seconds: Some(600),
+                }))
+        }
+        async fn remove_download(&self, client_id: &str, delete_files: bool) -> Result<()> {
+            self.removed
+                .lock()
+                .unwrap()
+                .push((client_id.to_string(), delete_files));
+            Ok(())
+        }
+        async fn pause_download(&self, _client_id: &str) -> Result<()> {
+            Ok(())
+        }
+        async fn resume_download(&self, _client_id: &str) -> Result<()> {
+            Ok(())
+        }
+        async fn get_all_downloads(&self) -> Result<Vec<ClientDownloadStatus>> {
+            Ok(vec![])
+        }
+    }
+
+    fn completed_item(movie_id: Uuid, client_id: &str) -> QueueItem {
+        let mut item = QueueItem::new(
+            movie_id,
+            Uuid::new_v4(),
+            client_id.to_string(),
+            "magnet:?xt=urn:btih:abc".to_string(),
+        );
+        item.download_client_id = Some(client_id.to_string());
+        item.update_status(QueueStatus::Completed);
+        item
+    }
+
+    #[test]
+    fn test_seed_criteria_from_indexer_settings() {
+        let criteria = SeedCriteria::from_indexer_settings(&serde_json::json!({
+            "base_url": "http://localhost:9696",
+            "seed_ratio": 1.5,
+        }));
+        assert_eq!(criteria.ratio, Some(1.5));
+        assert_eq!(criteria.seed_time_minutes, None);
+        assert!(SeedCriteria::from_indexer_settings(&serde_json::json!({})).is_empty());
+
+        let mut status = ClientDownloadStatus {
+            client_id: "hash".to_string(),
+            name: "Movie.mkv".to_string(),
+            status: "uploading".to_string(),
+            progress: 1.0,
+            download_speed: None,
+            upload_speed: None,
+            downloaded_bytes: None,
+            upload_bytes: None,
+            eta_seconds: None,
+            seeders: None,
+            leechers: None,
+            save_path: None,
+            ratio: Some(1.2),
+            seeding_time_seconds: Some(3600),
+        };
+        assert!(!criteria.is_met(&status));
+        status.ratio = Some(1.5);
+        assert!(criteria.is_met(&status));
+
+        let time_only = SeedCriteria {
+            ratio: None,
+            seed_time_minutes: Some(60),
+        };
+        assert!(time_only.is_met(&status));
+        status.seeding_time_seconds = Some(59 * 60);
+        assert!(!time_only.is_met(&status));
+    }
+
+    #[tokio::test]
+    async fn test_imported_torrents_removed_once_seeded() {
+        let root = std::env::temp_dir().join(format!("radarr-seeding-{}", Uuid::new_v4()));
+        let library = root.join("library");
+        std::fs::create_dir_all(&library).unwrap();
+        // "linked" was hardlinked into the library on import, "copied" was not
+        std::fs::write(root.join("linked.mkv"), b"movie").unwrap();
+        std::fs::hard_link(root.join("linked.mkv"), library.join("linked.mkv")).unwrap();
+        std::fs::write(root.join("copied.mkv"), b"movie").unwrap();
+
+        let movie_id = Uuid::new_v4();
+        let queue = Arc::new(MemoryQueue::default());
+        for client_id in ["linked", "copied", "leeching"] {
+            queue
+                .add_queue_item(&completed_item(movie_id, client_id))
+                .await
+                .unwrap();
+        }
+        let client = Arc::new(SeedingClient {
+            save_path: root.clone(),
+            ratios: HashMap::from([
+                ("linked".to_string(), 2.0),
+                ("copied".to_string(), 1.0),
+                ("leeching".to_string(), 0.3),
+            ]),
+            removed: Mutex::new(Vec::new()),
+        });
+        let manager = SeedingManager::new(
+            SeedingConfig {
+                seed_ratio: Some(1.0),
+                ..SeedingConfig::default()
+            },
+            queue.clone(),
+            client.clone(),
+        );
+
+        // Nothing is seeding before the import
+        assert_eq!(manager.cleanup().await.unwrap().checked, 0);
+        assert_eq!(manager.mark_imported(movie_id).await.unwrap(), 3);
+
+        let report = manager.cleanup().await.unwrap();
+        assert_eq!(report.checked, 3);
+        assert_eq!(report.removed, 2);
+        assert_eq!(report.data_deleted, 1);
+
+        let mut removed = client.removed.lock().unwrap().clone();
+        removed.sort();
+        assert_eq!(
+            removed,
+            vec![("copied".to_string(), false), ("linked".to_string(), true)]
+        );
+        let remaining = queue.get_queue_items(None).await.unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].title, "leeching");
+        assert_eq!(remaining[0].status, QueueStatus::Seeding);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
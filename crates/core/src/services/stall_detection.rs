@@ -0,0 +1,215 @@
+//! Stalled and slow download detection
+//!
+//! The queue processor samples each active download's speed and ETA on every
+//! sync. A download counts as slow while its speed is below the configured
+//! minimum, or its ETA beyond the configured maximum; once it has been slow
+//! without interruption for the configured time it is stalled. Stalled
+//! downloads are failed, which hands them to failed download handling to be
+//! blocklisted and replaced with the next-best release.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use uuid::Uuid;
+
+/// Samples kept per download
+const MAX_SAMPLES: usize = 120;
+
+/// When a download counts as stalled
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StallDetectionConfig {
+    /// Fail and replace stalled downloads
+    pub enabled: bool,
+    /// Download speed (bytes/s) below which a download is slow
+    pub min_speed_bytes_per_sec: u64,
+    /// ETA (minutes) above which a download is slow; 0 to ignore the ETA
+    pub max_eta_minutes: u64,
+    /// Minutes a download must stay slow before it is stalled
+    pub stall_minutes: u64,
+}
+
+impl Default for StallDetectionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            min_speed_bytes_per_sec: 10 * 1024,
+            max_eta_minutes: 0,
+            stall_minutes: 30,
+        }
+    }
+}
+
+impl StallDetectionConfig {
+    fn is_slow(&self, speed: u64, eta_seconds: Option<i64>) -> bool {
+        let eta_too_long = self.max_eta_minutes > 0
+            && eta_seconds.is_some_and(|eta| eta < 0 || eta as u64 > self.max_eta_minutes * 60);
+        speed < self.min_speed_bytes_per_sec || eta_too_long
+    }
+}
+
+/// One speed and ETA reading of a download
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct SpeedSample {
+    pub at: DateTime<Utc>,
+    pub speed_bytes_per_sec: u64,
+    pub eta_seconds: Option<i64>,
+}
+
+/// Speed history of one download
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DownloadSpeedHistory {
+    pub samples: VecDeque<SpeedSample>,
+    /// Since when the download has been slow without interruption
+    pub slow_since: Option<DateTime<Utc>>,
+}
+
+impl DownloadSpeedHistory {
+    /// Average speed over the kept samples
+    pub fn average_speed(&self) -> u64 {
+        if self.samples.is_empty() {
+            return 0;
+        }
+        let total: u64 = self.samples.iter().map(|s| s.speed_bytes_per_sec).sum();
+        total / self.samples.len() as u64
+    }
+}
+
+/// Outcome of recording a sample
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StallState {
+    Healthy,
+    /// Slow since the given time, but not for long enough yet
+    Slow(DateTime<Utc>),
+    /// Slow for at least the configured time
+    Stalled(Duration),
+}
+
+/// Per-download speed and ETA history, deciding when downloads are stalled
+#[derive(Debug, Default)]
+pub struct DownloadSpeedTracker {
+    config: StallDetectionConfig,
+    downloads: HashMap<Uuid, DownloadSpeedHistory>,
+}
+
+impl DownloadSpeedTracker {
+    pub fn new(config: StallDetectionConfig) -> Self {
+        Self {
+            config,
+            downloads: HashMap::new(),
+        }
+    }
+
+    pub fn config(&self) -> &StallDetectionConfig {
+        &self.config
+    }
+
+    /// Record a sample for the queue item `id` and classify the download
+    pub fn record(
+        &mut self,
+        id: Uuid,
+        speed_bytes_per_sec: u64,
+        eta_seconds: Option<i64>,
+        at: DateTime<Utc>,
+    ) -> StallState {
+        let history = self.downloads.entry(id).or_default();
+        history.samples.push_back(SpeedSample {
+            at,
+            speed_bytes_per_sec,
+            eta_seconds,
+        });
+        while history.samples.len() > MAX_SAMPLES {
+            history.samples.pop_front();
+        }
+
+        if !self.config.is_slow(speed_bytes_per_sec, eta_seconds) {
+            history.slow_since = None;
+            return StallState::Healthy;
+        }
+        let slow_since = *history.slow_since.get_or_insert(at);
+        let slow_for = at - slow_since;
+        if slow_for >= Duration::minutes(self.config.stall_minutes as i64) {
+            StallState::Stalled(slow_for)
+        } else {
+            StallState::Slow(slow_since)
+        }
+    }
+
+    /// Speed history of the queue item `id`
+    pub fn history(&self, id: Uuid) -> Option<&DownloadSpeedHistory> {
+        self.downloads.get(&id)
+    }
+
+    /// Drop the history of a download that is no longer active
+    pub fn forget(&mut self, id: Uuid) {
+        self.downloads.remove(&id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn minutes(n: i64) -> DateTime<Utc> {
+        DateTime::<Utc>::UNIX_EPOCH + Duration::minutes(n)
+    }
+
+    #[test]
+    fn test_slow_download_stalls_after_configured_time() {
+        let mut tracker = DownloadSpeedTracker::new(StallDetectionConfig {
+            stall_minutes: 30,
+            ..StallDetectionConfig::default()
+        });
+        let id = Uuid::new_v4();
+
+        assert_eq!(
+            tracker.record(id, 500_000, Some(600), minutes(0)),
+            StallState::Healthy
+        );
+        assert_eq!(
+            tracker.record(id, 100, Some(86_400), minutes(5)),
+            StallState::Slow(minutes(5))
+        );
+        // A burst of speed resets the clock
+        assert_eq!(
+            tracker.record(id, 200_000, Some(900), minutes(20)),
+            StallState::Healthy
+        );
+        assert_eq!(
+            tracker.record(id, 0, None, minutes(25)),
+            StallState::Slow(minutes(25))
+        );
+        assert_eq!(
+            tracker.record(id, 0, None, minutes(55)),
+            StallState::Stalled(Duration::minutes(30))
+        );
+        assert_eq!(tracker.history(id).unwrap().samples.len(), 5);
+        assert_eq!(tracker.history(id).unwrap().average_speed(), 140_020);
+
+        tracker.forget(id);
+        assert!(tracker.history(id).is_none());
+    }
+
+    #[test]
+    fn test_long_eta_counts_as_slow_when_configured() {
+        let mut tracker = DownloadSpeedTracker::new(StallDetectionConfig {
+            max_eta_minutes: 120,
+            stall_minutes: 10,
+            ..StallDetectionConfig::default()
+        });
+        let id = Uuid::new_v4();
+
+        assert_eq!(
+            tracker.record(id, 50_000, Some(3 * 3600), minutes(0)),
+            StallState::Slow(minutes(0))
+        );
+        assert_eq!(
+            tracker.record(id, 50_000, Some(3 * 3600), minutes(10)),
+            StallState::Stalled(Duration::minutes(10))
+        );
+        assert_eq!(
+            tracker.record(id, 50_000, Some(3600), minutes(15)),
+            StallState::Healthy
+        );
+    }
+}
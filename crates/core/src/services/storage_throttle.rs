@@ -0,0 +1,248 @@
+//! Storage-aware grab throttling
+//!
+//! Free space of every root folder is recorded here periodically. A folder
+//! whose free space drops below its threshold is throttled: new grabs for
+//! movies filed under it wait in the queue as pending instead of being sent
+//! to the download client. The folder is released again once its free space
+//! climbs back above the threshold plus a resume margin, so a folder hovering
+//! around the threshold does not flip on every check. A manual override lets
+//! grabs through regardless.
+
+use crate::models::RootFolder;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::RwLock;
+
+/// Thresholds applied to every root folder
+#[derive(Debug, Clone)]
+pub struct StorageThrottleConfig {
+    /// Grabs are held once free space drops below this; a root folder's own
+    /// minimum free space is used instead when it is larger
+    pub threshold_bytes: u64,
+    /// Free space above the threshold needed before grabs resume
+    pub resume_margin_bytes: u64,
+}
+
+impl Default for StorageThrottleConfig {
+    fn default() -> Self {
+        Self {
+            threshold_bytes: 20 * 1024 * 1024 * 1024,    // 20 GiB
+            resume_margin_bytes: 5 * 1024 * 1024 * 1024, // 5 GiB
+        }
+    }
+}
+
+/// Last recorded free space of a root folder
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RootFolderSpace {
+    pub root_folder_id: i32,
+    pub path: String,
+    pub free_bytes: u64,
+    pub threshold_bytes: u64,
+    /// Free space is below the threshold and has not recovered past the
+    /// resume margin yet
+    pub throttled: bool,
+    /// Grabs are let through even while throttled
+    pub overridden: bool,
+    pub checked_at: DateTime<Utc>,
+}
+
+impl RootFolderSpace {
+    /// Whether grabs for this folder are currently held
+    pub fn holds_grabs(&self) -> bool {
+        self.throttled && !self.overridden
+    }
+}
+
+/// Change in a root folder's throttle state caused by a measurement
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageChange {
+    /// Free space dropped below the threshold
+    Throttled,
+    /// Free space recovered past the resume margin
+    Resumed,
+}
+
+/// Free space per root folder, shared by the queue processor, the decision
+/// engine's callers and the API
+#[derive(Debug, Default)]
+pub struct StorageThrottle {
+    config: StorageThrottleConfig,
+    folders: RwLock<HashMap<i32, RootFolderSpace>>,
+}
+
+impl StorageThrottle {
+    pub fn new(config: StorageThrottleConfig) -> Self {
+        Self {
+            config,
+            folders: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Record a root folder's free space, returning how its state changed
+    pub fn record(&self, folder: &RootFolder, free_bytes: u64) -> Option<StorageChange> {
+        let threshold_bytes = self
+            .config
+            .threshold_bytes
+            .max(folder.min_free_space_bytes.max(0) as u64);
+
+        let mut folders = self.folders.write().unwrap_or_else(|e| e.into_inner());
+        let was_throttled = folders
+            .get(&folder.id)
+            .map(|space| space.throttled)
+            .unwrap_or(false);
+        let overridden = folders
+            .get(&folder.id)
+            .map(|space| space.overridden)
+            .unwrap_or(false);
+
+        let throttled = if was_throttled {
+            free_bytes < threshold_bytes.saturating_add(self.config.resume_margin_bytes)
+        } else {
+            free_bytes < threshold_bytes
+        };
+
+        folders.insert(
+            folder.id,
+            RootFolderSpace {
+                root_folder_id: folder.id,
+                path: folder.path.clone(),
+                free_bytes,
+                threshold_bytes,
+                throttled,
+                // An override only lasts for the low-space episode it was set in
+                overridden: overridden && throttled,
+                checked_at: Utc::now(),
+            },
+        );
+
+        match (was_throttled, throttled) {
+            (false, true) => Some(StorageChange::Throttled),
+            (true, false) => Some(StorageChange::Resumed),
+            _ => None,
+        }
+    }
+
+    /// Let grabs for a root folder through while it is low on space, or stop
+    /// doing so. Returns `None` for folders that have not been measured yet.
+    pub fn set_override(&self, root_folder_id: i32, overridden: bool) -> Option<RootFolderSpace> {
+        let mut folders = self.folders.write().unwrap_or_else(|e| e.into_inner());
+        let space = folders.get_mut(&root_folder_id)?;
+        space.overridden = overridden;
+        Some(space.clone())
+    }
+
+    /// Drop folders that are no longer configured
+    pub fn retain(&self, root_folder_ids: &[i32]) {
+        self.folders
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .retain(|id, _| root_folder_ids.contains(id));
+    }
+
+    /// The root folder holding back grabs for movies filed under `path`, if
+    /// any; the most specific measured folder containing the path decides
+    pub fn hold_for(&self, path: &str) -> Option<RootFolderSpace> {
+        let path = Path::new(path);
+        let folders = self.folders.read().unwrap_or_else(|e| e.into_inner());
+        folders
+            .values()
+            .filter(|space| path.starts_with(&space.path))
+            .max_by_key(|space| space.path.len())
+            .filter(|space| space.holds_grabs())
+            .cloned()
+    }
+
+    /// Every measured root folder, ordered by path
+    pub fn snapshot(&self) -> Vec<RootFolderSpace> {
+        let folders = self.folders.read().unwrap_or_else(|e| e.into_inner());
+        let mut spaces: Vec<RootFolderSpace> = folders.values().cloned().collect();
+        spaces.sort_by(|a, b| a.path.cmp(&b.path));
+        spaces
+    }
+
+    /// Health warnings for folders that are low on space
+    pub fn warnings(&self) -> Vec<String> {
+        self.snapshot()
+            .into_iter()
+            .filter(|space| space.throttled)
+            .map(|space| {
+                let action = if space.overridden {
+                    "grabs are let through by override"
+                } else {
+                    "new grabs are pending"
+                };
+                format!(
+                    "Root folder {} has {} bytes free, below the {} byte threshold; {}",
+                    space.path, space.free_bytes, space.threshold_bytes, action
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn folder(id: i32, path: &str) -> RootFolder {
+        let mut folder = RootFolder::new(path.to_string());
+        folder.id = id;
+        folder.min_free_space_bytes = 0;
+        folder
+    }
+
+    #[test]
+    fn test_throttles_and_resumes_with_margin() {
+        let throttle = StorageThrottle::new(StorageThrottleConfig {
+            threshold_bytes: 100,
+            resume_margin_bytes: 50,
+        });
+        let movies = folder(1, "/media/movies");
+
+        assert_eq!(throttle.record(&movies, 500), None);
+        assert!(throttle.hold_for("/media/movies/Dune (2021)").is_none());
+
+        assert_eq!(throttle.record(&movies, 90), Some(StorageChange::Throttled));
+        assert!(throttle.hold_for("/media/movies/Dune (2021)").is_some());
+        assert!(throttle.hold_for("/media/movies-4k/Dune (2021)").is_none());
+
+        // Back above the threshold but inside the margin: still held
+        assert_eq!(throttle.record(&movies, 120), None);
+        assert!(throttle.hold_for("/media/movies").is_some());
+
+        assert_eq!(throttle.record(&movies, 150), Some(StorageChange::Resumed));
+        assert!(throttle.hold_for("/media/movies").is_none());
+        assert!(throttle.warnings().is_empty());
+    }
+
+    #[test]
+    fn test_override_lets_grabs_through_until_space_recovers() {
+        let throttle = StorageThrottle::new(StorageThrottleConfig {
+            threshold_bytes: 100,
+            resume_margin_bytes: 0,
+        });
+        let movies = folder(1, "/media/movies");
+        let mut kids = folder(2, "/media/movies/kids");
+        kids.min_free_space_bytes = 1000;
+
+        assert!(throttle.set_override(1, true).is_none());
+
+        throttle.record(&movies, 10);
+        throttle.record(&kids, 500);
+        assert_eq!(throttle.snapshot()[1].threshold_bytes, 1000);
+
+        assert!(throttle.set_override(1, true).unwrap().overridden);
+        assert!(throttle.hold_for("/media/movies/Up (2009)").is_none());
+        // The deeper folder decides for paths inside it
+        assert!(throttle.hold_for("/media/movies/kids/Up (2009)").is_some());
+        assert_eq!(throttle.warnings().len(), 2);
+
+        throttle.record(&movies, 200);
+        throttle.record(&movies, 10);
+        assert!(throttle.hold_for("/media/movies/Up (2009)").is_some());
+    }
+}
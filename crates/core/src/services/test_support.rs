@@ -0,0 +1,124 @@
+//! In-memory queue, download client and search executor shared by the
+//! service tests
+
+use crate::commands::{Command, CommandExecutor, CommandProgress};
+use crate::models::{QueueItem, QueueStats, QueueStatus};
+use crate::services::{ClientDownloadStatus, DownloadClientService, QueueRepository};
+use crate::Result;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use uuid::Uuid;
+
+/// Queue repository backed by a map
+#[derive(Default)]
+pub struct MemoryQueue {
+    items: Mutex<HashMap<Uuid, QueueItem>>,
+}
+
+#[async_trait]
+impl QueueRepository for MemoryQueue {
+    async fn add_queue_item(&self, item: &QueueItem) -> Result<()> {
+        self.items.lock().unwrap().insert(item.id, item.clone());
+        Ok(())
+    }
+    async fn get_queue_item(&self, id: Uuid) -> Result<Option<QueueItem>> {
+        Ok(self.items.lock().unwrap().get(&id).cloned())
+    }
+    async fn get_queue_item_by_client_id(&self, _client_id: &str) -> Result<Option<QueueItem>> {
+        Ok(None)
+    }
+    async fn get_queue_items(&self, status: Option<QueueStatus>) -> Result<Vec<QueueItem>> {
+        Ok(self
+            .items
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|item| status.is_none_or(|status| item.status == status))
+            .cloned()
+            .collect())
+    }
+    async fn get_queue_items_for_movie(&self, movie_id: Uuid) -> Result<Vec<QueueItem>> {
+        Ok(self
+            .items
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|item| item.movie_id == movie_id)
+            .cloned()
+            .collect())
+    }
+    async fn update_queue_item(&self, item: &QueueItem) -> Result<()> {
+        self.add_queue_item(item).await
+    }
+    async fn delete_queue_item(&self, id: Uuid) -> Result<()> {
+        self.items.lock().unwrap().remove(&id);
+        Ok(())
+    }
+    async fn get_queue_stats(&self) -> Result<QueueStats> {
+        Ok(QueueStats::default())
+    }
+    async fn get_retry_items(&self) -> Result<Vec<QueueItem>> {
+        Ok(vec![])
+    }
+}
+
+/// Download client that records which downloads were removed
+#[derive(Default)]
+pub struct RecordingClient {
+    pub removed: Mutex<Vec<(String, bool)>>,
+}
+
+#[async_trait]
+impl DownloadClientService for RecordingClient {
+    async fn add_download(
+        &self,
+        _download_url: &str,
+        _category: Option<String>,
+        _save_path: Option<String>,
+    ) -> Result<String> {
+        Ok("hash".to_string())
+    }
+    async fn get_download_status(&self, _client_id: &str) -> Result<Option<ClientDownloadStatus>> {
+        Ok(None)
+    }
+    async fn remove_download(&self, client_id: &str, delete_files: bool) -> Result<()> {
+        self.removed
+            .lock()
+            .unwrap()
+            .push((client_id.to_string(), delete_files));
+        Ok(())
+    }
+    async fn pause_download(&self, _client_id: &str) -> Result<()> {
+        Ok(())
+    }
+    async fn resume_download(&self, _client_id: &str) -> Result<()> {
+        Ok(())
+    }
+    async fn get_all_downloads(&self) -> Result<Vec<ClientDownloadStatus>> {
+        Ok(vec![])
+    }
+}
+
+/// Search command executor that does nothing
+pub struct NoopSearch;
+
+#[async_trait]
+impl CommandExecutor for NoopSearch {
+    async fn execute(&self, _command: &Command, _progress: &CommandProgress) -> Result<String> {
+        Ok("searched".to_string())
+    }
+}
+
+/// Add a queue item grabbed by [`RecordingClient`] as `hash`
+pub async fn queued_item(queue: &MemoryQueue) -> QueueItem {
+    let mut item = QueueItem::new(
+        Uuid::new_v4(),
+        Uuid::new_v4(),
+        "Dune.2021.1080p.BluRay.x264".to_string(),
+        "http://indexer/download/1".to_string(),
+    );
+    item.set_download_client_id("hash".to_string());
+    queue.add_queue_item(&item).await.unwrap();
+    item
+}
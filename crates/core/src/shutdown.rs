@@ -0,0 +1,134 @@
+//! Coordinated shutdown of background services
+//!
+//! Background loops register with a [`ShutdownCoordinator`] instead of being
+//! detached with `tokio::spawn`. Shutting down cancels the coordinator's
+//! token; loops notice it at their next wait point rather than mid-cycle, so
+//! an import or a batch of events that is already being handled finishes.
+//! The coordinator then waits for every registered task, up to a grace
+//! period, before the process exits.
+
+use std::future::Future;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio_util::sync::{CancellationToken, WaitForCancellationFuture};
+use tokio_util::task::TaskTracker;
+use tracing::debug;
+
+/// Seconds background services get to finish their work on shutdown
+pub const DEFAULT_SHUTDOWN_GRACE_SECS: u64 = 30;
+
+/// How a shutdown ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShutdownOutcome {
+    /// Every registered task finished
+    Drained,
+    /// The grace period ran out with tasks still running
+    TimedOut { remaining: usize },
+}
+
+/// Cancellation signal and registry of the tasks that must stop on shutdown
+///
+/// Clones share the same signal and tasks.
+#[derive(Debug, Clone, Default)]
+pub struct ShutdownCoordinator {
+    token: CancellationToken,
+    tracker: TaskTracker,
+}
+
+impl ShutdownCoordinator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Token cancelled when shutdown starts
+    pub fn token(&self) -> CancellationToken {
+        self.token.clone()
+    }
+
+    /// Whether shutdown has started
+    pub fn is_shutting_down(&self) -> bool {
+        self.token.is_cancelled()
+    }
+
+    /// Resolves once shutdown has started
+    pub fn cancelled(&self) -> WaitForCancellationFuture<'_> {
+        self.token.cancelled()
+    }
+
+    /// Sleep for `duration`, returning `false` if shutdown started first
+    pub async fn sleep(&self, duration: Duration) -> bool {
+        tokio::select! {
+            _ = self.token.cancelled() => false,
+            _ = tokio::time::sleep(duration) => true,
+        }
+    }
+
+    /// Run a task that shutdown waits for
+    ///
+    /// The task should watch [`cancelled`](Self::cancelled) and return once
+    /// its current work is done.
+    pub fn spawn<F>(&self, name: &'static str, task: F) -> JoinHandle<()>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.tracker.spawn(async move {
+            task.await;
+            debug!("Background task '{}' stopped", name);
+        })
+    }
+
+    /// Number of registered tasks still running
+    pub fn running_tasks(&self) -> usize {
+        self.tracker.len()
+    }
+
+    /// Signal shutdown and wait up to `grace` for registered tasks to finish
+    pub async fn shutdown(&self, grace: Duration) -> ShutdownOutcome {
+        self.token.cancel();
+        self.tracker.close();
+        match tokio::time::timeout(grace, self.tracker.wait()).await {
+            Ok(()) => ShutdownOutcome::Drained,
+            Err(_) => ShutdownOutcome::TimedOut {
+                remaining: self.tracker.len(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_shutdown_waits_for_tasks_to_finish_their_work() {
+        let coordinator = ShutdownCoordinator::new();
+        let finished = Arc::new(AtomicBool::new(false));
+
+        let task_coordinator = coordinator.clone();
+        let task_finished = finished.clone();
+        coordinator.spawn("worker", async move {
+            task_coordinator.cancelled().await;
+            // Work still in flight when shutdown starts
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            task_finished.store(true, Ordering::SeqCst);
+        });
+        assert_eq!(coordinator.running_tasks(), 1);
+
+        let outcome = coordinator.shutdown(Duration::from_secs(5)).await;
+        assert_eq!(outcome, ShutdownOutcome::Drained);
+        assert!(finished.load(Ordering::SeqCst));
+        assert!(coordinator.is_shutting_down());
+        assert!(!coordinator.sleep(Duration::from_secs(60)).await);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_gives_up_after_the_grace_period() {
+        let coordinator = ShutdownCoordinator::new();
+        coordinator.spawn("stuck", std::future::pending());
+
+        let outcome = coordinator.shutdown(Duration::from_millis(20)).await;
+        assert_eq!(outcome, ShutdownOutcome::TimedOut { remaining: 1 });
+    }
+}
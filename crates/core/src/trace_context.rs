@@ -0,0 +1,288 @@
+//! W3C trace context for outbound HTTP calls
+//!
+//! [`TraceContextLayer`] gives every span a trace ID, shared with its parent
+//! span, and a span ID of its own. Requests sent with
+//! [`TracedRequest::send_traced`] run in a client span and carry it in a
+//! `traceparent` header, so Prowlarr, qBittorrent and TMDB calls show up under
+//! the operation that made them, e.g. a grab, in Jaeger.
+//!
+//! When spans are exported through OpenTelemetry, the exporter installs its
+//! own source with [`install_trace_context_source`] so the header names the
+//! exported span instead.
+
+use reqwest::header::{HeaderMap, HeaderValue};
+use reqwest::{RequestBuilder, Response};
+use std::fmt;
+use std::future::Future;
+use std::sync::OnceLock;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing::{Instrument, Subscriber};
+use tracing_subscriber::{
+    layer::{Context, Layer},
+    registry::LookupSpan,
+    Registry,
+};
+
+/// Header carrying the trace context
+pub const TRACEPARENT_HEADER: &str = "traceparent";
+
+/// Position of a span in a distributed trace
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceContext {
+    pub trace_id: u128,
+    pub span_id: u64,
+    pub sampled: bool,
+}
+
+impl TraceContext {
+    /// Context of a span starting a new trace
+    pub fn root() -> Self {
+        Self {
+            trace_id: random_id(rand::random::<u128>),
+            span_id: random_id(rand::random::<u64>),
+            sampled: true,
+        }
+    }
+
+    /// Context of a span below this one in the same trace
+    pub fn child(&self) -> Self {
+        Self {
+            span_id: random_id(rand::random::<u64>),
+            ..*self
+        }
+    }
+
+    /// Parse a `traceparent` header value
+    ///
+    /// Only version `00` is understood; IDs that are all zeros are invalid.
+    pub fn parse(traceparent: &str) -> Option<Self> {
+        let mut parts = traceparent.trim().split('-');
+        let (version, trace_id, span_id, flags) =
+            (parts.next()?, parts.next()?, parts.next()?, parts.next()?);
+        if version != "00" || parts.next().is_some() {
+            return None;
+        }
+        if trace_id.len() != 32 || span_id.len() != 16 || flags.len() != 2 {
+            return None;
+        }
+
+        let trace_id = u128::from_str_radix(trace_id, 16).ok()?;
+        let span_id = u64::from_str_radix(span_id, 16).ok()?;
+        let flags = u8::from_str_radix(flags, 16).ok()?;
+        (trace_id != 0 && span_id != 0).then_some(Self {
+            trace_id,
+            span_id,
+            sampled: flags & 0x01 != 0,
+        })
+    }
+
+    /// Value of the `traceparent` header naming this span as the parent
+    pub fn traceparent(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl fmt::Display for TraceContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "00-{:032x}-{:016x}-{:02x}",
+            self.trace_id,
+            self.span_id,
+            u8::from(self.sampled)
+        )
+    }
+}
+
+/// Draw a random ID, skipping the invalid all-zero one
+fn random_id<T: PartialEq + Default>(random: impl Fn() -> T) -> T {
+    loop {
+        let id = random();
+        if id != T::default() {
+            return id;
+        }
+    }
+}
+
+static TRACE_CONTEXT_SOURCE: OnceLock<fn() -> Option<TraceContext>> = OnceLock::new();
+
+/// Take the current trace context from `source` instead of [`TraceContextLayer`]
+///
+/// Only the first source installed is used.
+pub fn install_trace_context_source(source: fn() -> Option<TraceContext>) {
+    let _ = TRACE_CONTEXT_SOURCE.set(source);
+}
+
+/// Trace context of the current span, if there is one
+pub fn current_trace_context() -> Option<TraceContext> {
+    if let Some(source) = TRACE_CONTEXT_SOURCE.get() {
+        return source();
+    }
+    tracing::Span::current()
+        .with_subscriber(|(id, dispatch)| {
+            let registry = dispatch.downcast_ref::<Registry>()?;
+            let span = registry.span(id)?;
+            let context = span.extensions().get::<TraceContext>().copied();
+            context
+        })
+        .flatten()
+}
+
+/// Add the `traceparent` header of the current span to `headers`
+pub fn inject_trace_context(headers: &mut HeaderMap) {
+    let Some(context) = current_trace_context() else {
+        return;
+    };
+    if let Ok(value) = HeaderValue::from_str(&context.traceparent()) {
+        headers.insert(TRACEPARENT_HEADER, value);
+    }
+}
+
+/// A tracing layer assigning trace and span IDs to spans
+///
+/// A root span continues the trace named by its `traceparent` field, if it
+/// has a valid one, and starts a new trace otherwise.
+pub struct TraceContextLayer;
+
+impl<S> Layer<S> for TraceContextLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let parent = span
+            .parent()
+            .and_then(|parent| parent.extensions().get::<TraceContext>().copied())
+            .or_else(|| {
+                let mut visitor = TraceparentVisitor(None);
+                attrs.record(&mut visitor);
+                visitor.0
+            });
+
+        let context = parent.map_or_else(TraceContext::root, |parent| parent.child());
+        span.extensions_mut().insert(context);
+    }
+}
+
+/// Picks the remote parent out of a span's `traceparent` field
+struct TraceparentVisitor(Option<TraceContext>);
+
+impl Visit for TraceparentVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == TRACEPARENT_HEADER {
+            self.0 = TraceContext::parse(value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == TRACEPARENT_HEADER {
+            self.0 = TraceContext::parse(&format!("{:?}", value));
+        }
+    }
+}
+
+/// Sending requests in a client span that is propagated to the server
+pub trait TracedRequest {
+    /// Send the request to `service` in an `http_client` span, with the
+    /// span's `traceparent` header
+    fn send_traced(
+        self,
+        service: &'static str,
+    ) -> impl Future<Output = reqwest::Result<Response>> + Send;
+}
+
+impl TracedRequest for RequestBuilder {
+    fn send_traced(
+        self,
+        service: &'static str,
+    ) -> impl Future<Output = reqwest::Result<Response>> + Send {
+        let (client, request) = self.build_split();
+        async move {
+            let mut request = request?;
+            // The query is left out: some services take their API key there
+            let span = tracing::info_span!(
+                "http_client",
+                otel.name = %format!("{} {}", service, request.method()),
+                otel.kind = "client",
+                otel.status_code = tracing::field::Empty,
+                peer.service = service,
+                http.method = %request.method(),
+                server.address = request.url().host_str().unwrap_or_default(),
+                url.path = request.url().path(),
+                http.status_code = tracing::field::Empty,
+            );
+
+            async move {
+                inject_trace_context(request.headers_mut());
+                let result = client.execute(request).await;
+                let span = tracing::Span::current();
+                match &result {
+                    Ok(response) => {
+                        span.record("http.status_code", response.status().as_u16());
+                        if response.status().is_server_error() {
+                            span.record("otel.status_code", "ERROR");
+                        }
+                    }
+                    Err(_) => {
+                        span.record("otel.status_code", "ERROR");
+                    }
+                }
+                result
+            }
+            .instrument(span)
+            .await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn test_traceparent_round_trip() {
+        let header = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        let context = TraceContext::parse(header).unwrap();
+        assert_eq!(context.trace_id, 0x4bf92f3577b34da6a3ce929d0e0e4736);
+        assert_eq!(context.span_id, 0x00f067aa0ba902b7);
+        assert!(context.sampled);
+        assert_eq!(context.traceparent(), header);
+
+        assert!(
+            TraceContext::parse("01-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01")
+                .is_none()
+        );
+        assert!(
+            TraceContext::parse("00-00000000000000000000000000000000-00f067aa0ba902b7-01")
+                .is_none()
+        );
+        assert!(TraceContext::parse("00-4bf92f35-00f067aa0ba902b7-01").is_none());
+    }
+
+    #[test]
+    fn test_child_spans_share_the_trace() {
+        let subscriber = tracing_subscriber::registry().with(TraceContextLayer);
+        tracing::subscriber::with_default(subscriber, || {
+            assert_eq!(current_trace_context(), None);
+
+            let remote = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-00";
+            let grab = tracing::info_span!("grab", traceparent = remote);
+            let _grab = grab.enter();
+            let parent = current_trace_context().unwrap();
+            assert_eq!(parent.trace_id, 0x4bf92f3577b34da6a3ce929d0e0e4736);
+            assert_ne!(parent.span_id, 0x00f067aa0ba902b7);
+            assert!(!parent.sampled);
+
+            let _client = tracing::info_span!("http_client").entered();
+            let mut headers = HeaderMap::new();
+            inject_trace_context(&mut headers);
+            let child = TraceContext::parse(headers[TRACEPARENT_HEADER].to_str().unwrap()).unwrap();
+            assert_eq!(child.trace_id, parent.trace_id);
+            assert_ne!(child.span_id, parent.span_id);
+        });
+    }
+}
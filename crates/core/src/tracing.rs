@@ -5,8 +5,11 @@
 
 use crate::correlation::current_context;
 // use crate::correlation::{current_correlation_id, CorrelationId}; // Currently unused
-use serde::Serialize;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Mutex, OnceLock};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
 use tracing::{Event, Subscriber};
 // use tracing::Metadata; // Currently unused
 use tracing_subscriber::{
@@ -55,7 +58,7 @@ where
 }
 
 /// Structured logging entry with correlation context
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CorrelatedLogEntry {
     pub timestamp: chrono::DateTime<chrono::Utc>,
     pub level: String,
@@ -126,6 +129,178 @@ impl CorrelatedLogEntry {
     }
 }
 
+/// Number of correlated log entries kept by [`log_buffer`]
+const LOG_BUFFER_CAPACITY: usize = 5_000;
+
+/// Name of the span or event field carrying a correlation ID
+const CORRELATION_FIELD: &str = "correlation_id";
+
+/// Recent correlated log entries, kept in memory so the log of one
+/// operation can be looked up by its correlation ID
+pub struct LogBuffer {
+    entries: Mutex<VecDeque<CorrelatedLogEntry>>,
+    capacity: usize,
+}
+
+impl LogBuffer {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: Mutex::new(VecDeque::with_capacity(capacity.min(1024))),
+            capacity,
+        }
+    }
+
+    /// Add an entry, dropping the oldest once the buffer is full
+    pub fn push(&self, entry: CorrelatedLogEntry) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if entries.len() >= self.capacity {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+    }
+
+    /// Entries logged under `correlation_id`, oldest first
+    pub fn entries_for(&self, correlation_id: &str) -> Vec<CorrelatedLogEntry> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .filter(|entry| entry.correlation_id.as_deref() == Some(correlation_id))
+            .cloned()
+            .collect()
+    }
+}
+
+/// The process-wide buffer filled by [`LogBufferLayer`]
+pub fn log_buffer() -> &'static LogBuffer {
+    static BUFFER: OnceLock<LogBuffer> = OnceLock::new();
+    BUFFER.get_or_init(|| LogBuffer::new(LOG_BUFFER_CAPACITY))
+}
+
+/// Correlation ID recorded on a span
+struct SpanCorrelation(String);
+
+/// Collects an event's message and fields
+#[derive(Default)]
+struct EntryVisitor {
+    message: Option<String>,
+    correlation_id: Option<String>,
+    fields: HashMap<String, serde_json::Value>,
+}
+
+impl EntryVisitor {
+    fn record_value(&mut self, field: &Field, value: String) {
+        match field.name() {
+            "message" => self.message = Some(value),
+            CORRELATION_FIELD => self.correlation_id = Some(value),
+            name => {
+                self.fields
+                    .insert(name.to_string(), serde_json::Value::String(value));
+            }
+        }
+    }
+}
+
+impl Visit for EntryVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record_value(field, value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.record_value(field, format!("{:?}", value));
+    }
+}
+
+/// A tracing layer that copies events carrying a correlation ID into
+/// [`log_buffer`]
+///
+/// The ID is taken from the event's `correlation_id` field, the nearest
+/// enclosing span with one, or the current correlation context. Events
+/// without one are not kept.
+pub struct LogBufferLayer {
+    buffer: &'static LogBuffer,
+}
+
+impl LogBufferLayer {
+    pub fn new() -> Self {
+        Self {
+            buffer: log_buffer(),
+        }
+    }
+}
+
+impl Default for LogBufferLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> Layer<S> for LogBufferLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut visitor = EntryVisitor::default();
+        attrs.record(&mut visitor);
+        if let (Some(correlation_id), Some(span)) = (visitor.correlation_id, ctx.span(id)) {
+            span.extensions_mut()
+                .insert(SpanCorrelation(correlation_id));
+        }
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let mut visitor = EntryVisitor::default();
+        values.record(&mut visitor);
+        if let (Some(correlation_id), Some(span)) = (visitor.correlation_id, ctx.span(id)) {
+            let mut extensions = span.extensions_mut();
+            extensions.remove::<SpanCorrelation>();
+            extensions.insert(SpanCorrelation(correlation_id));
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let mut visitor = EntryVisitor::default();
+        event.record(&mut visitor);
+
+        let context = current_context();
+        let correlation_id = visitor
+            .correlation_id
+            .or_else(|| {
+                ctx.event_scope(event)?.find_map(|span| {
+                    span.extensions()
+                        .get::<SpanCorrelation>()
+                        .map(|correlation| correlation.0.clone())
+                })
+            })
+            .or_else(|| {
+                context
+                    .as_ref()
+                    .map(|context| context.correlation_id.to_string())
+            });
+        let correlation_id = match correlation_id {
+            Some(correlation_id) => correlation_id,
+            None => return,
+        };
+
+        let metadata = event.metadata();
+        visitor.fields.insert(
+            "target".to_string(),
+            serde_json::Value::String(metadata.target().to_string()),
+        );
+        self.buffer.push(CorrelatedLogEntry {
+            timestamp: chrono::Utc::now(),
+            level: metadata.level().to_string(),
+            message: visitor.message.unwrap_or_default(),
+            correlation_id: Some(correlation_id),
+            parent_id: context
+                .as_ref()
+                .and_then(|c| c.parent_id.map(|id| id.to_string())),
+            origin: context.map(|c| c.origin),
+            fields: visitor.fields,
+        });
+    }
+}
+
 /// Macro to log with correlation context
 #[macro_export]
 macro_rules! log_with_correlation {
@@ -273,6 +448,28 @@ mod tests {
         crate::correlation::clear_context();
     }
 
+    #[test]
+    fn test_log_buffer_layer_keeps_correlated_events() {
+        use tracing_subscriber::prelude::*;
+
+        let subscriber = tracing_subscriber::registry().with(LogBufferLayer::new());
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("import", correlation_id = "buffer-test");
+            let _guard = span.enter();
+            tracing::warn!(path = "/downloads/movie.mkv", "Hardlink failed");
+        });
+
+        let entries = log_buffer().entries_for("buffer-test");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].level, "WARN");
+        assert_eq!(entries[0].message, "Hardlink failed");
+        assert_eq!(
+            entries[0].fields.get("path"),
+            Some(&serde_json::json!("/downloads/movie.mkv"))
+        );
+        assert!(log_buffer().entries_for("other").is_empty());
+    }
+
     #[test]
     fn test_log_entry_without_correlation_context() {
         crate::correlation::clear_context();
@@ -4,11 +4,31 @@
 //! and scoring releases based on various criteria like codecs, groups,
 //! special features, etc.
 
+use radarr_core::models::{AudioCodec, MovieRatings, RatingSource, VideoCodec};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use uuid::Uuid;
 
+/// Specification types understood by [`FormatSpecification::matches`]
+pub const SPECIFICATION_TYPES: &[&str] = &[
+    "release_title",
+    "indexer_flag",
+    "size",
+    "seeders",
+    "release_group",
+    "codec",
+    "audio",
+    "source",
+    "imdb_rating",
+    "tmdb_rating",
+    "metacritic_rating",
+    "rotten_tomatoes_rating",
+];
+
+/// Indexer flags understood by the `indexer_flag` specification
+pub const INDEXER_FLAGS: &[&str] = &["freeleech", "internal"];
+
 /// Specification for a custom format rule
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FormatSpecification {
@@ -45,6 +65,45 @@ impl FormatSpecification {
         self
     }
 
+    /// Validate the specification value against its type
+    ///
+    /// Matching falls back to a substring search when a title pattern does
+    /// not compile, so a typo in a regex would otherwise go unnoticed.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.value.trim().is_empty() {
+            return Err("Value must not be empty".to_string());
+        }
+
+        match self.spec_type.as_str() {
+            "release_title" | "release_group" | "codec" | "audio" | "source" => {
+                Regex::new(&format!("(?i){}", self.value))
+                    .map(|_| ())
+                    .map_err(|e| format!("Invalid regex '{}': {}", self.value, e))
+            }
+            "indexer_flag" => {
+                if INDEXER_FLAGS.contains(&self.value.as_str()) {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "Unknown indexer flag '{}', expected one of: {}",
+                        self.value,
+                        INDEXER_FLAGS.join(", ")
+                    ))
+                }
+            }
+            "size" => NumericCondition::parse_size(&self.value).map(|_| ()),
+            "seeders" => NumericCondition::parse(&self.value).map(|_| ()),
+            spec_type if rating_source(spec_type).is_some() => {
+                NumericCondition::parse_rating(&self.value).map(|_| ())
+            }
+            other => Err(format!(
+                "Unknown specification type '{}', expected one of: {}",
+                other,
+                SPECIFICATION_TYPES.join(", ")
+            )),
+        }
+    }
+
     /// Check if this specification matches the given release data
     pub fn matches(&self, release_data: &ReleaseData) -> bool {
         let result = match self.spec_type.as_str() {
@@ -54,8 +113,12 @@ impl FormatSpecification {
             "seeders" => self.matches_seeders(release_data),
             "release_group" => self.matches_release_group(&release_data.title),
             "codec" => self.matches_codec(&release_data.title),
+            "audio" => self.matches_audio(&release_data.title),
             "source" => self.matches_source(&release_data.title),
-            _ => false,
+            spec_type => match rating_source(spec_type) {
+                Some(source) => self.matches_rating(release_data, source),
+                None => false,
+            },
         };
 
         if self.negate {
@@ -103,6 +166,21 @@ impl FormatSpecification {
         false
     }
 
+    /// Match against the movie's rating from `source`; releases of movies
+    /// without one never match
+    fn matches_rating(&self, release_data: &ReleaseData, source: RatingSource) -> bool {
+        let rating = release_data
+            .movie_ratings
+            .as_ref()
+            .and_then(|ratings| ratings.get(source));
+        match (rating, NumericCondition::parse_rating(&self.value)) {
+            (Some(rating), Ok(condition)) => {
+                condition.evaluate((rating.value * 10.0).round() as i64)
+            }
+            _ => false,
+        }
+    }
+
     /// Match against release group
     fn matches_release_group(&self, title: &str) -> bool {
         // Extract release group (usually after the last '-')
@@ -114,19 +192,33 @@ impl FormatSpecification {
     }
 
     /// Match against video codec
+    ///
+    /// Known codec names and aliases (`x265`, `HEVC`, `VVC`, `AV1`,
+    /// `AV1 Main10`, ...) match the codec parsed from the title; anything
+    /// else is treated as a title pattern.
     fn matches_codec(&self, title: &str) -> bool {
-        let title_lower = title.to_lowercase();
-        let value_lower = self.value.to_lowercase();
+        match VideoCodec::from_name(&self.value) {
+            Some(wanted) => VideoCodec::parse(title)
+                .map(|found| wanted.includes(&found))
+                .unwrap_or(false),
+            None => self.matches_title(title),
+        }
+    }
 
-        match value_lower.as_str() {
-            "x264" => title_lower.contains("x264") || title_lower.contains("h264"),
-            "x265" => {
-                title_lower.contains("x265")
-                    || title_lower.contains("h265")
-                    || title_lower.contains("hevc")
-            }
-            "xvid" => title_lower.contains("xvid"),
-            _ => self.matches_title(title),
+    /// Match against audio codec
+    ///
+    /// `Atmos` matches both DD+ Atmos and TrueHD Atmos; other known names
+    /// (`DD+`, `TrueHD Atmos`, `DTS-HD MA`, ...) match exactly, and anything
+    /// else is treated as a title pattern.
+    fn matches_audio(&self, title: &str) -> bool {
+        if self.value.trim().eq_ignore_ascii_case("atmos") {
+            return AudioCodec::parse_all(title)
+                .iter()
+                .any(AudioCodec::is_atmos);
+        }
+        match AudioCodec::from_name(&self.value) {
+            Some(wanted) => AudioCodec::parse_all(title).contains(&wanted),
+            None => self.matches_title(title),
         }
     }
 
@@ -161,6 +253,17 @@ impl FormatSpecification {
     }
 }
 
+/// Rating service matched by a rating specification type
+fn rating_source(spec_type: &str) -> Option<RatingSource> {
+    match spec_type {
+        "imdb_rating" => Some(RatingSource::Imdb),
+        "tmdb_rating" => Some(RatingSource::Tmdb),
+        "metacritic_rating" => Some(RatingSource::Metacritic),
+        "rotten_tomatoes_rating" => Some(RatingSource::RottenTomatoes),
+        _ => None,
+    }
+}
+
 /// Numeric condition for size/seeders matching
 #[derive(Debug, Clone)]
 pub struct NumericCondition {
@@ -246,6 +349,27 @@ impl NumericCondition {
         })
     }
 
+    /// Parse a rating condition such as `>=7.5`, a bare rating meaning at
+    /// least that rating
+    ///
+    /// The value is kept in tenths, so ratings are compared to one decimal.
+    pub fn parse_rating(condition: &str) -> Result<Self, String> {
+        let condition = condition.trim();
+        let value_str = condition.trim_start_matches(['<', '>', '=']);
+        let operator = match &condition[..condition.len() - value_str.len()] {
+            "" => ">=",
+            operator => operator,
+        };
+
+        let value = value_str
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|value| value.is_finite())
+            .ok_or_else(|| format!("Invalid rating value: {}", value_str))?;
+        Self::parse(&format!("{}{}", operator, (value * 10.0).round() as i64))
+    }
+
     /// Evaluate the condition against a value
     pub fn evaluate(&self, test_value: i64) -> bool {
         match self.operator.as_str() {
@@ -259,6 +383,14 @@ impl NumericCondition {
     }
 }
 
+/// Validation failure for a custom format, pointing at the offending field
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatValidationError {
+    /// Field path, e.g. `specifications[1].value`
+    pub field: String,
+    pub message: String,
+}
+
 /// Custom format definition
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CustomFormat {
@@ -292,6 +424,35 @@ impl CustomFormat {
         self
     }
 
+    /// Validate the format name and every specification
+    pub fn validate(&self) -> Result<(), FormatValidationError> {
+        if self.name.trim().is_empty() {
+            return Err(FormatValidationError {
+                field: "name".to_string(),
+                message: "Name must not be empty".to_string(),
+            });
+        }
+
+        if self.specifications.is_empty() {
+            return Err(FormatValidationError {
+                field: "specifications".to_string(),
+                message: "At least one specification is required".to_string(),
+            });
+        }
+
+        for (index, spec) in self.specifications.iter().enumerate() {
+            let field = if SPECIFICATION_TYPES.contains(&spec.spec_type.as_str()) {
+                format!("specifications[{}].value", index)
+            } else {
+                format!("specifications[{}].type", index)
+            };
+            spec.validate()
+                .map_err(|message| FormatValidationError { field, message })?;
+        }
+
+        Ok(())
+    }
+
     /// Check if this format matches the given release data
     pub fn matches(&self, release_data: &ReleaseData) -> bool {
         if !self.enabled {
@@ -330,6 +491,8 @@ pub struct ReleaseData {
     pub internal: Option<bool>,
     pub indexer: String,
     pub release_group: Option<String>,
+    /// Ratings of the movie the release is for, when known
+    pub movie_ratings: Option<MovieRatings>,
 }
 
 impl ReleaseData {
@@ -347,6 +510,7 @@ impl ReleaseData {
             internal: Some(internal),
             indexer: "Unknown".to_string(), // TODO: Pass from context
             release_group: result.release_group.clone(),
+            movie_ratings: None,
         }
     }
 
@@ -496,6 +660,7 @@ mod tests {
             internal: Some(false),
             indexer: "TestIndexer".to_string(),
             release_group: title.split('-').last().map(|s| s.trim().to_string()),
+            movie_ratings: None,
         }
     }
 
@@ -525,6 +690,52 @@ mod tests {
         assert!(!spec.matches(&release_normal));
     }
 
+    #[test]
+    fn test_format_specification_validation() {
+        assert!(FormatSpecification::new("release_title", "x265|HEVC")
+            .validate()
+            .is_ok());
+        assert!(FormatSpecification::new("size", ">25GB").validate().is_ok());
+        assert!(FormatSpecification::new("seeders", ">=20")
+            .validate()
+            .is_ok());
+        assert!(FormatSpecification::new("imdb_rating", ">=7.5")
+            .validate()
+            .is_ok());
+
+        assert!(FormatSpecification::new("release_title", "(unclosed")
+            .validate()
+            .is_err());
+        assert!(FormatSpecification::new("size", ">lots")
+            .validate()
+            .is_err());
+        assert!(FormatSpecification::new("indexer_flag", "golden")
+            .validate()
+            .is_err());
+        assert!(FormatSpecification::new("imdb_rating", "=>7")
+            .validate()
+            .is_err());
+        assert!(FormatSpecification::new("bitrate", "high")
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn test_custom_format_validation_reports_field() {
+        let format = CustomFormat::new("Broken", 5)
+            .add_spec(FormatSpecification::new("codec", "x265"))
+            .add_spec(FormatSpecification::new("release_title", "[HDR"));
+        let err = format.validate().unwrap_err();
+        assert_eq!(err.field, "specifications[1].value");
+
+        let format =
+            CustomFormat::new("Unknown", 5).add_spec(FormatSpecification::new("bitrate", "high"));
+        let err = format.validate().unwrap_err();
+        assert_eq!(err.field, "specifications[0].type");
+
+        assert!(CustomFormat::new("Empty", 5).validate().is_err());
+    }
+
     #[test]
     fn test_size_condition_parsing() {
         let condition = NumericCondition::parse_size(">5GB").unwrap();
@@ -574,4 +785,46 @@ mod tests {
         assert!(!format.matches(&scene_release));
         assert!(format.matches(&non_scene_release));
     }
+
+    #[test]
+    fn test_codec_and_audio_specifications() {
+        let vvc = create_test_release_data("Movie.2024.2160p.WEB-DL.DDP5.1.Atmos.H.266-GROUP");
+        let av1 = create_test_release_data("Movie.2024.2160p.WEB-DL.DDP5.1.AV1.10bit-GROUP");
+        let hevc = create_test_release_data("Movie.2024.2160p.BluRay.TrueHD.7.1.Atmos.x265-GROUP");
+
+        let codec = |value: &str| FormatSpecification::new("codec", value);
+        assert!(codec("VVC").matches(&vvc));
+        assert!(!codec("x265").matches(&vvc));
+        assert!(codec("AV1").matches(&av1));
+        assert!(codec("AV1 Main10").matches(&av1));
+        assert!(!codec("AV1 Professional").matches(&av1));
+        assert!(codec("HEVC").matches(&hevc));
+
+        let audio = |value: &str| FormatSpecification::new("audio", value);
+        assert!(audio("Atmos").matches(&vvc));
+        assert!(audio("Atmos").matches(&hevc));
+        assert!(audio("DD+ Atmos").matches(&vvc));
+        assert!(!audio("DD+ Atmos").matches(&hevc));
+        assert!(audio("TrueHD Atmos").matches(&hevc));
+        assert!(audio("DD+").matches(&av1));
+        assert!(!audio("Atmos").matches(&av1));
+        assert!(audio("DTS-HD MA").validate().is_ok());
+    }
+    #[test]
+    fn test_rating_specifications() {
+        let mut release = create_test_release_data("Movie.2024.1080p.BluRay.x264-GROUP");
+        let min_imdb = FormatSpecification::new("imdb_rating", ">=7.5");
+        assert!(!min_imdb.matches(&release));
+
+        release.movie_ratings = Some(MovieRatings {
+            imdb: Some(radarr_core::models::Rating::new(7.5, 1000)),
+            rotten_tomatoes: Some(radarr_core::models::Rating::new(91.0, 0)),
+            ..MovieRatings::default()
+        });
+        assert!(min_imdb.matches(&release));
+        assert!(FormatSpecification::new("imdb_rating", "7").matches(&release));
+        assert!(!FormatSpecification::new("imdb_rating", ">7.5").matches(&release));
+        assert!(FormatSpecification::new("rotten_tomatoes_rating", ">90").matches(&release));
+        assert!(!FormatSpecification::new("metacritic_rating", "<50").matches(&release));
+    }
 }
@@ -4,9 +4,20 @@
 //! multiple releases and selects the best one based on quality profiles
 //! and various release characteristics.
 
+use crate::custom_formats::{CustomFormatEngine, ReleaseData};
+use crate::language::LanguageProfile;
 use crate::quality::{Quality, QualityProfile, Source};
+use chrono::{DateTime, Utc};
+use radarr_core::models::{
+    parse_edition, parse_languages, same_edition, Language, MovieFile, MovieRatings,
+    ReleaseProtocol, Revision,
+};
+use radarr_core::services::{DownloadClientManager, RootFolderSpace};
+use radarr_core::RadarrError;
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
 
 /// Release information for decision making
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,6 +42,11 @@ pub struct Release {
     pub quality: Quality,
     /// Source detected from title
     pub source: Source,
+    /// Torrent or usenet, inferred from the download URL
+    pub protocol: ReleaseProtocol,
+    /// Indexer the release was found on
+    #[serde(default)]
+    pub indexer_id: Option<i32>,
 }
 
 impl Release {
@@ -38,6 +54,7 @@ impl Release {
     pub fn from_title(title: String, download_url: String) -> Self {
         let quality = Quality::from_resolution(&title);
         let source = Source::from_release_name(&title);
+        let protocol = DownloadClientManager::infer_protocol(&download_url);
 
         Self {
             title,
@@ -50,6 +67,8 @@ impl Release {
             freeleech: None,
             quality,
             source,
+            protocol,
+            indexer_id: None,
         }
     }
 
@@ -83,6 +102,39 @@ impl Release {
         self.freeleech = Some(freeleech);
         self
     }
+
+    /// Set the age from the indexer's publish date
+    ///
+    /// Publish dates in the future (indexer clock skew) count as zero hours.
+    pub fn with_publish_date(self, publish_date: DateTime<Utc>) -> Self {
+        let age_hours = (Utc::now() - publish_date).num_hours().max(0);
+        self.with_age_hours(u32::try_from(age_hours).unwrap_or(u32::MAX))
+    }
+
+    pub fn with_protocol(mut self, protocol: ReleaseProtocol) -> Self {
+        self.protocol = protocol;
+        self
+    }
+
+    pub fn with_indexer_id(mut self, indexer_id: i32) -> Self {
+        self.indexer_id = Some(indexer_id);
+        self
+    }
+
+    /// Edition named in the title, e.g. `Director's Cut`
+    pub fn edition(&self) -> Option<String> {
+        parse_edition(&self.title)
+    }
+
+    /// Proper or repack revision named in the title
+    pub fn revision(&self) -> Revision {
+        Revision::parse(&self.title)
+    }
+
+    /// Audio languages named in the title
+    pub fn languages(&self) -> Vec<Language> {
+        parse_languages(&self.title)
+    }
 }
 
 /// Release evaluation score
@@ -100,6 +152,23 @@ pub struct ReleaseScore {
     pub age_score: i32,
     /// Special bonuses (freeleech, etc.)
     pub bonus_score: i32,
+    /// Custom format score (0 when no custom formats are configured)
+    pub custom_format_score: i32,
+    /// Reasons the release would not be grabbed (empty when approved)
+    pub rejections: Vec<RejectionReason>,
+}
+
+impl ReleaseScore {
+    /// Whether the release passed every check
+    pub fn is_approved(&self) -> bool {
+        self.rejections.is_empty()
+    }
+
+    /// Whether the release is only held back temporarily and should be
+    /// queued as pending instead of dropped
+    pub fn is_pending(&self) -> bool {
+        !self.rejections.is_empty() && self.rejections.iter().all(RejectionReason::is_temporary)
+    }
 }
 
 impl PartialOrd for ReleaseScore {
@@ -114,6 +183,265 @@ impl Ord for ReleaseScore {
     }
 }
 
+/// The file currently in the library for a movie and edition
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExistingFile {
+    pub quality: Quality,
+    pub source: Source,
+    pub custom_format_score: i32,
+    /// Edition of the file; releases of other editions are not upgrades of it
+    #[serde(default)]
+    pub edition: Option<String>,
+    /// Proper or repack revision of the file
+    #[serde(default)]
+    pub revision: Revision,
+}
+
+impl ExistingFile {
+    pub fn new(quality: Quality, source: Source, custom_format_score: i32) -> Self {
+        Self {
+            quality,
+            source,
+            custom_format_score,
+            edition: None,
+            revision: Revision::default(),
+        }
+    }
+
+    pub fn with_edition(mut self, edition: impl Into<String>) -> Self {
+        self.edition = Some(edition.into());
+        self
+    }
+
+    pub fn with_revision(mut self, revision: Revision) -> Self {
+        self.revision = revision;
+        self
+    }
+
+    /// The file among a movie's files that `release` would replace: the one
+    /// of the same edition
+    pub fn for_release<'a>(files: &'a [ExistingFile], release: &Release) -> Option<&'a Self> {
+        let edition = release.edition();
+        files
+            .iter()
+            .find(|file| same_edition(file.edition.as_deref(), edition.as_deref()))
+    }
+
+    /// Derive quality and source from a stored movie file
+    ///
+    /// Uses the `resolution`/`source`/`revision` fields of the quality payload
+    /// when present and falls back to parsing `quality_name` and the file name.
+    pub fn from_movie_file(file: &MovieFile) -> Self {
+        let fallback = format!(
+            "{} {}",
+            file.quality_field("quality_name").unwrap_or_default(),
+            file.file_name()
+        );
+
+        let quality = match file
+            .quality_field("resolution")
+            .map(Quality::from_resolution)
+        {
+            Some(quality) if quality != Quality::Unknown => quality,
+            _ => Quality::from_resolution(&fallback),
+        };
+        let source = match file.quality_field("source").map(Source::from_release_name) {
+            Some(source) if source != Source::Unknown => source,
+            _ => Source::from_release_name(&fallback),
+        };
+
+        Self {
+            edition: file
+                .edition
+                .clone()
+                .or_else(|| parse_edition(file.file_name())),
+            revision: file
+                .quality
+                .get("revision")
+                .and_then(|revision| serde_json::from_value(revision.clone()).ok())
+                .unwrap_or_else(|| Revision::parse(file.file_name())),
+            ..Self::new(quality, source, file.custom_format_score)
+        }
+    }
+
+    fn rank(&self) -> (i32, i32) {
+        (self.quality.score(), self.source.score())
+    }
+}
+
+/// Structured reason a release was not approved
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "reason", rename_all = "snake_case")]
+pub enum RejectionReason {
+    /// Release is larger than the configured maximum
+    SizeTooLarge { size_gb: u64, max_gb: u32 },
+    /// Release has fewer seeders than required
+    TooFewSeeders { seeders: u32, minimum: u32 },
+    /// Release is older than the configured maximum age
+    TooOld { age_hours: u32, max_hours: u32 },
+    /// Release is younger than the configured minimum age
+    TooNew { age_hours: u32, min_hours: u32 },
+    /// Usenet release is older than the provider's retention
+    BeyondRetention { age_days: u32, retention_days: u32 },
+    /// Release is on the blocklist after an earlier failure
+    Blocklisted,
+    /// Release is in none of the language profile's languages
+    LanguageNotAllowed { languages: Vec<Language> },
+    /// Quality is not allowed by the profile
+    QualityNotAllowed { quality: Quality },
+    /// Custom format score is below the profile minimum
+    BelowMinimumFormatScore { score: i32, minimum: i32 },
+    /// Movie already has a file and the profile does not allow upgrades
+    UpgradesNotAllowed,
+    /// Existing file already meets the quality cutoff
+    CutoffMet { existing: Quality, cutoff: Quality },
+    /// Release quality is lower than the existing file
+    NotAnUpgrade {
+        existing: Quality,
+        candidate: Quality,
+    },
+    /// Same quality as the existing file without a better custom format score
+    FormatScoreNotHigher { existing: i32, candidate: i32 },
+    /// Same quality as the existing file but an older proper or repack revision
+    RevisionNotHigher {
+        existing: Revision,
+        candidate: Revision,
+    },
+    /// The movie's root folder is low on space; grabs wait until it frees up
+    InsufficientStorage {
+        path: String,
+        free_bytes: u64,
+        threshold_bytes: u64,
+    },
+}
+
+impl RejectionReason {
+    /// Whether the rejection goes away on its own, without a better release
+    pub fn is_temporary(&self) -> bool {
+        matches!(self, RejectionReason::InsufficientStorage { .. })
+    }
+}
+
+impl fmt::Display for RejectionReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RejectionReason::SizeTooLarge { size_gb, max_gb } => {
+                write!(f, "Size {} GB exceeds maximum of {} GB", size_gb, max_gb)
+            }
+            RejectionReason::TooFewSeeders { seeders, minimum } => {
+                write!(f, "{} seeders is below the minimum of {}", seeders, minimum)
+            }
+            RejectionReason::TooOld {
+                age_hours,
+                max_hours,
+            } => write!(
+                f,
+                "Release age of {} hours exceeds maximum of {} hours",
+                age_hours, max_hours
+            ),
+            RejectionReason::TooNew {
+                age_hours,
+                min_hours,
+            } => write!(
+                f,
+                "Release age of {} hours is below the minimum of {} hours",
+                age_hours, min_hours
+            ),
+            RejectionReason::BeyondRetention {
+                age_days,
+                retention_days,
+            } => write!(
+                f,
+                "Release age of {} days exceeds usenet retention of {} days",
+                age_days, retention_days
+            ),
+            RejectionReason::Blocklisted => write!(f, "Release is blocklisted"),
+            RejectionReason::LanguageNotAllowed { languages } => {
+                let names: Vec<&str> = languages.iter().map(Language::name).collect();
+                write!(f, "Language {} is not wanted", names.join(", "))
+            }
+            RejectionReason::QualityNotAllowed { quality } => {
+                write!(f, "Quality {:?} is not allowed by the profile", quality)
+            }
+            RejectionReason::BelowMinimumFormatScore { score, minimum } => write!(
+                f,
+                "Custom format score {} is below the minimum of {}",
+                score, minimum
+            ),
+            RejectionReason::UpgradesNotAllowed => {
+                write!(f, "Existing file present and upgrades are disabled")
+            }
+            RejectionReason::CutoffMet { existing, cutoff } => write!(
+                f,
+                "Existing file quality {:?} already meets cutoff {:?}",
+                existing, cutoff
+            ),
+            RejectionReason::NotAnUpgrade {
+                existing,
+                candidate,
+            } => write!(
+                f,
+                "Quality {:?} is not an upgrade over existing {:?}",
+                candidate, existing
+            ),
+            RejectionReason::FormatScoreNotHigher {
+                existing,
+                candidate,
+            } => write!(
+                f,
+                "Custom format score {} is not higher than existing {}",
+                candidate, existing
+            ),
+            RejectionReason::RevisionNotHigher {
+                existing,
+                candidate,
+            } => write!(
+                f,
+                "Revision {} is older than existing revision {}",
+                candidate.version, existing.version
+            ),
+            RejectionReason::InsufficientStorage {
+                path,
+                free_bytes,
+                threshold_bytes,
+            } => write!(
+                f,
+                "Root folder {} has {} bytes free, below the {} byte threshold",
+                path, free_bytes, threshold_bytes
+            ),
+        }
+    }
+}
+
+/// Whether propers and repacks replace files of the same quality
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ProperHandling {
+    /// Grab a proper or repack of the existing file's quality as an upgrade
+    #[default]
+    Prefer,
+    /// Treat propers and repacks like any other release
+    Ignore,
+}
+
+impl FromStr for ProperHandling {
+    type Err = RadarrError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "prefer" => Ok(ProperHandling::Prefer),
+            "ignore" => Ok(ProperHandling::Ignore),
+            _ => Err(RadarrError::ValidationError {
+                field: "proper_handling".to_string(),
+                message: format!(
+                    "Unknown proper handling '{}', expected prefer or ignore",
+                    value
+                ),
+            }),
+        }
+    }
+}
+
 /// Main decision engine
 #[derive(Debug)]
 pub struct DecisionEngine {
@@ -125,6 +453,20 @@ pub struct DecisionEngine {
     pub min_seeders: Option<u32>,
     /// Maximum age in hours (None = no limit)
     pub max_age_hours: Option<u32>,
+    /// Minimum age in hours before a release is grabbed (None = no wait)
+    pub min_age_hours: Option<u32>,
+    /// Usenet retention in days; older usenet releases are rejected (None = no limit)
+    pub usenet_retention_days: Option<u32>,
+    /// Custom formats used to score releases (None = no custom format scoring)
+    pub custom_formats: Option<CustomFormatEngine>,
+    /// Low-space root folder the movie is filed under (None = room to grab)
+    pub storage_hold: Option<RootFolderSpace>,
+    /// Whether propers and repacks upgrade files of the same quality
+    pub proper_handling: ProperHandling,
+    /// Languages releases must be in, and which are preferred
+    pub language_profile: LanguageProfile,
+    /// Ratings of the movie, matched by rating custom formats
+    pub movie_ratings: Option<MovieRatings>,
 }
 
 impl DecisionEngine {
@@ -135,6 +477,13 @@ impl DecisionEngine {
             max_size_gb: Some(50),       // Default 50GB limit
             min_seeders: Some(1),        // At least 1 seeder
             max_age_hours: Some(24 * 7), // Max 1 week old
+            min_age_hours: None,
+            usenet_retention_days: None,
+            custom_formats: None,
+            storage_hold: None,
+            proper_handling: ProperHandling::Prefer,
+            language_profile: LanguageProfile::default(),
+            movie_ratings: None,
         }
     }
 
@@ -145,9 +494,48 @@ impl DecisionEngine {
             max_size_gb: None,
             min_seeders: None,
             max_age_hours: None,
+            min_age_hours: None,
+            usenet_retention_days: None,
+            custom_formats: None,
+            storage_hold: None,
+            proper_handling: ProperHandling::Prefer,
+            language_profile: LanguageProfile::default(),
+            movie_ratings: None,
         }
     }
 
+    /// Score releases with the given custom formats
+    pub fn with_custom_formats(mut self, engine: CustomFormatEngine) -> Self {
+        self.custom_formats = Some(engine);
+        self
+    }
+
+    /// Hold grabs back while the movie's root folder is low on space, as
+    /// reported by [`StorageThrottle::hold_for`](radarr_core::services::StorageThrottle::hold_for)
+    pub fn with_storage_hold(mut self, hold: Option<RootFolderSpace>) -> Self {
+        self.storage_hold = hold;
+        self
+    }
+
+    /// Whether propers and repacks upgrade files of the same quality
+    pub fn with_proper_handling(mut self, proper_handling: ProperHandling) -> Self {
+        self.proper_handling = proper_handling;
+        self
+    }
+
+    /// Reject releases in unwanted languages and prefer the profile's
+    pub fn with_language_profile(mut self, language_profile: LanguageProfile) -> Self {
+        self.language_profile = language_profile;
+        self
+    }
+
+    /// Match rating custom formats against the ratings of the movie
+    /// releases are evaluated for
+    pub fn with_movie_ratings(mut self, ratings: MovieRatings) -> Self {
+        self.movie_ratings = Some(ratings);
+        self
+    }
+
     /// Evaluate a release and return its score
     pub fn evaluate_release(&self, release: &Release) -> Option<ReleaseScore> {
         // Check hard constraints first
@@ -155,29 +543,200 @@ impl DecisionEngine {
             return None;
         }
 
-        // Calculate component scores
-        let quality_score = self
-            .quality_profile
-            .calculate_quality_score(&release.quality, &release.source);
-        if quality_score < 0 {
+        let score = self.score_release(release);
+        if score.quality_score < 0 {
             return None; // Quality not allowed
         }
 
+        Some(score)
+    }
+
+    /// Score a release and record every reason it would be rejected
+    ///
+    /// Unlike [`evaluate_release`](Self::evaluate_release) this always returns
+    /// a score, so callers such as interactive search can show rejected
+    /// releases together with why they were rejected.
+    pub fn assess_release(
+        &self,
+        release: &Release,
+        existing: Option<&ExistingFile>,
+    ) -> ReleaseScore {
+        let mut score = self.score_release(release);
+        let mut rejections = self.constraint_rejections(release);
+
+        if score.quality_score < 0 {
+            rejections.push(RejectionReason::QualityNotAllowed {
+                quality: release.quality.clone(),
+            });
+        }
+
+        let profile = &self.quality_profile;
+        if score.custom_format_score < profile.min_format_score {
+            rejections.push(RejectionReason::BelowMinimumFormatScore {
+                score: score.custom_format_score,
+                minimum: profile.min_format_score,
+            });
+        }
+
+        if let Some(existing) = existing {
+            if let Some(reason) =
+                self.upgrade_rejection(release, score.custom_format_score, existing)
+            {
+                rejections.push(reason);
+            }
+        }
+
+        if let Some(hold) = &self.storage_hold {
+            rejections.push(RejectionReason::InsufficientStorage {
+                path: hold.path.clone(),
+                free_bytes: hold.free_bytes,
+                threshold_bytes: hold.threshold_bytes,
+            });
+        }
+
+        score.rejections = rejections;
+        score
+    }
+
+    /// Calculate all score components without applying any checks
+    fn score_release(&self, release: &Release) -> ReleaseScore {
+        let quality_score = self
+            .quality_profile
+            .calculate_quality_score(&release.quality, &release.source);
         let seeders_score = self.calculate_seeders_score(release);
         let size_score = self.calculate_size_score(release);
         let age_score = self.calculate_age_score(release);
         let bonus_score = self.calculate_bonus_score(release);
+        let custom_format_score = self.calculate_custom_format_score(release);
 
-        let total = quality_score + seeders_score + size_score + age_score + bonus_score;
+        let total = quality_score
+            + seeders_score
+            + size_score
+            + age_score
+            + bonus_score
+            + custom_format_score;
 
-        Some(ReleaseScore {
+        ReleaseScore {
             total,
             quality_score,
             seeders_score,
             size_score,
             age_score,
             bonus_score,
-        })
+            custom_format_score,
+            rejections: Vec::new(),
+        }
+    }
+
+    /// Evaluate a release against the movie's existing file
+    ///
+    /// Without an existing file this is [`evaluate_release`](Self::evaluate_release)
+    /// plus the profile's minimum format score. With one, the release is only
+    /// approved if it is a true upgrade: a better quality while the existing
+    /// file is below the cutoff, a proper or repack of the same quality when
+    /// propers are preferred, or the same quality with a higher custom format
+    /// score.
+    pub fn evaluate_upgrade(
+        &self,
+        release: &Release,
+        existing: Option<&ExistingFile>,
+    ) -> std::result::Result<ReleaseScore, RejectionReason> {
+        let score = self.assess_release(release, existing);
+        match score.rejections.first() {
+            Some(reason) => Err(reason.clone()),
+            None => Ok(score),
+        }
+    }
+
+    /// Why a release is not an upgrade over the existing file, if it is not
+    fn upgrade_rejection(
+        &self,
+        release: &Release,
+        custom_format_score: i32,
+        existing: &ExistingFile,
+    ) -> Option<RejectionReason> {
+        // A different edition is a file of its own next to this one
+        if !same_edition(release.edition().as_deref(), existing.edition.as_deref()) {
+            return None;
+        }
+
+        let profile = &self.quality_profile;
+        if !profile.upgrade_allowed {
+            return Some(RejectionReason::UpgradesNotAllowed);
+        }
+
+        let candidate_rank = (release.quality.score(), release.source.score());
+        match candidate_rank.cmp(&existing.rank()) {
+            Ordering::Less => Some(RejectionReason::NotAnUpgrade {
+                existing: existing.quality.clone(),
+                candidate: release.quality.clone(),
+            }),
+            Ordering::Greater if existing.quality.score() >= profile.cutoff.score() => {
+                Some(RejectionReason::CutoffMet {
+                    existing: existing.quality.clone(),
+                    cutoff: profile.cutoff.clone(),
+                })
+            }
+            Ordering::Greater => None,
+            // A fixed release replaces a broken one, even at the cutoff
+            Ordering::Equal if self.proper_handling == ProperHandling::Prefer => {
+                let candidate = release.revision();
+                match candidate.cmp(&existing.revision) {
+                    Ordering::Greater => None,
+                    Ordering::Less => Some(RejectionReason::RevisionNotHigher {
+                        existing: existing.revision,
+                        candidate,
+                    }),
+                    Ordering::Equal => self.format_score_rejection(custom_format_score, existing),
+                }
+            }
+            Ordering::Equal => self.format_score_rejection(custom_format_score, existing),
+        }
+    }
+
+    fn format_score_rejection(
+        &self,
+        custom_format_score: i32,
+        existing: &ExistingFile,
+    ) -> Option<RejectionReason> {
+        (custom_format_score <= existing.custom_format_score).then_some(
+            RejectionReason::FormatScoreNotHigher {
+                existing: existing.custom_format_score,
+                candidate: custom_format_score,
+            },
+        )
+    }
+
+    /// Select the best release that is an upgrade over the movie's existing
+    /// files
+    ///
+    /// Each release is compared with the file of its own edition; releases
+    /// of an edition the movie has no file of only need to be acceptable.
+    pub fn select_best_upgrade(
+        &self,
+        releases: Vec<Release>,
+        existing: &[ExistingFile],
+    ) -> Option<Release> {
+        let mut scored_releases: Vec<(Release, ReleaseScore)> = releases
+            .into_iter()
+            .filter_map(|release| {
+                let existing = ExistingFile::for_release(existing, &release);
+                match self.evaluate_upgrade(&release, existing) {
+                    Ok(score) => Some((release, score)),
+                    Err(reason) => {
+                        tracing::debug!("Rejected '{}': {}", release.title, reason);
+                        None
+                    }
+                }
+            })
+            .collect();
+
+        scored_releases.sort_by(|a, b| b.1.cmp(&a.1));
+
+        scored_releases
+            .into_iter()
+            .next()
+            .map(|(release, _)| release)
     }
 
     /// Select the best release from a list of candidates
@@ -202,29 +761,72 @@ impl DecisionEngine {
 
     /// Check if release meets hard constraints
     fn meets_constraints(&self, release: &Release) -> bool {
+        self.constraint_rejections(release).is_empty()
+    }
+
+    /// Size, seeder, age and language limits the release violates
+    fn constraint_rejections(&self, release: &Release) -> Vec<RejectionReason> {
+        let mut rejections = Vec::new();
+
         // Size constraint
         if let (Some(max_gb), Some(size)) = (self.max_size_gb, release.size) {
             let size_gb = size / (1024 * 1024 * 1024);
             if size_gb > max_gb as u64 {
-                return false;
+                rejections.push(RejectionReason::SizeTooLarge { size_gb, max_gb });
             }
         }
 
         // Seeders constraint
         if let (Some(min_seeders), Some(seeders)) = (self.min_seeders, release.seeders) {
             if seeders < min_seeders {
-                return false;
+                rejections.push(RejectionReason::TooFewSeeders {
+                    seeders,
+                    minimum: min_seeders,
+                });
             }
         }
 
-        // Age constraint
-        if let (Some(max_hours), Some(age)) = (self.max_age_hours, release.age_hours) {
-            if age > max_hours {
-                return false;
+        // Age constraints, with the profile's limits taking precedence
+        if let Some(age) = release.age_hours {
+            let profile = &self.quality_profile;
+
+            if let Some(max_hours) = profile.max_age_hours.or(self.max_age_hours) {
+                if age > max_hours {
+                    rejections.push(RejectionReason::TooOld {
+                        age_hours: age,
+                        max_hours,
+                    });
+                }
+            }
+
+            if let Some(min_hours) = profile.min_age_hours.or(self.min_age_hours) {
+                if age < min_hours {
+                    rejections.push(RejectionReason::TooNew {
+                        age_hours: age,
+                        min_hours,
+                    });
+                }
+            }
+
+            let retention = profile.usenet_retention_days.or(self.usenet_retention_days);
+            if let (Some(retention_days), ReleaseProtocol::Usenet) = (retention, &release.protocol)
+            {
+                let age_days = age / 24;
+                if age_days > retention_days {
+                    rejections.push(RejectionReason::BeyondRetention {
+                        age_days,
+                        retention_days,
+                    });
+                }
             }
         }
 
-        true
+        let languages = release.languages();
+        if !self.language_profile.is_allowed(&languages) {
+            rejections.push(RejectionReason::LanguageNotAllowed { languages });
+        }
+
+        rejections
     }
 
     /// Calculate seeders score (more seeders = better)
@@ -276,6 +878,17 @@ impl DecisionEngine {
         }
     }
 
+    /// Calculate custom format score using the configured formats
+    fn calculate_custom_format_score(&self, release: &Release) -> i32 {
+        match &self.custom_formats {
+            Some(engine) => engine.calculate_format_score(&ReleaseData {
+                movie_ratings: self.movie_ratings.clone(),
+                ..ReleaseData::from_search_result(release)
+            }),
+            None => 0,
+        }
+    }
+
     /// Calculate bonus score for special features
     fn calculate_bonus_score(&self, release: &Release) -> i32 {
         let mut bonus = 0;
@@ -285,6 +898,14 @@ impl DecisionEngine {
             bonus += 20;
         }
 
+        // Preferred languages, best first
+        bonus += self.language_profile.score(&release.languages());
+
+        // Propers and repacks fix a broken release of the same quality
+        if self.proper_handling == ProperHandling::Prefer && release.revision().is_fix() {
+            bonus += 15;
+        }
+
         // Known good release groups
         if let Some(ref group) = release.release_group {
             let group_lower = group.to_lowercase();
@@ -398,6 +1019,276 @@ mod tests {
         assert!(result.is_none()); // No suitable releases
     }
 
+    #[test]
+    fn test_upgrade_requires_better_quality_below_cutoff() {
+        let profile = QualityProfile::default(); // cutoff 1080p
+        let engine = DecisionEngine::permissive(profile);
+        let existing = ExistingFile::new(Quality::HD720p, Source::WebDL, 0);
+
+        let upgrade = create_test_release("Movie.2023.1080p.BluRay.x264");
+        assert!(engine.evaluate_upgrade(&upgrade, Some(&existing)).is_ok());
+
+        let downgrade = create_test_release("Movie.2023.720p.HDTV.x264");
+        assert_eq!(
+            engine.evaluate_upgrade(&downgrade, Some(&existing)),
+            Err(RejectionReason::NotAnUpgrade {
+                existing: Quality::HD720p,
+                candidate: Quality::HD720p,
+            })
+        );
+
+        let at_cutoff = ExistingFile::new(Quality::HD1080p, Source::WebDL, 0);
+        let uhd = create_test_release("Movie.2023.2160p.BluRay.x264");
+        assert_eq!(
+            engine.evaluate_upgrade(&uhd, Some(&at_cutoff)),
+            Err(RejectionReason::CutoffMet {
+                existing: Quality::HD1080p,
+                cutoff: Quality::HD1080p,
+            })
+        );
+    }
+
+    #[test]
+    fn test_same_quality_upgrade_needs_higher_format_score() {
+        use crate::custom_formats::{CustomFormat, FormatSpecification};
+
+        let engine = DecisionEngine::permissive(QualityProfile::default()).with_custom_formats(
+            CustomFormatEngine::with_formats(vec![
+                CustomFormat::new("x265", 10).add_spec(FormatSpecification::new("codec", "x265"))
+            ]),
+        );
+        let existing = ExistingFile::new(Quality::HD720p, Source::BluRay, 0);
+
+        let x265 = create_test_release("Movie.2023.720p.BluRay.x265");
+        assert_eq!(
+            engine
+                .evaluate_upgrade(&x265, Some(&existing))
+                .unwrap()
+                .custom_format_score,
+            10
+        );
+
+        let x264 = create_test_release("Movie.2023.720p.BluRay.x264");
+        assert_eq!(
+            engine.evaluate_upgrade(&x264, Some(&existing)),
+            Err(RejectionReason::FormatScoreNotHigher {
+                existing: 0,
+                candidate: 0,
+            })
+        );
+
+        // No existing file: any allowed release is approved
+        let best = engine.select_best_upgrade(vec![x264, x265], &[]).unwrap();
+        assert!(best.title.contains("x265"));
+    }
+
+    #[test]
+    fn test_other_edition_is_not_an_upgrade_candidate() {
+        let engine = DecisionEngine::permissive(QualityProfile::default());
+        let theatrical = ExistingFile::new(Quality::HD1080p, Source::BluRay, 0);
+        let directors_cut =
+            ExistingFile::new(Quality::HD720p, Source::BluRay, 0).with_edition("Director's Cut");
+
+        // Compared with the Director's Cut file, not the theatrical one at cutoff
+        let release = create_test_release("Movie.2023.Directors.Cut.1080p.BluRay.x264");
+        assert!(engine.evaluate_upgrade(&release, Some(&theatrical)).is_ok());
+        let files = [theatrical.clone(), directors_cut.clone()];
+        assert_eq!(
+            ExistingFile::for_release(&files, &release),
+            Some(&directors_cut)
+        );
+
+        let same_edition = create_test_release("Movie.2023.Directors.Cut.720p.BluRay.x264");
+        let extended = create_test_release("Movie.2023.Extended.720p.BluRay.x264");
+        let best = engine
+            .select_best_upgrade(vec![same_edition, extended], &files)
+            .unwrap();
+        assert!(best.title.contains("Extended"));
+    }
+
+    #[test]
+    fn test_proper_replaces_file_of_same_quality() {
+        let engine = DecisionEngine::permissive(QualityProfile::default());
+        // At the 1080p cutoff, so only a fix of the same quality is an upgrade
+        let existing = ExistingFile::new(Quality::HD1080p, Source::BluRay, 0);
+
+        let proper = create_test_release("Movie.2023.PROPER.1080p.BluRay.x264-OTHER");
+        assert!(engine.evaluate_upgrade(&proper, Some(&existing)).is_ok());
+
+        let repacked = existing
+            .clone()
+            .with_revision(Revision::parse("Movie.2023.REPACK2.1080p"));
+        assert_eq!(
+            engine.evaluate_upgrade(&proper, Some(&repacked)),
+            Err(RejectionReason::RevisionNotHigher {
+                existing: repacked.revision,
+                candidate: proper.revision(),
+            })
+        );
+
+        let ignoring = DecisionEngine::permissive(QualityProfile::default())
+            .with_proper_handling(ProperHandling::Ignore);
+        assert_eq!(
+            ignoring.evaluate_upgrade(&proper, Some(&existing)),
+            Err(RejectionReason::FormatScoreNotHigher {
+                existing: 0,
+                candidate: 0,
+            })
+        );
+        assert_eq!(
+            "IGNORE".parse::<ProperHandling>().unwrap(),
+            ignoring.proper_handling
+        );
+    }
+
+    #[test]
+    fn test_language_profile_filters_and_prefers() {
+        let engine = DecisionEngine::permissive(QualityProfile::default()).with_language_profile(
+            LanguageProfile {
+                preferred: vec![Language::German, Language::English],
+                allowed: Vec::new(),
+            },
+        );
+
+        let french = create_test_release("Movie.2023.FRENCH.1080p.BluRay.x264-GRP");
+        assert_eq!(
+            engine.assess_release(&french, None).rejections,
+            vec![RejectionReason::LanguageNotAllowed {
+                languages: vec![Language::French],
+            }]
+        );
+
+        let english = create_test_release("Movie.2023.1080p.BluRay.x264-GRP");
+        let german = create_test_release("Movie.2023.German.DL.1080p.BluRay.x264-GRP");
+        let best = engine
+            .select_best_release(vec![french, english, german])
+            .unwrap();
+        assert!(best.title.contains("German"));
+    }
+
+    #[test]
+    fn test_existing_file_from_movie_file() {
+        let file = MovieFile::new(
+            uuid::Uuid::new_v4(),
+            "Movie (2023)/Movie.2023.1080p.WEB-DL.mkv".to_string(),
+            1024,
+            serde_json::json!({"quality_name": "WEBDL-1080p", "resolution": "1080p"}),
+        );
+
+        let existing = ExistingFile::from_movie_file(&file);
+        assert_eq!(existing.quality, Quality::HD1080p);
+        assert_eq!(existing.source, Source::WebDL);
+    }
+
+    #[test]
+    fn test_assess_release_collects_all_rejections() {
+        let profile = QualityProfile::default();
+        let mut engine = DecisionEngine::new(profile);
+        engine.min_seeders = Some(5);
+        engine.max_size_gb = Some(10);
+
+        let release = create_test_release("Movie.2023.480p.DVD.x264")
+            .with_seeders(2)
+            .with_size(20 * 1024 * 1024 * 1024);
+        let score = engine.assess_release(&release, None);
+
+        assert!(!score.is_approved());
+        assert_eq!(
+            score.rejections,
+            vec![
+                RejectionReason::SizeTooLarge {
+                    size_gb: 20,
+                    max_gb: 10
+                },
+                RejectionReason::TooFewSeeders {
+                    seeders: 2,
+                    minimum: 5
+                },
+                RejectionReason::QualityNotAllowed {
+                    quality: Quality::SD
+                },
+            ]
+        );
+        assert_eq!(
+            score.rejections[0].to_string(),
+            "Size 20 GB exceeds maximum of 10 GB"
+        );
+
+        let good = create_test_release("Movie.2023.1080p.BluRay.x264").with_seeders(10);
+        assert!(engine.assess_release(&good, None).is_approved());
+    }
+
+    #[test]
+    fn test_storage_hold_makes_releases_pending() {
+        use radarr_core::models::RootFolder;
+        use radarr_core::services::{StorageThrottle, StorageThrottleConfig};
+
+        let throttle = StorageThrottle::new(StorageThrottleConfig {
+            threshold_bytes: 100,
+            resume_margin_bytes: 0,
+        });
+        let mut folder = RootFolder::new("/media/movies".to_string());
+        folder.id = 1;
+        throttle.record(&folder, 10);
+
+        let engine = DecisionEngine::permissive(QualityProfile::default())
+            .with_storage_hold(throttle.hold_for("/media/movies/Dune (2021)"));
+        let release = create_test_release("Movie.2023.1080p.BluRay.x264");
+        let score = engine.assess_release(&release, None);
+
+        assert!(!score.is_approved());
+        assert!(score.is_pending());
+
+        // A release rejected for a lasting reason is not pending
+        let sd = create_test_release("Movie.2023.480p.DVD.x264");
+        let mut strict = DecisionEngine::new(QualityProfile::default())
+            .with_storage_hold(throttle.hold_for("/media/movies"));
+        strict.min_seeders = None;
+        assert!(!strict.assess_release(&sd, None).is_pending());
+    }
+
+    #[test]
+    fn test_release_age_and_retention() {
+        let mut engine = DecisionEngine::permissive(QualityProfile::default());
+        engine.min_age_hours = Some(2);
+        engine.usenet_retention_days = Some(30);
+
+        let fresh = create_test_release("Movie.2023.1080p.BluRay.x264")
+            .with_publish_date(Utc::now() - chrono::Duration::minutes(30));
+        assert_eq!(
+            engine.constraint_rejections(&fresh),
+            vec![RejectionReason::TooNew {
+                age_hours: 0,
+                min_hours: 2
+            }]
+        );
+
+        // Retention only applies to usenet releases
+        let old_torrent =
+            create_test_release("Movie.2023.1080p.BluRay.x264").with_age_hours(24 * 40);
+        assert!(engine.meets_constraints(&old_torrent));
+
+        let old_nzb = Release::from_title(
+            "Movie.2023.1080p.BluRay.x264".to_string(),
+            "http://indexer/get/123.nzb".to_string(),
+        )
+        .with_age_hours(24 * 40);
+        assert_eq!(old_nzb.protocol, ReleaseProtocol::Usenet);
+        assert_eq!(
+            engine.constraint_rejections(&old_nzb),
+            vec![RejectionReason::BeyondRetention {
+                age_days: 40,
+                retention_days: 30
+            }]
+        );
+
+        // Profile limits override the engine's
+        engine.quality_profile.min_age_hours = Some(0);
+        engine.quality_profile.usenet_retention_days = Some(60);
+        assert!(engine.meets_constraints(&fresh));
+        assert!(engine.meets_constraints(&old_nzb));
+    }
+
     #[test]
     fn test_constraint_filtering() {
         let profile = QualityProfile::default();
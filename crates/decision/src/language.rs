@@ -0,0 +1,89 @@
+//! Language profiles
+//!
+//! A language profile lists the languages a user wants releases in, best
+//! first, and other languages they accept. Releases in none of them are
+//! rejected; among acceptable releases, earlier preferred languages score
+//! higher.
+
+use radarr_core::models::Language;
+use serde::{Deserialize, Serialize};
+
+/// How much more a preferred language scores than the next one
+const PREFERRED_LANGUAGE_STEP: i32 = 10;
+
+/// Preferred and allowed release languages
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LanguageProfile {
+    /// Wanted languages, best first
+    #[serde(default)]
+    pub preferred: Vec<Language>,
+    /// Other acceptable languages
+    #[serde(default)]
+    pub allowed: Vec<Language>,
+}
+
+impl Default for LanguageProfile {
+    fn default() -> Self {
+        Self {
+            preferred: vec![Language::English],
+            allowed: Vec::new(),
+        }
+    }
+}
+
+impl LanguageProfile {
+    /// A profile that accepts every language and prefers none
+    pub fn any() -> Self {
+        Self {
+            preferred: Vec::new(),
+            allowed: Vec::new(),
+        }
+    }
+
+    /// Whether a release in `languages` is acceptable
+    ///
+    /// A profile without languages accepts every release.
+    pub fn is_allowed(&self, languages: &[Language]) -> bool {
+        if self.preferred.is_empty() && self.allowed.is_empty() {
+            return true;
+        }
+        languages
+            .iter()
+            .any(|language| self.preferred.contains(language) || self.allowed.contains(language))
+    }
+
+    /// Score bonus of a release in `languages`, from its best preferred
+    /// language; the last preferred language and other languages score 0
+    pub fn score(&self, languages: &[Language]) -> i32 {
+        let last = self.preferred.len() as i32 - 1;
+        self.preferred
+            .iter()
+            .position(|preferred| languages.contains(preferred))
+            .map_or(0, |index| (last - index as i32) * PREFERRED_LANGUAGE_STEP)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_language_profile() {
+        let profile = LanguageProfile {
+            preferred: vec![Language::French, Language::English],
+            allowed: vec![Language::German],
+        };
+
+        assert!(profile.is_allowed(&[Language::German]));
+        assert!(!profile.is_allowed(&[Language::Italian]));
+        assert!(profile.score(&[Language::French]) > profile.score(&[Language::English]));
+        // A MULTI release scores for its best language
+        assert_eq!(
+            profile.score(&[Language::English, Language::French]),
+            profile.score(&[Language::French])
+        );
+        assert_eq!(profile.score(&[Language::German]), 0);
+
+        assert!(LanguageProfile::any().is_allowed(&[Language::Hindi]));
+    }
+}
@@ -5,9 +5,17 @@
 
 pub mod custom_formats;
 pub mod engine;
+pub mod language;
 pub mod quality;
+pub mod release_cache;
 
 // Re-export main types
-pub use custom_formats::{CustomFormat, CustomFormatEngine, FormatSpecification, ReleaseData};
-pub use engine::{DecisionEngine, Release, ReleaseScore};
+pub use custom_formats::{
+    CustomFormat, CustomFormatEngine, FormatSpecification, FormatValidationError, ReleaseData,
+};
+pub use engine::{
+    DecisionEngine, ExistingFile, ProperHandling, RejectionReason, Release, ReleaseScore,
+};
+pub use language::LanguageProfile;
 pub use quality::{Quality, QualityItem, QualityProfile, Source};
+pub use release_cache::{ReleaseScoreCache, ScoredRelease};
@@ -136,6 +136,15 @@ pub struct QualityProfile {
     pub min_format_score: i32,
     /// Whether upgrades are allowed
     pub upgrade_allowed: bool,
+    /// Minimum release age in hours, overriding the engine's
+    #[serde(default)]
+    pub min_age_hours: Option<u32>,
+    /// Maximum release age in hours, overriding the engine's
+    #[serde(default)]
+    pub max_age_hours: Option<u32>,
+    /// Usenet retention in days, overriding the engine's
+    #[serde(default)]
+    pub usenet_retention_days: Option<u32>,
 }
 
 impl QualityProfile {
@@ -148,6 +157,9 @@ impl QualityProfile {
             items: Self::default_quality_items(),
             min_format_score: 0,
             upgrade_allowed: true,
+            min_age_hours: None,
+            max_age_hours: None,
+            usenet_retention_days: None,
         }
     }
 
@@ -0,0 +1,252 @@
+//! Cache of recently seen releases with their custom format scores
+//!
+//! Search results are kept here together with the data needed to score them,
+//! so that editing a custom format can re-score what the user is currently
+//! looking at without hitting the indexers again.
+
+use crate::custom_formats::{CustomFormatEngine, ReleaseData};
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::RwLock;
+
+/// Default number of releases kept before the oldest are evicted
+pub const DEFAULT_RELEASE_CACHE_CAPACITY: usize = 1000;
+
+/// A cached release and its most recent custom format score
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScoredRelease {
+    pub guid: String,
+    pub title: String,
+    pub indexer: String,
+    pub custom_format_score: i32,
+    pub custom_formats: Vec<String>,
+    /// Where the release can be fetched from, used for manual grabs
+    #[serde(skip)]
+    pub download_url: Option<String>,
+    #[serde(skip)]
+    pub release: ReleaseData,
+}
+
+impl ScoredRelease {
+    fn score(
+        guid: String,
+        download_url: Option<String>,
+        release: ReleaseData,
+        engine: &CustomFormatEngine,
+    ) -> Self {
+        let custom_format_score = engine.calculate_format_score(&release);
+        let custom_formats = engine
+            .get_matching_formats(&release)
+            .into_iter()
+            .map(|format| format.name.clone())
+            .collect();
+
+        Self {
+            guid,
+            title: release.title.clone(),
+            indexer: release.indexer.clone(),
+            custom_format_score,
+            custom_formats,
+            download_url,
+            release,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct CacheEntries {
+    releases: HashMap<String, ScoredRelease>,
+    order: VecDeque<String>,
+}
+
+/// Bounded, thread-safe cache of scored releases keyed by GUID
+#[derive(Debug)]
+pub struct ReleaseScoreCache {
+    capacity: usize,
+    entries: RwLock<CacheEntries>,
+}
+
+impl ReleaseScoreCache {
+    /// Create a cache holding at most `capacity` releases
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            entries: RwLock::new(CacheEntries::default()),
+        }
+    }
+
+    /// Score a release and store it, replacing any entry with the same GUID
+    pub fn insert(
+        &self,
+        guid: impl Into<String>,
+        release: ReleaseData,
+        engine: &CustomFormatEngine,
+    ) -> ScoredRelease {
+        self.insert_with_download_url(guid, None, release, engine)
+    }
+
+    /// Like [`insert`](Self::insert), also remembering where the release can be downloaded
+    pub fn insert_with_download_url(
+        &self,
+        guid: impl Into<String>,
+        download_url: Option<String>,
+        release: ReleaseData,
+        engine: &CustomFormatEngine,
+    ) -> ScoredRelease {
+        let guid = guid.into();
+        let scored = ScoredRelease::score(guid.clone(), download_url, release, engine);
+
+        let mut entries = self.entries.write().unwrap_or_else(|e| e.into_inner());
+        if entries
+            .releases
+            .insert(guid.clone(), scored.clone())
+            .is_none()
+        {
+            entries.order.push_back(guid);
+        }
+        while entries.order.len() > self.capacity {
+            if let Some(oldest) = entries.order.pop_front() {
+                entries.releases.remove(&oldest);
+            }
+        }
+
+        scored
+    }
+
+    /// Re-score every cached release, returning how many scores changed
+    pub fn rescore(&self, engine: &CustomFormatEngine) -> usize {
+        let mut entries = self.entries.write().unwrap_or_else(|e| e.into_inner());
+        let mut changed = 0;
+
+        for cached in entries.releases.values_mut() {
+            let rescored = ScoredRelease::score(
+                cached.guid.clone(),
+                cached.download_url.clone(),
+                cached.release.clone(),
+                engine,
+            );
+            if rescored.custom_format_score != cached.custom_format_score
+                || rescored.custom_formats != cached.custom_formats
+            {
+                changed += 1;
+            }
+            *cached = rescored;
+        }
+
+        tracing::debug!(
+            "Re-scored {} cached releases, {} changed",
+            entries.releases.len(),
+            changed
+        );
+        changed
+    }
+
+    /// Look up a cached release by GUID
+    pub fn get(&self, guid: &str) -> Option<ScoredRelease> {
+        let entries = self.entries.read().unwrap_or_else(|e| e.into_inner());
+        entries.releases.get(guid).cloned()
+    }
+
+    /// All cached releases, highest score first
+    pub fn list(&self) -> Vec<ScoredRelease> {
+        let entries = self.entries.read().unwrap_or_else(|e| e.into_inner());
+        let mut releases: Vec<ScoredRelease> = entries.releases.values().cloned().collect();
+        releases.sort_by(|a, b| {
+            b.custom_format_score
+                .cmp(&a.custom_format_score)
+                .then_with(|| a.title.cmp(&b.title))
+        });
+        releases
+    }
+
+    /// Number of cached releases
+    pub fn len(&self) -> usize {
+        let entries = self.entries.read().unwrap_or_else(|e| e.into_inner());
+        entries.releases.len()
+    }
+
+    /// Whether the cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for ReleaseScoreCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_RELEASE_CACHE_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::custom_formats::{CustomFormat, FormatSpecification};
+
+    fn release(title: &str) -> ReleaseData {
+        ReleaseData {
+            title: title.to_string(),
+            size_bytes: Some(4 * 1024 * 1024 * 1024),
+            seeders: Some(10),
+            leechers: None,
+            freeleech: Some(false),
+            internal: Some(false),
+            indexer: "TestIndexer".to_string(),
+            release_group: title.split('-').next_back().map(|s| s.to_string()),
+            movie_ratings: None,
+        }
+    }
+
+    fn x265_engine(score: i32) -> CustomFormatEngine {
+        CustomFormatEngine::with_formats(vec![
+            CustomFormat::new("x265", score).add_spec(FormatSpecification::new("codec", "x265"))
+        ])
+    }
+
+    #[test]
+    fn test_rescore_applies_new_formats() {
+        let cache = ReleaseScoreCache::default();
+        let engine = x265_engine(5);
+        cache.insert("a", release("Movie.2024.1080p.x265-GRP"), &engine);
+        cache.insert("b", release("Movie.2024.1080p.x264-GRP"), &engine);
+        assert_eq!(cache.get("a").unwrap().custom_format_score, 5);
+
+        let changed = cache.rescore(&x265_engine(50));
+        assert_eq!(changed, 1);
+        assert_eq!(cache.get("a").unwrap().custom_format_score, 50);
+        assert_eq!(cache.get("b").unwrap().custom_format_score, 0);
+        assert_eq!(cache.list()[0].guid, "a");
+    }
+
+    #[test]
+    fn test_capacity_evicts_oldest() {
+        let cache = ReleaseScoreCache::new(2);
+        let engine = x265_engine(5);
+        cache.insert("a", release("A.x265-GRP"), &engine);
+        cache.insert("b", release("B.x265-GRP"), &engine);
+        cache.insert("c", release("C.x265-GRP"), &engine);
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get("a").is_none());
+        assert!(cache.get("c").is_some());
+    }
+
+    #[test]
+    fn test_download_url_survives_rescore() {
+        let cache = ReleaseScoreCache::default();
+        cache.insert_with_download_url(
+            "a",
+            Some("magnet:?xt=urn:btih:abc".to_string()),
+            release("A.x265-GRP"),
+            &x265_engine(5),
+        );
+        cache.rescore(&x265_engine(10));
+
+        let cached = cache.get("a").unwrap();
+        assert_eq!(cached.custom_format_score, 10);
+        assert_eq!(
+            cached.download_url.as_deref(),
+            Some("magnet:?xt=urn:btih:abc")
+        );
+    }
+}
@@ -1,15 +1,21 @@
 //! Radarr downloaders module
 //!
 //! This crate provides integrations with various download clients
-//! used for downloading torrents and managing the download queue.
+//! used for downloading torrents and NZBs and managing the download queue.
 
 pub mod qbittorrent;
+pub mod sabnzbd;
+pub mod torrent;
 
 #[cfg(test)]
 mod tests;
 
 // Re-export public types
 pub use qbittorrent::{
-    AddTorrentParams, AppPreferences, QBittorrentClient, QBittorrentConfig, TorrentData,
-    TorrentInfo,
+    AddTorrentParams, AppPreferences, PieceState, QBittorrentClient, QBittorrentConfig,
+    ServerState, TorrentData, TorrentFile, TorrentInfo,
+};
+pub use sabnzbd::{HistorySlot, QueueSlot, SabnzbdClient, SabnzbdConfig, SabnzbdQueue};
+pub use torrent::{
+    Bencode, FetchedTorrent, MagnetLink, TorrentFetcher, TorrentMetaFile, TorrentMetainfo,
 };
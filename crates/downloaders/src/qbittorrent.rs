@@ -10,13 +10,15 @@ use tokio::sync::RwLock;
 
 use radarr_core::{
     circuit_breaker::{CircuitBreaker, CircuitBreakerConfig},
-    RadarrError, Result,
+    RadarrError, Result, TracedRequest,
 };
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use tracing::{debug, info, warn};
+use tracing::{debug, info, instrument, warn};
 use url::Url;
 
+use crate::torrent::TorrentMetainfo;
+
 /// Configuration for qBittorrent client
 #[derive(Debug, Clone)]
 pub struct QBittorrentConfig {
@@ -85,6 +87,62 @@ pub struct TorrentInfo {
     pub category: String,
     /// Save path
     pub save_path: String,
+    /// Pieces are downloaded in order
+    #[serde(default)]
+    pub seq_dl: bool,
+    /// Total uploaded bytes
+    #[serde(default)]
+    pub uploaded: u64,
+    /// Share ratio (uploaded / downloaded)
+    #[serde(default)]
+    pub ratio: f64,
+    /// Seconds spent seeding since completion
+    #[serde(default)]
+    pub seeding_time: i64,
+}
+
+/// A file inside a torrent, from qBittorrent's `torrents/files` endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TorrentFile {
+    /// Path relative to the torrent's save path
+    pub name: String,
+    /// File size in bytes
+    pub size: u64,
+    /// Download progress (0.0 to 1.0)
+    pub progress: f64,
+    /// Download priority (0 = skipped)
+    pub priority: i32,
+    /// First and last piece holding the file's data
+    pub piece_range: [usize; 2],
+}
+
+/// Download state of a single piece from `torrents/pieceStates`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "u8", into = "u8")]
+pub enum PieceState {
+    Missing,
+    Downloading,
+    Downloaded,
+}
+
+impl From<u8> for PieceState {
+    fn from(state: u8) -> Self {
+        match state {
+            2 => PieceState::Downloaded,
+            1 => PieceState::Downloading,
+            _ => PieceState::Missing,
+        }
+    }
+}
+
+impl From<PieceState> for u8 {
+    fn from(state: PieceState) -> Self {
+        match state {
+            PieceState::Missing => 0,
+            PieceState::Downloading => 1,
+            PieceState::Downloaded => 2,
+        }
+    }
 }
 
 /// Parameters for adding a torrent
@@ -143,6 +201,23 @@ pub struct AppPreferences {
     pub up_limit: Option<u64>,
 }
 
+/// Global transfer state from qBittorrent's `sync/maindata` endpoint
+#[derive(Debug, Default, Deserialize)]
+pub struct ServerState {
+    /// Free space in the default save path, in bytes
+    pub free_space_on_disk: Option<u64>,
+    /// Global download speed (bytes/sec)
+    pub dl_info_speed: Option<u64>,
+    /// Global upload speed (bytes/sec)
+    pub up_info_speed: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MainData {
+    #[serde(default)]
+    server_state: ServerState,
+}
+
 impl QBittorrentClient {
     /// Create a new qBittorrent client
     pub fn new(config: QBittorrentConfig) -> Result<Self> {
@@ -247,7 +322,7 @@ impl QBittorrentClient {
             .client
             .post(login_url)
             .form(&form)
-            .send()
+            .send_traced("qBittorrent")
             .await
             .map_err(|e| RadarrError::ExternalServiceError {
                 service: "qBittorrent".to_string(),
@@ -323,6 +398,7 @@ impl QBittorrentClient {
     }
 
     /// Add a torrent to qBittorrent with retry logic
+    #[instrument(skip(self, params), fields(category = ?params.category))]
     pub async fn add_torrent(&self, params: AddTorrentParams) -> Result<String> {
         // Ensure we're authenticated before attempting
         self.ensure_authenticated().await?;
@@ -390,7 +466,7 @@ impl QBittorrentClient {
             .client
             .post(add_url)
             .multipart(form)
-            .send()
+            .send_traced("qBittorrent")
             .await
             .map_err(|e| RadarrError::ExternalServiceError {
                 service: "qBittorrent".to_string(),
@@ -420,10 +496,11 @@ impl QBittorrentClient {
                             Ok(format!("magnet_{:x}", md5::compute(url.as_bytes())))
                         }
                     }
-                    TorrentData::File(data) => {
-                        // For torrent files, we'll use a hash of the file content
-                        Ok(format!("file_{:x}", md5::compute(data)))
-                    }
+                    TorrentData::File(data) => match TorrentMetainfo::from_bytes(data) {
+                        Ok(metainfo) => Ok(metainfo.info_hash),
+                        // Not readable here, so fall back to a hash of the file content
+                        Err(_) => Ok(format!("file_{:x}", md5::compute(data))),
+                    },
                     _ => Ok(format!(
                         "unknown_{}",
                         std::time::SystemTime::now()
@@ -475,12 +552,15 @@ impl QBittorrentClient {
 
         debug!("Fetching torrent list from qBittorrent");
 
-        let response = self.client.get(torrents_url).send().await.map_err(|e| {
-            RadarrError::ExternalServiceError {
+        let response = self
+            .client
+            .get(torrents_url)
+            .send_traced("qBittorrent")
+            .await
+            .map_err(|e| RadarrError::ExternalServiceError {
                 service: "qBittorrent".to_string(),
                 error: format!("Get torrents request failed: {}", e),
-            }
-        })?;
+            })?;
 
         if response.status().is_success() {
             let torrents: Vec<TorrentInfo> =
@@ -508,6 +588,117 @@ impl QBittorrentClient {
         Ok(torrents.into_iter().find(|t| t.hash == hash))
     }
 
+    /// Get the files of a torrent with their piece ranges
+    pub async fn get_torrent_files(&self, hash: &str) -> Result<Vec<TorrentFile>> {
+        self.get_torrent_details("files", hash).await
+    }
+
+    /// Get the download state of every piece of a torrent, in piece order
+    pub async fn get_piece_states(&self, hash: &str) -> Result<Vec<PieceState>> {
+        self.get_torrent_details("pieceStates", hash).await
+    }
+
+    /// GET `api/v2/torrents/{endpoint}?hash=` and parse the JSON response
+    async fn get_torrent_details<T: serde::de::DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        hash: &str,
+    ) -> Result<T> {
+        self.ensure_authenticated().await?;
+
+        let mut details_url = self
+            .base_url
+            .join(&format!("api/v2/torrents/{}", endpoint))
+            .map_err(|e| RadarrError::ExternalServiceError {
+                service: "qBittorrent".to_string(),
+                error: format!("Failed to construct {} URL: {}", endpoint, e),
+            })?;
+        details_url.query_pairs_mut().append_pair("hash", hash);
+
+        let response = self
+            .client
+            .get(details_url)
+            .send_traced("qBittorrent")
+            .await
+            .map_err(|e| RadarrError::ExternalServiceError {
+                service: "qBittorrent".to_string(),
+                error: format!("Get {} request failed: {}", endpoint, e),
+            })?;
+
+        if response.status().is_success() {
+            response
+                .json()
+                .await
+                .map_err(|e| RadarrError::ExternalServiceError {
+                    service: "qBittorrent".to_string(),
+                    error: format!("Failed to parse {} response: {}", endpoint, e),
+                })
+        } else {
+            Err(RadarrError::ExternalServiceError {
+                service: "qBittorrent".to_string(),
+                error: format!("Get {} failed with status: {}", endpoint, response.status()),
+            })
+        }
+    }
+
+    /// Set the download priority of files of a torrent, identified by their
+    /// position in [`get_torrent_files`](Self::get_torrent_files); priority 0
+    /// skips them
+    pub async fn set_file_priority(
+        &self,
+        hash: &str,
+        file_ids: &[usize],
+        priority: i32,
+    ) -> Result<()> {
+        self.ensure_authenticated().await?;
+
+        let priority_url = self
+            .base_url
+            .join("api/v2/torrents/filePrio")
+            .map_err(|e| RadarrError::ExternalServiceError {
+                service: "qBittorrent".to_string(),
+                error: format!("Failed to construct file priority URL: {}", e),
+            })?;
+
+        let ids = file_ids
+            .iter()
+            .map(|id| id.to_string())
+            .collect::<Vec<_>>()
+            .join("|");
+        let priority = priority.to_string();
+        let mut form = HashMap::new();
+        form.insert("hash", hash);
+        form.insert("id", ids.as_str());
+        form.insert("priority", priority.as_str());
+
+        let response = self
+            .client
+            .post(priority_url)
+            .form(&form)
+            .send_traced("qBittorrent")
+            .await
+            .map_err(|e| RadarrError::ExternalServiceError {
+                service: "qBittorrent".to_string(),
+                error: format!("Set file priority request failed: {}", e),
+            })?;
+
+        if response.status().is_success() {
+            debug!(
+                "Set priority {} for files {} of torrent {}",
+                priority, ids, hash
+            );
+            Ok(())
+        } else {
+            Err(RadarrError::ExternalServiceError {
+                service: "qBittorrent".to_string(),
+                error: format!(
+                    "Set file priority failed with status: {}",
+                    response.status()
+                ),
+            })
+        }
+    }
+
     /// Delete a torrent from qBittorrent
     pub async fn delete_torrent(&self, hash: &str, delete_files: bool) -> Result<()> {
         let delete_url = self.base_url.join("api/v2/torrents/delete").map_err(|e| {
@@ -527,7 +718,7 @@ impl QBittorrentClient {
             .client
             .post(delete_url)
             .form(&form)
-            .send()
+            .send_traced("qBittorrent")
             .await
             .map_err(|e| RadarrError::ExternalServiceError {
                 service: "qBittorrent".to_string(),
@@ -561,7 +752,7 @@ impl QBittorrentClient {
             .client
             .post(pause_url)
             .form(&form)
-            .send()
+            .send_traced("qBittorrent")
             .await
             .map_err(|e| RadarrError::ExternalServiceError {
                 service: "qBittorrent".to_string(),
@@ -595,7 +786,7 @@ impl QBittorrentClient {
             .client
             .post(resume_url)
             .form(&form)
-            .send()
+            .send_traced("qBittorrent")
             .await
             .map_err(|e| RadarrError::ExternalServiceError {
                 service: "qBittorrent".to_string(),
@@ -622,12 +813,15 @@ impl QBittorrentClient {
             }
         })?;
 
-        let response = self.client.get(prefs_url).send().await.map_err(|e| {
-            RadarrError::ExternalServiceError {
+        let response = self
+            .client
+            .get(prefs_url)
+            .send_traced("qBittorrent")
+            .await
+            .map_err(|e| RadarrError::ExternalServiceError {
                 service: "qBittorrent".to_string(),
                 error: format!("Get preferences request failed: {}", e),
-            }
-        })?;
+            })?;
 
         if response.status().is_success() {
             let preferences: AppPreferences =
@@ -649,6 +843,47 @@ impl QBittorrentClient {
         }
     }
 
+    /// Get the global server state (free space, transfer speeds)
+    pub async fn get_server_state(&self) -> Result<ServerState> {
+        self.ensure_authenticated().await?;
+
+        let maindata_url = self.base_url.join("api/v2/sync/maindata").map_err(|e| {
+            RadarrError::ExternalServiceError {
+                service: "qBittorrent".to_string(),
+                error: format!("Failed to construct maindata URL: {}", e),
+            }
+        })?;
+
+        let response = self
+            .client
+            .get(maindata_url)
+            .send_traced("qBittorrent")
+            .await
+            .map_err(|e| RadarrError::ExternalServiceError {
+                service: "qBittorrent".to_string(),
+                error: format!("Get maindata request failed: {}", e),
+            })?;
+
+        if response.status().is_success() {
+            let maindata: MainData =
+                response
+                    .json()
+                    .await
+                    .map_err(|e| RadarrError::ExternalServiceError {
+                        service: "qBittorrent".to_string(),
+                        error: format!("Failed to parse maindata response: {}", e),
+                    })?;
+
+            debug!("Retrieved qBittorrent server state");
+            Ok(maindata.server_state)
+        } else {
+            Err(RadarrError::ExternalServiceError {
+                service: "qBittorrent".to_string(),
+                error: format!("Get maindata failed with status: {}", response.status()),
+            })
+        }
+    }
+
     /// Check if the client can connect to qBittorrent
     pub async fn test_connection(&self) -> Result<()> {
         debug!("Testing connection to qBittorrent");
@@ -676,7 +911,7 @@ impl QBittorrentClient {
                 let response = client_clone
                     .post(login_url)
                     .form(&form)
-                    .send()
+                    .send_traced("qBittorrent")
                     .await
                     .map_err(|e| RadarrError::ExternalServiceError {
                         service: "qBittorrent".to_string(),
@@ -714,12 +949,14 @@ impl QBittorrentClient {
                     }
                 })?;
 
-                let prefs_response = client_clone.get(prefs_url).send().await.map_err(|e| {
-                    RadarrError::ExternalServiceError {
+                let prefs_response = client_clone
+                    .get(prefs_url)
+                    .send_traced("qBittorrent")
+                    .await
+                    .map_err(|e| RadarrError::ExternalServiceError {
                         service: "qBittorrent".to_string(),
                         error: format!("Get preferences request failed: {}", e),
-                    }
-                })?;
+                    })?;
 
                 if !prefs_response.status().is_success() {
                     return Err(RadarrError::ExternalServiceError {
@@ -780,6 +1017,20 @@ mod tests {
         assert!(client.is_err());
     }
 
+    #[test]
+    fn test_maindata_server_state_parsing() {
+        let json = r#"{"rid":1,"full_update":true,"server_state":{"free_space_on_disk":53687091200,"dl_info_speed":1024,"up_info_speed":0,"connection_status":"connected"}}"#;
+        let maindata: MainData = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            maindata.server_state.free_space_on_disk,
+            Some(53_687_091_200)
+        );
+
+        // Incremental updates may omit server_state entirely
+        let maindata: MainData = serde_json::from_str(r#"{"rid":2}"#).unwrap();
+        assert!(maindata.server_state.free_space_on_disk.is_none());
+    }
+
     #[test]
     fn test_add_torrent_params_default() {
         let params = AddTorrentParams::default();
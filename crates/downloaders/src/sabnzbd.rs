@@ -0,0 +1,507 @@
+//! SABnzbd client implementation for usenet downloads
+//!
+//! This module provides a client for SABnzbd's JSON API. Every call is a GET
+//! on `/api` with a `mode` and the API key; NZBs are added by URL so SABnzbd
+//! fetches them from the indexer itself.
+
+use std::time::Duration;
+
+use radarr_core::{RadarrError, Result, TracedRequest};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::{debug, info, instrument};
+use url::Url;
+
+/// Configuration for SABnzbd client
+#[derive(Debug, Clone)]
+pub struct SabnzbdConfig {
+    /// Base URL of the SABnzbd web interface (e.g., "http://localhost:8085")
+    pub base_url: String,
+    /// API key from SABnzbd's General settings
+    pub api_key: String,
+    /// Request timeout in seconds
+    pub timeout: u64,
+}
+
+impl Default for SabnzbdConfig {
+    fn default() -> Self {
+        Self {
+            base_url: "http://localhost:8085".to_string(),
+            api_key: String::new(),
+            timeout: 30,
+        }
+    }
+}
+
+/// SABnzbd client for managing usenet downloads
+#[derive(Debug)]
+pub struct SabnzbdClient {
+    config: SabnzbdConfig,
+    client: Client,
+    api_url: Url,
+}
+
+/// The download queue from `mode=queue`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SabnzbdQueue {
+    /// Queued and active downloads
+    #[serde(default)]
+    pub slots: Vec<QueueSlot>,
+    /// Free space (GB) of the incomplete download folder
+    #[serde(default)]
+    pub diskspace1: String,
+    /// Free space (GB) of the complete download folder
+    #[serde(default)]
+    pub diskspace2: String,
+    /// Overall download speed in KB/s
+    #[serde(default)]
+    pub kbpersec: String,
+}
+
+/// A queued or active download
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueueSlot {
+    /// SABnzbd download ID (`SABnzbd_nzo_...`)
+    pub nzo_id: String,
+    /// Job name
+    pub filename: String,
+    /// Queued, Paused, Downloading, Fetching, Grabbing or Propagating
+    pub status: String,
+    /// Download progress in percent
+    #[serde(default)]
+    pub percentage: String,
+    /// Total size in MB
+    #[serde(default)]
+    pub mb: String,
+    /// Size left in MB
+    #[serde(default)]
+    pub mbleft: String,
+    /// Time left as `H:MM:SS`
+    #[serde(default)]
+    pub timeleft: String,
+    /// Category
+    #[serde(default)]
+    pub cat: String,
+}
+
+/// A finished, failed or post-processing download from `mode=history`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistorySlot {
+    /// SABnzbd download ID (`SABnzbd_nzo_...`)
+    pub nzo_id: String,
+    /// Job name
+    pub name: String,
+    /// Completed, Failed, or a post-processing stage such as Extracting
+    pub status: String,
+    /// Total size in bytes
+    #[serde(default)]
+    pub bytes: u64,
+    /// Final folder of the job once it has completed
+    #[serde(default)]
+    pub storage: Option<String>,
+    /// Why the job failed
+    #[serde(default)]
+    pub fail_message: String,
+    /// Category
+    #[serde(default)]
+    pub category: String,
+}
+
+impl QueueSlot {
+    /// Download progress (0.0 to 1.0)
+    pub fn progress(&self) -> f64 {
+        self.percentage.parse::<f64>().unwrap_or(0.0) / 100.0
+    }
+
+    /// Total size in bytes
+    pub fn size_bytes(&self) -> Option<u64> {
+        megabytes_to_bytes(&self.mb)
+    }
+
+    /// Downloaded bytes so far
+    pub fn downloaded_bytes(&self) -> Option<u64> {
+        Some(
+            self.size_bytes()?
+                .saturating_sub(megabytes_to_bytes(&self.mbleft)?),
+        )
+    }
+
+    /// Time left in seconds, if SABnzbd has an estimate
+    pub fn eta_seconds(&self) -> Option<i64> {
+        let mut seconds = 0i64;
+        for part in self.timeleft.split(':') {
+            seconds = seconds * 60 + part.trim().parse::<i64>().ok()?;
+        }
+        Some(seconds)
+    }
+}
+
+impl SabnzbdQueue {
+    /// Free space (bytes) available to new downloads, the smaller of the
+    /// incomplete and complete folders
+    pub fn free_space_bytes(&self) -> Option<u64> {
+        [&self.diskspace1, &self.diskspace2]
+            .into_iter()
+            .filter_map(|gb| gb.parse::<f64>().ok())
+            .map(|gb| (gb * 1024.0 * 1024.0 * 1024.0) as u64)
+            .min()
+    }
+}
+
+fn megabytes_to_bytes(mb: &str) -> Option<u64> {
+    mb.parse::<f64>()
+        .ok()
+        .map(|mb| (mb * 1024.0 * 1024.0) as u64)
+}
+
+/// Fail on SABnzbd's `{"status": false, "error": "..."}` responses
+pub fn check_api_error(body: &Value) -> Result<()> {
+    if body.get("status").and_then(Value::as_bool) == Some(false) {
+        let error = body
+            .get("error")
+            .and_then(Value::as_str)
+            .unwrap_or("unknown error");
+        return Err(RadarrError::ExternalServiceError {
+            service: "SABnzbd".to_string(),
+            error: error.to_string(),
+        });
+    }
+    Ok(())
+}
+
+impl SabnzbdClient {
+    /// Create a new SABnzbd client
+    pub fn new(config: SabnzbdConfig) -> Result<Self> {
+        let base_url =
+            Url::parse(&config.base_url).map_err(|e| RadarrError::ExternalServiceError {
+                service: "SABnzbd".to_string(),
+                error: format!("Invalid base URL: {}", e),
+            })?;
+        let api_url = base_url
+            .join("api")
+            .map_err(|e| RadarrError::ExternalServiceError {
+                service: "SABnzbd".to_string(),
+                error: format!("Failed to construct API URL: {}", e),
+            })?;
+
+        let client = Client::builder()
+            .timeout(Duration::from_secs(config.timeout))
+            .build()
+            .map_err(|e| RadarrError::ExternalServiceError {
+                service: "SABnzbd".to_string(),
+                error: format!("Failed to create HTTP client: {}", e),
+            })?;
+
+        Ok(Self {
+            config,
+            client,
+            api_url,
+        })
+    }
+
+    /// Call the API in `mode` with extra query parameters
+    async fn call(&self, mode: &str, params: &[(&str, &str)]) -> Result<Value> {
+        let mut url = self.api_url.clone();
+        url.query_pairs_mut()
+            .append_pair("mode", mode)
+            .append_pair("output", "json")
+            .append_pair("apikey", &self.config.api_key)
+            .extend_pairs(params);
+
+        debug!("Calling SABnzbd API mode {}", mode);
+
+        // Errors carry the URL, and with it the API key, unless stripped
+        let response = self
+            .client
+            .get(url)
+            .send_traced("SABnzbd")
+            .await
+            .map_err(|e| RadarrError::ExternalServiceError {
+                service: "SABnzbd".to_string(),
+                error: format!("{} request failed: {}", mode, e.without_url()),
+            })?;
+
+        if !response.status().is_success() {
+            return Err(RadarrError::ExternalServiceError {
+                service: "SABnzbd".to_string(),
+                error: format!("{} failed with status: {}", mode, response.status()),
+            });
+        }
+
+        let body: Value = response
+            .json()
+            .await
+            .map_err(|e| RadarrError::ExternalServiceError {
+                service: "SABnzbd".to_string(),
+                error: format!("Failed to parse {} response: {}", mode, e.without_url()),
+            })?;
+        check_api_error(&body)?;
+        Ok(body)
+    }
+
+    /// Add an NZB by URL, returning its `nzo_id`
+    #[instrument(skip(self, nzb_url))]
+    pub async fn add_url(&self, nzb_url: &str, category: Option<&str>) -> Result<String> {
+        let mut params = vec![("name", nzb_url)];
+        if let Some(category) = category {
+            params.push(("cat", category));
+        }
+
+        let body = self.call("addurl", &params).await?;
+        let nzo_id = body
+            .get("nzo_ids")
+            .and_then(Value::as_array)
+            .and_then(|ids| ids.first())
+            .and_then(Value::as_str)
+            .ok_or_else(|| RadarrError::ExternalServiceError {
+                service: "SABnzbd".to_string(),
+                error: "addurl response has no nzo_id".to_string(),
+            })?;
+
+        info!("Successfully added NZB to SABnzbd ({})", nzo_id);
+        Ok(nzo_id.to_string())
+    }
+
+    /// Get the download queue
+    pub async fn get_queue(&self) -> Result<SabnzbdQueue> {
+        let body = self.call("queue", &[]).await?;
+        parse_section(body, "queue")
+    }
+
+    /// Get the most recent history entries
+    pub async fn get_history(&self, limit: usize) -> Result<Vec<HistorySlot>> {
+        #[derive(Deserialize)]
+        struct History {
+            #[serde(default)]
+            slots: Vec<HistorySlot>,
+        }
+
+        let limit = limit.to_string();
+        let body = self.call("history", &[("limit", &limit)]).await?;
+        let history: History = parse_section(body, "history")?;
+        Ok(history.slots)
+    }
+
+    /// Remove a download from the queue or the history
+    pub async fn delete(&self, nzo_id: &str, delete_files: bool) -> Result<()> {
+        let del_files = if delete_files { "1" } else { "0" };
+        let params = [
+            ("name", "delete"),
+            ("value", nzo_id),
+            ("del_files", del_files),
+        ];
+
+        // SABnzbd keeps active and finished jobs apart; the ID is in one of them
+        self.call("queue", &params).await?;
+        self.call("history", &params).await?;
+        Ok(())
+    }
+
+    /// Pause a queued download
+    pub async fn pause(&self, nzo_id: &str) -> Result<()> {
+        self.call("queue", &[("name", "pause"), ("value", nzo_id)])
+            .await
+            .map(|_| ())
+    }
+
+    /// Resume a paused download
+    pub async fn resume(&self, nzo_id: &str) -> Result<()> {
+        self.call("queue", &[("name", "resume"), ("value", nzo_id)])
+            .await
+            .map(|_| ())
+    }
+
+    /// Check that SABnzbd is reachable and the API key is accepted
+    pub async fn test_connection(&self) -> Result<()> {
+        // `version` is the only mode that does not check the key
+        self.get_queue().await.map(|_| ())
+    }
+}
+
+fn parse_section<T: serde::de::DeserializeOwned>(mut body: Value, section: &str) -> Result<T> {
+    let section_value = body.get_mut(section).map(Value::take).ok_or_else(|| {
+        RadarrError::ExternalServiceError {
+            service: "SABnzbd".to_string(),
+            error: format!("Response has no '{}' section", section),
+        }
+    })?;
+    serde_json::from_value(section_value).map_err(|e| RadarrError::ExternalServiceError {
+        service: "SABnzbd".to_string(),
+        error: format!("Failed to parse {}: {}", section, e),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn client(server: &MockServer) -> SabnzbdClient {
+        SabnzbdClient::new(SabnzbdConfig {
+            base_url: server.uri(),
+            api_key: "secret".to_string(),
+            timeout: 5,
+        })
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_add_url_returns_nzo_id() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api"))
+            .and(query_param("mode", "addurl"))
+            .and(query_param("apikey", "secret"))
+            .and(query_param("name", "https://indexer/get/1.nzb"))
+            .and(query_param("cat", "movies"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "status": true,
+                "nzo_ids": ["SABnzbd_nzo_abc123"]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let nzo_id = client(&server)
+            .add_url("https://indexer/get/1.nzb", Some("movies"))
+            .await
+            .unwrap();
+        assert_eq!(nzo_id, "SABnzbd_nzo_abc123");
+    }
+
+    #[tokio::test]
+    async fn test_add_url_without_nzo_id_fails() {
+        let server = MockServer::start().await;
+        Mock::given(query_param("mode", "addurl"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(json!({"status": true, "nzo_ids": []})),
+            )
+            .mount(&server)
+            .await;
+
+        let result = client(&server)
+            .add_url("https://indexer/get/1.nzb", None)
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_get_queue_parses_slots() {
+        let server = MockServer::start().await;
+        Mock::given(query_param("mode", "queue"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "queue": {
+                    "diskspace1": "100.5",
+                    "diskspace2": "20.0",
+                    "kbpersec": "2048.0",
+                    "slots": [{
+                        "nzo_id": "SABnzbd_nzo_abc123",
+                        "filename": "Movie.2023.1080p.BluRay.x264",
+                        "status": "Downloading",
+                        "percentage": "25",
+                        "mb": "1024.0",
+                        "mbleft": "768.0",
+                        "timeleft": "0:10:30",
+                        "cat": "movies"
+                    }]
+                }
+            })))
+            .mount(&server)
+            .await;
+
+        let queue = client(&server).get_queue().await.unwrap();
+        assert_eq!(queue.free_space_bytes(), Some(20 * 1024 * 1024 * 1024));
+        let slot = &queue.slots[0];
+        assert_eq!(slot.nzo_id, "SABnzbd_nzo_abc123");
+        assert_eq!(slot.progress(), 0.25);
+        assert_eq!(slot.size_bytes(), Some(1024 * 1024 * 1024));
+        assert_eq!(slot.downloaded_bytes(), Some(256 * 1024 * 1024));
+        assert_eq!(slot.eta_seconds(), Some(630));
+    }
+
+    #[tokio::test]
+    async fn test_get_history_parses_slots() {
+        let server = MockServer::start().await;
+        Mock::given(query_param("mode", "history"))
+            .and(query_param("limit", "10"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "history": {
+                    "slots": [{
+                        "nzo_id": "SABnzbd_nzo_done",
+                        "name": "Movie.2023.1080p.BluRay.x264",
+                        "status": "Completed",
+                        "bytes": 1073741824u64,
+                        "storage": "/downloads/complete/Movie.2023.1080p.BluRay.x264",
+                        "category": "movies"
+                    }, {
+                        "nzo_id": "SABnzbd_nzo_failed",
+                        "name": "Other.2023.720p",
+                        "status": "Failed",
+                        "fail_message": "Unpacking failed, archive requires a password"
+                    }]
+                }
+            })))
+            .mount(&server)
+            .await;
+
+        let history = client(&server).get_history(10).await.unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(
+            history[0].storage.as_deref(),
+            Some("/downloads/complete/Movie.2023.1080p.BluRay.x264")
+        );
+        assert_eq!(history[1].status, "Failed");
+        assert!(history[1].fail_message.contains("password"));
+    }
+
+    #[tokio::test]
+    async fn test_delete_removes_from_queue_and_history() {
+        let server = MockServer::start().await;
+        for mode in ["queue", "history"] {
+            Mock::given(query_param("mode", mode))
+                .and(query_param("name", "delete"))
+                .and(query_param("value", "SABnzbd_nzo_abc123"))
+                .and(query_param("del_files", "1"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({"status": true})))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+
+        client(&server)
+            .delete("SABnzbd_nzo_abc123", true)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_api_error_response_fails() {
+        let server = MockServer::start().await;
+        Mock::given(query_param("mode", "queue"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "status": false,
+                "error": "API Key Incorrect"
+            })))
+            .mount(&server)
+            .await;
+
+        let error = client(&server).get_queue().await.unwrap_err();
+        assert!(error.to_string().contains("API Key Incorrect"));
+    }
+
+    #[tokio::test]
+    async fn test_http_error_status_fails() {
+        let server = MockServer::start().await;
+        Mock::given(query_param("mode", "queue"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&server)
+            .await;
+
+        let error = client(&server).test_connection().await.unwrap_err();
+        assert!(error.to_string().contains("503"));
+        assert!(!error.to_string().contains("secret"));
+    }
+}
@@ -100,6 +100,68 @@ mod integration_tests {
         }
     }
 
+    #[test]
+    fn test_torrent_file_and_piece_state_parsing() {
+        let files: Vec<crate::TorrentFile> = serde_json::from_str(
+            r#"[{"index": 0, "name": "Movie/Movie.mkv", "size": 4096, "progress": 0.5,
+                 "priority": 1, "is_seed": false, "piece_range": [0, 3], "availability": 1.0}]"#,
+        )
+        .unwrap();
+        assert_eq!(files[0].name, "Movie/Movie.mkv");
+        assert_eq!(files[0].piece_range, [0, 3]);
+
+        let pieces: Vec<crate::PieceState> = serde_json::from_str("[2, 2, 1, 0]").unwrap();
+        assert_eq!(
+            pieces,
+            vec![
+                crate::PieceState::Downloaded,
+                crate::PieceState::Downloaded,
+                crate::PieceState::Downloading,
+                crate::PieceState::Missing,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sabnzbd_queue_parsing() {
+        let queue: crate::SabnzbdQueue = serde_json::from_str(
+            r#"{"diskspace1": "120.5", "diskspace2": "80.25", "kbpersec": "2048.0",
+                "slots": [{"nzo_id": "SABnzbd_nzo_abc", "filename": "Movie.2023.1080p",
+                           "status": "Downloading", "percentage": "25", "mb": "4096.0",
+                           "mbleft": "3072.0", "timeleft": "0:12:30", "cat": "movies"}]}"#,
+        )
+        .unwrap();
+
+        let slot = &queue.slots[0];
+        assert_eq!(slot.progress(), 0.25);
+        assert_eq!(slot.size_bytes(), Some(4096 * 1024 * 1024));
+        assert_eq!(slot.downloaded_bytes(), Some(1024 * 1024 * 1024));
+        assert_eq!(slot.eta_seconds(), Some(750));
+        assert_eq!(
+            queue.free_space_bytes(),
+            Some((80.25 * 1024.0 * 1024.0 * 1024.0) as u64)
+        );
+    }
+
+    #[test]
+    fn test_sabnzbd_api_error() {
+        let error = serde_json::json!({"status": false, "error": "API Key Incorrect"});
+        assert!(crate::sabnzbd::check_api_error(&error)
+            .unwrap_err()
+            .to_string()
+            .contains("API Key Incorrect"));
+        assert!(crate::sabnzbd::check_api_error(&serde_json::json!({"status": true})).is_ok());
+    }
+
+    #[test]
+    fn test_sabnzbd_client_with_invalid_url() {
+        let config = crate::SabnzbdConfig {
+            base_url: "not-a-url".to_string(),
+            ..Default::default()
+        };
+        assert!(crate::SabnzbdClient::new(config).is_err());
+    }
+
     // Mock test to verify async functionality compiles
     #[tokio::test]
     async fn test_async_interface_compiles() {
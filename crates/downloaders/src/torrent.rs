@@ -0,0 +1,562 @@
+//! Torrent metainfo and magnet link parsing
+//!
+//! Indexers hand out either `.torrent` files or magnet links. Reading them
+//! before a grab reaches the download client gives the info hash (the ID
+//! qBittorrent knows the torrent by), the exact size and the file list, so
+//! grabs can be checked for free space and for duplicates already in the
+//! queue. Info hashes are rendered as uppercase hex, the form download client
+//! IDs are stored in.
+
+use radarr_core::{RadarrError, Result};
+use reqwest::redirect::Policy;
+use sha1::{Digest, Sha1};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Deepest nesting of lists and dictionaries accepted in a torrent file
+const MAX_DEPTH: usize = 32;
+
+/// A bencoded value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Bencode {
+    Int(i64),
+    Bytes(Vec<u8>),
+    List(Vec<Bencode>),
+    Dict(BTreeMap<Vec<u8>, Bencode>),
+}
+
+impl Bencode {
+    /// Decode a single bencoded value spanning all of `data`
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        let mut decoder = Decoder::new(data);
+        let value = decoder.value(0)?;
+        decoder.finish()?;
+        Ok(value)
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Bencode> {
+        match self {
+            Bencode::Dict(dict) => dict.get(key.as_bytes()),
+            _ => None,
+        }
+    }
+
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            Bencode::Int(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Bencode::Bytes(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    /// Byte string as text, replacing invalid UTF-8
+    pub fn as_string(&self) -> Option<String> {
+        self.as_bytes()
+            .map(|bytes| String::from_utf8_lossy(bytes).into_owned())
+    }
+
+    pub fn as_list(&self) -> Option<&[Bencode]> {
+        match self {
+            Bencode::List(list) => Some(list),
+            _ => None,
+        }
+    }
+}
+
+/// Bencode decoder remembering where the top-level `info` dictionary is,
+/// since the info hash is taken over its exact bytes
+struct Decoder<'a> {
+    data: &'a [u8],
+    pos: usize,
+    info_span: Option<(usize, usize)>,
+}
+
+impl<'a> Decoder<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self {
+            data,
+            pos: 0,
+            info_span: None,
+        }
+    }
+
+    fn error(&self, message: &str) -> RadarrError {
+        RadarrError::ValidationError {
+            field: "torrent".to_string(),
+            message: format!("{} at byte {}", message, self.pos),
+        }
+    }
+
+    fn peek(&self) -> Result<u8> {
+        self.data
+            .get(self.pos)
+            .copied()
+            .ok_or_else(|| self.error("Unexpected end of data"))
+    }
+
+    fn finish(&self) -> Result<()> {
+        if self.pos == self.data.len() {
+            Ok(())
+        } else {
+            Err(self.error("Trailing data"))
+        }
+    }
+
+    fn value(&mut self, depth: usize) -> Result<Bencode> {
+        if depth > MAX_DEPTH {
+            return Err(self.error("Nesting too deep"));
+        }
+        match self.peek()? {
+            b'i' => {
+                self.pos += 1;
+                let value = self.integer(b'e')?;
+                Ok(Bencode::Int(value))
+            }
+            b'0'..=b'9' => self.bytes().map(Bencode::Bytes),
+            b'l' => {
+                self.pos += 1;
+                let mut list = Vec::new();
+                while self.peek()? != b'e' {
+                    list.push(self.value(depth + 1)?);
+                }
+                self.pos += 1;
+                Ok(Bencode::List(list))
+            }
+            b'd' => {
+                self.pos += 1;
+                let mut dict = BTreeMap::new();
+                while self.peek()? != b'e' {
+                    let key = self.bytes()?;
+                    let start = self.pos;
+                    let value = self.value(depth + 1)?;
+                    if depth == 0 && key == b"info" {
+                        self.info_span = Some((start, self.pos));
+                    }
+                    dict.insert(key, value);
+                }
+                self.pos += 1;
+                Ok(Bencode::Dict(dict))
+            }
+            _ => Err(self.error("Invalid bencode value")),
+        }
+    }
+
+    /// Decimal integer up to `end`, which is consumed
+    fn integer(&mut self, end: u8) -> Result<i64> {
+        let start = self.pos;
+        while self.peek()? != end {
+            self.pos += 1;
+        }
+        let text = std::str::from_utf8(&self.data[start..self.pos])
+            .map_err(|_| self.error("Invalid integer"))?;
+        let value = text.parse().map_err(|_| self.error("Invalid integer"))?;
+        self.pos += 1;
+        Ok(value)
+    }
+
+    fn bytes(&mut self) -> Result<Vec<u8>> {
+        let len = self.integer(b':')?;
+        let len = usize::try_from(len).map_err(|_| self.error("Negative length"))?;
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.data.len())
+            .ok_or_else(|| self.error("Byte string past end of data"))?;
+        let bytes = self.data[self.pos..end].to_vec();
+        self.pos = end;
+        Ok(bytes)
+    }
+}
+
+/// A file inside a torrent
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TorrentMetaFile {
+    /// Path relative to the torrent's folder, `/`-separated
+    pub path: String,
+    pub length: u64,
+}
+
+/// What a `.torrent` file describes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TorrentMetainfo {
+    /// SHA-1 of the info dictionary, uppercase hex
+    pub info_hash: String,
+    pub name: String,
+    /// Sum of all file lengths
+    pub total_size: u64,
+    pub files: Vec<TorrentMetaFile>,
+    pub piece_length: u64,
+    /// Announce URLs, the primary tracker first
+    pub trackers: Vec<String>,
+    /// Private torrents only use their own trackers
+    pub private: bool,
+}
+
+impl TorrentMetainfo {
+    /// Parse the contents of a `.torrent` file
+    pub fn from_bytes(data: &[u8]) -> Result<Self> {
+        let mut decoder = Decoder::new(data);
+        let root = decoder.value(0)?;
+        decoder.finish()?;
+        let invalid = |message: &str| RadarrError::ValidationError {
+            field: "torrent".to_string(),
+            message: message.to_string(),
+        };
+
+        let (start, end) = decoder
+            .info_span
+            .ok_or_else(|| invalid("Missing info dictionary"))?;
+        let info = root
+            .get("info")
+            .ok_or_else(|| invalid("Missing info dictionary"))?;
+        let name = info
+            .get("name.utf-8")
+            .or_else(|| info.get("name"))
+            .and_then(Bencode::as_string)
+            .ok_or_else(|| invalid("Missing torrent name"))?;
+
+        let files = match (info.get("length"), info.get("files")) {
+            (Some(length), _) => vec![TorrentMetaFile {
+                path: name.clone(),
+                length: non_negative(length).ok_or_else(|| invalid("Invalid file length"))?,
+            }],
+            (None, Some(files)) => files
+                .as_list()
+                .ok_or_else(|| invalid("Invalid file list"))?
+                .iter()
+                .map(|file| meta_file(file).ok_or_else(|| invalid("Invalid file entry")))
+                .collect::<Result<Vec<_>>>()?,
+            (None, None) => return Err(invalid("Torrent has no files")),
+        };
+
+        let mut trackers = Vec::new();
+        if let Some(announce) = root.get("announce").and_then(Bencode::as_string) {
+            trackers.push(announce);
+        }
+        for tier in root
+            .get("announce-list")
+            .and_then(Bencode::as_list)
+            .unwrap_or_default()
+        {
+            for tracker in tier.as_list().unwrap_or_default() {
+                if let Some(tracker) = tracker.as_string() {
+                    if !trackers.contains(&tracker) {
+                        trackers.push(tracker);
+                    }
+                }
+            }
+        }
+
+        Ok(Self {
+            info_hash: hex_upper(&Sha1::digest(&data[start..end])),
+            name,
+            total_size: files.iter().map(|file| file.length).sum(),
+            files,
+            piece_length: info.get("piece length").and_then(non_negative).unwrap_or(0),
+            trackers,
+            private: info.get("private").and_then(Bencode::as_int) == Some(1),
+        })
+    }
+}
+
+fn non_negative(value: &Bencode) -> Option<u64> {
+    value.as_int().and_then(|value| u64::try_from(value).ok())
+}
+
+fn meta_file(file: &Bencode) -> Option<TorrentMetaFile> {
+    let path = file
+        .get("path.utf-8")
+        .or_else(|| file.get("path"))?
+        .as_list()?
+        .iter()
+        .map(Bencode::as_string)
+        .collect::<Option<Vec<_>>>()?
+        .join("/");
+    Some(TorrentMetaFile {
+        path,
+        length: non_negative(file.get("length")?)?,
+    })
+}
+
+fn hex_upper(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02X}", byte)).collect()
+}
+
+/// What a magnet link tells about its torrent
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MagnetLink {
+    /// Info hash from `xt=urn:btih:`, uppercase hex
+    pub info_hash: String,
+    /// `dn`
+    pub display_name: Option<String>,
+    /// `xl`, the total size when the link carries it
+    pub exact_length: Option<u64>,
+    /// `tr`
+    pub trackers: Vec<String>,
+}
+
+impl MagnetLink {
+    /// Parse a `magnet:?` URI with a BitTorrent v1 info hash, in hex or
+    /// base32
+    pub fn parse(uri: &str) -> Result<Self> {
+        let invalid = |message: &str| RadarrError::ValidationError {
+            field: "magnet".to_string(),
+            message: message.to_string(),
+        };
+        let url = url::Url::parse(uri).map_err(|e| invalid(&e.to_string()))?;
+        if url.scheme() != "magnet" {
+            return Err(invalid("Not a magnet link"));
+        }
+
+        let mut info_hash = None;
+        let mut display_name = None;
+        let mut exact_length = None;
+        let mut trackers = Vec::new();
+        for (key, value) in url.query_pairs() {
+            match key.as_ref() {
+                "xt" => {
+                    if let Some(hash) = value.strip_prefix("urn:btih:") {
+                        info_hash =
+                            Some(parse_info_hash(hash).ok_or_else(|| {
+                                invalid(&format!("Invalid info hash '{}'", hash))
+                            })?);
+                    }
+                }
+                "dn" => display_name = Some(value.into_owned()),
+                "xl" => exact_length = value.parse().ok(),
+                "tr" => trackers.push(value.into_owned()),
+                _ => {}
+            }
+        }
+
+        Ok(Self {
+            info_hash: info_hash.ok_or_else(|| invalid("Missing BitTorrent info hash"))?,
+            display_name,
+            exact_length,
+            trackers,
+        })
+    }
+}
+
+/// 40 hex digits, or 32 base32 characters, as uppercase hex
+fn parse_info_hash(hash: &str) -> Option<String> {
+    match hash.len() {
+        40 if hash.chars().all(|c| c.is_ascii_hexdigit()) => Some(hash.to_uppercase()),
+        32 => base32_decode(hash).map(|bytes| hex_upper(&bytes)),
+        _ => None,
+    }
+}
+
+/// RFC 4648 base32 without padding
+fn base32_decode(text: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(text.len() * 5 / 8);
+    let (mut buffer, mut bits) = (0u64, 0u32);
+    for c in text.chars() {
+        let value = match c.to_ascii_uppercase() {
+            c @ 'A'..='Z' => c as u64 - 'A' as u64,
+            c @ '2'..='7' => c as u64 - '2' as u64 + 26,
+            _ => return None,
+        };
+        buffer = (buffer << 5) | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    Some(bytes)
+}
+
+/// A release's torrent, as fetched from its indexer
+#[derive(Debug, Clone)]
+pub enum FetchedTorrent {
+    File {
+        data: Vec<u8>,
+        metainfo: TorrentMetainfo,
+    },
+    /// The download URL redirected to a magnet link
+    Magnet { link: MagnetLink, uri: String },
+}
+
+impl FetchedTorrent {
+    pub fn info_hash(&self) -> &str {
+        match self {
+            FetchedTorrent::File { metainfo, .. } => &metainfo.info_hash,
+            FetchedTorrent::Magnet { link, .. } => &link.info_hash,
+        }
+    }
+}
+
+/// Downloads `.torrent` files from indexers
+#[derive(Debug, Clone)]
+pub struct TorrentFetcher {
+    client: reqwest::Client,
+}
+
+impl TorrentFetcher {
+    pub fn new(timeout: Duration) -> Result<Self> {
+        // Redirects are followed by hand, as indexers redirect to magnet links
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .redirect(Policy::none())
+            .build()
+            .map_err(|e| RadarrError::ExternalServiceError {
+                service: "torrent_fetcher".to_string(),
+                error: e.to_string(),
+            })?;
+        Ok(Self { client })
+    }
+
+    /// Fetch and parse the torrent at `url`, following up to five redirects
+    pub async fn fetch(&self, url: &str) -> Result<FetchedTorrent> {
+        let mut url = url.to_string();
+        for _ in 0..5 {
+            if url.starts_with("magnet:") {
+                return Ok(FetchedTorrent::Magnet {
+                    link: MagnetLink::parse(&url)?,
+                    uri: url,
+                });
+            }
+
+            let response =
+                self.client
+                    .get(&url)
+                    .send()
+                    .await
+                    .map_err(|e| RadarrError::NetworkError {
+                        message: format!("Failed to fetch torrent: {}", e),
+                    })?;
+            let status = response.status();
+            if status.is_redirection() {
+                let location = response
+                    .headers()
+                    .get(reqwest::header::LOCATION)
+                    .and_then(|location| location.to_str().ok())
+                    .ok_or_else(|| RadarrError::ExternalServiceError {
+                        service: "indexer".to_string(),
+                        error: format!("Redirect without location from {}", url),
+                    })?;
+                url = response
+                    .url()
+                    .join(location)
+                    .map(|next| next.to_string())
+                    .unwrap_or_else(|_| location.to_string());
+                continue;
+            }
+            if !status.is_success() {
+                return Err(RadarrError::ExternalServiceError {
+                    service: "indexer".to_string(),
+                    error: format!("Torrent download failed with status {}", status),
+                });
+            }
+
+            let data = response
+                .bytes()
+                .await
+                .map_err(|e| RadarrError::NetworkError {
+                    message: format!("Failed to read torrent: {}", e),
+                })?
+                .to_vec();
+            let metainfo = TorrentMetainfo::from_bytes(&data)?;
+            return Ok(FetchedTorrent::File { data, metainfo });
+        }
+
+        Err(RadarrError::ExternalServiceError {
+            service: "indexer".to_string(),
+            error: format!("Too many redirects fetching {}", url),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Multi-file torrent with a private flag and a second tracker tier
+    fn multi_file_torrent() -> Vec<u8> {
+        let info = b"d5:filesld6:lengthi1000e4:pathl6:Sample10:sample.mkveed6:lengthi700000e4:pathl9:Movie.mkveee4:name10:Movie.2024\
+12:piece lengthi16384e6:pieces0:7:privatei1ee";
+        let mut data = b"d8:announce18:http://tracker/ann13:announce-listll18:http://tracker/annel15:udp://backup:80ee4:info".to_vec();
+        data.extend_from_slice(info);
+        data.push(b'e');
+        data
+    }
+
+    #[test]
+    fn test_parse_multi_file_torrent() {
+        let data = multi_file_torrent();
+        let metainfo = TorrentMetainfo::from_bytes(&data).unwrap();
+
+        assert_eq!(metainfo.name, "Movie.2024");
+        assert_eq!(metainfo.total_size, 701_000);
+        assert_eq!(metainfo.piece_length, 16384);
+        assert!(metainfo.private);
+        assert_eq!(
+            metainfo.files,
+            vec![
+                TorrentMetaFile {
+                    path: "Sample/sample.mkv".to_string(),
+                    length: 1000,
+                },
+                TorrentMetaFile {
+                    path: "Movie.mkv".to_string(),
+                    length: 700_000,
+                },
+            ]
+        );
+        assert_eq!(
+            metainfo.trackers,
+            vec!["http://tracker/ann", "udp://backup:80"]
+        );
+
+        // The hash covers exactly the bytes of the info dictionary
+        let start = data.windows(6).position(|w| w == b"4:info").unwrap() + 6;
+        let expected = hex_upper(&Sha1::digest(&data[start..data.len() - 1]));
+        assert_eq!(metainfo.info_hash, expected);
+        assert_eq!(metainfo.info_hash.len(), 40);
+    }
+
+    #[test]
+    fn test_single_file_torrent_and_invalid_data() {
+        let data = b"d4:infod6:lengthi42e4:name9:Movie.mkv12:piece lengthi16384e6:pieces0:ee";
+        let metainfo = TorrentMetainfo::from_bytes(data).unwrap();
+        assert_eq!(metainfo.total_size, 42);
+        assert_eq!(metainfo.files[0].path, "Movie.mkv");
+        assert!(!metainfo.private);
+
+        assert!(TorrentMetainfo::from_bytes(b"d4:infod4:name1:xee").is_err());
+        assert!(TorrentMetainfo::from_bytes(b"d4:info").is_err());
+        assert!(TorrentMetainfo::from_bytes(b"<html>Login required</html>").is_err());
+        assert!(Bencode::decode(b"i42eextra").is_err());
+        assert!(Bencode::decode(b"5:abc").is_err());
+    }
+
+    #[test]
+    fn test_parse_magnet_links() {
+        let magnet = MagnetLink::parse(
+            "magnet:?xt=urn:btih:c12fe1c06bba254a9dc9f519b335aa7c1367a88a&dn=Movie.2024.1080p\
+&xl=7340032&tr=udp%3A%2F%2Ftracker%3A80",
+        )
+        .unwrap();
+        assert_eq!(magnet.info_hash, "C12FE1C06BBA254A9DC9F519B335AA7C1367A88A");
+        assert_eq!(magnet.display_name.as_deref(), Some("Movie.2024.1080p"));
+        assert_eq!(magnet.exact_length, Some(7_340_032));
+        assert_eq!(magnet.trackers, vec!["udp://tracker:80"]);
+
+        // The same hash in base32
+        let base32 =
+            MagnetLink::parse("magnet:?xt=urn:btih:YEX6DQDLXISUVHOJ6UM3GNNKPQJWPKEK").unwrap();
+        assert_eq!(base32.info_hash, magnet.info_hash);
+
+        assert!(MagnetLink::parse("magnet:?dn=NoHash").is_err());
+        assert!(MagnetLink::parse("magnet:?xt=urn:btih:nothex").is_err());
+        assert!(MagnetLink::parse("http://indexer/download/1").is_err());
+    }
+}
@@ -0,0 +1,613 @@
+//! Downloads folder cleanup for orphaned data
+//!
+//! After imports, leftovers such as samples, nfo-only directories and failed
+//! extractions accumulate in the downloads folder. This module finds top-level
+//! entries that are no longer referenced by any queue item or download client
+//! (e.g. a seeding torrent) and removes them once they are older than a grace
+//! period. A dry run only lists what would be removed.
+
+use radarr_core::{
+    models::{QueueItem, QueueStatus},
+    services::{ClientDownloadStatus, DownloadClientService, QueueRepository},
+    RadarrError, Result,
+};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tokio::fs;
+use tracing::{debug, info, warn};
+
+/// Configuration for downloads folder cleanup
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CleanupConfig {
+    /// Downloads folder whose top-level entries are considered for cleanup
+    pub downloads_dir: PathBuf,
+    /// Minimum age (since last modification) before an orphan is removed
+    pub grace_period: Duration,
+    /// Glob patterns (matched against the entry name) that are never removed
+    pub exclude_globs: Vec<String>,
+    /// Only list candidates without deleting anything
+    pub dry_run: bool,
+}
+
+impl Default for CleanupConfig {
+    fn default() -> Self {
+        Self {
+            downloads_dir: PathBuf::from("/downloads"),
+            grace_period: Duration::from_secs(24 * 60 * 60),
+            exclude_globs: vec![".*".to_string(), "*.!qB".to_string(), "*.part".to_string()],
+            dry_run: true,
+        }
+    }
+}
+
+/// An orphaned entry in the downloads folder
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CleanupCandidate {
+    pub path: PathBuf,
+    pub is_dir: bool,
+    pub size_bytes: u64,
+    /// Seconds since the newest modification inside the entry
+    pub age_seconds: u64,
+}
+
+/// Result of a cleanup run
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CleanupReport {
+    pub dry_run: bool,
+    pub candidates: Vec<CleanupCandidate>,
+    pub removed: Vec<PathBuf>,
+    pub bytes_freed: u64,
+    pub skipped_excluded: usize,
+    pub skipped_referenced: usize,
+    pub skipped_recent: usize,
+    pub errors: Vec<String>,
+}
+
+/// Set of paths and names still in use by the queue or a download client
+#[derive(Debug, Clone, Default)]
+pub struct DownloadReferences {
+    paths: HashSet<PathBuf>,
+    names: HashSet<String>,
+}
+
+impl DownloadReferences {
+    /// Mark a path as referenced
+    pub fn add_path(&mut self, path: impl Into<PathBuf>) {
+        self.paths.insert(path.into());
+    }
+
+    /// Mark a top-level entry name as referenced
+    pub fn add_name(&mut self, name: impl AsRef<str>) {
+        self.names.insert(name.as_ref().to_lowercase());
+    }
+
+    /// Add references from a queue item
+    pub fn add_queue_item(&mut self, item: &QueueItem) {
+        if let Some(path) = &item.download_path {
+            self.add_path(path);
+        }
+        self.add_name(&item.title);
+    }
+
+    /// Add references from a download reported by a client
+    pub fn add_client_download(&mut self, download: &ClientDownloadStatus) {
+        if let Some(save_path) = &download.save_path {
+            self.add_path(Path::new(save_path).join(&download.name));
+        }
+        self.add_name(&download.name);
+    }
+
+    /// Check whether a top-level downloads entry is still in use.
+    ///
+    /// An entry is referenced when its name matches a download, or when a
+    /// referenced path points at the entry or anything inside it.
+    pub fn is_referenced(&self, entry: &Path) -> bool {
+        let name_match = entry
+            .file_name()
+            .map(|n| self.names.contains(&n.to_string_lossy().to_lowercase()))
+            .unwrap_or(false);
+
+        name_match || self.paths.iter().any(|p| p.starts_with(entry))
+    }
+}
+
+/// Service that removes orphaned data from the downloads folder
+pub struct DownloadsCleanupService {
+    config: CleanupConfig,
+    exclude_patterns: Vec<Regex>,
+    queue_repository: Arc<dyn QueueRepository>,
+    download_client: Option<Arc<dyn DownloadClientService>>,
+}
+
+impl DownloadsCleanupService {
+    /// Create a new cleanup service
+    pub fn new(config: CleanupConfig, queue_repository: Arc<dyn QueueRepository>) -> Result<Self> {
+        let exclude_patterns = config
+            .exclude_globs
+            .iter()
+            .map(|g| glob_to_regex(g))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            config,
+            exclude_patterns,
+            queue_repository,
+            download_client: None,
+        })
+    }
+
+    /// Also treat everything the download client still knows about (e.g. seeding torrents) as referenced
+    pub fn with_download_client(mut self, client: Arc<dyn DownloadClientService>) -> Self {
+        self.download_client = Some(client);
+        self
+    }
+
+    /// Collect references from the queue and download client
+    pub async fn collect_references(&self) -> Result<DownloadReferences> {
+        let mut references = DownloadReferences::default();
+        let now = chrono::Utc::now();
+        let grace = chrono::Duration::from_std(self.config.grace_period)
+            .unwrap_or_else(|_| chrono::Duration::days(1));
+
+        for item in self.queue_repository.get_queue_items(None).await? {
+            let referenced = match item.status {
+                QueueStatus::Failed | QueueStatus::Cancelled => false,
+                // Completed downloads may still be waiting for import
+                QueueStatus::Completed => item
+                    .completed_at
+                    .map(|completed| now - completed < grace)
+                    .unwrap_or(true),
+                _ => true,
+            };
+            if referenced {
+                references.add_queue_item(&item);
+            }
+        }
+
+        if let Some(client) = &self.download_client {
+            // If the client can't be reached, nothing can safely be considered orphaned
+            let downloads = client.get_all_downloads().await.map_err(|e| {
+                warn!("Cleanup aborted, download client unavailable: {}", e);
+                e
+            })?;
+            for download in &downloads {
+                references.add_client_download(download);
+            }
+        }
+
+        Ok(references)
+    }
+
+    /// List orphaned entries without removing anything
+    pub async fn scan(&self) -> Result<CleanupReport> {
+        let references = self.collect_references().await?;
+        self.scan_with_references(&references).await
+    }
+
+    /// Find orphaned entries given a set of references
+    pub async fn scan_with_references(
+        &self,
+        references: &DownloadReferences,
+    ) -> Result<CleanupReport> {
+        let downloads_dir = &self.config.downloads_dir;
+        let mut report = CleanupReport {
+            dry_run: true,
+            ..Default::default()
+        };
+
+        let mut entries = fs::read_dir(downloads_dir).await.map_err(|e| {
+            RadarrError::IoError(format!(
+                "Failed to read downloads directory {}: {}",
+                downloads_dir.display(),
+                e
+            ))
+        })?;
+
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .map_err(|e| RadarrError::IoError(e.to_string()))?
+        {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+
+            if self.is_excluded(&name) {
+                debug!("Cleanup: skipping excluded entry {}", path.display());
+                report.skipped_excluded += 1;
+                continue;
+            }
+
+            if references.is_referenced(&path) {
+                report.skipped_referenced += 1;
+                continue;
+            }
+
+            let metadata = match fs::symlink_metadata(&path).await {
+                Ok(metadata) => metadata,
+                Err(e) => {
+                    report
+                        .errors
+                        .push(format!("Failed to stat {}: {}", path.display(), e));
+                    continue;
+                }
+            };
+
+            let (size_bytes, newest) = match entry_usage(path.clone()).await {
+                Ok(usage) => usage,
+                Err(e) => {
+                    report.errors.push(e.to_string());
+                    continue;
+                }
+            };
+
+            let age = SystemTime::now()
+                .duration_since(newest)
+                .unwrap_or(Duration::ZERO);
+            if age < self.config.grace_period {
+                report.skipped_recent += 1;
+                continue;
+            }
+
+            report.candidates.push(CleanupCandidate {
+                path,
+                is_dir: metadata.is_dir(),
+                size_bytes,
+                age_seconds: age.as_secs(),
+            });
+        }
+
+        report.candidates.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(report)
+    }
+
+    /// Scan and remove orphaned entries (unless configured as a dry run)
+    pub async fn run(&self) -> Result<CleanupReport> {
+        self.run_with(self.config.dry_run).await
+    }
+
+    /// Scan and remove orphaned entries, or only list them when `dry_run`
+    ///
+    /// A service configured as a dry run never removes anything, whatever
+    /// `dry_run` says.
+    pub async fn run_with(&self, dry_run: bool) -> Result<CleanupReport> {
+        let dry_run = self.config.dry_run || dry_run;
+        let mut report = self.scan().await?;
+        report.dry_run = dry_run;
+
+        if dry_run {
+            info!(
+                "Cleanup dry run: {} orphaned entries in {}",
+                report.candidates.len(),
+                self.config.downloads_dir.display()
+            );
+            return Ok(report);
+        }
+
+        for candidate in &report.candidates {
+            let result = if candidate.is_dir {
+                fs::remove_dir_all(&candidate.path).await
+            } else {
+                fs::remove_file(&candidate.path).await
+            };
+
+            match result {
+                Ok(()) => {
+                    info!("Cleanup: removed orphaned {}", candidate.path.display());
+                    report.removed.push(candidate.path.clone());
+                    report.bytes_freed += candidate.size_bytes;
+                }
+                Err(e) => {
+                    warn!(
+                        "Cleanup: failed to remove {}: {}",
+                        candidate.path.display(),
+                        e
+                    );
+                    report.errors.push(format!(
+                        "Failed to remove {}: {}",
+                        candidate.path.display(),
+                        e
+                    ));
+                }
+            }
+        }
+
+        info!(
+            "Cleanup removed {} entries, freed {} bytes",
+            report.removed.len(),
+            report.bytes_freed
+        );
+        Ok(report)
+    }
+
+    fn is_excluded(&self, name: &str) -> bool {
+        self.exclude_patterns.iter().any(|re| re.is_match(name))
+    }
+}
+
+/// Total size and newest modification time of an entry, boxed for recursion
+type EntryUsageFuture = Pin<Box<dyn Future<Output = Result<(u64, SystemTime)>> + Send>>;
+
+/// Compute the total size and newest modification time of a file or directory
+fn entry_usage(path: PathBuf) -> EntryUsageFuture {
+    Box::pin(async move {
+        let metadata = fs::symlink_metadata(&path).await.map_err(|e| {
+            RadarrError::IoError(format!("Failed to stat {}: {}", path.display(), e))
+        })?;
+        let mut newest = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+
+        if !metadata.is_dir() {
+            return Ok((metadata.len(), newest));
+        }
+
+        let mut size = 0;
+        let mut entries = fs::read_dir(&path).await.map_err(|e| {
+            RadarrError::IoError(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .map_err(|e| RadarrError::IoError(e.to_string()))?
+        {
+            let (child_size, child_newest) = entry_usage(entry.path()).await?;
+            size += child_size;
+            newest = newest.max(child_newest);
+        }
+
+        Ok((size, newest))
+    })
+}
+
+/// Convert a simple glob (`*`, `?`) into an anchored, case-insensitive regex
+fn glob_to_regex(glob: &str) -> Result<Regex> {
+    let mut pattern = String::from("(?i)^");
+    for c in glob.chars() {
+        match c {
+            '*' => pattern.push_str(".*"),
+            '?' => pattern.push('.'),
+            c => pattern.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    pattern.push('$');
+
+    Regex::new(&pattern).map_err(|e| RadarrError::ValidationError {
+        field: "exclude_globs".to_string(),
+        message: format!("Invalid glob '{}': {}", glob, e),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use radarr_core::models::{QueueStats, ReleaseProtocol};
+    use radarr_core::services::{DownloadClientManager, ManagedDownloadClient};
+    use tempfile::TempDir;
+    use uuid::Uuid;
+
+    struct StaticQueue(Vec<QueueItem>);
+
+    #[async_trait]
+    impl QueueRepository for StaticQueue {
+        async fn add_queue_item(&self, _item: &QueueItem) -> Result<()> {
+            Ok(())
+        }
+        async fn get_queue_item(&self, _id: Uuid) -> Result<Option<QueueItem>> {
+            Ok(None)
+        }
+        async fn get_queue_item_by_client_id(&self, _client_id: &str) -> Result<Option<QueueItem>> {
+            Ok(None)
+        }
+        async fn get_queue_items(&self, _status: Option<QueueStatus>) -> Result<Vec<QueueItem>> {
+            Ok(self.0.clone())
+        }
+        async fn get_queue_items_for_movie(&self, _movie_id: Uuid) -> Result<Vec<QueueItem>> {
+            Ok(vec![])
+        }
+        async fn update_queue_item(&self, _item: &QueueItem) -> Result<()> {
+            Ok(())
+        }
+        async fn delete_queue_item(&self, _id: Uuid) -> Result<()> {
+            Ok(())
+        }
+        async fn get_queue_stats(&self) -> Result<QueueStats> {
+            Ok(QueueStats::default())
+        }
+        async fn get_retry_items(&self) -> Result<Vec<QueueItem>> {
+            Ok(vec![])
+        }
+    }
+
+    /// Client that can't be reached
+    struct UnreachableClient;
+
+    #[async_trait]
+    impl DownloadClientService for UnreachableClient {
+        async fn add_download(
+            &self,
+            _download_url: &str,
+            _category: Option<String>,
+            _save_path: Option<String>,
+        ) -> Result<String> {
+            Err(unreachable())
+        }
+        async fn get_download_status(
+            &self,
+            _client_id: &str,
+        ) -> Result<Option<ClientDownloadStatus>> {
+            Err(unreachable())
+        }
+        async fn remove_download(&self, _client_id: &str, _delete_files: bool) -> Result<()> {
+            Err(unreachable())
+        }
+        async fn pause_download(&self, _client_id: &str) -> Result<()> {
+            Err(unreachable())
+        }
+        async fn resume_download(&self, _client_id: &str) -> Result<()> {
+            Err(unreachable())
+        }
+        async fn get_all_downloads(&self) -> Result<Vec<ClientDownloadStatus>> {
+            Err(unreachable())
+        }
+    }
+
+    fn unreachable() -> RadarrError {
+        RadarrError::ExternalServiceError {
+            service: "qbittorrent".to_string(),
+            error: "connection refused".to_string(),
+        }
+    }
+
+    fn queue_item(title: &str, status: QueueStatus) -> QueueItem {
+        let mut item = QueueItem::new(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            title.to_string(),
+            "magnet:?xt=urn:btih:abc".to_string(),
+        );
+        item.status = status;
+        item
+    }
+
+    async fn setup() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        for name in [
+            "Active.Movie.2023",
+            "Old.Movie.2020",
+            "Failed.Movie.2021",
+            "keep-me",
+        ] {
+            fs::create_dir(dir.path().join(name)).await.unwrap();
+            fs::write(dir.path().join(name).join("movie.nfo"), b"nfo")
+                .await
+                .unwrap();
+        }
+        fs::write(dir.path().join("stray.nfo"), b"stray")
+            .await
+            .unwrap();
+        dir
+    }
+
+    fn service(dir: &TempDir, grace_period: Duration, dry_run: bool) -> DownloadsCleanupService {
+        let queue = StaticQueue(vec![
+            queue_item("Active.Movie.2023", QueueStatus::Downloading),
+            queue_item("Failed.Movie.2021", QueueStatus::Failed),
+        ]);
+        DownloadsCleanupService::new(
+            CleanupConfig {
+                downloads_dir: dir.path().to_path_buf(),
+                grace_period,
+                exclude_globs: vec!["keep-*".to_string()],
+                dry_run,
+            },
+            Arc::new(queue),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_lists_orphans() {
+        let dir = setup().await;
+        let report = service(&dir, Duration::ZERO, true).run().await.unwrap();
+
+        let names: Vec<String> = report
+            .candidates
+            .iter()
+            .map(|c| c.path.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(
+            names,
+            vec!["Failed.Movie.2021", "Old.Movie.2020", "stray.nfo"]
+        );
+        assert_eq!(report.skipped_referenced, 1);
+        assert_eq!(report.skipped_excluded, 1);
+        assert!(report.removed.is_empty());
+        assert!(dir.path().join("Old.Movie.2020").exists());
+    }
+
+    #[tokio::test]
+    async fn test_run_removes_orphans() {
+        let dir = setup().await;
+        let report = service(&dir, Duration::ZERO, false).run().await.unwrap();
+
+        assert_eq!(report.removed.len(), 3);
+        assert!(report.bytes_freed > 0);
+        assert!(!dir.path().join("Old.Movie.2020").exists());
+        assert!(!dir.path().join("stray.nfo").exists());
+        assert!(dir.path().join("Active.Movie.2023").exists());
+        assert!(dir.path().join("keep-me").exists());
+    }
+
+    #[tokio::test]
+    async fn test_run_with_dry_run_overrides_config() {
+        let dir = setup().await;
+        let report = service(&dir, Duration::ZERO, false)
+            .run_with(true)
+            .await
+            .unwrap();
+
+        assert!(report.dry_run);
+        assert_eq!(report.candidates.len(), 3);
+        assert!(report.removed.is_empty());
+        assert!(dir.path().join("Old.Movie.2020").exists());
+    }
+
+    #[tokio::test]
+    async fn test_run_with_cannot_turn_off_configured_dry_run() {
+        let dir = setup().await;
+        let report = service(&dir, Duration::ZERO, true)
+            .run_with(false)
+            .await
+            .unwrap();
+
+        assert!(report.dry_run);
+        assert!(report.removed.is_empty());
+        assert!(dir.path().join("Old.Movie.2020").exists());
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_client_behind_manager_removes_nothing() {
+        let dir = setup().await;
+        let manager = DownloadClientManager::new();
+        manager
+            .add_client(ManagedDownloadClient::new(
+                1,
+                "qBittorrent",
+                ReleaseProtocol::Torrent,
+                1,
+                Arc::new(UnreachableClient),
+            ))
+            .await;
+        let cleanup = service(&dir, Duration::ZERO, false).with_download_client(Arc::new(manager));
+
+        assert!(cleanup.run().await.is_err());
+        assert!(dir.path().join("Old.Movie.2020").exists());
+        assert!(dir.path().join("Failed.Movie.2021").exists());
+        assert!(dir.path().join("stray.nfo").exists());
+    }
+
+    #[tokio::test]
+    async fn test_grace_period_protects_recent_data() {
+        let dir = setup().await;
+        let report = service(&dir, Duration::from_secs(3600), false)
+            .run()
+            .await
+            .unwrap();
+
+        assert!(report.candidates.is_empty());
+        assert_eq!(report.skipped_recent, 3);
+    }
+
+    #[test]
+    fn test_glob_matching() {
+        let re = glob_to_regex("*.!qB").unwrap();
+        assert!(re.is_match("movie.mkv.!qb"));
+        assert!(!re.is_match("movie.mkv"));
+        assert!(glob_to_regex("sample?").unwrap().is_match("Sample1"));
+    }
+}
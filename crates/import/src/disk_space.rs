@@ -0,0 +1,150 @@
+//! Free disk space checks
+//!
+//! Reads the space available to unprivileged writers on the filesystem
+//! holding a path (`statvfs`'s `f_bavail`, not `f_bfree`), so the reserve
+//! kept for root stays out of the numbers. Paths that do not exist yet are
+//! measured at their nearest existing ancestor.
+
+use radarr_core::{
+    services::{StorageChange, StorageThrottle},
+    RadarrError, RootFolder,
+};
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// Free and total size of a filesystem
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiskUsage {
+    /// Bytes available to unprivileged writers
+    pub free_bytes: u64,
+    /// Size of the filesystem in bytes
+    pub total_bytes: u64,
+}
+
+/// Bytes available to unprivileged writers on the filesystem holding `path`
+pub async fn free_space(path: &Path) -> Result<u64, RadarrError> {
+    Ok(disk_usage(path).await?.free_bytes)
+}
+
+/// Free and total space of the filesystem holding `path`
+pub async fn disk_usage(path: &Path) -> Result<DiskUsage, RadarrError> {
+    let path = existing_ancestor(path).ok_or_else(|| RadarrError::ValidationError {
+        field: "path".to_string(),
+        message: format!("No existing directory above {}", path.display()),
+    })?;
+
+    tokio::task::spawn_blocking(move || statvfs_usage(&path)).await?
+}
+
+/// Measure every mounted root folder and record it with the storage
+/// throttle, returning the folders whose throttle state changed
+///
+/// Folders whose directory is missing are skipped rather than measured at an
+/// ancestor, which would report the wrong filesystem.
+pub async fn refresh_storage_throttle(
+    throttle: &StorageThrottle,
+    folders: &[RootFolder],
+) -> Vec<(RootFolder, StorageChange)> {
+    let ids: Vec<i32> = folders.iter().map(|folder| folder.id).collect();
+    throttle.retain(&ids);
+
+    let mut changes = Vec::new();
+    for folder in folders {
+        let path = Path::new(&folder.path);
+        if !tokio::fs::metadata(path)
+            .await
+            .map(|metadata| metadata.is_dir())
+            .unwrap_or(false)
+        {
+            debug!("Root folder {} is not mounted", folder.path);
+            continue;
+        }
+
+        match disk_usage(path).await {
+            Ok(usage) => {
+                if let Some(change) = throttle.record(folder, usage.free_bytes) {
+                    changes.push((folder.clone(), change));
+                }
+            }
+            Err(e) => debug!("Cannot read disk space of {}: {}", folder.path, e),
+        }
+    }
+    changes
+}
+
+/// The path itself or its closest ancestor that exists
+fn existing_ancestor(path: &Path) -> Option<PathBuf> {
+    path.ancestors()
+        .find(|ancestor| ancestor.exists())
+        .map(Path::to_path_buf)
+}
+
+#[cfg(unix)]
+fn statvfs_usage(path: &Path) -> Result<DiskUsage, RadarrError> {
+    let stats =
+        nix::sys::statvfs::statvfs(path).map_err(|e| RadarrError::ExternalServiceError {
+            service: "filesystem".to_string(),
+            error: format!("Failed to read free space of {}: {}", path.display(), e),
+        })?;
+
+    let fragment_size = stats.fragment_size() as u64;
+    Ok(DiskUsage {
+        free_bytes: (stats.blocks_available() as u64).saturating_mul(fragment_size),
+        total_bytes: (stats.blocks() as u64).saturating_mul(fragment_size),
+    })
+}
+
+#[cfg(not(unix))]
+fn statvfs_usage(path: &Path) -> Result<DiskUsage, RadarrError> {
+    Err(RadarrError::ExternalServiceError {
+        service: "filesystem".to_string(),
+        error: format!(
+            "Free space checks are not supported on this platform ({})",
+            path.display()
+        ),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_free_space_of_missing_path_uses_ancestor() {
+        let temp_dir = TempDir::new().unwrap();
+        let missing = temp_dir.path().join("Dune (2021)").join("Dune.mkv");
+
+        assert!(free_space(&missing).await.unwrap() > 0);
+    }
+
+    #[tokio::test]
+    async fn test_refresh_storage_throttle_skips_unmounted_folders() {
+        use radarr_core::services::StorageThrottleConfig;
+
+        let temp_dir = TempDir::new().unwrap();
+        let throttle = StorageThrottle::new(StorageThrottleConfig {
+            threshold_bytes: u64::MAX,
+            resume_margin_bytes: 0,
+        });
+        let mut mounted = RootFolder::new(temp_dir.path().to_string_lossy().into_owned());
+        mounted.id = 1;
+        let mut missing = RootFolder::new(temp_dir.path().join("gone").to_string_lossy().into());
+        missing.id = 2;
+
+        let changes = refresh_storage_throttle(&throttle, &[mounted, missing]).await;
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].0.id, 1);
+        assert_eq!(changes[0].1, StorageChange::Throttled);
+        assert_eq!(throttle.snapshot().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_disk_usage_free_within_total() {
+        let temp_dir = TempDir::new().unwrap();
+        let usage = disk_usage(temp_dir.path()).await.unwrap();
+
+        assert!(usage.total_bytes > 0);
+        assert!(usage.free_bytes <= usage.total_bytes);
+    }
+}
@@ -0,0 +1,511 @@
+//! Archive extraction before import
+//!
+//! Scene releases often arrive as RAR archives split into many volumes, or
+//! as zip files, with no playable file beside them. When enabled, archives
+//! in a completed download are extracted to a work directory and the media
+//! inside is imported together with any loose files. The download itself is
+//! never modified, so seeding continues, and the work directory is removed
+//! once the import is done.
+//!
+//! Extraction needs the `archives` feature. Zip archives are read in
+//! process; RAR archives are extracted with the `unrar` tool, which follows
+//! the volumes of a multi-part set from its first one. `unrar` is a hard
+//! requirement once extraction is enabled: [`Extractor::check_unrar`] is run
+//! at startup so a missing tool fails there, not on the first RAR download.
+
+use once_cell::sync::Lazy;
+use radarr_core::RadarrError;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tokio::sync::mpsc::UnboundedSender;
+use tracing::{debug, info, warn};
+
+/// `name.part01.rar`, the newer multi-volume naming
+static RAR_PART_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)\.part(?P<part>\d+)\.rar$").unwrap());
+
+/// Percentages `unrar` prints while it extracts
+#[cfg(feature = "archives")]
+static UNRAR_PROGRESS_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(\d{1,3})%").unwrap());
+
+/// Configuration for the extraction stage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtractConfig {
+    /// Whether archives in downloads are extracted before import
+    pub enabled: bool,
+    /// `unrar` binary, looked up on `PATH` when not absolute
+    pub unrar_path: PathBuf,
+    /// Where archives are extracted to; the system temp dir when unset
+    pub work_directory: Option<PathBuf>,
+    /// Give up on a single archive after this many seconds
+    pub timeout_seconds: u64,
+}
+
+impl Default for ExtractConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            unrar_path: PathBuf::from("unrar"),
+            work_directory: None,
+            timeout_seconds: 60 * 60,
+        }
+    }
+}
+
+/// Kind of archive, by how it is extracted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ArchiveKind {
+    Rar,
+    Zip,
+}
+
+/// Archives extracted for one download
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Extraction {
+    /// First volume of each archive that was extracted
+    pub archives: Vec<PathBuf>,
+    /// Directory holding the extracted files, one subdirectory per archive
+    pub output_dir: PathBuf,
+}
+
+impl Extraction {
+    /// Remove the extracted files
+    pub async fn cleanup(&self) {
+        match tokio::fs::remove_dir_all(&self.output_dir).await {
+            Ok(()) => debug!("Removed extracted files in {}", self.output_dir.display()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => warn!(
+                "Failed to remove extracted files in {}: {}",
+                self.output_dir.display(),
+                e
+            ),
+        }
+    }
+}
+
+/// Extracts archives according to an [`ExtractConfig`]
+#[derive(Debug, Clone)]
+pub struct Extractor {
+    config: ExtractConfig,
+}
+
+impl Extractor {
+    /// Create an extractor
+    pub fn new(config: ExtractConfig) -> Self {
+        if config.enabled && !cfg!(feature = "archives") {
+            warn!("Archive extraction is enabled, but this build lacks the `archives` feature");
+        }
+        Self { config }
+    }
+
+    /// Get the configuration
+    pub fn config(&self) -> &ExtractConfig {
+        &self.config
+    }
+
+    /// Whether downloads are searched for archives at all
+    pub fn is_enabled(&self) -> bool {
+        self.config.enabled && cfg!(feature = "archives")
+    }
+
+    /// Extract every archive below `source_dir`
+    ///
+    /// Returns `None` when extraction is off or there is nothing to extract.
+    /// Overall progress in percent is sent on `progress`. When an archive
+    /// fails, everything extracted so far is removed.
+    pub async fn extract_all(
+        &self,
+        source_dir: &Path,
+        progress: Option<&UnboundedSender<f32>>,
+    ) -> Result<Option<Extraction>, RadarrError> {
+        if !self.is_enabled() {
+            return Ok(None);
+        }
+        let archives = find_archives(source_dir).await?;
+        if archives.is_empty() {
+            return Ok(None);
+        }
+
+        let extraction = Extraction {
+            archives: archives.iter().map(|(path, _)| path.clone()).collect(),
+            output_dir: self.work_directory().join(uuid::Uuid::new_v4().to_string()),
+        };
+        info!(
+            "Extracting {} archives from {} to {}",
+            archives.len(),
+            source_dir.display(),
+            extraction.output_dir.display()
+        );
+
+        let total = archives.len() as f32;
+        for (index, (archive, kind)) in archives.iter().enumerate() {
+            // Archives get their own folder so equal file names can't clash
+            let output = extraction.output_dir.join(index.to_string());
+            let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel::<f32>();
+            let forward = async {
+                while let Some(percentage) = receiver.recv().await {
+                    if let Some(progress) = progress {
+                        let _ = progress.send((index as f32 + percentage / 100.0) / total * 100.0);
+                    }
+                }
+            };
+            let extract = async {
+                let result = self.extract(archive, *kind, &output, &sender).await;
+                drop(sender);
+                result
+            };
+            let (result, ()) = tokio::join!(extract, forward);
+
+            if let Err(e) = result {
+                extraction.cleanup().await;
+                return Err(e);
+            }
+            debug!("Extracted {} to {}", archive.display(), output.display());
+        }
+
+        if let Some(progress) = progress {
+            let _ = progress.send(100.0);
+        }
+        Ok(Some(extraction))
+    }
+
+    /// Check that the configured `unrar` tool can be run
+    ///
+    /// Does nothing when extraction is off. Only whether the tool starts is
+    /// checked, since `unrar` without arguments prints its usage.
+    pub async fn check_unrar(&self) -> Result<(), RadarrError> {
+        if !self.is_enabled() {
+            return Ok(());
+        }
+
+        let unrar_path = &self.config.unrar_path;
+        tokio::process::Command::new(unrar_path)
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .kill_on_drop(true)
+            .status()
+            .await
+            .map_err(|e| RadarrError::ConfigurationError {
+                field: "import.unrar_path".to_string(),
+                message: format!(
+                    "Archive extraction needs the unrar tool, but {} could not be run: {}",
+                    unrar_path.display(),
+                    e
+                ),
+            })?;
+        debug!("Found unrar at {}", unrar_path.display());
+        Ok(())
+    }
+
+    fn work_directory(&self) -> PathBuf {
+        self.config
+            .work_directory
+            .clone()
+            .unwrap_or_else(|| std::env::temp_dir().join("radarr-extract"))
+    }
+
+    #[cfg(feature = "archives")]
+    async fn extract(
+        &self,
+        archive: &Path,
+        kind: ArchiveKind,
+        output: &Path,
+        progress: &UnboundedSender<f32>,
+    ) -> Result<(), RadarrError> {
+        tokio::fs::create_dir_all(output).await?;
+        let timeout = std::time::Duration::from_secs(self.config.timeout_seconds);
+        let run = async {
+            match kind {
+                ArchiveKind::Zip => {
+                    let archive = archive.to_path_buf();
+                    let output = output.to_path_buf();
+                    let progress = progress.clone();
+                    tokio::task::spawn_blocking(move || extract_zip(&archive, &output, &progress))
+                        .await?
+                }
+                ArchiveKind::Rar => self.extract_rar(archive, output, progress).await,
+            }
+        };
+        match tokio::time::timeout(timeout, run).await {
+            Ok(result) => result,
+            Err(_) => Err(RadarrError::Timeout {
+                operation: format!("extraction of {}", archive.display()),
+            }),
+        }
+    }
+
+    #[cfg(not(feature = "archives"))]
+    async fn extract(
+        &self,
+        archive: &Path,
+        _kind: ArchiveKind,
+        _output: &Path,
+        _progress: &UnboundedSender<f32>,
+    ) -> Result<(), RadarrError> {
+        Err(RadarrError::ValidationError {
+            field: "archive".to_string(),
+            message: format!(
+                "Cannot extract {}: built without the `archives` feature",
+                archive.display()
+            ),
+        })
+    }
+
+    /// Run `unrar` on the first volume of a RAR set
+    #[cfg(feature = "archives")]
+    async fn extract_rar(
+        &self,
+        archive: &Path,
+        output: &Path,
+        progress: &UnboundedSender<f32>,
+    ) -> Result<(), RadarrError> {
+        use std::process::Stdio;
+        use tokio::io::AsyncReadExt;
+
+        // -o+ overwrites, -p- never prompts for a password, -idc hides the banner
+        let mut destination = output.as_os_str().to_owned();
+        destination.push(std::path::MAIN_SEPARATOR_STR);
+        let mut child = tokio::process::Command::new(&self.config.unrar_path)
+            .args(["x", "-o+", "-p-", "-y", "-idc"])
+            .arg(archive)
+            .arg(destination)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| RadarrError::ExternalServiceError {
+                service: "unrar".to_string(),
+                error: format!(
+                    "Failed to start {}: {}",
+                    self.config.unrar_path.display(),
+                    e
+                ),
+            })?;
+
+        let stderr = child.stderr.take().map(|mut stderr| {
+            tokio::spawn(async move {
+                let mut errors = String::new();
+                let _ = stderr.read_to_string(&mut errors).await;
+                errors
+            })
+        });
+        // Progress is redrawn in place with backspaces, so read raw chunks
+        if let Some(mut stdout) = child.stdout.take() {
+            let mut buffer = [0u8; 4096];
+            while let Ok(read) = stdout.read(&mut buffer).await {
+                if read == 0 {
+                    break;
+                }
+                let chunk = String::from_utf8_lossy(&buffer[..read]);
+                let last = UNRAR_PROGRESS_REGEX
+                    .captures_iter(&chunk)
+                    .filter_map(|captures| captures[1].parse::<f32>().ok())
+                    .last();
+                if let Some(percentage) = last {
+                    let _ = progress.send(percentage.min(99.0));
+                }
+            }
+        }
+
+        let status = child.wait().await?;
+        if !status.success() {
+            let errors = match stderr {
+                Some(task) => task.await.unwrap_or_default(),
+                None => String::new(),
+            };
+            return Err(RadarrError::ExternalServiceError {
+                service: "unrar".to_string(),
+                error: format!(
+                    "{} exited with {}: {}",
+                    archive.display(),
+                    status,
+                    errors.trim()
+                ),
+            });
+        }
+        let _ = progress.send(100.0);
+        Ok(())
+    }
+}
+
+/// Extract a zip archive, skipping entries that would land outside `output`
+#[cfg(feature = "archives")]
+fn extract_zip(
+    archive: &Path,
+    output: &Path,
+    progress: &UnboundedSender<f32>,
+) -> Result<(), RadarrError> {
+    let zip_error = |e: zip::result::ZipError| RadarrError::ExternalServiceError {
+        service: "zip".to_string(),
+        error: format!("{}: {}", archive.display(), e),
+    };
+
+    let file = std::fs::File::open(archive)?;
+    let mut zip = zip::ZipArchive::new(file).map_err(zip_error)?;
+    let entries = zip.len().max(1);
+    for index in 0..zip.len() {
+        let mut entry = zip.by_index(index).map_err(zip_error)?;
+        let Some(relative) = entry.enclosed_name() else {
+            warn!(
+                "Skipping zip entry outside the archive root: {}",
+                entry.name()
+            );
+            continue;
+        };
+        let path = output.join(relative);
+        if entry.is_dir() {
+            std::fs::create_dir_all(&path)?;
+        } else {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let mut target = std::fs::File::create(&path)?;
+            std::io::copy(&mut entry, &mut target)?;
+        }
+        let _ = progress.send((index + 1) as f32 / entries as f32 * 100.0);
+    }
+    Ok(())
+}
+
+/// What kind of archive `path` starts, if any
+///
+/// Only the first volume of a multi-part set counts: `.part1.rar` (or
+/// `.part01.rar`) for the newer naming, the `.rar` beside `.r00`, `.r01`, ...
+/// for the older one.
+pub fn archive_kind(path: &Path) -> Option<ArchiveKind> {
+    let name = path.file_name()?.to_str()?;
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    match extension.as_str() {
+        "zip" => Some(ArchiveKind::Zip),
+        "rar" => match RAR_PART_REGEX.captures(name) {
+            Some(captures) => {
+                (captures["part"].parse::<u32>().ok() == Some(1)).then_some(ArchiveKind::Rar)
+            }
+            None => Some(ArchiveKind::Rar),
+        },
+        _ => None,
+    }
+}
+
+/// First volumes of the archives below `dir`, sorted by path
+pub async fn find_archives(dir: &Path) -> Result<Vec<(PathBuf, ArchiveKind)>, RadarrError> {
+    let mut archives = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        let mut entries = tokio::fs::read_dir(&current).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let file_type = entry.file_type().await?;
+            let path = entry.path();
+            if file_type.is_dir() {
+                pending.push(path);
+            } else if let Some(kind) = archive_kind(&path) {
+                archives.push((path, kind));
+            }
+        }
+    }
+    archives.sort();
+    Ok(archives)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_archive_kind_first_volumes() {
+        let kind = |name: &str| archive_kind(Path::new(name));
+        assert_eq!(kind("movie.rar"), Some(ArchiveKind::Rar));
+        assert_eq!(kind("movie.part1.rar"), Some(ArchiveKind::Rar));
+        assert_eq!(kind("movie.part01.RAR"), Some(ArchiveKind::Rar));
+        assert_eq!(kind("movie.part02.rar"), None);
+        assert_eq!(kind("movie.r00"), None);
+        assert_eq!(kind("subs.zip"), Some(ArchiveKind::Zip));
+        assert_eq!(kind("movie.mkv"), None);
+    }
+
+    #[tokio::test]
+    async fn test_find_archives_in_subfolders() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::create_dir_all(root.join("CD2")).unwrap();
+        for name in [
+            "movie.part01.rar",
+            "movie.part02.rar",
+            "CD2/movie.rar",
+            "CD2/movie.r00",
+            "movie.nfo",
+        ] {
+            std::fs::write(root.join(name), b"").unwrap();
+        }
+
+        let archives = find_archives(root).await.unwrap();
+        let names: Vec<_> = archives
+            .iter()
+            .map(|(path, _)| path.strip_prefix(root).unwrap().to_path_buf())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                PathBuf::from("CD2/movie.rar"),
+                PathBuf::from("movie.part01.rar")
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_check_unrar_only_when_enabled() {
+        let config = ExtractConfig {
+            unrar_path: PathBuf::from("/nonexistent/unrar"),
+            ..ExtractConfig::default()
+        };
+        assert!(Extractor::new(config.clone()).check_unrar().await.is_ok());
+
+        let enabled = Extractor::new(ExtractConfig {
+            enabled: true,
+            ..config
+        });
+        assert_eq!(
+            enabled.check_unrar().await.is_err(),
+            cfg!(feature = "archives")
+        );
+    }
+
+    #[cfg(feature = "archives")]
+    #[tokio::test]
+    async fn test_extract_zip() {
+        use std::io::Write;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let download = temp_dir.path().join("download");
+        std::fs::create_dir_all(&download).unwrap();
+        let mut writer =
+            zip::ZipWriter::new(std::fs::File::create(download.join("movie.zip")).unwrap());
+        writer
+            .start_file(
+                "Movie.2020.1080p.mkv",
+                zip::write::SimpleFileOptions::default(),
+            )
+            .unwrap();
+        writer.write_all(b"video").unwrap();
+        writer.finish().unwrap();
+
+        let extractor = Extractor::new(ExtractConfig {
+            enabled: true,
+            work_directory: Some(temp_dir.path().join("work")),
+            ..ExtractConfig::default()
+        });
+        let extraction = extractor
+            .extract_all(&download, None)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let extracted = extraction.output_dir.join("0/Movie.2020.1080p.mkv");
+        assert_eq!(std::fs::read(&extracted).unwrap(), b"video");
+        extraction.cleanup().await;
+        assert!(!extraction.output_dir.exists());
+    }
+}
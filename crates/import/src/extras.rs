@@ -0,0 +1,254 @@
+//! Sample and extras classification
+//!
+//! Besides the movie, releases often carry samples, trailers, featurettes
+//! and other bonus material. Files are classified by tokens in their name
+//! or folder (`sample`, `trailer`, `featurette`, `Behind The Scenes`, ...)
+//! and, failing that, by size: a video much smaller than the largest one in
+//! the same download is taken to be an extra. Each kind is then skipped,
+//! deleted or imported into the movie folder's subfolder for that kind,
+//! named as Plex and Jellyfin expect (`Trailers`, `Featurettes`, ...).
+
+use once_cell::sync::Lazy;
+use radarr_core::RadarrError;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::file_scanner::DetectedFile;
+
+/// Kind of extra a file is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ExtraType {
+    Sample,
+    Trailer,
+    Featurette,
+    BehindTheScenes,
+    DeletedScene,
+    Interview,
+    Other,
+}
+
+/// Name and folder tokens, checked in order; the first match wins
+static EXTRA_TOKENS: Lazy<Vec<(ExtraType, Regex)>> = Lazy::new(|| {
+    [
+        (ExtraType::Sample, r"sample|proof|rarbg"),
+        (ExtraType::Trailer, r"trailers?|teasers?|preview"),
+        (
+            ExtraType::BehindTheScenes,
+            r"behind[ ._-]?the[ ._-]?scenes|making[ ._-]of",
+        ),
+        (ExtraType::DeletedScene, r"deleted[ ._-]?scenes?"),
+        (ExtraType::Interview, r"interviews?"),
+        (ExtraType::Featurette, r"featurettes?"),
+        (ExtraType::Other, r"extras?|bonus|shorts?"),
+    ]
+    .into_iter()
+    .map(|(extra, pattern)| {
+        let regex = Regex::new(&format!(r"(?i)(?:^|[^a-z])(?:{})(?:[^a-z]|$)", pattern)).unwrap();
+        (extra, regex)
+    })
+    .collect()
+});
+
+impl ExtraType {
+    /// Subfolder of the movie folder Plex and Jellyfin look for this kind in
+    pub fn folder_name(&self) -> &'static str {
+        match self {
+            ExtraType::Sample => "Samples",
+            ExtraType::Trailer => "Trailers",
+            ExtraType::Featurette => "Featurettes",
+            ExtraType::BehindTheScenes => "Behind The Scenes",
+            ExtraType::DeletedScene => "Deleted Scenes",
+            ExtraType::Interview => "Interviews",
+            ExtraType::Other => "Other",
+        }
+    }
+
+    /// Kind named by a token in the file name or one of its folders
+    ///
+    /// Only the part of `path` below `root` is looked at, so a download
+    /// folder called `Extras` doesn't make every file in it an extra.
+    pub fn from_path(path: &Path, root: &Path) -> Option<Self> {
+        let relative = path.strip_prefix(root).unwrap_or(path);
+        let stem = relative.file_stem()?.to_str()?;
+        let folders = relative
+            .parent()
+            .into_iter()
+            .flat_map(|parent| parent.components())
+            .filter_map(|component| component.as_os_str().to_str());
+        std::iter::once(stem).chain(folders).find_map(|name| {
+            EXTRA_TOKENS
+                .iter()
+                .find(|(_, regex)| regex.is_match(name))
+                .map(|(extra, _)| *extra)
+        })
+    }
+}
+
+/// What is done with one kind of extra
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ExtraAction {
+    /// Leave the file in the download
+    #[default]
+    Skip,
+    /// Import it into the movie folder's subfolder for its kind
+    Import,
+    /// Delete it from the download
+    Delete,
+}
+
+impl FromStr for ExtraAction {
+    type Err = RadarrError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "skip" => Ok(ExtraAction::Skip),
+            "import" => Ok(ExtraAction::Import),
+            "delete" => Ok(ExtraAction::Delete),
+            _ => Err(RadarrError::ValidationError {
+                field: "extra_action".to_string(),
+                message: format!(
+                    "Unknown extras action '{}', expected skip, import or delete",
+                    value
+                ),
+            }),
+        }
+    }
+}
+
+/// How samples and extras found next to a movie are handled
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtrasConfig {
+    pub samples: ExtraAction,
+    pub trailers: ExtraAction,
+    pub featurettes: ExtraAction,
+    /// Behind the scenes, deleted scenes, interviews and other extras
+    pub other: ExtraAction,
+    /// Videos without a token that are smaller than this share of the
+    /// largest video in the download are extras
+    pub size_ratio: f64,
+}
+
+impl Default for ExtrasConfig {
+    fn default() -> Self {
+        Self {
+            samples: ExtraAction::Skip,
+            trailers: ExtraAction::Skip,
+            featurettes: ExtraAction::Skip,
+            other: ExtraAction::Skip,
+            size_ratio: 0.2,
+        }
+    }
+}
+
+impl ExtrasConfig {
+    /// Action configured for `extra`
+    pub fn action(&self, extra: ExtraType) -> ExtraAction {
+        match extra {
+            ExtraType::Sample => self.samples,
+            ExtraType::Trailer => self.trailers,
+            ExtraType::Featurette => self.featurettes,
+            ExtraType::BehindTheScenes
+            | ExtraType::DeletedScene
+            | ExtraType::Interview
+            | ExtraType::Other => self.other,
+        }
+    }
+
+    /// Classify the files of one download
+    ///
+    /// Files the scanner tagged keep their kind. Of the rest, videos smaller
+    /// than `size_ratio` of the largest file are `Other` extras; the largest
+    /// is always a movie, as are files of similar size such as the parts of
+    /// a multi-disc release.
+    pub fn classify(&self, files: &mut [DetectedFile]) {
+        let largest = files
+            .iter()
+            .filter(|file| file.extra_type.is_none())
+            .map(|file| file.size)
+            .max()
+            .unwrap_or(0);
+        let threshold = (largest as f64 * self.size_ratio) as u64;
+        for file in files.iter_mut().filter(|file| file.extra_type.is_none()) {
+            if file.size < threshold {
+                file.extra_type = Some(ExtraType::Other);
+                file.is_sample = true;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_scanner::MediaType;
+    use std::path::PathBuf;
+    use std::time::SystemTime;
+
+    fn detected(path: &str, size: u64, extra_type: Option<ExtraType>) -> DetectedFile {
+        DetectedFile {
+            path: PathBuf::from(path),
+            size,
+            extension: "mkv".to_string(),
+            modified: SystemTime::now(),
+            media_type: MediaType::Movie,
+            is_sample: extra_type == Some(ExtraType::Sample),
+            extra_type,
+        }
+    }
+
+    #[test]
+    fn test_extra_type_from_tokens() {
+        let root = Path::new("/downloads/Movie.2020.1080p.BluRay-GROUP");
+        let kind = |relative: &str| ExtraType::from_path(&root.join(relative), root);
+
+        assert_eq!(kind("Movie.2020.1080p.BluRay-GROUP.mkv"), None);
+        assert_eq!(
+            kind("Sample/group-movie-sample.mkv"),
+            Some(ExtraType::Sample)
+        );
+        assert_eq!(kind("Movie.2020.Trailer.mp4"), Some(ExtraType::Trailer));
+        assert_eq!(
+            kind("Featurettes/The Look of the Film.mkv"),
+            Some(ExtraType::Featurette)
+        );
+        assert_eq!(
+            kind("Extras/Behind.The.Scenes.mkv"),
+            Some(ExtraType::BehindTheScenes)
+        );
+        assert_eq!(kind("Extras/Gag Reel.mkv"), Some(ExtraType::Other));
+        // Words merely containing a token don't count
+        assert_eq!(kind("Movie.2020.Samplers.mkv"), None);
+        // Nor does the download's own folder name
+        assert_eq!(
+            ExtraType::from_path(
+                Path::new("/downloads/Extras Collection/Movie.2020.mkv"),
+                Path::new("/downloads/Extras Collection")
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_classify_by_size_ratio() {
+        let config = ExtrasConfig::default();
+        let mut files = vec![
+            detected("/d/movie.cd1.mkv", 4_000, None),
+            detected("/d/movie.cd2.mkv", 3_500, None),
+            detected("/d/gag reel.mkv", 300, None),
+            detected("/d/sample.mkv", 50, Some(ExtraType::Sample)),
+        ];
+
+        config.classify(&mut files);
+
+        let kinds: Vec<_> = files.iter().map(|file| file.extra_type).collect();
+        assert_eq!(
+            kinds,
+            vec![None, None, Some(ExtraType::Other), Some(ExtraType::Sample)]
+        );
+        assert_eq!(config.action(ExtraType::Interview), config.other);
+    }
+}
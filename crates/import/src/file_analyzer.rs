@@ -4,7 +4,7 @@
 //! extracting movie information, quality details, and release metadata.
 
 use once_cell::sync::Lazy;
-use radarr_core::RadarrError;
+use radarr_core::{parse_edition, AudioCodec, RadarrError, Revision};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
@@ -23,6 +23,9 @@ pub struct AnalyzedFile {
     pub quality: QualityInfo,
     /// Release group that created this file
     pub release_group: Option<String>,
+    /// Edition of the movie, e.g. `Director's Cut`
+    #[serde(default)]
+    pub edition: Option<String>,
     /// Whether this is a sample file
     pub is_sample: bool,
     /// Confidence score (0.0 - 1.0) for the analysis
@@ -44,6 +47,9 @@ pub struct QualityInfo {
     pub source: Option<String>,
     /// HDR information (HDR, HDR10, Dolby Vision, etc.)
     pub hdr: Option<String>,
+    /// Proper or repack revision
+    #[serde(default)]
+    pub revision: Revision,
 }
 
 impl Default for QualityInfo {
@@ -54,6 +60,7 @@ impl Default for QualityInfo {
             audio: None,
             source: None,
             hdr: None,
+            revision: Revision::default(),
         }
     }
 }
@@ -65,8 +72,9 @@ static YEAR_REGEX: Lazy<Regex> =
 static RESOLUTION_REGEX: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"(?i)\b(2160p|1080p|720p|480p|4K|UHD)\b").unwrap());
 
-static CODEC_REGEX: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"(?i)\b(x264|x265|h264|h265|HEVC|AVC|XviD|DivX)\b").unwrap());
+static CODEC_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b(x266|h266|VVC|AV1|x265|h265|HEVC|x264|h264|AVC|VP9|XviD|DivX)\b").unwrap()
+});
 
 static AUDIO_REGEX: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"(?i)\b(DTS|AC3|AAC|MP3|FLAC|TrueHD|Atmos|DD|EAC3)\b").unwrap());
@@ -173,6 +181,8 @@ impl FileAnalyzer {
             confidence += 0.1;
         }
 
+        let edition = parse_edition(filename);
+
         // Extract and clean movie title
         let title = self.extract_title(filename, year);
         if title.is_some() {
@@ -193,6 +203,7 @@ impl FileAnalyzer {
             year,
             quality,
             release_group,
+            edition,
             is_sample,
             confidence,
             original_filename,
@@ -217,9 +228,15 @@ impl FileAnalyzer {
             .find(filename)
             .map(|m| m.as_str().to_uppercase());
 
-        let audio = AUDIO_REGEX
-            .find(filename)
-            .map(|m| m.as_str().to_uppercase());
+        // The codec taxonomy tells DD+ Atmos from TrueHD Atmos; the raw tag
+        // covers formats it does not know
+        let audio = AudioCodec::parse(filename)
+            .map(|codec| codec.name().to_uppercase())
+            .or_else(|| {
+                AUDIO_REGEX
+                    .find(filename)
+                    .map(|m| m.as_str().to_uppercase())
+            });
 
         let source = SOURCE_REGEX
             .find(filename)
@@ -233,6 +250,7 @@ impl FileAnalyzer {
             audio,
             source,
             hdr,
+            revision: Revision::parse(filename),
         }
     }
 
@@ -334,7 +352,10 @@ impl FileAnalyzer {
         // Codec scoring
         if let Some(ref codec) = quality.codec {
             score += match codec.as_str() {
+                "X266" | "H266" | "VVC" => 250,
+                "AV1" => 225,
                 "X265" | "H265" | "HEVC" => 200,
+                "VP9" => 175,
                 "X264" | "H264" | "AVC" => 150,
                 _ => 50,
             };
@@ -390,6 +411,11 @@ mod tests {
     fn test_extract_quality() {
         let analyzer = FileAnalyzer::new();
 
+        let quality_av1 =
+            analyzer.extract_quality("Movie.2023.2160p.WEB-DL.DDP5.1.Atmos.AV1-GROUP");
+        assert_eq!(quality_av1.codec, Some("AV1".to_string()));
+        assert_eq!(quality_av1.audio, Some("DD+ ATMOS".to_string()));
+
         let quality = analyzer.extract_quality("Movie.2023.1080p.BluRay.x264.DTS-GROUP");
         assert_eq!(quality.resolution, Some("1080P".to_string()));
         assert_eq!(quality.codec, Some("X264".to_string()));
@@ -461,6 +487,7 @@ mod tests {
             source: Some("BLURAY".to_string()),
             hdr: Some("HDR10".to_string()),
             audio: None,
+            revision: Revision::default(),
         };
 
         let low_quality = QualityInfo {
@@ -469,6 +496,7 @@ mod tests {
             source: Some("HDTV".to_string()),
             hdr: None,
             audio: None,
+            revision: Revision::default(),
         };
 
         assert!(
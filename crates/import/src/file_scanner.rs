@@ -3,14 +3,22 @@
 //! This module provides functionality to recursively scan directories
 //! for video files and extract metadata about them.
 
-use radarr_core::RadarrError;
+use radarr_core::{
+    events::{EventBus, SystemEvent},
+    progress::OperationType,
+    RadarrError,
+};
 use serde::{Deserialize, Serialize};
-use std::future::Future;
+use std::collections::VecDeque;
 use std::path::{Path, PathBuf};
-use std::pin::Pin;
-use std::time::SystemTime;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
 use tokio::fs;
+use tokio::task::JoinSet;
 use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::extras::ExtraType;
 
 /// Configuration for file scanning operations
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,8 +29,30 @@ pub struct ScanConfig {
     pub follow_symlinks: bool,
     /// Minimum file size in bytes to consider
     pub min_file_size: u64,
+    /// Minimum size of files named as samples or extras, which are
+    /// usually far smaller than the movie
+    #[serde(default = "default_min_extra_size")]
+    pub min_extra_size: u64,
     /// Maximum file size in bytes to consider (0 = no limit)
     pub max_file_size: u64,
+    /// Directories read at the same time
+    #[serde(default = "default_max_concurrency")]
+    pub max_concurrency: usize,
+    /// Files examined between progress events
+    #[serde(default = "default_progress_interval")]
+    pub progress_interval: usize,
+}
+
+fn default_min_extra_size() -> u64 {
+    1024 * 1024
+}
+
+fn default_max_concurrency() -> usize {
+    8
+}
+
+fn default_progress_interval() -> usize {
+    1000
 }
 
 impl Default for ScanConfig {
@@ -31,7 +61,10 @@ impl Default for ScanConfig {
             max_depth: 10,
             follow_symlinks: false,
             min_file_size: 100 * 1024 * 1024, // 100MB minimum
-            max_file_size: 0,                 // No limit
+            min_extra_size: default_min_extra_size(),
+            max_file_size: 0, // No limit
+            max_concurrency: default_max_concurrency(),
+            progress_interval: default_progress_interval(),
         }
     }
 }
@@ -49,8 +82,11 @@ pub struct DetectedFile {
     pub modified: SystemTime,
     /// Detected media type
     pub media_type: MediaType,
-    /// Whether this appears to be a sample file
+    /// Whether this appears to be a sample or other extra rather than the movie
     pub is_sample: bool,
+    /// Kind of extra the file's name or folder marks it as
+    #[serde(default)]
+    pub extra_type: Option<ExtraType>,
 }
 
 /// Type of media content detected
@@ -70,18 +106,41 @@ const VIDEO_EXTENSIONS: &[&str] = &[
     "roq", "nsv", "f4v", "f4p", "f4a", "f4b",
 ];
 
-/// Sample file indicators in filename
-const SAMPLE_INDICATORS: &[&str] = &["sample", "trailer", "preview", "rarbg", "proof"];
+/// Throughput of a directory scan
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScanStats {
+    /// Directories read
+    pub directories_scanned: usize,
+    /// Files looked at, media or not
+    pub files_examined: usize,
+    /// Media files found
+    pub media_files: usize,
+    /// Wall time of the scan
+    pub duration: Duration,
+    /// Files examined per second
+    pub files_per_second: f64,
+}
+
+/// Contents of one directory read by a scan task
+struct DirectoryListing {
+    subdirectories: Vec<PathBuf>,
+    files_examined: usize,
+    detected: Vec<DetectedFile>,
+}
 
 /// File scanner for discovering media files
 pub struct FileScanner {
-    config: ScanConfig,
+    config: Arc<ScanConfig>,
+    event_bus: Option<Arc<EventBus>>,
 }
 
 impl FileScanner {
     /// Create a new file scanner with the given configuration
     pub fn new(config: ScanConfig) -> Self {
-        Self { config }
+        Self {
+            config: Arc::new(config),
+            event_bus: None,
+        }
     }
 
     /// Create a file scanner with default configuration
@@ -89,8 +148,27 @@ impl FileScanner {
         Self::new(ScanConfig::default())
     }
 
+    /// Publish scan progress events
+    pub fn with_event_bus(mut self, event_bus: Arc<EventBus>) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
     /// Scan a directory for media files
     pub async fn scan_directory(&self, path: &Path) -> Result<Vec<DetectedFile>, RadarrError> {
+        self.scan_directory_with_stats(path)
+            .await
+            .map(|(files, _)| files)
+    }
+
+    /// Scan a directory for media files, also returning the scan's throughput
+    ///
+    /// Directories are read by up to `max_concurrency` tasks at once. Files
+    /// come back sorted by path.
+    pub async fn scan_directory_with_stats(
+        &self,
+        path: &Path,
+    ) -> Result<(Vec<DetectedFile>, ScanStats), RadarrError> {
         info!("Starting scan of directory: {}", path.display());
 
         if !path.exists() {
@@ -107,67 +185,199 @@ impl FileScanner {
             });
         }
 
+        let start = Instant::now();
+        let operation_id = Uuid::new_v4();
+        let max_concurrency = self.config.max_concurrency.max(1);
+        let progress_interval = self.config.progress_interval.max(1);
+
+        let root: Arc<Path> = Arc::from(path);
+        let mut pending: VecDeque<(PathBuf, u8)> = VecDeque::from([(path.to_path_buf(), 0)]);
+        let mut tasks = JoinSet::new();
         let mut detected_files = Vec::new();
-        self.scan_recursive(path, 0, &mut detected_files).await?;
+        let mut stats = ScanStats::default();
+        let mut directories_found = 1usize;
+        let mut next_report = progress_interval;
+
+        while !pending.is_empty() || !tasks.is_empty() {
+            while tasks.len() < max_concurrency {
+                let Some((directory, depth)) = pending.pop_front() else {
+                    break;
+                };
+                if depth >= self.config.max_depth {
+                    debug!(
+                        "Reached max depth {} at {}",
+                        self.config.max_depth,
+                        directory.display()
+                    );
+                    stats.directories_scanned += 1;
+                    continue;
+                }
+                let config = self.config.clone();
+                let root = root.clone();
+                tasks.spawn(async move {
+                    let listing = Self::read_directory(&config, &root, &directory).await;
+                    (listing, depth)
+                });
+            }
 
-        info!("Scan complete. Found {} media files", detected_files.len());
-        Ok(detected_files)
+            let Some(joined) = tasks.join_next().await else {
+                continue;
+            };
+            let (listing, depth) = joined?;
+            let listing = listing?;
+
+            stats.directories_scanned += 1;
+            stats.files_examined += listing.files_examined;
+            directories_found += listing.subdirectories.len();
+            pending.extend(
+                listing
+                    .subdirectories
+                    .into_iter()
+                    .map(|directory| (directory, depth + 1)),
+            );
+            detected_files.extend(listing.detected);
+
+            if stats.files_examined >= next_report {
+                next_report = stats.files_examined + progress_interval;
+                self.report_progress(
+                    operation_id,
+                    &stats,
+                    detected_files.len(),
+                    directories_found,
+                    start.elapsed(),
+                )
+                .await;
+            }
+        }
+
+        detected_files.sort_by(|a, b| a.path.cmp(&b.path));
+        stats.media_files = detected_files.len();
+        stats.duration = start.elapsed();
+        let seconds = stats.duration.as_secs_f64();
+        if seconds > 0.0 {
+            stats.files_per_second = stats.files_examined as f64 / seconds;
+        }
+
+        if let Some(bus) = &self.event_bus {
+            let _ = bus
+                .publish(SystemEvent::OperationComplete {
+                    operation_id,
+                    operation_type: OperationType::LibraryScan,
+                    success: true,
+                    message: format!(
+                        "Scanned {} files, found {} media files",
+                        stats.files_examined, stats.media_files
+                    ),
+                })
+                .await;
+        }
+
+        info!(
+            "Scan complete. Found {} media files in {} files ({:.0} files/s)",
+            stats.media_files, stats.files_examined, stats.files_per_second
+        );
+        Ok((detected_files, stats))
+    }
+
+    /// Publish how far the scan has got
+    ///
+    /// The total is unknown until the walk finishes, so the percentage is the
+    /// share of directories found so far that have been read.
+    async fn report_progress(
+        &self,
+        operation_id: Uuid,
+        stats: &ScanStats,
+        media_files: usize,
+        directories_found: usize,
+        elapsed: Duration,
+    ) {
+        let Some(bus) = &self.event_bus else {
+            return;
+        };
+        let percentage = stats.directories_scanned as f32 / directories_found as f32 * 100.0;
+        let remaining = directories_found - stats.directories_scanned;
+        let eta_seconds = (stats.directories_scanned > 0).then(|| {
+            let per_directory = elapsed.as_secs_f64() / stats.directories_scanned as f64;
+            (per_directory * remaining as f64).round() as u64
+        });
+        let _ = bus
+            .publish(SystemEvent::ProgressUpdate {
+                operation_id,
+                operation_type: OperationType::LibraryScan,
+                percentage,
+                message: format!(
+                    "Scanned {} files, found {} media files",
+                    stats.files_examined, media_files
+                ),
+                eta_seconds,
+            })
+            .await;
     }
 
-    /// Recursively scan directories up to max_depth
-    fn scan_recursive<'a>(
-        &'a self,
-        path: &'a Path,
-        current_depth: u8,
-        detected_files: &'a mut Vec<DetectedFile>,
-    ) -> Pin<Box<dyn Future<Output = Result<(), RadarrError>> + 'a>> {
-        Box::pin(async move {
-            if current_depth >= self.config.max_depth {
-                debug!(
-                    "Reached max depth {} at {}",
-                    self.config.max_depth,
-                    path.display()
-                );
-                return Ok(());
+    /// Read one directory, analyzing its files and listing its subdirectories
+    async fn read_directory(
+        config: &ScanConfig,
+        root: &Path,
+        path: &Path,
+    ) -> Result<DirectoryListing, RadarrError> {
+        let mut listing = DirectoryListing {
+            subdirectories: Vec::new(),
+            files_examined: 0,
+            detected: Vec::new(),
+        };
+
+        let mut entries = match fs::read_dir(path).await {
+            Ok(entries) => entries,
+            Err(e) => {
+                warn!("Failed to read directory {}: {}", path.display(), e);
+                return Ok(listing); // Continue scanning other directories
             }
+        };
 
-            let mut entries = match fs::read_dir(path).await {
-                Ok(entries) => entries,
-                Err(e) => {
-                    warn!("Failed to read directory {}: {}", path.display(), e);
-                    return Ok(()); // Continue scanning other directories
-                }
+        while let Some(entry) =
+            entries
+                .next_entry()
+                .await
+                .map_err(|e| RadarrError::ExternalServiceError {
+                    service: "filesystem".to_string(),
+                    error: e.to_string(),
+                })?
+        {
+            let entry_path = entry.path();
+            let Ok(mut file_type) = entry.file_type().await else {
+                continue;
             };
+            if file_type.is_symlink() {
+                // Classify links by what they point at
+                match fs::metadata(&entry_path).await {
+                    Ok(metadata) => file_type = metadata.file_type(),
+                    Err(_) => continue,
+                }
+            }
 
-            while let Some(entry) =
-                entries
-                    .next_entry()
-                    .await
-                    .map_err(|e| RadarrError::ExternalServiceError {
-                        service: "filesystem".to_string(),
-                        error: e.to_string(),
-                    })?
-            {
-                let entry_path = entry.path();
-
-                if entry_path.is_dir() {
-                    // Recursively scan subdirectories
-                    self.scan_recursive(&entry_path, current_depth + 1, detected_files)
-                        .await?;
-                } else if entry_path.is_file() {
-                    // Check if this is a video file
-                    if let Some(detected) = self.analyze_file(&entry_path).await? {
-                        detected_files.push(detected);
-                    }
+            if file_type.is_dir() {
+                listing.subdirectories.push(entry_path);
+            } else if file_type.is_file() {
+                listing.files_examined += 1;
+                // Check if this is a video file
+                if let Some(detected) = Self::analyze_file(config, root, &entry_path).await? {
+                    listing.detected.push(detected);
                 }
             }
+        }
 
-            Ok(())
-        })
+        Ok(listing)
     }
 
     /// Analyze a single file to see if it's a valid media file
-    async fn analyze_file(&self, path: &Path) -> Result<Option<DetectedFile>, RadarrError> {
+    ///
+    /// `root` is the directory being scanned; only folders below it count
+    /// towards classifying the file as an extra.
+    async fn analyze_file(
+        config: &ScanConfig,
+        root: &Path,
+        path: &Path,
+    ) -> Result<Option<DetectedFile>, RadarrError> {
         let extension = path
             .extension()
             .and_then(|ext| ext.to_str())
@@ -195,13 +405,21 @@ impl FileScanner {
                 error: e.to_string(),
             })?;
 
+        // Samples and extras are held to their own, lower minimum
+        let extra_type = ExtraType::from_path(path, root);
+        let min_size = if extra_type.is_some() {
+            config.min_extra_size
+        } else {
+            config.min_file_size
+        };
+
         // Check file size constraints
-        if size < self.config.min_file_size {
+        if size < min_size {
             debug!("File too small: {} ({} bytes)", path.display(), size);
             return Ok(None);
         }
 
-        if self.config.max_file_size > 0 && size > self.config.max_file_size {
+        if config.max_file_size > 0 && size > config.max_file_size {
             debug!("File too large: {} ({} bytes)", path.display(), size);
             return Ok(None);
         }
@@ -212,13 +430,8 @@ impl FileScanner {
             .unwrap_or("")
             .to_lowercase();
 
-        // Detect if it's a sample file
-        let is_sample = SAMPLE_INDICATORS
-            .iter()
-            .any(|indicator| filename.contains(indicator));
-
         // Basic media type detection (refined in file_analyzer)
-        let media_type = self.detect_media_type(&filename);
+        let media_type = Self::detect_media_type(&filename);
 
         debug!(
             "Detected media file: {} ({} bytes, {})",
@@ -233,12 +446,13 @@ impl FileScanner {
             extension,
             modified,
             media_type,
-            is_sample,
+            is_sample: extra_type.is_some(),
+            extra_type,
         }))
     }
 
     /// Basic media type detection based on filename patterns
-    fn detect_media_type(&self, filename: &str) -> MediaType {
+    fn detect_media_type(filename: &str) -> MediaType {
         // Look for TV show patterns (S01E01, 1x01, etc.)
         if filename.contains("s01e")
             || filename.contains("s1e")
@@ -305,6 +519,30 @@ mod tests {
 
         let sample_file = files.iter().find(|f| f.path == sample_path).unwrap();
         assert!(sample_file.is_sample);
+        assert_eq!(sample_file.extra_type, Some(ExtraType::Sample));
+    }
+
+    #[tokio::test]
+    async fn test_small_extras_are_detected() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = ScanConfig {
+            min_file_size: 1024,
+            min_extra_size: 16,
+            ..Default::default()
+        };
+        let scanner = FileScanner::new(config);
+
+        let trailer = temp_dir.path().join("Trailers").join("Teaser.mp4");
+        fs::create_dir_all(trailer.parent().unwrap()).await.unwrap();
+        fs::write(&trailer, vec![0u8; 64]).await.unwrap();
+        fs::write(temp_dir.path().join("movie.2023.mkv"), vec![0u8; 64])
+            .await
+            .unwrap();
+
+        let files = scanner.scan_directory(temp_dir.path()).await.unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, trailer);
+        assert_eq!(files[0].extra_type, Some(ExtraType::Trailer));
     }
 
     #[tokio::test]
@@ -326,6 +564,53 @@ mod tests {
         assert!(files.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_parallel_scan_reports_progress() {
+        let temp_dir = TempDir::new().unwrap();
+        for movie in 0..6 {
+            let folder = temp_dir
+                .path()
+                .join(format!("Movie {}", movie))
+                .join("extras");
+            fs::create_dir_all(&folder).await.unwrap();
+            fs::write(folder.join(format!("movie.{}.1080p.mkv", movie)), b"video")
+                .await
+                .unwrap();
+            fs::write(folder.join("movie.nfo"), b"info").await.unwrap();
+        }
+
+        let bus = Arc::new(EventBus::new());
+        let config = ScanConfig {
+            min_file_size: 0,
+            min_extra_size: 0,
+            max_concurrency: 3,
+            progress_interval: 4,
+            ..Default::default()
+        };
+        let scanner = FileScanner::new(config).with_event_bus(bus.clone());
+
+        let (files, stats) = scanner
+            .scan_directory_with_stats(temp_dir.path())
+            .await
+            .unwrap();
+        assert_eq!(files.len(), 6);
+        assert!(files.windows(2).all(|pair| pair[0].path < pair[1].path));
+        assert_eq!(stats.files_examined, 12);
+        assert_eq!(stats.media_files, 6);
+        assert_eq!(stats.directories_scanned, 13);
+
+        let events = bus.replay_after(None);
+        let progress = events
+            .iter()
+            .filter(|e| matches!(e.event, SystemEvent::ProgressUpdate { .. }))
+            .count();
+        assert!(progress >= 2);
+        assert!(matches!(
+            events.last().map(|e| &e.event),
+            Some(SystemEvent::OperationComplete { success: true, .. })
+        ));
+    }
+
     #[tokio::test]
     async fn test_nonexistent_directory() {
         let scanner = FileScanner::default();
@@ -0,0 +1,313 @@
+//! Forensic bundles for failed imports
+//!
+//! When a file fails to import, the pipeline writes everything needed to
+//! understand the failure to one JSON file under the diagnostics directory:
+//! the scanner output, the analyzer's result and confidence, the rename it
+//! computed, what the filesystem reported for the paths involved and the log
+//! lines recorded under the import's correlation ID. Bundles can be attached
+//! to bug reports as they are.
+
+use chrono::{DateTime, Utc};
+use radarr_core::tracing::{log_buffer, CorrelatedLogEntry};
+use radarr_core::{RadarrError, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tracing::{debug, info, warn};
+
+use crate::{
+    file_analyzer::AnalyzedFile, file_scanner::DetectedFile, hardlink_manager::HardlinkResult,
+    pipeline::ImportConfig, rename_engine::RenameResult,
+};
+
+/// Bundles kept before the oldest are removed
+const DEFAULT_MAX_BUNDLES: usize = 200;
+
+/// File extension of a bundle
+const BUNDLE_EXTENSION: &str = "json";
+
+/// The rename the pipeline computed for a file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenameComputation {
+    pub planned: Option<RenameResult>,
+    /// Why no rename could be planned
+    pub error: Option<String>,
+}
+
+/// What the filesystem reported for one path
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FilesystemCheck {
+    pub path: PathBuf,
+    /// Role of the path in the import, e.g. `source` or `destination`
+    pub role: String,
+    pub exists: bool,
+    pub is_dir: bool,
+    pub size: Option<u64>,
+    pub readonly: Option<bool>,
+    /// Error returned when reading the path's metadata
+    pub error: Option<String>,
+}
+
+impl FilesystemCheck {
+    /// Read the metadata of `path`
+    pub async fn probe(path: &Path, role: &str) -> Self {
+        match tokio::fs::metadata(path).await {
+            Ok(metadata) => Self {
+                path: path.to_path_buf(),
+                role: role.to_string(),
+                exists: true,
+                is_dir: metadata.is_dir(),
+                size: metadata.is_file().then_some(metadata.len()),
+                readonly: Some(metadata.permissions().readonly()),
+                error: None,
+            },
+            Err(e) => Self {
+                path: path.to_path_buf(),
+                role: role.to_string(),
+                exists: e.kind() != std::io::ErrorKind::NotFound,
+                is_dir: false,
+                size: None,
+                readonly: None,
+                error: Some(format!("{:?}: {}", e.kind(), e)),
+            },
+        }
+    }
+}
+
+/// Everything known about one failed import
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForensicBundle {
+    pub id: String,
+    pub correlation_id: String,
+    pub created_at: DateTime<Utc>,
+    /// File that failed, or the scanned directory when the scan failed
+    pub source_path: PathBuf,
+    pub destination_dir: PathBuf,
+    pub error: String,
+    /// Files the scanner detected in the same import
+    pub scanned_files: Vec<DetectedFile>,
+    pub analysis: Option<AnalyzedFile>,
+    pub confidence: Option<f32>,
+    pub min_confidence: f32,
+    pub rename: RenameComputation,
+    pub hardlink: Option<HardlinkResult>,
+    pub filesystem: Vec<FilesystemCheck>,
+    /// Log lines recorded under `correlation_id` up to the failure
+    pub logs: Vec<CorrelatedLogEntry>,
+    pub config: ImportConfig,
+}
+
+impl ForensicBundle {
+    /// Start a bundle for a failure of `source_path`, attaching the log slice
+    /// of `correlation_id`
+    pub fn new(
+        correlation_id: impl Into<String>,
+        source_path: &Path,
+        destination_dir: &Path,
+        error: impl Into<String>,
+        config: &ImportConfig,
+    ) -> Self {
+        let correlation_id = correlation_id.into();
+        let created_at = Utc::now();
+        let suffix = uuid::Uuid::new_v4().simple().to_string();
+
+        Self {
+            id: format!("{}-{}", created_at.format("%Y%m%dT%H%M%S"), &suffix[..8]),
+            logs: log_buffer().entries_for(&correlation_id),
+            correlation_id,
+            created_at,
+            source_path: source_path.to_path_buf(),
+            destination_dir: destination_dir.to_path_buf(),
+            error: error.into(),
+            scanned_files: Vec::new(),
+            analysis: None,
+            confidence: None,
+            min_confidence: config.min_confidence,
+            rename: RenameComputation::default(),
+            hardlink: None,
+            filesystem: Vec::new(),
+            config: config.clone(),
+        }
+    }
+}
+
+/// Listing entry for a stored bundle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForensicBundleSummary {
+    pub id: String,
+    pub correlation_id: String,
+    pub created_at: DateTime<Utc>,
+    pub source_path: PathBuf,
+    pub error: String,
+    /// Size of the bundle file in bytes
+    #[serde(default)]
+    pub size: u64,
+}
+
+/// Stores forensic bundles as JSON files in a diagnostics directory
+pub struct ForensicBundleStore {
+    directory: PathBuf,
+    max_bundles: usize,
+}
+
+impl ForensicBundleStore {
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+            max_bundles: DEFAULT_MAX_BUNDLES,
+        }
+    }
+
+    /// Keep at most `max_bundles`, removing the oldest first
+    pub fn with_max_bundles(mut self, max_bundles: usize) -> Self {
+        self.max_bundles = max_bundles.max(1);
+        self
+    }
+
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Write `bundle`, returning the path of its file
+    pub async fn save(&self, bundle: &ForensicBundle) -> Result<PathBuf> {
+        tokio::fs::create_dir_all(&self.directory).await?;
+        let path = self.bundle_path(&bundle.id)?;
+        tokio::fs::write(&path, serde_json::to_vec_pretty(bundle)?).await?;
+        info!(
+            "Wrote import forensic bundle {} for {}",
+            bundle.id,
+            bundle.source_path.display()
+        );
+
+        self.prune().await;
+        Ok(path)
+    }
+
+    /// Stored bundles, newest first
+    pub async fn list(&self) -> Result<Vec<ForensicBundleSummary>> {
+        let mut entries = match tokio::fs::read_dir(&self.directory).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut summaries = Vec::new();
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(BUNDLE_EXTENSION) {
+                continue;
+            }
+            let contents = tokio::fs::read(&path).await?;
+            match serde_json::from_slice::<ForensicBundleSummary>(&contents) {
+                Ok(mut summary) => {
+                    summary.size = contents.len() as u64;
+                    summaries.push(summary);
+                }
+                Err(e) => debug!("Skipping unreadable bundle {}: {}", path.display(), e),
+            }
+        }
+
+        summaries.sort_by_key(|summary| std::cmp::Reverse(summary.created_at));
+        Ok(summaries)
+    }
+
+    /// Raw JSON of the bundle `id`
+    pub async fn read(&self, id: &str) -> Result<Vec<u8>> {
+        let path = self.bundle_path(id)?;
+        match tokio::fs::read(&path).await {
+            Ok(contents) => Ok(contents),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Err(RadarrError::NotFound {
+                resource: format!("forensic bundle {}", id),
+            }),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Path of the bundle `id`, refusing IDs that could leave the directory
+    fn bundle_path(&self, id: &str) -> Result<PathBuf> {
+        let valid = !id.is_empty()
+            && id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            return Err(RadarrError::ValidationError {
+                field: "id".to_string(),
+                message: format!("Invalid forensic bundle ID: {}", id),
+            });
+        }
+        Ok(self.directory.join(format!("{}.{}", id, BUNDLE_EXTENSION)))
+    }
+
+    /// Remove the oldest bundles beyond the limit
+    async fn prune(&self) {
+        let bundles = match self.list().await {
+            Ok(bundles) => bundles,
+            Err(e) => {
+                warn!("Failed to list forensic bundles: {}", e);
+                return;
+            }
+        };
+
+        for bundle in bundles.iter().skip(self.max_bundles) {
+            if let Ok(path) = self.bundle_path(&bundle.id) {
+                if let Err(e) = tokio::fs::remove_file(&path).await {
+                    warn!("Failed to remove forensic bundle {}: {}", bundle.id, e);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_save_list_read_and_prune() {
+        let temp = TempDir::new().unwrap();
+        let store = ForensicBundleStore::new(temp.path().join("diagnostics")).with_max_bundles(2);
+        assert!(store.list().await.unwrap().is_empty());
+
+        let config = ImportConfig::default();
+        for (index, minutes) in [30, 20, 10].into_iter().enumerate() {
+            let mut bundle = ForensicBundle::new(
+                "correlation",
+                Path::new("/downloads/Movie.2020.1080p.mkv"),
+                Path::new("/movies"),
+                "Hardlink failed: permission denied",
+                &config,
+            );
+            bundle.id = format!("bundle-{}", index);
+            bundle.created_at = Utc::now() - chrono::Duration::minutes(minutes);
+            bundle
+                .filesystem
+                .push(FilesystemCheck::probe(&temp.path().join("missing"), "source").await);
+            store.save(&bundle).await.unwrap();
+        }
+
+        // The oldest bundle was pruned, newest is listed first
+        let summaries = store.list().await.unwrap();
+        let listed: Vec<&str> = summaries.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(listed, vec!["bundle-2", "bundle-1"]);
+        assert!(summaries[0].size > 0);
+
+        let bundle: ForensicBundle =
+            serde_json::from_slice(&store.read("bundle-2").await.unwrap()).unwrap();
+        assert_eq!(bundle.error, "Hardlink failed: permission denied");
+        assert!(!bundle.filesystem[0].exists);
+        assert!(bundle.filesystem[0].error.is_some());
+
+        assert!(matches!(
+            store.read("bundle-0").await,
+            Err(RadarrError::NotFound { .. })
+        ));
+        assert!(matches!(
+            store.read("../secrets").await,
+            Err(RadarrError::ValidationError { .. })
+        ));
+    }
+}
@@ -3,14 +3,32 @@
 //! This module provides functionality to create hardlinks for imported files,
 //! preserving the original files for seeding while organizing them in the
 //! media library structure.
-
-use radarr_core::RadarrError;
+//!
+//! After a transfer the imported file's modification time can be set to the
+//! movie's cinema or release date, or kept at the source file's date (see
+//! [`FileDateMode`]). Hardlinks share their inode with the source, so the
+//! source file's date changes too.
+//!
+//! Copies, used when the destination is on another filesystem, are written
+//! to a `.radarr-partial` file next to the destination, synced to disk and
+//! then renamed into place, so a crash mid-copy never leaves a truncated
+//! file under the real name. Hardlinks go through the same partial name, so
+//! a file being replaced stays in place until its replacement is renamed
+//! over it. Partial files left behind by a crash are removed with
+//! [`HardlinkManager::remove_partial_files`].
+
+use chrono::{DateTime, NaiveDate, Utc};
+use radarr_core::{Movie, RadarrError};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 use tokio::fs as async_fs;
 use tracing::{debug, error, info, warn};
 
+/// Suffix of copies and links that are not yet in place
+pub const PARTIAL_FILE_SUFFIX: &str = ".radarr-partial";
+
 /// Configuration for hardlink operations
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HardlinkConfig {
@@ -22,6 +40,9 @@ pub struct HardlinkConfig {
     pub verify_links: bool,
     /// Maximum file size for copy operations (bytes, 0 = no limit)
     pub max_copy_size: u64,
+    /// Modification time given to imported files
+    #[serde(default)]
+    pub file_date: FileDateMode,
 }
 
 impl Default for HardlinkConfig {
@@ -31,10 +52,87 @@ impl Default for HardlinkConfig {
             copy_fallback: true,
             verify_links: true,
             max_copy_size: 50 * 1024 * 1024 * 1024, // 50GB limit for copies
+            file_date: FileDateMode::default(),
+        }
+    }
+}
+
+/// Which date an imported file's modification time is set to
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FileDateMode {
+    /// Leave the modification time alone (a copy gets the import time)
+    #[default]
+    None,
+    /// The movie's cinema release date
+    Cinemas,
+    /// The digital or physical release date, else the cinema date
+    Release,
+    /// The source file's modification time, also for copies
+    Original,
+}
+
+impl std::str::FromStr for FileDateMode {
+    type Err = RadarrError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "none" => Ok(FileDateMode::None),
+            "cinemas" => Ok(FileDateMode::Cinemas),
+            "release" => Ok(FileDateMode::Release),
+            "original" => Ok(FileDateMode::Original),
+            _ => Err(RadarrError::ValidationError {
+                field: "file_date".to_string(),
+                message: format!(
+                    "Unknown file date mode '{}', expected none, cinemas, release or original",
+                    value
+                ),
+            }),
         }
     }
 }
 
+/// Release dates of the movie a file belongs to
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReleaseDates {
+    pub in_cinemas: Option<NaiveDate>,
+    pub digital_release: Option<NaiveDate>,
+    pub physical_release: Option<NaiveDate>,
+}
+
+impl ReleaseDates {
+    /// Read the release dates from a movie's TMDB metadata
+    pub fn from_movie(movie: &Movie) -> Self {
+        let date = |key: &str| {
+            movie
+                .metadata
+                .get("tmdb")
+                .and_then(|tmdb| tmdb.get(key))
+                .and_then(|value| value.as_str())
+                .and_then(|value| NaiveDate::parse_from_str(value, "%Y-%m-%d").ok())
+        };
+
+        Self {
+            in_cinemas: date("release_date"),
+            digital_release: date("digital_release_date"),
+            physical_release: date("physical_release_date"),
+        }
+    }
+
+    /// Date to use for a file date mode, at midnight UTC
+    fn for_mode(&self, mode: FileDateMode) -> Option<DateTime<Utc>> {
+        let date = match mode {
+            FileDateMode::Cinemas => self.in_cinemas,
+            FileDateMode::Release => self
+                .digital_release
+                .or(self.physical_release)
+                .or(self.in_cinemas),
+            FileDateMode::None | FileDateMode::Original => None,
+        }?;
+        Some(date.and_hms_opt(0, 0, 0)?.and_utc())
+    }
+}
+
 /// Result of a hardlink operation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HardlinkResult {
@@ -48,6 +146,9 @@ pub struct HardlinkResult {
     pub file_size: u64,
     /// Time taken for the operation in milliseconds
     pub duration_ms: u64,
+    /// Modification time given to the destination, when one was set
+    #[serde(default)]
+    pub file_date: Option<DateTime<Utc>>,
 }
 
 /// Statistics for a batch of hardlink operations
@@ -88,6 +189,19 @@ impl HardlinkManager {
         &self,
         source: &Path,
         destination: &Path,
+    ) -> Result<HardlinkResult, RadarrError> {
+        self.create_hardlink_with_dates(source, destination, &ReleaseDates::default())
+            .await
+    }
+
+    /// Create a hardlink, then apply the configured file date
+    ///
+    /// `release_dates` are only needed for the cinema and release modes.
+    pub async fn create_hardlink_with_dates(
+        &self,
+        source: &Path,
+        destination: &Path,
+        release_dates: &ReleaseDates,
     ) -> Result<HardlinkResult, RadarrError> {
         let start_time = std::time::Instant::now();
 
@@ -126,16 +240,6 @@ impl HardlinkManager {
             })?;
         }
 
-        // Remove destination if it already exists
-        if destination.exists() {
-            async_fs::remove_file(destination).await.map_err(|e| {
-                RadarrError::ExternalServiceError {
-                    service: "filesystem".to_string(),
-                    error: format!("Failed to remove existing destination: {}", e),
-                }
-            })?;
-        }
-
         let mut is_hardlink = false;
 
         // Try hardlink first if enabled
@@ -169,6 +273,10 @@ impl HardlinkManager {
             self.verify_file(destination, file_size).await?;
         }
 
+        let file_date = self
+            .apply_file_date(destination, &source_metadata, release_dates)
+            .await?;
+
         let duration_ms = start_time.elapsed().as_millis() as u64;
 
         Ok(HardlinkResult {
@@ -177,16 +285,67 @@ impl HardlinkManager {
             is_hardlink,
             file_size,
             duration_ms,
+            file_date,
         })
     }
 
+    /// Set the destination's modification time per the file date mode
+    ///
+    /// Returns the time that was set; `None` when the mode is off or the
+    /// movie has no date for it.
+    async fn apply_file_date(
+        &self,
+        destination: &Path,
+        source_metadata: &fs::Metadata,
+        release_dates: &ReleaseDates,
+    ) -> Result<Option<DateTime<Utc>>, RadarrError> {
+        let file_date = match self.config.file_date {
+            FileDateMode::None => return Ok(None),
+            FileDateMode::Original => source_metadata.modified().ok().map(DateTime::<Utc>::from),
+            mode => release_dates.for_mode(mode),
+        };
+        let Some(file_date) = file_date else {
+            debug!(
+                "No {:?} date known for {}, leaving its modification time",
+                self.config.file_date,
+                destination.display()
+            );
+            return Ok(None);
+        };
+
+        let path = destination.to_path_buf();
+        let modified = SystemTime::from(file_date);
+        tokio::task::spawn_blocking(move || {
+            fs::File::options()
+                .write(true)
+                .open(&path)?
+                .set_modified(modified)
+        })
+        .await?
+        .map_err(|e| RadarrError::ExternalServiceError {
+            service: "filesystem".to_string(),
+            error: format!("Failed to set file date: {}", e),
+        })?;
+
+        debug!(
+            "Set modification time of {} to {}",
+            destination.display(),
+            file_date
+        );
+        Ok(Some(file_date))
+    }
+
     /// Attempt to create a hardlink using the system call
+    ///
+    /// Like a copy, the link is made under the partial name and renamed over
+    /// the destination, so an existing file stays until its replacement is
+    /// in place, also when linking fails and the copy fallback runs.
     async fn try_hardlink(&self, source: &Path, destination: &Path) -> Result<(), std::io::Error> {
-        // Use tokio::task::spawn_blocking for the blocking fs::hard_link call
+        // Use tokio::task::spawn_blocking for the blocking link and rename
         let source = source.to_path_buf();
         let destination = destination.to_path_buf();
 
-        tokio::task::spawn_blocking(move || fs::hard_link(&source, &destination))
+        tokio::task::spawn_blocking(move || link_atomically(&source, &destination))
             .await
             .map_err(|e| {
                 std::io::Error::new(std::io::ErrorKind::Other, format!("Task join error: {}", e))
@@ -194,6 +353,10 @@ impl HardlinkManager {
     }
 
     /// Copy file when hardlink is not possible
+    ///
+    /// The copy is written to a partial file, synced and renamed over the
+    /// destination, so the destination is either absent, the old file or
+    /// the complete copy.
     async fn copy_file(
         &self,
         source: &Path,
@@ -211,16 +374,29 @@ impl HardlinkManager {
             });
         }
 
-        async_fs::copy(source, destination).await.map_err(|e| {
-            RadarrError::ExternalServiceError {
+        let source = source.to_path_buf();
+        let destination = destination.to_path_buf();
+        tokio::task::spawn_blocking(move || copy_atomically(&source, &destination))
+            .await?
+            .map_err(|e| RadarrError::ExternalServiceError {
                 service: "filesystem".to_string(),
                 error: format!("Failed to copy file: {}", e),
-            }
-        })?;
+            })?;
 
         Ok(())
     }
 
+    /// Remove partial copies left below `root` by an interrupted import
+    ///
+    /// Returns how many were removed. Meant to run at startup, before any
+    /// import could be writing one. Subdirectories that can't be read are
+    /// skipped with a warning.
+    pub async fn remove_partial_files(root: &Path) -> Result<usize, RadarrError> {
+        let root = root.to_path_buf();
+        let removed = tokio::task::spawn_blocking(move || remove_partial_files_in(&root)).await??;
+        Ok(removed)
+    }
+
     /// Verify that the destination file was created correctly
     async fn verify_file(&self, destination: &Path, expected_size: u64) -> Result<(), RadarrError> {
         let dest_metadata = async_fs::metadata(destination).await.map_err(|e| {
@@ -345,27 +521,101 @@ impl HardlinkManager {
         destination: &Path,
         required_size: u64,
     ) -> Result<bool, RadarrError> {
-        let dest_dir = destination.parent().unwrap_or(destination);
-
-        // This is a simplified check - real implementation would use
-        // platform-specific APIs to get free space
-        match async_fs::metadata(dest_dir).await {
-            Ok(_) => {
-                // Assume we have enough space for now
-                // Real implementation would call statvfs on Unix or GetDiskFreeSpace on Windows
-                debug!(
-                    "Space check passed for {} bytes at {}",
-                    required_size,
-                    dest_dir.display()
-                );
-                Ok(true)
+        let free = crate::disk_space::free_space(destination).await?;
+        debug!(
+            "{} bytes free at {} for {} bytes",
+            free,
+            destination.display(),
+            required_size
+        );
+        Ok(free >= required_size)
+    }
+}
+
+/// Path of the partial file a copy or link to `destination` is made at
+fn partial_path(destination: &Path) -> PathBuf {
+    let mut name = destination
+        .file_name()
+        .map(|name| name.to_os_string())
+        .unwrap_or_default();
+    name.push(PARTIAL_FILE_SUFFIX);
+    destination.with_file_name(name)
+}
+
+/// Copy through a synced partial file that is renamed into place
+fn copy_atomically(source: &Path, destination: &Path) -> std::io::Result<()> {
+    let partial = partial_path(destination);
+    let copied = fs::copy(source, &partial)
+        .and_then(|_| fs::File::open(&partial)?.sync_all())
+        .and_then(|_| fs::rename(&partial, destination));
+    if let Err(e) = copied {
+        let _ = fs::remove_file(&partial);
+        return Err(e);
+    }
+    sync_parent(destination)
+}
+
+/// Link through a partial file that is renamed into place
+fn link_atomically(source: &Path, destination: &Path) -> std::io::Result<()> {
+    let partial = partial_path(destination);
+    // A partial file left by an interrupted import would make the link fail
+    match fs::remove_file(&partial) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e),
+        _ => {}
+    }
+    fs::hard_link(source, &partial)?;
+    if let Err(e) = fs::rename(&partial, destination) {
+        let _ = fs::remove_file(&partial);
+        return Err(e);
+    }
+    sync_parent(destination)
+}
+
+/// Persist the rename by syncing the directory entry
+#[cfg(unix)]
+fn sync_parent(path: &Path) -> std::io::Result<()> {
+    match path.parent() {
+        Some(parent) => fs::File::open(parent)?.sync_all(),
+        None => Ok(()),
+    }
+}
+
+/// Directories can't be opened for syncing on Windows; NTFS journals renames
+#[cfg(not(unix))]
+fn sync_parent(_path: &Path) -> std::io::Result<()> {
+    Ok(())
+}
+
+fn remove_partial_files_in(dir: &Path) -> std::io::Result<usize> {
+    let mut removed = 0;
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            // One unreadable folder shouldn't leave the rest of the tree uncleaned
+            match remove_partial_files_in(&path) {
+                Ok(count) => removed += count,
+                Err(e) => warn!(
+                    "Skipping {} while removing partial copies: {}",
+                    path.display(),
+                    e
+                ),
             }
-            Err(e) => Err(RadarrError::ExternalServiceError {
-                service: "filesystem".to_string(),
-                error: format!("Failed to check available space: {}", e),
-            }),
+        } else if file_type.is_file()
+            && path
+                .file_name()
+                .is_some_and(|name| name.to_string_lossy().ends_with(PARTIAL_FILE_SUFFIX))
+        {
+            warn!(
+                "Removing partial copy left by an interrupted import: {}",
+                path.display()
+            );
+            fs::remove_file(&path)?;
+            removed += 1;
         }
     }
+    Ok(removed)
 }
 
 #[cfg(test)]
@@ -404,6 +654,7 @@ mod tests {
             copy_fallback: true,
             verify_links: true,
             max_copy_size: 1024 * 1024, // 1MB limit
+            file_date: FileDateMode::None,
         };
         let manager = HardlinkManager::new(config);
 
@@ -421,6 +672,124 @@ mod tests {
 
         assert!(!result.is_hardlink); // Should be a copy
         assert!(destination.exists());
+        assert!(!partial_path(&destination).exists());
+    }
+
+    #[tokio::test]
+    async fn test_copy_replaces_existing_file() {
+        let manager = HardlinkManager::new(HardlinkConfig {
+            enable_hardlinks: false,
+            file_date: FileDateMode::None,
+            ..HardlinkConfig::default()
+        });
+
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("source.mkv");
+        std::fs::write(&source, b"new release").unwrap();
+        let destination = temp_dir.path().join("Movie (2020)/Movie (2020).mkv");
+        std::fs::create_dir_all(destination.parent().unwrap()).unwrap();
+        std::fs::write(&destination, b"old").unwrap();
+
+        manager
+            .create_hardlink(&source, &destination)
+            .await
+            .unwrap();
+
+        assert_eq!(std::fs::read(&destination).unwrap(), b"new release");
+        assert!(!partial_path(&destination).exists());
+    }
+
+    #[tokio::test]
+    async fn test_hardlink_replaces_existing_file() {
+        let manager = HardlinkManager::new(HardlinkConfig {
+            file_date: FileDateMode::None,
+            ..HardlinkConfig::default()
+        });
+
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("source.mkv");
+        std::fs::write(&source, b"new release").unwrap();
+        let destination = temp_dir.path().join("Movie (2020)/Movie (2020).mkv");
+        std::fs::create_dir_all(destination.parent().unwrap()).unwrap();
+        std::fs::write(&destination, b"old").unwrap();
+
+        let result = manager
+            .create_hardlink(&source, &destination)
+            .await
+            .unwrap();
+
+        assert!(result.is_hardlink);
+        assert_eq!(std::fs::read(&destination).unwrap(), b"new release");
+        assert!(!partial_path(&destination).exists());
+    }
+
+    #[tokio::test]
+    async fn test_failed_hardlink_keeps_existing_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("source.mkv");
+        std::fs::write(&source, b"new release").unwrap();
+        let destination = temp_dir.path().join("Movie (2020).mkv");
+        std::fs::write(&destination, b"old").unwrap();
+        // Something the link can't be created over, so linking fails
+        std::fs::create_dir(partial_path(&destination)).unwrap();
+
+        let manager = HardlinkManager::new(HardlinkConfig {
+            copy_fallback: false,
+            file_date: FileDateMode::None,
+            ..HardlinkConfig::default()
+        });
+        assert!(manager
+            .create_hardlink(&source, &destination)
+            .await
+            .is_err());
+        assert_eq!(std::fs::read(&destination).unwrap(), b"old");
+
+        std::fs::remove_dir(partial_path(&destination)).unwrap();
+
+        // A link that fails after the stale partial is gone leaves nothing behind
+        let missing = temp_dir.path().join("missing.mkv");
+        assert!(link_atomically(&missing, &destination).is_err());
+        assert_eq!(std::fs::read(&destination).unwrap(), b"old");
+        assert!(!partial_path(&destination).exists());
+    }
+
+    #[tokio::test]
+    async fn test_remove_partial_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let folder = temp_dir.path().join("Movie (2020)");
+        std::fs::create_dir_all(&folder).unwrap();
+        let movie = folder.join("Movie (2020).mkv");
+        std::fs::write(&movie, b"complete").unwrap();
+        std::fs::write(partial_path(&folder.join("Other (2021).mkv")), b"half").unwrap();
+
+        let removed = HardlinkManager::remove_partial_files(temp_dir.path())
+            .await
+            .unwrap();
+
+        assert_eq!(removed, 1);
+        assert!(movie.exists());
+        assert_eq!(std::fs::read_dir(&folder).unwrap().count(), 1);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_remove_partial_files_skips_unreadable_folders() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let locked = temp_dir.path().join("Locked (2019)");
+        let folder = temp_dir.path().join("Movie (2020)");
+        std::fs::create_dir_all(&locked).unwrap();
+        std::fs::create_dir_all(&folder).unwrap();
+        let partial = partial_path(&folder.join("Movie (2020).mkv"));
+        std::fs::write(&partial, b"half").unwrap();
+        std::fs::set_permissions(&locked, std::fs::Permissions::from_mode(0o000)).unwrap();
+
+        let removed = HardlinkManager::remove_partial_files(temp_dir.path()).await;
+        std::fs::set_permissions(&locked, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        assert_eq!(removed.unwrap(), 1);
+        assert!(!partial.exists());
     }
 
     #[tokio::test]
@@ -430,6 +799,7 @@ mod tests {
             copy_fallback: true,
             verify_links: false,
             max_copy_size: 5, // Very small limit
+            file_date: FileDateMode::None,
         };
         let manager = HardlinkManager::new(config);
 
@@ -473,6 +843,50 @@ mod tests {
         assert!(stats.hardlinks_created + stats.copies_created == 2);
     }
 
+    #[tokio::test]
+    async fn test_file_date_modes() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("source.mkv");
+        std::fs::write(&source, b"movie").unwrap();
+        let source_modified = DateTime::<Utc>::from_timestamp(1_000_000_000, 0).unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&source)
+            .unwrap()
+            .set_modified(SystemTime::from(source_modified))
+            .unwrap();
+
+        let dates = ReleaseDates {
+            in_cinemas: NaiveDate::from_ymd_opt(2021, 10, 22),
+            digital_release: None,
+            physical_release: NaiveDate::from_ymd_opt(2022, 1, 11),
+        };
+        let cases = [
+            (FileDateMode::Cinemas, "2021-10-22T00:00:00Z"),
+            (FileDateMode::Release, "2022-01-11T00:00:00Z"),
+            (FileDateMode::Original, "2001-09-09T01:46:40Z"),
+        ];
+
+        for (mode, expected) in cases {
+            let manager = HardlinkManager::new(HardlinkConfig {
+                enable_hardlinks: false,
+                file_date: mode,
+                ..HardlinkConfig::default()
+            });
+            let destination = temp_dir.path().join(format!("{:?}.mkv", mode));
+
+            let result = manager
+                .create_hardlink_with_dates(&source, &destination, &dates)
+                .await
+                .unwrap();
+
+            let expected: DateTime<Utc> = expected.parse().unwrap();
+            assert_eq!(result.file_date, Some(expected));
+            let modified = std::fs::metadata(&destination).unwrap().modified().unwrap();
+            assert_eq!(DateTime::<Utc>::from(modified), expected);
+        }
+    }
+
     #[tokio::test]
     async fn test_nonexistent_source() {
         let temp_dir = TempDir::new().unwrap();
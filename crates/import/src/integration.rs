@@ -6,18 +6,19 @@
 
 use chrono::Utc;
 use radarr_core::{
-    domain::repositories::{DownloadRepository, MovieRepository},
+    domain::repositories::{DownloadRepository, ImportLockRepository, MovieRepository},
     models::{Download, DownloadStatus, Movie},
     retry::{retry_with_backoff, RetryConfig, RetryPolicy},
     RadarrError, Result,
 };
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use tracing::{error, info, instrument, warn};
 
 use crate::{
     AnalyzedFile, FileScanner, ImportConfig, ImportPipeline, ImportResult, ImportStats,
-    RenameEngine,
+    RenameEngine, ScanStats, SourcePathLocks,
 };
 
 /// Configuration for integrated import operations
@@ -75,6 +76,7 @@ pub struct ImportService<M: MovieRepository, D: DownloadRepository> {
     pipeline: ImportPipeline,
     movie_repo: M,
     download_repo: D,
+    source_locks: SourcePathLocks,
 }
 
 impl<M: MovieRepository, D: DownloadRepository> ImportService<M, D> {
@@ -86,10 +88,21 @@ impl<M: MovieRepository, D: DownloadRepository> ImportService<M, D> {
             pipeline,
             movie_repo,
             download_repo,
+            source_locks: SourcePathLocks::new(),
         }
     }
 
+    /// Also lock source paths in the database so other instances skip them
+    pub fn with_import_locks(mut self, import_locks: Arc<dyn ImportLockRepository>) -> Self {
+        self.source_locks = self.source_locks.with_shared_locks(import_locks);
+        self
+    }
+
     /// Process a completed download
+    ///
+    /// The source path is locked for the whole import, so a download reported
+    /// by several triggers at once is imported exactly once. Callers that lose
+    /// the race get a `TemporaryError`.
     #[instrument(skip(self))]
     pub async fn process_download(
         &self,
@@ -97,14 +110,40 @@ impl<M: MovieRepository, D: DownloadRepository> ImportService<M, D> {
     ) -> Result<IntegratedImportResult> {
         info!("Processing download: {}", download_id);
 
-        // Get download record
-        let mut download = self
-            .download_repo
+        let download = self.find_download(download_id).await?;
+        let source_path = self.get_download_path(&download)?;
+
+        let guard = self
+            .source_locks
+            .try_acquire(&source_path)
+            .await?
+            .ok_or_else(|| RadarrError::TemporaryError {
+                message: format!("{} is already being imported", source_path.display()),
+            })?;
+
+        let result = self.import_download(download_id, source_path).await;
+        guard.release().await;
+        result
+    }
+
+    /// Get a download record by ID
+    async fn find_download(&self, download_id: uuid::Uuid) -> Result<Download> {
+        self.download_repo
             .find_by_id(download_id)
             .await?
             .ok_or_else(|| RadarrError::NotFound {
                 resource: format!("Download {} not found", download_id),
-            })?;
+            })
+    }
+
+    /// Import a download whose source path is locked
+    async fn import_download(
+        &self,
+        download_id: uuid::Uuid,
+        source_path: PathBuf,
+    ) -> Result<IntegratedImportResult> {
+        // Re-read under the lock: a previous holder may have imported it already
+        let mut download = self.find_download(download_id).await?;
 
         // Verify download is complete
         if download.status != DownloadStatus::Completed {
@@ -120,8 +159,7 @@ impl<M: MovieRepository, D: DownloadRepository> ImportService<M, D> {
         // Get associated movie
         let movie = self.movie_repo.find_by_id(download.movie_id).await?;
 
-        // Determine source and destination paths
-        let source_path = self.get_download_path(&download)?;
+        // Determine destination path
         let dest_path = self.get_library_path(&download, &movie)?;
 
         info!(
@@ -201,6 +239,9 @@ impl<M: MovieRepository, D: DownloadRepository> ImportService<M, D> {
         for download in pending_downloads {
             match self.process_download(download.id).await {
                 Ok(result) => results.push(result),
+                Err(RadarrError::TemporaryError { message }) => {
+                    info!("Skipping download {}: {}", download.id, message);
+                }
                 Err(e) => {
                     error!("Failed to process download {}: {}", download.id, e);
                     if !self.config.import_config.continue_on_error {
@@ -359,6 +400,9 @@ impl<M: MovieRepository, D: DownloadRepository> ImportService<M, D> {
             total_duration,
             hardlinks_created,
             files_copied,
+            scan: ScanStats::default(),
+            extras_imported: 0,
+            extras_deleted: 0,
         }
     }
 }
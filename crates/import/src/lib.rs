@@ -11,6 +11,14 @@
 //! - **Hardlink Manager**: Creates hardlinks or copies files while preserving originals
 //! - **Rename Engine**: Generates organized filenames based on configurable templates
 //! - **Import Pipeline**: Orchestrates the complete import workflow
+//! - **Downloads Cleanup**: Removes orphaned leftovers from the downloads folder
+//! - **Transcoder**: Optionally remuxes or transcodes files with ffmpeg before import
+//! - **Extractor**: Optionally unpacks RAR and zip archives in downloads before import
+//! - **Forensics**: Writes a diagnostic bundle for every file that fails to import
+//! - **Library Import**: Adds the movies of an existing, organized library in place
+//! - **Library Rename**: Renames imported files after the naming templates change
+//! - **Movie Move**: Moves a movie's folder to a new root folder or folder name
+//! - **Metrics**: Reports scans, confidence, transfers and import outcomes per root folder
 //!
 //! # Example Usage
 //!
@@ -33,20 +41,53 @@
 //! }
 //! ```
 
+pub mod cleanup;
+pub mod disk_space;
+pub mod extract;
+pub mod extras;
 pub mod file_analyzer;
 pub mod file_scanner;
+pub mod forensics;
 pub mod hardlink_manager;
 pub mod integration;
+pub mod library_import;
+pub mod library_rename;
+pub mod metrics;
+pub mod movie_move;
 pub mod pipeline;
 pub mod rename_engine;
+pub mod source_lock;
+pub mod transcode;
 
 // Re-export main types for convenience
+pub use cleanup::{
+    CleanupCandidate, CleanupConfig, CleanupReport, DownloadReferences, DownloadsCleanupService,
+};
+pub use disk_space::{disk_usage, free_space, refresh_storage_throttle, DiskUsage};
+pub use extract::{ArchiveKind, ExtractConfig, Extraction, Extractor};
+pub use extras::{ExtraAction, ExtraType, ExtrasConfig};
 pub use file_analyzer::{AnalyzedFile, FileAnalyzer, QualityInfo};
-pub use file_scanner::{DetectedFile, FileScanner, MediaType, ScanConfig};
-pub use hardlink_manager::{HardlinkConfig, HardlinkManager, HardlinkResult, HardlinkStats};
+pub use file_scanner::{DetectedFile, FileScanner, MediaType, ScanConfig, ScanStats};
+pub use forensics::{
+    FilesystemCheck, ForensicBundle, ForensicBundleStore, ForensicBundleSummary, RenameComputation,
+};
+pub use hardlink_manager::{
+    FileDateMode, HardlinkConfig, HardlinkManager, HardlinkResult, HardlinkStats, ReleaseDates,
+};
 pub use integration::{ImportService, IntegratedImportConfig, IntegratedImportResult};
+pub use library_import::{
+    LibraryImportMatch, LibraryImportOptions, LibraryImportReport, LibraryImportService,
+    MovieLookup, UnmatchedFolder,
+};
+pub use library_rename::{LibraryRenameService, RenamePreview};
+pub use metrics::{ImportFailureReason, ImportMetrics};
+pub use movie_move::{MoveMovieJob, MoveMovieReport, MoveMovieRequest, MovieFolderRenameHandler};
 pub use pipeline::{ImportConfig, ImportPipeline, ImportResult, ImportStats};
-pub use rename_engine::{RenameConfig, RenameEngine, RenameResult};
+pub use rename_engine::{
+    ColonReplacement, MovieNaming, NamingExamples, RenameConfig, RenameEngine, RenameResult,
+};
+pub use source_lock::{SourcePathGuard, SourcePathLocks};
+pub use transcode::{TranscodeConfig, TranscodeOutput, TranscodeProfile, Transcoder};
 
 // Re-export core error types
 pub use radarr_core::{RadarrError, Result};
@@ -0,0 +1,647 @@
+//! Import of an existing, already organized movie library
+//!
+//! Used on first setup: every folder under a library root is taken to hold
+//! one movie. The folder name, or failing that the movie file's name, is
+//! parsed for a title and year and matched on TMDB, and movie and movie file
+//! records are created for the file where it is. Nothing on disk is moved or
+//! renamed, so the library root must lie below the media root that movie
+//! file paths are stored relative to. Folders that can't be matched are
+//! reported so they can be imported by hand.
+
+use async_trait::async_trait;
+use once_cell::sync::Lazy;
+use radarr_core::{
+    domain::repositories::{MovieFileRepository, MovieRepository},
+    events::{EventBus, SystemEvent},
+    models::{media_relative_path, Movie, MovieFile},
+    progress::OperationType,
+    RadarrError, Result,
+};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::fs;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::{
+    file_analyzer::FileAnalyzer,
+    file_scanner::{DetectedFile, FileScanner, ScanConfig},
+};
+
+/// `Title (Year)`, as Radarr names movie folders
+static FOLDER_TITLE_YEAR_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(?P<title>.+?)\s*\((?P<year>(?:19|20)\d{2})\)").unwrap());
+
+/// `{tmdb-603}` or `[tmdbid-603]` tags some naming schemes add to folders
+static FOLDER_TMDB_ID_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)[\[{]tmdb(?:id)?[-=](?P<id>\d+)[\]}]").unwrap());
+
+/// TMDB search used to match library folders to movies
+#[async_trait]
+pub trait MovieLookup: Send + Sync {
+    /// Movies matching `title`, best matches first
+    async fn search(&self, title: &str, year: Option<i32>) -> Result<Vec<Movie>>;
+
+    /// Full details of the movie with `tmdb_id`
+    async fn get(&self, tmdb_id: i32) -> Result<Movie>;
+}
+
+/// Options of one library import run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LibraryImportOptions {
+    /// Monitor the movies that are added
+    pub monitored: bool,
+    /// Quality profile given to the movies that are added
+    pub quality_profile_id: Option<i32>,
+    /// Only report what would be imported
+    pub dry_run: bool,
+}
+
+impl Default for LibraryImportOptions {
+    fn default() -> Self {
+        Self {
+            monitored: true,
+            quality_profile_id: None,
+            dry_run: false,
+        }
+    }
+}
+
+/// Title, year and TMDB ID parsed from a movie folder name
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedFolder {
+    pub title: String,
+    pub year: Option<i32>,
+    pub tmdb_id: Option<i32>,
+}
+
+/// A library folder matched to a movie
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LibraryImportMatch {
+    pub folder: PathBuf,
+    pub file: PathBuf,
+    /// ID of the movie the file was attached to; unset in a dry run
+    pub movie_id: Option<Uuid>,
+    pub tmdb_id: i32,
+    pub title: String,
+    pub year: Option<i32>,
+    /// Whether the movie was already in the library without a file
+    pub existing_movie: bool,
+}
+
+/// A library folder that could not be imported
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnmatchedFolder {
+    pub folder: PathBuf,
+    pub reason: String,
+}
+
+/// Result of a library import run
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LibraryImportReport {
+    pub dry_run: bool,
+    pub folders_scanned: usize,
+    pub imported: Vec<LibraryImportMatch>,
+    /// Folders of movies that already have a file
+    pub already_imported: Vec<PathBuf>,
+    pub unmatched: Vec<UnmatchedFolder>,
+}
+
+/// Service creating movie and movie file records for an existing library
+pub struct LibraryImportService {
+    movie_repository: Arc<dyn MovieRepository>,
+    movie_file_repository: Arc<dyn MovieFileRepository>,
+    lookup: Arc<dyn MovieLookup>,
+    file_scanner: FileScanner,
+    file_analyzer: FileAnalyzer,
+    /// Folder movie file paths are stored relative to
+    media_root: PathBuf,
+    event_bus: Option<Arc<EventBus>>,
+}
+
+impl LibraryImportService {
+    /// Create a library import service for files stored below `media_root`
+    pub fn new(
+        movie_repository: Arc<dyn MovieRepository>,
+        movie_file_repository: Arc<dyn MovieFileRepository>,
+        lookup: Arc<dyn MovieLookup>,
+        media_root: impl Into<PathBuf>,
+    ) -> Self {
+        Self {
+            movie_repository,
+            movie_file_repository,
+            lookup,
+            file_scanner: FileScanner::new(ScanConfig::default()),
+            file_analyzer: FileAnalyzer::new(),
+            media_root: media_root.into(),
+            event_bus: None,
+        }
+    }
+
+    /// Find movie files in each folder with `config`
+    pub fn with_scan_config(mut self, config: ScanConfig) -> Self {
+        self.file_scanner = FileScanner::new(config);
+        self
+    }
+
+    /// Publish progress and movie added events
+    pub fn with_event_bus(mut self, event_bus: Arc<EventBus>) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
+    /// Import every movie folder directly under `root`
+    pub async fn import_library(
+        &self,
+        root: &Path,
+        options: &LibraryImportOptions,
+    ) -> Result<LibraryImportReport> {
+        if !root.starts_with(&self.media_root) {
+            return Err(RadarrError::ValidationError {
+                field: "path".to_string(),
+                message: format!(
+                    "Library folder {} is outside the media root {}",
+                    root.display(),
+                    self.media_root.display()
+                ),
+            });
+        }
+        let folders = list_folders(root).await?;
+        info!(
+            "Importing existing library at {} ({} folders)",
+            root.display(),
+            folders.len()
+        );
+
+        let operation_id = Uuid::new_v4();
+        let mut report = LibraryImportReport {
+            dry_run: options.dry_run,
+            ..LibraryImportReport::default()
+        };
+        for (index, folder) in folders.iter().enumerate() {
+            report.folders_scanned += 1;
+            match self.import_folder(root, folder, options).await {
+                Ok(FolderOutcome::Imported(matched)) => report.imported.push(matched),
+                Ok(FolderOutcome::AlreadyImported) => report.already_imported.push(folder.clone()),
+                Ok(FolderOutcome::Unmatched(reason)) => {
+                    debug!("Library folder {} unmatched: {}", folder.display(), reason);
+                    report.unmatched.push(UnmatchedFolder {
+                        folder: folder.clone(),
+                        reason,
+                    });
+                }
+                Err(e) => {
+                    warn!(
+                        "Failed to import library folder {}: {}",
+                        folder.display(),
+                        e
+                    );
+                    report.unmatched.push(UnmatchedFolder {
+                        folder: folder.clone(),
+                        reason: e.to_string(),
+                    });
+                }
+            }
+
+            if let Some(bus) = &self.event_bus {
+                let _ = bus
+                    .publish(SystemEvent::ProgressUpdate {
+                        operation_id,
+                        operation_type: OperationType::LibraryScan,
+                        percentage: (index + 1) as f32 * 100.0 / folders.len() as f32,
+                        message: format!("Imported {} of {} folders", index + 1, folders.len()),
+                        eta_seconds: None,
+                    })
+                    .await;
+            }
+        }
+
+        info!(
+            "Library import of {} complete: {} imported, {} already imported, {} unmatched",
+            root.display(),
+            report.imported.len(),
+            report.already_imported.len(),
+            report.unmatched.len()
+        );
+        Ok(report)
+    }
+
+    /// Match one folder and record its movie file
+    async fn import_folder(
+        &self,
+        root: &Path,
+        folder: &Path,
+        options: &LibraryImportOptions,
+    ) -> Result<FolderOutcome> {
+        let files = self.file_scanner.scan_directory(folder).await?;
+        let Some(file) = main_movie_file(&files) else {
+            return Ok(FolderOutcome::Unmatched("No movie file found".to_string()));
+        };
+
+        let folder_name = folder
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let analyzed = self.file_analyzer.analyze_file(&file.path).ok();
+        let parsed = parse_folder_name(&folder_name).or_else(|| {
+            let analyzed = analyzed.as_ref()?;
+            Some(ParsedFolder {
+                title: analyzed.title.clone()?,
+                year: analyzed.year.map(i32::from),
+                tmdb_id: None,
+            })
+        });
+        let Some(parsed) = parsed else {
+            return Ok(FolderOutcome::Unmatched(
+                "No title found in the folder or file name".to_string(),
+            ));
+        };
+
+        let tmdb_id = match parsed.tmdb_id {
+            Some(tmdb_id) => tmdb_id,
+            None => {
+                let candidates = self.lookup.search(&parsed.title, parsed.year).await?;
+                match choose_match(&candidates, &parsed) {
+                    Some(candidate) => candidate.tmdb_id,
+                    None => {
+                        return Ok(FolderOutcome::Unmatched(format!(
+                            "No TMDB match for '{}'{}",
+                            parsed.title,
+                            parsed.year.map(|y| format!(" ({})", y)).unwrap_or_default()
+                        )))
+                    }
+                }
+            }
+        };
+
+        let existing = self.movie_repository.find_by_tmdb_id(tmdb_id).await?;
+        if existing.as_ref().is_some_and(|movie| movie.has_file) {
+            return Ok(FolderOutcome::AlreadyImported);
+        }
+        let existing_movie = existing.is_some();
+        let movie = match existing {
+            Some(movie) => movie,
+            None => {
+                let mut movie = self.lookup.get(tmdb_id).await?;
+                movie.monitored = options.monitored;
+                movie.quality_profile_id = options.quality_profile_id;
+                if let Some(metadata) = movie.metadata.as_object_mut() {
+                    metadata.insert(
+                        "root_folder_path".to_string(),
+                        serde_json::json!(root.to_string_lossy()),
+                    );
+                }
+                movie
+            }
+        };
+
+        let mut matched = LibraryImportMatch {
+            folder: folder.to_path_buf(),
+            file: file.path.clone(),
+            movie_id: None,
+            tmdb_id,
+            title: movie.title.clone(),
+            year: movie.year,
+            existing_movie,
+        };
+        if options.dry_run {
+            return Ok(FolderOutcome::Imported(matched));
+        }
+
+        let relative_path = media_relative_path(&self.media_root, &file.path).ok_or_else(|| {
+            RadarrError::ValidationError {
+                field: "path".to_string(),
+                message: format!("{} is outside the media root", file.path.display()),
+            }
+        })?;
+        let mut movie = if existing_movie {
+            movie
+        } else {
+            let created = self.movie_repository.create(&movie).await?;
+            self.publish_movie_added(&created).await;
+            created
+        };
+
+        let quality = analyzed
+            .as_ref()
+            .map(|analyzed| serde_json::to_value(&analyzed.quality))
+            .transpose()?
+            .unwrap_or_else(|| serde_json::json!({}));
+        let mut movie_file = MovieFile::new(movie.id, relative_path, file.size as i64, quality);
+        movie_file.edition = analyzed.and_then(|analyzed| analyzed.edition.clone());
+        movie_file.last_write_time = Some(file.modified.into());
+        let movie_file = self.movie_file_repository.create(&movie_file).await?;
+
+        movie.set_has_file(movie_file.id);
+        self.movie_repository.update(&movie).await?;
+
+        matched.movie_id = Some(movie.id);
+        Ok(FolderOutcome::Imported(matched))
+    }
+
+    async fn publish_movie_added(&self, movie: &Movie) {
+        let Some(bus) = &self.event_bus else {
+            return;
+        };
+        let event = SystemEvent::MovieAdded {
+            movie_id: movie.id,
+            tmdb_id: movie.tmdb_id,
+            title: movie.title.clone(),
+        };
+        if let Err(e) = bus.publish(event).await {
+            warn!("Failed to publish movie added event: {}", e);
+        }
+    }
+}
+
+/// What happened to one library folder
+enum FolderOutcome {
+    Imported(LibraryImportMatch),
+    AlreadyImported,
+    Unmatched(String),
+}
+
+/// Movie folders directly under `root`, sorted by name; hidden folders are skipped
+async fn list_folders(root: &Path) -> Result<Vec<PathBuf>> {
+    if !root.is_dir() {
+        return Err(RadarrError::ValidationError {
+            field: "path".to_string(),
+            message: format!("Library folder does not exist: {}", root.display()),
+        });
+    }
+
+    let mut folders = Vec::new();
+    let mut entries = fs::read_dir(root).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let hidden = entry.file_name().to_string_lossy().starts_with('.');
+        if !hidden && entry.path().is_dir() {
+            folders.push(entry.path());
+        }
+    }
+    folders.sort();
+    Ok(folders)
+}
+
+/// The largest file that isn't a sample, taken to be the movie itself
+fn main_movie_file(files: &[DetectedFile]) -> Option<&DetectedFile> {
+    files
+        .iter()
+        .filter(|file| !file.is_sample)
+        .max_by_key(|file| file.size)
+}
+
+/// Parse a `Title (Year)` folder name, with an optional TMDB ID tag
+pub fn parse_folder_name(name: &str) -> Option<ParsedFolder> {
+    let tmdb_id = FOLDER_TMDB_ID_REGEX
+        .captures(name)
+        .and_then(|captures| captures["id"].parse().ok());
+    let captures = FOLDER_TITLE_YEAR_REGEX.captures(name)?;
+    let title = captures["title"].trim().to_string();
+    if title.is_empty() {
+        return None;
+    }
+
+    Some(ParsedFolder {
+        title,
+        year: captures["year"].parse().ok(),
+        tmdb_id,
+    })
+}
+
+/// Search result whose title matches the folder's, within a year of it
+///
+/// Release years differ by one between countries often enough that an exact
+/// year would reject correct matches.
+pub fn choose_match<'a>(candidates: &'a [Movie], parsed: &ParsedFolder) -> Option<&'a Movie> {
+    let title = normalize_title(&parsed.title);
+    candidates.iter().find(|candidate| {
+        let year_matches = match (parsed.year, candidate.year) {
+            (Some(wanted), Some(year)) => (wanted - year).abs() <= 1,
+            _ => true,
+        };
+        let title_matches = normalize_title(&candidate.title) == title
+            || candidate
+                .original_title
+                .as_deref()
+                .is_some_and(|original| normalize_title(original) == title);
+        year_matches && title_matches
+    })
+}
+
+/// Lowercase letters and digits only, so punctuation differences don't matter
+fn normalize_title(title: &str) -> String {
+    title
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, Utc};
+    use radarr_core::models::resolve_media_path;
+    use std::sync::Mutex;
+    use tempfile::TempDir;
+
+    /// Movies and movie files kept in memory; TMDB knows every movie
+    #[derive(Default)]
+    struct Library {
+        movies: Mutex<Vec<Movie>>,
+        files: Mutex<Vec<MovieFile>>,
+    }
+
+    #[async_trait]
+    impl MovieLookup for Library {
+        async fn search(&self, _title: &str, _year: Option<i32>) -> Result<Vec<Movie>> {
+            Ok(vec![])
+        }
+        async fn get(&self, tmdb_id: i32) -> Result<Movie> {
+            Ok(Movie::new(tmdb_id, "Heat".to_string()))
+        }
+    }
+
+    #[async_trait]
+    impl MovieRepository for Library {
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<Movie>> {
+            let movies = self.movies.lock().unwrap();
+            Ok(movies.iter().find(|movie| movie.id == id).cloned())
+        }
+        async fn find_by_tmdb_id(&self, tmdb_id: i32) -> Result<Option<Movie>> {
+            let movies = self.movies.lock().unwrap();
+            Ok(movies
+                .iter()
+                .find(|movie| movie.tmdb_id == tmdb_id)
+                .cloned())
+        }
+        async fn find_by_imdb_id(&self, _imdb_id: &str) -> Result<Option<Movie>> {
+            Ok(None)
+        }
+        async fn find_monitored(&self) -> Result<Vec<Movie>> {
+            Ok(vec![])
+        }
+        async fn find_missing_files(&self) -> Result<Vec<Movie>> {
+            Ok(vec![])
+        }
+        async fn find_added_since(&self, _since: DateTime<Utc>) -> Result<Vec<Movie>> {
+            Ok(vec![])
+        }
+        async fn search_by_title(&self, _query: &str, _limit: i32) -> Result<Vec<Movie>> {
+            Ok(vec![])
+        }
+        async fn create(&self, movie: &Movie) -> Result<Movie> {
+            self.movies.lock().unwrap().push(movie.clone());
+            Ok(movie.clone())
+        }
+        async fn update(&self, movie: &Movie) -> Result<Movie> {
+            let mut movies = self.movies.lock().unwrap();
+            if let Some(stored) = movies.iter_mut().find(|stored| stored.id == movie.id) {
+                *stored = movie.clone();
+            }
+            Ok(movie.clone())
+        }
+        async fn delete(&self, _id: Uuid) -> Result<()> {
+            Ok(())
+        }
+        async fn list(&self, _offset: i64, _limit: i32) -> Result<Vec<Movie>> {
+            Ok(self.movies.lock().unwrap().clone())
+        }
+        async fn count(&self) -> Result<i64> {
+            Ok(self.movies.lock().unwrap().len() as i64)
+        }
+        async fn update_last_search_time(&self, _id: Uuid) -> Result<()> {
+            Ok(())
+        }
+        async fn record_search_result(&self, _id: Uuid, _found: bool) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl MovieFileRepository for Library {
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<MovieFile>> {
+            let files = self.files.lock().unwrap();
+            Ok(files.iter().find(|file| file.id == id).cloned())
+        }
+        async fn find_by_movie_id(&self, movie_id: Uuid) -> Result<Option<MovieFile>> {
+            let files = self.files.lock().unwrap();
+            Ok(files.iter().find(|file| file.movie_id == movie_id).cloned())
+        }
+        async fn list(&self, _movie_id: Option<Uuid>) -> Result<Vec<MovieFile>> {
+            Ok(self.files.lock().unwrap().clone())
+        }
+        async fn create(&self, file: &MovieFile) -> Result<MovieFile> {
+            self.files.lock().unwrap().push(file.clone());
+            Ok(file.clone())
+        }
+        async fn update(&self, file: &MovieFile) -> Result<MovieFile> {
+            Ok(file.clone())
+        }
+        async fn delete(&self, _id: Uuid) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    fn service(library: &Arc<Library>, media_root: &Path) -> LibraryImportService {
+        LibraryImportService::new(
+            library.clone(),
+            library.clone(),
+            library.clone(),
+            media_root,
+        )
+        .with_scan_config(ScanConfig {
+            min_file_size: 0,
+            ..ScanConfig::default()
+        })
+    }
+
+    fn movie(tmdb_id: i32, title: &str, year: i32) -> Movie {
+        let mut movie = Movie::new(tmdb_id, title.to_string());
+        movie.year = Some(year);
+        movie
+    }
+
+    #[test]
+    fn test_parse_folder_name() {
+        assert_eq!(
+            parse_folder_name("The Matrix (1999)"),
+            Some(ParsedFolder {
+                title: "The Matrix".to_string(),
+                year: Some(1999),
+                tmdb_id: None,
+            })
+        );
+        assert_eq!(
+            parse_folder_name("Blade Runner 2049 (2017) {tmdb-335984}"),
+            Some(ParsedFolder {
+                title: "Blade Runner 2049".to_string(),
+                year: Some(2017),
+                tmdb_id: Some(335984),
+            })
+        );
+        assert_eq!(parse_folder_name("Random Folder"), None);
+    }
+
+    #[test]
+    fn test_choose_match_requires_title_and_close_year() {
+        let candidates = vec![
+            movie(1, "The Thing", 2011),
+            movie(2, "The Thing", 1982),
+            movie(3, "Things", 1982),
+        ];
+        let parsed = ParsedFolder {
+            title: "The Thing".to_string(),
+            year: Some(1982),
+            tmdb_id: None,
+        };
+        assert_eq!(choose_match(&candidates, &parsed).unwrap().tmdb_id, 2);
+
+        let parsed = ParsedFolder {
+            title: "Thing, The".to_string(),
+            year: Some(1982),
+            tmdb_id: None,
+        };
+        assert!(choose_match(&candidates, &parsed).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_import_stores_paths_relative_to_media_root() {
+        let media_root = TempDir::new().unwrap();
+        let root = media_root.path().join("library/hd");
+        let folder = root.join("Heat (1995) {tmdb-949}");
+        std::fs::create_dir_all(&folder).unwrap();
+        std::fs::write(folder.join("Heat (1995).mkv"), b"movie").unwrap();
+        let library = Arc::new(Library::default());
+
+        let report = service(&library, media_root.path())
+            .import_library(&root, &LibraryImportOptions::default())
+            .await
+            .unwrap();
+
+        assert_eq!(report.imported.len(), 1);
+        let files = library.files.lock().unwrap().clone();
+        assert_eq!(
+            files[0].relative_path,
+            "library/hd/Heat (1995) {tmdb-949}/Heat (1995).mkv"
+        );
+        let path = resolve_media_path(media_root.path(), &files[0].relative_path).unwrap();
+        assert_eq!(std::fs::read(path).unwrap(), b"movie");
+    }
+
+    #[tokio::test]
+    async fn test_import_refuses_root_outside_media_root() {
+        let media_root = TempDir::new().unwrap();
+        let elsewhere = TempDir::new().unwrap();
+        let library = Arc::new(Library::default());
+
+        let result = service(&library, media_root.path())
+            .import_library(elsewhere.path(), &LibraryImportOptions::default())
+            .await;
+
+        assert!(result.is_err());
+        assert!(library.movies.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,390 @@
+//! Renaming of already imported movie files
+//!
+//! When the naming templates change, files imported under the old templates
+//! keep their old names. This module works out the name each movie file
+//! would get under the current templates and renames the files whose name
+//! differs. Renames are all or nothing: if one file can't be moved or its
+//! record can't be updated, the files already moved are put back and their
+//! records restored.
+
+use radarr_core::{
+    domain::repositories::{MovieFileRepository, MovieRepository},
+    models::{resolve_media_path, Movie, MovieFile},
+    RadarrError, Result,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{hash_map::Entry, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::fs;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::{
+    file_analyzer::{FileAnalyzer, QualityInfo},
+    rename_engine::{MovieNaming, RenameConfig, RenameEngine},
+};
+
+/// A movie file whose name differs from what the templates give
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenamePreview {
+    pub movie_id: Uuid,
+    pub movie_file_id: Uuid,
+    /// Current path below the media root
+    pub existing_path: String,
+    /// Path below the media root the file is renamed to
+    pub new_path: String,
+}
+
+/// A file move that was carried out, kept to undo it
+#[derive(Debug)]
+struct CompletedMove {
+    from: PathBuf,
+    to: PathBuf,
+}
+
+/// Service renaming imported movie files to the current naming templates
+pub struct LibraryRenameService {
+    movie_repository: Arc<dyn MovieRepository>,
+    movie_file_repository: Arc<dyn MovieFileRepository>,
+    rename_engine: RenameEngine,
+    file_analyzer: FileAnalyzer,
+    media_root: PathBuf,
+}
+
+impl LibraryRenameService {
+    /// Create a rename service for files stored below `media_root`
+    pub fn new(
+        movie_repository: Arc<dyn MovieRepository>,
+        movie_file_repository: Arc<dyn MovieFileRepository>,
+        rename_config: RenameConfig,
+        media_root: impl Into<PathBuf>,
+    ) -> Self {
+        Self {
+            movie_repository,
+            movie_file_repository,
+            rename_engine: RenameEngine::new(rename_config),
+            file_analyzer: FileAnalyzer::new(),
+            media_root: media_root.into(),
+        }
+    }
+
+    /// Files that would be renamed, optionally only those of `movie_ids`
+    pub async fn preview(&self, movie_ids: Option<&[Uuid]>) -> Result<Vec<RenamePreview>> {
+        let files = self.current_files(movie_ids).await?;
+        Ok(self.plan_all(&files))
+    }
+
+    /// Rename the files of `movie_ids`, or of every movie, to the templates
+    ///
+    /// Either every file is renamed or, after a failure, none is: files that
+    /// were already moved are moved back and their records restored.
+    pub async fn rename(&self, movie_ids: Option<&[Uuid]>) -> Result<Vec<RenamePreview>> {
+        let files = self.current_files(movie_ids).await?;
+        let previews = self.plan_all(&files);
+        self.check_conflicts(&previews).await?;
+
+        let originals: HashMap<Uuid, &MovieFile> = files
+            .iter()
+            .map(|(_, movie_file)| (movie_file.id, movie_file))
+            .collect();
+        let mut moves = Vec::with_capacity(previews.len());
+        let mut updated: Vec<MovieFile> = Vec::with_capacity(previews.len());
+        for preview in &previews {
+            let original = originals[&preview.movie_file_id];
+            if let Err(e) = self.rename_one(preview, original, &mut moves).await {
+                warn!(
+                    "Renaming {} failed, rolling back {} renamed files: {}",
+                    preview.existing_path,
+                    updated.len(),
+                    e
+                );
+                self.restore_records(&updated).await;
+                undo_moves(&moves).await;
+                return Err(e);
+            }
+            updated.push(original.clone());
+        }
+
+        for completed in &moves {
+            remove_empty_parent(&completed.from, &self.media_root).await;
+        }
+        info!("Renamed {} movie files", previews.len());
+        Ok(previews)
+    }
+
+    /// New paths of the files that are not named after the templates
+    fn plan_all(&self, files: &[(Movie, MovieFile)]) -> Vec<RenamePreview> {
+        let mut previews = Vec::new();
+        for (movie, movie_file) in files {
+            match self.plan(movie, movie_file) {
+                Ok(Some(preview)) => previews.push(preview),
+                Ok(None) => {}
+                Err(e) => warn!(
+                    "Cannot work out new name for movie file {} ({}): {}",
+                    movie_file.id, movie_file.relative_path, e
+                ),
+            }
+        }
+        previews
+    }
+
+    /// Move one file and point its record at the new path
+    async fn rename_one(
+        &self,
+        preview: &RenamePreview,
+        original: &MovieFile,
+        moves: &mut Vec<CompletedMove>,
+    ) -> Result<()> {
+        let from = self.resolve(&preview.existing_path)?;
+        let to = self.resolve(&preview.new_path)?;
+        move_file(&from, &to).await?;
+        moves.push(CompletedMove { from, to });
+
+        let mut movie_file = original.clone();
+        movie_file.relative_path = preview.new_path.clone();
+        self.movie_file_repository.update(&movie_file).await?;
+        debug!(
+            "Renamed movie file {}: {} -> {}",
+            movie_file.id, preview.existing_path, preview.new_path
+        );
+        Ok(())
+    }
+
+    /// Put back the records of files renamed before a failure
+    async fn restore_records(&self, originals: &[MovieFile]) {
+        for original in originals.iter().rev() {
+            if let Err(e) = self.movie_file_repository.update(original).await {
+                warn!(
+                    "Failed to restore path of movie file {} to {}: {}",
+                    original.id, original.relative_path, e
+                );
+            }
+        }
+    }
+
+    /// Refuse renames that would overwrite another file
+    async fn check_conflicts(&self, previews: &[RenamePreview]) -> Result<()> {
+        let mut targets = HashSet::new();
+        for preview in previews {
+            if !targets.insert(preview.new_path.as_str()) {
+                return Err(RadarrError::ValidationError {
+                    field: "newPath".to_string(),
+                    message: format!(
+                        "Several movie files would be renamed to {}",
+                        preview.new_path
+                    ),
+                });
+            }
+            // A rename that only changes case finds the file itself on
+            // case-insensitive filesystems
+            let same_file = preview
+                .new_path
+                .eq_ignore_ascii_case(&preview.existing_path);
+            if !same_file && fs::try_exists(self.resolve(&preview.new_path)?).await? {
+                return Err(RadarrError::ValidationError {
+                    field: "newPath".to_string(),
+                    message: format!(
+                        "Renaming {} would overwrite {}",
+                        preview.existing_path, preview.new_path
+                    ),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Movies with their current file of each edition, optionally only of
+    /// `movie_ids`
+    ///
+    /// Older files of an edition that were replaced keep their names.
+    async fn current_files(&self, movie_ids: Option<&[Uuid]>) -> Result<Vec<(Movie, MovieFile)>> {
+        let movie_files = match movie_ids {
+            Some(ids) => {
+                let mut files = Vec::new();
+                for id in ids {
+                    files.extend(self.movie_file_repository.list(Some(*id)).await?);
+                }
+                files
+            }
+            None => self.movie_file_repository.list(None).await?,
+        };
+        let movie_files = MovieFile::current_per_edition(movie_files);
+
+        let mut movies: HashMap<Uuid, Option<Movie>> = HashMap::new();
+        let mut current = Vec::new();
+        for movie_file in movie_files {
+            let movie = match movies.entry(movie_file.movie_id) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    let movie = self
+                        .movie_repository
+                        .find_by_id(movie_file.movie_id)
+                        .await?;
+                    entry.insert(movie)
+                }
+            };
+            let Some(movie) = movie else {
+                continue;
+            };
+            current.push((movie.clone(), movie_file));
+        }
+        Ok(current)
+    }
+
+    /// New path of a movie file, or `None` when it is already named right
+    fn plan(&self, movie: &Movie, movie_file: &MovieFile) -> Result<Option<RenamePreview>> {
+        let path = self.resolve(&movie_file.relative_path)?;
+        let mut analyzed = self.file_analyzer.analyze_file(&path)?;
+        // The quality recorded at import wins over what the name now says
+        if let Ok(quality) = serde_json::from_value::<QualityInfo>(movie_file.quality.clone()) {
+            analyzed.quality = quality;
+        }
+
+        let naming = MovieNaming {
+            edition: movie_file.edition.clone(),
+            ..MovieNaming::from_movie(movie)
+        };
+        let planned = self.rename_engine.generate_movie_filename(
+            &analyzed,
+            Some(&naming),
+            &self.media_root,
+        )?;
+        let new_path = planned
+            .new_path
+            .strip_prefix(&self.media_root)
+            .unwrap_or(&planned.new_path)
+            .to_string_lossy()
+            .to_string();
+
+        if new_path == movie_file.relative_path {
+            return Ok(None);
+        }
+        Ok(Some(RenamePreview {
+            movie_id: movie.id,
+            movie_file_id: movie_file.id,
+            existing_path: movie_file.relative_path.clone(),
+            new_path,
+        }))
+    }
+
+    /// Absolute path of a path below the media root
+    fn resolve(&self, relative_path: &str) -> Result<PathBuf> {
+        resolve_media_path(&self.media_root, relative_path).ok_or_else(|| {
+            RadarrError::ValidationError {
+                field: "relativePath".to_string(),
+                message: format!("{} is outside the media root", relative_path),
+            }
+        })
+    }
+}
+
+/// Rename `from` to `to`, creating the destination folder
+///
+/// Both paths are below the media root, so the rename stays on one
+/// filesystem and is atomic.
+async fn move_file(from: &Path, to: &Path) -> Result<()> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)
+            .await
+            .map_err(|e| RadarrError::ExternalServiceError {
+                service: "filesystem".to_string(),
+                error: format!("Failed to create {}: {}", parent.display(), e),
+            })?;
+    }
+    fs::rename(from, to)
+        .await
+        .map_err(|e| RadarrError::ExternalServiceError {
+            service: "filesystem".to_string(),
+            error: format!(
+                "Failed to rename {} to {}: {}",
+                from.display(),
+                to.display(),
+                e
+            ),
+        })
+}
+
+/// Move renamed files back, newest first
+async fn undo_moves(moves: &[CompletedMove]) {
+    for completed in moves.iter().rev() {
+        if let Err(e) = move_file(&completed.to, &completed.from).await {
+            warn!(
+                "Failed to move {} back to {}: {}",
+                completed.to.display(),
+                completed.from.display(),
+                e
+            );
+            continue;
+        }
+        if let Some(parent) = completed.to.parent() {
+            // Only succeeds for folders the rename created and left empty
+            let _ = fs::remove_dir(parent).await;
+        }
+    }
+}
+
+/// Remove the folder a file was renamed out of once it is empty
+async fn remove_empty_parent(path: &Path, media_root: &Path) {
+    let Some(parent) = path.parent() else {
+        return;
+    };
+    if parent == media_root {
+        return;
+    }
+    if fs::remove_dir(parent).await.is_ok() {
+        debug!("Removed empty folder {}", parent.display());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_undo_moves_restores_files_and_removes_created_folders() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let first = root.join("Old One/movie.mkv");
+        let second = root.join("Old Two/movie.mkv");
+        for path in [&first, &second] {
+            fs::create_dir_all(path.parent().unwrap()).await.unwrap();
+            fs::write(path, b"data").await.unwrap();
+        }
+
+        let first_target = root.join("New One (2020)/New One (2020).mkv");
+        let second_target = root.join("New Two (2021)/New Two (2021).mkv");
+        move_file(&first, &first_target).await.unwrap();
+        move_file(&second, &second_target).await.unwrap();
+        let moves = vec![
+            CompletedMove {
+                from: first.clone(),
+                to: first_target.clone(),
+            },
+            CompletedMove {
+                from: second.clone(),
+                to: second_target.clone(),
+            },
+        ];
+
+        undo_moves(&moves).await;
+
+        assert!(first.exists());
+        assert!(second.exists());
+        assert!(!first_target.parent().unwrap().exists());
+        assert!(!second_target.parent().unwrap().exists());
+    }
+
+    #[tokio::test]
+    async fn test_move_file_fails_without_source() {
+        let temp_dir = TempDir::new().unwrap();
+        let result = move_file(
+            &temp_dir.path().join("missing.mkv"),
+            &temp_dir.path().join("Movie (2020)/Movie (2020).mkv"),
+        )
+        .await;
+        assert!(result.is_err());
+    }
+}
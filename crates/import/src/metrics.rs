@@ -0,0 +1,65 @@
+//! Import pipeline measurements
+//!
+//! The pipeline reports what it scanned, how confidently it recognized files,
+//! whether they were linked or copied and how long each import took to an
+//! [`ImportMetrics`] implementation, e.g. one exporting them to Prometheus.
+//! Everything is labeled with the root folder the files are imported into.
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Root folder label for imports outside every root folder
+pub const NO_ROOT_FOLDER: &str = "none";
+
+/// Why a file was not imported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ImportFailureReason {
+    /// Recognized with less than the minimum confidence
+    LowConfidence,
+    /// The file name could not be analyzed
+    Unrecognized,
+    /// The configured transcode failed
+    Transcode,
+    /// No destination name could be built
+    Naming,
+    /// The root folder has too little free space
+    DiskSpace,
+    /// Linking or copying the file failed
+    Transfer,
+}
+
+impl ImportFailureReason {
+    /// Metric label
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImportFailureReason::LowConfidence => "low_confidence",
+            ImportFailureReason::Unrecognized => "unrecognized",
+            ImportFailureReason::Transcode => "transcode",
+            ImportFailureReason::Naming => "naming",
+            ImportFailureReason::DiskSpace => "disk_space",
+            ImportFailureReason::Transfer => "transfer",
+        }
+    }
+}
+
+/// Receives import pipeline measurements
+pub trait ImportMetrics: Send + Sync {
+    /// A download was scanned, looking at `files_examined` files of which
+    /// `media_files` were media
+    fn record_scan(&self, root_folder: &str, files_examined: usize, media_files: usize);
+
+    /// A file was recognized with `confidence` between 0 and 1
+    fn record_analysis(&self, root_folder: &str, confidence: f32);
+
+    /// A file was hardlinked, or copied when `hardlink` is false
+    fn record_transfer(&self, root_folder: &str, hardlink: bool, bytes: u64);
+
+    /// Importing a file finished, failing for `failure` if set
+    fn record_import(
+        &self,
+        root_folder: &str,
+        duration: Duration,
+        failure: Option<ImportFailureReason>,
+    );
+}
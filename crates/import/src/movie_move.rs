@@ -0,0 +1,826 @@
+//! Moving movie folders
+//!
+//! A movie's files lie in a folder of their own below its root folder, and
+//! their paths are stored relative to the media root every root folder is
+//! in. When the movie moves to another root folder, or a refresh changes its
+//! title or year, [`MoveMovieJob`] moves that folder and points the file
+//! records at the new location.
+//!
+//! Within one filesystem the folder is renamed, which is instant and keeps
+//! files that are also hardlinked from the downloads folder shared with the
+//! seeding copy. Across filesystems every file goes through the
+//! [`HardlinkManager`], which copies to a partial file, verifies its size and
+//! renames it into place. The source folder is only removed once every file
+//! was copied; a failed copy removes what was copied and leaves the source
+//! untouched. Progress is published as `ProgressUpdate` events.
+
+use radarr_core::{
+    domain::repositories::{MovieFileRepository, MovieRepository},
+    events::{EventBus, EventEnvelope, EventHandler, SystemEvent},
+    models::{media_relative_path, resolve_media_path, Movie, MovieFile},
+    progress::OperationType,
+    CommandName, CommandProgress, CommandQueue, CommandTrigger, RadarrError, Result,
+};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use tokio::fs;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::{
+    file_analyzer::FileAnalyzer,
+    hardlink_manager::{FileDateMode, HardlinkConfig, HardlinkManager},
+    rename_engine::{MovieNaming, RenameConfig, RenameEngine},
+};
+
+/// Which movie to move, and from where
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MoveMovieRequest {
+    pub movie_id: Uuid,
+    /// Root folder the movie's folder is in now; its current root folder
+    /// when unset
+    pub source_root: Option<PathBuf>,
+    /// Name the folder after the movie's title and year as the naming
+    /// templates do, instead of keeping its name
+    #[serde(default)]
+    pub rename_folder: bool,
+}
+
+/// Outcome of a move
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MoveMovieReport {
+    pub movie_id: Uuid,
+    pub source: PathBuf,
+    pub destination: PathBuf,
+    /// Whether anything moved; `false` when the folder already was in place
+    pub moved: bool,
+    /// Whether the files were copied to another filesystem
+    pub copied: bool,
+    /// Files copied that are also hardlinked elsewhere, e.g. by a seeding
+    /// download, so removing the source freed no space for them
+    pub shared_files: usize,
+    pub files: usize,
+    pub bytes: u64,
+}
+
+/// A file below the folder being moved
+#[derive(Debug)]
+struct FolderFile {
+    /// Path below the folder
+    relative: PathBuf,
+    size: u64,
+    /// Whether other hardlinks to the file exist
+    shared: bool,
+}
+
+/// Moves a movie's folder and updates its file records
+pub struct MoveMovieJob {
+    movie_repository: Arc<dyn MovieRepository>,
+    movie_file_repository: Arc<dyn MovieFileRepository>,
+    hardlink_manager: HardlinkManager,
+    file_analyzer: FileAnalyzer,
+    /// Naming settings, shared with the settings API
+    rename_config: Arc<RwLock<RenameConfig>>,
+    /// Folder movie file paths are stored relative to
+    media_root: PathBuf,
+    event_bus: Option<Arc<EventBus>>,
+}
+
+impl MoveMovieJob {
+    /// Create a move job for files stored below `media_root`
+    pub fn new(
+        movie_repository: Arc<dyn MovieRepository>,
+        movie_file_repository: Arc<dyn MovieFileRepository>,
+        media_root: impl Into<PathBuf>,
+    ) -> Self {
+        Self {
+            movie_repository,
+            movie_file_repository,
+            // Moved files keep their dates, and a movie is never too big to move
+            hardlink_manager: HardlinkManager::new(HardlinkConfig {
+                max_copy_size: 0,
+                file_date: FileDateMode::Original,
+                ..HardlinkConfig::default()
+            }),
+            file_analyzer: FileAnalyzer::new(),
+            rename_config: Arc::new(RwLock::new(RenameConfig::default())),
+            media_root: media_root.into(),
+            event_bus: None,
+        }
+    }
+
+    /// Name renamed folders with the templates the import pipeline uses
+    pub fn with_rename_config(mut self, config: Arc<RwLock<RenameConfig>>) -> Self {
+        self.rename_config = config;
+        self
+    }
+
+    /// Publish progress and the updated movie on the event bus
+    pub fn with_event_bus(mut self, event_bus: Arc<EventBus>) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
+    /// Move the movie's folder into its root folder
+    pub async fn run(
+        &self,
+        request: &MoveMovieRequest,
+        progress: Option<&CommandProgress>,
+    ) -> Result<MoveMovieReport> {
+        let movie = self
+            .movie_repository
+            .find_by_id(request.movie_id)
+            .await?
+            .ok_or_else(|| RadarrError::MovieNotFound {
+                id: request.movie_id.to_string(),
+            })?;
+        let destination_root = movie.root_folder_path().map(PathBuf::from).ok_or_else(|| {
+            RadarrError::ValidationError {
+                field: "rootFolderPath".to_string(),
+                message: format!("{} has no root folder", movie.title),
+            }
+        })?;
+        let source_root = request
+            .source_root
+            .clone()
+            .unwrap_or_else(|| destination_root.clone());
+
+        let files = self.movie_file_repository.list(Some(movie.id)).await?;
+        let Some(folder) = movie_folder(&files)? else {
+            debug!("{} has no files, nothing to move", movie.title);
+            return Ok(MoveMovieReport::unmoved(
+                movie.id,
+                source_root.clone(),
+                destination_root,
+            ));
+        };
+        let source = self.resolve(&folder)?;
+        if source_root.starts_with(&source) {
+            return Err(RadarrError::ValidationError {
+                field: "relativePath".to_string(),
+                message: format!("{} has no folder of its own", movie.title),
+            });
+        }
+        // Nested folders below the root folder, e.g. by year, are kept
+        let folder_in_root = match source.strip_prefix(&source_root) {
+            Ok(relative) if !relative.as_os_str().is_empty() => relative.to_path_buf(),
+            _ => PathBuf::from(folder.file_name().unwrap_or(folder.as_os_str())),
+        };
+        let new_folder_in_root = if request.rename_folder {
+            self.named_folder(&movie, &files)?
+        } else {
+            folder_in_root
+        };
+
+        let destination = destination_root.join(&new_folder_in_root);
+        if source == destination {
+            return Ok(MoveMovieReport::unmoved(movie.id, source, destination));
+        }
+        let new_folder = media_relative_path(&self.media_root, &destination)
+            .map(PathBuf::from)
+            .ok_or_else(|| RadarrError::ValidationError {
+                field: "rootFolderPath".to_string(),
+                message: format!(
+                    "{} is outside the media root {}",
+                    destination_root.display(),
+                    self.media_root.display()
+                ),
+            })?;
+        if fs::try_exists(&destination).await? {
+            return Err(RadarrError::ValidationError {
+                field: "destination".to_string(),
+                message: format!("{} already exists", destination.display()),
+            });
+        }
+
+        let operation_id = Uuid::new_v4();
+        let mut report = match self
+            .transfer(&source, &destination, operation_id, progress)
+            .await
+        {
+            Ok(report) => report,
+            Err(e) => {
+                self.finish(operation_id, false, e.to_string()).await;
+                return Err(e);
+            }
+        };
+        report.movie_id = movie.id;
+
+        if let Err(e) = self.update_files(&files, &folder, &new_folder).await {
+            warn!(
+                "Failed to update file paths of {}, moving its folder back: {}",
+                movie.title, e
+            );
+            if let Err(undo) = self
+                .transfer(&destination, &source, operation_id, None)
+                .await
+            {
+                warn!(
+                    "Failed to move {} back to {}: {}",
+                    destination.display(),
+                    source.display(),
+                    undo
+                );
+            }
+            self.finish(operation_id, false, e.to_string()).await;
+            return Err(e);
+        }
+
+        info!(
+            "Moved {} from {} to {} ({} files{})",
+            movie.title,
+            source.display(),
+            destination.display(),
+            report.files,
+            if report.copied { ", copied" } else { "" }
+        );
+        if report.shared_files > 0 {
+            warn!(
+                "{} moved files of {} are still hardlinked elsewhere and now use space twice",
+                report.shared_files, movie.title
+            );
+        }
+        self.finish(
+            operation_id,
+            true,
+            format!("Moved {} to {}", movie.title, destination.display()),
+        )
+        .await;
+        if let Some(event_bus) = &self.event_bus {
+            let event = SystemEvent::MovieUpdated {
+                movie_id: movie.id,
+                changes: vec!["path".to_string()],
+            };
+            if let Err(e) = event_bus.publish(event).await {
+                warn!("Failed to publish movie update event: {}", e);
+            }
+        }
+        Ok(report)
+    }
+
+    /// Absolute path of a folder stored below the media root
+    fn resolve(&self, folder: &Path) -> Result<PathBuf> {
+        resolve_media_path(&self.media_root, &folder.to_string_lossy()).ok_or_else(|| {
+            RadarrError::ValidationError {
+                field: "relativePath".to_string(),
+                message: format!("{} is outside the media root", folder.display()),
+            }
+        })
+    }
+
+    /// Folder below the root folder the naming templates give the movie
+    fn named_folder(&self, movie: &Movie, files: &[MovieFile]) -> Result<PathBuf> {
+        let current = MovieFile::current_per_edition(files.to_vec());
+        let Some(file) = current.first() else {
+            return Err(RadarrError::ValidationError {
+                field: "movieId".to_string(),
+                message: format!("{} has no files", movie.title),
+            });
+        };
+        let path = self.resolve(Path::new(&file.relative_path))?;
+        let analyzed = self.file_analyzer.analyze_file(&path)?;
+        let config = self
+            .rename_config
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        let planned = RenameEngine::new(config).generate_movie_filename(
+            &analyzed,
+            Some(&MovieNaming::from_movie(movie)),
+            Path::new(""),
+        )?;
+        Ok(planned.folder_path)
+    }
+
+    /// Move `source` to `destination`, renaming when both are on one
+    /// filesystem and copying file by file otherwise
+    async fn transfer(
+        &self,
+        source: &Path,
+        destination: &Path,
+        operation_id: Uuid,
+        progress: Option<&CommandProgress>,
+    ) -> Result<MoveMovieReport> {
+        let files = list_folder(source).await?;
+        let mut report = MoveMovieReport {
+            movie_id: Uuid::nil(),
+            source: source.to_path_buf(),
+            destination: destination.to_path_buf(),
+            moved: true,
+            copied: false,
+            shared_files: 0,
+            files: files.len(),
+            bytes: files.iter().map(|file| file.size).sum(),
+        };
+
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        match fs::rename(source, destination).await {
+            Ok(()) => return Ok(report),
+            Err(e) if e.kind() == std::io::ErrorKind::CrossesDevices => {
+                debug!(
+                    "{} is on another filesystem than {}, copying",
+                    destination.display(),
+                    source.display()
+                );
+            }
+            Err(e) => {
+                return Err(RadarrError::ExternalServiceError {
+                    service: "filesystem".to_string(),
+                    error: format!(
+                        "Failed to move {} to {}: {}",
+                        source.display(),
+                        destination.display(),
+                        e
+                    ),
+                })
+            }
+        }
+
+        let free_space_target = destination.parent().unwrap_or(destination);
+        if !self
+            .hardlink_manager
+            .check_available_space(free_space_target, report.bytes)
+            .await?
+        {
+            return Err(RadarrError::ValidationError {
+                field: "destination".to_string(),
+                message: format!(
+                    "Not enough free space in {} for {} bytes",
+                    free_space_target.display(),
+                    report.bytes
+                ),
+            });
+        }
+
+        report.copied = true;
+        let mut copied = 0u64;
+        for file in &files {
+            let from = source.join(&file.relative);
+            let to = destination.join(&file.relative);
+            if let Err(e) = self.hardlink_manager.create_hardlink(&from, &to).await {
+                if let Err(cleanup) = fs::remove_dir_all(destination).await {
+                    warn!(
+                        "Failed to remove partly copied {}: {}",
+                        destination.display(),
+                        cleanup
+                    );
+                }
+                return Err(e);
+            }
+            if file.shared {
+                report.shared_files += 1;
+            }
+            copied += file.size;
+            self.report_progress(operation_id, progress, copied, report.bytes, &file.relative)
+                .await;
+        }
+        fs::remove_dir_all(source).await?;
+        Ok(report)
+    }
+
+    /// Point the file records below `folder` at `new_folder`
+    ///
+    /// Either every record is updated or, after a failure, the records
+    /// already updated are restored.
+    async fn update_files(
+        &self,
+        files: &[MovieFile],
+        folder: &Path,
+        new_folder: &Path,
+    ) -> Result<()> {
+        if folder == new_folder {
+            return Ok(());
+        }
+        let mut updated: Vec<&MovieFile> = Vec::with_capacity(files.len());
+        for file in files {
+            let mut moved = file.clone();
+            moved.relative_path = rebase(&file.relative_path, folder, new_folder);
+            if let Err(e) = self.movie_file_repository.update(&moved).await {
+                for original in updated.iter().rev() {
+                    if let Err(e) = self.movie_file_repository.update(original).await {
+                        warn!(
+                            "Failed to restore path of movie file {}: {}",
+                            original.id, e
+                        );
+                    }
+                }
+                return Err(e);
+            }
+            updated.push(file);
+        }
+        Ok(())
+    }
+
+    async fn report_progress(
+        &self,
+        operation_id: Uuid,
+        progress: Option<&CommandProgress>,
+        copied: u64,
+        total: u64,
+        file: &Path,
+    ) {
+        let percentage = if total == 0 {
+            100.0
+        } else {
+            copied as f32 * 100.0 / total as f32
+        };
+        let message = format!("Copied {}", file.display());
+        if let Some(progress) = progress {
+            progress.update(percentage, message.clone()).await;
+        }
+        if let Some(event_bus) = &self.event_bus {
+            let _ = event_bus
+                .publish(SystemEvent::ProgressUpdate {
+                    operation_id,
+                    operation_type: OperationType::MoveMovie,
+                    percentage,
+                    message,
+                    eta_seconds: None,
+                })
+                .await;
+        }
+    }
+
+    async fn finish(&self, operation_id: Uuid, success: bool, message: String) {
+        if let Some(event_bus) = &self.event_bus {
+            let _ = event_bus
+                .publish(SystemEvent::OperationComplete {
+                    operation_id,
+                    operation_type: OperationType::MoveMovie,
+                    success,
+                    message,
+                })
+                .await;
+        }
+    }
+}
+
+impl MoveMovieReport {
+    fn unmoved(movie_id: Uuid, source: PathBuf, destination: PathBuf) -> Self {
+        Self {
+            movie_id,
+            source,
+            destination,
+            moved: false,
+            copied: false,
+            shared_files: 0,
+            files: 0,
+            bytes: 0,
+        }
+    }
+}
+
+/// Queues a `MoveMovie` command when a refresh changes a movie's title or
+/// year, so its folder is renamed to match
+pub struct MovieFolderRenameHandler {
+    command_queue: Arc<CommandQueue>,
+}
+
+impl MovieFolderRenameHandler {
+    pub fn new(command_queue: Arc<CommandQueue>) -> Self {
+        Self { command_queue }
+    }
+}
+
+#[async_trait::async_trait]
+impl EventHandler for MovieFolderRenameHandler {
+    async fn handle_event(&self, envelope: &EventEnvelope) -> Result<()> {
+        let SystemEvent::MovieUpdated { movie_id, .. } = &envelope.event else {
+            return Ok(());
+        };
+        let request = MoveMovieRequest {
+            movie_id: *movie_id,
+            source_root: None,
+            rename_folder: true,
+        };
+        self.command_queue
+            .enqueue(
+                CommandName::MoveMovie,
+                serde_json::to_value(&request)?,
+                CommandTrigger::Scheduled,
+            )
+            .await?;
+        Ok(())
+    }
+
+    fn should_handle(&self, envelope: &EventEnvelope) -> bool {
+        matches!(
+            &envelope.event,
+            SystemEvent::MovieUpdated { changes, .. }
+                if changes.iter().any(|change| change == "title" || change == "year")
+        )
+    }
+}
+
+/// Folder below the media root holding the movie's files
+///
+/// `None` when the movie has no files; files directly in the media root, or
+/// in different folders, can't be moved as one folder.
+fn movie_folder(files: &[MovieFile]) -> Result<Option<PathBuf>> {
+    let Some(folder) = files
+        .iter()
+        .filter_map(|file| Path::new(&file.relative_path).parent())
+        .min_by_key(|parent| parent.components().count())
+    else {
+        return Ok(None);
+    };
+    let contained = files
+        .iter()
+        .all(|file| Path::new(&file.relative_path).starts_with(folder));
+    if folder.as_os_str().is_empty() || !contained {
+        return Err(RadarrError::ValidationError {
+            field: "relativePath".to_string(),
+            message: "Movie files are not in a folder of their own".to_string(),
+        });
+    }
+    Ok(Some(folder.to_path_buf()))
+}
+
+/// `relative_path` below `folder`, moved below `new_folder`
+fn rebase(relative_path: &str, folder: &Path, new_folder: &Path) -> String {
+    let path = Path::new(relative_path);
+    path.strip_prefix(folder)
+        .map(|rest| new_folder.join(rest))
+        .unwrap_or_else(|_| path.to_path_buf())
+        .to_string_lossy()
+        .to_string()
+}
+
+/// Every file below `folder`, with its size and whether it is hardlinked
+async fn list_folder(folder: &Path) -> Result<Vec<FolderFile>> {
+    let folder = folder.to_path_buf();
+    let files = tokio::task::spawn_blocking(move || {
+        let mut files = Vec::new();
+        list_folder_in(&folder, Path::new(""), &mut files).map(|_| files)
+    })
+    .await??;
+    Ok(files)
+}
+
+fn list_folder_in(
+    root: &Path,
+    relative: &Path,
+    files: &mut Vec<FolderFile>,
+) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(root.join(relative))? {
+        let entry = entry?;
+        let path = relative.join(entry.file_name());
+        let metadata = entry.metadata()?;
+        if metadata.is_dir() {
+            list_folder_in(root, &path, files)?;
+        } else if metadata.is_file() {
+            files.push(FolderFile {
+                relative: path,
+                size: metadata.len(),
+                shared: link_count(&metadata) > 1,
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn link_count(metadata: &std::fs::Metadata) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    metadata.nlink()
+}
+
+/// Link counts aren't exposed on other platforms; files count as unshared
+#[cfg(not(unix))]
+fn link_count(_metadata: &std::fs::Metadata) -> u64 {
+    1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, Utc};
+    use std::sync::Mutex;
+    use tempfile::TempDir;
+
+    /// One movie and its files, kept in memory
+    struct Library {
+        movie: Movie,
+        files: Mutex<Vec<MovieFile>>,
+    }
+
+    #[async_trait::async_trait]
+    impl MovieRepository for Library {
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<Movie>> {
+            Ok((id == self.movie.id).then(|| self.movie.clone()))
+        }
+        async fn find_by_tmdb_id(&self, _tmdb_id: i32) -> Result<Option<Movie>> {
+            Ok(None)
+        }
+        async fn find_by_imdb_id(&self, _imdb_id: &str) -> Result<Option<Movie>> {
+            Ok(None)
+        }
+        async fn find_monitored(&self) -> Result<Vec<Movie>> {
+            Ok(vec![])
+        }
+        async fn find_missing_files(&self) -> Result<Vec<Movie>> {
+            Ok(vec![])
+        }
+        async fn find_added_since(&self, _since: DateTime<Utc>) -> Result<Vec<Movie>> {
+            Ok(vec![])
+        }
+        async fn search_by_title(&self, _query: &str, _limit: i32) -> Result<Vec<Movie>> {
+            Ok(vec![])
+        }
+        async fn create(&self, movie: &Movie) -> Result<Movie> {
+            Ok(movie.clone())
+        }
+        async fn update(&self, movie: &Movie) -> Result<Movie> {
+            Ok(movie.clone())
+        }
+        async fn delete(&self, _id: Uuid) -> Result<()> {
+            Ok(())
+        }
+        async fn list(&self, _offset: i64, _limit: i32) -> Result<Vec<Movie>> {
+            Ok(vec![self.movie.clone()])
+        }
+        async fn count(&self) -> Result<i64> {
+            Ok(1)
+        }
+        async fn update_last_search_time(&self, _id: Uuid) -> Result<()> {
+            Ok(())
+        }
+        async fn record_search_result(&self, _id: Uuid, _found: bool) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl MovieFileRepository for Library {
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<MovieFile>> {
+            let files = self.files.lock().unwrap();
+            Ok(files.iter().find(|file| file.id == id).cloned())
+        }
+        async fn find_by_movie_id(&self, movie_id: Uuid) -> Result<Option<MovieFile>> {
+            let files = self.files.lock().unwrap();
+            Ok(files.iter().find(|file| file.movie_id == movie_id).cloned())
+        }
+        async fn list(&self, movie_id: Option<Uuid>) -> Result<Vec<MovieFile>> {
+            let files = self.files.lock().unwrap();
+            Ok(files
+                .iter()
+                .filter(|file| movie_id.is_none_or(|id| file.movie_id == id))
+                .cloned()
+                .collect())
+        }
+        async fn create(&self, file: &MovieFile) -> Result<MovieFile> {
+            self.files.lock().unwrap().push(file.clone());
+            Ok(file.clone())
+        }
+        async fn update(&self, file: &MovieFile) -> Result<MovieFile> {
+            let mut files = self.files.lock().unwrap();
+            if let Some(stored) = files.iter_mut().find(|stored| stored.id == file.id) {
+                *stored = file.clone();
+            }
+            Ok(file.clone())
+        }
+        async fn delete(&self, id: Uuid) -> Result<()> {
+            self.files.lock().unwrap().retain(|file| file.id != id);
+            Ok(())
+        }
+    }
+
+    fn file(relative_path: &str) -> MovieFile {
+        MovieFile::new(
+            Uuid::nil(),
+            relative_path.to_string(),
+            1,
+            serde_json::json!({}),
+        )
+    }
+
+    #[test]
+    fn test_movie_folder() {
+        let files = vec![
+            file("The Matrix (1999)/Extras/Trailer.mkv"),
+            file("The Matrix (1999)/The Matrix (1999).mkv"),
+        ];
+        assert_eq!(
+            movie_folder(&files).unwrap(),
+            Some(PathBuf::from("The Matrix (1999)"))
+        );
+        assert_eq!(movie_folder(&[]).unwrap(), None);
+        assert!(movie_folder(&[file("Loose.mkv")]).is_err());
+        assert!(movie_folder(&[file("One/a.mkv"), file("Two/b.mkv")]).is_err());
+    }
+
+    #[test]
+    fn test_rebase() {
+        assert_eq!(
+            rebase(
+                "1999/The Matrix (1999)/The Matrix (1999).mkv",
+                Path::new("1999/The Matrix (1999)"),
+                Path::new("1999/The Matrix Reloaded (1999)"),
+            ),
+            "1999/The Matrix Reloaded (1999)/The Matrix (1999).mkv"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_folder_finds_nested_and_hardlinked_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let folder = temp_dir.path().join("Movie (2020)");
+        std::fs::create_dir_all(folder.join("Extras")).unwrap();
+        std::fs::write(folder.join("Movie (2020).mkv"), b"movie").unwrap();
+        std::fs::write(folder.join("Extras/Trailer.mkv"), b"trailer!").unwrap();
+        std::fs::hard_link(
+            folder.join("Movie (2020).mkv"),
+            temp_dir.path().join("seeding.mkv"),
+        )
+        .unwrap();
+
+        let mut files = list_folder(&folder).await.unwrap();
+        files.sort_by(|a, b| a.relative.cmp(&b.relative));
+
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].relative, PathBuf::from("Extras/Trailer.mkv"));
+        assert_eq!(files[0].size, 8);
+        assert!(!files[0].shared);
+        assert!(files[1].shared);
+    }
+
+    #[tokio::test]
+    async fn test_move_to_another_root_keeps_paths_below_media_root() {
+        let media_root = TempDir::new().unwrap();
+        let hd = media_root.path().join("hd");
+        let uhd = media_root.path().join("4k");
+        std::fs::create_dir_all(hd.join("Heat (1995)")).unwrap();
+        std::fs::create_dir_all(&uhd).unwrap();
+        std::fs::write(hd.join("Heat (1995)/Heat (1995).mkv"), b"movie").unwrap();
+
+        let mut movie = Movie::new(949, "Heat".to_string());
+        movie.metadata = serde_json::json!({ "root_folder_path": uhd.to_string_lossy() });
+        let library = Arc::new(Library {
+            files: Mutex::new(vec![MovieFile::new(
+                movie.id,
+                "hd/Heat (1995)/Heat (1995).mkv".to_string(),
+                5,
+                serde_json::json!({}),
+            )]),
+            movie: movie.clone(),
+        });
+        let job = MoveMovieJob::new(library.clone(), library.clone(), media_root.path());
+
+        let report = job
+            .run(
+                &MoveMovieRequest {
+                    movie_id: movie.id,
+                    source_root: Some(hd.clone()),
+                    rename_folder: false,
+                },
+                None,
+            )
+            .await
+            .unwrap();
+
+        assert!(report.moved);
+        assert_eq!(report.destination, uhd.join("Heat (1995)"));
+        let files = library.files.lock().unwrap().clone();
+        assert_eq!(files[0].relative_path, "4k/Heat (1995)/Heat (1995).mkv");
+        // Streaming, renaming and deleting resolve against the media root
+        let path = resolve_media_path(media_root.path(), &files[0].relative_path).unwrap();
+        assert_eq!(std::fs::read(path).unwrap(), b"movie");
+        assert!(!hd.join("Heat (1995)").exists());
+    }
+
+    #[tokio::test]
+    async fn test_move_outside_media_root_is_refused() {
+        let media_root = TempDir::new().unwrap();
+        let elsewhere = TempDir::new().unwrap();
+        let folder = media_root.path().join("hd/Heat (1995)");
+        std::fs::create_dir_all(&folder).unwrap();
+        std::fs::write(folder.join("Heat (1995).mkv"), b"movie").unwrap();
+
+        let mut movie = Movie::new(949, "Heat".to_string());
+        movie.metadata =
+            serde_json::json!({ "root_folder_path": elsewhere.path().to_string_lossy() });
+        let library = Arc::new(Library {
+            files: Mutex::new(vec![MovieFile::new(
+                movie.id,
+                "hd/Heat (1995)/Heat (1995).mkv".to_string(),
+                5,
+                serde_json::json!({}),
+            )]),
+            movie: movie.clone(),
+        });
+        let job = MoveMovieJob::new(library.clone(), library.clone(), media_root.path());
+
+        let request = MoveMovieRequest {
+            movie_id: movie.id,
+            source_root: Some(media_root.path().join("hd")),
+            rename_folder: false,
+        };
+        assert!(job.run(&request, None).await.is_err());
+        assert!(folder.join("Heat (1995).mkv").exists());
+    }
+}
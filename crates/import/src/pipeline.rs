@@ -3,17 +3,31 @@
 //! This module provides the main ImportPipeline that coordinates all import
 //! operations including scanning, analysis, hardlinking, and renaming.
 
-use radarr_core::RadarrError;
+use radarr_core::{
+    correlation::current_correlation_id,
+    domain::repositories::RootFolderRepository,
+    events::{EventBus, SystemEvent},
+    models::{Movie, RootFolder},
+    progress::{OperationType, ProgressTracker},
+    RadarrError,
+};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
+use std::sync::{Arc, RwLock, RwLockReadGuard};
 use std::time::{Duration, Instant};
 use tracing::{debug, info, instrument, warn};
 
 use crate::{
+    disk_space::free_space,
+    extract::{find_archives, ExtractConfig, Extraction, Extractor},
+    extras::{ExtraAction, ExtrasConfig},
     file_analyzer::{AnalyzedFile, FileAnalyzer},
-    file_scanner::{DetectedFile, FileScanner, ScanConfig},
-    hardlink_manager::{HardlinkConfig, HardlinkManager, HardlinkResult},
-    rename_engine::{RenameConfig, RenameEngine, RenameResult},
+    file_scanner::{DetectedFile, FileScanner, ScanConfig, ScanStats},
+    forensics::{FilesystemCheck, ForensicBundle, ForensicBundleStore, RenameComputation},
+    hardlink_manager::{HardlinkConfig, HardlinkManager, HardlinkResult, ReleaseDates},
+    metrics::{ImportFailureReason, ImportMetrics, NO_ROOT_FOLDER},
+    rename_engine::{MovieNaming, RenameConfig, RenameEngine, RenameResult},
+    transcode::{TranscodeConfig, TranscodeOutput, Transcoder},
 };
 
 /// Complete configuration for the import pipeline
@@ -25,6 +39,15 @@ pub struct ImportConfig {
     pub hardlink_config: HardlinkConfig,
     /// File renaming configuration
     pub rename_config: RenameConfig,
+    /// Optional ffmpeg stage run before files are imported
+    #[serde(default)]
+    pub transcode_config: TranscodeConfig,
+    /// Optional extraction of archives found in downloads
+    #[serde(default)]
+    pub extract_config: ExtractConfig,
+    /// What is done with samples, trailers and other extras in downloads
+    #[serde(default)]
+    pub extras_config: ExtrasConfig,
     /// Whether to actually move files or just plan the operation
     pub dry_run: bool,
     /// Minimum confidence score to accept analyzed files
@@ -43,6 +66,9 @@ impl Default for ImportConfig {
             scan_config: ScanConfig::default(),
             hardlink_config: HardlinkConfig::default(),
             rename_config: RenameConfig::default(),
+            transcode_config: TranscodeConfig::default(),
+            extract_config: ExtractConfig::default(),
+            extras_config: ExtrasConfig::default(),
             dry_run: false,
             min_confidence: 0.3,
             skip_samples: true,
@@ -63,10 +89,16 @@ pub struct ImportResult {
     pub hardlink_result: Option<HardlinkResult>,
     /// Rename operation result
     pub rename_result: Option<RenameResult>,
+    /// Transcode that produced the imported file, if one ran
+    #[serde(default)]
+    pub transcode: Option<TranscodeOutput>,
     /// Whether the import was successful
     pub success: bool,
     /// Error message if import failed
     pub error: Option<String>,
+    /// Why the import failed, if it did
+    #[serde(default)]
+    pub failure_reason: Option<ImportFailureReason>,
     /// Time taken for this import
     pub duration: Duration,
 }
@@ -92,6 +124,15 @@ pub struct ImportStats {
     pub hardlinks_created: usize,
     /// Number of files copied
     pub files_copied: usize,
+    /// Throughput of the directory scan
+    #[serde(default)]
+    pub scan: ScanStats,
+    /// Samples and extras imported next to the movie
+    #[serde(default)]
+    pub extras_imported: usize,
+    /// Samples and extras deleted from the download
+    #[serde(default)]
+    pub extras_deleted: usize,
 }
 
 /// Main import pipeline orchestrator
@@ -100,7 +141,15 @@ pub struct ImportPipeline {
     file_scanner: FileScanner,
     file_analyzer: FileAnalyzer,
     hardlink_manager: HardlinkManager,
-    rename_engine: RenameEngine,
+    /// Replaced when the naming settings change
+    rename_engine: RwLock<RenameEngine>,
+    transcoder: Transcoder,
+    extractor: Extractor,
+    progress_tracker: Option<Arc<ProgressTracker>>,
+    event_bus: Option<Arc<EventBus>>,
+    root_folders: Option<Arc<dyn RootFolderRepository>>,
+    forensics: Option<Arc<ForensicBundleStore>>,
+    metrics: Option<Arc<dyn ImportMetrics>>,
 }
 
 impl ImportPipeline {
@@ -110,16 +159,77 @@ impl ImportPipeline {
         let file_analyzer = FileAnalyzer::new();
         let hardlink_manager = HardlinkManager::new(config.hardlink_config.clone());
         let rename_engine = RenameEngine::new(config.rename_config.clone());
+        let transcoder = Transcoder::new(config.transcode_config.clone());
+        let extractor = Extractor::new(config.extract_config.clone());
 
         Self {
             config,
             file_scanner,
             file_analyzer,
             hardlink_manager,
-            rename_engine,
+            rename_engine: RwLock::new(rename_engine),
+            transcoder,
+            extractor,
+            progress_tracker: None,
+            event_bus: None,
+            root_folders: None,
+            forensics: None,
+            metrics: None,
         }
     }
 
+    /// Track transcodes in a progress tracker
+    pub fn with_progress_tracker(mut self, tracker: Arc<ProgressTracker>) -> Self {
+        self.progress_tracker = Some(tracker);
+        self
+    }
+
+    /// Publish scan and transcode progress events
+    pub fn with_event_bus(mut self, event_bus: Arc<EventBus>) -> Self {
+        self.file_scanner = self.file_scanner.with_event_bus(event_bus.clone());
+        self.event_bus = Some(event_bus);
+        self
+    }
+
+    /// Refuse imports that would leave a root folder below its free space reserve
+    pub fn with_root_folders(mut self, root_folders: Arc<dyn RootFolderRepository>) -> Self {
+        self.root_folders = Some(root_folders);
+        self
+    }
+
+    /// Write a forensic bundle to `store` for every file that fails to import
+    pub fn with_forensics(mut self, store: Arc<ForensicBundleStore>) -> Self {
+        self.forensics = Some(store);
+        self
+    }
+
+    /// Report scans, analysis confidence, transfers and import outcomes
+    pub fn with_metrics(mut self, metrics: Arc<dyn ImportMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Remove partial copies an interrupted import left in the root folders
+    ///
+    /// Returns how many were removed; folders that can't be read are skipped.
+    pub async fn remove_partial_copies(&self) -> Result<usize, RadarrError> {
+        let Some(root_folders) = &self.root_folders else {
+            return Ok(0);
+        };
+
+        let mut removed = 0;
+        for folder in root_folders.list().await? {
+            match HardlinkManager::remove_partial_files(Path::new(&folder.path)).await {
+                Ok(count) => removed += count,
+                Err(e) => warn!(
+                    "Failed to look for partial copies in {}: {}",
+                    folder.path, e
+                ),
+            }
+        }
+        Ok(removed)
+    }
+
     /// Create an import pipeline with default configuration
     pub fn default() -> Self {
         Self::new(ImportConfig::default())
@@ -132,67 +242,96 @@ impl ImportPipeline {
         source_dir: &Path,
         dest_dir: &Path,
     ) -> Result<ImportStats, RadarrError> {
-        let start_time = Instant::now();
-        info!(
-            "Starting import from {} to {}",
-            source_dir.display(),
-            dest_dir.display()
-        );
-
-        // Phase 1: Scan for media files
-        let detected_files = self.scan_phase(source_dir).await?;
-        info!(
-            "Scan phase complete: {} files detected",
-            detected_files.len()
-        );
-
-        // Phase 2: Analyze detected files
-        let analyzed_files = self.analyze_phase(&detected_files).await?;
-        info!(
-            "Analysis phase complete: {} files analyzed",
-            analyzed_files.len()
-        );
-
-        // Phase 3: Import files (hardlink + rename)
-        let import_results = self.import_phase(&analyzed_files, dest_dir).await?;
-        info!(
-            "Import phase complete: {} files processed",
-            import_results.len()
-        );
-
-        // Generate statistics
-        let stats = self.generate_stats(&detected_files, &import_results, start_time.elapsed());
-        info!(
-            "Import operation complete: {} successful, {} failed, {} skipped",
-            stats.successful_imports, stats.failed_imports, stats.skipped_files
-        );
-
-        Ok(stats)
+        self.import_directory_with_results(source_dir, dest_dir)
+            .await
+            .map(|(stats, _)| stats)
     }
 
     /// Import all files from source directory to destination, returning both stats and individual results
-    #[instrument(skip(self), fields(source = %source_dir.display(), dest = %dest_dir.display()))]
+    #[instrument(
+        skip(self),
+        fields(
+            source = %source_dir.display(),
+            dest = %dest_dir.display(),
+            correlation_id = tracing::field::Empty
+        )
+    )]
     pub async fn import_directory_with_results(
         &self,
         source_dir: &Path,
         dest_dir: &Path,
     ) -> Result<(ImportStats, Vec<ImportResult>), RadarrError> {
         let start_time = Instant::now();
+        let correlation_id = current_correlation_id().to_string();
+        tracing::Span::current().record("correlation_id", correlation_id.as_str());
         info!(
             "Starting import from {} to {}",
             source_dir.display(),
             dest_dir.display()
         );
 
+        // Phase 0: Extract archives; what they hold is imported with the loose files
+        let extraction = match self.extract_phase(source_dir).await {
+            Ok(extraction) => extraction,
+            Err(e) => {
+                self.capture_scan_failure(&correlation_id, source_dir, dest_dir, &e)
+                    .await;
+                return Err(e);
+            }
+        };
+        let result = self
+            .import_scanned(source_dir, dest_dir, extraction.as_ref(), start_time)
+            .await;
+        if let Some(extraction) = &extraction {
+            extraction.cleanup().await;
+        }
+        result
+    }
+
+    /// Scan, analyze and import the files of a download and its extracted archives
+    async fn import_scanned(
+        &self,
+        source_dir: &Path,
+        dest_dir: &Path,
+        extraction: Option<&Extraction>,
+        start_time: Instant,
+    ) -> Result<(ImportStats, Vec<ImportResult>), RadarrError> {
+        let correlation_id = current_correlation_id().to_string();
+
         // Phase 1: Scan for media files
-        let detected_files = self.scan_phase(source_dir).await?;
+        let (mut detected_files, scan_stats) = match self.scan_phase(source_dir, extraction).await {
+            Ok(scanned) => scanned,
+            Err(e) => {
+                self.capture_scan_failure(&correlation_id, source_dir, dest_dir, &e)
+                    .await;
+                return Err(e);
+            }
+        };
         info!(
             "Scan phase complete: {} files detected",
             detected_files.len()
         );
+        let metrics = self.import_metrics(dest_dir).await;
+        if let Some((metrics, root_folder)) = &metrics {
+            metrics.record_scan(
+                root_folder,
+                scan_stats.files_examined,
+                scan_stats.media_files,
+            );
+        }
+        let metrics = metrics
+            .as_ref()
+            .map(|(metrics, root_folder)| (*metrics, root_folder.as_str()));
+
+        // Samples and extras are handled after the movie, never imported as one
+        self.config.extras_config.classify(&mut detected_files);
+        let (extras, movie_files): (Vec<_>, Vec<_>) = detected_files
+            .iter()
+            .cloned()
+            .partition(|file| file.extra_type.is_some());
 
         // Phase 2: Analyze detected files
-        let analyzed_files = self.analyze_phase(&detected_files).await?;
+        let analyzed_files = self.analyze_phase(&movie_files, metrics).await?;
         info!(
             "Analysis phase complete: {} files analyzed",
             analyzed_files.len()
@@ -204,9 +343,31 @@ impl ImportPipeline {
             "Import phase complete: {} files processed",
             import_results.len()
         );
+        for result in &import_results {
+            if let Some((metrics, root_folder)) = metrics {
+                Self::record_result(metrics, root_folder, result);
+            }
+            if !result.success {
+                self.capture_failure(&correlation_id, result, &detected_files, dest_dir)
+                    .await;
+            }
+        }
+
+        // Phase 4: Import, delete or leave the extras
+        let (extras_imported, extras_deleted) = self.extras_phase(&extras, &import_results).await;
 
         // Generate statistics
-        let stats = self.generate_stats(&detected_files, &import_results, start_time.elapsed());
+        let mut stats = self.generate_stats(
+            &detected_files,
+            &import_results,
+            scan_stats,
+            start_time.elapsed(),
+        );
+        stats.extras_imported = extras_imported;
+        stats.extras_deleted = extras_deleted;
+        stats.skipped_files = stats
+            .skipped_files
+            .saturating_sub(extras_imported + extras_deleted);
         info!(
             "Import operation complete: {} successful, {} failed, {} skipped",
             stats.successful_imports, stats.failed_imports, stats.skipped_files
@@ -221,6 +382,68 @@ impl ImportPipeline {
         &self,
         source_path: &Path,
         dest_dir: &Path,
+    ) -> Result<ImportResult, RadarrError> {
+        self.import_file_with_dates(source_path, dest_dir, &ReleaseDates::default())
+            .await
+    }
+
+    /// Import a single file, dating it from the movie's release dates
+    ///
+    /// The dates are used when the hardlink config's file date mode is
+    /// cinemas or release.
+    #[instrument(skip(self, release_dates), fields(correlation_id = tracing::field::Empty))]
+    pub async fn import_file_with_dates(
+        &self,
+        source_path: &Path,
+        dest_dir: &Path,
+        release_dates: &ReleaseDates,
+    ) -> Result<ImportResult, RadarrError> {
+        let correlation_id = current_correlation_id().to_string();
+        tracing::Span::current().record("correlation_id", correlation_id.as_str());
+
+        let result = self
+            .analyze_and_import(source_path, dest_dir, release_dates, None)
+            .await?;
+        self.record_single_import(dest_dir, &result).await;
+        if !result.success {
+            self.capture_failure(&correlation_id, &result, &[], dest_dir)
+                .await;
+        }
+        Ok(result)
+    }
+
+    /// Import a single file of `movie`, naming it from the movie's title,
+    /// year and IDs and dating it from its release dates
+    #[instrument(skip(self, movie), fields(movie = %movie.title, correlation_id = tracing::field::Empty))]
+    pub async fn import_movie_file(
+        &self,
+        source_path: &Path,
+        dest_dir: &Path,
+        movie: &Movie,
+    ) -> Result<ImportResult, RadarrError> {
+        let correlation_id = current_correlation_id().to_string();
+        tracing::Span::current().record("correlation_id", correlation_id.as_str());
+
+        let release_dates = ReleaseDates::from_movie(movie);
+        let naming = MovieNaming::from_movie(movie);
+        let result = self
+            .analyze_and_import(source_path, dest_dir, &release_dates, Some(&naming))
+            .await?;
+        self.record_single_import(dest_dir, &result).await;
+        if !result.success {
+            self.capture_failure(&correlation_id, &result, &[], dest_dir)
+                .await;
+        }
+        Ok(result)
+    }
+
+    /// Analyze a single file and import it if it is recognized
+    async fn analyze_and_import(
+        &self,
+        source_path: &Path,
+        dest_dir: &Path,
+        release_dates: &ReleaseDates,
+        naming: Option<&MovieNaming>,
     ) -> Result<ImportResult, RadarrError> {
         let start_time = Instant::now();
         debug!("Importing single file: {}", source_path.display());
@@ -237,12 +460,15 @@ impl ImportPipeline {
                             modified: std::time::SystemTime::now(),
                             media_type: crate::file_scanner::MediaType::Unknown,
                             is_sample: false,
+                            extra_type: None,
                         },
                         analyzed_file: Some(analyzed),
                         hardlink_result: None,
                         rename_result: None,
+                        transcode: None,
                         success: false,
                         error: Some("Confidence too low".to_string()),
+                        failure_reason: Some(ImportFailureReason::LowConfidence),
                         duration: start_time.elapsed(),
                     });
                 }
@@ -257,12 +483,15 @@ impl ImportPipeline {
                         modified: std::time::SystemTime::now(),
                         media_type: crate::file_scanner::MediaType::Unknown,
                         is_sample: false,
+                        extra_type: None,
                     },
                     analyzed_file: None,
                     hardlink_result: None,
                     rename_result: None,
+                    transcode: None,
                     success: false,
                     error: Some(e.to_string()),
+                    failure_reason: Some(ImportFailureReason::Unrecognized),
                     duration: start_time.elapsed(),
                 });
             }
@@ -270,20 +499,88 @@ impl ImportPipeline {
 
         // Execute the import
         Ok(self
-            .import_single_file(&analyzed_file, dest_dir, start_time)
+            .import_single_file(&analyzed_file, dest_dir, release_dates, naming, start_time)
             .await)
     }
 
     /// Scan phase: discover all media files
-    async fn scan_phase(&self, source_dir: &Path) -> Result<Vec<DetectedFile>, RadarrError> {
+    async fn scan_phase(
+        &self,
+        source_dir: &Path,
+        extraction: Option<&Extraction>,
+    ) -> Result<(Vec<DetectedFile>, ScanStats), RadarrError> {
         debug!("Starting scan phase");
-        self.file_scanner.scan_directory(source_dir).await
+        let (mut files, mut stats) = self
+            .file_scanner
+            .scan_directory_with_stats(source_dir)
+            .await?;
+        if let Some(extraction) = extraction {
+            let (extracted, extracted_stats) = self
+                .file_scanner
+                .scan_directory_with_stats(&extraction.output_dir)
+                .await?;
+            files.extend(extracted);
+            stats.directories_scanned += extracted_stats.directories_scanned;
+            stats.files_examined += extracted_stats.files_examined;
+            stats.media_files += extracted_stats.media_files;
+            stats.duration += extracted_stats.duration;
+            stats.files_per_second =
+                stats.files_examined as f64 / stats.duration.as_secs_f64().max(f64::EPSILON);
+        }
+        Ok((files, stats))
+    }
+
+    /// Extraction phase: unpack the archives in a download to a work directory
+    ///
+    /// Progress is reported like a transcode's. Returns `None` when
+    /// extraction is off, in a dry run or when there are no archives.
+    async fn extract_phase(&self, source_dir: &Path) -> Result<Option<Extraction>, RadarrError> {
+        if !self.extractor.is_enabled() {
+            return Ok(None);
+        }
+        if self.config.dry_run {
+            info!(
+                "DRY RUN: Would extract archives in {}",
+                source_dir.display()
+            );
+            return Ok(None);
+        }
+        // Downloads without archives don't show up as an operation
+        if find_archives(source_dir).await?.is_empty() {
+            return Ok(None);
+        }
+
+        let title = format!("Extracting: {}", source_dir.display());
+        let (operation_id, sender, reporter) = self
+            .start_progress(OperationType::Extract, title, "Extracting")
+            .await;
+        let result = self.extractor.extract_all(source_dir, Some(&sender)).await;
+        drop(sender);
+        let _ = reporter.await;
+
+        let message = match &result {
+            Ok(Some(extraction)) => format!("Extracted {} archives", extraction.archives.len()),
+            Ok(None) => "No archives to extract".to_string(),
+            Err(e) => format!("Extraction failed: {}", e),
+        };
+        self.finish_progress(
+            operation_id,
+            OperationType::Extract,
+            result.is_ok(),
+            message,
+        )
+        .await;
+        result
     }
 
     /// Analysis phase: analyze all detected files
+    ///
+    /// Files that are skipped for low confidence or can't be analyzed are
+    /// reported to `metrics` as failed imports.
     async fn analyze_phase(
         &self,
         detected_files: &[DetectedFile],
+        metrics: Option<(&dyn ImportMetrics, &str)>,
     ) -> Result<Vec<AnalyzedFile>, RadarrError> {
         debug!("Starting analysis phase for {} files", detected_files.len());
 
@@ -296,11 +593,22 @@ impl ImportPipeline {
                 continue;
             }
 
+            let start_time = Instant::now();
             match self.file_analyzer.analyze_file(&detected_file.path) {
                 Ok(analyzed) => {
+                    if let Some((metrics, root_folder)) = metrics {
+                        metrics.record_analysis(root_folder, analyzed.confidence);
+                    }
                     if analyzed.confidence >= self.config.min_confidence {
                         analyzed_files.push(analyzed);
                     } else {
+                        if let Some((metrics, root_folder)) = metrics {
+                            metrics.record_import(
+                                root_folder,
+                                start_time.elapsed(),
+                                Some(ImportFailureReason::LowConfidence),
+                            );
+                        }
                         debug!(
                             "Skipping file with low confidence {}: {}",
                             analyzed.confidence,
@@ -309,6 +617,13 @@ impl ImportPipeline {
                     }
                 }
                 Err(e) => {
+                    if let Some((metrics, root_folder)) = metrics {
+                        metrics.record_import(
+                            root_folder,
+                            start_time.elapsed(),
+                            Some(ImportFailureReason::Unrecognized),
+                        );
+                    }
                     if self.config.continue_on_error {
                         warn!("Failed to analyze {}: {}", detected_file.path.display(), e);
                     } else {
@@ -321,6 +636,92 @@ impl ImportPipeline {
         Ok(analyzed_files)
     }
 
+    /// Extras phase: carry out the configured action for each extra
+    ///
+    /// Extras are only touched once a movie from the same download was
+    /// imported, and are imported into that movie's folder, in the subfolder
+    /// for their kind. Returns how many were imported and deleted.
+    async fn extras_phase(
+        &self,
+        extras: &[DetectedFile],
+        import_results: &[ImportResult],
+    ) -> (usize, usize) {
+        let movie_folder = import_results
+            .iter()
+            .filter(|result| result.success)
+            .find_map(|result| result.rename_result.as_ref()?.new_path.parent());
+        let Some(movie_folder) = movie_folder else {
+            if !extras.is_empty() {
+                debug!("No movie imported, leaving {} extras", extras.len());
+            }
+            return (0, 0);
+        };
+
+        let (mut imported, mut deleted) = (0, 0);
+        for extra in extras {
+            let Some(extra_type) = extra.extra_type else {
+                continue;
+            };
+            match self.config.extras_config.action(extra_type) {
+                ExtraAction::Skip => {
+                    debug!("Skipping {:?}: {}", extra_type, extra.path.display());
+                }
+                ExtraAction::Import => {
+                    let Some(file_name) = extra.path.file_name() else {
+                        continue;
+                    };
+                    let destination = movie_folder.join(extra_type.folder_name()).join(file_name);
+                    if self.config.dry_run {
+                        info!(
+                            "DRY RUN: Would import {:?} {} -> {}",
+                            extra_type,
+                            extra.path.display(),
+                            destination.display()
+                        );
+                        continue;
+                    }
+                    match self
+                        .hardlink_manager
+                        .create_hardlink(&extra.path, &destination)
+                        .await
+                    {
+                        Ok(_) => imported += 1,
+                        Err(e) => warn!(
+                            "Failed to import {:?} {}: {}",
+                            extra_type,
+                            extra.path.display(),
+                            e
+                        ),
+                    }
+                }
+                ExtraAction::Delete => {
+                    if self.config.dry_run {
+                        info!(
+                            "DRY RUN: Would delete {:?} {}",
+                            extra_type,
+                            extra.path.display()
+                        );
+                        continue;
+                    }
+                    match tokio::fs::remove_file(&extra.path).await {
+                        Ok(()) => deleted += 1,
+                        Err(e) => warn!(
+                            "Failed to delete {:?} {}: {}",
+                            extra_type,
+                            extra.path.display(),
+                            e
+                        ),
+                    }
+                }
+            }
+        }
+
+        if imported + deleted > 0 {
+            info!("Extras: {} imported, {} deleted", imported, deleted);
+        }
+        (imported, deleted)
+    }
+
     /// Import phase: hardlink and rename files
     async fn import_phase(
         &self,
@@ -330,13 +731,20 @@ impl ImportPipeline {
         debug!("Starting import phase for {} files", analyzed_files.len());
 
         let mut results = Vec::new();
+        let release_dates = ReleaseDates::default();
 
         // Process files in batches to control parallelism
         for chunk in analyzed_files.chunks(self.config.max_parallel) {
             let mut batch_futures = Vec::new();
 
             for analyzed_file in chunk {
-                let future = self.import_single_file(analyzed_file, dest_dir, Instant::now());
+                let future = self.import_single_file(
+                    analyzed_file,
+                    dest_dir,
+                    &release_dates,
+                    None,
+                    Instant::now(),
+                );
                 batch_futures.push(future);
             }
 
@@ -353,6 +761,8 @@ impl ImportPipeline {
         &self,
         analyzed_file: &AnalyzedFile,
         dest_dir: &Path,
+        release_dates: &ReleaseDates,
+        naming: Option<&MovieNaming>,
         start_time: Instant,
     ) -> ImportResult {
         debug!("Importing file: {}", analyzed_file.path.display());
@@ -369,22 +779,51 @@ impl ImportPipeline {
             modified: std::time::SystemTime::now(),
             media_type: crate::file_scanner::MediaType::Movie, // Simplified
             is_sample: analyzed_file.is_sample,
+            extra_type: None,
+        };
+
+        // Step 0: Optional transcode; its output is imported instead of the original
+        let transcode = match self.transcode_stage(analyzed_file).await {
+            Ok(transcode) => transcode,
+            Err(e) => {
+                return ImportResult {
+                    detected_file,
+                    analyzed_file: Some(analyzed_file.clone()),
+                    hardlink_result: None,
+                    rename_result: None,
+                    transcode: None,
+                    success: false,
+                    error: Some(format!("Transcode failed: {}", e)),
+                    failure_reason: Some(ImportFailureReason::Transcode),
+                    duration: start_time.elapsed(),
+                };
+            }
+        };
+        let import_file = match &transcode {
+            Some(transcode) => AnalyzedFile {
+                path: transcode.output.clone(),
+                ..analyzed_file.clone()
+            },
+            None => analyzed_file.clone(),
         };
 
         // Step 1: Generate rename plan
-        let mut rename_result = match self
-            .rename_engine
-            .generate_filename(analyzed_file, dest_dir)
-        {
+        let planned = self
+            .rename_engine()
+            .generate_movie_filename(&import_file, naming, dest_dir);
+        let mut rename_result = match planned {
             Ok(result) => result,
             Err(e) => {
+                self.discard_transcode(&transcode).await;
                 return ImportResult {
                     detected_file,
                     analyzed_file: Some(analyzed_file.clone()),
                     hardlink_result: None,
                     rename_result: None,
+                    transcode,
                     success: false,
                     error: Some(format!("Rename planning failed: {}", e)),
+                    failure_reason: Some(ImportFailureReason::Naming),
                     duration: start_time.elapsed(),
                 };
             }
@@ -392,11 +831,36 @@ impl ImportPipeline {
 
         // Step 2: Create hardlink/copy to new location
         let hardlink_result = if !self.config.dry_run {
-            match self
-                .hardlink_manager
-                .create_hardlink(&analyzed_file.path, &rename_result.new_path)
+            if let Err(e) = self
+                .check_free_space(&import_file.path, &rename_result.new_path)
                 .await
             {
+                self.discard_transcode(&transcode).await;
+                return ImportResult {
+                    detected_file,
+                    analyzed_file: Some(analyzed_file.clone()),
+                    hardlink_result: None,
+                    rename_result: Some(rename_result),
+                    transcode,
+                    success: false,
+                    error: Some(e.to_string()),
+                    failure_reason: Some(ImportFailureReason::DiskSpace),
+                    duration: start_time.elapsed(),
+                };
+            }
+
+            let result = self
+                .hardlink_manager
+                .create_hardlink_with_dates(
+                    &import_file.path,
+                    &rename_result.new_path,
+                    release_dates,
+                )
+                .await;
+            // The library now has its own link or copy of the output
+            self.discard_transcode(&transcode).await;
+
+            match result {
                 Ok(result) => Some(result),
                 Err(e) => {
                     return ImportResult {
@@ -404,8 +868,10 @@ impl ImportPipeline {
                         analyzed_file: Some(analyzed_file.clone()),
                         hardlink_result: None,
                         rename_result: Some(rename_result),
+                        transcode,
                         success: false,
                         error: Some(format!("Hardlink failed: {}", e)),
+                        failure_reason: Some(ImportFailureReason::Transfer),
                         duration: start_time.elapsed(),
                     };
                 }
@@ -413,7 +879,7 @@ impl ImportPipeline {
         } else {
             info!(
                 "DRY RUN: Would create hardlink {} -> {}",
-                analyzed_file.path.display(),
+                import_file.path.display(),
                 rename_result.new_path.display()
             );
             None
@@ -429,17 +895,338 @@ impl ImportPipeline {
             analyzed_file: Some(analyzed_file.clone()),
             hardlink_result,
             rename_result: Some(rename_result),
+            transcode,
             success: true,
             error: None,
+            failure_reason: None,
             duration: start_time.elapsed(),
         }
     }
 
+    /// Run the configured transcode profile for a file, if one applies
+    ///
+    /// Progress is reported through the progress tracker and as
+    /// `ProgressUpdate`/`OperationComplete` events when those are configured.
+    async fn transcode_stage(
+        &self,
+        analyzed_file: &AnalyzedFile,
+    ) -> Result<Option<TranscodeOutput>, RadarrError> {
+        let Some(profile) = self.transcoder.profile_for(&analyzed_file.path) else {
+            return Ok(None);
+        };
+        if self.config.dry_run {
+            info!(
+                "DRY RUN: Would transcode {} with profile '{}'",
+                analyzed_file.path.display(),
+                profile.name
+            );
+            return Ok(None);
+        }
+
+        let title = format!("Transcoding: {}", analyzed_file.original_filename);
+        let (operation_id, sender, reporter) = self
+            .start_progress(OperationType::Transcode, title, "Transcoding")
+            .await;
+        let result = self
+            .transcoder
+            .transcode(&analyzed_file.path, profile, Some(&sender))
+            .await;
+        drop(sender);
+        let _ = reporter.await;
+
+        let message = match &result {
+            Ok(output) => format!("Transcoded with profile '{}'", output.profile),
+            Err(e) => format!("Transcode failed: {}", e),
+        };
+        self.finish_progress(
+            operation_id,
+            OperationType::Transcode,
+            result.is_ok(),
+            message,
+        )
+        .await;
+
+        result.map(Some)
+    }
+
+    /// Start tracking an operation whose progress in percent is sent on the
+    /// returned channel
+    ///
+    /// Progress goes to the tracker and as `ProgressUpdate` events until the
+    /// sender is dropped; the returned task ends after that.
+    async fn start_progress(
+        &self,
+        operation_type: OperationType,
+        title: String,
+        step: &'static str,
+    ) -> (
+        uuid::Uuid,
+        tokio::sync::mpsc::UnboundedSender<f32>,
+        tokio::task::JoinHandle<()>,
+    ) {
+        let operation_id = match &self.progress_tracker {
+            Some(tracker) => tracker.start_operation(operation_type, title.clone()).await,
+            None => uuid::Uuid::new_v4(),
+        };
+
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel::<f32>();
+        let tracker = self.progress_tracker.clone();
+        let event_bus = self.event_bus.clone();
+        let reporter = tokio::spawn(async move {
+            // Whole percents are plenty for a progress bar
+            let mut last_reported = -1.0f32;
+            while let Some(percentage) = receiver.recv().await {
+                if percentage.floor() <= last_reported {
+                    continue;
+                }
+                last_reported = percentage.floor();
+
+                if let Some(tracker) = &tracker {
+                    tracker
+                        .update_progress(operation_id, percentage, step)
+                        .await;
+                }
+                if let Some(bus) = &event_bus {
+                    let _ = bus
+                        .publish(SystemEvent::ProgressUpdate {
+                            operation_id,
+                            operation_type,
+                            percentage,
+                            message: title.clone(),
+                            eta_seconds: None,
+                        })
+                        .await;
+                }
+            }
+        });
+        (operation_id, sender, reporter)
+    }
+
+    /// Complete or fail an operation started with [`Self::start_progress`]
+    async fn finish_progress(
+        &self,
+        operation_id: uuid::Uuid,
+        operation_type: OperationType,
+        success: bool,
+        message: String,
+    ) {
+        if let Some(tracker) = &self.progress_tracker {
+            if success {
+                tracker.complete_operation(operation_id, &message).await;
+            } else {
+                tracker.fail_operation(operation_id, &message).await;
+            }
+        }
+        if let Some(bus) = &self.event_bus {
+            let _ = bus
+                .publish(SystemEvent::OperationComplete {
+                    operation_id,
+                    operation_type,
+                    success,
+                    message,
+                })
+                .await;
+        }
+    }
+
+    /// Make sure the destination's root folder has room for the file
+    ///
+    /// The file's full size is required even though a hardlink would not use
+    /// any, since whether linking works is only known once it is tried.
+    /// Destinations outside every root folder are not checked.
+    async fn check_free_space(&self, source: &Path, destination: &Path) -> Result<(), RadarrError> {
+        let Some(root_folders) = &self.root_folders else {
+            return Ok(());
+        };
+        let folders = root_folders.list().await?;
+        let Some(folder) = RootFolder::find_containing(&folders, destination) else {
+            return Ok(());
+        };
+
+        let required = tokio::fs::metadata(source).await?.len();
+        let free = free_space(destination).await?;
+        if !folder.has_room_for(free, required) {
+            return Err(RadarrError::ValidationError {
+                field: "freeSpace".to_string(),
+                message: format!(
+                    "Not enough free space in {}: {} bytes free, {} needed plus {} reserved",
+                    folder.path, free, required, folder.min_free_space_bytes
+                ),
+            });
+        }
+
+        debug!(
+            "{} has {} bytes free for {} bytes",
+            folder.path, free, required
+        );
+        Ok(())
+    }
+
+    /// Metrics to report to and the root folder label for imports into `dest_dir`
+    ///
+    /// The root folders are only looked up when metrics are reported.
+    async fn import_metrics(&self, dest_dir: &Path) -> Option<(&dyn ImportMetrics, String)> {
+        let metrics = self.metrics.as_deref()?;
+        let root_folder = match &self.root_folders {
+            Some(root_folders) => match root_folders.list().await {
+                Ok(folders) => RootFolder::find_containing(&folders, dest_dir)
+                    .map(|folder| folder.path.clone()),
+                Err(e) => {
+                    debug!("Failed to list root folders for import metrics: {}", e);
+                    None
+                }
+            },
+            None => None,
+        };
+        Some((
+            metrics,
+            root_folder.unwrap_or_else(|| NO_ROOT_FOLDER.to_string()),
+        ))
+    }
+
+    /// Report how a single file import went
+    async fn record_single_import(&self, dest_dir: &Path, result: &ImportResult) {
+        let Some((metrics, root_folder)) = self.import_metrics(dest_dir).await else {
+            return;
+        };
+        if let Some(analyzed) = &result.analyzed_file {
+            metrics.record_analysis(&root_folder, analyzed.confidence);
+        }
+        Self::record_result(metrics, &root_folder, result);
+    }
+
+    /// Report the transfer and outcome of an analyzed file's import
+    fn record_result(metrics: &dyn ImportMetrics, root_folder: &str, result: &ImportResult) {
+        if let Some(hardlink) = &result.hardlink_result {
+            metrics.record_transfer(root_folder, hardlink.is_hardlink, hardlink.file_size);
+        }
+        metrics.record_import(root_folder, result.duration, result.failure_reason);
+    }
+
+    /// Write a forensic bundle for a file that failed to import
+    ///
+    /// When no rename was planned, the rename is computed again so the
+    /// bundle shows what it would have been, or why it could not be.
+    async fn capture_failure(
+        &self,
+        correlation_id: &str,
+        result: &ImportResult,
+        scanned_files: &[DetectedFile],
+        dest_dir: &Path,
+    ) {
+        let Some(store) = &self.forensics else {
+            return;
+        };
+
+        let source = &result.detected_file.path;
+        let error = result
+            .error
+            .clone()
+            .unwrap_or_else(|| "Import failed".to_string());
+        let mut bundle = ForensicBundle::new(correlation_id, source, dest_dir, error, &self.config);
+        bundle.scanned_files = scanned_files.to_vec();
+        bundle.analysis = result.analyzed_file.clone();
+        bundle.confidence = result.analyzed_file.as_ref().map(|file| file.confidence);
+        bundle.rename = match (&result.rename_result, &result.analyzed_file) {
+            (Some(planned), _) => RenameComputation {
+                planned: Some(planned.clone()),
+                error: None,
+            },
+            (None, Some(analyzed)) => {
+                match self.rename_engine().generate_filename(analyzed, dest_dir) {
+                    Ok(planned) => RenameComputation {
+                        planned: Some(planned),
+                        error: None,
+                    },
+                    Err(e) => RenameComputation {
+                        planned: None,
+                        error: Some(e.to_string()),
+                    },
+                }
+            }
+            (None, None) => RenameComputation::default(),
+        };
+        bundle.hardlink = result.hardlink_result.clone();
+
+        let mut paths = vec![
+            (source.clone(), "source"),
+            (dest_dir.to_path_buf(), "destination"),
+        ];
+        if let Some(parent) = source.parent() {
+            paths.insert(1, (parent.to_path_buf(), "source_dir"));
+        }
+        if let Some(planned) = &bundle.rename.planned {
+            paths.push((planned.folder_path.clone(), "target_dir"));
+            paths.push((planned.new_path.clone(), "target"));
+        }
+        for (path, role) in paths {
+            bundle
+                .filesystem
+                .push(FilesystemCheck::probe(&path, role).await);
+        }
+
+        if let Err(e) = store.save(&bundle).await {
+            warn!(
+                "Failed to write forensic bundle for {}: {}",
+                source.display(),
+                e
+            );
+        }
+    }
+
+    /// Write a forensic bundle for an import whose scan failed
+    async fn capture_scan_failure(
+        &self,
+        correlation_id: &str,
+        source_dir: &Path,
+        dest_dir: &Path,
+        error: &RadarrError,
+    ) {
+        let Some(store) = &self.forensics else {
+            return;
+        };
+
+        let mut bundle = ForensicBundle::new(
+            correlation_id,
+            source_dir,
+            dest_dir,
+            format!("Scan failed: {}", error),
+            &self.config,
+        );
+        bundle.filesystem = vec![
+            FilesystemCheck::probe(source_dir, "source").await,
+            FilesystemCheck::probe(dest_dir, "destination").await,
+        ];
+
+        if let Err(e) = store.save(&bundle).await {
+            warn!(
+                "Failed to write forensic bundle for {}: {}",
+                source_dir.display(),
+                e
+            );
+        }
+    }
+
+    /// Remove a transcode output once it is no longer needed
+    async fn discard_transcode(&self, transcode: &Option<TranscodeOutput>) {
+        let Some(transcode) = transcode else {
+            return;
+        };
+        if let Err(e) = tokio::fs::remove_file(&transcode.output).await {
+            warn!(
+                "Failed to remove transcode output {}: {}",
+                transcode.output.display(),
+                e
+            );
+        }
+    }
+
     /// Generate comprehensive statistics for the import operation
     fn generate_stats(
         &self,
         detected_files: &[DetectedFile],
         import_results: &[ImportResult],
+        scan: ScanStats,
         total_duration: Duration,
     ) -> ImportStats {
         let files_scanned = detected_files.len();
@@ -479,29 +1266,62 @@ impl ImportPipeline {
             total_duration,
             hardlinks_created,
             files_copied,
+            scan,
+            extras_imported: 0,
+            extras_deleted: 0,
         }
     }
 
+    /// Store forensic bundles of failed imports are written to, if any
+    pub fn forensics(&self) -> Option<&Arc<ForensicBundleStore>> {
+        self.forensics.as_ref()
+    }
+
     /// Get configuration for this pipeline
+    ///
+    /// The naming settings are the ones the pipeline was created with, see
+    /// [`ImportPipeline::rename_config`] for those in use.
     pub fn config(&self) -> &ImportConfig {
         &self.config
     }
 
+    /// Naming settings files are imported with
+    pub fn rename_config(&self) -> RenameConfig {
+        self.rename_engine().config().clone()
+    }
+
+    /// Name files imported from now on with `config`
+    pub fn set_rename_config(&self, config: RenameConfig) {
+        *self
+            .rename_engine
+            .write()
+            .unwrap_or_else(|e| e.into_inner()) = RenameEngine::new(config);
+    }
+
+    fn rename_engine(&self) -> RwLockReadGuard<'_, RenameEngine> {
+        self.rename_engine.read().unwrap_or_else(|e| e.into_inner())
+    }
+
     /// Update pipeline configuration
     pub fn update_config(&mut self, config: ImportConfig) {
         self.config = config.clone();
-        self.file_scanner = FileScanner::new(config.scan_config);
+        let file_scanner = FileScanner::new(config.scan_config);
+        self.file_scanner = match &self.event_bus {
+            Some(event_bus) => file_scanner.with_event_bus(event_bus.clone()),
+            None => file_scanner,
+        };
         self.hardlink_manager = HardlinkManager::new(config.hardlink_config);
-        self.rename_engine = RenameEngine::new(config.rename_config);
+        self.rename_engine = RwLock::new(RenameEngine::new(config.rename_config));
+        self.transcoder = Transcoder::new(config.transcode_config);
+        self.extractor = Extractor::new(config.extract_config);
     }
 
     /// Validate that the pipeline is properly configured
     pub fn validate_config(&self) -> Result<(), RadarrError> {
         // Validate rename template
-        self.rename_engine
-            .validate_template(&self.config.rename_config.movie_template)?;
-        self.rename_engine
-            .validate_template(&self.config.rename_config.folder_template)?;
+        let rename_engine = self.rename_engine();
+        rename_engine.validate_template(&rename_engine.config().movie_template)?;
+        rename_engine.validate_template(&rename_engine.config().folder_template)?;
 
         // Validate confidence threshold
         if self.config.min_confidence < 0.0 || self.config.min_confidence > 1.0 {
@@ -570,6 +1390,195 @@ mod tests {
         // In dry run, no actual files should be moved
     }
 
+    #[tokio::test]
+    async fn test_extras_are_imported_or_deleted() {
+        let temp_dir = TempDir::new().unwrap();
+        let source_dir = temp_dir
+            .path()
+            .join("The.Matrix.1999.1080p.BluRay.x264-GROUP");
+        let dest_dir = temp_dir.path().join("dest");
+        fs::create_dir_all(source_dir.join("Featurettes")).unwrap();
+
+        fs::write(
+            source_dir.join("The.Matrix.1999.1080p.BluRay.x264-GROUP.mkv"),
+            vec![0u8; 4096],
+        )
+        .unwrap();
+        let featurette = source_dir.join("Featurettes").join("Bullet Time.mkv");
+        fs::write(&featurette, vec![0u8; 512]).unwrap();
+        let sample = source_dir.join("the.matrix.1999.sample.mkv");
+        fs::write(&sample, vec![0u8; 512]).unwrap();
+
+        let config = ImportConfig {
+            min_confidence: 0.1,
+            scan_config: ScanConfig {
+                min_file_size: 1024,
+                min_extra_size: 256,
+                ..Default::default()
+            },
+            extras_config: ExtrasConfig {
+                featurettes: ExtraAction::Import,
+                samples: ExtraAction::Delete,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let pipeline = ImportPipeline::new(config);
+
+        let (stats, results) = pipeline
+            .import_directory_with_results(&source_dir, &dest_dir)
+            .await
+            .unwrap();
+
+        assert_eq!(stats.files_scanned, 3);
+        assert_eq!(results.len(), 1);
+        assert!(results[0].success);
+        assert_eq!(stats.extras_imported, 1);
+        assert_eq!(stats.extras_deleted, 1);
+        assert_eq!(stats.skipped_files, 0);
+
+        let movie_folder = results[0]
+            .rename_result
+            .as_ref()
+            .unwrap()
+            .new_path
+            .parent()
+            .unwrap()
+            .to_path_buf();
+        assert!(movie_folder
+            .join("Featurettes")
+            .join("Bullet Time.mkv")
+            .exists());
+        assert!(featurette.exists());
+        assert!(!sample.exists());
+    }
+
+    #[tokio::test]
+    async fn test_failed_import_writes_forensic_bundle() {
+        let temp_dir = TempDir::new().unwrap();
+        let dest_dir = temp_dir.path().join("dest");
+        // No year or quality, so analysis confidence stays below the minimum
+        let source = temp_dir.path().join("home.video.mkv");
+        fs::write(&source, b"not a movie").unwrap();
+
+        let store = Arc::new(ForensicBundleStore::new(
+            temp_dir.path().join("diagnostics"),
+        ));
+        let pipeline = ImportPipeline::default().with_forensics(store.clone());
+
+        let result = pipeline.import_file(&source, &dest_dir).await.unwrap();
+        assert!(!result.success);
+
+        let summaries = store.list().await.unwrap();
+        assert_eq!(summaries.len(), 1);
+        let bundle: ForensicBundle =
+            serde_json::from_slice(&store.read(&summaries[0].id).await.unwrap()).unwrap();
+        assert_eq!(bundle.source_path, source);
+        assert_eq!(bundle.error, "Confidence too low");
+        assert!(bundle.confidence.unwrap() < bundle.min_confidence);
+        assert!(bundle.rename.planned.is_some());
+        let source_check = &bundle.filesystem[0];
+        assert_eq!(source_check.role, "source");
+        assert_eq!(source_check.size, Some(11));
+        assert!(bundle
+            .filesystem
+            .iter()
+            .any(|check| check.role == "destination" && !check.exists));
+    }
+
+    #[derive(Default)]
+    struct RecordingMetrics {
+        events: std::sync::Mutex<Vec<String>>,
+    }
+
+    impl RecordingMetrics {
+        fn push(&self, event: String) {
+            self.events.lock().unwrap().push(event);
+        }
+    }
+
+    impl ImportMetrics for RecordingMetrics {
+        fn record_scan(&self, root_folder: &str, files_examined: usize, media_files: usize) {
+            self.push(format!(
+                "scan {} {} {}",
+                root_folder, files_examined, media_files
+            ));
+        }
+
+        fn record_analysis(&self, root_folder: &str, _confidence: f32) {
+            self.push(format!("analysis {}", root_folder));
+        }
+
+        fn record_transfer(&self, root_folder: &str, hardlink: bool, bytes: u64) {
+            self.push(format!("transfer {} {} {}", root_folder, hardlink, bytes));
+        }
+
+        fn record_import(
+            &self,
+            root_folder: &str,
+            _duration: Duration,
+            failure: Option<ImportFailureReason>,
+        ) {
+            let outcome = failure.map_or("success", |reason| reason.as_str());
+            self.push(format!("import {} {}", root_folder, outcome));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_import_reports_metrics() {
+        let temp_dir = TempDir::new().unwrap();
+        let source_dir = temp_dir.path().join("source");
+        let dest_dir = temp_dir.path().join("dest");
+        fs::create_dir_all(&source_dir).unwrap();
+        fs::write(
+            source_dir.join("The.Matrix.1999.1080p.BluRay.x264-GROUP.mkv"),
+            vec![0u8; 4096],
+        )
+        .unwrap();
+
+        let config = ImportConfig {
+            min_confidence: 0.1,
+            scan_config: ScanConfig {
+                min_file_size: 1024,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let metrics = Arc::new(RecordingMetrics::default());
+        let pipeline = ImportPipeline::new(config).with_metrics(metrics.clone());
+
+        let (stats, _) = pipeline
+            .import_directory_with_results(&source_dir, &dest_dir)
+            .await
+            .unwrap();
+        assert_eq!(stats.successful_imports, 1);
+
+        let low_confidence = temp_dir.path().join("home.video.mkv");
+        fs::write(&low_confidence, b"not a movie").unwrap();
+        let strict = ImportPipeline::default().with_metrics(metrics.clone());
+        let result = strict
+            .import_file(&low_confidence, &dest_dir)
+            .await
+            .unwrap();
+        assert_eq!(
+            result.failure_reason,
+            Some(ImportFailureReason::LowConfidence)
+        );
+
+        let events = metrics.events.lock().unwrap().clone();
+        assert_eq!(
+            events,
+            vec![
+                "scan none 1 1",
+                "analysis none",
+                "transfer none true 4096",
+                "import none success",
+                "analysis none",
+                "import none low_confidence",
+            ]
+        );
+    }
+
     #[test]
     fn test_config_validation() {
         let mut config = ImportConfig::default();
@@ -586,7 +1595,12 @@ mod tests {
         let import_results = vec![];
         let duration = Duration::from_secs(10);
 
-        let stats = pipeline.generate_stats(&detected_files, &import_results, duration);
+        let stats = pipeline.generate_stats(
+            &detected_files,
+            &import_results,
+            ScanStats::default(),
+            duration,
+        );
 
         assert_eq!(stats.files_scanned, 0);
         assert_eq!(stats.successful_imports, 0);
@@ -2,16 +2,81 @@
 //!
 //! This module provides functionality to rename imported media files
 //! according to configurable templates and naming conventions.
-
-use crate::file_analyzer::AnalyzedFile;
+//!
+//! Templates use Radarr's naming tokens, e.g. `{Movie CleanTitle}`,
+//! `{Release Year}`, `{Quality Full}` or `{MediaInfo VideoCodec}`. A token's
+//! casing sets the value's casing: `{MOVIE TITLE}` is upper case,
+//! `{movie title}` lower case and anything else is left as is. Writing the
+//! token with `.`, `-` or `_` between its words (`{Movie.Title}`) puts that
+//! separator between the value's words. Characters such as `[`, `(` or `-`
+//! just inside the braces (`{[Quality Full]}`, `{-Release Group}`) are only
+//! kept when the value isn't empty, and `{Movie Title:20}` truncates the
+//! value. The original lower case tokens (`{title}`, `{year}`, ...) still
+//! work.
+
+use crate::file_analyzer::{AnalyzedFile, QualityInfo};
 use once_cell::sync::Lazy;
-use radarr_core::RadarrError;
+use radarr_core::{models::Movie, RadarrError};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use tracing::{debug, warn};
 
+/// How colons in names are replaced, as Radarr's colon replacement setting
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ColonReplacement {
+    /// Colons are removed
+    Delete,
+    /// Colons become `-`
+    Dash,
+    /// Colons become ` -`, so `Movie: Title` becomes `Movie - Title`
+    SpaceDash,
+    /// Colons become ` - `
+    SpaceDashSpace,
+    /// A dash between words (`Movie - Title`) and a bare dash elsewhere
+    /// (`12-30`)
+    #[default]
+    Smart,
+}
+
+impl ColonReplacement {
+    /// Replace every colon in `name`
+    pub fn apply(&self, name: &str) -> String {
+        match self {
+            ColonReplacement::Delete => name.replace(':', ""),
+            ColonReplacement::Dash => name.replace(':', "-"),
+            ColonReplacement::SpaceDash => name.replace(':', " -"),
+            ColonReplacement::SpaceDashSpace => name.replace(':', " - "),
+            ColonReplacement::Smart => name.replace(": ", " - ").replace(':', "-"),
+        }
+    }
+}
+
+impl FromStr for ColonReplacement {
+    type Err = RadarrError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().replace(['-', '_'], "").as_str() {
+            "delete" => Ok(ColonReplacement::Delete),
+            "dash" => Ok(ColonReplacement::Dash),
+            "spacedash" => Ok(ColonReplacement::SpaceDash),
+            "spacedashspace" => Ok(ColonReplacement::SpaceDashSpace),
+            "smart" => Ok(ColonReplacement::Smart),
+            _ => Err(RadarrError::ValidationError {
+                field: "colon_replacement".to_string(),
+                message: format!(
+                    "Unknown colon replacement '{}', expected delete, dash, spaceDash, \
+                     spaceDashSpace or smart",
+                    value
+                ),
+            }),
+        }
+    }
+}
+
 /// Configuration for file renaming operations
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RenameConfig {
@@ -27,6 +92,9 @@ pub struct RenameConfig {
     pub max_filename_length: usize,
     /// Whether to create year-based folders
     pub year_folders: bool,
+    /// How colons in names are replaced
+    #[serde(default)]
+    pub colon_replacement: ColonReplacement,
 }
 
 impl Default for RenameConfig {
@@ -34,7 +102,6 @@ impl Default for RenameConfig {
         let mut invalid_chars = HashMap::new();
         invalid_chars.insert('<', "".to_string());
         invalid_chars.insert('>', "".to_string());
-        invalid_chars.insert(':', " -".to_string());
         invalid_chars.insert('"', "'".to_string());
         invalid_chars.insert('|', " -".to_string());
         invalid_chars.insert('?', "".to_string());
@@ -43,12 +110,14 @@ impl Default for RenameConfig {
         invalid_chars.insert('\\', " -".to_string());
 
         Self {
-            movie_template: "{title} ({year}) [{quality}] - {release_group}".to_string(),
+            movie_template: "{title} ({year}){ Edition Tags} [{quality}] - {release_group}"
+                .to_string(),
             folder_template: "{title} ({year})".to_string(),
             replace_existing: false,
             invalid_chars,
             max_filename_length: 255,
             year_folders: true,
+            colon_replacement: ColonReplacement::default(),
         }
     }
 }
@@ -68,6 +137,36 @@ pub struct RenameResult {
     pub folder_path: PathBuf,
 }
 
+/// Movie details for naming tokens that the file name can't provide
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MovieNaming {
+    pub title: String,
+    pub original_title: Option<String>,
+    pub year: Option<i32>,
+    pub tmdb_id: Option<i32>,
+    pub imdb_id: Option<String>,
+    /// Edition of the file, e.g. `Director's Cut`; the one the file analyzer
+    /// found in the file name when unset
+    pub edition: Option<String>,
+    /// Custom formats the file's release matched
+    pub custom_formats: Vec<String>,
+}
+
+impl MovieNaming {
+    /// Naming details of a library movie
+    pub fn from_movie(movie: &Movie) -> Self {
+        Self {
+            title: movie.title.clone(),
+            original_title: movie.original_title.clone(),
+            year: movie.year,
+            tmdb_id: Some(movie.tmdb_id),
+            imdb_id: movie.imdb_id.clone(),
+            edition: None,
+            custom_formats: Vec::new(),
+        }
+    }
+}
+
 /// Template variables available for renaming
 #[derive(Debug, Clone)]
 pub struct TemplateVariables {
@@ -80,10 +179,92 @@ pub struct TemplateVariables {
     pub resolution: String,
     pub audio: String,
     pub extension: String,
+    pub original_title: String,
+    pub imdb_id: String,
+    pub tmdb_id: String,
+    /// Radarr quality name, e.g. `Bluray-1080p`
+    pub quality_title: String,
+    /// `Proper` or `Repack` when the release is one
+    pub quality_revision: String,
+    pub dynamic_range: String,
+    pub dynamic_range_type: String,
+    pub edition: String,
+    pub custom_formats: Vec<String>,
+    /// File name the release came with, without its extension
+    pub original_filename: String,
+}
+
+/// Sample names rendered from the configured templates
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NamingExamples {
+    pub movie_example: String,
+    pub movie_folder_example: String,
 }
 
 /// Regular expressions for template parsing
-static TEMPLATE_VAR_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\{([^}]+)\}").unwrap());
+static TEMPLATE_VAR_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)\{(?P<prefix>[- ._\[(]*)(?P<token>[a-z0-9]+(?:[- ._]+[a-z0-9]+)*)(?::(?P<format>[a-z0-9 +-]+))?(?P<suffix>[- ._)\]]*)\}",
+    )
+    .unwrap()
+});
+
+/// Separators between the words of a token
+static TOKEN_SEPARATOR_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"[- ._]+").unwrap());
+
+/// Tokens of the original template syntax, rendered without casing or
+/// separator changes
+const LEGACY_TOKENS: &[&str] = &[
+    "title",
+    "year",
+    "quality",
+    "codec",
+    "source",
+    "release_group",
+    "resolution",
+    "audio",
+    "extension",
+];
+
+/// Radarr tokens, as lower case words joined by single spaces
+const TOKENS: &[&str] = &[
+    "movie title",
+    "movie cleantitle",
+    "movie titlethe",
+    "movie cleantitlethe",
+    "movie titleyear",
+    "movie cleantitleyear",
+    "movie titlefirstcharacter",
+    "movie originaltitle",
+    "movie cleanoriginaltitle",
+    "release year",
+    "imdbid",
+    "tmdbid",
+    "quality full",
+    "quality title",
+    "quality proper",
+    "mediainfo simple",
+    "mediainfo full",
+    "mediainfo videocodec",
+    "mediainfo audiocodec",
+    "mediainfo videodynamicrange",
+    "mediainfo videodynamicrangetype",
+    "release group",
+    "edition tags",
+    "custom formats",
+    "custom format",
+    "original title",
+    "original filename",
+];
+
+/// Casing a token asks for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TokenCase {
+    Upper,
+    Lower,
+    AsIs,
+}
 
 /// File rename engine
 pub struct RenameEngine {
@@ -101,16 +282,32 @@ impl RenameEngine {
         Self::new(RenameConfig::default())
     }
 
+    /// Naming settings of this engine
+    pub fn config(&self) -> &RenameConfig {
+        &self.config
+    }
+
     /// Generate a new filename based on the analyzed file
     pub fn generate_filename(
         &self,
         analyzed_file: &AnalyzedFile,
         base_path: &Path,
+    ) -> Result<RenameResult, RadarrError> {
+        self.generate_movie_filename(analyzed_file, None, base_path)
+    }
+
+    /// Generate a new filename, taking the title, year and IDs from `movie`
+    /// instead of the file name when given
+    pub fn generate_movie_filename(
+        &self,
+        analyzed_file: &AnalyzedFile,
+        movie: Option<&MovieNaming>,
+        base_path: &Path,
     ) -> Result<RenameResult, RadarrError> {
         debug!("Generating filename for: {}", analyzed_file.path.display());
 
         // Extract template variables from analyzed file
-        let variables = self.extract_template_variables(analyzed_file)?;
+        let variables = self.extract_template_variables(analyzed_file, movie)?;
 
         // Generate the folder name
         let folder_name = self.apply_template(&self.config.folder_template, &variables)?;
@@ -245,13 +442,15 @@ impl RenameEngine {
     fn extract_template_variables(
         &self,
         analyzed_file: &AnalyzedFile,
+        movie: Option<&MovieNaming>,
     ) -> Result<TemplateVariables, RadarrError> {
-        let title = analyzed_file
-            .title
-            .clone()
+        let title = movie
+            .map(|movie| movie.title.clone())
+            .or_else(|| analyzed_file.title.clone())
             .unwrap_or_else(|| "Unknown Movie".to_string());
-        let year = analyzed_file
-            .year
+        let year = movie
+            .and_then(|movie| movie.year)
+            .or(analyzed_file.year.map(i32::from))
             .map(|y| y.to_string())
             .unwrap_or_default();
 
@@ -273,8 +472,16 @@ impl RenameEngine {
             .unwrap_or("mkv")
             .to_string();
 
+        let original_filename = &analyzed_file.original_filename;
+        let quality_revision = analyzed_file.quality.revision.label().to_string();
+        let edition = movie
+            .and_then(|movie| movie.edition.clone())
+            .or_else(|| analyzed_file.edition.clone())
+            .unwrap_or_default();
+        let hdr = analyzed_file.quality.hdr.clone().unwrap_or_default();
+
         Ok(TemplateVariables {
-            title,
+            title: title.clone(),
             year,
             quality,
             codec,
@@ -283,6 +490,29 @@ impl RenameEngine {
             resolution,
             audio,
             extension,
+            original_title: movie
+                .and_then(|movie| movie.original_title.clone())
+                .unwrap_or(title),
+            imdb_id: movie
+                .and_then(|movie| movie.imdb_id.clone())
+                .unwrap_or_default(),
+            tmdb_id: movie
+                .and_then(|movie| movie.tmdb_id)
+                .map(|id| id.to_string())
+                .unwrap_or_default(),
+            quality_title: quality_title(&analyzed_file.quality),
+            quality_revision,
+            dynamic_range: if hdr.is_empty() {
+                String::new()
+            } else {
+                "HDR".to_string()
+            },
+            dynamic_range_type: hdr,
+            edition,
+            custom_formats: movie
+                .map(|movie| movie.custom_formats.clone())
+                .unwrap_or_default(),
+            original_filename: original_filename.clone(),
         })
     }
 
@@ -322,36 +552,29 @@ impl RenameEngine {
         template: &str,
         variables: &TemplateVariables,
     ) -> Result<String, RadarrError> {
-        let mut result = template.to_string();
-
-        // Replace all template variables
-        result = TEMPLATE_VAR_REGEX
-            .replace_all(&result, |caps: &regex::Captures| {
-                let var_name = &caps[1];
-                match var_name {
-                    "title" => &variables.title,
-                    "year" => &variables.year,
-                    "quality" => &variables.quality,
-                    "codec" => &variables.codec,
-                    "source" => &variables.source,
-                    "release_group" => &variables.release_group,
-                    "resolution" => &variables.resolution,
-                    "audio" => &variables.audio,
-                    "extension" => &variables.extension,
-                    _ => {
-                        warn!("Unknown template variable: {}", var_name);
-                        ""
-                    }
+        let mut result = TEMPLATE_VAR_REGEX
+            .replace_all(template, |caps: &regex::Captures| {
+                let token = &caps["token"];
+                let value = if LEGACY_TOKENS.contains(&token) {
+                    legacy_token_value(token, variables).to_string()
+                } else {
+                    render_token(token, caps.name("format").map(|m| m.as_str()), variables)
+                };
+                if value.is_empty() {
+                    return String::new();
                 }
+                format!("{}{}{}", &caps["prefix"], value, &caps["suffix"])
             })
             .to_string();
 
         // Clean up extra spaces and brackets with empty content
+        result = result.replace("[]", "").replace("()", "");
+        while result.contains("  ") {
+            result = result.replace("  ", " ");
+        }
+        // Separators left dangling by empty tokens at either end
         result = result
-            .replace("[]", "")
-            .replace("()", "")
-            .replace("  ", " ")
-            .trim()
+            .trim_matches(|c: char| c == ' ' || c == '-' || c == '_')
             .to_string();
 
         Ok(result)
@@ -359,7 +582,7 @@ impl RenameEngine {
 
     /// Sanitize filename by replacing invalid characters
     fn sanitize_filename(&self, filename: &str) -> Result<String, RadarrError> {
-        let mut sanitized = filename.to_string();
+        let mut sanitized = self.config.colon_replacement.apply(filename);
 
         // Replace invalid characters
         for (invalid_char, replacement) in &self.config.invalid_chars {
@@ -422,25 +645,286 @@ impl RenameEngine {
 
         // Check for valid variable names
         for caps in TEMPLATE_VAR_REGEX.captures_iter(template) {
-            let var_name = &caps[1];
-            if !matches!(
-                var_name,
-                "title"
-                    | "year"
-                    | "quality"
-                    | "codec"
-                    | "source"
-                    | "release_group"
-                    | "resolution"
-                    | "audio"
-                    | "extension"
-            ) {
-                warn!("Unknown template variable: {}", var_name);
+            let token = &caps["token"];
+            if !LEGACY_TOKENS.contains(&token) && !TOKENS.contains(&token_key(token).as_str()) {
+                warn!("Unknown template variable: {}", token);
             }
         }
 
         Ok(())
     }
+
+    /// Render the configured templates for a sample movie
+    pub fn naming_examples(&self) -> Result<NamingExamples, RadarrError> {
+        self.validate_template(&self.config.movie_template)?;
+        self.validate_template(&self.config.folder_template)?;
+
+        let analyzed_file = AnalyzedFile {
+            path: PathBuf::from(
+                "The.Movie.Title.2010.Ultimate.Extended.Edition.1080p.BluRay.x264.DTS-RlsGrp.mkv",
+            ),
+            title: Some("The Movie Title".to_string()),
+            year: Some(2010),
+            quality: QualityInfo {
+                resolution: Some("1080p".to_string()),
+                codec: Some("x264".to_string()),
+                audio: Some("DTS".to_string()),
+                source: Some("BluRay".to_string()),
+                hdr: None,
+                revision: Default::default(),
+            },
+            release_group: Some("RlsGrp".to_string()),
+            edition: Some("Ultimate Extended Edition".to_string()),
+            is_sample: false,
+            confidence: 1.0,
+            original_filename:
+                "The.Movie.Title.2010.Ultimate.Extended.Edition.1080p.BluRay.x264.DTS-RlsGrp"
+                    .to_string(),
+        };
+        let movie = MovieNaming {
+            title: "The Movie: Title".to_string(),
+            original_title: Some("Le Titre du Film".to_string()),
+            year: Some(2010),
+            tmdb_id: Some(345691),
+            imdb_id: Some("tt0066921".to_string()),
+            edition: Some("Ultimate Extended Edition".to_string()),
+            custom_formats: vec!["Surround Sound".to_string(), "x264".to_string()],
+        };
+
+        let variables = self.extract_template_variables(&analyzed_file, Some(&movie))?;
+        let movie_name = self.apply_template(&self.config.movie_template, &variables)?;
+        let folder_name = self.apply_template(&self.config.folder_template, &variables)?;
+        Ok(NamingExamples {
+            movie_example: format!(
+                "{}.{}",
+                self.sanitize_filename(&movie_name)?,
+                variables.extension
+            ),
+            movie_folder_example: self.sanitize_filename(&folder_name)?,
+        })
+    }
+}
+
+/// Value of one of the original lower case tokens
+fn legacy_token_value<'a>(token: &str, variables: &'a TemplateVariables) -> &'a str {
+    match token {
+        "title" => &variables.title,
+        "year" => &variables.year,
+        "quality" => &variables.quality,
+        "codec" => &variables.codec,
+        "source" => &variables.source,
+        "release_group" => &variables.release_group,
+        "resolution" => &variables.resolution,
+        "audio" => &variables.audio,
+        "extension" => &variables.extension,
+        _ => "",
+    }
+}
+
+/// Lower case words of a token joined by single spaces, e.g. `movie title`
+/// for `{Movie.Title}`
+fn token_key(token: &str) -> String {
+    TOKEN_SEPARATOR_REGEX
+        .split(token)
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Render a Radarr token with its casing, separator and format applied
+fn render_token(token: &str, format: Option<&str>, variables: &TemplateVariables) -> String {
+    let key = token_key(token);
+    let mut value = match key.as_str() {
+        "movie title" => variables.title.clone(),
+        "movie cleantitle" => clean_title(&variables.title),
+        "movie titlethe" => title_the(&variables.title),
+        "movie cleantitlethe" => clean_title(&title_the(&variables.title)),
+        "movie titleyear" => with_year(&variables.title, &variables.year),
+        "movie cleantitleyear" => with_year(&clean_title(&variables.title), &variables.year),
+        "movie titlefirstcharacter" => title_the(&variables.title)
+            .chars()
+            .find(|c| c.is_alphanumeric())
+            .map(|c| c.to_uppercase().to_string())
+            .unwrap_or_default(),
+        "movie originaltitle" => variables.original_title.clone(),
+        "movie cleanoriginaltitle" => clean_title(&variables.original_title),
+        "release year" => variables.year.clone(),
+        "imdbid" => variables.imdb_id.clone(),
+        "tmdbid" => variables.tmdb_id.clone(),
+        "quality full" => join_words(&[&variables.quality_title, &variables.quality_revision]),
+        "quality title" => variables.quality_title.clone(),
+        "quality proper" => variables.quality_revision.clone(),
+        "mediainfo simple" | "mediainfo full" => join_words(&[
+            &canonical_codec(&variables.codec),
+            &canonical_audio(&variables.audio),
+        ]),
+        "mediainfo videocodec" => canonical_codec(&variables.codec),
+        "mediainfo audiocodec" => canonical_audio(&variables.audio),
+        "mediainfo videodynamicrange" => variables.dynamic_range.clone(),
+        "mediainfo videodynamicrangetype" => variables.dynamic_range_type.clone(),
+        "release group" => variables.release_group.clone(),
+        "edition tags" => variables.edition.clone(),
+        "custom formats" => variables.custom_formats.join(" "),
+        // `{Custom Format:Name}` shows the format only when the release matched it
+        "custom format" => format
+            .and_then(|name| {
+                variables
+                    .custom_formats
+                    .iter()
+                    .find(|format| format.eq_ignore_ascii_case(name))
+            })
+            .cloned()
+            .unwrap_or_default(),
+        "original title" | "original filename" => variables.original_filename.clone(),
+        _ => {
+            warn!("Unknown template variable: {}", token);
+            String::new()
+        }
+    };
+
+    if let Some(length) = format.and_then(|format| format.trim().parse::<i64>().ok()) {
+        value = truncate(&value, length);
+    }
+
+    let separator = TOKEN_SEPARATOR_REGEX
+        .find(token)
+        .and_then(|m| m.as_str().chars().next())
+        .filter(|separator| *separator != ' ');
+    if let Some(separator) = separator {
+        value = value
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(&separator.to_string());
+    }
+
+    match token_case(token) {
+        TokenCase::Upper => value.to_uppercase(),
+        TokenCase::Lower => value.to_lowercase(),
+        TokenCase::AsIs => value,
+    }
+}
+
+/// Casing asked for by the letters of a token
+fn token_case(token: &str) -> TokenCase {
+    let letters: Vec<char> = token.chars().filter(|c| c.is_alphabetic()).collect();
+    if letters.iter().all(|c| c.is_uppercase()) {
+        TokenCase::Upper
+    } else if letters.iter().all(|c| c.is_lowercase()) {
+        TokenCase::Lower
+    } else {
+        TokenCase::AsIs
+    }
+}
+
+/// Keep the first `length` characters, or the last ones when negative
+fn truncate(value: &str, length: i64) -> String {
+    let chars: Vec<char> = value.chars().collect();
+    let keep = (length.unsigned_abs() as usize).min(chars.len());
+    let kept: String = if length >= 0 {
+        chars[..keep].iter().collect()
+    } else {
+        chars[chars.len() - keep..].iter().collect()
+    };
+    kept.trim().to_string()
+}
+
+/// Title without punctuation, `&` spelled out, as Radarr's clean title
+fn clean_title(title: &str) -> String {
+    let title = title.replace('&', " and ").replace(['/', '\\'], " ");
+    title
+        .chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace() || *c == '-')
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Leading article moved to the end, e.g. `Matrix, The`
+fn title_the(title: &str) -> String {
+    for article in ["The ", "A ", "An "] {
+        if let Some(rest) = title.strip_prefix(article) {
+            if !rest.is_empty() {
+                return format!("{}, {}", rest, article.trim_end());
+            }
+        }
+    }
+    title.to_string()
+}
+
+fn with_year(title: &str, year: &str) -> String {
+    if year.is_empty() {
+        title.to_string()
+    } else {
+        format!("{} ({})", title, year)
+    }
+}
+
+/// Non-empty words joined by spaces
+fn join_words(words: &[&str]) -> String {
+    words
+        .iter()
+        .filter(|word| !word.is_empty())
+        .copied()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Radarr's quality name, e.g. `Bluray-1080p` or `WEBDL-2160p`
+fn quality_title(quality: &QualityInfo) -> String {
+    let source =
+        quality
+            .source
+            .as_deref()
+            .map(|source| match source.to_ascii_lowercase().as_str() {
+                "bluray" | "bdrip" => "Bluray".to_string(),
+                "web-dl" => "WEBDL".to_string(),
+                "webrip" => "WEBRip".to_string(),
+                "dvdrip" => "DVD".to_string(),
+                "ts" => "TELESYNC".to_string(),
+                "tc" => "TELECINE".to_string(),
+                other => other.to_uppercase(),
+            });
+    let resolution = quality.resolution.as_deref().map(|resolution| {
+        match resolution.to_ascii_lowercase().as_str() {
+            "4k" | "uhd" => "2160p".to_string(),
+            other => other.to_string(),
+        }
+    });
+    match (source, resolution) {
+        (Some(source), Some(resolution)) => format!("{}-{}", source, resolution),
+        (Some(source), None) => source,
+        (None, Some(resolution)) => resolution,
+        (None, None) => "Unknown".to_string(),
+    }
+}
+
+/// Video codec spelled the way Radarr's media info tokens spell it
+fn canonical_codec(codec: &str) -> String {
+    match codec.to_ascii_lowercase().as_str() {
+        "" => String::new(),
+        "hevc" | "h265" => "h265".to_string(),
+        "avc" | "h264" => "h264".to_string(),
+        "vvc" | "h266" => "h266".to_string(),
+        "xvid" => "XviD".to_string(),
+        "divx" => "DivX".to_string(),
+        "av1" => "AV1".to_string(),
+        "vp9" => "VP9".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Audio codec spelled the way Radarr's media info tokens spell it
+fn canonical_audio(audio: &str) -> String {
+    match audio.to_ascii_lowercase().as_str() {
+        "" => String::new(),
+        "truehd" => "TrueHD".to_string(),
+        "atmos" => "TrueHD Atmos".to_string(),
+        "eac3" => "EAC3".to_string(),
+        "dd" | "ac3" => "AC3".to_string(),
+        "flac" => "FLAC".to_string(),
+        other => other.to_uppercase(),
+    }
 }
 
 impl Default for RenameEngine {
@@ -467,8 +951,10 @@ mod tests {
                 audio: Some("DTS".to_string()),
                 source: Some("BLURAY".to_string()),
                 hdr: None,
+                revision: Default::default(),
             },
             release_group: Some("GROUP".to_string()),
+            edition: None,
             is_sample: false,
             confidence: 0.9,
             original_filename: "The.Matrix.1999.1080p.BluRay.x264.DTS-GROUP".to_string(),
@@ -521,7 +1007,9 @@ mod tests {
     fn test_apply_template() {
         let engine = RenameEngine::default();
         let analyzed_file = create_test_analyzed_file();
-        let variables = engine.extract_template_variables(&analyzed_file).unwrap();
+        let variables = engine
+            .extract_template_variables(&analyzed_file, None)
+            .unwrap();
 
         let result = engine
             .apply_template("{title} ({year}) [{quality}]", &variables)
@@ -541,6 +1029,7 @@ mod tests {
             source: Some("BLURAY".to_string()),
             hdr: Some("HDR10".to_string()),
             audio: None,
+            revision: Default::default(),
         };
 
         let result = engine.format_quality_string(&quality).unwrap();
@@ -575,4 +1064,90 @@ mod tests {
         assert!(preview.contains("The Matrix"));
         assert!(preview.contains("1999"));
     }
+
+    #[test]
+    fn test_radarr_tokens() {
+        let engine = RenameEngine::default();
+        let analyzed_file = create_test_analyzed_file();
+        let movie = MovieNaming {
+            title: "The Matrix".to_string(),
+            year: Some(1999),
+            tmdb_id: Some(603),
+            imdb_id: Some("tt0133093".to_string()),
+            custom_formats: vec!["DTS".to_string(), "x264".to_string()],
+            ..MovieNaming::default()
+        };
+        let variables = engine
+            .extract_template_variables(&analyzed_file, Some(&movie))
+            .unwrap();
+        let render = |template: &str| engine.apply_template(template, &variables).unwrap();
+
+        assert_eq!(
+            render("{Movie CleanTitle} ({Release Year}) {Quality Full}"),
+            "The Matrix (1999) Bluray-1080p"
+        );
+        assert_eq!(render("{Movie TitleThe}"), "Matrix, The");
+        assert_eq!(render("{Movie.Title}.{Release.Year}"), "The.Matrix.1999");
+        assert_eq!(
+            render("{MOVIE TITLE} {movie title}"),
+            "THE MATRIX the matrix"
+        );
+        assert_eq!(
+            render("{ImdbId} {TmdbId} {MediaInfo VideoCodec} {MediaInfo AudioCodec}"),
+            "tt0133093 603 x264 DTS"
+        );
+        assert_eq!(render("{Custom Formats}"), "DTS x264");
+        assert_eq!(render("{Movie Title:3}"), "The");
+        // Prefix and suffix only appear with a value
+        assert_eq!(render("{Movie Title}{ [Edition Tags]}"), "The Matrix");
+        assert_eq!(
+            render("{Movie Title}{ [Quality Title]}"),
+            "The Matrix [Bluray-1080p]"
+        );
+    }
+
+    #[test]
+    fn test_colon_replacement() {
+        assert_eq!(
+            ColonReplacement::Delete.apply("Alien: Covenant"),
+            "Alien Covenant"
+        );
+        assert_eq!(
+            ColonReplacement::Dash.apply("Alien: Covenant"),
+            "Alien- Covenant"
+        );
+        assert_eq!(
+            ColonReplacement::SpaceDash.apply("Alien: Covenant"),
+            "Alien - Covenant"
+        );
+        assert_eq!(
+            ColonReplacement::Smart.apply("Alien: Covenant 12:30"),
+            "Alien - Covenant 12-30"
+        );
+        assert_eq!(
+            "space-dash-space".parse::<ColonReplacement>().unwrap(),
+            ColonReplacement::SpaceDashSpace
+        );
+        assert!("colon".parse::<ColonReplacement>().is_err());
+    }
+
+    #[test]
+    fn test_naming_examples() {
+        let engine = RenameEngine::new(RenameConfig {
+            movie_template: "{Movie CleanTitle} ({Release Year}) {Edition Tags} {Quality Full}"
+                .to_string(),
+            folder_template: "{Movie Title} ({Release Year}) {tmdb-{TmdbId}}".to_string(),
+            ..RenameConfig::default()
+        });
+
+        let examples = engine.naming_examples().unwrap();
+        assert_eq!(
+            examples.movie_example,
+            "The Movie Title (2010) Ultimate Extended Edition Bluray-1080p.mkv"
+        );
+        assert_eq!(
+            examples.movie_folder_example,
+            "The Movie - Title (2010) {tmdb-345691}"
+        );
+    }
 }
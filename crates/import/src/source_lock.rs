@@ -0,0 +1,148 @@
+//! Per-source-path import locks
+//!
+//! A completed download can be picked up by more than one trigger (watch
+//! folder scan, queue completion event) at the same time. Whoever takes the
+//! lock for a source path imports it; everyone else backs off. Locks are held
+//! in a process-wide set, and optionally also through an
+//! [`ImportLockRepository`] so other instances sharing the database see them.
+
+use once_cell::sync::Lazy;
+use radarr_core::{domain::repositories::ImportLockRepository, Result};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tracing::{debug, warn};
+
+/// Source paths being imported by this process
+static IN_PROCESS: Lazy<Arc<Mutex<HashSet<PathBuf>>>> =
+    Lazy::new(|| Arc::new(Mutex::new(HashSet::new())));
+
+/// Locks on import source paths
+#[derive(Clone)]
+pub struct SourcePathLocks {
+    in_process: Arc<Mutex<HashSet<PathBuf>>>,
+    shared: Option<Arc<dyn ImportLockRepository>>,
+}
+
+impl Default for SourcePathLocks {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SourcePathLocks {
+    /// Locks shared by everything in this process
+    pub fn new() -> Self {
+        Self {
+            in_process: IN_PROCESS.clone(),
+            shared: None,
+        }
+    }
+
+    /// Also take a database lock so other instances are excluded
+    pub fn with_shared_locks(mut self, shared: Arc<dyn ImportLockRepository>) -> Self {
+        self.shared = Some(shared);
+        self
+    }
+
+    /// Lock `source_path`, or `None` if it is already being imported
+    pub async fn try_acquire(&self, source_path: &Path) -> Result<Option<SourcePathGuard>> {
+        let path = tokio::fs::canonicalize(source_path)
+            .await
+            .unwrap_or_else(|_| source_path.to_path_buf());
+
+        if !self.in_process.lock().unwrap().insert(path.clone()) {
+            debug!("{} is already being imported here", path.display());
+            return Ok(None);
+        }
+
+        let mut guard = SourcePathGuard {
+            in_process: self.in_process.clone(),
+            shared: None,
+            path,
+        };
+
+        if let Some(shared) = &self.shared {
+            let key = guard.path.to_string_lossy().into_owned();
+            // Dropping the guard on either early return frees the in-process slot
+            if !shared.try_lock(&key).await? {
+                debug!("{} is already being imported by another instance", key);
+                return Ok(None);
+            }
+            guard.shared = Some(shared.clone());
+        }
+
+        Ok(Some(guard))
+    }
+}
+
+/// Held lock on a source path, released by [`SourcePathGuard::release`]
+///
+/// Dropping the guard without releasing frees the in-process lock at once and
+/// the database lock in the background.
+pub struct SourcePathGuard {
+    in_process: Arc<Mutex<HashSet<PathBuf>>>,
+    shared: Option<Arc<dyn ImportLockRepository>>,
+    path: PathBuf,
+}
+
+impl SourcePathGuard {
+    /// The locked source path
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Release the lock
+    pub async fn release(mut self) {
+        if let Some(shared) = self.shared.take() {
+            let key = self.path.to_string_lossy();
+            if let Err(e) = shared.unlock(&key).await {
+                warn!("Failed to release import lock for {}: {}", key, e);
+            }
+        }
+    }
+}
+
+impl Drop for SourcePathGuard {
+    fn drop(&mut self) {
+        self.in_process.lock().unwrap().remove(&self.path);
+
+        if let Some(shared) = self.shared.take() {
+            let key = self.path.to_string_lossy().into_owned();
+            match tokio::runtime::Handle::try_current() {
+                Ok(handle) => {
+                    handle.spawn(async move {
+                        if let Err(e) = shared.unlock(&key).await {
+                            warn!("Failed to release import lock for {}: {}", key, e);
+                        }
+                    });
+                }
+                Err(_) => warn!("Import lock for {} leaked: no runtime to release it", key),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_source_path_is_locked_once() {
+        let temp_dir = TempDir::new().unwrap();
+        let locks = SourcePathLocks::new();
+
+        let guard = locks.try_acquire(temp_dir.path()).await.unwrap();
+        assert!(guard.is_some());
+
+        // Another handle shares the process-wide set; trailing components
+        // resolve to the same canonical path
+        let other = SourcePathLocks::new();
+        let alias = temp_dir.path().join(".");
+        assert!(other.try_acquire(&alias).await.unwrap().is_none());
+
+        guard.unwrap().release().await;
+        assert!(other.try_acquire(temp_dir.path()).await.unwrap().is_some());
+    }
+}
@@ -0,0 +1,430 @@
+//! Pre-import transcoding
+//!
+//! An optional stage that runs ffmpeg over a downloaded file before it is
+//! imported, for example to remux mkv into mp4 or to reorder audio tracks.
+//! The output is written to a work directory and imported in place of the
+//! original; the download itself is never modified, so seeding continues.
+
+use radarr_core::RadarrError;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::Command;
+use tokio::sync::mpsc::UnboundedSender;
+use tracing::{debug, info, warn};
+
+/// Number of ffmpeg log lines kept for error messages
+const ERROR_TAIL_LINES: usize = 20;
+
+/// A named set of ffmpeg arguments
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscodeProfile {
+    /// Profile name, referenced by [`TranscodeConfig::active_profile`]
+    pub name: String,
+    /// Extension of the output file without the dot, e.g. `mp4`
+    pub output_extension: String,
+    /// ffmpeg arguments placed between the input and the output file
+    pub args: Vec<String>,
+    /// Only transcode sources with these extensions; empty means all
+    #[serde(default)]
+    pub source_extensions: Vec<String>,
+}
+
+impl TranscodeProfile {
+    /// Lossless mkv → mp4 remux keeping every video and audio track
+    pub fn remux_mp4() -> Self {
+        Self {
+            name: "remux-mp4".to_string(),
+            output_extension: "mp4".to_string(),
+            args: [
+                "-map",
+                "0:v",
+                "-map",
+                "0:a",
+                "-c",
+                "copy",
+                "-movflags",
+                "+faststart",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+            source_extensions: vec!["mkv".to_string()],
+        }
+    }
+
+    /// Whether this profile should run for `source`
+    pub fn applies_to(&self, source: &Path) -> bool {
+        if self.source_extensions.is_empty() {
+            return true;
+        }
+        source
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| {
+                self.source_extensions
+                    .iter()
+                    .any(|allowed| allowed.trim_start_matches('.').eq_ignore_ascii_case(ext))
+            })
+    }
+}
+
+/// Configuration for the transcode stage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscodeConfig {
+    /// Whether files are transcoded before import
+    pub enabled: bool,
+    /// ffmpeg binary, looked up on `PATH` when not absolute
+    pub ffmpeg_path: PathBuf,
+    /// Available profiles
+    pub profiles: Vec<TranscodeProfile>,
+    /// Profile to run; the first profile when unset
+    pub active_profile: Option<String>,
+    /// Where outputs are written before import; the system temp dir when unset
+    pub work_directory: Option<PathBuf>,
+    /// Give up on a single file after this many seconds
+    pub timeout_seconds: u64,
+}
+
+impl Default for TranscodeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            ffmpeg_path: PathBuf::from("ffmpeg"),
+            profiles: vec![TranscodeProfile::remux_mp4()],
+            active_profile: None,
+            work_directory: None,
+            timeout_seconds: 4 * 60 * 60,
+        }
+    }
+}
+
+impl TranscodeConfig {
+    /// The profile that will run, if any
+    pub fn active_profile(&self) -> Option<&TranscodeProfile> {
+        match &self.active_profile {
+            Some(name) => self.profiles.iter().find(|profile| &profile.name == name),
+            None => self.profiles.first(),
+        }
+    }
+
+    fn work_directory(&self) -> PathBuf {
+        self.work_directory
+            .clone()
+            .unwrap_or_else(|| std::env::temp_dir().join("radarr-transcode"))
+    }
+}
+
+/// A finished transcode
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscodeOutput {
+    /// File that was transcoded
+    pub source: PathBuf,
+    /// File to import instead of the source
+    pub output: PathBuf,
+    /// Profile that produced the output
+    pub profile: String,
+    /// Time ffmpeg took
+    pub duration: Duration,
+}
+
+/// Runs ffmpeg according to a [`TranscodeConfig`]
+#[derive(Debug, Clone)]
+pub struct Transcoder {
+    config: TranscodeConfig,
+}
+
+impl Transcoder {
+    /// Create a transcoder
+    pub fn new(config: TranscodeConfig) -> Self {
+        Self { config }
+    }
+
+    /// Get the configuration
+    pub fn config(&self) -> &TranscodeConfig {
+        &self.config
+    }
+
+    /// The profile to run for `source`, or `None` if it should be imported as is
+    pub fn profile_for(&self, source: &Path) -> Option<&TranscodeProfile> {
+        if !self.config.enabled {
+            return None;
+        }
+        self.config
+            .active_profile()
+            .filter(|profile| profile.applies_to(source))
+    }
+
+    /// Where the output for `source` is written
+    pub fn output_path(&self, source: &Path, profile: &TranscodeProfile) -> PathBuf {
+        let stem = source
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "output".to_string());
+        let output = self
+            .config
+            .work_directory()
+            .join(format!("{}.{}", stem, profile.output_extension));
+
+        // Never let ffmpeg overwrite its own input
+        if output == source {
+            return output
+                .with_file_name(format!("{}.transcoded.{}", stem, profile.output_extension));
+        }
+        output
+    }
+
+    /// Transcode `source` with `profile`
+    ///
+    /// Progress in percent is sent on `progress` while ffmpeg runs. A failed
+    /// or timed out run leaves no output behind.
+    pub async fn transcode(
+        &self,
+        source: &Path,
+        profile: &TranscodeProfile,
+        progress: Option<&UnboundedSender<f32>>,
+    ) -> Result<TranscodeOutput, RadarrError> {
+        let started = Instant::now();
+        let output = self.output_path(source, profile);
+        if let Some(parent) = output.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        info!(
+            "Transcoding {} with profile '{}' to {}",
+            source.display(),
+            profile.name,
+            output.display()
+        );
+
+        let mut child = Command::new(&self.config.ffmpeg_path)
+            .args(build_args(profile, source, &output))
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| RadarrError::ExternalServiceError {
+                service: "ffmpeg".to_string(),
+                error: format!(
+                    "Failed to start {}: {}",
+                    self.config.ffmpeg_path.display(),
+                    e
+                ),
+            })?;
+
+        // ffmpeg reports the input duration on stderr and the position on stdout
+        let duration_ms = Arc::new(AtomicU64::new(0));
+        let stderr = child.stderr.take().map(|stderr| {
+            let duration_ms = duration_ms.clone();
+            tokio::spawn(async move {
+                let mut tail = VecDeque::with_capacity(ERROR_TAIL_LINES);
+                let mut lines = BufReader::new(stderr).lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    if let Some(seconds) = parse_duration(&line) {
+                        duration_ms.store((seconds * 1000.0) as u64, Ordering::Relaxed);
+                    }
+                    if tail.len() == ERROR_TAIL_LINES {
+                        tail.pop_front();
+                    }
+                    tail.push_back(line);
+                }
+                tail.into_iter().collect::<Vec<_>>().join("\n")
+            })
+        });
+        let stdout = child.stdout.take();
+
+        let run = async {
+            if let Some(stdout) = stdout {
+                let mut lines = BufReader::new(stdout).lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    let (Some(position), Some(sender)) = (parse_progress(&line), progress) else {
+                        continue;
+                    };
+                    let total = duration_ms.load(Ordering::Relaxed) as f64 / 1000.0;
+                    if total > 0.0 {
+                        // 100% is only reported once ffmpeg exits cleanly
+                        let _ = sender.send((position / total * 100.0).clamp(0.0, 99.0) as f32);
+                    }
+                }
+            }
+            child.wait().await
+        };
+
+        let status =
+            match tokio::time::timeout(Duration::from_secs(self.config.timeout_seconds), run).await
+            {
+                Ok(status) => status?,
+                Err(_) => {
+                    let _ = child.kill().await;
+                    remove_partial(&output).await;
+                    return Err(RadarrError::Timeout {
+                        operation: format!("transcode of {}", source.display()),
+                    });
+                }
+            };
+
+        let log_tail = match stderr {
+            Some(task) => task.await.unwrap_or_default(),
+            None => String::new(),
+        };
+
+        if !status.success() {
+            remove_partial(&output).await;
+            return Err(RadarrError::ExternalServiceError {
+                service: "ffmpeg".to_string(),
+                error: format!("exited with {}: {}", status, log_tail),
+            });
+        }
+
+        let size = tokio::fs::metadata(&output)
+            .await
+            .map(|metadata| metadata.len())
+            .unwrap_or(0);
+        if size == 0 {
+            remove_partial(&output).await;
+            return Err(RadarrError::ExternalServiceError {
+                service: "ffmpeg".to_string(),
+                error: format!("produced no output for {}", source.display()),
+            });
+        }
+
+        if let Some(sender) = progress {
+            let _ = sender.send(100.0);
+        }
+        info!(
+            "Transcoded {} in {:.1}s ({} bytes)",
+            source.display(),
+            started.elapsed().as_secs_f64(),
+            size
+        );
+
+        Ok(TranscodeOutput {
+            source: source.to_path_buf(),
+            output,
+            profile: profile.name.clone(),
+            duration: started.elapsed(),
+        })
+    }
+}
+
+/// Full ffmpeg command line for one run
+fn build_args(profile: &TranscodeProfile, source: &Path, output: &Path) -> Vec<OsString> {
+    let mut args: Vec<OsString> = ["-hide_banner", "-nostdin", "-y", "-i"]
+        .into_iter()
+        .map(OsString::from)
+        .collect();
+    args.push(source.as_os_str().to_owned());
+    args.extend(profile.args.iter().map(OsString::from));
+    args.extend(["-progress", "pipe:1", "-nostats"].map(OsString::from));
+    args.push(output.as_os_str().to_owned());
+    args
+}
+
+/// Parse `HH:MM:SS.ss` into seconds
+fn parse_timestamp(value: &str) -> Option<f64> {
+    let mut parts = value.trim().splitn(3, ':');
+    let hours: f64 = parts.next()?.parse().ok()?;
+    let minutes: f64 = parts.next()?.parse().ok()?;
+    let seconds: f64 = parts.next()?.parse().ok()?;
+    Some(hours * 3600.0 + minutes * 60.0 + seconds)
+}
+
+/// Input duration from an ffmpeg log line such as
+/// `  Duration: 01:58:12.34, start: 0.000000, bitrate: 9023 kb/s`
+fn parse_duration(line: &str) -> Option<f64> {
+    let rest = line.trim_start().strip_prefix("Duration:")?;
+    parse_timestamp(rest.split(',').next()?)
+}
+
+/// Output position in seconds from a `-progress` line
+///
+/// `out_time_ms` is, despite its name, in microseconds like `out_time_us`.
+fn parse_progress(line: &str) -> Option<f64> {
+    let (key, value) = line.trim().split_once('=')?;
+    match key {
+        "out_time_us" | "out_time_ms" => value.parse::<f64>().ok().map(|us| us / 1_000_000.0),
+        "out_time" => parse_timestamp(value),
+        _ => None,
+    }
+}
+
+async fn remove_partial(output: &Path) {
+    match tokio::fs::remove_file(output).await {
+        Ok(()) => debug!("Removed partial output {}", output.display()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => warn!(
+            "Failed to remove partial output {}: {}",
+            output.display(),
+            e
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_selection() {
+        let mut config = TranscodeConfig::default();
+        let transcoder = Transcoder::new(config.clone());
+        assert!(transcoder.profile_for(Path::new("/dl/Movie.mkv")).is_none());
+
+        config.enabled = true;
+        let transcoder = Transcoder::new(config.clone());
+        assert_eq!(
+            transcoder
+                .profile_for(Path::new("/dl/Movie.MKV"))
+                .map(|profile| profile.name.as_str()),
+            Some("remux-mp4")
+        );
+        assert!(transcoder.profile_for(Path::new("/dl/Movie.mp4")).is_none());
+
+        config.active_profile = Some("missing".to_string());
+        assert!(Transcoder::new(config)
+            .profile_for(Path::new("/dl/Movie.mkv"))
+            .is_none());
+    }
+
+    #[test]
+    fn test_output_path_and_args() {
+        let transcoder = Transcoder::new(TranscodeConfig {
+            enabled: true,
+            work_directory: Some(PathBuf::from("/work")),
+            ..Default::default()
+        });
+        let profile = TranscodeProfile::remux_mp4();
+        let source = Path::new("/dl/Movie.2021.mkv");
+
+        let output = transcoder.output_path(source, &profile);
+        assert_eq!(output, PathBuf::from("/work/Movie.2021.mp4"));
+        assert_eq!(
+            transcoder.output_path(Path::new("/work/Movie.2021.mp4"), &profile),
+            PathBuf::from("/work/Movie.2021.transcoded.mp4")
+        );
+
+        let args = build_args(&profile, source, &output);
+        assert_eq!(args[4], OsString::from("/dl/Movie.2021.mkv"));
+        assert_eq!(args.last(), Some(&OsString::from("/work/Movie.2021.mp4")));
+        assert!(args.contains(&OsString::from("pipe:1")));
+    }
+
+    #[test]
+    fn test_parse_ffmpeg_output() {
+        assert_eq!(
+            parse_duration("  Duration: 01:00:30.50, start: 0.000000, bitrate: 9023 kb/s"),
+            Some(3630.5)
+        );
+        assert_eq!(parse_duration("  Duration: N/A, bitrate: N/A"), None);
+        assert_eq!(parse_progress("out_time_us=1500000"), Some(1.5));
+        assert_eq!(parse_progress("out_time=00:00:02.000000"), Some(2.0));
+        assert_eq!(parse_progress("progress=continue"), None);
+    }
+}
@@ -62,6 +62,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         tmdb_id: None,
         categories: vec![2000], // Movies
         indexer_ids: vec![],
+        excluded_indexer_ids: vec![],
         limit: Some(3),
         offset: None,
         min_seeders: Some(5),
@@ -1,6 +1,7 @@
 //! Release name parser for extracting quality information
 
 use once_cell::sync::Lazy;
+use radarr_core::models::{AudioCodec, VideoCodec};
 use regex::Regex;
 use serde_json::{json, Value};
 use std::collections::HashMap;
@@ -105,7 +106,16 @@ fn normalize_source(source: &str) -> String {
 }
 
 /// Extract codec from release name
+///
+/// H.266/VVC, AV1 and VP9 use the codec taxonomy's names; older codecs keep
+/// the scene names (`x264`, `x265`) the scorer expects.
 fn extract_codec(name: &str) -> Option<String> {
+    if let Some(codec @ (VideoCodec::H266 | VideoCodec::Av1(_) | VideoCodec::Vp9)) =
+        VideoCodec::parse(name)
+    {
+        return Some(codec.name().to_string());
+    }
+
     static CODEC_REGEX: Lazy<Regex> = Lazy::new(|| {
         Regex::new(r"(?i)\b(x264|x265|H\.?264|H\.?265|HEVC|AVC|XviD|DivX|VC-1)\b").unwrap()
     });
@@ -127,7 +137,14 @@ fn normalize_codec(codec: &str) -> String {
 }
 
 /// Extract audio information from release name
+///
+/// Known codecs use the codec taxonomy's names, which tell DD+ Atmos from
+/// TrueHD Atmos; other formats fall back to the raw tag.
 fn extract_audio(name: &str) -> Option<String> {
+    if let Some(codec) = AudioCodec::parse(name) {
+        return Some(codec.name().to_string());
+    }
+
     static AUDIO_REGEX: Lazy<Regex> = Lazy::new(|| {
         Regex::new(r"(?i)\b(DTS-HD\.?MA|DTS-HD|TRUEHD|TrueHD|DD\+?5\.1|DD\+?7\.1|AC3|DTS|AAC|MP3|FLAC|ATMOS|DTS-X)\b").unwrap()
     });
@@ -267,7 +284,10 @@ fn calculate_quality_score(quality: &HashMap<String, Value>) -> i32 {
     // Codec scoring
     if let Some(codec) = quality.get("codec").and_then(|v| v.as_str()) {
         score += match codec {
+            "H.266" => 25,
+            c if c.starts_with("AV1") => 22,
             "x265" => 20,
+            "VP9" => 16,
             "x264" => 15,
             "XviD" => 10,
             _ => 0,
@@ -277,12 +297,14 @@ fn calculate_quality_score(quality: &HashMap<String, Value>) -> i32 {
     // Audio scoring
     if let Some(audio) = quality.get("audio").and_then(|v| v.as_str()) {
         score += match audio {
+            "TrueHD Atmos" => 18,
+            "DTS:X" => 16,
             "TrueHD" | "DTS-HD MA" => 15,
-            "DTS-HD" | "Atmos" | "DTS-X" => 12,
+            "DD+ Atmos" | "DTS-HD" | "FLAC" | "PCM" => 12,
             "DTS" => 10,
-            "DD+5.1" | "DD+7.1" => 8,
-            "DD5.1" | "AC3" => 5,
-            "AAC" => 3,
+            "DD+" => 8,
+            "DD" => 5,
+            "AAC" | "Opus" => 3,
             _ => 0,
         };
     }
@@ -377,6 +399,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_new_codec_parsing() {
+        assert_eq!(
+            extract_codec("Movie.2024.2160p.WEB-DL.H.266-GROUP"),
+            Some("H.266".to_string())
+        );
+        assert_eq!(
+            extract_codec("Movie.2024.2160p.WEB-DL.AV1.Main10-GROUP"),
+            Some("AV1 Main10".to_string())
+        );
+        assert_eq!(
+            extract_audio("Movie.2024.2160p.WEB-DL.DDP5.1.Atmos.H.265-GROUP"),
+            Some("DD+ Atmos".to_string())
+        );
+        assert_eq!(
+            extract_audio("Movie.2024.2160p.BluRay.TrueHD.7.1.Atmos.x265-GROUP"),
+            Some("TrueHD Atmos".to_string())
+        );
+    }
+
     #[test]
     fn test_quality_score() {
         let quality = parse_quality("Movie.2024.2160p.BluRay.x265.TrueHD.Atmos.HDR-GROUP");
@@ -88,6 +88,7 @@ fn test_hdbits_search_request_building() {
         tmdb_id: None,
         categories: vec![],
         indexer_ids: vec![],
+        excluded_indexer_ids: vec![],
         limit: None,
         offset: None,
         min_seeders: None,
@@ -119,6 +120,7 @@ fn test_hdbits_search_request_with_imdb() {
         tmdb_id: None,
         categories: vec![],
         indexer_ids: vec![],
+        excluded_indexer_ids: vec![],
         limit: None,
         offset: None,
         min_seeders: None,
@@ -167,6 +169,7 @@ async fn test_indexer_client_interface() {
         tmdb_id: None,
         categories: vec![2000],
         indexer_ids: vec![],
+        excluded_indexer_ids: vec![],
         limit: Some(5),
         offset: None,
         min_seeders: Some(1),
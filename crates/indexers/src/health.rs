@@ -0,0 +1,75 @@
+//! Indexer availability for the health checks behind `/api/v3/health`
+
+use crate::prowlarr::IndexerClient;
+use async_trait::async_trait;
+use chrono::Utc;
+use radarr_core::{i18n::translate, HealthCheck, HealthIssue};
+use std::sync::Arc;
+
+const WIKI_ANCHOR: &str = "indexers-are-unavailable-due-to-failures";
+
+/// Reports Prowlarr being unreachable and indexers disabled after failures
+pub struct IndexerCheck {
+    client: Arc<dyn IndexerClient + Send + Sync>,
+}
+
+impl IndexerCheck {
+    pub fn new(client: Arc<dyn IndexerClient + Send + Sync>) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl HealthCheck for IndexerCheck {
+    fn name(&self) -> &'static str {
+        "IndexerCheck"
+    }
+
+    async fn check(&self) -> Vec<HealthIssue> {
+        let unavailable = |error: String| {
+            vec![HealthIssue::error(
+                self.name(),
+                translate("health-indexers-unavailable", &[("error", error)]),
+                WIKI_ANCHOR,
+            )]
+        };
+        let indexers = match self.client.get_indexers().await {
+            Ok(indexers) => indexers,
+            Err(e) => return unavailable(e.to_string()),
+        };
+
+        let enabled: Vec<_> = indexers.iter().filter(|indexer| indexer.enable).collect();
+        if enabled.is_empty() {
+            return vec![HealthIssue::error(
+                self.name(),
+                translate("health-no-indexers-enabled", &[]),
+                "no-indexers-available-with-automatic-search-enabled",
+            )];
+        }
+
+        let now = Utc::now();
+        let disabled: Vec<&str> = enabled
+            .iter()
+            .filter(|indexer| indexer.status.disabled_till.is_some_and(|till| till > now))
+            .map(|indexer| indexer.name.as_str())
+            .collect();
+        if disabled.is_empty() {
+            Vec::new()
+        } else if disabled.len() == enabled.len() {
+            vec![HealthIssue::error(
+                self.name(),
+                translate("health-all-indexers-disabled", &[]),
+                WIKI_ANCHOR,
+            )]
+        } else {
+            vec![HealthIssue::warning(
+                self.name(),
+                translate(
+                    "health-indexers-disabled",
+                    &[("names", disabled.join(", "))],
+                ),
+                WIKI_ANCHOR,
+            )]
+        }
+    }
+}
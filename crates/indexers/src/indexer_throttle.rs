@@ -0,0 +1,385 @@
+//! Per-indexer rate limiting and adaptive backoff
+//!
+//! Prowlarr fans a search out to every indexer it names, so one global rate
+//! limit cannot keep a strict tracker happy without slowing all the others.
+//! Each indexer gets its own sliding one-minute window, and indexers that
+//! answer with 429s or temporary failures are backed off exponentially
+//! (honouring Retry-After) and left out of searches until the backoff ends.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+const WINDOW: Duration = Duration::from_secs(60);
+
+/// Per-indexer limits and backoff bounds
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexerThrottleConfig {
+    /// Requests per minute for indexers without their own limit; 0 for no limit
+    pub default_requests_per_minute: u32,
+    /// Requests per minute by indexer id
+    pub requests_per_minute: HashMap<i32, u32>,
+    /// Backoff after the first failure
+    pub initial_backoff: Duration,
+    /// Upper bound of the backoff, including Retry-After
+    pub max_backoff: Duration,
+}
+
+impl Default for IndexerThrottleConfig {
+    fn default() -> Self {
+        Self {
+            default_requests_per_minute: 0,
+            requests_per_minute: HashMap::new(),
+            initial_backoff: Duration::from_secs(30),
+            max_backoff: Duration::from_secs(3600),
+        }
+    }
+}
+
+impl IndexerThrottleConfig {
+    fn limit_for(&self, indexer_id: i32) -> u32 {
+        self.requests_per_minute
+            .get(&indexer_id)
+            .copied()
+            .unwrap_or(self.default_requests_per_minute)
+    }
+}
+
+/// Why an indexer was left out of a search
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Throttled {
+    /// Its requests per minute are used up
+    RateLimit(Duration),
+    /// It is backing off after 429s or failures
+    Backoff(Duration),
+}
+
+impl Throttled {
+    /// Time until the indexer may be searched again
+    pub fn wait(&self) -> Duration {
+        match self {
+            Throttled::RateLimit(wait) | Throttled::Backoff(wait) => *wait,
+        }
+    }
+}
+
+/// Request and throttling counters of one indexer
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexerThrottleMetrics {
+    pub indexer_id: i32,
+    /// Requests per minute allowed; 0 for no limit
+    pub requests_per_minute: u32,
+    /// Searches sent to the indexer
+    pub requests: u64,
+    /// Searches the indexer was left out of by the rate limit
+    pub rate_limit_skips: u64,
+    /// Searches the indexer was left out of while backing off
+    pub backoff_skips: u64,
+    /// 429 responses
+    pub rate_limited_responses: u64,
+    /// Temporary failures
+    pub failures: u64,
+    pub consecutive_failures: u32,
+    /// Seconds left of the current backoff
+    pub backoff_remaining_seconds: Option<u64>,
+    pub last_rate_limited_at: Option<DateTime<Utc>>,
+    pub last_failure_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Default)]
+struct IndexerState {
+    requests: VecDeque<Instant>,
+    backoff_until: Option<Instant>,
+    metrics: IndexerThrottleMetrics,
+}
+
+/// Rate limits and backoff state of every indexer searched
+#[derive(Debug, Default)]
+pub struct IndexerThrottle {
+    config: IndexerThrottleConfig,
+    indexers: Mutex<HashMap<i32, IndexerState>>,
+}
+
+impl IndexerThrottle {
+    pub fn new(config: IndexerThrottleConfig) -> Self {
+        Self {
+            config,
+            indexers: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn config(&self) -> &IndexerThrottleConfig {
+        &self.config
+    }
+
+    /// Take a request slot for the indexer, or say why it has to sit out
+    pub fn try_acquire(&self, indexer_id: i32) -> Result<(), Throttled> {
+        self.try_acquire_at(indexer_id, Instant::now())
+    }
+
+    fn try_acquire_at(&self, indexer_id: i32, now: Instant) -> Result<(), Throttled> {
+        let limit = self.config.limit_for(indexer_id);
+        let mut indexers = self.indexers.lock().unwrap_or_else(|e| e.into_inner());
+        let state = indexers.entry(indexer_id).or_default();
+        state.metrics.indexer_id = indexer_id;
+        state.metrics.requests_per_minute = limit;
+
+        if let Some(until) = state.backoff_until {
+            if until > now {
+                state.metrics.backoff_skips += 1;
+                return Err(Throttled::Backoff(until - now));
+            }
+            state.backoff_until = None;
+        }
+
+        while state
+            .requests
+            .front()
+            .is_some_and(|&at| now.duration_since(at) >= WINDOW)
+        {
+            state.requests.pop_front();
+        }
+        if limit > 0 && state.requests.len() >= limit as usize {
+            state.metrics.rate_limit_skips += 1;
+            let oldest = state.requests[0];
+            return Err(Throttled::RateLimit(WINDOW - now.duration_since(oldest)));
+        }
+
+        state.requests.push_back(now);
+        state.metrics.requests += 1;
+        Ok(())
+    }
+
+    /// The indexer answered normally, ending any failure streak
+    pub fn record_success(&self, indexer_id: i32) {
+        let mut indexers = self.indexers.lock().unwrap_or_else(|e| e.into_inner());
+        let state = indexers.entry(indexer_id).or_default();
+        state.metrics.indexer_id = indexer_id;
+        state.metrics.consecutive_failures = 0;
+    }
+
+    /// The indexer answered with a 429; back off for its Retry-After if given
+    pub fn record_rate_limited(&self, indexer_id: i32, retry_after: Option<Duration>) -> Duration {
+        self.record_rate_limited_at(indexer_id, retry_after, Instant::now())
+    }
+
+    fn record_rate_limited_at(
+        &self,
+        indexer_id: i32,
+        retry_after: Option<Duration>,
+        now: Instant,
+    ) -> Duration {
+        let mut indexers = self.indexers.lock().unwrap_or_else(|e| e.into_inner());
+        let state = indexers.entry(indexer_id).or_default();
+        state.metrics.indexer_id = indexer_id;
+        state.metrics.rate_limited_responses += 1;
+        state.metrics.last_rate_limited_at = Some(Utc::now());
+        self.back_off(state, retry_after, now)
+    }
+
+    /// The indexer failed temporarily (timeout, 5xx)
+    pub fn record_failure(&self, indexer_id: i32) -> Duration {
+        self.record_failure_at(indexer_id, Instant::now())
+    }
+
+    fn record_failure_at(&self, indexer_id: i32, now: Instant) -> Duration {
+        let mut indexers = self.indexers.lock().unwrap_or_else(|e| e.into_inner());
+        let state = indexers.entry(indexer_id).or_default();
+        state.metrics.indexer_id = indexer_id;
+        state.metrics.failures += 1;
+        state.metrics.last_failure_at = Some(Utc::now());
+        self.back_off(state, None, now)
+    }
+
+    /// Doubles with every consecutive failure; Retry-After wins when longer
+    fn back_off(
+        &self,
+        state: &mut IndexerState,
+        retry_after: Option<Duration>,
+        now: Instant,
+    ) -> Duration {
+        state.metrics.consecutive_failures += 1;
+        let exponent = (state.metrics.consecutive_failures - 1).min(16);
+        let backoff = self
+            .config
+            .initial_backoff
+            .saturating_mul(1 << exponent)
+            .max(retry_after.unwrap_or_default())
+            .min(self.config.max_backoff);
+        state.backoff_until = Some(now + backoff);
+        backoff
+    }
+
+    /// Counters of every indexer seen so far, by indexer id
+    pub fn metrics(&self) -> Vec<IndexerThrottleMetrics> {
+        let now = Instant::now();
+        let indexers = self.indexers.lock().unwrap_or_else(|e| e.into_inner());
+        let mut metrics: Vec<_> = indexers
+            .values()
+            .map(|state| IndexerThrottleMetrics {
+                backoff_remaining_seconds: state
+                    .backoff_until
+                    .filter(|&until| until > now)
+                    .map(|until| (until - now).as_secs().max(1)),
+                ..state.metrics.clone()
+            })
+            .collect();
+        metrics.sort_by_key(|m| m.indexer_id);
+        metrics
+    }
+}
+
+/// Name, help text, type and value of a Prometheus series
+type Series = (
+    &'static str,
+    &'static str,
+    &'static str,
+    fn(&IndexerThrottleMetrics) -> u64,
+);
+
+/// Prometheus text exposition of per-indexer counters
+pub fn render_prometheus(metrics: &[IndexerThrottleMetrics]) -> String {
+    let series: [Series; 6] = [
+        (
+            "requests_total",
+            "Searches sent to the indexer",
+            "counter",
+            |m| m.requests,
+        ),
+        (
+            "rate_limit_skips_total",
+            "Searches skipped by the per-indexer rate limit",
+            "counter",
+            |m| m.rate_limit_skips,
+        ),
+        (
+            "backoff_skips_total",
+            "Searches skipped while the indexer was backing off",
+            "counter",
+            |m| m.backoff_skips,
+        ),
+        (
+            "rate_limited_responses_total",
+            "429 responses from the indexer",
+            "counter",
+            |m| m.rate_limited_responses,
+        ),
+        (
+            "failures_total",
+            "Temporary failures of the indexer",
+            "counter",
+            |m| m.failures,
+        ),
+        (
+            "backoff_seconds",
+            "Seconds left of the indexer's backoff",
+            "gauge",
+            |m| m.backoff_remaining_seconds.unwrap_or(0),
+        ),
+    ];
+
+    let mut output = String::new();
+    for (name, help, kind, value) in series {
+        output.push_str(&format!("# HELP radarr_indexer_{} {}\n", name, help));
+        output.push_str(&format!("# TYPE radarr_indexer_{} {}\n", name, kind));
+        for m in metrics {
+            output.push_str(&format!(
+                "radarr_indexer_{}{{indexer_id=\"{}\"}} {}\n",
+                name,
+                m.indexer_id,
+                value(m)
+            ));
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limits_apply_per_indexer() {
+        let throttle = IndexerThrottle::new(IndexerThrottleConfig {
+            default_requests_per_minute: 3,
+            requests_per_minute: HashMap::from([(7, 1)]),
+            ..IndexerThrottleConfig::default()
+        });
+        let start = Instant::now();
+
+        assert!(throttle.try_acquire_at(7, start).is_ok());
+        assert_eq!(
+            throttle.try_acquire_at(7, start + Duration::from_secs(20)),
+            Err(Throttled::RateLimit(Duration::from_secs(40)))
+        );
+        // Other indexers keep their own budget
+        for _ in 0..3 {
+            assert!(throttle.try_acquire_at(9, start).is_ok());
+        }
+        assert!(throttle.try_acquire_at(9, start).is_err());
+        // The window slides
+        assert!(throttle
+            .try_acquire_at(7, start + Duration::from_secs(60))
+            .is_ok());
+
+        let metrics = throttle.metrics();
+        assert_eq!(metrics[0].indexer_id, 7);
+        assert_eq!(metrics[0].requests, 2);
+        assert_eq!(metrics[0].rate_limit_skips, 1);
+        assert_eq!(metrics[1].requests_per_minute, 3);
+        assert_eq!(metrics[1].rate_limit_skips, 1);
+    }
+
+    #[test]
+    fn test_backoff_grows_and_honours_retry_after() {
+        let throttle = IndexerThrottle::new(IndexerThrottleConfig {
+            initial_backoff: Duration::from_secs(30),
+            max_backoff: Duration::from_secs(600),
+            ..IndexerThrottleConfig::default()
+        });
+        let start = Instant::now();
+
+        assert_eq!(
+            throttle.record_failure_at(4, start),
+            Duration::from_secs(30)
+        );
+        assert_eq!(
+            throttle.try_acquire_at(4, start + Duration::from_secs(10)),
+            Err(Throttled::Backoff(Duration::from_secs(20)))
+        );
+        assert_eq!(
+            throttle.record_failure_at(4, start),
+            Duration::from_secs(60)
+        );
+        assert_eq!(
+            throttle.record_rate_limited_at(4, Some(Duration::from_secs(300)), start),
+            Duration::from_secs(300)
+        );
+        assert_eq!(
+            throttle.record_rate_limited_at(4, Some(Duration::from_secs(7200)), start),
+            Duration::from_secs(600)
+        );
+        assert!(throttle
+            .try_acquire_at(4, start + Duration::from_secs(600))
+            .is_ok());
+
+        throttle.record_success(4);
+        assert_eq!(
+            throttle.record_failure_at(4, start),
+            Duration::from_secs(30)
+        );
+
+        let metrics = &throttle.metrics()[0];
+        assert_eq!(metrics.rate_limited_responses, 2);
+        assert_eq!(metrics.failures, 3);
+        assert_eq!(metrics.backoff_skips, 1);
+        assert!(metrics.last_rate_limited_at.is_some());
+
+        let output = render_prometheus(&throttle.metrics());
+        assert!(output.contains("radarr_indexer_rate_limited_responses_total{indexer_id=\"4\"} 2"));
+        assert!(output.contains("# TYPE radarr_indexer_backoff_seconds gauge"));
+    }
+}
@@ -1,14 +1,18 @@
 //! Radarr indexers module
 //!
 //! This crate provides integration with torrent and NZB indexers
-//! through the Prowlarr API and direct indexer implementations.
+//! through the Prowlarr API and direct indexer implementations
+//! (HDBits, generic Torznab/Newznab).
 //! Includes rate limiting, error handling, circuit breaker pattern,
 //! and production-ready client implementations.
 
 pub mod hdbits;
+pub mod health;
+pub mod indexer_throttle;
 pub mod models;
 pub mod prowlarr;
 pub mod service_health;
+pub mod torznab;
 // pub mod multi_indexer; // TODO: Fix compilation issues
 
 #[cfg(test)]
@@ -16,9 +20,12 @@ pub mod tests;
 
 // Re-export common types
 pub use hdbits::{HDBitsClient, HDBitsConfig, MovieSearchRequest};
+pub use health::IndexerCheck;
+pub use indexer_throttle::{IndexerThrottle, IndexerThrottleConfig, IndexerThrottleMetrics};
 pub use models::*;
 pub use prowlarr::{IndexerClient, ProwlarrClient, ProwlarrConfig, ProwlarrConfigBuilder};
 pub use service_health::{HealthStatus, ServiceHealth, ServiceMetrics};
+pub use torznab::{TorznabCapabilities, TorznabClient, TorznabConfig, TorznabProtocol};
 // pub use multi_indexer::{MultiIndexerService, MultiIndexerConfig, IndexerSearchResult};
 
 #[cfg(test)]
@@ -176,6 +176,9 @@ pub struct SearchRequest {
     /// Specific indexer IDs to search (empty = all enabled)
     pub indexer_ids: Vec<i32>,
 
+    /// Indexer IDs never to search, even when listed in `indexer_ids`
+    pub excluded_indexer_ids: Vec<i32>,
+
     /// Maximum number of results to return
     pub limit: Option<i32>,
 
@@ -242,6 +245,18 @@ impl SearchRequest {
         self.indexer_ids = indexer_ids;
         self
     }
+
+    /// Never search the given indexer IDs
+    pub fn with_excluded_indexers(mut self, indexer_ids: Vec<i32>) -> Self {
+        self.excluded_indexer_ids = indexer_ids;
+        self
+    }
+
+    /// Whether results from `indexer_id` may be used for this request
+    pub fn allows_indexer(&self, indexer_id: i32) -> bool {
+        (self.indexer_ids.is_empty() || self.indexer_ids.contains(&indexer_id))
+            && !self.excluded_indexer_ids.contains(&indexer_id)
+    }
 }
 
 /// Response from a Prowlarr search operation
@@ -3,14 +3,18 @@
 //! This module provides a production-ready client for interacting with Prowlarr,
 //! including search functionality, indexer status checking, and rate limiting.
 
-use crate::models::{IndexerStats, ProwlarrIndexer, SearchRequest, SearchResponse};
+use crate::indexer_throttle::{
+    IndexerThrottle, IndexerThrottleConfig, IndexerThrottleMetrics, Throttled,
+};
+use crate::models::{IndexerStats, ProwlarrIndexer, SearchError, SearchRequest, SearchResponse};
 use crate::service_health::{CircuitBreakerConfig, ServiceHealth};
 use async_trait::async_trait;
-use radarr_core::{RadarrError, Result};
-use reqwest::{Client, Response, StatusCode};
+use radarr_core::{RadarrError, Result, TracedRequest};
+use reqwest::{header::RETRY_AFTER, Client, Response, StatusCode};
+use std::collections::HashMap;
 use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
-use tracing::{debug, info, warn};
+use tracing::{debug, info, instrument, warn};
 use url::Url;
 
 /// Configuration for the Prowlarr client
@@ -28,6 +32,20 @@ pub struct ProwlarrConfig {
     /// Rate limiting: maximum requests per minute
     pub max_requests_per_minute: u32,
 
+    /// Searches per minute for each indexer without its own limit; 0 for no
+    /// limit
+    pub default_indexer_rate_limit: u32,
+
+    /// Searches per minute by indexer ID
+    pub indexer_rate_limits: HashMap<i32, u32>,
+
+    /// Backoff in seconds after an indexer's first 429 or temporary failure,
+    /// doubling with each one after
+    pub backoff_initial_seconds: u64,
+
+    /// Longest backoff in seconds, including Retry-After
+    pub backoff_max_seconds: u64,
+
     /// User agent string to send with requests
     pub user_agent: String,
 
@@ -42,22 +60,40 @@ impl Default for ProwlarrConfig {
             api_key: String::new(),
             timeout: 30,
             max_requests_per_minute: 60,
+            default_indexer_rate_limit: 0,
+            indexer_rate_limits: HashMap::new(),
+            backoff_initial_seconds: 30,
+            backoff_max_seconds: 3600,
             user_agent: "Radarr-Rust/1.0".to_string(),
             verify_ssl: true,
         }
     }
 }
 
+impl ProwlarrConfig {
+    fn throttle_config(&self) -> IndexerThrottleConfig {
+        IndexerThrottleConfig {
+            default_requests_per_minute: self.default_indexer_rate_limit,
+            requests_per_minute: self.indexer_rate_limits.clone(),
+            initial_backoff: Duration::from_secs(self.backoff_initial_seconds),
+            max_backoff: Duration::from_secs(self.backoff_max_seconds),
+        }
+    }
+}
+
+/// How long the indexer list used to plan searches is reused
+const INDEXER_LIST_TTL: Duration = Duration::from_secs(300);
+
 /// Rate limiter for API requests
 #[derive(Debug)]
-struct RateLimiter {
+pub(crate) struct RateLimiter {
     max_requests: u32,
     window_duration: Duration,
     requests: Mutex<Vec<Instant>>,
 }
 
 impl RateLimiter {
-    fn new(max_requests_per_minute: u32) -> Self {
+    pub(crate) fn new(max_requests_per_minute: u32) -> Self {
         Self {
             max_requests: max_requests_per_minute,
             window_duration: Duration::from_secs(60),
@@ -65,7 +101,7 @@ impl RateLimiter {
         }
     }
 
-    async fn wait_if_needed(&self) -> Result<()> {
+    pub(crate) async fn wait_if_needed(&self) -> Result<()> {
         let mut requests = self.requests.lock().await;
         let now = Instant::now();
 
@@ -101,6 +137,8 @@ pub struct ProwlarrClient {
     config: ProwlarrConfig,
     client: Client,
     rate_limiter: RateLimiter,
+    throttle: IndexerThrottle,
+    indexer_list: Mutex<Option<(Instant, Vec<ProwlarrIndexer>)>>,
     base_url: Url,
     health_monitor: ServiceHealth,
 }
@@ -125,6 +163,7 @@ impl ProwlarrClient {
             })?;
 
         let rate_limiter = RateLimiter::new(config.max_requests_per_minute);
+        let throttle = IndexerThrottle::new(config.throttle_config());
 
         // Configure circuit breaker for production reliability
         let circuit_config = CircuitBreakerConfig {
@@ -139,19 +178,138 @@ impl ProwlarrClient {
             config,
             client,
             rate_limiter,
+            throttle,
+            indexer_list: Mutex::new(None),
             base_url,
             health_monitor,
         })
     }
 
     /// Search for releases using the given search request
+    ///
+    /// Prowlarr has no exclusion parameter, and indexers that are rate
+    /// limited or backing off must sit the search out, so the request always
+    /// names the indexers to search.
+    #[instrument(skip(self, request), fields(tmdb_id = ?request.tmdb_id, imdb_id = ?request.imdb_id))]
     pub async fn search(&self, request: &SearchRequest) -> Result<SearchResponse> {
+        let candidates = if request.indexer_ids.is_empty() {
+            allowed_indexer_ids(request, &self.search_indexers().await?)
+        } else {
+            request
+                .indexer_ids
+                .iter()
+                .copied()
+                .filter(|&id| request.allows_indexer(id))
+                .collect()
+        };
+
+        let mut throttled = Vec::new();
+        let mut indexer_ids = Vec::new();
+        for id in candidates {
+            match self.throttle.try_acquire(id) {
+                Ok(()) => indexer_ids.push(id),
+                Err(reason) => {
+                    debug!("Skipping indexer {}: {:?}", id, reason);
+                    throttled.push(throttled_error(id, reason));
+                }
+            }
+        }
+        if indexer_ids.is_empty() {
+            debug!("No indexer may be searched, skipping search");
+            let mut response = empty_response();
+            response.indexers_with_errors = throttled.len() as i32;
+            response.errors = throttled;
+            return Ok(response);
+        }
+        let request = request.clone().with_indexers(indexer_ids.clone());
+
         self.rate_limiter.wait_if_needed().await?;
 
         // Execute with health monitoring and circuit breaker
-        self.health_monitor
-            .execute_request(async { self.search_internal(request).await })
-            .await
+        let result = self
+            .health_monitor
+            .execute_request(async { self.search_internal(&request).await })
+            .await;
+        let mut response = match result {
+            Ok(response) => response,
+            Err(e) => {
+                // Only a single-indexer search pins the failure on the indexer
+                if let [id] = indexer_ids[..] {
+                    self.record_indexer_error(id, &e);
+                }
+                return Err(e);
+            }
+        };
+        self.record_search_outcome(&indexer_ids, &response.errors)
+            .await;
+
+        response
+            .results
+            .retain(|result| request.allows_indexer(result.indexer_id));
+        response.indexers_with_errors += throttled.len() as i32;
+        response.errors.extend(throttled);
+        Ok(response)
+    }
+
+    /// Indexer list for planning searches, refreshed every few minutes
+    async fn search_indexers(&self) -> Result<Vec<ProwlarrIndexer>> {
+        let mut cached = self.indexer_list.lock().await;
+        if let Some((fetched_at, indexers)) = cached.as_ref() {
+            if fetched_at.elapsed() < INDEXER_LIST_TTL {
+                return Ok(indexers.clone());
+            }
+        }
+        let indexers = self.get_indexers().await?;
+        *cached = Some((Instant::now(), indexers.clone()));
+        Ok(indexers)
+    }
+
+    /// Back off indexers that reported errors and clear the others
+    async fn record_search_outcome(&self, indexer_ids: &[i32], errors: &[SearchError]) {
+        let mut failed = HashMap::new();
+        if !errors.is_empty() {
+            let indexers = self.search_indexers().await.unwrap_or_default();
+            for error in errors {
+                let id = indexers
+                    .iter()
+                    .find(|indexer| indexer.name == error.indexer)
+                    .map(|indexer| indexer.id)
+                    .or_else(|| error.indexer.parse().ok());
+                if let Some(id) = id.filter(|id| indexer_ids.contains(id)) {
+                    failed.insert(id, is_rate_limit_error(error));
+                }
+            }
+        }
+
+        for &id in indexer_ids {
+            match failed.get(&id) {
+                Some(true) => {
+                    let backoff = self.throttle.record_rate_limited(id, None);
+                    warn!("Indexer {} is rate limited, backing off {:?}", id, backoff);
+                }
+                Some(false) => {
+                    let backoff = self.throttle.record_failure(id);
+                    warn!("Indexer {} failed, backing off {:?}", id, backoff);
+                }
+                None => self.throttle.record_success(id),
+            }
+        }
+    }
+
+    fn record_indexer_error(&self, indexer_id: i32, error: &RadarrError) {
+        let backoff = match error {
+            RadarrError::RateLimited { retry_after, .. } => self
+                .throttle
+                .record_rate_limited(indexer_id, retry_after.map(Duration::from_secs)),
+            RadarrError::TemporaryError { .. } | RadarrError::Timeout { .. } => {
+                self.throttle.record_failure(indexer_id)
+            }
+            _ => return,
+        };
+        warn!(
+            "Indexer {} search failed ({}), backing off {:?}",
+            indexer_id, error, backoff
+        );
     }
 
     /// Internal search implementation without health monitoring
@@ -218,7 +376,7 @@ impl ProwlarrClient {
             .client
             .get(url)
             .header("X-Api-Key", &self.config.api_key)
-            .send()
+            .send_traced("prowlarr")
             .await
             .map_err(|e| RadarrError::ExternalServiceError {
                 service: "prowlarr".to_string(),
@@ -245,7 +403,7 @@ impl ProwlarrClient {
             .client
             .get(url)
             .header("X-Api-Key", &self.config.api_key)
-            .send()
+            .send_traced("prowlarr")
             .await
             .map_err(|e| RadarrError::ExternalServiceError {
                 service: "prowlarr".to_string(),
@@ -273,7 +431,7 @@ impl ProwlarrClient {
             .client
             .get(url)
             .header("X-Api-Key", &self.config.api_key)
-            .send()
+            .send_traced("prowlarr")
             .await
             .map_err(|e| RadarrError::ExternalServiceError {
                 service: "prowlarr".to_string(),
@@ -301,7 +459,7 @@ impl ProwlarrClient {
             .client
             .post(url)
             .header("X-Api-Key", &self.config.api_key)
-            .send()
+            .send_traced("prowlarr")
             .await
             .map_err(|e| RadarrError::ExternalServiceError {
                 service: "prowlarr".to_string(),
@@ -340,7 +498,7 @@ impl ProwlarrClient {
             .client
             .get(url)
             .header("X-Api-Key", &self.config.api_key)
-            .send()
+            .send_traced("prowlarr")
             .await
             .map_err(|e| RadarrError::ExternalServiceError {
                 service: "prowlarr".to_string(),
@@ -360,6 +518,11 @@ impl ProwlarrClient {
         self.health_monitor.get_metrics().await
     }
 
+    /// Request, throttling and backoff counters of each indexer searched
+    pub fn get_indexer_metrics(&self) -> Vec<IndexerThrottleMetrics> {
+        self.throttle.metrics()
+    }
+
     /// Reset service health monitoring (useful for testing)
     pub async fn reset_health_monitoring(&self) {
         self.health_monitor.reset().await;
@@ -382,7 +545,7 @@ impl ProwlarrClient {
             .get(url)
             .header("X-Api-Key", &self.config.api_key)
             .timeout(Duration::from_secs(5)) // Short timeout for health checks
-            .send()
+            .send_traced("prowlarr")
             .await
             .map_err(|e| RadarrError::ExternalServiceError {
                 service: "prowlarr".to_string(),
@@ -399,6 +562,18 @@ impl ProwlarrClient {
     {
         let status = response.status();
 
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = response
+                .headers()
+                .get(RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse().ok());
+            return Err(RadarrError::RateLimited {
+                service: "prowlarr".to_string(),
+                retry_after,
+            });
+        }
+
         if status.is_success() {
             let text = response
                 .text()
@@ -412,6 +587,14 @@ impl ProwlarrClient {
                 service: "prowlarr".to_string(),
                 error: format!("Failed to parse JSON response: {}", e),
             })
+        } else if matches!(
+            status,
+            StatusCode::BAD_GATEWAY | StatusCode::SERVICE_UNAVAILABLE | StatusCode::GATEWAY_TIMEOUT
+        ) {
+            let error_text = response.text().await.unwrap_or_default();
+            Err(RadarrError::TemporaryError {
+                message: format!("prowlarr HTTP {}: {}", status, error_text),
+            })
         } else {
             let error_text = response.text().await.unwrap_or_default();
             Err(RadarrError::ExternalServiceError {
@@ -422,6 +605,50 @@ impl ProwlarrClient {
     }
 }
 
+/// Whether an indexer's search error means it is throttling us
+fn is_rate_limit_error(error: &SearchError) -> bool {
+    let message = error.message.to_lowercase();
+    error.code.as_deref() == Some("429")
+        || message.contains("429")
+        || message.contains("too many requests")
+        || message.contains("rate limit")
+}
+
+/// Search error reported for an indexer left out of a search
+fn throttled_error(indexer_id: i32, reason: Throttled) -> SearchError {
+    let (message, code) = match reason {
+        Throttled::RateLimit(_) => ("Rate limit reached", "rate_limited"),
+        Throttled::Backoff(_) => ("Backing off after errors", "backoff"),
+    };
+    SearchError {
+        indexer: indexer_id.to_string(),
+        message: format!("{}, retry in {}s", message, reason.wait().as_secs().max(1)),
+        code: Some(code.to_string()),
+    }
+}
+
+/// Enabled indexers a request may search
+pub(crate) fn allowed_indexer_ids(
+    request: &SearchRequest,
+    indexers: &[ProwlarrIndexer],
+) -> Vec<i32> {
+    indexers
+        .iter()
+        .filter(|indexer| indexer.enable && request.allows_indexer(indexer.id))
+        .map(|indexer| indexer.id)
+        .collect()
+}
+
+fn empty_response() -> SearchResponse {
+    SearchResponse {
+        total: 0,
+        results: Vec::new(),
+        indexers_searched: 0,
+        indexers_with_errors: 0,
+        errors: Vec::new(),
+    }
+}
+
 /// Trait for indexer clients to allow for testing and different implementations
 #[async_trait]
 pub trait IndexerClient: Send + Sync {
@@ -446,6 +673,11 @@ pub trait IndexerClient: Send + Sync {
     async fn get_service_metrics(&self) -> crate::service_health::ServiceMetrics {
         crate::service_health::ServiceMetrics::default()
     }
+
+    /// Get per-indexer throttling metrics (optional, default implementation returns none)
+    async fn get_indexer_metrics(&self) -> Vec<IndexerThrottleMetrics> {
+        Vec::new()
+    }
 }
 
 #[async_trait]
@@ -473,6 +705,10 @@ impl IndexerClient for ProwlarrClient {
     async fn get_service_metrics(&self) -> crate::service_health::ServiceMetrics {
         self.get_service_metrics().await
     }
+
+    async fn get_indexer_metrics(&self) -> Vec<IndexerThrottleMetrics> {
+        self.get_indexer_metrics()
+    }
 }
 
 /// Builder for ProwlarrConfig to make configuration easier
@@ -507,6 +743,26 @@ impl ProwlarrConfigBuilder {
         self
     }
 
+    /// Searches per minute for indexers without their own limit
+    pub fn default_indexer_rate_limit(mut self, requests_per_minute: u32) -> Self {
+        self.config.default_indexer_rate_limit = requests_per_minute;
+        self
+    }
+
+    /// Searches per minute for one indexer
+    pub fn indexer_rate_limit(mut self, indexer_id: i32, requests_per_minute: u32) -> Self {
+        self.config
+            .indexer_rate_limits
+            .insert(indexer_id, requests_per_minute);
+        self
+    }
+
+    pub fn backoff(mut self, initial_seconds: u64, max_seconds: u64) -> Self {
+        self.config.backoff_initial_seconds = initial_seconds;
+        self.config.backoff_max_seconds = max_seconds;
+        self
+    }
+
     pub fn user_agent<S: Into<String>>(mut self, agent: S) -> Self {
         self.config.user_agent = agent.into();
         self
@@ -601,4 +857,26 @@ mod tests {
         assert_eq!(config.user_agent, "Test-Agent/1.0");
         assert!(!config.verify_ssl);
     }
+
+    #[test]
+    fn test_indexer_errors_classified_for_backoff() {
+        let error = |message: &str, code: Option<&str>| SearchError {
+            indexer: "Tracker".to_string(),
+            message: message.to_string(),
+            code: code.map(String::from),
+        };
+
+        assert!(is_rate_limit_error(&error(
+            "HTTP 429 Too Many Requests",
+            None
+        )));
+        assert!(is_rate_limit_error(&error("Query limit", Some("429"))));
+        assert!(is_rate_limit_error(&error("API rate limit exceeded", None)));
+        assert!(!is_rate_limit_error(&error("Connection timed out", None)));
+
+        let skipped = throttled_error(12, Throttled::Backoff(Duration::from_millis(300)));
+        assert_eq!(skipped.indexer, "12");
+        assert_eq!(skipped.code.as_deref(), Some("backoff"));
+        assert!(skipped.message.ends_with("retry in 1s"));
+    }
 }
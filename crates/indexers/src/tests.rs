@@ -215,6 +215,68 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_indexer_check_reports_disabled_indexers() {
+        use radarr_core::{HealthCheck, HealthIssueType};
+        use std::sync::Arc;
+
+        let indexer = |id: i32, name: &str, disabled: bool| {
+            let mut indexer = ProwlarrIndexer {
+                id,
+                name: name.to_string(),
+                ..create_test_indexer()
+            };
+            if disabled {
+                indexer.status.disabled_till = Some(Utc::now() + chrono::Duration::hours(1));
+            }
+            indexer
+        };
+
+        let healthy = MockIndexerClient::new().with_indexer(indexer(1, "One", false));
+        let issues = crate::IndexerCheck::new(Arc::new(healthy)).check().await;
+        assert!(issues.is_empty());
+
+        let partly = MockIndexerClient::new()
+            .with_indexer(indexer(1, "One", false))
+            .with_indexer(indexer(2, "Two", true));
+        let issues = crate::IndexerCheck::new(Arc::new(partly)).check().await;
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].issue_type, HealthIssueType::Warning);
+        assert!(issues[0].message.contains("Two"));
+
+        let all = MockIndexerClient::new().with_indexer(indexer(2, "Two", true));
+        let issues = crate::IndexerCheck::new(Arc::new(all)).check().await;
+        assert_eq!(issues[0].issue_type, HealthIssueType::Error);
+
+        let none = MockIndexerClient::new();
+        let issues = crate::IndexerCheck::new(Arc::new(none)).check().await;
+        assert_eq!(issues[0].issue_type, HealthIssueType::Error);
+    }
+
+    #[test]
+    fn test_excluded_indexers_are_not_searched() {
+        let indexer = |id: i32, enable: bool| ProwlarrIndexer {
+            id,
+            enable,
+            ..create_test_indexer()
+        };
+        let indexers = vec![indexer(1, true), indexer(2, true), indexer(3, false)];
+
+        let request = SearchRequest::for_movie_tmdb(603).with_excluded_indexers(vec![2]);
+        assert_eq!(
+            crate::prowlarr::allowed_indexer_ids(&request, &indexers),
+            vec![1]
+        );
+        assert!(request.allows_indexer(1));
+        assert!(!request.allows_indexer(2));
+
+        // Exclusions win over an explicit indexer list
+        let request = request.with_indexers(vec![2, 3]);
+        assert!(!request.allows_indexer(2));
+        assert!(request.allows_indexer(3));
+        assert!(!request.allows_indexer(1));
+    }
+
     #[test]
     fn test_prowlarr_config_builder() {
         let config = ProwlarrConfigBuilder::new()
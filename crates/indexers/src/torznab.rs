@@ -0,0 +1,947 @@
+//! Direct Torznab/Newznab indexer client
+//!
+//! Speaks the Torznab (torrents) and Newznab (usenet) `caps` and `search`
+//! APIs directly so Jackett or native indexers can be used without Prowlarr.
+//! Requested categories are mapped onto the categories the indexer reports in
+//! its capabilities, and requests are rate limited per indexer.
+
+use crate::models::{
+    Category, IndexerCapabilities, IndexerStatus, ProwlarrIndexer, ProwlarrSearchResult,
+    SearchLimits, SearchRequest, SearchResponse,
+};
+use crate::prowlarr::{IndexerClient, RateLimiter};
+use crate::service_health::{CircuitBreakerConfig, ServiceHealth};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use radarr_core::{RadarrError, Result};
+use regex::Regex;
+use reqwest::Client;
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tracing::{debug, info, warn};
+use url::Url;
+
+static ITEM_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?s)<item>(.*?)</item>").unwrap());
+static ATTR_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"<(?:torznab|newznab):attr\s+name="([^"]*)"\s+value="([^"]*)"\s*/?>"#).unwrap()
+});
+static ENCLOSURE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"<enclosure[^>]*\surl="([^"]*)""#).unwrap());
+static ERROR_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"<error\s+code="(\d+)"\s+description="([^"]*)"\s*/?>"#).unwrap());
+static CATEGORY_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"<(?:category|subcat)\s+id="(\d+)"\s+name="([^"]*)""#).unwrap());
+static SERVER_TITLE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"<server[^>]*\stitle="([^"]*)""#).unwrap());
+static LIMITS_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r#"<limits([^>]*)/?>"#).unwrap());
+
+/// Newznab standard movie category
+pub const MOVIES_CATEGORY: i32 = 2000;
+
+/// Flavour of the indexer API
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TorznabProtocol {
+    /// Torznab (torrent) indexer, e.g. Jackett
+    Torznab,
+    /// Newznab (usenet) indexer
+    Newznab,
+}
+
+impl TorznabProtocol {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Torznab => "torznab",
+            Self::Newznab => "newznab",
+        }
+    }
+}
+
+/// Configuration for a single Torznab/Newznab indexer
+#[derive(Debug, Clone)]
+pub struct TorznabConfig {
+    /// Indexer ID used in search results
+    pub indexer_id: i32,
+
+    /// Human-readable indexer name
+    pub name: String,
+
+    /// Base URL of the indexer (e.g., "http://localhost:9117/api/v2.0/indexers/all/results/torznab")
+    pub base_url: String,
+
+    /// Path of the API endpoint relative to the base URL
+    pub api_path: String,
+
+    /// API key for authentication
+    pub api_key: String,
+
+    /// Torznab or Newznab
+    pub protocol: TorznabProtocol,
+
+    /// Categories used when a request doesn't specify any
+    pub categories: Vec<i32>,
+
+    /// Request timeout in seconds
+    pub timeout: u64,
+
+    /// Rate limiting: maximum requests per minute
+    pub max_requests_per_minute: u32,
+
+    /// User agent string to send with requests
+    pub user_agent: String,
+
+    /// Whether to verify SSL certificates
+    pub verify_ssl: bool,
+}
+
+impl Default for TorznabConfig {
+    fn default() -> Self {
+        Self {
+            indexer_id: 1,
+            name: "Torznab".to_string(),
+            base_url: String::new(),
+            api_path: "/api".to_string(),
+            api_key: String::new(),
+            protocol: TorznabProtocol::Torznab,
+            categories: vec![MOVIES_CATEGORY],
+            timeout: 30,
+            max_requests_per_minute: 30,
+            user_agent: "Radarr-Rust/1.0".to_string(),
+            verify_ssl: true,
+        }
+    }
+}
+
+impl TorznabConfig {
+    /// Create a configuration for a Torznab indexer
+    pub fn torznab(
+        name: impl Into<String>,
+        base_url: impl Into<String>,
+        api_key: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            base_url: base_url.into(),
+            api_key: api_key.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Create a configuration for a Newznab indexer
+    pub fn newznab(
+        name: impl Into<String>,
+        base_url: impl Into<String>,
+        api_key: impl Into<String>,
+    ) -> Self {
+        Self {
+            protocol: TorznabProtocol::Newznab,
+            ..Self::torznab(name, base_url, api_key)
+        }
+    }
+
+    /// Set the indexer ID reported in results
+    pub fn with_indexer_id(mut self, indexer_id: i32) -> Self {
+        self.indexer_id = indexer_id;
+        self
+    }
+
+    /// Set the API path (Jackett uses "/api", some indexers use "/api/v1/api")
+    pub fn with_api_path(mut self, api_path: impl Into<String>) -> Self {
+        self.api_path = api_path.into();
+        self
+    }
+
+    /// Set the default categories
+    pub fn with_categories(mut self, categories: Vec<i32>) -> Self {
+        self.categories = categories;
+        self
+    }
+
+    /// Set the rate limit
+    pub fn with_rate_limit(mut self, requests_per_minute: u32) -> Self {
+        self.max_requests_per_minute = requests_per_minute;
+        self
+    }
+
+    /// Validate configuration
+    pub fn validate(&self) -> Result<()> {
+        if self.base_url.is_empty() {
+            return Err(RadarrError::ConfigurationError {
+                field: "base_url".to_string(),
+                message: "Base URL cannot be empty".to_string(),
+            });
+        }
+
+        if self.max_requests_per_minute == 0 {
+            return Err(RadarrError::ConfigurationError {
+                field: "max_requests_per_minute".to_string(),
+                message: "Rate limit must be greater than 0".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Capabilities reported by the indexer's `t=caps` endpoint
+#[derive(Debug, Clone, Default)]
+pub struct TorznabCapabilities {
+    pub server_title: Option<String>,
+    pub search_available: bool,
+    pub movie_search_available: bool,
+    pub movie_search_params: Vec<String>,
+    pub categories: Vec<Category>,
+    pub limit_max: Option<i32>,
+    pub limit_default: Option<i32>,
+}
+
+impl TorznabCapabilities {
+    /// Parse a caps XML document
+    pub fn parse(xml: &str) -> Result<Self> {
+        check_error_response(xml)?;
+
+        if !xml.contains("<caps") {
+            return Err(RadarrError::ExternalServiceError {
+                service: "torznab".to_string(),
+                error: "Invalid caps response".to_string(),
+            });
+        }
+
+        let search_tag = |tag: &str| -> Option<String> {
+            let pattern = format!(r#"<{}\s([^>]*)/?>"#, tag);
+            Regex::new(&pattern)
+                .ok()
+                .and_then(|re| re.captures(xml).map(|c| c[1].to_string()))
+        };
+        let available = |attrs: &Option<String>| {
+            attrs
+                .as_deref()
+                .and_then(|a| attribute_value(a, "available"))
+                .map(|v| v == "yes")
+                .unwrap_or(false)
+        };
+
+        let search = search_tag("search");
+        let movie_search = search_tag("movie-search");
+
+        let movie_search_params = movie_search
+            .as_deref()
+            .and_then(|a| attribute_value(a, "supportedParams"))
+            .map(|p| {
+                p.split(',')
+                    .map(|s| s.trim().to_lowercase())
+                    .filter(|s| !s.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let categories = CATEGORY_REGEX
+            .captures_iter(xml)
+            .filter_map(|c| {
+                c[1].parse().ok().map(|id| Category {
+                    id,
+                    name: decode_xml_entities(&c[2]),
+                    description: None,
+                })
+            })
+            .collect();
+
+        let limits = LIMITS_REGEX.captures(xml).map(|c| c[1].to_string());
+        let limit = |name: &str| {
+            limits
+                .as_deref()
+                .and_then(|a| attribute_value(a, name))
+                .and_then(|v| v.parse().ok())
+        };
+
+        Ok(Self {
+            server_title: SERVER_TITLE_REGEX
+                .captures(xml)
+                .map(|c| decode_xml_entities(&c[1])),
+            search_available: available(&search),
+            movie_search_available: available(&movie_search),
+            movie_search_params,
+            categories,
+            limit_max: limit("max"),
+            limit_default: limit("default"),
+        })
+    }
+
+    /// Whether the movie search supports the given parameter
+    pub fn supports_movie_param(&self, param: &str) -> bool {
+        self.movie_search_available && self.movie_search_params.iter().any(|p| p == param)
+    }
+
+    /// Map requested categories onto the categories the indexer supports.
+    ///
+    /// A requested parent category (e.g. 2000) also matches its subcategories
+    /// (2010, 2040, ...). Falls back to the requested categories when the
+    /// indexer didn't report any.
+    pub fn map_categories(&self, requested: &[i32]) -> Vec<i32> {
+        if self.categories.is_empty() {
+            return requested.to_vec();
+        }
+
+        let mut mapped: Vec<i32> = self
+            .categories
+            .iter()
+            .map(|c| c.id)
+            .filter(|id| {
+                requested
+                    .iter()
+                    .any(|r| r == id || (r % 1000 == 0 && id / 1000 == r / 1000))
+            })
+            .collect();
+        mapped.sort_unstable();
+        mapped.dedup();
+
+        if mapped.is_empty() {
+            requested.to_vec()
+        } else {
+            mapped
+        }
+    }
+}
+
+/// Direct Torznab/Newznab API client
+#[derive(Debug)]
+pub struct TorznabClient {
+    config: TorznabConfig,
+    client: Client,
+    rate_limiter: RateLimiter,
+    api_url: Url,
+    health_monitor: ServiceHealth,
+    capabilities: RwLock<Option<TorznabCapabilities>>,
+}
+
+impl TorznabClient {
+    /// Create a new Torznab/Newznab client
+    pub fn new(config: TorznabConfig) -> Result<Self> {
+        config.validate()?;
+
+        let service = config.protocol.as_str();
+        let api_url = Url::parse(&format!(
+            "{}/{}",
+            config.base_url.trim_end_matches('/'),
+            config.api_path.trim_start_matches('/')
+        ))
+        .map_err(|e| RadarrError::ExternalServiceError {
+            service: service.to_string(),
+            error: format!("Invalid base URL: {}", e),
+        })?;
+
+        let client = Client::builder()
+            .timeout(Duration::from_secs(config.timeout))
+            .user_agent(&config.user_agent)
+            .danger_accept_invalid_certs(!config.verify_ssl)
+            .build()
+            .map_err(|e| RadarrError::ExternalServiceError {
+                service: service.to_string(),
+                error: format!("Failed to create HTTP client: {}", e),
+            })?;
+
+        let rate_limiter = RateLimiter::new(config.max_requests_per_minute);
+
+        let circuit_config = CircuitBreakerConfig {
+            failure_threshold: 5,
+            timeout: Duration::from_secs(60),
+            success_threshold: 3,
+            failure_window: Duration::from_secs(300),
+        };
+        let health_monitor =
+            ServiceHealth::with_config(format!("{}:{}", service, config.name), circuit_config);
+
+        Ok(Self {
+            config,
+            client,
+            rate_limiter,
+            api_url,
+            health_monitor,
+            capabilities: RwLock::new(None),
+        })
+    }
+
+    /// Get the client configuration
+    pub fn config(&self) -> &TorznabConfig {
+        &self.config
+    }
+
+    /// Fetch indexer capabilities, using the cached copy when available
+    pub async fn get_capabilities(&self) -> Result<TorznabCapabilities> {
+        if let Some(caps) = self.capabilities.read().await.as_ref() {
+            return Ok(caps.clone());
+        }
+
+        let caps = self.refresh_capabilities().await?;
+        Ok(caps)
+    }
+
+    /// Fetch capabilities from the indexer and update the cache
+    pub async fn refresh_capabilities(&self) -> Result<TorznabCapabilities> {
+        let body = self.get(&[("t", "caps".to_string())]).await?;
+        let caps = TorznabCapabilities::parse(&body)?;
+
+        info!(
+            "Loaded {} capabilities for '{}': {} categories, movie search {}",
+            self.config.protocol.as_str(),
+            self.config.name,
+            caps.categories.len(),
+            caps.movie_search_available
+        );
+
+        *self.capabilities.write().await = Some(caps.clone());
+        Ok(caps)
+    }
+
+    /// Search for releases
+    pub async fn search(&self, request: &SearchRequest) -> Result<SearchResponse> {
+        self.health_monitor
+            .execute_request(async { self.search_internal(request).await })
+            .await
+    }
+
+    async fn search_internal(&self, request: &SearchRequest) -> Result<SearchResponse> {
+        let caps = self.get_capabilities().await?;
+        let params = self.build_search_params(request, &caps);
+
+        let body = self.get(&params).await?;
+        let mut results = parse_search_results(&body, &self.config)?;
+
+        if let Some(min_seeders) = request.min_seeders {
+            if self.config.protocol == TorznabProtocol::Torznab {
+                results.retain(|r| r.seeders.unwrap_or(0) >= min_seeders);
+            }
+        }
+        if let Some(min_size) = request.min_size {
+            results.retain(|r| r.size.is_none_or(|s| s >= min_size));
+        }
+        if let Some(max_size) = request.max_size {
+            results.retain(|r| r.size.is_none_or(|s| s <= max_size));
+        }
+
+        Ok(SearchResponse {
+            total: results.len() as i32,
+            results,
+            indexers_searched: 1,
+            indexers_with_errors: 0,
+            errors: vec![],
+        })
+    }
+
+    /// Build the query parameters for a search request
+    pub(crate) fn build_search_params(
+        &self,
+        request: &SearchRequest,
+        caps: &TorznabCapabilities,
+    ) -> Vec<(&'static str, String)> {
+        let mut params = Vec::new();
+
+        let imdb_id = request
+            .imdb_id
+            .as_ref()
+            .filter(|_| caps.supports_movie_param("imdbid"));
+        let tmdb_id = request
+            .tmdb_id
+            .filter(|_| caps.supports_movie_param("tmdbid"));
+
+        if caps.movie_search_available {
+            params.push(("t", "movie".to_string()));
+            if let Some(imdb_id) = imdb_id {
+                params.push(("imdbid", imdb_id.trim_start_matches("tt").to_string()));
+            }
+            if let Some(tmdb_id) = tmdb_id {
+                params.push(("tmdbid", tmdb_id.to_string()));
+            }
+        } else {
+            params.push(("t", "search".to_string()));
+        }
+
+        // Fall back to a text query when no ID parameter is usable
+        if imdb_id.is_none() && tmdb_id.is_none() {
+            if let Some(query) = &request.query {
+                params.push(("q", query.clone()));
+            }
+        }
+
+        let requested = if request.categories.is_empty() {
+            &self.config.categories
+        } else {
+            &request.categories
+        };
+        let categories = caps.map_categories(requested);
+        if !categories.is_empty() {
+            params.push((
+                "cat",
+                categories
+                    .iter()
+                    .map(|c| c.to_string())
+                    .collect::<Vec<_>>()
+                    .join(","),
+            ));
+        }
+
+        let limit = match (request.limit, caps.limit_max) {
+            (Some(limit), Some(max)) => Some(limit.min(max)),
+            (limit, _) => limit,
+        };
+        if let Some(limit) = limit {
+            params.push(("limit", limit.to_string()));
+        }
+        if let Some(offset) = request.offset {
+            params.push(("offset", offset.to_string()));
+        }
+        params.push(("extended", "1".to_string()));
+
+        params
+    }
+
+    /// Execute a rate limited GET against the API endpoint
+    async fn get(&self, params: &[(&str, String)]) -> Result<String> {
+        self.rate_limiter.wait_if_needed().await?;
+
+        let service = self.config.protocol.as_str();
+        let mut url = self.api_url.clone();
+        {
+            let mut query_pairs = url.query_pairs_mut();
+            for (key, value) in params {
+                query_pairs.append_pair(key, value);
+            }
+            if !self.config.api_key.is_empty() {
+                query_pairs.append_pair("apikey", &self.config.api_key);
+            }
+        }
+
+        debug!(
+            "Querying {} indexer '{}' ({:?})",
+            service, self.config.name, params
+        );
+
+        // The URL carries the API key, so it is left out of errors
+        let response =
+            self.client
+                .get(url)
+                .send()
+                .await
+                .map_err(|e| RadarrError::ExternalServiceError {
+                    service: service.to_string(),
+                    error: format!("Request failed: {}", e.without_url()),
+                })?;
+
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .map_err(|e| RadarrError::ExternalServiceError {
+                service: service.to_string(),
+                error: format!("Failed to read response: {}", e.without_url()),
+            })?;
+
+        if status.as_u16() == 429 {
+            return Err(RadarrError::RateLimited {
+                service: self.config.name.clone(),
+                retry_after: None,
+            });
+        }
+        if !status.is_success() {
+            return Err(RadarrError::ExternalServiceError {
+                service: service.to_string(),
+                error: format!("HTTP {}: {}", status, body),
+            });
+        }
+
+        check_error_response(&body)?;
+        Ok(body)
+    }
+
+    /// Describe this indexer in the shared indexer model
+    fn as_indexer(&self, caps: Option<&TorznabCapabilities>) -> ProwlarrIndexer {
+        let caps = caps.cloned().unwrap_or_default();
+        ProwlarrIndexer {
+            id: self.config.indexer_id,
+            name: self.config.name.clone(),
+            implementation: self.config.protocol.as_str().to_string(),
+            base_url: self.config.base_url.clone(),
+            enable: true,
+            status: IndexerStatus {
+                status: "healthy".to_string(),
+                last_error: None,
+                failure_count: 0,
+                last_test: Some(Utc::now()),
+                disabled_till: None,
+            },
+            categories: caps.categories.clone(),
+            capabilities: IndexerCapabilities {
+                search_params: caps.movie_search_params.clone(),
+                tv_search: false,
+                movie_search: caps.movie_search_available,
+                music_search: false,
+                book_search: false,
+                limits: Some(SearchLimits {
+                    max: caps.limit_max,
+                    default: caps.limit_default,
+                }),
+            },
+            priority: 25,
+            supports_rss: true,
+            supports_search: caps.search_available || caps.movie_search_available,
+            last_sync: Some(Utc::now()),
+        }
+    }
+}
+
+#[async_trait]
+impl IndexerClient for TorznabClient {
+    async fn search(&self, request: &SearchRequest) -> Result<SearchResponse> {
+        self.search(request).await
+    }
+
+    async fn get_indexers(&self) -> Result<Vec<ProwlarrIndexer>> {
+        let caps = self.get_capabilities().await.ok();
+        Ok(vec![self.as_indexer(caps.as_ref())])
+    }
+
+    async fn test_indexer(&self, _indexer_id: i32) -> Result<bool> {
+        match self.refresh_capabilities().await {
+            Ok(_) => Ok(true),
+            Err(e) => {
+                warn!("Indexer '{}' test failed: {}", self.config.name, e);
+                Ok(false)
+            }
+        }
+    }
+
+    async fn health_check(&self) -> Result<bool> {
+        self.refresh_capabilities().await.map(|_| true)
+    }
+
+    async fn get_service_health(&self) -> crate::service_health::HealthStatus {
+        self.health_monitor.get_health_status().await
+    }
+
+    async fn get_service_metrics(&self) -> crate::service_health::ServiceMetrics {
+        self.health_monitor.get_metrics().await
+    }
+}
+
+/// Return an error if the body is a Torznab/Newznab error document
+fn check_error_response(xml: &str) -> Result<()> {
+    if let Some(caps) = ERROR_REGEX.captures(xml) {
+        let code: u32 = caps[1].parse().unwrap_or(0);
+        let description = decode_xml_entities(&caps[2]);
+        return Err(match code {
+            // 100-199: credentials / account errors
+            100..=199 => RadarrError::AuthenticationRequired {
+                service: "torznab".to_string(),
+                message: description,
+            },
+            // 429 is used by some indexers for request limits
+            429 | 500 => RadarrError::RateLimited {
+                service: "torznab".to_string(),
+                retry_after: None,
+            },
+            _ => RadarrError::ExternalServiceError {
+                service: "torznab".to_string(),
+                error: format!("Indexer error {}: {}", code, description),
+            },
+        });
+    }
+    Ok(())
+}
+
+/// Parse the RSS items of a search response into search results
+pub fn parse_search_results(
+    xml: &str,
+    config: &TorznabConfig,
+) -> Result<Vec<ProwlarrSearchResult>> {
+    check_error_response(xml)?;
+
+    let mut results = Vec::new();
+    for item in ITEM_REGEX.captures_iter(xml) {
+        match parse_item(&item[1], config) {
+            Some(result) => results.push(result),
+            None => debug!("Skipping Torznab item without title or link"),
+        }
+    }
+    Ok(results)
+}
+
+fn parse_item(item: &str, config: &TorznabConfig) -> Option<ProwlarrSearchResult> {
+    let title = element_text(item, "title")?;
+
+    let attrs: HashMap<String, String> = ATTR_REGEX
+        .captures_iter(item)
+        .map(|c| (c[1].to_lowercase(), decode_xml_entities(&c[2])))
+        .collect();
+    let attr_i64 = |name: &str| attrs.get(name).and_then(|v| v.parse::<i64>().ok());
+    let attr_f64 = |name: &str| attrs.get(name).and_then(|v| v.parse::<f64>().ok());
+
+    let download_url = ENCLOSURE_REGEX
+        .captures(item)
+        .map(|c| decode_xml_entities(&c[1]))
+        .or_else(|| element_text(item, "link"))
+        .or_else(|| attrs.get("magneturl").cloned())?;
+
+    let size = attr_i64("size").or_else(|| element_text(item, "size").and_then(|s| s.parse().ok()));
+    let seeders = attr_i64("seeders").map(|s| s as i32);
+    let leechers = attr_i64("leechers").map(|l| l as i32).or_else(|| {
+        attr_i64("peers").map(|peers| (peers - seeders.unwrap_or(0) as i64).max(0) as i32)
+    });
+    let download_factor = attr_f64("downloadvolumefactor");
+
+    let publish_date = element_text(item, "pubDate")
+        .and_then(|d| DateTime::parse_from_rfc2822(&d).ok())
+        .map(|d| d.with_timezone(&Utc));
+
+    let mut category_ids: Vec<i32> = ATTR_REGEX
+        .captures_iter(item)
+        .filter(|c| c[1].eq_ignore_ascii_case("category"))
+        .filter_map(|c| c[2].parse().ok())
+        .collect();
+    if category_ids.is_empty() {
+        category_ids = config.categories.clone();
+    }
+    let categories = category_ids
+        .into_iter()
+        .map(|id| Category {
+            id,
+            name: category_name(id).to_string(),
+            description: None,
+        })
+        .collect();
+
+    let imdb_id = attrs.get("imdbid").or_else(|| attrs.get("imdb")).map(|id| {
+        if id.starts_with("tt") {
+            id.clone()
+        } else {
+            format!("tt{:0>7}", id)
+        }
+    });
+
+    let attributes = attrs
+        .iter()
+        .map(|(k, v)| (k.clone(), serde_json::Value::String(v.clone())))
+        .collect();
+
+    Some(ProwlarrSearchResult {
+        title,
+        download_url,
+        info_url: element_text(item, "comments").or_else(|| element_text(item, "guid")),
+        indexer_id: config.indexer_id,
+        indexer: config.name.clone(),
+        size,
+        seeders,
+        leechers,
+        download_factor,
+        upload_factor: attr_f64("uploadvolumefactor"),
+        publish_date,
+        categories,
+        attributes,
+        imdb_id,
+        tmdb_id: attr_i64("tmdbid").map(|id| id as i32),
+        freeleech: download_factor.map(|f| f == 0.0),
+        info_hash: attrs.get("infohash").cloned(),
+    })
+}
+
+/// Get the text content of an XML element, unwrapping CDATA
+fn element_text(xml: &str, tag: &str) -> Option<String> {
+    let pattern = format!(
+        r"(?s)<{tag}(?:\s[^>]*)?>(.*?)</{tag}>",
+        tag = regex::escape(tag)
+    );
+    let re = Regex::new(&pattern).ok()?;
+    let raw = re.captures(xml)?.get(1)?.as_str().trim();
+    let text = raw
+        .strip_prefix("<![CDATA[")
+        .and_then(|s| s.strip_suffix("]]>"))
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| decode_xml_entities(raw));
+
+    if text.is_empty() {
+        None
+    } else {
+        Some(text)
+    }
+}
+
+/// Get an attribute value from the attribute section of a tag
+fn attribute_value(attrs: &str, name: &str) -> Option<String> {
+    let pattern = format!(r#"\b{}="([^"]*)""#, regex::escape(name));
+    Regex::new(&pattern)
+        .ok()?
+        .captures(attrs)
+        .map(|c| decode_xml_entities(&c[1]))
+}
+
+fn decode_xml_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Name of a Newznab standard movie category
+fn category_name(id: i32) -> &'static str {
+    match id {
+        2000 => "Movies",
+        2010 => "Movies/Foreign",
+        2020 => "Movies/Other",
+        2030 => "Movies/SD",
+        2040 => "Movies/HD",
+        2045 => "Movies/UHD",
+        2050 => "Movies/BluRay",
+        2060 => "Movies/3D",
+        2070 => "Movies/DVD",
+        2080 => "Movies/WEB-DL",
+        _ => "Other",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CAPS_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<caps>
+  <server title="Jackett" />
+  <limits default="50" max="100" />
+  <searching>
+    <search available="yes" supportedParams="q" />
+    <movie-search available="yes" supportedParams="q,imdbid" />
+  </searching>
+  <categories>
+    <category id="2000" name="Movies">
+      <subcat id="2040" name="Movies/HD" />
+      <subcat id="2045" name="Movies/UHD" />
+    </category>
+    <category id="5000" name="TV" />
+  </categories>
+</caps>"#;
+
+    const SEARCH_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0" xmlns:torznab="http://torznab.com/schemas/2015/feed">
+  <channel>
+    <item>
+      <title>The.Matrix.1999.1080p.BluRay.x264-GROUP</title>
+      <guid>https://tracker.example/details/123</guid>
+      <link>https://tracker.example/dl/123?key=abc&amp;file=1</link>
+      <pubDate>Mon, 01 Jan 2024 12:00:00 +0000</pubDate>
+      <size>8589934592</size>
+      <enclosure url="https://tracker.example/dl/123?key=abc&amp;file=1" length="8589934592" type="application/x-bittorrent" />
+      <torznab:attr name="category" value="2040" />
+      <torznab:attr name="seeders" value="42" />
+      <torznab:attr name="peers" value="50" />
+      <torznab:attr name="imdbid" value="0133093" />
+      <torznab:attr name="infohash" value="ABCDEF" />
+      <torznab:attr name="downloadvolumefactor" value="0" />
+    </item>
+    <item>
+      <title><![CDATA[The Matrix 1999 2160p]]></title>
+      <link>magnet:?xt=urn:btih:123</link>
+    </item>
+  </channel>
+</rss>"#;
+
+    fn client() -> TorznabClient {
+        TorznabClient::new(
+            TorznabConfig::torznab("Jackett", "http://localhost:9117", "key").with_indexer_id(7),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parse_capabilities() {
+        let caps = TorznabCapabilities::parse(CAPS_XML).unwrap();
+        assert_eq!(caps.server_title.as_deref(), Some("Jackett"));
+        assert!(caps.search_available);
+        assert!(caps.movie_search_available);
+        assert!(caps.supports_movie_param("imdbid"));
+        assert!(!caps.supports_movie_param("tmdbid"));
+        assert_eq!(caps.limit_max, Some(100));
+        assert_eq!(caps.categories.len(), 4);
+    }
+
+    #[test]
+    fn test_category_mapping() {
+        let caps = TorznabCapabilities::parse(CAPS_XML).unwrap();
+        assert_eq!(caps.map_categories(&[2000]), vec![2000, 2040, 2045]);
+        assert_eq!(caps.map_categories(&[2045]), vec![2045]);
+        // Unknown categories fall back to the request
+        assert_eq!(caps.map_categories(&[8000]), vec![8000]);
+    }
+
+    #[test]
+    fn test_parse_search_results() {
+        let config =
+            TorznabConfig::torznab("Jackett", "http://localhost:9117", "key").with_indexer_id(7);
+        let results = parse_search_results(SEARCH_XML, &config).unwrap();
+        assert_eq!(results.len(), 2);
+
+        let first = &results[0];
+        assert_eq!(first.title, "The.Matrix.1999.1080p.BluRay.x264-GROUP");
+        assert_eq!(
+            first.download_url,
+            "https://tracker.example/dl/123?key=abc&file=1"
+        );
+        assert_eq!(first.indexer_id, 7);
+        assert_eq!(first.size, Some(8589934592));
+        assert_eq!(first.seeders, Some(42));
+        assert_eq!(first.leechers, Some(8));
+        assert_eq!(first.imdb_id.as_deref(), Some("tt0133093"));
+        assert_eq!(first.freeleech, Some(true));
+        assert_eq!(first.categories[0].id, 2040);
+        assert!(first.publish_date.is_some());
+
+        let second = &results[1];
+        assert_eq!(second.title, "The Matrix 1999 2160p");
+        assert_eq!(second.download_url, "magnet:?xt=urn:btih:123");
+    }
+
+    #[test]
+    fn test_error_response() {
+        let xml =
+            r#"<?xml version="1.0"?><error code="100" description="Incorrect user credentials"/>"#;
+        let err = parse_search_results(xml, &TorznabConfig::default()).unwrap_err();
+        assert!(matches!(err, RadarrError::AuthenticationRequired { .. }));
+    }
+
+    #[test]
+    fn test_build_search_params() {
+        let client = client();
+        let caps = TorznabCapabilities::parse(CAPS_XML).unwrap();
+
+        let request = SearchRequest::for_movie_imdb("tt0133093").with_limit(500);
+        let params: HashMap<_, _> = client
+            .build_search_params(&request, &caps)
+            .into_iter()
+            .collect();
+        assert_eq!(params["t"], "movie");
+        assert_eq!(params["imdbid"], "0133093");
+        assert_eq!(params["cat"], "2000,2040,2045");
+        assert_eq!(params["limit"], "100");
+        assert!(!params.contains_key("q"));
+
+        // tmdbid isn't supported, so a title query is used instead
+        let request = SearchRequest {
+            tmdb_id: Some(603),
+            ..SearchRequest::for_title("The Matrix")
+        };
+        let params: HashMap<_, _> = client
+            .build_search_params(&request, &caps)
+            .into_iter()
+            .collect();
+        assert_eq!(params["q"], "The Matrix");
+        assert!(!params.contains_key("tmdbid"));
+    }
+
+    #[test]
+    fn test_config_validation() {
+        assert!(TorznabClient::new(TorznabConfig::default()).is_err());
+        let config = TorznabConfig::newznab("NZBGeek", "https://api.nzbgeek.info", "key");
+        assert_eq!(config.protocol, TorznabProtocol::Newznab);
+        assert!(config.validate().is_ok());
+    }
+}
@@ -0,0 +1,578 @@
+//! Configuration backups
+//!
+//! A backup is a zip file holding `manifest.json` and, for every
+//! configuration table, a JSON array of its rows under `tables/`: stored
+//! settings, tags, quality definitions and profiles, custom formats,
+//! indexers, download clients, notifications, root folders, import lists and
+//! list exclusions. Movies, their files and history are not included; a
+//! library import finds them again.
+//!
+//! Restoring runs in one transaction. Rows missing from the backup are
+//! deleted and the others are written back by primary key, so a backup that
+//! does not fit the database, e.g. one without a quality profile that movies
+//! still use, is rejected without changing anything. Settings loaded at
+//! startup take effect after a restart.
+
+use crate::database::DatabasePool;
+use chrono::{DateTime, Utc};
+use radarr_core::{RadarrError, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::Row;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{Cursor, Read, Write};
+use std::path::{Path, PathBuf};
+use tracing::{debug, info, warn};
+
+/// Version of the backup layout; newer backups are refused
+pub const BACKUP_FORMAT_VERSION: u32 = 1;
+
+/// File holding the manifest inside a backup
+const MANIFEST_FILE: &str = "manifest.json";
+
+/// Prefix of backup file names
+const BACKUP_PREFIX: &str = "radarr_backup";
+
+/// Backed up tables and their primary keys, parents before children
+const BACKUP_TABLES: &[(&str, &str)] = &[
+    ("config", "section"),
+    ("tags", "id"),
+    ("quality_definitions", "id"),
+    ("quality_profiles", "id"),
+    ("custom_formats", "id"),
+    ("indexers", "id"),
+    ("download_clients", "id"),
+    ("notifications", "id"),
+    ("root_folders", "id"),
+    ("import_lists", "id"),
+    ("list_exclusions", "id"),
+];
+
+/// Where backups are written and how many scheduled ones are kept
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackupConfig {
+    /// Directory backups are written to
+    pub directory: String,
+    /// Days between scheduled backups
+    pub interval_days: u64,
+    /// Scheduled backups kept before the oldest are removed
+    pub max_backups: usize,
+}
+
+impl Default for BackupConfig {
+    fn default() -> Self {
+        Self {
+            directory: "Backups".to_string(),
+            interval_days: 7,
+            max_backups: 4,
+        }
+    }
+}
+
+/// Why a backup was made
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BackupType {
+    /// By the scheduled `Backup` task; only these are pruned
+    Scheduled,
+    /// On request
+    Manual,
+    /// Of the configuration a restore replaced
+    BeforeRestore,
+}
+
+impl BackupType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BackupType::Scheduled => "scheduled",
+            BackupType::Manual => "manual",
+            BackupType::BeforeRestore => "beforeRestore",
+        }
+    }
+}
+
+/// Contents of `manifest.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupManifest {
+    pub version: u32,
+    pub app_version: String,
+    pub backup_type: BackupType,
+    pub created_at: DateTime<Utc>,
+    /// Rows per table
+    pub tables: BTreeMap<String, usize>,
+}
+
+/// A stored backup
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupSummary {
+    /// File name, used to download, restore or delete it
+    pub name: String,
+    pub backup_type: BackupType,
+    pub created_at: DateTime<Utc>,
+    pub size: u64,
+    pub tables: BTreeMap<String, usize>,
+}
+
+/// Result of a restore
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreReport {
+    /// Rows restored per table
+    pub tables: BTreeMap<String, usize>,
+    /// Backup of the configuration the restore replaced
+    pub previous_backup: String,
+}
+
+/// Rows of every table in a backup
+type TableRows = BTreeMap<String, Vec<Value>>;
+
+fn invalid_backup(message: impl Into<String>) -> RadarrError {
+    RadarrError::ValidationError {
+        field: "backup".to_string(),
+        message: message.into(),
+    }
+}
+
+/// Zip the manifest and table rows into a backup
+pub fn write_backup(manifest: &BackupManifest, tables: &TableRows) -> Result<Vec<u8>> {
+    let zip_error = |e: zip::result::ZipError| RadarrError::IoError(e.to_string());
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+
+    let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    zip.start_file(MANIFEST_FILE, options).map_err(zip_error)?;
+    zip.write_all(&serde_json::to_vec_pretty(manifest)?)?;
+    for (table, rows) in tables {
+        zip.start_file(format!("tables/{}.json", table), options)
+            .map_err(zip_error)?;
+        zip.write_all(&serde_json::to_vec(rows)?)?;
+    }
+    Ok(zip.finish().map_err(zip_error)?.into_inner())
+}
+
+/// Read and validate a backup, returning its manifest and table rows
+pub fn read_backup(contents: &[u8]) -> Result<(BackupManifest, TableRows)> {
+    let mut zip = zip::ZipArchive::new(Cursor::new(contents))
+        .map_err(|e| invalid_backup(format!("Not a backup zip file: {}", e)))?;
+    let mut read_file = |name: &str| -> Result<Vec<u8>> {
+        let mut file = zip
+            .by_name(name)
+            .map_err(|_| invalid_backup(format!("Backup has no {}", name)))?;
+        let mut contents = Vec::new();
+        file.read_to_end(&mut contents)?;
+        Ok(contents)
+    };
+
+    let manifest: BackupManifest = serde_json::from_slice(&read_file(MANIFEST_FILE)?)
+        .map_err(|e| invalid_backup(format!("Invalid backup manifest: {}", e)))?;
+    if manifest.version > BACKUP_FORMAT_VERSION {
+        return Err(invalid_backup(format!(
+            "Backup format {} is newer than the supported format {}",
+            manifest.version, BACKUP_FORMAT_VERSION
+        )));
+    }
+
+    let mut tables = TableRows::new();
+    for (table, &count) in &manifest.tables {
+        if !BACKUP_TABLES.iter().any(|(name, _)| name == table) {
+            return Err(invalid_backup(format!("Unknown table {} in backup", table)));
+        }
+        let rows: Vec<Value> =
+            serde_json::from_slice(&read_file(&format!("tables/{}.json", table))?)
+                .map_err(|e| invalid_backup(format!("Invalid rows for {}: {}", table, e)))?;
+        if rows.len() != count || !rows.iter().all(Value::is_object) {
+            return Err(invalid_backup(format!(
+                "Rows for {} do not match the manifest",
+                table
+            )));
+        }
+        tables.insert(table.clone(), rows);
+    }
+    Ok((manifest, tables))
+}
+
+/// Writes, lists and restores configuration backups
+pub struct BackupService {
+    pool: DatabasePool,
+    directory: PathBuf,
+    max_backups: usize,
+}
+
+impl BackupService {
+    pub fn new(pool: DatabasePool, config: &BackupConfig) -> Self {
+        Self {
+            pool,
+            directory: PathBuf::from(&config.directory),
+            max_backups: config.max_backups.max(1),
+        }
+    }
+
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Back up the configuration tables
+    pub async fn create(&self, backup_type: BackupType) -> Result<BackupSummary> {
+        let tables = self.export().await?;
+        let manifest = BackupManifest {
+            version: BACKUP_FORMAT_VERSION,
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            backup_type,
+            created_at: Utc::now(),
+            tables: tables
+                .iter()
+                .map(|(table, rows)| (table.clone(), rows.len()))
+                .collect(),
+        };
+        let contents = write_backup(&manifest, &tables)?;
+
+        tokio::fs::create_dir_all(&self.directory).await?;
+        let name = format!(
+            "{}_{}_{}.zip",
+            BACKUP_PREFIX,
+            backup_type.as_str(),
+            manifest.created_at.format("%Y.%m.%d_%H.%M.%S%.3f")
+        );
+        tokio::fs::write(self.directory.join(&name), &contents).await?;
+        info!("Wrote {} backup {}", backup_type.as_str(), name);
+
+        if backup_type == BackupType::Scheduled {
+            self.prune().await;
+        }
+        Ok(BackupSummary {
+            name,
+            backup_type,
+            created_at: manifest.created_at,
+            size: contents.len() as u64,
+            tables: manifest.tables,
+        })
+    }
+
+    /// Stored backups, newest first
+    pub async fn list(&self) -> Result<Vec<BackupSummary>> {
+        let mut entries = match tokio::fs::read_dir(&self.directory).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut backups = Vec::new();
+        while let Some(entry) = entries.next_entry().await? {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if !is_backup_name(&name) {
+                continue;
+            }
+            let contents = tokio::fs::read(entry.path()).await?;
+            match read_backup(&contents) {
+                Ok((manifest, _)) => backups.push(BackupSummary {
+                    name,
+                    backup_type: manifest.backup_type,
+                    created_at: manifest.created_at,
+                    size: contents.len() as u64,
+                    tables: manifest.tables,
+                }),
+                Err(e) => debug!("Skipping unreadable backup {}: {}", name, e),
+            }
+        }
+
+        backups.sort_by_key(|backup| std::cmp::Reverse(backup.created_at));
+        Ok(backups)
+    }
+
+    /// Contents of the backup `name`
+    pub async fn read(&self, name: &str) -> Result<Vec<u8>> {
+        let path = self.backup_path(name)?;
+        match tokio::fs::read(&path).await {
+            Ok(contents) => Ok(contents),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Err(RadarrError::NotFound {
+                resource: format!("backup {}", name),
+            }),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Remove the backup `name`
+    pub async fn delete(&self, name: &str) -> Result<()> {
+        let path = self.backup_path(name)?;
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Err(RadarrError::NotFound {
+                resource: format!("backup {}", name),
+            }),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Replace the configuration with the backup in `contents`
+    ///
+    /// The current configuration is backed up first. Nothing changes if the
+    /// backup is invalid or any of its rows cannot be written.
+    pub async fn restore(&self, contents: &[u8]) -> Result<RestoreReport> {
+        let (manifest, tables) = read_backup(contents)?;
+        let previous = self.create(BackupType::BeforeRestore).await?;
+
+        let mut tx = self.pool.begin().await?;
+        // Children first, so deleting a parent is only refused while rows
+        // the backup keeps still reference it
+        for (table, key) in BACKUP_TABLES.iter().rev() {
+            let Some(rows) = tables.get(*table) else {
+                continue;
+            };
+            let keys: Vec<String> = rows
+                .iter()
+                .filter_map(|row| row.get(*key))
+                .map(|value| match value {
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                })
+                .collect();
+            sqlx::query(&format!(
+                "DELETE FROM \"{table}\" WHERE NOT (\"{key}\"::text = ANY($1))"
+            ))
+            .bind(&keys)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| restore_error(table, e))?;
+        }
+
+        let mut restored = BTreeMap::new();
+        for (table, key) in BACKUP_TABLES {
+            let Some(rows) = tables.get(*table) else {
+                continue;
+            };
+            restored.insert(table.to_string(), rows.len());
+            if rows.is_empty() {
+                continue;
+            }
+
+            // Columns added since the backup keep their defaults, columns
+            // dropped since are ignored
+            let existing: BTreeSet<String> = sqlx::query(
+                "SELECT column_name::text FROM information_schema.columns
+                 WHERE table_schema = current_schema() AND table_name = $1",
+            )
+            .bind(table)
+            .fetch_all(&mut *tx)
+            .await?
+            .iter()
+            .map(|row| row.try_get::<String, _>(0))
+            .collect::<std::result::Result<_, _>>()?;
+            let columns: Vec<&String> = rows
+                .iter()
+                .filter_map(Value::as_object)
+                .flat_map(|row| row.keys())
+                .filter(|column| existing.contains(*column))
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect();
+
+            sqlx::query(&upsert_statement(table, key, &columns))
+                .bind(Value::Array(rows.clone()))
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| restore_error(table, e))?;
+
+            // Serial keys continue after the restored rows
+            sqlx::query(&format!(
+                "SELECT setval(seq, COALESCE((SELECT MAX(\"{key}\") FROM \"{table}\"), 0) + 1, false)
+                 FROM pg_get_serial_sequence('{table}', '{key}') AS seq WHERE seq IS NOT NULL"
+            ))
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+
+        info!(
+            "Restored backup from {} ({} tables), previous configuration saved as {}",
+            manifest.created_at,
+            restored.len(),
+            previous.name
+        );
+        Ok(RestoreReport {
+            tables: restored,
+            previous_backup: previous.name,
+        })
+    }
+
+    /// Rows of every backed up table, read in one snapshot
+    async fn export(&self) -> Result<TableRows> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ, READ ONLY")
+            .execute(&mut *tx)
+            .await?;
+
+        let mut tables = TableRows::new();
+        for (table, key) in BACKUP_TABLES {
+            let rows: Value = sqlx::query(&format!(
+                "SELECT COALESCE(json_agg(t), '[]'::json)
+                 FROM (SELECT * FROM \"{table}\" ORDER BY \"{key}\") t"
+            ))
+            .fetch_one(&mut *tx)
+            .await?
+            .try_get(0)?;
+            let Value::Array(rows) = rows else {
+                continue;
+            };
+            tables.insert(table.to_string(), rows);
+        }
+        tx.commit().await?;
+        Ok(tables)
+    }
+
+    /// Path of the backup `name`, refusing names that could leave the directory
+    fn backup_path(&self, name: &str) -> Result<PathBuf> {
+        if !is_backup_name(name) {
+            return Err(RadarrError::ValidationError {
+                field: "name".to_string(),
+                message: format!("Invalid backup name: {}", name),
+            });
+        }
+        Ok(self.directory.join(name))
+    }
+
+    /// Remove the oldest scheduled backups beyond the limit
+    async fn prune(&self) {
+        let backups = match self.list().await {
+            Ok(backups) => backups,
+            Err(e) => {
+                warn!("Failed to list backups: {}", e);
+                return;
+            }
+        };
+
+        let scheduled = backups
+            .iter()
+            .filter(|backup| backup.backup_type == BackupType::Scheduled);
+        for backup in scheduled.skip(self.max_backups) {
+            if let Err(e) = self.delete(&backup.name).await {
+                warn!("Failed to remove backup {}: {}", backup.name, e);
+            }
+        }
+    }
+}
+
+fn is_backup_name(name: &str) -> bool {
+    name.starts_with(BACKUP_PREFIX)
+        && name.ends_with(".zip")
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+        && !name.contains("..")
+}
+
+fn restore_error(table: &str, error: sqlx::Error) -> RadarrError {
+    invalid_backup(format!(
+        "Restoring {} failed, nothing was changed: {}",
+        table, error
+    ))
+}
+
+/// Statement writing a JSON array of rows into `table`, by primary key
+fn upsert_statement(table: &str, key: &str, columns: &[&String]) -> String {
+    let list = columns
+        .iter()
+        .map(|column| format!("\"{}\"", column))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let updates = columns
+        .iter()
+        .filter(|column| column.as_str() != key)
+        .map(|column| format!("\"{column}\" = EXCLUDED.\"{column}\""))
+        .collect::<Vec<_>>();
+    let on_conflict = if updates.is_empty() {
+        "DO NOTHING".to_string()
+    } else {
+        format!("DO UPDATE SET {}", updates.join(", "))
+    };
+
+    format!(
+        "INSERT INTO \"{table}\" ({list})
+         SELECT {list} FROM jsonb_populate_recordset(NULL::\"{table}\", $1)
+         ON CONFLICT (\"{key}\") {on_conflict}"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn manifest(tables: &TableRows) -> BackupManifest {
+        BackupManifest {
+            version: BACKUP_FORMAT_VERSION,
+            app_version: "0.1.0".to_string(),
+            backup_type: BackupType::Manual,
+            created_at: Utc::now(),
+            tables: tables
+                .iter()
+                .map(|(table, rows)| (table.clone(), rows.len()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_backups_round_trip_and_are_validated() {
+        let mut tables = TableRows::new();
+        tables.insert(
+            "tags".to_string(),
+            vec![
+                json!({"id": 1, "label": "kids"}),
+                json!({"id": 2, "label": "4k"}),
+            ],
+        );
+        tables.insert("config".to_string(), Vec::new());
+
+        let contents = write_backup(&manifest(&tables), &tables).unwrap();
+        let (read, rows) = read_backup(&contents).unwrap();
+        assert_eq!(read.backup_type, BackupType::Manual);
+        assert_eq!(read.tables["tags"], 2);
+        assert_eq!(rows["tags"][1]["label"], "4k");
+        assert!(rows["config"].is_empty());
+
+        // Counts must match the manifest
+        let mut wrong = manifest(&tables);
+        wrong.tables.insert("tags".to_string(), 3);
+        let contents = write_backup(&wrong, &tables).unwrap();
+        assert!(read_backup(&contents).is_err());
+
+        // Only known tables are restored
+        let mut unknown = TableRows::new();
+        unknown.insert("movies".to_string(), Vec::new());
+        let contents = write_backup(&manifest(&unknown), &unknown).unwrap();
+        assert!(read_backup(&contents).is_err());
+
+        let mut newer = manifest(&tables);
+        newer.version = BACKUP_FORMAT_VERSION + 1;
+        let contents = write_backup(&newer, &tables).unwrap();
+        assert!(read_backup(&contents).is_err());
+
+        assert!(read_backup(b"not a zip").is_err());
+    }
+
+    #[test]
+    fn test_backup_names_stay_in_the_directory() {
+        assert!(is_backup_name(
+            "radarr_backup_scheduled_2025.02.13_04.00.00.000.zip"
+        ));
+        assert!(!is_backup_name("radarr_backup_../../etc/passwd.zip"));
+        assert!(!is_backup_name("radarr_backup_..zip"));
+        assert!(!is_backup_name("notes.zip"));
+        assert!(!is_backup_name("radarr_backup_manual.json"));
+    }
+
+    #[test]
+    fn test_upsert_statement_updates_all_but_the_key() {
+        let (section, settings) = ("section".to_string(), "settings".to_string());
+        let statement = upsert_statement("config", "section", &[&section, &settings]);
+        assert!(statement.contains("INSERT INTO \"config\" (\"section\", \"settings\")"));
+        assert!(statement.contains(
+            "ON CONFLICT (\"section\") DO UPDATE SET \"settings\" = EXCLUDED.\"settings\""
+        ));
+
+        let statement = upsert_statement("tags", "id", &[&"id".to_string()]);
+        assert!(statement.ends_with("ON CONFLICT (\"id\") DO NOTHING"));
+    }
+}
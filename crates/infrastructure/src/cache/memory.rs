@@ -1,6 +1,5 @@
 use super::{Cache, CacheError};
 use async_trait::async_trait;
-use serde::{de::DeserializeOwned, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -19,18 +18,24 @@ struct CacheEntry {
     expires_at: Instant,
 }
 
+impl Default for MemoryCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl MemoryCache {
     pub fn new() -> Self {
         Self::with_config(10000, Duration::from_secs(300))
     }
-    
+
     pub fn with_config(max_size: usize, cleanup_interval: Duration) -> Self {
         let cache = Self {
             store: Arc::new(RwLock::new(HashMap::new())),
             max_size,
             cleanup_interval,
         };
-        
+
         // Start background cleanup task
         let store_clone = cache.store.clone();
         let cleanup_interval = cache.cleanup_interval;
@@ -41,91 +46,97 @@ impl MemoryCache {
                 Self::cleanup_expired(&store_clone).await;
             }
         });
-        
+
         cache
     }
-    
+
     async fn cleanup_expired(store: &Arc<RwLock<HashMap<String, CacheEntry>>>) {
         let mut store = store.write().await;
         let now = Instant::now();
         let before_size = store.len();
-        
+
         store.retain(|_key, entry| entry.expires_at > now);
-        
+
         let removed = before_size - store.len();
         if removed > 0 {
             debug!("Cleaned up {} expired cache entries", removed);
         }
     }
-    
-    async fn evict_if_needed(&self, store: &mut HashMap<String, CacheEntry>) {
-        if store.len() >= self.max_size {
-            // Simple eviction: remove oldest entries (LRU would be better)
-            let to_remove = store.len() - (self.max_size * 9 / 10); // Remove 10%
-            let mut keys_to_remove: Vec<String> = Vec::new();
-            
-            for (key, _) in store.iter().take(to_remove) {
-                keys_to_remove.push(key.clone());
-            }
-            
-            for key in keys_to_remove {
-                store.remove(&key);
-            }
-            
-            debug!("Evicted {} cache entries", to_remove);
+
+    /// Make room for a new key by dropping expired entries, then the 10% that
+    /// expire soonest; returns how many live entries were evicted
+    fn evict_if_needed(&self, store: &mut HashMap<String, CacheEntry>, key: &str) -> usize {
+        if store.len() < self.max_size || store.contains_key(key) {
+            return 0;
+        }
+
+        let now = Instant::now();
+        store.retain(|_key, entry| entry.expires_at > now);
+        if store.len() < self.max_size {
+            return 0;
         }
+
+        let to_remove = store.len() - (self.max_size * 9 / 10);
+        let mut by_expiry: Vec<(Instant, String)> = store
+            .iter()
+            .map(|(key, entry)| (entry.expires_at, key.clone()))
+            .collect();
+        by_expiry.sort();
+        for (_, key) in by_expiry.into_iter().take(to_remove) {
+            store.remove(&key);
+        }
+
+        debug!("Evicted {} cache entries", to_remove);
+        to_remove
     }
 }
 
 #[async_trait]
 impl Cache for MemoryCache {
-    async fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+    fn backend(&self) -> &'static str {
+        "memory"
+    }
+
+    async fn get_raw(&self, key: &str) -> Result<Option<Vec<u8>>, CacheError> {
         let store = self.store.read().await;
-        
-        if let Some(entry) = store.get(key) {
-            if entry.expires_at > Instant::now() {
+
+        match store.get(key) {
+            Some(entry) if entry.expires_at > Instant::now() => {
                 trace!("Cache hit for key: {}", key);
-                match serde_json::from_slice(&entry.data) {
-                    Ok(value) => return Some(value),
-                    Err(e) => {
-                        debug!("Failed to deserialize cache entry for key {}: {}", key, e);
-                        return None;
-                    }
-                }
-            } else {
+                Ok(Some(entry.data.clone()))
+            }
+            Some(_) => {
                 trace!("Cache entry expired for key: {}", key);
+                Ok(None)
+            }
+            None => {
+                trace!("Cache miss for key: {}", key);
+                Ok(None)
             }
-        } else {
-            trace!("Cache miss for key: {}", key);
         }
-        
-        None
     }
-    
-    async fn set<T: Serialize>(&self, key: &str, value: &T, ttl: Duration) -> Result<(), CacheError> {
-        let data = serde_json::to_vec(value)
-            .map_err(|e| CacheError::Serialization(e.to_string()))?;
-        
+
+    async fn set_raw(&self, key: &str, data: Vec<u8>, ttl: Duration) -> Result<usize, CacheError> {
         let entry = CacheEntry {
             data,
             expires_at: Instant::now() + ttl,
         };
-        
+
         let mut store = self.store.write().await;
-        self.evict_if_needed(&mut store).await;
+        let evicted = self.evict_if_needed(&mut store, key);
         store.insert(key.to_string(), entry);
-        
+
         trace!("Cached value for key: {} with TTL: {:?}", key, ttl);
-        Ok(())
+        Ok(evicted)
     }
-    
+
     async fn delete(&self, key: &str) -> Result<(), CacheError> {
         let mut store = self.store.write().await;
         store.remove(key);
         trace!("Deleted cache entry for key: {}", key);
         Ok(())
     }
-    
+
     async fn clear(&self) -> Result<(), CacheError> {
         let mut store = self.store.write().await;
         let count = store.len();
@@ -133,7 +144,7 @@ impl Cache for MemoryCache {
         debug!("Cleared {} cache entries", count);
         Ok(())
     }
-    
+
     async fn exists(&self, key: &str) -> bool {
         let store = self.store.read().await;
         if let Some(entry) = store.get(key) {
@@ -142,7 +153,7 @@ impl Cache for MemoryCache {
             false
         }
     }
-    
+
     async fn ttl(&self, key: &str) -> Option<Duration> {
         let store = self.store.read().await;
         if let Some(entry) = store.get(key) {
@@ -161,65 +172,68 @@ impl Cache for MemoryCache {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cache::CacheExt;
     use serde::{Deserialize, Serialize};
-    
+
     #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
     struct TestData {
         id: i32,
         name: String,
     }
-    
+
     #[tokio::test]
     async fn test_memory_cache_basic() {
         let cache = MemoryCache::new();
-        
+
         let data = TestData {
             id: 1,
             name: "Test".to_string(),
         };
-        
+
         // Set value
-        cache.set("test_key", &data, Duration::from_secs(60))
+        cache
+            .set("test_key", &data, Duration::from_secs(60))
             .await
             .unwrap();
-        
+
         // Get value
         let retrieved: Option<TestData> = cache.get("test_key").await;
         assert_eq!(retrieved, Some(data.clone()));
-        
+
         // Check exists
         assert!(cache.exists("test_key").await);
         assert!(!cache.exists("non_existent").await);
-        
+
         // Delete value
         cache.delete("test_key").await.unwrap();
         let retrieved: Option<TestData> = cache.get("test_key").await;
         assert_eq!(retrieved, None);
     }
-    
+
     #[tokio::test]
     async fn test_memory_cache_expiration() {
         let cache = MemoryCache::new();
-        
+
         let data = TestData {
             id: 2,
             name: "Expires".to_string(),
         };
-        
+
         // Set with short TTL
-        cache.set("expires", &data, Duration::from_millis(100))
+        cache
+            .set("expires", &data, Duration::from_millis(100))
             .await
             .unwrap();
-        
+
         // Should exist immediately
         assert!(cache.exists("expires").await);
-        
+
         // Wait for expiration
         tokio::time::sleep(Duration::from_millis(150)).await;
-        
+
         // Should be expired
         assert!(!cache.exists("expires").await);
         let retrieved: Option<TestData> = cache.get("expires").await;
         assert_eq!(retrieved, None);
     }
-}
\ No newline at end of file
+}
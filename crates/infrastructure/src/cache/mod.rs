@@ -1,94 +1,310 @@
+//! Layered cache for external lookups
+//!
+//! The cache manager checks an in-memory layer first and, with the redis
+//! backend, a shared Redis layer after it, backfilling the earlier layers on
+//! a hit. Cache failures are logged and treated as misses, so a Redis outage
+//! only slows lookups down.
+
 use async_trait::async_trait;
-use serde::{de::DeserializeOwned, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::future::Future;
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::RwLock;
+use tracing::{debug, warn};
 
 pub mod memory;
+#[cfg(feature = "redis")]
 pub mod redis;
 
 pub use memory::MemoryCache;
 #[cfg(feature = "redis")]
 pub use redis::RedisCache;
 
+/// A cache layer storing serialized values with a TTL
 #[async_trait]
 pub trait Cache: Send + Sync {
-    /// Get a value from the cache
-    async fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T>;
-    
-    /// Set a value in the cache with a TTL
-    async fn set<T: Serialize>(&self, key: &str, value: &T, ttl: Duration) -> Result<(), CacheError>;
-    
+    /// Name of the backend, used as a metrics label
+    fn backend(&self) -> &'static str;
+
+    /// Get the serialized value of a key
+    async fn get_raw(&self, key: &str) -> Result<Option<Vec<u8>>, CacheError>;
+
+    /// Set a serialized value with a TTL, returning how many entries were
+    /// evicted to make room
+    async fn set_raw(&self, key: &str, data: Vec<u8>, ttl: Duration) -> Result<usize, CacheError>;
+
     /// Delete a value from the cache
     async fn delete(&self, key: &str) -> Result<(), CacheError>;
-    
+
     /// Clear all values from the cache
     async fn clear(&self) -> Result<(), CacheError>;
-    
+
     /// Check if a key exists
     async fn exists(&self, key: &str) -> bool;
-    
+
     /// Get the remaining TTL for a key
     async fn ttl(&self, key: &str) -> Option<Duration>;
 }
 
+/// Typed access to a single cache layer
+#[async_trait]
+pub trait CacheExt: Cache {
+    /// Get a value from the cache
+    async fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let data = self.get_raw(key).await.ok().flatten()?;
+        serde_json::from_slice(&data)
+            .map_err(|e| debug!("Failed to deserialize cache entry for key {}: {}", key, e))
+            .ok()
+    }
+
+    /// Set a value in the cache with a TTL
+    async fn set<T: Serialize + Sync>(
+        &self,
+        key: &str,
+        value: &T,
+        ttl: Duration,
+    ) -> Result<(), CacheError> {
+        let data =
+            serde_json::to_vec(value).map_err(|e| CacheError::Serialization(e.to_string()))?;
+        self.set_raw(key, data, ttl).await.map(|_| ())
+    }
+}
+
+impl<C: Cache + ?Sized> CacheExt for C {}
+
 #[derive(Debug, thiserror::Error)]
 pub enum CacheError {
     #[error("Serialization error: {0}")]
     Serialization(String),
-    
+
     #[error("Deserialization error: {0}")]
     Deserialization(String),
-    
+
     #[error("Cache backend error: {0}")]
     Backend(String),
-    
+
     #[error("Key not found")]
     KeyNotFound,
 }
 
+/// Receives cache hits, misses and evictions, e.g. to export them to
+/// Prometheus
+pub trait CacheMetrics: Send + Sync {
+    /// A lookup in the `cache` namespace found its key in `backend`
+    fn record_hit(&self, cache: &str, backend: &str);
+
+    /// A lookup in the `cache` namespace missed `backend`
+    fn record_miss(&self, cache: &str, backend: &str);
+
+    /// `backend` evicted `count` entries to make room
+    fn record_evictions(&self, backend: &str, count: u64);
+}
+
+/// Where cached lookups are kept
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CacheBackend {
+    /// In this process only
+    #[default]
+    Memory,
+    /// In memory, backed by a Redis server shared between instances
+    Redis,
+}
+
+impl CacheBackend {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CacheBackend::Memory => "memory",
+            CacheBackend::Redis => "redis",
+        }
+    }
+}
+
+impl std::str::FromStr for CacheBackend {
+    type Err = CacheError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "memory" => Ok(CacheBackend::Memory),
+            "redis" => Ok(CacheBackend::Redis),
+            other => Err(CacheError::Backend(format!(
+                "Unknown cache backend '{}', expected memory or redis",
+                other
+            ))),
+        }
+    }
+}
+
+/// Cache backend selection and TTLs of cached lookups
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CacheConfig {
+    pub backend: CacheBackend,
+    /// Redis connection URL, used by the redis backend
+    pub redis_url: String,
+    /// Prefix of every Redis key, so instances can share a server
+    pub key_prefix: String,
+    /// Entries kept in memory before the soonest to expire are evicted
+    pub max_entries: usize,
+    /// Seconds TMDB lookups are cached
+    pub tmdb_ttl_seconds: u64,
+    /// Seconds streaming lookups are kept in front of the database
+    pub streaming_ttl_seconds: u64,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            backend: CacheBackend::Memory,
+            redis_url: "redis://127.0.0.1:6379".to_string(),
+            key_prefix: "radarr:".to_string(),
+            max_entries: 10_000,
+            tmdb_ttl_seconds: 6 * 3600,
+            streaming_ttl_seconds: 3600,
+        }
+    }
+}
+
+impl CacheConfig {
+    pub fn tmdb_ttl(&self) -> Duration {
+        Duration::from_secs(self.tmdb_ttl_seconds)
+    }
+
+    pub fn streaming_ttl(&self) -> Duration {
+        Duration::from_secs(self.streaming_ttl_seconds)
+    }
+}
+
 /// Cache manager that handles multiple cache layers
 pub struct CacheManager {
     layers: Vec<Arc<dyn Cache>>,
+    metrics: Option<Arc<dyn CacheMetrics>>,
+}
+
+impl Default for CacheManager {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl CacheManager {
     pub fn new() -> Self {
         Self {
             layers: vec![Arc::new(MemoryCache::new())],
+            metrics: None,
         }
     }
-    
+
+    /// Build the layers of the configured backend
+    pub async fn from_config(config: &CacheConfig) -> Result<Self, CacheError> {
+        let memory = Arc::new(MemoryCache::with_config(
+            config.max_entries,
+            Duration::from_secs(300),
+        ));
+        let manager = Self {
+            layers: vec![memory],
+            metrics: None,
+        };
+        match config.backend {
+            CacheBackend::Memory => Ok(manager),
+            #[cfg(feature = "redis")]
+            CacheBackend::Redis => {
+                let redis = RedisCache::connect(&config.redis_url, &config.key_prefix).await?;
+                Ok(manager.with_layer(Arc::new(redis)))
+            }
+            #[cfg(not(feature = "redis"))]
+            CacheBackend::Redis => Err(CacheError::Backend(
+                "Redis cache backend requires the redis feature".to_string(),
+            )),
+        }
+    }
+
     pub fn with_layer(mut self, cache: Arc<dyn Cache>) -> Self {
         self.layers.push(cache);
         self
     }
-    
+
+    /// Report hits, misses and evictions
+    pub fn with_metrics(mut self, metrics: Arc<dyn CacheMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Backends of the layers, in lookup order
+    pub fn backends(&self) -> Vec<&'static str> {
+        self.layers.iter().map(|layer| layer.backend()).collect()
+    }
+
     /// Get from the first cache layer that has the value
     pub async fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
-        for layer in &self.layers {
-            if let Some(value) = layer.get(key).await {
-                // Write-through to earlier layers
-                for earlier_layer in &self.layers {
-                    if Arc::ptr_eq(earlier_layer, layer) {
-                        break;
-                    }
-                    let _ = earlier_layer.set(&key, &value, Duration::from_secs(3600)).await;
+        let namespace = namespace(key);
+        for (index, layer) in self.layers.iter().enumerate() {
+            let data = match layer.get_raw(key).await {
+                Ok(Some(data)) => data,
+                Ok(None) => {
+                    self.record(|m| m.record_miss(namespace, layer.backend()));
+                    continue;
+                }
+                Err(e) => {
+                    warn!("{} cache lookup of {} failed: {}", layer.backend(), key, e);
+                    self.record(|m| m.record_miss(namespace, layer.backend()));
+                    continue;
+                }
+            };
+            self.record(|m| m.record_hit(namespace, layer.backend()));
+
+            let value = match serde_json::from_slice(&data) {
+                Ok(value) => value,
+                Err(e) => {
+                    debug!("Failed to deserialize cache entry for key {}: {}", key, e);
+                    return None;
+                }
+            };
+
+            // Write-through to earlier layers for the rest of the entry's TTL
+            if index > 0 {
+                let ttl = layer.ttl(key).await.unwrap_or(Duration::from_secs(3600));
+                for earlier_layer in &self.layers[..index] {
+                    self.set_layer(earlier_layer, key, data.clone(), ttl).await;
                 }
-                return Some(value);
             }
+            return Some(value);
         }
         None
     }
-    
+
     /// Set in all cache layers
-    pub async fn set<T: Serialize>(&self, key: &str, value: &T, ttl: Duration) -> Result<(), CacheError> {
+    pub async fn set<T: Serialize>(
+        &self,
+        key: &str,
+        value: &T,
+        ttl: Duration,
+    ) -> Result<(), CacheError> {
+        let data =
+            serde_json::to_vec(value).map_err(|e| CacheError::Serialization(e.to_string()))?;
         for layer in &self.layers {
-            layer.set(key, value, ttl).await?;
+            self.set_layer(layer, key, data.clone(), ttl).await;
         }
         Ok(())
     }
-    
+
+    /// Get the cached value, or load and cache it
+    pub async fn get_or_load<T, E, F, Fut>(&self, key: &str, ttl: Duration, load: F) -> Result<T, E>
+    where
+        T: Serialize + DeserializeOwned,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        if let Some(value) = self.get(key).await {
+            return Ok(value);
+        }
+        let value = load().await?;
+        if let Err(e) = self.set(key, &value, ttl).await {
+            warn!("Failed to cache {}: {}", key, e);
+        }
+        Ok(value)
+    }
+
     /// Delete from all cache layers
     pub async fn delete(&self, key: &str) -> Result<(), CacheError> {
         for layer in &self.layers {
@@ -96,6 +312,38 @@ impl CacheManager {
         }
         Ok(())
     }
+
+    /// Clear all cache layers
+    pub async fn clear(&self) -> Result<(), CacheError> {
+        for layer in &self.layers {
+            layer.clear().await?;
+        }
+        Ok(())
+    }
+
+    async fn set_layer(&self, layer: &Arc<dyn Cache>, key: &str, data: Vec<u8>, ttl: Duration) {
+        match layer.set_raw(key, data, ttl).await {
+            Ok(0) => {}
+            Ok(evicted) => self.record(|m| m.record_evictions(layer.backend(), evicted as u64)),
+            Err(e) => warn!(
+                "Failed to write {} to the {} cache: {}",
+                key,
+                layer.backend(),
+                e
+            ),
+        }
+    }
+
+    fn record(&self, f: impl FnOnce(&dyn CacheMetrics)) {
+        if let Some(metrics) = &self.metrics {
+            f(metrics.as_ref());
+        }
+    }
+}
+
+/// Metrics label of a key: everything before its first colon
+fn namespace(key: &str) -> &str {
+    key.split(':').next().unwrap_or(key)
 }
 
 /// Cache key builder for consistent key generation
@@ -105,28 +353,158 @@ impl CacheKey {
     pub fn tmdb_movie(id: i32) -> String {
         format!("tmdb:movie:{}", id)
     }
-    
+
     pub fn tmdb_search(query: &str, page: i32) -> String {
-        format!("tmdb:search:{}:{}", query.to_lowercase().replace(' ', "_"), page)
+        format!(
+            "tmdb:search:{}:{}",
+            query.to_lowercase().replace(' ', "_"),
+            page
+        )
     }
-    
+
     pub fn tmdb_popular(page: i32) -> String {
         format!("tmdb:popular:{}", page)
     }
-    
+
     pub fn tmdb_upcoming(page: i32) -> String {
         format!("tmdb:upcoming:{}", page)
     }
-    
+
+    pub fn tmdb_collection(id: i32) -> String {
+        format!("tmdb:collection:{}", id)
+    }
+
+    pub fn streaming(key: &str) -> String {
+        format!("streaming:{}", key)
+    }
+
     pub fn hdbits_scene_group(name: &str) -> String {
         format!("hdbits:scene_group:{}", name.to_uppercase())
     }
-    
+
     pub fn quality_score(release_title: &str) -> String {
         format!("quality:score:{}", release_title)
     }
-    
+
     pub fn indexer_search(indexer: &str, query: &str) -> String {
-        format!("indexer:{}:search:{}", indexer, query.to_lowercase().replace(' ', "_"))
+        format!(
+            "indexer:{}:search:{}",
+            indexer,
+            query.to_lowercase().replace(' ', "_")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct RecordedMetrics(Mutex<Vec<String>>);
+
+    impl CacheMetrics for RecordedMetrics {
+        fn record_hit(&self, cache: &str, backend: &str) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("hit {} {}", cache, backend));
+        }
+
+        fn record_miss(&self, cache: &str, backend: &str) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("miss {} {}", cache, backend));
+        }
+
+        fn record_evictions(&self, backend: &str, count: u64) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("evict {} {}", backend, count));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lower_layer_hits_backfill_and_are_recorded() {
+        let metrics = Arc::new(RecordedMetrics::default());
+        let shared = Arc::new(MemoryCache::new());
+        let manager = CacheManager::new()
+            .with_layer(shared.clone())
+            .with_metrics(metrics.clone());
+
+        shared
+            .set("tmdb:movie:603", &"The Matrix", Duration::from_secs(60))
+            .await
+            .unwrap();
+
+        let loaded: Option<String> = manager.get("tmdb:movie:603").await;
+        assert_eq!(loaded.as_deref(), Some("The Matrix"));
+        // The first layer now has it too
+        let loaded: Option<String> = manager.get("tmdb:movie:603").await;
+        assert_eq!(loaded.as_deref(), Some("The Matrix"));
+        assert!(manager.get::<String>("tmdb:movie:1").await.is_none());
+
+        assert_eq!(
+            *metrics.0.lock().unwrap(),
+            vec![
+                "miss tmdb memory",
+                "hit tmdb memory",
+                "hit tmdb memory",
+                "miss tmdb memory",
+                "miss tmdb memory",
+            ]
+        );
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_get_or_load_caches_and_reports_evictions() {
+        let metrics = Arc::new(RecordedMetrics::default());
+        let manager = CacheManager::from_config(&CacheConfig {
+            max_entries: 10,
+            ..CacheConfig::default()
+        })
+        .await
+        .unwrap()
+        .with_metrics(metrics.clone());
+
+        let first: Result<i32, CacheError> = manager
+            .get_or_load("streaming:trending", Duration::from_secs(60), || async {
+                Ok(1)
+            })
+            .await;
+        let second: Result<i32, CacheError> = manager
+            .get_or_load("streaming:trending", Duration::from_secs(60), || async {
+                Ok(2)
+            })
+            .await;
+        assert_eq!(first.unwrap(), 1);
+        assert_eq!(second.unwrap(), 1);
+
+        for i in 0..10 {
+            manager
+                .set(&format!("tmdb:movie:{}", i), &i, Duration::from_secs(60))
+                .await
+                .unwrap();
+        }
+        assert!(metrics
+            .0
+            .lock()
+            .unwrap()
+            .contains(&"evict memory 1".to_string()));
+    }
+
+    #[test]
+    fn test_backend_selection_parses() {
+        assert_eq!(
+            "Redis".parse::<CacheBackend>().unwrap(),
+            CacheBackend::Redis
+        );
+        assert_eq!(
+            "memory".parse::<CacheBackend>().unwrap(),
+            CacheBackend::Memory
+        );
+        assert!("memcached".parse::<CacheBackend>().is_err());
+    }
+}
@@ -0,0 +1,105 @@
+use super::{Cache, CacheError};
+use async_trait::async_trait;
+use redis::aio::ConnectionManager;
+use redis::AsyncCommands;
+use std::time::Duration;
+use tracing::{debug, info, trace};
+
+/// Redis cache layer shared between instances, with keys under a prefix
+pub struct RedisCache {
+    connection: ConnectionManager,
+    key_prefix: String,
+}
+
+impl RedisCache {
+    /// Connect to the Redis server at `url`; the connection reconnects by
+    /// itself after failures
+    pub async fn connect(url: &str, key_prefix: &str) -> Result<Self, CacheError> {
+        let client = redis::Client::open(url).map_err(backend_error)?;
+        let connection = ConnectionManager::new(client)
+            .await
+            .map_err(backend_error)?;
+        info!("Connected to Redis cache at {}", url);
+        Ok(Self {
+            connection,
+            key_prefix: key_prefix.to_string(),
+        })
+    }
+
+    fn key(&self, key: &str) -> String {
+        format!("{}{}", self.key_prefix, key)
+    }
+}
+
+fn backend_error(e: redis::RedisError) -> CacheError {
+    CacheError::Backend(e.to_string())
+}
+
+#[async_trait]
+impl Cache for RedisCache {
+    fn backend(&self) -> &'static str {
+        "redis"
+    }
+
+    async fn get_raw(&self, key: &str) -> Result<Option<Vec<u8>>, CacheError> {
+        let mut connection = self.connection.clone();
+        let data: Option<Vec<u8>> = connection.get(self.key(key)).await.map_err(backend_error)?;
+        trace!(
+            "Redis cache {} for key: {}",
+            if data.is_some() { "hit" } else { "miss" },
+            key
+        );
+        Ok(data)
+    }
+
+    /// Redis evicts on its own according to its maxmemory policy
+    async fn set_raw(&self, key: &str, data: Vec<u8>, ttl: Duration) -> Result<usize, CacheError> {
+        let mut connection = self.connection.clone();
+        let millis = ttl.as_millis().clamp(1, u64::MAX as u128) as u64;
+        let _: () = connection
+            .pset_ex(self.key(key), data, millis)
+            .await
+            .map_err(backend_error)?;
+        Ok(0)
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), CacheError> {
+        let mut connection = self.connection.clone();
+        let _: () = connection.del(self.key(key)).await.map_err(backend_error)?;
+        Ok(())
+    }
+
+    /// Only removes keys under this cache's prefix
+    async fn clear(&self) -> Result<(), CacheError> {
+        let mut connection = self.connection.clone();
+        let keys: Vec<String> = {
+            let mut iter = connection
+                .scan_match::<_, String>(format!("{}*", self.key_prefix))
+                .await
+                .map_err(backend_error)?;
+            let mut keys = Vec::new();
+            while let Some(key) = iter.next_item().await {
+                keys.push(key);
+            }
+            keys
+        };
+
+        let mut connection = self.connection.clone();
+        for chunk in keys.chunks(500) {
+            let _: () = connection.del(chunk).await.map_err(backend_error)?;
+        }
+        debug!("Cleared {} Redis cache entries", keys.len());
+        Ok(())
+    }
+
+    async fn exists(&self, key: &str) -> bool {
+        let mut connection = self.connection.clone();
+        connection.exists(self.key(key)).await.unwrap_or(false)
+    }
+
+    async fn ttl(&self, key: &str) -> Option<Duration> {
+        let mut connection = self.connection.clone();
+        let millis: i64 = connection.pttl(self.key(key)).await.ok()?;
+        (millis > 0).then(|| Duration::from_millis(millis as u64))
+    }
+}
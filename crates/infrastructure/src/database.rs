@@ -1,11 +1,15 @@
 //! Database connection and configuration
 //!
 //! This module handles PostgreSQL connection setup and pool management.
+//! Connecting at startup and queries that fail because the server went away
+//! are retried with backoff, so a restarting Postgres delays requests instead
+//! of failing them or the whole application.
 
-use crate::error::InfrastructureError;
 use radarr_core::Result;
 use sqlx::{Pool, Postgres};
+use std::future::Future;
 use std::time::Duration;
+use tracing::warn;
 
 /// Database configuration
 #[derive(Debug, Clone)]
@@ -70,9 +74,117 @@ impl DatabaseConfig {
 /// Database pool wrapper
 pub type DatabasePool = Pool<Postgres>;
 
+/// How operations are retried while the database server cannot be reached
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DatabaseRetryPolicy {
+    /// Attempts including the first; 1 disables retries
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled for each further one
+    pub initial_delay: Duration,
+    /// Longest delay between two attempts
+    pub max_delay: Duration,
+}
+
+impl Default for DatabaseRetryPolicy {
+    /// Brief retries for queries, riding out a server restart or failover
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_delay: Duration::from_millis(250),
+            max_delay: Duration::from_secs(2),
+        }
+    }
+}
+
+impl DatabaseRetryPolicy {
+    /// Patient retries for connecting at startup, e.g. while the database
+    /// container is still starting
+    pub fn startup(max_attempts: u32) -> Self {
+        Self {
+            max_attempts,
+            initial_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+
+    /// Delay before retry number `retry`, counting from 1
+    pub fn delay(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        self.initial_delay
+            .saturating_mul(factor)
+            .min(self.max_delay)
+    }
+
+    /// Run `operation`, retrying it while it fails with connection errors
+    ///
+    /// A closed pool is not retried: it only closes on shutdown.
+    pub async fn run<T, F, Fut>(
+        &self,
+        operation_name: &str,
+        operation: F,
+    ) -> std::result::Result<T, sqlx::Error>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = std::result::Result<T, sqlx::Error>>,
+    {
+        let mut attempt = 1;
+        loop {
+            match operation().await {
+                Err(e)
+                    if is_connection_error(&e)
+                        && !matches!(e, sqlx::Error::PoolClosed)
+                        && attempt < self.max_attempts =>
+                {
+                    let delay = self.delay(attempt);
+                    warn!(
+                        "{} failed on attempt {}/{}, database unreachable: {}. Retrying in {:?}",
+                        operation_name, attempt, self.max_attempts, e, delay
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+/// Errors that mean the server or connection is unusable rather than the
+/// query
+pub fn is_connection_error(error: &sqlx::Error) -> bool {
+    matches!(
+        error,
+        sqlx::Error::Io(_)
+            | sqlx::Error::Tls(_)
+            | sqlx::Error::Protocol(_)
+            | sqlx::Error::PoolTimedOut
+            | sqlx::Error::PoolClosed
+            | sqlx::Error::WorkerCrashed
+    )
+}
+
+/// Create a PostgreSQL connection pool, retrying while the server cannot be
+/// reached
+pub async fn create_pool_with_retry(
+    config: DatabaseConfig,
+    retry: &DatabaseRetryPolicy,
+) -> Result<DatabasePool> {
+    let pool = retry
+        .run("Connecting to the database", || connect(config.clone()))
+        .await?;
+
+    Ok(pool)
+}
+
 /// Create a PostgreSQL connection pool
 pub async fn create_pool(config: DatabaseConfig) -> Result<DatabasePool> {
-    let pool = sqlx::postgres::PgPoolOptions::new()
+    let pool = connect(config).await?;
+
+    Ok(pool)
+}
+
+async fn connect(config: DatabaseConfig) -> std::result::Result<DatabasePool, sqlx::Error> {
+    sqlx::postgres::PgPoolOptions::new()
         .max_connections(config.max_connections)
         .min_connections(config.min_connections)
         .acquire_timeout(config.acquire_timeout)
@@ -81,24 +193,91 @@ pub async fn create_pool(config: DatabaseConfig) -> Result<DatabasePool> {
         .test_before_acquire(true)
         // TODO: Add session optimization after fixing connection lifetime issues
         .connect(&config.database_url)
-        .await?;
+        .await
+}
+
+/// Create a PostgreSQL connection pool that connects on first use, for
+/// servers such as a read replica that may not be reachable at startup
+pub fn create_lazy_pool(config: DatabaseConfig) -> Result<DatabasePool> {
+    let pool = sqlx::postgres::PgPoolOptions::new()
+        .max_connections(config.max_connections)
+        .min_connections(config.min_connections)
+        .acquire_timeout(config.acquire_timeout)
+        .idle_timeout(config.idle_timeout)
+        .max_lifetime(config.max_lifetime)
+        .test_before_acquire(true)
+        .connect_lazy(&config.database_url)?;
 
     Ok(pool)
 }
 
 /// Run database migrations
 pub async fn migrate(pool: &DatabasePool) -> Result<()> {
-    sqlx::migrate!("../../migrations")
-        .run(pool)
-        .await
-        .map_err(|e| InfrastructureError::Migration(e.to_string()))?;
-
-    Ok(())
+    crate::migrations::apply_migrations(pool).await
 }
 
 /// Test database connection
 pub async fn test_connection(pool: &DatabasePool) -> Result<()> {
-    sqlx::query("SELECT 1").fetch_one(pool).await?;
+    DatabaseRetryPolicy::default()
+        .run("Testing the database connection", || {
+            sqlx::query("SELECT 1").fetch_one(pool)
+        })
+        .await?;
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[test]
+    fn test_retry_delay_doubles_up_to_max() {
+        let policy = DatabaseRetryPolicy::default();
+        assert_eq!(policy.delay(1), Duration::from_millis(250));
+        assert_eq!(policy.delay(2), Duration::from_millis(500));
+        assert_eq!(policy.delay(4), Duration::from_secs(2));
+        assert_eq!(policy.delay(40), Duration::from_secs(2));
+    }
+
+    #[tokio::test]
+    async fn test_retry_only_connection_errors() {
+        let policy = DatabaseRetryPolicy {
+            max_attempts: 3,
+            initial_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(1),
+        };
+
+        let attempts = AtomicU32::new(0);
+        let result: std::result::Result<(), _> = policy
+            .run("test", || async {
+                attempts.fetch_add(1, Ordering::Relaxed);
+                Err(sqlx::Error::PoolTimedOut)
+            })
+            .await;
+        assert!(matches!(result, Err(sqlx::Error::PoolTimedOut)));
+        assert_eq!(attempts.load(Ordering::Relaxed), 3);
+
+        let attempts = AtomicU32::new(0);
+        let result: std::result::Result<(), _> = policy
+            .run("test", || async {
+                attempts.fetch_add(1, Ordering::Relaxed);
+                Err(sqlx::Error::RowNotFound)
+            })
+            .await;
+        assert!(matches!(result, Err(sqlx::Error::RowNotFound)));
+        assert_eq!(attempts.load(Ordering::Relaxed), 1);
+
+        let attempts = AtomicU32::new(0);
+        let result = policy
+            .run("test", || async {
+                match attempts.fetch_add(1, Ordering::Relaxed) {
+                    0 => Err(sqlx::Error::WorkerCrashed),
+                    _ => Ok(42),
+                }
+            })
+            .await;
+        assert_eq!(result.unwrap(), 42);
+    }
+}
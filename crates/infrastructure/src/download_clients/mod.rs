@@ -1,6 +1,8 @@
 //! Download client implementations
 
 pub mod qbittorrent;
+pub mod sabnzbd;
 
 // Re-export
 pub use qbittorrent::*;
+pub use sabnzbd::*;
@@ -1,19 +1,62 @@
 //! qBittorrent download client adapter
 
 use async_trait::async_trait;
-use radarr_core::{ClientDownloadStatus, DownloadClientService, Result};
-use radarr_downloaders::{AddTorrentParams, QBittorrentClient, QBittorrentConfig, TorrentData};
+use radarr_core::{
+    ClientDownloadStatus, DownloadClientService, DownloadFile, DownloadMetadata, DownloadPieces,
+    Result,
+};
+use radarr_downloaders::{
+    AddTorrentParams, FetchedTorrent, MagnetLink, PieceState, QBittorrentClient, QBittorrentConfig,
+    TorrentData, TorrentFetcher, TorrentFile,
+};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tracing::debug;
+
+/// Fetched torrents kept between inspecting and adding a grab
+const MAX_FETCHED_TORRENTS: usize = 32;
 
 /// qBittorrent download client adapter
 pub struct QBittorrentDownloadClient {
     client: QBittorrentClient,
+    fetcher: TorrentFetcher,
+    /// Torrents fetched for inspection, by download URL, so adding them
+    /// does not download them again
+    fetched: Mutex<HashMap<String, FetchedTorrent>>,
 }
 
 impl QBittorrentDownloadClient {
     /// Create a new qBittorrent download client
     pub fn new(config: QBittorrentConfig) -> Result<Self> {
+        let fetcher = TorrentFetcher::new(Duration::from_secs(config.timeout))?;
         let client = QBittorrentClient::new(config)?;
-        Ok(Self { client })
+        Ok(Self {
+            client,
+            fetcher,
+            fetched: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// The torrent behind an indexer's download URL
+    async fn fetch(&self, download_url: &str) -> Result<FetchedTorrent> {
+        let cached = self
+            .fetched
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(download_url)
+            .cloned();
+        if let Some(torrent) = cached {
+            return Ok(torrent);
+        }
+
+        let torrent = self.fetcher.fetch(download_url).await?;
+        let mut fetched = self.fetched.lock().unwrap_or_else(|e| e.into_inner());
+        if fetched.len() >= MAX_FETCHED_TORRENTS {
+            fetched.clear();
+        }
+        fetched.insert(download_url.to_string(), torrent.clone());
+        Ok(torrent)
     }
 }
 
@@ -25,12 +68,24 @@ impl DownloadClientService for QBittorrentDownloadClient {
         category: Option<String>,
         save_path: Option<String>,
     ) -> Result<String> {
+        // Torrent files are sent as files, so the client ID is their info
+        // hash; qBittorrent fetches the URL itself if we cannot
         let torrent_data = if download_url.starts_with("magnet:") {
             TorrentData::Url(download_url.to_string())
         } else {
-            // For now, assume it's a magnet URL. In the future, we could support
-            // torrent file downloads by fetching the URL and converting to bytes
-            TorrentData::Url(download_url.to_string())
+            let fetched = self.fetch(download_url).await;
+            self.fetched
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .remove(download_url);
+            match fetched {
+                Ok(FetchedTorrent::File { data, .. }) => TorrentData::File(data),
+                Ok(FetchedTorrent::Magnet { uri, .. }) => TorrentData::Url(uri),
+                Err(e) => {
+                    debug!("Could not fetch torrent, passing on its URL: {}", e);
+                    TorrentData::Url(download_url.to_string())
+                }
+            }
         };
 
         let params = AddTorrentParams {
@@ -56,11 +111,13 @@ impl DownloadClientService for QBittorrentDownloadClient {
                     download_speed: Some(torrent_info.dlspeed),
                     upload_speed: Some(torrent_info.upspeed),
                     downloaded_bytes: Some(torrent_info.completed as i64),
-                    upload_bytes: None, // qBittorrent doesn't provide total uploaded in TorrentInfo
+                    upload_bytes: Some(torrent_info.uploaded as i64),
                     eta_seconds: Some(torrent_info.eta),
                     seeders: None,  // Not available in TorrentInfo
                     leechers: None, // Not available in TorrentInfo
                     save_path: Some(torrent_info.save_path),
+                    ratio: Some(torrent_info.ratio),
+                    seeding_time_seconds: Some(torrent_info.seeding_time),
                 };
                 Ok(Some(status))
             }
@@ -93,15 +150,92 @@ impl DownloadClientService for QBittorrentDownloadClient {
                 download_speed: Some(torrent_info.dlspeed),
                 upload_speed: Some(torrent_info.upspeed),
                 downloaded_bytes: Some(torrent_info.completed as i64),
-                upload_bytes: None,
+                upload_bytes: Some(torrent_info.uploaded as i64),
                 eta_seconds: Some(torrent_info.eta),
                 seeders: None,
                 leechers: None,
                 save_path: Some(torrent_info.save_path),
+                ratio: Some(torrent_info.ratio),
+                seeding_time_seconds: Some(torrent_info.seeding_time),
             };
             downloads.push(status);
         }
 
         Ok(downloads)
     }
+    async fn free_space(&self, _download_url: &str) -> Result<Option<u64>> {
+        // qBittorrent reports free space for its default save path only
+        Ok(self.client.get_server_state().await?.free_space_on_disk)
+    }
+
+    async fn download_pieces(&self, client_id: &str) -> Result<Option<DownloadPieces>> {
+        let Some(torrent) = self.client.get_torrent_status(client_id).await? else {
+            return Ok(None);
+        };
+        let files = self.client.get_torrent_files(client_id).await?;
+        let pieces = self.client.get_piece_states(client_id).await?;
+
+        Ok(Some(DownloadPieces {
+            sequential: torrent.seq_dl,
+            save_path: torrent.save_path,
+            files: files.into_iter().map(download_file).collect(),
+            downloaded: pieces
+                .into_iter()
+                .map(|piece| piece == PieceState::Downloaded)
+                .collect(),
+        }))
+    }
+
+    async fn download_files(&self, client_id: &str) -> Result<Option<Vec<DownloadFile>>> {
+        let files = self.client.get_torrent_files(client_id).await?;
+        Ok(Some(files.into_iter().map(download_file).collect()))
+    }
+
+    async fn skip_files(&self, client_id: &str, indices: &[usize]) -> Result<()> {
+        // Priority 0 means "do not download"
+        self.client.set_file_priority(client_id, indices, 0).await
+    }
+
+    async fn inspect_download(&self, download_url: &str) -> Result<Option<DownloadMetadata>> {
+        let torrent = if download_url.starts_with("magnet:") {
+            FetchedTorrent::Magnet {
+                link: MagnetLink::parse(download_url)?,
+                uri: download_url.to_string(),
+            }
+        } else {
+            self.fetch(download_url).await?
+        };
+        Ok(Some(download_metadata(torrent)))
+    }
+}
+
+fn download_metadata(torrent: FetchedTorrent) -> DownloadMetadata {
+    match torrent {
+        FetchedTorrent::File { metainfo, .. } => DownloadMetadata {
+            client_id: metainfo.info_hash,
+            name: Some(metainfo.name),
+            size_bytes: Some(metainfo.total_size),
+            files: metainfo
+                .files
+                .into_iter()
+                .map(|file| (file.path, file.length))
+                .collect(),
+        },
+        FetchedTorrent::Magnet { link, .. } => DownloadMetadata {
+            client_id: link.info_hash,
+            name: link.display_name,
+            size_bytes: link.exact_length,
+            files: Vec::new(),
+        },
+    }
+}
+
+fn download_file(file: TorrentFile) -> DownloadFile {
+    DownloadFile {
+        name: file.name,
+        size: file.size,
+        first_piece: file.piece_range[0],
+        last_piece: file.piece_range[1],
+        skipped: file.priority == 0,
+    }
 }
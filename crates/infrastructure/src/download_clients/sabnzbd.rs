@@ -0,0 +1,129 @@
+//! SABnzbd download client adapter
+
+use async_trait::async_trait;
+use radarr_core::{ClientDownloadStatus, DownloadClientService, Result};
+use radarr_downloaders::{HistorySlot, QueueSlot, SabnzbdClient, SabnzbdConfig};
+
+/// History entries searched for finished downloads
+const HISTORY_LIMIT: usize = 100;
+
+/// SABnzbd download client adapter
+pub struct SabnzbdDownloadClient {
+    client: SabnzbdClient,
+}
+
+impl SabnzbdDownloadClient {
+    /// Create a new SABnzbd download client
+    pub fn new(config: SabnzbdConfig) -> Result<Self> {
+        Ok(Self {
+            client: SabnzbdClient::new(config)?,
+        })
+    }
+}
+
+#[async_trait]
+impl DownloadClientService for SabnzbdDownloadClient {
+    async fn add_download(
+        &self,
+        download_url: &str,
+        category: Option<String>,
+        _save_path: Option<String>,
+    ) -> Result<String> {
+        // SABnzbd moves finished jobs by category; it has no per-job path
+        self.client.add_url(download_url, category.as_deref()).await
+    }
+
+    async fn get_download_status(&self, client_id: &str) -> Result<Option<ClientDownloadStatus>> {
+        let queue = self.client.get_queue().await?;
+        if let Some(slot) = queue.slots.into_iter().find(|s| s.nzo_id == client_id) {
+            return Ok(Some(queue_status(slot)));
+        }
+
+        let history = self.client.get_history(HISTORY_LIMIT).await?;
+        Ok(history
+            .into_iter()
+            .find(|s| s.nzo_id == client_id)
+            .map(history_status))
+    }
+
+    async fn remove_download(&self, client_id: &str, delete_files: bool) -> Result<()> {
+        self.client.delete(client_id, delete_files).await
+    }
+
+    async fn pause_download(&self, client_id: &str) -> Result<()> {
+        self.client.pause(client_id).await
+    }
+
+    async fn resume_download(&self, client_id: &str) -> Result<()> {
+        self.client.resume(client_id).await
+    }
+
+    async fn get_all_downloads(&self) -> Result<Vec<ClientDownloadStatus>> {
+        let queue = self.client.get_queue().await?;
+        let history = self.client.get_history(HISTORY_LIMIT).await?;
+
+        Ok(queue
+            .slots
+            .into_iter()
+            .map(queue_status)
+            .chain(history.into_iter().map(history_status))
+            .collect())
+    }
+
+    async fn free_space(&self, _download_url: &str) -> Result<Option<u64>> {
+        Ok(self.client.get_queue().await?.free_space_bytes())
+    }
+}
+
+/// Status of a queued or active download, in the queue's status vocabulary
+fn queue_status(slot: QueueSlot) -> ClientDownloadStatus {
+    let status = match slot.status.as_str() {
+        "Paused" => "paused_dl",
+        _ => "downloading",
+    };
+
+    ClientDownloadStatus {
+        progress: slot.progress(),
+        downloaded_bytes: slot.downloaded_bytes().map(|bytes| bytes as i64),
+        eta_seconds: slot.eta_seconds(),
+        client_id: slot.nzo_id,
+        name: slot.filename,
+        status: status.to_string(),
+        download_speed: None,
+        upload_speed: None,
+        upload_bytes: None,
+        seeders: None,
+        leechers: None,
+        save_path: None,
+        ratio: None,
+        seeding_time_seconds: None,
+    }
+}
+
+/// Status of a finished, failed or post-processing download
+fn history_status(slot: HistorySlot) -> ClientDownloadStatus {
+    // Everything but Completed and Failed is still being verified, repaired
+    // or unpacked, so it is not ready to import yet
+    let status = match slot.status.as_str() {
+        "Completed" => "completed",
+        "Failed" => "error",
+        _ => "downloading",
+    };
+
+    ClientDownloadStatus {
+        client_id: slot.nzo_id,
+        name: slot.name,
+        status: status.to_string(),
+        progress: 1.0,
+        download_speed: None,
+        upload_speed: None,
+        downloaded_bytes: Some(slot.bytes as i64),
+        upload_bytes: None,
+        eta_seconds: Some(0),
+        seeders: None,
+        leechers: None,
+        save_path: slot.storage,
+        ratio: None,
+        seeding_time_seconds: None,
+    }
+}
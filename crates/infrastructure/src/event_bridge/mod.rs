@@ -0,0 +1,495 @@
+//! External event bridge
+//!
+//! Republishes `EventEnvelope`s from the in-process event bus to an external
+//! message broker so home automation and data pipelines can react to Radarr
+//! events without polling the API. The bridge is an `EventHandler`, so it is
+//! attached to the regular `EventProcessor` like any other handler.
+//!
+//! Broker clients are behind cargo features: `nats` enables the NATS
+//! publisher and `mqtt` enables the MQTT publisher.
+
+#[cfg(feature = "mqtt")]
+pub mod mqtt;
+#[cfg(feature = "nats")]
+pub mod nats;
+
+use async_trait::async_trait;
+use radarr_core::events::{EventEnvelope, EventHandler};
+use radarr_core::{RadarrError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+
+/// Broker the bridge publishes to
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum BridgeTransport {
+    /// NATS server, e.g. `nats://localhost:4222`
+    Nats {
+        url: String,
+        /// Optional token for token authentication
+        token: Option<String>,
+    },
+    /// MQTT broker
+    Mqtt {
+        host: String,
+        port: u16,
+        client_id: String,
+        username: Option<String>,
+        password: Option<String>,
+        /// Quality of service level (0, 1 or 2)
+        qos: u8,
+        /// Whether the broker should retain the last message per topic
+        retain: bool,
+    },
+}
+
+impl BridgeTransport {
+    /// Separator between topic levels for this broker
+    pub fn topic_separator(&self) -> char {
+        match self {
+            BridgeTransport::Nats { .. } => '.',
+            BridgeTransport::Mqtt { .. } => '/',
+        }
+    }
+
+    /// Short broker name for logs
+    pub fn name(&self) -> &'static str {
+        match self {
+            BridgeTransport::Nats { .. } => "nats",
+            BridgeTransport::Mqtt { .. } => "mqtt",
+        }
+    }
+}
+
+/// Event bridge configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventBridgeConfig {
+    /// Broker connection settings
+    pub transport: BridgeTransport,
+    /// Prefix for generated topics (e.g. `radarr` gives `radarr.download_complete`)
+    pub topic_prefix: String,
+    /// Explicit topic per event type, overriding the generated topic
+    pub topic_overrides: HashMap<String, String>,
+    /// Event types to forward; empty forwards everything
+    pub events: HashSet<String>,
+}
+
+impl EventBridgeConfig {
+    /// Create a configuration with generated topics for every event
+    pub fn new(transport: BridgeTransport) -> Self {
+        Self {
+            transport,
+            topic_prefix: "radarr".to_string(),
+            topic_overrides: HashMap::new(),
+            events: HashSet::new(),
+        }
+    }
+
+    pub fn with_topic_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.topic_prefix = prefix.into();
+        self
+    }
+
+    pub fn with_topic(mut self, event_type: impl Into<String>, topic: impl Into<String>) -> Self {
+        self.topic_overrides.insert(event_type.into(), topic.into());
+        self
+    }
+
+    pub fn with_events<I, S>(mut self, events: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.events = events.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Load configuration from environment variables
+    ///
+    /// Returns `Ok(None)` when `EVENT_BRIDGE_TRANSPORT` is not set.
+    /// - `EVENT_BRIDGE_TRANSPORT`: `nats` or `mqtt`
+    /// - `EVENT_BRIDGE_URL`: NATS server URL
+    /// - `EVENT_BRIDGE_TOKEN`: NATS auth token
+    /// - `EVENT_BRIDGE_MQTT_HOST`, `EVENT_BRIDGE_MQTT_PORT`, `EVENT_BRIDGE_MQTT_CLIENT_ID`,
+    ///   `EVENT_BRIDGE_MQTT_USERNAME`, `EVENT_BRIDGE_MQTT_PASSWORD`,
+    ///   `EVENT_BRIDGE_MQTT_QOS`, `EVENT_BRIDGE_MQTT_RETAIN`
+    /// - `EVENT_BRIDGE_TOPIC_PREFIX`: prefix for generated topics
+    /// - `EVENT_BRIDGE_TOPICS`: overrides as `DownloadComplete=home/radarr/done,...`
+    /// - `EVENT_BRIDGE_EVENTS`: comma separated event types to forward
+    pub fn from_env() -> Result<Option<Self>> {
+        let transport = match env::var("EVENT_BRIDGE_TRANSPORT") {
+            Ok(value) if !value.trim().is_empty() => value.trim().to_lowercase(),
+            _ => return Ok(None),
+        };
+
+        let transport = match transport.as_str() {
+            "nats" => BridgeTransport::Nats {
+                url: env::var("EVENT_BRIDGE_URL")
+                    .unwrap_or_else(|_| "nats://localhost:4222".to_string()),
+                token: env::var("EVENT_BRIDGE_TOKEN").ok(),
+            },
+            "mqtt" => BridgeTransport::Mqtt {
+                host: env::var("EVENT_BRIDGE_MQTT_HOST")
+                    .unwrap_or_else(|_| "localhost".to_string()),
+                port: parse_env("EVENT_BRIDGE_MQTT_PORT", 1883)?,
+                client_id: env::var("EVENT_BRIDGE_MQTT_CLIENT_ID")
+                    .unwrap_or_else(|_| "radarr".to_string()),
+                username: env::var("EVENT_BRIDGE_MQTT_USERNAME").ok(),
+                password: env::var("EVENT_BRIDGE_MQTT_PASSWORD").ok(),
+                qos: parse_env("EVENT_BRIDGE_MQTT_QOS", 1)?,
+                retain: parse_env("EVENT_BRIDGE_MQTT_RETAIN", false)?,
+            },
+            other => {
+                return Err(RadarrError::ConfigurationError {
+                    field: "EVENT_BRIDGE_TRANSPORT".to_string(),
+                    message: format!("Unsupported transport '{}', expected nats or mqtt", other),
+                })
+            }
+        };
+
+        let mut config = Self::new(transport);
+        if let Ok(prefix) = env::var("EVENT_BRIDGE_TOPIC_PREFIX") {
+            config.topic_prefix = prefix;
+        }
+        if let Ok(topics) = env::var("EVENT_BRIDGE_TOPICS") {
+            config.topic_overrides = parse_topic_overrides(&topics)?;
+        }
+        if let Ok(events) = env::var("EVENT_BRIDGE_EVENTS") {
+            config.events = events
+                .split(',')
+                .map(str::trim)
+                .filter(|e| !e.is_empty())
+                .map(str::to_string)
+                .collect();
+        }
+
+        config.validate()?;
+        Ok(Some(config))
+    }
+
+    /// Validate broker settings
+    pub fn validate(&self) -> Result<()> {
+        match &self.transport {
+            BridgeTransport::Nats { url, .. } if url.trim().is_empty() => {
+                Err(RadarrError::ConfigurationError {
+                    field: "event_bridge.url".to_string(),
+                    message: "NATS URL cannot be empty".to_string(),
+                })
+            }
+            BridgeTransport::Mqtt { host, .. } if host.trim().is_empty() => {
+                Err(RadarrError::ConfigurationError {
+                    field: "event_bridge.host".to_string(),
+                    message: "MQTT host cannot be empty".to_string(),
+                })
+            }
+            BridgeTransport::Mqtt { qos, .. } if *qos > 2 => Err(RadarrError::ConfigurationError {
+                field: "event_bridge.qos".to_string(),
+                message: format!("MQTT QoS must be 0, 1 or 2, got {}", qos),
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn parse_env<T: std::str::FromStr>(name: &str, default: T) -> Result<T> {
+    match env::var(name) {
+        Ok(value) => value
+            .trim()
+            .parse()
+            .map_err(|_| RadarrError::ConfigurationError {
+                field: name.to_string(),
+                message: format!("Invalid value: {}", value),
+            }),
+        Err(_) => Ok(default),
+    }
+}
+
+/// Parse `Event=topic,Event=topic` pairs
+fn parse_topic_overrides(value: &str) -> Result<HashMap<String, String>> {
+    let mut overrides = HashMap::new();
+    for pair in value.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let (event, topic) =
+            pair.split_once('=')
+                .ok_or_else(|| RadarrError::ConfigurationError {
+                    field: "EVENT_BRIDGE_TOPICS".to_string(),
+                    message: format!("Expected Event=topic, got '{}'", pair),
+                })?;
+        overrides.insert(event.trim().to_string(), topic.trim().to_string());
+    }
+    Ok(overrides)
+}
+
+/// Maps event types to broker topics
+#[derive(Debug, Clone)]
+pub struct TopicMapper {
+    prefix: String,
+    separator: char,
+    overrides: HashMap<String, String>,
+}
+
+impl TopicMapper {
+    pub fn new(
+        prefix: impl Into<String>,
+        separator: char,
+        overrides: HashMap<String, String>,
+    ) -> Self {
+        Self {
+            prefix: prefix.into(),
+            separator,
+            overrides,
+        }
+    }
+
+    /// Topic for an event type, e.g. `DownloadComplete` -> `radarr.download_complete`
+    pub fn topic_for(&self, event_type: &str) -> String {
+        if let Some(topic) = self.overrides.get(event_type) {
+            return topic.clone();
+        }
+
+        let name = to_snake_case(event_type);
+        if self.prefix.is_empty() {
+            name
+        } else {
+            format!("{}{}{}", self.prefix, self.separator, name)
+        }
+    }
+}
+
+fn to_snake_case(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 4);
+    for (i, c) in value.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 {
+                out.push('_');
+            }
+            out.extend(c.to_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Publisher for a specific broker
+#[async_trait]
+pub trait BrokerPublisher: Send + Sync {
+    /// Broker name for logs and metrics
+    fn name(&self) -> &str;
+
+    /// Publish a payload to a topic
+    async fn publish(&self, topic: &str, payload: Vec<u8>) -> Result<()>;
+}
+
+/// Counters for bridge activity
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EventBridgeStats {
+    pub published: u64,
+    pub failed: u64,
+}
+
+/// Forwards event envelopes to an external broker
+pub struct EventBridge {
+    publisher: Arc<dyn BrokerPublisher>,
+    mapper: TopicMapper,
+    events: HashSet<String>,
+    published: AtomicU64,
+    failed: AtomicU64,
+}
+
+impl EventBridge {
+    /// Create a bridge over an already connected publisher
+    pub fn new(config: &EventBridgeConfig, publisher: Arc<dyn BrokerPublisher>) -> Self {
+        Self {
+            publisher,
+            mapper: TopicMapper::new(
+                config.topic_prefix.clone(),
+                config.transport.topic_separator(),
+                config.topic_overrides.clone(),
+            ),
+            events: config.events.clone(),
+            published: AtomicU64::new(0),
+            failed: AtomicU64::new(0),
+        }
+    }
+
+    /// Connect to the configured broker and create the bridge
+    pub async fn connect(config: &EventBridgeConfig) -> Result<Self> {
+        config.validate()?;
+
+        let publisher = Self::connect_publisher(&config.transport).await?;
+        info!(
+            "Event bridge connected to {} (topic prefix '{}')",
+            publisher.name(),
+            config.topic_prefix
+        );
+        Ok(Self::new(config, publisher))
+    }
+
+    /// Connect a publisher for `transport`, if its support is compiled in
+    async fn connect_publisher(transport: &BridgeTransport) -> Result<Arc<dyn BrokerPublisher>> {
+        match transport {
+            #[cfg(feature = "nats")]
+            BridgeTransport::Nats { url, token } => Ok(Arc::new(
+                nats::NatsPublisher::connect(url, token.as_deref()).await?,
+            )),
+            #[cfg(feature = "mqtt")]
+            transport @ BridgeTransport::Mqtt { .. } => {
+                Ok(Arc::new(mqtt::MqttPublisher::connect(transport)?))
+            }
+            #[allow(unreachable_patterns)]
+            transport => Err(RadarrError::ConfigurationError {
+                field: "event_bridge.transport".to_string(),
+                message: format!(
+                    "{} support is not compiled in; rebuild with the '{}' feature",
+                    transport.name(),
+                    transport.name()
+                ),
+            }),
+        }
+    }
+
+    /// Topic an envelope would be published to
+    pub fn topic_for(&self, envelope: &EventEnvelope) -> String {
+        self.mapper.topic_for(envelope.event.event_type())
+    }
+
+    pub fn stats(&self) -> EventBridgeStats {
+        EventBridgeStats {
+            published: self.published.load(Ordering::Relaxed),
+            failed: self.failed.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[async_trait]
+impl EventHandler for EventBridge {
+    async fn handle_event(&self, envelope: &EventEnvelope) -> Result<()> {
+        let topic = self.topic_for(envelope);
+        let payload = serde_json::to_vec(envelope)?;
+
+        match self.publisher.publish(&topic, payload).await {
+            Ok(()) => {
+                self.published.fetch_add(1, Ordering::Relaxed);
+                debug!(
+                    "Bridged {} to {} topic {}",
+                    envelope.event.event_type(),
+                    self.publisher.name(),
+                    topic
+                );
+                Ok(())
+            }
+            Err(e) => {
+                self.failed.fetch_add(1, Ordering::Relaxed);
+                warn!(
+                    "Failed to bridge {} to {}: {}",
+                    envelope.event.event_type(),
+                    self.publisher.name(),
+                    e
+                );
+                Err(e)
+            }
+        }
+    }
+
+    fn should_handle(&self, envelope: &EventEnvelope) -> bool {
+        self.events.is_empty() || self.events.contains(envelope.event.event_type())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use radarr_core::events::SystemEvent;
+    use tokio::sync::Mutex;
+    use uuid::Uuid;
+
+    #[derive(Default)]
+    struct RecordingPublisher {
+        messages: Mutex<Vec<(String, Vec<u8>)>>,
+    }
+
+    #[async_trait]
+    impl BrokerPublisher for RecordingPublisher {
+        fn name(&self) -> &str {
+            "recording"
+        }
+
+        async fn publish(&self, topic: &str, payload: Vec<u8>) -> Result<()> {
+            self.messages
+                .lock()
+                .await
+                .push((topic.to_string(), payload));
+            Ok(())
+        }
+    }
+
+    fn nats_config() -> EventBridgeConfig {
+        EventBridgeConfig::new(BridgeTransport::Nats {
+            url: "nats://localhost:4222".to_string(),
+            token: None,
+        })
+    }
+
+    fn import_complete() -> EventEnvelope {
+        EventEnvelope::new(SystemEvent::ImportComplete {
+            movie_id: Uuid::new_v4(),
+            destination_path: "/movies/Test (2023)".to_string(),
+            file_count: 1,
+        })
+    }
+
+    #[test]
+    fn test_topic_mapping() {
+        let mapper = TopicMapper::new(
+            "radarr",
+            '/',
+            HashMap::from([("ImportFailed".to_string(), "alerts/radarr".to_string())]),
+        );
+        assert_eq!(
+            mapper.topic_for("DownloadComplete"),
+            "radarr/download_complete"
+        );
+        assert_eq!(mapper.topic_for("ImportFailed"), "alerts/radarr");
+
+        let mapper = TopicMapper::new("", '.', HashMap::new());
+        assert_eq!(mapper.topic_for("SystemHealth"), "system_health");
+    }
+
+    #[test]
+    fn test_parse_topic_overrides() {
+        let overrides =
+            parse_topic_overrides("DownloadComplete=home/radarr/done, ImportFailed=alerts")
+                .unwrap();
+        assert_eq!(overrides["DownloadComplete"], "home/radarr/done");
+        assert_eq!(overrides["ImportFailed"], "alerts");
+        assert!(parse_topic_overrides("missing-separator").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_bridge_publishes_envelope() {
+        let publisher = Arc::new(RecordingPublisher::default());
+        let bridge = EventBridge::new(&nats_config(), publisher.clone());
+
+        let envelope = import_complete();
+        assert!(bridge.should_handle(&envelope));
+        bridge.handle_event(&envelope).await.unwrap();
+
+        let messages = publisher.messages.lock().await;
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].0, "radarr.import_complete");
+        let decoded: EventEnvelope = serde_json::from_slice(&messages[0].1).unwrap();
+        assert_eq!(decoded.event_id, envelope.event_id);
+        assert_eq!(bridge.stats().published, 1);
+    }
+
+    #[test]
+    fn test_event_filter() {
+        let config = nats_config().with_events(["DownloadComplete"]);
+        let bridge = EventBridge::new(&config, Arc::new(RecordingPublisher::default()));
+        assert!(!bridge.should_handle(&import_complete()));
+    }
+}
@@ -0,0 +1,94 @@
+//! MQTT publisher for the event bridge
+
+use super::{BridgeTransport, BrokerPublisher};
+use async_trait::async_trait;
+use radarr_core::{RadarrError, Result};
+use rumqttc::{AsyncClient, Event, MqttOptions, Packet, QoS};
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+/// Capacity of the outgoing request channel to the MQTT event loop
+const REQUEST_CAPACITY: usize = 100;
+
+/// Publishes bridged events as MQTT messages
+pub struct MqttPublisher {
+    client: AsyncClient,
+    qos: QoS,
+    retain: bool,
+}
+
+impl MqttPublisher {
+    /// Create the client and spawn its event loop
+    ///
+    /// The event loop reconnects on its own; publishes made while the broker
+    /// is unreachable are buffered up to `REQUEST_CAPACITY`.
+    pub fn connect(transport: &BridgeTransport) -> Result<Self> {
+        let BridgeTransport::Mqtt {
+            host,
+            port,
+            client_id,
+            username,
+            password,
+            qos,
+            retain,
+        } = transport
+        else {
+            return Err(RadarrError::ConfigurationError {
+                field: "event_bridge.transport".to_string(),
+                message: "MQTT publisher requires an MQTT transport".to_string(),
+            });
+        };
+
+        let mut options = MqttOptions::new(client_id.clone(), host.clone(), *port);
+        options.set_keep_alive(Duration::from_secs(30));
+        if let Some(username) = username {
+            options.set_credentials(username.clone(), password.clone().unwrap_or_default());
+        }
+
+        let qos = match qos {
+            0 => QoS::AtMostOnce,
+            1 => QoS::AtLeastOnce,
+            _ => QoS::ExactlyOnce,
+        };
+
+        let (client, mut event_loop) = AsyncClient::new(options, REQUEST_CAPACITY);
+        let broker = format!("{}:{}", host, port);
+        tokio::spawn(async move {
+            loop {
+                match event_loop.poll().await {
+                    Ok(Event::Incoming(Packet::ConnAck(_))) => {
+                        info!("Connected to MQTT broker at {}", broker)
+                    }
+                    Ok(event) => debug!("MQTT event: {:?}", event),
+                    Err(e) => {
+                        warn!("MQTT connection to {} failed: {}", broker, e);
+                        tokio::time::sleep(Duration::from_secs(5)).await;
+                    }
+                }
+            }
+        });
+
+        Ok(Self {
+            client,
+            qos,
+            retain: *retain,
+        })
+    }
+}
+
+#[async_trait]
+impl BrokerPublisher for MqttPublisher {
+    fn name(&self) -> &str {
+        "mqtt"
+    }
+
+    async fn publish(&self, topic: &str, payload: Vec<u8>) -> Result<()> {
+        self.client
+            .publish(topic, self.qos, self.retain, payload)
+            .await
+            .map_err(|e| RadarrError::ExternalServiceError {
+                service: "mqtt".to_string(),
+                error: e.to_string(),
+            })
+    }
+}
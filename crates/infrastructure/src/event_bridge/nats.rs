@@ -0,0 +1,49 @@
+//! NATS publisher for the event bridge
+
+use super::BrokerPublisher;
+use async_trait::async_trait;
+use radarr_core::{RadarrError, Result};
+use tracing::info;
+
+/// Publishes bridged events as NATS messages
+pub struct NatsPublisher {
+    client: async_nats::Client,
+}
+
+impl NatsPublisher {
+    /// Connect to a NATS server, optionally using token authentication
+    pub async fn connect(url: &str, token: Option<&str>) -> Result<Self> {
+        let mut options = async_nats::ConnectOptions::new().name("radarr-event-bridge");
+        if let Some(token) = token {
+            options = options.token(token.to_string());
+        }
+
+        let client = options
+            .connect(url)
+            .await
+            .map_err(|e| RadarrError::ExternalServiceError {
+                service: "nats".to_string(),
+                error: format!("Failed to connect to {}: {}", url, e),
+            })?;
+
+        info!("Connected to NATS at {}", url);
+        Ok(Self { client })
+    }
+}
+
+#[async_trait]
+impl BrokerPublisher for NatsPublisher {
+    fn name(&self) -> &str {
+        "nats"
+    }
+
+    async fn publish(&self, topic: &str, payload: Vec<u8>) -> Result<()> {
+        self.client
+            .publish(topic.to_string(), payload.into())
+            .await
+            .map_err(|e| RadarrError::ExternalServiceError {
+                service: "nats".to_string(),
+                error: e.to_string(),
+            })
+    }
+}
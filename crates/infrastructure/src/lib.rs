@@ -3,22 +3,39 @@
 //! This module provides concrete implementations of repository traits
 //! defined in the core domain layer, using PostgreSQL as the data store.
 
+pub mod backup;
+pub mod cache;
 pub mod database;
 pub mod download_clients;
 pub mod error;
+pub mod event_bridge;
 pub mod lists;
+pub mod media_cover;
+pub mod migrations;
 pub mod monitoring;
+pub mod omdb;
+pub mod pool_monitor;
+pub mod read_replica;
 pub mod repositories;
 pub mod streaming;
 pub mod tmdb;
 pub mod trakt;
+pub mod updates;
 pub mod watchmode;
 
 // Re-export for easy access
+pub use backup::{BackupConfig, BackupService, BackupSummary, BackupType, RestoreReport};
+pub use cache::{CacheBackend, CacheConfig, CacheKey, CacheManager, CacheMetrics};
 pub use database::*;
 pub use download_clients::*;
 pub use error::*;
 pub use lists::*;
+pub use media_cover::{MediaCoverConfig, MediaCoverService};
+pub use migrations::{MigrationInfo, MigrationMode, MigrationStatus};
 pub use monitoring::*;
+pub use omdb::OmdbClient;
+pub use pool_monitor::{DatabaseCheck, PoolMetrics, PoolMonitor, PoolStatus};
+pub use read_replica::*;
 pub use repositories::*;
 pub use tmdb::*;
+pub use updates::{UpdateChecker, UpdateConfig, UpdateRelease, UpdateStatus};
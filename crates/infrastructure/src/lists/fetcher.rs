@@ -0,0 +1,66 @@
+//! List fetcher backed by the list parsers
+//!
+//! Connects the list sync scheduler to the providers in this module: a sync
+//! job's `source_type` selects the parser and its `list_url` is what gets
+//! parsed.
+
+use super::common::{ListItem, ListParseError, ListParser};
+use async_trait::async_trait;
+use radarr_core::jobs::{ListFetcher, ListSyncItem, SyncError, SyncJob};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Dispatches sync jobs to list parsers by source type
+#[derive(Default, Clone)]
+pub struct ParserListFetcher {
+    parsers: HashMap<String, Arc<dyn ListParser>>,
+}
+
+impl ParserListFetcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Handle jobs of `source_type` (matched case-insensitively) with `parser`
+    pub fn with_parser(mut self, source_type: &str, parser: Arc<dyn ListParser>) -> Self {
+        self.parsers.insert(source_type.to_lowercase(), parser);
+        self
+    }
+}
+
+#[async_trait]
+impl ListFetcher for ParserListFetcher {
+    async fn fetch(&self, job: &SyncJob) -> Result<Vec<ListSyncItem>, SyncError> {
+        let parser = self
+            .parsers
+            .get(&job.source_type.to_lowercase())
+            .ok_or_else(|| {
+                SyncError::Unknown(format!("No list provider for '{}'", job.source_type))
+            })?;
+        let list_url = job.list_url.as_deref().ok_or_else(|| {
+            SyncError::ParseError(format!("List '{}' has no list URL", job.list_name))
+        })?;
+
+        let items = parser.parse_list(list_url).await.map_err(|e| match e {
+            ListParseError::NotFound => SyncError::ListNotFound(job.list_id),
+            ListParseError::RateLimited => SyncError::RateLimited,
+            ListParseError::HttpError(e) => SyncError::NetworkError(e.to_string()),
+            ListParseError::InvalidUrl(message) | ListParseError::ParseError(message) => {
+                SyncError::ParseError(message)
+            }
+            e => SyncError::Unknown(e.to_string()),
+        })?;
+
+        Ok(items.into_iter().map(to_sync_item).collect())
+    }
+}
+
+fn to_sync_item(item: ListItem) -> ListSyncItem {
+    ListSyncItem {
+        tmdb_id: item.tmdb_id,
+        imdb_id: item.imdb_id,
+        title: item.title,
+        year: item.year,
+        metadata: item.source_metadata,
+    }
+}
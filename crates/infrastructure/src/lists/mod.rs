@@ -1,7 +1,13 @@
 pub mod common;
+pub mod fetcher;
 pub mod imdb;
+pub mod plex;
 pub mod tmdb;
+pub mod trakt;
 
 pub use common::{ListItem, ListSource, ListSyncResult};
+pub use fetcher::ParserListFetcher;
 pub use imdb::ImdbListParser;
+pub use plex::PlexWatchlistProvider;
 pub use tmdb::TmdbListClient;
+pub use trakt::{TraktDeviceAuthStatus, TraktListKind, TraktListProvider};
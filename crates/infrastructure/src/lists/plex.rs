@@ -0,0 +1,274 @@
+//! Plex watchlist import list
+//!
+//! Reads the watchlist of the Plex account a token belongs to from Plex's
+//! discover service. Watchlist entries only carry Plex's own keys, so each
+//! movie's metadata is looked up to find its TMDb and IMDb IDs.
+
+use super::common::{ListItem, ListParseError, ListParser, ListSource};
+use async_trait::async_trait;
+use futures::{stream, StreamExt, TryStreamExt};
+use reqwest::{
+    header::{HeaderMap, HeaderValue, ACCEPT, USER_AGENT},
+    Client, StatusCode,
+};
+use serde::{de::DeserializeOwned, Deserialize};
+use tracing::{debug, info};
+
+/// Watchlist section; type=1 limits it to movies
+const WATCHLIST_PATH: &str = "/library/sections/watchlist/all?type=1";
+
+/// Watchlist entries requested per page
+const PAGE_SIZE: usize = 100;
+
+/// Metadata lookups in flight at once
+const LOOKUP_CONCURRENCY: usize = 4;
+
+/// Plex watchlist provider
+pub struct PlexWatchlistProvider {
+    client: Client,
+    token: String,
+    client_identifier: String,
+    base_url: String,
+}
+
+impl PlexWatchlistProvider {
+    pub fn new(token: String) -> Self {
+        Self {
+            client: Client::new(),
+            token,
+            client_identifier: "radarr-mvp".to_string(),
+            base_url: "https://discover.provider.plex.tv".to_string(),
+        }
+    }
+
+    /// Identify this client to Plex as `client_identifier`
+    pub fn with_client_identifier(mut self, client_identifier: String) -> Self {
+        self.client_identifier = client_identifier;
+        self
+    }
+
+    /// Fetch the watchlist's movies
+    pub async fn fetch_watchlist(&self) -> Result<Vec<ListItem>, ListParseError> {
+        info!("Fetching Plex watchlist");
+
+        let mut entries = Vec::new();
+        loop {
+            let path = format!(
+                "{}&X-Plex-Container-Start={}&X-Plex-Container-Size={}",
+                WATCHLIST_PATH,
+                entries.len(),
+                PAGE_SIZE
+            );
+            let page: PlexResponse = self.get(&path).await?;
+            let container = page.media_container;
+            let received = container.metadata.len();
+            entries.extend(container.metadata);
+
+            let total = container.total_size.unwrap_or(entries.len());
+            if received == 0 || entries.len() >= total {
+                break;
+            }
+        }
+
+        let items: Vec<ListItem> = stream::iter(
+            entries
+                .into_iter()
+                .filter(|entry| entry.kind.as_deref().unwrap_or("movie") == "movie"),
+        )
+        .map(|entry| self.resolve(entry))
+        .buffered(LOOKUP_CONCURRENCY)
+        .try_collect()
+        .await?;
+
+        debug!("Plex watchlist returned {} movies", items.len());
+        Ok(items)
+    }
+
+    /// Look up an entry's external IDs
+    async fn resolve(&self, entry: PlexMetadata) -> Result<ListItem, ListParseError> {
+        let path = format!("/library/metadata/{}?includeGuids=1", entry.rating_key);
+        let details: PlexResponse = self.get(&path).await?;
+        let guids = details
+            .media_container
+            .metadata
+            .into_iter()
+            .next()
+            .map(|metadata| metadata.guids)
+            .unwrap_or_default();
+
+        Ok(entry.into_item(&guids))
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T, ListParseError> {
+        let response = self
+            .client
+            .get(format!("{}{}", self.base_url, path))
+            .headers(self.build_headers())
+            .send()
+            .await?;
+
+        match response.status() {
+            status if status.is_success() => {}
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+                return Err(ListParseError::AuthRequired)
+            }
+            StatusCode::NOT_FOUND => return Err(ListParseError::NotFound),
+            StatusCode::TOO_MANY_REQUESTS => return Err(ListParseError::RateLimited),
+            status => {
+                return Err(ListParseError::Unknown(format!(
+                    "Plex returned HTTP {}",
+                    status
+                )))
+            }
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| ListParseError::ParseError(e.to_string()))
+    }
+
+    fn build_headers(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
+        headers.insert(USER_AGENT, HeaderValue::from_static("Radarr-MVP/1.0"));
+        headers.insert("X-Plex-Product", HeaderValue::from_static("Radarr-MVP"));
+        if let Ok(token) = HeaderValue::from_str(&self.token) {
+            headers.insert("X-Plex-Token", token);
+        }
+        if let Ok(identifier) = HeaderValue::from_str(&self.client_identifier) {
+            headers.insert("X-Plex-Client-Identifier", identifier);
+        }
+        headers
+    }
+}
+
+#[async_trait]
+impl ListParser for PlexWatchlistProvider {
+    async fn parse_list(&self, list_url: &str) -> Result<Vec<ListItem>, ListParseError> {
+        if !self.validate_url(list_url) {
+            return Err(ListParseError::InvalidUrl(format!(
+                "Invalid Plex list: {}",
+                list_url
+            )));
+        }
+        self.fetch_watchlist().await
+    }
+
+    fn source_type(&self) -> ListSource {
+        ListSource::Plex
+    }
+
+    /// The only Plex list is the account watchlist, named `watchlist` or by
+    /// its app.plex.tv URL
+    fn validate_url(&self, url: &str) -> bool {
+        let url = url.trim().trim_end_matches('/');
+        url.eq_ignore_ascii_case("watchlist")
+            || (url.contains("plex.tv") && url.ends_with("/watchlist"))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PlexResponse {
+    #[serde(rename = "MediaContainer")]
+    media_container: PlexMediaContainer,
+}
+
+#[derive(Debug, Deserialize)]
+struct PlexMediaContainer {
+    #[serde(rename = "totalSize")]
+    total_size: Option<usize>,
+    #[serde(rename = "Metadata", default)]
+    metadata: Vec<PlexMetadata>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PlexMetadata {
+    #[serde(rename = "ratingKey")]
+    rating_key: String,
+    #[serde(default)]
+    title: String,
+    year: Option<i32>,
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    #[serde(rename = "Guid", default)]
+    guids: Vec<PlexGuid>,
+}
+
+/// External ID such as `tmdb://438631` or `imdb://tt1160419`
+#[derive(Debug, Deserialize)]
+struct PlexGuid {
+    id: String,
+}
+
+impl PlexMetadata {
+    fn into_item(self, guids: &[PlexGuid]) -> ListItem {
+        ListItem {
+            tmdb_id: external_id(guids, "tmdb://").and_then(|id| id.parse().ok()),
+            imdb_id: external_id(guids, "imdb://").map(str::to_string),
+            title: self.title,
+            year: self.year,
+            overview: None,
+            poster_path: None,
+            backdrop_path: None,
+            release_date: None,
+            runtime: None,
+            genres: vec![],
+            original_language: None,
+            vote_average: None,
+            vote_count: None,
+            popularity: None,
+            source_metadata: serde_json::json!({
+                "source": "plex",
+                "plex_rating_key": self.rating_key,
+            }),
+        }
+    }
+}
+
+fn external_id<'a>(guids: &'a [PlexGuid], scheme: &str) -> Option<&'a str> {
+    guids.iter().find_map(|guid| guid.id.strip_prefix(scheme))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_watchlist_locations() {
+        let provider = PlexWatchlistProvider::new("token".to_string());
+        assert!(provider.validate_url("watchlist"));
+        assert!(provider.validate_url("https://app.plex.tv/desktop/#!/watchlist/"));
+        assert!(!provider.validate_url("https://trakt.tv/users/alice/watchlist"));
+        assert!(!provider.validate_url("popular"));
+    }
+
+    #[test]
+    fn test_entries_resolve_to_external_ids() {
+        let page: PlexResponse = serde_json::from_str(
+            r#"{"MediaContainer": {"totalSize": 1, "Metadata": [
+                {"ratingKey": "5d776b59ad5437001f79c6f8", "title": "Dune",
+                 "year": 2021, "type": "movie"}
+            ]}}"#,
+        )
+        .unwrap();
+        let details: PlexResponse = serde_json::from_str(
+            r#"{"MediaContainer": {"Metadata": [{"ratingKey": "5d776b59ad5437001f79c6f8",
+                "Guid": [{"id": "imdb://tt1160419"}, {"id": "tmdb://438631"},
+                         {"id": "tvdb://13011"}]}]}}"#,
+        )
+        .unwrap();
+
+        let entry = page.media_container.metadata.into_iter().next().unwrap();
+        let guids = &details.media_container.metadata[0].guids;
+        let item = entry.into_item(guids);
+
+        assert_eq!(item.tmdb_id, Some(438631));
+        assert_eq!(item.imdb_id.as_deref(), Some("tt1160419"));
+        assert_eq!(item.title, "Dune");
+        assert_eq!(
+            item.source_metadata["plex_rating_key"],
+            "5d776b59ad5437001f79c6f8"
+        );
+    }
+}
@@ -0,0 +1,376 @@
+//! Trakt import lists
+//!
+//! Supports the authenticated user's watchlist, any user's custom lists and
+//! the popular/anticipated charts. Watchlists and private lists need a user
+//! token obtained through the OAuth device flow; the token is kept in the
+//! OAuth token store and refreshed there when it is about to expire, so a
+//! restart does not require authorizing again.
+
+use super::common::{ListItem, ListParseError, ListParser, ListSource};
+use crate::trakt::oauth::{TraktOAuth, TraktOAuthConfig};
+use async_trait::async_trait;
+use radarr_core::{
+    streaming::{traits::OAuthTokenRepository, OAuthToken, TraktDeviceCode},
+    RadarrError,
+};
+use reqwest::{
+    header::{HeaderMap, HeaderValue, AUTHORIZATION, USER_AGENT},
+    Client, StatusCode,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use tracing::{debug, info};
+
+/// Service name Trakt tokens are stored under
+const TOKEN_SERVICE: &str = "trakt";
+
+/// Items requested per chart page
+const CHART_LIMIT: u32 = 100;
+
+/// A Trakt list to import
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TraktListKind {
+    /// The authenticated user's watchlist
+    Watchlist,
+    /// A user's custom list
+    CustomList { user: String, slug: String },
+    /// Most popular movies
+    Popular,
+    /// Most anticipated upcoming movies
+    Anticipated,
+}
+
+impl TraktListKind {
+    /// Parse a list location: `watchlist`, `popular`, `anticipated`,
+    /// `<user>/<list>` or a trakt.tv URL of any of these
+    pub fn parse(location: &str) -> Option<Self> {
+        let path = location
+            .trim()
+            .trim_start_matches("https://")
+            .trim_start_matches("http://")
+            .trim_start_matches("www.")
+            .trim_start_matches("trakt.tv");
+        let path = path.split(['?', '#']).next().unwrap_or_default();
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+        match segments.as_slice() {
+            ["watchlist"] | ["users", _, "watchlist"] => Some(Self::Watchlist),
+            ["popular"] | ["movies", "popular"] => Some(Self::Popular),
+            ["anticipated"] | ["movies", "anticipated"] => Some(Self::Anticipated),
+            ["users", user, "lists", slug] | [user, slug]
+                if !matches!(*user, "movies" | "users") =>
+            {
+                Some(Self::CustomList {
+                    user: user.to_string(),
+                    slug: slug.to_string(),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// API endpoint returning the list's movies
+    fn endpoint(&self) -> String {
+        match self {
+            Self::Watchlist => "/users/me/watchlist/movies".to_string(),
+            Self::CustomList { user, slug } => {
+                format!("/users/{}/lists/{}/items/movies", user, slug)
+            }
+            Self::Popular => format!("/movies/popular?limit={}", CHART_LIMIT),
+            Self::Anticipated => format!("/movies/anticipated?limit={}", CHART_LIMIT),
+        }
+    }
+
+    /// Whether the list can only be read with a user token
+    fn requires_auth(&self) -> bool {
+        matches!(self, Self::Watchlist)
+    }
+}
+
+/// Outcome of polling a pending device authorization
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TraktDeviceAuthStatus {
+    /// The user approved the device; the token has been stored
+    Authorized,
+    /// The user has not entered the code yet
+    Pending,
+}
+
+/// Trakt import list provider
+pub struct TraktListProvider {
+    client: Client,
+    oauth: TraktOAuth,
+    token_repo: Arc<dyn OAuthTokenRepository>,
+    client_id: String,
+    base_url: String,
+}
+
+impl TraktListProvider {
+    pub fn new(
+        client_id: String,
+        client_secret: String,
+        token_repo: Arc<dyn OAuthTokenRepository>,
+    ) -> Self {
+        Self {
+            client: Client::new(),
+            oauth: TraktOAuth::new(TraktOAuthConfig::new(client_id.clone(), client_secret)),
+            token_repo,
+            client_id,
+            base_url: "https://api.trakt.tv".to_string(),
+        }
+    }
+
+    /// Start the device flow; the user enters the returned code at its URL
+    pub async fn start_device_auth(&self) -> Result<TraktDeviceCode, RadarrError> {
+        self.oauth.initiate_device_flow().await
+    }
+
+    /// Check once whether the user approved `device_code`, storing the token
+    /// when they did
+    pub async fn complete_device_auth(
+        &self,
+        device_code: &str,
+    ) -> Result<TraktDeviceAuthStatus, RadarrError> {
+        match self.oauth.poll_for_token(device_code).await {
+            Ok(response) => {
+                let token = self.oauth.token_to_oauth(response);
+                self.token_repo.store_token(token).await?;
+                info!("Trakt account authorized for import lists");
+                Ok(TraktDeviceAuthStatus::Authorized)
+            }
+            Err(RadarrError::ExternalServiceError { error, .. })
+                if error.contains("Authorization pending") =>
+            {
+                Ok(TraktDeviceAuthStatus::Pending)
+            }
+            Err(RadarrError::RateLimited { .. }) => Ok(TraktDeviceAuthStatus::Pending),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Whether a Trakt user token is stored
+    pub async fn is_authorized(&self) -> Result<bool, RadarrError> {
+        Ok(self.token_repo.get_token(TOKEN_SERVICE).await?.is_some())
+    }
+
+    /// Fetch the movies of a list
+    pub async fn fetch(&self, kind: &TraktListKind) -> Result<Vec<ListItem>, ListParseError> {
+        info!("Fetching Trakt list {:?}", kind);
+
+        let access_token = self.access_token(kind.requires_auth()).await?;
+        let response = self
+            .client
+            .get(format!("{}{}", self.base_url, kind.endpoint()))
+            .headers(self.build_headers(access_token.as_deref()))
+            .send()
+            .await?;
+
+        match response.status() {
+            status if status.is_success() => {}
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+                return Err(ListParseError::AuthRequired)
+            }
+            StatusCode::NOT_FOUND => return Err(ListParseError::NotFound),
+            StatusCode::TOO_MANY_REQUESTS => return Err(ListParseError::RateLimited),
+            status => {
+                return Err(ListParseError::Unknown(format!(
+                    "Trakt returned HTTP {}",
+                    status
+                )))
+            }
+        }
+
+        let entries: Vec<TraktListEntry> = response
+            .json()
+            .await
+            .map_err(|e| ListParseError::ParseError(e.to_string()))?;
+        let items: Vec<ListItem> = entries.into_iter().map(TraktListEntry::into_item).collect();
+
+        debug!("Trakt list {:?} returned {} movies", kind, items.len());
+        Ok(items)
+    }
+
+    /// A valid user token, refreshing and persisting it when it is about to
+    /// expire. Public lists are read with the token when there is one, so
+    /// private custom lists of the authorized user work too.
+    async fn access_token(&self, required: bool) -> Result<Option<String>, ListParseError> {
+        let stored = self
+            .token_repo
+            .get_token(TOKEN_SERVICE)
+            .await
+            .map_err(|e| ListParseError::Unknown(e.to_string()))?;
+
+        let token = match stored {
+            Some(token) if !token.needs_refresh() => Some(token.access_token),
+            Some(token) => match self.refresh(&token).await {
+                Ok(access_token) => Some(access_token),
+                Err(e) if required => return Err(e),
+                Err(_) => None,
+            },
+            None => None,
+        };
+
+        match token {
+            None if required => Err(ListParseError::AuthRequired),
+            token => Ok(token),
+        }
+    }
+
+    async fn refresh(&self, token: &OAuthToken) -> Result<String, ListParseError> {
+        let refresh_token = match &token.refresh_token {
+            Some(refresh_token) => refresh_token,
+            None => return Err(ListParseError::AuthRequired),
+        };
+
+        info!("Refreshing Trakt token for import lists");
+        let response = self
+            .oauth
+            .refresh_token(refresh_token)
+            .await
+            .map_err(|_| ListParseError::AuthRequired)?;
+        let refreshed = self.oauth.token_to_oauth(response);
+        self.token_repo
+            .update_token(TOKEN_SERVICE, refreshed.clone())
+            .await
+            .map_err(|e| ListParseError::Unknown(e.to_string()))?;
+        Ok(refreshed.access_token)
+    }
+
+    fn build_headers(&self, access_token: Option<&str>) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert("Content-Type", HeaderValue::from_static("application/json"));
+        headers.insert("trakt-api-version", HeaderValue::from_static("2"));
+        if let Ok(client_id) = HeaderValue::from_str(&self.client_id) {
+            headers.insert("trakt-api-key", client_id);
+        }
+        headers.insert(USER_AGENT, HeaderValue::from_static("Radarr-MVP/1.0"));
+        if let Some(token) = access_token {
+            if let Ok(value) = HeaderValue::from_str(&format!("Bearer {}", token)) {
+                headers.insert(AUTHORIZATION, value);
+            }
+        }
+        headers
+    }
+}
+
+#[async_trait]
+impl ListParser for TraktListProvider {
+    async fn parse_list(&self, list_url: &str) -> Result<Vec<ListItem>, ListParseError> {
+        let kind = TraktListKind::parse(list_url).ok_or_else(|| {
+            ListParseError::InvalidUrl(format!("Invalid Trakt list: {}", list_url))
+        })?;
+        self.fetch(&kind).await
+    }
+
+    fn source_type(&self) -> ListSource {
+        ListSource::Trakt
+    }
+
+    fn validate_url(&self, url: &str) -> bool {
+        TraktListKind::parse(url).is_some()
+    }
+}
+
+/// Entry of a Trakt list or chart; charts return bare movies, lists wrap
+/// them with list metadata
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum TraktListEntry {
+    Wrapped { movie: TraktListMovie },
+    Bare(TraktListMovie),
+}
+
+#[derive(Debug, Deserialize)]
+struct TraktListMovie {
+    title: String,
+    year: Option<i32>,
+    ids: TraktListIds,
+}
+
+#[derive(Debug, Deserialize)]
+struct TraktListIds {
+    trakt: Option<i64>,
+    imdb: Option<String>,
+    tmdb: Option<i32>,
+}
+
+impl TraktListEntry {
+    fn into_item(self) -> ListItem {
+        let movie = match self {
+            Self::Wrapped { movie } | Self::Bare(movie) => movie,
+        };
+
+        ListItem {
+            tmdb_id: movie.ids.tmdb,
+            imdb_id: movie.ids.imdb,
+            title: movie.title,
+            year: movie.year,
+            overview: None,
+            poster_path: None,
+            backdrop_path: None,
+            release_date: None,
+            runtime: None,
+            genres: vec![],
+            original_language: None,
+            vote_average: None,
+            vote_count: None,
+            popularity: None,
+            source_metadata: serde_json::json!({
+                "source": "trakt",
+                "trakt_id": movie.ids.trakt,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_list_locations() {
+        assert_eq!(
+            TraktListKind::parse("watchlist"),
+            Some(TraktListKind::Watchlist)
+        );
+        assert_eq!(
+            TraktListKind::parse("https://trakt.tv/users/alice/watchlist"),
+            Some(TraktListKind::Watchlist)
+        );
+        assert_eq!(
+            TraktListKind::parse("https://trakt.tv/movies/anticipated"),
+            Some(TraktListKind::Anticipated)
+        );
+        assert_eq!(
+            TraktListKind::parse("https://trakt.tv/users/alice/lists/sci-fi?sort=rank"),
+            Some(TraktListKind::CustomList {
+                user: "alice".to_string(),
+                slug: "sci-fi".to_string(),
+            })
+        );
+        assert_eq!(
+            TraktListKind::parse("alice/sci-fi").map(|kind| kind.endpoint()),
+            Some("/users/alice/lists/sci-fi/items/movies".to_string())
+        );
+        assert_eq!(TraktListKind::parse("https://trakt.tv/"), None);
+        assert_eq!(TraktListKind::parse("movies/trending"), None);
+    }
+
+    #[test]
+    fn test_list_and_chart_entries_map_to_items() {
+        let entries: Vec<TraktListEntry> = serde_json::from_str(
+            r#"[
+                {"rank": 1, "type": "movie", "movie": {"title": "Dune", "year": 2021,
+                    "ids": {"trakt": 1, "imdb": "tt1160419", "tmdb": 438631}}},
+                {"title": "Arrival", "year": 2016, "ids": {"trakt": 2, "tmdb": 329865}}
+            ]"#,
+        )
+        .unwrap();
+        let items: Vec<ListItem> = entries.into_iter().map(TraktListEntry::into_item).collect();
+
+        assert_eq!(items[0].tmdb_id, Some(438631));
+        assert_eq!(items[0].imdb_id.as_deref(), Some("tt1160419"));
+        assert_eq!(items[1].title, "Arrival");
+        assert_eq!(items[1].imdb_id, None);
+    }
+}
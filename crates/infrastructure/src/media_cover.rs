@@ -0,0 +1,249 @@
+//! Cached movie artwork
+//!
+//! Posters and fanart are downloaded from TMDB the first time they are
+//! requested and kept in `{directory}/{movie_id}/`, with resized copies made
+//! by ffmpeg. A refresh that changes a movie's images drops its cached
+//! covers so the new ones are fetched on the next request, and deleting a
+//! movie removes them.
+
+use async_trait::async_trait;
+use radarr_core::{
+    events::{EventEnvelope, EventHandler, SystemEvent},
+    models::{MediaCoverType, Movie},
+    RadarrError, Result,
+};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+/// Movie `changes` entry of a refresh that changed the artwork
+const IMAGES_CHANGE: &str = "images";
+
+/// Where artwork is cached
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MediaCoverConfig {
+    /// Directory holding a folder of covers per movie
+    pub directory: String,
+}
+
+impl Default for MediaCoverConfig {
+    fn default() -> Self {
+        Self {
+            directory: "MediaCover".to_string(),
+        }
+    }
+}
+
+/// Downloads, resizes and caches movie artwork
+pub struct MediaCoverService {
+    client: reqwest::Client,
+    directory: PathBuf,
+    ffmpeg_path: PathBuf,
+    /// Held while fetching, so concurrent requests download a cover once
+    fetching: Mutex<()>,
+}
+
+impl MediaCoverService {
+    pub fn new(config: &MediaCoverConfig) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            directory: PathBuf::from(&config.directory),
+            ffmpeg_path: PathBuf::from("ffmpeg"),
+            fetching: Mutex::new(()),
+        }
+    }
+
+    /// Resize covers with the ffmpeg binary at `path`
+    pub fn with_ffmpeg_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.ffmpeg_path = path.into();
+        self
+    }
+
+    /// Cache path of a movie's cover, resized to `width` if given
+    pub fn cover_path(
+        &self,
+        movie_id: Uuid,
+        cover_type: MediaCoverType,
+        width: Option<u32>,
+    ) -> PathBuf {
+        self.directory
+            .join(movie_id.to_string())
+            .join(cover_type.file_name(width))
+    }
+
+    /// Path of the cached cover, fetched first when it isn't cached yet
+    ///
+    /// `None` when the movie has no artwork of `cover_type`. When resizing
+    /// fails the full image is returned instead.
+    pub async fn cover(
+        &self,
+        movie: &Movie,
+        cover_type: MediaCoverType,
+        width: Option<u32>,
+    ) -> Result<Option<PathBuf>> {
+        let path = self.cover_path(movie.id, cover_type, width);
+        if tokio::fs::try_exists(&path).await? {
+            return Ok(Some(path));
+        }
+        let Some(url) = movie.media_cover_url(cover_type) else {
+            return Ok(None);
+        };
+
+        let _fetching = self.fetching.lock().await;
+        let original = self.cover_path(movie.id, cover_type, None);
+        if !tokio::fs::try_exists(&original).await? {
+            self.download(&url, &original).await?;
+        }
+        let Some(width) = width else {
+            return Ok(Some(original));
+        };
+        if tokio::fs::try_exists(&path).await? {
+            return Ok(Some(path));
+        }
+        match self.resize(&original, &path, width).await {
+            Ok(()) => Ok(Some(path)),
+            Err(e) => {
+                warn!("Failed to resize {}: {}", original.display(), e);
+                Ok(Some(original))
+            }
+        }
+    }
+
+    /// Drop a movie's cached covers
+    pub async fn remove(&self, movie_id: Uuid) -> Result<()> {
+        let folder = self.directory.join(movie_id.to_string());
+        if tokio::fs::try_exists(&folder).await? {
+            tokio::fs::remove_dir_all(&folder).await?;
+            debug!("Removed cached covers of movie {}", movie_id);
+        }
+        Ok(())
+    }
+
+    async fn download(&self, url: &str, destination: &Path) -> Result<()> {
+        if let Some(parent) = destination.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        let response = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| RadarrError::ExternalServiceError {
+                service: "tmdb".to_string(),
+                error: format!("Failed to download {}: {}", url, e),
+            })?;
+        let bytes = response
+            .bytes()
+            .await
+            .map_err(|e| RadarrError::NetworkError {
+                message: format!("Failed to read {}: {}", url, e),
+            })?;
+
+        // Written aside first so a partial download is never served
+        let partial = destination.with_extension("partial");
+        tokio::fs::write(&partial, &bytes).await?;
+        tokio::fs::rename(&partial, destination).await?;
+        info!("Downloaded {} to {}", url, destination.display());
+        Ok(())
+    }
+
+    async fn resize(&self, source: &Path, destination: &Path, width: u32) -> Result<()> {
+        let output = tokio::process::Command::new(&self.ffmpeg_path)
+            .arg("-y")
+            .args(["-loglevel", "error"])
+            .arg("-i")
+            .arg(source)
+            .arg("-vf")
+            .arg(format!("scale={}:-2", width))
+            .args(["-q:v", "3"])
+            .arg(destination)
+            .stdin(Stdio::null())
+            .kill_on_drop(true)
+            .output()
+            .await
+            .map_err(|e| RadarrError::ExternalServiceError {
+                service: "ffmpeg".to_string(),
+                error: format!("Failed to start {}: {}", self.ffmpeg_path.display(), e),
+            })?;
+
+        if !output.status.success() {
+            let _ = tokio::fs::remove_file(destination).await;
+            return Err(RadarrError::ExternalServiceError {
+                service: "ffmpeg".to_string(),
+                error: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl EventHandler for MediaCoverService {
+    async fn handle_event(&self, envelope: &EventEnvelope) -> Result<()> {
+        match &envelope.event {
+            SystemEvent::MovieUpdated { movie_id, .. }
+            | SystemEvent::MovieDeleted { movie_id, .. } => self.remove(*movie_id).await,
+            _ => Ok(()),
+        }
+    }
+
+    fn should_handle(&self, envelope: &EventEnvelope) -> bool {
+        match &envelope.event {
+            SystemEvent::MovieUpdated { changes, .. } => {
+                changes.iter().any(|change| change == IMAGES_CHANGE)
+            }
+            SystemEvent::MovieDeleted { .. } => true,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_cached_covers_are_served_and_removed() {
+        let directory = tempfile::tempdir().unwrap();
+        let service = MediaCoverService::new(&MediaCoverConfig {
+            directory: directory.path().to_string_lossy().into_owned(),
+        });
+        let movie = Movie::new(550, "Fight Club".to_string());
+
+        // Without TMDB artwork there is nothing to fetch
+        assert_eq!(
+            service
+                .cover(&movie, MediaCoverType::Poster, None)
+                .await
+                .unwrap(),
+            None
+        );
+
+        let poster = service.cover_path(movie.id, MediaCoverType::Poster, Some(250));
+        tokio::fs::create_dir_all(poster.parent().unwrap())
+            .await
+            .unwrap();
+        tokio::fs::write(&poster, b"jpeg").await.unwrap();
+        assert_eq!(
+            service
+                .cover(&movie, MediaCoverType::Poster, Some(250))
+                .await
+                .unwrap(),
+            Some(poster.clone())
+        );
+
+        let updated = EventEnvelope::new(SystemEvent::MovieUpdated {
+            movie_id: movie.id,
+            changes: vec!["images".to_string()],
+        });
+        assert!(service.should_handle(&updated));
+        service.handle_event(&updated).await.unwrap();
+        assert!(!poster.exists());
+    }
+}
@@ -0,0 +1,307 @@
+//! Schema migration status
+//!
+//! The migrations under `migrations/` are embedded at build time. Comparing
+//! them with the `_sqlx_migrations` table tells which are applied, which are
+//! still pending and which were applied by a newer build than this one. That
+//! matters when several containers share one database: an upgraded instance
+//! migrates it for everyone, so an operator wants to see the plan, or refuse to
+//! start, before anything changes.
+
+use crate::database::DatabasePool;
+use crate::error::InfrastructureError;
+use chrono::{DateTime, Utc};
+use radarr_core::Result;
+use serde::{Deserialize, Serialize};
+use sqlx::migrate::Migrator;
+use sqlx::Row;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Migrations shipped with this build
+pub static MIGRATOR: Migrator = sqlx::migrate!("../../migrations");
+
+/// What to do with pending migrations at startup
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MigrationMode {
+    /// Apply pending migrations, then start
+    #[default]
+    Apply,
+    /// Start only if the schema is already up to date; never migrate
+    Check,
+    /// Print the pending migrations and exit without applying them
+    DryRun,
+}
+
+impl MigrationMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MigrationMode::Apply => "apply",
+            MigrationMode::Check => "check",
+            MigrationMode::DryRun => "dry-run",
+        }
+    }
+}
+
+impl fmt::Display for MigrationMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for MigrationMode {
+    type Err = String;
+
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "apply" => Ok(MigrationMode::Apply),
+            "check" => Ok(MigrationMode::Check),
+            "dry-run" | "dryrun" | "plan" => Ok(MigrationMode::DryRun),
+            other => Err(format!(
+                "unknown migration mode '{}', expected apply, check or dry-run",
+                other
+            )),
+        }
+    }
+}
+
+/// A single migration and whether it has been applied
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationInfo {
+    pub version: i64,
+    pub description: String,
+    pub installed_on: Option<DateTime<Utc>>,
+    /// How long applying it took
+    pub execution_ms: Option<i64>,
+    /// The applied script differs from the one in this build
+    pub checksum_mismatch: bool,
+    /// Applying it started but did not finish
+    pub failed: bool,
+}
+
+/// Applied and pending migrations of a database
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationStatus {
+    /// Applied migrations this build knows, oldest first
+    pub applied: Vec<MigrationInfo>,
+    /// Migrations this build would apply, in order
+    pub pending: Vec<MigrationInfo>,
+    /// Applied migrations this build does not know, i.e. the database was
+    /// migrated by a newer version
+    pub unknown: Vec<MigrationInfo>,
+}
+
+impl MigrationStatus {
+    /// Nothing to apply and nothing that would stop migrating
+    pub fn is_up_to_date(&self) -> bool {
+        self.pending.is_empty() && self.problems().is_empty()
+    }
+
+    /// Reasons migrating would fail or the schema cannot be trusted
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        for migration in &self.applied {
+            if migration.failed {
+                problems.push(format!(
+                    "migration {} ({}) did not complete",
+                    migration.version, migration.description
+                ));
+            } else if migration.checksum_mismatch {
+                problems.push(format!(
+                    "migration {} ({}) was changed after it was applied",
+                    migration.version, migration.description
+                ));
+            }
+        }
+        for migration in &self.unknown {
+            problems.push(format!(
+                "migration {} ({}) was applied by a newer version",
+                migration.version, migration.description
+            ));
+        }
+        problems
+    }
+
+    /// Human readable plan, one line per migration
+    pub fn plan(&self) -> String {
+        let mut lines = vec![format!(
+            "{} migrations applied, {} pending",
+            self.applied.len(),
+            self.pending.len()
+        )];
+        lines.extend(
+            self.pending.iter().map(|migration| {
+                format!("  apply {} {}", migration.version, migration.description)
+            }),
+        );
+        lines.extend(
+            self.problems()
+                .into_iter()
+                .map(|problem| format!("  ! {}", problem)),
+        );
+        lines.join("\n")
+    }
+}
+
+/// Migration recorded in `_sqlx_migrations`
+struct AppliedMigration {
+    version: i64,
+    description: String,
+    installed_on: DateTime<Utc>,
+    success: bool,
+    checksum: Vec<u8>,
+    execution_time: i64,
+}
+
+/// Compare the migrations of this build with those applied to `pool`
+pub async fn migration_status(pool: &DatabasePool) -> Result<MigrationStatus> {
+    Ok(compare(&MIGRATOR, applied_migrations(pool).await?))
+}
+
+/// Apply pending migrations
+pub async fn apply_migrations(pool: &DatabasePool) -> Result<()> {
+    MIGRATOR
+        .run(pool)
+        .await
+        .map_err(|e| InfrastructureError::Migration(e.to_string()))?;
+
+    Ok(())
+}
+
+async fn applied_migrations(pool: &DatabasePool) -> Result<Vec<AppliedMigration>> {
+    // A database that was never migrated has no table yet
+    let exists: bool = sqlx::query_scalar("SELECT to_regclass('_sqlx_migrations') IS NOT NULL")
+        .fetch_one(pool)
+        .await?;
+    if !exists {
+        return Ok(Vec::new());
+    }
+
+    let rows = sqlx::query(
+        "SELECT version, description, installed_on, success, checksum, execution_time
+         FROM _sqlx_migrations ORDER BY version",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    rows.iter()
+        .map(|row| -> Result<AppliedMigration> {
+            Ok(AppliedMigration {
+                version: row.try_get("version")?,
+                description: row.try_get("description")?,
+                installed_on: row.try_get("installed_on")?,
+                success: row.try_get("success")?,
+                checksum: row.try_get("checksum")?,
+                execution_time: row.try_get("execution_time")?,
+            })
+        })
+        .collect()
+}
+
+fn compare(migrator: &Migrator, applied: Vec<AppliedMigration>) -> MigrationStatus {
+    let mut applied: HashMap<i64, AppliedMigration> = applied
+        .into_iter()
+        .map(|migration| (migration.version, migration))
+        .collect();
+    let mut status = MigrationStatus::default();
+
+    for migration in migrator
+        .iter()
+        .filter(|migration| !migration.migration_type.is_down_migration())
+    {
+        match applied.remove(&migration.version) {
+            Some(record) => status.applied.push(MigrationInfo {
+                version: migration.version,
+                description: migration.description.to_string(),
+                installed_on: Some(record.installed_on),
+                // Recorded in nanoseconds
+                execution_ms: Some(record.execution_time / 1_000_000),
+                checksum_mismatch: record.checksum != *migration.checksum,
+                failed: !record.success,
+            }),
+            None => status.pending.push(MigrationInfo {
+                version: migration.version,
+                description: migration.description.to_string(),
+                installed_on: None,
+                execution_ms: None,
+                checksum_mismatch: false,
+                failed: false,
+            }),
+        }
+    }
+
+    status.unknown = applied
+        .into_values()
+        .map(|record| MigrationInfo {
+            version: record.version,
+            description: record.description,
+            installed_on: Some(record.installed_on),
+            execution_ms: Some(record.execution_time / 1_000_000),
+            checksum_mismatch: false,
+            failed: !record.success,
+        })
+        .collect();
+    status.unknown.sort_by_key(|migration| migration.version);
+    status
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn applied(version: i64, checksum: &[u8]) -> AppliedMigration {
+        AppliedMigration {
+            version,
+            description: format!("migration {}", version),
+            installed_on: Utc::now(),
+            success: true,
+            checksum: checksum.to_vec(),
+            execution_time: 12_000_000,
+        }
+    }
+
+    #[test]
+    fn test_fresh_database_has_everything_pending() {
+        let status = compare(&MIGRATOR, Vec::new());
+        assert!(status.applied.is_empty());
+        assert_eq!(status.pending.len(), MIGRATOR.iter().count());
+        assert!(!status.is_up_to_date());
+        assert!(status.problems().is_empty());
+    }
+
+    #[test]
+    fn test_compare_splits_applied_pending_and_unknown() {
+        let mut migrations = MIGRATOR.iter();
+        let first = migrations.next().unwrap();
+        let second = migrations.next().unwrap();
+
+        let status = compare(
+            &MIGRATOR,
+            vec![
+                applied(first.version, &first.checksum),
+                applied(second.version, b"edited"),
+                applied(99_990_101, b"future"),
+            ],
+        );
+
+        assert_eq!(status.applied.len(), 2);
+        assert_eq!(status.applied[0].execution_ms, Some(12));
+        assert!(!status.applied[0].checksum_mismatch);
+        assert!(status.applied[1].checksum_mismatch);
+        assert_eq!(status.pending.len(), MIGRATOR.iter().count() - 2);
+        assert_eq!(status.unknown[0].version, 99_990_101);
+        assert_eq!(status.problems().len(), 2);
+        assert!(status.plan().contains("2 migrations applied"));
+    }
+
+    #[test]
+    fn test_migration_mode_parse() {
+        assert_eq!("apply".parse(), Ok(MigrationMode::Apply));
+        assert_eq!("CHECK".parse(), Ok(MigrationMode::Check));
+        assert_eq!("dry-run".parse(), Ok(MigrationMode::DryRun));
+        assert!("skip".parse::<MigrationMode>().is_err());
+    }
+}
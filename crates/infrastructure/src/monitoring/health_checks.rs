@@ -3,13 +3,18 @@
 //! This module provides health monitoring for external list services
 //! including IMDb, TMDb, Trakt, and Plex integrations.
 
+use crate::updates::{UpdateChecker, CURRENT_VERSION};
 use chrono::{DateTime, Duration, Utc};
+use radarr_core::events::{EventBus, SystemEvent};
+use radarr_core::i18n::translate;
+use radarr_core::polling::{PollConfig, PollSchedule};
+use radarr_core::services::ConfigDriftReconciler;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration as StdDuration;
 use tokio::sync::RwLock;
-use tokio::time::{interval, timeout, Duration as TokioDuration};
+use tokio::time::{timeout, Duration as TokioDuration};
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
@@ -208,6 +213,8 @@ pub struct HealthChecker {
     config: HealthCheckConfig,
     checkers: Vec<Box<dyn ServiceHealthChecker>>,
     health_status: Arc<RwLock<HashMap<String, ServiceHealth>>>,
+    /// Receives a `SystemHealth` event whenever a service changes status
+    event_bus: Option<Arc<EventBus>>,
 }
 
 impl HealthChecker {
@@ -217,9 +224,15 @@ impl HealthChecker {
             config,
             checkers: Vec::new(),
             health_status: Arc::new(RwLock::new(HashMap::new())),
+            event_bus: None,
         }
     }
 
+    /// Publish status changes to `event_bus`
+    pub fn set_event_bus(&mut self, event_bus: Arc<EventBus>) {
+        self.event_bus = Some(event_bus);
+    }
+
     /// Add a service health checker
     pub fn add_checker(&mut self, checker: Box<dyn ServiceHealthChecker>) {
         let service_name = checker.service_name().to_string();
@@ -243,11 +256,10 @@ impl HealthChecker {
             "Starting health check loop"
         );
 
-        let mut interval = interval(self.config.check_interval);
+        // Checks are not backed off on failure: that is when they matter most
+        let schedule = PollSchedule::new(PollConfig::new(self.config.check_interval));
 
         loop {
-            interval.tick().await;
-
             // Run health checks for all services in parallel
             let check_futures: Vec<_> = self
                 .checkers
@@ -259,6 +271,7 @@ impl HealthChecker {
             futures::future::join_all(check_futures).await;
 
             debug!("Completed health check cycle");
+            schedule.wait().await;
         }
     }
 
@@ -280,13 +293,14 @@ impl HealthChecker {
 
         let check_result = match result {
             Ok(check_result) => check_result,
-            Err(_) => HealthCheckResult::unhealthy("Health check timed out"),
+            Err(_) => HealthCheckResult::unhealthy(translate("health-check-timed-out", &[])),
         };
 
         let elapsed = start_time.elapsed();
 
         // Update service health status
         let mut health_status = self.health_status.write().await;
+        let mut changed = false;
         if let Some(service_health) = health_status.get_mut(service_name) {
             let old_status = service_health.status;
             service_health.update(
@@ -298,6 +312,7 @@ impl HealthChecker {
 
             // Log status changes
             if old_status != check_result.status {
+                changed = true;
                 match check_result.status {
                     HealthStatus::Healthy => {
                         info!(
@@ -334,6 +349,20 @@ impl HealthChecker {
                 );
             }
         }
+        drop(health_status);
+
+        if changed {
+            if let Some(event_bus) = &self.event_bus {
+                let event = SystemEvent::SystemHealth {
+                    component: service_name.to_string(),
+                    status: check_result.status.as_str().to_string(),
+                    message: check_result.error_message,
+                };
+                if let Err(e) = event_bus.publish(event).await {
+                    warn!("Failed to publish health change of {}: {}", service_name, e);
+                }
+            }
+        }
     }
 
     /// Get health status for all services
@@ -403,7 +432,10 @@ impl HealthChecker {
                     }
                     Some(check_result)
                 }
-                Err(_) => Some(HealthCheckResult::unhealthy("Health check timed out")),
+                Err(_) => Some(HealthCheckResult::unhealthy(translate(
+                    "health-check-timed-out",
+                    &[],
+                ))),
             }
         } else {
             None
@@ -473,9 +505,14 @@ impl ServiceHealthChecker for HttpHealthChecker {
         let response = match timeout(self.timeout, self.client.get(&self.url).send()).await {
             Ok(Ok(response)) => response,
             Ok(Err(e)) => {
-                return HealthCheckResult::unhealthy(format!("HTTP request failed: {}", e))
+                return HealthCheckResult::unhealthy(translate(
+                    "health-request-failed",
+                    &[("error", e.to_string())],
+                ))
+            }
+            Err(_) => {
+                return HealthCheckResult::unhealthy(translate("health-request-timed-out", &[]))
             }
-            Err(_) => return HealthCheckResult::unhealthy("Request timed out"),
         };
 
         let elapsed = start_time.elapsed();
@@ -485,10 +522,17 @@ impl ServiceHealthChecker for HttpHealthChecker {
             HealthCheckResult::healthy(elapsed)
                 .with_metadata("status_code", serde_json::Value::Number(status_code.into()))
         } else if status_code >= 400 && status_code < 500 {
-            HealthCheckResult::degraded(elapsed, format!("Unexpected status code: {}", status_code))
+            let message = translate(
+                "health-unexpected-status",
+                &[("status", status_code.to_string())],
+            );
+            HealthCheckResult::degraded(elapsed, message)
                 .with_metadata("status_code", serde_json::Value::Number(status_code.into()))
         } else {
-            HealthCheckResult::unhealthy(format!("HTTP error: {}", status_code))
+            HealthCheckResult::unhealthy(translate(
+                "health-http-error",
+                &[("status", status_code.to_string())],
+            ))
         }
     }
 
@@ -497,6 +541,79 @@ impl ServiceHealthChecker for HttpHealthChecker {
     }
 }
 
+/// Reports drift found by the latest desired state check; the check itself
+/// runs as a scheduled command
+pub struct ConfigDriftHealthChecker {
+    reconciler: Arc<ConfigDriftReconciler>,
+}
+
+impl ConfigDriftHealthChecker {
+    pub fn new(reconciler: Arc<ConfigDriftReconciler>) -> Self {
+        Self { reconciler }
+    }
+}
+
+#[async_trait::async_trait]
+impl ServiceHealthChecker for ConfigDriftHealthChecker {
+    async fn check_health(&self) -> HealthCheckResult {
+        let start_time = std::time::Instant::now();
+        let warnings = self.reconciler.warnings();
+        if warnings.is_empty() {
+            return HealthCheckResult::healthy(start_time.elapsed());
+        }
+
+        let message = translate(
+            "health-config-drift",
+            &[
+                ("count", warnings.len().to_string()),
+                ("details", warnings.join("; ")),
+            ],
+        );
+        HealthCheckResult::degraded(start_time.elapsed(), message)
+            .with_metadata("drift", serde_json::json!(warnings))
+    }
+
+    fn service_name(&self) -> &str {
+        "desired_state"
+    }
+}
+
+/// Reports a release newer than the running version found by the latest
+/// update check; the check itself runs as a scheduled command
+pub struct UpdateHealthChecker {
+    updates: Arc<UpdateChecker>,
+}
+
+impl UpdateHealthChecker {
+    pub fn new(updates: Arc<UpdateChecker>) -> Self {
+        Self { updates }
+    }
+}
+
+#[async_trait::async_trait]
+impl ServiceHealthChecker for UpdateHealthChecker {
+    async fn check_health(&self) -> HealthCheckResult {
+        let start_time = std::time::Instant::now();
+        let Some(update) = self.updates.available() else {
+            return HealthCheckResult::healthy(start_time.elapsed());
+        };
+
+        let message = translate(
+            "health-update-available",
+            &[
+                ("version", update.version.clone()),
+                ("current_version", CURRENT_VERSION.to_string()),
+            ],
+        );
+        HealthCheckResult::degraded(start_time.elapsed(), message)
+            .with_metadata("url", serde_json::json!(update.url))
+    }
+
+    fn service_name(&self) -> &str {
+        "update"
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
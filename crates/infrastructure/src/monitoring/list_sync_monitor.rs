@@ -10,11 +10,13 @@ use crate::monitoring::{
         HealthCheckConfig, HealthChecker, HealthStatus, HttpHealthChecker, ServiceHealthChecker,
     },
     metrics::{MetricsConfig, PrometheusMetrics, SyncMetrics},
+    recovery::{RecoveryAttempt, RecoveryConfig, RecoveryManager},
 };
 use chrono::{DateTime, Utc};
 use radarr_core::circuit_breaker::{
     CircuitBreaker, CircuitBreakerConfig, CircuitBreakerMetrics, CircuitBreakerState,
 };
+use radarr_core::ShutdownCoordinator;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -41,6 +43,8 @@ pub struct ListSyncMonitorConfig {
     pub alert_retention_days: i64,
     /// Circuit breaker configurations for each service
     pub circuit_breaker_configs: HashMap<String, CircuitBreakerConfig>,
+    /// Automated recovery configuration
+    pub recovery: RecoveryConfig,
 }
 
 impl Default for ListSyncMonitorConfig {
@@ -88,6 +92,7 @@ impl Default for ListSyncMonitorConfig {
             alert_cleanup_interval: TokioDuration::from_secs(3600),  // 1 hour
             alert_retention_days: 30,
             circuit_breaker_configs: cb_configs,
+            recovery: RecoveryConfig::default(),
         }
     }
 }
@@ -99,6 +104,7 @@ pub struct ListSyncMonitor {
     alert_manager: Arc<AlertManager>,
     health_checker: Arc<RwLock<HealthChecker>>,
     circuit_breakers: Arc<RwLock<HashMap<String, CircuitBreaker>>>,
+    recovery_manager: Arc<RecoveryManager>,
     monitoring_stats: Arc<RwLock<MonitoringStats>>,
 }
 
@@ -110,6 +116,7 @@ struct MonitoringStats {
     pub total_alerts_fired: u64,
     pub total_health_checks_performed: u64,
     pub circuit_breaker_activations: u64,
+    pub total_recovery_attempts: u64,
     pub last_prometheus_scrape: Option<DateTime<Utc>>,
 }
 
@@ -121,6 +128,7 @@ impl Default for MonitoringStats {
             total_alerts_fired: 0,
             total_health_checks_performed: 0,
             circuit_breaker_activations: 0,
+            total_recovery_attempts: 0,
             last_prometheus_scrape: None,
         }
     }
@@ -135,6 +143,7 @@ impl ListSyncMonitor {
             config.health_checks.clone(),
         )));
         let circuit_breakers = Arc::new(RwLock::new(HashMap::new()));
+        let recovery_manager = Arc::new(RecoveryManager::new(config.recovery.clone()));
 
         // Initialize circuit breakers
         {
@@ -152,6 +161,7 @@ impl ListSyncMonitor {
             alert_manager,
             health_checker,
             circuit_breakers,
+            recovery_manager,
             monitoring_stats: Arc::new(RwLock::new(MonitoringStats::default())),
         };
 
@@ -182,6 +192,11 @@ impl ListSyncMonitor {
         Ok(())
     }
 
+    /// Publish health status changes of the monitored services to `event_bus`
+    pub async fn set_event_bus(&self, event_bus: Arc<radarr_core::EventBus>) {
+        self.health_checker.write().await.set_event_bus(event_bus);
+    }
+
     /// Add a health checker for a specific service
     pub async fn add_health_checker(&self, checker: Box<dyn ServiceHealthChecker>) {
         let service_name = checker.service_name().to_string();
@@ -226,14 +241,24 @@ impl ListSyncMonitor {
     }
 
     /// Start all monitoring components
-    pub async fn start_monitoring(&self) -> Result<(), MonitoringError> {
+    ///
+    /// The loops are registered with `shutdown` and stop once it is
+    /// signalled; alert evaluation and cleanup finish the cycle they are in.
+    pub async fn start_monitoring(
+        &self,
+        shutdown: &ShutdownCoordinator,
+    ) -> Result<(), MonitoringError> {
         info!("Starting List Sync monitoring components");
 
-        // Start health check loop
+        // Start health check loop; a probe in progress is simply dropped
         let health_checker = self.health_checker.clone();
-        tokio::spawn(async move {
+        let stop = shutdown.clone();
+        shutdown.spawn("list sync health checks", async move {
             let health_checker = health_checker.read().await;
-            health_checker.start().await;
+            tokio::select! {
+                _ = stop.cancelled() => {}
+                _ = health_checker.start() => {}
+            }
         });
 
         // Start alert evaluation loop
@@ -241,12 +266,18 @@ impl ListSyncMonitor {
         let metrics = self.metrics.clone();
         let health_checker_clone = self.health_checker.clone();
         let circuit_breakers = self.circuit_breakers.clone();
+        let recovery_manager = self.recovery_manager.clone();
+        let monitoring_stats = self.monitoring_stats.clone();
         let alert_interval = self.config.alert_evaluation_interval;
 
-        tokio::spawn(async move {
+        let stop = shutdown.clone();
+        shutdown.spawn("list sync alert evaluation", async move {
             let mut interval = interval(alert_interval);
             loop {
-                interval.tick().await;
+                tokio::select! {
+                    _ = stop.cancelled() => break,
+                    _ = interval.tick() => {}
+                }
                 Self::evaluate_alert_conditions(
                     &alert_manager,
                     &metrics,
@@ -254,6 +285,12 @@ impl ListSyncMonitor {
                     &circuit_breakers,
                 )
                 .await;
+                Self::run_recovery_actions(
+                    &recovery_manager,
+                    &health_checker_clone,
+                    &monitoring_stats,
+                )
+                .await;
             }
         });
 
@@ -262,10 +299,14 @@ impl ListSyncMonitor {
         let cleanup_interval = self.config.alert_cleanup_interval;
         let retention_days = self.config.alert_retention_days;
 
-        tokio::spawn(async move {
+        let stop = shutdown.clone();
+        shutdown.spawn("list sync alert cleanup", async move {
             let mut interval = interval(cleanup_interval);
             loop {
-                interval.tick().await;
+                tokio::select! {
+                    _ = stop.cancelled() => break,
+                    _ = interval.tick() => {}
+                }
                 alert_manager_cleanup
                     .cleanup_old_alerts(retention_days)
                     .await;
@@ -276,6 +317,16 @@ impl ListSyncMonitor {
         Ok(())
     }
 
+    /// Get the recovery manager so services can register recovery actions
+    pub fn recovery_manager(&self) -> Arc<RecoveryManager> {
+        self.recovery_manager.clone()
+    }
+
+    /// Get the most recent automated recovery attempts, newest first
+    pub async fn get_recovery_attempts(&self, limit: usize) -> Vec<RecoveryAttempt> {
+        self.recovery_manager.get_audit_trail(limit).await
+    }
+
     /// Record a sync operation for monitoring
     pub async fn record_sync_operation(
         &self,
@@ -442,6 +493,7 @@ impl ListSyncMonitor {
             health_summary,
             circuit_breaker_status,
             total_operations_monitored: stats.total_sync_operations_monitored,
+            total_recovery_attempts: stats.total_recovery_attempts,
             uptime_seconds: (Utc::now() - stats.started_at).num_seconds() as u64,
         }
     }
@@ -499,6 +551,24 @@ impl ListSyncMonitor {
 
         debug!("Completed alert condition evaluation cycle");
     }
+
+    /// Run recovery actions for services whose health checks keep failing
+    async fn run_recovery_actions(
+        recovery_manager: &RecoveryManager,
+        health_checker: &RwLock<HealthChecker>,
+        monitoring_stats: &RwLock<MonitoringStats>,
+    ) {
+        let all_health = {
+            let health_checker = health_checker.read().await;
+            health_checker.get_all_health_status().await
+        };
+
+        let attempts = recovery_manager.evaluate(&all_health).await;
+        if !attempts.is_empty() {
+            let mut stats = monitoring_stats.write().await;
+            stats.total_recovery_attempts += attempts.len() as u64;
+        }
+    }
 }
 
 /// Monitoring error types
@@ -530,6 +600,7 @@ pub struct MonitoringStatus {
     pub health_summary: crate::monitoring::health_checks::HealthSummary,
     pub circuit_breaker_status: HashMap<String, CircuitBreakerStatus>,
     pub total_operations_monitored: u64,
+    pub total_recovery_attempts: u64,
     pub uptime_seconds: u64,
 }
 
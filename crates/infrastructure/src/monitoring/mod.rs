@@ -7,14 +7,22 @@
 //! - Health checks for external services
 //! - Circuit breaker integration
 //! - Alert management for failures and rate limits
+//! - Automated recovery actions for failing services
 //! - Prometheus metrics export
 
 pub mod alert_manager;
 pub mod health_checks;
 pub mod list_sync_monitor;
 pub mod metrics;
+pub mod recovery;
 
 pub use alert_manager::{Alert, AlertLevel, AlertManager, AlertRule};
-pub use health_checks::{HealthChecker, HealthStatus, ServiceHealth};
+pub use health_checks::{
+    ConfigDriftHealthChecker, HealthChecker, HealthStatus, ServiceHealth, UpdateHealthChecker,
+};
 pub use list_sync_monitor::ListSyncMonitor;
 pub use metrics::{PrometheusMetrics, ServiceMetrics, SyncMetrics};
+pub use recovery::{
+    FnRecoveryAction, RecoveryAction, RecoveryAttempt, RecoveryConfig, RecoveryManager,
+    RecoveryOutcome,
+};
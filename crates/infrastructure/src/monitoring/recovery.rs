@@ -0,0 +1,364 @@
+//! Automated recovery actions driven by health check results
+//!
+//! Health checks only observe service state. This module lets a failing
+//! service trigger a registered recovery action (reconnect a download client,
+//! re-authenticate an indexer, restart a background task) without waiting for
+//! an operator. Attempts are rate limited per service and every attempt is
+//! kept in an in-memory audit trail.
+
+use crate::monitoring::health_checks::{HealthStatus, ServiceHealth};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+
+/// Trait for a recovery action bound to a monitored service
+#[async_trait::async_trait]
+pub trait RecoveryAction: Send + Sync {
+    /// Short name of the action for logs and the audit trail
+    fn name(&self) -> &str;
+
+    /// Execute the recovery action, returning a human readable outcome message
+    async fn execute(&self) -> Result<String, String>;
+}
+
+/// Recovery action backed by an async closure
+pub struct FnRecoveryAction<F> {
+    name: String,
+    action: F,
+}
+
+impl<F> FnRecoveryAction<F> {
+    pub fn new(name: impl Into<String>, action: F) -> Self {
+        Self {
+            name: name.into(),
+            action,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<F, Fut> RecoveryAction for FnRecoveryAction<F>
+where
+    F: Fn() -> Fut + Send + Sync,
+    Fut: Future<Output = Result<String, String>> + Send,
+{
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn execute(&self) -> Result<String, String> {
+        (self.action)().await
+    }
+}
+
+/// Configuration for automated recovery
+#[derive(Debug, Clone)]
+pub struct RecoveryConfig {
+    /// Whether automated recovery is enabled
+    pub enabled: bool,
+    /// Consecutive health check failures required before acting
+    pub failure_threshold: u32,
+    /// Maximum attempts per service inside `attempt_window`
+    pub max_attempts: u32,
+    /// Sliding window used for rate limiting attempts
+    pub attempt_window: Duration,
+    /// Minimum spacing between two attempts for the same service
+    pub min_interval: Duration,
+    /// Number of audit entries kept in memory
+    pub audit_retention: usize,
+}
+
+impl Default for RecoveryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            failure_threshold: 3,
+            max_attempts: 3,
+            attempt_window: Duration::hours(1),
+            min_interval: Duration::minutes(5),
+            audit_retention: 500,
+        }
+    }
+}
+
+/// Outcome of a recovery attempt
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RecoveryOutcome {
+    Succeeded,
+    Failed,
+}
+
+impl RecoveryOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Succeeded => "succeeded",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+/// Audit trail entry for a single recovery attempt
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecoveryAttempt {
+    pub id: Uuid,
+    pub service: String,
+    pub action: String,
+    pub outcome: RecoveryOutcome,
+    pub message: String,
+    pub consecutive_failures: u32,
+    pub attempted_at: DateTime<Utc>,
+}
+
+/// Coordinates recovery actions for failing services
+pub struct RecoveryManager {
+    config: RecoveryConfig,
+    actions: RwLock<HashMap<String, Arc<dyn RecoveryAction>>>,
+    audit_trail: RwLock<VecDeque<RecoveryAttempt>>,
+}
+
+impl RecoveryManager {
+    /// Create a new recovery manager
+    pub fn new(config: RecoveryConfig) -> Self {
+        Self {
+            config,
+            actions: RwLock::new(HashMap::new()),
+            audit_trail: RwLock::new(VecDeque::new()),
+        }
+    }
+
+    /// Register the recovery action for a service, replacing any existing one
+    pub async fn register_action(
+        &self,
+        service: impl Into<String>,
+        action: Arc<dyn RecoveryAction>,
+    ) {
+        let service = service.into();
+        info!(
+            service = %service,
+            action = action.name(),
+            "Registered recovery action"
+        );
+        self.actions.write().await.insert(service, action);
+    }
+
+    /// Services that currently have a recovery action registered
+    pub async fn registered_services(&self) -> Vec<String> {
+        self.actions.read().await.keys().cloned().collect()
+    }
+
+    /// Evaluate health results and run recovery for services past the failure threshold
+    pub async fn evaluate(&self, health: &HashMap<String, ServiceHealth>) -> Vec<RecoveryAttempt> {
+        if !self.config.enabled {
+            return Vec::new();
+        }
+
+        let mut attempts = Vec::new();
+        for (service, service_health) in health {
+            if service_health.status != HealthStatus::Unhealthy
+                || !service_health.is_unhealthy_by_failures(self.config.failure_threshold)
+            {
+                continue;
+            }
+
+            if let Some(attempt) = self
+                .attempt_recovery(service, service_health.consecutive_failures)
+                .await
+            {
+                attempts.push(attempt);
+            }
+        }
+
+        attempts
+    }
+
+    /// Run the recovery action for a service if the rate limit allows it
+    pub async fn attempt_recovery(
+        &self,
+        service: &str,
+        consecutive_failures: u32,
+    ) -> Option<RecoveryAttempt> {
+        let action = self.actions.read().await.get(service).cloned()?;
+
+        if !self.is_attempt_allowed(service).await {
+            debug!(
+                service = service,
+                "Recovery attempt skipped due to rate limit"
+            );
+            return None;
+        }
+
+        warn!(
+            service = service,
+            action = action.name(),
+            consecutive_failures = consecutive_failures,
+            "Running automated recovery action"
+        );
+
+        let (outcome, message) = match action.execute().await {
+            Ok(message) => {
+                info!(
+                    service = service,
+                    action = action.name(),
+                    "Recovery action succeeded"
+                );
+                (RecoveryOutcome::Succeeded, message)
+            }
+            Err(message) => {
+                error!(
+                    service = service,
+                    action = action.name(),
+                    error = %message,
+                    "Recovery action failed"
+                );
+                (RecoveryOutcome::Failed, message)
+            }
+        };
+
+        let attempt = RecoveryAttempt {
+            id: Uuid::new_v4(),
+            service: service.to_string(),
+            action: action.name().to_string(),
+            outcome,
+            message,
+            consecutive_failures,
+            attempted_at: Utc::now(),
+        };
+
+        let mut audit_trail = self.audit_trail.write().await;
+        audit_trail.push_back(attempt.clone());
+        while audit_trail.len() > self.config.audit_retention {
+            audit_trail.pop_front();
+        }
+
+        Some(attempt)
+    }
+
+    /// Get the most recent recovery attempts, newest first
+    pub async fn get_audit_trail(&self, limit: usize) -> Vec<RecoveryAttempt> {
+        self.audit_trail
+            .read()
+            .await
+            .iter()
+            .rev()
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    /// Check the per-service rate limit against the audit trail
+    async fn is_attempt_allowed(&self, service: &str) -> bool {
+        let now = Utc::now();
+        let audit_trail = self.audit_trail.read().await;
+        let recent: Vec<&RecoveryAttempt> = audit_trail
+            .iter()
+            .filter(|a| a.service == service && now - a.attempted_at < self.config.attempt_window)
+            .collect();
+
+        if recent.len() as u32 >= self.config.max_attempts {
+            return false;
+        }
+
+        match recent.last() {
+            Some(last) => now - last.attempted_at >= self.config.min_interval,
+            None => true,
+        }
+    }
+}
+
+impl Default for RecoveryManager {
+    fn default() -> Self {
+        Self::new(RecoveryConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn unhealthy(service: &str, failures: u32) -> HashMap<String, ServiceHealth> {
+        let mut health = ServiceHealth::new(service);
+        for _ in 0..failures {
+            health.update(HealthStatus::Unhealthy, None, Some("down".to_string()));
+        }
+        HashMap::from([(service.to_string(), health)])
+    }
+
+    fn counting_action(counter: Arc<AtomicU32>) -> Arc<dyn RecoveryAction> {
+        Arc::new(FnRecoveryAction::new("reconnect", move || {
+            let counter = counter.clone();
+            async move {
+                counter.fetch_add(1, Ordering::SeqCst);
+                Ok("reconnected".to_string())
+            }
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_recovery_runs_after_threshold() {
+        let manager = RecoveryManager::new(RecoveryConfig {
+            min_interval: Duration::zero(),
+            ..RecoveryConfig::default()
+        });
+        let counter = Arc::new(AtomicU32::new(0));
+        manager
+            .register_action("qbittorrent", counting_action(counter.clone()))
+            .await;
+
+        // Below threshold - nothing happens
+        assert!(manager
+            .evaluate(&unhealthy("qbittorrent", 2))
+            .await
+            .is_empty());
+
+        let attempts = manager.evaluate(&unhealthy("qbittorrent", 3)).await;
+        assert_eq!(attempts.len(), 1);
+        assert_eq!(attempts[0].outcome, RecoveryOutcome::Succeeded);
+        assert_eq!(counter.load(Ordering::SeqCst), 1);
+        assert_eq!(manager.get_audit_trail(10).await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_recovery_is_rate_limited() {
+        let manager = RecoveryManager::new(RecoveryConfig {
+            max_attempts: 2,
+            min_interval: Duration::zero(),
+            ..RecoveryConfig::default()
+        });
+        let counter = Arc::new(AtomicU32::new(0));
+        manager
+            .register_action("prowlarr", counting_action(counter.clone()))
+            .await;
+
+        for _ in 0..5 {
+            manager.evaluate(&unhealthy("prowlarr", 5)).await;
+        }
+
+        assert_eq!(counter.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_failed_recovery_is_audited() {
+        let manager = RecoveryManager::default();
+        manager
+            .register_action(
+                "rss",
+                Arc::new(FnRecoveryAction::new("restart_rss", || async {
+                    Err("still down".to_string())
+                })),
+            )
+            .await;
+
+        let attempt = manager.attempt_recovery("rss", 4).await.unwrap();
+        assert_eq!(attempt.outcome, RecoveryOutcome::Failed);
+        assert_eq!(attempt.message, "still down");
+
+        // Unregistered services are ignored
+        assert!(manager.attempt_recovery("imdb", 4).await.is_none());
+    }
+}
@@ -0,0 +1,144 @@
+//! OMDb ratings lookup
+//!
+//! OMDb reports a movie's IMDb rating along with its Rotten Tomatoes and
+//! Metacritic scores, looked up by IMDb ID. It needs an API key.
+
+use async_trait::async_trait;
+use radarr_core::{
+    jobs::MovieRatingsSource,
+    models::{MovieRatings, Rating},
+    RadarrError, Result,
+};
+use reqwest::Client;
+use serde::Deserialize;
+use std::time::Duration;
+use tracing::debug;
+
+/// OMDb API client
+pub struct OmdbClient {
+    client: Client,
+    api_key: String,
+    base_url: String,
+}
+
+impl OmdbClient {
+    pub fn new(api_key: String) -> Self {
+        Self {
+            client: Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap_or_default(),
+            api_key,
+            base_url: "https://www.omdbapi.com/".to_string(),
+        }
+    }
+
+    fn error(error: impl std::fmt::Display) -> RadarrError {
+        RadarrError::ExternalServiceError {
+            service: "omdb".to_string(),
+            error: error.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl MovieRatingsSource for OmdbClient {
+    async fn movie_ratings(&self, imdb_id: &str) -> Result<MovieRatings> {
+        debug!("Fetching OMDb ratings: imdb_id={}", imdb_id);
+
+        // OMDb reports errors, including a bad API key, in the body
+        let response: OmdbResponse = self
+            .client
+            .get(&self.base_url)
+            .query(&[("apikey", self.api_key.as_str()), ("i", imdb_id)])
+            .send()
+            .await
+            .map_err(Self::error)?
+            .json()
+            .await
+            .map_err(Self::error)?;
+
+        if response.response != "True" {
+            return Err(Self::error(
+                response
+                    .error
+                    .unwrap_or_else(|| "Unknown error".to_string()),
+            ));
+        }
+        Ok(response.ratings())
+    }
+}
+
+/// OMDb title response
+#[derive(Debug, Deserialize)]
+struct OmdbResponse {
+    #[serde(rename = "Response")]
+    response: String,
+    #[serde(rename = "Error")]
+    error: Option<String>,
+    #[serde(rename = "imdbRating")]
+    imdb_rating: Option<String>,
+    #[serde(rename = "imdbVotes")]
+    imdb_votes: Option<String>,
+    #[serde(rename = "Ratings", default)]
+    ratings: Vec<OmdbRating>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OmdbRating {
+    #[serde(rename = "Source")]
+    source: String,
+    /// e.g. `8.8/10`, `79%` or `67/100`
+    #[serde(rename = "Value")]
+    value: String,
+}
+
+impl OmdbResponse {
+    fn ratings(&self) -> MovieRatings {
+        // Missing values are reported as "N/A"
+        let number = |value: &str| value.replace(',', "").parse::<f64>().ok();
+        let score = |source: &str| {
+            let rating = self.ratings.iter().find(|rating| rating.source == source)?;
+            let value = rating.value.trim_end_matches('%');
+            let value = value.split_once('/').map_or(value, |(value, _)| value);
+            number(value).map(|value| Rating::new(value, 0))
+        };
+
+        MovieRatings {
+            imdb: self.imdb_rating.as_deref().and_then(number).map(|value| {
+                let votes = self.imdb_votes.as_deref().and_then(number).unwrap_or(0.0);
+                Rating::new(value, votes as i64)
+            }),
+            tmdb: None,
+            metacritic: score("Metacritic"),
+            rotten_tomatoes: score("Rotten Tomatoes"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_omdb_ratings() {
+        let response: OmdbResponse = serde_json::from_value(serde_json::json!({
+            "Title": "Fight Club",
+            "imdbRating": "8.8",
+            "imdbVotes": "2,300,000",
+            "Ratings": [
+                { "Source": "Internet Movie Database", "Value": "8.8/10" },
+                { "Source": "Rotten Tomatoes", "Value": "79%" },
+                { "Source": "Metacritic", "Value": "N/A" }
+            ],
+            "Response": "True"
+        }))
+        .unwrap();
+
+        let ratings = response.ratings();
+        assert_eq!(ratings.imdb, Some(Rating::new(8.8, 2_300_000)));
+        assert_eq!(ratings.rotten_tomatoes, Some(Rating::new(79.0, 0)));
+        assert_eq!(ratings.metacritic, None);
+        assert_eq!(ratings.tmdb, None);
+    }
+}
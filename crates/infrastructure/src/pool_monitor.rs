@@ -0,0 +1,285 @@
+//! Database pool health
+//!
+//! A [`PoolMonitor`] periodically acquires a connection from the pool and runs
+//! a trivial query, timing how long the acquire took. The outcome, together
+//! with how many of the pool's connections are busy and idle, is kept for the
+//! [`DatabaseCheck`] behind `/api/v3/health` and handed to [`PoolMetrics`] for
+//! export to Prometheus. The pool replaces broken connections on its own once
+//! the server is back; the monitor only reports what it sees.
+
+use crate::database::DatabasePool;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use radarr_core::{i18n::translate, HealthCheck, HealthIssue};
+use serde::Serialize;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+/// Acquire latency above which the pool is reported as saturated
+const SLOW_ACQUIRE: Duration = Duration::from_secs(1);
+
+/// Receives pool usage and acquire latencies, e.g. to export them to
+/// Prometheus
+pub trait PoolMetrics: Send + Sync {
+    /// `pool` has `busy` connections in use and `idle` ones waiting, of at
+    /// most `max`
+    fn record_pool_usage(&self, pool: &str, busy: u32, idle: u32, max: u32);
+
+    /// Acquiring a connection from `pool` took `latency`, or failed after it
+    fn record_acquire(&self, pool: &str, latency: Duration, success: bool);
+}
+
+/// Pool health as of the last probe
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolStatus {
+    /// The last probe got a connection and ran its query
+    pub reachable: bool,
+    pub busy: u32,
+    pub idle: u32,
+    pub max_connections: u32,
+    /// How long the last successful probe waited for a connection
+    pub acquire_ms: Option<u64>,
+    /// Probes that failed since the last successful one
+    pub consecutive_failures: u32,
+    pub last_error: Option<String>,
+    pub checked_at: Option<DateTime<Utc>>,
+}
+
+impl PoolStatus {
+    /// Every connection is in use or getting one was slow
+    pub fn saturated(&self) -> bool {
+        self.reachable
+            && (self.busy >= self.max_connections
+                || self
+                    .acquire_ms
+                    .is_some_and(|ms| ms >= SLOW_ACQUIRE.as_millis() as u64))
+    }
+}
+
+/// Probes a database pool and keeps the latest result
+#[derive(Clone)]
+pub struct PoolMonitor {
+    /// Label of the pool in logs and metrics, e.g. `primary`
+    name: &'static str,
+    pool: DatabasePool,
+    status: Arc<RwLock<PoolStatus>>,
+    metrics: Option<Arc<dyn PoolMetrics>>,
+}
+
+impl PoolMonitor {
+    pub fn new(name: &'static str, pool: DatabasePool) -> Self {
+        Self {
+            name,
+            pool,
+            status: Arc::new(RwLock::new(PoolStatus::default())),
+            metrics: None,
+        }
+    }
+
+    /// Report pool usage and acquire latencies to `metrics`
+    pub fn with_metrics(mut self, metrics: Arc<dyn PoolMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Label of the monitored pool
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Acquire a connection, run a trivial query and record the outcome
+    pub async fn probe(&self) -> PoolStatus {
+        let started = Instant::now();
+        let result = async {
+            let mut connection = self.pool.acquire().await?;
+            let acquired = started.elapsed();
+            sqlx::query("SELECT 1").execute(&mut *connection).await?;
+            Ok::<_, sqlx::Error>(acquired)
+        }
+        .await;
+
+        let max = self.pool.options().get_max_connections();
+        let idle = self.pool.num_idle() as u32;
+        let busy = self.pool.size().saturating_sub(idle);
+        if let Some(metrics) = &self.metrics {
+            metrics.record_pool_usage(self.name, busy, idle, max);
+            match &result {
+                Ok(acquired) => metrics.record_acquire(self.name, *acquired, true),
+                Err(_) => metrics.record_acquire(self.name, started.elapsed(), false),
+            }
+        }
+
+        let mut status = self.status.write().unwrap_or_else(|e| e.into_inner());
+        let was_reachable = status.reachable || status.checked_at.is_none();
+        *status = match result {
+            Ok(acquired) => {
+                if !was_reachable {
+                    info!(
+                        "Database {} reachable again after {} failed checks",
+                        self.name, status.consecutive_failures
+                    );
+                }
+                PoolStatus {
+                    reachable: true,
+                    busy,
+                    idle,
+                    max_connections: max,
+                    acquire_ms: Some(acquired.as_millis() as u64),
+                    consecutive_failures: 0,
+                    last_error: None,
+                    checked_at: Some(Utc::now()),
+                }
+            }
+            Err(e) => {
+                if was_reachable {
+                    warn!("Database {} unreachable: {}", self.name, e);
+                } else {
+                    debug!("Database {} still unreachable: {}", self.name, e);
+                }
+                PoolStatus {
+                    reachable: false,
+                    busy,
+                    idle,
+                    max_connections: max,
+                    acquire_ms: None,
+                    consecutive_failures: status.consecutive_failures + 1,
+                    last_error: Some(e.to_string()),
+                    checked_at: Some(Utc::now()),
+                }
+            }
+        };
+        status.clone()
+    }
+
+    /// Pool health as of the last probe
+    pub fn status(&self) -> PoolStatus {
+        self.status
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Probe the pool every `interval`, starting now
+    pub fn spawn_monitor(&self, interval: Duration) -> JoinHandle<()> {
+        let monitor = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                monitor.probe().await;
+            }
+        })
+    }
+}
+
+/// Errors while the database cannot be reached, warns while its pool is
+/// saturated
+pub struct DatabaseCheck {
+    monitor: PoolMonitor,
+}
+
+impl DatabaseCheck {
+    pub fn new(monitor: PoolMonitor) -> Self {
+        Self { monitor }
+    }
+}
+
+#[async_trait]
+impl HealthCheck for DatabaseCheck {
+    fn name(&self) -> &'static str {
+        "DatabaseCheck"
+    }
+
+    async fn check(&self) -> Vec<HealthIssue> {
+        let status = self.monitor.probe().await;
+
+        if !status.reachable {
+            vec![HealthIssue::error(
+                self.name(),
+                translate(
+                    "health-database-unavailable",
+                    &[("error", status.last_error.unwrap_or_default())],
+                ),
+                "database-is-unavailable",
+            )]
+        } else if status.saturated() {
+            vec![HealthIssue::warning(
+                self.name(),
+                translate(
+                    "health-database-pool-saturated",
+                    &[
+                        ("busy", status.busy.to_string()),
+                        ("max", status.max_connections.to_string()),
+                        (
+                            "acquire_ms",
+                            status.acquire_ms.unwrap_or_default().to_string(),
+                        ),
+                    ],
+                ),
+                "database-connections-are-exhausted",
+            )]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::postgres::PgPoolOptions;
+
+    #[test]
+    fn test_saturated_when_all_busy_or_slow() {
+        let status = PoolStatus {
+            reachable: true,
+            busy: 3,
+            idle: 2,
+            max_connections: 5,
+            acquire_ms: Some(4),
+            ..Default::default()
+        };
+        assert!(!status.saturated());
+
+        assert!(PoolStatus {
+            busy: 5,
+            idle: 0,
+            ..status.clone()
+        }
+        .saturated());
+        assert!(PoolStatus {
+            acquire_ms: Some(1500),
+            ..status.clone()
+        }
+        .saturated());
+        assert!(!PoolStatus {
+            reachable: false,
+            busy: 5,
+            ..status
+        }
+        .saturated());
+    }
+
+    #[tokio::test]
+    async fn test_probe_counts_failures_of_unreachable_pool() {
+        let pool = PgPoolOptions::new()
+            .acquire_timeout(Duration::from_millis(100))
+            .connect_lazy("postgresql://radarr@127.0.0.1:1/radarr")
+            .unwrap();
+        let monitor = PoolMonitor::new("primary", pool);
+
+        monitor.probe().await;
+        let status = monitor.probe().await;
+        assert!(!status.reachable);
+        assert_eq!(status.consecutive_failures, 2);
+        assert!(status.last_error.is_some());
+        assert_eq!(monitor.status(), status);
+
+        let issues = DatabaseCheck::new(monitor).check().await;
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].source, "DatabaseCheck");
+    }
+}
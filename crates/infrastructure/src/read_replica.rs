@@ -0,0 +1,253 @@
+//! Read replica routing
+//!
+//! Heavy read-only queries such as the library listing, statistics and
+//! history can be served by a streaming replica while every write stays on
+//! the primary. The replica's replication lag is checked periodically; while
+//! it lags more than the allowed maximum or cannot be reached, reads go to the
+//! primary. A read that fails on the replica with a connection error is
+//! retried on the primary and takes the replica out of rotation until the
+//! next successful check. Reads on the primary are retried briefly while it
+//! cannot be reached, e.g. during a restart.
+
+use crate::database::{is_connection_error, DatabasePool, DatabaseRetryPolicy};
+use serde::Serialize;
+use sqlx::Row;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+/// Replication lag in seconds; zero on a caught-up replica even when the
+/// primary has been idle since the last replayed transaction
+const LAG_QUERY: &str = "SELECT COALESCE(CASE
+        WHEN NOT pg_is_in_recovery() THEN 0
+        WHEN pg_last_wal_receive_lsn() = pg_last_wal_replay_lsn() THEN 0
+        ELSE EXTRACT(EPOCH FROM now() - pg_last_xact_replay_timestamp())
+    END, 0)::float8 AS lag_seconds";
+
+/// Lag value of a replica that is unreachable or not checked yet
+const UNAVAILABLE: u64 = u64::MAX;
+
+/// Replica health as of the last check
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplicaStatus {
+    pub configured: bool,
+    pub available: bool,
+    pub lag_ms: Option<u64>,
+    pub max_lag_ms: u64,
+    /// Reads are currently sent to the replica
+    pub serving_reads: bool,
+}
+
+/// Routes read-only queries to the replica when it is healthy
+#[derive(Clone)]
+pub struct ReadPool {
+    primary: DatabasePool,
+    replica: Option<DatabasePool>,
+    max_lag: Duration,
+    /// Last measured lag in milliseconds, `UNAVAILABLE` when unknown
+    lag_ms: Arc<AtomicU64>,
+    /// How reads on the primary are retried while it cannot be reached
+    retry: DatabaseRetryPolicy,
+}
+
+impl ReadPool {
+    /// Send every read to the primary
+    pub fn primary_only(primary: DatabasePool) -> Self {
+        Self {
+            primary,
+            replica: None,
+            max_lag: Duration::ZERO,
+            lag_ms: Arc::new(AtomicU64::new(UNAVAILABLE)),
+            retry: DatabaseRetryPolicy::default(),
+        }
+    }
+
+    /// Send reads to `replica` while it lags at most `max_lag`; it stays out
+    /// of rotation until its first successful check
+    pub fn with_replica(primary: DatabasePool, replica: DatabasePool, max_lag: Duration) -> Self {
+        Self {
+            primary,
+            replica: Some(replica),
+            max_lag,
+            lag_ms: Arc::new(AtomicU64::new(UNAVAILABLE)),
+            retry: DatabaseRetryPolicy::default(),
+        }
+    }
+
+    /// Retry reads on the primary according to `retry`
+    pub fn with_retry_policy(mut self, retry: DatabaseRetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// The primary, for writes and reads that must see them immediately
+    pub fn primary(&self) -> &DatabasePool {
+        &self.primary
+    }
+
+    /// Run a read-only query on the replica if it is in rotation, otherwise
+    /// or after a connection failure on the primary, retrying while the
+    /// primary cannot be reached
+    pub async fn read<T, F, Fut>(&self, query: F) -> Result<T, sqlx::Error>
+    where
+        F: Fn(DatabasePool) -> Fut,
+        Fut: Future<Output = Result<T, sqlx::Error>>,
+    {
+        if let Some(replica) = self.replica.as_ref().filter(|_| self.serves_reads()) {
+            match query(replica.clone()).await {
+                Err(e) if is_connection_error(&e) => {
+                    warn!("Read replica failed, falling back to the primary: {}", e);
+                    self.record_lag(None);
+                }
+                result => return result,
+            }
+        }
+        self.retry
+            .run("Read query", || query(self.primary.clone()))
+            .await
+    }
+
+    /// Measure the replica's lag and update its rotation
+    pub async fn check(&self) -> ReplicaStatus {
+        if let Some(replica) = &self.replica {
+            let lag = sqlx::query(LAG_QUERY)
+                .fetch_one(replica)
+                .await
+                .and_then(|row| row.try_get::<f64, _>("lag_seconds"));
+            match lag {
+                Ok(seconds) => self.record_lag(Some(Duration::from_secs_f64(seconds.max(0.0)))),
+                Err(e) => {
+                    debug!("Read replica check failed: {}", e);
+                    self.record_lag(None);
+                }
+            }
+        }
+        self.status()
+    }
+
+    /// Replica health as of the last check
+    pub fn status(&self) -> ReplicaStatus {
+        let lag_ms = self.lag_ms.load(Ordering::Relaxed);
+        ReplicaStatus {
+            configured: self.replica.is_some(),
+            available: self.replica.is_some() && lag_ms != UNAVAILABLE,
+            lag_ms: (self.replica.is_some() && lag_ms != UNAVAILABLE).then_some(lag_ms),
+            max_lag_ms: self.max_lag.as_millis() as u64,
+            serving_reads: self.serves_reads(),
+        }
+    }
+
+    /// Check the replica every `interval`, starting now; `None` without one
+    pub fn spawn_monitor(&self, interval: Duration) -> Option<JoinHandle<()>> {
+        self.replica.as_ref()?;
+        let reads = self.clone();
+        Some(tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                reads.check().await;
+            }
+        }))
+    }
+
+    fn serves_reads(&self) -> bool {
+        self.replica.is_some()
+            && self.lag_ms.load(Ordering::Relaxed) <= self.max_lag.as_millis() as u64
+    }
+
+    /// Store a measured lag, `None` when the replica could not be reached
+    fn record_lag(&self, lag: Option<Duration>) {
+        let was_serving = self.serves_reads();
+        let lag_ms = lag.map_or(UNAVAILABLE, |lag| lag.as_millis() as u64);
+        self.lag_ms.store(lag_ms, Ordering::Relaxed);
+
+        match (was_serving, self.serves_reads()) {
+            (false, true) => info!("Read replica back in rotation ({} ms behind)", lag_ms),
+            (true, false) if lag.is_some() => warn!(
+                "Read replica is {} ms behind (max {} ms), reading from the primary",
+                lag_ms,
+                self.max_lag.as_millis()
+            ),
+            (true, false) => warn!("Read replica unavailable, reading from the primary"),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::postgres::PgPoolOptions;
+
+    fn lazy_pool(database: &str) -> DatabasePool {
+        PgPoolOptions::new()
+            .connect_lazy(&format!("postgresql://radarr@localhost/{}", database))
+            .unwrap()
+    }
+
+    /// Name of the database a read was sent to
+    async fn database(pool: DatabasePool) -> Result<String, sqlx::Error> {
+        Ok(pool
+            .connect_options()
+            .get_database()
+            .unwrap_or_default()
+            .to_string())
+    }
+
+    #[tokio::test]
+    async fn test_reads_follow_replica_lag() {
+        let reads = ReadPool::with_replica(
+            lazy_pool("primary"),
+            lazy_pool("replica"),
+            Duration::from_secs(30),
+        );
+        assert_eq!(reads.read(database).await.unwrap(), "primary");
+
+        reads.record_lag(Some(Duration::from_secs(2)));
+        assert_eq!(reads.read(database).await.unwrap(), "replica");
+        assert_eq!(reads.status().lag_ms, Some(2000));
+
+        reads.record_lag(Some(Duration::from_secs(90)));
+        assert_eq!(reads.read(database).await.unwrap(), "primary");
+        assert!(reads.status().available);
+        assert!(!reads.status().serving_reads);
+
+        let primary_only = ReadPool::primary_only(lazy_pool("primary"));
+        primary_only.record_lag(Some(Duration::ZERO));
+        assert_eq!(primary_only.read(database).await.unwrap(), "primary");
+        assert!(!primary_only.status().configured);
+    }
+
+    #[tokio::test]
+    async fn test_connection_failure_falls_back_to_primary() {
+        let reads = ReadPool::with_replica(
+            lazy_pool("primary"),
+            lazy_pool("replica"),
+            Duration::from_secs(30),
+        );
+        reads.record_lag(Some(Duration::ZERO));
+
+        let result = reads
+            .read(|pool| async move {
+                match database(pool).await?.as_str() {
+                    "replica" => Err(sqlx::Error::PoolTimedOut),
+                    name => Ok(name.to_string()),
+                }
+            })
+            .await;
+        assert_eq!(result.unwrap(), "primary");
+        assert!(!reads.status().available);
+
+        // Query errors are not the replica's fault and are returned as is
+        reads.record_lag(Some(Duration::ZERO));
+        let result: Result<(), _> = reads
+            .read(|_| async { Err(sqlx::Error::RowNotFound) })
+            .await;
+        assert!(matches!(result, Err(sqlx::Error::RowNotFound)));
+        assert!(reads.status().serving_reads);
+    }
+}
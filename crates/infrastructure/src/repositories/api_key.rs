@@ -0,0 +1,120 @@
+//! PostgreSQL implementation of ApiKeyRepository
+
+use crate::database::DatabasePool;
+use async_trait::async_trait;
+use radarr_core::{
+    domain::repositories::ApiKeyRepository,
+    models::{ApiKey, ApiKeyScope},
+    RadarrError, Result,
+};
+use sqlx::Row;
+use uuid::Uuid;
+
+const API_KEY_COLUMNS: &str = "id, name, key_hash, scope, created_at, last_used_at, revoked_at";
+
+/// PostgreSQL implementation of ApiKeyRepository
+pub struct PostgresApiKeyRepository {
+    pool: DatabasePool,
+}
+
+impl PostgresApiKeyRepository {
+    /// Create a new PostgreSQL API key repository
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert database row to ApiKey
+    fn row_to_key(row: &sqlx::postgres::PgRow) -> Result<ApiKey> {
+        let scope: String = row.try_get("scope")?;
+        let scope = ApiKeyScope::parse(&scope).ok_or_else(|| RadarrError::DatabaseError {
+            message: format!("Invalid API key scope: {}", scope),
+        })?;
+
+        Ok(ApiKey {
+            id: row.try_get("id")?,
+            name: row.try_get("name")?,
+            key_hash: row.try_get("key_hash")?,
+            scope,
+            created_at: row.try_get("created_at")?,
+            last_used_at: row.try_get("last_used_at")?,
+            revoked_at: row.try_get("revoked_at")?,
+        })
+    }
+}
+
+#[async_trait]
+impl ApiKeyRepository for PostgresApiKeyRepository {
+    async fn list(&self) -> Result<Vec<ApiKey>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM api_keys ORDER BY created_at DESC",
+            API_KEY_COLUMNS
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(Self::row_to_key).collect()
+    }
+
+    async fn create(&self, key: &ApiKey) -> Result<ApiKey> {
+        let row = sqlx::query(&format!(
+            "INSERT INTO api_keys (id, name, key_hash, scope, created_at, last_used_at,
+             revoked_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7)
+             RETURNING {}",
+            API_KEY_COLUMNS
+        ))
+        .bind(key.id)
+        .bind(&key.name)
+        .bind(&key.key_hash)
+        .bind(key.scope.as_str())
+        .bind(key.created_at)
+        .bind(key.last_used_at)
+        .bind(key.revoked_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Self::row_to_key(&row)
+    }
+
+    async fn rotate(&self, id: Uuid, key_hash: &str) -> Result<Option<ApiKey>> {
+        let row = sqlx::query(&format!(
+            "UPDATE api_keys SET key_hash = $2
+             WHERE id = $1 AND revoked_at IS NULL
+             RETURNING {}",
+            API_KEY_COLUMNS
+        ))
+        .bind(id)
+        .bind(key_hash)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.as_ref().map(Self::row_to_key).transpose()
+    }
+
+    async fn revoke(&self, id: Uuid) -> Result<Option<ApiKey>> {
+        let row = sqlx::query(&format!(
+            "UPDATE api_keys SET revoked_at = COALESCE(revoked_at, NOW())
+             WHERE id = $1
+             RETURNING {}",
+            API_KEY_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.as_ref().map(Self::row_to_key).transpose()
+    }
+
+    async fn touch(&self, id: Uuid, at: chrono::DateTime<chrono::Utc>) -> Result<()> {
+        sqlx::query(
+            "UPDATE api_keys SET last_used_at = GREATEST(last_used_at, $2)
+             WHERE id = $1",
+        )
+        .bind(id)
+        .bind(at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
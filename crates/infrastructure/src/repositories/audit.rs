@@ -0,0 +1,117 @@
+//! PostgreSQL implementation of AuditRepository
+
+use crate::database::DatabasePool;
+use async_trait::async_trait;
+use radarr_core::{
+    domain::repositories::AuditRepository,
+    models::{AuditAction, AuditActor, AuditQuery, AuditRecord},
+    RadarrError, Result,
+};
+use sqlx::Row;
+
+const AUDIT_COLUMNS: &str =
+    "id, action, actor, api_key_id, target, details, event_id, correlation_id, date";
+
+/// PostgreSQL implementation of AuditRepository
+pub struct PostgresAuditRepository {
+    pool: DatabasePool,
+}
+
+impl PostgresAuditRepository {
+    /// Create a new PostgreSQL audit repository
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert database row to AuditRecord
+    fn row_to_audit(row: &sqlx::postgres::PgRow) -> Result<AuditRecord> {
+        let action: String = row.try_get("action")?;
+
+        Ok(AuditRecord {
+            id: row.try_get("id")?,
+            action: AuditAction::parse(&action).ok_or_else(|| RadarrError::DatabaseError {
+                message: format!("Invalid audit action: {}", action),
+            })?,
+            actor: AuditActor {
+                name: row.try_get("actor")?,
+                api_key_id: row.try_get("api_key_id")?,
+            },
+            target: row.try_get("target")?,
+            details: row.try_get("details")?,
+            event_id: row.try_get("event_id")?,
+            correlation_id: row.try_get("correlation_id")?,
+            date: row.try_get("date")?,
+        })
+    }
+}
+
+#[async_trait]
+impl AuditRepository for PostgresAuditRepository {
+    async fn create(&self, record: &AuditRecord) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO audit_log (id, action, actor, api_key_id, target, details,
+             event_id, correlation_id, date)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+             ON CONFLICT (event_id) DO NOTHING",
+        )
+        .bind(record.id)
+        .bind(record.action.as_str())
+        .bind(&record.actor.name)
+        .bind(record.actor.api_key_id)
+        .bind(&record.target)
+        .bind(&record.details)
+        .bind(record.event_id)
+        .bind(record.correlation_id)
+        .bind(record.date)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn list(&self, query: &AuditQuery) -> Result<(Vec<AuditRecord>, i64)> {
+        // An empty filter is bound as NULL so it matches every action
+        let actions: Option<Vec<&str>> = (!query.actions.is_empty())
+            .then(|| query.actions.iter().map(|action| action.as_str()).collect());
+
+        let total: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM audit_log
+             WHERE ($1::text[] IS NULL OR action = ANY($1))
+               AND ($2::uuid IS NULL OR api_key_id = $2)
+               AND ($3::text IS NULL OR LOWER(actor) = LOWER($3))
+               AND ($4::timestamptz IS NULL OR date >= $4)",
+        )
+        .bind(&actions)
+        .bind(query.api_key_id)
+        .bind(&query.actor)
+        .bind(query.since)
+        .fetch_one(&self.pool)
+        .await?;
+
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM audit_log
+             WHERE ($1::text[] IS NULL OR action = ANY($1))
+               AND ($2::uuid IS NULL OR api_key_id = $2)
+               AND ($3::text IS NULL OR LOWER(actor) = LOWER($3))
+               AND ($4::timestamptz IS NULL OR date >= $4)
+             ORDER BY date DESC, id
+             LIMIT $5 OFFSET $6",
+            AUDIT_COLUMNS
+        ))
+        .bind(&actions)
+        .bind(query.api_key_id)
+        .bind(&query.actor)
+        .bind(query.since)
+        .bind(query.limit)
+        .bind(query.offset)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let records = rows
+            .iter()
+            .map(Self::row_to_audit)
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok((records, total))
+    }
+}
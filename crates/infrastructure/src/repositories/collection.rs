@@ -0,0 +1,139 @@
+//! PostgreSQL implementation of CollectionRepository
+
+use crate::database::DatabasePool;
+use crate::repositories::movie::parse_minimum_availability;
+use async_trait::async_trait;
+use radarr_core::{
+    domain::repositories::CollectionRepository, models::MovieCollection, RadarrError, Result,
+};
+use sqlx::Row;
+use uuid::Uuid;
+
+const COLLECTION_COLUMNS: &str = "id, tmdb_id, title, overview, poster_path, backdrop_path,
+     monitored, quality_profile_id, minimum_availability, movies,
+     created_at, updated_at, last_info_sync";
+
+/// PostgreSQL implementation of CollectionRepository
+pub struct PostgresCollectionRepository {
+    pool: DatabasePool,
+}
+
+impl PostgresCollectionRepository {
+    /// Create a new PostgreSQL collection repository
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert database row to MovieCollection
+    fn row_to_collection(row: &sqlx::postgres::PgRow) -> Result<MovieCollection> {
+        Ok(MovieCollection {
+            id: row.try_get("id")?,
+            tmdb_id: row.try_get("tmdb_id")?,
+            title: row.try_get("title")?,
+            overview: row.try_get("overview")?,
+            poster_path: row.try_get("poster_path")?,
+            backdrop_path: row.try_get("backdrop_path")?,
+            monitored: row.try_get("monitored")?,
+            quality_profile_id: row.try_get("quality_profile_id")?,
+            minimum_availability: parse_minimum_availability(
+                &row.try_get::<String, _>("minimum_availability")?,
+            )?,
+            movies: serde_json::from_value(row.try_get("movies")?)?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+            last_info_sync: row.try_get("last_info_sync")?,
+        })
+    }
+}
+
+#[async_trait]
+impl CollectionRepository for PostgresCollectionRepository {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<MovieCollection>> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM collections WHERE id = $1",
+            COLLECTION_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.as_ref().map(Self::row_to_collection).transpose()
+    }
+
+    async fn find_by_tmdb_id(&self, tmdb_id: i32) -> Result<Option<MovieCollection>> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM collections WHERE tmdb_id = $1",
+            COLLECTION_COLUMNS
+        ))
+        .bind(tmdb_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.as_ref().map(Self::row_to_collection).transpose()
+    }
+
+    async fn list(&self) -> Result<Vec<MovieCollection>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM collections ORDER BY title, tmdb_id",
+            COLLECTION_COLUMNS
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(Self::row_to_collection).collect()
+    }
+
+    async fn upsert(&self, collection: &MovieCollection) -> Result<MovieCollection> {
+        let row = sqlx::query(&format!(
+            "INSERT INTO collections (id, tmdb_id, title, overview, poster_path, backdrop_path,
+             monitored, quality_profile_id, minimum_availability, movies,
+             created_at, updated_at, last_info_sync)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+             ON CONFLICT (tmdb_id) DO UPDATE SET
+                title = EXCLUDED.title,
+                overview = EXCLUDED.overview,
+                poster_path = EXCLUDED.poster_path,
+                backdrop_path = EXCLUDED.backdrop_path,
+                monitored = EXCLUDED.monitored,
+                quality_profile_id = EXCLUDED.quality_profile_id,
+                minimum_availability = EXCLUDED.minimum_availability,
+                movies = EXCLUDED.movies,
+                updated_at = EXCLUDED.updated_at,
+                last_info_sync = EXCLUDED.last_info_sync
+             RETURNING {}",
+            COLLECTION_COLUMNS
+        ))
+        .bind(collection.id)
+        .bind(collection.tmdb_id)
+        .bind(&collection.title)
+        .bind(&collection.overview)
+        .bind(&collection.poster_path)
+        .bind(&collection.backdrop_path)
+        .bind(collection.monitored)
+        .bind(collection.quality_profile_id)
+        .bind(collection.minimum_availability.to_string())
+        .bind(serde_json::to_value(&collection.movies)?)
+        .bind(collection.created_at)
+        .bind(collection.updated_at)
+        .bind(collection.last_info_sync)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Self::row_to_collection(&row)
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<()> {
+        let result = sqlx::query("DELETE FROM collections WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(RadarrError::NotFound {
+                resource: format!("collection {}", id),
+            });
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+//! PostgreSQL implementation of ConfigRepository
+
+use crate::database::DatabasePool;
+use async_trait::async_trait;
+use radarr_core::{domain::repositories::ConfigRepository, models::ConfigSection, Result};
+use sqlx::Row;
+
+/// PostgreSQL implementation of ConfigRepository
+pub struct PostgresConfigRepository {
+    pool: DatabasePool,
+}
+
+impl PostgresConfigRepository {
+    /// Create a new PostgreSQL config repository
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ConfigRepository for PostgresConfigRepository {
+    async fn get(&self, section: ConfigSection) -> Result<Option<serde_json::Value>> {
+        let row = sqlx::query("SELECT settings FROM config WHERE section = $1")
+            .bind(section.as_str())
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.map(|row| row.try_get("settings").map_err(Into::into))
+            .transpose()
+    }
+
+    async fn save(&self, section: ConfigSection, settings: &serde_json::Value) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO config (section, settings, updated_at) VALUES ($1, $2, NOW())
+             ON CONFLICT (section) DO UPDATE SET settings = $2, updated_at = NOW()",
+        )
+        .bind(section.as_str())
+        .bind(settings)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -16,12 +16,22 @@ pub trait CustomFormatsRepository: Send + Sync {
     /// Find custom format by name
     async fn find_by_name(&self, name: &str) -> Result<Option<CustomFormat>>;
 
+    /// Find a custom format with the given name other than `exclude_id`
+    async fn find_by_name_excluding(
+        &self,
+        name: &str,
+        exclude_id: &Uuid,
+    ) -> Result<Option<CustomFormat>>;
+
     /// Create a new custom format
     async fn create(&self, format: &CustomFormat) -> Result<CustomFormat>;
 
     /// Update an existing custom format
     async fn update(&self, format: &CustomFormat) -> Result<CustomFormat>;
 
+    /// Enable or disable a custom format, returning false if it does not exist
+    async fn set_enabled(&self, id: &Uuid, enabled: bool) -> Result<bool>;
+
     /// Delete a custom format
     async fn delete(&self, id: &Uuid) -> Result<()>;
 
@@ -99,6 +109,26 @@ impl CustomFormatsRepository for PostgresCustomFormatsRepository {
         }
     }
 
+    async fn find_by_name_excluding(
+        &self,
+        name: &str,
+        exclude_id: &Uuid,
+    ) -> Result<Option<CustomFormat>> {
+        let row = sqlx::query(
+            "SELECT id, name, specifications, score, enabled, created_at, updated_at 
+             FROM custom_formats WHERE name = $1 AND id <> $2",
+        )
+        .bind(name)
+        .bind(exclude_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match row {
+            Some(row) => Ok(Some(self.row_to_custom_format(&row)?)),
+            None => Ok(None),
+        }
+    }
+
     async fn create(&self, format: &CustomFormat) -> Result<CustomFormat> {
         let specifications_json = serde_json::to_value(&format.specifications).map_err(|e| {
             RadarrError::SerializationError(format!("Failed to serialize specifications: {}", e))
@@ -140,6 +170,17 @@ impl CustomFormatsRepository for PostgresCustomFormatsRepository {
         Ok(format.clone())
     }
 
+    async fn set_enabled(&self, id: &Uuid, enabled: bool) -> Result<bool> {
+        let result =
+            sqlx::query("UPDATE custom_formats SET enabled = $2, updated_at = NOW() WHERE id = $1")
+                .bind(id)
+                .bind(enabled)
+                .execute(&self.pool)
+                .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
     async fn delete(&self, id: &Uuid) -> Result<()> {
         sqlx::query("DELETE FROM custom_formats WHERE id = $1")
             .bind(id)
@@ -0,0 +1,156 @@
+//! PostgreSQL implementation of HistoryRepository
+
+use crate::database::DatabasePool;
+use crate::read_replica::ReadPool;
+use async_trait::async_trait;
+use radarr_core::{
+    domain::repositories::HistoryRepository,
+    models::{HistoryEventType, HistoryQuery, HistoryRecord},
+    RadarrError, Result,
+};
+use sqlx::Row;
+use uuid::Uuid;
+
+const HISTORY_COLUMNS: &str =
+    "id, event_type, movie_id, source_title, download_id, data, event_id, correlation_id, date";
+
+/// PostgreSQL implementation of HistoryRepository
+pub struct PostgresHistoryRepository {
+    pool: DatabasePool,
+    /// History listing, which may be served by a read replica
+    reads: ReadPool,
+}
+
+impl PostgresHistoryRepository {
+    /// Create a new PostgreSQL history repository
+    pub fn new(pool: DatabasePool) -> Self {
+        Self {
+            reads: ReadPool::primary_only(pool.clone()),
+            pool,
+        }
+    }
+
+    /// Serve the history listing from `reads`
+    pub fn with_read_pool(mut self, reads: ReadPool) -> Self {
+        self.reads = reads;
+        self
+    }
+
+    /// Convert database row to HistoryRecord
+    fn row_to_history(row: &sqlx::postgres::PgRow) -> Result<HistoryRecord> {
+        let event_type: String = row.try_get("event_type")?;
+
+        Ok(HistoryRecord {
+            id: row.try_get("id")?,
+            event_type: HistoryEventType::parse(&event_type).ok_or_else(|| {
+                RadarrError::DatabaseError {
+                    message: format!("Invalid history event type: {}", event_type),
+                }
+            })?,
+            movie_id: row.try_get("movie_id")?,
+            source_title: row.try_get("source_title")?,
+            download_id: row.try_get("download_id")?,
+            data: row.try_get("data")?,
+            event_id: row.try_get("event_id")?,
+            correlation_id: row.try_get("correlation_id")?,
+            date: row.try_get("date")?,
+        })
+    }
+}
+
+#[async_trait]
+impl HistoryRepository for PostgresHistoryRepository {
+    async fn create(&self, record: &HistoryRecord) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO history (id, event_type, movie_id, source_title, download_id, data,
+             event_id, correlation_id, date)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+             ON CONFLICT (event_id) DO NOTHING",
+        )
+        .bind(record.id)
+        .bind(record.event_type.as_str())
+        .bind(record.movie_id)
+        .bind(&record.source_title)
+        .bind(&record.download_id)
+        .bind(&record.data)
+        .bind(record.event_id)
+        .bind(record.correlation_id)
+        .bind(record.date)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn list(&self, query: &HistoryQuery) -> Result<(Vec<HistoryRecord>, i64)> {
+        // An empty filter is bound as NULL so it matches every event type
+        let event_types: Option<Vec<&str>> = (!query.event_types.is_empty()).then(|| {
+            query
+                .event_types
+                .iter()
+                .map(|event_type| event_type.as_str())
+                .collect()
+        });
+
+        let event_types = &event_types;
+
+        let total: i64 = self
+            .reads
+            .read(|pool| async move {
+                sqlx::query_scalar(
+                    "SELECT COUNT(*) FROM history
+                     WHERE ($1::text[] IS NULL OR event_type = ANY($1))
+                       AND ($2::uuid IS NULL OR movie_id = $2)
+                       AND ($3::timestamptz IS NULL OR date >= $3)",
+                )
+                .bind(event_types)
+                .bind(query.movie_id)
+                .bind(query.since)
+                .fetch_one(&pool)
+                .await
+            })
+            .await?;
+
+        let sql = &format!(
+            "SELECT {} FROM history
+             WHERE ($1::text[] IS NULL OR event_type = ANY($1))
+               AND ($2::uuid IS NULL OR movie_id = $2)
+               AND ($3::timestamptz IS NULL OR date >= $3)
+             ORDER BY date DESC, id
+             LIMIT $4 OFFSET $5",
+            HISTORY_COLUMNS
+        );
+        let rows = self
+            .reads
+            .read(|pool| async move {
+                sqlx::query(sql)
+                    .bind(event_types)
+                    .bind(query.movie_id)
+                    .bind(query.since)
+                    .bind(query.limit)
+                    .bind(query.offset)
+                    .fetch_all(&pool)
+                    .await
+            })
+            .await?;
+
+        let records = rows
+            .iter()
+            .map(Self::row_to_history)
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok((records, total))
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<HistoryRecord>> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM history WHERE id = $1",
+            HISTORY_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.as_ref().map(Self::row_to_history).transpose()
+    }
+}
@@ -0,0 +1,146 @@
+//! PostgreSQL implementation of ImportListRepository
+
+use crate::database::DatabasePool;
+use async_trait::async_trait;
+use radarr_core::{
+    domain::repositories::ImportListRepository, models::ImportList, RadarrError, Result,
+};
+use sqlx::Row;
+
+const IMPORT_LIST_COLUMNS: &str = "id, name, source_type, list_url, list_id, enabled, \
+     sync_enabled, sync_interval, add_monitored, minimum_availability, quality_profile_id, \
+     root_folder_path, tags, created_at, updated_at";
+
+/// PostgreSQL implementation of ImportListRepository
+pub struct PostgresImportListRepository {
+    pool: DatabasePool,
+}
+
+impl PostgresImportListRepository {
+    /// Create a new PostgreSQL import list repository
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert database row to ImportList; nullable columns fall back to the
+    /// table defaults
+    fn row_to_import_list(row: &sqlx::postgres::PgRow) -> Result<ImportList> {
+        let now = chrono::Utc::now();
+        Ok(ImportList {
+            id: row.try_get("id")?,
+            name: row.try_get("name")?,
+            source_type: row.try_get("source_type")?,
+            list_url: row.try_get("list_url")?,
+            list_id: row.try_get("list_id")?,
+            enabled: row.try_get::<Option<bool>, _>("enabled")?.unwrap_or(true),
+            sync_enabled: row
+                .try_get::<Option<bool>, _>("sync_enabled")?
+                .unwrap_or(true),
+            sync_interval: row
+                .try_get::<Option<i32>, _>("sync_interval")?
+                .unwrap_or(360),
+            add_monitored: row
+                .try_get::<Option<bool>, _>("add_monitored")?
+                .unwrap_or(true),
+            minimum_availability: row
+                .try_get::<Option<String>, _>("minimum_availability")?
+                .unwrap_or_else(|| "announced".to_string()),
+            quality_profile_id: row.try_get("quality_profile_id")?,
+            root_folder_path: row.try_get("root_folder_path")?,
+            tags: row.try_get("tags")?,
+            created_at: row.try_get::<Option<_>, _>("created_at")?.unwrap_or(now),
+            updated_at: row.try_get::<Option<_>, _>("updated_at")?.unwrap_or(now),
+        })
+    }
+}
+
+#[async_trait]
+impl ImportListRepository for PostgresImportListRepository {
+    async fn find_by_name(&self, name: &str) -> Result<Option<ImportList>> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM import_lists WHERE name = $1",
+            IMPORT_LIST_COLUMNS
+        ))
+        .bind(name)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.as_ref().map(Self::row_to_import_list).transpose()
+    }
+
+    async fn list(&self) -> Result<Vec<ImportList>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM import_lists ORDER BY name",
+            IMPORT_LIST_COLUMNS
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(Self::row_to_import_list).collect()
+    }
+
+    async fn create(&self, list: &ImportList) -> Result<ImportList> {
+        let row = sqlx::query(&format!(
+            "INSERT INTO import_lists (id, name, source_type, list_url, list_id, enabled,
+             sync_enabled, sync_interval, add_monitored, minimum_availability,
+             quality_profile_id, root_folder_path, tags, created_at, updated_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+             RETURNING {}",
+            IMPORT_LIST_COLUMNS
+        ))
+        .bind(list.id)
+        .bind(&list.name)
+        .bind(&list.source_type)
+        .bind(&list.list_url)
+        .bind(&list.list_id)
+        .bind(list.enabled)
+        .bind(list.sync_enabled)
+        .bind(list.sync_interval)
+        .bind(list.add_monitored)
+        .bind(&list.minimum_availability)
+        .bind(list.quality_profile_id)
+        .bind(&list.root_folder_path)
+        .bind(&list.tags)
+        .bind(list.created_at)
+        .bind(list.updated_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Self::row_to_import_list(&row)
+    }
+
+    async fn update(&self, list: &ImportList) -> Result<ImportList> {
+        let row = sqlx::query(&format!(
+            "UPDATE import_lists SET name = $2, source_type = $3, list_url = $4, list_id = $5,
+             enabled = $6, sync_enabled = $7, sync_interval = $8, add_monitored = $9,
+             minimum_availability = $10, quality_profile_id = $11, root_folder_path = $12,
+             tags = $13, updated_at = $14
+             WHERE id = $1
+             RETURNING {}",
+            IMPORT_LIST_COLUMNS
+        ))
+        .bind(list.id)
+        .bind(&list.name)
+        .bind(&list.source_type)
+        .bind(&list.list_url)
+        .bind(&list.list_id)
+        .bind(list.enabled)
+        .bind(list.sync_enabled)
+        .bind(list.sync_interval)
+        .bind(list.add_monitored)
+        .bind(&list.minimum_availability)
+        .bind(list.quality_profile_id)
+        .bind(&list.root_folder_path)
+        .bind(&list.tags)
+        .bind(list.updated_at)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match row {
+            Some(row) => Self::row_to_import_list(&row),
+            None => Err(RadarrError::NotFound {
+                resource: format!("import list {}", list.id),
+            }),
+        }
+    }
+}
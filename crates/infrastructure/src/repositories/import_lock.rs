@@ -0,0 +1,74 @@
+//! PostgreSQL implementation of ImportLockRepository
+//!
+//! Uses session-level advisory locks. A lock belongs to the connection that
+//! took it, so each held lock keeps its pooled connection checked out until
+//! it is released on that same connection.
+
+use crate::database::DatabasePool;
+use async_trait::async_trait;
+use radarr_core::{domain::repositories::ImportLockRepository, Result};
+use sqlx::{pool::PoolConnection, Postgres, Row};
+use std::collections::HashMap;
+use tokio::sync::Mutex;
+use tracing::warn;
+
+/// First key of the two-key advisory lock form, reserved for import sources
+const IMPORT_LOCK_CLASS: i32 = 0x494d_5054; // "IMPT"
+
+/// PostgreSQL implementation of ImportLockRepository
+pub struct PostgresImportLockRepository {
+    pool: DatabasePool,
+    held: Mutex<HashMap<String, PoolConnection<Postgres>>>,
+}
+
+impl PostgresImportLockRepository {
+    /// Create a new PostgreSQL import lock repository
+    pub fn new(pool: DatabasePool) -> Self {
+        Self {
+            pool,
+            held: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl ImportLockRepository for PostgresImportLockRepository {
+    async fn try_lock(&self, source_path: &str) -> Result<bool> {
+        let mut held = self.held.lock().await;
+        if held.contains_key(source_path) {
+            return Ok(false);
+        }
+
+        let mut conn = self.pool.acquire().await?;
+        let row = sqlx::query("SELECT pg_try_advisory_lock($1, hashtext($2)) AS locked")
+            .bind(IMPORT_LOCK_CLASS)
+            .bind(source_path)
+            .fetch_one(&mut *conn)
+            .await?;
+        let locked: bool = row.try_get("locked")?;
+
+        if locked {
+            held.insert(source_path.to_string(), conn);
+        }
+        Ok(locked)
+    }
+
+    async fn unlock(&self, source_path: &str) -> Result<()> {
+        let mut conn = match self.held.lock().await.remove(source_path) {
+            Some(conn) => conn,
+            None => return Ok(()),
+        };
+
+        let row = sqlx::query("SELECT pg_advisory_unlock($1, hashtext($2)) AS unlocked")
+            .bind(IMPORT_LOCK_CLASS)
+            .bind(source_path)
+            .fetch_one(&mut *conn)
+            .await?;
+        let unlocked: bool = row.try_get("unlocked")?;
+
+        if !unlocked {
+            warn!("Advisory lock for {} was not held", source_path);
+        }
+        Ok(())
+    }
+}
@@ -9,6 +9,10 @@ use radarr_core::{
 };
 use sqlx::Row;
 
+const INDEXER_COLUMNS: &str = "id, name, implementation, settings, enabled, priority,
+     enable_rss, enable_automatic_search, enable_interactive_search,
+     download_client_id, tags, created_at, updated_at";
+
 /// PostgreSQL implementation of IndexerRepository
 pub struct PostgresIndexerRepository {
     pool: DatabasePool,
@@ -19,119 +23,73 @@ impl PostgresIndexerRepository {
     pub fn new(pool: DatabasePool) -> Self {
         Self { pool }
     }
+
+    /// Convert database row to Indexer
+    fn row_to_indexer(row: &sqlx::postgres::PgRow) -> Result<Indexer> {
+        Ok(Indexer {
+            id: row.try_get("id")?,
+            name: row.try_get("name")?,
+            implementation: parse_indexer_implementation(
+                &row.try_get::<String, _>("implementation")?,
+            )?,
+            settings: row.try_get("settings")?,
+            enabled: row.try_get("enabled")?,
+            priority: row.try_get("priority")?,
+            enable_rss: row.try_get("enable_rss")?,
+            enable_automatic_search: row.try_get("enable_automatic_search")?,
+            enable_interactive_search: row.try_get("enable_interactive_search")?,
+            download_client_id: row.try_get("download_client_id")?,
+            tags: row.try_get("tags")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+        })
+    }
 }
 
 #[async_trait]
 impl IndexerRepository for PostgresIndexerRepository {
     async fn find_by_id(&self, id: i32) -> Result<Option<Indexer>> {
-        let row = sqlx::query(
-            "SELECT id, name, implementation, settings, enabled, priority,
-             enable_rss, enable_automatic_search, enable_interactive_search,
-             download_client_id, created_at, updated_at
-             FROM indexers WHERE id = $1",
-        )
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM indexers WHERE id = $1",
+            INDEXER_COLUMNS
+        ))
         .bind(id)
         .fetch_optional(&self.pool)
         .await?;
 
-        match row {
-            Some(row) => {
-                let indexer = Indexer {
-                    id: row.try_get("id")?,
-                    name: row.try_get("name")?,
-                    implementation: parse_indexer_implementation(
-                        &row.try_get::<String, _>("implementation")?,
-                    )?,
-                    settings: row.try_get("settings")?,
-                    enabled: row.try_get("enabled")?,
-                    priority: row.try_get("priority")?,
-                    enable_rss: row.try_get("enable_rss")?,
-                    enable_automatic_search: row.try_get("enable_automatic_search")?,
-                    enable_interactive_search: row.try_get("enable_interactive_search")?,
-                    download_client_id: row.try_get("download_client_id")?,
-                    created_at: row.try_get("created_at")?,
-                    updated_at: row.try_get("updated_at")?,
-                };
-                Ok(Some(indexer))
-            }
-            None => Ok(None),
-        }
+        row.as_ref().map(Self::row_to_indexer).transpose()
     }
 
     async fn find_by_name(&self, name: &str) -> Result<Option<Indexer>> {
-        let row = sqlx::query(
-            "SELECT id, name, implementation, settings, enabled, priority,
-             enable_rss, enable_automatic_search, enable_interactive_search,
-             download_client_id, created_at, updated_at
-             FROM indexers WHERE name = $1",
-        )
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM indexers WHERE name = $1",
+            INDEXER_COLUMNS
+        ))
         .bind(name)
         .fetch_optional(&self.pool)
         .await?;
 
-        match row {
-            Some(row) => {
-                let indexer = Indexer {
-                    id: row.try_get("id")?,
-                    name: row.try_get("name")?,
-                    implementation: parse_indexer_implementation(
-                        &row.try_get::<String, _>("implementation")?,
-                    )?,
-                    settings: row.try_get("settings")?,
-                    enabled: row.try_get("enabled")?,
-                    priority: row.try_get("priority")?,
-                    enable_rss: row.try_get("enable_rss")?,
-                    enable_automatic_search: row.try_get("enable_automatic_search")?,
-                    enable_interactive_search: row.try_get("enable_interactive_search")?,
-                    download_client_id: row.try_get("download_client_id")?,
-                    created_at: row.try_get("created_at")?,
-                    updated_at: row.try_get("updated_at")?,
-                };
-                Ok(Some(indexer))
-            }
-            None => Ok(None),
-        }
+        row.as_ref().map(Self::row_to_indexer).transpose()
     }
 
     async fn find_enabled(&self) -> Result<Vec<Indexer>> {
-        let rows = sqlx::query(
-            "SELECT id, name, implementation, settings, enabled, priority,
-             enable_rss, enable_automatic_search, enable_interactive_search,
-             download_client_id, created_at, updated_at
-             FROM indexers WHERE enabled = true ORDER BY priority ASC, name ASC",
-        )
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM indexers WHERE enabled = true ORDER BY priority ASC, name ASC",
+            INDEXER_COLUMNS
+        ))
         .fetch_all(&self.pool)
         .await?;
 
-        let mut indexers = Vec::new();
-        for row in rows {
-            let indexer = Indexer {
-                id: row.try_get("id")?,
-                name: row.try_get("name")?,
-                implementation: parse_indexer_implementation(
-                    &row.try_get::<String, _>("implementation")?,
-                )?,
-                settings: row.try_get("settings")?,
-                enabled: row.try_get("enabled")?,
-                priority: row.try_get("priority")?,
-                enable_rss: row.try_get("enable_rss")?,
-                enable_automatic_search: row.try_get("enable_automatic_search")?,
-                enable_interactive_search: row.try_get("enable_interactive_search")?,
-                download_client_id: row.try_get("download_client_id")?,
-                created_at: row.try_get("created_at")?,
-                updated_at: row.try_get("updated_at")?,
-            };
-            indexers.push(indexer);
-        }
-        Ok(indexers)
+        rows.iter().map(Self::row_to_indexer).collect()
     }
 
     async fn create(&self, indexer: &Indexer) -> Result<Indexer> {
-        let _result = sqlx::query(
+        let row = sqlx::query(
             "INSERT INTO indexers (name, implementation, settings, enabled, priority,
              enable_rss, enable_automatic_search, enable_interactive_search,
-             download_client_id, created_at, updated_at)
-             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)",
+             download_client_id, tags, created_at, updated_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+             RETURNING id",
         )
         .bind(&indexer.name)
         .bind(indexer.implementation.to_string())
@@ -142,19 +100,23 @@ impl IndexerRepository for PostgresIndexerRepository {
         .bind(indexer.enable_automatic_search)
         .bind(indexer.enable_interactive_search)
         .bind(indexer.download_client_id)
+        .bind(&indexer.tags)
         .bind(indexer.created_at)
         .bind(indexer.updated_at)
-        .execute(&self.pool)
+        .fetch_one(&self.pool)
         .await?;
 
-        Ok(indexer.clone())
+        let mut created = indexer.clone();
+        created.id = row.try_get("id")?;
+        Ok(created)
     }
 
     async fn update(&self, indexer: &Indexer) -> Result<Indexer> {
         let _result = sqlx::query(
             "UPDATE indexers SET name = $2, implementation = $3, settings = $4, enabled = $5,
              priority = $6, enable_rss = $7, enable_automatic_search = $8,
-             enable_interactive_search = $9, download_client_id = $10, updated_at = $11
+             enable_interactive_search = $9, download_client_id = $10, tags = $11,
+             updated_at = $12
              WHERE id = $1",
         )
         .bind(indexer.id)
@@ -167,6 +129,7 @@ impl IndexerRepository for PostgresIndexerRepository {
         .bind(indexer.enable_automatic_search)
         .bind(indexer.enable_interactive_search)
         .bind(indexer.download_client_id)
+        .bind(&indexer.tags)
         .bind(indexer.updated_at)
         .execute(&self.pool)
         .await?;
@@ -183,36 +146,14 @@ impl IndexerRepository for PostgresIndexerRepository {
     }
 
     async fn list(&self) -> Result<Vec<Indexer>> {
-        let rows = sqlx::query(
-            "SELECT id, name, implementation, settings, enabled, priority,
-             enable_rss, enable_automatic_search, enable_interactive_search,
-             download_client_id, created_at, updated_at
-             FROM indexers ORDER BY priority ASC, name ASC",
-        )
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM indexers ORDER BY priority ASC, name ASC",
+            INDEXER_COLUMNS
+        ))
         .fetch_all(&self.pool)
         .await?;
 
-        let mut indexers = Vec::new();
-        for row in rows {
-            let indexer = Indexer {
-                id: row.try_get("id")?,
-                name: row.try_get("name")?,
-                implementation: parse_indexer_implementation(
-                    &row.try_get::<String, _>("implementation")?,
-                )?,
-                settings: row.try_get("settings")?,
-                enabled: row.try_get("enabled")?,
-                priority: row.try_get("priority")?,
-                enable_rss: row.try_get("enable_rss")?,
-                enable_automatic_search: row.try_get("enable_automatic_search")?,
-                enable_interactive_search: row.try_get("enable_interactive_search")?,
-                download_client_id: row.try_get("download_client_id")?,
-                created_at: row.try_get("created_at")?,
-                updated_at: row.try_get("updated_at")?,
-            };
-            indexers.push(indexer);
-        }
-        Ok(indexers)
+        rows.iter().map(Self::row_to_indexer).collect()
     }
 
     async fn test_connection(&self, id: i32) -> Result<bool> {
@@ -243,6 +184,7 @@ fn parse_indexer_implementation(implementation_str: &str) -> Result<IndexerImple
         "jackett" => Ok(IndexerImplementation::Jackett),
         "torznab" => Ok(IndexerImplementation::Torznab),
         "newznab" => Ok(IndexerImplementation::Newznab),
+        "hdbits" => Ok(IndexerImplementation::HDBits),
         _ => Err(radarr_core::RadarrError::ValidationError {
             field: "implementation".to_string(),
             message: format!("Invalid indexer implementation: {}", implementation_str),
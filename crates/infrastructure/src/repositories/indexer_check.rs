@@ -0,0 +1,97 @@
+//! PostgreSQL implementation of IndexerCheckRepository
+
+use crate::database::DatabasePool;
+use async_trait::async_trait;
+use radarr_core::{
+    domain::repositories::IndexerCheckRepository,
+    models::{IndexerCheck, IndexerCheckStatus},
+    RadarrError, Result,
+};
+use sqlx::Row;
+
+const INDEXER_CHECK_COLUMNS: &str = "indexer_id, status, message, movie_search, search_params,
+     canary_query, canary_results, parseable_results, sample_titles, duration_ms, checked_at";
+
+/// PostgreSQL implementation of IndexerCheckRepository
+pub struct PostgresIndexerCheckRepository {
+    pool: DatabasePool,
+}
+
+impl PostgresIndexerCheckRepository {
+    /// Create a new PostgreSQL indexer check repository
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert database row to IndexerCheck
+    fn row_to_check(row: &sqlx::postgres::PgRow) -> Result<IndexerCheck> {
+        let status: String = row.try_get("status")?;
+
+        Ok(IndexerCheck {
+            indexer_id: row.try_get("indexer_id")?,
+            status: IndexerCheckStatus::parse(&status).ok_or_else(|| {
+                RadarrError::DatabaseError {
+                    message: format!("Invalid indexer check status: {}", status),
+                }
+            })?,
+            message: row.try_get("message")?,
+            movie_search: row.try_get("movie_search")?,
+            search_params: serde_json::from_value(row.try_get("search_params")?)?,
+            canary_query: row.try_get("canary_query")?,
+            canary_results: row.try_get("canary_results")?,
+            parseable_results: row.try_get("parseable_results")?,
+            sample_titles: serde_json::from_value(row.try_get("sample_titles")?)?,
+            duration_ms: row.try_get("duration_ms")?,
+            checked_at: row.try_get("checked_at")?,
+        })
+    }
+}
+
+#[async_trait]
+impl IndexerCheckRepository for PostgresIndexerCheckRepository {
+    async fn upsert(&self, check: &IndexerCheck) -> Result<()> {
+        sqlx::query(&format!(
+            "INSERT INTO indexer_checks ({})
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+             ON CONFLICT (indexer_id) DO UPDATE SET
+                status = EXCLUDED.status,
+                message = EXCLUDED.message,
+                movie_search = EXCLUDED.movie_search,
+                search_params = EXCLUDED.search_params,
+                canary_query = EXCLUDED.canary_query,
+                canary_results = EXCLUDED.canary_results,
+                parseable_results = EXCLUDED.parseable_results,
+                sample_titles = EXCLUDED.sample_titles,
+                duration_ms = EXCLUDED.duration_ms,
+                checked_at = EXCLUDED.checked_at",
+            INDEXER_CHECK_COLUMNS
+        ))
+        .bind(check.indexer_id)
+        .bind(check.status.as_str())
+        .bind(&check.message)
+        .bind(check.movie_search)
+        .bind(serde_json::to_value(&check.search_params)?)
+        .bind(&check.canary_query)
+        .bind(check.canary_results)
+        .bind(check.parseable_results)
+        .bind(serde_json::to_value(&check.sample_titles)?)
+        .bind(check.duration_ms)
+        .bind(check.checked_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_by_indexer_id(&self, indexer_id: i32) -> Result<Option<IndexerCheck>> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM indexer_checks WHERE indexer_id = $1",
+            INDEXER_CHECK_COLUMNS
+        ))
+        .bind(indexer_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.as_ref().map(Self::row_to_check).transpose()
+    }
+}
@@ -0,0 +1,129 @@
+//! PostgreSQL implementation of ListExclusionRepository
+
+use crate::database::DatabasePool;
+use async_trait::async_trait;
+use radarr_core::{
+    domain::repositories::ListExclusionRepository, models::ListExclusion, RadarrError, Result,
+};
+use sqlx::{postgres::PgArguments, query::Query, Postgres, Row};
+use uuid::Uuid;
+
+/// Columns selected for a ListExclusion
+const COLUMNS: &str = "id, tmdb_id, imdb_id, title, year, reason, excluded_by, created_at";
+
+/// Insert statement for `exclusion`; an existing exclusion is kept
+pub(crate) fn insert_query(exclusion: &ListExclusion) -> Query<'_, Postgres, PgArguments> {
+    sqlx::query(
+        "INSERT INTO list_exclusions (id, tmdb_id, imdb_id, title, year, reason,
+         excluded_by, created_at)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+         ON CONFLICT DO NOTHING",
+    )
+    .bind(exclusion.id)
+    .bind(exclusion.tmdb_id)
+    .bind(&exclusion.imdb_id)
+    .bind(&exclusion.title)
+    .bind(exclusion.year)
+    .bind(&exclusion.reason)
+    .bind(&exclusion.excluded_by)
+    .bind(exclusion.created_at)
+}
+
+/// PostgreSQL implementation of ListExclusionRepository
+pub struct PostgresListExclusionRepository {
+    pool: DatabasePool,
+}
+
+impl PostgresListExclusionRepository {
+    /// Create a new PostgreSQL list exclusion repository
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert database row to ListExclusion
+    fn row_to_exclusion(row: &sqlx::postgres::PgRow) -> Result<ListExclusion> {
+        Ok(ListExclusion {
+            id: row.try_get("id")?,
+            tmdb_id: row.try_get("tmdb_id")?,
+            imdb_id: row.try_get("imdb_id")?,
+            title: row.try_get("title")?,
+            year: row.try_get("year")?,
+            reason: row.try_get("reason")?,
+            excluded_by: row.try_get("excluded_by")?,
+            created_at: row
+                .try_get::<Option<chrono::DateTime<chrono::Utc>>, _>("created_at")?
+                .unwrap_or_else(chrono::Utc::now),
+        })
+    }
+}
+
+#[async_trait]
+impl ListExclusionRepository for PostgresListExclusionRepository {
+    async fn create(&self, exclusion: &ListExclusion) -> Result<()> {
+        insert_query(exclusion).execute(&self.pool).await?;
+
+        Ok(())
+    }
+
+    async fn find_by_tmdb_id(&self, tmdb_id: i32) -> Result<Option<ListExclusion>> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM list_exclusions WHERE tmdb_id = $1",
+            COLUMNS
+        ))
+        .bind(tmdb_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.as_ref().map(Self::row_to_exclusion).transpose()
+    }
+
+    async fn find_by_imdb_id(&self, imdb_id: &str) -> Result<Option<ListExclusion>> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM list_exclusions WHERE imdb_id = $1",
+            COLUMNS
+        ))
+        .bind(imdb_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.as_ref().map(Self::row_to_exclusion).transpose()
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<ListExclusion>> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM list_exclusions WHERE id = $1",
+            COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.as_ref().map(Self::row_to_exclusion).transpose()
+    }
+
+    async fn list(&self) -> Result<Vec<ListExclusion>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM list_exclusions ORDER BY created_at DESC",
+            COLUMNS
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(Self::row_to_exclusion).collect()
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<()> {
+        let result = sqlx::query("DELETE FROM list_exclusions WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(RadarrError::NotFound {
+                resource: format!("list exclusion {}", id),
+            });
+        }
+
+        Ok(())
+    }
+}
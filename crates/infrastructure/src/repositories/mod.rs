@@ -3,24 +3,54 @@
 //! This module contains PostgreSQL implementations of all repository traits
 //! defined in the core domain layer.
 
+pub mod api_key;
+pub mod audit;
+pub mod collection;
+pub mod command;
+pub mod config;
 pub mod custom_formats;
 pub mod download;
+pub mod history;
+pub mod import_list;
+pub mod import_lock;
 pub mod indexer;
+pub mod indexer_check;
+pub mod list_exclusion;
 pub mod movie;
+pub mod movie_file;
+pub mod notification;
 pub mod quality_profile;
 pub mod queue;
+pub mod root_folder;
+pub mod share_token;
 pub mod streaming_cache;
+pub mod tag;
 // pub mod list_sync; // Temporarily disabled - has SQLX type issues
 pub mod blocklist;
 
 // Re-export all repository implementations
+pub use api_key::PostgresApiKeyRepository;
+pub use audit::PostgresAuditRepository;
+pub use collection::PostgresCollectionRepository;
+pub use command::PostgresCommandRepository;
+pub use config::PostgresConfigRepository;
 pub use custom_formats::{CustomFormatsRepository, PostgresCustomFormatsRepository};
 pub use download::PostgresDownloadRepository;
+pub use history::PostgresHistoryRepository;
+pub use import_list::PostgresImportListRepository;
+pub use import_lock::PostgresImportLockRepository;
 pub use indexer::PostgresIndexerRepository;
+pub use indexer_check::PostgresIndexerCheckRepository;
+pub use list_exclusion::PostgresListExclusionRepository;
 pub use movie::PostgresMovieRepository;
+pub use movie_file::PostgresMovieFileRepository;
+pub use notification::PostgresNotificationRepository;
 pub use quality_profile::PostgresQualityProfileRepository;
 pub use queue::PostgresQueueRepository;
+pub use root_folder::PostgresRootFolderRepository;
+pub use share_token::PostgresShareTokenRepository;
 pub use streaming_cache::PostgresStreamingCache;
+pub use tag::PostgresTagRepository;
 // pub use list_sync::PostgresListSyncRepository; // Temporarily disabled
 pub use blocklist::PostgresBlocklistRepository;
 
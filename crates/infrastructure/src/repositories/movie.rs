@@ -2,30 +2,92 @@
 //! Uses JSONB fields for metadata and full-text search capabilities
 
 use crate::database::DatabasePool;
+use crate::read_replica::ReadPool;
+use crate::repositories::list_exclusion;
 use async_trait::async_trait;
 use radarr_core::{
     domain::repositories::MovieRepository,
-    models::{MinimumAvailability, Movie, MovieStatus},
+    models::{normalize_search_title, ListExclusion, MinimumAvailability, Movie, MovieStatus},
     Result,
 };
-use sqlx::Row;
+use sqlx::{postgres::PgArguments, query::Query, Postgres, Row};
 use uuid::Uuid;
 
 /// Standard movie columns for SELECT queries
 const MOVIE_COLUMNS: &str = "id, tmdb_id, imdb_id, title, original_title, year, runtime,
                              status, monitored, quality_profile_id, minimum_availability,
                              has_file, movie_file_id, metadata, alternative_titles,
-                             created_at, updated_at, last_search_time, last_info_sync";
+                             created_at, updated_at, last_search_time, last_info_sync,
+                             failed_search_count, excluded_indexer_ids, ratings, tags";
+
+/// Columns filled by batch inserts
+///
+/// UNNEST cannot produce array columns, so batch-created movies start
+/// with the column defaults for those.
+const MOVIE_BATCH_COLUMNS: &str = "id, tmdb_id, imdb_id, title, original_title, year, runtime,
+                                   status, monitored, quality_profile_id, minimum_availability,
+                                   has_file, movie_file_id, metadata, alternative_titles,
+                                   created_at, updated_at, last_search_time, last_info_sync,
+                                   failed_search_count, ratings";
+
+/// Statement writing every column of a movie but its ID and creation time
+const UPDATE_MOVIE_SQL: &str = "UPDATE movies SET tmdb_id = $2, imdb_id = $3, title = $4,
+             original_title = $5, year = $6, runtime = $7, status = $8, monitored = $9,
+             quality_profile_id = $10, minimum_availability = $11,
+             has_file = $12, movie_file_id = $13, metadata = $14,
+             alternative_titles = $15, updated_at = $16,
+             last_search_time = $17, last_info_sync = $18,
+             failed_search_count = $19, excluded_indexer_ids = $20, ratings = $21,
+             tags = $22
+             WHERE id = $1";
+
+/// Update statement for `movie`, bound to its values
+fn update_query(movie: &Movie) -> Result<Query<'_, Postgres, PgArguments>> {
+    Ok(sqlx::query(UPDATE_MOVIE_SQL)
+        .bind(movie.id)
+        .bind(movie.tmdb_id)
+        .bind(&movie.imdb_id)
+        .bind(&movie.title)
+        .bind(&movie.original_title)
+        .bind(movie.year)
+        .bind(movie.runtime)
+        .bind(movie.status.to_string())
+        .bind(movie.monitored)
+        .bind(movie.quality_profile_id)
+        .bind(movie.minimum_availability.to_string())
+        .bind(movie.has_file)
+        .bind(movie.movie_file_id)
+        .bind(&movie.metadata)
+        .bind(&movie.alternative_titles)
+        .bind(movie.updated_at)
+        .bind(movie.last_search_time)
+        .bind(movie.last_info_sync)
+        .bind(movie.failed_search_count)
+        .bind(&movie.excluded_indexer_ids)
+        .bind(serde_json::to_value(&movie.ratings)?)
+        .bind(&movie.tags))
+}
 
 /// PostgreSQL implementation of MovieRepository
 pub struct PostgresMovieRepository {
     pool: DatabasePool,
+    /// Library listing and counts, which may be served by a read replica
+    reads: ReadPool,
 }
 
 impl PostgresMovieRepository {
     /// Create a new PostgreSQL movie repository
     pub fn new(pool: DatabasePool) -> Self {
-        Self { pool }
+        Self {
+            reads: ReadPool::primary_only(pool.clone()),
+            pool,
+        }
+    }
+
+    /// Serve the library listing, counts and title search from `reads`
+    pub fn with_read_pool(mut self, reads: ReadPool) -> Self {
+        self.reads = reads;
+        self
     }
 
     /// Helper function to parse a row into a Movie
@@ -52,6 +114,10 @@ impl PostgresMovieRepository {
             updated_at: row.try_get("updated_at")?,
             last_search_time: row.try_get("last_search_time")?,
             last_info_sync: row.try_get("last_info_sync")?,
+            failed_search_count: row.try_get("failed_search_count")?,
+            excluded_indexer_ids: row.try_get("excluded_indexer_ids")?,
+            ratings: serde_json::from_value(row.try_get("ratings")?)?,
+            tags: row.try_get("tags")?,
         })
     }
 
@@ -82,6 +148,8 @@ impl PostgresMovieRepository {
         let mut updated_ats = Vec::new();
         let mut last_search_times = Vec::new();
         let mut last_info_syncs = Vec::new();
+        let mut failed_search_counts = Vec::new();
+        let mut ratings_list = Vec::new();
 
         for movie in movies {
             ids.push(movie.id);
@@ -103,6 +171,8 @@ impl PostgresMovieRepository {
             updated_ats.push(movie.updated_at);
             last_search_times.push(movie.last_search_time);
             last_info_syncs.push(movie.last_info_sync);
+            failed_search_counts.push(movie.failed_search_count);
+            ratings_list.push(serde_json::to_value(&movie.ratings)?);
         }
 
         sqlx::query(&format!(
@@ -110,7 +180,8 @@ impl PostgresMovieRepository {
              SELECT * FROM UNNEST($1::uuid[], $2::int[], $3::text[], $4::text[], $5::text[], 
                                   $6::int[], $7::int[], $8::text[], $9::boolean[], $10::int[], 
                                   $11::text[], $12::boolean[], $13::uuid[], $14::jsonb[], $15::jsonb[],
-                                  $16::timestamptz[], $17::timestamptz[], $18::timestamptz[], $19::timestamptz[])
+                                  $16::timestamptz[], $17::timestamptz[], $18::timestamptz[], $19::timestamptz[],
+                                  $20::int[], $21::jsonb[])
              ON CONFLICT (tmdb_id) DO UPDATE SET 
                 title = EXCLUDED.title,
                 original_title = EXCLUDED.original_title,
@@ -120,8 +191,9 @@ impl PostgresMovieRepository {
                 minimum_availability = EXCLUDED.minimum_availability,
                 metadata = EXCLUDED.metadata,
                 alternative_titles = EXCLUDED.alternative_titles,
+                ratings = EXCLUDED.ratings,
                 updated_at = EXCLUDED.updated_at", 
-            MOVIE_COLUMNS.replace(", ", ", ")
+            MOVIE_BATCH_COLUMNS
         ))
         .bind(&ids)
         .bind(&tmdb_ids)
@@ -142,12 +214,45 @@ impl PostgresMovieRepository {
         .bind(&updated_ats)
         .bind(&last_search_times)
         .bind(&last_info_syncs)
+        .bind(&failed_search_counts)
+        .bind(&ratings_list)
         .execute(&self.pool)
         .await?;
 
         Ok(movies.to_vec())
     }
 
+    /// Save several edited movies in one transaction
+    ///
+    /// Either every movie is updated or, when one update fails, none is.
+    pub async fn update_batch(&self, movies: &[Movie]) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        for movie in movies {
+            update_query(movie)?.execute(&mut *tx).await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Delete several movies at once, returning how many existed
+    ///
+    /// `exclusions` are added in the same transaction: either the movies are
+    /// deleted and excluded, or nothing changes.
+    pub async fn delete_batch(&self, ids: &[Uuid], exclusions: &[ListExclusion]) -> Result<u64> {
+        let mut tx = self.pool.begin().await?;
+        for exclusion in exclusions {
+            list_exclusion::insert_query(exclusion)
+                .execute(&mut *tx)
+                .await?;
+        }
+        let result = sqlx::query("DELETE FROM movies WHERE id = ANY($1)")
+            .bind(ids)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(result.rows_affected())
+    }
+
     /// Find movies by metadata field using JSONB operators
     pub async fn find_by_metadata_field(
         &self,
@@ -171,6 +276,63 @@ impl PostgresMovieRepository {
         }
         Ok(movies)
     }
+
+    /// Find movies without a file that are monitored, or unmonitored if
+    /// `monitored` is false
+    pub async fn find_missing(&self, monitored: bool) -> Result<Vec<Movie>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM movies WHERE has_file = false AND monitored = $1 ORDER BY title ASC",
+            MOVIE_COLUMNS
+        ))
+        .bind(monitored)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut movies = Vec::new();
+        for row in rows {
+            movies.push(Self::parse_movie_from_row(&row)?);
+        }
+        Ok(movies)
+    }
+
+    /// Find movies with a cinema, digital or physical release from `start`
+    /// to `end`, both inclusive; only monitored ones unless
+    /// `include_unmonitored`
+    pub async fn find_releasing_between(
+        &self,
+        start: chrono::NaiveDate,
+        end: chrono::NaiveDate,
+        include_unmonitored: bool,
+    ) -> Result<Vec<Movie>> {
+        // TMDB leaves unknown dates empty, so only well-formed ones are cast
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM movies
+             WHERE ($3 OR monitored = true)
+               AND EXISTS (
+                   SELECT 1
+                   FROM unnest(ARRAY[
+                       metadata #>> '{{tmdb,release_date}}',
+                       metadata #>> '{{tmdb,digital_release_date}}',
+                       metadata #>> '{{tmdb,physical_release_date}}'
+                   ]) AS release(date)
+                   WHERE CASE WHEN release.date ~ '^\\d{{4}}-\\d{{2}}-\\d{{2}}$'
+                              THEN release.date::date END BETWEEN $1 AND $2
+               )
+             ORDER BY title ASC",
+            MOVIE_COLUMNS
+        ))
+        .bind(start)
+        .bind(end)
+        .bind(include_unmonitored)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut movies = Vec::new();
+        for row in rows {
+            movies.push(Self::parse_movie_from_row(&row)?);
+        }
+        Ok(movies)
+    }
 }
 
 #[async_trait]
@@ -250,32 +412,12 @@ impl MovieRepository for PostgresMovieRepository {
         Ok(movies)
     }
 
-    async fn search_by_title(&self, query: &str, limit: i32) -> Result<Vec<Movie>> {
-        let search_query = format!("%{}%", query);
-        // PostgreSQL optimized search using ILIKE and JSONB operators
-        // This query prioritizes exact title matches, then uses JSONB array search for alternative titles
+    async fn find_added_since(&self, since: chrono::DateTime<chrono::Utc>) -> Result<Vec<Movie>> {
         let rows = sqlx::query(&format!(
-            "SELECT {} FROM movies 
-             WHERE title ILIKE $1 
-                OR original_title ILIKE $1 
-                OR EXISTS (
-                    SELECT 1 FROM jsonb_array_elements_text(alternative_titles) AS alt_title
-                    WHERE alt_title ILIKE $1
-                )
-             ORDER BY 
-                CASE 
-                    WHEN LOWER(title) = LOWER($2) THEN 1
-                    WHEN title ILIKE $1 THEN 2
-                    WHEN original_title ILIKE $1 THEN 3
-                    ELSE 4 
-                END,
-                ts_rank_cd(to_tsvector('english', title || ' ' || COALESCE(original_title, '')), plainto_tsquery('english', $2)) DESC,
-                title ASC
-             LIMIT $3", MOVIE_COLUMNS
+            "SELECT {} FROM movies WHERE created_at >= $1 ORDER BY created_at ASC",
+            MOVIE_COLUMNS
         ))
-        .bind(&search_query)
-        .bind(query.trim())
-        .bind(limit)
+        .bind(since)
         .fetch_all(&self.pool)
         .await?;
 
@@ -286,13 +428,55 @@ impl MovieRepository for PostgresMovieRepository {
         Ok(movies)
     }
 
+    async fn search_by_title(&self, query: &str, limit: i32) -> Result<Vec<Movie>> {
+        // search_text holds the normalized title, original title and
+        // alternative titles. Substring and fuzzy (word similarity) matches
+        // use its trigram index, word matches its full-text index. Exact
+        // matches of the title or of one of the movie_alt_titles rank first,
+        // then the closest fuzzy matches.
+        let search_query = normalize_search_title(query);
+        let sql = format!(
+            "SELECT {} FROM movies
+             WHERE search_text LIKE '%' || $1 || '%'
+                OR $1 <% search_text
+                OR to_tsvector('simple', search_text) @@ plainto_tsquery('simple', $1)
+             ORDER BY
+                CASE WHEN movie_search_text(title, NULL, NULL) = $1
+                       OR EXISTS (SELECT 1 FROM movie_alt_titles alt
+                                  WHERE alt.movie_id = movies.id AND alt.clean_title = $1)
+                     THEN 0 ELSE 1 END,
+                word_similarity($1, search_text) DESC,
+                title ASC
+             LIMIT $2",
+            MOVIE_COLUMNS
+        );
+        let (sql, search_query) = (&sql, &search_query);
+        let rows = self
+            .reads
+            .read(|pool| async move {
+                sqlx::query(sql)
+                    .bind(search_query)
+                    .bind(limit)
+                    .fetch_all(&pool)
+                    .await
+            })
+            .await?;
+
+        let mut movies = Vec::new();
+        for row in rows {
+            movies.push(Self::parse_movie_from_row(&row)?);
+        }
+        Ok(movies)
+    }
+
     async fn create(&self, movie: &Movie) -> Result<Movie> {
         let _result = sqlx::query(
             "INSERT INTO movies (id, tmdb_id, imdb_id, title, original_title, year, runtime,
              status, monitored, quality_profile_id, minimum_availability,
              has_file, movie_file_id, metadata, alternative_titles,
-             created_at, updated_at, last_search_time, last_info_sync)
-             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19)"
+             created_at, updated_at, last_search_time, last_info_sync, failed_search_count,
+             excluded_indexer_ids, ratings, tags)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23)"
         )
         .bind(movie.id)
         .bind(movie.tmdb_id)
@@ -313,6 +497,10 @@ impl MovieRepository for PostgresMovieRepository {
         .bind(movie.updated_at)
         .bind(movie.last_search_time)
         .bind(movie.last_info_sync)
+        .bind(movie.failed_search_count)
+        .bind(&movie.excluded_indexer_ids)
+        .bind(serde_json::to_value(&movie.ratings)?)
+        .bind(&movie.tags)
         .execute(&self.pool)
         .await?;
 
@@ -320,36 +508,7 @@ impl MovieRepository for PostgresMovieRepository {
     }
 
     async fn update(&self, movie: &Movie) -> Result<Movie> {
-        let _result = sqlx::query(
-            "UPDATE movies SET tmdb_id = $2, imdb_id = $3, title = $4, original_title = $5,
-             year = $6, runtime = $7, status = $8, monitored = $9,
-             quality_profile_id = $10, minimum_availability = $11,
-             has_file = $12, movie_file_id = $13, metadata = $14,
-             alternative_titles = $15, updated_at = $16,
-             last_search_time = $17, last_info_sync = $18
-             WHERE id = $1",
-        )
-        .bind(movie.id)
-        .bind(movie.tmdb_id)
-        .bind(&movie.imdb_id)
-        .bind(&movie.title)
-        .bind(&movie.original_title)
-        .bind(movie.year)
-        .bind(movie.runtime)
-        .bind(movie.status.to_string())
-        .bind(movie.monitored)
-        .bind(movie.quality_profile_id)
-        .bind(movie.minimum_availability.to_string())
-        .bind(movie.has_file)
-        .bind(movie.movie_file_id)
-        .bind(&movie.metadata)
-        .bind(&movie.alternative_titles)
-        .bind(movie.updated_at)
-        .bind(movie.last_search_time)
-        .bind(movie.last_info_sync)
-        .execute(&self.pool)
-        .await?;
-
+        update_query(movie)?.execute(&self.pool).await?;
         Ok(movie.clone())
     }
 
@@ -362,16 +521,22 @@ impl MovieRepository for PostgresMovieRepository {
     }
 
     async fn list(&self, offset: i64, limit: i32) -> Result<Vec<Movie>> {
-        let rows = sqlx::query(&format!(
+        let sql = &format!(
             "SELECT {} FROM movies 
              ORDER BY title ASC
              LIMIT $1 OFFSET $2",
             MOVIE_COLUMNS
-        ))
-        .bind(limit)
-        .bind(offset)
-        .fetch_all(&self.pool)
-        .await?;
+        );
+        let rows = self
+            .reads
+            .read(|pool| async move {
+                sqlx::query(sql)
+                    .bind(limit)
+                    .bind(offset)
+                    .fetch_all(&pool)
+                    .await
+            })
+            .await?;
 
         let mut movies = Vec::new();
         for row in rows {
@@ -381,8 +546,13 @@ impl MovieRepository for PostgresMovieRepository {
     }
 
     async fn count(&self) -> Result<i64> {
-        let row = sqlx::query("SELECT COUNT(*) as count FROM movies")
-            .fetch_one(&self.pool)
+        let row = self
+            .reads
+            .read(|pool| async move {
+                sqlx::query("SELECT COUNT(*) as count FROM movies")
+                    .fetch_one(&pool)
+                    .await
+            })
             .await?;
 
         Ok(row.try_get::<i64, _>("count").unwrap_or(0))
@@ -395,6 +565,19 @@ impl MovieRepository for PostgresMovieRepository {
             .await?;
         Ok(())
     }
+
+    async fn record_search_result(&self, id: Uuid, found: bool) -> Result<()> {
+        sqlx::query(
+            "UPDATE movies SET last_search_time = NOW(), updated_at = NOW(),
+             failed_search_count = CASE WHEN $2 THEN 0 ELSE failed_search_count + 1 END
+             WHERE id = $1",
+        )
+        .bind(id)
+        .bind(found)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
 }
 
 fn parse_movie_status(status_str: &str) -> Result<MovieStatus> {
@@ -411,7 +594,7 @@ fn parse_movie_status(status_str: &str) -> Result<MovieStatus> {
     }
 }
 
-fn parse_minimum_availability(availability_str: &str) -> Result<MinimumAvailability> {
+pub(crate) fn parse_minimum_availability(availability_str: &str) -> Result<MinimumAvailability> {
     match availability_str {
         "announced" => Ok(MinimumAvailability::Announced),
         "in_cinemas" => Ok(MinimumAvailability::InCinemas),
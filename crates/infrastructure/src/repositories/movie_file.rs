@@ -0,0 +1,147 @@
+//! PostgreSQL implementation of MovieFileRepository
+
+use crate::database::DatabasePool;
+use async_trait::async_trait;
+use radarr_core::{domain::repositories::MovieFileRepository, models::MovieFile, Result};
+use sqlx::Row;
+use uuid::Uuid;
+
+const MOVIE_FILE_COLUMNS: &str = "id, movie_id, relative_path, size_bytes, quality, media_info,
+     custom_format_score, edition, date_added, last_write_time, checksum, created_at, updated_at";
+
+/// PostgreSQL implementation of MovieFileRepository
+pub struct PostgresMovieFileRepository {
+    pool: DatabasePool,
+}
+
+impl PostgresMovieFileRepository {
+    /// Create a new PostgreSQL movie file repository
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert database row to MovieFile
+    fn row_to_movie_file(row: &sqlx::postgres::PgRow) -> Result<MovieFile> {
+        Ok(MovieFile {
+            id: row.try_get("id")?,
+            movie_id: row.try_get("movie_id")?,
+            relative_path: row.try_get("relative_path")?,
+            size_bytes: row.try_get("size_bytes")?,
+            quality: row.try_get("quality")?,
+            media_info: row.try_get("media_info")?,
+            custom_format_score: row.try_get("custom_format_score")?,
+            edition: row.try_get("edition")?,
+            date_added: row.try_get("date_added")?,
+            last_write_time: row.try_get("last_write_time")?,
+            checksum: row.try_get("checksum")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+        })
+    }
+}
+
+#[async_trait]
+impl MovieFileRepository for PostgresMovieFileRepository {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<MovieFile>> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM movie_files WHERE id = $1",
+            MOVIE_FILE_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.as_ref().map(Self::row_to_movie_file).transpose()
+    }
+
+    async fn find_by_movie_id(&self, movie_id: Uuid) -> Result<Option<MovieFile>> {
+        // Prefer the file the movie points at, then the most recently added one
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM movie_files f
+             WHERE f.movie_id = $1
+             ORDER BY (f.id = (SELECT movie_file_id FROM movies WHERE id = $1)) DESC NULLS LAST,
+                      f.date_added DESC
+             LIMIT 1",
+            MOVIE_FILE_COLUMNS
+        ))
+        .bind(movie_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.as_ref().map(Self::row_to_movie_file).transpose()
+    }
+
+    async fn list(&self, movie_id: Option<Uuid>) -> Result<Vec<MovieFile>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM movie_files
+             WHERE ($1::uuid IS NULL OR movie_id = $1)
+             ORDER BY date_added DESC, id",
+            MOVIE_FILE_COLUMNS
+        ))
+        .bind(movie_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(Self::row_to_movie_file).collect()
+    }
+
+    async fn create(&self, file: &MovieFile) -> Result<MovieFile> {
+        sqlx::query(
+            "INSERT INTO movie_files (id, movie_id, relative_path, size_bytes, quality,
+             media_info, custom_format_score, edition, date_added, last_write_time, checksum,
+             created_at, updated_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)",
+        )
+        .bind(file.id)
+        .bind(file.movie_id)
+        .bind(&file.relative_path)
+        .bind(file.size_bytes)
+        .bind(&file.quality)
+        .bind(&file.media_info)
+        .bind(file.custom_format_score)
+        .bind(&file.edition)
+        .bind(file.date_added)
+        .bind(file.last_write_time)
+        .bind(&file.checksum)
+        .bind(file.created_at)
+        .bind(file.updated_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(file.clone())
+    }
+
+    async fn update(&self, file: &MovieFile) -> Result<MovieFile> {
+        let mut updated = file.clone();
+        updated.updated_at = chrono::Utc::now();
+
+        sqlx::query(
+            "UPDATE movie_files SET relative_path = $2, size_bytes = $3, quality = $4,
+             media_info = $5, custom_format_score = $6, last_write_time = $7, checksum = $8,
+             updated_at = $9, edition = $10
+             WHERE id = $1",
+        )
+        .bind(updated.id)
+        .bind(&updated.relative_path)
+        .bind(updated.size_bytes)
+        .bind(&updated.quality)
+        .bind(&updated.media_info)
+        .bind(updated.custom_format_score)
+        .bind(updated.last_write_time)
+        .bind(&updated.checksum)
+        .bind(updated.updated_at)
+        .bind(&updated.edition)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(updated)
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<()> {
+        sqlx::query("DELETE FROM movie_files WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,146 @@
+//! PostgreSQL implementation of NotificationRepository
+
+use crate::database::DatabasePool;
+use async_trait::async_trait;
+use radarr_core::{
+    domain::repositories::NotificationRepository,
+    models::{NotificationDefinition, NotificationImplementation},
+    RadarrError, Result,
+};
+use sqlx::Row;
+
+const NOTIFICATION_COLUMNS: &str = "id, name, implementation, settings, on_grab, on_import,
+     on_upgrade, on_health_issue, on_application_update, tags, created_at, updated_at";
+
+/// PostgreSQL implementation of NotificationRepository
+pub struct PostgresNotificationRepository {
+    pool: DatabasePool,
+}
+
+impl PostgresNotificationRepository {
+    /// Create a new PostgreSQL notification repository
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert database row to NotificationDefinition
+    fn row_to_definition(row: &sqlx::postgres::PgRow) -> Result<NotificationDefinition> {
+        let implementation: String = row.try_get("implementation")?;
+
+        Ok(NotificationDefinition {
+            id: row.try_get("id")?,
+            name: row.try_get("name")?,
+            implementation: NotificationImplementation::parse(&implementation).ok_or_else(
+                || RadarrError::DatabaseError {
+                    message: format!("Invalid notification implementation: {}", implementation),
+                },
+            )?,
+            settings: row.try_get("settings")?,
+            on_grab: row.try_get("on_grab")?,
+            on_import: row.try_get("on_import")?,
+            on_upgrade: row.try_get("on_upgrade")?,
+            on_health_issue: row.try_get("on_health_issue")?,
+            on_application_update: row.try_get("on_application_update")?,
+            tags: row.try_get("tags")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+        })
+    }
+}
+
+#[async_trait]
+impl NotificationRepository for PostgresNotificationRepository {
+    async fn find_by_id(&self, id: i32) -> Result<Option<NotificationDefinition>> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM notifications WHERE id = $1",
+            NOTIFICATION_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.as_ref().map(Self::row_to_definition).transpose()
+    }
+
+    async fn list(&self) -> Result<Vec<NotificationDefinition>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM notifications ORDER BY name",
+            NOTIFICATION_COLUMNS
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(Self::row_to_definition).collect()
+    }
+
+    async fn create(&self, definition: &NotificationDefinition) -> Result<NotificationDefinition> {
+        let row = sqlx::query(&format!(
+            "INSERT INTO notifications (name, implementation, settings, on_grab, on_import,
+             on_upgrade, on_health_issue, on_application_update, tags, created_at, updated_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+             RETURNING {}",
+            NOTIFICATION_COLUMNS
+        ))
+        .bind(&definition.name)
+        .bind(definition.implementation.as_str())
+        .bind(&definition.settings)
+        .bind(definition.on_grab)
+        .bind(definition.on_import)
+        .bind(definition.on_upgrade)
+        .bind(definition.on_health_issue)
+        .bind(definition.on_application_update)
+        .bind(&definition.tags)
+        .bind(definition.created_at)
+        .bind(definition.updated_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Self::row_to_definition(&row)
+    }
+
+    async fn update(&self, definition: &NotificationDefinition) -> Result<NotificationDefinition> {
+        let row = sqlx::query(&format!(
+            "UPDATE notifications SET name = $2, implementation = $3, settings = $4,
+             on_grab = $5, on_import = $6, on_upgrade = $7, on_health_issue = $8,
+             on_application_update = $9, tags = $10, updated_at = $11
+             WHERE id = $1
+             RETURNING {}",
+            NOTIFICATION_COLUMNS
+        ))
+        .bind(definition.id)
+        .bind(&definition.name)
+        .bind(definition.implementation.as_str())
+        .bind(&definition.settings)
+        .bind(definition.on_grab)
+        .bind(definition.on_import)
+        .bind(definition.on_upgrade)
+        .bind(definition.on_health_issue)
+        .bind(definition.on_application_update)
+        .bind(&definition.tags)
+        .bind(definition.updated_at)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match row {
+            Some(row) => Self::row_to_definition(&row),
+            None => Err(RadarrError::NotFound {
+                resource: format!("notification {}", definition.id),
+            }),
+        }
+    }
+
+    async fn delete(&self, id: i32) -> Result<()> {
+        let result = sqlx::query("DELETE FROM notifications WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(RadarrError::NotFound {
+                resource: format!("notification {}", id),
+            });
+        }
+
+        Ok(())
+    }
+}
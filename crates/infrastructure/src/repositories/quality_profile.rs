@@ -22,7 +22,7 @@ impl QualityProfileRepository for PostgresQualityProfileRepository {
     async fn find_by_id(&self, id: i32) -> Result<Option<QualityProfile>> {
         let row = sqlx::query(
             "SELECT id, name, cutoff_quality_id, upgrade_allowed, items, language,
-             created_at, updated_at FROM quality_profiles WHERE id = $1",
+             min_format_score, cutoff_format_score, created_at, updated_at FROM quality_profiles WHERE id = $1",
         )
         .bind(id)
         .fetch_optional(&self.pool)
@@ -37,6 +37,8 @@ impl QualityProfileRepository for PostgresQualityProfileRepository {
                     upgrade_allowed: row.try_get("upgrade_allowed")?,
                     items: row.try_get("items")?,
                     language: row.try_get("language")?,
+                    min_format_score: row.try_get("min_format_score")?,
+                    cutoff_format_score: row.try_get("cutoff_format_score")?,
                     created_at: row.try_get("created_at")?,
                     updated_at: row.try_get("updated_at")?,
                 };
@@ -49,7 +51,7 @@ impl QualityProfileRepository for PostgresQualityProfileRepository {
     async fn find_by_name(&self, name: &str) -> Result<Option<QualityProfile>> {
         let row = sqlx::query(
             "SELECT id, name, cutoff_quality_id, upgrade_allowed, items, language,
-             created_at, updated_at FROM quality_profiles WHERE name = $1",
+             min_format_score, cutoff_format_score, created_at, updated_at FROM quality_profiles WHERE name = $1",
         )
         .bind(name)
         .fetch_optional(&self.pool)
@@ -64,6 +66,8 @@ impl QualityProfileRepository for PostgresQualityProfileRepository {
                     upgrade_allowed: row.try_get("upgrade_allowed")?,
                     items: row.try_get("items")?,
                     language: row.try_get("language")?,
+                    min_format_score: row.try_get("min_format_score")?,
+                    cutoff_format_score: row.try_get("cutoff_format_score")?,
                     created_at: row.try_get("created_at")?,
                     updated_at: row.try_get("updated_at")?,
                 };
@@ -74,27 +78,33 @@ impl QualityProfileRepository for PostgresQualityProfileRepository {
     }
 
     async fn create(&self, profile: &QualityProfile) -> Result<QualityProfile> {
-        let _result = sqlx::query(
+        let row = sqlx::query(
             "INSERT INTO quality_profiles (name, cutoff_quality_id, upgrade_allowed, items, language,
-             created_at, updated_at) VALUES ($1, $2, $3, $4, $5, $6, $7)"
+             min_format_score, cutoff_format_score, created_at, updated_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) RETURNING id",
         )
         .bind(&profile.name)
         .bind(profile.cutoff_quality_id)
         .bind(profile.upgrade_allowed)
         .bind(&profile.items)
         .bind(&profile.language)
+        .bind(profile.min_format_score)
+        .bind(profile.cutoff_format_score)
         .bind(profile.created_at)
         .bind(profile.updated_at)
-        .execute(&self.pool)
+        .fetch_one(&self.pool)
         .await?;
 
-        Ok(profile.clone())
+        let mut created = profile.clone();
+        created.id = row.try_get("id")?;
+        Ok(created)
     }
 
     async fn update(&self, profile: &QualityProfile) -> Result<QualityProfile> {
         let _result = sqlx::query(
             "UPDATE quality_profiles SET name = $2, cutoff_quality_id = $3, upgrade_allowed = $4,
-             items = $5, language = $6, updated_at = $7 WHERE id = $1",
+             items = $5, language = $6, min_format_score = $7, cutoff_format_score = $8,
+             updated_at = $9 WHERE id = $1",
         )
         .bind(profile.id)
         .bind(&profile.name)
@@ -102,6 +112,8 @@ impl QualityProfileRepository for PostgresQualityProfileRepository {
         .bind(profile.upgrade_allowed)
         .bind(&profile.items)
         .bind(&profile.language)
+        .bind(profile.min_format_score)
+        .bind(profile.cutoff_format_score)
         .bind(profile.updated_at)
         .execute(&self.pool)
         .await?;
@@ -120,7 +132,7 @@ impl QualityProfileRepository for PostgresQualityProfileRepository {
     async fn list(&self) -> Result<Vec<QualityProfile>> {
         let rows = sqlx::query(
             "SELECT id, name, cutoff_quality_id, upgrade_allowed, items, language,
-             created_at, updated_at FROM quality_profiles ORDER BY name ASC",
+             min_format_score, cutoff_format_score, created_at, updated_at FROM quality_profiles ORDER BY name ASC",
         )
         .fetch_all(&self.pool)
         .await?;
@@ -134,6 +146,8 @@ impl QualityProfileRepository for PostgresQualityProfileRepository {
                 upgrade_allowed: row.try_get("upgrade_allowed")?,
                 items: row.try_get("items")?,
                 language: row.try_get("language")?,
+                min_format_score: row.try_get("min_format_score")?,
+                cutoff_format_score: row.try_get("cutoff_format_score")?,
                 created_at: row.try_get("created_at")?,
                 updated_at: row.try_get("updated_at")?,
             };
@@ -146,7 +160,7 @@ impl QualityProfileRepository for PostgresQualityProfileRepository {
         // Look for a profile named 'Default' first, then fall back to the first profile
         let row = sqlx::query(
             "SELECT id, name, cutoff_quality_id, upgrade_allowed, items, language,
-             created_at, updated_at FROM quality_profiles 
+             min_format_score, cutoff_format_score, created_at, updated_at FROM quality_profiles 
              WHERE name ILIKE 'default%' OR name ILIKE '%default%'
              ORDER BY 
                 CASE WHEN LOWER(name) = 'default' THEN 1 ELSE 2 END,
@@ -165,6 +179,8 @@ impl QualityProfileRepository for PostgresQualityProfileRepository {
                     upgrade_allowed: row.try_get("upgrade_allowed")?,
                     items: row.try_get("items")?,
                     language: row.try_get("language")?,
+                    min_format_score: row.try_get("min_format_score")?,
+                    cutoff_format_score: row.try_get("cutoff_format_score")?,
                     created_at: row.try_get("created_at")?,
                     updated_at: row.try_get("updated_at")?,
                 };
@@ -174,7 +190,7 @@ impl QualityProfileRepository for PostgresQualityProfileRepository {
                 // If no default found, return the first profile available
                 let row = sqlx::query(
                     "SELECT id, name, cutoff_quality_id, upgrade_allowed, items, language,
-                     created_at, updated_at FROM quality_profiles ORDER BY id ASC LIMIT 1",
+                     min_format_score, cutoff_format_score, created_at, updated_at FROM quality_profiles ORDER BY id ASC LIMIT 1",
                 )
                 .fetch_optional(&self.pool)
                 .await?;
@@ -188,6 +204,8 @@ impl QualityProfileRepository for PostgresQualityProfileRepository {
                             upgrade_allowed: row.try_get("upgrade_allowed")?,
                             items: row.try_get("items")?,
                             language: row.try_get("language")?,
+                            min_format_score: row.try_get("min_format_score")?,
+                            cutoff_format_score: row.try_get("cutoff_format_score")?,
                             created_at: row.try_get("created_at")?,
                             updated_at: row.try_get("updated_at")?,
                         };
@@ -198,4 +216,12 @@ impl QualityProfileRepository for PostgresQualityProfileRepository {
             }
         }
     }
+
+    async fn count_movies_using(&self, id: i32) -> Result<i64> {
+        let row = sqlx::query("SELECT COUNT(*) AS count FROM movies WHERE quality_profile_id = $1")
+            .bind(id)
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(row.try_get("count")?)
+    }
 }
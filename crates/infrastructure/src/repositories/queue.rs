@@ -24,6 +24,7 @@ impl PostgresQueueRepository {
 
         let status = match status_str.as_str() {
             "queued" => QueueStatus::Queued,
+            "pending" => QueueStatus::Pending,
             "downloading" => QueueStatus::Downloading,
             "completed" => QueueStatus::Completed,
             "failed" => QueueStatus::Failed,
@@ -64,6 +65,7 @@ impl PostgresQueueRepository {
             id: row.try_get("id")?,
             movie_id: row.try_get("movie_id")?,
             release_id: row.try_get("release_id")?,
+            indexer_id: row.try_get("indexer_id")?,
             title: row.try_get("title")?,
             download_url: row.try_get("download_url")?,
             magnet_url: row.try_get("magnet_url")?,
@@ -74,6 +76,8 @@ impl PostgresQueueRepository {
             download_client_id: row.try_get("download_client_id")?,
             download_path: row.try_get("download_path")?,
             category: row.try_get("category")?,
+            root_folder_path: row.try_get("root_folder_path")?,
+            preview_path: row.try_get("preview_path")?,
             downloaded_bytes: row.try_get("downloaded_bytes")?,
             upload_bytes: row.try_get("upload_bytes")?,
             download_speed: row
@@ -106,10 +110,12 @@ impl QueueRepository for PostgresQueueRepository {
                 status, priority, progress, download_client_id, download_path, category,
                 downloaded_bytes, upload_bytes, download_speed, upload_speed, eta_seconds,
                 seeders, leechers, error_message, retry_count, max_retries,
-                created_at, updated_at, started_at, completed_at
+                created_at, updated_at, started_at, completed_at, root_folder_path,
+                preview_path, indexer_id
             ) VALUES (
                 $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13,
-                $14, $15, $16, $17, $18, $19, $20, $21, $22, $23, $24, $25, $26, $27
+                $14, $15, $16, $17, $18, $19, $20, $21, $22, $23, $24, $25, $26, $27, $28,
+                $29, $30
             )
             "#,
         )
@@ -140,6 +146,9 @@ impl QueueRepository for PostgresQueueRepository {
         .bind(item.updated_at)
         .bind(item.started_at)
         .bind(item.completed_at)
+        .bind(&item.root_folder_path)
+        .bind(&item.preview_path)
+        .bind(item.indexer_id)
         .execute(&self.pool)
         .await
         .map_err(|e| RadarrError::DatabaseError {
@@ -230,7 +239,8 @@ impl QueueRepository for PostgresQueueRepository {
                 upload_bytes = $13, download_speed = $14, upload_speed = $15,
                 eta_seconds = $16, seeders = $17, leechers = $18, error_message = $19,
                 retry_count = $20, max_retries = $21, updated_at = $22,
-                started_at = $23, completed_at = $24
+                started_at = $23, completed_at = $24, root_folder_path = $25,
+                preview_path = $26, indexer_id = $27
             WHERE id = $1
             "#,
         )
@@ -258,6 +268,9 @@ impl QueueRepository for PostgresQueueRepository {
         .bind(item.updated_at)
         .bind(item.started_at)
         .bind(item.completed_at)
+        .bind(&item.root_folder_path)
+        .bind(&item.preview_path)
+        .bind(item.indexer_id)
         .execute(&self.pool)
         .await
         .map_err(|e| RadarrError::DatabaseError {
@@ -274,6 +287,91 @@ impl QueueRepository for PostgresQueueRepository {
         Ok(())
     }
 
+    async fn update_queue_items_batch(&self, items: &[QueueItem]) -> Result<usize> {
+        if items.is_empty() {
+            return Ok(0);
+        }
+
+        // One UPDATE for the whole sync cycle, fed by parallel arrays
+        let ids: Vec<Uuid> = items.iter().map(|item| item.id).collect();
+        let statuses: Vec<String> = items.iter().map(|item| item.status.to_string()).collect();
+        let progress: Vec<rust_decimal::Decimal> = items
+            .iter()
+            .map(|item| rust_decimal::Decimal::from_f64_retain(item.progress).unwrap_or_default())
+            .collect();
+        let downloaded_bytes: Vec<Option<i64>> =
+            items.iter().map(|item| item.downloaded_bytes).collect();
+        let upload_bytes: Vec<Option<i64>> = items.iter().map(|item| item.upload_bytes).collect();
+        let download_speeds: Vec<Option<i64>> = items
+            .iter()
+            .map(|item| item.download_speed.map(|v| v as i64))
+            .collect();
+        let upload_speeds: Vec<Option<i64>> = items
+            .iter()
+            .map(|item| item.upload_speed.map(|v| v as i64))
+            .collect();
+        let eta_seconds: Vec<Option<i64>> = items.iter().map(|item| item.eta_seconds).collect();
+        let seeders: Vec<Option<i32>> = items.iter().map(|item| item.seeders).collect();
+        let leechers: Vec<Option<i32>> = items.iter().map(|item| item.leechers).collect();
+        let error_messages: Vec<Option<String>> = items
+            .iter()
+            .map(|item| item.error_message.clone())
+            .collect();
+        let retry_counts: Vec<i32> = items.iter().map(|item| item.retry_count).collect();
+        let updated_at: Vec<chrono::DateTime<chrono::Utc>> =
+            items.iter().map(|item| item.updated_at).collect();
+        let started_at: Vec<Option<chrono::DateTime<chrono::Utc>>> =
+            items.iter().map(|item| item.started_at).collect();
+        let completed_at: Vec<Option<chrono::DateTime<chrono::Utc>>> =
+            items.iter().map(|item| item.completed_at).collect();
+
+        let affected = sqlx::query(
+            r#"
+            UPDATE queue AS q SET
+                status = u.status, progress = u.progress,
+                downloaded_bytes = u.downloaded_bytes, upload_bytes = u.upload_bytes,
+                download_speed = u.download_speed, upload_speed = u.upload_speed,
+                eta_seconds = u.eta_seconds, seeders = u.seeders, leechers = u.leechers,
+                error_message = u.error_message, retry_count = u.retry_count,
+                updated_at = u.updated_at, started_at = u.started_at,
+                completed_at = u.completed_at
+            FROM UNNEST(
+                $1::uuid[], $2::text[], $3::numeric[], $4::bigint[], $5::bigint[],
+                $6::bigint[], $7::bigint[], $8::bigint[], $9::int[], $10::int[],
+                $11::text[], $12::int[], $13::timestamptz[], $14::timestamptz[],
+                $15::timestamptz[]
+            ) AS u(
+                id, status, progress, downloaded_bytes, upload_bytes,
+                download_speed, upload_speed, eta_seconds, seeders, leechers,
+                error_message, retry_count, updated_at, started_at, completed_at
+            )
+            WHERE q.id = u.id
+            "#,
+        )
+        .bind(&ids)
+        .bind(&statuses)
+        .bind(&progress)
+        .bind(&downloaded_bytes)
+        .bind(&upload_bytes)
+        .bind(&download_speeds)
+        .bind(&upload_speeds)
+        .bind(&eta_seconds)
+        .bind(&seeders)
+        .bind(&leechers)
+        .bind(&error_messages)
+        .bind(&retry_counts)
+        .bind(&updated_at)
+        .bind(&started_at)
+        .bind(&completed_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| RadarrError::DatabaseError {
+            message: format!("Failed to batch update queue items: {}", e),
+        })?;
+
+        Ok(affected.rows_affected() as usize)
+    }
+
     async fn delete_queue_item(&self, id: Uuid) -> Result<()> {
         let affected = sqlx::query("DELETE FROM queue WHERE id = $1")
             .bind(id)
@@ -0,0 +1,131 @@
+//! PostgreSQL implementation of RootFolderRepository
+
+use crate::database::DatabasePool;
+use async_trait::async_trait;
+use radarr_core::{
+    domain::repositories::RootFolderRepository, models::RootFolder, RadarrError, Result,
+};
+use sqlx::Row;
+
+const ROOT_FOLDER_COLUMNS: &str =
+    "id, path, default_quality_profile_id, min_free_space_bytes, created_at, updated_at";
+
+/// PostgreSQL implementation of RootFolderRepository
+pub struct PostgresRootFolderRepository {
+    pool: DatabasePool,
+}
+
+impl PostgresRootFolderRepository {
+    /// Create a new PostgreSQL root folder repository
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert database row to RootFolder
+    fn row_to_root_folder(row: &sqlx::postgres::PgRow) -> Result<RootFolder> {
+        Ok(RootFolder {
+            id: row.try_get("id")?,
+            path: row.try_get("path")?,
+            default_quality_profile_id: row.try_get("default_quality_profile_id")?,
+            min_free_space_bytes: row.try_get("min_free_space_bytes")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+        })
+    }
+}
+
+#[async_trait]
+impl RootFolderRepository for PostgresRootFolderRepository {
+    async fn find_by_id(&self, id: i32) -> Result<Option<RootFolder>> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM root_folders WHERE id = $1",
+            ROOT_FOLDER_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.as_ref().map(Self::row_to_root_folder).transpose()
+    }
+
+    async fn find_by_path(&self, path: &str) -> Result<Option<RootFolder>> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM root_folders WHERE path = $1",
+            ROOT_FOLDER_COLUMNS
+        ))
+        .bind(path)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.as_ref().map(Self::row_to_root_folder).transpose()
+    }
+
+    async fn list(&self) -> Result<Vec<RootFolder>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM root_folders ORDER BY path",
+            ROOT_FOLDER_COLUMNS
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(Self::row_to_root_folder).collect()
+    }
+
+    async fn create(&self, root_folder: &RootFolder) -> Result<RootFolder> {
+        let row = sqlx::query(&format!(
+            "INSERT INTO root_folders (path, default_quality_profile_id, min_free_space_bytes,
+             created_at, updated_at)
+             VALUES ($1, $2, $3, $4, $5)
+             RETURNING {}",
+            ROOT_FOLDER_COLUMNS
+        ))
+        .bind(&root_folder.path)
+        .bind(root_folder.default_quality_profile_id)
+        .bind(root_folder.min_free_space_bytes)
+        .bind(root_folder.created_at)
+        .bind(root_folder.updated_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Self::row_to_root_folder(&row)
+    }
+
+    async fn update(&self, root_folder: &RootFolder) -> Result<RootFolder> {
+        let row = sqlx::query(&format!(
+            "UPDATE root_folders SET path = $2, default_quality_profile_id = $3,
+             min_free_space_bytes = $4, updated_at = $5
+             WHERE id = $1
+             RETURNING {}",
+            ROOT_FOLDER_COLUMNS
+        ))
+        .bind(root_folder.id)
+        .bind(&root_folder.path)
+        .bind(root_folder.default_quality_profile_id)
+        .bind(root_folder.min_free_space_bytes)
+        .bind(root_folder.updated_at)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match row {
+            Some(row) => Self::row_to_root_folder(&row),
+            None => Err(RadarrError::NotFound {
+                resource: format!("root folder {}", root_folder.id),
+            }),
+        }
+    }
+
+    async fn delete(&self, id: i32) -> Result<()> {
+        let result = sqlx::query("DELETE FROM root_folders WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(RadarrError::NotFound {
+                resource: format!("root folder {}", id),
+            });
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,100 @@
+//! PostgreSQL implementation of ShareTokenRepository
+
+use crate::database::DatabasePool;
+use async_trait::async_trait;
+use radarr_core::{
+    domain::repositories::ShareTokenRepository,
+    models::{ShareScope, ShareToken},
+    RadarrError, Result,
+};
+use sqlx::Row;
+use uuid::Uuid;
+
+const SHARE_TOKEN_COLUMNS: &str =
+    "id, name, token_hash, scopes, created_at, expires_at, revoked_at";
+
+/// PostgreSQL implementation of ShareTokenRepository
+pub struct PostgresShareTokenRepository {
+    pool: DatabasePool,
+}
+
+impl PostgresShareTokenRepository {
+    /// Create a new PostgreSQL share token repository
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert database row to ShareToken
+    fn row_to_token(row: &sqlx::postgres::PgRow) -> Result<ShareToken> {
+        let scopes: Vec<String> = row.try_get("scopes")?;
+        let scopes = scopes
+            .iter()
+            .map(|scope| {
+                ShareScope::parse(scope).ok_or_else(|| RadarrError::DatabaseError {
+                    message: format!("Invalid share token scope: {}", scope),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(ShareToken {
+            id: row.try_get("id")?,
+            name: row.try_get("name")?,
+            token_hash: row.try_get("token_hash")?,
+            scopes,
+            created_at: row.try_get("created_at")?,
+            expires_at: row.try_get("expires_at")?,
+            revoked_at: row.try_get("revoked_at")?,
+        })
+    }
+}
+
+#[async_trait]
+impl ShareTokenRepository for PostgresShareTokenRepository {
+    async fn list(&self) -> Result<Vec<ShareToken>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM share_tokens ORDER BY created_at DESC",
+            SHARE_TOKEN_COLUMNS
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(Self::row_to_token).collect()
+    }
+
+    async fn create(&self, token: &ShareToken) -> Result<ShareToken> {
+        let scopes: Vec<&str> = token.scopes.iter().map(|scope| scope.as_str()).collect();
+
+        let row = sqlx::query(&format!(
+            "INSERT INTO share_tokens (id, name, token_hash, scopes, created_at, expires_at,
+             revoked_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7)
+             RETURNING {}",
+            SHARE_TOKEN_COLUMNS
+        ))
+        .bind(token.id)
+        .bind(&token.name)
+        .bind(&token.token_hash)
+        .bind(&scopes)
+        .bind(token.created_at)
+        .bind(token.expires_at)
+        .bind(token.revoked_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Self::row_to_token(&row)
+    }
+
+    async fn revoke(&self, id: Uuid) -> Result<Option<ShareToken>> {
+        let row = sqlx::query(&format!(
+            "UPDATE share_tokens SET revoked_at = COALESCE(revoked_at, NOW())
+             WHERE id = $1
+             RETURNING {}",
+            SHARE_TOKEN_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.as_ref().map(Self::row_to_token).transpose()
+    }
+}
@@ -0,0 +1,133 @@
+//! PostgreSQL implementation of TagRepository
+
+use crate::database::DatabasePool;
+use async_trait::async_trait;
+use radarr_core::{
+    domain::repositories::TagRepository,
+    models::{Tag, TagUsage},
+    RadarrError, Result,
+};
+use sqlx::Row;
+
+const TAG_COLUMNS: &str = "id, label, created_at";
+
+/// PostgreSQL implementation of TagRepository
+pub struct PostgresTagRepository {
+    pool: DatabasePool,
+}
+
+impl PostgresTagRepository {
+    /// Create a new PostgreSQL tag repository
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert database row to Tag
+    fn row_to_tag(row: &sqlx::postgres::PgRow) -> Result<Tag> {
+        Ok(Tag {
+            id: row.try_get("id")?,
+            label: row.try_get("label")?,
+            created_at: row.try_get("created_at")?,
+        })
+    }
+}
+
+#[async_trait]
+impl TagRepository for PostgresTagRepository {
+    async fn find_by_id(&self, id: i32) -> Result<Option<Tag>> {
+        let row = sqlx::query(&format!("SELECT {} FROM tags WHERE id = $1", TAG_COLUMNS))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.as_ref().map(Self::row_to_tag).transpose()
+    }
+
+    async fn find_by_label(&self, label: &str) -> Result<Option<Tag>> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM tags WHERE label = $1",
+            TAG_COLUMNS
+        ))
+        .bind(label)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.as_ref().map(Self::row_to_tag).transpose()
+    }
+
+    async fn list(&self) -> Result<Vec<Tag>> {
+        let rows = sqlx::query(&format!("SELECT {} FROM tags ORDER BY label", TAG_COLUMNS))
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.iter().map(Self::row_to_tag).collect()
+    }
+
+    async fn create(&self, tag: &Tag) -> Result<Tag> {
+        let row = sqlx::query(&format!(
+            "INSERT INTO tags (label, created_at) VALUES ($1, $2) RETURNING {}",
+            TAG_COLUMNS
+        ))
+        .bind(&tag.label)
+        .bind(tag.created_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Self::row_to_tag(&row)
+    }
+
+    async fn update(&self, tag: &Tag) -> Result<Tag> {
+        let row = sqlx::query(&format!(
+            "UPDATE tags SET label = $2 WHERE id = $1 RETURNING {}",
+            TAG_COLUMNS
+        ))
+        .bind(tag.id)
+        .bind(&tag.label)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match row {
+            Some(row) => Self::row_to_tag(&row),
+            None => Err(RadarrError::NotFound {
+                resource: format!("tag {}", tag.id),
+            }),
+        }
+    }
+
+    async fn delete(&self, id: i32) -> Result<()> {
+        let result = sqlx::query("DELETE FROM tags WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(RadarrError::NotFound {
+                resource: format!("tag {}", id),
+            });
+        }
+
+        Ok(())
+    }
+
+    async fn usage(&self, id: i32) -> Result<TagUsage> {
+        let row = sqlx::query(
+            "SELECT
+                ARRAY(SELECT id FROM movies WHERE $1 = ANY(tags) ORDER BY title) AS movie_ids,
+                ARRAY(SELECT id FROM indexers WHERE $1 = ANY(tags) ORDER BY id) AS indexer_ids,
+                ARRAY(SELECT id FROM notifications WHERE $1 = ANY(tags) ORDER BY id)
+                    AS notification_ids,
+                ARRAY(SELECT id FROM import_lists WHERE $1 = ANY(tags) ORDER BY name)
+                    AS import_list_ids",
+        )
+        .bind(id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(TagUsage {
+            movie_ids: row.try_get("movie_ids")?,
+            indexer_ids: row.try_get("indexer_ids")?,
+            notification_ids: row.try_get("notification_ids")?,
+            import_list_ids: row.try_get("import_list_ids")?,
+        })
+    }
+}
@@ -8,10 +8,12 @@ use radarr_core::streaming::{
 use sqlx::PgPool;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use tracing::info;
 
 use crate::{
-    repositories::PostgresStreamingCache, tmdb::TmdbStreamingClient, trakt::TraktClient,
+    cache::CacheManager, repositories::PostgresStreamingCache,
+    streaming::CachedStreamingRepository, tmdb::TmdbStreamingClient, trakt::TraktClient,
     watchmode::WatchmodeClient,
 };
 
@@ -22,6 +24,7 @@ pub struct StreamingServiceFactory {
     trakt_client_id: Option<String>,
     trakt_client_secret: Option<String>,
     watchmode_api_key: Option<String>,
+    cache: Option<(Arc<CacheManager>, Duration)>,
 }
 
 impl StreamingServiceFactory {
@@ -32,6 +35,7 @@ impl StreamingServiceFactory {
             trakt_client_id: None,
             trakt_client_secret: None,
             watchmode_api_key: None,
+            cache: None,
         }
     }
 
@@ -51,6 +55,12 @@ impl StreamingServiceFactory {
         self
     }
 
+    /// Answer cache lookups from the cache manager before the database
+    pub fn with_cache(mut self, cache: Arc<CacheManager>, ttl: Duration) -> Self {
+        self.cache = Some((cache, ttl));
+        self
+    }
+
     /// Build the complete streaming aggregator with all configured services
     pub fn build_aggregator(self) -> Arc<dyn StreamingAggregator> {
         info!("Building streaming service aggregator");
@@ -60,6 +70,10 @@ impl StreamingServiceFactory {
         let trending_repo = cache_repo.clone() as Arc<dyn TrendingRepository>;
         let availability_repo = cache_repo.clone() as Arc<dyn AvailabilityRepository>;
         let token_repo = cache_repo.clone() as Arc<dyn OAuthTokenRepository>;
+        let cache_repo: Arc<dyn StreamingCacheRepository> = match self.cache.clone() {
+            Some((cache, ttl)) => Arc::new(CachedStreamingRepository::new(cache, cache_repo, ttl)),
+            None => cache_repo,
+        };
 
         // Create TMDB client (required)
         let tmdb_client: Arc<dyn radarr_core::streaming::traits::TmdbAdapter> =
@@ -79,7 +93,7 @@ impl StreamingServiceFactory {
                 Arc::new(TraktClient::new(
                     "dummy".to_string(),
                     "dummy".to_string(),
-                    cache_repo.clone(),
+                    token_repo,
                 ))
             };
 
@@ -115,7 +129,11 @@ impl StreamingServiceFactory {
 
     /// Build just the cache repository
     pub fn build_cache_repository(self) -> Arc<dyn StreamingCacheRepository> {
-        Arc::new(PostgresStreamingCache::new(self.pool))
+        let store = Arc::new(PostgresStreamingCache::new(self.pool));
+        match self.cache {
+            Some((cache, ttl)) => Arc::new(CachedStreamingRepository::new(cache, store, ttl)),
+            None => store,
+        }
     }
 
     /// Build streaming configuration with cache TTLs
@@ -141,8 +159,13 @@ impl StreamingServiceFactory {
     }
 }
 
-/// Create a default aggregator from environment variables
-pub fn create_default_aggregator(pool: PgPool) -> Arc<dyn StreamingAggregator> {
+/// Create a default aggregator from environment variables, caching lookups
+/// in `cache` for up to `cache_ttl`
+pub fn create_default_aggregator(
+    pool: PgPool,
+    cache: Arc<CacheManager>,
+    cache_ttl: Duration,
+) -> Arc<dyn StreamingAggregator> {
     use std::env;
 
     let tmdb_api_key =
@@ -152,7 +175,9 @@ pub fn create_default_aggregator(pool: PgPool) -> Arc<dyn StreamingAggregator> {
     let trakt_client_secret = env::var("TRAKT_CLIENT_SECRET").ok();
     let watchmode_api_key = env::var("WATCHMODE_API_KEY").ok();
 
-    let mut factory = StreamingServiceFactory::new(pool).with_tmdb(tmdb_api_key);
+    let mut factory = StreamingServiceFactory::new(pool)
+        .with_tmdb(tmdb_api_key)
+        .with_cache(cache, cache_ttl);
 
     if let (Some(id), Some(secret)) = (trakt_client_id, trakt_client_secret) {
         factory = factory.with_trakt(id, secret);
@@ -0,0 +1,97 @@
+use crate::cache::{CacheKey, CacheManager};
+use async_trait::async_trait;
+use radarr_core::{
+    streaming::{traits::StreamingCacheRepository, IdMapping, MediaType},
+    RadarrError,
+};
+use serde_json::Value as JsonValue;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::warn;
+
+/// Streaming cache answering lookups from the cache manager before the
+/// database, so repeated trending and availability lookups skip Postgres
+pub struct CachedStreamingRepository {
+    cache: Arc<CacheManager>,
+    store: Arc<dyn StreamingCacheRepository>,
+    ttl: Duration,
+}
+
+impl CachedStreamingRepository {
+    /// Keep entries in the cache manager for at most `ttl`
+    pub fn new(
+        cache: Arc<CacheManager>,
+        store: Arc<dyn StreamingCacheRepository>,
+        ttl: Duration,
+    ) -> Self {
+        Self { cache, store, ttl }
+    }
+
+    /// Cached for the entry's own TTL, but never longer than ours
+    fn ttl_for(&self, ttl_hours: i64) -> Duration {
+        let entry_ttl = Duration::from_secs(ttl_hours.max(0) as u64 * 3600);
+        entry_ttl.min(self.ttl)
+    }
+}
+
+#[async_trait]
+impl StreamingCacheRepository for CachedStreamingRepository {
+    async fn get_raw(&self, key: &str) -> Result<Option<JsonValue>, RadarrError> {
+        let cache_key = CacheKey::streaming(key);
+        if let Some(data) = self.cache.get(&cache_key).await {
+            return Ok(Some(data));
+        }
+
+        let data = self.store.get_raw(key).await?;
+        if let Some(data) = &data {
+            // Entries carry their own expiry, so a stale copy is never used
+            if let Err(e) = self.cache.set(&cache_key, data, self.ttl).await {
+                warn!("Failed to cache streaming entry {}: {}", key, e);
+            }
+        }
+        Ok(data)
+    }
+
+    async fn set_raw(&self, key: &str, data: JsonValue, ttl_hours: i64) -> Result<(), RadarrError> {
+        let cache_key = CacheKey::streaming(key);
+        if let Err(e) = self
+            .cache
+            .set(&cache_key, &data, self.ttl_for(ttl_hours))
+            .await
+        {
+            warn!("Failed to cache streaming entry {}: {}", key, e);
+        }
+        self.store.set_raw(key, data, ttl_hours).await
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), RadarrError> {
+        if let Err(e) = self.cache.delete(&CacheKey::streaming(key)).await {
+            warn!("Failed to drop cached streaming entry {}: {}", key, e);
+        }
+        self.store.delete(key).await
+    }
+
+    async fn clear_expired(&self) -> Result<usize, RadarrError> {
+        self.store.clear_expired().await
+    }
+
+    async fn store_id_mappings(&self, mappings: Vec<IdMapping>) -> Result<usize, RadarrError> {
+        self.store.store_id_mappings(mappings).await
+    }
+
+    async fn get_watchmode_id(
+        &self,
+        tmdb_id: i32,
+        media_type: MediaType,
+    ) -> Result<Option<i32>, RadarrError> {
+        self.store.get_watchmode_id(tmdb_id, media_type).await
+    }
+
+    async fn get_id_mapping(
+        &self,
+        tmdb_id: i32,
+        media_type: MediaType,
+    ) -> Result<Option<IdMapping>, RadarrError> {
+        self.store.get_id_mapping(tmdb_id, media_type).await
+    }
+}
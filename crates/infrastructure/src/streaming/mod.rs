@@ -1,3 +1,5 @@
 pub mod aggregator_factory;
+pub mod cached_repository;
 
 pub use aggregator_factory::{create_default_aggregator, StreamingServiceFactory};
+pub use cached_repository::CachedStreamingRepository;
@@ -1,21 +1,68 @@
-use crate::tmdb::TmdbClient;
-use radarr_core::models::Movie;
+use crate::cache::{CacheKey, CacheManager};
+use crate::tmdb::{TmdbClient, TmdbError};
+use radarr_core::models::{Movie, MovieCollection};
+use serde::{de::DeserializeOwned, Serialize};
+use std::future::Future;
+use std::sync::Arc;
 use std::time::Duration;
-use tracing::{debug, info};
+use tracing::{debug, warn};
 
-/// Simple wrapper for TMDB client (caching disabled for MVP)
+/// TMDB client caching lookups in the configured cache, if any
 pub struct CachedTmdbClient {
     client: TmdbClient,
+    cache: Option<Arc<CacheManager>>,
+    ttl: Duration,
 }
 
 impl CachedTmdbClient {
     pub fn new(client: TmdbClient) -> Self {
-        Self { client }
+        Self {
+            client,
+            cache: None,
+            ttl: Duration::from_secs(6 * 3600),
+        }
+    }
+
+    /// Cache lookups for `ttl`
+    pub fn with_cache(mut self, cache: Arc<CacheManager>, ttl: Duration) -> Self {
+        self.cache = Some(cache);
+        self.ttl = ttl;
+        self
+    }
+
+    async fn cached<T, F, Fut>(&self, key: String, load: F) -> Result<T, TmdbError>
+    where
+        T: Serialize + DeserializeOwned,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, TmdbError>>,
+    {
+        match &self.cache {
+            Some(cache) => cache.get_or_load(&key, self.ttl, load).await,
+            None => load().await,
+        }
     }
 
     pub async fn get_movie(&self, tmdb_id: i32) -> Result<Movie, crate::tmdb::TmdbError> {
         debug!("Fetching TMDB movie: id={}", tmdb_id);
-        self.client.get_movie(tmdb_id).await
+        self.cached(CacheKey::tmdb_movie(tmdb_id), || {
+            self.client.get_movie(tmdb_id)
+        })
+        .await
+    }
+
+    /// Fetch the current movie details, replacing the cached ones
+    pub async fn refresh_movie(&self, tmdb_id: i32) -> Result<Movie, crate::tmdb::TmdbError> {
+        debug!("Refreshing TMDB movie: id={}", tmdb_id);
+        let movie = self.client.get_movie(tmdb_id).await?;
+        if let Some(cache) = &self.cache {
+            if let Err(e) = cache
+                .set(&CacheKey::tmdb_movie(tmdb_id), &movie, self.ttl)
+                .await
+            {
+                warn!("Failed to cache TMDB movie {}: {}", tmdb_id, e);
+            }
+        }
+        Ok(movie)
     }
 
     pub async fn search_movies(
@@ -24,7 +71,10 @@ impl CachedTmdbClient {
         page: Option<i32>,
     ) -> Result<Vec<Movie>, crate::tmdb::TmdbError> {
         debug!("Searching TMDB movies: query={}, page={:?}", query, page);
-        self.client.search_movies(query, page).await
+        self.cached(CacheKey::tmdb_search(query, page.unwrap_or(1)), || {
+            self.client.search_movies(query, page)
+        })
+        .await
     }
 
     pub async fn get_popular(
@@ -32,7 +82,10 @@ impl CachedTmdbClient {
         page: Option<i32>,
     ) -> Result<Vec<Movie>, crate::tmdb::TmdbError> {
         debug!("Fetching TMDB popular movies: page={:?}", page);
-        self.client.get_popular(page).await
+        self.cached(CacheKey::tmdb_popular(page.unwrap_or(1)), || {
+            self.client.get_popular(page)
+        })
+        .await
     }
 
     pub async fn get_upcoming(
@@ -40,6 +93,20 @@ impl CachedTmdbClient {
         page: Option<i32>,
     ) -> Result<Vec<Movie>, crate::tmdb::TmdbError> {
         debug!("Fetching TMDB upcoming movies: page={:?}", page);
-        self.client.get_upcoming(page).await
+        self.cached(CacheKey::tmdb_upcoming(page.unwrap_or(1)), || {
+            self.client.get_upcoming(page)
+        })
+        .await
+    }
+
+    pub async fn get_movie_collection(
+        &self,
+        collection_id: i32,
+    ) -> Result<MovieCollection, crate::tmdb::TmdbError> {
+        debug!("Fetching TMDB collection: id={}", collection_id);
+        self.cached(CacheKey::tmdb_collection(collection_id), || {
+            self.client.get_movie_collection(collection_id)
+        })
+        .await
     }
 }
@@ -1,6 +1,6 @@
 use radarr_core::{
     circuit_breaker::{CircuitBreaker, CircuitBreakerConfig},
-    Movie, MovieStatus, RadarrError,
+    CollectionMovie, Movie, MovieCollection, MovieStatus, RadarrError, Rating, TracedRequest,
 };
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
@@ -21,13 +21,51 @@ pub enum TmdbError {
 
     #[error("Movie not found")]
     NotFound,
+
+    #[error("Rate limited by TMDB")]
+    RateLimited { retry_after: Option<u64> },
+
+    /// Failure outside the request itself, e.g. an open circuit breaker
+    #[error(transparent)]
+    Service(RadarrError),
+}
+
+impl TmdbError {
+    /// Error for an unsuccessful TMDB response
+    async fn from_response(response: reqwest::Response) -> Self {
+        let status = response.status();
+        let retry_after = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse().ok());
+        let text = response.text().await.unwrap_or_default();
+        error!("TMDB API error: {} - {}", status, text);
+
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return TmdbError::RateLimited { retry_after };
+        }
+        TmdbError::ApiError {
+            message: format!("HTTP {}: {}", status, text),
+        }
+    }
 }
 
 impl From<TmdbError> for RadarrError {
     fn from(error: TmdbError) -> Self {
-        RadarrError::ExternalServiceError {
-            service: "tmdb".to_string(),
-            error: error.to_string(),
+        match error {
+            TmdbError::RateLimited { retry_after } => RadarrError::RateLimited {
+                service: "tmdb".to_string(),
+                retry_after,
+            },
+            TmdbError::NotFound => RadarrError::NotFound {
+                resource: "TMDB movie".to_string(),
+            },
+            TmdbError::Service(error) => error,
+            error => RadarrError::ExternalServiceError {
+                service: "tmdb".to_string(),
+                error: error.to_string(),
+            },
         }
     }
 }
@@ -97,17 +135,12 @@ impl TmdbClient {
                         ("query", &query_clone),
                         ("page", &page.to_string()),
                     ])
-                    .send()
+                    .send_traced("TMDB")
                     .await
                     .map_err(TmdbError::HttpError)?;
 
                 if !response.status().is_success() {
-                    let status = response.status();
-                    let text = response.text().await.unwrap_or_default();
-                    error!("TMDB API error: {} - {}", status, text);
-                    return Err(TmdbError::ApiError {
-                        message: format!("HTTP {}: {}", status, text),
-                    });
+                    return Err(TmdbError::from_response(response).await);
                 }
 
                 let search_response: TmdbSearchResponse =
@@ -132,18 +165,8 @@ impl TmdbClient {
                     .collect();
                 Ok(movies)
             }
-            Err(RadarrError::CircuitBreakerOpen { service }) => Err(TmdbError::ApiError {
-                message: format!(
-                    "TMDB service unavailable: circuit breaker open for {}",
-                    service
-                ),
-            }),
-            Err(RadarrError::Timeout { operation }) => Err(TmdbError::ApiError {
-                message: format!("TMDB request timed out: {}", operation),
-            }),
-            Err(e) => Err(TmdbError::ApiError {
-                message: format!("TMDB service error: {}", e),
-            }),
+            // Keeps open circuits, timeouts and rate limits distinguishable
+            Err(e) => Err(TmdbError::Service(e)),
         }
     }
 
@@ -156,8 +179,14 @@ impl TmdbClient {
         let response = self
             .client
             .get(&url)
-            .query(&[("api_key", &self.api_key)])
-            .send()
+            .query(&[
+                ("api_key", self.api_key.as_str()),
+                (
+                    "append_to_response",
+                    "release_dates,alternative_titles,external_ids",
+                ),
+            ])
+            .send_traced("TMDB")
             .await?;
 
         if response.status() == 404 {
@@ -165,12 +194,7 @@ impl TmdbClient {
         }
 
         if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_default();
-            error!("TMDB API error: {} - {}", status, text);
-            return Err(TmdbError::ApiError {
-                message: format!("HTTP {}: {}", status, text),
-            });
+            return Err(TmdbError::from_response(response).await);
         }
 
         let tmdb_movie: TmdbMovie = response.json().await?;
@@ -191,16 +215,11 @@ impl TmdbClient {
             .client
             .get(&url)
             .query(&[("api_key", &self.api_key), ("page", &page.to_string())])
-            .send()
+            .send_traced("TMDB")
             .await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_default();
-            error!("TMDB API error: {} - {}", status, text);
-            return Err(TmdbError::ApiError {
-                message: format!("HTTP {}: {}", status, text),
-            });
+            return Err(TmdbError::from_response(response).await);
         }
 
         let search_response: TmdbSearchResponse = response.json().await?;
@@ -231,16 +250,11 @@ impl TmdbClient {
             .client
             .get(&url)
             .query(&[("api_key", &self.api_key), ("page", &page.to_string())])
-            .send()
+            .send_traced("TMDB")
             .await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_default();
-            error!("TMDB API error: {} - {}", status, text);
-            return Err(TmdbError::ApiError {
-                message: format!("HTTP {}: {}", status, text),
-            });
+            return Err(TmdbError::from_response(response).await);
         }
 
         let search_response: TmdbSearchResponse = response.json().await?;
@@ -271,16 +285,11 @@ impl TmdbClient {
             .client
             .get(&url)
             .query(&[("api_key", &self.api_key), ("page", &page.to_string())])
-            .send()
+            .send_traced("TMDB")
             .await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_default();
-            error!("TMDB API error: {} - {}", status, text);
-            return Err(TmdbError::ApiError {
-                message: format!("HTTP {}: {}", status, text),
-            });
+            return Err(TmdbError::from_response(response).await);
         }
 
         let search_response: TmdbSearchResponse = response.json().await?;
@@ -311,16 +320,11 @@ impl TmdbClient {
             .client
             .get(&url)
             .query(&[("api_key", &self.api_key), ("page", &page.to_string())])
-            .send()
+            .send_traced("TMDB")
             .await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_default();
-            error!("TMDB API error: {} - {}", status, text);
-            return Err(TmdbError::ApiError {
-                message: format!("HTTP {}: {}", status, text),
-            });
+            return Err(TmdbError::from_response(response).await);
         }
 
         let search_response: TmdbSearchResponse = response.json().await?;
@@ -350,7 +354,7 @@ impl TmdbClient {
             .client
             .get(&url)
             .query(&[("api_key", &self.api_key)])
-            .send()
+            .send_traced("TMDB")
             .await?;
 
         if response.status() == 404 {
@@ -358,12 +362,7 @@ impl TmdbClient {
         }
 
         if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_default();
-            error!("TMDB API error: {} - {}", status, text);
-            return Err(TmdbError::ApiError {
-                message: format!("HTTP {}: {}", status, text),
-            });
+            return Err(TmdbError::from_response(response).await);
         }
 
         let collection_response: TmdbCollectionResponse = response.json().await?;
@@ -383,6 +382,74 @@ impl TmdbClient {
         Ok(movies)
     }
 
+    /// Get a collection's details and films, in release order
+    pub async fn get_movie_collection(
+        &self,
+        collection_id: i32,
+    ) -> Result<MovieCollection, TmdbError> {
+        let url = format!("{}/collection/{}", self.base_url, collection_id);
+
+        debug!("Fetching TMDB collection details: id={}", collection_id);
+
+        let response = self
+            .client
+            .get(&url)
+            .query(&[("api_key", &self.api_key)])
+            .send_traced("TMDB")
+            .await?;
+
+        if response.status() == 404 {
+            return Err(TmdbError::NotFound);
+        }
+
+        if !response.status().is_success() {
+            return Err(TmdbError::from_response(response).await);
+        }
+
+        let collection_response: TmdbCollectionResponse = response.json().await?;
+
+        debug!(
+            "TMDB collection {} has {} movies",
+            collection_response.name,
+            collection_response.parts.len()
+        );
+
+        Ok(Self::tmdb_collection_to_collection(collection_response))
+    }
+
+    /// Convert a TMDB collection to our MovieCollection model
+    fn tmdb_collection_to_collection(response: TmdbCollectionResponse) -> MovieCollection {
+        let mut collection = MovieCollection::new(response.id, response.name);
+        collection.overview = response.overview;
+        collection.poster_path = response.poster_path;
+        collection.backdrop_path = response.backdrop_path;
+
+        let mut parts = response.parts;
+        // Unreleased films have no date and sort last
+        parts.sort_by(|a, b| match (&a.release_date, &b.release_date) {
+            (Some(a), Some(b)) if !a.is_empty() && !b.is_empty() => a.cmp(b),
+            (Some(a), _) if !a.is_empty() => std::cmp::Ordering::Less,
+            (_, Some(b)) if !b.is_empty() => std::cmp::Ordering::Greater,
+            _ => std::cmp::Ordering::Equal,
+        });
+        collection.movies = parts
+            .into_iter()
+            .map(|part| CollectionMovie {
+                tmdb_id: part.id,
+                year: part
+                    .release_date
+                    .as_ref()
+                    .and_then(|date| date.split('-').next().and_then(|year| year.parse().ok())),
+                title: part.title,
+                overview: part.overview,
+                poster_path: part.poster_path,
+            })
+            .collect();
+        collection.last_info_sync = Some(chrono::Utc::now());
+
+        collection
+    }
+
     /// Get movies by person (actor/director)
     pub async fn get_person_movies(&self, person_id: i32) -> Result<Vec<Movie>, TmdbError> {
         let url = format!("{}/person/{}/movie_credits", self.base_url, person_id);
@@ -393,7 +460,7 @@ impl TmdbClient {
             .client
             .get(&url)
             .query(&[("api_key", &self.api_key)])
-            .send()
+            .send_traced("TMDB")
             .await?;
 
         if response.status() == 404 {
@@ -401,12 +468,7 @@ impl TmdbClient {
         }
 
         if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_default();
-            error!("TMDB API error: {} - {}", status, text);
-            return Err(TmdbError::ApiError {
-                message: format!("HTTP {}: {}", status, text),
-            });
+            return Err(TmdbError::from_response(response).await);
         }
 
         let credits_response: TmdbPersonCreditsResponse = response.json().await?;
@@ -449,7 +511,7 @@ impl TmdbClient {
             .client
             .get(&url)
             .query(&[("api_key", &self.api_key), ("page", &page.to_string())])
-            .send()
+            .send_traced("TMDB")
             .await?;
 
         if response.status() == 404 {
@@ -457,12 +519,7 @@ impl TmdbClient {
         }
 
         if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_default();
-            error!("TMDB API error: {} - {}", status, text);
-            return Err(TmdbError::ApiError {
-                message: format!("HTTP {}: {}", status, text),
-            });
+            return Err(TmdbError::from_response(response).await);
         }
 
         let search_response: TmdbSearchResponse = response.json().await?;
@@ -492,7 +549,7 @@ impl TmdbClient {
             .client
             .get(&url)
             .query(&[("api_key", &self.api_key)])
-            .send()
+            .send_traced("TMDB")
             .await?;
 
         if response.status() == 404 {
@@ -500,12 +557,7 @@ impl TmdbClient {
         }
 
         if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_default();
-            error!("TMDB API error: {} - {}", status, text);
-            return Err(TmdbError::ApiError {
-                message: format!("HTTP {}: {}", status, text),
-            });
+            return Err(TmdbError::from_response(response).await);
         }
 
         let list_response: TmdbListResponse = response.json().await?;
@@ -544,10 +596,20 @@ impl TmdbClient {
             _ => MovieStatus::Announced,
         };
 
-        // Set IMDB ID if available
-        movie.imdb_id = tmdb_movie.imdb_id.clone();
-
-        // Store TMDB metadata
+        // Set IMDB ID if available, from the external IDs when the movie
+        // details leave it out
+        movie.imdb_id = tmdb_movie
+            .imdb_id
+            .clone()
+            .or_else(|| tmdb_movie.external_ids.as_ref()?.imdb_id.clone())
+            .filter(|imdb_id| !imdb_id.is_empty());
+        movie.ratings.tmdb = tmdb_movie
+            .vote_average
+            .map(|value| Rating::new(value, tmdb_movie.vote_count.unwrap_or(0).into()));
+
+        // Store TMDB metadata; release dates and alternative titles are only
+        // in the movie details
+        let release_dates = tmdb_movie.release_dates.as_ref();
         movie.metadata = serde_json::json!({
             "tmdb": {
                 "id": tmdb_movie.id,
@@ -569,10 +631,21 @@ impl TmdbClient {
                 "tagline": tmdb_movie.tagline,
                 "homepage": tmdb_movie.homepage,
                 "budget": tmdb_movie.budget,
-                "revenue": tmdb_movie.revenue
+                "revenue": tmdb_movie.revenue,
+                "collection": tmdb_movie.belongs_to_collection,
+                "digital_release_date": release_dates.and_then(|dates| dates.earliest(RELEASE_TYPE_DIGITAL)),
+                "physical_release_date": release_dates.and_then(|dates| dates.earliest(RELEASE_TYPE_PHYSICAL))
             }
         });
 
+        if let Some(alternative_titles) = tmdb_movie.alternative_titles {
+            movie.alternative_titles = alternative_titles
+                .titles
+                .into_iter()
+                .map(|title| serde_json::json!({ "title": title.title, "country": title.iso_3166_1 }))
+                .collect();
+        }
+
         movie
     }
 
@@ -587,7 +660,7 @@ impl TmdbClient {
             .client
             .get(&url)
             .query(&[("api_key", &self.api_key), ("page", &page.to_string())])
-            .send()
+            .send_traced("TMDB")
             .await?;
 
         if response.status() == 404 {
@@ -595,12 +668,7 @@ impl TmdbClient {
         }
 
         if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_default();
-            error!("TMDB API error: {} - {}", status, text);
-            return Err(TmdbError::ApiError {
-                message: format!("HTTP {}: {}", status, text),
-            });
+            return Err(TmdbError::from_response(response).await);
         }
 
         let search_response: TmdbSearchResponse = response.json().await?;
@@ -632,15 +700,15 @@ impl TmdbClient {
         let mut query_params = vec![("api_key", self.api_key.as_str())];
         query_params.extend_from_slice(params);
 
-        let response = self.client.get(&url).query(&query_params).send().await?;
+        let response = self
+            .client
+            .get(&url)
+            .query(&query_params)
+            .send_traced("TMDB")
+            .await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_default();
-            error!("TMDB API error: {} - {}", status, text);
-            return Err(TmdbError::ApiError {
-                message: format!("HTTP {}: {}", status, text),
-            });
+            return Err(TmdbError::from_response(response).await);
         }
 
         let search_response: TmdbSearchResponse = response.json().await?;
@@ -706,6 +774,76 @@ struct TmdbMovie {
     budget: Option<i64>,
     revenue: Option<i64>,
     imdb_id: Option<String>,
+    belongs_to_collection: Option<TmdbCollectionRef>,
+
+    // Appended to the movie details
+    release_dates: Option<TmdbReleaseDates>,
+    alternative_titles: Option<TmdbAlternativeTitles>,
+    external_ids: Option<TmdbExternalIds>,
+}
+
+/// Digital release type in TMDB release dates
+const RELEASE_TYPE_DIGITAL: i32 = 4;
+/// Physical release type in TMDB release dates
+const RELEASE_TYPE_PHYSICAL: i32 = 5;
+
+/// Release dates of a movie by country
+#[derive(Debug, Deserialize)]
+struct TmdbReleaseDates {
+    results: Vec<TmdbCountryReleaseDates>,
+}
+
+impl TmdbReleaseDates {
+    /// Earliest date, as `YYYY-MM-DD`, of any country's release of
+    /// `release_type`
+    fn earliest(&self, release_type: i32) -> Option<String> {
+        self.results
+            .iter()
+            .flat_map(|country| &country.release_dates)
+            .filter(|date| date.release_type == release_type)
+            .filter_map(|date| date.release_date.get(..10))
+            .min()
+            .map(str::to_string)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct TmdbCountryReleaseDates {
+    release_dates: Vec<TmdbReleaseDate>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TmdbReleaseDate {
+    release_date: String,
+    #[serde(rename = "type")]
+    release_type: i32,
+}
+
+/// Alternative titles of a movie
+#[derive(Debug, Deserialize)]
+struct TmdbAlternativeTitles {
+    titles: Vec<TmdbAlternativeTitle>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TmdbAlternativeTitle {
+    iso_3166_1: String,
+    title: String,
+}
+
+/// IDs of a movie on other services
+#[derive(Debug, Deserialize)]
+struct TmdbExternalIds {
+    imdb_id: Option<String>,
+}
+
+/// Collection a movie belongs to, as embedded in the movie details
+#[derive(Debug, Deserialize, Serialize)]
+struct TmdbCollectionRef {
+    id: i32,
+    name: String,
+    poster_path: Option<String>,
+    backdrop_path: Option<String>,
 }
 
 /// TMDB collection response
@@ -0,0 +1,323 @@
+//! Update checks against GitHub releases
+//!
+//! The scheduled `ApplicationUpdateCheck` task reads the project's published
+//! releases and keeps the ones newer than the running version; drafts and
+//! prereleases are skipped. `/api/v3/update` lists them, the health checks
+//! report the newest and a notification goes out the first time it is seen.
+//! Nothing is installed: the instance is updated by whatever deployed it.
+
+use chrono::{DateTime, Utc};
+use radarr_core::{events::EventBus, RadarrError, Result, SystemEvent};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+/// Repository releases are read from unless configured otherwise
+pub const DEFAULT_UPDATE_REPOSITORY: &str = "zimmermanc/radarr-mvp";
+
+/// Version of the running build
+pub const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Whether and where to look for new releases
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UpdateConfig {
+    /// Ask GitHub for new releases; off, the instance never calls out
+    pub enabled: bool,
+    /// GitHub repository as `owner/name`
+    pub repository: String,
+    /// Hours between checks
+    pub check_interval_hours: u64,
+}
+
+impl Default for UpdateConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            repository: DEFAULT_UPDATE_REPOSITORY.to_string(),
+            check_interval_hours: 6,
+        }
+    }
+}
+
+/// A published release newer than the running version
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateRelease {
+    pub version: String,
+    pub release_date: Option<DateTime<Utc>>,
+    /// Release page on GitHub
+    pub url: String,
+    /// Release notes as written on GitHub
+    pub changes: Option<String>,
+}
+
+/// Outcome of the latest check
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateStatus {
+    pub checked_at: DateTime<Utc>,
+    /// Newest first
+    pub releases: Vec<UpdateRelease>,
+}
+
+/// Release as returned by the GitHub API
+#[derive(Debug, Deserialize)]
+struct GithubRelease {
+    tag_name: String,
+    html_url: String,
+    body: Option<String>,
+    #[serde(default)]
+    draft: bool,
+    #[serde(default)]
+    prerelease: bool,
+    published_at: Option<DateTime<Utc>>,
+}
+
+/// Looks for releases newer than the running version
+pub struct UpdateChecker {
+    config: UpdateConfig,
+    client: Client,
+    api_url: String,
+    latest: RwLock<Option<UpdateStatus>>,
+    /// Newest version a notification was sent for
+    notified: Mutex<Option<String>>,
+    event_bus: Option<Arc<EventBus>>,
+}
+
+impl UpdateChecker {
+    pub fn new(config: UpdateConfig) -> Self {
+        Self {
+            config,
+            client: Client::builder()
+                .timeout(Duration::from_secs(15))
+                .user_agent(format!("radarr-mvp/{}", CURRENT_VERSION))
+                .build()
+                .unwrap_or_default(),
+            api_url: "https://api.github.com".to_string(),
+            latest: RwLock::new(None),
+            notified: Mutex::new(None),
+            event_bus: None,
+        }
+    }
+
+    /// Announce newly found releases on `event_bus`
+    pub fn with_event_bus(mut self, event_bus: Arc<EventBus>) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
+    pub fn config(&self) -> &UpdateConfig {
+        &self.config
+    }
+
+    /// Outcome of the latest check, if one ran
+    pub fn status(&self) -> Option<UpdateStatus> {
+        self.latest
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Newest release found by the latest check
+    pub fn available(&self) -> Option<UpdateRelease> {
+        self.status()
+            .and_then(|status| status.releases.into_iter().next())
+    }
+
+    /// Ask GitHub for releases newer than the running version
+    pub async fn check(&self) -> Result<UpdateStatus> {
+        if !self.config.enabled {
+            return Err(RadarrError::ConfigurationError {
+                field: "updates.enabled".to_string(),
+                message: "Update checks are turned off".to_string(),
+            });
+        }
+
+        let url = format!("{}/repos/{}/releases", self.api_url, self.config.repository);
+        debug!("Checking for updates: {}", url);
+        let response = self
+            .client
+            .get(&url)
+            .header("Accept", "application/vnd.github+json")
+            .query(&[("per_page", "20")])
+            .send()
+            .await
+            .map_err(Self::error)?;
+        if !response.status().is_success() {
+            return Err(Self::error(format!(
+                "GitHub answered {} for {}",
+                response.status(),
+                self.config.repository
+            )));
+        }
+        let releases: Vec<GithubRelease> = response.json().await.map_err(Self::error)?;
+
+        Ok(self.record(newer_releases(CURRENT_VERSION, releases)).await)
+    }
+
+    /// Keep `releases` as the latest outcome, announcing a new newest one
+    async fn record(&self, releases: Vec<UpdateRelease>) -> UpdateStatus {
+        let status = UpdateStatus {
+            checked_at: Utc::now(),
+            releases,
+        };
+        *self.latest.write().unwrap_or_else(|e| e.into_inner()) = Some(status.clone());
+
+        let Some(newest) = status.releases.first() else {
+            debug!("Running the latest version {}", CURRENT_VERSION);
+            return status;
+        };
+        let first_seen = {
+            let mut notified = self.notified.lock().unwrap_or_else(|e| e.into_inner());
+            let first_seen = notified.as_deref() != Some(newest.version.as_str());
+            *notified = Some(newest.version.clone());
+            first_seen
+        };
+        if first_seen {
+            info!(
+                "Version {} is available (running {})",
+                newest.version, CURRENT_VERSION
+            );
+            if let Some(event_bus) = &self.event_bus {
+                let event = SystemEvent::ApplicationUpdateAvailable {
+                    current_version: CURRENT_VERSION.to_string(),
+                    new_version: newest.version.clone(),
+                    release_url: Some(newest.url.clone()),
+                };
+                if let Err(e) = event_bus.publish(event).await {
+                    warn!("Failed to announce update {}: {}", newest.version, e);
+                }
+            }
+        }
+        status
+    }
+
+    fn error(error: impl std::fmt::Display) -> RadarrError {
+        RadarrError::ExternalServiceError {
+            service: "github".to_string(),
+            error: error.to_string(),
+        }
+    }
+}
+
+/// `major.minor.patch` of a version or tag such as `v1.2.3`
+///
+/// Prerelease and build suffixes are ignored; a missing patch counts as 0.
+fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let version = version.trim().trim_start_matches(['v', 'V']);
+    let core = version.split(['-', '+']).next()?;
+    let mut parts = core.split('.').map(|part| part.parse::<u64>().ok());
+    let major = parts.next()??;
+    let minor = parts.next().unwrap_or(Some(0))?;
+    let patch = parts.next().unwrap_or(Some(0))?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((major, minor, patch))
+}
+
+/// Published releases newer than `current`, newest first
+fn newer_releases(current: &str, releases: Vec<GithubRelease>) -> Vec<UpdateRelease> {
+    let Some(current) = parse_version(current) else {
+        return Vec::new();
+    };
+
+    let mut newer: Vec<_> = releases
+        .into_iter()
+        .filter(|release| !release.draft && !release.prerelease)
+        .filter_map(|release| {
+            let version = parse_version(&release.tag_name)?;
+            (version > current).then_some((version, release))
+        })
+        .collect();
+    newer.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+    newer
+        .into_iter()
+        .map(|((major, minor, patch), release)| UpdateRelease {
+            version: format!("{}.{}.{}", major, minor, patch),
+            release_date: release.published_at,
+            url: release.html_url,
+            changes: release.body.filter(|body| !body.trim().is_empty()),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn release(tag: &str) -> GithubRelease {
+        GithubRelease {
+            tag_name: tag.to_string(),
+            html_url: format!(
+                "https://github.com/zimmermanc/radarr-mvp/releases/tag/{}",
+                tag
+            ),
+            body: Some("Fixes".to_string()),
+            draft: false,
+            prerelease: false,
+            published_at: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(parse_version("v1.2.3"), Some((1, 2, 3)));
+        assert_eq!(parse_version("1.4"), Some((1, 4, 0)));
+        assert_eq!(parse_version("2.0.0-beta.1"), Some((2, 0, 0)));
+        assert_eq!(parse_version("1.2.3.4"), None);
+        assert_eq!(parse_version("nightly"), None);
+        assert!(parse_version("0.10.0") > parse_version("0.9.9"));
+    }
+
+    #[test]
+    fn test_only_published_newer_releases_are_kept() {
+        let mut draft = release("v0.4.0");
+        draft.draft = true;
+        let mut prerelease = release("v0.5.0-rc.1");
+        prerelease.prerelease = true;
+
+        let releases = newer_releases(
+            "0.2.0",
+            vec![
+                release("v0.1.0"),
+                release("v0.2.0"),
+                release("v0.2.1"),
+                release("v0.3.0"),
+                draft,
+                prerelease,
+                release("latest"),
+            ],
+        );
+
+        let versions: Vec<_> = releases.iter().map(|r| r.version.as_str()).collect();
+        assert_eq!(versions, vec!["0.3.0", "0.2.1"]);
+        assert_eq!(releases[0].changes.as_deref(), Some("Fixes"));
+    }
+
+    #[tokio::test]
+    async fn test_new_releases_are_announced_once() {
+        let event_bus = Arc::new(EventBus::new());
+        let checker = UpdateChecker::new(UpdateConfig::default()).with_event_bus(event_bus.clone());
+        let found = || newer_releases("0.1.0", vec![release("v9.0.0")]);
+
+        checker.record(found()).await;
+        checker.record(found()).await;
+        assert_eq!(checker.available().unwrap().version, "9.0.0");
+
+        let announced = event_bus.replay_after(None);
+        assert_eq!(announced.len(), 1);
+        assert!(matches!(
+            &announced[0].event,
+            SystemEvent::ApplicationUpdateAvailable { new_version, .. } if new_version == "9.0.0"
+        ));
+
+        // The running version is the newest again
+        checker.record(Vec::new()).await;
+        assert!(checker.available().is_none());
+    }
+}
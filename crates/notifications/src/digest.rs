@@ -0,0 +1,352 @@
+//! Daily digest notifications
+//!
+//! Once a day the digest summarizes the library changes of the past period:
+//! movies added, imports, upgrades, failures and health issues. Everything is
+//! read back from the movie and history tables, so the digest is complete even
+//! if the process restarted during the day. It is sent through the same
+//! providers as real-time notifications, but only to those with
+//! `daily_digest` turned on.
+
+use chrono::{DateTime, Duration, Timelike, Utc};
+use radarr_core::{
+    domain::repositories::{HistoryRepository, MovieRepository},
+    models::{HistoryEventType, HistoryQuery, HistoryRecord, Movie, UPGRADE_DELETE_REASON},
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{debug, error, info};
+use uuid::Uuid;
+
+use crate::models::{DigestNotificationData, Notification};
+use crate::service::NotificationService;
+
+/// Most history entries read for one digest
+const MAX_DIGEST_HISTORY: i64 = 5000;
+
+/// When and how the daily digest is sent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DigestConfig {
+    pub enabled: bool,
+    /// Hour of the day (UTC) the digest goes out
+    pub send_hour_utc: u32,
+    /// Length of the summarized period in hours
+    pub period_hours: i64,
+    /// Send a digest even when nothing happened
+    pub send_when_empty: bool,
+}
+
+impl Default for DigestConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            send_hour_utc: 8,
+            period_hours: 24,
+            send_when_empty: false,
+        }
+    }
+}
+
+impl DigestConfig {
+    /// First send time strictly after `now`
+    pub fn next_run_after(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        let today = now
+            .with_hour(self.send_hour_utc.min(23))
+            .and_then(|time| time.with_minute(0))
+            .and_then(|time| time.with_second(0))
+            .and_then(|time| time.with_nanosecond(0))
+            .unwrap_or(now);
+
+        if today > now {
+            today
+        } else {
+            today + Duration::days(1)
+        }
+    }
+}
+
+/// Builds digests from the movie and history repositories
+pub struct DigestBuilder {
+    movie_repo: Arc<dyn MovieRepository>,
+    history_repo: Arc<dyn HistoryRepository>,
+}
+
+impl DigestBuilder {
+    pub fn new(
+        movie_repo: Arc<dyn MovieRepository>,
+        history_repo: Arc<dyn HistoryRepository>,
+    ) -> Self {
+        Self {
+            movie_repo,
+            history_repo,
+        }
+    }
+
+    /// Summarize the library changes between `period_start` and `period_end`
+    pub async fn build(
+        &self,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+    ) -> radarr_core::Result<DigestNotificationData> {
+        let added: Vec<Movie> = self
+            .movie_repo
+            .find_added_since(period_start)
+            .await?
+            .into_iter()
+            .filter(|movie| movie.created_at < period_end)
+            .collect();
+
+        let (records, _) = self
+            .history_repo
+            .list(&HistoryQuery {
+                event_types: vec![
+                    HistoryEventType::Imported,
+                    HistoryEventType::DownloadFailed,
+                    HistoryEventType::ImportFailed,
+                    HistoryEventType::MovieFileDeleted,
+                    HistoryEventType::HealthIssue,
+                ],
+                since: Some(period_start),
+                limit: MAX_DIGEST_HISTORY,
+                ..HistoryQuery::default()
+            })
+            .await?;
+        let records: Vec<HistoryRecord> = records
+            .into_iter()
+            .filter(|record| record.date < period_end)
+            .collect();
+
+        let mut titles: HashMap<Uuid, String> = added
+            .iter()
+            .map(|movie| (movie.id, movie_label(movie)))
+            .collect();
+        for movie_id in records.iter().filter_map(|record| record.movie_id) {
+            if titles.contains_key(&movie_id) {
+                continue;
+            }
+            if let Some(movie) = self.movie_repo.find_by_id(movie_id).await? {
+                titles.insert(movie_id, movie_label(&movie));
+            }
+        }
+
+        Ok(summarize(
+            period_start,
+            period_end,
+            &added,
+            &records,
+            &titles,
+        ))
+    }
+}
+
+/// Sends the digest once a day through a notification service
+pub struct DigestScheduler {
+    config: DigestConfig,
+    builder: DigestBuilder,
+    notifications: Arc<NotificationService>,
+}
+
+impl DigestScheduler {
+    pub fn new(
+        config: DigestConfig,
+        builder: DigestBuilder,
+        notifications: Arc<NotificationService>,
+    ) -> Self {
+        Self {
+            config,
+            builder,
+            notifications,
+        }
+    }
+
+    /// Send a digest every day at the configured hour; runs until cancelled
+    pub async fn start(self: Arc<Self>) {
+        if !self.config.enabled {
+            info!("Daily digest is disabled");
+            return;
+        }
+
+        loop {
+            let now = Utc::now();
+            let next_run = self.config.next_run_after(now);
+            info!("Next daily digest at {}", next_run);
+            let wait = (next_run - now).to_std().unwrap_or_default();
+            tokio::time::sleep(wait).await;
+
+            if let Err(e) = self.send_digest(Utc::now()).await {
+                error!("Failed to send daily digest: {}", e);
+            }
+        }
+    }
+
+    /// Build and send the digest for the period ending at `period_end`
+    ///
+    /// Returns whether a digest was sent.
+    pub async fn send_digest(&self, period_end: DateTime<Utc>) -> anyhow::Result<bool> {
+        let period_start = period_end - Duration::hours(self.config.period_hours);
+        let digest = self.builder.build(period_start, period_end).await?;
+
+        if digest.is_empty() && !self.config.send_when_empty {
+            debug!("Nothing happened since {}, skipping digest", period_start);
+            return Ok(false);
+        }
+
+        self.notifications
+            .send(Notification::digest(digest))
+            .await?;
+        Ok(true)
+    }
+}
+
+/// Sort history entries into the digest sections
+fn summarize(
+    period_start: DateTime<Utc>,
+    period_end: DateTime<Utc>,
+    added: &[Movie],
+    records: &[HistoryRecord],
+    titles: &HashMap<Uuid, String>,
+) -> DigestNotificationData {
+    let mut digest = DigestNotificationData {
+        period_start,
+        period_end,
+        movies_added: added.iter().map(movie_label).collect(),
+        ..DigestNotificationData::default()
+    };
+
+    let label = |record: &HistoryRecord| {
+        record
+            .movie_id
+            .and_then(|movie_id| titles.get(&movie_id).cloned())
+            .or_else(|| record.source_title.clone())
+            .unwrap_or_else(|| record.id.to_string())
+    };
+    let message = |record: &HistoryRecord| {
+        record
+            .data
+            .get("message")
+            .and_then(|message| message.as_str())
+            .map(str::to_string)
+    };
+
+    // History comes newest first; the digest lists oldest first
+    let mut health: Vec<(String, String)> = Vec::new();
+    for record in records.iter().rev() {
+        match record.event_type {
+            HistoryEventType::Imported => digest.imports_completed.push(label(record)),
+            HistoryEventType::DownloadFailed | HistoryEventType::ImportFailed => {
+                digest.failures.push(match message(record) {
+                    Some(message) => format!("{} ({})", label(record), message),
+                    None => label(record),
+                })
+            }
+            HistoryEventType::MovieFileDeleted => {
+                let reason = record.data.get("reason").and_then(|reason| reason.as_str());
+                if reason == Some(UPGRADE_DELETE_REASON) {
+                    digest.upgrades.push(label(record));
+                }
+            }
+            HistoryEventType::HealthIssue => {
+                // One line per component, with its latest state
+                let component = record.source_title.clone().unwrap_or_default();
+                let status = record
+                    .data
+                    .get("status")
+                    .and_then(|status| status.as_str())
+                    .unwrap_or_default();
+                let line = match message(record) {
+                    Some(message) => format!("{} {}: {}", component, status, message),
+                    None => format!("{} {}", component, status),
+                };
+                match health
+                    .iter_mut()
+                    .find(|(existing, _)| *existing == component)
+                {
+                    Some(entry) => entry.1 = line,
+                    None => health.push((component, line)),
+                }
+            }
+            _ => {}
+        }
+    }
+    digest.health_issues = health.into_iter().map(|(_, line)| line).collect();
+
+    digest
+}
+
+fn movie_label(movie: &Movie) -> String {
+    match movie.year {
+        Some(year) => format!("{} ({})", movie.title, year),
+        None => movie.title.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_next_run_after() {
+        let config = DigestConfig {
+            send_hour_utc: 8,
+            ..DigestConfig::default()
+        };
+
+        let before = Utc.with_ymd_and_hms(2024, 3, 1, 6, 30, 0).unwrap();
+        assert_eq!(
+            config.next_run_after(before),
+            Utc.with_ymd_and_hms(2024, 3, 1, 8, 0, 0).unwrap()
+        );
+
+        let at = Utc.with_ymd_and_hms(2024, 3, 1, 8, 0, 0).unwrap();
+        assert_eq!(
+            config.next_run_after(at),
+            Utc.with_ymd_and_hms(2024, 3, 2, 8, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_summarize_sorts_history_into_sections() {
+        let end = Utc.with_ymd_and_hms(2024, 3, 2, 8, 0, 0).unwrap();
+        let start = end - Duration::hours(24);
+
+        let mut dune = Movie::new(438631, "Dune".to_string());
+        dune.year = Some(2021);
+        let titles = HashMap::from([(dune.id, movie_label(&dune))]);
+
+        let health = |status: &str, message: &str| {
+            HistoryRecord::new(HistoryEventType::HealthIssue)
+                .with_source_title("qbittorrent")
+                .with_data(serde_json::json!({ "status": status, "message": message }))
+        };
+        // Newest first, as the repository returns them
+        let records = vec![
+            health("unhealthy", "connection refused"),
+            HistoryRecord::new(HistoryEventType::ImportFailed)
+                .with_source_title("/downloads/Heat.1995.mkv")
+                .with_data(serde_json::json!({ "message": "disk full" })),
+            HistoryRecord::new(HistoryEventType::MovieFileDeleted)
+                .with_movie(Some(dune.id))
+                .with_data(serde_json::json!({ "reason": UPGRADE_DELETE_REASON })),
+            HistoryRecord::new(HistoryEventType::MovieFileDeleted)
+                .with_movie(Some(dune.id))
+                .with_data(serde_json::json!({ "reason": "manual" })),
+            HistoryRecord::new(HistoryEventType::Imported).with_movie(Some(dune.id)),
+            health("degraded", "slow"),
+        ];
+
+        let digest = summarize(start, end, &[dune], &records, &titles);
+        assert_eq!(digest.movies_added, vec!["Dune (2021)"]);
+        assert_eq!(digest.imports_completed, vec!["Dune (2021)"]);
+        assert_eq!(digest.upgrades, vec!["Dune (2021)"]);
+        assert_eq!(
+            digest.failures,
+            vec!["/downloads/Heat.1995.mkv (disk full)"]
+        );
+        assert_eq!(
+            digest.health_issues,
+            vec!["qbittorrent unhealthy: connection refused"]
+        );
+        assert!(!digest.is_empty());
+    }
+}
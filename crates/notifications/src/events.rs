@@ -0,0 +1,198 @@
+//! Notifications for system events
+//!
+//! Turns grabs, imports, upgrades, health problems and available updates
+//! published on the event bus into notifications. Which providers receive them is decided by the
+//! providers themselves (see `NotificationProvider::wants_event`).
+
+use async_trait::async_trait;
+use radarr_core::{
+    domain::repositories::MovieRepository,
+    i18n::localizer,
+    models::{Movie, HEALTHY_STATUS, UPGRADE_DELETE_REASON},
+    EventEnvelope, EventHandler, SystemEvent,
+};
+use std::sync::Arc;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::models::{
+    DownloadNotificationData, HealthNotificationData, ImportNotificationData,
+    MovieNotificationData, Notification, NotificationData, NotificationEventType,
+    UpdateNotificationData,
+};
+use crate::service::NotificationService;
+
+/// Sends notifications for system events
+pub struct NotificationEventHandler {
+    notifications: Arc<NotificationService>,
+    movie_repo: Arc<dyn MovieRepository>,
+}
+
+impl NotificationEventHandler {
+    pub fn new(
+        notifications: Arc<NotificationService>,
+        movie_repo: Arc<dyn MovieRepository>,
+    ) -> Self {
+        Self {
+            notifications,
+            movie_repo,
+        }
+    }
+
+    async fn find_movie(&self, movie_id: Uuid) -> Option<Movie> {
+        match self.movie_repo.find_by_id(movie_id).await {
+            Ok(movie) => movie,
+            Err(e) => {
+                warn!(
+                    "Failed to look up movie {} for notification: {}",
+                    movie_id, e
+                );
+                None
+            }
+        }
+    }
+
+    /// The notification for an event, if the event is one we notify about
+    async fn notification_for(&self, event: &SystemEvent) -> Option<Notification> {
+        let unknown = || localizer().message(None, "notification-value-unknown", &[]);
+
+        let notification = match event {
+            SystemEvent::DownloadQueued {
+                movie_id, title, ..
+            } => {
+                let movie = self.find_movie(*movie_id).await?;
+                grab_notification(&movie, title, unknown())
+            }
+            SystemEvent::OverrideGrab {
+                movie_id: Some(movie_id),
+                title,
+                indexer,
+                ..
+            } => {
+                let movie = self.find_movie(*movie_id).await?;
+                grab_notification(&movie, title, indexer.clone())
+            }
+            SystemEvent::ImportComplete {
+                movie_id,
+                destination_path,
+                ..
+            } => {
+                let movie = self.find_movie(*movie_id).await?;
+                Notification::new(
+                    NotificationEventType::ImportCompleted,
+                    format!("Import Complete: {}", movie.title),
+                    format!("{} has been imported to {}", movie.title, destination_path),
+                    NotificationData::Import(ImportNotificationData {
+                        movie_id: movie.tmdb_id,
+                        movie_title: movie.title.clone(),
+                        source_path: String::new(),
+                        destination_path: destination_path.clone(),
+                        quality: unknown(),
+                        size: 0,
+                        status: "imported".to_string(),
+                    }),
+                )
+                .with_movie(movie)
+            }
+            SystemEvent::MovieFileDeleted {
+                movie_id, reason, ..
+            } if reason == UPGRADE_DELETE_REASON => {
+                let movie = self.find_movie(*movie_id).await?;
+                Notification::new(
+                    NotificationEventType::MovieUpgraded,
+                    format!("Movie Upgraded: {}", movie.title),
+                    format!("{} has been upgraded to a better release", movie.title),
+                    NotificationData::Movie(Box::new(MovieNotificationData {
+                        movie,
+                        action: "upgraded".to_string(),
+                    })),
+                )
+            }
+            SystemEvent::SystemHealth {
+                component,
+                status,
+                message,
+            } if !status.eq_ignore_ascii_case(HEALTHY_STATUS) => Notification::new(
+                NotificationEventType::HealthCheckFailed,
+                format!("Health Check Failed: {}", component),
+                message.clone().unwrap_or_else(|| status.clone()),
+                NotificationData::Health(HealthNotificationData {
+                    check_name: component.clone(),
+                    status: status.clone(),
+                    message: message.clone().unwrap_or_default(),
+                    details: None,
+                }),
+            ),
+            SystemEvent::ApplicationUpdateAvailable {
+                current_version,
+                new_version,
+                release_url,
+            } => Notification::new(
+                NotificationEventType::UpdateAvailable,
+                format!("Update Available: {}", new_version),
+                format!(
+                    "Version {} is available (currently running {})",
+                    new_version, current_version
+                ),
+                NotificationData::Update(UpdateNotificationData {
+                    current_version: current_version.clone(),
+                    new_version: new_version.clone(),
+                    release_notes: None,
+                    download_url: release_url.clone(),
+                }),
+            ),
+            _ => return None,
+        };
+
+        Some(notification)
+    }
+}
+
+/// Notification for a release sent to the download client
+///
+/// Download data carries an integer movie ID, so the TMDB ID is used.
+fn grab_notification(movie: &Movie, release_title: &str, indexer: String) -> Notification {
+    Notification::new(
+        NotificationEventType::DownloadStarted,
+        format!("Grabbed: {}", movie.title),
+        format!("Grabbed {} for {}", release_title, movie.title),
+        NotificationData::Download(DownloadNotificationData {
+            movie_id: movie.tmdb_id,
+            movie_title: movie.title.clone(),
+            release_title: Some(release_title.to_string()),
+            quality: release_title.to_string(),
+            size: 0,
+            indexer,
+            download_client: String::new(),
+            status: "grabbed".to_string(),
+            progress: None,
+            eta: None,
+        }),
+    )
+    .with_movie(movie.clone())
+}
+
+#[async_trait]
+impl EventHandler for NotificationEventHandler {
+    async fn handle_event(&self, envelope: &EventEnvelope) -> radarr_core::Result<()> {
+        if let Some(notification) = self.notification_for(&envelope.event).await {
+            // A failing provider must not stall event processing
+            if let Err(e) = self.notifications.send(notification).await {
+                warn!("Failed to send notification: {}", e);
+            }
+        }
+        Ok(())
+    }
+
+    fn should_handle(&self, envelope: &EventEnvelope) -> bool {
+        matches!(
+            envelope.event,
+            SystemEvent::DownloadQueued { .. }
+                | SystemEvent::OverrideGrab { .. }
+                | SystemEvent::ImportComplete { .. }
+                | SystemEvent::MovieFileDeleted { .. }
+                | SystemEvent::SystemHealth { .. }
+                | SystemEvent::ApplicationUpdateAvailable { .. }
+        )
+    }
+}
@@ -1,8 +1,12 @@
+pub mod digest;
+pub mod events;
 pub mod models;
 pub mod providers;
 pub mod service;
 pub mod templates;
 
+pub use digest::{DigestBuilder, DigestConfig, DigestScheduler};
+pub use events::NotificationEventHandler;
 pub use models::*;
 pub use providers::*;
 pub use service::NotificationService;
@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use radarr_core::models::Movie;
+use radarr_core::models::{Movie, NotificationTrigger};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
@@ -39,6 +39,23 @@ pub trait NotificationProvider: Send + Sync {
 
     /// Check if this provider is enabled
     fn is_enabled(&self) -> bool;
+
+    /// Whether this provider opted in to the daily digest
+    fn wants_digest(&self) -> bool {
+        false
+    }
+
+    /// Whether this provider should receive events of `event_type`
+    fn wants_event(&self, event_type: &NotificationEventType) -> bool {
+        let _ = event_type;
+        true
+    }
+
+    /// Whether this provider should receive events about `movie`
+    fn wants_movie(&self, movie: &Movie) -> bool {
+        let _ = movie;
+        true
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,6 +65,9 @@ pub struct Notification {
     pub message: String,
     pub data: NotificationData,
     pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Library movie the event is about, when known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub movie: Option<Movie>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -61,8 +81,11 @@ pub enum NotificationEventType {
     ImportStarted,
     ImportCompleted,
     ImportFailed,
+    MovieUpgraded,
     HealthCheckFailed,
     UpdateAvailable,
+    Digest,
+    Test,
 }
 
 impl NotificationEventType {
@@ -76,30 +99,50 @@ impl NotificationEventType {
             Self::ImportStarted => "📁",
             Self::ImportCompleted => "✅",
             Self::ImportFailed => "❌",
+            Self::MovieUpgraded => "⬆️",
             Self::HealthCheckFailed => "⚠️",
             Self::UpdateAvailable => "🆕",
+            Self::Digest => "📰",
+            Self::Test => "🧪",
         }
     }
 
     pub fn color(&self) -> u32 {
         match self {
-            Self::MovieAdded | Self::DownloadCompleted | Self::ImportCompleted => 0x00FF00, // Green
-            Self::DownloadStarted | Self::ImportStarted => 0x0099FF,                        // Blue
+            Self::MovieAdded
+            | Self::DownloadCompleted
+            | Self::ImportCompleted
+            | Self::MovieUpgraded => 0x00FF00, // Green
+            Self::DownloadStarted | Self::ImportStarted | Self::Digest | Self::Test => 0x0099FF, // Blue
             Self::MovieDeleted => 0xFFFF00, // Yellow
             Self::DownloadFailed | Self::ImportFailed | Self::HealthCheckFailed => 0xFF0000, // Red
             Self::UpdateAvailable => 0x9933FF, // Purple
         }
     }
+
+    /// Trigger a configured notification must have turned on to receive this
+    /// event; `None` for events configured notifications never receive
+    pub fn trigger(&self) -> Option<NotificationTrigger> {
+        match self {
+            Self::DownloadStarted => Some(NotificationTrigger::Grab),
+            Self::ImportCompleted => Some(NotificationTrigger::Import),
+            Self::MovieUpgraded => Some(NotificationTrigger::Upgrade),
+            Self::HealthCheckFailed => Some(NotificationTrigger::HealthIssue),
+            Self::UpdateAvailable => Some(NotificationTrigger::ApplicationUpdate),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum NotificationData {
-    Movie(MovieNotificationData),
+    Movie(Box<MovieNotificationData>),
     Download(DownloadNotificationData),
     Import(ImportNotificationData),
     Health(HealthNotificationData),
     Update(UpdateNotificationData),
+    Digest(Box<DigestNotificationData>),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -112,6 +155,9 @@ pub struct MovieNotificationData {
 pub struct DownloadNotificationData {
     pub movie_id: i32,
     pub movie_title: String,
+    /// Title of the grabbed release, when known
+    #[serde(default)]
+    pub release_title: Option<String>,
     pub quality: String,
     pub size: i64,
     pub indexer: String,
@@ -148,6 +194,31 @@ pub struct UpdateNotificationData {
     pub download_url: Option<String>,
 }
 
+/// Library changes over one digest period
+///
+/// Each list holds display labels, e.g. "Dune (2021)", oldest first.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DigestNotificationData {
+    pub period_start: chrono::DateTime<chrono::Utc>,
+    pub period_end: chrono::DateTime<chrono::Utc>,
+    pub movies_added: Vec<String>,
+    pub imports_completed: Vec<String>,
+    pub upgrades: Vec<String>,
+    pub failures: Vec<String>,
+    pub health_issues: Vec<String>,
+}
+
+impl DigestNotificationData {
+    /// Whether nothing happened during the period
+    pub fn is_empty(&self) -> bool {
+        self.movies_added.is_empty()
+            && self.imports_completed.is_empty()
+            && self.upgrades.is_empty()
+            && self.failures.is_empty()
+            && self.health_issues.is_empty()
+    }
+}
+
 impl Notification {
     pub fn new(
         event_type: NotificationEventType,
@@ -161,9 +232,16 @@ impl Notification {
             message,
             data,
             timestamp: chrono::Utc::now(),
+            movie: None,
         }
     }
 
+    /// Attach the library movie the event is about
+    pub fn with_movie(mut self, movie: Movie) -> Self {
+        self.movie = Some(movie);
+        self
+    }
+
     pub fn movie_added(movie: Movie) -> Self {
         let title = format!("Movie Added: {}", movie.title);
         let message = format!(
@@ -176,13 +254,55 @@ impl Notification {
             NotificationEventType::MovieAdded,
             title,
             message,
-            NotificationData::Movie(MovieNotificationData {
+            NotificationData::Movie(Box::new(MovieNotificationData {
                 movie,
                 action: "added".to_string(),
+            })),
+        )
+    }
+
+    /// Test notification for a provider, in the given locale
+    pub fn test(provider: &str, locale: Option<&str>) -> Self {
+        let localizer = radarr_core::i18n::localizer();
+        let provider_arg = [("provider", provider.to_string())];
+
+        Self::new(
+            NotificationEventType::Test,
+            localizer.message(locale, "notification-test-title", &[]),
+            localizer.message(locale, "notification-test-message", &[]),
+            NotificationData::Health(HealthNotificationData {
+                check_name: localizer.message(
+                    locale,
+                    "notification-test-check-name",
+                    &provider_arg,
+                ),
+                status: "success".to_string(),
+                message: localizer.message(locale, "notification-test-check-ok", &provider_arg),
+                details: None,
             }),
         )
     }
 
+    /// Daily digest; only sent to providers that opted in
+    pub fn digest(data: DigestNotificationData) -> Self {
+        let title = format!("Daily Digest: {}", data.period_end.format("%Y-%m-%d"));
+        let message = format!(
+            "{} added, {} imported, {} upgraded, {} failed, {} health issues",
+            data.movies_added.len(),
+            data.imports_completed.len(),
+            data.upgrades.len(),
+            data.failures.len(),
+            data.health_issues.len()
+        );
+
+        Self::new(
+            NotificationEventType::Digest,
+            title,
+            message,
+            NotificationData::Digest(Box::new(data)),
+        )
+    }
+
     pub fn download_completed(data: DownloadNotificationData) -> Self {
         let title = format!("Download Complete: {}", data.movie_title);
         let message = format!(
@@ -0,0 +1,221 @@
+use async_trait::async_trait;
+use radarr_core::models::{Movie, NotificationDefinition, NotificationImplementation};
+use std::sync::Arc;
+
+use crate::models::{
+    Notification, NotificationError, NotificationEventType, NotificationProvider, Result,
+};
+use crate::providers::{
+    DiscordConfig, DiscordProvider, EmailConfig, EmailProvider, GotifyConfig, GotifyProvider,
+    PushoverConfig, PushoverProvider, TelegramConfig, TelegramProvider, WebhookConfig,
+    WebhookProvider,
+};
+
+/// A provider built from a stored notification definition
+///
+/// Only receives events whose trigger is turned on in the definition, and
+/// when the definition has tags, only events about movies sharing one of
+/// them. Tests and digests bypass the triggers like they do for compiled-in
+/// providers.
+pub struct ConfiguredProvider {
+    definition: NotificationDefinition,
+    inner: Arc<dyn NotificationProvider>,
+}
+
+impl ConfiguredProvider {
+    /// Build the provider for `definition`
+    ///
+    /// Fails with `InvalidConfig` when the settings do not fit the
+    /// definition's implementation.
+    pub fn new(definition: NotificationDefinition) -> Result<Self> {
+        let inner = build_provider(definition.implementation, &definition.settings)?;
+        Ok(Self { definition, inner })
+    }
+
+    /// ID of the definition this provider was built from
+    pub fn definition_id(&self) -> i32 {
+        self.definition.id
+    }
+}
+
+/// Deserialize `settings` into the config of `implementation`
+///
+/// Definitions have no enabled flag of their own (a definition with every
+/// trigger off simply receives nothing), so `enabled` defaults to true.
+pub fn build_provider(
+    implementation: NotificationImplementation,
+    settings: &serde_json::Value,
+) -> Result<Arc<dyn NotificationProvider>> {
+    let mut settings = settings.clone();
+    match settings.as_object_mut() {
+        Some(object) => {
+            object
+                .entry("enabled")
+                .or_insert(serde_json::Value::Bool(true));
+        }
+        None => {
+            return Err(NotificationError::InvalidConfig(
+                "Notification settings must be an object".to_string(),
+            ))
+        }
+    }
+
+    let invalid = |e: serde_json::Error| {
+        NotificationError::InvalidConfig(format!("Invalid {} settings: {}", implementation, e))
+    };
+
+    let provider: Arc<dyn NotificationProvider> = match implementation {
+        NotificationImplementation::Discord => {
+            let config: DiscordConfig = serde_json::from_value(settings).map_err(invalid)?;
+            if config.webhook_url.trim().is_empty() {
+                return Err(NotificationError::InvalidConfig(
+                    "Discord webhook URL is required".to_string(),
+                ));
+            }
+            Arc::new(DiscordProvider::new(config))
+        }
+        NotificationImplementation::Email => {
+            let config: EmailConfig = serde_json::from_value(settings).map_err(invalid)?;
+            if config.to_addresses.is_empty() {
+                return Err(NotificationError::InvalidConfig(
+                    "At least one recipient address is required".to_string(),
+                ));
+            }
+            Arc::new(EmailProvider::new(config))
+        }
+        NotificationImplementation::Telegram => {
+            let config: TelegramConfig = serde_json::from_value(settings).map_err(invalid)?;
+            if config.bot_token.trim().is_empty() || config.chat_id.trim().is_empty() {
+                return Err(NotificationError::InvalidConfig(
+                    "Telegram bot token and chat ID are required".to_string(),
+                ));
+            }
+            Arc::new(TelegramProvider::new(config))
+        }
+        NotificationImplementation::Pushover => {
+            let config: PushoverConfig = serde_json::from_value(settings).map_err(invalid)?;
+            if config.api_token.trim().is_empty() || config.user_key.trim().is_empty() {
+                return Err(NotificationError::InvalidConfig(
+                    "Pushover API token and user key are required".to_string(),
+                ));
+            }
+            Arc::new(PushoverProvider::new(config))
+        }
+        NotificationImplementation::Gotify => {
+            let config: GotifyConfig = serde_json::from_value(settings).map_err(invalid)?;
+            if config.server_url.trim().is_empty() || config.app_token.trim().is_empty() {
+                return Err(NotificationError::InvalidConfig(
+                    "Gotify server URL and app token are required".to_string(),
+                ));
+            }
+            Arc::new(GotifyProvider::new(config))
+        }
+        NotificationImplementation::Webhook => {
+            let config: WebhookConfig = serde_json::from_value(settings).map_err(invalid)?;
+            if reqwest::Url::parse(&config.url).is_err() {
+                return Err(NotificationError::InvalidConfig(
+                    "Webhook URL must be an absolute URL".to_string(),
+                ));
+            }
+            Arc::new(WebhookProvider::new(config))
+        }
+    };
+
+    Ok(provider)
+}
+
+#[async_trait]
+impl NotificationProvider for ConfiguredProvider {
+    fn name(&self) -> &str {
+        &self.definition.name
+    }
+
+    async fn test(&self) -> Result<()> {
+        self.inner.test().await
+    }
+
+    async fn send(&self, notification: &Notification) -> Result<()> {
+        self.inner.send(notification).await
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.inner.is_enabled()
+    }
+
+    fn wants_digest(&self) -> bool {
+        self.inner.wants_digest()
+    }
+
+    fn wants_event(&self, event_type: &NotificationEventType) -> bool {
+        match event_type {
+            NotificationEventType::Test | NotificationEventType::Digest => true,
+            other => other
+                .trigger()
+                .is_some_and(|trigger| self.definition.triggers_on(trigger)),
+        }
+    }
+
+    fn wants_movie(&self, movie: &Movie) -> bool {
+        self.definition.applies_to(&movie.tags)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn discord_definition() -> NotificationDefinition {
+        NotificationDefinition::new(
+            "Discord".to_string(),
+            NotificationImplementation::Discord,
+            json!({ "webhook_url": "https://discord.com/api/webhooks/1/abc" }),
+        )
+    }
+
+    #[test]
+    fn test_configured_provider_respects_triggers() {
+        let mut definition = discord_definition();
+        definition.on_grab = true;
+        let provider = ConfiguredProvider::new(definition).unwrap();
+
+        assert!(provider.is_enabled());
+        assert!(provider.wants_event(&NotificationEventType::DownloadStarted));
+        assert!(!provider.wants_event(&NotificationEventType::ImportCompleted));
+        assert!(!provider.wants_event(&NotificationEventType::MovieAdded));
+        assert!(provider.wants_event(&NotificationEventType::Test));
+    }
+
+    #[test]
+    fn test_configured_provider_respects_tags() {
+        let mut definition = discord_definition();
+        definition.tags = vec![2];
+        let provider = ConfiguredProvider::new(definition).unwrap();
+
+        let mut movie = Movie::new(438631, "Dune".to_string());
+        assert!(!provider.wants_movie(&movie));
+        movie.tags = vec![1, 2];
+        assert!(provider.wants_movie(&movie));
+    }
+
+    #[test]
+    fn test_build_provider_rejects_bad_settings() {
+        assert!(build_provider(NotificationImplementation::Discord, &json!({})).is_err());
+        assert!(build_provider(
+            NotificationImplementation::Discord,
+            &json!({ "webhook_url": "" })
+        )
+        .is_err());
+        assert!(build_provider(NotificationImplementation::Email, &json!([])).is_err());
+        assert!(build_provider(
+            NotificationImplementation::Telegram,
+            &json!({ "bot_token": "123:abc", "chat_id": "" })
+        )
+        .is_err());
+        assert!(build_provider(
+            NotificationImplementation::Gotify,
+            &json!({ "server_url": "https://gotify.example.com", "app_token": "token" })
+        )
+        .is_ok());
+    }
+}
@@ -5,7 +5,8 @@ use tracing::{debug, error, info};
 
 use crate::{
     models::{Notification, NotificationError, NotificationProvider, Result},
-    NotificationData, NotificationEventType,
+    templates::NotificationTemplates,
+    NotificationData,
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,18 +15,28 @@ pub struct DiscordConfig {
     pub enabled: bool,
     pub username: Option<String>,
     pub avatar_url: Option<String>,
+    #[serde(default)]
     pub mention_everyone: bool,
+    #[serde(default)]
     pub mention_roles: Vec<String>,
+    /// Locale for messages from this provider; the instance default when unset
+    #[serde(default)]
+    pub locale: Option<String>,
+    /// Also receive the daily digest
+    #[serde(default)]
+    pub daily_digest: bool,
 }
 
 pub struct DiscordProvider {
     config: DiscordConfig,
     client: reqwest::Client,
+    templates: NotificationTemplates,
 }
 
 impl DiscordProvider {
     pub fn new(config: DiscordConfig) -> Self {
         Self {
+            templates: NotificationTemplates::for_locale(config.locale.clone()),
             config,
             client: reqwest::Client::new(),
         }
@@ -36,8 +47,11 @@ impl DiscordProvider {
         let emoji = notification.event_type.emoji();
 
         let mut embed = json!({
-            "title": format!("{} {}", emoji, notification.title),
-            "description": notification.message,
+            "title": format!("{} {}", emoji, self.templates.render_title(notification)),
+            "description": self
+                .templates
+                .render(notification)
+                .unwrap_or_else(|_| notification.message.clone()),
             "color": color,
             "timestamp": notification.timestamp.to_rfc3339(),
             "footer": {
@@ -72,17 +86,17 @@ impl DiscordProvider {
         match data {
             NotificationData::Movie(movie_data) => Some(json!([
                 {
-                    "name": "Title",
+                    "name": self.templates.translate("notification-field-title", &[]),
                     "value": movie_data.movie.title.clone(),
                     "inline": true
                 },
                 {
-                    "name": "Year",
-                    "value": movie_data.movie.year.map(|y| y.to_string()).unwrap_or_else(|| "Unknown".to_string()),
+                    "name": self.templates.translate("notification-field-year", &[]),
+                    "value": movie_data.movie.year.map(|y| y.to_string()).unwrap_or_else(|| self.templates.translate("notification-value-unknown", &[])),
                     "inline": true
                 },
                 {
-                    "name": "TMDB ID",
+                    "name": self.templates.translate("notification-field-tmdb-id", &[]),
                     "value": movie_data.movie.tmdb_id.to_string(),
                     "inline": true
                 }
@@ -90,56 +104,56 @@ impl DiscordProvider {
 
             NotificationData::Download(dl_data) => Some(json!([
                 {
-                    "name": "Movie",
+                    "name": self.templates.translate("notification-field-movie", &[]),
                     "value": dl_data.movie_title.clone(),
                     "inline": true
                 },
                 {
-                    "name": "Quality",
+                    "name": self.templates.translate("notification-field-quality", &[]),
                     "value": dl_data.quality.clone(),
                     "inline": true
                 },
                 {
-                    "name": "Size",
+                    "name": self.templates.translate("notification-field-size", &[]),
                     "value": format_size(dl_data.size),
                     "inline": true
                 },
                 {
-                    "name": "Indexer",
+                    "name": self.templates.translate("notification-field-indexer", &[]),
                     "value": dl_data.indexer.clone(),
                     "inline": true
                 },
                 {
-                    "name": "Download Client",
+                    "name": self.templates.translate("notification-field-download-client", &[]),
                     "value": dl_data.download_client.clone(),
                     "inline": true
                 },
                 {
-                    "name": "Progress",
+                    "name": self.templates.translate("notification-field-progress", &[]),
                     "value": dl_data.progress.map(|p| format!("{}%", p))
-                        .unwrap_or_else(|| "N/A".to_string()),
+                        .unwrap_or_else(|| self.templates.translate("notification-value-not-available", &[])),
                     "inline": true
                 }
             ])),
 
             NotificationData::Import(import_data) => Some(json!([
                 {
-                    "name": "Movie",
+                    "name": self.templates.translate("notification-field-movie", &[]),
                     "value": import_data.movie_title.clone(),
                     "inline": true
                 },
                 {
-                    "name": "Quality",
+                    "name": self.templates.translate("notification-field-quality", &[]),
                     "value": import_data.quality.clone(),
                     "inline": true
                 },
                 {
-                    "name": "Size",
+                    "name": self.templates.translate("notification-field-size", &[]),
                     "value": format_size(import_data.size),
                     "inline": true
                 },
                 {
-                    "name": "Destination",
+                    "name": self.templates.translate("notification-field-destination", &[]),
                     "value": truncate_path(&import_data.destination_path),
                     "inline": false
                 }
@@ -160,6 +174,10 @@ impl NotificationProvider for DiscordProvider {
         self.config.enabled
     }
 
+    fn wants_digest(&self) -> bool {
+        self.config.daily_digest
+    }
+
     async fn test(&self) -> Result<()> {
         if !self.is_enabled() {
             return Err(NotificationError::ProviderUnavailable(
@@ -167,17 +185,7 @@ impl NotificationProvider for DiscordProvider {
             ));
         }
 
-        let test_notification = Notification::new(
-            NotificationEventType::HealthCheckFailed,
-            "Test Notification".to_string(),
-            "This is a test notification from Radarr MVP".to_string(),
-            NotificationData::Health(crate::HealthNotificationData {
-                check_name: "Discord Integration".to_string(),
-                status: "success".to_string(),
-                message: "Discord webhook is configured correctly".to_string(),
-                details: None,
-            }),
-        );
+        let test_notification = Notification::test(self.name(), self.config.locale.as_deref());
 
         self.send(&test_notification).await
     }
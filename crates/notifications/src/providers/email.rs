@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 use tracing::{debug, error, info};
 
 use crate::models::{Notification, NotificationError, NotificationProvider, Result};
+use crate::templates::NotificationTemplates;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EmailConfig {
@@ -17,11 +18,18 @@ pub struct EmailConfig {
     pub from_name: Option<String>,
     pub to_addresses: Vec<String>,
     pub use_tls: bool,
+    /// Locale for messages from this provider; the instance default when unset
+    #[serde(default)]
+    pub locale: Option<String>,
+    /// Also receive the daily digest
+    #[serde(default)]
+    pub daily_digest: bool,
 }
 
 pub struct EmailProvider {
     config: EmailConfig,
     smtp: Option<AsyncSmtpTransport<Tokio1Executor>>,
+    templates: NotificationTemplates,
 }
 
 impl EmailProvider {
@@ -32,7 +40,11 @@ impl EmailProvider {
             None
         };
 
-        Self { config, smtp }
+        Self {
+            templates: NotificationTemplates::for_locale(config.locale.clone()),
+            config,
+            smtp,
+        }
     }
 
     fn create_smtp_client(config: &EmailConfig) -> Result<AsyncSmtpTransport<Tokio1Executor>> {
@@ -64,6 +76,10 @@ impl NotificationProvider for EmailProvider {
         self.config.enabled && self.smtp.is_some()
     }
 
+    fn wants_digest(&self) -> bool {
+        self.config.daily_digest
+    }
+
     async fn test(&self) -> Result<()> {
         if !self.is_enabled() {
             return Err(NotificationError::ProviderUnavailable(
@@ -72,17 +88,7 @@ impl NotificationProvider for EmailProvider {
         }
 
         // Send a test email
-        let test_notification = Notification::new(
-            crate::NotificationEventType::HealthCheckFailed,
-            "Test Email".to_string(),
-            "This is a test email from Radarr MVP".to_string(),
-            crate::NotificationData::Health(crate::HealthNotificationData {
-                check_name: "Email Integration".to_string(),
-                status: "success".to_string(),
-                message: "Email provider is configured correctly".to_string(),
-                details: None,
-            }),
-        );
+        let test_notification = Notification::test(self.name(), self.config.locale.as_deref());
 
         self.send(&test_notification).await
     }
@@ -96,7 +102,10 @@ impl NotificationProvider for EmailProvider {
         let smtp = self.smtp.as_ref().unwrap();
 
         for to_address in &self.config.to_addresses {
-            let subject = format!("[Radarr] {}", notification.title);
+            let subject = self.templates.translate(
+                "email-subject",
+                &[("title", self.templates.render_title(notification))],
+            );
             let body = self.create_email_body(notification);
 
             let from_name = self.config.from_name.as_deref().unwrap_or("Radarr MVP");
@@ -133,12 +142,23 @@ impl NotificationProvider for EmailProvider {
 
 impl EmailProvider {
     fn create_email_body(&self, notification: &Notification) -> String {
+        let label = |id: &str| self.templates.translate(id, &[]);
+        let message = self
+            .templates
+            .render(notification)
+            .unwrap_or_else(|_| notification.message.clone());
+
         format!(
-            "Subject: {}\n\nMessage: {}\n\nEvent Type: {:?}\n\nTimestamp: {}\n\n---\nThis email was sent by Radarr MVP",
-            notification.title,
-            notification.message,
+            "{}: {}\n\n{}: {}\n\n{}: {:?}\n\n{}: {}\n\n---\n{}",
+            label("email-label-subject"),
+            self.templates.render_title(notification),
+            label("email-label-message"),
+            message,
+            label("email-label-event-type"),
             notification.event_type,
-            notification.timestamp.format("%Y-%m-%d %H:%M:%S UTC")
+            label("email-label-timestamp"),
+            notification.timestamp.format("%Y-%m-%d %H:%M:%S UTC"),
+            label("email-footer")
         )
     }
 }
@@ -0,0 +1,166 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tracing::{debug, error, info};
+
+use crate::models::{Notification, NotificationError, NotificationProvider, Result};
+use crate::templates::NotificationTemplates;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GotifyConfig {
+    pub enabled: bool,
+    /// Base URL of the Gotify server
+    pub server_url: String,
+    /// Token of the Gotify application messages are posted as
+    pub app_token: String,
+    /// Message priority; Gotify clients alert from 4 up by default
+    #[serde(default = "default_priority")]
+    pub priority: i32,
+    /// Locale for messages from this provider; the instance default when unset
+    #[serde(default)]
+    pub locale: Option<String>,
+    /// Also receive the daily digest
+    #[serde(default)]
+    pub daily_digest: bool,
+}
+
+fn default_priority() -> i32 {
+    5
+}
+
+pub struct GotifyProvider {
+    config: GotifyConfig,
+    client: reqwest::Client,
+    templates: NotificationTemplates,
+}
+
+impl GotifyProvider {
+    pub fn new(config: GotifyConfig) -> Self {
+        Self {
+            templates: NotificationTemplates::for_locale(config.locale.clone()),
+            config,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    fn message_url(&self) -> String {
+        format!("{}/message", self.config.server_url.trim_end_matches('/'))
+    }
+
+    fn create_message(&self, notification: &Notification) -> serde_json::Value {
+        json!({
+            "title": format!(
+                "{} {}",
+                notification.event_type.emoji(),
+                self.templates.render_title(notification)
+            ),
+            "message": self.templates.render_markdown(notification),
+            "priority": self.config.priority,
+            "extras": {
+                "client::display": { "contentType": "text/markdown" }
+            }
+        })
+    }
+}
+
+#[async_trait]
+impl NotificationProvider for GotifyProvider {
+    fn name(&self) -> &str {
+        "Gotify"
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    fn wants_digest(&self) -> bool {
+        self.config.daily_digest
+    }
+
+    async fn test(&self) -> Result<()> {
+        if !self.is_enabled() {
+            return Err(NotificationError::ProviderUnavailable(
+                "Gotify provider is disabled".to_string(),
+            ));
+        }
+
+        let test_notification = Notification::test(self.name(), self.config.locale.as_deref());
+
+        self.send(&test_notification).await
+    }
+
+    async fn send(&self, notification: &Notification) -> Result<()> {
+        if !self.is_enabled() {
+            debug!("Gotify provider is disabled, skipping notification");
+            return Ok(());
+        }
+
+        let response = self
+            .client
+            .post(self.message_url())
+            .header("X-Gotify-Key", &self.config.app_token)
+            .json(&self.create_message(notification))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            error!("Gotify request failed with status {}: {}", status, text);
+            return Err(NotificationError::SendFailed(format!(
+                "Gotify returned status {}: {}",
+                status, text
+            )));
+        }
+
+        info!(
+            "Successfully sent Gotify notification for event: {:?}",
+            notification.event_type
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::DownloadNotificationData;
+
+    #[test]
+    fn test_grab_message_is_markdown() {
+        let config: GotifyConfig = serde_json::from_value(json!({
+            "enabled": true,
+            "server_url": "https://gotify.example.com/",
+            "app_token": "token"
+        }))
+        .unwrap();
+        let provider = GotifyProvider::new(config);
+        assert_eq!(provider.message_url(), "https://gotify.example.com/message");
+
+        let notification = Notification::download_completed(DownloadNotificationData {
+            movie_id: 438631,
+            movie_title: "Dune".to_string(),
+            release_title: None,
+            quality: "Bluray-1080p".to_string(),
+            size: 0,
+            indexer: "HDBits".to_string(),
+            download_client: String::new(),
+            status: "completed".to_string(),
+            progress: None,
+            eta: None,
+        });
+        let message = provider.create_message(&notification);
+
+        assert_eq!(message["priority"], 5);
+        assert_eq!(
+            message["extras"]["client::display"]["contentType"],
+            "text/markdown"
+        );
+        let body = message["message"].as_str().unwrap();
+        assert!(body.contains("**Quality:** Bluray-1080p"));
+        assert!(body.contains("**Indexer:** HDBits"));
+    }
+}
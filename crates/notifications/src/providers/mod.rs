@@ -1,5 +1,15 @@
+pub mod configured;
 pub mod discord;
 pub mod email;
+pub mod gotify;
+pub mod pushover;
+pub mod telegram;
+pub mod webhook;
 
-pub use discord::DiscordProvider;
-pub use email::EmailProvider;
+pub use configured::{build_provider, ConfiguredProvider};
+pub use discord::{DiscordConfig, DiscordProvider};
+pub use email::{EmailConfig, EmailProvider};
+pub use gotify::{GotifyConfig, GotifyProvider};
+pub use pushover::{PushoverConfig, PushoverProvider};
+pub use telegram::{TelegramConfig, TelegramProvider};
+pub use webhook::{WebhookConfig, WebhookMethod, WebhookProvider};
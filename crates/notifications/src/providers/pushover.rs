@@ -0,0 +1,225 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, error, info};
+
+use crate::models::{Notification, NotificationError, NotificationProvider, Result};
+use crate::templates::NotificationTemplates;
+use crate::NotificationData;
+
+const PUSHOVER_MESSAGES_URL: &str = "https://api.pushover.net/1/messages.json";
+
+/// Pushover caps titles at 250 characters and messages at 1024
+const MAX_TITLE_LENGTH: usize = 250;
+const MAX_MESSAGE_LENGTH: usize = 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PushoverConfig {
+    pub enabled: bool,
+    /// Application API token
+    pub api_token: String,
+    /// User or group key to deliver to
+    pub user_key: String,
+    /// Devices to deliver to; all of the user's devices when empty
+    #[serde(default)]
+    pub devices: Vec<String>,
+    /// Message priority from -2 (silent) to 1 (high)
+    #[serde(default)]
+    pub priority: i8,
+    /// Notification sound; the user's default when unset
+    #[serde(default)]
+    pub sound: Option<String>,
+    /// Locale for messages from this provider; the instance default when unset
+    #[serde(default)]
+    pub locale: Option<String>,
+    /// Also receive the daily digest
+    #[serde(default)]
+    pub daily_digest: bool,
+}
+
+pub struct PushoverProvider {
+    config: PushoverConfig,
+    client: reqwest::Client,
+    templates: NotificationTemplates,
+}
+
+impl PushoverProvider {
+    pub fn new(config: PushoverConfig) -> Self {
+        Self {
+            templates: NotificationTemplates::for_locale(config.locale.clone()),
+            config,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Form fields of the message for `notification`
+    fn create_params(&self, notification: &Notification) -> Vec<(&'static str, String)> {
+        let title = format!(
+            "{} {}",
+            notification.event_type.emoji(),
+            self.templates.render_title(notification)
+        );
+
+        // Emergency priority (2) needs retry settings, so it is not offered
+        let mut params = vec![
+            ("token", self.config.api_token.clone()),
+            ("user", self.config.user_key.clone()),
+            ("title", truncate(&title, MAX_TITLE_LENGTH)),
+            (
+                "message",
+                truncate(
+                    &self.templates.render_html(notification),
+                    MAX_MESSAGE_LENGTH,
+                ),
+            ),
+            ("html", "1".to_string()),
+            ("priority", self.config.priority.clamp(-2, 1).to_string()),
+            ("timestamp", notification.timestamp.timestamp().to_string()),
+        ];
+
+        if !self.config.devices.is_empty() {
+            params.push(("device", self.config.devices.join(",")));
+        }
+        if let Some(sound) = &self.config.sound {
+            params.push(("sound", sound.clone()));
+        }
+        if let Some(tmdb_id) = tmdb_id(&notification.data) {
+            params.push((
+                "url",
+                format!("https://www.themoviedb.org/movie/{}", tmdb_id),
+            ));
+            params.push(("url_title", "TMDB".to_string()));
+        }
+
+        params
+    }
+}
+
+/// TMDB ID of the notification's movie, if it has one
+fn tmdb_id(data: &NotificationData) -> Option<i32> {
+    match data {
+        NotificationData::Movie(data) => Some(data.movie.tmdb_id),
+        NotificationData::Download(data) => Some(data.movie_id),
+        NotificationData::Import(data) => Some(data.movie_id),
+        _ => None,
+    }
+    .filter(|id| *id > 0)
+}
+
+/// Cut `text` to at most `max` characters
+fn truncate(text: &str, max: usize) -> String {
+    match text.char_indices().nth(max) {
+        Some((index, _)) => text[..index].to_string(),
+        None => text.to_string(),
+    }
+}
+
+#[async_trait]
+impl NotificationProvider for PushoverProvider {
+    fn name(&self) -> &str {
+        "Pushover"
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    fn wants_digest(&self) -> bool {
+        self.config.daily_digest
+    }
+
+    async fn test(&self) -> Result<()> {
+        if !self.is_enabled() {
+            return Err(NotificationError::ProviderUnavailable(
+                "Pushover provider is disabled".to_string(),
+            ));
+        }
+
+        let test_notification = Notification::test(self.name(), self.config.locale.as_deref());
+
+        self.send(&test_notification).await
+    }
+
+    async fn send(&self, notification: &Notification) -> Result<()> {
+        if !self.is_enabled() {
+            debug!("Pushover provider is disabled, skipping notification");
+            return Ok(());
+        }
+
+        let response = self
+            .client
+            .post(PUSHOVER_MESSAGES_URL)
+            .form(&self.create_params(notification))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            error!("Pushover request failed with status {}: {}", status, text);
+            return Err(NotificationError::SendFailed(format!(
+                "Pushover returned status {}: {}",
+                status, text
+            )));
+        }
+
+        info!(
+            "Successfully sent Pushover notification for event: {:?}",
+            notification.event_type
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ImportNotificationData;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_import_message_params() {
+        let provider = PushoverProvider::new(PushoverConfig {
+            enabled: true,
+            api_token: "app-token".to_string(),
+            user_key: "user-key".to_string(),
+            devices: vec!["phone".to_string(), "tablet".to_string()],
+            priority: 2,
+            sound: None,
+            locale: None,
+            daily_digest: false,
+        });
+
+        let notification = Notification::new(
+            crate::models::NotificationEventType::ImportCompleted,
+            "Import Complete: Dune".to_string(),
+            "Dune has been imported".to_string(),
+            NotificationData::Import(ImportNotificationData {
+                movie_id: 438631,
+                movie_title: "Dune".to_string(),
+                source_path: String::new(),
+                destination_path: "/movies/Dune (2021)".to_string(),
+                quality: "Bluray-1080p".to_string(),
+                size: 0,
+                status: "imported".to_string(),
+            }),
+        );
+        let params: HashMap<_, _> = provider.create_params(&notification).into_iter().collect();
+
+        assert_eq!(params["priority"], "1");
+        assert_eq!(params["device"], "phone,tablet");
+        assert_eq!(params["html"], "1");
+        assert_eq!(params["url"], "https://www.themoviedb.org/movie/438631");
+        assert!(params["message"].contains("<b>Quality:</b> Bluray-1080p"));
+        assert!(params["message"].contains("<b>Destination:</b> /movies/Dune (2021)"));
+        assert!(!params.contains_key("sound"));
+    }
+
+    #[test]
+    fn test_truncate_respects_char_boundaries() {
+        assert_eq!(truncate("Amélie", 3), "Amé");
+        assert_eq!(truncate("Dune", 10), "Dune");
+    }
+}
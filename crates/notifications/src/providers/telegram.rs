@@ -0,0 +1,183 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tracing::{debug, error, info};
+
+use crate::models::{Notification, NotificationError, NotificationProvider, Result};
+use crate::templates::{escape_html, NotificationTemplates};
+
+const TELEGRAM_API_URL: &str = "https://api.telegram.org";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TelegramConfig {
+    pub enabled: bool,
+    /// Token of the bot sending the messages, from @BotFather
+    pub bot_token: String,
+    /// Chat, group or channel ID (or @channelname) to send to
+    pub chat_id: String,
+    /// Deliver messages without a notification sound
+    #[serde(default)]
+    pub send_silently: bool,
+    /// Bot API server; the public one when unset
+    #[serde(default)]
+    pub api_url: Option<String>,
+    /// Locale for messages from this provider; the instance default when unset
+    #[serde(default)]
+    pub locale: Option<String>,
+    /// Also receive the daily digest
+    #[serde(default)]
+    pub daily_digest: bool,
+}
+
+pub struct TelegramProvider {
+    config: TelegramConfig,
+    client: reqwest::Client,
+    templates: NotificationTemplates,
+}
+
+impl TelegramProvider {
+    pub fn new(config: TelegramConfig) -> Self {
+        Self {
+            templates: NotificationTemplates::for_locale(config.locale.clone()),
+            config,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    fn send_message_url(&self) -> String {
+        format!(
+            "{}/bot{}/sendMessage",
+            self.config
+                .api_url
+                .as_deref()
+                .unwrap_or(TELEGRAM_API_URL)
+                .trim_end_matches('/'),
+            self.config.bot_token
+        )
+    }
+
+    fn create_text(&self, notification: &Notification) -> String {
+        format!(
+            "<b>{} {}</b>\n{}",
+            notification.event_type.emoji(),
+            escape_html(&self.templates.render_title(notification)),
+            self.templates.render_html(notification)
+        )
+    }
+}
+
+#[async_trait]
+impl NotificationProvider for TelegramProvider {
+    fn name(&self) -> &str {
+        "Telegram"
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    fn wants_digest(&self) -> bool {
+        self.config.daily_digest
+    }
+
+    async fn test(&self) -> Result<()> {
+        if !self.is_enabled() {
+            return Err(NotificationError::ProviderUnavailable(
+                "Telegram provider is disabled".to_string(),
+            ));
+        }
+
+        let test_notification = Notification::test(self.name(), self.config.locale.as_deref());
+
+        self.send(&test_notification).await
+    }
+
+    async fn send(&self, notification: &Notification) -> Result<()> {
+        if !self.is_enabled() {
+            debug!("Telegram provider is disabled, skipping notification");
+            return Ok(());
+        }
+
+        let message = json!({
+            "chat_id": self.config.chat_id,
+            "text": self.create_text(notification),
+            "parse_mode": "HTML",
+            "disable_notification": self.config.send_silently,
+            "disable_web_page_preview": true
+        });
+
+        let response = self
+            .client
+            .post(self.send_message_url())
+            .json(&message)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            error!(
+                "Telegram sendMessage failed with status {}: {}",
+                status, text
+            );
+            return Err(NotificationError::SendFailed(format!(
+                "Telegram returned status {}: {}",
+                status, text
+            )));
+        }
+
+        info!(
+            "Successfully sent Telegram notification for event: {:?}",
+            notification.event_type
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::DownloadNotificationData;
+
+    #[test]
+    fn test_message_describes_release() {
+        let provider = TelegramProvider::new(TelegramConfig {
+            enabled: true,
+            bot_token: "123:abc".to_string(),
+            chat_id: "42".to_string(),
+            send_silently: false,
+            api_url: Some("http://localhost:8081/".to_string()),
+            locale: None,
+            daily_digest: false,
+        });
+        assert_eq!(
+            provider.send_message_url(),
+            "http://localhost:8081/bot123:abc/sendMessage"
+        );
+
+        let notification = Notification::download_completed(DownloadNotificationData {
+            movie_id: 438631,
+            movie_title: "Dune <Part One>".to_string(),
+            release_title: None,
+            quality: "Bluray-1080p".to_string(),
+            size: 0,
+            indexer: "HDBits".to_string(),
+            download_client: String::new(),
+            status: "completed".to_string(),
+            progress: None,
+            eta: None,
+        });
+        let text = provider.create_text(&notification);
+
+        assert!(text.starts_with("<b>✅ Download Complete: Dune &lt;Part One&gt;</b>\n"));
+        assert!(text.contains("<b>Movie:</b> Dune &lt;Part One&gt;"));
+        assert!(text.contains("<b>Quality:</b> Bluray-1080p"));
+        assert!(text.contains("<b>Indexer:</b> HDBits"));
+        // Unknown size and client are left out
+        assert!(!text.contains("<b>Size:</b>"));
+        assert!(!text.contains("<b>Download Client:</b>"));
+    }
+}
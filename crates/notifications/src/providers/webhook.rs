@@ -0,0 +1,335 @@
+//! Generic webhook provider
+//!
+//! Posts payloads shaped like Radarr v3 webhooks (`eventType`, `movie`,
+//! `remoteMovie`, `release`, `movieFile`) so integrations written against
+//! Radarr, such as Notifiarr, Overseerr or Tautulli automations, accept them
+//! unchanged. Movie IDs are UUIDs here, so `movie.id` is a string; consumers
+//! should match on `tmdbId`.
+
+use async_trait::async_trait;
+use radarr_core::models::Movie;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use tracing::{debug, error, info};
+
+use crate::models::{
+    Notification, NotificationError, NotificationEventType, NotificationProvider, Result,
+};
+use crate::NotificationData;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookMethod {
+    #[default]
+    Post,
+    Put,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    pub enabled: bool,
+    /// URL the payloads are sent to
+    pub url: String,
+    #[serde(default)]
+    pub method: WebhookMethod,
+    /// Basic auth credentials, sent when a username is set
+    #[serde(default)]
+    pub username: Option<String>,
+    #[serde(default)]
+    pub password: Option<String>,
+    /// Extra headers sent with every request
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+}
+
+pub struct WebhookProvider {
+    config: WebhookConfig,
+    client: reqwest::Client,
+}
+
+impl WebhookProvider {
+    pub fn new(config: WebhookConfig) -> Self {
+        Self {
+            config,
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+/// Radarr event type of a notification; `None` for events Radarr has no
+/// webhook for
+fn radarr_event_type(event_type: &NotificationEventType) -> Option<&'static str> {
+    match event_type {
+        NotificationEventType::Test => Some("Test"),
+        NotificationEventType::DownloadStarted => Some("Grab"),
+        NotificationEventType::ImportCompleted => Some("Download"),
+        NotificationEventType::MovieUpgraded => Some("MovieFileDelete"),
+        NotificationEventType::MovieAdded => Some("MovieAdded"),
+        NotificationEventType::MovieDeleted => Some("MovieDelete"),
+        NotificationEventType::HealthCheckFailed => Some("Health"),
+        NotificationEventType::UpdateAvailable => Some("ApplicationUpdate"),
+        _ => None,
+    }
+}
+
+/// Radarr `movie` object for a library movie
+fn movie_json(movie: &Movie) -> Value {
+    json!({
+        "id": movie.id,
+        "title": movie.title,
+        "year": movie.year.unwrap_or(0),
+        "tmdbId": movie.tmdb_id,
+        "imdbId": movie.imdb_id,
+        "overview": movie.overview(),
+    })
+}
+
+/// Radarr `remoteMovie` object for a library movie
+fn remote_movie_json(movie: &Movie) -> Value {
+    json!({
+        "tmdbId": movie.tmdb_id,
+        "imdbId": movie.imdb_id,
+        "title": movie.title,
+        "year": movie.year.unwrap_or(0),
+    })
+}
+
+/// Library movie of a notification, falling back to the movie in its data
+fn notification_movie(notification: &Notification) -> Option<&Movie> {
+    match (&notification.movie, &notification.data) {
+        (Some(movie), _) => Some(movie),
+        (None, NotificationData::Movie(data)) => Some(&data.movie),
+        _ => None,
+    }
+}
+
+/// The Radarr v3 webhook payload for `notification`, or `None` when Radarr
+/// has no webhook for its event type
+pub fn radarr_payload(notification: &Notification) -> Option<Value> {
+    let event_type = radarr_event_type(&notification.event_type)?;
+
+    let mut payload = Map::new();
+    payload.insert("eventType".to_string(), json!(event_type));
+    payload.insert("instanceName".to_string(), json!("Radarr"));
+
+    match notification_movie(notification) {
+        Some(movie) => {
+            payload.insert("movie".to_string(), movie_json(movie));
+            payload.insert("remoteMovie".to_string(), remote_movie_json(movie));
+        }
+        None => {
+            // Only the TMDB ID and title travel with download and import data
+            let known = match &notification.data {
+                NotificationData::Download(data) => Some((data.movie_id, &data.movie_title)),
+                NotificationData::Import(data) => Some((data.movie_id, &data.movie_title)),
+                _ => None,
+            };
+            if let Some((tmdb_id, title)) = known {
+                let movie = json!({ "title": title, "tmdbId": tmdb_id });
+                payload.insert("movie".to_string(), movie.clone());
+                payload.insert("remoteMovie".to_string(), movie);
+            }
+        }
+    }
+
+    match (&notification.event_type, &notification.data) {
+        (NotificationEventType::Test, _) => {
+            // Radarr's test payload uses a placeholder movie and release
+            payload.insert(
+                "movie".to_string(),
+                json!({ "id": 1, "title": "Test Title", "year": 1970, "tmdbId": 0 }),
+            );
+            payload.insert(
+                "remoteMovie".to_string(),
+                json!({ "tmdbId": 1234, "imdbId": "5678", "title": "Test title", "year": 1970 }),
+            );
+            payload.insert(
+                "release".to_string(),
+                json!({
+                    "quality": "Test Quality",
+                    "qualityVersion": 1,
+                    "releaseGroup": "Test Group",
+                    "releaseTitle": "Test Title",
+                    "indexer": "Test Indexer",
+                    "size": 9999999,
+                }),
+            );
+        }
+        (_, NotificationData::Download(data)) => {
+            payload.insert(
+                "release".to_string(),
+                json!({
+                    "quality": data.quality,
+                    "qualityVersion": 1,
+                    "releaseTitle": data.release_title.as_deref().unwrap_or(&data.movie_title),
+                    "indexer": data.indexer,
+                    "size": data.size,
+                }),
+            );
+            if !data.download_client.is_empty() {
+                payload.insert("downloadClient".to_string(), json!(data.download_client));
+            }
+        }
+        (_, NotificationData::Import(data)) => {
+            let relative_path = std::path::Path::new(&data.destination_path)
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| data.destination_path.clone());
+            payload.insert(
+                "movieFile".to_string(),
+                json!({
+                    "relativePath": relative_path,
+                    "path": data.destination_path,
+                    "quality": data.quality,
+                    "qualityVersion": 1,
+                    "size": data.size,
+                }),
+            );
+            payload.insert("isUpgrade".to_string(), json!(false));
+        }
+        (NotificationEventType::MovieUpgraded, _) => {
+            payload.insert("deleteReason".to_string(), json!("upgrade"));
+        }
+        (_, NotificationData::Health(data)) => {
+            payload.insert("level".to_string(), json!("warning"));
+            payload.insert("message".to_string(), json!(notification.message));
+            payload.insert("type".to_string(), json!(data.check_name));
+        }
+        (_, NotificationData::Update(data)) => {
+            payload.insert("message".to_string(), json!(notification.message));
+            payload.insert("previousVersion".to_string(), json!(data.current_version));
+            payload.insert("newVersion".to_string(), json!(data.new_version));
+        }
+        _ => {}
+    }
+
+    Some(Value::Object(payload))
+}
+
+#[async_trait]
+impl NotificationProvider for WebhookProvider {
+    fn name(&self) -> &str {
+        "Webhook"
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    async fn test(&self) -> Result<()> {
+        if !self.is_enabled() {
+            return Err(NotificationError::ProviderUnavailable(
+                "Webhook provider is disabled".to_string(),
+            ));
+        }
+
+        let test_notification = Notification::test(self.name(), None);
+
+        self.send(&test_notification).await
+    }
+
+    async fn send(&self, notification: &Notification) -> Result<()> {
+        if !self.is_enabled() {
+            debug!("Webhook provider is disabled, skipping notification");
+            return Ok(());
+        }
+
+        let payload = match radarr_payload(notification) {
+            Some(payload) => payload,
+            None => {
+                debug!(
+                    "No webhook payload for event {:?}, skipping notification",
+                    notification.event_type
+                );
+                return Ok(());
+            }
+        };
+
+        let mut request = match self.config.method {
+            WebhookMethod::Post => self.client.post(&self.config.url),
+            WebhookMethod::Put => self.client.put(&self.config.url),
+        };
+        for (name, value) in &self.config.headers {
+            request = request.header(name, value);
+        }
+        if let Some(username) = self.config.username.as_deref().filter(|u| !u.is_empty()) {
+            request = request.basic_auth(username, self.config.password.as_deref());
+        }
+
+        let response = request.json(&payload).send().await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            error!("Webhook request failed with status {}: {}", status, text);
+            return Err(NotificationError::SendFailed(format!(
+                "Webhook returned status {}: {}",
+                status, text
+            )));
+        }
+
+        info!(
+            "Successfully sent webhook notification for event: {:?}",
+            notification.event_type
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::DownloadNotificationData;
+
+    #[test]
+    fn test_grab_payload_matches_radarr() {
+        let mut movie = Movie::new(438631, "Dune".to_string());
+        movie.year = Some(2021);
+        movie.imdb_id = Some("tt1160419".to_string());
+
+        let notification = Notification::new(
+            NotificationEventType::DownloadStarted,
+            "Grabbed: Dune".to_string(),
+            "Grabbed Dune.2021.1080p.BluRay.x264-GROUP for Dune".to_string(),
+            NotificationData::Download(DownloadNotificationData {
+                movie_id: 438631,
+                movie_title: "Dune".to_string(),
+                release_title: Some("Dune.2021.1080p.BluRay.x264-GROUP".to_string()),
+                quality: "Bluray-1080p".to_string(),
+                size: 1024,
+                indexer: "HDBits".to_string(),
+                download_client: String::new(),
+                status: "grabbed".to_string(),
+                progress: None,
+                eta: None,
+            }),
+        )
+        .with_movie(movie);
+
+        let payload = radarr_payload(&notification).unwrap();
+        assert_eq!(payload["eventType"], "Grab");
+        assert_eq!(payload["movie"]["tmdbId"], 438631);
+        assert_eq!(payload["movie"]["year"], 2021);
+        assert_eq!(payload["remoteMovie"]["imdbId"], "tt1160419");
+        assert_eq!(
+            payload["release"]["releaseTitle"],
+            "Dune.2021.1080p.BluRay.x264-GROUP"
+        );
+        assert_eq!(payload["release"]["indexer"], "HDBits");
+        assert!(payload.get("downloadClient").is_none());
+    }
+
+    #[test]
+    fn test_payload_event_types() {
+        let test = radarr_payload(&Notification::test("Webhook", None)).unwrap();
+        assert_eq!(test["eventType"], "Test");
+        assert_eq!(test["movie"]["title"], "Test Title");
+
+        let digest = Notification::digest(Default::default());
+        assert!(radarr_payload(&digest).is_none());
+    }
+}
@@ -1,12 +1,19 @@
-use std::sync::Arc;
+use radarr_core::models::NotificationDefinition;
+use std::sync::{Arc, RwLock};
 use tokio::sync::broadcast;
 use tracing::{debug, error, info, warn};
 
-use crate::models::{Notification, NotificationProvider, Result};
+use crate::models::{Notification, NotificationEventType, NotificationProvider, Result};
+use crate::providers::ConfiguredProvider;
 
 /// Central notification service that manages multiple providers
+///
+/// Providers added in code live alongside the ones configured through the
+/// API; the latter are replaced as a whole whenever the stored definitions
+/// change.
 pub struct NotificationService {
     providers: Vec<Arc<dyn NotificationProvider>>,
+    configured: RwLock<Vec<Arc<dyn NotificationProvider>>>,
     sender: broadcast::Sender<Notification>,
 }
 
@@ -16,6 +23,7 @@ impl NotificationService {
 
         Self {
             providers: Vec::new(),
+            configured: RwLock::new(Vec::new()),
             sender,
         }
     }
@@ -26,17 +34,50 @@ impl NotificationService {
         self.providers.push(provider);
     }
 
+    /// Replace the configured providers with ones built from `definitions`
+    ///
+    /// Definitions whose settings are invalid are skipped and logged.
+    /// Returns the number of providers loaded.
+    pub fn load_definitions(&self, definitions: Vec<NotificationDefinition>) -> usize {
+        let mut providers: Vec<Arc<dyn NotificationProvider>> = Vec::new();
+        for definition in definitions {
+            let name = definition.name.clone();
+            match ConfiguredProvider::new(definition) {
+                Ok(provider) => providers.push(Arc::new(provider)),
+                Err(e) => warn!("Skipping notification '{}': {}", name, e),
+            }
+        }
+
+        let loaded = providers.len();
+        *self.configured.write().unwrap() = providers;
+        info!("Loaded {} configured notification providers", loaded);
+        loaded
+    }
+
+    /// Compiled-in and configured providers
+    fn all_providers(&self) -> Vec<Arc<dyn NotificationProvider>> {
+        let configured = self.configured.read().unwrap();
+        self.providers
+            .iter()
+            .chain(configured.iter())
+            .cloned()
+            .collect()
+    }
+
     /// Subscribe to notifications
     pub fn subscribe(&self) -> broadcast::Receiver<Notification> {
         self.sender.subscribe()
     }
 
     /// Send a notification to all enabled providers
+    ///
+    /// Digests only go to providers that opted in to them.
     pub async fn send(&self, notification: Notification) -> Result<()> {
+        let providers = self.all_providers();
         debug!(
             "Sending notification: {} to {} providers",
             notification.title,
-            self.providers.len()
+            providers.len()
         );
 
         // Broadcast to subscribers first
@@ -48,11 +89,35 @@ impl NotificationService {
         let mut sent_count = 0;
 
         // Send to all enabled providers
-        for provider in &self.providers {
+        for provider in &providers {
             if !provider.is_enabled() {
                 debug!("Skipping disabled provider: {}", provider.name());
                 continue;
             }
+            if !provider.wants_event(&notification.event_type) {
+                debug!(
+                    "Provider {} is not triggered by {:?}",
+                    provider.name(),
+                    notification.event_type
+                );
+                continue;
+            }
+            if let Some(movie) = &notification.movie {
+                if !provider.wants_movie(movie) {
+                    debug!(
+                        "Provider {} is not tagged for '{}'",
+                        provider.name(),
+                        movie.title
+                    );
+                    continue;
+                }
+            }
+            if matches!(notification.event_type, NotificationEventType::Digest)
+                && !provider.wants_digest()
+            {
+                debug!("Skipping digest for provider: {}", provider.name());
+                continue;
+            }
 
             match provider.send(&notification).await {
                 Ok(_) => {
@@ -76,7 +141,7 @@ impl NotificationService {
         info!(
             "Notification sent successfully to {}/{} providers",
             sent_count,
-            self.providers.len()
+            providers.len()
         );
 
         Ok(())
@@ -86,7 +151,7 @@ impl NotificationService {
     pub async fn test_all(&self) -> Vec<(String, Result<()>)> {
         let mut results = Vec::new();
 
-        for provider in &self.providers {
+        for provider in &self.all_providers() {
             let result = provider.test().await;
             results.push((provider.name().to_string(), result));
         }
@@ -96,7 +161,7 @@ impl NotificationService {
 
     /// Get list of enabled providers
     pub fn get_enabled_providers(&self) -> Vec<String> {
-        self.providers
+        self.all_providers()
             .iter()
             .filter(|p| p.is_enabled())
             .map(|p| p.name().to_string())
@@ -105,11 +170,14 @@ impl NotificationService {
 
     /// Get total number of providers
     pub fn provider_count(&self) -> usize {
-        self.providers.len()
+        self.providers.len() + self.configured.read().unwrap().len()
     }
 
     /// Get number of enabled providers
     pub fn enabled_provider_count(&self) -> usize {
-        self.providers.iter().filter(|p| p.is_enabled()).count()
+        self.all_providers()
+            .iter()
+            .filter(|p| p.is_enabled())
+            .count()
     }
 }
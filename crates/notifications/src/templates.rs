@@ -1,144 +1,325 @@
-use handlebars::Handlebars;
-use serde_json::json;
+use radarr_core::i18n::{localizer, MessageArg};
 
-use crate::models::{Notification, NotificationData, NotificationError, Result};
+use crate::models::{
+    DigestNotificationData, Notification, NotificationData, NotificationEventType, Result,
+};
+
+/// Items listed per digest section before the rest is summarized as a count
+const DIGEST_ITEMS_PER_SECTION: usize = 10;
 
 /// Template engine for formatting notifications
+///
+/// Titles and messages come from the localization catalogs in `radarr-core`,
+/// rendered in the template's locale or the instance default.
 pub struct NotificationTemplates {
-    handlebars: Handlebars<'static>,
+    locale: Option<String>,
 }
 
 impl NotificationTemplates {
     pub fn new() -> Result<Self> {
-        let mut handlebars = Handlebars::new();
-
-        // Register built-in templates
-        Self::register_builtin_templates(&mut handlebars)?;
-
-        Ok(Self { handlebars })
-    }
-
-    fn register_builtin_templates(hb: &mut Handlebars) -> Result<()> {
-        // Movie notification templates
-        hb.register_template_string(
-            "movie_added",
-            "🎬 **{{title}}** ({{year}}) has been added to your library",
-        )
-        .map_err(|e| NotificationError::TemplateError(e.to_string()))?;
-
-        hb.register_template_string(
-            "movie_deleted",
-            "🗑️ **{{title}}** has been removed from your library",
-        )
-        .map_err(|e| NotificationError::TemplateError(e.to_string()))?;
-
-        // Download notification templates
-        hb.register_template_string(
-            "download_started",
-            "⬇️ Started downloading **{{movie_title}}** ({{quality}}) from {{indexer}}",
-        )
-        .map_err(|e| NotificationError::TemplateError(e.to_string()))?;
-
-        hb.register_template_string(
-            "download_completed",
-            "✅ **{{movie_title}}** ({{quality}}) download completed! Size: {{size}}",
-        )
-        .map_err(|e| NotificationError::TemplateError(e.to_string()))?;
-
-        hb.register_template_string(
-            "download_failed",
-            "❌ Download failed for **{{movie_title}}** ({{quality}})",
-        )
-        .map_err(|e| NotificationError::TemplateError(e.to_string()))?;
-
-        // Import notification templates
-        hb.register_template_string(
-            "import_completed",
-            "📁 **{{movie_title}}** has been imported to your library at {{destination_path}}",
-        )
-        .map_err(|e| NotificationError::TemplateError(e.to_string()))?;
-
-        hb.register_template_string(
-            "import_failed",
-            "❌ Failed to import **{{movie_title}}** - {{error_message}}",
-        )
-        .map_err(|e| NotificationError::TemplateError(e.to_string()))?;
-
-        Ok(())
+        Ok(Self { locale: None })
+    }
+
+    /// Templates rendering in `locale`, or the instance default when `None`
+    pub fn for_locale(locale: Option<String>) -> Self {
+        Self { locale }
+    }
+
+    /// Render in a specific locale instead of the instance default
+    pub fn with_locale(mut self, locale: Option<String>) -> Self {
+        self.locale = locale;
+        self
     }
 
     pub fn render(&self, notification: &Notification) -> Result<String> {
-        let template_name = self.get_template_name(&notification.event_type);
-        let context = self.create_template_context(notification);
+        let (_, message_id) = Self::message_ids(&notification.event_type);
+        Ok(self.translate(message_id, &self.create_template_args(notification)))
+    }
+
+    /// Render the notification's title
+    pub fn render_title(&self, notification: &Notification) -> String {
+        let (title_id, _) = Self::message_ids(&notification.event_type);
+        self.translate(title_id, &self.create_template_args(notification))
+    }
+
+    /// Labeled details of the notification's movie, release and client
+    ///
+    /// Values that are unknown (empty, or a size of zero) are left out.
+    pub fn details(&self, data: &NotificationData) -> Vec<(String, String)> {
+        let size = |bytes: i64| (bytes > 0).then(|| self.format_size(bytes));
+
+        let details: Vec<(String, Option<String>)> = match data {
+            NotificationData::Movie(data) => vec![
+                (
+                    self.translate("notification-field-title", &[]),
+                    Some(data.movie.title.clone()),
+                ),
+                (
+                    self.translate("notification-field-year", &[]),
+                    data.movie.year.map(|year| year.to_string()),
+                ),
+                (
+                    self.translate("notification-field-tmdb-id", &[]),
+                    Some(data.movie.tmdb_id.to_string()),
+                ),
+            ],
+            NotificationData::Download(data) => vec![
+                (
+                    self.translate("notification-field-movie", &[]),
+                    Some(data.movie_title.clone()),
+                ),
+                (
+                    self.translate("notification-field-quality", &[]),
+                    Some(data.quality.clone()),
+                ),
+                (
+                    self.translate("notification-field-size", &[]),
+                    size(data.size),
+                ),
+                (
+                    self.translate("notification-field-indexer", &[]),
+                    Some(data.indexer.clone()),
+                ),
+                (
+                    self.translate("notification-field-download-client", &[]),
+                    Some(data.download_client.clone()),
+                ),
+            ],
+            NotificationData::Import(data) => vec![
+                (
+                    self.translate("notification-field-movie", &[]),
+                    Some(data.movie_title.clone()),
+                ),
+                (
+                    self.translate("notification-field-quality", &[]),
+                    Some(data.quality.clone()),
+                ),
+                (
+                    self.translate("notification-field-size", &[]),
+                    size(data.size),
+                ),
+                (
+                    self.translate("notification-field-destination", &[]),
+                    Some(data.destination_path.clone()),
+                ),
+            ],
+            _ => Vec::new(),
+        };
+
+        details
+            .into_iter()
+            .filter_map(|(label, value)| {
+                value
+                    .filter(|value| !value.is_empty())
+                    .map(|value| (label, value))
+            })
+            .collect()
+    }
+
+    /// Message and details as the HTML subset Telegram and Pushover accept
+    pub fn render_html(&self, notification: &Notification) -> String {
+        let message = self
+            .render(notification)
+            .unwrap_or_else(|_| notification.message.clone());
+        let mut html = markdown_bold_to_html(&message);
 
-        self.handlebars
-            .render(&template_name, &context)
-            .map_err(|e| NotificationError::TemplateError(e.to_string()))
+        let details = self.details(&notification.data);
+        if !details.is_empty() {
+            html.push('\n');
+            for (label, value) in details {
+                html.push_str(&format!(
+                    "\n<b>{}:</b> {}",
+                    escape_html(&label),
+                    escape_html(&value)
+                ));
+            }
+        }
+        html
+    }
+
+    /// Message and details as Markdown
+    pub fn render_markdown(&self, notification: &Notification) -> String {
+        let mut markdown = self
+            .render(notification)
+            .unwrap_or_else(|_| notification.message.clone());
+
+        let details = self.details(&notification.data);
+        if !details.is_empty() {
+            markdown.push('\n');
+            // Trailing double spaces keep the lines from being joined
+            for (label, value) in details {
+                markdown.push_str(&format!("\n**{}:** {}  ", label, value));
+            }
+        }
+        markdown
     }
 
-    fn get_template_name(&self, event_type: &crate::NotificationEventType) -> String {
+    /// Translate a single catalog entry, e.g. a field label
+    pub fn translate(&self, id: &str, args: &[MessageArg<'_>]) -> String {
+        localizer().message(self.locale.as_deref(), id, args)
+    }
+
+    fn message_ids(event_type: &NotificationEventType) -> (&'static str, &'static str) {
         match event_type {
-            crate::NotificationEventType::MovieAdded => "movie_added",
-            crate::NotificationEventType::MovieDeleted => "movie_deleted",
-            crate::NotificationEventType::DownloadStarted => "download_started",
-            crate::NotificationEventType::DownloadCompleted => "download_completed",
-            crate::NotificationEventType::DownloadFailed => "download_failed",
-            crate::NotificationEventType::ImportCompleted => "import_completed",
-            crate::NotificationEventType::ImportFailed => "import_failed",
-            _ => "default",
+            NotificationEventType::MovieAdded => {
+                ("notification-movie-added-title", "notification-movie-added")
+            }
+            NotificationEventType::MovieDeleted => (
+                "notification-movie-deleted-title",
+                "notification-movie-deleted",
+            ),
+            NotificationEventType::DownloadStarted => (
+                "notification-download-started-title",
+                "notification-download-started",
+            ),
+            NotificationEventType::DownloadCompleted => (
+                "notification-download-completed-title",
+                "notification-download-completed",
+            ),
+            NotificationEventType::DownloadFailed => (
+                "notification-download-failed-title",
+                "notification-download-failed",
+            ),
+            NotificationEventType::ImportStarted => (
+                "notification-import-started-title",
+                "notification-import-started",
+            ),
+            NotificationEventType::ImportCompleted => (
+                "notification-import-completed-title",
+                "notification-import-completed",
+            ),
+            NotificationEventType::ImportFailed => (
+                "notification-import-failed-title",
+                "notification-import-failed",
+            ),
+            NotificationEventType::MovieUpgraded => (
+                "notification-movie-upgraded-title",
+                "notification-movie-upgraded",
+            ),
+            NotificationEventType::HealthCheckFailed => (
+                "notification-health-check-failed-title",
+                "notification-health-check-failed",
+            ),
+            NotificationEventType::UpdateAvailable => (
+                "notification-update-available-title",
+                "notification-update-available",
+            ),
+            NotificationEventType::Digest => ("notification-digest-title", "notification-digest"),
+            NotificationEventType::Test => ("notification-test-title", "notification-test-message"),
         }
-        .to_string()
     }
 
-    fn create_template_context(&self, notification: &Notification) -> serde_json::Value {
-        let mut context = json!({
-            "title": notification.title,
-            "message": notification.message,
-            "timestamp": notification.timestamp,
-            "event_type": notification.event_type
-        });
+    fn create_template_args(&self, notification: &Notification) -> Vec<MessageArg<'static>> {
+        let mut args = vec![
+            ("title", notification.title.clone()),
+            ("message", notification.message.clone()),
+            ("error_message", notification.message.clone()),
+            ("timestamp", notification.timestamp.to_rfc3339()),
+        ];
 
         // Add specific data based on notification type
         match &notification.data {
             NotificationData::Movie(data) => {
-                context["title"] = json!(data.movie.title);
-                context["year"] = json!(data.movie.year);
-                context["action"] = json!(data.action);
+                let year = data
+                    .movie
+                    .year
+                    .map(|year| year.to_string())
+                    .unwrap_or_else(|| self.translate("notification-value-unknown", &[]));
+                set_arg(&mut args, "title", data.movie.title.clone());
+                args.push(("year", year));
+                args.push(("action", data.action.clone()));
             }
             NotificationData::Download(data) => {
-                context["movie_title"] = json!(data.movie_title);
-                context["quality"] = json!(data.quality);
-                context["size"] = json!(self.format_size(data.size));
-                context["indexer"] = json!(data.indexer);
-                context["download_client"] = json!(data.download_client);
-                context["status"] = json!(data.status);
+                args.push(("movie_title", data.movie_title.clone()));
+                args.push(("quality", data.quality.clone()));
+                args.push(("size", self.format_size(data.size)));
+                args.push(("indexer", data.indexer.clone()));
+                args.push(("download_client", data.download_client.clone()));
+                args.push(("status", data.status.clone()));
                 if let Some(progress) = data.progress {
-                    context["progress"] = json!(format!("{}%", progress));
+                    args.push(("progress", format!("{}%", progress)));
                 }
             }
             NotificationData::Import(data) => {
-                context["movie_title"] = json!(data.movie_title);
-                context["quality"] = json!(data.quality);
-                context["size"] = json!(self.format_size(data.size));
-                context["source_path"] = json!(data.source_path);
-                context["destination_path"] = json!(data.destination_path);
-                context["status"] = json!(data.status);
+                args.push(("movie_title", data.movie_title.clone()));
+                args.push(("quality", data.quality.clone()));
+                args.push(("size", self.format_size(data.size)));
+                args.push(("source_path", data.source_path.clone()));
+                args.push(("destination_path", data.destination_path.clone()));
+                args.push(("status", data.status.clone()));
             }
             NotificationData::Health(data) => {
-                context["check_name"] = json!(data.check_name);
-                context["status"] = json!(data.status);
-                context["error_message"] = json!(data.message);
+                args.push(("check_name", data.check_name.clone()));
+                args.push(("status", data.status.clone()));
+                set_arg(&mut args, "error_message", data.message.clone());
             }
             NotificationData::Update(data) => {
-                context["current_version"] = json!(data.current_version);
-                context["new_version"] = json!(data.new_version);
+                args.push(("current_version", data.current_version.clone()));
+                args.push(("new_version", data.new_version.clone()));
                 if let Some(notes) = &data.release_notes {
-                    context["release_notes"] = json!(notes);
+                    args.push(("release_notes", notes.clone()));
                 }
             }
+            NotificationData::Digest(data) => {
+                args.push(("date", data.period_end.format("%Y-%m-%d").to_string()));
+                args.push((
+                    "period_start",
+                    data.period_start.format("%Y-%m-%d %H:%M UTC").to_string(),
+                ));
+                args.push(("movies_added", data.movies_added.len().to_string()));
+                args.push(("imports", data.imports_completed.len().to_string()));
+                args.push(("upgrades", data.upgrades.len().to_string()));
+                args.push(("failures", data.failures.len().to_string()));
+                args.push(("health_issues", data.health_issues.len().to_string()));
+                args.push(("details", self.digest_details(data)));
+            }
         }
 
-        context
+        args
+    }
+
+    /// One line per non-empty digest section, e.g. "Added: Dune (2021), Heat (1995)"
+    fn digest_details(&self, data: &DigestNotificationData) -> String {
+        let sections = [
+            ("notification-digest-section-added", &data.movies_added),
+            (
+                "notification-digest-section-imported",
+                &data.imports_completed,
+            ),
+            ("notification-digest-section-upgraded", &data.upgrades),
+            ("notification-digest-section-failed", &data.failures),
+            ("notification-digest-section-health", &data.health_issues),
+        ];
+
+        let lines: Vec<String> = sections
+            .into_iter()
+            .filter(|(_, items)| !items.is_empty())
+            .map(|(heading_id, items)| {
+                let mut listed = items
+                    .iter()
+                    .take(DIGEST_ITEMS_PER_SECTION)
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                if items.len() > DIGEST_ITEMS_PER_SECTION {
+                    listed.push(' ');
+                    listed.push_str(&self.translate(
+                        "notification-digest-more",
+                        &[(
+                            "count",
+                            (items.len() - DIGEST_ITEMS_PER_SECTION).to_string(),
+                        )],
+                    ));
+                }
+                format!("{}: {}", self.translate(heading_id, &[]), listed)
+            })
+            .collect();
+
+        if lines.is_empty() {
+            self.translate("notification-digest-quiet", &[])
+        } else {
+            lines.join("\n")
+        }
     }
 
     fn format_size(&self, bytes: i64) -> String {
@@ -154,3 +335,111 @@ impl NotificationTemplates {
         format!("{:.2} {}", size, UNITS[unit_index])
     }
 }
+
+/// Escape text for inclusion in HTML
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Escape `text` and turn its `**bold**` spans into `<b>` tags
+///
+/// Catalog messages use Discord-style Markdown; this is the only markup
+/// they contain.
+fn markdown_bold_to_html(text: &str) -> String {
+    let mut html = String::new();
+    for (index, part) in escape_html(text).split("**").enumerate() {
+        if index % 2 == 1 {
+            html.push_str("<b>");
+            html.push_str(part);
+            html.push_str("</b>");
+        } else {
+            html.push_str(part);
+        }
+    }
+    html
+}
+
+/// Replace an argument that was already set from the generic fields
+fn set_arg(args: &mut Vec<MessageArg<'static>>, name: &'static str, value: String) {
+    match args.iter_mut().find(|(existing, _)| *existing == name) {
+        Some(arg) => arg.1 = value,
+        None => args.push((name, value)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{DigestNotificationData, DownloadNotificationData};
+
+    fn download_completed() -> Notification {
+        Notification::download_completed(DownloadNotificationData {
+            movie_id: 1,
+            movie_title: "Dune".to_string(),
+            release_title: None,
+            quality: "Bluray-1080p".to_string(),
+            size: 2 * 1024 * 1024 * 1024,
+            indexer: "HDBits".to_string(),
+            download_client: "qBittorrent".to_string(),
+            status: "completed".to_string(),
+            progress: Some(100.0),
+            eta: None,
+        })
+    }
+
+    #[test]
+    fn test_render_uses_template_locale() {
+        let notification = download_completed();
+
+        let english = NotificationTemplates::new().unwrap();
+        assert_eq!(
+            english.render(&notification).unwrap(),
+            "✅ **Dune** (Bluray-1080p) download completed! Size: 2.00 GB"
+        );
+        assert_eq!(
+            english.render_title(&notification),
+            "Download Complete: Dune"
+        );
+
+        let german = NotificationTemplates::new()
+            .unwrap()
+            .with_locale(Some("de-DE".to_string()));
+        assert_eq!(
+            german.render_title(&notification),
+            "Download abgeschlossen: Dune"
+        );
+    }
+
+    #[test]
+    fn test_render_digest_lists_sections() {
+        let period_end = chrono::DateTime::parse_from_rfc3339("2024-03-02T08:00:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let mut data = DigestNotificationData {
+            period_start: period_end - chrono::Duration::hours(24),
+            period_end,
+            movies_added: vec!["Dune (2021)".to_string()],
+            ..DigestNotificationData::default()
+        };
+        data.failures = (1..=12).map(|n| format!("Movie {}", n)).collect();
+
+        let templates = NotificationTemplates::new().unwrap();
+        let notification = Notification::digest(data);
+        assert_eq!(
+            templates.render_title(&notification),
+            "Daily Digest: 2024-03-02"
+        );
+
+        let rendered = templates.render(&notification).unwrap();
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(
+            lines[0],
+            "📰 Since 2024-03-01 08:00 UTC: 1 added, 0 imported, 0 upgraded, 12 failed, 0 health issues"
+        );
+        assert_eq!(lines[1], "Added: Dune (2021)");
+        assert!(lines[2].starts_with("Failed: Movie 1, Movie 2"));
+        assert!(lines[2].ends_with("Movie 10 and 2 more"));
+    }
+}
@@ -108,6 +108,7 @@ impl IndexerService {
                 tmdb_id: None,
                 categories: vec![2000], // Movies
                 indexer_ids: vec![],
+                excluded_indexer_ids: vec![],
                 limit,
                 offset: None,
                 min_seeders: Some(1),
@@ -159,6 +159,8 @@ impl DownloadClientService for MockDownloadClient {
             seeders: Some(10),
             leechers: Some(3),
             save_path: Some("/downloads".to_string()),
+            ratio: None,
+            seeding_time_seconds: None,
         };
 
         let mut downloads = self.downloads.write().await;
@@ -0,0 +1,6 @@
+-- Track consecutive unsuccessful automatic searches per movie so the
+-- search cooldown can space out searches for movies that keep coming back empty
+
+ALTER TABLE movies ADD COLUMN IF NOT EXISTS failed_search_count INTEGER NOT NULL DEFAULT 0;
+
+CREATE INDEX IF NOT EXISTS idx_movies_last_search_time ON movies(last_search_time);
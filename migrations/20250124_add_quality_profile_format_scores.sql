@@ -0,0 +1,5 @@
+-- Custom format score thresholds for quality profiles, exposed through the
+-- v3 qualityprofile API as minFormatScore/cutoffFormatScore
+
+ALTER TABLE quality_profiles ADD COLUMN IF NOT EXISTS min_format_score INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE quality_profiles ADD COLUMN IF NOT EXISTS cutoff_format_score INTEGER NOT NULL DEFAULT 0;
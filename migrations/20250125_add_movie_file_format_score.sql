@@ -0,0 +1,4 @@
+-- Custom format score of the release each library file was imported from,
+-- compared against candidate releases when deciding on upgrades
+
+ALTER TABLE movie_files ADD COLUMN IF NOT EXISTS custom_format_score INTEGER NOT NULL DEFAULT 0;
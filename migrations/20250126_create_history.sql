@@ -0,0 +1,20 @@
+-- Persistent history of grabs, imports, failures and deletions
+--
+-- Rows are written from system events. movie_id deliberately has no foreign
+-- key so that history survives the movie being removed from the library.
+
+CREATE TABLE IF NOT EXISTS history (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    event_type VARCHAR(50) NOT NULL,
+    movie_id UUID,
+    source_title TEXT,
+    download_id VARCHAR(255),
+    data JSONB NOT NULL DEFAULT '{}',
+    event_id UUID UNIQUE,
+    correlation_id UUID,
+    date TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_history_date ON history(date DESC);
+CREATE INDEX IF NOT EXISTS idx_history_movie_id_date ON history(movie_id, date DESC);
+CREATE INDEX IF NOT EXISTS idx_history_event_type_date ON history(event_type, date DESC);
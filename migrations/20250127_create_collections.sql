@@ -0,0 +1,22 @@
+-- Movie collections synced from TMDB
+--
+-- movies holds the collection's films as last fetched from TMDB; whether
+-- each one is in the library is worked out against the movies table.
+
+CREATE TABLE IF NOT EXISTS collections (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tmdb_id INTEGER NOT NULL UNIQUE,
+    title TEXT NOT NULL,
+    overview TEXT,
+    poster_path TEXT,
+    backdrop_path TEXT,
+    monitored BOOLEAN NOT NULL DEFAULT false,
+    quality_profile_id INTEGER,
+    minimum_availability VARCHAR(20) NOT NULL DEFAULT 'released',
+    movies JSONB NOT NULL DEFAULT '[]',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_info_sync TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_collections_monitored ON collections(monitored);
@@ -0,0 +1,17 @@
+-- Latest capability probe and canary search of each indexer
+--
+-- One row per indexer, replaced every time the indexer is checked.
+
+CREATE TABLE IF NOT EXISTS indexer_checks (
+    indexer_id INTEGER PRIMARY KEY REFERENCES indexers(id) ON DELETE CASCADE,
+    status VARCHAR(20) NOT NULL,
+    message TEXT NOT NULL DEFAULT '',
+    movie_search BOOLEAN NOT NULL DEFAULT false,
+    search_params JSONB NOT NULL DEFAULT '[]',
+    canary_query TEXT,
+    canary_results INTEGER NOT NULL DEFAULT 0,
+    parseable_results INTEGER NOT NULL DEFAULT 0,
+    sample_titles JSONB NOT NULL DEFAULT '[]',
+    duration_ms BIGINT NOT NULL DEFAULT 0,
+    checked_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,11 @@
+-- Library root folders movies are imported into
+
+CREATE TABLE IF NOT EXISTS root_folders (
+    id SERIAL PRIMARY KEY,
+    path TEXT NOT NULL UNIQUE,
+    default_quality_profile_id INTEGER REFERENCES quality_profiles(id) ON DELETE SET NULL,
+    -- Imports that would leave less free space than this are refused
+    min_free_space_bytes BIGINT NOT NULL DEFAULT 104857600,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,15 @@
+-- User-configured notification providers with per-event triggers
+
+CREATE TABLE IF NOT EXISTS notifications (
+    id SERIAL PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    -- Provider the settings are for: discord, email
+    implementation TEXT NOT NULL,
+    settings JSONB NOT NULL DEFAULT '{}',
+    on_grab BOOLEAN NOT NULL DEFAULT FALSE,
+    on_import BOOLEAN NOT NULL DEFAULT FALSE,
+    on_upgrade BOOLEAN NOT NULL DEFAULT FALSE,
+    on_health_issue BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,4 @@
+-- Indexers that are never searched for a movie (e.g. public trackers for
+-- niche content). IDs are the indexer IDs reported on search results.
+
+ALTER TABLE movies ADD COLUMN IF NOT EXISTS excluded_indexer_ids INTEGER[] NOT NULL DEFAULT '{}';
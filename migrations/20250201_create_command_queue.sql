@@ -0,0 +1,26 @@
+-- Commands of the command queue, stored so queued and running commands
+-- survive a restart
+--
+-- 001_initial_schema.sql already has a `commands` table with UUID IDs and
+-- JSON progress, so the queue gets a table of its own.
+
+CREATE TABLE IF NOT EXISTS command_queue (
+    -- Assigned by the queue, continuing after the highest stored ID
+    id INTEGER PRIMARY KEY,
+    -- RefreshMovie, RssSync, ImportListSync, DownloadedMoviesScan
+    name TEXT NOT NULL,
+    body JSONB NOT NULL DEFAULT '{}',
+    status TEXT NOT NULL,
+    -- manual, scheduled
+    trigger TEXT NOT NULL,
+    message TEXT,
+    progress REAL,
+    queued_at TIMESTAMPTZ NOT NULL,
+    started_at TIMESTAMPTZ,
+    ended_at TIMESTAMPTZ,
+    CONSTRAINT chk_command_queue_status
+        CHECK (status IN ('queued', 'started', 'completed', 'failed'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_command_queue_unfinished ON command_queue (id)
+    WHERE status IN ('queued', 'started');
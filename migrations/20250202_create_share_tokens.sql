@@ -0,0 +1,13 @@
+-- Read-only share tokens with an expiry; only the token's hash is stored
+
+CREATE TABLE IF NOT EXISTS share_tokens (
+    id UUID PRIMARY KEY,
+    name TEXT NOT NULL,
+    -- Hex SHA-256 digest of the token
+    token_hash TEXT NOT NULL UNIQUE,
+    -- library, calendar
+    scopes TEXT[] NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    revoked_at TIMESTAMPTZ
+);
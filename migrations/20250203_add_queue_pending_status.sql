@@ -0,0 +1,7 @@
+-- Grabs held back while their root folder is low on space wait as 'pending'
+
+ALTER TABLE queue ADD COLUMN IF NOT EXISTS root_folder_path TEXT;
+
+ALTER TABLE queue DROP CONSTRAINT IF EXISTS chk_queue_status;
+ALTER TABLE queue ADD CONSTRAINT chk_queue_status
+    CHECK (status IN ('queued', 'pending', 'downloading', 'completed', 'failed', 'cancelled', 'paused', 'stalled', 'seeding'));
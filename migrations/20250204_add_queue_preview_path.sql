@@ -0,0 +1,3 @@
+-- Staged early-import preview of a sequentially downloaded torrent
+
+ALTER TABLE queue ADD COLUMN IF NOT EXISTS preview_path TEXT;
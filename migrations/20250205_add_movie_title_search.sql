@@ -0,0 +1,47 @@
+-- Fuzzy, accent-insensitive library title search
+--
+-- Titles, original titles and alternative titles are folded into one
+-- lowercased, unaccented search_text column backed by a trigram index for
+-- substring and fuzzy matching and a full-text index for word matching.
+
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+CREATE EXTENSION IF NOT EXISTS unaccent;
+
+-- unaccent() is only STABLE because its dictionary can change; pinning the
+-- dictionary makes it safe to use in a generated column and index
+CREATE OR REPLACE FUNCTION immutable_unaccent(value TEXT)
+RETURNS TEXT
+LANGUAGE sql IMMUTABLE PARALLEL SAFE STRICT
+AS $$ SELECT public.unaccent('public.unaccent'::regdictionary, value) $$;
+
+-- Lowercased, unaccented titles with punctuation collapsed to single spaces;
+-- matches normalize_search_title() in the core crate
+CREATE OR REPLACE FUNCTION movie_search_text(
+    title TEXT,
+    original_title TEXT,
+    alternative_titles JSONB
+)
+RETURNS TEXT
+LANGUAGE sql IMMUTABLE PARALLEL SAFE
+AS $$
+    SELECT btrim(regexp_replace(
+        lower(immutable_unaccent(concat_ws(' ',
+            title,
+            original_title,
+            (SELECT string_agg(alt, ' ')
+             FROM jsonb_array_elements_text(
+                 CASE WHEN jsonb_typeof(alternative_titles) = 'array'
+                      THEN alternative_titles ELSE '[]'::jsonb END
+             ) AS alt)
+        ))),
+        '[^[:alnum:]]+', ' ', 'g'
+    ))
+$$;
+
+ALTER TABLE movies ADD COLUMN IF NOT EXISTS search_text TEXT
+    GENERATED ALWAYS AS (movie_search_text(title, original_title, alternative_titles)) STORED;
+
+CREATE INDEX IF NOT EXISTS idx_movies_search_text_trgm
+    ON movies USING GIN (search_text gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_movies_search_text_fts
+    ON movies USING GIN (to_tsvector('simple', search_text));
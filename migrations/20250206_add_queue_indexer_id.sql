@@ -0,0 +1,3 @@
+-- Indexer a queued release was grabbed from, whose seed goals apply after import
+
+ALTER TABLE queue ADD COLUMN IF NOT EXISTS indexer_id INTEGER;
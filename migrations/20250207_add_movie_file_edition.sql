@@ -0,0 +1,4 @@
+-- Edition of each library file (Director's Cut, Extended, ...), so a movie
+-- can keep a file of each edition and upgrades only replace their own edition
+
+ALTER TABLE movie_files ADD COLUMN IF NOT EXISTS edition TEXT;
@@ -0,0 +1,101 @@
+-- Original and alternative titles of each movie, one row per title
+--
+-- Rows are kept in sync with movies.original_title and
+-- movies.alternative_titles by a trigger, so every write path (single,
+-- batch and refresh) maintains them. clean_title holds the normalized
+-- title that release names are matched against.
+
+CREATE TABLE IF NOT EXISTS movie_alt_titles (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    movie_id UUID NOT NULL REFERENCES movies(id) ON DELETE CASCADE,
+    title TEXT NOT NULL,
+    clean_title TEXT NOT NULL,
+    -- original, alternative
+    source TEXT NOT NULL,
+    -- ISO 3166-1 country the title is used in, if known
+    country TEXT,
+    CONSTRAINT chk_movie_alt_titles_source CHECK (source IN ('original', 'alternative')),
+    UNIQUE (movie_id, clean_title)
+);
+
+CREATE INDEX IF NOT EXISTS idx_movie_alt_titles_clean_title ON movie_alt_titles (clean_title);
+CREATE INDEX IF NOT EXISTS idx_movie_alt_titles_clean_title_trgm
+    ON movie_alt_titles USING GIN (clean_title gin_trgm_ops);
+
+-- Alternative titles are stored as strings or as {"title", "country"}
+-- objects; read the title of either
+CREATE OR REPLACE FUNCTION alternative_title_text(alternative JSONB)
+RETURNS TEXT
+LANGUAGE sql IMMUTABLE PARALLEL SAFE
+AS $$
+    SELECT CASE jsonb_typeof(alternative)
+        WHEN 'object' THEN alternative->>'title'
+        WHEN 'string' THEN alternative #>> '{}'
+    END
+$$;
+
+-- Object alternative titles used to add their keys and country codes to
+-- search_text
+CREATE OR REPLACE FUNCTION movie_search_text(
+    title TEXT,
+    original_title TEXT,
+    alternative_titles JSONB
+)
+RETURNS TEXT
+LANGUAGE sql IMMUTABLE PARALLEL SAFE
+AS $$
+    SELECT btrim(regexp_replace(
+        lower(immutable_unaccent(concat_ws(' ',
+            title,
+            original_title,
+            (SELECT string_agg(alternative_title_text(alt), ' ')
+             FROM jsonb_array_elements(
+                 CASE WHEN jsonb_typeof(alternative_titles) = 'array'
+                      THEN alternative_titles ELSE '[]'::jsonb END
+             ) AS alt)
+        ))),
+        '[^[:alnum:]]+', ' ', 'g'
+    ))
+$$;
+
+CREATE OR REPLACE FUNCTION sync_movie_alt_titles()
+RETURNS TRIGGER AS $$
+BEGIN
+    DELETE FROM movie_alt_titles WHERE movie_id = NEW.id;
+
+    INSERT INTO movie_alt_titles (movie_id, title, clean_title, source, country)
+    SELECT DISTINCT ON (clean_title) NEW.id, title, clean_title, source, country
+    FROM (
+        SELECT NEW.original_title AS title,
+               movie_search_text(NEW.original_title, NULL, NULL) AS clean_title,
+               'original' AS source,
+               NULL AS country,
+               0 AS rank
+        UNION ALL
+        SELECT alternative_title_text(alt),
+               movie_search_text(alternative_title_text(alt), NULL, NULL),
+               'alternative',
+               CASE WHEN jsonb_typeof(alt) = 'object' THEN alt->>'country' END,
+               1
+        FROM jsonb_array_elements(
+            CASE WHEN jsonb_typeof(NEW.alternative_titles) = 'array'
+                 THEN NEW.alternative_titles ELSE '[]'::jsonb END
+        ) AS alt
+    ) AS titles
+    WHERE clean_title <> ''
+      AND clean_title <> movie_search_text(NEW.title, NULL, NULL)
+    ORDER BY clean_title, rank;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS trigger_movies_sync_alt_titles ON movies;
+CREATE TRIGGER trigger_movies_sync_alt_titles
+    AFTER INSERT OR UPDATE OF title, original_title, alternative_titles ON movies
+    FOR EACH ROW
+    EXECUTE FUNCTION sync_movie_alt_titles();
+
+-- Recompute search_text with the new function and fill the table for
+-- existing movies
+UPDATE movies SET alternative_titles = alternative_titles;
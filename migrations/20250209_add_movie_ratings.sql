@@ -0,0 +1,14 @@
+-- Ratings of each movie by service, e.g.
+-- {"imdb": {"value": 8.8, "votes": 2300000}, "rottenTomatoes": {"value": 79, "votes": 0}}
+
+ALTER TABLE movies ADD COLUMN IF NOT EXISTS ratings JSONB NOT NULL DEFAULT '{}';
+
+-- Existing movies start with the TMDB rating from their stored details; the
+-- other services are filled in by the next refresh
+UPDATE movies
+SET ratings = jsonb_build_object('tmdb', jsonb_build_object(
+    'value', (metadata #>> '{tmdb,vote_average}')::DOUBLE PRECISION,
+    'votes', COALESCE((metadata #>> '{tmdb,vote_count}')::BIGINT, 0)
+))
+WHERE ratings = '{}'
+  AND jsonb_typeof(metadata #> '{tmdb,vote_average}') = 'number';
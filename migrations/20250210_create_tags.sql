@@ -0,0 +1,44 @@
+-- Tags scoping indexers and notifications to movies
+--
+-- Movies, indexers, notifications and import lists reference tags by ID.
+-- An indexer or notification with tags is only used for movies sharing at
+-- least one of them; movies added by an import list get the list's tags.
+
+CREATE TABLE IF NOT EXISTS tags (
+    id SERIAL PRIMARY KEY,
+    -- Lowercase letters, digits and dashes
+    label TEXT NOT NULL UNIQUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+ALTER TABLE movies ADD COLUMN IF NOT EXISTS tags INTEGER[] NOT NULL DEFAULT '{}';
+ALTER TABLE indexers ADD COLUMN IF NOT EXISTS tags INTEGER[] NOT NULL DEFAULT '{}';
+ALTER TABLE notifications ADD COLUMN IF NOT EXISTS tags INTEGER[] NOT NULL DEFAULT '{}';
+
+CREATE INDEX IF NOT EXISTS idx_movies_tags ON movies USING GIN (tags);
+
+-- Import lists named their tags; create those tags and reference them by ID
+DO $$
+BEGIN
+    IF EXISTS (
+        SELECT 1 FROM information_schema.columns
+        WHERE table_name = 'import_lists' AND column_name = 'tags' AND udt_name = '_text'
+    ) THEN
+        INSERT INTO tags (label)
+        SELECT DISTINCT btrim(regexp_replace(lower(list_tag.name), '[^a-z0-9]+', '-', 'g'), '-')
+        FROM import_lists, unnest(import_lists.tags) AS list_tag(name)
+        WHERE btrim(regexp_replace(lower(list_tag.name), '[^a-z0-9]+', '-', 'g'), '-') <> ''
+        ON CONFLICT (label) DO NOTHING;
+
+        ALTER TABLE import_lists ADD COLUMN tag_ids INTEGER[] NOT NULL DEFAULT '{}';
+        UPDATE import_lists SET tag_ids = ARRAY(
+            SELECT DISTINCT t.id
+            FROM unnest(import_lists.tags) AS list_tag(name)
+            JOIN tags t
+                ON t.label = btrim(regexp_replace(lower(list_tag.name), '[^a-z0-9]+', '-', 'g'), '-')
+            ORDER BY t.id
+        );
+        ALTER TABLE import_lists DROP COLUMN tags;
+        ALTER TABLE import_lists RENAME COLUMN tag_ids TO tags;
+    END IF;
+END $$;
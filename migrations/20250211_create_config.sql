@@ -0,0 +1,11 @@
+-- Settings changed through /api/v3/config, stored per section
+--
+-- Stored settings win over the environment, which only supplies the values
+-- a section starts with.
+
+CREATE TABLE IF NOT EXISTS config (
+    -- host, naming or mediamanagement
+    section TEXT PRIMARY KEY,
+    settings JSONB NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,13 @@
+-- Named API keys next to the admin key; only the key's hash is stored
+
+CREATE TABLE IF NOT EXISTS api_keys (
+    id UUID PRIMARY KEY,
+    name TEXT NOT NULL,
+    -- Hex SHA-256 digest of the key
+    key_hash TEXT NOT NULL UNIQUE,
+    -- readOnly, full
+    scope TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_used_at TIMESTAMPTZ,
+    revoked_at TIMESTAMPTZ
+);
@@ -0,0 +1,4 @@
+-- Notifications can be sent when a newer release is found
+
+ALTER TABLE notifications
+    ADD COLUMN IF NOT EXISTS on_application_update BOOLEAN NOT NULL DEFAULT FALSE;
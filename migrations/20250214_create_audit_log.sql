@@ -0,0 +1,20 @@
+-- Audit log of deletions and settings changes made through the API
+--
+-- Rows are written from DestructiveOperation events. api_key_id deliberately
+-- has no foreign key so that entries survive the key being deleted.
+
+CREATE TABLE IF NOT EXISTS audit_log (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    action VARCHAR(50) NOT NULL,
+    actor VARCHAR(255) NOT NULL,
+    api_key_id UUID,
+    target TEXT NOT NULL,
+    details JSONB NOT NULL DEFAULT '{}',
+    event_id UUID UNIQUE,
+    correlation_id UUID,
+    date TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_date ON audit_log(date DESC);
+CREATE INDEX IF NOT EXISTS idx_audit_log_action_date ON audit_log(action, date DESC);
+CREATE INDEX IF NOT EXISTS idx_audit_log_api_key_id_date ON audit_log(api_key_id, date DESC);
@@ -1,6 +1,9 @@
 -- Migration: Blocklist System for Failed Releases
 -- Adds comprehensive blocklist functionality to track and manage failed releases
 -- preventing unnecessary retry attempts and improving system efficiency.
+--
+-- Previously kept as 007_blocklist_system.sql.disabled, which never ran; the
+-- blocklist API, failed download handling and queue removal all need it.
 
 -- Blocklist table stores failed releases with TTL-based retry logic
 CREATE TABLE IF NOT EXISTS blocklist (
@@ -22,27 +25,27 @@ CREATE TABLE IF NOT EXISTS blocklist (
 );
 
 -- Indexes for efficient blocklist operations
-CREATE INDEX idx_blocklist_release_indexer ON blocklist(release_id, indexer);
-CREATE INDEX idx_blocklist_indexer ON blocklist(indexer);
-CREATE INDEX idx_blocklist_reason ON blocklist(reason);
-CREATE INDEX idx_blocklist_movie ON blocklist(movie_id) WHERE movie_id IS NOT NULL;
-CREATE INDEX idx_blocklist_blocked_until ON blocklist(blocked_until);
+CREATE INDEX IF NOT EXISTS idx_blocklist_release_indexer ON blocklist(release_id, indexer);
+CREATE INDEX IF NOT EXISTS idx_blocklist_indexer ON blocklist(indexer);
+CREATE INDEX IF NOT EXISTS idx_blocklist_reason ON blocklist(reason);
+CREATE INDEX IF NOT EXISTS idx_blocklist_movie ON blocklist(movie_id) WHERE movie_id IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_blocklist_blocked_until ON blocklist(blocked_until);
 -- Removed immutable function calls from index predicates
--- CREATE INDEX idx_blocklist_expired ON blocklist(blocked_until) WHERE blocked_until <= NOW();
--- CREATE INDEX idx_blocklist_active ON blocklist(blocked_until) WHERE blocked_until > NOW();
-CREATE INDEX idx_blocklist_created_at ON blocklist(created_at DESC);
-CREATE INDEX idx_blocklist_retry_count ON blocklist(retry_count);
+-- CREATE INDEX IF NOT EXISTS idx_blocklist_expired ON blocklist(blocked_until) WHERE blocked_until <= NOW();
+-- CREATE INDEX IF NOT EXISTS idx_blocklist_active ON blocklist(blocked_until) WHERE blocked_until > NOW();
+CREATE INDEX IF NOT EXISTS idx_blocklist_created_at ON blocklist(created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_blocklist_retry_count ON blocklist(retry_count);
 
 -- Composite indexes for common query patterns
-CREATE INDEX idx_blocklist_indexer_reason ON blocklist(indexer, reason);
-CREATE INDEX idx_blocklist_movie_reason ON blocklist(movie_id, reason) WHERE movie_id IS NOT NULL;
-CREATE INDEX idx_blocklist_expiry_retry ON blocklist(blocked_until, retry_count);
+CREATE INDEX IF NOT EXISTS idx_blocklist_indexer_reason ON blocklist(indexer, reason);
+CREATE INDEX IF NOT EXISTS idx_blocklist_movie_reason ON blocklist(movie_id, reason) WHERE movie_id IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_blocklist_expiry_retry ON blocklist(blocked_until, retry_count);
 
 -- Partial indexes for specific failure types (performance optimization)
-CREATE INDEX idx_blocklist_permanent ON blocklist(created_at DESC) 
+CREATE INDEX IF NOT EXISTS idx_blocklist_permanent ON blocklist(created_at DESC) 
 WHERE reason IN ('ManuallyRejected', 'QualityRejected', 'SizeRejected', 'ExclusionMatched');
 
-CREATE INDEX idx_blocklist_retryable ON blocklist(blocked_until) 
+CREATE INDEX IF NOT EXISTS idx_blocklist_retryable ON blocklist(blocked_until) 
 WHERE reason NOT IN ('ManuallyRejected', 'QualityRejected', 'SizeRejected', 'ExclusionMatched') 
 AND retry_count < 10; -- Reasonable max retry limit
 
@@ -182,6 +185,7 @@ BEGIN
 END;
 $$ LANGUAGE plpgsql;
 
+DROP TRIGGER IF EXISTS trigger_blocklist_updated_at ON blocklist;
 CREATE TRIGGER trigger_blocklist_updated_at
     BEFORE UPDATE ON blocklist
     FOR EACH ROW
@@ -201,7 +205,8 @@ SELECT
     -- Most problematic indexer
     (SELECT indexer FROM blocklist GROUP BY indexer ORDER BY COUNT(*) DESC LIMIT 1) as top_failing_indexer,
     -- Count for top failing indexer
-    (SELECT COUNT(*) FROM blocklist WHERE indexer = (SELECT indexer FROM blocklist GROUP BY indexer ORDER BY COUNT(*) DESC LIMIT 1)) as top_indexer_failure_count;
+    (SELECT COUNT(*) FROM blocklist WHERE indexer = (SELECT indexer FROM blocklist GROUP BY indexer ORDER BY COUNT(*) DESC LIMIT 1)) as top_indexer_failure_count
+FROM blocklist;
 
 -- View for failure reason analysis
 CREATE OR REPLACE VIEW blocklist_failure_analysis AS
@@ -0,0 +1,10 @@
+//! API endpoint exposing the configuration schema for UI form generation
+
+use crate::config::schema::ConfigSchema;
+use crate::config::AppConfig;
+use axum::response::Json;
+
+/// Get the machine-readable schema for all configuration sections
+pub async fn get_config_schema() -> Json<ConfigSchema> {
+    Json(AppConfig::schema())
+}
@@ -1,9 +1,11 @@
 //! API module for additional endpoints
 
+pub mod config_schema;
 pub mod retry_status;
 pub mod rss_endpoints;
 pub mod v3_movies;
 
+pub use config_schema::*;
 pub use retry_status::*;
 pub use rss_endpoints::*;
 pub use v3_movies::*;
@@ -6,6 +6,7 @@ use axum::{
     http::StatusCode,
     response::Json,
 };
+use radarr_api::core_error_status;
 use radarr_core::rss::{CalendarEntry, RssFeed};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -93,7 +94,7 @@ pub async fn add_feed(
     rss_service
         .add_feed(feed.clone())
         .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(|e| core_error_status(&e))?;
 
     let response = FeedResponse {
         id: feed_id,
@@ -117,7 +118,7 @@ pub async fn remove_feed(
 ) -> StatusCode {
     match rss_service.remove_feed(id).await {
         Ok(_) => StatusCode::NO_CONTENT,
-        Err(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        Err(e) => core_error_status(&e),
     }
 }
 
@@ -227,6 +228,6 @@ pub async fn add_calendar_entry(
 ) -> StatusCode {
     match rss_service.add_calendar_entry(entry).await {
         Ok(_) => StatusCode::CREATED,
-        Err(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        Err(e) => core_error_status(&e),
     }
 }
@@ -4,7 +4,12 @@ use axum::{
     http::StatusCode,
     response::Json,
 };
-use radarr_core::{domain::repositories::MovieRepository, models::Movie};
+use radarr_api::core_error_status;
+use radarr_core::{
+    domain::repositories::MovieRepository,
+    models::{Movie, MovieRatings},
+    SearchCooldownPolicy,
+};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::sync::Arc;
@@ -58,7 +63,10 @@ pub struct MovieResponse {
     backdrop_url: Option<String>,
     trailer_url: Option<String>,
     genres: Vec<String>,
-    ratings: Value,
+    ratings: MovieRatings,
+    last_searched: Option<String>,
+    next_search: Option<String>,
+    excluded_indexer_ids: Vec<i32>,
 }
 
 /// GET /api/v3/movie - List all movies with filtering
@@ -80,7 +88,7 @@ pub async fn list_movies(
             Ok(movies) => movies,
             Err(e) => {
                 error!("Failed to search movies by title: {}", e);
-                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+                return Err(core_error_status(&e));
             }
         }
     } else {
@@ -108,7 +116,7 @@ pub async fn list_movies(
                 }
                 Err(e) => {
                     error!("Failed to fetch monitored movies: {}", e);
-                    return Err(StatusCode::INTERNAL_SERVER_ERROR);
+                    return Err(core_error_status(&e));
                 }
             }
         } else if params.has_file == Some(false) {
@@ -124,7 +132,7 @@ pub async fn list_movies(
                 }
                 Err(e) => {
                     error!("Failed to fetch movies without files: {}", e);
-                    return Err(StatusCode::INTERNAL_SERVER_ERROR);
+                    return Err(core_error_status(&e));
                 }
             }
         } else {
@@ -133,7 +141,7 @@ pub async fn list_movies(
                 Ok(movies) => movies,
                 Err(e) => {
                     error!("Failed to fetch movies from database: {}", e);
-                    return Err(StatusCode::INTERNAL_SERVER_ERROR);
+                    return Err(core_error_status(&e));
                 }
             }
         }
@@ -191,7 +199,7 @@ pub async fn get_movie(
         }
         Err(e) => {
             error!("Failed to fetch movie with ID {}: {}", id, e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            Err(core_error_status(&e))
         }
     }
 }
@@ -221,7 +229,7 @@ pub async fn update_movie(
         }
         Err(e) => {
             error!("Failed to fetch movie with ID {}: {}", id, e);
-            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            return Err(core_error_status(&e));
         }
     };
 
@@ -240,6 +248,21 @@ pub async fn update_movie(
         debug!("Updated movie quality profile to: {}", quality_profile_id);
     }
 
+    if let Some(excluded) = payload
+        .get("excluded_indexer_ids")
+        .and_then(|v| v.as_array())
+    {
+        let mut excluded: Vec<i32> = excluded
+            .iter()
+            .filter_map(|v| v.as_i64())
+            .map(|v| v as i32)
+            .collect();
+        excluded.sort_unstable();
+        excluded.dedup();
+        debug!("Updated movie excluded indexers to: {:?}", excluded);
+        movie.excluded_indexer_ids = excluded;
+    }
+
     // Update the movie in repository
     match services.movie_repository.update(&movie).await {
         Ok(updated_movie) => {
@@ -248,7 +271,7 @@ pub async fn update_movie(
         }
         Err(e) => {
             error!("Failed to update movie: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            Err(core_error_status(&e))
         }
     }
 }
@@ -354,17 +377,6 @@ fn convert_movie_to_response(movie: Movie) -> MovieResponse {
         })
         .unwrap_or_default();
 
-    let rating = movie.rating().unwrap_or(0.0);
-    let ratings = json!({
-        "tmdb": {
-            "value": rating,
-            "votes": movie.metadata.get("tmdb")
-                .and_then(|tmdb| tmdb.get("vote_count"))
-                .and_then(|count| count.as_i64())
-                .unwrap_or(0)
-        }
-    });
-
     let release_date = movie
         .metadata
         .get("tmdb")
@@ -375,6 +387,11 @@ fn convert_movie_to_response(movie: Movie) -> MovieResponse {
     // Generate sort title (lowercase, remove articles)
     let sort_title = generate_sort_title(&movie.title);
 
+    let next_search = SearchCooldownPolicy::default()
+        .next_search_time(&movie)
+        .map(|t| t.to_rfc3339());
+    let last_searched = movie.last_search_time.map(|t| t.to_rfc3339());
+
     // Use tmdb_id as the API id since the web UI expects integer IDs
     MovieResponse {
         id: movie.tmdb_id,
@@ -399,7 +416,10 @@ fn convert_movie_to_response(movie: Movie) -> MovieResponse {
         backdrop_url,
         trailer_url: None, // TODO: Add trailer URL support
         genres,
-        ratings,
+        ratings: movie.ratings.clone(),
+        last_searched,
+        next_search,
+        excluded_indexer_ids: movie.excluded_indexer_ids,
     }
 }
 
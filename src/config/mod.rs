@@ -4,10 +4,16 @@
 //! including database, external services, and component-specific settings.
 
 pub mod retry_config;
+pub mod schema;
 
+use radarr_api::handlers::config::{
+    HostConfigResource, MediaManagementConfigResource, NamingConfigResource,
+};
 use radarr_core::{RadarrError, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::env;
+use std::time::Duration;
 
 /// Simplified Prowlarr configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,10 +22,31 @@ pub struct ProwlarrConfig {
     pub api_key: String,
     pub timeout: u64,
     pub max_requests_per_minute: u32,
+    /// Searches per minute for each indexer without its own limit; 0 for no
+    /// limit
+    #[serde(default)]
+    pub default_indexer_rate_limit: u32,
+    /// Searches per minute by indexer ID
+    #[serde(default)]
+    pub indexer_rate_limits: HashMap<i32, u32>,
+    /// Backoff (seconds) after an indexer's first 429 or temporary failure
+    #[serde(default = "default_backoff_initial_seconds")]
+    pub backoff_initial_seconds: u64,
+    /// Longest backoff (seconds) of an indexer
+    #[serde(default = "default_backoff_max_seconds")]
+    pub backoff_max_seconds: u64,
     pub user_agent: String,
     pub verify_ssl: bool,
 }
 
+fn default_backoff_initial_seconds() -> u64 {
+    30
+}
+
+fn default_backoff_max_seconds() -> u64 {
+    3600
+}
+
 impl Default for ProwlarrConfig {
     fn default() -> Self {
         Self {
@@ -27,6 +54,10 @@ impl Default for ProwlarrConfig {
             api_key: String::new(),
             timeout: 30,
             max_requests_per_minute: 60,
+            default_indexer_rate_limit: 0,
+            indexer_rate_limits: HashMap::new(),
+            backoff_initial_seconds: default_backoff_initial_seconds(),
+            backoff_max_seconds: default_backoff_max_seconds(),
             user_agent: "Radarr-Rust/1.0".to_string(),
             verify_ssl: true,
         }
@@ -40,6 +71,28 @@ pub struct QBittorrentConfig {
     pub username: String,
     pub password: String,
     pub timeout: u64,
+    /// Free space (MiB) that must remain after a grab's expected size
+    #[serde(default = "default_free_space_headroom_mb")]
+    pub free_space_headroom_mb: u64,
+    /// Skip samples, proofs and other files besides the movie in multi-file
+    /// torrents
+    #[serde(default = "default_skip_unwanted_files")]
+    pub skip_unwanted_files: bool,
+    /// Order among torrent clients; lower values are tried first
+    #[serde(default = "default_download_client_priority")]
+    pub priority: i32,
+}
+
+fn default_free_space_headroom_mb() -> u64 {
+    1024
+}
+
+fn default_skip_unwanted_files() -> bool {
+    true
+}
+
+fn default_download_client_priority() -> i32 {
+    1
 }
 
 impl Default for QBittorrentConfig {
@@ -49,6 +102,86 @@ impl Default for QBittorrentConfig {
             username: "admin".to_string(),
             password: String::new(),
             timeout: 30,
+            free_space_headroom_mb: default_free_space_headroom_mb(),
+            skip_unwanted_files: default_skip_unwanted_files(),
+            priority: default_download_client_priority(),
+        }
+    }
+}
+
+/// SABnzbd usenet downloader configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SabnzbdConfig {
+    /// Send usenet releases to SABnzbd
+    pub enabled: bool,
+    pub base_url: String,
+    pub api_key: String,
+    pub timeout: u64,
+    /// Order among usenet clients; lower values are tried first
+    #[serde(default = "default_download_client_priority")]
+    pub priority: i32,
+}
+
+impl Default for SabnzbdConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            base_url: "http://localhost:8085".to_string(),
+            api_key: String::new(),
+            timeout: 30,
+            priority: default_download_client_priority(),
+        }
+    }
+}
+
+/// Routing grabs between the configured download clients
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DownloadClientsConfig {
+    /// Download client (`qbittorrent` or `sabnzbd`) by indexer ID, for
+    /// sending an indexer's grabs to a specific client
+    #[serde(default)]
+    pub indexer_routes: HashMap<i32, String>,
+}
+
+/// Removal of orphaned data from the downloads folder
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DownloadsCleanupConfig {
+    /// Run the cleanup on a schedule; it can always be queued as a command
+    pub enabled: bool,
+    /// Folder whose top-level entries are considered for cleanup
+    pub downloads_dir: String,
+    /// Hours since an entry last changed before it counts as orphaned
+    pub grace_period_hours: u64,
+    /// Entry names that are never removed, as globs
+    pub exclude_globs: Vec<String>,
+    /// Only list orphaned entries in the command result
+    pub dry_run: bool,
+    /// Hours between scheduled runs
+    pub interval_hours: u64,
+}
+
+impl DownloadsCleanupConfig {
+    /// Settings for the downloads cleanup service
+    pub fn cleanup_config(&self) -> radarr_import::CleanupConfig {
+        radarr_import::CleanupConfig {
+            downloads_dir: self.downloads_dir.clone().into(),
+            grace_period: Duration::from_secs(self.grace_period_hours * 60 * 60),
+            exclude_globs: self.exclude_globs.clone(),
+            dry_run: self.dry_run,
+        }
+    }
+}
+
+impl Default for DownloadsCleanupConfig {
+    fn default() -> Self {
+        let defaults = radarr_import::CleanupConfig::default();
+        Self {
+            enabled: false,
+            downloads_dir: defaults.downloads_dir.to_string_lossy().to_string(),
+            grace_period_hours: defaults.grace_period.as_secs() / (60 * 60),
+            exclude_globs: defaults.exclude_globs,
+            dry_run: defaults.dry_run,
+            interval_hours: 24,
         }
     }
 }
@@ -61,6 +194,145 @@ pub struct ImportConfig {
     pub skip_samples: bool,
     pub continue_on_error: bool,
     pub max_parallel: usize,
+    /// Directories read at the same time while scanning for files
+    #[serde(default = "default_scan_concurrency")]
+    pub scan_concurrency: usize,
+    /// Run files through ffmpeg before importing them
+    #[serde(default)]
+    pub transcode_enabled: bool,
+    #[serde(default = "default_ffmpeg_path")]
+    pub ffmpeg_path: String,
+    /// Transcode profile to run; the first built-in profile when unset
+    #[serde(default)]
+    pub transcode_profile: Option<String>,
+    /// Scratch directory for transcode output; the system temp dir when unset
+    #[serde(default)]
+    pub transcode_work_dir: Option<String>,
+    /// Extract RAR and zip archives in downloads before importing them
+    #[serde(default)]
+    pub extract_enabled: bool,
+    #[serde(default = "default_unrar_path")]
+    pub unrar_path: String,
+    /// Scratch directory archives are extracted to; the system temp dir when unset
+    #[serde(default)]
+    pub extract_work_dir: Option<String>,
+    /// What is done with samples found next to a movie: skip, import or delete
+    #[serde(default)]
+    pub extras_samples: radarr_import::ExtraAction,
+    #[serde(default)]
+    pub extras_trailers: radarr_import::ExtraAction,
+    #[serde(default)]
+    pub extras_featurettes: radarr_import::ExtraAction,
+    /// Behind the scenes, deleted scenes, interviews and other extras
+    #[serde(default)]
+    pub extras_other: radarr_import::ExtraAction,
+    /// Whether propers and repacks are grabbed to replace a file of the same
+    /// quality: prefer or ignore
+    #[serde(default)]
+    pub proper_handling: radarr_decision::ProperHandling,
+    /// Modification time given to imported files
+    #[serde(default)]
+    pub file_date: radarr_import::FileDateMode,
+    /// Naming template of imported movie files, with Radarr naming tokens
+    #[serde(default = "default_movie_template")]
+    pub movie_template: String,
+    /// Naming template of movie folders
+    #[serde(default = "default_folder_template")]
+    pub folder_template: String,
+    /// How colons in movie and folder names are replaced
+    #[serde(default)]
+    pub colon_replacement: radarr_import::ColonReplacement,
+    /// Directory forensic bundles of failed imports are written to
+    #[serde(default = "default_diagnostics_dir")]
+    pub diagnostics_dir: String,
+    /// Staging directory for early previews of sequential torrents; previews
+    /// are off when unset
+    #[serde(default)]
+    pub preview_dir: Option<String>,
+    /// Gap-free share (0.0-1.0) of the movie file downloaded before a preview
+    #[serde(default = "default_preview_min_progress")]
+    pub preview_min_progress: f64,
+}
+
+fn default_scan_concurrency() -> usize {
+    8
+}
+
+fn default_ffmpeg_path() -> String {
+    "ffmpeg".to_string()
+}
+
+fn default_unrar_path() -> String {
+    "unrar".to_string()
+}
+
+fn default_movie_template() -> String {
+    radarr_import::RenameConfig::default().movie_template
+}
+
+fn default_folder_template() -> String {
+    radarr_import::RenameConfig::default().folder_template
+}
+
+fn default_diagnostics_dir() -> String {
+    "diagnostics".to_string()
+}
+
+fn default_preview_min_progress() -> f64 {
+    0.95
+}
+
+impl ImportConfig {
+    /// Transcode settings for the import pipeline
+    pub fn transcode_config(&self) -> radarr_import::TranscodeConfig {
+        radarr_import::TranscodeConfig {
+            enabled: self.transcode_enabled,
+            ffmpeg_path: self.ffmpeg_path.clone().into(),
+            active_profile: self.transcode_profile.clone(),
+            work_directory: self.transcode_work_dir.clone().map(Into::into),
+            ..radarr_import::TranscodeConfig::default()
+        }
+    }
+
+    /// Archive extraction settings for the import pipeline
+    pub fn extract_config(&self) -> radarr_import::ExtractConfig {
+        radarr_import::ExtractConfig {
+            enabled: self.extract_enabled,
+            unrar_path: self.unrar_path.clone().into(),
+            work_directory: self.extract_work_dir.clone().map(Into::into),
+            ..radarr_import::ExtractConfig::default()
+        }
+    }
+
+    /// Sample and extras handling for the import pipeline
+    pub fn extras_config(&self) -> radarr_import::ExtrasConfig {
+        radarr_import::ExtrasConfig {
+            samples: self.extras_samples,
+            trailers: self.extras_trailers,
+            featurettes: self.extras_featurettes,
+            other: self.extras_other,
+            ..radarr_import::ExtrasConfig::default()
+        }
+    }
+
+    /// File and folder naming settings for the import pipeline
+    pub fn rename_config(&self) -> radarr_import::RenameConfig {
+        radarr_import::RenameConfig {
+            movie_template: self.movie_template.clone(),
+            folder_template: self.folder_template.clone(),
+            colon_replacement: self.colon_replacement,
+            ..radarr_import::RenameConfig::default()
+        }
+    }
+
+    /// Early import preview settings, `None` when previews are off
+    pub fn preview_config(&self) -> Option<radarr_core::PreviewImportConfig> {
+        let staging_dir = self.preview_dir.as_ref()?;
+        Some(radarr_core::PreviewImportConfig {
+            min_progress: self.preview_min_progress.clamp(0.0, 1.0),
+            ..radarr_core::PreviewImportConfig::new(staging_dir)
+        })
+    }
 }
 
 impl Default for ImportConfig {
@@ -71,6 +343,26 @@ impl Default for ImportConfig {
             skip_samples: true,
             continue_on_error: true,
             max_parallel: 4,
+            scan_concurrency: default_scan_concurrency(),
+            transcode_enabled: false,
+            ffmpeg_path: default_ffmpeg_path(),
+            transcode_profile: None,
+            transcode_work_dir: None,
+            extract_enabled: false,
+            unrar_path: default_unrar_path(),
+            extract_work_dir: None,
+            extras_samples: radarr_import::ExtraAction::default(),
+            extras_trailers: radarr_import::ExtraAction::default(),
+            extras_featurettes: radarr_import::ExtraAction::default(),
+            extras_other: radarr_import::ExtraAction::default(),
+            proper_handling: radarr_decision::ProperHandling::default(),
+            file_date: radarr_import::FileDateMode::default(),
+            movie_template: default_movie_template(),
+            folder_template: default_folder_template(),
+            colon_replacement: radarr_import::ColonReplacement::default(),
+            diagnostics_dir: default_diagnostics_dir(),
+            preview_dir: None,
+            preview_min_progress: default_preview_min_progress(),
         }
     }
 }
@@ -92,6 +384,15 @@ pub struct TmdbConfig {
     pub enabled: bool,
 }
 
+/// OMDb configuration, the source of IMDb, Rotten Tomatoes and Metacritic
+/// ratings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OmdbConfig {
+    /// Ratings are only looked up with an API key
+    pub api_key: String,
+}
+
 impl Default for RssServiceConfig {
     fn default() -> Self {
         Self {
@@ -124,10 +425,49 @@ pub struct AppConfig {
     pub prowlarr: ProwlarrConfig,
     /// qBittorrent downloader configuration
     pub qbittorrent: QBittorrentConfig,
+    /// SABnzbd downloader configuration
+    #[serde(default)]
+    pub sabnzbd: SabnzbdConfig,
+    /// Routing grabs between download clients
+    #[serde(default)]
+    pub download_clients: DownloadClientsConfig,
     /// Import pipeline configuration
     pub import: ImportConfig,
+    /// Removal of orphaned data from the downloads folder
+    #[serde(default)]
+    pub downloads_cleanup: DownloadsCleanupConfig,
     /// TMDB API configuration
     pub tmdb: TmdbConfig,
+    /// OMDb ratings lookup
+    #[serde(default)]
+    pub omdb: OmdbConfig,
+    /// Failed download handling
+    #[serde(default)]
+    pub download_handling: radarr_core::DownloadHandlingConfig,
+    /// Replacing downloads that stay slow or stalled
+    #[serde(default)]
+    pub stall_detection: radarr_core::StallDetectionConfig,
+    /// Seed goals of imported torrents
+    #[serde(default)]
+    pub seeding: radarr_core::SeedingConfig,
+    /// Languages releases are grabbed in
+    #[serde(default)]
+    pub languages: radarr_decision::LanguageProfile,
+    /// Drift detection against a declarative desired state spec
+    #[serde(default)]
+    pub desired_state: radarr_core::ConfigDriftConfig,
+    /// Cache of TMDB and streaming lookups
+    #[serde(default)]
+    pub cache: radarr_infrastructure::CacheConfig,
+    /// Cache of movie posters and fanart
+    #[serde(default)]
+    pub media_covers: radarr_infrastructure::MediaCoverConfig,
+    /// Scheduled configuration backups
+    #[serde(default)]
+    pub backups: radarr_infrastructure::BackupConfig,
+    /// Checks for newer releases
+    #[serde(default)]
+    pub updates: radarr_infrastructure::UpdateConfig,
     /// Logging configuration
     pub logging: LoggingConfig,
 }
@@ -145,6 +485,20 @@ pub struct ServerConfig {
     pub max_connections: usize,
     /// Request timeout in seconds
     pub request_timeout: u64,
+    /// Default locale for user-facing messages (e.g. "en-US", "de-DE")
+    #[serde(default = "default_locale")]
+    pub locale: String,
+    /// Seconds background services get to finish their work on shutdown
+    #[serde(default = "default_shutdown_grace_period")]
+    pub shutdown_grace_period: u64,
+}
+
+fn default_locale() -> String {
+    radarr_core::i18n::DEFAULT_LOCALE.to_string()
+}
+
+fn default_shutdown_grace_period() -> u64 {
+    radarr_core::shutdown::DEFAULT_SHUTDOWN_GRACE_SECS
 }
 
 /// Database configuration
@@ -158,6 +512,34 @@ pub struct DatabaseConfig {
     pub connect_timeout: u64,
     /// Enable query logging
     pub log_queries: bool,
+    /// Read replica for heavy read-only queries; writes always go to `url`
+    #[serde(default)]
+    pub read_replica_url: Option<String>,
+    /// Replication lag in seconds above which reads fall back to the primary
+    #[serde(default = "default_replica_max_lag_secs")]
+    pub replica_max_lag_secs: u64,
+    /// Attempts to reach the database at startup before giving up
+    #[serde(default = "default_connect_retries")]
+    pub connect_retries: u32,
+    /// Seconds between probes of the connection pool
+    #[serde(default = "default_pool_check_interval_secs")]
+    pub pool_check_interval_secs: u64,
+    /// Whether pending migrations are applied, only checked, or printed at
+    /// startup
+    #[serde(default)]
+    pub migration_mode: radarr_infrastructure::MigrationMode,
+}
+
+fn default_replica_max_lag_secs() -> u64 {
+    30
+}
+
+fn default_connect_retries() -> u32 {
+    10
+}
+
+fn default_pool_check_interval_secs() -> u64 {
+    15
 }
 
 /// Logging configuration
@@ -178,8 +560,21 @@ impl Default for AppConfig {
             database: DatabaseConfig::default(),
             prowlarr: ProwlarrConfig::default(),
             qbittorrent: QBittorrentConfig::default(),
+            sabnzbd: SabnzbdConfig::default(),
+            download_clients: DownloadClientsConfig::default(),
             import: ImportConfig::default(),
+            downloads_cleanup: DownloadsCleanupConfig::default(),
             tmdb: TmdbConfig::default(),
+            omdb: OmdbConfig::default(),
+            download_handling: radarr_core::DownloadHandlingConfig::default(),
+            stall_detection: radarr_core::StallDetectionConfig::default(),
+            seeding: radarr_core::SeedingConfig::default(),
+            languages: radarr_decision::LanguageProfile::default(),
+            desired_state: radarr_core::ConfigDriftConfig::default(),
+            cache: radarr_infrastructure::CacheConfig::default(),
+            media_covers: radarr_infrastructure::MediaCoverConfig::default(),
+            backups: radarr_infrastructure::BackupConfig::default(),
+            updates: radarr_infrastructure::UpdateConfig::default(),
             logging: LoggingConfig::default(),
         }
     }
@@ -193,6 +588,8 @@ impl Default for ServerConfig {
             api_key: "changeme123".to_string(),
             max_connections: 1000,
             request_timeout: 30,
+            locale: default_locale(),
+            shutdown_grace_period: default_shutdown_grace_period(),
         }
     }
 }
@@ -204,6 +601,11 @@ impl Default for DatabaseConfig {
             max_connections: 10,
             connect_timeout: 30,
             log_queries: false,
+            read_replica_url: None,
+            replica_max_lag_secs: default_replica_max_lag_secs(),
+            connect_retries: default_connect_retries(),
+            pool_check_interval_secs: default_pool_check_interval_secs(),
+            migration_mode: radarr_infrastructure::MigrationMode::default(),
         }
     }
 }
@@ -236,6 +638,10 @@ impl AppConfig {
         if let Ok(api_key) = env::var("RADARR_API_KEY") {
             config.server.api_key = api_key;
         }
+        // A pre-computed key hash keeps the plain key out of the environment entirely
+        if let Ok(api_key_hash) = env::var("RADARR_API_KEY_HASH") {
+            config.server.api_key = api_key_hash;
+        }
         if let Ok(max_conn) = env::var("RADARR_MAX_CONNECTIONS") {
             config.server.max_connections =
                 max_conn.parse().map_err(|e| RadarrError::ValidationError {
@@ -251,6 +657,17 @@ impl AppConfig {
                 })?;
         }
 
+        if let Ok(locale) = env::var("RADARR_LOCALE") {
+            config.server.locale = locale;
+        }
+        if let Ok(grace) = env::var("RADARR_SHUTDOWN_GRACE_PERIOD") {
+            config.server.shutdown_grace_period =
+                grace.parse().map_err(|e| RadarrError::ValidationError {
+                    field: "RADARR_SHUTDOWN_GRACE_PERIOD".to_string(),
+                    message: format!("Invalid shutdown grace period: {}", e),
+                })?;
+        }
+
         // Database configuration
         if let Ok(db_url) = env::var("DATABASE_URL") {
             config.database.url = db_url;
@@ -272,6 +689,38 @@ impl AppConfig {
         if let Ok(log_queries) = env::var("DATABASE_LOG_QUERIES") {
             config.database.log_queries = log_queries.parse().unwrap_or(false);
         }
+        if let Ok(replica_url) = env::var("DATABASE_READ_REPLICA_URL") {
+            config.database.read_replica_url = Some(replica_url).filter(|url| !url.is_empty());
+        }
+        if let Ok(max_lag) = env::var("DATABASE_REPLICA_MAX_LAG_SECS") {
+            config.database.replica_max_lag_secs =
+                max_lag.parse().map_err(|e| RadarrError::ValidationError {
+                    field: "DATABASE_REPLICA_MAX_LAG_SECS".to_string(),
+                    message: format!("Invalid replica max lag: {}", e),
+                })?;
+        }
+        if let Ok(retries) = env::var("DATABASE_CONNECT_RETRIES") {
+            config.database.connect_retries =
+                retries.parse().map_err(|e| RadarrError::ValidationError {
+                    field: "DATABASE_CONNECT_RETRIES".to_string(),
+                    message: format!("Invalid connect retries: {}", e),
+                })?;
+        }
+        if let Ok(interval) = env::var("DATABASE_POOL_CHECK_INTERVAL_SECS") {
+            config.database.pool_check_interval_secs =
+                interval.parse().map_err(|e| RadarrError::ValidationError {
+                    field: "DATABASE_POOL_CHECK_INTERVAL_SECS".to_string(),
+                    message: format!("Invalid pool check interval: {}", e),
+                })?;
+        }
+        if let Ok(mode) = env::var("DATABASE_MIGRATIONS") {
+            config.database.migration_mode =
+                mode.parse()
+                    .map_err(|message| RadarrError::ValidationError {
+                        field: "DATABASE_MIGRATIONS".to_string(),
+                        message,
+                    })?;
+        }
 
         // Prowlarr configuration
         if let Ok(base_url) = env::var("PROWLARR_BASE_URL") {
@@ -296,6 +745,25 @@ impl AppConfig {
                         message: format!("Invalid rate limit: {}", e),
                     })?;
         }
+        if let Ok(rate_limit) = env::var("PROWLARR_INDEXER_RATE_LIMIT") {
+            config.prowlarr.default_indexer_rate_limit =
+                rate_limit
+                    .parse()
+                    .map_err(|e| RadarrError::ValidationError {
+                        field: "PROWLARR_INDEXER_RATE_LIMIT".to_string(),
+                        message: format!("Invalid rate limit: {}", e),
+                    })?;
+        }
+        if let Ok(rate_limits) = env::var("PROWLARR_INDEXER_RATE_LIMITS") {
+            config.prowlarr.indexer_rate_limits = parse_indexer_rate_limits(&rate_limits)?;
+        }
+        if let Ok(seconds) = env::var("PROWLARR_BACKOFF_MAX_SECONDS") {
+            config.prowlarr.backoff_max_seconds =
+                seconds.parse().map_err(|e| RadarrError::ValidationError {
+                    field: "PROWLARR_BACKOFF_MAX_SECONDS".to_string(),
+                    message: format!("Invalid backoff: {}", e),
+                })?;
+        }
 
         // qBittorrent configuration
         if let Ok(base_url) = env::var("QBITTORRENT_BASE_URL") {
@@ -314,6 +782,48 @@ impl AppConfig {
                     message: format!("Invalid timeout: {}", e),
                 })?;
         }
+        if let Ok(headroom) = env::var("QBITTORRENT_FREE_SPACE_HEADROOM_MB") {
+            config.qbittorrent.free_space_headroom_mb =
+                headroom.parse().map_err(|e| RadarrError::ValidationError {
+                    field: "QBITTORRENT_FREE_SPACE_HEADROOM_MB".to_string(),
+                    message: format!("Invalid free space headroom: {}", e),
+                })?;
+        }
+        if let Ok(skip) = env::var("QBITTORRENT_SKIP_UNWANTED_FILES") {
+            config.qbittorrent.skip_unwanted_files = skip.parse().unwrap_or(true);
+        }
+        if let Ok(priority) = env::var("QBITTORRENT_PRIORITY") {
+            config.qbittorrent.priority =
+                priority.parse().map_err(|e| RadarrError::ValidationError {
+                    field: "QBITTORRENT_PRIORITY".to_string(),
+                    message: format!("Invalid priority: {}", e),
+                })?;
+        }
+
+        // SABnzbd configuration
+        if let Ok(base_url) = env::var("SABNZBD_BASE_URL") {
+            config.sabnzbd.base_url = base_url;
+        }
+        if let Ok(api_key) = env::var("SABNZBD_API_KEY") {
+            config.sabnzbd.api_key = api_key;
+            config.sabnzbd.enabled = true;
+        }
+        if let Ok(timeout) = env::var("SABNZBD_TIMEOUT") {
+            config.sabnzbd.timeout = timeout.parse().map_err(|e| RadarrError::ValidationError {
+                field: "SABNZBD_TIMEOUT".to_string(),
+                message: format!("Invalid timeout: {}", e),
+            })?;
+        }
+        if let Ok(priority) = env::var("SABNZBD_PRIORITY") {
+            config.sabnzbd.priority =
+                priority.parse().map_err(|e| RadarrError::ValidationError {
+                    field: "SABNZBD_PRIORITY".to_string(),
+                    message: format!("Invalid priority: {}", e),
+                })?;
+        }
+        if let Ok(routes) = env::var("DOWNLOAD_CLIENT_INDEXER_ROUTES") {
+            config.download_clients.indexer_routes = parse_download_client_routes(&routes)?;
+        }
 
         // TMDB configuration
         if let Ok(api_key) = env::var("TMDB_API_KEY") {
@@ -329,6 +839,239 @@ impl AppConfig {
         if let Ok(enabled) = env::var("TMDB_ENABLED") {
             config.tmdb.enabled = enabled.parse().unwrap_or(false);
         }
+        if let Ok(api_key) = env::var("OMDB_API_KEY") {
+            config.omdb.api_key = api_key;
+        }
+
+        if let Ok(concurrency) = env::var("RADARR_SCAN_CONCURRENCY") {
+            config.import.scan_concurrency = concurrency
+                .parse()
+                .unwrap_or_else(|_| default_scan_concurrency());
+        }
+
+        // Transcode configuration
+        if let Ok(enabled) = env::var("RADARR_TRANSCODE_ENABLED") {
+            config.import.transcode_enabled = enabled.parse().unwrap_or(false);
+        }
+        if let Ok(ffmpeg_path) = env::var("RADARR_FFMPEG_PATH") {
+            config.import.ffmpeg_path = ffmpeg_path;
+        }
+        if let Ok(profile) = env::var("RADARR_TRANSCODE_PROFILE") {
+            config.import.transcode_profile = Some(profile);
+        }
+        if let Ok(work_dir) = env::var("RADARR_TRANSCODE_WORK_DIR") {
+            config.import.transcode_work_dir = Some(work_dir);
+        }
+        // Archive extraction
+        if let Ok(enabled) = env::var("RADARR_EXTRACT_ENABLED") {
+            config.import.extract_enabled = enabled.parse().unwrap_or(false);
+        }
+        if let Ok(unrar_path) = env::var("RADARR_UNRAR_PATH") {
+            config.import.unrar_path = unrar_path;
+        }
+        if let Ok(work_dir) = env::var("RADARR_EXTRACT_WORK_DIR") {
+            config.import.extract_work_dir = Some(work_dir);
+        }
+        // Samples and extras
+        if let Ok(action) = env::var("RADARR_EXTRAS_SAMPLES") {
+            config.import.extras_samples = action.parse().unwrap_or_default();
+        }
+        if let Ok(action) = env::var("RADARR_EXTRAS_TRAILERS") {
+            config.import.extras_trailers = action.parse().unwrap_or_default();
+        }
+        if let Ok(action) = env::var("RADARR_EXTRAS_FEATURETTES") {
+            config.import.extras_featurettes = action.parse().unwrap_or_default();
+        }
+        if let Ok(action) = env::var("RADARR_EXTRAS_OTHER") {
+            config.import.extras_other = action.parse().unwrap_or_default();
+        }
+        if let Ok(handling) = env::var("RADARR_PROPER_HANDLING") {
+            config.import.proper_handling = handling.parse().unwrap_or_default();
+        }
+        if let Ok(file_date) = env::var("RADARR_FILE_DATE") {
+            config.import.file_date = file_date.parse().unwrap_or_default();
+        }
+        if let Ok(template) = env::var("RADARR_MOVIE_TEMPLATE") {
+            config.import.movie_template = template;
+        }
+        if let Ok(template) = env::var("RADARR_FOLDER_TEMPLATE") {
+            config.import.folder_template = template;
+        }
+        if let Ok(colon_replacement) = env::var("RADARR_COLON_REPLACEMENT") {
+            config.import.colon_replacement = colon_replacement.parse()?;
+        }
+        if let Ok(diagnostics_dir) = env::var("RADARR_DIAGNOSTICS_DIR") {
+            config.import.diagnostics_dir = diagnostics_dir;
+        }
+        if let Ok(preview_dir) = env::var("RADARR_PREVIEW_DIR") {
+            config.import.preview_dir = Some(preview_dir);
+        }
+        if let Ok(min_progress) = env::var("RADARR_PREVIEW_MIN_PROGRESS") {
+            config.import.preview_min_progress = min_progress
+                .parse()
+                .unwrap_or_else(|_| default_preview_min_progress());
+        }
+
+        // Failed download handling
+        if let Ok(enabled) = env::var("RADARR_FAILED_DOWNLOAD_HANDLING") {
+            config.download_handling.enabled = enabled.parse().unwrap_or(true);
+        }
+        if let Ok(blocklist) = env::var("RADARR_BLOCKLIST_FAILED_DOWNLOADS") {
+            config.download_handling.blocklist_failed = blocklist.parse().unwrap_or(true);
+        }
+        if let Ok(remove) = env::var("RADARR_REMOVE_FAILED_DOWNLOADS") {
+            config.download_handling.remove_from_client = remove.parse().unwrap_or(true);
+        }
+        if let Ok(delete_files) = env::var("RADARR_DELETE_FAILED_DOWNLOAD_FILES") {
+            config.download_handling.delete_files = delete_files.parse().unwrap_or(true);
+        }
+        if let Ok(redownload) = env::var("RADARR_REDOWNLOAD_FAILED") {
+            config.download_handling.redownload = redownload.parse().unwrap_or(true);
+        }
+
+        // Stalled download detection
+        if let Ok(enabled) = env::var("RADARR_STALL_DETECTION") {
+            config.stall_detection.enabled = enabled.parse().unwrap_or(true);
+        }
+        if let Ok(speed) = env::var("RADARR_STALL_MIN_SPEED") {
+            config.stall_detection.min_speed_bytes_per_sec = speed.parse().unwrap_or(10 * 1024);
+        }
+        if let Ok(eta) = env::var("RADARR_STALL_MAX_ETA_MINUTES") {
+            config.stall_detection.max_eta_minutes = eta.parse().unwrap_or(0);
+        }
+        if let Ok(minutes) = env::var("RADARR_STALL_MINUTES") {
+            config.stall_detection.stall_minutes = minutes.parse().unwrap_or(30);
+        }
+
+        // Seeding after import
+        if let Ok(enabled) = env::var("RADARR_SEEDING_CLEANUP") {
+            config.seeding.enabled = parse_env_value("RADARR_SEEDING_CLEANUP", &enabled)?;
+        }
+        // An empty value turns the limit off
+        if let Ok(ratio) = env::var("RADARR_SEED_RATIO") {
+            config.seeding.seed_ratio = match ratio.trim() {
+                "" => None,
+                ratio => Some(parse_env_value("RADARR_SEED_RATIO", ratio)?),
+            };
+        }
+        if let Ok(minutes) = env::var("RADARR_SEED_TIME_MINUTES") {
+            config.seeding.seed_time_minutes = match minutes.trim() {
+                "" => None,
+                minutes => Some(parse_env_value("RADARR_SEED_TIME_MINUTES", minutes)?),
+            };
+        }
+        if let Ok(delete) = env::var("RADARR_SEEDING_DELETE_HARDLINKED") {
+            config.seeding.delete_hardlinked_data =
+                parse_env_value("RADARR_SEEDING_DELETE_HARDLINKED", &delete)?;
+        }
+        if let Ok(interval) = env::var("RADARR_SEEDING_INTERVAL_MINUTES") {
+            config.seeding.check_interval_minutes =
+                parse_env_value("RADARR_SEEDING_INTERVAL_MINUTES", &interval)?;
+        }
+
+        // Release languages
+        if let Ok(languages) = env::var("RADARR_PREFERRED_LANGUAGES") {
+            config.languages.preferred =
+                parse_language_list(&languages, "RADARR_PREFERRED_LANGUAGES")?;
+        }
+        if let Ok(languages) = env::var("RADARR_ALLOWED_LANGUAGES") {
+            config.languages.allowed = parse_language_list(&languages, "RADARR_ALLOWED_LANGUAGES")?;
+        }
+
+        // Desired state drift detection
+        if let Ok(path) = env::var("RADARR_DESIRED_STATE_PATH") {
+            config.desired_state.spec_path = Some(path).filter(|path| !path.is_empty());
+        }
+        if let Ok(auto_apply) = env::var("RADARR_DESIRED_STATE_AUTO_APPLY") {
+            config.desired_state.auto_apply = auto_apply.parse().unwrap_or(false);
+        }
+        if let Ok(interval) = env::var("RADARR_DESIRED_STATE_INTERVAL_MINUTES") {
+            config.desired_state.check_interval_minutes = interval.parse().unwrap_or(60);
+        }
+
+        // Cache backend
+        if let Ok(backend) = env::var("RADARR_CACHE_BACKEND") {
+            config.cache.backend =
+                backend
+                    .parse()
+                    .map_err(|e: radarr_infrastructure::cache::CacheError| {
+                        RadarrError::ValidationError {
+                            field: "RADARR_CACHE_BACKEND".to_string(),
+                            message: e.to_string(),
+                        }
+                    })?;
+        }
+        if let Ok(url) = env::var("REDIS_URL") {
+            config.cache.redis_url = url;
+        }
+        if let Ok(prefix) = env::var("RADARR_CACHE_KEY_PREFIX") {
+            config.cache.key_prefix = prefix;
+        }
+        if let Ok(max_entries) = env::var("RADARR_CACHE_MAX_ENTRIES") {
+            config.cache.max_entries = max_entries.parse().unwrap_or(10_000);
+        }
+        if let Ok(ttl) = env::var("RADARR_CACHE_TMDB_TTL_SECONDS") {
+            config.cache.tmdb_ttl_seconds = ttl.parse().unwrap_or(6 * 3600);
+        }
+        if let Ok(ttl) = env::var("RADARR_CACHE_STREAMING_TTL_SECONDS") {
+            config.cache.streaming_ttl_seconds = ttl.parse().unwrap_or(3600);
+        }
+
+        // Artwork cache
+        if let Ok(directory) = env::var("RADARR_MEDIA_COVER_DIR") {
+            config.media_covers.directory = directory;
+        }
+
+        // Downloads folder cleanup
+        if let Ok(enabled) = env::var("RADARR_DOWNLOADS_CLEANUP") {
+            config.downloads_cleanup.enabled =
+                parse_env_value("RADARR_DOWNLOADS_CLEANUP", &enabled)?;
+        }
+        if let Ok(directory) = env::var("RADARR_DOWNLOADS_DIR") {
+            config.downloads_cleanup.downloads_dir = directory;
+        }
+        if let Ok(hours) = env::var("RADARR_DOWNLOADS_CLEANUP_GRACE_HOURS") {
+            config.downloads_cleanup.grace_period_hours =
+                parse_env_value("RADARR_DOWNLOADS_CLEANUP_GRACE_HOURS", &hours)?;
+        }
+        if let Ok(globs) = env::var("RADARR_DOWNLOADS_CLEANUP_EXCLUDE") {
+            config.downloads_cleanup.exclude_globs = globs
+                .split(',')
+                .map(str::trim)
+                .filter(|glob| !glob.is_empty())
+                .map(String::from)
+                .collect();
+        }
+        if let Ok(dry_run) = env::var("RADARR_DOWNLOADS_CLEANUP_DRY_RUN") {
+            config.downloads_cleanup.dry_run =
+                parse_env_value("RADARR_DOWNLOADS_CLEANUP_DRY_RUN", &dry_run)?;
+        }
+        if let Ok(hours) = env::var("RADARR_DOWNLOADS_CLEANUP_INTERVAL_HOURS") {
+            config.downloads_cleanup.interval_hours =
+                parse_env_value("RADARR_DOWNLOADS_CLEANUP_INTERVAL_HOURS", &hours)?;
+        }
+
+        // Configuration backups
+        if let Ok(directory) = env::var("RADARR_BACKUP_DIR") {
+            config.backups.directory = directory;
+        }
+        if let Ok(days) = env::var("RADARR_BACKUP_INTERVAL_DAYS") {
+            config.backups.interval_days = days.parse().unwrap_or(7);
+        }
+        if let Ok(count) = env::var("RADARR_BACKUP_RETENTION") {
+            config.backups.max_backups = count.parse().unwrap_or(4);
+        }
+
+        // Update checks
+        if let Ok(enabled) = env::var("RADARR_UPDATE_CHECK_ENABLED") {
+            config.updates.enabled = enabled.parse().unwrap_or(true);
+        }
+        if let Ok(repository) = env::var("RADARR_UPDATE_REPOSITORY") {
+            config.updates.repository = repository;
+        }
+        if let Ok(hours) = env::var("RADARR_UPDATE_CHECK_INTERVAL_HOURS") {
+            config.updates.check_interval_hours = hours.parse().unwrap_or(6);
+        }
 
         // Logging configuration
         if let Ok(level) = env::var("RUST_LOG") {
@@ -402,6 +1145,20 @@ impl AppConfig {
             });
         }
 
+        if self.database.connect_retries == 0 {
+            return Err(RadarrError::ValidationError {
+                field: "database.connect_retries".to_string(),
+                message: "Database connect retries must be greater than 0".to_string(),
+            });
+        }
+
+        if self.database.pool_check_interval_secs == 0 {
+            return Err(RadarrError::ValidationError {
+                field: "database.pool_check_interval_secs".to_string(),
+                message: "Database pool check interval must be greater than 0".to_string(),
+            });
+        }
+
         // Validate Prowlarr config
         if self.prowlarr.base_url.is_empty() {
             return Err(RadarrError::ValidationError {
@@ -420,6 +1177,142 @@ impl AppConfig {
             });
         }
 
+        if self.sabnzbd.enabled && self.sabnzbd.api_key.is_empty() {
+            return Err(RadarrError::ValidationError {
+                field: "sabnzbd.api_key".to_string(),
+                message: "SABnzbd API key cannot be empty".to_string(),
+            });
+        }
+
+        for (indexer_id, client) in &self.download_clients.indexer_routes {
+            let configured = match client.as_str() {
+                "qbittorrent" => true,
+                "sabnzbd" => self.sabnzbd.enabled,
+                _ => false,
+            };
+            if !configured {
+                return Err(RadarrError::ValidationError {
+                    field: "download_clients.indexer_routes".to_string(),
+                    message: format!(
+                        "Indexer {} is routed to '{}', which is not a configured download client",
+                        indexer_id, client
+                    ),
+                });
+            }
+        }
+
         Ok(())
     }
+
+    /// Host settings as served by /api/v3/config/host
+    pub fn host_settings(&self) -> HostConfigResource {
+        HostConfigResource {
+            port: self.server.port,
+            locale: self.server.locale.clone(),
+        }
+    }
+
+    /// Media management settings as served by /api/v3/config/mediamanagement
+    pub fn media_management_settings(&self) -> MediaManagementConfigResource {
+        MediaManagementConfigResource {
+            download_propers_and_repacks: self.import.proper_handling,
+            file_date: self.import.file_date,
+            samples: self.import.extras_samples,
+            trailers: self.import.extras_trailers,
+            featurettes: self.import.extras_featurettes,
+            other_extras: self.import.extras_other,
+        }
+    }
+
+    /// Use host settings saved through the API instead of the environment's
+    pub fn apply_host_settings(&mut self, settings: HostConfigResource) {
+        self.server.port = settings.port;
+        self.server.locale = settings.locale;
+    }
+
+    /// Use naming settings saved through the API instead of the environment's
+    pub fn apply_naming_settings(&mut self, settings: NamingConfigResource) {
+        self.import.movie_template = settings.standard_movie_format;
+        self.import.folder_template = settings.movie_folder_format;
+        self.import.colon_replacement = settings.colon_replacement_format;
+    }
+
+    /// Use media management settings saved through the API instead of the
+    /// environment's
+    pub fn apply_media_management_settings(&mut self, settings: MediaManagementConfigResource) {
+        self.import.proper_handling = settings.download_propers_and_repacks;
+        self.import.file_date = settings.file_date;
+        self.import.extras_samples = settings.samples;
+        self.import.extras_trailers = settings.trailers;
+        self.import.extras_featurettes = settings.featurettes;
+        self.import.extras_other = settings.other_extras;
+    }
+}
+
+/// Parse the value of environment variable `name`
+///
+/// An invalid value is an error rather than the default, so a typo can't
+/// silently change behaviour.
+fn parse_env_value<T: std::str::FromStr>(name: &str, value: &str) -> Result<T> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| RadarrError::ConfigurationError {
+            field: name.to_string(),
+            message: format!("Invalid value: {}", value),
+        })
+}
+
+/// Parse a comma-separated list of language names, e.g. `french,english`
+fn parse_language_list(value: &str, field: &str) -> Result<Vec<radarr_core::Language>> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| {
+            name.parse().map_err(|_| RadarrError::ValidationError {
+                field: field.to_string(),
+                message: format!("Unknown language '{}'", name),
+            })
+        })
+        .collect()
+}
+
+/// Parse indexer routes given as `indexer_id=client` pairs separated by
+/// commas, e.g. `3=sabnzbd,7=qbittorrent`
+fn parse_download_client_routes(value: &str) -> Result<HashMap<i32, String>> {
+    let invalid = |entry: &str| RadarrError::ValidationError {
+        field: "DOWNLOAD_CLIENT_INDEXER_ROUTES".to_string(),
+        message: format!("Invalid route '{}', expected indexer_id=client", entry),
+    };
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (id, client) = entry.split_once('=').ok_or_else(|| invalid(entry))?;
+            let id = id.trim().parse().map_err(|_| invalid(entry))?;
+            Ok((id, client.trim().to_lowercase()))
+        })
+        .collect()
+}
+
+/// Parse per-indexer rate limits given as `id=requests_per_minute` pairs
+/// separated by commas, e.g. `12=10,15=30`
+fn parse_indexer_rate_limits(value: &str) -> Result<HashMap<i32, u32>> {
+    let invalid = |entry: &str| RadarrError::ValidationError {
+        field: "PROWLARR_INDEXER_RATE_LIMITS".to_string(),
+        message: format!("Invalid indexer rate limit '{}', expected id=limit", entry),
+    };
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (id, limit) = entry.split_once('=').ok_or_else(|| invalid(entry))?;
+            let id = id.trim().parse().map_err(|_| invalid(entry))?;
+            let limit = limit.trim().parse().map_err(|_| invalid(entry))?;
+            Ok((id, limit))
+        })
+        .collect()
 }
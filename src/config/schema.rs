@@ -0,0 +1,1282 @@
+//! Machine-readable configuration schema
+//!
+//! Describes every configurable section (field names, types, defaults, help
+//! text and validation constraints) so the web UI can render settings forms
+//! without hardcoding them. Defaults are read from the `Default` impls of the
+//! config structs so the schema cannot drift from the real values.
+
+use super::{
+    AppConfig, DatabaseConfig, DownloadClientsConfig, DownloadsCleanupConfig, ImportConfig,
+    LoggingConfig, OmdbConfig, ProwlarrConfig, QBittorrentConfig, RssServiceConfig, SabnzbdConfig,
+    ServerConfig, TmdbConfig,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Type of a configuration field as rendered by the UI
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldType {
+    String,
+    Integer,
+    Float,
+    Boolean,
+    Url,
+    Password,
+    Select,
+}
+
+/// Validation constraints for a field
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldConstraints {
+    pub required: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<usize>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<String>,
+}
+
+/// Description of a single configuration field
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigField {
+    pub name: String,
+    pub label: String,
+    #[serde(rename = "type")]
+    pub field_type: FieldType,
+    pub default: Value,
+    pub help_text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub env_var: Option<String>,
+    pub advanced: bool,
+    pub constraints: FieldConstraints,
+}
+
+impl ConfigField {
+    pub fn new(name: impl Into<String>, label: impl Into<String>, field_type: FieldType) -> Self {
+        Self {
+            name: name.into(),
+            label: label.into(),
+            field_type,
+            default: Value::Null,
+            help_text: String::new(),
+            env_var: None,
+            advanced: false,
+            constraints: FieldConstraints::default(),
+        }
+    }
+
+    pub fn with_default(mut self, default: impl Into<Value>) -> Self {
+        self.default = default.into();
+        self
+    }
+
+    pub fn with_help(mut self, help_text: impl Into<String>) -> Self {
+        self.help_text = help_text.into();
+        self
+    }
+
+    pub fn with_env_var(mut self, env_var: impl Into<String>) -> Self {
+        self.env_var = Some(env_var.into());
+        self
+    }
+
+    pub fn required(mut self) -> Self {
+        self.constraints.required = true;
+        self
+    }
+
+    pub fn advanced(mut self) -> Self {
+        self.advanced = true;
+        self
+    }
+
+    pub fn with_range(mut self, min: Option<f64>, max: Option<f64>) -> Self {
+        self.constraints.min = min;
+        self.constraints.max = max;
+        self
+    }
+
+    pub fn with_min_length(mut self, min_length: usize) -> Self {
+        self.constraints.min_length = Some(min_length);
+        self
+    }
+
+    pub fn with_options<I, S>(mut self, options: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.constraints.options = options.into_iter().map(Into::into).collect();
+        self
+    }
+}
+
+/// Description of a configuration section
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigSection {
+    pub name: String,
+    pub label: String,
+    pub description: String,
+    pub fields: Vec<ConfigField>,
+}
+
+impl ConfigSection {
+    pub fn new(
+        name: impl Into<String>,
+        label: impl Into<String>,
+        description: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            label: label.into(),
+            description: description.into(),
+            fields: Vec::new(),
+        }
+    }
+
+    pub fn field(mut self, field: ConfigField) -> Self {
+        self.fields.push(field);
+        self
+    }
+}
+
+/// Complete configuration schema
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigSchema {
+    pub version: u32,
+    pub sections: Vec<ConfigSection>,
+}
+
+/// Implemented by config structs that can describe themselves
+pub trait ConfigSchemaProvider {
+    fn config_section() -> ConfigSection;
+}
+
+impl ConfigSchemaProvider for ServerConfig {
+    fn config_section() -> ConfigSection {
+        let defaults = Self::default();
+        ConfigSection::new("server", "Server", "HTTP server and API settings")
+            .field(
+                ConfigField::new("host", "Bind Address", FieldType::String)
+                    .with_default(defaults.host)
+                    .with_help("Address the HTTP server listens on")
+                    .with_env_var("RADARR_HOST")
+                    .required(),
+            )
+            .field(
+                ConfigField::new("port", "Port", FieldType::Integer)
+                    .with_default(defaults.port)
+                    .with_help("Port the HTTP server listens on")
+                    .with_env_var("RADARR_PORT")
+                    .with_range(Some(1.0), Some(65535.0))
+                    .required(),
+            )
+            .field(
+                ConfigField::new("api_key", "API Key", FieldType::Password)
+                    .with_help("Key required in the X-Api-Key header for API requests")
+                    .with_env_var("RADARR_API_KEY")
+                    .with_min_length(8)
+                    .required(),
+            )
+            .field(
+                ConfigField::new("max_connections", "Max Connections", FieldType::Integer)
+                    .with_default(defaults.max_connections)
+                    .with_help("Maximum concurrent HTTP connections")
+                    .with_env_var("RADARR_MAX_CONNECTIONS")
+                    .with_range(Some(1.0), None)
+                    .advanced(),
+            )
+            .field(
+                ConfigField::new("request_timeout", "Request Timeout", FieldType::Integer)
+                    .with_default(defaults.request_timeout)
+                    .with_help("Request timeout in seconds")
+                    .with_env_var("RADARR_REQUEST_TIMEOUT")
+                    .with_range(Some(1.0), None)
+                    .advanced(),
+            )
+            .field(
+                ConfigField::new(
+                    "shutdown_grace_period",
+                    "Shutdown Grace Period",
+                    FieldType::Integer,
+                )
+                .with_default(defaults.shutdown_grace_period)
+                .with_help("Seconds background work may take to finish on shutdown")
+                .with_env_var("RADARR_SHUTDOWN_GRACE_PERIOD")
+                .with_range(Some(0.0), None)
+                .advanced(),
+            )
+            .field(
+                ConfigField::new("locale", "Language", FieldType::Select)
+                    .with_default(defaults.locale)
+                    .with_help("Language for notifications and health check messages")
+                    .with_env_var("RADARR_LOCALE")
+                    .with_options(radarr_core::i18n::localizer().available_locales()),
+            )
+    }
+}
+
+impl ConfigSchemaProvider for DatabaseConfig {
+    fn config_section() -> ConfigSection {
+        let defaults = Self::default();
+        ConfigSection::new("database", "Database", "PostgreSQL connection settings")
+            .field(
+                ConfigField::new("url", "Database URL", FieldType::Password)
+                    .with_help("PostgreSQL connection string (contains credentials)")
+                    .with_env_var("DATABASE_URL")
+                    .required(),
+            )
+            .field(
+                ConfigField::new("max_connections", "Pool Size", FieldType::Integer)
+                    .with_default(defaults.max_connections)
+                    .with_help("Maximum number of connections in the pool")
+                    .with_env_var("DATABASE_MAX_CONNECTIONS")
+                    .with_range(Some(1.0), None)
+                    .advanced(),
+            )
+            .field(
+                ConfigField::new("connect_timeout", "Connect Timeout", FieldType::Integer)
+                    .with_default(defaults.connect_timeout)
+                    .with_help("Connection timeout in seconds")
+                    .with_env_var("DATABASE_CONNECT_TIMEOUT")
+                    .with_range(Some(1.0), None)
+                    .advanced(),
+            )
+            .field(
+                ConfigField::new("log_queries", "Log Queries", FieldType::Boolean)
+                    .with_default(defaults.log_queries)
+                    .with_help("Log every SQL query (debugging only)")
+                    .with_env_var("DATABASE_LOG_QUERIES")
+                    .advanced(),
+            )
+            .field(
+                ConfigField::new("read_replica_url", "Read Replica URL", FieldType::Password)
+                    .with_help(
+                        "PostgreSQL replica for library listings and history; \
+                         reads fall back to the primary while it lags or is down",
+                    )
+                    .with_env_var("DATABASE_READ_REPLICA_URL")
+                    .advanced(),
+            )
+            .field(
+                ConfigField::new(
+                    "replica_max_lag_secs",
+                    "Replica Max Lag",
+                    FieldType::Integer,
+                )
+                .with_default(defaults.replica_max_lag_secs)
+                .with_help("Replication lag in seconds above which reads use the primary")
+                .with_env_var("DATABASE_REPLICA_MAX_LAG_SECS")
+                .with_range(Some(0.0), None)
+                .advanced(),
+            )
+            .field(
+                ConfigField::new("connect_retries", "Connect Retries", FieldType::Integer)
+                    .with_default(defaults.connect_retries)
+                    .with_help("Attempts to reach the database at startup before giving up")
+                    .with_env_var("DATABASE_CONNECT_RETRIES")
+                    .with_range(Some(1.0), None)
+                    .advanced(),
+            )
+            .field(
+                ConfigField::new(
+                    "pool_check_interval_secs",
+                    "Pool Check Interval",
+                    FieldType::Integer,
+                )
+                .with_default(defaults.pool_check_interval_secs)
+                .with_help("Seconds between probes of the connection pool")
+                .with_env_var("DATABASE_POOL_CHECK_INTERVAL_SECS")
+                .with_range(Some(1.0), None)
+                .advanced(),
+            )
+            .field(
+                ConfigField::new("migration_mode", "Migrations", FieldType::Select)
+                    .with_default(defaults.migration_mode.as_str())
+                    .with_help(
+                        "Apply pending migrations at startup, refuse to start while any \
+                         are pending, or print them and exit",
+                    )
+                    .with_env_var("DATABASE_MIGRATIONS")
+                    .with_options(["apply", "check", "dry-run"])
+                    .advanced(),
+            )
+    }
+}
+
+impl ConfigSchemaProvider for ProwlarrConfig {
+    fn config_section() -> ConfigSection {
+        let defaults = Self::default();
+        ConfigSection::new("prowlarr", "Prowlarr", "Indexer aggregation via Prowlarr")
+            .field(
+                ConfigField::new("base_url", "URL", FieldType::Url)
+                    .with_default(defaults.base_url)
+                    .with_help("Base URL of the Prowlarr instance")
+                    .with_env_var("PROWLARR_BASE_URL")
+                    .required(),
+            )
+            .field(
+                ConfigField::new("api_key", "API Key", FieldType::Password)
+                    .with_help("Prowlarr API key (Settings > General)")
+                    .with_env_var("PROWLARR_API_KEY"),
+            )
+            .field(
+                ConfigField::new("timeout", "Timeout", FieldType::Integer)
+                    .with_default(defaults.timeout)
+                    .with_help("Request timeout in seconds")
+                    .with_env_var("PROWLARR_TIMEOUT")
+                    .with_range(Some(1.0), None)
+                    .advanced(),
+            )
+            .field(
+                ConfigField::new(
+                    "max_requests_per_minute",
+                    "Requests Per Minute",
+                    FieldType::Integer,
+                )
+                .with_default(defaults.max_requests_per_minute)
+                .with_help("Rate limit for requests sent to Prowlarr")
+                .with_env_var("PROWLARR_RATE_LIMIT")
+                .with_range(Some(1.0), None)
+                .advanced(),
+            )
+            .field(
+                ConfigField::new(
+                    "default_indexer_rate_limit",
+                    "Searches Per Indexer Per Minute",
+                    FieldType::Integer,
+                )
+                .with_default(defaults.default_indexer_rate_limit)
+                .with_help("Searches per minute for each indexer without its own limit (0 = no limit)")
+                .with_env_var("PROWLARR_INDEXER_RATE_LIMIT")
+                .with_range(Some(0.0), None)
+                .advanced(),
+            )
+            .field(
+                ConfigField::new("indexer_rate_limits", "Indexer Rate Limits", FieldType::String)
+                    .with_help("Searches per minute by indexer ID, e.g. 12=10,15=30")
+                    .with_env_var("PROWLARR_INDEXER_RATE_LIMITS")
+                    .advanced(),
+            )
+            .field(
+                ConfigField::new(
+                    "backoff_initial_seconds",
+                    "Initial Backoff",
+                    FieldType::Integer,
+                )
+                .with_default(defaults.backoff_initial_seconds)
+                .with_help("Seconds an indexer sits out after a 429 or temporary failure, doubling with each one after")
+                .with_range(Some(1.0), None)
+                .advanced(),
+            )
+            .field(
+                ConfigField::new("backoff_max_seconds", "Maximum Backoff", FieldType::Integer)
+                    .with_default(defaults.backoff_max_seconds)
+                    .with_help("Longest an indexer sits out, including its Retry-After")
+                    .with_env_var("PROWLARR_BACKOFF_MAX_SECONDS")
+                    .with_range(Some(1.0), None)
+                    .advanced(),
+            )
+            .field(
+                ConfigField::new("user_agent", "User Agent", FieldType::String)
+                    .with_default(defaults.user_agent)
+                    .with_help("User agent sent with indexer requests")
+                    .advanced(),
+            )
+            .field(
+                ConfigField::new("verify_ssl", "Verify SSL", FieldType::Boolean)
+                    .with_default(defaults.verify_ssl)
+                    .with_help("Validate TLS certificates when connecting")
+                    .advanced(),
+            )
+    }
+}
+
+impl ConfigSchemaProvider for QBittorrentConfig {
+    fn config_section() -> ConfigSection {
+        let defaults = Self::default();
+        ConfigSection::new("qbittorrent", "qBittorrent", "Torrent download client")
+            .field(
+                ConfigField::new("base_url", "URL", FieldType::Url)
+                    .with_default(defaults.base_url)
+                    .with_help("Base URL of the qBittorrent Web UI")
+                    .with_env_var("QBITTORRENT_BASE_URL")
+                    .required(),
+            )
+            .field(
+                ConfigField::new("username", "Username", FieldType::String)
+                    .with_default(defaults.username)
+                    .with_help("Web UI username")
+                    .with_env_var("QBITTORRENT_USERNAME"),
+            )
+            .field(
+                ConfigField::new("password", "Password", FieldType::Password)
+                    .with_help("Web UI password")
+                    .with_env_var("QBITTORRENT_PASSWORD"),
+            )
+            .field(
+                ConfigField::new("timeout", "Timeout", FieldType::Integer)
+                    .with_default(defaults.timeout)
+                    .with_help("Request timeout in seconds")
+                    .with_env_var("QBITTORRENT_TIMEOUT")
+                    .with_range(Some(1.0), None)
+                    .advanced(),
+            )
+            .field(
+                ConfigField::new(
+                    "free_space_headroom_mb",
+                    "Free Space Headroom",
+                    FieldType::Integer,
+                )
+                .with_default(defaults.free_space_headroom_mb)
+                .with_help(
+                    "Free space (MiB) that must remain on the client's disk after a release \
+                     is grabbed",
+                )
+                .with_env_var("QBITTORRENT_FREE_SPACE_HEADROOM_MB")
+                .with_range(Some(0.0), None)
+                .advanced(),
+            )
+            .field(
+                ConfigField::new(
+                    "skip_unwanted_files",
+                    "Skip Unwanted Files",
+                    FieldType::Boolean,
+                )
+                .with_default(defaults.skip_unwanted_files)
+                .with_help("Don't download samples, proofs and other files besides the movie")
+                .with_env_var("QBITTORRENT_SKIP_UNWANTED_FILES"),
+            )
+            .field(
+                ConfigField::new("priority", "Priority", FieldType::Integer)
+                    .with_default(defaults.priority)
+                    .with_help("Order among torrent clients; lower values are tried first")
+                    .with_env_var("QBITTORRENT_PRIORITY")
+                    .advanced(),
+            )
+    }
+}
+
+impl ConfigSchemaProvider for SabnzbdConfig {
+    fn config_section() -> ConfigSection {
+        let defaults = Self::default();
+        ConfigSection::new("sabnzbd", "SABnzbd", "Usenet download client")
+            .field(
+                ConfigField::new("enabled", "Enabled", FieldType::Boolean)
+                    .with_default(defaults.enabled)
+                    .with_help("Send usenet releases to SABnzbd (on when an API key is set)"),
+            )
+            .field(
+                ConfigField::new("base_url", "URL", FieldType::Url)
+                    .with_default(defaults.base_url)
+                    .with_help("Base URL of the SABnzbd web interface")
+                    .with_env_var("SABNZBD_BASE_URL"),
+            )
+            .field(
+                ConfigField::new("api_key", "API Key", FieldType::Password)
+                    .with_help("API key from SABnzbd's General settings")
+                    .with_env_var("SABNZBD_API_KEY"),
+            )
+            .field(
+                ConfigField::new("timeout", "Timeout", FieldType::Integer)
+                    .with_default(defaults.timeout)
+                    .with_help("Request timeout in seconds")
+                    .with_env_var("SABNZBD_TIMEOUT")
+                    .with_range(Some(1.0), None)
+                    .advanced(),
+            )
+            .field(
+                ConfigField::new("priority", "Priority", FieldType::Integer)
+                    .with_default(defaults.priority)
+                    .with_help("Order among usenet clients; lower values are tried first")
+                    .with_env_var("SABNZBD_PRIORITY")
+                    .advanced(),
+            )
+    }
+}
+
+impl ConfigSchemaProvider for DownloadClientsConfig {
+    fn config_section() -> ConfigSection {
+        ConfigSection::new(
+            "download_clients",
+            "Download Clients",
+            "Routing grabs between download clients",
+        )
+        .field(
+            ConfigField::new("indexer_routes", "Indexer Routes", FieldType::String)
+                .with_help("Download client by indexer ID, e.g. 3=sabnzbd,7=qbittorrent")
+                .with_env_var("DOWNLOAD_CLIENT_INDEXER_ROUTES")
+                .advanced(),
+        )
+    }
+}
+
+impl ConfigSchemaProvider for ImportConfig {
+    fn config_section() -> ConfigSection {
+        let defaults = Self::default();
+        ConfigSection::new("import", "Import", "Completed download import pipeline")
+            .field(
+                ConfigField::new("dry_run", "Dry Run", FieldType::Boolean)
+                    .with_default(defaults.dry_run)
+                    .with_help("Analyze imports without moving or linking files"),
+            )
+            .field(
+                ConfigField::new("min_confidence", "Minimum Confidence", FieldType::Float)
+                    .with_default(defaults.min_confidence)
+                    .with_help("Minimum match confidence required to import a file")
+                    .with_range(Some(0.0), Some(1.0)),
+            )
+            .field(
+                ConfigField::new("skip_samples", "Skip Samples", FieldType::Boolean)
+                    .with_default(defaults.skip_samples)
+                    .with_help("Ignore sample files during import"),
+            )
+            .field(
+                ConfigField::new("continue_on_error", "Continue On Error", FieldType::Boolean)
+                    .with_default(defaults.continue_on_error)
+                    .with_help("Keep importing remaining files when one fails")
+                    .advanced(),
+            )
+            .field(
+                ConfigField::new("max_parallel", "Parallel Imports", FieldType::Integer)
+                    .with_default(defaults.max_parallel)
+                    .with_help("Maximum number of files imported concurrently")
+                    .with_range(Some(1.0), Some(32.0))
+                    .advanced(),
+            )
+            .field(
+                ConfigField::new("scan_concurrency", "Scan Concurrency", FieldType::Integer)
+                    .with_default(defaults.scan_concurrency)
+                    .with_help("Directories read at the same time while scanning for files")
+                    .with_env_var("RADARR_SCAN_CONCURRENCY")
+                    .with_range(Some(1.0), Some(64.0))
+                    .advanced(),
+            )
+            .field(
+                ConfigField::new(
+                    "transcode_enabled",
+                    "Transcode Before Import",
+                    FieldType::Boolean,
+                )
+                .with_default(defaults.transcode_enabled)
+                .with_help("Run files through ffmpeg before importing them")
+                .with_env_var("RADARR_TRANSCODE_ENABLED"),
+            )
+            .field(
+                ConfigField::new("ffmpeg_path", "ffmpeg Path", FieldType::String)
+                    .with_default(defaults.ffmpeg_path)
+                    .with_help("ffmpeg binary used for transcoding")
+                    .with_env_var("RADARR_FFMPEG_PATH")
+                    .advanced(),
+            )
+            .field(
+                ConfigField::new("transcode_profile", "Transcode Profile", FieldType::String)
+                    .with_help("Profile to run; the first profile when empty")
+                    .with_env_var("RADARR_TRANSCODE_PROFILE")
+                    .advanced(),
+            )
+            .field(
+                ConfigField::new(
+                    "transcode_work_dir",
+                    "Transcode Directory",
+                    FieldType::String,
+                )
+                .with_help("Scratch directory for transcode output")
+                .with_env_var("RADARR_TRANSCODE_WORK_DIR")
+                .advanced(),
+            )
+            .field(
+                ConfigField::new(
+                    "extract_enabled",
+                    "Extract Archives",
+                    FieldType::Boolean,
+                )
+                .with_default(defaults.extract_enabled)
+                .with_help(
+                    "Extract RAR and zip archives in downloads before importing them; \
+                     needs a build with the archives feature",
+                )
+                .with_env_var("RADARR_EXTRACT_ENABLED"),
+            )
+            .field(
+                ConfigField::new("unrar_path", "unrar Path", FieldType::String)
+                    .with_default(defaults.unrar_path)
+                    .with_help("unrar binary used for RAR archives")
+                    .with_env_var("RADARR_UNRAR_PATH")
+                    .advanced(),
+            )
+            .field(
+                ConfigField::new("extract_work_dir", "Extraction Directory", FieldType::String)
+                    .with_help("Scratch directory archives are extracted to")
+                    .with_env_var("RADARR_EXTRACT_WORK_DIR")
+                    .advanced(),
+            )
+            .field(
+                ConfigField::new("extras_samples", "Samples", FieldType::Select)
+                    .with_default(serde_json::json!(defaults.extras_samples))
+                    .with_help("Skip, import into a Samples folder or delete sample files")
+                    .with_env_var("RADARR_EXTRAS_SAMPLES")
+                    .with_options(["skip", "import", "delete"]),
+            )
+            .field(
+                ConfigField::new("extras_trailers", "Trailers", FieldType::Select)
+                    .with_default(serde_json::json!(defaults.extras_trailers))
+                    .with_help("Skip, import into a Trailers folder or delete trailers")
+                    .with_env_var("RADARR_EXTRAS_TRAILERS")
+                    .with_options(["skip", "import", "delete"]),
+            )
+            .field(
+                ConfigField::new("extras_featurettes", "Featurettes", FieldType::Select)
+                    .with_default(serde_json::json!(defaults.extras_featurettes))
+                    .with_help("Skip, import into a Featurettes folder or delete featurettes")
+                    .with_env_var("RADARR_EXTRAS_FEATURETTES")
+                    .with_options(["skip", "import", "delete"]),
+            )
+            .field(
+                ConfigField::new("extras_other", "Other Extras", FieldType::Select)
+                    .with_default(serde_json::json!(defaults.extras_other))
+                    .with_help(
+                        "Skip, import or delete behind the scenes, deleted scenes, \
+                         interviews and other extras",
+                    )
+                    .with_env_var("RADARR_EXTRAS_OTHER")
+                    .with_options(["skip", "import", "delete"]),
+            )
+            .field(
+                ConfigField::new(
+                    "proper_handling",
+                    "Download Propers and Repacks",
+                    FieldType::Select,
+                )
+                .with_default(serde_json::json!(defaults.proper_handling))
+                .with_help(
+                    "Prefer grabs propers and repacks to replace a file of the same \
+                     quality; ignore treats them like any other release",
+                )
+                .with_env_var("RADARR_PROPER_HANDLING")
+                .with_options(["prefer", "ignore"]),
+            )
+            .field(
+                ConfigField::new("file_date", "Change File Date", FieldType::Select)
+                    .with_default(serde_json::json!(defaults.file_date))
+                    .with_help("Set imported files' modification time to this date")
+                    .with_env_var("RADARR_FILE_DATE")
+                    .with_options(["none", "cinemas", "release", "original"]),
+            )
+            .field(
+                ConfigField::new("movie_template", "Movie File Format", FieldType::String)
+                    .with_default(defaults.movie_template)
+                    .with_help("Name of imported movie files, e.g. {Movie CleanTitle} ({Release Year}) {Quality Full}")
+                    .with_env_var("RADARR_MOVIE_TEMPLATE"),
+            )
+            .field(
+                ConfigField::new("folder_template", "Movie Folder Format", FieldType::String)
+                    .with_default(defaults.folder_template)
+                    .with_help("Name of movie folders, e.g. {Movie Title} ({Release Year})")
+                    .with_env_var("RADARR_FOLDER_TEMPLATE"),
+            )
+            .field(
+                ConfigField::new("colon_replacement", "Colon Replacement", FieldType::Select)
+                    .with_default(serde_json::json!(defaults.colon_replacement))
+                    .with_help("How colons in movie and folder names are replaced")
+                    .with_env_var("RADARR_COLON_REPLACEMENT")
+                    .with_options(["delete", "dash", "spaceDash", "spaceDashSpace", "smart"]),
+            )
+            .field(
+                ConfigField::new(
+                    "diagnostics_dir",
+                    "Diagnostics Directory",
+                    FieldType::String,
+                )
+                .with_default(defaults.diagnostics_dir)
+                .with_help("Directory forensic bundles of failed imports are written to")
+                .with_env_var("RADARR_DIAGNOSTICS_DIR")
+                .advanced(),
+            )
+            .field(
+                ConfigField::new("preview_dir", "Preview Directory", FieldType::String)
+                    .with_help("Previews of nearly complete sequential torrents; off when empty")
+                    .with_env_var("RADARR_PREVIEW_DIR")
+                    .advanced(),
+            )
+            .field(
+                ConfigField::new(
+                    "preview_min_progress",
+                    "Preview Threshold",
+                    FieldType::Float,
+                )
+                .with_default(defaults.preview_min_progress)
+                .with_help("Share of the movie file downloaded in order before a preview")
+                .with_range(Some(0.0), Some(1.0))
+                .with_env_var("RADARR_PREVIEW_MIN_PROGRESS")
+                .advanced(),
+            )
+    }
+}
+
+impl ConfigSchemaProvider for TmdbConfig {
+    fn config_section() -> ConfigSection {
+        let defaults = Self::default();
+        ConfigSection::new("tmdb", "TMDB", "The Movie Database metadata provider")
+            .field(
+                ConfigField::new("enabled", "Enabled", FieldType::Boolean)
+                    .with_default(defaults.enabled)
+                    .with_help("Enable TMDB lookups for movie metadata")
+                    .with_env_var("TMDB_ENABLED"),
+            )
+            .field(
+                ConfigField::new("api_key", "API Key", FieldType::Password)
+                    .with_help("TMDB v3 API key")
+                    .with_env_var("TMDB_API_KEY"),
+            )
+            .field(
+                ConfigField::new("timeout", "Timeout", FieldType::Integer)
+                    .with_default(defaults.timeout)
+                    .with_help("Request timeout in seconds")
+                    .with_env_var("TMDB_TIMEOUT")
+                    .with_range(Some(1.0), None)
+                    .advanced(),
+            )
+    }
+}
+
+impl ConfigSchemaProvider for OmdbConfig {
+    fn config_section() -> ConfigSection {
+        ConfigSection::new(
+            "omdb",
+            "OMDb",
+            "IMDb, Rotten Tomatoes and Metacritic ratings",
+        )
+        .field(
+            ConfigField::new("api_key", "API Key", FieldType::Password)
+                .with_help("OMDb API key; ratings are looked up when movies are refreshed")
+                .with_env_var("OMDB_API_KEY"),
+        )
+    }
+}
+
+impl ConfigSchemaProvider for radarr_core::DownloadHandlingConfig {
+    fn config_section() -> ConfigSection {
+        let defaults = Self::default();
+        ConfigSection::new(
+            "download_handling",
+            "Failed Download Handling",
+            "What happens to downloads that fail in the download client",
+        )
+        .field(
+            ConfigField::new("enabled", "Enabled", FieldType::Boolean)
+                .with_default(defaults.enabled)
+                .with_help("Handle failed downloads; otherwise they are retried as they are")
+                .with_env_var("RADARR_FAILED_DOWNLOAD_HANDLING"),
+        )
+        .field(
+            ConfigField::new("blocklist_failed", "Blocklist", FieldType::Boolean)
+                .with_default(defaults.blocklist_failed)
+                .with_help("Blocklist failed releases so searches skip them")
+                .with_env_var("RADARR_BLOCKLIST_FAILED_DOWNLOADS"),
+        )
+        .field(
+            ConfigField::new("remove_from_client", "Remove", FieldType::Boolean)
+                .with_default(defaults.remove_from_client)
+                .with_help("Remove failed downloads from the download client")
+                .with_env_var("RADARR_REMOVE_FAILED_DOWNLOADS"),
+        )
+        .field(
+            ConfigField::new("delete_files", "Delete Files", FieldType::Boolean)
+                .with_default(defaults.delete_files)
+                .with_help("Delete downloaded data of removed downloads")
+                .with_env_var("RADARR_DELETE_FAILED_DOWNLOAD_FILES")
+                .advanced(),
+        )
+        .field(
+            ConfigField::new("redownload", "Redownload", FieldType::Boolean)
+                .with_default(defaults.redownload)
+                .with_help("Search for another release of the movie")
+                .with_env_var("RADARR_REDOWNLOAD_FAILED"),
+        )
+    }
+}
+
+impl ConfigSchemaProvider for radarr_core::StallDetectionConfig {
+    fn config_section() -> ConfigSection {
+        let defaults = Self::default();
+        ConfigSection::new(
+            "stall_detection",
+            "Stalled Downloads",
+            "Fail downloads that stay slow, so they are blocklisted and replaced",
+        )
+        .field(
+            ConfigField::new("enabled", "Enabled", FieldType::Boolean)
+                .with_default(defaults.enabled)
+                .with_help("Replace downloads that stay slow or stalled")
+                .with_env_var("RADARR_STALL_DETECTION"),
+        )
+        .field(
+            ConfigField::new(
+                "min_speed_bytes_per_sec",
+                "Minimum Speed",
+                FieldType::Integer,
+            )
+            .with_default(defaults.min_speed_bytes_per_sec)
+            .with_help("Download speed in bytes/s below which a download is slow")
+            .with_env_var("RADARR_STALL_MIN_SPEED")
+            .with_range(Some(0.0), None),
+        )
+        .field(
+            ConfigField::new("max_eta_minutes", "Maximum ETA", FieldType::Integer)
+                .with_default(defaults.max_eta_minutes)
+                .with_help("ETA in minutes above which a download is slow; 0 ignores the ETA")
+                .with_env_var("RADARR_STALL_MAX_ETA_MINUTES")
+                .advanced(),
+        )
+        .field(
+            ConfigField::new("stall_minutes", "Stall Time", FieldType::Integer)
+                .with_default(defaults.stall_minutes)
+                .with_help("Minutes a download must stay slow before it is replaced")
+                .with_env_var("RADARR_STALL_MINUTES")
+                .with_range(Some(1.0), None),
+        )
+    }
+}
+
+impl ConfigSchemaProvider for radarr_core::SeedingConfig {
+    fn config_section() -> ConfigSection {
+        let defaults = Self::default();
+        ConfigSection::new(
+            "seeding",
+            "Seeding",
+            "Remove imported torrents once they met their seed goals",
+        )
+        .field(
+            ConfigField::new("enabled", "Enabled", FieldType::Boolean)
+                .with_default(defaults.enabled)
+                .with_help("Remove torrents that met their seed ratio or time")
+                .with_env_var("RADARR_SEEDING_CLEANUP"),
+        )
+        .field(
+            ConfigField::new("seed_ratio", "Seed Ratio", FieldType::Float)
+                .with_help("Ratio to seed to, unless the indexer sets seed_ratio")
+                .with_env_var("RADARR_SEED_RATIO")
+                .with_range(Some(0.0), None),
+        )
+        .field(
+            ConfigField::new("seed_time_minutes", "Seed Time", FieldType::Integer)
+                .with_help("Minutes to seed for, unless the indexer sets seed_time_minutes")
+                .with_env_var("RADARR_SEED_TIME_MINUTES")
+                .with_range(Some(0.0), None),
+        )
+        .field(
+            ConfigField::new(
+                "delete_hardlinked_data",
+                "Delete Hardlinked Data",
+                FieldType::Boolean,
+            )
+            .with_default(defaults.delete_hardlinked_data)
+            .with_help("Delete the download with the torrent when the library has a hardlink of it")
+            .with_env_var("RADARR_SEEDING_DELETE_HARDLINKED"),
+        )
+        .field(
+            ConfigField::new(
+                "check_interval_minutes",
+                "Check Interval",
+                FieldType::Integer,
+            )
+            .with_default(defaults.check_interval_minutes)
+            .with_help("Minutes between seed goal checks")
+            .with_env_var("RADARR_SEEDING_INTERVAL_MINUTES")
+            .with_range(Some(1.0), None)
+            .advanced(),
+        )
+    }
+}
+
+impl ConfigSchemaProvider for radarr_decision::LanguageProfile {
+    fn config_section() -> ConfigSection {
+        let defaults = Self::default();
+        ConfigSection::new(
+            "languages",
+            "Languages",
+            "Languages releases are grabbed in; releases in others are rejected",
+        )
+        .field(
+            ConfigField::new("preferred", "Preferred Languages", FieldType::String)
+                .with_default(language_names(&defaults.preferred))
+                .with_help("Comma-separated languages, best first; earlier ones score higher")
+                .with_env_var("RADARR_PREFERRED_LANGUAGES"),
+        )
+        .field(
+            ConfigField::new("allowed", "Allowed Languages", FieldType::String)
+                .with_default(language_names(&defaults.allowed))
+                .with_help(
+                    "Comma-separated languages accepted without a bonus; with no preferred \
+                     or allowed languages every release is accepted",
+                )
+                .with_env_var("RADARR_ALLOWED_LANGUAGES"),
+        )
+    }
+}
+
+/// Languages as the comma-separated names the env vars take
+fn language_names(languages: &[radarr_core::Language]) -> String {
+    languages
+        .iter()
+        .map(|language| language.name().to_lowercase())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+impl ConfigSchemaProvider for radarr_core::ConfigDriftConfig {
+    fn config_section() -> ConfigSection {
+        let defaults = Self::default();
+        ConfigSection::new(
+            "desired_state",
+            "Desired State",
+            "Compare profiles, indexers, import lists and naming with a declarative spec",
+        )
+        .field(
+            ConfigField::new("spec_path", "Spec File", FieldType::String)
+                .with_help("Path of desired-state.yml; drift detection is off when empty")
+                .with_env_var("RADARR_DESIRED_STATE_PATH"),
+        )
+        .field(
+            ConfigField::new("auto_apply", "Auto Apply", FieldType::Boolean)
+                .with_default(defaults.auto_apply)
+                .with_help("Change the live configuration to match the spec")
+                .with_env_var("RADARR_DESIRED_STATE_AUTO_APPLY"),
+        )
+        .field(
+            ConfigField::new(
+                "check_interval_minutes",
+                "Check Interval",
+                FieldType::Integer,
+            )
+            .with_default(defaults.check_interval_minutes)
+            .with_help("Minutes between drift checks")
+            .with_env_var("RADARR_DESIRED_STATE_INTERVAL_MINUTES")
+            .with_range(Some(1.0), None)
+            .advanced(),
+        )
+    }
+}
+
+impl ConfigSchemaProvider for radarr_infrastructure::CacheConfig {
+    fn config_section() -> ConfigSection {
+        let defaults = Self::default();
+        ConfigSection::new("cache", "Cache", "Caching of TMDB and streaming lookups")
+            .field(
+                ConfigField::new("backend", "Backend", FieldType::Select)
+                    .with_default(defaults.backend.as_str())
+                    .with_help(
+                        "Keep cached lookups in memory, or in memory and a shared Redis server",
+                    )
+                    .with_env_var("RADARR_CACHE_BACKEND")
+                    .with_options(["memory", "redis"]),
+            )
+            .field(
+                ConfigField::new("redis_url", "Redis URL", FieldType::Url)
+                    .with_default(defaults.redis_url)
+                    .with_help("Redis server used by the redis backend")
+                    .with_env_var("REDIS_URL"),
+            )
+            .field(
+                ConfigField::new("key_prefix", "Key Prefix", FieldType::String)
+                    .with_default(defaults.key_prefix)
+                    .with_help("Prefix of every Redis key, so instances can share a server")
+                    .with_env_var("RADARR_CACHE_KEY_PREFIX")
+                    .advanced(),
+            )
+            .field(
+                ConfigField::new("max_entries", "Memory Entries", FieldType::Integer)
+                    .with_default(defaults.max_entries)
+                    .with_help("Entries kept in memory before the soonest to expire are evicted")
+                    .with_env_var("RADARR_CACHE_MAX_ENTRIES")
+                    .with_range(Some(1.0), None)
+                    .advanced(),
+            )
+            .field(
+                ConfigField::new("tmdb_ttl_seconds", "TMDB TTL", FieldType::Integer)
+                    .with_default(defaults.tmdb_ttl_seconds)
+                    .with_help("Seconds TMDB lookups are cached")
+                    .with_env_var("RADARR_CACHE_TMDB_TTL_SECONDS")
+                    .with_range(Some(0.0), None)
+                    .advanced(),
+            )
+            .field(
+                ConfigField::new("streaming_ttl_seconds", "Streaming TTL", FieldType::Integer)
+                    .with_default(defaults.streaming_ttl_seconds)
+                    .with_help("Seconds streaming lookups are kept in front of the database")
+                    .with_env_var("RADARR_CACHE_STREAMING_TTL_SECONDS")
+                    .with_range(Some(0.0), None)
+                    .advanced(),
+            )
+    }
+}
+
+impl ConfigSchemaProvider for radarr_infrastructure::MediaCoverConfig {
+    fn config_section() -> ConfigSection {
+        let defaults = Self::default();
+        ConfigSection::new("media_covers", "Media Covers", "Cached posters and fanart").field(
+            ConfigField::new("directory", "Directory", FieldType::String)
+                .with_default(defaults.directory)
+                .with_help("Directory posters and fanart downloaded from TMDB are kept in")
+                .with_env_var("RADARR_MEDIA_COVER_DIR")
+                .advanced(),
+        )
+    }
+}
+
+impl ConfigSchemaProvider for radarr_infrastructure::BackupConfig {
+    fn config_section() -> ConfigSection {
+        let defaults = Self::default();
+        ConfigSection::new("backups", "Backups", "Scheduled configuration backups")
+            .field(
+                ConfigField::new("directory", "Directory", FieldType::String)
+                    .with_default(defaults.directory)
+                    .with_help("Directory backup zip files are written to")
+                    .with_env_var("RADARR_BACKUP_DIR"),
+            )
+            .field(
+                ConfigField::new("interval_days", "Interval", FieldType::Integer)
+                    .with_default(defaults.interval_days)
+                    .with_help("Days between scheduled backups")
+                    .with_env_var("RADARR_BACKUP_INTERVAL_DAYS")
+                    .with_range(Some(1.0), None),
+            )
+            .field(
+                ConfigField::new("max_backups", "Retention", FieldType::Integer)
+                    .with_default(defaults.max_backups)
+                    .with_help("Scheduled backups kept; manual backups are never removed")
+                    .with_env_var("RADARR_BACKUP_RETENTION")
+                    .with_range(Some(1.0), None)
+                    .advanced(),
+            )
+    }
+}
+
+impl ConfigSchemaProvider for radarr_infrastructure::UpdateConfig {
+    fn config_section() -> ConfigSection {
+        let defaults = Self::default();
+        ConfigSection::new("updates", "Updates", "Checks for newer releases on GitHub")
+            .field(
+                ConfigField::new("enabled", "Check for Updates", FieldType::Boolean)
+                    .with_default(defaults.enabled)
+                    .with_help("Look for newer releases; when off, GitHub is never contacted")
+                    .with_env_var("RADARR_UPDATE_CHECK_ENABLED"),
+            )
+            .field(
+                ConfigField::new("repository", "Repository", FieldType::String)
+                    .with_default(defaults.repository)
+                    .with_help("GitHub repository releases are read from, as owner/name")
+                    .with_env_var("RADARR_UPDATE_REPOSITORY")
+                    .advanced(),
+            )
+            .field(
+                ConfigField::new("check_interval_hours", "Interval", FieldType::Integer)
+                    .with_default(defaults.check_interval_hours)
+                    .with_help("Hours between update checks")
+                    .with_env_var("RADARR_UPDATE_CHECK_INTERVAL_HOURS")
+                    .with_range(Some(1.0), None)
+                    .advanced(),
+            )
+    }
+}
+
+impl ConfigSchemaProvider for DownloadsCleanupConfig {
+    fn config_section() -> ConfigSection {
+        let defaults = Self::default();
+        ConfigSection::new(
+            "downloads_cleanup",
+            "Downloads Cleanup",
+            "Removal of orphaned data from the downloads folder",
+        )
+        .field(
+            ConfigField::new("enabled", "Scheduled", FieldType::Boolean)
+                .with_default(defaults.enabled)
+                .with_help("Run on a schedule; the DownloadsCleanup command works either way")
+                .with_env_var("RADARR_DOWNLOADS_CLEANUP"),
+        )
+        .field(
+            ConfigField::new("downloads_dir", "Downloads Folder", FieldType::String)
+                .with_default(defaults.downloads_dir)
+                .with_help("Folder whose top-level entries are considered for cleanup")
+                .with_env_var("RADARR_DOWNLOADS_DIR"),
+        )
+        .field(
+            ConfigField::new("grace_period_hours", "Grace Period", FieldType::Integer)
+                .with_default(defaults.grace_period_hours)
+                .with_help("Hours since an entry last changed before it counts as orphaned")
+                .with_env_var("RADARR_DOWNLOADS_CLEANUP_GRACE_HOURS")
+                .with_range(Some(0.0), None),
+        )
+        .field(
+            ConfigField::new("exclude_globs", "Excluded Names", FieldType::String)
+                .with_default(defaults.exclude_globs)
+                .with_help("Comma-separated globs of entry names that are never removed")
+                .with_env_var("RADARR_DOWNLOADS_CLEANUP_EXCLUDE")
+                .advanced(),
+        )
+        .field(
+            ConfigField::new("dry_run", "Dry Run", FieldType::Boolean)
+                .with_default(defaults.dry_run)
+                .with_help("Only list orphaned entries instead of removing them")
+                .with_env_var("RADARR_DOWNLOADS_CLEANUP_DRY_RUN"),
+        )
+        .field(
+            ConfigField::new("interval_hours", "Interval", FieldType::Integer)
+                .with_default(defaults.interval_hours)
+                .with_help("Hours between scheduled cleanups")
+                .with_env_var("RADARR_DOWNLOADS_CLEANUP_INTERVAL_HOURS")
+                .with_range(Some(1.0), None)
+                .advanced(),
+        )
+    }
+}
+
+impl ConfigSchemaProvider for LoggingConfig {
+    fn config_section() -> ConfigSection {
+        let defaults = Self::default();
+        ConfigSection::new("logging", "Logging", "Application log output")
+            .field(
+                ConfigField::new("level", "Log Level", FieldType::Select)
+                    .with_default(defaults.level)
+                    .with_help("Minimum severity of log messages")
+                    .with_env_var("RUST_LOG")
+                    .with_options(["error", "warn", "info", "debug", "trace"]),
+            )
+            .field(
+                ConfigField::new("json_format", "JSON Logs", FieldType::Boolean)
+                    .with_default(defaults.json_format)
+                    .with_help("Emit structured JSON log lines")
+                    .with_env_var("LOG_JSON_FORMAT")
+                    .advanced(),
+            )
+            .field(
+                ConfigField::new("log_file", "Log File", FieldType::String)
+                    .with_help("Optional file path to write logs to")
+                    .with_env_var("LOG_FILE")
+                    .advanced(),
+            )
+    }
+}
+
+impl ConfigSchemaProvider for RssServiceConfig {
+    fn config_section() -> ConfigSection {
+        let defaults = Self::default();
+        ConfigSection::new("rss", "RSS Sync", "Periodic RSS feed monitoring")
+            .field(
+                ConfigField::new("enabled", "Enabled", FieldType::Boolean)
+                    .with_default(defaults.enabled)
+                    .with_help("Enable periodic RSS sync"),
+            )
+            .field(
+                ConfigField::new("update_interval_minutes", "Interval", FieldType::Integer)
+                    .with_default(defaults.update_interval_minutes)
+                    .with_help("Minutes between RSS sync runs")
+                    .with_range(Some(10.0), Some(120.0)),
+            )
+            .field(
+                ConfigField::new("max_items_per_feed", "Max Items", FieldType::Integer)
+                    .with_default(defaults.max_items_per_feed)
+                    .with_help("Maximum items processed per feed")
+                    .with_range(Some(1.0), None)
+                    .advanced(),
+            )
+            .field(
+                ConfigField::new("timeout_seconds", "Timeout", FieldType::Integer)
+                    .with_default(defaults.timeout_seconds)
+                    .with_help("Feed request timeout in seconds")
+                    .with_range(Some(1.0), None)
+                    .advanced(),
+            )
+    }
+}
+
+impl AppConfig {
+    /// Build the schema describing every configurable section
+    pub fn schema() -> ConfigSchema {
+        ConfigSchema {
+            version: 1,
+            sections: vec![
+                ServerConfig::config_section(),
+                DatabaseConfig::config_section(),
+                ProwlarrConfig::config_section(),
+                QBittorrentConfig::config_section(),
+                SabnzbdConfig::config_section(),
+                DownloadClientsConfig::config_section(),
+                ImportConfig::config_section(),
+                DownloadsCleanupConfig::config_section(),
+                TmdbConfig::config_section(),
+                OmdbConfig::config_section(),
+                radarr_core::DownloadHandlingConfig::config_section(),
+                radarr_core::StallDetectionConfig::config_section(),
+                radarr_core::SeedingConfig::config_section(),
+                radarr_decision::LanguageProfile::config_section(),
+                radarr_core::ConfigDriftConfig::config_section(),
+                radarr_infrastructure::CacheConfig::config_section(),
+                radarr_infrastructure::MediaCoverConfig::config_section(),
+                radarr_infrastructure::BackupConfig::config_section(),
+                radarr_infrastructure::UpdateConfig::config_section(),
+                LoggingConfig::config_section(),
+                RssServiceConfig::config_section(),
+            ],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn section<'a>(schema: &'a ConfigSchema, name: &str) -> Option<&'a ConfigSection> {
+        schema.sections.iter().find(|s| s.name == name)
+    }
+
+    fn field<'a>(section: &'a ConfigSection, name: &str) -> Option<&'a ConfigField> {
+        section.fields.iter().find(|f| f.name == name)
+    }
+
+    #[test]
+    fn test_schema_covers_all_struct_fields() {
+        let schema = AppConfig::schema();
+        let config = serde_json::to_value(AppConfig::default()).unwrap();
+
+        for (section_name, section_value) in config.as_object().unwrap() {
+            let section = section(&schema, section_name)
+                .unwrap_or_else(|| panic!("missing schema section {}", section_name));
+            for field_name in section_value.as_object().unwrap().keys() {
+                assert!(
+                    field(section, field_name).is_some(),
+                    "missing schema field {}.{}",
+                    section_name,
+                    field_name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_schema_defaults_and_secrets() {
+        let schema = AppConfig::schema();
+        let server = section(&schema, "server").unwrap();
+
+        let port = field(server, "port").unwrap();
+        assert_eq!(port.default, Value::from(7878));
+        assert_eq!(port.constraints.max, Some(65535.0));
+
+        // Secrets never expose their default values
+        let api_key = field(server, "api_key").unwrap();
+        assert_eq!(api_key.field_type, FieldType::Password);
+        assert_eq!(api_key.default, Value::Null);
+
+        let json = serde_json::to_value(&schema).unwrap();
+        assert_eq!(json["sections"][0]["fields"][1]["type"], "integer");
+    }
+}
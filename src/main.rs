@@ -17,15 +17,36 @@ use axum::{
 };
 use include_dir::{include_dir, Dir};
 use radarr_api::{
-    create_simple_api_router, init_telemetry, middleware::require_api_key, shutdown_telemetry,
-    MetricsCollector, SimpleApiState, TelemetryConfig,
+    create_simple_api_router, init_telemetry,
+    middleware::{rate_limit, require_api_key},
+    shutdown_telemetry, MetricsCollector, SimpleApiState, TelemetryConfig,
+};
+use radarr_core::{
+    jobs::{
+        CutoffUnmetSearchConfig, CutoffUnmetSearchJob, MissingMovieSearchConfig,
+        MissingMovieSearchJob, RefreshMovieJob,
+    },
+    media_root,
+    repositories::ConfigRepository,
+    CommandName, ConfigSection, DownloadClientManager, ManagedDownloadClient, RadarrError,
+    ReleaseProtocol, Result, ShutdownOutcome,
 };
-use radarr_core::{RadarrError, Result};
 use radarr_downloaders::QBittorrentClient;
-use radarr_import::ImportPipeline;
+use radarr_import::{
+    DownloadsCleanupService, Extractor, ForensicBundleStore, ImportPipeline, LibraryImportService,
+    MoveMovieJob,
+};
 use radarr_indexers::{IndexerClient, ProwlarrClient};
-use radarr_infrastructure::{create_pool, DatabaseConfig};
+use radarr_infrastructure::{
+    create_lazy_pool, create_pool, create_pool_with_retry, event_bridge::EventBridgeConfig,
+    migrations, CacheManager, DatabaseConfig, DatabaseRetryPolicy, MediaCoverService,
+    MigrationMode, MigrationStatus, OmdbClient, PoolMonitor, PostgresConfigRepository,
+    PostgresMovieFileRepository, PostgresQualityProfileRepository, PostgresRootFolderRepository,
+    QBittorrentDownloadClient, ReadPool, SabnzbdDownloadClient,
+};
+use serde::de::DeserializeOwned;
 use serde_json::{json, Value};
+use std::future::IntoFuture;
 use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::Duration;
@@ -37,12 +58,21 @@ use tracing::{debug, info, instrument, warn};
 mod api;
 mod config;
 mod services;
+mod sse;
+mod startup;
 mod websocket;
 
 use config::retry_config;
 use config::AppConfig;
 use services::RssServiceConfig;
-use services::{AppServices, ServiceBuilder as AppServiceBuilder};
+use services::{
+    AppServices, ApplicationUpdateCheckCommand, BackupCommand, CheckFreeSpaceCommand,
+    CutoffUnmetSearchCommand, DownloadedMoviesScanCommand, DownloadsCleanupCommand,
+    LibraryImportCommand, MissingMoviesSearchCommand, MoveMovieCommand, MoviesSearchCommand,
+    ReconcileConfigCommand, RefreshMovieCommand, RssSyncCommand, SeedingCleanupCommand,
+    ServiceBuilder as AppServiceBuilder, TmdbMovieLookup,
+};
+use startup::{StartupPhase, StartupState, StartupTracker};
 
 // Embed the web UI assets at compile time
 static WEB_ASSETS: Dir = include_dir!("web/dist");
@@ -130,6 +160,7 @@ pub struct AppState {
     pub config: AppConfig,
     pub progress_tracker: Arc<radarr_core::progress::ProgressTracker>,
     pub event_bus: Arc<radarr_core::events::EventBus>,
+    pub metrics: Arc<MetricsCollector>,
 }
 
 #[tokio::main]
@@ -138,36 +169,33 @@ async fn main() -> Result<()> {
     init_logging().await?;
 
     info!("🚀 Starting Radarr MVP Application");
+    let startup_tracker = Arc::new(StartupTracker::new());
 
     // Load configuration
-    let config = load_config().await?;
+    let mut config = startup_tracker
+        .run(StartupPhase::Configuration, load_config())
+        .await?;
+    if let Some(mode) = migration_mode_flag(std::env::args())? {
+        config.database.migration_mode = mode;
+    }
     info!("✅ Configuration loaded successfully");
 
-    // Run database migrations
-    run_migrations(&config).await?;
-    info!("✅ Database migrations completed");
-
-    // Initialize all services
-    let services = initialize_services(&config).await?;
-    info!("✅ All services initialized successfully");
-
-    // Create progress tracker and event bus
-    let progress_tracker = Arc::new(radarr_core::progress::ProgressTracker::new());
-    let event_bus = Arc::new(radarr_core::events::EventBus::new());
+    // A dry run only reports what an upgrade would change
+    if config.database.migration_mode == MigrationMode::DryRun {
+        let status = migration_status(&config).await?;
+        println!("{}", status.plan());
+        return Ok(());
+    }
 
-    // Create application state
-    let app_state = AppState {
-        services,
-        config: config.clone(),
-        progress_tracker,
-        event_bus,
-    };
+    let localizer = radarr_core::i18n::init_localizer(&config.server.locale);
+    info!("Using locale {}", localizer.default_locale());
 
-    // Build HTTP server
-    let app = build_router(app_state);
-    info!("✅ HTTP router configured");
+    // Chaos mode must be on before any outbound client makes a call
+    if let Some(chaos) = radarr_core::chaos::ChaosConfig::from_env()? {
+        enable_chaos_mode(chaos);
+    }
 
-    // Start server
+    // Listen right away so /health and /ready answer while starting up
     let addr = SocketAddr::from(([0, 0, 0, 0], config.server.port));
     info!("🌐 Starting HTTP server on {}", addr);
 
@@ -183,32 +211,113 @@ async fn main() -> Result<()> {
     let _tcp_keepalive = Some(Duration::from_secs(60));
 
     // Create server with proper configuration
+    let startup_state = StartupState::new(startup_tracker.clone());
+    let app = startup::startup_router(startup_state.clone());
     let server = axum::serve(
         listener,
         app.into_make_service_with_connect_info::<SocketAddr>(),
     )
     .tcp_nodelay(tcp_nodelay)
-    .with_graceful_shutdown(shutdown_signal());
+    .with_graceful_shutdown(shutdown_signal())
+    .into_future();
+    let server = tokio::spawn(server);
 
-    // Run server with timeout protection
-    tokio::select! {
-        result = server => {
+    // Run database migrations
+    startup_tracker
+        .run(StartupPhase::Migrations, run_migrations(&config))
+        .await?;
+    info!("✅ Database migrations completed");
+
+    // Create progress tracker and event bus
+    let progress_tracker = Arc::new(radarr_core::progress::ProgressTracker::new());
+    let event_bus = Arc::new(radarr_core::events::EventBus::new());
+
+    // Metrics are registered once, before services start recording them
+    let metrics = Arc::new(MetricsCollector::new().expect("Failed to create metrics collector"));
+
+    // Initialize all services
+    let services = startup_tracker
+        .run(
+            StartupPhase::Services,
+            initialize_services(&config, &progress_tracker, &event_bus, &metrics),
+        )
+        .await?;
+    info!("✅ All services initialized successfully");
+
+    // Create application state
+    let app_state = AppState {
+        services: services.clone(),
+        config: config.clone(),
+        progress_tracker,
+        event_bus,
+        metrics,
+    };
+
+    // Build HTTP router and start routing requests to it
+    let router = startup_tracker
+        .run(StartupPhase::Router, async {
+            Ok::<_, RadarrError>(build_router(app_state))
+        })
+        .await?;
+
+    // Forget authentication failures once they no longer count towards a lockout
+    if let Some(verifier) = radarr_api::auth::global_verifier() {
+        verifier.spawn_pruning(Duration::from_secs(60));
+    }
+
+    // Pick up commands stored before a restart, now that their executors are registered
+    match services.task_scheduler.recover().await {
+        Ok(recovery) => info!(
+            "✅ Command queue recovered: {} resumed, {} interrupted",
+            recovery.resumed, recovery.interrupted
+        ),
+        Err(e) => warn!("Failed to recover stored commands: {}", e),
+    }
+
+    startup_state.install(router, services.clone());
+    info!("✅ HTTP router configured, instance ready");
+
+    // Run scheduled tasks once everything they queue is registered
+    let scheduler_task = services.task_scheduler.start();
+    info!("✅ Task scheduler started");
+
+    // The server returns once a shutdown signal arrived and open requests finished
+    let served = server
+        .await
+        .map_err(|e| RadarrError::ExternalServiceError {
+            service: "http_server".to_string(),
+            error: format!("Server task failed: {}", e),
+        })
+        .and_then(|result| {
             result.map_err(|e| RadarrError::ExternalServiceError {
                 service: "http_server".to_string(),
                 error: format!("Server error: {}", e),
-            })?
-        }
-        _ = tokio::time::sleep(Duration::from_secs(3600)) => {
-            warn!("Server timeout after 1 hour - forcing restart");
-        }
-    }
+            })
+        });
 
     info!("👋 Radarr MVP application shutting down");
 
+    // Queue no new work, then let background services finish what they started
+    scheduler_task.abort();
+    let grace = Duration::from_secs(config.server.shutdown_grace_period);
+    info!(
+        "Waiting up to {}s for {} background services to stop",
+        grace.as_secs(),
+        services.shutdown.running_tasks()
+    );
+    match services.shutdown.shutdown(grace).await {
+        ShutdownOutcome::Drained => info!("✅ Background services stopped"),
+        ShutdownOutcome::TimedOut { remaining } => warn!(
+            "{} background services still running after {}s, exiting anyway",
+            remaining,
+            grace.as_secs()
+        ),
+    }
+
     // Shutdown telemetry gracefully
     shutdown_telemetry();
 
-    Ok(())
+    served
 }
 
 /// Initialize telemetry (tracing, metrics, and logging) using OpenTelemetry
@@ -226,11 +335,15 @@ async fn init_logging() -> Result<()> {
     Ok(())
 }
 
-/// Load configuration from environment and validate
+/// Load configuration from environment, take over settings saved through
+/// the API and validate
 async fn load_config() -> Result<AppConfig> {
     debug!("Loading configuration from environment");
 
-    let config = AppConfig::from_env()?;
+    let mut config = AppConfig::from_env()?;
+    if let Err(e) = apply_stored_config(&mut config).await {
+        warn!("Using environment settings, stored ones unavailable: {}", e);
+    }
     config.validate()?;
 
     debug!(
@@ -241,32 +354,131 @@ async fn load_config() -> Result<AppConfig> {
     Ok(config)
 }
 
-/// Run database migrations
-async fn run_migrations(config: &AppConfig) -> Result<()> {
-    debug!("Running database migrations");
+/// Take over settings saved through /api/v3/config, which win over the
+/// environment's
+async fn apply_stored_config(config: &mut AppConfig) -> Result<()> {
+    let db_config = DatabaseConfig {
+        database_url: config.database.url.clone(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let repo = PostgresConfigRepository::new(create_pool(db_config).await?);
+
+    if let Some(host) = load_stored_section(&repo, ConfigSection::Host).await? {
+        config.apply_host_settings(host);
+    }
+    if let Some(naming) = load_stored_section(&repo, ConfigSection::Naming).await? {
+        config.apply_naming_settings(naming);
+    }
+    if let Some(settings) = load_stored_section(&repo, ConfigSection::MediaManagement).await? {
+        config.apply_media_management_settings(settings);
+    }
+    Ok(())
+}
 
+/// Settings saved for a section; ones that no longer parse are ignored
+async fn load_stored_section<T: DeserializeOwned>(
+    repo: &dyn ConfigRepository,
+    section: ConfigSection,
+) -> Result<Option<T>> {
+    let Some(value) = repo.get(section).await? else {
+        return Ok(None);
+    };
+    match serde_json::from_value(value) {
+        Ok(settings) => {
+            debug!("Using stored {} settings", section);
+            Ok(Some(settings))
+        }
+        Err(e) => {
+            warn!("Ignoring stored {} settings: {}", section, e);
+            Ok(None)
+        }
+    }
+}
+
+/// Database pool for the migration status and migrations
+async fn migration_pool(config: &AppConfig) -> Result<radarr_infrastructure::DatabasePool> {
     let db_config = DatabaseConfig {
         database_url: config.database.url.clone(),
         max_connections: 1, // Single connection for migrations
         ..DatabaseConfig::default()
     };
-    let pool = create_pool(db_config).await?;
+    // Migrations run first, so this is where a database that is still
+    // starting up is waited for
+    create_pool_with_retry(
+        db_config,
+        &DatabaseRetryPolicy::startup(config.database.connect_retries),
+    )
+    .await
+}
 
-    sqlx::migrate!("./migrations")
-        .run(&pool)
-        .await
-        .map_err(|e| RadarrError::ExternalServiceError {
-            service: "database_migrations".to_string(),
-            error: e.to_string(),
-        })?;
+/// Applied and pending migrations of the configured database
+async fn migration_status(config: &AppConfig) -> Result<MigrationStatus> {
+    migrations::migration_status(&migration_pool(config).await?).await
+}
+
+/// Run database migrations, or only verify none are pending in check mode
+async fn run_migrations(config: &AppConfig) -> Result<()> {
+    debug!("Running database migrations");
+
+    let pool = migration_pool(config).await?;
+    let status = migrations::migration_status(&pool).await?;
+    if status.is_up_to_date() {
+        info!(
+            "Database schema is up to date ({} migrations applied)",
+            status.applied.len()
+        );
+        return Ok(());
+    }
+
+    if config.database.migration_mode == MigrationMode::Check {
+        return Err(RadarrError::ConfigurationError {
+            field: "DATABASE_MIGRATIONS".to_string(),
+            message: format!(
+                "Database schema is not up to date and migration mode is check:\n{}",
+                status.plan()
+            ),
+        });
+    }
+
+    info!("Applying database migrations:\n{}", status.plan());
+    migrations::apply_migrations(&pool).await?;
 
     info!("Database migrations completed successfully");
     Ok(())
 }
 
+/// Migration mode given as `--migrations <mode>` or `--migrations=<mode>`,
+/// which wins over `DATABASE_MIGRATIONS`
+fn migration_mode_flag(args: impl IntoIterator<Item = String>) -> Result<Option<MigrationMode>> {
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let value = if arg == "--migrations" {
+            args.next().unwrap_or_default()
+        } else if let Some(value) = arg.strip_prefix("--migrations=") {
+            value.to_string()
+        } else {
+            continue;
+        };
+        let mode = value
+            .parse()
+            .map_err(|message| RadarrError::ValidationError {
+                field: "--migrations".to_string(),
+                message,
+            })?;
+        return Ok(Some(mode));
+    }
+    Ok(None)
+}
+
 /// Initialize all application services
-#[instrument(skip(config))]
-async fn initialize_services(config: &AppConfig) -> Result<AppServices> {
+#[instrument(skip(config, progress_tracker, event_bus, metrics))]
+async fn initialize_services(
+    config: &AppConfig,
+    progress_tracker: &Arc<radarr_core::progress::ProgressTracker>,
+    event_bus: &Arc<radarr_core::events::EventBus>,
+    metrics: &Arc<MetricsCollector>,
+) -> Result<AppServices> {
     debug!("Initializing all application services");
 
     // Create database pool
@@ -275,18 +487,61 @@ async fn initialize_services(config: &AppConfig) -> Result<AppServices> {
         max_connections: config.database.max_connections,
         ..DatabaseConfig::default()
     };
-    let database_pool = create_pool(db_config).await?;
+    let database_pool = create_pool_with_retry(
+        db_config,
+        &DatabaseRetryPolicy::startup(config.database.connect_retries),
+    )
+    .await?;
     info!(
         "✅ Database pool created with {} max connections",
         config.database.max_connections
     );
 
+    // Pool usage for /metrics and the database health check
+    let pool_monitor =
+        PoolMonitor::new("primary", database_pool.clone()).with_metrics(metrics.clone());
+    pool_monitor.spawn_monitor(Duration::from_secs(
+        config.database.pool_check_interval_secs,
+    ));
+
+    // Library listings and history may be read from a replica
+    let read_pool = match &config.database.read_replica_url {
+        Some(replica_url) => {
+            let replica_config = DatabaseConfig {
+                database_url: replica_url.clone(),
+                max_connections: config.database.max_connections,
+                ..DatabaseConfig::default()
+            };
+            match create_lazy_pool(replica_config) {
+                Ok(replica_pool) => {
+                    let reads = ReadPool::with_replica(
+                        database_pool.clone(),
+                        replica_pool,
+                        Duration::from_secs(config.database.replica_max_lag_secs),
+                    );
+                    reads.spawn_monitor(Duration::from_secs(15));
+                    info!("✅ Read replica configured");
+                    reads
+                }
+                Err(e) => {
+                    warn!("Invalid read replica URL, reading from the primary: {}", e);
+                    ReadPool::primary_only(database_pool.clone())
+                }
+            }
+        }
+        None => ReadPool::primary_only(database_pool.clone()),
+    };
+
     // Initialize Prowlarr client (convert config)
     let prowlarr_config = radarr_indexers::ProwlarrConfig {
         base_url: config.prowlarr.base_url.clone(),
         api_key: config.prowlarr.api_key.clone(),
         timeout: config.prowlarr.timeout,
         max_requests_per_minute: config.prowlarr.max_requests_per_minute,
+        default_indexer_rate_limit: config.prowlarr.default_indexer_rate_limit,
+        indexer_rate_limits: config.prowlarr.indexer_rate_limits.clone(),
+        backoff_initial_seconds: config.prowlarr.backoff_initial_seconds,
+        backoff_max_seconds: config.prowlarr.backoff_max_seconds,
         user_agent: config.prowlarr.user_agent.clone(),
         verify_ssl: config.prowlarr.verify_ssl,
     };
@@ -319,6 +574,8 @@ async fn initialize_services(config: &AppConfig) -> Result<AppServices> {
         config.qbittorrent.base_url
     );
 
+    let download_clients = build_download_clients(config, qbittorrent_config).await?;
+
     // Initialize import pipeline (convert config)
     let import_config = radarr_import::ImportConfig {
         dry_run: config.import.dry_run,
@@ -326,25 +583,126 @@ async fn initialize_services(config: &AppConfig) -> Result<AppServices> {
         skip_samples: config.import.skip_samples,
         continue_on_error: config.import.continue_on_error,
         max_parallel: config.import.max_parallel,
-        ..radarr_import::ImportConfig::default()
+        scan_config: radarr_import::ScanConfig {
+            max_concurrency: config.import.scan_concurrency,
+            ..radarr_import::ScanConfig::default()
+        },
+        transcode_config: config.import.transcode_config(),
+        extract_config: config.import.extract_config(),
+        extras_config: config.import.extras_config(),
+        rename_config: config.import.rename_config(),
+        hardlink_config: radarr_import::HardlinkConfig {
+            file_date: config.import.file_date,
+            ..radarr_import::HardlinkConfig::default()
+        },
     };
-    let import_pipeline = Arc::new(ImportPipeline::new(import_config));
+    // RAR extraction shells out to unrar; fail now rather than on the first RAR
+    Extractor::new(config.import.extract_config())
+        .check_unrar()
+        .await?;
+
+    // Scan and transcode progress goes to the bus the WebSocket clients watch,
+    // import measurements to /metrics
+    let import_pipeline = Arc::new(
+        ImportPipeline::new(import_config)
+            .with_progress_tracker(progress_tracker.clone())
+            .with_event_bus(event_bus.clone())
+            .with_root_folders(Arc::new(PostgresRootFolderRepository::new(
+                database_pool.clone(),
+            )))
+            .with_forensics(Arc::new(ForensicBundleStore::new(
+                &config.import.diagnostics_dir,
+            )))
+            .with_metrics(metrics.clone()),
+    );
     info!("✅ Import pipeline initialized");
 
+    // Copies cut off by a crash would otherwise be found by later scans. This
+    // finishes before recovered import commands run, so a resumed copy's
+    // partial file is never mistaken for a leftover.
+    match import_pipeline.remove_partial_copies().await {
+        Ok(0) => {}
+        Ok(removed) => info!(
+            "Removed {} partial copies from interrupted imports",
+            removed
+        ),
+        Err(e) => warn!("Failed to remove partial copies: {}", e),
+    }
+
+    // Shared by TMDB lookups and the streaming aggregator
+    let cache = match CacheManager::from_config(&config.cache).await {
+        Ok(cache) => cache,
+        Err(e) => {
+            warn!(
+                "{} cache unavailable, caching in memory only: {}",
+                config.cache.backend.as_str(),
+                e
+            );
+            CacheManager::new()
+        }
+    };
+    let cache = Arc::new(cache.with_metrics(metrics.clone()));
+    info!("✅ Cache initialized: {}", cache.backends().join(" + "));
+
     // Build services using service builder
-    let mut services = AppServiceBuilder::new()
+    let mut service_builder = AppServiceBuilder::new()
         .with_database(database_pool)
+        .with_read_pool(read_pool)
+        .with_pool_monitor(pool_monitor)
         .with_prowlarr(prowlarr_client)
         .with_qbittorrent(qbittorrent_client)
-        .with_qbittorrent_config(qbittorrent_config)
-        .with_import_pipeline(import_pipeline)
-        .build()
-        .await?;
+        .with_download_clients(download_clients)
+        .with_queue_config(radarr_core::QueueProcessorConfig {
+            free_space_headroom_bytes: config.qbittorrent.free_space_headroom_mb * 1024 * 1024,
+            skip_unwanted_files: config.qbittorrent.skip_unwanted_files,
+            stall_detection: config.stall_detection.clone(),
+            ..radarr_core::QueueProcessorConfig::default()
+        })
+        .with_download_handling(config.download_handling.clone())
+        .with_seeding(config.seeding.clone())
+        .with_desired_state(config.desired_state.clone())
+        .with_cache(cache)
+        .with_media_covers(Arc::new(
+            MediaCoverService::new(&config.media_covers)
+                .with_ffmpeg_path(&config.import.ffmpeg_path),
+        ))
+        .with_backups(config.backups.clone())
+        .with_updates(config.updates.clone())
+        .with_import_pipeline(import_pipeline);
+    if let Some(preview_import) = config.import.preview_config() {
+        info!(
+            "Early import previews enabled in {}",
+            preview_import.staging_dir.display()
+        );
+        service_builder = service_builder.with_preview_import(preview_import);
+    }
+    let mut services = service_builder.build().await?;
 
     // Initialize and test all services
     services.initialize().await?;
     info!("✅ All services initialized and tested");
 
+    // Connect the optional external event bridge before event processing starts
+    if let Some(bridge_config) = EventBridgeConfig::from_env()? {
+        match services.initialize_event_bridge(&bridge_config).await {
+            Ok(()) => info!(
+                "✅ Event bridge publishing to {}",
+                bridge_config.transport.name()
+            ),
+            Err(e) => warn!("Event bridge disabled: {}", e),
+        }
+    }
+
+    // Load configured notification providers before events start flowing
+    services.initialize_notifications().await?;
+    info!("✅ Notification providers loaded");
+
+    // Share tokens and API keys must be known before the API starts accepting them
+    let share_tokens = services.initialize_share_tokens().await?;
+    info!("✅ {} active share tokens loaded", share_tokens);
+    let api_keys = services.initialize_api_keys().await?;
+    info!("✅ {} active API keys loaded", api_keys);
+
     // Start event processing system
     services.start_event_processing().await?;
     info!("✅ Event processing system started");
@@ -354,18 +712,26 @@ async fn initialize_services(config: &AppConfig) -> Result<AppServices> {
     info!("✅ Queue processor started");
 
     // Initialize and start RSS service
-    services.initialize_rss_service(RssServiceConfig::default())?;
+    services.initialize_rss_service(RssServiceConfig {
+        proper_handling: config.import.proper_handling,
+        language_profile: config.languages.clone(),
+        ..RssServiceConfig::default()
+    })?;
     services.start_rss_service().await?;
     info!("✅ RSS monitoring service started");
 
     // Initialize streaming aggregator
-    services.initialize_streaming_aggregator()?;
+    services.initialize_streaming_aggregator(config.cache.streaming_ttl())?;
     info!("✅ Streaming service aggregator initialized");
 
     // Initialize list sync monitor
     services.initialize_list_sync_monitor().await?;
     info!("✅ List sync monitor initialized");
 
+    // Register health-check driven recovery actions
+    services.register_recovery_actions().await;
+    info!("✅ Automated recovery actions registered");
+
     // Start list sync monitor
     services.start_list_sync_monitor().await?;
     info!("✅ List sync monitor started");
@@ -373,10 +739,58 @@ async fn initialize_services(config: &AppConfig) -> Result<AppServices> {
     Ok(services)
 }
 
+/// qBittorrent and, when configured, SABnzbd, with the configured indexer routes
+async fn build_download_clients(
+    config: &AppConfig,
+    qbittorrent_config: radarr_downloaders::QBittorrentConfig,
+) -> Result<Arc<DownloadClientManager>> {
+    const QBITTORRENT_ID: i32 = 1;
+    const SABNZBD_ID: i32 = 2;
+
+    let manager = Arc::new(DownloadClientManager::new());
+    manager
+        .add_client(ManagedDownloadClient::new(
+            QBITTORRENT_ID,
+            "qbittorrent",
+            ReleaseProtocol::Torrent,
+            config.qbittorrent.priority,
+            Arc::new(QBittorrentDownloadClient::new(qbittorrent_config)?),
+        ))
+        .await;
+
+    if config.sabnzbd.enabled {
+        let sabnzbd_config = radarr_downloaders::SabnzbdConfig {
+            base_url: config.sabnzbd.base_url.clone(),
+            api_key: config.sabnzbd.api_key.clone(),
+            timeout: config.sabnzbd.timeout,
+        };
+        manager
+            .add_client(ManagedDownloadClient::new(
+                SABNZBD_ID,
+                "sabnzbd",
+                ReleaseProtocol::Usenet,
+                config.sabnzbd.priority,
+                Arc::new(SabnzbdDownloadClient::new(sabnzbd_config)?),
+            ))
+            .await;
+        info!("✅ SABnzbd client initialized: {}", config.sabnzbd.base_url);
+    }
+
+    // Names were checked against the enabled clients by AppConfig::validate
+    for (indexer_id, client) in &config.download_clients.indexer_routes {
+        let client_id = match client.as_str() {
+            "sabnzbd" => SABNZBD_ID,
+            _ => QBITTORRENT_ID,
+        };
+        manager.set_indexer_route(*indexer_id, client_id).await;
+    }
+
+    Ok(manager)
+}
+
 /// Build the Axum router with all routes and middleware
 fn build_router(app_state: AppState) -> Router {
-    // Initialize metrics collector
-    let metrics = Arc::new(MetricsCollector::new().expect("Failed to create metrics collector"));
+    let metrics = app_state.metrics.clone();
 
     // Create WebSocket state
     let ws_state = Arc::new(websocket::WsState {
@@ -398,7 +812,10 @@ fn build_router(app_state: AppState) -> Router {
     {
         use radarr_infrastructure::{CachedTmdbClient, TmdbClient};
         let tmdb = TmdbClient::new(app_state.config.tmdb.api_key.clone());
-        let cached_tmdb = CachedTmdbClient::new(tmdb);
+        let cached_tmdb = CachedTmdbClient::new(tmdb).with_cache(
+            app_state.services.cache.clone(),
+            app_state.config.cache.tmdb_ttl(),
+        );
         Some(Arc::new(cached_tmdb))
     } else {
         warn!("TMDB client disabled or not configured - movie lookup will not work");
@@ -407,26 +824,263 @@ fn build_router(app_state: AppState) -> Router {
 
     // Create simple API state with database pool and indexer client
     let mut simple_api_state = SimpleApiState::new(app_state.services.database_pool.clone())
+        .with_read_pool(app_state.services.read_pool.clone())
         .with_indexer_client(app_state.services.indexer_client.clone())
-        .with_metrics_collector(metrics.clone());
+        .with_metrics_collector(metrics.clone())
+        .with_event_bus(app_state.services.event_bus.clone())
+        .with_notification_service(app_state.services.notification_service.clone())
+        .with_media_covers(app_state.services.media_covers.clone());
+
+    // Manual grabs go straight to the queue processor's download client
+    if let Some(download_client) = app_state.services.download_client.clone() {
+        simple_api_state = simple_api_state.with_download_client(download_client);
+    }
 
     // Add TMDB client if available
     if let Some(tmdb) = tmdb_client {
         simple_api_state = simple_api_state.with_tmdb_client(tmdb);
     }
 
+    // Commands queued through /api/v3/command and by scheduled tasks;
+    // import list syncs have no scheduler here
+    let command_queue = app_state.services.command_queue.clone();
+    let task_scheduler = app_state.services.task_scheduler.clone();
+    if let Some(rss) = &rss_service {
+        command_queue.register(CommandName::RssSync, Arc::new(RssSyncCommand::new(rss.clone())));
+        if rss.is_enabled() {
+            task_scheduler.register(CommandName::RssSync, rss.check_interval());
+        }
+
+        // Monitored movies without files, searched with the RSS service's
+        // indexers, decision engine and queue
+        let missing_search = MissingMovieSearchJob::new(
+            app_state.services.movie_repository.clone(),
+            rss.clone(),
+            MissingMovieSearchConfig::default(),
+        )
+        .with_search_cooldown(rss.search_cooldown().clone());
+        command_queue.register(
+            CommandName::MissingMoviesSearch,
+            Arc::new(MissingMoviesSearchCommand::new(Arc::new(missing_search))),
+        );
+        task_scheduler.register(CommandName::MissingMoviesSearch, rss.missing_search_interval());
+
+        // Movies below their quality cutoff; the RSS service only grabs
+        // releases that upgrade the existing file
+        let cutoff_search = CutoffUnmetSearchJob::new(
+            app_state.services.movie_repository.clone(),
+            Arc::new(PostgresMovieFileRepository::new(
+                app_state.services.database_pool.clone(),
+            )),
+            Arc::new(PostgresQualityProfileRepository::new(
+                app_state.services.database_pool.clone(),
+            )),
+            rss.clone(),
+            CutoffUnmetSearchConfig::default(),
+        )
+        .with_search_cooldown(rss.search_cooldown().clone());
+        command_queue.register(
+            CommandName::CutoffUnmetSearch,
+            Arc::new(CutoffUnmetSearchCommand::new(Arc::new(cutoff_search))),
+        );
+        task_scheduler.register(CommandName::CutoffUnmetSearch, rss.cutoff_search_interval());
+
+        // On-demand searches for specific movies, e.g. blocklist retries
+        command_queue.register(
+            CommandName::MoviesSearch,
+            Arc::new(MoviesSearchCommand::new(
+                app_state.services.movie_repository.clone(),
+                rss.clone(),
+            )),
+        );
+    }
+    if let Some(tmdb) = &simple_api_state.tmdb_client {
+        let tmdb_lookup = Arc::new(TmdbMovieLookup::new(tmdb.clone()));
+
+        // Daily refresh of every movie's TMDB details; changes are
+        // published as MovieUpdated events
+        let mut refresh = RefreshMovieJob::new(
+            app_state.services.movie_repository.clone(),
+            tmdb_lookup.clone(),
+        )
+        .with_event_bus(app_state.services.event_bus.clone());
+        // IMDb, Rotten Tomatoes and Metacritic ratings come from OMDb
+        if !app_state.config.omdb.api_key.is_empty() {
+            refresh = refresh.with_ratings_source(Arc::new(OmdbClient::new(
+                app_state.config.omdb.api_key.clone(),
+            )));
+        }
+        command_queue.register(
+            CommandName::RefreshMovie,
+            Arc::new(RefreshMovieCommand::new(Arc::new(refresh))),
+        );
+        task_scheduler.register(CommandName::RefreshMovie, Duration::from_secs(24 * 60 * 60));
+
+        // Existing libraries are added in place on first setup
+        let library_import = LibraryImportService::new(
+            app_state.services.movie_repository.clone(),
+            Arc::new(PostgresMovieFileRepository::new(
+                app_state.services.database_pool.clone(),
+            )),
+            tmdb_lookup,
+            media_root(),
+        )
+        .with_event_bus(app_state.services.event_bus.clone());
+        command_queue.register(
+            CommandName::LibraryImport,
+            Arc::new(LibraryImportCommand::new(
+                Arc::new(library_import),
+                Arc::new(PostgresRootFolderRepository::new(
+                    app_state.services.database_pool.clone(),
+                )),
+            )),
+        );
+    }
+    command_queue.register(
+        CommandName::DownloadedMoviesScan,
+        Arc::new(DownloadedMoviesScanCommand::new(
+            app_state.services.import_pipeline.clone(),
+            Arc::new(PostgresRootFolderRepository::new(
+                app_state.services.database_pool.clone(),
+            )),
+        )),
+    );
+    // Movie folders follow root folder changes from the movie editor and
+    // title changes from refreshes, named with the settings API's templates
+    let move_movie = MoveMovieJob::new(
+        app_state.services.movie_repository.clone(),
+        Arc::new(PostgresMovieFileRepository::new(
+            app_state.services.database_pool.clone(),
+        )),
+        media_root(),
+    )
+    .with_rename_config(simple_api_state.rename_config.clone())
+    .with_event_bus(app_state.services.event_bus.clone());
+    command_queue.register(
+        CommandName::MoveMovie,
+        Arc::new(MoveMovieCommand::new(Arc::new(move_movie))),
+    );
+    // Grabs for nearly full root folders wait as pending until this frees them
+    command_queue.register(
+        CommandName::CheckFreeSpace,
+        Arc::new(CheckFreeSpaceCommand::new(
+            app_state.services.storage_throttle.clone(),
+            Arc::new(PostgresRootFolderRepository::new(
+                app_state.services.database_pool.clone(),
+            )),
+        )),
+    );
+    task_scheduler.register(CommandName::CheckFreeSpace, Duration::from_secs(5 * 60));
+    // Configuration backups; /api/v3/system/backup lists and restores them
+    command_queue.register(
+        CommandName::Backup,
+        Arc::new(BackupCommand::new(app_state.services.backups.clone())),
+    );
+    task_scheduler.register(
+        CommandName::Backup,
+        Duration::from_secs(app_state.config.backups.interval_days.max(1) * 24 * 60 * 60),
+    );
+    // Newer releases show up in /api/v3/update, the health checks and
+    // notifications; turned off, GitHub is never contacted
+    let updates = app_state.services.updates.clone();
+    if updates.config().enabled {
+        let interval = updates.config().check_interval_hours.max(1);
+        command_queue.register(
+            CommandName::ApplicationUpdateCheck,
+            Arc::new(ApplicationUpdateCheckCommand::new(updates.clone())),
+        );
+        task_scheduler.register(
+            CommandName::ApplicationUpdateCheck,
+            Duration::from_secs(interval * 60 * 60),
+        );
+    }
+    // Drift from desired-state.yml is reported through the health checks
+    if let Some(reconciler) = &app_state.services.config_drift {
+        let interval = reconciler.config().check_interval_minutes.max(1);
+        command_queue.register(
+            CommandName::ReconcileConfig,
+            Arc::new(ReconcileConfigCommand::new(reconciler.clone())),
+        );
+        task_scheduler.register(
+            CommandName::ReconcileConfig,
+            Duration::from_secs(interval * 60),
+        );
+    }
+    // Leftovers no queue item or download client refers to are removed from
+    // the downloads folder; /api/v3/system/cleanup/downloads lists them
+    if let Some(queue_repository) = &app_state.services.queue_repository {
+        match DownloadsCleanupService::new(
+            app_state.config.downloads_cleanup.cleanup_config(),
+            queue_repository.clone(),
+        ) {
+            Ok(mut cleanup) => {
+                if let Some(download_client) = &app_state.services.download_client {
+                    cleanup = cleanup.with_download_client(download_client.clone());
+                }
+                let cleanup = Arc::new(cleanup);
+                command_queue.register(
+                    CommandName::DownloadsCleanup,
+                    Arc::new(DownloadsCleanupCommand::new(cleanup.clone())),
+                );
+                if app_state.config.downloads_cleanup.enabled {
+                    let interval = app_state.config.downloads_cleanup.interval_hours.max(1);
+                    task_scheduler.register(
+                        CommandName::DownloadsCleanup,
+                        Duration::from_secs(interval * 60 * 60),
+                    );
+                }
+                simple_api_state = simple_api_state.with_downloads_cleanup(cleanup);
+            }
+            Err(e) => warn!("Downloads cleanup unavailable: {}", e),
+        }
+    }
+    // Imported torrents seed until their indexer's ratio or time goal is met
+    if let Some(seeding) = &app_state.services.seeding {
+        let interval = seeding.config().check_interval_minutes.max(1);
+        command_queue.register(
+            CommandName::SeedingCleanup,
+            Arc::new(SeedingCleanupCommand::new(seeding.clone())),
+        );
+        task_scheduler.register(
+            CommandName::SeedingCleanup,
+            Duration::from_secs(interval * 60),
+        );
+    }
+    simple_api_state = simple_api_state
+        .with_storage_throttle(app_state.services.storage_throttle.clone())
+        .with_rename_config(app_state.config.import.rename_config())
+        .with_import_pipeline(app_state.services.import_pipeline.clone())
+        .with_backups(app_state.services.backups.clone())
+        .with_update_checker(updates)
+        .with_health_checks(app_state.services.health_checks.clone())
+        .with_library_statistics(app_state.services.library_statistics.clone())
+        .with_host_config(app_state.config.host_settings())
+        .with_media_management_config(app_state.config.media_management_settings())
+        .with_proper_handling(app_state.config.import.proper_handling)
+        .with_language_profile(app_state.config.languages.clone())
+        .with_command_queue(command_queue)
+        .with_task_scheduler(task_scheduler);
+    if let Some(forensics) = app_state.services.import_pipeline.forensics() {
+        simple_api_state = simple_api_state.with_forensic_bundles(forensics.clone());
+    }
+
     // Build the base router with all endpoints
     let mut router = create_simple_api_router(simple_api_state)
         // Add legacy health check endpoints
         .route("/health/detailed", get(detailed_health_check_simple))
         .route("/api/v1/system/status", get(system_status_simple))
+        .route("/api/v3/system/status", get(system_status_simple))
         .route("/api/v1/test/connectivity", post(test_connectivity_simple))
         // Add queue status endpoint
         .route("/api/queue/status", get(queue_status))
         // Add WebSocket endpoint for progress tracking
         .route("/ws", get(websocket::websocket_handler))
+        // Server-sent events fallback for proxies without WebSocket support
+        .route("/api/v3/events", get(sse::events_handler))
         // Add retry status endpoint
         .route("/api/retry/status", get(api::get_retry_status))
+        // Add configuration schema endpoint for settings forms
+        .route("/api/v3/config/schema", get(api::get_config_schema))
         // Add RSS endpoints
         .route("/api/rss/feeds", get(api::get_feeds).post(api::add_feed))
         .route("/api/rss/feeds/:id", delete(api::remove_feed))
@@ -537,11 +1191,32 @@ fn build_router(app_state: AppState) -> Router {
             ServiceBuilder::new()
                 .layer(TimeoutLayer::new(Duration::from_secs(30)))
                 .layer(middleware::from_fn(require_api_key))
+                // Inside auth, so clients are limited per credential where known;
+                // auth charges wrong keys to the source IP itself
+                .layer(middleware::from_fn(rate_limit))
                 .layer(TraceLayer::new_for_http())
                 .into_inner(),
         )
 }
 
+/// Inject the configured faults into outbound calls
+#[cfg(feature = "chaos")]
+fn enable_chaos_mode(config: radarr_core::chaos::ChaosConfig) {
+    warn!(
+        services = ?config.services,
+        error_rate = config.error_rate,
+        latency_rate = config.latency_rate,
+        latency_ms = config.latency.as_millis() as u64,
+        "⚠️ Chaos mode enabled: outbound calls will be delayed and failed on purpose"
+    );
+    radarr_core::chaos::install(config);
+}
+
+#[cfg(not(feature = "chaos"))]
+fn enable_chaos_mode(_config: radarr_core::chaos::ChaosConfig) {
+    warn!("RADARR_CHAOS_ENABLED is set but this build lacks the `chaos` feature; ignoring");
+}
+
 /// Basic health check endpoint
 async fn health_check() -> Json<Value> {
     Json(json!({
@@ -755,7 +1430,17 @@ async fn detailed_health_check_simple() -> impl axum::response::IntoResponse {
 }
 
 /// Simplified system status endpoint
-async fn system_status_simple() -> Json<Value> {
+async fn system_status_simple(services: axum::extract::Extension<Arc<AppServices>>) -> Json<Value> {
+    // Dashboards read the library totals from here; the status itself is
+    // still served when they cannot be computed
+    let statistics = match services.library_statistics.statistics().await {
+        Ok(statistics) => Some(statistics),
+        Err(e) => {
+            warn!("Library statistics unavailable: {}", e);
+            None
+        }
+    };
+
     Json(json!({
         "service": "radarr-mvp",
         "version": "1.0.0",
@@ -766,7 +1451,8 @@ async fn system_status_simple() -> Json<Value> {
         "config": {
             "server_port": 7878,
             "api_version": "v3"
-        }
+        },
+        "statistics": statistics
     }))
 }
 
@@ -802,11 +1488,17 @@ async fn queue_status(
         })
     };
 
+    let write_metrics = services
+        .queue_sync_metrics
+        .as_ref()
+        .map(|metrics| metrics.snapshot());
+
     (
         StatusCode::OK,
         Json(json!({
             "timestamp": chrono::Utc::now().to_rfc3339(),
-            "queue_processor": status
+            "queue_processor": status,
+            "write_metrics": write_metrics
         })),
     )
 }
@@ -867,4 +1559,20 @@ mod tests {
         std::env::remove_var("RADARR_PORT");
         std::env::remove_var("DATABASE_URL");
     }
+
+    #[test]
+    fn test_migration_mode_flag() {
+        let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(migration_mode_flag(args(&["radarr-mvp"])).unwrap(), None);
+        assert_eq!(
+            migration_mode_flag(args(&["radarr-mvp", "--migrations", "check"])).unwrap(),
+            Some(MigrationMode::Check)
+        );
+        assert_eq!(
+            migration_mode_flag(args(&["radarr-mvp", "--migrations=dry-run"])).unwrap(),
+            Some(MigrationMode::DryRun)
+        );
+        assert!(migration_mode_flag(args(&["radarr-mvp", "--migrations"])).is_err());
+    }
 }
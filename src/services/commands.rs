@@ -0,0 +1,709 @@
+//! Executors for commands queued through `/api/v3/command`
+
+use super::RssService;
+use async_trait::async_trait;
+use radarr_core::{
+    domain::repositories::{MovieRepository, RootFolderRepository},
+    jobs::{
+        CutoffUnmetSearchJob, MissingMovieSearchJob, MovieMetadataSource, MovieSearcher,
+        RefreshMovieJob,
+    },
+    models::Movie,
+    services::{ConfigDriftReconciler, SeedingManager, StorageChange, StorageThrottle},
+    Command, CommandExecutor, CommandProgress, CommandTrigger, RadarrError, Result,
+};
+use radarr_import::{
+    refresh_storage_throttle, DownloadsCleanupService, ImportPipeline, LibraryImportOptions,
+    LibraryImportService, MoveMovieJob, MoveMovieRequest, MovieLookup,
+};
+use radarr_infrastructure::{BackupService, BackupType, CachedTmdbClient, UpdateChecker};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Movie IDs in the `movieIds` array of a command body, if there is one
+fn body_movie_ids(command: &Command) -> Result<Option<Vec<Uuid>>> {
+    let Some(ids) = command.body.get("movieIds").and_then(|v| v.as_array()) else {
+        return Ok(None);
+    };
+    ids.iter()
+        .map(|id| {
+            id.as_str()
+                .and_then(|id| Uuid::parse_str(id).ok())
+                .ok_or_else(|| RadarrError::ValidationError {
+                    field: "movieIds".to_string(),
+                    message: format!("Invalid movie ID: {}", id),
+                })
+        })
+        .collect::<Result<Vec<_>>>()
+        .map(Some)
+}
+
+/// Runs `RssSync`: the scheduled task checks the feeds that are due, a
+/// manual run checks every enabled feed
+pub struct RssSyncCommand {
+    rss_service: Arc<RssService>,
+}
+
+impl RssSyncCommand {
+    pub fn new(rss_service: Arc<RssService>) -> Self {
+        Self { rss_service }
+    }
+}
+
+#[async_trait]
+impl CommandExecutor for RssSyncCommand {
+    async fn execute(&self, command: &Command, progress: &CommandProgress) -> Result<String> {
+        progress.update(0.0, "Checking RSS feeds").await;
+        let new_items = match command.trigger {
+            CommandTrigger::Scheduled => self.rss_service.sync_due_feeds().await?,
+            CommandTrigger::Manual => self.rss_service.sync_all_feeds().await?,
+        };
+        Ok(format!("Processed {} new RSS items", new_items))
+    }
+}
+
+/// Runs `RefreshMovie`: updates metadata from TMDB for the movies in
+/// `movieIds`, or for every movie when none are given
+pub struct RefreshMovieCommand {
+    job: Arc<RefreshMovieJob>,
+}
+
+impl RefreshMovieCommand {
+    pub fn new(job: Arc<RefreshMovieJob>) -> Self {
+        Self { job }
+    }
+}
+
+#[async_trait]
+impl CommandExecutor for RefreshMovieCommand {
+    async fn execute(&self, command: &Command, progress: &CommandProgress) -> Result<String> {
+        let summary = self
+            .job
+            .run(body_movie_ids(command)?, Some(progress))
+            .await?;
+        let total = summary.refreshed + summary.failed;
+
+        if total > 0 && summary.failed == total {
+            return Err(RadarrError::ExternalServiceError {
+                service: "tmdb".to_string(),
+                error: format!("Failed to refresh all {} movies", total),
+            });
+        }
+        Ok(format!(
+            "Refreshed {} of {} movies, {} changed",
+            summary.refreshed, total, summary.updated
+        ))
+    }
+}
+
+/// Runs `DownloadedMoviesScan`: imports the completed downloads in `path`
+/// into `outputPath`, or into the first root folder
+pub struct DownloadedMoviesScanCommand {
+    import_pipeline: Arc<ImportPipeline>,
+    root_folders: Arc<dyn RootFolderRepository>,
+}
+
+impl DownloadedMoviesScanCommand {
+    pub fn new(
+        import_pipeline: Arc<ImportPipeline>,
+        root_folders: Arc<dyn RootFolderRepository>,
+    ) -> Self {
+        Self {
+            import_pipeline,
+            root_folders,
+        }
+    }
+
+    async fn output_path(&self, command: &Command) -> Result<PathBuf> {
+        if let Some(path) = command.body.get("outputPath").and_then(|v| v.as_str()) {
+            return Ok(PathBuf::from(path));
+        }
+        self.root_folders
+            .list()
+            .await?
+            .into_iter()
+            .next()
+            .map(|folder| PathBuf::from(folder.path))
+            .ok_or_else(|| RadarrError::ValidationError {
+                field: "outputPath".to_string(),
+                message: "No outputPath given and no root folder configured".to_string(),
+            })
+    }
+}
+
+#[async_trait]
+impl CommandExecutor for DownloadedMoviesScanCommand {
+    async fn execute(&self, command: &Command, progress: &CommandProgress) -> Result<String> {
+        let source = command
+            .body
+            .get("path")
+            .and_then(|v| v.as_str())
+            .map(PathBuf::from)
+            .ok_or_else(|| RadarrError::ValidationError {
+                field: "path".to_string(),
+                message: "Path of the download folder is required".to_string(),
+            })?;
+        let destination = self.output_path(command).await?;
+
+        progress
+            .update(0.0, format!("Scanning {}", source.display()))
+            .await;
+        let stats = self
+            .import_pipeline
+            .import_directory(&source, &destination)
+            .await?;
+
+        Ok(format!(
+            "Imported {} of {} files ({} failed, {} skipped)",
+            stats.successful_imports,
+            stats.files_scanned,
+            stats.failed_imports,
+            stats.skipped_files
+        ))
+    }
+}
+
+/// Runs `CheckFreeSpace`: measures every root folder so grabs for folders
+/// running out of space are held as pending, and released once space frees up
+pub struct CheckFreeSpaceCommand {
+    storage_throttle: Arc<StorageThrottle>,
+    root_folders: Arc<dyn RootFolderRepository>,
+}
+
+impl CheckFreeSpaceCommand {
+    pub fn new(
+        storage_throttle: Arc<StorageThrottle>,
+        root_folders: Arc<dyn RootFolderRepository>,
+    ) -> Self {
+        Self {
+            storage_throttle,
+            root_folders,
+        }
+    }
+}
+
+#[async_trait]
+impl CommandExecutor for CheckFreeSpaceCommand {
+    async fn execute(&self, _command: &Command, _progress: &CommandProgress) -> Result<String> {
+        let folders = self.root_folders.list().await?;
+        let changes = refresh_storage_throttle(&self.storage_throttle, &folders).await;
+        for (folder, change) in &changes {
+            match change {
+                StorageChange::Throttled => warn!(
+                    "Root folder {} is low on free space, holding new grabs",
+                    folder.path
+                ),
+                StorageChange::Resumed => info!(
+                    "Root folder {} has free space again, resuming grabs",
+                    folder.path
+                ),
+            }
+        }
+
+        let held = self
+            .storage_throttle
+            .snapshot()
+            .iter()
+            .filter(|space| space.holds_grabs())
+            .count();
+        Ok(format!(
+            "Checked {} root folders, {} holding grabs",
+            folders.len(),
+            held
+        ))
+    }
+}
+
+/// Runs `MissingMoviesSearch`: searches indexers for the monitored movies
+/// without files that are available and due for a search
+pub struct MissingMoviesSearchCommand {
+    job: Arc<MissingMovieSearchJob>,
+}
+
+impl MissingMoviesSearchCommand {
+    pub fn new(job: Arc<MissingMovieSearchJob>) -> Self {
+        Self { job }
+    }
+}
+
+#[async_trait]
+impl CommandExecutor for MissingMoviesSearchCommand {
+    async fn execute(&self, _command: &Command, progress: &CommandProgress) -> Result<String> {
+        progress.update(0.0, "Selecting missing movies").await;
+        let summary = self.job.run(Some(progress)).await?;
+
+        if summary.searched > 0 && summary.failed == summary.searched {
+            return Err(RadarrError::ExternalServiceError {
+                service: "indexers".to_string(),
+                error: format!("All {} searches failed", summary.searched),
+            });
+        }
+        Ok(format!(
+            "Searched {} of {} missing movies, grabbed {} ({} failed)",
+            summary.searched, summary.missing, summary.grabbed, summary.failed
+        ))
+    }
+}
+
+/// Runs `CutoffUnmetSearch`: searches indexers for upgrades of monitored
+/// movies whose file is below their quality profile's cutoff
+pub struct CutoffUnmetSearchCommand {
+    job: Arc<CutoffUnmetSearchJob>,
+}
+
+impl CutoffUnmetSearchCommand {
+    pub fn new(job: Arc<CutoffUnmetSearchJob>) -> Self {
+        Self { job }
+    }
+}
+
+#[async_trait]
+impl CommandExecutor for CutoffUnmetSearchCommand {
+    async fn execute(&self, _command: &Command, progress: &CommandProgress) -> Result<String> {
+        progress.update(0.0, "Selecting cutoff unmet movies").await;
+        let summary = self.job.run(Some(progress)).await?;
+
+        if summary.searched > 0 && summary.failed == summary.searched {
+            return Err(RadarrError::ExternalServiceError {
+                service: "indexers".to_string(),
+                error: format!("All {} searches failed", summary.searched),
+            });
+        }
+        Ok(format!(
+            "Searched {} of {} cutoff unmet movies, grabbed {} upgrades ({} failed)",
+            summary.searched, summary.cutoff_unmet, summary.grabbed, summary.failed
+        ))
+    }
+}
+
+/// Runs `MoviesSearch`: searches indexers for the movies in `movieIds`,
+/// e.g. after a blocklisted release is retried
+pub struct MoviesSearchCommand {
+    movie_repository: Arc<dyn MovieRepository>,
+    searcher: Arc<dyn MovieSearcher>,
+}
+
+impl MoviesSearchCommand {
+    pub fn new(
+        movie_repository: Arc<dyn MovieRepository>,
+        searcher: Arc<dyn MovieSearcher>,
+    ) -> Self {
+        Self {
+            movie_repository,
+            searcher,
+        }
+    }
+
+    /// Search for one movie, returning whether a release was grabbed
+    async fn search(&self, id: Uuid) -> Result<bool> {
+        let movie =
+            self.movie_repository
+                .find_by_id(id)
+                .await?
+                .ok_or_else(|| RadarrError::NotFound {
+                    resource: format!("movie {}", id),
+                })?;
+        let grabbed = self.searcher.search_and_grab(&movie).await?;
+        if let Some(release) = &grabbed {
+            info!("Grabbed '{}' for {}", release, movie.title);
+        }
+        if let Err(e) = self
+            .movie_repository
+            .record_search_result(movie.id, grabbed.is_some())
+            .await
+        {
+            warn!("Failed to record search for {}: {}", movie.title, e);
+        }
+        Ok(grabbed.is_some())
+    }
+}
+
+#[async_trait]
+impl CommandExecutor for MoviesSearchCommand {
+    async fn execute(&self, command: &Command, progress: &CommandProgress) -> Result<String> {
+        let ids = body_movie_ids(command)?
+            .filter(|ids| !ids.is_empty())
+            .ok_or_else(|| RadarrError::ValidationError {
+                field: "movieIds".to_string(),
+                message: "At least one movie ID is required".to_string(),
+            })?;
+        let total = ids.len();
+        let (mut grabbed, mut failed) = (0, 0);
+
+        for (index, id) in ids.into_iter().enumerate() {
+            match self.search(id).await {
+                Ok(true) => grabbed += 1,
+                Ok(false) => {}
+                Err(e) => {
+                    warn!("Search for movie {} failed: {}", id, e);
+                    failed += 1;
+                }
+            }
+            progress
+                .update(
+                    (index + 1) as f32 * 100.0 / total as f32,
+                    format!("Searched {} of {} movies", index + 1, total),
+                )
+                .await;
+        }
+
+        if failed == total {
+            return Err(RadarrError::ExternalServiceError {
+                service: "indexers".to_string(),
+                error: format!("All {} searches failed", total),
+            });
+        }
+        Ok(format!(
+            "Searched {} movies, grabbed {} ({} failed)",
+            total, grabbed, failed
+        ))
+    }
+}
+
+/// Runs `ReconcileConfig`: compares quality profiles, indexers, import lists
+/// and naming with the desired state spec, applying the spec when auto-apply
+/// is on or the command body has `"apply": true`
+pub struct ReconcileConfigCommand {
+    reconciler: Arc<ConfigDriftReconciler>,
+}
+
+impl ReconcileConfigCommand {
+    pub fn new(reconciler: Arc<ConfigDriftReconciler>) -> Self {
+        Self { reconciler }
+    }
+}
+
+#[async_trait]
+impl CommandExecutor for ReconcileConfigCommand {
+    async fn execute(&self, command: &Command, progress: &CommandProgress) -> Result<String> {
+        let apply = command
+            .body
+            .get("apply")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(self.reconciler.config().auto_apply);
+
+        progress
+            .update(0.0, "Comparing configuration with desired state")
+            .await;
+        let report = self.reconciler.reconcile(apply).await?;
+
+        Ok(format!(
+            "Found {} differences from the desired state, applied {}",
+            report.drift.len(),
+            report.applied()
+        ))
+    }
+}
+
+/// Runs `DownloadsCleanup`: removes orphaned entries from the downloads
+/// folder, or only lists them when configured as a dry run or the command
+/// body has `"dryRun": true`; the body can't turn a configured dry run off
+pub struct DownloadsCleanupCommand {
+    cleanup: Arc<DownloadsCleanupService>,
+}
+
+impl DownloadsCleanupCommand {
+    pub fn new(cleanup: Arc<DownloadsCleanupService>) -> Self {
+        Self { cleanup }
+    }
+}
+
+#[async_trait]
+impl CommandExecutor for DownloadsCleanupCommand {
+    async fn execute(&self, command: &Command, progress: &CommandProgress) -> Result<String> {
+        progress.update(0.0, "Scanning downloads folder").await;
+        let dry_run = command.body.get("dryRun").and_then(|v| v.as_bool()) == Some(true);
+        let report = self.cleanup.run_with(dry_run).await?;
+
+        if report.dry_run {
+            return Ok(format!(
+                "Found {} orphaned entries ({} bytes), nothing removed",
+                report.candidates.len(),
+                report.candidates.iter().map(|c| c.size_bytes).sum::<u64>()
+            ));
+        }
+        Ok(format!(
+            "Removed {} orphaned entries, freed {} bytes ({} errors)",
+            report.removed.len(),
+            report.bytes_freed,
+            report.errors.len()
+        ))
+    }
+}
+
+/// Runs `SeedingCleanup`: removes imported torrents that met their seed goals
+pub struct SeedingCleanupCommand {
+    seeding: Arc<SeedingManager>,
+}
+
+impl SeedingCleanupCommand {
+    pub fn new(seeding: Arc<SeedingManager>) -> Self {
+        Self { seeding }
+    }
+}
+
+#[async_trait]
+impl CommandExecutor for SeedingCleanupCommand {
+    async fn execute(&self, _command: &Command, progress: &CommandProgress) -> Result<String> {
+        progress.update(0.0, "Checking seeding torrents").await;
+        let report = self.seeding.cleanup().await?;
+
+        Ok(format!(
+            "Checked {} seeding torrents, removed {} ({} with data)",
+            report.checked, report.removed, report.data_deleted
+        ))
+    }
+}
+
+/// Runs `Backup`: writes the configuration tables to a zip in the backups
+/// directory; scheduled runs prune the oldest scheduled backups
+pub struct BackupCommand {
+    backups: Arc<BackupService>,
+}
+
+impl BackupCommand {
+    pub fn new(backups: Arc<BackupService>) -> Self {
+        Self { backups }
+    }
+}
+
+#[async_trait]
+impl CommandExecutor for BackupCommand {
+    async fn execute(&self, command: &Command, progress: &CommandProgress) -> Result<String> {
+        let backup_type = match command.trigger {
+            CommandTrigger::Scheduled => BackupType::Scheduled,
+            CommandTrigger::Manual => BackupType::Manual,
+        };
+        progress.update(0.0, "Backing up configuration").await;
+        let backup = self.backups.create(backup_type).await?;
+
+        Ok(format!(
+            "Wrote {} ({} tables, {} bytes)",
+            backup.name,
+            backup.tables.len(),
+            backup.size
+        ))
+    }
+}
+
+/// Runs `ApplicationUpdateCheck`: asks GitHub for releases newer than the
+/// running version
+pub struct ApplicationUpdateCheckCommand {
+    updates: Arc<UpdateChecker>,
+}
+
+impl ApplicationUpdateCheckCommand {
+    pub fn new(updates: Arc<UpdateChecker>) -> Self {
+        Self { updates }
+    }
+}
+
+#[async_trait]
+impl CommandExecutor for ApplicationUpdateCheckCommand {
+    async fn execute(&self, _command: &Command, progress: &CommandProgress) -> Result<String> {
+        progress.update(0.0, "Checking for updates").await;
+        let status = self.updates.check().await?;
+
+        Ok(match status.releases.first() {
+            Some(newest) => format!("Version {} is available", newest.version),
+            None => "No update available".to_string(),
+        })
+    }
+}
+
+/// Library folders named in the command output before the rest are counted
+const UNMATCHED_LISTED: usize = 10;
+
+/// TMDB search matching library folders to movies, and the source of movie
+/// refreshes
+pub struct TmdbMovieLookup {
+    tmdb: Arc<CachedTmdbClient>,
+}
+
+impl TmdbMovieLookup {
+    pub fn new(tmdb: Arc<CachedTmdbClient>) -> Self {
+        Self { tmdb }
+    }
+}
+
+#[async_trait]
+impl MovieLookup for TmdbMovieLookup {
+    async fn search(&self, title: &str, _year: Option<i32>) -> Result<Vec<Movie>> {
+        Ok(self.tmdb.search_movies(title, None).await?)
+    }
+
+    async fn get(&self, tmdb_id: i32) -> Result<Movie> {
+        Ok(self.tmdb.get_movie(tmdb_id).await?)
+    }
+}
+
+#[async_trait]
+impl MovieMetadataSource for TmdbMovieLookup {
+    async fn movie_metadata(&self, tmdb_id: i32) -> Result<Movie> {
+        Ok(self.tmdb.refresh_movie(tmdb_id).await?)
+    }
+}
+
+/// Runs `LibraryImport`: adds the movies already in the library folder
+/// `path`, or in every root folder, leaving the files where they are. The
+/// body may set `monitored`, `qualityProfileId` and `dryRun`
+pub struct LibraryImportCommand {
+    library_import: Arc<LibraryImportService>,
+    root_folders: Arc<dyn RootFolderRepository>,
+}
+
+impl LibraryImportCommand {
+    pub fn new(
+        library_import: Arc<LibraryImportService>,
+        root_folders: Arc<dyn RootFolderRepository>,
+    ) -> Self {
+        Self {
+            library_import,
+            root_folders,
+        }
+    }
+
+    async fn library_paths(&self, command: &Command) -> Result<Vec<PathBuf>> {
+        if let Some(path) = command.body.get("path").and_then(|v| v.as_str()) {
+            return Ok(vec![PathBuf::from(path)]);
+        }
+        let paths: Vec<PathBuf> = self
+            .root_folders
+            .list()
+            .await?
+            .into_iter()
+            .map(|folder| PathBuf::from(folder.path))
+            .collect();
+        if paths.is_empty() {
+            return Err(RadarrError::ValidationError {
+                field: "path".to_string(),
+                message: "No path given and no root folder configured".to_string(),
+            });
+        }
+        Ok(paths)
+    }
+}
+
+#[async_trait]
+impl CommandExecutor for LibraryImportCommand {
+    async fn execute(&self, command: &Command, progress: &CommandProgress) -> Result<String> {
+        let defaults = LibraryImportOptions::default();
+        let options = LibraryImportOptions {
+            monitored: command
+                .body
+                .get("monitored")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(defaults.monitored),
+            quality_profile_id: command
+                .body
+                .get("qualityProfileId")
+                .and_then(|v| v.as_i64())
+                .map(|id| id as i32),
+            dry_run: command
+                .body
+                .get("dryRun")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(defaults.dry_run),
+        };
+
+        let paths = self.library_paths(command).await?;
+        let mut imported = 0;
+        let mut already_imported = 0;
+        let mut unmatched = Vec::new();
+        for (index, path) in paths.iter().enumerate() {
+            progress
+                .update(
+                    index as f32 * 100.0 / paths.len() as f32,
+                    format!("Importing movies in {}", path.display()),
+                )
+                .await;
+            let report = self.library_import.import_library(path, &options).await?;
+            imported += report.imported.len();
+            already_imported += report.already_imported.len();
+            for folder in report.unmatched {
+                info!(
+                    "Unmatched library folder {}: {}",
+                    folder.folder.display(),
+                    folder.reason
+                );
+                unmatched.push(folder);
+            }
+        }
+
+        let verb = if options.dry_run {
+            "Would import"
+        } else {
+            "Imported"
+        };
+        let mut message = format!(
+            "{} {} movies, {} already imported, {} unmatched",
+            verb,
+            imported,
+            already_imported,
+            unmatched.len()
+        );
+        if !unmatched.is_empty() {
+            let names: Vec<String> = unmatched
+                .iter()
+                .take(UNMATCHED_LISTED)
+                .map(|folder| {
+                    folder
+                        .folder
+                        .file_name()
+                        .unwrap_or(folder.folder.as_os_str())
+                        .to_string_lossy()
+                        .to_string()
+                })
+                .collect();
+            message.push_str(&format!(": {}", names.join(", ")));
+            if unmatched.len() > UNMATCHED_LISTED {
+                message.push_str(&format!(" and {} more", unmatched.len() - UNMATCHED_LISTED));
+            }
+        }
+        Ok(message)
+    }
+}
+
+/// Runs `MoveMovie`: moves the folder of the movie `movieId` from below
+/// `sourceRoot`, or its current root folder, into its root folder, renamed
+/// after the naming templates when `renameFolder` is set
+pub struct MoveMovieCommand {
+    job: Arc<MoveMovieJob>,
+}
+
+impl MoveMovieCommand {
+    pub fn new(job: Arc<MoveMovieJob>) -> Self {
+        Self { job }
+    }
+}
+
+#[async_trait]
+impl CommandExecutor for MoveMovieCommand {
+    async fn execute(&self, command: &Command, progress: &CommandProgress) -> Result<String> {
+        let request: MoveMovieRequest =
+            serde_json::from_value(command.body.clone()).map_err(|e| {
+                RadarrError::ValidationError {
+                    field: "movieId".to_string(),
+                    message: format!("Invalid MoveMovie body: {}", e),
+                }
+            })?;
+        let report = self.job.run(&request, Some(progress)).await?;
+
+        if !report.moved {
+            return Ok(format!(
+                "{} is already in place",
+                report.destination.display()
+            ));
+        }
+        Ok(format!(
+            "Moved {} files ({} bytes) from {} to {}{}",
+            report.files,
+            report.bytes,
+            report.source.display(),
+            report.destination.display(),
+            if report.copied { " by copying" } else { "" }
+        ))
+    }
+}
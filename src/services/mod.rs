@@ -6,22 +6,42 @@
 //! - Business logic coordination
 
 use radarr_core::{
-    EventBus, EventProcessor, QueueProcessor, QueueProcessorConfig, RadarrError, Result,
+    domain::repositories::{ApiKeyRepository, NotificationRepository, ShareTokenRepository},
+    jobs::TaskScheduler,
+    CommandQueue, ConfigDriftConfig, ConfigDriftReconciler, DiskSpaceCheck, DownloadClientCheck,
+    DownloadClientManager, DownloadHandlingConfig, EventBus, EventProcessor, FailedDownloadHandler,
+    HealthCheckRegistry, ImportFailureCheck, LibraryStatisticsService, NamingSettings,
+    PreviewImportConfig, QueueProcessor, QueueProcessorConfig, QueueSyncMetrics, RadarrError,
+    Result, RootFolderCheck, SeedingConfig, SeedingManager, ShutdownCoordinator, StorageThrottle,
 };
+use radarr_decision::{DecisionEngine, QualityProfile};
 use radarr_downloaders::QBittorrentClient;
-use radarr_import::ImportPipeline;
-use radarr_indexers::IndexerClient;
+use radarr_import::{ImportPipeline, MovieFolderRenameHandler};
+use radarr_indexers::{IndexerCheck, IndexerClient};
 use radarr_infrastructure::{
+    event_bridge::{EventBridge, EventBridgeConfig},
     monitoring::list_sync_monitor::{ListSyncMonitor, ListSyncMonitorConfig},
-    DatabasePool, PostgresMovieRepository, PostgresQueueRepository, QBittorrentDownloadClient,
+    BackupConfig, BackupService, CacheManager, ConfigDriftHealthChecker, DatabaseCheck,
+    DatabasePool, MediaCoverConfig, MediaCoverService, PoolMonitor, PostgresApiKeyRepository,
+    PostgresAuditRepository, PostgresBlocklistRepository, PostgresCommandRepository,
+    PostgresHistoryRepository, PostgresImportListRepository, PostgresIndexerRepository,
+    PostgresMovieFileRepository, PostgresMovieRepository, PostgresNotificationRepository,
+    PostgresQualityProfileRepository, PostgresQueueRepository, PostgresRootFolderRepository,
+    PostgresShareTokenRepository, ReadPool, UpdateChecker, UpdateConfig, UpdateHealthChecker,
 };
+use radarr_notifications::{NotificationEventHandler, NotificationService};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 use tracing::{debug, error, info, warn};
 
+pub mod commands;
+pub mod recovery;
 pub mod rss_service;
 pub mod simplified_media_service;
 pub mod workflow;
 
+pub use commands::*;
 pub use rss_service::*;
 pub use simplified_media_service::*;
 pub use workflow::*;
@@ -33,6 +53,10 @@ pub struct AppServices {
     pub media_service: Arc<SimplifiedMediaService>,
     /// Database pool
     pub database_pool: DatabasePool,
+    /// Read-only queries, served by the read replica while it keeps up
+    pub read_pool: ReadPool,
+    /// Probes the database pool for the health check and metrics
+    pub pool_monitor: PoolMonitor,
     /// Movie repository for database operations
     pub movie_repository: Arc<PostgresMovieRepository>,
     /// Indexer client for direct API access
@@ -43,13 +67,52 @@ pub struct AppServices {
     pub queue_repository: Option<Arc<dyn radarr_core::services::QueueRepository + Send + Sync>>,
     /// Queue processor for background download processing
     pub queue_processor:
-        Option<Arc<QueueProcessor<PostgresQueueRepository, QBittorrentDownloadClient>>>,
+        Option<Arc<QueueProcessor<PostgresQueueRepository, DownloadClientManager>>>,
+    /// Download client shared with the queue processor, used for manual grabs
+    pub download_client: Option<Arc<dyn radarr_core::DownloadClientService>>,
+    /// Write counters from the queue processor's download client sync
+    pub queue_sync_metrics: Option<Arc<QueueSyncMetrics>>,
+    /// Root folder free space, holding grabs while a folder is nearly full
+    pub storage_throttle: Arc<StorageThrottle>,
+    /// What happens to downloads that fail in the download client
+    pub download_handling: DownloadHandlingConfig,
+    /// Removes imported torrents once they met their seed goals
+    pub seeding: Option<Arc<SeedingManager>>,
+    /// Drift detection against the desired state spec, if one is configured
+    pub config_drift: Option<Arc<ConfigDriftReconciler>>,
     /// RSS monitoring service
     pub rss_service: Option<Arc<RssService>>,
+    /// Cache of TMDB and streaming lookups, in memory or Redis
+    pub cache: Arc<CacheManager>,
+    /// Cached posters and fanart served from `/MediaCover`
+    pub media_covers: Arc<MediaCoverService>,
+    /// Configuration backups written by the `Backup` task and the API
+    pub backups: Arc<BackupService>,
+    /// Releases newer than the running version, found by the
+    /// `ApplicationUpdateCheck` task
+    pub updates: Arc<UpdateChecker>,
+    /// Checks behind `/api/v3/health`
+    pub health_checks: Arc<HealthCheckRegistry>,
+    /// Cached library totals for `/api/v3/movie/stats` and the system status
+    pub library_statistics: Arc<LibraryStatisticsService>,
     /// Streaming service aggregator
     pub streaming_aggregator: Option<Arc<dyn radarr_core::streaming::traits::StreamingAggregator>>,
     /// List sync monitor for system monitoring
     pub list_sync_monitor: Option<Arc<ListSyncMonitor>>,
+    /// Bridge republishing events to an external broker
+    pub event_bridge: Option<Arc<EventBridge>>,
+    /// Notification providers configured through the API
+    pub notification_service: Arc<NotificationService>,
+    /// Import pipeline shared with the media service
+    pub import_pipeline: Arc<ImportPipeline>,
+    /// Commands queued through the API
+    pub command_queue: Arc<CommandQueue>,
+    /// Recurring tasks, run as commands on the command queue
+    pub task_scheduler: Arc<TaskScheduler>,
+    /// Whether the event processor task is running
+    pub event_processor_running: Arc<AtomicBool>,
+    /// Background services stop and drain through this on shutdown
+    pub shutdown: ShutdownCoordinator,
 }
 
 impl AppServices {
@@ -67,30 +130,71 @@ impl AppServices {
             database_pool.clone(),
             prowlarr_client.clone(),
             qbittorrent_client.clone(),
-            import_pipeline,
+            import_pipeline.clone(),
         ));
 
         // Create movie repository
         let movie_repository = Arc::new(PostgresMovieRepository::new(database_pool.clone()));
 
+        // Stored so queued and running commands survive a restart
+        let command_queue = Arc::new(CommandQueue::default().with_repository(Arc::new(
+            PostgresCommandRepository::new(database_pool.clone()),
+        )));
+        let task_scheduler = Arc::new(TaskScheduler::new(command_queue.clone()));
+
+        let library_statistics = Arc::new(LibraryStatisticsService::new(
+            movie_repository.clone(),
+            Arc::new(PostgresMovieFileRepository::new(database_pool.clone())),
+            Arc::new(PostgresQualityProfileRepository::new(database_pool.clone())),
+        ));
+        let backups = Arc::new(BackupService::new(
+            database_pool.clone(),
+            &BackupConfig::default(),
+        ));
+        let updates =
+            Arc::new(UpdateChecker::new(UpdateConfig::default()).with_event_bus(event_bus.clone()));
+
         Ok(Self {
             media_service,
+            read_pool: ReadPool::primary_only(database_pool.clone()),
+            pool_monitor: PoolMonitor::new("primary", database_pool.clone()),
             database_pool,
             movie_repository,
             indexer_client: prowlarr_client,
             event_bus,
-            queue_repository: None,     // Will be initialized separately
-            queue_processor: None,      // Will be initialized separately
-            rss_service: None,          // Will be initialized separately
+            queue_repository: None,   // Will be initialized separately
+            queue_processor: None,    // Will be initialized separately
+            download_client: None,    // Set with the queue processor
+            queue_sync_metrics: None, // Set with the queue processor
+            storage_throttle: Arc::new(StorageThrottle::default()),
+            download_handling: DownloadHandlingConfig::default(),
+            seeding: None,      // Set with the queue processor
+            config_drift: None, // Set by the builder when a spec is configured
+            rss_service: None,  // Will be initialized separately
+            cache: Arc::new(CacheManager::new()),
+            media_covers: Arc::new(MediaCoverService::new(&MediaCoverConfig::default())),
+            backups,
+            updates,
+            health_checks: Arc::new(HealthCheckRegistry::new()),
+            library_statistics,
             streaming_aggregator: None, // Will be initialized separately
             list_sync_monitor: None,    // Will be initialized separately
+            event_bridge: None,         // Will be initialized separately
+            notification_service: Arc::new(NotificationService::new()),
+            import_pipeline,
+            command_queue,
+            task_scheduler,
+            event_processor_running: Arc::new(AtomicBool::new(false)),
+            shutdown: ShutdownCoordinator::new(),
         })
     }
 
     /// Initialize queue processor with proper configuration
     pub fn initialize_queue_processor(
         &mut self,
-        qbittorrent_config: radarr_downloaders::QBittorrentConfig,
+        download_clients: Arc<DownloadClientManager>,
+        queue_config: QueueProcessorConfig,
+        preview_import: Option<PreviewImportConfig>,
     ) -> Result<()> {
         // Create queue repository
         let queue_repo = Arc::new(PostgresQueueRepository::new(self.database_pool.clone()));
@@ -98,17 +202,19 @@ impl AppServices {
         // Store queue repository for use by other services
         self.queue_repository = Some(queue_repo.clone());
 
-        // Create download client service adapter
-        let download_client = Arc::new(QBittorrentDownloadClient::new(qbittorrent_config)?);
-
-        // Create queue processor
-        let queue_config = QueueProcessorConfig::default();
-        let queue_processor = Arc::new(QueueProcessor::new(
-            queue_config,
-            queue_repo,
-            download_client,
-        ));
+        // Create queue processor; grabs are routed between the configured clients
+        let mut queue_processor =
+            QueueProcessor::new(queue_config, queue_repo, download_clients.clone())
+                .with_storage_throttle(self.storage_throttle.clone())
+                .with_event_bus(self.event_bus.clone())
+                .with_shutdown(self.shutdown.clone());
+        if let Some(preview_import) = preview_import {
+            queue_processor = queue_processor.with_preview_import(preview_import);
+        }
+        let queue_processor = Arc::new(queue_processor);
+        self.download_client = Some(download_clients);
 
+        self.queue_sync_metrics = Some(queue_processor.sync_metrics());
         self.queue_processor = Some(queue_processor);
         Ok(())
     }
@@ -124,6 +230,9 @@ impl AppServices {
                     message: "Queue repository must be initialized before RSS service".to_string(),
                 })?;
 
+        let decision_engine = DecisionEngine::permissive(QualityProfile::default())
+            .with_proper_handling(config.proper_handling)
+            .with_language_profile(config.language_profile.clone());
         let rss_service = Arc::new(
             RssService::new(
                 config,
@@ -132,25 +241,58 @@ impl AppServices {
                 self.movie_repository.clone(),
                 queue_repository.clone(),
             )
-            .with_event_bus(self.event_bus.clone()),
+            .with_event_bus(self.event_bus.clone())
+            .with_movie_file_repository(Arc::new(PostgresMovieFileRepository::new(
+                self.database_pool.clone(),
+            )))
+            .with_blocklist_repository(Arc::new(PostgresBlocklistRepository::new(
+                self.database_pool.clone(),
+            )))
+            .with_indexer_repository(Arc::new(PostgresIndexerRepository::new(
+                self.database_pool.clone(),
+            )))
+            .with_decision_engine(decision_engine)
+            .with_shutdown(self.shutdown.clone()),
         );
 
         self.rss_service = Some(rss_service);
         Ok(())
     }
 
-    /// Initialize streaming service aggregator
-    pub fn initialize_streaming_aggregator(&mut self) -> Result<()> {
+    /// Initialize streaming service aggregator, keeping lookups in the cache
+    /// for up to `cache_ttl`
+    pub fn initialize_streaming_aggregator(&mut self, cache_ttl: Duration) -> Result<()> {
         use radarr_infrastructure::streaming::create_default_aggregator;
 
         // Create aggregator from environment variables
-        let aggregator = create_default_aggregator(self.database_pool.clone());
+        let aggregator =
+            create_default_aggregator(self.database_pool.clone(), self.cache.clone(), cache_ttl);
         self.streaming_aggregator = Some(aggregator);
 
         info!("Streaming service aggregator initialized");
         Ok(())
     }
 
+    /// Register the checks reported by `/api/v3/health`
+    ///
+    /// Call once the download client is set up, so it is checked as well.
+    pub fn initialize_health_checks(&self) {
+        let checks = &self.health_checks;
+        checks.register(Arc::new(DatabaseCheck::new(self.pool_monitor.clone())));
+        checks.register(Arc::new(IndexerCheck::new(self.indexer_client.clone())));
+        if let Some(download_client) = &self.download_client {
+            checks.register(Arc::new(DownloadClientCheck::new(download_client.clone())));
+        }
+        checks.register(Arc::new(RootFolderCheck::new(Arc::new(
+            PostgresRootFolderRepository::new(self.database_pool.clone()),
+        ))));
+        checks.register(Arc::new(DiskSpaceCheck::new(self.storage_throttle.clone())));
+        checks.register(Arc::new(ImportFailureCheck::new(Arc::new(
+            PostgresHistoryRepository::new(self.database_pool.clone()),
+        ))));
+        debug!("Health checks registered: {:?}", checks.names());
+    }
+
     /// Initialize list sync monitor with default configuration
     pub async fn initialize_list_sync_monitor(&mut self) -> Result<()> {
         let config = ListSyncMonitorConfig::default();
@@ -169,6 +311,23 @@ impl AppServices {
             }
         })?;
 
+        // Drift from the desired state spec shows up as a degraded service
+        if let Some(reconciler) = &self.config_drift {
+            monitor
+                .add_health_checker(Box::new(ConfigDriftHealthChecker::new(reconciler.clone())))
+                .await;
+        }
+
+        // So does a release newer than the running version
+        if self.updates.config().enabled {
+            monitor
+                .add_health_checker(Box::new(UpdateHealthChecker::new(self.updates.clone())))
+                .await;
+        }
+
+        // Health changes reach push clients and history through the event bus
+        monitor.set_event_bus(self.event_bus.clone()).await;
+
         self.list_sync_monitor = Some(monitor);
         info!("List sync monitor initialized");
         Ok(())
@@ -178,7 +337,7 @@ impl AppServices {
     pub async fn start_list_sync_monitor(&self) -> Result<()> {
         if let Some(monitor) = &self.list_sync_monitor {
             monitor
-                .start_monitoring()
+                .start_monitoring(&self.shutdown)
                 .await
                 .map_err(|e| RadarrError::ExternalServiceError {
                     service: "list_sync_monitor".to_string(),
@@ -213,7 +372,7 @@ impl AppServices {
                         .to_string(),
                 })?;
 
-            tokio::spawn(async move {
+            self.shutdown.spawn("queue processor", async move {
                 info!("Starting queue processor...");
                 if let Err(e) = processor.start().await {
                     error!("Queue processor failed: {}", e);
@@ -243,6 +402,48 @@ impl AppServices {
         Ok(())
     }
 
+    /// Connect the external event bridge (NATS/MQTT)
+    ///
+    /// Must be called before `start_event_processing` for the bridge to
+    /// receive events.
+    pub async fn initialize_event_bridge(&mut self, config: &EventBridgeConfig) -> Result<()> {
+        let bridge = EventBridge::connect(config).await?;
+        self.event_bridge = Some(Arc::new(bridge));
+        Ok(())
+    }
+
+    /// Load the notification providers configured through the API
+    pub async fn initialize_notifications(&self) -> Result<()> {
+        let definitions = PostgresNotificationRepository::new(self.database_pool.clone())
+            .list()
+            .await?;
+        self.notification_service.load_definitions(definitions);
+        Ok(())
+    }
+
+    /// Load the stored share tokens into the auth middleware's registry
+    pub async fn initialize_share_tokens(&self) -> Result<usize> {
+        let tokens = PostgresShareTokenRepository::new(self.database_pool.clone())
+            .list()
+            .await?;
+        let now = chrono::Utc::now();
+        let active = tokens.iter().filter(|token| token.is_active(now)).count();
+        radarr_api::share_tokens::global_registry().replace(tokens);
+        Ok(active)
+    }
+
+    /// Load the stored API keys into the auth middleware's registry
+    pub async fn initialize_api_keys(&self) -> Result<usize> {
+        let repository = Arc::new(PostgresApiKeyRepository::new(self.database_pool.clone()));
+        let keys = repository.list().await?;
+        let active = keys.iter().filter(|key| key.is_active()).count();
+
+        let registry = radarr_api::api_keys::global_registry();
+        registry.replace(keys);
+        registry.set_repository(repository);
+        Ok(active)
+    }
+
     /// Start event processing with all handlers
     pub async fn start_event_processing(&self) -> Result<()> {
         info!("Starting event processing system");
@@ -254,18 +455,58 @@ impl AppServices {
             self.database_pool.clone(),
             self.event_bus.clone(),
         ));
+        let history_handler = Arc::new(HistoryEventHandler::new(Arc::new(
+            PostgresHistoryRepository::new(self.database_pool.clone()),
+        )));
+        let audit_handler = Arc::new(AuditEventHandler::new(Arc::new(
+            PostgresAuditRepository::new(self.database_pool.clone()),
+        )));
+        let notification_handler = Arc::new(NotificationEventHandler::new(
+            self.notification_service.clone(),
+            self.movie_repository.clone(),
+        ));
 
         // Create event processor
-        let event_processor = EventProcessor::new(&self.event_bus)
+        let mut event_processor = EventProcessor::new(&self.event_bus)
+            .with_shutdown(self.shutdown.clone())
             .add_handler(logging_handler)
-            .add_handler(download_import_handler);
+            .add_handler(download_import_handler)
+            .add_handler(history_handler)
+            .add_handler(audit_handler)
+            .add_handler(notification_handler);
+        if let Some(bridge) = &self.event_bridge {
+            event_processor = event_processor.add_handler(bridge.clone());
+        }
+        if let (Some(queue_repository), Some(download_client)) =
+            (&self.queue_repository, &self.download_client)
+        {
+            let failed_download_handler = FailedDownloadHandler::new(
+                self.download_handling.clone(),
+                queue_repository.clone(),
+                download_client.clone(),
+                Arc::new(PostgresBlocklistRepository::new(self.database_pool.clone())),
+            )
+            .with_command_queue(self.command_queue.clone());
+            event_processor = event_processor.add_handler(Arc::new(failed_download_handler));
+        }
+        if let Some(seeding) = &self.seeding {
+            event_processor = event_processor.add_handler(seeding.clone());
+        }
+        event_processor = event_processor.add_handler(self.media_covers.clone());
+        // Folders follow title and year changes from metadata refreshes
+        event_processor = event_processor.add_handler(Arc::new(MovieFolderRenameHandler::new(
+            self.command_queue.clone(),
+        )));
 
         // Start event processor in background
         let event_bus = self.event_bus.clone();
-        tokio::spawn(async move {
+        let running = self.event_processor_running.clone();
+        running.store(true, Ordering::Relaxed);
+        self.shutdown.spawn("event processor", async move {
             if let Err(e) = event_processor.run().await {
                 error!("Event processor failed: {}", e);
             }
+            running.store(false, Ordering::Relaxed);
         });
 
         info!(
@@ -297,9 +538,20 @@ impl AppServices {
 /// Service initialization helper
 pub struct ServiceBuilder {
     database_pool: Option<DatabasePool>,
+    read_pool: Option<ReadPool>,
+    pool_monitor: Option<PoolMonitor>,
     prowlarr_client: Option<Arc<dyn IndexerClient + Send + Sync>>,
     qbittorrent_client: Option<Arc<QBittorrentClient>>,
-    qbittorrent_config: Option<radarr_downloaders::QBittorrentConfig>,
+    download_clients: Option<Arc<DownloadClientManager>>,
+    queue_config: QueueProcessorConfig,
+    preview_import: Option<PreviewImportConfig>,
+    download_handling: DownloadHandlingConfig,
+    seeding: SeedingConfig,
+    desired_state: ConfigDriftConfig,
+    cache: Option<Arc<CacheManager>>,
+    media_covers: Option<Arc<MediaCoverService>>,
+    backups: BackupConfig,
+    updates: UpdateConfig,
     import_pipeline: Option<Arc<ImportPipeline>>,
 }
 
@@ -307,9 +559,20 @@ impl ServiceBuilder {
     pub fn new() -> Self {
         Self {
             database_pool: None,
+            read_pool: None,
+            pool_monitor: None,
             prowlarr_client: None,
             qbittorrent_client: None,
-            qbittorrent_config: None,
+            download_clients: None,
+            queue_config: QueueProcessorConfig::default(),
+            preview_import: None,
+            download_handling: DownloadHandlingConfig::default(),
+            seeding: SeedingConfig::default(),
+            desired_state: ConfigDriftConfig::default(),
+            cache: None,
+            media_covers: None,
+            backups: BackupConfig::default(),
+            updates: UpdateConfig::default(),
             import_pipeline: None,
         }
     }
@@ -319,6 +582,18 @@ impl ServiceBuilder {
         self
     }
 
+    /// Serve library listings and history from a read replica
+    pub fn with_read_pool(mut self, reads: ReadPool) -> Self {
+        self.read_pool = Some(reads);
+        self
+    }
+
+    /// Report the pool monitored for metrics in the database health check
+    pub fn with_pool_monitor(mut self, monitor: PoolMonitor) -> Self {
+        self.pool_monitor = Some(monitor);
+        self
+    }
+
     pub fn with_prowlarr(mut self, client: Arc<dyn IndexerClient + Send + Sync>) -> Self {
         self.prowlarr_client = Some(client);
         self
@@ -329,11 +604,62 @@ impl ServiceBuilder {
         self
     }
 
-    pub fn with_qbittorrent_config(
-        mut self,
-        config: radarr_downloaders::QBittorrentConfig,
-    ) -> Self {
-        self.qbittorrent_config = Some(config);
+    /// Download clients that grabs are sent to, enabling the queue processor
+    pub fn with_download_clients(mut self, clients: Arc<DownloadClientManager>) -> Self {
+        self.download_clients = Some(clients);
+        self
+    }
+
+    pub fn with_queue_config(mut self, config: QueueProcessorConfig) -> Self {
+        self.queue_config = config;
+        self
+    }
+
+    /// Stage early import previews of sequential torrents
+    pub fn with_preview_import(mut self, config: PreviewImportConfig) -> Self {
+        self.preview_import = Some(config);
+        self
+    }
+
+    /// Blocklist, remove and replace downloads that fail
+    pub fn with_download_handling(mut self, config: DownloadHandlingConfig) -> Self {
+        self.download_handling = config;
+        self
+    }
+
+    /// Remove imported torrents once they met their seed goals
+    pub fn with_seeding(mut self, config: SeedingConfig) -> Self {
+        self.seeding = config;
+        self
+    }
+
+    /// Compare the configuration with a declarative desired state spec
+    pub fn with_desired_state(mut self, config: ConfigDriftConfig) -> Self {
+        self.desired_state = config;
+        self
+    }
+
+    /// Cache for TMDB and streaming lookups
+    pub fn with_cache(mut self, cache: Arc<CacheManager>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Cache for movie posters and fanart
+    pub fn with_media_covers(mut self, covers: Arc<MediaCoverService>) -> Self {
+        self.media_covers = Some(covers);
+        self
+    }
+
+    /// Where configuration backups are written and how many are kept
+    pub fn with_backups(mut self, config: BackupConfig) -> Self {
+        self.backups = config;
+        self
+    }
+
+    /// Whether and where to look for new releases
+    pub fn with_updates(mut self, config: UpdateConfig) -> Self {
+        self.updates = config;
         self
     }
 
@@ -371,6 +697,7 @@ impl ServiceBuilder {
                 message: "Import pipeline is required".to_string(),
             })?;
 
+        let rename_config = import_pipeline.config().rename_config.clone();
         let mut services = AppServices::new(
             database_pool.clone(),
             prowlarr_client,
@@ -378,11 +705,72 @@ impl ServiceBuilder {
             import_pipeline,
         )
         .await?;
+        services.download_handling = self.download_handling;
+        if let Some(cache) = self.cache {
+            services.cache = cache;
+        }
+        if let Some(covers) = self.media_covers {
+            services.media_covers = covers;
+        }
+        services.backups = Arc::new(BackupService::new(database_pool.clone(), &self.backups));
+        services.updates =
+            Arc::new(UpdateChecker::new(self.updates).with_event_bus(services.event_bus.clone()));
+
+        if self.desired_state.spec_path.is_some() {
+            let reconciler = ConfigDriftReconciler::new(
+                self.desired_state,
+                Arc::new(PostgresQualityProfileRepository::new(database_pool.clone())),
+                Arc::new(PostgresIndexerRepository::new(database_pool.clone())),
+            )
+            .with_import_lists(Arc::new(PostgresImportListRepository::new(
+                database_pool.clone(),
+            )))
+            .with_naming(NamingSettings {
+                movie_template: rename_config.movie_template,
+                folder_template: rename_config.folder_template,
+            });
+            services.config_drift = Some(Arc::new(reconciler));
+        }
+
+        if let Some(reads) = self.read_pool {
+            services.movie_repository = Arc::new(
+                PostgresMovieRepository::new(database_pool.clone()).with_read_pool(reads.clone()),
+            );
+            services.read_pool = reads;
+            services.library_statistics = Arc::new(LibraryStatisticsService::new(
+                services.movie_repository.clone(),
+                Arc::new(PostgresMovieFileRepository::new(database_pool.clone())),
+                Arc::new(PostgresQualityProfileRepository::new(database_pool.clone())),
+            ));
+        }
+        if let Some(monitor) = self.pool_monitor {
+            services.pool_monitor = monitor;
+        }
 
-        // Initialize queue processor if config is provided
-        if let Some(qbittorrent_config) = self.qbittorrent_config {
-            services.initialize_queue_processor(qbittorrent_config)?;
+        // Initialize queue processor if download clients are configured
+        if let Some(download_clients) = self.download_clients {
+            services.initialize_queue_processor(
+                download_clients,
+                self.queue_config,
+                self.preview_import,
+            )?;
+        }
+        if let (true, Some(queue_repository), Some(download_client)) = (
+            self.seeding.enabled,
+            &services.queue_repository,
+            &services.download_client,
+        ) {
+            let seeding = SeedingManager::new(
+                self.seeding,
+                queue_repository.clone(),
+                download_client.clone(),
+            )
+            .with_indexers(Arc::new(PostgresIndexerRepository::new(
+                database_pool.clone(),
+            )));
+            services.seeding = Some(Arc::new(seeding));
         }
+        services.initialize_health_checks();
 
         Ok(services)
     }
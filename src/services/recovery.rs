@@ -0,0 +1,169 @@
+//! Health checks and automated recovery actions for core services
+//!
+//! Registers health checkers for the download client, indexer and RSS
+//! background tasks with the list sync monitor, and binds each of them to a
+//! recovery action that the monitor runs when the service keeps failing.
+
+use super::{AppServices, RssService};
+use radarr_core::i18n::translate;
+use radarr_downloaders::QBittorrentClient;
+use radarr_indexers::IndexerClient;
+use radarr_infrastructure::monitoring::{
+    health_checks::{HealthCheckResult, ServiceHealthChecker},
+    FnRecoveryAction,
+};
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::info;
+
+/// Health checker for the qBittorrent download client
+pub struct DownloadClientHealthChecker {
+    client: Arc<QBittorrentClient>,
+}
+
+#[async_trait::async_trait]
+impl ServiceHealthChecker for DownloadClientHealthChecker {
+    async fn check_health(&self) -> HealthCheckResult {
+        let start = Instant::now();
+        match self.client.test_connection().await {
+            Ok(()) => HealthCheckResult::healthy(start.elapsed()),
+            Err(e) => HealthCheckResult::unhealthy(e.to_string()),
+        }
+    }
+
+    fn service_name(&self) -> &str {
+        "qbittorrent"
+    }
+}
+
+/// Health checker for the configured indexer client
+pub struct IndexerHealthChecker {
+    client: Arc<dyn IndexerClient + Send + Sync>,
+}
+
+#[async_trait::async_trait]
+impl ServiceHealthChecker for IndexerHealthChecker {
+    async fn check_health(&self) -> HealthCheckResult {
+        let start = Instant::now();
+        match self.client.health_check().await {
+            Ok(true) => HealthCheckResult::healthy(start.elapsed()),
+            Ok(false) => {
+                HealthCheckResult::unhealthy(translate("health-indexer-check-failed", &[]))
+            }
+            Err(e) => HealthCheckResult::unhealthy(e.to_string()),
+        }
+    }
+
+    fn service_name(&self) -> &str {
+        "indexer"
+    }
+}
+
+/// Health checker for the RSS monitoring background tasks
+pub struct RssTaskHealthChecker {
+    rss_service: Arc<RssService>,
+}
+
+#[async_trait::async_trait]
+impl ServiceHealthChecker for RssTaskHealthChecker {
+    async fn check_health(&self) -> HealthCheckResult {
+        let start = Instant::now();
+        if !self.rss_service.is_enabled() || self.rss_service.is_running().await {
+            HealthCheckResult::healthy(start.elapsed())
+        } else {
+            HealthCheckResult::unhealthy(translate("health-rss-not-running", &[]))
+        }
+    }
+
+    fn service_name(&self) -> &str {
+        "rss"
+    }
+}
+
+impl AppServices {
+    /// Register health checkers and recovery actions with the list sync monitor
+    pub async fn register_recovery_actions(&self) {
+        let Some(monitor) = &self.list_sync_monitor else {
+            return;
+        };
+        let recovery_manager = monitor.recovery_manager();
+
+        // Download client: drop the session and log in again
+        let download_client = self.media_service.download_client();
+        monitor
+            .add_health_checker(Box::new(DownloadClientHealthChecker {
+                client: download_client.clone(),
+            }))
+            .await;
+        recovery_manager
+            .register_action(
+                "qbittorrent",
+                Arc::new(FnRecoveryAction::new(
+                    "reconnect_download_client",
+                    move || {
+                        let client = download_client.clone();
+                        async move {
+                            client
+                                .login()
+                                .await
+                                .map(|_| "Re-authenticated with qBittorrent".to_string())
+                                .map_err(|e| e.to_string())
+                        }
+                    },
+                )),
+            )
+            .await;
+
+        // Indexer: re-validate the API key by fetching the indexer list
+        let indexer_client = self.indexer_client.clone();
+        monitor
+            .add_health_checker(Box::new(IndexerHealthChecker {
+                client: indexer_client.clone(),
+            }))
+            .await;
+        recovery_manager
+            .register_action(
+                "indexer",
+                Arc::new(FnRecoveryAction::new("reauthenticate_indexer", move || {
+                    let client = indexer_client.clone();
+                    async move {
+                        client
+                            .get_indexers()
+                            .await
+                            .map(|indexers| {
+                                format!("Indexer API reachable ({} indexers)", indexers.len())
+                            })
+                            .map_err(|e| e.to_string())
+                    }
+                })),
+            )
+            .await;
+
+        // RSS: restart the background monitoring tasks
+        if let Some(rss_service) = &self.rss_service {
+            let rss_service = rss_service.clone();
+            monitor
+                .add_health_checker(Box::new(RssTaskHealthChecker {
+                    rss_service: rss_service.clone(),
+                }))
+                .await;
+            recovery_manager
+                .register_action(
+                    "rss",
+                    Arc::new(FnRecoveryAction::new("restart_rss_service", move || {
+                        let rss_service = rss_service.clone();
+                        async move {
+                            rss_service
+                                .restart()
+                                .await
+                                .map(|_| "RSS monitoring tasks restarted".to_string())
+                                .map_err(|e| e.to_string())
+                        }
+                    })),
+                )
+                .await;
+        }
+
+        info!("Registered automated recovery actions");
+    }
+}
@@ -1,21 +1,27 @@
 //! RSS monitoring service for automated discovery
 
+use async_trait::async_trait;
 use chrono::Utc;
 use radarr_core::{
-    domain::repositories::MovieRepository,
+    blocklist::BlocklistRepository,
+    domain::repositories::{IndexerRepository, MovieFileRepository, MovieRepository},
     events::{EventBus, SystemEvent},
-    models::{Movie, QueueItem, QueuePriority},
+    jobs::MovieSearcher,
+    models::{normalize_search_title, Movie, MovieFile, QueueItem, QueuePriority},
+    polling::{PollConfig, PollSchedule},
     progress::{OperationType, ProgressTracker},
     rss::{CalendarEntry, RssFeed, RssItem, RssMonitor, RssParser},
-    services::QueueRepository,
-    RadarrError, Result,
+    services::{QueueRepository, SearchCooldownPolicy},
+    RadarrError, Result, ShutdownCoordinator,
 };
-use radarr_decision::{DecisionEngine, Release};
+use radarr_decision::{DecisionEngine, ExistingFile, LanguageProfile, ProperHandling, Release};
 use radarr_indexers::{IndexerClient, SearchRequest};
 use radarr_infrastructure::DatabasePool;
+use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::RwLock;
-use tokio::time::{interval, Duration};
+use tokio::sync::{Mutex, RwLock};
+use tokio::task::JoinHandle;
+use tokio::time::Duration;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
@@ -30,6 +36,17 @@ pub struct RssServiceConfig {
     pub max_items_per_feed: usize,
     /// Whether RSS monitoring is enabled
     pub enabled: bool,
+    /// How often to search for missing movies (seconds)
+    pub missing_search_interval_seconds: u64,
+    /// How often to search for upgrades of cutoff unmet movies (seconds)
+    pub cutoff_search_interval_seconds: u64,
+    /// How long a movie's search results are reused, e.g. to replace a
+    /// failed or stalled download with the next-best release (seconds)
+    pub search_cache_ttl_seconds: u64,
+    /// Whether propers and repacks replace existing files of the same quality
+    pub proper_handling: ProperHandling,
+    /// Languages releases must be in, and which are preferred
+    pub language_profile: LanguageProfile,
 }
 
 impl Default for RssServiceConfig {
@@ -39,10 +56,22 @@ impl Default for RssServiceConfig {
             calendar_interval_seconds: 3600, // 1 hour
             max_items_per_feed: 100,
             enabled: true,
+            missing_search_interval_seconds: 6 * 3600, // 6 hours
+            cutoff_search_interval_seconds: 24 * 3600, // 24 hours
+            search_cache_ttl_seconds: 3600,            // 1 hour
+            proper_handling: ProperHandling::Prefer,
+            language_profile: LanguageProfile::default(),
         }
     }
 }
 
+/// Releases found by a movie search, reused until they expire
+struct CachedSearch {
+    searched_at: chrono::DateTime<Utc>,
+    excluded_indexer_ids: Vec<i32>,
+    releases: Vec<Release>,
+}
+
 /// RSS monitoring service
 pub struct RssService {
     config: RssServiceConfig,
@@ -55,6 +84,13 @@ pub struct RssService {
     movie_repository: Arc<dyn MovieRepository + Send + Sync>,
     queue_repository: Arc<dyn QueueRepository + Send + Sync>,
     decision_engine: Option<DecisionEngine>,
+    movie_file_repository: Option<Arc<dyn MovieFileRepository + Send + Sync>>,
+    search_cooldown: SearchCooldownPolicy,
+    blocklist_repository: Option<Arc<dyn BlocklistRepository>>,
+    indexer_repository: Option<Arc<dyn IndexerRepository>>,
+    search_cache: Mutex<HashMap<Uuid, CachedSearch>>,
+    task_handles: Mutex<Vec<JoinHandle<()>>>,
+    shutdown: ShutdownCoordinator,
 }
 
 impl RssService {
@@ -77,6 +113,13 @@ impl RssService {
             movie_repository,
             queue_repository,
             decision_engine: None,
+            movie_file_repository: None,
+            search_cooldown: SearchCooldownPolicy::default(),
+            blocklist_repository: None,
+            indexer_repository: None,
+            search_cache: Mutex::new(HashMap::new()),
+            task_handles: Mutex::new(Vec::new()),
+            shutdown: ShutdownCoordinator::new(),
         }
     }
 
@@ -98,6 +141,88 @@ impl RssService {
         self
     }
 
+    /// Set movie file repository so releases are only grabbed as upgrades
+    pub fn with_movie_file_repository(
+        mut self,
+        repository: Arc<dyn MovieFileRepository + Send + Sync>,
+    ) -> Self {
+        self.movie_file_repository = Some(repository);
+        self
+    }
+
+    /// Load the movie's current library file of each edition for upgrade
+    /// decisions
+    async fn existing_files(&self, movie_id: Uuid) -> Result<Vec<ExistingFile>> {
+        match &self.movie_file_repository {
+            Some(repository) => {
+                let files = repository.list(Some(movie_id)).await?;
+                Ok(MovieFile::current_per_edition(files)
+                    .iter()
+                    .map(ExistingFile::from_movie_file)
+                    .collect())
+            }
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Set the per-movie cooldown applied between automatic searches
+    pub fn with_search_cooldown(mut self, policy: SearchCooldownPolicy) -> Self {
+        self.search_cooldown = policy;
+        self
+    }
+
+    /// Skip releases blocklisted for a movie when searching for it
+    pub fn with_blocklist_repository(mut self, repository: Arc<dyn BlocklistRepository>) -> Self {
+        self.blocklist_repository = Some(repository);
+        self
+    }
+
+    /// Skip indexers restricted to tags a movie doesn't have
+    pub fn with_indexer_repository(mut self, repository: Arc<dyn IndexerRepository>) -> Self {
+        self.indexer_repository = Some(repository);
+        self
+    }
+
+    /// Register monitoring tasks with `shutdown`, which stops them between checks
+    pub fn with_shutdown(mut self, shutdown: ShutdownCoordinator) -> Self {
+        self.shutdown = shutdown;
+        self
+    }
+
+    /// Indexers never searched for a movie: the ones it excludes and those
+    /// restricted to other tags
+    async fn excluded_indexers(&self, movie: &Movie) -> Vec<i32> {
+        let indexers = match &self.indexer_repository {
+            Some(repository) => match repository.list().await {
+                Ok(indexers) => indexers,
+                Err(e) => {
+                    warn!("Failed to load indexer tags: {}", e);
+                    Vec::new()
+                }
+            },
+            None => Vec::new(),
+        };
+        movie.excluded_indexers(&indexers)
+    }
+
+    /// Titles of the releases currently blocklisted for a movie
+    async fn blocklisted_titles(&self, movie_id: Uuid) -> Vec<String> {
+        let Some(repository) = &self.blocklist_repository else {
+            return Vec::new();
+        };
+        match repository.get_entries_for_movie(movie_id).await {
+            Ok(entries) => entries
+                .into_iter()
+                .filter(|entry| !entry.is_expired())
+                .map(|entry| entry.release_title)
+                .collect(),
+            Err(e) => {
+                warn!("Blocklist unavailable, skipping blocklist checks: {}", e);
+                Vec::new()
+            }
+        }
+    }
+
     /// Start the RSS monitoring service
     pub async fn start(self: Arc<Self>) -> Result<()> {
         if !self.config.enabled {
@@ -107,89 +232,168 @@ impl RssService {
 
         info!("Starting RSS monitoring service");
 
-        // Spawn RSS feed checker
-        let rss_service = self.clone();
-        tokio::spawn(async move {
-            rss_service.run_rss_monitor().await;
-        });
-
-        // Spawn calendar checker
+        // Spawn calendar checker; feeds are checked by the RssSync scheduled task
         let calendar_service = self.clone();
-        tokio::spawn(async move {
+        let calendar_handle = self.shutdown.spawn("rss calendar monitor", async move {
             calendar_service.run_calendar_monitor().await;
         });
 
+        let mut task_handles = self.task_handles.lock().await;
+        task_handles.push(calendar_handle);
+
         Ok(())
     }
 
-    /// Run RSS feed monitoring loop
-    async fn run_rss_monitor(&self) {
-        let mut check_interval = interval(Duration::from_secs(self.config.check_interval_seconds));
+    /// Whether RSS monitoring is enabled in configuration
+    pub fn is_enabled(&self) -> bool {
+        self.config.enabled
+    }
 
-        loop {
-            check_interval.tick().await;
+    /// Check whether all background monitoring tasks are still running
+    pub async fn is_running(&self) -> bool {
+        let task_handles = self.task_handles.lock().await;
+        !task_handles.is_empty() && task_handles.iter().all(|handle| !handle.is_finished())
+    }
 
-            let due_feeds = {
-                let monitor = self.monitor.read().await;
-                monitor
-                    .get_due_feeds()
-                    .into_iter()
-                    .cloned()
-                    .collect::<Vec<_>>()
-            };
+    /// Abort any running monitoring tasks and start them again
+    pub async fn restart(self: Arc<Self>) -> Result<()> {
+        {
+            let mut task_handles = self.task_handles.lock().await;
+            for handle in task_handles.drain(..) {
+                handle.abort();
+            }
+        }
+
+        warn!("Restarting RSS monitoring service tasks");
+        self.start().await
+    }
+
+    /// How often the RssSync task should check feeds
+    pub fn check_interval(&self) -> Duration {
+        Duration::from_secs(self.config.check_interval_seconds)
+    }
+
+    /// Interval of the MissingMoviesSearch scheduled task
+    pub fn missing_search_interval(&self) -> Duration {
+        Duration::from_secs(self.config.missing_search_interval_seconds)
+    }
+
+    /// Interval of the CutoffUnmetSearch scheduled task
+    pub fn cutoff_search_interval(&self) -> Duration {
+        Duration::from_secs(self.config.cutoff_search_interval_seconds)
+    }
+
+    /// Cooldown between automatic searches of the same movie, shared with
+    /// the scheduled missing and cutoff unmet searches
+    pub fn search_cooldown(&self) -> &SearchCooldownPolicy {
+        &self.search_cooldown
+    }
+
+    /// Check the feeds that are due, as the RssSync scheduled task does
+    ///
+    /// Returns the number of new items processed. Fails only when every due
+    /// feed failed (e.g. network down).
+    pub async fn sync_due_feeds(&self) -> Result<usize> {
+        let due_feeds = {
+            let monitor = self.monitor.read().await;
+            monitor
+                .get_due_feeds()
+                .into_iter()
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+        let due_count = due_feeds.len();
+
+        let (new_count, failed_count) = self.check_feeds(due_feeds).await;
+        if due_count > 0 && failed_count == due_count {
+            return Err(RadarrError::ExternalServiceError {
+                service: "rss".to_string(),
+                error: format!("All {} due RSS feeds failed", due_count),
+            });
+        }
+        Ok(new_count)
+    }
+
+    /// Check `feeds`, returning the number of new items and of failed feeds
+    async fn check_feeds(&self, feeds: Vec<RssFeed>) -> (usize, usize) {
+        let mut new_count = 0;
+        let mut failed_count = 0;
+
+        for feed in feeds {
+            let feed_id = feed.id;
+            let feed_name = feed.name.clone();
 
-            for feed in due_feeds {
-                let feed_id = feed.id;
-                let feed_name = feed.name.clone();
+            debug!("Checking RSS feed: {}", feed_name);
 
-                debug!("Checking RSS feed: {}", feed_name);
+            // Start progress tracking
+            let progress_id = if let Some(tracker) = &self.progress_tracker {
+                Some(
+                    tracker
+                        .start_operation(
+                            OperationType::IndexerSearch,
+                            format!("Checking RSS: {}", feed_name),
+                        )
+                        .await,
+                )
+            } else {
+                None
+            };
 
-                // Start progress tracking
-                let progress_id = if let Some(tracker) = &self.progress_tracker {
-                    Some(
+            // Check the feed
+            match self.check_feed(&feed).await {
+                Ok(new_items) => {
+                    new_count += new_items;
+                    info!("Found {} new items in feed {}", new_items, feed_name);
+
+                    // Complete progress
+                    if let (Some(tracker), Some(id)) = (&self.progress_tracker, progress_id) {
                         tracker
-                            .start_operation(
-                                OperationType::IndexerSearch,
-                                format!("Checking RSS: {}", feed_name),
-                            )
-                            .await,
-                    )
-                } else {
-                    None
-                };
-
-                // Check the feed
-                match self.check_feed(&feed).await {
-                    Ok(new_items) => {
-                        info!("Found {} new items in feed {}", new_items, feed_name);
-
-                        // Complete progress
-                        if let (Some(tracker), Some(id)) = (&self.progress_tracker, progress_id) {
-                            tracker
-                                .complete_operation(
-                                    id,
-                                    format!("Processed {} new items", new_items),
-                                )
-                                .await;
-                        }
+                            .complete_operation(id, format!("Processed {} new items", new_items))
+                            .await;
                     }
-                    Err(e) => {
-                        error!("Failed to check RSS feed {}: {}", feed_name, e);
+                }
+                Err(e) => {
+                    failed_count += 1;
+                    error!("Failed to check RSS feed {}: {}", feed_name, e);
 
-                        // Fail progress
-                        if let (Some(tracker), Some(id)) = (&self.progress_tracker, progress_id) {
-                            tracker.fail_operation(id, e.to_string()).await;
-                        }
+                    // Fail progress
+                    if let (Some(tracker), Some(id)) = (&self.progress_tracker, progress_id) {
+                        tracker.fail_operation(id, e.to_string()).await;
                     }
                 }
+            }
 
-                // Mark feed as checked
-                {
-                    let mut monitor = self.monitor.write().await;
-                    monitor.mark_feed_checked(feed_id);
-                }
+            // Mark feed as checked
+            {
+                let mut monitor = self.monitor.write().await;
+                monitor.mark_feed_checked(feed_id);
             }
         }
+
+        (new_count, failed_count)
+    }
+
+    /// Check every enabled feed now, regardless of when it is next due
+    ///
+    /// Returns the number of new items processed. Fails only when every
+    /// feed failed.
+    pub async fn sync_all_feeds(&self) -> Result<usize> {
+        let feeds: Vec<RssFeed> = self
+            .get_feeds()
+            .await
+            .into_iter()
+            .filter(|feed| feed.enabled)
+            .collect();
+        let feed_count = feeds.len();
+
+        let (new_count, failed_count) = self.check_feeds(feeds).await;
+        if feed_count > 0 && failed_count == feed_count {
+            return Err(RadarrError::ExternalServiceError {
+                service: "rss".to_string(),
+                error: format!("All {} RSS feeds failed", feed_count),
+            });
+        }
+        Ok(new_count)
     }
 
     /// Check a single RSS feed
@@ -255,13 +459,17 @@ impl RssService {
             .await
         {
             Ok(movies) => {
-                // Find best match based on title similarity
-                for movie in movies {
-                    if self.titles_match(&movie.title, &cleaned_title) {
-                        return Ok(Some(movie));
-                    }
-                }
-                Ok(None)
+                // Closest matches come first, exact title or alternative
+                // title matches before fuzzy ones, so a foreign-title release
+                // finds the movie stored under its English title; take the
+                // first one whose title, original title or an alternative
+                // title matches
+                Ok(movies.into_iter().find(|movie| {
+                    movie
+                        .search_titles()
+                        .into_iter()
+                        .any(|title| self.titles_match(title, &cleaned_title))
+                }))
             }
             Err(e) => {
                 error!(
@@ -313,10 +521,14 @@ impl RssService {
         }
     }
 
-    /// Check if two movie titles match (case-insensitive, flexible matching)
+    /// Check if two movie titles match (ignoring case, accents and
+    /// punctuation, flexible matching)
     fn titles_match(&self, movie_title: &str, extracted_title: &str) -> bool {
-        let movie_lower = movie_title.to_lowercase();
-        let extracted_lower = extracted_title.to_lowercase();
+        let movie_lower = normalize_search_title(movie_title);
+        let extracted_lower = normalize_search_title(extracted_title);
+        if movie_lower.is_empty() || extracted_lower.is_empty() {
+            return false;
+        }
 
         // Exact match
         if movie_lower == extracted_lower {
@@ -373,7 +585,8 @@ impl RssService {
         };
 
         // Parse release information from the title
-        let release = Release::from_title(item.title.clone(), item.url.clone());
+        let release = Release::from_title(item.title.clone(), item.url.clone())
+            .with_publish_date(item.pub_date);
 
         // Check if the release meets quality requirements
         if decision_engine.evaluate_release(&release).is_none() {
@@ -428,10 +641,7 @@ impl RssService {
             release = release.with_leechers(leechers);
         }
 
-        // Calculate age in hours
-        let age = Utc::now() - item.pub_date;
-        let age_hours = age.num_hours().max(0) as u32;
-        release = release.with_age_hours(age_hours);
+        release = release.with_publish_date(item.pub_date);
 
         // Find the matching movie
         let movie = match self.find_matching_movie(&item.title).await? {
@@ -442,13 +652,13 @@ impl RssService {
             }
         };
 
-        // Verify quality requirements one more time with decision engine
+        // Verify the release is acceptable, and an upgrade over any existing
+        // file of its edition
         if let Some(decision_engine) = &self.decision_engine {
-            if decision_engine.evaluate_release(&release).is_none() {
-                warn!(
-                    "RSS item '{}' failed quality check during processing",
-                    item.title
-                );
+            let existing = self.existing_files(movie.id).await?;
+            let existing = ExistingFile::for_release(&existing, &release);
+            if let Err(reason) = decision_engine.evaluate_upgrade(&release, existing) {
+                info!("RSS item '{}' rejected: {}", item.title, reason);
                 return Ok(());
             }
         }
@@ -505,26 +715,50 @@ impl RssService {
 
     /// Run calendar monitoring loop
     async fn run_calendar_monitor(&self) {
-        let mut check_interval =
-            interval(Duration::from_secs(self.config.calendar_interval_seconds));
+        let schedule = PollSchedule::new(PollConfig::new(Duration::from_secs(
+            self.config.calendar_interval_seconds,
+        )));
 
         loop {
-            check_interval.tick().await;
+            {
+                let monitor = self.monitor.read().await;
+                let searchable = monitor.get_searchable_entries();
 
-            let monitor = self.monitor.read().await;
-            let searchable = monitor.get_searchable_entries();
+                for entry in searchable {
+                    info!("Calendar trigger for movie: {}", entry.title);
 
-            for entry in searchable {
-                info!("Calendar trigger for movie: {}", entry.title);
+                    // Perform indexer search for calendar-triggered movie
+                    self.search_movie(entry).await;
+                }
+            }
 
-                // Perform indexer search for calendar-triggered movie
-                self.search_movie(entry).await;
+            tokio::select! {
+                _ = self.shutdown.cancelled() => break,
+                _ = schedule.wait() => {}
             }
         }
     }
 
     /// Search for a movie based on calendar entry
     async fn search_movie(&self, entry: &CalendarEntry) {
+        // Respect the per-movie cooldown so repeatedly unfound movies don't hammer indexers
+        let excluded_indexer_ids = match self.movie_repository.find_by_id(entry.movie_id).await {
+            Ok(Some(movie)) if !self.search_cooldown.is_due(&movie, Utc::now()) => {
+                debug!(
+                    "Skipping search for '{}', next search at {:?}",
+                    entry.title,
+                    self.search_cooldown.next_search_time(&movie)
+                );
+                return;
+            }
+            Ok(Some(movie)) => self.excluded_indexers(&movie).await,
+            Ok(None) => Vec::new(),
+            Err(e) => {
+                warn!("Failed to load search history for '{}': {}", entry.title, e);
+                Vec::new()
+            }
+        };
+
         debug!("Searching for movie: {}", entry.title);
 
         // Start progress tracking
@@ -541,7 +775,23 @@ impl RssService {
             None
         };
 
-        let search_result = self.perform_movie_search(entry).await;
+        let search_result = self
+            .perform_movie_search(entry.movie_id, &entry.title, excluded_indexer_ids)
+            .await;
+
+        // Indexer errors don't count against the movie's cooldown
+        if let Ok(found) = search_result.as_ref().map(|r| r.is_some()) {
+            if let Err(e) = self
+                .movie_repository
+                .record_search_result(entry.movie_id, found)
+                .await
+            {
+                warn!(
+                    "Failed to record search result for '{}': {}",
+                    entry.title, e
+                );
+            }
+        }
 
         // Complete or fail progress based on result
         if let (Some(tracker), Some(id)) = (&self.progress_tracker, progress_id) {
@@ -563,42 +813,113 @@ impl RssService {
         }
     }
 
+    /// Releases of the movie's last search while they are fresh, so a
+    /// failed or stalled download is replaced without asking the indexers
+    /// again
+    async fn cached_releases(
+        &self,
+        movie_id: Uuid,
+        excluded_indexer_ids: &[i32],
+    ) -> Option<Vec<Release>> {
+        let ttl = chrono::Duration::seconds(self.config.search_cache_ttl_seconds as i64);
+        let mut cache = self.search_cache.lock().await;
+        cache.retain(|_, search| Utc::now() - search.searched_at < ttl);
+        cache
+            .get(&movie_id)
+            .filter(|search| search.excluded_indexer_ids == excluded_indexer_ids)
+            .map(|search| search.releases.clone())
+    }
+
     /// Perform the actual movie search and queue the best match
-    async fn perform_movie_search(&self, entry: &CalendarEntry) -> Result<Option<String>> {
+    async fn perform_movie_search(
+        &self,
+        movie_id: Uuid,
+        title: &str,
+        excluded_indexer_ids: Vec<i32>,
+    ) -> Result<Option<String>> {
+        let releases = match self.cached_releases(movie_id, &excluded_indexer_ids).await {
+            Some(releases) => {
+                info!(
+                    "Using {} cached search results for movie: {}",
+                    releases.len(),
+                    title
+                );
+                releases
+            }
+            None => {
+                let releases = self
+                    .search_releases(title, excluded_indexer_ids.clone())
+                    .await?;
+                if releases.is_empty() {
+                    return Ok(None);
+                }
+                self.search_cache.lock().await.insert(
+                    movie_id,
+                    CachedSearch {
+                        searched_at: Utc::now(),
+                        excluded_indexer_ids,
+                        releases: releases.clone(),
+                    },
+                );
+                releases
+            }
+        };
+
+        // Pass over releases that already failed for this movie
+        let blocklisted = self.blocklisted_titles(movie_id).await;
+        let releases: Vec<Release> = releases
+            .into_iter()
+            .filter(|release| {
+                let blocked = blocklisted.contains(&release.title);
+                if blocked {
+                    debug!("Skipping blocklisted release: {}", release.title);
+                }
+                !blocked
+            })
+            .collect();
+
+        self.grab_best_release(movie_id, title, releases).await
+    }
+
+    /// Search the indexers for a movie
+    async fn search_releases(
+        &self,
+        title: &str,
+        excluded_indexer_ids: Vec<i32>,
+    ) -> Result<Vec<Release>> {
         // 1. Build search query using the movie title
-        let search_request = SearchRequest::for_movie_title(&entry.title)
+        let search_request = SearchRequest::for_movie_title(title)
             .with_limit(50) // Get up to 50 results to choose from
-            .with_min_seeders(1); // Require at least 1 seeder
+            .with_min_seeders(1) // Require at least 1 seeder
+            .with_excluded_indexers(excluded_indexer_ids);
 
         // 2. Search indexers
         let search_response = match self.indexer_client.search(&search_request).await {
             Ok(response) => response,
             Err(e) => {
-                error!(
-                    "Failed to search indexers for movie '{}': {}",
-                    entry.title, e
-                );
+                error!("Failed to search indexers for movie '{}': {}", title, e);
                 return Err(e.into());
             }
         };
 
         if search_response.results.is_empty() {
-            info!("No search results found for movie: {}", entry.title);
-            return Ok(None);
+            info!("No search results found for movie: {}", title);
+            return Ok(Vec::new());
         }
 
         info!(
             "Found {} search results for movie: {}",
             search_response.results.len(),
-            entry.title
+            title
         );
 
         // 3. Convert search results to Release objects for evaluation
-        let releases: Vec<Release> = search_response
+        let releases = search_response
             .results
             .into_iter()
             .map(|result| {
-                let mut release = Release::from_title(result.title.clone(), result.download_url);
+                let mut release = Release::from_title(result.title.clone(), result.download_url)
+                    .with_indexer_id(result.indexer_id);
 
                 // Set additional properties from search result
                 if let Some(size) = result.size {
@@ -617,9 +938,7 @@ impl RssService {
                     }
                 }
                 if let Some(publish_date) = result.publish_date {
-                    let age = Utc::now() - publish_date;
-                    let age_hours = age.num_hours().max(0) as u32;
-                    release = release.with_age_hours(age_hours);
+                    release = release.with_publish_date(publish_date);
                 }
                 if result.freeleech == Some(true) {
                     release = release.with_freeleech(true);
@@ -628,15 +947,25 @@ impl RssService {
                 release
             })
             .collect();
+        Ok(releases)
+    }
 
-        // 4. Evaluate results using decision engine if available
+    /// Pick the best of `releases` and queue it
+    async fn grab_best_release(
+        &self,
+        movie_id: Uuid,
+        title: &str,
+        releases: Vec<Release>,
+    ) -> Result<Option<String>> {
+        // Evaluate results using decision engine if available
         let best_release = if let Some(decision_engine) = &self.decision_engine {
-            match decision_engine.select_best_release(releases) {
+            let existing = self.existing_files(movie_id).await?;
+            match decision_engine.select_best_upgrade(releases, &existing) {
                 Some(release) => release,
                 None => {
                     info!(
-                        "No releases met quality requirements for movie: {}",
-                        entry.title
+                        "No releases met quality or upgrade requirements for movie: {}",
+                        title
                     );
                     return Ok(None);
                 }
@@ -655,21 +984,26 @@ impl RssService {
 
         info!(
             "Selected release for movie '{}': {}",
-            entry.title, best_release.title
+            title, best_release.title
         );
 
-        // 5. Queue the best match
-        self.queue_movie_release(entry, &best_release)
+        // Queue the best match
+        self.queue_movie_release(movie_id, title, &best_release)
             .await
             .map(|_| Some(best_release.title))
     }
 
     /// Queue a movie release for download
-    async fn queue_movie_release(&self, entry: &CalendarEntry, release: &Release) -> Result<()> {
+    async fn queue_movie_release(
+        &self,
+        movie_id: Uuid,
+        title: &str,
+        release: &Release,
+    ) -> Result<()> {
         // Create queue item
         let mut queue_item = QueueItem::new(
-            entry.movie_id,
-            Uuid::new_v4(), // Release ID (generated for calendar searches)
+            movie_id,
+            Uuid::new_v4(), // Release ID (generated for searches)
             release.title.clone(),
             release.download_url.clone(),
         );
@@ -678,8 +1012,9 @@ impl RssService {
         if let Some(size) = release.size {
             queue_item.size_bytes = Some(size as i64);
         }
+        queue_item.indexer_id = release.indexer_id;
 
-        // Set priority for calendar-triggered searches
+        // Set priority for searched releases
         queue_item.priority = QueuePriority::High;
 
         // Add to download queue
@@ -688,22 +1023,22 @@ impl RssService {
             .await
             .map_err(|e| {
                 error!(
-                    "Failed to queue calendar release '{}' for movie '{}': {}",
-                    release.title, entry.title, e
+                    "Failed to queue release '{}' for movie '{}': {}",
+                    release.title, title, e
                 );
                 e
             })?;
 
         info!(
-            "Successfully queued calendar release '{}' for movie '{}'",
-            release.title, entry.title
+            "Successfully queued release '{}' for movie '{}'",
+            release.title, title
         );
 
         // Emit event
         if let Some(bus) = &self.event_bus {
             let _ = bus
                 .publish(SystemEvent::DownloadQueued {
-                    movie_id: entry.movie_id,
+                    movie_id,
                     release_id: queue_item.release_id,
                     download_url: release.download_url.clone(),
                     title: release.title.clone(),
@@ -774,3 +1109,14 @@ impl RssService {
         RssParser::parse_feed(&content)
     }
 }
+
+/// Searches for missing movies with the same indexers, decision engine and
+/// queue as calendar searches
+#[async_trait]
+impl MovieSearcher for RssService {
+    async fn search_and_grab(&self, movie: &Movie) -> Result<Option<String>> {
+        let excluded_indexer_ids = self.excluded_indexers(movie).await;
+        self.perform_movie_search(movie.id, &movie.title, excluded_indexer_ids)
+            .await
+    }
+}
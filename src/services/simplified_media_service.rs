@@ -86,6 +86,11 @@ impl SimplifiedMediaService {
         }
     }
 
+    /// Get the download client (used by automated recovery)
+    pub fn download_client(&self) -> Arc<QBittorrentClient> {
+        self.download_client.clone()
+    }
+
     /// Test downloader connectivity (used by health checks)
     pub async fn test_downloader_connectivity(&self) -> Result<()> {
         debug!("Testing downloader connectivity");
@@ -6,11 +6,20 @@
 
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use radarr_core::domain::repositories::MovieRepository;
-use radarr_core::{EventBus, EventEnvelope, EventHandler, RadarrError, Result, SystemEvent};
-use radarr_import::ImportPipeline;
-use radarr_infrastructure::{repositories::movie::PostgresMovieRepository, DatabasePool};
+use radarr_core::domain::repositories::{
+    AuditRepository, HistoryRepository, MovieFileRepository, MovieRepository,
+};
+use radarr_core::{
+    media_relative_path, media_root, resolve_media_path, same_edition, AuditRecord, EventBus,
+    EventEnvelope, EventHandler, HistoryRecord, Movie, MovieFile, RadarrError, Result, SystemEvent,
+};
+use radarr_import::{ImportPipeline, ImportResult};
+use radarr_infrastructure::{
+    repositories::{movie::PostgresMovieRepository, movie_file::PostgresMovieFileRepository},
+    DatabasePool,
+};
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{debug, error, info, instrument, warn};
@@ -460,6 +469,7 @@ pub struct DownloadImportHandler {
     import_pipeline: Arc<ImportPipeline>,
     database_pool: DatabasePool,
     movie_repository: Arc<PostgresMovieRepository>,
+    movie_file_repository: Arc<PostgresMovieFileRepository>,
     event_bus: Arc<EventBus>,
 }
 
@@ -470,13 +480,98 @@ impl DownloadImportHandler {
         event_bus: Arc<EventBus>,
     ) -> Self {
         let movie_repository = Arc::new(PostgresMovieRepository::new(database_pool.clone()));
+        let movie_file_repository =
+            Arc::new(PostgresMovieFileRepository::new(database_pool.clone()));
         Self {
             import_pipeline,
             database_pool,
             movie_repository,
+            movie_file_repository,
             event_bus,
         }
     }
+
+    /// Record the imported file and attach it to the movie
+    ///
+    /// The file's modification time, when the import changed it, is kept as
+    /// `last_write_time`. The imported file is an upgrade, e.g. a proper of
+    /// the same quality, so it replaces the current file of its edition.
+    async fn record_movie_file(&self, movie: &Movie, import_result: &ImportResult) -> Result<()> {
+        let Some(hardlink_result) = &import_result.hardlink_result else {
+            return Ok(());
+        };
+
+        let media_root = media_root();
+        let relative_path = media_relative_path(&media_root, &hardlink_result.destination)
+            .unwrap_or_else(|| hardlink_result.destination.to_string_lossy().to_string());
+        let quality = import_result
+            .analyzed_file
+            .as_ref()
+            .map(|analyzed| serde_json::to_value(&analyzed.quality))
+            .transpose()?
+            .unwrap_or_else(|| serde_json::json!({}));
+
+        let mut movie_file = MovieFile::new(
+            movie.id,
+            relative_path,
+            hardlink_result.file_size as i64,
+            quality,
+        );
+        movie_file.edition = import_result
+            .analyzed_file
+            .as_ref()
+            .and_then(|analyzed| analyzed.edition.clone());
+        movie_file.last_write_time = hardlink_result.file_date;
+        let files = self.movie_file_repository.list(Some(movie.id)).await?;
+        let replaced = MovieFile::current_per_edition(files)
+            .into_iter()
+            .find(|file| same_edition(file.edition.as_deref(), movie_file.edition.as_deref()));
+        let movie_file = self.movie_file_repository.create(&movie_file).await?;
+        if let Some(replaced) = replaced {
+            self.remove_replaced_file(&replaced, &movie_file, &media_root)
+                .await?;
+        }
+
+        let mut movie = movie.clone();
+        movie.set_has_file(movie_file.id);
+        self.movie_repository.update(&movie).await?;
+
+        debug!(
+            "Recorded movie file {} for movie {}",
+            movie_file.relative_path, movie.id
+        );
+        Ok(())
+    }
+
+    /// Drop the record of a replaced file and delete it from disk
+    ///
+    /// A file imported under the same name already overwrote it.
+    async fn remove_replaced_file(
+        &self,
+        replaced: &MovieFile,
+        movie_file: &MovieFile,
+        media_root: &Path,
+    ) -> Result<()> {
+        self.movie_file_repository.delete(replaced.id).await?;
+        if replaced.relative_path != movie_file.relative_path {
+            match resolve_media_path(media_root, &replaced.relative_path) {
+                Some(path) => {
+                    if let Err(e) = tokio::fs::remove_file(&path).await {
+                        warn!("Failed to delete replaced file {}: {}", path.display(), e);
+                    }
+                }
+                None => warn!(
+                    "Replaced file {} is outside the media root, leaving it on disk",
+                    replaced.relative_path
+                ),
+            }
+        }
+        info!(
+            "Replaced movie file {} with {}",
+            replaced.relative_path, movie_file.relative_path
+        );
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -521,11 +616,22 @@ impl EventHandler for DownloadImportHandler {
                     .parent()
                     .unwrap_or_else(|| Path::new("/downloads"));
 
-                match self
-                    .import_pipeline
-                    .import_file(source_path, dest_dir)
-                    .await
-                {
+                // The movie's title, IDs and release dates name and date the
+                // imported file
+                let import = match &movie_info {
+                    Some(movie) => {
+                        self.import_pipeline
+                            .import_movie_file(source_path, dest_dir, movie)
+                            .await
+                    }
+                    None => {
+                        self.import_pipeline
+                            .import_file(source_path, dest_dir)
+                            .await
+                    }
+                };
+
+                match import {
                     Ok(import_result) => {
                         info!(
                             "Import triggered successfully for {}: success={}",
@@ -533,6 +639,16 @@ impl EventHandler for DownloadImportHandler {
                         );
 
                         if import_result.success {
+                            if let Some(movie) = &movie_info {
+                                if let Err(e) = self.record_movie_file(movie, &import_result).await
+                                {
+                                    error!(
+                                        "Failed to record imported file for movie {}: {}",
+                                        movie_id, e
+                                    );
+                                }
+                            }
+
                             // Publish ImportComplete event
                             let destination_path = import_result
                                 .hardlink_result
@@ -612,6 +728,80 @@ impl EventHandler for DownloadImportHandler {
     }
 }
 
+/// Handler that persists grabs, imports, failures and deletions as history
+pub struct HistoryEventHandler {
+    history_repository: Arc<dyn HistoryRepository>,
+}
+
+impl HistoryEventHandler {
+    pub fn new(history_repository: Arc<dyn HistoryRepository>) -> Self {
+        Self { history_repository }
+    }
+}
+
+#[async_trait]
+impl EventHandler for HistoryEventHandler {
+    async fn handle_event(&self, envelope: &EventEnvelope) -> Result<()> {
+        let Some(record) = HistoryRecord::from_event(envelope) else {
+            return Ok(());
+        };
+
+        debug!(
+            "Recording {} history for movie {:?}",
+            record.event_type, record.movie_id
+        );
+        self.history_repository.create(&record).await
+    }
+
+    fn should_handle(&self, envelope: &EventEnvelope) -> bool {
+        matches!(
+            envelope.event,
+            SystemEvent::DownloadQueued { .. }
+                | SystemEvent::OverrideGrab { .. }
+                | SystemEvent::DownloadFailed { .. }
+                | SystemEvent::ImportComplete { .. }
+                | SystemEvent::ImportFailed { .. }
+                | SystemEvent::MovieDeleted { .. }
+                | SystemEvent::MovieFileDeleted { .. }
+                | SystemEvent::SystemHealth { .. }
+        )
+    }
+}
+
+/// Handler that persists destructive operations to the audit log
+pub struct AuditEventHandler {
+    audit_repository: Arc<dyn AuditRepository>,
+}
+
+impl AuditEventHandler {
+    pub fn new(audit_repository: Arc<dyn AuditRepository>) -> Self {
+        Self { audit_repository }
+    }
+}
+
+#[async_trait]
+impl EventHandler for AuditEventHandler {
+    async fn handle_event(&self, envelope: &EventEnvelope) -> Result<()> {
+        let Some(record) = AuditRecord::from_event(envelope) else {
+            return Ok(());
+        };
+
+        info!(
+            target: "audit",
+            action = %record.action,
+            actor = %record.actor.name,
+            api_key = ?record.actor.api_key_id,
+            target_resource = %record.target,
+            "Destructive operation performed"
+        );
+        self.audit_repository.create(&record).await
+    }
+
+    fn should_handle(&self, envelope: &EventEnvelope) -> bool {
+        matches!(envelope.event, SystemEvent::DestructiveOperation { .. })
+    }
+}
+
 /// Handler that logs all events for debugging
 pub struct LoggingEventHandler;
 
@@ -0,0 +1,117 @@
+//! Server-sent events stream of system events
+//!
+//! `/api/v3/events` streams the same messages as the WebSocket for clients
+//! behind proxies that make WebSockets painful. Each SSE event carries the
+//! event ID as its `id`, so a reconnecting `EventSource` sends it back in
+//! `Last-Event-ID` and first receives the events it missed, as far as the
+//! event bus still keeps them. The `lastEventId` query parameter does the
+//! same for clients that cannot set headers.
+
+use crate::websocket::{push_response, WsResponse, WsState};
+use axum::{
+    extract::{Extension, Query},
+    http::{HeaderMap, HeaderValue},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
+};
+use futures::stream::{self, StreamExt};
+use radarr_core::{correlation::CorrelationId, events::EventEnvelope};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::convert::Infallible;
+use std::sync::Arc;
+use tracing::{debug, info};
+use uuid::Uuid;
+
+/// Query parameters of the event stream
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SseQuery {
+    /// Resume after this event, like the `Last-Event-ID` header
+    pub last_event_id: Option<String>,
+}
+
+/// Data of one SSE event: the pushed message plus its envelope metadata
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SseData<'a> {
+    event_id: Uuid,
+    correlation_id: CorrelationId,
+    timestamp: chrono::DateTime<chrono::Utc>,
+    source: Option<&'a str>,
+    #[serde(flatten)]
+    message: WsResponse,
+}
+
+/// GET /api/v3/events - Stream system events as server-sent events
+pub async fn events_handler(
+    headers: HeaderMap,
+    Query(params): Query<SseQuery>,
+    Extension(state): Extension<Arc<WsState>>,
+) -> Response {
+    let last_event_id = headers
+        .get("Last-Event-ID")
+        .and_then(|value| value.to_str().ok())
+        .or(params.last_event_id.as_deref())
+        .and_then(|id| Uuid::parse_str(id.trim()).ok());
+
+    // Subscribe before reading the replay buffer so nothing published in
+    // between is lost; events seen in both are only sent once
+    let subscriber = state.event_bus.subscribe();
+    let replay = state.event_bus.replay_after(last_event_id);
+    let replayed: HashSet<Uuid> = replay.iter().map(|envelope| envelope.event_id).collect();
+    info!(
+        "SSE client connected (resuming after {:?}, {} events replayed)",
+        last_event_id,
+        replay.len()
+    );
+
+    let live = stream::unfold(subscriber, |mut subscriber| async move {
+        match subscriber.recv().await {
+            Ok(envelope) => Some((envelope, subscriber)),
+            Err(e) => {
+                debug!("SSE stream ending: {}", e);
+                None
+            }
+        }
+    })
+    .filter(move |envelope| std::future::ready(!replayed.contains(&envelope.event_id)));
+
+    let events = stream::iter(replay)
+        .chain(live)
+        .filter_map(|envelope| std::future::ready(to_sse_event(&envelope)))
+        .map(Ok::<_, Infallible>);
+
+    let mut response = Sse::new(events)
+        .keep_alive(KeepAlive::default())
+        .into_response();
+    // Keep nginx from buffering the stream
+    response
+        .headers_mut()
+        .insert("X-Accel-Buffering", HeaderValue::from_static("no"));
+    response
+}
+
+/// SSE event for an envelope, identified by its event ID
+fn to_sse_event(envelope: &EventEnvelope) -> Option<Event> {
+    let data = SseData {
+        event_id: envelope.event_id,
+        correlation_id: envelope.correlation_id,
+        timestamp: envelope.timestamp,
+        source: envelope.source.as_deref(),
+        message: push_response(&envelope.event),
+    };
+
+    match Event::default()
+        .id(envelope.event_id.to_string())
+        .json_data(&data)
+    {
+        Ok(event) => Some(event),
+        Err(e) => {
+            debug!("Failed to serialize event {}: {}", envelope.event_id, e);
+            None
+        }
+    }
+}
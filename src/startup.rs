@@ -0,0 +1,314 @@
+//! Startup phase tracking and the readiness endpoint
+//!
+//! The HTTP listener is bound before the slow parts of startup (migrations,
+//! service initialization) so orchestrators can probe it right away:
+//! - `/health` is liveness and answers 200 as soon as the process listens
+//! - `/ready` answers 503 until every phase has finished, the database pool
+//!   responds and the event processor is running, then 200
+//!
+//! Every other route answers 503 until the application router is installed.
+//! Phase timings are included in both `/ready` responses.
+
+use crate::services::AppServices;
+use axum::{
+    body::Body,
+    extract::State,
+    http::{Request, StatusCode},
+    response::{IntoResponse, Json, Response},
+    routing::get,
+    Router,
+};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::json;
+use std::future::Future;
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, OnceLock, RwLock};
+use std::time::{Duration, Instant};
+use tower::ServiceExt;
+use tracing::info;
+
+/// Time allowed for the readiness database probe
+const DATABASE_PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Phases of application startup, in order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StartupPhase {
+    Configuration,
+    Migrations,
+    Services,
+    Router,
+}
+
+impl StartupPhase {
+    pub const ALL: [StartupPhase; 4] = [
+        StartupPhase::Configuration,
+        StartupPhase::Migrations,
+        StartupPhase::Services,
+        StartupPhase::Router,
+    ];
+}
+
+/// Timing of one startup phase
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PhaseTiming {
+    pub phase: StartupPhase,
+    pub started_at: DateTime<Utc>,
+    /// Set once the phase has finished
+    pub duration_ms: Option<u64>,
+    /// Error that ended the phase, if it failed
+    pub error: Option<String>,
+}
+
+/// Records startup phases and whether startup has finished
+pub struct StartupTracker {
+    started: Instant,
+    started_at: DateTime<Utc>,
+    phases: RwLock<Vec<PhaseTiming>>,
+    finished: OnceLock<Duration>,
+}
+
+impl StartupTracker {
+    pub fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            started_at: Utc::now(),
+            phases: RwLock::new(Vec::new()),
+            finished: OnceLock::new(),
+        }
+    }
+
+    /// Run `future` as `phase`, recording its duration and any error
+    pub async fn run<T, E, F>(&self, phase: StartupPhase, future: F) -> Result<T, E>
+    where
+        E: std::fmt::Display,
+        F: Future<Output = Result<T, E>>,
+    {
+        let index = {
+            let mut phases = self.phases.write().unwrap_or_else(|e| e.into_inner());
+            phases.push(PhaseTiming {
+                phase,
+                started_at: Utc::now(),
+                duration_ms: None,
+                error: None,
+            });
+            phases.len() - 1
+        };
+
+        let started = Instant::now();
+        let result = future.await;
+        let elapsed = started.elapsed();
+
+        let mut phases = self.phases.write().unwrap_or_else(|e| e.into_inner());
+        phases[index].duration_ms = Some(elapsed.as_millis() as u64);
+        if let Err(e) = &result {
+            phases[index].error = Some(e.to_string());
+        }
+        info!("Startup phase {:?} took {:?}", phase, elapsed);
+        result
+    }
+
+    /// Whether `phase` has finished without error
+    pub fn completed(&self, phase: StartupPhase) -> bool {
+        self.phases
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .any(|timing| {
+                timing.phase == phase && timing.duration_ms.is_some() && timing.error.is_none()
+            })
+    }
+
+    /// Whether a phase has failed
+    pub fn failed(&self) -> bool {
+        self.phases
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .any(|timing| timing.error.is_some())
+    }
+
+    /// Mark startup as finished
+    pub fn finish(&self) {
+        let elapsed = self.started.elapsed();
+        if self.finished.set(elapsed).is_ok() {
+            info!("Startup finished in {:?}", elapsed);
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished.get().is_some()
+    }
+
+    fn phases(&self) -> Vec<PhaseTiming> {
+        self.phases
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+}
+
+impl Default for StartupTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The application once startup has finished
+struct ReadyApp {
+    router: Router,
+    services: AppServices,
+}
+
+/// State of the router served while starting up
+#[derive(Clone)]
+pub struct StartupState {
+    tracker: Arc<StartupTracker>,
+    app: Arc<OnceLock<ReadyApp>>,
+}
+
+impl StartupState {
+    pub fn new(tracker: Arc<StartupTracker>) -> Self {
+        Self {
+            tracker,
+            app: Arc::new(OnceLock::new()),
+        }
+    }
+
+    /// Start routing requests to `router` and finish startup
+    pub fn install(&self, router: Router, services: AppServices) {
+        if self.app.set(ReadyApp { router, services }).is_ok() {
+            self.tracker.finish();
+        }
+    }
+}
+
+/// Router answering `/health` and `/ready` from the start and forwarding
+/// everything else to the application router once it is installed
+pub fn startup_router(state: StartupState) -> Router {
+    Router::new()
+        .route("/health", get(liveness))
+        .route("/ready", get(readiness))
+        .fallback(forward)
+        .with_state(state)
+}
+
+/// GET /health - the process is up
+async fn liveness() -> Json<serde_json::Value> {
+    Json(json!({
+        "status": "healthy",
+        "service": "radarr-mvp",
+        "timestamp": Utc::now().to_rfc3339()
+    }))
+}
+
+/// GET /ready - the instance can serve traffic
+async fn readiness(State(state): State<StartupState>) -> Response {
+    let tracker = &state.tracker;
+
+    let (database, event_processor) = match state.app.get() {
+        Some(app) => {
+            // Queried directly; test_database logs every call and probes are frequent
+            let probe = sqlx::query("SELECT 1").execute(&app.services.database_pool);
+            let database = matches!(
+                tokio::time::timeout(DATABASE_PROBE_TIMEOUT, probe).await,
+                Ok(Ok(_))
+            );
+            let event_processor = app.services.event_processor_running.load(Ordering::Relaxed);
+            (database, event_processor)
+        }
+        None => (false, false),
+    };
+
+    let checks = json!({
+        "migrations": tracker.completed(StartupPhase::Migrations),
+        "database": database,
+        "services": tracker.completed(StartupPhase::Services),
+        "eventProcessor": event_processor,
+        "router": tracker.completed(StartupPhase::Router),
+    });
+    let ready = tracker.is_finished()
+        && database
+        && event_processor
+        && StartupPhase::ALL
+            .into_iter()
+            .all(|phase| tracker.completed(phase));
+
+    let status = match (ready, tracker.failed()) {
+        (true, _) => "ready",
+        (false, true) => "failed",
+        (false, false) if tracker.is_finished() => "degraded",
+        (false, false) => "starting",
+    };
+
+    let body = Json(json!({
+        "status": status,
+        "checks": checks,
+        "startedAt": tracker.started_at.to_rfc3339(),
+        "startupDurationMs": tracker.finished.get().map(|d| d.as_millis() as u64),
+        "phases": tracker.phases(),
+    }));
+
+    let code = if ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (code, body).into_response()
+}
+
+/// Forward a request to the application router, or 503 while starting
+async fn forward(State(state): State<StartupState>, request: Request<Body>) -> Response {
+    match state.app.get() {
+        Some(app) => match app.router.clone().oneshot(request).await {
+            Ok(response) => response,
+            Err(infallible) => match infallible {},
+        },
+        None => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            [(axum::http::header::RETRY_AFTER, "5")],
+            Json(json!({
+                "error": {
+                    "message": "Service is starting",
+                    "code": 503,
+                }
+            })),
+        )
+            .into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_phases_record_timings_and_errors() {
+        let tracker = StartupTracker::new();
+
+        let ok: Result<u8, String> = tracker
+            .run(StartupPhase::Configuration, async { Ok(1) })
+            .await;
+        assert_eq!(ok, Ok(1));
+        assert!(tracker.completed(StartupPhase::Configuration));
+        assert!(!tracker.completed(StartupPhase::Migrations));
+        assert!(!tracker.failed());
+
+        let failed: Result<(), String> = tracker
+            .run(StartupPhase::Migrations, async {
+                Err("no database".to_string())
+            })
+            .await;
+        assert!(failed.is_err());
+        assert!(!tracker.completed(StartupPhase::Migrations));
+        assert!(tracker.failed());
+
+        let phases = tracker.phases();
+        assert_eq!(phases.len(), 2);
+        assert!(phases.iter().all(|timing| timing.duration_ms.is_some()));
+        assert_eq!(phases[1].error.as_deref(), Some("no database"));
+        assert!(!tracker.is_finished());
+    }
+}
@@ -1,19 +1,28 @@
 //! WebSocket handler for real-time progress updates
+//!
+//! Besides operation progress, changes to movies, movie files, the queue,
+//! quality profiles and service health are pushed as typed `Resource`
+//! messages (`{ name, body: { action, resource } }`), so the UI can refresh
+//! what changed instead of polling the REST endpoints. Clients receive every
+//! topic until they narrow it down with `SubscribeTopics`/`UnsubscribeTopics`.
 
 use axum::{
     extract::{
         ws::{Message, WebSocket, WebSocketUpgrade},
-        Extension, Query,
+        ConnectInfo, Extension, Query,
     },
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
     response::Response,
 };
 use futures::{sink::SinkExt, stream::StreamExt};
+use radarr_api::auth::{self, AuthOutcome};
+use radarr_api::{api_keys, sessions};
 use radarr_core::{
-    events::{EventBus, SystemEvent},
+    events::{EventBus, ResourceMessage, ResourceTopic, SystemEvent},
     progress::{OperationType, ProgressTracker},
 };
 use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
 use std::sync::Arc;
 use tracing::{debug, error, info, warn};
 
@@ -25,6 +34,10 @@ pub enum WsMessage {
     Subscribe { operations: Vec<OperationType> },
     /// Unsubscribe from operation types
     Unsubscribe { operations: Vec<OperationType> },
+    /// Subscribe to resource update topics
+    SubscribeTopics { topics: Vec<ResourceTopic> },
+    /// Unsubscribe from resource update topics
+    UnsubscribeTopics { topics: Vec<ResourceTopic> },
     /// Request current progress for all operations
     GetProgress,
     /// Ping to keep connection alive
@@ -53,7 +66,9 @@ pub enum WsResponse {
         success: bool,
         message: String,
     },
-    /// System event
+    /// A movie, movie file, queue item, quality profile or service health changed
+    Resource(ResourceMessage),
+    /// System event that does not change a resource
     Event { event: SystemEvent },
     /// Error message
     Error { message: String },
@@ -81,24 +96,45 @@ pub struct WsQuery {
 pub async fn websocket_handler(
     ws: WebSocketUpgrade,
     Query(params): Query<WsQuery>,
+    headers: HeaderMap,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
     Extension(state): Extension<Arc<WsState>>,
 ) -> Result<Response, StatusCode> {
     // Verify API key
-    let expected_api_key = std::env::var("RADARR_API_KEY").map_err(|_| {
-        error!("RADARR_API_KEY environment variable not set");
+    let verifier = auth::global_verifier().ok_or_else(|| {
+        error!("RADARR_API_KEY or RADARR_API_KEY_HASH environment variable not set");
         StatusCode::INTERNAL_SERVER_ERROR
     })?;
 
-    match params.apikey {
-        Some(provided_key) if provided_key == expected_api_key => {
+    // The web UI may be logged in with a session cookie instead
+    if params.apikey.is_none() {
+        let session = sessions::global_sessions().zip(sessions::session_token(&headers));
+        if let Some((store, token)) = session {
+            if store.authenticate(token, chrono::Utc::now()).is_some() {
+                info!("WebSocket client authenticated by session");
+                return Ok(ws.on_upgrade(|socket| handle_socket(socket, state)));
+            }
+        }
+    }
+
+    let client = auth::client_identity(Some(addr.ip()));
+    let outcome = verifier.authenticate_with(params.apikey.as_deref(), &client, "/ws", |key| {
+        api_keys::global_registry().find(key).is_some()
+    });
+    match outcome {
+        AuthOutcome::Authorized => {
             info!("WebSocket client authenticated successfully");
             Ok(ws.on_upgrade(|socket| handle_socket(socket, state)))
         }
-        Some(_) => {
+        AuthOutcome::LockedOut { .. } => {
+            warn!("WebSocket authentication refused: client locked out");
+            Err(StatusCode::TOO_MANY_REQUESTS)
+        }
+        AuthOutcome::Invalid => {
             warn!("WebSocket authentication failed: invalid API key");
             Err(StatusCode::UNAUTHORIZED)
         }
-        None => {
+        AuthOutcome::Missing => {
             warn!("WebSocket authentication failed: no API key provided");
             Err(StatusCode::UNAUTHORIZED)
         }
@@ -117,7 +153,11 @@ async fn handle_socket(socket: WebSocket, state: Arc<WsState>) {
         OperationType::Download,
         OperationType::Import,
         OperationType::IndexerSearch,
+        OperationType::Transcode,
+        OperationType::Extract,
+        OperationType::MoveMovie,
     ]; // Default subscriptions
+    let mut subscribed_topics: Vec<ResourceTopic> = ResourceTopic::ALL.to_vec();
 
     info!("WebSocket client connected");
 
@@ -136,7 +176,14 @@ async fn handle_socket(socket: WebSocket, state: Arc<WsState>) {
                 match ws_msg {
                     Some(Ok(Message::Text(text))) => {
                         if let Ok(ws_msg) = serde_json::from_str::<WsMessage>(&text) {
-                            handle_message(ws_msg, &state, &mut sender, &mut subscribed_operations).await;
+                            handle_message(
+                                ws_msg,
+                                &state,
+                                &mut sender,
+                                &mut subscribed_operations,
+                                &mut subscribed_topics,
+                            )
+                            .await;
                         }
                     }
                     Some(Ok(Message::Ping(data))) => {
@@ -159,51 +206,19 @@ async fn handle_socket(socket: WebSocket, state: Arc<WsState>) {
             event = event_receiver.recv() => {
                 match event {
                     Ok(envelope) => {
-                        // Convert event to WsResponse
-                        let response = match &envelope.event {
-                            SystemEvent::ProgressUpdate {
-                                operation_id,
-                                operation_type,
-                                percentage,
-                                message,
-                                eta_seconds
-                            } => {
-                                // Check if subscribed to this operation type
-                                if subscribed_operations.contains(operation_type) {
-                                    Some(WsResponse::Progress {
-                                        operation_id: *operation_id,
-                                        operation_type: *operation_type,
-                                        percentage: *percentage,
-                                        message: message.clone(),
-                                        eta_seconds: *eta_seconds,
-                                        status: "in_progress".to_string(),
-                                    })
-                                } else {
-                                    None
-                                }
-                            }
-                            SystemEvent::OperationComplete {
-                                operation_id,
-                                operation_type,
-                                success,
-                                message
-                            } => {
-                                if subscribed_operations.contains(operation_type) {
-                                    Some(WsResponse::Complete {
-                                        operation_id: *operation_id,
-                                        operation_type: *operation_type,
-                                        success: *success,
-                                        message: message.clone(),
-                                    })
-                                } else {
-                                    None
-                                }
+                        // Convert event to WsResponse, keeping subscribed ones
+                        let response = push_response(&envelope.event);
+                        let wanted = match &response {
+                            WsResponse::Progress { operation_type, .. }
+                            | WsResponse::Complete { operation_type, .. } => {
+                                subscribed_operations.contains(operation_type)
                             }
-                            _ => {
-                                // Forward all other events
-                                Some(WsResponse::Event { event: envelope.event.clone() })
+                            WsResponse::Resource(message) => {
+                                subscribed_topics.contains(&message.name)
                             }
+                            _ => true,
                         };
+                        let response = wanted.then_some(response);
 
                         // Send response if we have one
                         if let Some(response) = response {
@@ -225,12 +240,54 @@ async fn handle_socket(socket: WebSocket, state: Arc<WsState>) {
     info!("WebSocket handler shutting down");
 }
 
+/// Message pushed to clients for an event
+///
+/// Shared by the WebSocket and the server-sent events stream: operation
+/// progress becomes `Progress`/`Complete`, resource changes become
+/// `Resource`, and any other event is forwarded as it is.
+pub fn push_response(event: &SystemEvent) -> WsResponse {
+    match event {
+        SystemEvent::ProgressUpdate {
+            operation_id,
+            operation_type,
+            percentage,
+            message,
+            eta_seconds,
+        } => WsResponse::Progress {
+            operation_id: *operation_id,
+            operation_type: *operation_type,
+            percentage: *percentage,
+            message: message.clone(),
+            eta_seconds: *eta_seconds,
+            status: "in_progress".to_string(),
+        },
+        SystemEvent::OperationComplete {
+            operation_id,
+            operation_type,
+            success,
+            message,
+        } => WsResponse::Complete {
+            operation_id: *operation_id,
+            operation_type: *operation_type,
+            success: *success,
+            message: message.clone(),
+        },
+        event => match ResourceMessage::from_event(event) {
+            Some(message) => WsResponse::Resource(message),
+            None => WsResponse::Event {
+                event: event.clone(),
+            },
+        },
+    }
+}
+
 /// Handle incoming WebSocket message
 async fn handle_message(
     msg: WsMessage,
     state: &Arc<WsState>,
     sender: &mut futures::stream::SplitSink<WebSocket, Message>,
     subscribed_operations: &mut Vec<OperationType>,
+    subscribed_topics: &mut Vec<ResourceTopic>,
 ) {
     match msg {
         WsMessage::Subscribe { operations } => {
@@ -248,6 +305,21 @@ async fn handle_message(
                 subscribed_operations
             );
         }
+        WsMessage::SubscribeTopics { topics } => {
+            for topic in topics {
+                if !subscribed_topics.contains(&topic) {
+                    subscribed_topics.push(topic);
+                }
+            }
+            debug!("Subscribed to topics: {:?}", subscribed_topics);
+        }
+        WsMessage::UnsubscribeTopics { topics } => {
+            subscribed_topics.retain(|topic| !topics.contains(topic));
+            debug!(
+                "Unsubscribed from topics, now subscribed to: {:?}",
+                subscribed_topics
+            );
+        }
         WsMessage::GetProgress => {
             let operations = state.progress_tracker.get_all_operations().await;
             let response = WsResponse::ProgressSnapshot { operations };
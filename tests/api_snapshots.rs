@@ -0,0 +1,647 @@
+//! Snapshot tests for API response contracts
+//!
+//! Responses from the v3 handlers (movies, movie files, releases, downloads,
+//! commands, queue, blocklist, wanted, calendar, history, quality profiles,
+//! custom formats, indexers, tags, root folders, exclusions, notifications,
+//! collections, config, tasks, statistics, health and audit) are serialized
+//! against the seeded test database from `common` and compared with the
+//! stored snapshot under `tests/snapshots/`, so renamed or dropped fields show
+//! up as a readable diff instead of a silent contract change.
+//! Generated UUIDs (including ones embedded in messages and mock download
+//! hashes) and timestamps are replaced with stable placeholders before
+//! comparison.
+//!
+//! After an intentional contract change, review and accept the new snapshots
+//! with `cargo insta test --review --test api_snapshots`.
+
+mod common;
+
+use std::sync::Arc;
+
+use axum::{http::StatusCode, Router};
+use axum_test::TestServer;
+use common::{create_test_app, seed_snapshot_fixtures, TestContext};
+use insta::assert_json_snapshot;
+use radarr_api::handlers::{
+    audit::{create_audit_router, AuditState},
+    blocklist::{create_blocklist_router, BlocklistState},
+    calendar::{create_calendar_router, CalendarState},
+    collections::{create_collection_router, CollectionState},
+    commands::{create_command_router, CommandQueueState},
+    config::{create_config_router, ConfigState},
+    exclusions::{create_exclusion_router, ExclusionState},
+    history::{create_history_router, HistoryState},
+    indexers::{create_indexer_router, IndexerState},
+    movie_files::{create_movie_file_router, MovieFileState},
+    notifications::{create_notification_router, NotificationState},
+    quality::{create_quality_router, QualityState},
+    quality_profiles::{create_quality_profile_router, QualityProfileState},
+    queue::{create_queue_router, QueueState},
+    root_folders::{create_root_folder_router, RootFolderState},
+    statistics::{create_statistics_router, StatisticsState},
+    system_health::{create_system_health_router, SystemHealthState},
+    tags::{create_tag_router, TagState},
+    tasks::{create_task_router, TaskState},
+    wanted::{create_wanted_router, WantedState},
+};
+use radarr_core::{
+    commands::{Command, CommandExecutor, CommandName, CommandProgress},
+    jobs::TaskScheduler,
+    repositories::{HistoryRepository, MovieFileRepository, MovieRepository},
+    BlocklistEntry, BlocklistRepository, CommandQueue, FailureReason, HealthCheckRegistry,
+    HistoryEventType, HistoryRecord, MovieFile, QueueItem, QueueRepository,
+};
+use radarr_infrastructure::{
+    DatabasePool, PostgresBlocklistRepository, PostgresHistoryRepository,
+    PostgresMovieFileRepository, PostgresMovieRepository, PostgresQueueRepository,
+};
+use serde_json::{json, Value};
+
+/// Finishes every command straight away, so a queued command's final state
+/// can be snapshotted
+struct SnapshotCommandExecutor;
+
+#[async_trait::async_trait]
+impl CommandExecutor for SnapshotCommandExecutor {
+    async fn execute(
+        &self,
+        _command: &Command,
+        _progress: &CommandProgress,
+    ) -> radarr_core::Result<String> {
+        Ok("Completed".to_string())
+    }
+}
+
+/// Test app plus the routers that are normally nested under `/api`
+async fn snapshot_server(pool: DatabasePool) -> TestServer {
+    let command_queue = Arc::new(CommandQueue::default());
+    command_queue.register(CommandName::RssSync, Arc::new(SnapshotCommandExecutor));
+    let api_routes = create_quality_profile_router(QualityProfileState::new(pool.clone()))
+        .merge(create_indexer_router(IndexerState::new(pool.clone())))
+        .merge(create_movie_file_router(MovieFileState::new(pool.clone())))
+        .merge(create_command_router(CommandQueueState::new(
+            command_queue.clone(),
+        )))
+        .merge(create_queue_router(QueueState::new(
+            pool.clone(),
+            command_queue.clone(),
+        )))
+        .merge(create_blocklist_router(BlocklistState::new(
+            pool.clone(),
+            command_queue.clone(),
+        )))
+        .merge(create_wanted_router(WantedState::new(
+            pool.clone(),
+            command_queue.clone(),
+        )))
+        .merge(create_calendar_router(CalendarState::new(pool.clone())))
+        .merge(create_history_router(HistoryState::new(pool.clone())))
+        .merge(create_tag_router(TagState::new(pool.clone())))
+        .merge(create_root_folder_router(RootFolderState::new(
+            pool.clone(),
+        )))
+        .merge(create_exclusion_router(ExclusionState::new(pool.clone())))
+        .merge(create_notification_router(NotificationState::new(
+            pool.clone(),
+        )))
+        .merge(create_collection_router(CollectionState::new(pool.clone())))
+        .merge(create_config_router(ConfigState::new(pool.clone())))
+        .merge(create_quality_router(QualityState::new(pool.clone())))
+        .merge(create_task_router(TaskState::new(Arc::new(
+            TaskScheduler::new(command_queue),
+        ))))
+        .merge(create_statistics_router(StatisticsState::from_pool(
+            pool.clone(),
+        )))
+        .merge(create_system_health_router(SystemHealthState::new(
+            Arc::new(HealthCheckRegistry::new()),
+        )))
+        .merge(create_audit_router(AuditState::new(pool.clone())));
+
+    let app = create_test_app(pool)
+        .await
+        .merge(Router::new().nest("/api", api_routes));
+
+    TestServer::new(app).unwrap()
+}
+
+/// Replace hyphenated or simple UUIDs inside a longer string, e.g. error
+/// messages and mock download hashes
+fn replace_embedded_uuids(s: &str) -> String {
+    let is_uuid_char = |c: char| c.is_ascii_hexdigit() || c == '-';
+    s.split_inclusive(|c: char| !is_uuid_char(c))
+        .map(|part| {
+            let token = part.trim_end_matches(|c: char| !is_uuid_char(c));
+            if uuid::Uuid::parse_str(token).is_ok() {
+                format!("[uuid]{}", &part[token.len()..])
+            } else {
+                part.to_string()
+            }
+        })
+        .collect()
+}
+
+/// Replace generated UUIDs and timestamps with stable placeholders
+fn normalize(value: Value) -> Value {
+    match value {
+        Value::String(s) if uuid::Uuid::parse_str(&s).is_ok() => json!("[uuid]"),
+        Value::String(s) if chrono::DateTime::parse_from_rfc3339(&s).is_ok() => {
+            json!("[timestamp]")
+        }
+        Value::String(s) => Value::String(replace_embedded_uuids(&s)),
+        Value::Array(items) => Value::Array(items.into_iter().map(normalize).collect()),
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, value)| (key, normalize(value)))
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
+#[tokio::test]
+async fn snapshot_health_responses() {
+    let test_ctx = TestContext::new().await;
+    let server = snapshot_server(test_ctx.pool.clone()).await;
+
+    let response = server.get("/health").await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let mut body: Value = response.json();
+    body["version"] = json!("[version]");
+    assert_json_snapshot!("health", normalize(body));
+
+    test_ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn snapshot_movie_responses() {
+    let test_ctx = TestContext::new().await;
+    let fixtures = seed_snapshot_fixtures(&test_ctx.pool).await;
+    let server = snapshot_server(test_ctx.pool.clone()).await;
+
+    let response = server.get("/api/v3/movie").await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert_json_snapshot!("movie_list", normalize(response.json()));
+
+    let response = server
+        .get(&format!("/api/v3/movie/{}", fixtures.movies[0].id))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert_json_snapshot!("movie_get", normalize(response.json()));
+
+    let response = server
+        .get(&format!("/api/v3/movie/{}", uuid::Uuid::new_v4()))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::NOT_FOUND);
+    assert_json_snapshot!("movie_not_found", normalize(response.json()));
+
+    let response = server
+        .post("/api/v3/movie")
+        .json(&json!({
+            "tmdbId": 680,
+            "title": "Pulp Fiction",
+            "monitored": true,
+            "qualityProfileId": fixtures.quality_profile.id,
+            "minimumAvailability": "Released"
+        }))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::CREATED);
+    assert_json_snapshot!("movie_create", normalize(response.json()));
+
+    test_ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn snapshot_release_and_download_responses() {
+    let test_ctx = TestContext::new().await;
+    let fixtures = seed_snapshot_fixtures(&test_ctx.pool).await;
+    let server = snapshot_server(test_ctx.pool.clone()).await;
+
+    let response = server
+        .get("/api/v3/release")
+        .add_query_param("movieId", fixtures.movies[0].id)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert_json_snapshot!("release_search", normalize(response.json()));
+
+    let response = server
+        .get("/api/v3/release")
+        .add_query_param("fail_prowlarr", true)
+        .await;
+    assert_json_snapshot!("release_search_error", normalize(response.json()));
+
+    let response = server
+        .post("/api/v3/command/download")
+        .json(&json!({"guid": "test-guid-1", "indexerId": 1}))
+        .await;
+    assert!(response.status_code().is_success());
+    let download: Value = response.json();
+    assert_json_snapshot!("download_start", normalize(download.clone()));
+
+    let response = server
+        .get(&format!(
+            "/api/v3/download/{}",
+            download["id"].as_str().unwrap()
+        ))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert_json_snapshot!("download_status", normalize(response.json()));
+
+    test_ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn snapshot_quality_profile_responses() {
+    let test_ctx = TestContext::new().await;
+    let fixtures = seed_snapshot_fixtures(&test_ctx.pool).await;
+    let server = snapshot_server(test_ctx.pool.clone()).await;
+
+    let response = server.get("/api/v3/qualityprofile").await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert_json_snapshot!("quality_profile_list", normalize(response.json()));
+
+    let response = server
+        .get(&format!(
+            "/api/v3/qualityprofile/{}",
+            fixtures.quality_profile.id
+        ))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert_json_snapshot!("quality_profile_get", normalize(response.json()));
+
+    let response = server.get("/api/v3/qualityprofile/schema").await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert_json_snapshot!("quality_profile_schema", normalize(response.json()));
+
+    let response = server
+        .post("/api/v3/qualityprofile")
+        .json(&json!({
+            "name": "Invalid",
+            "cutoff": 4,
+            "items": [{"quality": {"id": 4}, "allowed": false}]
+        }))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+    assert_json_snapshot!("quality_profile_invalid", normalize(response.json()));
+
+    test_ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn snapshot_indexer_responses() {
+    let test_ctx = TestContext::new().await;
+    let fixtures = seed_snapshot_fixtures(&test_ctx.pool).await;
+    let server = snapshot_server(test_ctx.pool.clone()).await;
+
+    let response = server.get("/api/v3/indexer").await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert_json_snapshot!("indexer_list", normalize(response.json()));
+
+    let response = server
+        .get(&format!("/api/v3/indexer/{}", fixtures.indexer.id))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert_json_snapshot!("indexer_get", normalize(response.json()));
+
+    let response = server
+        .post("/api/v3/indexer")
+        .json(&json!({
+            "name": "Missing Key",
+            "implementation": "torznab",
+            "settings": {"base_url": "http://localhost:9117"}
+        }))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+    assert_json_snapshot!("indexer_invalid", normalize(response.json()));
+
+    test_ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn snapshot_movie_file_responses() {
+    let test_ctx = TestContext::new().await;
+    let fixtures = seed_snapshot_fixtures(&test_ctx.pool).await;
+    let server = snapshot_server(test_ctx.pool.clone()).await;
+
+    let movie_file = PostgresMovieFileRepository::new(test_ctx.pool.clone())
+        .create(&MovieFile::new(
+            fixtures.movies[0].id,
+            "Fight Club (1999)/Fight Club (1999) Bluray-1080p.mkv".to_string(),
+            8_589_934_592,
+            json!({"quality": {"id": 7, "name": "Bluray-1080p"}}),
+        ))
+        .await
+        .unwrap();
+
+    let response = server
+        .get("/api/v3/moviefile")
+        .add_query_param("movieId", fixtures.movies[0].id)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert_json_snapshot!("movie_file_list", normalize(response.json()));
+
+    let response = server
+        .get(&format!("/api/v3/moviefile/{}", movie_file.id))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert_json_snapshot!("movie_file_get", normalize(response.json()));
+
+    let response = server
+        .get(&format!("/api/v3/moviefile/{}", uuid::Uuid::new_v4()))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::NOT_FOUND);
+    assert_json_snapshot!("movie_file_not_found", normalize(response.json()));
+
+    test_ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn snapshot_command_responses() {
+    let test_ctx = TestContext::new().await;
+    let server = snapshot_server(test_ctx.pool.clone()).await;
+
+    let response = server
+        .post("/api/v3/command")
+        .json(&json!({"name": "RssSync"}))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::CREATED);
+    let command: Value = response.json();
+    assert_json_snapshot!("command_queue", normalize(command.clone()));
+
+    // The executor runs in the background; wait for it to finish
+    let path = format!("/api/v3/command/{}", command["id"]);
+    let mut finished: Value = server.get(&path).await.json();
+    for _ in 0..50 {
+        if finished["status"] == "completed" {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        finished = server.get(&path).await.json();
+    }
+    assert_json_snapshot!("command_get", normalize(finished));
+
+    let response = server.get("/api/v3/command").await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert_json_snapshot!("command_list", normalize(response.json()));
+
+    let response = server
+        .post("/api/v3/command")
+        .json(&json!({"name": "NoSuchCommand"}))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+    assert_json_snapshot!("command_unknown", normalize(response.json()));
+
+    test_ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn snapshot_queue_and_blocklist_responses() {
+    let test_ctx = TestContext::new().await;
+    let fixtures = seed_snapshot_fixtures(&test_ctx.pool).await;
+    let server = snapshot_server(test_ctx.pool.clone()).await;
+    let movie_id = fixtures.movies[1].id;
+
+    PostgresQueueRepository::new(test_ctx.pool.clone())
+        .add_queue_item(&QueueItem::new(
+            movie_id,
+            uuid::Uuid::new_v4(),
+            "The.Matrix.1999.1080p.BluRay.x264-GROUP".to_string(),
+            "magnet:?xt=urn:btih:0123456789abcdef0123456789abcdef01234567".to_string(),
+        ))
+        .await
+        .unwrap();
+
+    PostgresBlocklistRepository::new(test_ctx.pool.clone())
+        .add_entry(&BlocklistEntry::new_for_movie(
+            "release-1".to_string(),
+            "Test Jackett".to_string(),
+            FailureReason::ParseError,
+            "The.Matrix.1999.720p.HDTV-BAD".to_string(),
+            movie_id,
+        ))
+        .await
+        .unwrap();
+
+    let response = server.get("/api/v3/queue").await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert_json_snapshot!("queue_list", normalize(response.json()));
+
+    let response = server.get("/api/v3/blocklist").await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert_json_snapshot!("blocklist_list", normalize(response.json()));
+
+    let response = server
+        .get("/api/v3/blocklist/movie")
+        .add_query_param("movieId", movie_id)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert_json_snapshot!("blocklist_movie", normalize(response.json()));
+
+    test_ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn snapshot_wanted_responses() {
+    let test_ctx = TestContext::new().await;
+    seed_snapshot_fixtures(&test_ctx.pool).await;
+    let server = snapshot_server(test_ctx.pool.clone()).await;
+
+    let response = server.get("/api/v3/wanted/missing").await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert_json_snapshot!("wanted_missing", normalize(response.json()));
+
+    let response = server.get("/api/v3/wanted/cutoff").await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert_json_snapshot!("wanted_cutoff", normalize(response.json()));
+
+    test_ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn snapshot_calendar_responses() {
+    let test_ctx = TestContext::new().await;
+    let fixtures = seed_snapshot_fixtures(&test_ctx.pool).await;
+    let server = snapshot_server(test_ctx.pool.clone()).await;
+
+    let mut movie = fixtures.movies[1].clone();
+    movie.metadata = json!({
+        "tmdb": {
+            "release_date": "1999-03-31",
+            "digital_release_date": "1999-09-21"
+        }
+    });
+    PostgresMovieRepository::new(test_ctx.pool.clone())
+        .update(&movie)
+        .await
+        .unwrap();
+
+    let response = server
+        .get("/api/v3/calendar")
+        .add_query_param("start", "1999-03-01")
+        .add_query_param("end", "1999-04-30")
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert_json_snapshot!("calendar", normalize(response.json()));
+
+    let response = server
+        .get("/api/v3/calendar")
+        .add_query_param("start", "1999-04-30")
+        .add_query_param("end", "1999-03-01")
+        .await;
+    assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+    assert_json_snapshot!("calendar_invalid_range", normalize(response.json()));
+
+    test_ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn snapshot_history_responses() {
+    let test_ctx = TestContext::new().await;
+    let fixtures = seed_snapshot_fixtures(&test_ctx.pool).await;
+    let server = snapshot_server(test_ctx.pool.clone()).await;
+    let movie_id = fixtures.movies[0].id;
+
+    let history_repo = PostgresHistoryRepository::new(test_ctx.pool.clone());
+    history_repo
+        .create(
+            &HistoryRecord::new(HistoryEventType::Grabbed)
+                .with_movie(Some(movie_id))
+                .with_source_title("Fight.Club.1999.1080p.BluRay.x264-GROUP")
+                .with_download_id("0123456789abcdef0123456789abcdef01234567")
+                .with_data(json!({"indexer": "Test Jackett", "size": 8589934592_i64})),
+        )
+        .await
+        .unwrap();
+
+    let response = server.get("/api/v3/history").await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert_json_snapshot!("history_list", normalize(response.json()));
+
+    let response = server
+        .get(&format!("/api/v3/history/movie/{}", movie_id))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert_json_snapshot!("history_movie", normalize(response.json()));
+
+    test_ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn snapshot_library_settings_responses() {
+    let test_ctx = TestContext::new().await;
+    let fixtures = seed_snapshot_fixtures(&test_ctx.pool).await;
+    let server = snapshot_server(test_ctx.pool.clone()).await;
+
+    let response = server
+        .post("/api/v3/tag")
+        .json(&json!({"label": "4k"}))
+        .await;
+    assert!(response.status_code().is_success());
+    assert_json_snapshot!("tag_create", normalize(response.json()));
+
+    let response = server.get("/api/v3/tag/detail").await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert_json_snapshot!("tag_detail_list", normalize(response.json()));
+
+    let response = server
+        .post("/api/v3/rootfolder")
+        .json(&json!({
+            "path": "/nonexistent/movies",
+            "defaultQualityProfileId": fixtures.quality_profile.id
+        }))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+    assert_json_snapshot!("root_folder_invalid", normalize(response.json()));
+
+    let response = server.get("/api/v3/rootfolder").await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert_json_snapshot!("root_folder_list", normalize(response.json()));
+
+    let response = server
+        .post("/api/v3/exclusions")
+        .json(&json!({
+            "tmdbId": 13,
+            "movieTitle": "Forrest Gump",
+            "movieYear": 1994,
+            "reason": "Not interested"
+        }))
+        .await;
+    assert!(response.status_code().is_success());
+    assert_json_snapshot!("exclusion_create", normalize(response.json()));
+
+    let response = server.get("/api/v3/exclusions").await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert_json_snapshot!("exclusion_list", normalize(response.json()));
+
+    let response = server
+        .post("/api/v3/notification")
+        .json(&json!({
+            "name": "Discord",
+            "implementation": "discord",
+            "settings": {"webhook_url": "https://discord.com/api/webhooks/1/abc"},
+            "onGrab": true,
+            "onImport": true
+        }))
+        .await;
+    assert!(response.status_code().is_success());
+    assert_json_snapshot!("notification_create", normalize(response.json()));
+
+    let response = server.get("/api/v3/notification").await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert_json_snapshot!("notification_list", normalize(response.json()));
+
+    let response = server.get("/api/v3/collection").await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert_json_snapshot!("collection_list", normalize(response.json()));
+
+    let response = server.get("/api/v3/customformat/codecs").await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert_json_snapshot!("custom_format_codecs", normalize(response.json()));
+
+    test_ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn snapshot_config_responses() {
+    let test_ctx = TestContext::new().await;
+    let server = snapshot_server(test_ctx.pool.clone()).await;
+
+    let response = server.get("/api/v3/config/host").await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert_json_snapshot!("config_host", normalize(response.json()));
+
+    let response = server.get("/api/v3/config/naming").await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert_json_snapshot!("config_naming", normalize(response.json()));
+
+    let response = server.get("/api/v3/config/mediamanagement").await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert_json_snapshot!("config_media_management", normalize(response.json()));
+
+    test_ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn snapshot_system_responses() {
+    let test_ctx = TestContext::new().await;
+    seed_snapshot_fixtures(&test_ctx.pool).await;
+    let server = snapshot_server(test_ctx.pool.clone()).await;
+
+    let response = server.get("/api/v3/system/task").await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert_json_snapshot!("task_list", normalize(response.json()));
+
+    let response = server.get("/api/v3/movie/stats").await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert_json_snapshot!("movie_stats", normalize(response.json()));
+
+    let response = server.get("/api/v3/health").await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert_json_snapshot!("health_issues", normalize(response.json()));
+
+    let response = server.get("/api/v3/audit").await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert_json_snapshot!("audit_list", normalize(response.json()));
+
+    test_ctx.cleanup().await;
+}
@@ -3,12 +3,16 @@
 use axum::Router;
 use radarr_api::handlers::AppState;
 use radarr_core::{
-    models::{MinimumAvailability, Movie, MovieStatus},
-    repositories::MovieRepository,
+    models::{
+        Indexer, IndexerImplementation, MinimumAvailability, Movie, MovieStatus, QualityProfile,
+    },
+    repositories::{IndexerRepository, MovieRepository, QualityProfileRepository},
 };
 use radarr_infrastructure::{
     database::{create_pool, migrate, DatabaseConfig},
-    repositories::PostgresMovieRepository,
+    repositories::{
+        PostgresIndexerRepository, PostgresMovieRepository, PostgresQualityProfileRepository,
+    },
     DatabasePool,
 };
 use uuid::Uuid;
@@ -214,6 +218,74 @@ pub async fn create_test_movies(pool: &DatabasePool, count: usize) -> Vec<Movie>
     movies
 }
 
+/// Deterministic records seeded for API snapshot tests
+pub struct SnapshotFixtures {
+    pub movies: Vec<Movie>,
+    pub quality_profile: QualityProfile,
+    pub indexer: Indexer,
+}
+
+/// Seed a fixed set of movies, a quality profile and an indexer so API
+/// responses are stable across runs (apart from generated IDs and timestamps)
+pub async fn seed_snapshot_fixtures(pool: &DatabasePool) -> SnapshotFixtures {
+    let profile_repo = PostgresQualityProfileRepository::new(pool.clone());
+    let mut profile = QualityProfile::new("HD-1080p".to_string(), 7);
+    profile.update_items(serde_json::json!([
+        {"quality": {"id": 4}, "items": [], "allowed": false},
+        {"quality": {"id": 3}, "items": [], "allowed": true},
+        {"quality": {"id": 7}, "items": [], "allowed": true}
+    ]));
+    let quality_profile = profile_repo
+        .create(&profile)
+        .await
+        .expect("Failed to create test quality profile");
+
+    let indexer_repo = PostgresIndexerRepository::new(pool.clone());
+    let mut indexer = Indexer::new("Test Jackett".to_string(), IndexerImplementation::Jackett);
+    indexer.update_settings(serde_json::json!({
+        "base_url": "http://localhost:9117/api/v2.0/indexers/all/results/torznab",
+        "api_key": "test-jackett-key"
+    }));
+    let indexer = indexer_repo
+        .create(&indexer)
+        .await
+        .expect("Failed to create test indexer");
+
+    let movie_repo = PostgresMovieRepository::new(pool.clone());
+    let seeds = [
+        (550, "Fight Club", 1999, MovieStatus::Released, true),
+        (603, "The Matrix", 1999, MovieStatus::Released, true),
+        (
+            693134,
+            "Dune: Part Two",
+            2024,
+            MovieStatus::Announced,
+            false,
+        ),
+    ];
+    let mut movies = Vec::new();
+    for (tmdb_id, title, year, status, monitored) in seeds {
+        let mut movie = Movie::new(tmdb_id, title.to_string());
+        movie.year = Some(year);
+        movie.status = status;
+        movie.monitored = monitored;
+        movie.quality_profile_id = Some(quality_profile.id);
+        movie.minimum_availability = MinimumAvailability::Released;
+        movies.push(
+            movie_repo
+                .create(&movie)
+                .await
+                .expect("Failed to create snapshot movie"),
+        );
+    }
+
+    SnapshotFixtures {
+        movies,
+        quality_profile,
+        indexer,
+    }
+}
+
 /// Helper to wait for a condition with timeout
 pub async fn wait_for_condition<F, Fut>(
     mut condition: F,
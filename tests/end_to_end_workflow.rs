@@ -5,7 +5,7 @@
 
 use async_trait::async_trait;
 use chrono::Utc;
-use radarr_core::{MinimumAvailability, Movie, MovieStatus};
+use radarr_core::{MinimumAvailability, Movie, MovieRatings, MovieStatus};
 use radarr_downloaders::{
     AddTorrentParams, QBittorrentClient, QBittorrentConfig, TorrentData, TorrentInfo,
 };
@@ -121,6 +121,10 @@ impl MockQBittorrentClient {
             priority: 0,
             category: params.category.unwrap_or_default(),
             save_path: params.save_path.unwrap_or("/downloads".to_string()),
+            seq_dl: false,
+            uploaded: 0,
+            ratio: 0.0,
+            seeding_time: 0,
         };
 
         {
@@ -475,10 +479,14 @@ mod tests {
                 "path": "/media/movies/The Matrix (1999)"
             }),
             alternative_titles: serde_json::json!([]),
+            ratings: MovieRatings::default(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
             last_search_time: None,
             last_info_sync: None,
+            failed_search_count: 0,
+            excluded_indexer_ids: Vec::new(),
+            tags: Vec::new(),
         }
     }
 
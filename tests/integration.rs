@@ -37,6 +37,7 @@ async fn test_complete_movie_workflow() {
         monitored: true,
         quality_profile_id: Some(1),
         minimum_availability: Some(MinimumAvailability::Released),
+        tags: Vec::new(),
         metadata: Some(json!({
             "tmdb": {
                 "overview": "An insomniac office worker and a devil-may-care soapmaker form an underground fight club.",
@@ -162,6 +163,7 @@ async fn test_invalid_movie_id_error_handling() {
         monitored: Some(false),
         quality_profile_id: None,
         minimum_availability: None,
+        tags: None,
         metadata: None,
     };
 
@@ -200,10 +202,7 @@ async fn test_external_service_failures() {
         ))
         .await;
 
-    assert_eq!(
-        search_response.status_code(),
-        StatusCode::INTERNAL_SERVER_ERROR
-    );
+    assert_eq!(search_response.status_code(), StatusCode::BAD_GATEWAY);
 
     // Test download with failing qBittorrent
     let download_request = DownloadRequest {
@@ -216,10 +215,7 @@ async fn test_external_service_failures() {
         .json(&download_request)
         .await;
 
-    assert_eq!(
-        download_response.status_code(),
-        StatusCode::INTERNAL_SERVER_ERROR
-    );
+    assert_eq!(download_response.status_code(), StatusCode::BAD_GATEWAY);
 
     test_ctx.cleanup().await;
 }
@@ -238,6 +234,7 @@ async fn test_database_error_handling() {
         monitored: true,
         quality_profile_id: None,
         minimum_availability: None,
+        tags: Vec::new(),
         metadata: None,
     };
 
@@ -314,6 +311,7 @@ async fn test_concurrent_operations() {
             monitored: true,
             quality_profile_id: Some(1),
             minimum_availability: None,
+            tags: Vec::new(),
             metadata: None,
         })
         .collect();
@@ -433,6 +431,7 @@ async fn test_pagination() {
             monitored: true,
             quality_profile_id: None,
             minimum_availability: None,
+            tags: Vec::new(),
             metadata: None,
         };
 
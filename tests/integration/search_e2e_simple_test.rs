@@ -622,7 +622,7 @@ mod tests {
             .get("/api/v3/release?fail_prowlarr=true")
             .await;
         
-        assert_eq!(response.status_code(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(response.status_code(), StatusCode::BAD_GATEWAY);
         
         let error_body: serde_json::Value = response.json();
         assert!(error_body.get("error").is_some(), "Should return error details");
@@ -122,6 +122,10 @@ impl MockQBittorrentClient {
             priority: params.priority,
             category: params.category.unwrap_or_default(),
             save_path: params.save_path.unwrap_or_else(|| "/downloads".to_string()),
+            seq_dl: false,
+            uploaded: 0,
+            ratio: 0.0,
+            seeding_time: 0,
         };
 
         // Store the torrent
@@ -349,6 +353,10 @@ pub mod download_helpers {
             priority: 0,
             category: "movies".to_string(),
             save_path: "/downloads/movies".to_string(),
+            seq_dl: false,
+            uploaded: 0,
+            ratio: 0.0,
+            seeding_time: 0,
         }
     }
 
@@ -366,6 +374,10 @@ pub mod download_helpers {
             priority: 0,
             category: "movies".to_string(),
             save_path: "/downloads/movies".to_string(),
+            seq_dl: false,
+            uploaded: 0,
+            ratio: 0.0,
+            seeding_time: 0,
         }
     }
 
@@ -383,6 +395,10 @@ pub mod download_helpers {
             priority: 0,
             category: "movies".to_string(),
             save_path: "/downloads/movies".to_string(),
+            seq_dl: false,
+            uploaded: 0,
+            ratio: 0.0,
+            seeding_time: 0,
         }
     }
 }